@@ -0,0 +1,10 @@
+//! Print the deterministic test-vector corpus as JSON.
+//!
+//! Usage: `cargo run -p tc-crypto --example export_test_vectors --features test-vectors`
+//! (or `just export-test-vectors`, which writes it to the web test fixtures).
+
+#![allow(clippy::print_stdout)]
+
+fn main() {
+    print!("{}", tc_crypto::test_vectors::corpus_json());
+}
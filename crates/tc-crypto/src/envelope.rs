@@ -46,6 +46,9 @@ const MIN_P_COST: u32 = 1;
 pub struct BackupEnvelope {
     salt: [u8; 16],
     version: u8,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
     raw: Vec<u8>,
 }
 
@@ -101,6 +104,9 @@ impl BackupEnvelope {
         Ok(Self {
             salt,
             version: bytes[0],
+            m_cost,
+            t_cost,
+            p_cost,
             raw: bytes,
         })
     }
@@ -145,6 +151,9 @@ impl BackupEnvelope {
         Ok(Self {
             salt,
             version: VERSION,
+            m_cost,
+            t_cost,
+            p_cost,
             raw,
         })
     }
@@ -155,6 +164,24 @@ impl BackupEnvelope {
         &self.salt
     }
 
+    /// Argon2id memory cost in KiB.
+    #[must_use]
+    pub const fn m_cost(&self) -> u32 {
+        self.m_cost
+    }
+
+    /// Argon2id time cost (iterations).
+    #[must_use]
+    pub const fn t_cost(&self) -> u32 {
+        self.t_cost
+    }
+
+    /// Argon2id parallelism.
+    #[must_use]
+    pub const fn p_cost(&self) -> u32 {
+        self.p_cost
+    }
+
     /// Envelope version (currently always 1).
     #[must_use]
     pub fn version(&self) -> i32 {
@@ -206,6 +233,10 @@ mod tests {
         let parsed = BackupEnvelope::parse(envelope.into_bytes()).expect("parse");
         assert_eq!(parsed.salt(), &salt);
         assert_eq!(parsed.version(), 1);
+        assert_eq!(
+            (parsed.m_cost(), parsed.t_cost(), parsed.p_cost()),
+            (65536, 3, 1)
+        );
     }
 
     #[test]
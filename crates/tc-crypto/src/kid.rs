@@ -93,6 +93,56 @@ impl<'de> serde::Deserialize<'de> for Kid {
     }
 }
 
+/// Stored as text; decoding validates, so a malformed value in the database
+/// surfaces as a decode error instead of an unchecked `Kid`.
+#[cfg(feature = "sqlx")]
+mod sqlx_impls {
+    use super::Kid;
+    use sqlx_core::database::Database;
+    use sqlx_core::decode::Decode;
+    use sqlx_core::encode::{Encode, IsNull};
+    use sqlx_core::error::BoxDynError;
+    use sqlx_core::types::Type;
+
+    impl<DB: Database> Type<DB> for Kid
+    where
+        String: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <String as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <String as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for Kid
+    where
+        String: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            self.0.encode_by_ref(buf)
+        }
+
+        fn size_hint(&self) -> usize {
+            self.0.size_hint()
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for Kid
+    where
+        String: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            Ok(String::decode(value)?.parse()?)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -14,6 +14,21 @@ pub use kid::{Kid, KidError};
 mod envelope;
 pub use envelope::{BackupEnvelope, EnvelopeError};
 
+mod request;
+pub use request::{
+    canonical_request_v1, canonicalize_header_value, canonicalize_host, BodyHash, BodyHasher,
+    RequestParts, SignatureAlg, UnsupportedAlgError, REQUEST_V2_TAG, SIGNED_HEADERS_V2,
+};
+
+mod webhook;
+pub use webhook::{
+    sign_webhook, verify_webhook, verify_webhook_js, WebhookError, DEFAULT_WEBHOOK_TOLERANCE_SECS,
+    WEBHOOK_SCHEME_V1, WEBHOOK_SIGNATURE_HEADER,
+};
+
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+
 /// Error type for base64url decoding failures
 #[derive(Debug, thiserror::Error)]
 #[error("invalid base64url encoding: {0}")]
@@ -0,0 +1,402 @@
+//! Canonical messages for signed API requests.
+//!
+//! A device signs a canonical string built from the request and sends the
+//! signature in `X-Signature`. Two versions exist:
+//!
+//! - **v1** (legacy): `METHOD\nPATH_AND_QUERY\nTIMESTAMP\nNONCE\nhex(SHA-256(body))`
+//! - **v2**: selected by sending `X-Signature-Alg`. Adds a version tag, the
+//!   algorithm, the `Host`, and the canonicalized [`SIGNED_HEADERS_V2`]:
+//!
+//! ```text
+//! tc-request-v2
+//! {ALG}
+//! {METHOD}
+//! {host}
+//! {PATH_AND_QUERY}
+//! {TIMESTAMP}
+//! {NONCE}
+//! content-type:{value}
+//! x-device-kid:{value}
+//! {hex(SHA-256(body))}
+//! ```
+//!
+//! A request answering a server-issued challenge (`X-Challenge`) signs one
+//! more line, `x-challenge:{value}`, just before the body hash. Requests
+//! without a challenge sign exactly the lines above.
+//!
+//! Binding the algorithm into the signed bytes prevents downgrading a v2
+//! request to another algorithm; binding the host prevents replaying a
+//! request captured for one deployment against another.
+
+use std::fmt;
+use std::str::FromStr;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Digest, Sha256};
+
+/// First line of every v2 canonical message.
+pub const REQUEST_V2_TAG: &str = "tc-request-v2";
+
+/// Headers covered by a v2 signature, in signing order (lowercase).
+///
+/// A header that is absent is signed with an empty value.
+pub const SIGNED_HEADERS_V2: [&str; 2] = ["content-type", "x-device-kid"];
+
+/// Header carrying a server-issued challenge, signed only when present.
+pub const CHALLENGE_HEADER: &str = "x-challenge";
+
+/// Algorithm named in the `X-Signature-Alg` header of a v2 request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureAlg {
+    Ed25519,
+}
+
+impl SignatureAlg {
+    /// Header value for this algorithm.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "ed25519",
+        }
+    }
+}
+
+impl fmt::Display for SignatureAlg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an `X-Signature-Alg` value this crate does not know.
+#[derive(Debug, thiserror::Error)]
+#[error("unsupported signature algorithm")]
+pub struct UnsupportedAlgError;
+
+impl FromStr for SignatureAlg {
+    type Err = UnsupportedAlgError;
+
+    /// Parse an exact, lowercase algorithm name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ed25519" => Ok(Self::Ed25519),
+            _ => Err(UnsupportedAlgError),
+        }
+    }
+}
+
+/// SHA-256 of a request body, as signed in the last line of the canonical
+/// message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyHash([u8; 32]);
+
+impl BodyHash {
+    /// Hash a body held in memory.
+    #[must_use]
+    pub fn of(body: &[u8]) -> Self {
+        Self(Sha256::digest(body).into())
+    }
+
+    /// Raw digest bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Lowercase hex, as it appears in the canonical message.
+impl fmt::Display for BodyHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Incremental [`BodyHash`] for bodies read in chunks, so the body never has
+/// to be contiguous in memory just to be hashed.
+#[derive(Debug, Clone, Default)]
+pub struct BodyHasher {
+    digest: Sha256,
+    len: usize,
+}
+
+impl BodyHasher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of the body.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.digest.update(chunk);
+        self.len = self.len.saturating_add(chunk.len());
+    }
+
+    /// Bytes hashed so far.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no bytes have been hashed yet.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn finish(self) -> BodyHash {
+        BodyHash(self.digest.finalize().into())
+    }
+}
+
+/// The parts of an HTTP request that a device signs.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestParts<'a> {
+    pub method: &'a str,
+    /// `Host` header value (v2 only).
+    pub host: &'a str,
+    /// Path including the query string, exactly as sent.
+    pub path_and_query: &'a str,
+    pub timestamp: i64,
+    pub nonce: &'a str,
+    /// `Content-Type` header value, or `""` when absent (v2 only).
+    pub content_type: &'a str,
+    /// `X-Device-Kid` header value (v2 only).
+    pub device_kid: &'a str,
+    /// `X-Challenge` header value, or `""` when absent (v2 only).
+    pub challenge: &'a str,
+    pub body: &'a [u8],
+}
+
+impl RequestParts<'_> {
+    /// v1 canonical message.
+    #[must_use]
+    pub fn canonical_v1(&self) -> String {
+        self.canonical_v1_prehashed(&BodyHash::of(self.body))
+    }
+
+    /// v1 canonical message using an already computed body hash; `body` is
+    /// ignored.
+    #[must_use]
+    pub fn canonical_v1_prehashed(&self, body_hash: &BodyHash) -> String {
+        format!(
+            "{}\n{}\n{}\n{}\n{body_hash}",
+            self.method, self.path_and_query, self.timestamp, self.nonce
+        )
+    }
+
+    /// v2 canonical message for `alg`.
+    #[must_use]
+    pub fn canonical_v2(&self, alg: SignatureAlg) -> String {
+        self.canonical_v2_prehashed(alg, &BodyHash::of(self.body))
+    }
+
+    /// v2 canonical message using an already computed body hash; `body` is
+    /// ignored.
+    #[must_use]
+    pub fn canonical_v2_prehashed(&self, alg: SignatureAlg, body_hash: &BodyHash) -> String {
+        let [content_type, device_kid] = SIGNED_HEADERS_V2;
+        let challenge = canonicalize_header_value(self.challenge);
+        let challenge_line = if challenge.is_empty() {
+            String::new()
+        } else {
+            format!("{CHALLENGE_HEADER}:{challenge}\n")
+        };
+        format!(
+            "{REQUEST_V2_TAG}\n{alg}\n{}\n{}\n{}\n{}\n{}\n\
+             {content_type}:{}\n{device_kid}:{}\n{challenge_line}{body_hash}",
+            self.method,
+            canonicalize_host(self.host),
+            self.path_and_query,
+            self.timestamp,
+            self.nonce,
+            canonicalize_header_value(self.content_type),
+            canonicalize_header_value(self.device_kid),
+        )
+    }
+}
+
+/// v1 canonical message:
+/// `METHOD\nPATH_AND_QUERY\nTIMESTAMP\nNONCE\nhex(SHA-256(body))`.
+#[must_use]
+pub fn canonical_request_v1(
+    method: &str,
+    path_and_query: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    let body_hash = Sha256::digest(body);
+    format!("{method}\n{path_and_query}\n{timestamp}\n{nonce}\n{body_hash:x}")
+}
+
+/// Lowercase and trim a `Host` value.
+#[must_use]
+pub fn canonicalize_host(host: &str) -> String {
+    host.trim().to_ascii_lowercase()
+}
+
+/// Trim a header value and collapse internal whitespace runs to one space.
+///
+/// Remaining control characters are removed so a value cannot inject extra
+/// lines into the canonical message.
+#[must_use]
+pub fn canonicalize_header_value(value: &str) -> String {
+    value
+        .split(|c: char| c.is_ascii_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.replace(|c: char| c.is_control(), ""))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Build the v2 canonical message (WASM binding).
+///
+/// For native Rust code, use [`RequestParts::canonical_v2`] instead.
+///
+/// # Errors
+/// Returns `JsError` if `alg` is not a supported algorithm.
+#[wasm_bindgen(js_name = "canonical_request_v2")]
+#[allow(clippy::too_many_arguments)]
+pub fn canonical_request_v2_js(
+    alg: &str,
+    method: &str,
+    host: &str,
+    path_and_query: &str,
+    timestamp: i64,
+    nonce: &str,
+    content_type: &str,
+    device_kid: &str,
+    challenge: &str,
+    body: &[u8],
+) -> Result<String, JsError> {
+    let alg: SignatureAlg = alg
+        .parse()
+        .map_err(|e: UnsupportedAlgError| JsError::new(&e.to_string()))?;
+    Ok(RequestParts {
+        method,
+        host,
+        path_and_query,
+        timestamp,
+        nonce,
+        content_type,
+        device_kid,
+        challenge,
+        body,
+    }
+    .canonical_v2(alg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(body: &[u8]) -> RequestParts<'_> {
+        RequestParts {
+            method: "POST",
+            host: "API.Example.com",
+            path_and_query: "/auth/devices?x=1",
+            timestamp: 1_700_000_000,
+            nonce: "n-1",
+            content_type: "application/json",
+            device_kid: "IKn0gXXhyRumNLQ-rmSR5Q",
+            challenge: "",
+            body,
+        }
+    }
+
+    #[test]
+    fn v1_format() {
+        assert_eq!(
+            parts(b"").canonical_v1(),
+            "POST\n/auth/devices?x=1\n1700000000\nn-1\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn v2_format() {
+        assert_eq!(
+            parts(b"").canonical_v2(SignatureAlg::Ed25519),
+            "tc-request-v2\ned25519\nPOST\napi.example.com\n/auth/devices?x=1\n\
+             1700000000\nn-1\ncontent-type:application/json\n\
+             x-device-kid:IKn0gXXhyRumNLQ-rmSR5Q\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn v2_binds_host_and_headers() {
+        let base = parts(b"{}").canonical_v2(SignatureAlg::Ed25519);
+        let other_host = RequestParts {
+            host: "evil.example.com",
+            ..parts(b"{}")
+        };
+        let other_type = RequestParts {
+            content_type: "text/plain",
+            ..parts(b"{}")
+        };
+        assert_ne!(base, other_host.canonical_v2(SignatureAlg::Ed25519));
+        assert_ne!(base, other_type.canonical_v2(SignatureAlg::Ed25519));
+    }
+
+    #[test]
+    fn v2_signs_challenge_only_when_present() {
+        let challenged = RequestParts {
+            challenge: " chal-1 ",
+            ..parts(b"")
+        };
+        assert_eq!(
+            challenged.canonical_v2(SignatureAlg::Ed25519),
+            "tc-request-v2\ned25519\nPOST\napi.example.com\n/auth/devices?x=1\n\
+             1700000000\nn-1\ncontent-type:application/json\n\
+             x-device-kid:IKn0gXXhyRumNLQ-rmSR5Q\nx-challenge:chal-1\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        // v1 has no slot for a challenge
+        assert_eq!(challenged.canonical_v1(), parts(b"").canonical_v1());
+    }
+
+    #[test]
+    fn streamed_hash_matches_one_shot() {
+        let body = b"{\"name\":\"laptop\",\"padding\":\"xxxxxxxxxxxxxxxx\"}";
+        let mut hasher = BodyHasher::new();
+        for chunk in body.chunks(7) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.len(), body.len());
+        let hash = hasher.finish();
+        assert_eq!(hash, BodyHash::of(body));
+        let p = parts(body);
+        assert_eq!(p.canonical_v1_prehashed(&hash), p.canonical_v1());
+        assert_eq!(
+            parts(b"").canonical_v2_prehashed(SignatureAlg::Ed25519, &hash),
+            p.canonical_v2(SignatureAlg::Ed25519)
+        );
+    }
+
+    #[test]
+    fn header_values_are_canonicalized() {
+        assert_eq!(
+            canonicalize_header_value("  application/json;   charset=utf-8 "),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(canonicalize_header_value("a\r\nb"), "a b");
+        assert_eq!(canonicalize_header_value("a\u{7f}b"), "ab");
+        assert_eq!(canonicalize_host(" Example.COM:8080 "), "example.com:8080");
+    }
+
+    #[test]
+    fn alg_parsing_is_exact() {
+        assert_eq!(
+            "ed25519".parse::<SignatureAlg>().ok(),
+            Some(SignatureAlg::Ed25519)
+        );
+        assert!("Ed25519".parse::<SignatureAlg>().is_err());
+        assert!("none".parse::<SignatureAlg>().is_err());
+        assert_eq!(SignatureAlg::Ed25519.to_string(), "ed25519");
+    }
+}
@@ -0,0 +1,326 @@
+//! Deterministic test vectors shared by the Rust and TypeScript test suites.
+//!
+//! Every key and byte string is derived from a label, so the same label
+//! always yields the same keypair, certificate, envelope, or signed request.
+//! [`corpus`] collects them into a versioned JSON document that is exported
+//! to `web/` (see `just export-test-vectors`) so both implementations are
+//! checked against identical inputs and outputs.
+//!
+//! Only available with the `test-vectors` feature. These keys are public —
+//! never use them outside tests.
+
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::{json, Value};
+
+use crate::{
+    canonical_request_v1, derive_kid, encode_base64url, sign_webhook, BackupEnvelope, Digest,
+    RequestParts, Sha256, SignatureAlg,
+};
+
+/// Corpus format version. Bump when any derivation or output changes.
+pub const VECTORS_VERSION: u32 = 1;
+
+/// Domain prefix for label-derived bytes: `SHA-256(SEED_DOMAIN || label)`.
+pub const SEED_DOMAIN: &str = "tc-test-vectors:v1:";
+
+/// Timestamp used by timestamp-bound vectors (2023-11-14T22:13:20Z).
+pub const FIXED_TIMESTAMP: i64 = 1_700_000_000;
+
+/// Relative path of the exported corpus from the repository root.
+pub const EXPORT_PATH: &str = "web/src/features/identity/keys/__tests__/tc-crypto-vectors.json";
+
+/// `Host` used by v2 request vectors.
+pub const VECTOR_HOST: &str = "api.tiny-congress.test";
+
+/// Labels of the keypairs included in the corpus.
+pub const KEYPAIR_LABELS: [&str; 3] = ["root", "device", "device-2"];
+
+/// 32 bytes derived from `label`.
+#[must_use]
+pub fn seed(label: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SEED_DOMAIN.as_bytes());
+    hasher.update(label.as_bytes());
+    hasher.finalize().into()
+}
+
+/// `len` bytes derived from `label`, extending [`seed`] with a counter suffix.
+#[must_use]
+pub fn bytes(label: &str, len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len);
+    let mut block = 0u32;
+    while out.len() < len {
+        out.extend_from_slice(&seed(&format!("{label}#{block}")));
+        block += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+/// Ed25519 keypair whose secret key is the [`seed`] for `label`.
+#[must_use]
+pub fn keypair(label: &str) -> SigningKey {
+    SigningKey::from_bytes(&seed(label))
+}
+
+/// Message a root key signs to certify a device key.
+///
+/// Version 1 (signup) is the raw 32-byte public key; version 2 (login)
+/// appends the little-endian `i64` timestamp.
+#[must_use]
+pub fn certificate_message(device_pubkey: &[u8; 32], timestamp: Option<i64>) -> Vec<u8> {
+    let mut message = device_pubkey.to_vec();
+    if let Some(timestamp) = timestamp {
+        message.extend_from_slice(&timestamp.to_le_bytes());
+    }
+    message
+}
+
+/// v1 canonical string a device signs for an authenticated request:
+/// `METHOD\nPATH\nTIMESTAMP\nNONCE\nhex(SHA-256(body))`.
+#[must_use]
+pub fn canonical_request(
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    canonical_request_v1(method, path, timestamp, nonce, body)
+}
+
+/// Argon2id backup envelope with label-derived salt, nonce, and ciphertext.
+///
+/// The ciphertext is not a real encryption; the vector pins the byte layout.
+///
+/// # Panics
+///
+/// Never in practice: the fixed parameters are always valid.
+#[must_use]
+#[allow(clippy::expect_used)]
+pub fn envelope() -> BackupEnvelope {
+    let salt: [u8; 16] = bytes("envelope-salt", 16).try_into().expect("16 bytes");
+    let nonce: [u8; 12] = bytes("envelope-nonce", 12).try_into().expect("12 bytes");
+    BackupEnvelope::build(salt, 65536, 3, 1, nonce, &bytes("envelope-ciphertext", 48))
+        .expect("valid envelope parameters")
+}
+
+fn keypair_vector(label: &str) -> Value {
+    let public_key = keypair(label).verifying_key().to_bytes();
+    json!({
+        "label": label,
+        "seed": encode_base64url(&seed(label)),
+        "public_key": encode_base64url(&public_key),
+        "kid": derive_kid(&public_key),
+    })
+}
+
+fn certificate_vector(name: &str, subject: &str, timestamp: Option<i64>) -> Value {
+    let device_pubkey = keypair(subject).verifying_key().to_bytes();
+    let message = certificate_message(&device_pubkey, timestamp);
+    json!({
+        "name": name,
+        "signer": "root",
+        "subject": subject,
+        "version": if timestamp.is_some() { 2 } else { 1 },
+        "timestamp": timestamp,
+        "message": encode_base64url(&message),
+        "signature": encode_base64url(&keypair("root").sign(&message).to_bytes()),
+    })
+}
+
+fn request_vector(method: &str, path: &str, nonce: &str, body: &str) -> Value {
+    let canonical = canonical_request(method, path, FIXED_TIMESTAMP, nonce, body.as_bytes());
+    json!({
+        "signer": "device",
+        "method": method,
+        "path": path,
+        "timestamp": FIXED_TIMESTAMP,
+        "nonce": nonce,
+        "body": body,
+        "body_sha256": format!("{:x}", Sha256::digest(body.as_bytes())),
+        "canonical": canonical,
+        "signature": encode_base64url(&keypair("device").sign(canonical.as_bytes()).to_bytes()),
+    })
+}
+
+fn request_v2_vector(method: &str, path: &str, nonce: &str, challenge: &str, body: &str) -> Value {
+    let kid = derive_kid(&keypair("device").verifying_key().to_bytes());
+    let content_type = if body.is_empty() {
+        ""
+    } else {
+        "application/json"
+    };
+    let alg = SignatureAlg::Ed25519;
+    let canonical = RequestParts {
+        method,
+        host: VECTOR_HOST,
+        path_and_query: path,
+        timestamp: FIXED_TIMESTAMP,
+        nonce,
+        content_type,
+        device_kid: &kid,
+        challenge,
+        body: body.as_bytes(),
+    }
+    .canonical_v2(alg);
+    json!({
+        "signer": "device",
+        "alg": alg.as_str(),
+        "method": method,
+        "host": VECTOR_HOST,
+        "path": path,
+        "timestamp": FIXED_TIMESTAMP,
+        "nonce": nonce,
+        "content_type": content_type,
+        "device_kid": kid,
+        "challenge": challenge,
+        "body": body,
+        "canonical": canonical,
+        "signature": encode_base64url(&keypair("device").sign(canonical.as_bytes()).to_bytes()),
+    })
+}
+
+fn webhook_vector(name: &str, payload: &str) -> Value {
+    let secret = bytes("webhook-secret", 32);
+    json!({
+        "name": name,
+        "secret": encode_base64url(&secret),
+        "timestamp": FIXED_TIMESTAMP,
+        "payload": payload,
+        "header": sign_webhook(payload.as_bytes(), &secret, FIXED_TIMESTAMP),
+    })
+}
+
+/// The full versioned vector corpus.
+#[must_use]
+pub fn corpus() -> Value {
+    let envelope = envelope();
+    json!({
+        "version": VECTORS_VERSION,
+        "seed_derivation": format!("SHA-256(\"{SEED_DOMAIN}\" || label)"),
+        "encoding": "binary values are base64url without padding",
+        "keypairs": KEYPAIR_LABELS.iter().map(|l| keypair_vector(l)).collect::<Vec<_>>(),
+        "certificates": [
+            certificate_vector("signup", "device", None),
+            certificate_vector("login", "device-2", Some(FIXED_TIMESTAMP)),
+        ],
+        "envelopes": [{
+            "name": "argon2id-v1",
+            "m_cost": 65536,
+            "t_cost": 3,
+            "p_cost": 1,
+            "salt": encode_base64url(envelope.salt()),
+            "nonce": encode_base64url(&bytes("envelope-nonce", 12)),
+            "ciphertext": encode_base64url(&bytes("envelope-ciphertext", 48)),
+            "envelope": encode_base64url(envelope.as_bytes()),
+        }],
+        "requests": [
+            request_vector("GET", "/auth/devices", "vector-nonce-0001", ""),
+            request_vector(
+                "POST",
+                "/auth/devices",
+                "vector-nonce-0002",
+                r#"{"device_name":"Vector Device"}"#,
+            ),
+            request_vector("GET", "/auth/devices?include_revoked=false", "vector-nonce-0003", ""),
+        ],
+        "requests_v2": [
+            request_v2_vector("GET", "/auth/devices", "vector-nonce-v2-0001", "", ""),
+            request_v2_vector(
+                "POST",
+                "/auth/devices",
+                "vector-nonce-v2-0002",
+                "",
+                r#"{"device_name":"Vector Device"}"#,
+            ),
+            request_v2_vector(
+                "POST",
+                "/auth/sessions",
+                "vector-nonce-v2-0003",
+                &encode_base64url(&bytes("challenge", 32)),
+                "",
+            ),
+        ],
+        "webhooks": [
+            webhook_vector("empty", ""),
+            webhook_vector(
+                "device-revoked",
+                r#"{"type":"device_revoked","data":{"device_kid":"IKn0gXXhyRumNLQ-rmSR5Q"}}"#,
+            ),
+        ],
+    })
+}
+
+/// [`corpus`] as pretty-printed JSON with a trailing newline, exactly as
+/// written to [`EXPORT_PATH`].
+///
+/// # Panics
+///
+/// Never in practice: the corpus contains only JSON-safe values.
+#[must_use]
+#[allow(clippy::expect_used)]
+pub fn corpus_json() -> String {
+    let mut out = serde_json::to_string_pretty(&corpus()).expect("corpus serializes");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify_ed25519;
+
+    #[test]
+    fn derivation_is_stable() {
+        // If this changes, bump VECTORS_VERSION and re-export the corpus
+        let root = keypair("root").verifying_key().to_bytes();
+        assert_eq!(
+            encode_base64url(&root),
+            "5n3uId-M13zrSWeYVeM898koR8cnXl_hd1i1ffqCqgQ"
+        );
+        assert_eq!(derive_kid(&root), "IKn0gXXhyRumNLQ-rmSR5Q");
+        assert_ne!(seed("root"), seed("device"));
+        assert_eq!(bytes("x", 40)[..32], seed("x#0"));
+    }
+
+    #[test]
+    fn signatures_verify() {
+        let root = keypair("root").verifying_key().to_bytes();
+        let device = keypair("device").verifying_key().to_bytes();
+        let message = certificate_message(&device, None);
+        let signature = keypair("root").sign(&message).to_bytes();
+        assert!(verify_ed25519(&root, &message, &signature).is_ok());
+
+        let canonical = canonical_request("GET", "/auth/devices", FIXED_TIMESTAMP, "n", b"");
+        let signature = keypair("device").sign(canonical.as_bytes()).to_bytes();
+        assert!(verify_ed25519(&device, canonical.as_bytes(), &signature).is_ok());
+    }
+
+    #[test]
+    fn envelope_parses() {
+        let raw = envelope().as_bytes().to_vec();
+        assert!(BackupEnvelope::parse(raw).is_ok());
+    }
+
+    #[test]
+    fn canonical_request_hashes_body() {
+        let canonical = canonical_request("GET", "/auth/devices", FIXED_TIMESTAMP, "n", b"");
+        assert_eq!(
+            canonical,
+            "GET\n/auth/devices\n1700000000\nn\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn exported_corpus_is_current() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../").to_string() + EXPORT_PATH;
+        let exported = std::fs::read_to_string(&path).expect("exported corpus exists");
+        let exported: Value = serde_json::from_str(&exported).expect("exported corpus is JSON");
+        assert!(
+            exported == corpus(),
+            "{EXPORT_PATH} is stale; run `just export-test-vectors`"
+        );
+    }
+}
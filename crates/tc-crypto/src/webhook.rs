@@ -0,0 +1,246 @@
+//! HMAC-SHA256 signatures for event webhook deliveries.
+//!
+//! Every delivery carries a signature header built by [`sign_webhook`]:
+//!
+//! ```text
+//! X-TC-Webhook-Signature: t=1700000000,v1=<hex HMAC-SHA256>
+//! ```
+//!
+//! The MAC is keyed by the endpoint's secret and covers `{t}.{payload}`: the
+//! decimal timestamp, a dot, then the raw body bytes. Binding the timestamp
+//! lets [`verify_webhook`] refuse replays older than a tolerance window.
+//!
+//! While a secret is being rotated the header may carry one `v1=` signature
+//! per secret; a receiver accepts the delivery if any of them matches its
+//! secret. Unknown keys are ignored so new schemes can be added alongside
+//! `v1`.
+
+use std::fmt::Write as _;
+
+use hmac::{Hmac, Mac};
+use wasm_bindgen::prelude::*;
+
+use crate::Sha256;
+
+/// Header carrying the delivery signature (lowercase).
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-tc-webhook-signature";
+
+/// Key of the HMAC-SHA256 signatures in the header.
+pub const WEBHOOK_SCHEME_V1: &str = "v1";
+
+/// Default allowed difference between a delivery's timestamp and the
+/// receiver's clock, in seconds.
+pub const DEFAULT_WEBHOOK_TOLERANCE_SECS: u64 = 300;
+
+/// Why a delivery failed verification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WebhookError {
+    #[error("malformed webhook signature header")]
+    MalformedHeader,
+    #[error("no webhook signature matches")]
+    SignatureMismatch,
+    #[error("webhook timestamp {timestamp} is outside the tolerance window")]
+    OutsideTolerance { timestamp: i64 },
+}
+
+/// MAC over `{timestamp}.{payload}`. HMAC accepts keys of any length, so
+/// this is always `Some`.
+fn mac_for(secret: &[u8], timestamp: i64, payload: &[u8]) -> Option<Hmac<Sha256>> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret).ok()?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+    Some(mac)
+}
+
+/// Build the signature header value for a delivery of `payload` at
+/// `timestamp` (Unix seconds).
+#[wasm_bindgen]
+#[must_use]
+pub fn sign_webhook(payload: &[u8], secret: &[u8], timestamp: i64) -> String {
+    let signature: String = mac_for(secret, timestamp, payload)
+        .map(|mac| mac.finalize().into_bytes())
+        .into_iter()
+        .flatten()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            // Writing to a String cannot fail
+            write!(hex, "{byte:02x}").ok();
+            hex
+        });
+    format!("t={timestamp},{WEBHOOK_SCHEME_V1}={signature}")
+}
+
+/// Check a delivery's signature header against the raw body, returning the
+/// signed timestamp.
+///
+/// The signature is compared in constant time. The timestamp must be within
+/// `tolerance_secs` of `now` in either direction.
+///
+/// # Errors
+///
+/// Returns [`WebhookError::MalformedHeader`] if the header lacks exactly one
+/// `t=` or any `v1=` signature, [`WebhookError::SignatureMismatch`] if no
+/// signature matches, and [`WebhookError::OutsideTolerance`] for a valid but
+/// stale (or future) delivery.
+pub fn verify_webhook(
+    payload: &[u8],
+    secret: &[u8],
+    header: &str,
+    now: i64,
+    tolerance_secs: u64,
+) -> Result<i64, WebhookError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        let (key, value) = part
+            .trim()
+            .split_once('=')
+            .ok_or(WebhookError::MalformedHeader)?;
+        match key {
+            "t" => {
+                if timestamp.is_some() {
+                    return Err(WebhookError::MalformedHeader);
+                }
+                timestamp = Some(
+                    value
+                        .parse::<i64>()
+                        .map_err(|_| WebhookError::MalformedHeader)?,
+                );
+            }
+            WEBHOOK_SCHEME_V1 => {
+                signatures.push(decode_hex(value).ok_or(WebhookError::MalformedHeader)?);
+            }
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or(WebhookError::MalformedHeader)?;
+    if signatures.is_empty() {
+        return Err(WebhookError::MalformedHeader);
+    }
+
+    let mac = mac_for(secret, timestamp, payload).ok_or(WebhookError::SignatureMismatch)?;
+    if !signatures
+        .iter()
+        .any(|signature| mac.clone().verify_slice(signature).is_ok())
+    {
+        return Err(WebhookError::SignatureMismatch);
+    }
+    if now.abs_diff(timestamp) > tolerance_secs {
+        return Err(WebhookError::OutsideTolerance { timestamp });
+    }
+    Ok(timestamp)
+}
+
+/// Check a delivery's signature header (WASM binding).
+///
+/// For native Rust code, use [`verify_webhook`] instead.
+///
+/// # Errors
+/// Returns `JsError` describing why the delivery was refused.
+#[wasm_bindgen(js_name = "verify_webhook")]
+pub fn verify_webhook_js(
+    payload: &[u8],
+    secret: &[u8],
+    header: &str,
+    now: i64,
+    tolerance_secs: u64,
+) -> Result<i64, JsError> {
+    verify_webhook(payload, secret, header, now, tolerance_secs)
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"whsec-test";
+    const PAYLOAD: &[u8] = br#"{"type":"device_revoked"}"#;
+    const T: i64 = 1_700_000_000;
+
+    #[test]
+    fn signature_round_trips() {
+        let header = sign_webhook(PAYLOAD, SECRET, T);
+        assert!(header.starts_with("t=1700000000,v1="));
+        assert_eq!(header.len(), "t=1700000000,v1=".len() + 64);
+        assert_eq!(verify_webhook(PAYLOAD, SECRET, &header, T + 10, 300), Ok(T));
+    }
+
+    #[test]
+    fn tampering_is_rejected() {
+        let header = sign_webhook(PAYLOAD, SECRET, T);
+        assert_eq!(
+            verify_webhook(b"{}", SECRET, &header, T, 300),
+            Err(WebhookError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify_webhook(PAYLOAD, b"other", &header, T, 300),
+            Err(WebhookError::SignatureMismatch)
+        );
+        // Moving the timestamp invalidates the MAC
+        let moved = header.replacen("t=1700000000", "t=1700000100", 1);
+        assert_eq!(
+            verify_webhook(PAYLOAD, SECRET, &moved, T + 100, 300),
+            Err(WebhookError::SignatureMismatch)
+        );
+    }
+
+    #[test]
+    fn tolerance_applies_both_ways() {
+        let header = sign_webhook(PAYLOAD, SECRET, T);
+        assert_eq!(
+            verify_webhook(PAYLOAD, SECRET, &header, T + 300, 300),
+            Ok(T)
+        );
+        assert_eq!(
+            verify_webhook(PAYLOAD, SECRET, &header, T + 301, 300),
+            Err(WebhookError::OutsideTolerance { timestamp: T })
+        );
+        assert_eq!(
+            verify_webhook(PAYLOAD, SECRET, &header, T - 301, 300),
+            Err(WebhookError::OutsideTolerance { timestamp: T })
+        );
+    }
+
+    #[test]
+    fn any_signature_may_match_during_rotation() {
+        let old = sign_webhook(PAYLOAD, b"old-secret", T);
+        let new = sign_webhook(PAYLOAD, SECRET, T);
+        let (_, new_sig) = new.split_once(',').expect("two parts");
+        let header = format!("{old}, {new_sig}, v2=future");
+        assert_eq!(verify_webhook(PAYLOAD, SECRET, &header, T, 300), Ok(T));
+        assert_eq!(
+            verify_webhook(PAYLOAD, b"old-secret", &header, T, 300),
+            Ok(T)
+        );
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        let sig = sign_webhook(PAYLOAD, SECRET, T);
+        let (_, v1) = sig.split_once(',').expect("two parts");
+        for header in [
+            "",
+            v1,
+            "t=1700000000",
+            "t=soon,v1=00",
+            "t=1700000000,v1=zz",
+            &format!("t=1,t=2,{v1}"),
+        ] {
+            assert_eq!(
+                verify_webhook(PAYLOAD, SECRET, header, T, 300),
+                Err(WebhookError::MalformedHeader),
+                "{header}"
+            );
+        }
+    }
+}
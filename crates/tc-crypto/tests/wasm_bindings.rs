@@ -0,0 +1,144 @@
+//! Contract tests for the WASM build of tc-crypto.
+//!
+//! These run the crate compiled to `wasm32-unknown-unknown` under Node
+//! (`just test-wasm-bindings`) and check the exported bindings against the
+//! test-vector corpus in `web/`. The native test suite asserts that corpus is
+//! exactly what the native build produces, so any divergence introduced by
+//! `wasm-bindgen` marshalling or a wasm-only code path fails here.
+//!
+//! Signing needs the `ed25519` feature, which the shipped WASM build does not
+//! enable; those checks run when the tests are built with it.
+
+#![cfg(target_arch = "wasm32")]
+
+use serde_json::Value;
+use tc_crypto::{
+    decode_base64url, decode_base64url_js, derive_kid, encode_base64url, sign_webhook,
+    verify_webhook_js, BackupEnvelope, Kid,
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const CORPUS: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../web/src/features/identity/keys/__tests__/tc-crypto-vectors.json"
+));
+
+fn corpus() -> Value {
+    serde_json::from_str(CORPUS).expect("corpus parses")
+}
+
+fn entries<'a>(corpus: &'a Value, key: &str) -> &'a [Value] {
+    corpus[key].as_array().expect("corpus section")
+}
+
+fn b64(value: &Value) -> Vec<u8> {
+    decode_base64url(value.as_str().expect("string")).expect("base64url")
+}
+
+#[wasm_bindgen_test]
+fn kid_derivation_matches_native() {
+    let corpus = corpus();
+    for keypair in entries(&corpus, "keypairs") {
+        let public_key = b64(&keypair["public_key"]);
+        assert_eq!(derive_kid(&public_key), keypair["kid"]);
+        assert_eq!(Kid::derive(&public_key).as_str(), keypair["kid"]);
+    }
+}
+
+#[wasm_bindgen_test]
+fn base64url_bindings_round_trip() {
+    let corpus = corpus();
+    for keypair in entries(&corpus, "keypairs") {
+        let encoded = keypair["seed"].as_str().expect("seed");
+        let Ok(decoded) = decode_base64url_js(encoded) else {
+            panic!("{encoded} does not decode");
+        };
+        assert_eq!(decoded.len(), 32);
+        assert_eq!(encode_base64url(&decoded), encoded);
+    }
+    assert!(decode_base64url_js("not base64url!").is_err());
+}
+
+#[wasm_bindgen_test]
+fn envelope_build_and_parse_match_native() {
+    let corpus = corpus();
+    for vector in entries(&corpus, "envelopes") {
+        let bytes = b64(&vector["envelope"]);
+        let parsed = BackupEnvelope::parse(bytes.clone()).expect("parses");
+        assert_eq!(parsed.version(), 1);
+        assert_eq!(parsed.salt().as_slice(), b64(&vector["salt"]));
+
+        let param = |name: &str| u32::try_from(vector[name].as_u64().expect("cost")).expect("u32");
+        let built = BackupEnvelope::build(
+            b64(&vector["salt"]).try_into().expect("16-byte salt"),
+            param("m_cost"),
+            param("t_cost"),
+            param("p_cost"),
+            b64(&vector["nonce"]).try_into().expect("12-byte nonce"),
+            &b64(&vector["ciphertext"]),
+        )
+        .expect("builds");
+        assert_eq!(built.as_bytes(), bytes.as_slice());
+    }
+}
+
+#[wasm_bindgen_test]
+fn webhook_signatures_match_native() {
+    let corpus = corpus();
+    for vector in entries(&corpus, "webhooks") {
+        let secret = b64(&vector["secret"]);
+        let payload = vector["payload"].as_str().expect("payload").as_bytes();
+        let timestamp = vector["timestamp"].as_i64().expect("timestamp");
+        let header = vector["header"].as_str().expect("header");
+        assert_eq!(sign_webhook(payload, &secret, timestamp), header);
+        assert_eq!(
+            verify_webhook_js(payload, &secret, header, timestamp, 300).ok(),
+            Some(timestamp)
+        );
+        assert!(verify_webhook_js(b"tampered", &secret, header, timestamp, 300).is_err());
+        assert!(verify_webhook_js(payload, &secret, header, timestamp + 301, 300).is_err());
+    }
+}
+
+#[cfg(feature = "ed25519")]
+#[wasm_bindgen_test]
+fn signatures_match_native() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use tc_crypto::{verify_ed25519, RequestParts, SignatureAlg};
+
+    let corpus = corpus();
+    let key = |label: &Value| {
+        let keypair = entries(&corpus, "keypairs")
+            .iter()
+            .find(|k| k["label"] == *label)
+            .expect("keypair");
+        SigningKey::from_bytes(&b64(&keypair["seed"]).try_into().expect("32-byte seed"))
+    };
+
+    for cert in entries(&corpus, "certificates") {
+        let signer = key(&cert["signer"]);
+        let message = b64(&cert["message"]);
+        let signature = signer.sign(&message).to_bytes();
+        assert_eq!(encode_base64url(&signature), cert["signature"]);
+        verify_ed25519(&signer.verifying_key().to_bytes(), &message, &signature).expect("verifies");
+    }
+
+    for req in entries(&corpus, "requests_v2") {
+        let str_of = |name: &str| req[name].as_str().expect("string field");
+        let canonical = RequestParts {
+            method: str_of("method"),
+            host: str_of("host"),
+            path_and_query: str_of("path"),
+            timestamp: req["timestamp"].as_i64().expect("timestamp"),
+            nonce: str_of("nonce"),
+            content_type: str_of("content_type"),
+            device_kid: str_of("device_kid"),
+            challenge: str_of("challenge"),
+            body: str_of("body").as_bytes(),
+        }
+        .canonical_v2(SignatureAlg::Ed25519);
+        assert_eq!(canonical, str_of("canonical"));
+        let signature = key(&req["signer"]).sign(canonical.as_bytes()).to_bytes();
+        assert_eq!(encode_base64url(&signature), req["signature"]);
+    }
+}
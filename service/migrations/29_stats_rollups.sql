@@ -0,0 +1,23 @@
+-- Daily instance-level rollups for public dashboards (GET /api/v1/stats).
+-- Populated by the stats rollup job; one row per UTC day. Rows are recomputed
+-- idempotently, so rerunning the job for a day simply overwrites it.
+CREATE TABLE IF NOT EXISTS stats__daily_rollups (
+    day DATE PRIMARY KEY,
+    signups BIGINT NOT NULL,
+    -- Non-revoked devices used within the 30 days ending on `day`, as observed
+    -- when the rollup ran (device_keys only keeps the latest last_used_at).
+    active_devices BIGINT NOT NULL,
+    -- Distinct (poll, voter) pairs with a vote cast or updated on `day`.
+    ballots_cast BIGINT NOT NULL,
+    -- Distinct accounts that cast or updated a vote on `day`.
+    voters BIGINT NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Endorsements created per topic per day.
+CREATE TABLE IF NOT EXISTS stats__daily_topic_endorsements (
+    day DATE NOT NULL REFERENCES stats__daily_rollups(day) ON DELETE CASCADE,
+    topic TEXT NOT NULL,
+    endorsements BIGINT NOT NULL,
+    PRIMARY KEY (day, topic)
+);
@@ -0,0 +1,19 @@
+-- Per-account API tokens for server-to-server integrations.
+-- Tokens authenticate selected read endpoints without device signatures.
+-- Only the SHA-256 of the token is stored; the plaintext is shown once at
+-- creation and cannot be recovered.
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,                   -- user-provided label
+    token_hash BYTEA NOT NULL,            -- SHA-256 of the bearer token
+    scopes TEXT[] NOT NULL,               -- e.g. {devices:read,trust:read}
+    expires_at TIMESTAMPTZ,               -- NULL = never expires
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+    CONSTRAINT uq_api_tokens_hash UNIQUE (token_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_account ON api_tokens(account_id);
@@ -0,0 +1,11 @@
+-- Device history audit fields.
+-- revoked_by_kid records which device performed the revocation, so users can
+-- spot a revocation they didn't make.
+-- certificate_version identifies what the root key signed:
+--   1 = raw device pubkey (signup, POST /auth/devices)
+--   2 = device pubkey || certificate_timestamp (login)
+-- Rows created before this migration have no recorded version.
+ALTER TABLE device_keys
+    ADD COLUMN IF NOT EXISTS revoked_by_kid TEXT,
+    ADD COLUMN IF NOT EXISTS certificate_version INTEGER,
+    ADD COLUMN IF NOT EXISTS certificate_timestamp BIGINT;
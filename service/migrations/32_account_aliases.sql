@@ -0,0 +1,54 @@
+-- Secondary handles (aliases) for accounts. Lookup endpoints resolve an
+-- alias to its account and report the canonical username.
+--
+-- A handle is either one account's username or one account's alias, never
+-- both. The alias primary key covers alias/alias collisions; the triggers
+-- below cover username/alias collisions in both directions. Both triggers
+-- take the same per-handle advisory lock, so concurrent signup and alias
+-- creation for one handle serialize instead of racing past each other.
+CREATE TABLE IF NOT EXISTS account_aliases (
+    alias TEXT PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- Assigned by operators (e.g. for verified organizations); the account
+    -- holder cannot remove it
+    reserved BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_aliases_account ON account_aliases(account_id);
+
+CREATE OR REPLACE FUNCTION lock_account_handle(handle TEXT) RETURNS VOID AS $$
+    SELECT pg_advisory_xact_lock(hashtext('account_handle:' || handle));
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION account_alias_not_a_username() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM lock_account_handle(NEW.alias);
+    IF EXISTS (SELECT 1 FROM accounts WHERE username = NEW.alias) THEN
+        RAISE EXCEPTION 'handle "%" is already a username', NEW.alias
+            USING ERRCODE = 'unique_violation', CONSTRAINT = 'account_handles_unique';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION account_username_not_an_alias() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM lock_account_handle(NEW.username);
+    IF EXISTS (SELECT 1 FROM account_aliases WHERE alias = NEW.username) THEN
+        RAISE EXCEPTION 'handle "%" is already an alias', NEW.username
+            USING ERRCODE = 'unique_violation', CONSTRAINT = 'account_handles_unique';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_account_aliases_handle_unique ON account_aliases;
+CREATE TRIGGER trg_account_aliases_handle_unique
+    BEFORE INSERT OR UPDATE OF alias ON account_aliases
+    FOR EACH ROW EXECUTE FUNCTION account_alias_not_a_username();
+
+DROP TRIGGER IF EXISTS trg_accounts_handle_unique ON accounts;
+CREATE TRIGGER trg_accounts_handle_unique
+    BEFORE INSERT OR UPDATE OF username ON accounts
+    FOR EACH ROW EXECUTE FUNCTION account_username_not_an_alias();
@@ -0,0 +1,19 @@
+-- Trust edges decay with the time since they were last confirmed (see
+-- trust::weight::decay_factor). Only a co-signed re-confirmation
+-- (POST /trust/reconfirm) resets the clock; existing edges count as confirmed
+-- when they were created.
+ALTER TABLE reputation__endorsements
+    ADD COLUMN IF NOT EXISTS last_confirmed_at TIMESTAMPTZ;
+
+UPDATE reputation__endorsements
+    SET last_confirmed_at = created_at
+    WHERE last_confirmed_at IS NULL;
+
+ALTER TABLE reputation__endorsements
+    ALTER COLUMN last_confirmed_at SET DEFAULT now(),
+    ALTER COLUMN last_confirmed_at SET NOT NULL;
+
+-- Re-confirmation prompts list an account's stale trust edges.
+CREATE INDEX IF NOT EXISTS idx_endorsements_trust_last_confirmed
+    ON reputation__endorsements (last_confirmed_at)
+    WHERE topic = 'trust' AND revoked_at IS NULL;
@@ -0,0 +1,30 @@
+-- Per-account notification inbox (GET /notifications).
+-- Rows are written by the notification projector from domain events; the
+-- payload carries identifiers only, never request bodies or key material.
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    category TEXT NOT NULL,               -- e.g. security, trust
+    kind TEXT NOT NULL,                   -- e.g. device_revoked
+    payload JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    read_at TIMESTAMPTZ                   -- NULL = unread
+);
+
+-- Keyset pagination, newest first.
+CREATE INDEX IF NOT EXISTS idx_notifications_account_created
+    ON notifications (account_id, created_at DESC, id DESC);
+
+-- Unread counts.
+CREATE INDEX IF NOT EXISTS idx_notifications_account_unread
+    ON notifications (account_id)
+    WHERE read_at IS NULL;
+
+-- Per-category opt-outs. A missing row means the category is enabled.
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    category TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, category)
+);
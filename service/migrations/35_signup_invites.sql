@@ -0,0 +1,16 @@
+-- Invite codes for invite-only signup (TC_SIGNUP__INVITE_ONLY).
+-- Only the SHA-256 of each code is stored; the plaintext is shown once to
+-- the issuing account. A code is redeemed by at most one new account.
+CREATE TABLE IF NOT EXISTS signup_invites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code_hash BYTEA NOT NULL UNIQUE,
+    created_by UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    redeemed_by UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    redeemed_at TIMESTAMPTZ
+);
+
+-- Listing an account's codes and counting its outstanding ones.
+CREATE INDEX IF NOT EXISTS idx_signup_invites_created_by
+    ON signup_invites (created_by, created_at DESC);
@@ -0,0 +1,13 @@
+-- Per-day request counts for API tokens, used for daily/monthly quotas and
+-- the top-consumers admin view. Counters are kept in memory by each API
+-- instance and flushed here in batches, so rows may trail live traffic by
+-- the flush interval.
+CREATE TABLE IF NOT EXISTS api_token_usage (
+    token_id UUID NOT NULL REFERENCES api_tokens(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (token_id, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_token_usage_day ON api_token_usage(day);
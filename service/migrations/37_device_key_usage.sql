@@ -0,0 +1,16 @@
+-- Per-device request counters for the device listing. Counts are kept in
+-- hourly buckets so 24h and 7d totals can be summed cheaply; buckets older
+-- than eight days are pruned on each flush. The API batches updates in memory
+-- and writes them periodically, so totals trail live traffic slightly.
+CREATE TABLE IF NOT EXISTS device_key_usage (
+    device_kid TEXT NOT NULL REFERENCES device_keys(device_kid) ON DELETE CASCADE,
+    hour TIMESTAMPTZ NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (device_kid, hour)
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_key_usage_hour ON device_key_usage(hour);
+
+-- Route template of the most recent signed request, e.g. "GET /auth/devices"
+ALTER TABLE device_keys ADD COLUMN IF NOT EXISTS last_endpoint TEXT;
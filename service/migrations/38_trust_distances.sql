@@ -0,0 +1,37 @@
+-- Precomputed bounded-depth trust distances (see trust::distances).
+--
+-- trust__distance_sources tracks which accounts have their outgoing distances
+-- materialized. A source is marked dirty when a trust edge it can reach within
+-- the depth bound changes, and the refresher recomputes dirty or stale sources
+-- in the background. Lookups on a dirty source, or for a pair with no row,
+-- fall back to a live traversal.
+CREATE TABLE IF NOT EXISTS trust__distance_sources (
+    source_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    dirty BOOLEAN NOT NULL DEFAULT true,
+    refreshed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_trust_distance_sources_refreshed
+    ON trust__distance_sources (refreshed_at NULLS FIRST);
+
+CREATE TABLE IF NOT EXISTS trust__distances (
+    source_id UUID NOT NULL REFERENCES trust__distance_sources(source_id) ON DELETE CASCADE,
+    target_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    distance REAL NOT NULL,
+    hops INTEGER NOT NULL,
+
+    PRIMARY KEY (source_id, target_id)
+);
+
+-- Finding the sources affected by a change to an edge out of `target_id`
+CREATE INDEX IF NOT EXISTS idx_trust_distances_target ON trust__distances (target_id, hops);
+
+-- Every account with an active trust edge starts out dirty
+INSERT INTO trust__distance_sources (source_id)
+SELECT DISTINCT endorser_id
+FROM reputation__endorsements
+WHERE revoked_at IS NULL
+  AND endorser_id IS NOT NULL
+  AND topic = 'trust'
+  AND in_slot = true
+ON CONFLICT (source_id) DO NOTHING;
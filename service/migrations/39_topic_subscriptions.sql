@@ -0,0 +1,28 @@
+-- Endorsement topic subscriptions and per-subscriber feeds (GET /topics/feed).
+-- The topic feed projector fans each endorsement event out to one row per
+-- subscriber of its topic. Trust edges are never fanned out.
+CREATE TABLE IF NOT EXISTS topic_subscriptions (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    topic TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, topic)
+);
+
+-- Fan-out looks up subscribers by topic.
+CREATE INDEX IF NOT EXISTS idx_topic_subscriptions_topic ON topic_subscriptions (topic);
+
+CREATE TABLE IF NOT EXISTS topic_feed_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    topic TEXT NOT NULL,
+    endorsement_id UUID NOT NULL REFERENCES reputation__endorsements(id) ON DELETE CASCADE,
+    subject_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    issuer_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    -- Re-issued endorsements upsert the same row, so deliver each one once.
+    UNIQUE (account_id, endorsement_id)
+);
+
+-- Keyset pagination, newest first.
+CREATE INDEX IF NOT EXISTS idx_topic_feed_items_account_created
+    ON topic_feed_items (account_id, created_at DESC, id DESC);
@@ -0,0 +1,15 @@
+-- Per-endorsement visibility, chosen by the issuer.
+--   public        listed to anyone, counted in public aggregates
+--   subject_only  listed to the subject only, still counted in aggregates
+--   private       listed to the subject only, excluded from public aggregates
+ALTER TABLE reputation__endorsements
+    ADD COLUMN IF NOT EXISTS visibility TEXT NOT NULL DEFAULT 'public'
+        CHECK (visibility IN ('public', 'subject_only', 'private'));
+
+-- The issuer's device-signed request that created (or last re-issued) the
+-- endorsement. Its body names the visibility, so a change to the column
+-- that the issuer did not sign is detectable by re-verifying the envelope
+-- against the issuing device's public key. NULL for endorsements not issued
+-- through a signed request (genesis, ID.me, trust edges).
+ALTER TABLE reputation__endorsements
+    ADD COLUMN IF NOT EXISTS issuance_envelope JSONB;
@@ -0,0 +1,20 @@
+-- Research export requests (POST /research/exports). A researcher files a
+-- request for one dataset; an admin other than the requester approves or
+-- rejects it. Approved exports can be downloaded a limited number of times
+-- until they expire. Exported rows are aggregates only.
+CREATE TABLE IF NOT EXISTS research_exports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    requester_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    dataset TEXT NOT NULL CHECK (dataset IN ('endorsement_aggregates', 'trust_degrees')),
+    purpose TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    decided_by UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    download_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Listing a researcher's requests and counting today's.
+CREATE INDEX IF NOT EXISTS idx_research_exports_requester_created
+    ON research_exports (requester_id, created_at DESC);
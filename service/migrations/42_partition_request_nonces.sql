@@ -0,0 +1,46 @@
+-- Range-partition request_nonces by created_at so expired nonces are dropped
+-- a partition at a time instead of deleted row by row.
+--
+-- The retention worker creates hourly partitions ahead of time
+-- (request_nonces_pYYYYMMDDHH) and drops them once they are wholly older than
+-- auth.nonce_ttl_secs. Rows land in request_nonces_default when no hourly
+-- partition covers them yet, which includes every row copied below; the
+-- regular nonce purge empties it.
+--
+-- A partitioned table cannot keep nonce_hash as its primary key (unique
+-- indexes must include the partition key), so replay detection now checks
+-- for an existing hash under a per-nonce advisory lock instead of relying on
+-- ON CONFLICT. See identity::repo::nonces.
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM pg_partitioned_table pt
+        JOIN pg_class c ON c.oid = pt.partrelid
+        WHERE c.relname = 'request_nonces'
+    ) THEN
+        RETURN;
+    END IF;
+
+    ALTER TABLE request_nonces RENAME TO request_nonces_unpartitioned;
+    ALTER TABLE request_nonces_unpartitioned
+        RENAME CONSTRAINT request_nonces_pkey TO request_nonces_unpartitioned_pkey;
+    ALTER INDEX IF EXISTS idx_request_nonces_created_at
+        RENAME TO idx_request_nonces_unpartitioned_created_at;
+
+    CREATE TABLE request_nonces (
+        nonce_hash  BYTEA NOT NULL,
+        created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+    ) PARTITION BY RANGE (created_at);
+
+    CREATE TABLE request_nonces_default PARTITION OF request_nonces DEFAULT;
+
+    -- Replay lookups; each partition gets its own copy
+    CREATE INDEX idx_request_nonces_hash ON request_nonces (nonce_hash);
+    CREATE INDEX idx_request_nonces_created_at ON request_nonces (created_at);
+
+    INSERT INTO request_nonces (nonce_hash, created_at)
+    SELECT nonce_hash, created_at FROM request_nonces_unpartitioned;
+
+    DROP TABLE request_nonces_unpartitioned;
+END
+$$;
@@ -0,0 +1,21 @@
+-- Account lifecycle status. Only 'active' accounts can authenticate; see
+-- identity::status for the allowed transitions and who may make them.
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'suspended', 'locked', 'deleted')),
+    ADD COLUMN IF NOT EXISTS status_changed_at TIMESTAMPTZ;
+
+-- Audit trail of every status change. actor_account_id is the owner for
+-- self-service changes and the admin otherwise.
+CREATE TABLE IF NOT EXISTS account_status_changes (
+    id BIGSERIAL PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    actor_account_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_status_changes_account_created
+    ON account_status_changes (account_id, created_at DESC);
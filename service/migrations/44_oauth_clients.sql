@@ -0,0 +1,22 @@
+-- Third-party OAuth clients registered through /developer/clients. Only the
+-- SHA-256 of each client secret is stored. After a rotation the previous
+-- secret keeps working until previous_secret_expires_at so deployed clients
+-- can roll over without downtime.
+CREATE TABLE IF NOT EXISTS oauth_clients (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    client_id TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    redirect_uris TEXT[] NOT NULL,
+    scopes TEXT[] NOT NULL,
+    secret_hash BYTEA NOT NULL UNIQUE,
+    previous_secret_hash BYTEA,
+    previous_secret_expires_at TIMESTAMPTZ,
+    secret_rotated_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_oauth_clients_owner_created
+    ON oauth_clients (owner_account_id, created_at DESC);
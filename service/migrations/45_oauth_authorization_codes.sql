@@ -0,0 +1,28 @@
+-- Authorization server for registered OAuth clients (code flow with PKCE).
+-- Codes are single-use and short-lived; only their SHA-256 is stored. The
+-- access tokens they exchange for are ordinary api_tokens rows tagged with
+-- the issuing client, so bearer auth and scope checks work unchanged and
+-- revoking the client revokes its tokens.
+CREATE TABLE IF NOT EXISTS oauth_authorization_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code_hash BYTEA NOT NULL UNIQUE,
+    client_id UUID NOT NULL REFERENCES oauth_clients(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    redirect_uri TEXT NOT NULL,
+    scopes TEXT[] NOT NULL,
+    code_challenge TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_oauth_authorization_codes_expires
+    ON oauth_authorization_codes (expires_at);
+
+-- Tokens issued to a client do not count against the account's own
+-- personal token limit.
+ALTER TABLE api_tokens
+    ADD COLUMN IF NOT EXISTS oauth_client_id UUID REFERENCES oauth_clients(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_api_tokens_oauth_client
+    ON api_tokens (oauth_client_id) WHERE oauth_client_id IS NOT NULL;
@@ -0,0 +1,13 @@
+-- Short pairing codes for trust invites, typed in where a QR code cannot be
+-- scanned (e.g. in-app browsers without camera access). Codes live for
+-- minutes, never outlive their invite, and are rate limited per endorser.
+CREATE TABLE IF NOT EXISTS trust__invite_pairing_codes (
+    code TEXT PRIMARY KEY,
+    invite_id UUID NOT NULL REFERENCES trust__invites(id) ON DELETE CASCADE,
+    endorser_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_invite_pairing_codes_endorser_created
+    ON trust__invite_pairing_codes (endorser_id, created_at DESC);
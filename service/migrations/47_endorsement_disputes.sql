@@ -0,0 +1,26 @@
+-- Disputes raised by the subject of an endorsement. The subject's signed
+-- request is kept as the dispute statement. A dispute may pause the
+-- endorsement's inclusion in public aggregates until a moderator upholds
+-- it (revoking the endorsement) or dismisses it.
+CREATE TABLE IF NOT EXISTS reputation__endorsement_disputes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    endorsement_id UUID NOT NULL UNIQUE
+        REFERENCES reputation__endorsements(id) ON DELETE CASCADE,
+    subject_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    issuer_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    statement TEXT NOT NULL,
+    envelope JSONB NOT NULL,
+    pauses_aggregates BOOLEAN NOT NULL DEFAULT false,
+    status TEXT NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'upheld', 'dismissed')),
+    resolved_by UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    resolution_note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_endorsement_disputes_open
+    ON reputation__endorsement_disputes (created_at) WHERE status = 'open';
+
+ALTER TABLE reputation__endorsements
+    ADD COLUMN IF NOT EXISTS aggregates_paused BOOLEAN NOT NULL DEFAULT false;
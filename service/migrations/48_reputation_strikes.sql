@@ -0,0 +1,44 @@
+-- Moderator-issued strikes. Each strike subtracts a penalty from the
+-- account's reputation score that decays linearly to zero at expires_at; a
+-- granted appeal removes it entirely. See reputation::strikes.
+CREATE TABLE IF NOT EXISTS reputation__strikes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    issued_by UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    reason_code TEXT NOT NULL
+        CHECK (reason_code IN ('spam', 'harassment', 'false_endorsement',
+                               'impersonation', 'vote_manipulation', 'other')),
+    note TEXT,
+    penalty DOUBLE PRECISION NOT NULL CHECK (penalty > 0),
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    appeal_status TEXT NOT NULL DEFAULT 'none'
+        CHECK (appeal_status IN ('none', 'pending', 'granted', 'denied')),
+    appeal_statement TEXT,
+    appealed_at TIMESTAMPTZ,
+    appeal_resolved_by UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    appeal_resolved_at TIMESTAMPTZ,
+    CHECK (expires_at > issued_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_reputation_strikes_account
+    ON reputation__strikes (account_id, issued_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_reputation_strikes_pending_appeals
+    ON reputation__strikes (appealed_at)
+    WHERE appeal_status = 'pending';
+
+-- Audit trail of every strike action, shown to the affected account.
+-- actor_account_id is the moderator, or the account itself for appeals.
+CREATE TABLE IF NOT EXISTS reputation__strike_events (
+    id BIGSERIAL PRIMARY KEY,
+    strike_id UUID NOT NULL REFERENCES reputation__strikes(id) ON DELETE CASCADE,
+    action TEXT NOT NULL
+        CHECK (action IN ('issued', 'appealed', 'appeal_granted', 'appeal_denied')),
+    actor_account_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_reputation_strike_events_strike
+    ON reputation__strike_events (strike_id, created_at);
@@ -0,0 +1,39 @@
+-- Completed account verification methods. See reputation::verification.
+-- destination_hash is SHA-256 of the normalized email address or phone
+-- number (never the raw value), or of the provider's reference for
+-- government ID checks; it keeps one destination from verifying two accounts.
+CREATE TABLE IF NOT EXISTS reputation__verifications (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    method TEXT NOT NULL
+        CHECK (method IN ('email', 'phone', 'in_person', 'government_id')),
+    provider TEXT NOT NULL,
+    destination_hash BYTEA,
+    verified_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, method)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_reputation_verifications_destination
+    ON reputation__verifications (method, destination_hash)
+    WHERE destination_hash IS NOT NULL;
+
+-- Outstanding email/phone codes, at most one per account and method.
+-- Requesting a new code replaces the old one and resets attempts.
+CREATE TABLE IF NOT EXISTS reputation__verification_challenges (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    method TEXT NOT NULL CHECK (method IN ('email', 'phone')),
+    destination_hash BYTEA NOT NULL,
+    code_hash BYTEA NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (account_id, method)
+);
+
+-- Verified accounts vouching that they met the subject in person.
+CREATE TABLE IF NOT EXISTS reputation__in_person_attestations (
+    subject_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    attester_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (subject_id, attester_id),
+    CHECK (subject_id <> attester_id)
+);
@@ -0,0 +1,28 @@
+-- Phone numbers enrolled for step-up confirmation. See identity::step_up.
+-- The number is stored in full because codes are texted to it.
+CREATE TABLE IF NOT EXISTS step_up_phones (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    phone_number TEXT NOT NULL,
+    enrolled_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Texted codes. A challenge is bound to the device that created it; it is
+-- confirmed with the code and then consumed by retrying the gated request
+-- (enroll_phone challenges are consumed by their confirmation).
+CREATE TABLE IF NOT EXISTS step_up_challenges (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    device_kid TEXT NOT NULL,
+    action TEXT NOT NULL
+        CHECK (action IN ('enroll_phone', 'revoke_device', 'remove_phone')),
+    phone_number TEXT NOT NULL,
+    code_hash BYTEA NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    confirmed_at TIMESTAMPTZ,
+    consumed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_step_up_challenges_account
+    ON step_up_challenges (account_id, created_at);
@@ -0,0 +1,16 @@
+-- Names new accounts and aliases may not take, managed by account admins
+-- through /auth/reserved-usernames. See identity::username. The API servers
+-- cache this list and reload it every minute.
+CREATE TABLE IF NOT EXISTS reserved_usernames (
+    name TEXT PRIMARY KEY CHECK (name = lower(name) AND name <> ''),
+    reason TEXT,
+    created_by UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- The list that used to be compiled in
+INSERT INTO reserved_usernames (name) VALUES
+    ('admin'), ('administrator'), ('root'), ('system'), ('mod'),
+    ('moderator'), ('support'), ('help'), ('api'), ('graphql'), ('auth'),
+    ('signup'), ('login'), ('null'), ('undefined'), ('anonymous')
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,46 @@
+-- Usernames and aliases that stopped belonging to an account, so signup and
+-- alias creation can refuse them for usernames.release_cooldown_days. The
+-- triggers record every way a handle is freed: removing an alias, renaming
+-- or purging an account. Account admins can lift a quarantine early through
+-- /auth/released-usernames. Handles are stored lowercased.
+CREATE TABLE IF NOT EXISTS released_handles (
+    handle TEXT PRIMARY KEY CHECK (handle = lower(handle)),
+    -- No foreign key: the account may be gone
+    account_id UUID NOT NULL,
+    released_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE OR REPLACE FUNCTION record_released_handle(handle TEXT, account_id UUID) RETURNS VOID AS $$
+    INSERT INTO released_handles (handle, account_id)
+    VALUES (lower(handle), account_id)
+    ON CONFLICT (handle) DO UPDATE
+        SET account_id = EXCLUDED.account_id, released_at = now();
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION account_alias_released() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' OR NEW.alias IS DISTINCT FROM OLD.alias THEN
+        PERFORM record_released_handle(OLD.alias, OLD.account_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION account_username_released() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' OR NEW.username IS DISTINCT FROM OLD.username THEN
+        PERFORM record_released_handle(OLD.username, OLD.id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_account_aliases_released ON account_aliases;
+CREATE TRIGGER trg_account_aliases_released
+    AFTER DELETE OR UPDATE OF alias ON account_aliases
+    FOR EACH ROW EXECUTE FUNCTION account_alias_released();
+
+DROP TRIGGER IF EXISTS trg_accounts_released ON accounts;
+CREATE TRIGGER trg_accounts_released
+    AFTER DELETE OR UPDATE OF username ON accounts
+    FOR EACH ROW EXECUTE FUNCTION account_username_released();
@@ -0,0 +1,8 @@
+-- Per-account GraphQL cost spent in the current one-minute window; see
+-- src/graphql/cost.rs. One row per account: a charge in a later window
+-- replaces the previous window's total rather than adding a row.
+CREATE TABLE IF NOT EXISTS graphql_cost_budgets (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    window_start TIMESTAMPTZ NOT NULL,
+    spent INTEGER NOT NULL CHECK (spent >= 0)
+);
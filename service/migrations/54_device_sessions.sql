@@ -0,0 +1,15 @@
+-- Short-lived bearer sessions issued to a device by a signed
+-- POST /auth/sessions. Only the SHA-256 of each token is stored. A session
+-- dies with its device: lookups join device_keys and refuse revoked devices,
+-- and deleting the device cascades.
+CREATE TABLE IF NOT EXISTS device_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    device_kid TEXT NOT NULL REFERENCES device_keys(device_kid) ON DELETE CASCADE,
+    token_hash BYTEA NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_sessions_device ON device_sessions (device_kid);
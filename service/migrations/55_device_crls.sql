@@ -0,0 +1,16 @@
+-- Device revocation lists for offline verifiers.
+-- revocation_envelope keeps the revoking device's signed DELETE request so a
+-- revocation can be checked against the account's root key without trusting
+-- the server. Devices revoked before this migration have none.
+ALTER TABLE device_keys
+    ADD COLUMN IF NOT EXISTS revocation_envelope JSONB;
+
+-- One published CRL document per account, regenerated whenever one of its
+-- devices is revoked. crl_number is the count of revoked devices, so it only
+-- grows; a row whose number trails the current count is stale.
+CREATE TABLE IF NOT EXISTS account_crls (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    crl_number BIGINT NOT NULL CHECK (crl_number >= 0),
+    document JSONB NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
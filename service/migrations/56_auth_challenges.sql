@@ -0,0 +1,11 @@
+-- Server-issued challenges for signed requests (GET /auth/challenge).
+-- Each challenge is bound to one device KID, used at most once, and only its
+-- SHA-256 is stored. Expired rows are purged by the retention worker.
+CREATE TABLE IF NOT EXISTS auth_challenges (
+    challenge_hash BYTEA PRIMARY KEY,
+    device_kid TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS auth_challenges_expires_at_idx ON auth_challenges (expires_at);
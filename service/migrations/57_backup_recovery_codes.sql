@@ -0,0 +1,4 @@
+-- Optional recovery code gating GET /auth/backups/{root_kid}.
+-- Stores SHA-256 of the code; NULL means the backup is served without one.
+ALTER TABLE account_backups
+    ADD COLUMN IF NOT EXISTS recovery_code_hash BYTEA;
@@ -0,0 +1,30 @@
+-- SELECT-only group role for the optional reader pool (database.reader_user).
+-- Operators create a LOGIN role and GRANT tc_reader TO it; the service
+-- refuses to start if that login can write.
+--
+-- Roles are cluster-wide, so creation tolerates an existing role and a
+-- migration user without CREATEROLE (grants are then skipped until an
+-- operator creates the role and re-applies them).
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'tc_reader') THEN
+        CREATE ROLE tc_reader NOLOGIN;
+    END IF;
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+    WHEN insufficient_privilege THEN
+        RAISE NOTICE 'tc_reader not created: migration role lacks CREATEROLE';
+END
+$$;
+
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'tc_reader') THEN
+        GRANT USAGE ON SCHEMA public TO tc_reader;
+        GRANT SELECT ON ALL TABLES IN SCHEMA public TO tc_reader;
+        -- Tables created by later migrations (run as this same role)
+        ALTER DEFAULT PRIVILEGES IN SCHEMA public GRANT SELECT ON TABLES TO tc_reader;
+    END IF;
+END
+$$;
@@ -0,0 +1,11 @@
+-- Device key rotation: a device replaces its key pair without losing its
+-- identity. The new row points at the key it replaced and keeps the old
+-- key's signature over the new public key. A key is rotated at most once.
+
+ALTER TABLE device_keys
+    ADD COLUMN IF NOT EXISTS rotated_from_kid TEXT REFERENCES device_keys (device_kid),
+    ADD COLUMN IF NOT EXISTS rotation_signature BYTEA;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_device_keys_rotated_from
+    ON device_keys (rotated_from_kid)
+    WHERE rotated_from_kid IS NOT NULL;
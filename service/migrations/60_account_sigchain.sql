@@ -0,0 +1,17 @@
+-- Per-account sigchain: an append-only, hash-linked log of the signed
+-- requests that changed an account's device set. envelope holds the exact
+-- JSON text of the device's signed request; hash covers seqno, prev_hash,
+-- link_type, and those bytes, so clients can re-verify the chain locally.
+CREATE TABLE IF NOT EXISTS account_sigchain (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    seqno BIGINT NOT NULL CHECK (seqno >= 1),
+    link_type TEXT NOT NULL,
+    signer_kid TEXT NOT NULL,
+    envelope TEXT NOT NULL,
+    prev_hash BYTEA CHECK (prev_hash IS NULL OR length(prev_hash) = 32),
+    hash BYTEA NOT NULL CHECK (length(hash) = 32),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, seqno),
+    -- Only the first link has no predecessor
+    CHECK ((seqno = 1) = (prev_hash IS NULL))
+);
@@ -0,0 +1,9 @@
+-- Precomputed feed rankings for accounts with a large trust reach; see
+-- crate::feed. items is the ranked list, best first, scored for computed_at.
+CREATE TABLE IF NOT EXISTS feed__snapshots (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    computed_at TIMESTAMPTZ NOT NULL,
+    items JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_feed_snapshots_computed_at ON feed__snapshots (computed_at);
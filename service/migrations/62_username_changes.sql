@@ -0,0 +1,5 @@
+-- When an account last changed its username, so PATCH
+-- /auth/accounts/me/username can enforce usernames.change_cooldown_days.
+-- NULL until the first change. The old name is quarantined by the
+-- released_handles trigger from migration 52.
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS username_changed_at TIMESTAMPTZ;
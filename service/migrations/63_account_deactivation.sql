@@ -0,0 +1,13 @@
+-- Self-service account deletion with a grace period. DELETE
+-- /auth/accounts/me moves an account to 'deactivated' and sets purge_after;
+-- the owner's root key can restore it until then, after which the purge
+-- sweeper deletes the row. See identity::deletion.
+ALTER TABLE accounts DROP CONSTRAINT IF EXISTS accounts_status_check;
+ALTER TABLE accounts ADD CONSTRAINT accounts_status_check
+    CHECK (status IN ('active', 'suspended', 'locked', 'deleted', 'deactivated'));
+
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS purge_after TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_accounts_purge_after
+    ON accounts (purge_after)
+    WHERE purge_after IS NOT NULL;
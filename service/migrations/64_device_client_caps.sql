@@ -0,0 +1,4 @@
+-- Envelope versions each device has declared it can produce (tokens such as
+-- 'sig-v2'). Only ever raised; requests in an older version are refused.
+-- See identity::capabilities.
+ALTER TABLE device_keys ADD COLUMN IF NOT EXISTS client_caps TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,20 @@
+-- Stored rows that failed the background integrity sweep (see
+-- integrity::worker). One row per failing record and check; each sweep that
+-- still finds it bumps last_seen_at and reopens it if it had been resolved.
+CREATE TABLE IF NOT EXISTS integrity_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code TEXT NOT NULL,
+    account_id UUID REFERENCES accounts(id) ON DELETE CASCADE,
+    record TEXT NOT NULL,
+    detail TEXT NOT NULL,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    resolved_at TIMESTAMPTZ,
+    resolved_by UUID REFERENCES accounts(id) ON DELETE SET NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_integrity_alerts_record
+    ON integrity_alerts (code, COALESCE(account_id, '00000000-0000-0000-0000-000000000000'::uuid), record);
+
+CREATE INDEX IF NOT EXISTS idx_integrity_alerts_open
+    ON integrity_alerts (last_seen_at DESC) WHERE resolved_at IS NULL;
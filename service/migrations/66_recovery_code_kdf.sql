@@ -0,0 +1,14 @@
+-- Recovery codes are stored as Argon2id PHC strings from now on. Existing
+-- unsalted SHA-256 digests become 'sha256$<base64url digest>' so they keep
+-- working; the server rehashes each one after its next successful use.
+-- See identity::recovery_code.
+DO $$ BEGIN
+    IF EXISTS (SELECT 1 FROM information_schema.columns
+               WHERE table_name = 'account_backups'
+                 AND column_name = 'recovery_code_hash'
+                 AND data_type = 'bytea') THEN
+        ALTER TABLE account_backups
+            ALTER COLUMN recovery_code_hash TYPE TEXT
+            USING 'sha256$' || rtrim(translate(encode(recovery_code_hash, 'base64'), '+/', '-_'), '=');
+    END IF;
+END $$;
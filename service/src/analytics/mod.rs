@@ -0,0 +1,374 @@
+//! Columnar exports for analytics.
+//!
+//! On a schedule (see [`crate::config::AnalyticsExportConfig`]) the export
+//! worker reads each [`ExportDataset`] from the reader pool in keyset pages
+//! and writes it as one Snappy-compressed Parquet file to an [`sink::ExportSink`]
+//! (a local directory or an S3-compatible bucket), so analysts can query
+//! activity with `DuckDB` or Spark instead of the production database.
+//!
+//! Each run is a full snapshot, so revocations show up in later files. Files
+//! are laid out as
+//!
+//! ```text
+//! <prefix>/<dataset>/v<schema_version>/<YYYY-MM-DD>/<dataset>-<YYYYMMDDTHHMMSSZ>.parquet
+//! ```
+//!
+//! and carry `tc.dataset` and `tc.schema_version` in their key-value
+//! metadata. Schema evolution follows two rules: a column may be added, as
+//! nullable and after the existing ones, without changing the version, so
+//! readers combining old and new files use `union_by_name`; renaming,
+//! retyping or dropping a column bumps the version, which starts a new
+//! `v<N>` directory that is never mixed with the old one.
+//!
+//! The export holds account IDs, so the destination must be treated like a
+//! database backup. Private endorsements, evidence and signed envelopes are
+//! left out.
+
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float32Array, Int64Array, RecordBatch, StringArray,
+    TimestampMicrosecondArray,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+
+pub mod repo;
+pub mod sink;
+pub mod worker;
+
+use repo::{EndorsementExportRow, SigchainExportRow};
+
+/// A table exported for analytics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportDataset {
+    /// Every non-private endorsement, revoked ones included.
+    Endorsements,
+    /// Every account sigchain link: the signed requests that changed an
+    /// account's device set.
+    SigchainLinks,
+}
+
+impl ExportDataset {
+    /// Every dataset, in export order.
+    pub const ALL: [Self; 2] = [Self::Endorsements, Self::SigchainLinks];
+
+    /// Directory name and `tc.dataset` metadata value.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Endorsements => "endorsements",
+            Self::SigchainLinks => "sigchain_links",
+        }
+    }
+
+    /// Bumped whenever a column is renamed, retyped or dropped.
+    #[must_use]
+    pub const fn schema_version(self) -> u32 {
+        match self {
+            Self::Endorsements | Self::SigchainLinks => 1,
+        }
+    }
+
+    /// Arrow schema of the exported file.
+    #[must_use]
+    pub fn schema(self) -> SchemaRef {
+        let fields = match self {
+            Self::Endorsements => vec![
+                Field::new("id", DataType::Utf8, false),
+                Field::new("subject_id", DataType::Utf8, false),
+                Field::new("endorser_id", DataType::Utf8, true),
+                Field::new("topic", DataType::Utf8, false),
+                Field::new("weight", DataType::Float32, false),
+                Field::new("visibility", DataType::Utf8, false),
+                Field::new("in_slot", DataType::Boolean, false),
+                Field::new("created_at", timestamp(), false),
+                Field::new("revoked_at", timestamp(), true),
+                Field::new("last_confirmed_at", timestamp(), false),
+            ],
+            Self::SigchainLinks => vec![
+                Field::new("account_id", DataType::Utf8, false),
+                Field::new("seqno", DataType::Int64, false),
+                Field::new("link_type", DataType::Utf8, false),
+                Field::new("signer_kid", DataType::Utf8, false),
+                Field::new("created_at", timestamp(), false),
+            ],
+        };
+        Arc::new(Schema::new(fields))
+    }
+
+    /// Object key for a snapshot taken at `now`.
+    #[must_use]
+    pub fn object_key(self, prefix: &str, now: DateTime<Utc>) -> String {
+        format!(
+            "{prefix}/{name}/v{version}/{date}/{name}-{stamp}.parquet",
+            name = self.as_str(),
+            version = self.schema_version(),
+            date = now.format("%Y-%m-%d"),
+            stamp = now.format("%Y%m%dT%H%M%SZ"),
+        )
+    }
+}
+
+fn timestamp() -> DataType {
+    DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+}
+
+fn timestamps(values: impl Iterator<Item = DateTime<Utc>>) -> ArrayRef {
+    Arc::new(
+        TimestampMicrosecondArray::from(values.map(|t| t.timestamp_micros()).collect::<Vec<_>>())
+            .with_timezone("UTC"),
+    )
+}
+
+fn optional_timestamps(values: impl Iterator<Item = Option<DateTime<Utc>>>) -> ArrayRef {
+    Arc::new(
+        TimestampMicrosecondArray::from(
+            values
+                .map(|t| t.map(|t| t.timestamp_micros()))
+                .collect::<Vec<_>>(),
+        )
+        .with_timezone("UTC"),
+    )
+}
+
+/// Errors while encoding an export.
+#[derive(Debug, thiserror::Error)]
+pub enum EncodeError {
+    #[error("arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+    #[error("parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+}
+
+/// Arrow batch for a page of endorsements.
+///
+/// # Errors
+///
+/// Returns an error if the columns do not match the schema.
+pub fn endorsement_batch(rows: &[EndorsementExportRow]) -> Result<RecordBatch, ArrowError> {
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.id.to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.subject_id.to_string()),
+        )),
+        Arc::new(
+            rows.iter()
+                .map(|r| r.endorser_id.map(|id| id.to_string()))
+                .collect::<StringArray>(),
+        ),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.topic.as_str()),
+        )),
+        Arc::new(Float32Array::from_iter_values(
+            rows.iter().map(|r| r.weight),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.visibility.as_str()),
+        )),
+        Arc::new(BooleanArray::from(
+            rows.iter().map(|r| r.in_slot).collect::<Vec<_>>(),
+        )),
+        timestamps(rows.iter().map(|r| r.created_at)),
+        optional_timestamps(rows.iter().map(|r| r.revoked_at)),
+        timestamps(rows.iter().map(|r| r.last_confirmed_at)),
+    ];
+    RecordBatch::try_new(ExportDataset::Endorsements.schema(), columns)
+}
+
+/// Arrow batch for a page of sigchain links.
+///
+/// # Errors
+///
+/// Returns an error if the columns do not match the schema.
+pub fn sigchain_batch(rows: &[SigchainExportRow]) -> Result<RecordBatch, ArrowError> {
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.account_id.to_string()),
+        )),
+        Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.seqno))),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.link_type.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.signer_kid.as_str()),
+        )),
+        timestamps(rows.iter().map(|r| r.created_at)),
+    ];
+    RecordBatch::try_new(ExportDataset::SigchainLinks.schema(), columns)
+}
+
+/// Writes batches of one dataset into an in-memory Parquet file, one row
+/// group per batch.
+pub struct ParquetFile {
+    writer: ArrowWriter<Vec<u8>>,
+    rows: u64,
+}
+
+impl ParquetFile {
+    /// Start a file for `dataset` with row groups of up to `batch_rows`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer cannot be created.
+    pub fn new(dataset: ExportDataset, batch_rows: u32) -> Result<Self, EncodeError> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_max_row_group_size(usize::try_from(batch_rows).unwrap_or(usize::MAX))
+            .set_key_value_metadata(Some(vec![
+                KeyValue::new("tc.dataset".to_string(), dataset.as_str().to_string()),
+                KeyValue::new(
+                    "tc.schema_version".to_string(),
+                    dataset.schema_version().to_string(),
+                ),
+            ]))
+            .build();
+        let writer = ArrowWriter::try_new(Vec::new(), dataset.schema(), Some(properties))?;
+        Ok(Self { writer, rows: 0 })
+    }
+
+    /// Append one batch as a row group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch does not match the schema.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<(), EncodeError> {
+        self.writer.write(batch)?;
+        self.writer.flush()?;
+        self.rows += batch.num_rows() as u64;
+        Ok(())
+    }
+
+    /// Rows written so far.
+    #[must_use]
+    pub const fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// Write the footer and return the file's bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the footer cannot be written.
+    pub fn finish(self) -> Result<Vec<u8>, EncodeError> {
+        Ok(self.writer.into_inner()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use chrono::TimeZone;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use uuid::Uuid;
+
+    fn endorsement(revoked: bool) -> EndorsementExportRow {
+        let created_at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        EndorsementExportRow {
+            id: Uuid::new_v4(),
+            subject_id: Uuid::new_v4(),
+            endorser_id: (!revoked).then(Uuid::new_v4),
+            topic: "trust".to_string(),
+            weight: 0.5,
+            visibility: "public".to_string(),
+            in_slot: true,
+            created_at,
+            revoked_at: revoked.then_some(created_at),
+            last_confirmed_at: created_at,
+        }
+    }
+
+    #[test]
+    fn schema_versions_pin_their_columns() {
+        // Changing a v1 column without bumping `schema_version` breaks readers
+        let columns = |dataset: ExportDataset| -> Vec<String> {
+            dataset
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect()
+        };
+        assert_eq!(ExportDataset::Endorsements.schema_version(), 1);
+        assert_eq!(
+            columns(ExportDataset::Endorsements),
+            [
+                "id",
+                "subject_id",
+                "endorser_id",
+                "topic",
+                "weight",
+                "visibility",
+                "in_slot",
+                "created_at",
+                "revoked_at",
+                "last_confirmed_at"
+            ]
+        );
+        assert_eq!(ExportDataset::SigchainLinks.schema_version(), 1);
+        assert_eq!(
+            columns(ExportDataset::SigchainLinks),
+            [
+                "account_id",
+                "seqno",
+                "link_type",
+                "signer_kid",
+                "created_at"
+            ]
+        );
+    }
+
+    #[test]
+    fn object_keys_are_partitioned_by_version_and_day() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 4, 5, 6).unwrap();
+        assert_eq!(
+            ExportDataset::Endorsements.object_key("prod/tc", now),
+            "prod/tc/endorsements/v1/2026-10-17/endorsements-20261017T040506Z.parquet"
+        );
+    }
+
+    #[test]
+    fn parquet_round_trips_with_metadata() {
+        let rows = [endorsement(false), endorsement(true), endorsement(false)];
+        let mut file = ParquetFile::new(ExportDataset::Endorsements, 2).expect("writer");
+        file.write(&endorsement_batch(&rows[..2]).expect("batch"))
+            .expect("write");
+        file.write(&endorsement_batch(&rows[2..]).expect("batch"))
+            .expect("write");
+        assert_eq!(file.rows(), 3);
+        let bytes = Bytes::from(file.finish().expect("finish"));
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes).expect("reader");
+        let metadata = builder.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 3);
+        let kv = metadata.key_value_metadata().expect("metadata");
+        assert!(kv
+            .iter()
+            .any(|e| e.key == "tc.schema_version" && e.value.as_deref() == Some("1")));
+        assert_eq!(builder.metadata().num_row_groups(), 2);
+
+        let batches: Vec<RecordBatch> = builder
+            .build()
+            .expect("reader")
+            .collect::<Result<_, _>>()
+            .expect("batches");
+        let batch = &batches[0];
+        assert_eq!(batch.schema(), ExportDataset::Endorsements.schema());
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("ids");
+        assert_eq!(ids.value(0), rows[0].id.to_string());
+        assert!(batch.column(2).is_null(1));
+        assert!(batch.column(8).is_valid(1));
+    }
+}
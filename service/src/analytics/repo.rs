@@ -0,0 +1,92 @@
+//! Keyset-paged reads of the exported tables
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tc_crypto::Kid;
+use uuid::Uuid;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// One endorsement as exported; evidence and envelopes are left out.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct EndorsementExportRow {
+    pub id: Uuid,
+    pub subject_id: Uuid,
+    /// `None` for genesis endorsements issued by the platform.
+    pub endorser_id: Option<Uuid>,
+    pub topic: String,
+    pub weight: f32,
+    /// `public` or `subject_only`; private endorsements are not exported.
+    pub visibility: String,
+    pub in_slot: bool,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_confirmed_at: DateTime<Utc>,
+}
+
+/// One sigchain link as exported; the signed envelope is left out.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct SigchainExportRow {
+    pub account_id: Uuid,
+    pub seqno: i64,
+    pub link_type: String,
+    pub signer_kid: Kid,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// Up to `limit` non-private endorsements with IDs after `after`, by ID.
+///
+/// # Errors
+///
+/// Returns the underlying database error.
+pub async fn endorsements_page(
+    pool: &PgPool,
+    after: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<EndorsementExportRow>, sqlx::Error> {
+    sqlx::query_as::<_, EndorsementExportRow>(
+        r"
+        SELECT id, subject_id, endorser_id, topic, weight, visibility, in_slot,
+               created_at, revoked_at, last_confirmed_at
+        FROM reputation__endorsements
+        WHERE visibility <> 'private'
+          AND ($1::uuid IS NULL OR id > $1)
+        ORDER BY id
+        LIMIT $2
+        ",
+    )
+    .bind(after)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Up to `limit` sigchain links after `after` (account, seqno), in that
+/// order.
+///
+/// # Errors
+///
+/// Returns the underlying database error.
+pub async fn sigchain_page(
+    pool: &PgPool,
+    after: Option<(Uuid, i64)>,
+    limit: i64,
+) -> Result<Vec<SigchainExportRow>, sqlx::Error> {
+    let (after_account, after_seqno) = after.unzip();
+    sqlx::query_as::<_, SigchainExportRow>(
+        r"
+        SELECT account_id, seqno, link_type, signer_kid, created_at
+        FROM account_sigchain
+        WHERE $1::uuid IS NULL OR (account_id, seqno) > ($1, $2)
+        ORDER BY account_id, seqno
+        LIMIT $3
+        ",
+    )
+    .bind(after_account)
+    .bind(after_seqno)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,350 @@
+//! Destinations for export files.
+//!
+//! [`ExportSink`] stores one object under a `/`-separated key. The sink is
+//! chosen by `analytics_export.sink`:
+//!
+//! - [`LocalDirSink`]: files under a directory, written to a temporary name
+//!   and renamed so readers never see a partial file.
+//! - [`S3Sink`]: `PutObject` to an S3-compatible bucket, path-style, signed
+//!   with AWS Signature Version 4 by the `aws-sigv4` crate.
+//! - [`MemorySink`]: keeps objects in memory, for tests.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use aws_credential_types::Credentials;
+use aws_sigv4::http_request::{
+    sign, PayloadChecksumKind, PercentEncodingMode, SignableBody, SignableRequest, SigningSettings,
+    UriPathNormalizationMode,
+};
+use aws_sigv4::sign::v4;
+use sha2::{Digest, Sha256};
+
+use crate::config::{AnalyticsExportConfig, AnalyticsSinkKind};
+
+/// How long to wait for one upload before giving up.
+const UPLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// Errors returned by an [`ExportSink`].
+#[derive(Debug, thiserror::Error)]
+pub enum SinkError {
+    #[error("file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("upload failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("object store returned {0}")]
+    Status(reqwest::StatusCode),
+
+    #[error("invalid endpoint: {0}")]
+    Endpoint(String),
+
+    #[error("request signing failed: {0}")]
+    Signing(String),
+}
+
+/// Stores export files.
+#[async_trait]
+pub trait ExportSink: Send + Sync {
+    /// Sink name, for logs.
+    fn name(&self) -> &'static str;
+
+    /// Store `body` under `key`, replacing any existing object.
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), SinkError>;
+}
+
+// ─── Local directory ───────────────────────────────────────────────────────
+
+/// Files under a root directory.
+pub struct LocalDirSink {
+    root: PathBuf,
+}
+
+impl LocalDirSink {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl ExportSink for LocalDirSink {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), SinkError> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, body).await?;
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(())
+    }
+}
+
+// ─── S3 ────────────────────────────────────────────────────────────────────
+
+/// `PutObject` to an S3-compatible bucket.
+pub struct S3Sink {
+    client: reqwest::Client,
+    endpoint: reqwest::Url,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl S3Sink {
+    /// An empty `endpoint` means AWS S3 in `region`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SinkError::Endpoint`] if `endpoint` is not an `http(s)` URL
+    /// with a host.
+    pub fn new(
+        endpoint: &str,
+        bucket: &str,
+        region: &str,
+        access_key_id: &str,
+        secret_access_key: &str,
+    ) -> Result<Self, SinkError> {
+        let endpoint = if endpoint.is_empty() {
+            format!("https://s3.{region}.amazonaws.com")
+        } else {
+            endpoint.to_string()
+        };
+        let endpoint = reqwest::Url::parse(&endpoint)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+            .ok_or(SinkError::Endpoint(endpoint))?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+        })
+    }
+
+    /// Path-style object path, each segment URI-encoded as `SigV4` requires.
+    fn object_path(&self, key: &str) -> String {
+        let base = self.endpoint.path().trim_end_matches('/');
+        std::iter::once(self.bucket.as_str())
+            .chain(key.split('/'))
+            .fold(base.to_string(), |mut path, segment| {
+                path.push('/');
+                path.push_str(&urlencoding::encode(segment));
+                path
+            })
+    }
+
+    /// `SigV4` headers for a `PUT` of `url` whose body hashes to
+    /// `payload_hash`: `authorization`, `x-amz-date` and
+    /// `x-amz-content-sha256`.
+    fn signed_headers(
+        &self,
+        now: SystemTime,
+        url: &str,
+        payload_hash: &str,
+    ) -> Result<Vec<(String, String)>, SinkError> {
+        let identity = Credentials::new(
+            &self.access_key_id,
+            &self.secret_access_key,
+            None,
+            None,
+            "tc-analytics-export",
+        )
+        .into();
+        let mut settings = SigningSettings::default();
+        settings.payload_checksum_kind = PayloadChecksumKind::XAmzSha256;
+        // S3 object keys are signed as sent, without re-encoding
+        settings.percent_encoding_mode = PercentEncodingMode::Single;
+        settings.uri_path_normalization_mode = UriPathNormalizationMode::Disabled;
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.region)
+            .name("s3")
+            .time(now)
+            .settings(settings)
+            .build()
+            .map_err(|e| SinkError::Signing(e.to_string()))?
+            .into();
+        let request = SignableRequest::new(
+            "PUT",
+            url,
+            std::iter::empty(),
+            SignableBody::Precomputed(payload_hash.to_string()),
+        )
+        .map_err(|e| SinkError::Signing(e.to_string()))?;
+        let (instructions, _signature) = sign(request, &params)
+            .map_err(|e| SinkError::Signing(e.to_string()))?
+            .into_parts();
+        Ok(instructions
+            .headers()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ExportSink for S3Sink {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), SinkError> {
+        let path = self.object_path(key);
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+        let payload_hash = format!("{:x}", Sha256::digest(&body));
+        let mut request = self
+            .client
+            .put(url.as_str())
+            .timeout(std::time::Duration::from_secs(UPLOAD_TIMEOUT_SECS))
+            .header("content-type", "application/vnd.apache.parquet");
+        for (name, value) in self.signed_headers(SystemTime::now(), url.as_str(), &payload_hash)? {
+            request = request.header(name, value);
+        }
+        let response = request.body(body).send().await?;
+        if !response.status().is_success() {
+            return Err(SinkError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
+
+// ─── Memory ────────────────────────────────────────────────────────────────
+
+/// Keeps objects in memory, in the order they were stored.
+#[derive(Default)]
+pub struct MemorySink {
+    objects: Mutex<Vec<(String, Vec<u8>)>>,
+}
+
+impl MemorySink {
+    /// Stored objects as `(key, body)`.
+    #[must_use]
+    pub fn objects(&self) -> Vec<(String, Vec<u8>)> {
+        self.objects
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl ExportSink for MemorySink {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), SinkError> {
+        self.objects
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((key.to_string(), body));
+        Ok(())
+    }
+}
+
+/// Build the sink named by `config`, or `None` if export is disabled.
+///
+/// # Errors
+///
+/// Returns [`SinkError::Endpoint`] for an unusable `s3_endpoint`.
+pub fn build_sink(
+    config: &AnalyticsExportConfig,
+) -> Result<Option<Arc<dyn ExportSink>>, SinkError> {
+    Ok(match config.sink {
+        AnalyticsSinkKind::Disabled => None,
+        AnalyticsSinkKind::Local => Some(Arc::new(LocalDirSink::new(&config.local_dir))),
+        AnalyticsSinkKind::S3 => Some(Arc::new(S3Sink::new(
+            &config.s3_endpoint,
+            &config.s3_bucket,
+            &config.s3_region,
+            &config.s3_access_key_id,
+            &config.s3_secret_access_key,
+        )?)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sink(endpoint: &str) -> S3Sink {
+        S3Sink::new(
+            endpoint,
+            "tc-analytics",
+            "us-east-1",
+            "AKIDEXAMPLE",
+            "secret",
+        )
+        .expect("sink")
+    }
+
+    #[test]
+    fn s3_paths_are_path_style_and_encoded() {
+        let aws = sink("");
+        assert_eq!(aws.endpoint.as_str(), "https://s3.us-east-1.amazonaws.com/");
+        assert_eq!(
+            aws.object_path("tc/endorsements/v1/2026-10-17/a b.parquet"),
+            "/tc-analytics/tc/endorsements/v1/2026-10-17/a%20b.parquet"
+        );
+        let minio = sink("http://minio:9000/storage/");
+        assert_eq!(minio.object_path("k"), "/storage/tc-analytics/k");
+        assert!(S3Sink::new("ftp://minio", "b", "r", "a", "s").is_err());
+    }
+
+    #[test]
+    fn s3_signature_matches_reference() {
+        // Reference value computed independently from the SigV4 spec
+        let now = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_767_323_045);
+        let payload_hash = format!("{:x}", Sha256::digest(b"parquet"));
+        let headers = sink("")
+            .signed_headers(
+                now,
+                "https://s3.us-east-1.amazonaws.com/tc-analytics/tc/x.parquet",
+                &payload_hash,
+            )
+            .expect("signed");
+        let authorization = headers
+            .iter()
+            .find(|(name, _)| name == "authorization")
+            .map(|(_, value)| value.as_str());
+        assert_eq!(
+            authorization,
+            Some(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260102/us-east-1/s3/aws4_request, \
+                 SignedHeaders=host;x-amz-content-sha256;x-amz-date, \
+                 Signature=3842e59397e5fcce2e824ff7751f1f770a6b4bcf13d1af6c94aa2293e4b2fb5e"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn local_sink_writes_nested_keys() {
+        let root = std::env::temp_dir().join(format!("tc-analytics-{}", uuid::Uuid::new_v4()));
+        let sink = LocalDirSink::new(&root);
+        sink.put("a/b/c.parquet", b"one".to_vec())
+            .await
+            .expect("put");
+        sink.put("a/b/c.parquet", b"two".to_vec())
+            .await
+            .expect("put");
+        assert_eq!(
+            std::fs::read(root.join("a/b/c.parquet")).expect("read"),
+            b"two"
+        );
+        assert!(!root.join("a/b/c.partial").exists());
+        std::fs::remove_dir_all(root).expect("cleanup");
+    }
+}
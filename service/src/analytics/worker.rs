@@ -0,0 +1,157 @@
+//! Scheduled analytics export — snapshots every [`ExportDataset`] from the
+//! reader pool to the configured [`ExportSink`] every
+//! `analytics_export.interval_secs`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use super::sink::{build_sink, ExportSink, SinkError};
+use super::{endorsement_batch, repo, sigchain_batch, EncodeError, ExportDataset, ParquetFile};
+use crate::config::AnalyticsExportConfig;
+
+/// Errors while exporting one dataset.
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Encode(#[from] EncodeError),
+    #[error("sink error: {0}")]
+    Sink(#[from] SinkError),
+}
+
+impl From<arrow_schema::ArrowError> for ExportError {
+    fn from(e: arrow_schema::ArrowError) -> Self {
+        Self::Encode(e.into())
+    }
+}
+
+/// One dataset written by a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOutcome {
+    pub dataset: ExportDataset,
+    pub key: String,
+    pub rows: u64,
+}
+
+/// Read `dataset` page by page and encode it as one Parquet file.
+///
+/// # Errors
+///
+/// Returns the first database or encoding error.
+pub async fn encode_dataset(
+    reader: &PgPool,
+    dataset: ExportDataset,
+    batch_rows: u32,
+) -> Result<ParquetFile, ExportError> {
+    let limit = i64::from(batch_rows);
+    let mut file = ParquetFile::new(dataset, batch_rows)?;
+    match dataset {
+        ExportDataset::Endorsements => {
+            let mut after = None;
+            loop {
+                let rows = repo::endorsements_page(reader, after, limit).await?;
+                let Some(last) = rows.last() else {
+                    break;
+                };
+                after = Some(last.id);
+                file.write(&endorsement_batch(&rows)?)?;
+            }
+        }
+        ExportDataset::SigchainLinks => {
+            let mut after = None;
+            loop {
+                let rows = repo::sigchain_page(reader, after, limit).await?;
+                let Some(last) = rows.last() else {
+                    break;
+                };
+                after = Some((last.account_id, last.seqno));
+                file.write(&sigchain_batch(&rows)?)?;
+            }
+        }
+    }
+    Ok(file)
+}
+
+/// Export one dataset as of `now` and store it through `sink`.
+///
+/// # Errors
+///
+/// Returns the first database, encoding or sink error.
+pub async fn export_dataset(
+    reader: &PgPool,
+    sink: &dyn ExportSink,
+    config: &AnalyticsExportConfig,
+    dataset: ExportDataset,
+    now: DateTime<Utc>,
+) -> Result<ExportOutcome, ExportError> {
+    let file = encode_dataset(reader, dataset, config.batch_rows).await?;
+    let rows = file.rows();
+    let key = dataset.object_key(&config.prefix, now);
+    sink.put(&key, file.finish()?).await?;
+    Ok(ExportOutcome { dataset, key, rows })
+}
+
+/// Export every dataset once, logging and recording metrics per dataset.
+///
+/// A failing dataset is logged and skipped so one table cannot block the
+/// rest.
+pub async fn run_export(
+    reader: &PgPool,
+    sink: &dyn ExportSink,
+    config: &AnalyticsExportConfig,
+) -> Vec<ExportOutcome> {
+    let now = Utc::now();
+    let mut outcomes = Vec::with_capacity(ExportDataset::ALL.len());
+    for dataset in ExportDataset::ALL {
+        match export_dataset(reader, sink, config, dataset, now).await {
+            Ok(outcome) => {
+                metrics::counter!("tc_analytics_export_rows_total", "dataset" => dataset.as_str())
+                    .increment(outcome.rows);
+                tracing::info!(
+                    dataset = dataset.as_str(),
+                    rows = outcome.rows,
+                    key = %outcome.key,
+                    sink = sink.name(),
+                    "Analytics export written"
+                );
+                outcomes.push(outcome);
+            }
+            Err(e) => {
+                metrics::counter!("tc_analytics_export_failures_total", "dataset" => dataset.as_str())
+                    .increment(1);
+                tracing::warn!(dataset = dataset.as_str(), "Analytics export failed: {e}");
+            }
+        }
+    }
+    outcomes
+}
+
+/// Run [`run_export`] every `interval_secs`, unless no sink is configured.
+///
+/// # Errors
+///
+/// Returns an error if the configured sink cannot be built.
+pub fn spawn_analytics_export(
+    reader: PgPool,
+    config: &AnalyticsExportConfig,
+) -> Result<(), SinkError> {
+    let Some(sink) = build_sink(config)? else {
+        tracing::info!("Analytics export disabled (enable via TC_ANALYTICS_EXPORT__SINK)");
+        return Ok(());
+    };
+    let config = config.clone();
+    tokio::spawn(async move {
+        let sink: Arc<dyn ExportSink> = sink;
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            run_export(&reader, sink.as_ref(), &config).await;
+        }
+    });
+    Ok(())
+}
@@ -15,10 +15,18 @@ use std::io::Read as _;
 use std::time::Instant;
 
 use anyhow::Context as _;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use tc_llm::{build_synthesis_messages, CompanyEvidence, SearchResponse, DIMENSIONS};
-
-/// `TinyCongress` operations CLI for prompt iteration and research R&D.
+use tinycongress_api::config::DatabaseConfig;
+use tinycongress_api::db::schema_check::{self, SchemaCheckOptions};
+use tinycongress_api::db::setup_database;
+use tinycongress_api::identity::portability;
+use tinycongress_api::integrity;
+use tinycongress_api::trust::http::InviteTreeResponse;
+use tinycongress_api::trust::repo::{PgTrustRepo, TrustRepo as _};
+
+/// `TinyCongress` operations CLI for prompt iteration, research R&D, and
+/// identity data backup/restore.
 #[derive(Parser)]
 #[command(name = "tc-ops", version, about)]
 struct Cli {
@@ -30,6 +38,78 @@ struct Cli {
 enum Commands {
     /// Run the research pipeline for a company (no DB required).
     Research(ResearchArgs),
+    /// Dump accounts, devices, and encrypted backups (reads `TC_DATABASE__*`).
+    ExportAccounts(ExportAccountsArgs),
+    /// Verify and restore an account dump (reads `TC_DATABASE__*`).
+    ImportAccounts(ImportAccountsArgs),
+    /// Print an account's downstream invite tree as JSON (reads `TC_DATABASE__*`).
+    InviteTree(InviteTreeArgs),
+    /// Re-verify stored signatures and report discrepancies as JSON (reads `TC_DATABASE__*`).
+    VerifyChains(VerifyChainsArgs),
+    /// Compare the live schema with the migrations and report drift as JSON (reads `TC_DATABASE__*`).
+    CheckSchema(CheckSchemaArgs),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    /// One JSON object per account, newline-delimited.
+    Jsonl,
+}
+
+#[derive(clap::Args)]
+struct ExportAccountsArgs {
+    /// Output format.
+    #[arg(long, value_enum, default_value = "jsonl")]
+    format: ExportFormat,
+
+    /// Write to this file instead of stdout.
+    #[arg(long)]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(clap::Args)]
+struct ImportAccountsArgs {
+    /// Read from this file instead of stdin.
+    #[arg(long)]
+    input: Option<std::path::PathBuf>,
+
+    /// Verify the dump and check for conflicts, then roll back.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(clap::Args)]
+struct InviteTreeArgs {
+    /// Account ID at the root of the tree.
+    #[arg(long)]
+    account: uuid::Uuid,
+
+    /// Hops to follow from the root.
+    #[arg(long, default_value_t = 10)]
+    max_depth: i32,
+}
+
+#[derive(clap::Args)]
+struct VerifyChainsArgs {
+    /// Write the report to this file instead of stdout.
+    #[arg(long)]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(clap::Args)]
+struct CheckSchemaArgs {
+    /// Also check sqlx offline query metadata (`query-*.json`) in this directory.
+    #[arg(long)]
+    sqlx_metadata: Option<std::path::PathBuf>,
+
+    /// Only check migration history; skip comparing schema objects, which
+    /// needs `CREATEDB` for a scratch database.
+    #[arg(long)]
+    skip_objects: bool,
+
+    /// Write the report to this file instead of stdout.
+    #[arg(long)]
+    output: Option<std::path::PathBuf>,
 }
 
 #[derive(clap::Args)]
@@ -125,7 +205,131 @@ async fn main() -> Result<(), anyhow::Error> {
     let cli = Cli::parse();
     match cli.command {
         Commands::Research(args) => research(args).await,
+        Commands::ExportAccounts(args) => export_accounts(args).await,
+        Commands::ImportAccounts(args) => import_accounts(args).await,
+        Commands::InviteTree(args) => invite_tree(args).await,
+        Commands::VerifyChains(args) => verify_chains(args).await,
+        Commands::CheckSchema(args) => check_schema(args).await,
+    }
+}
+
+async fn connect_database() -> Result<sqlx::PgPool, anyhow::Error> {
+    let config = DatabaseConfig::load().context("loading database config")?;
+    setup_database(&config).await
+}
+
+async fn export_accounts(args: ExportAccountsArgs) -> Result<(), anyhow::Error> {
+    let ExportFormat::Jsonl = args.format;
+    let pool = connect_database().await?;
+
+    let count = if let Some(path) = &args.output {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("creating output file: {}", path.display()))?;
+        portability::export_accounts(&pool, &mut std::io::BufWriter::new(file)).await?
+    } else {
+        portability::export_accounts(&pool, &mut std::io::BufWriter::new(std::io::stdout())).await?
+    };
+
+    eprintln!("tc-ops export-accounts: exported {count} accounts");
+    Ok(())
+}
+
+async fn import_accounts(args: ImportAccountsArgs) -> Result<(), anyhow::Error> {
+    let pool = connect_database().await?;
+
+    let summary = if let Some(path) = &args.input {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("opening input file: {}", path.display()))?;
+        portability::import_accounts(&pool, std::io::BufReader::new(file), args.dry_run).await?
+    } else {
+        portability::import_accounts(
+            &pool,
+            std::io::BufReader::new(std::io::stdin()),
+            args.dry_run,
+        )
+        .await?
+    };
+
+    eprintln!(
+        "tc-ops import-accounts{}: imported {} accounts, skipped {} already present",
+        if args.dry_run { " (dry run)" } else { "" },
+        summary.imported,
+        summary.skipped
+    );
+    Ok(())
+}
+
+async fn invite_tree(args: InviteTreeArgs) -> Result<(), anyhow::Error> {
+    anyhow::ensure!(args.max_depth >= 1, "--max-depth must be at least 1");
+    let pool = connect_database().await?;
+
+    let edges = PgTrustRepo::new(pool)
+        .get_invite_tree(args.account, args.max_depth)
+        .await?;
+    let tree = InviteTreeResponse::from_edges(args.account, args.max_depth, edges);
+    serde_json::to_writer_pretty(std::io::stdout(), &tree)?;
+
+    eprintln!(
+        "\ntc-ops invite-tree: {} invitees within {} hops",
+        tree.total_invitees, args.max_depth
+    );
+    Ok(())
+}
+
+async fn verify_chains(args: VerifyChainsArgs) -> Result<(), anyhow::Error> {
+    let pool = connect_database().await?;
+    let report = integrity::sweep(&pool).await?;
+
+    if let Some(path) = &args.output {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("creating output file: {}", path.display()))?;
+        serde_json::to_writer_pretty(std::io::BufWriter::new(file), &report)?;
+    } else {
+        serde_json::to_writer_pretty(std::io::stdout(), &report)?;
     }
+
+    eprintln!(
+        "\ntc-ops verify-chains: checked {} accounts, {} devices, {} events, {} endorsements; {} discrepancies",
+        report.accounts_checked,
+        report.devices_checked,
+        report.events_checked,
+        report.endorsements_checked,
+        report.discrepancies.len()
+    );
+    // Non-zero exit so scheduled runs can alert on it
+    anyhow::ensure!(report.is_clean(), "integrity check failed");
+    Ok(())
+}
+
+async fn check_schema(args: CheckSchemaArgs) -> Result<(), anyhow::Error> {
+    // Connects without migrating, so the schema is checked as deployed
+    let config = DatabaseConfig::load().context("loading database config")?;
+    let options = SchemaCheckOptions {
+        skip_objects: args.skip_objects,
+        sqlx_metadata: args.sqlx_metadata,
+    };
+    let report = schema_check::check_schema(&config, &options).await?;
+
+    if let Some(path) = &args.output {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("creating output file: {}", path.display()))?;
+        serde_json::to_writer_pretty(std::io::BufWriter::new(file), &report)?;
+    } else {
+        serde_json::to_writer_pretty(std::io::stdout(), &report)?;
+    }
+
+    eprintln!(
+        "\ntc-ops check-schema: {} of {} migrations applied, {} objects and {} queries compared; {} drift, {} warnings",
+        report.migrations_applied,
+        report.migrations_on_disk,
+        report.objects_compared,
+        report.queries_checked,
+        report.drift.len(),
+        report.warnings.len()
+    );
+    // Non-zero exit so it can gate readiness or a deploy
+    anyhow::ensure!(report.is_clean(), "schema drift detected");
+    Ok(())
 }
 
 #[allow(clippy::too_many_lines)]
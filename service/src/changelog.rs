@@ -0,0 +1,305 @@
+//! Changelog of API-visible behavior at `GET /api/v1/changelog`.
+//!
+//! The entries live in `changelog.json` next to this file and are compiled
+//! into the binary, so the list always matches the code that serves it.
+//! Versions are the REST API's own `MAJOR.MINOR.PATCH` revision (the `OpenAPI`
+//! `info.version`), not the build version. A client that remembers the
+//! revision it was built against passes it as `since` and prompts its user
+//! to update when any newer change is marked `breaking`.
+//!
+//! Add a release at the top of `changelog.json` for every change a client
+//! could notice — a new endpoint, a deprecation, a tightened validation rule
+//! — and bump `info.version` in [`crate::rest::ApiDoc`] to match.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::header::CACHE_CONTROL,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// The embedded manifest.
+const MANIFEST: &str = include_str!("changelog.json");
+
+/// A REST API revision, `MAJOR.MINOR.PATCH`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Error returned for a version that is not `MAJOR.MINOR.PATCH`.
+#[derive(Debug, thiserror::Error)]
+#[error("version must be MAJOR.MINOR.PATCH, e.g. 1.2.0")]
+pub struct InvalidApiVersion;
+
+impl FromStr for ApiVersion {
+    type Err = InvalidApiVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('.').map(str::parse::<u32>);
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok(Self {
+                major,
+                minor,
+                patch,
+            }),
+            _ => Err(InvalidApiVersion),
+        }
+    }
+}
+
+/// What kind of behavior a change affects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// A new endpoint or field.
+    Added,
+    /// Existing behavior works differently.
+    Changed,
+    /// Input that used to be accepted may now be refused, or vice versa.
+    Validation,
+    /// Still works, but will be removed in a later revision.
+    Deprecated,
+    /// No longer available.
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChangelogChange {
+    pub kind: ChangeKind,
+    pub summary: String,
+    /// Affected endpoints as `METHOD /path`; empty for cross-cutting changes
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+    /// Whether clients built against an earlier revision may break
+    #[serde(default)]
+    pub breaking: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChangelogRelease {
+    /// API revision, `MAJOR.MINOR.PATCH`
+    pub version: String,
+    pub changes: Vec<ChangelogChange>,
+}
+
+impl ChangelogRelease {
+    fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|c| c.breaking)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChangelogResponse {
+    /// API revision this server implements
+    pub current_version: String,
+    /// Releases newer than `since` (all of them without it), newest first
+    pub releases: Vec<ChangelogRelease>,
+    /// Whether any listed release contains a breaking change
+    pub breaking: bool,
+}
+
+/// Why the embedded manifest was rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum ChangelogError {
+    #[error("changelog manifest is not valid JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("changelog release {0:?}: {1}")]
+    Version(String, InvalidApiVersion),
+    #[error("changelog releases must be listed newest first without repeats ({0} follows {1})")]
+    Order(ApiVersion, ApiVersion),
+    #[error("changelog manifest has no releases")]
+    Empty,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    releases: Vec<ChangelogRelease>,
+}
+
+/// Parsed changelog, newest release first.
+#[derive(Debug, Clone)]
+pub struct Changelog {
+    releases: Vec<(ApiVersion, ChangelogRelease)>,
+}
+
+impl Changelog {
+    /// Parse the manifest compiled into this binary.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChangelogError`] if the manifest is malformed; the tests
+    /// below keep that from reaching a release.
+    pub fn embedded() -> Result<Self, ChangelogError> {
+        Self::parse(MANIFEST)
+    }
+
+    /// Parse a manifest, checking versions are valid and strictly decreasing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChangelogError`] if the JSON or a version is invalid, or
+    /// releases are out of order.
+    pub fn parse(json: &str) -> Result<Self, ChangelogError> {
+        let manifest: Manifest = serde_json::from_str(json)?;
+        let mut releases = Vec::with_capacity(manifest.releases.len());
+        for release in manifest.releases {
+            let version = release
+                .version
+                .parse::<ApiVersion>()
+                .map_err(|e| ChangelogError::Version(release.version.clone(), e))?;
+            if let Some((previous, _)) = releases.last() {
+                if version.cmp(previous) != Ordering::Less {
+                    return Err(ChangelogError::Order(version, *previous));
+                }
+            }
+            releases.push((version, release));
+        }
+        if releases.is_empty() {
+            return Err(ChangelogError::Empty);
+        }
+        Ok(Self { releases })
+    }
+
+    /// The newest revision.
+    #[must_use]
+    pub fn current_version(&self) -> ApiVersion {
+        self.releases[0].0
+    }
+
+    /// Releases newer than `since`, or all of them, newest first.
+    #[must_use]
+    pub fn since(&self, since: Option<ApiVersion>) -> ChangelogResponse {
+        let releases: Vec<ChangelogRelease> = self
+            .releases
+            .iter()
+            .filter(|(version, _)| since.is_none_or(|since| *version > since))
+            .map(|(_, release)| release.clone())
+            .collect();
+        ChangelogResponse {
+            current_version: self.current_version().to_string(),
+            breaking: releases.iter().any(ChangelogRelease::is_breaking),
+            releases,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct ChangelogQuery {
+    /// Only list releases newer than this API revision, e.g. `1.2.0`
+    pub since: Option<String>,
+}
+
+/// List API-visible behavior changes, optionally only those newer than the
+/// caller's revision.
+#[utoipa::path(
+    get,
+    path = "/changelog",
+    tag = "System",
+    params(ChangelogQuery),
+    responses(
+        (status = 200, description = "Changelog, newest release first", body = ChangelogResponse),
+        (status = 400, description = "Invalid since version")
+    )
+)]
+#[allow(clippy::unused_async)] // Required for Axum handler signature
+pub async fn get_changelog(
+    Extension(changelog): Extension<Arc<Changelog>>,
+    Query(query): Query<ChangelogQuery>,
+) -> impl IntoResponse {
+    let since = match query.since.as_deref().map(str::parse::<ApiVersion>) {
+        None => None,
+        Some(Ok(v)) => Some(v),
+        Some(Err(e)) => return crate::http::bad_request(&e.to_string()),
+    };
+    (
+        [(CACHE_CONTROL, "public, max-age=300")],
+        Json(changelog.since(since)),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utoipa::OpenApi;
+
+    fn version(s: &str) -> ApiVersion {
+        s.parse().expect("version")
+    }
+
+    #[test]
+    fn embedded_manifest_parses_and_matches_openapi() {
+        let changelog = Changelog::embedded().expect("embedded changelog");
+        assert_eq!(
+            changelog.current_version().to_string(),
+            crate::rest::ApiDoc::openapi().info.version,
+            "bump info.version in rest::ApiDoc with each changelog release"
+        );
+    }
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert!(version("1.10.0") > version("1.9.3"));
+        assert!(version("2.0.0") > version("1.99.99"));
+        for bad in ["", "1.2", "1.2.3.4", "v1.2.3", "1.-2.0"] {
+            assert!(bad.parse::<ApiVersion>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn since_filters_and_reports_breaking_changes() {
+        let changelog = Changelog::parse(
+            r#"{"releases": [
+                {"version": "1.2.0", "changes": [{"kind": "added", "summary": "c"}]},
+                {"version": "1.1.0", "changes": [{"kind": "removed", "summary": "b", "breaking": true}]},
+                {"version": "1.0.0", "changes": [{"kind": "added", "summary": "a"}]}
+            ]}"#,
+        )
+        .expect("parse");
+
+        let all = changelog.since(None);
+        assert_eq!(all.current_version, "1.2.0");
+        assert_eq!(all.releases.len(), 3);
+        assert!(all.breaking);
+
+        let behind = changelog.since(Some(version("1.0.5")));
+        assert_eq!(behind.releases.len(), 2);
+        assert!(behind.breaking);
+
+        let current = changelog.since(Some(version("1.1.0")));
+        assert_eq!(current.releases.len(), 1);
+        assert!(!current.breaking);
+    }
+
+    #[test]
+    fn parse_rejects_unordered_releases() {
+        let err = Changelog::parse(
+            r#"{"releases": [
+                {"version": "1.0.0", "changes": []},
+                {"version": "1.1.0", "changes": []}
+            ]}"#,
+        )
+        .expect_err("out of order");
+        assert!(matches!(err, ChangelogError::Order(_, _)));
+        assert!(matches!(
+            Changelog::parse(r#"{"releases": []}"#),
+            Err(ChangelogError::Empty)
+        ));
+    }
+}
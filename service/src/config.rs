@@ -4,6 +4,9 @@ use figment::{
 };
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_aux::prelude::deserialize_vec_from_string_or_vec;
+use uuid::Uuid;
+
+use crate::reputation::verification::VerificationMethod;
 
 /// Application configuration loaded from multiple sources.
 ///
@@ -22,9 +25,20 @@ pub struct Config {
     #[serde(default)]
     pub security_headers: SecurityHeadersConfig,
     #[serde(default)]
+    pub compression: CompressionConfig,
+    /// Per-route-group in-flight request caps.
+    #[serde(default)]
+    pub concurrency: ConcurrencyConfig,
+    /// Read-only degradation while the database refuses writes.
+    #[serde(default)]
+    pub read_only: ReadOnlyConfig,
+    #[serde(default)]
     pub graphql: GraphQLConfig,
     #[serde(default)]
     pub swagger: SwaggerConfig,
+    /// Who may reach the GraphQL Playground and Swagger UI once enabled.
+    #[serde(default)]
+    pub dev_tools: DevToolsConfig,
     /// HMAC key for generating synthetic backup envelopes.
     ///
     /// Required — prevents username enumeration by making `GET /auth/backup/{username}`
@@ -50,6 +64,85 @@ pub struct Config {
     /// Rate limiting for unauthenticated auth endpoints.
     #[serde(default)]
     pub rate_limit: RateLimitConfig,
+    /// Signed-request timestamp window and nonce retention.
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// Domain event mirroring to an external message bus.
+    #[serde(default)]
+    pub events: EventsConfig,
+    /// Per-table retention and the background purge worker.
+    #[serde(default)]
+    pub retention: RetentionConfig,
+
+    /// Optional Sentry-compatible error reporting.
+    #[serde(default)]
+    pub error_reporting: ErrorReportingConfig,
+
+    /// Public description served at `/.well-known/tinycongress.json`.
+    #[serde(default)]
+    pub instance: InstanceConfig,
+
+    /// Open or invite-only signup, and invite code quotas.
+    #[serde(default)]
+    pub signup: SignupConfig,
+
+    /// Character set, length bounds and reserved names for usernames.
+    #[serde(default)]
+    pub usernames: UsernameConfig,
+
+    /// Minimum account age, activity and verification for sensitive trust actions.
+    #[serde(default)]
+    pub account_gates: AccountGatesConfig,
+
+    /// Per-token request quotas for API tokens.
+    #[serde(default)]
+    pub api_tokens: ApiTokenConfig,
+
+    /// Who may request and approve anonymized research exports.
+    #[serde(default)]
+    pub research: ResearchConfig,
+
+    /// Differential-privacy noise on public aggregate counts.
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+
+    /// Who may suspend, restore and delete other accounts, review
+    /// endorsement disputes, and issue strikes.
+    #[serde(default)]
+    pub accounts: AccountsConfig,
+
+    /// Score thresholds for the named reputation tiers.
+    #[serde(default)]
+    pub reputation: ReputationConfig,
+
+    /// Email and phone code delivery, in-person attestation thresholds and
+    /// the external ID verification webhook.
+    #[serde(default)]
+    pub verification: VerificationConfig,
+
+    /// SMS provider used for step-up confirmation codes.
+    #[serde(default)]
+    pub sms: SmsConfig,
+
+    /// When high-risk actions need a code texted to the account's phone.
+    #[serde(default)]
+    pub step_up: StepUpConfig,
+
+    /// Which accounts get a precomputed ranked feed, and how often.
+    #[serde(default)]
+    pub feed: FeedConfig,
+
+    /// Grace period before a self-deleted account is purged.
+    #[serde(default)]
+    pub account_deletion: AccountDeletionConfig,
+
+    /// Background re-verification of stored signed material.
+    #[serde(default)]
+    pub integrity: IntegrityConfig,
+
+    /// Scheduled Parquet export of endorsement history and sigchain links.
+    #[serde(default)]
+    pub analytics_export: AnalyticsExportConfig,
 }
 
 /// Configuration for a platform-bootstrapped verifier account.
@@ -96,6 +189,34 @@ pub struct DatabaseConfig {
     /// demo environment. Default: false.
     #[serde(default)]
     pub auto_reset_on_migration_failure: bool,
+
+    /// How long startup keeps retrying transient database failures (connection
+    /// refused, server starting up, pgmq not yet installed) before exiting.
+    /// Default: 120 seconds, well within the Kubernetes startup probe budget.
+    #[serde(default = "default_db_startup_deadline_secs")]
+    pub startup_deadline_secs: u64,
+
+    /// Wait for the `pgmq` extension to become available before running
+    /// migrations, instead of failing the migration that creates it.
+    /// Default: false.
+    #[serde(default)]
+    pub wait_for_pgmq: bool,
+
+    /// Log an `EXPLAIN ANALYZE` plan for every instrumented query run on the
+    /// pool (see [`crate::db::instrumented`]). Runs each such query twice;
+    /// for local profiling only. Default: false.
+    #[serde(default)]
+    pub explain_queries: bool,
+
+    /// Login for the reader pool used by read-only paths. Should be a role
+    /// granted `tc_reader` (SELECT-only, created by migrations); startup
+    /// fails if it can write. When unset, reads use the primary pool.
+    #[serde(default)]
+    pub reader_user: Option<String>,
+
+    /// Password for `reader_user`.
+    #[serde(default)]
+    pub reader_password: Option<String>,
 }
 
 impl std::fmt::Debug for DatabaseConfig {
@@ -112,11 +233,36 @@ impl std::fmt::Debug for DatabaseConfig {
                 "auto_reset_on_migration_failure",
                 &self.auto_reset_on_migration_failure,
             )
+            .field("startup_deadline_secs", &self.startup_deadline_secs)
+            .field("wait_for_pgmq", &self.wait_for_pgmq)
+            .field("explain_queries", &self.explain_queries)
+            .field("reader_user", &self.reader_user)
+            .field(
+                "reader_password",
+                &self.reader_password.as_ref().map(|_| "[REDACTED]"),
+            )
             .finish()
     }
 }
 
 impl DatabaseConfig {
+    /// Load only the `database` section from the standard sources.
+    ///
+    /// For operator tooling that needs a connection but none of the
+    /// service-only settings (e.g. `synthetic_backup_key`).
+    ///
+    /// # Errors
+    /// Returns an error if the section cannot be loaded or credentials are missing.
+    pub fn load() -> Result<Self, ConfigError> {
+        let database: Self = Config::figment().extract_inner("database")?;
+        if database.user.is_empty() || database.password.is_empty() {
+            return Err(ConfigError::Validation(
+                "database.user and database.password are required. Set TC_DATABASE__USER and TC_DATABASE__PASSWORD.".into(),
+            ));
+        }
+        Ok(database)
+    }
+
     /// Build `PgConnectOptions` from individual fields.
     ///
     /// Uses structured options instead of URL assembly to avoid issues with
@@ -131,6 +277,21 @@ impl DatabaseConfig {
             .password(&self.password)
     }
 
+    /// Build `PgConnectOptions` for the reader pool, if `reader_user` is set.
+    ///
+    /// Sessions default to read-only transactions, so even a misconfigured
+    /// role cannot write through this pool by accident.
+    #[must_use]
+    pub fn reader_connect_options(&self) -> Option<sqlx_postgres::PgConnectOptions> {
+        let user = self.reader_user.as_deref()?;
+        Some(
+            self.connect_options()
+                .username(user)
+                .password(self.reader_password.as_deref().unwrap_or_default())
+                .options([("default_transaction_read_only", "on")]),
+        )
+    }
+
     /// Build `PgConnectOptions` targeting the `postgres` system database.
     ///
     /// Used for administrative operations (DROP/CREATE DATABASE) that cannot
@@ -170,7 +331,8 @@ pub struct LoggingConfig {
 /// You must explicitly configure allowed origins for the frontend to work.
 ///
 /// Set via `TC_CORS__ALLOWED_ORIGINS` (comma-separated) or `cors.allowed_origins`
-/// in config.yaml.
+/// in config.yaml. Auth routes and public embeds can override the list; see
+/// [`crate::http::cors`].
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CorsConfig {
     /// Allowed origins for CORS requests.
@@ -179,18 +341,37 @@ pub struct CorsConfig {
     /// Example: `["http://localhost:5173"]` or `"http://localhost:5173,https://app.example.com"`
     #[serde(
         default = "default_allowed_origins",
-        deserialize_with = "deserialize_origins"
+        deserialize_with = "deserialize_string_list"
     )]
     pub allowed_origins: Vec<String>,
+    /// Origins for public read-only embeds (`/badges/*`, `/api/v1/stats`),
+    /// e.g. `"*"`. Empty uses `allowed_origins`.
+    #[serde(default, deserialize_with = "deserialize_string_list")]
+    pub public_origins: Vec<String>,
+    /// Origins for `/auth/*` routes. Empty uses `allowed_origins`; `"*"` is
+    /// rejected.
+    #[serde(default, deserialize_with = "deserialize_string_list")]
+    pub auth_origins: Vec<String>,
+}
+
+/// Deserialize a list from comma-separated string or array, filtering empty values.
+fn deserialize_string_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values: Vec<String> = deserialize_vec_from_string_or_vec(deserializer)?;
+    Ok(values.into_iter().filter(|s| !s.is_empty()).collect())
 }
 
-/// Deserialize origins from comma-separated string or array, filtering empty values.
-fn deserialize_origins<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+/// Like [`deserialize_string_list`], parsing each entry as a UUID.
+fn deserialize_uuid_list<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let origins: Vec<String> = deserialize_vec_from_string_or_vec(deserializer)?;
-    Ok(origins.into_iter().filter(|s| !s.is_empty()).collect())
+    deserialize_string_list(deserializer)?
+        .iter()
+        .map(|s| s.trim().parse().map_err(serde::de::Error::custom))
+        .collect()
 }
 
 // These functions cannot be const because serde uses function pointers for defaults
@@ -199,6 +380,11 @@ fn default_max_connections() -> u32 {
     10
 }
 
+#[allow(clippy::missing_const_for_fn)]
+fn default_db_startup_deadline_secs() -> u64 {
+    120
+}
+
 #[allow(clippy::missing_const_for_fn)]
 fn default_port() -> u16 {
     8080
@@ -236,6 +422,8 @@ impl Default for CorsConfig {
     fn default() -> Self {
         Self {
             allowed_origins: default_allowed_origins(),
+            public_origins: Vec::new(),
+            auth_origins: Vec::new(),
         }
     }
 }
@@ -326,13 +514,280 @@ impl Default for SecurityHeadersConfig {
     }
 }
 
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+/// Response compression configuration.
+///
+/// Set via `TC_COMPRESSION__*` environment variables or `compression.*` in
+/// config.yaml. Responses are only compressed when the client sends a matching
+/// `Accept-Encoding` and the body is at least `min_size_bytes`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompressionConfig {
+    /// Enable response compression (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Offer gzip (default: true).
+    #[serde(default = "default_true")]
+    pub gzip: bool,
+
+    /// Offer brotli (default: true).
+    #[serde(default = "default_true")]
+    pub br: bool,
+
+    /// Smallest response body worth compressing, in bytes (default: 1024).
+    #[serde(default = "default_compression_min_size_bytes")]
+    pub min_size_bytes: u16,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_compression_min_size_bytes() -> u16 {
+    1024
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            gzip: default_true(),
+            br: default_true(),
+            min_size_bytes: default_compression_min_size_bytes(),
+        }
+    }
+}
+
+/// In-flight request limits per route group.
+///
+/// Set via `TC_CONCURRENCY__*` environment variables or `concurrency.*` in
+/// config.yaml. Requests over a group's limit are shed with `503` and
+/// `Retry-After` instead of queueing for a database connection; see
+/// [`crate::http::concurrency`]. A limit of 0 leaves that group unlimited.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConcurrencyConfig {
+    /// Enable concurrency limits (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Max in-flight requests to expensive routes (default: 8).
+    #[serde(default = "default_expensive_concurrency_limit")]
+    pub expensive_limit: u32,
+
+    /// Max in-flight requests to all other routes (default: 256).
+    /// `/health`, `/ready`, and `/metrics` are never limited.
+    #[serde(default = "default_concurrency_limit")]
+    pub default_limit: u32,
+
+    /// Route prefixes counted as expensive. `{param}` segments match any
+    /// value. Accepts either an array or comma-separated string.
+    #[serde(
+        default = "default_expensive_routes",
+        deserialize_with = "deserialize_string_list"
+    )]
+    pub expensive_routes: Vec<String>,
+
+    /// `Retry-After` sent with shed requests, in seconds (default: 1).
+    #[serde(default = "default_shed_retry_after_secs")]
+    pub retry_after_secs: u32,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_expensive_concurrency_limit() -> u32 {
+    8
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_concurrency_limit() -> u32 {
+    256
+}
+
+fn default_expensive_routes() -> Vec<String> {
+    [
+        "/research/exports",
+        "/trust/invites/tree",
+        "/rooms/{room_id}/polls/{poll_id}/results",
+        "/rooms/{room_id}/polls/{poll_id}/traces",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_shed_retry_after_secs() -> u32 {
+    1
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            expensive_limit: default_expensive_concurrency_limit(),
+            default_limit: default_concurrency_limit(),
+            expensive_routes: default_expensive_routes(),
+            retry_after_secs: default_shed_retry_after_secs(),
+        }
+    }
+}
+
+/// Emergency read-only mode during database failover.
+///
+/// Set via `TC_READ_ONLY__*` environment variables or `read_only.*` in
+/// config.yaml. The database is probed every `probe_interval_secs`; once
+/// `failure_threshold` probes in a row find it answering but refusing
+/// writes, mutations are rejected with `503` until `recovery_threshold`
+/// probes in a row find it writable again. See [`crate::db::read_only`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReadOnlyConfig {
+    /// Degrade to read-only while the database refuses writes (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Seconds between write-availability probes (default: 5).
+    #[serde(default = "default_read_only_probe_interval_secs")]
+    pub probe_interval_secs: u64,
+
+    /// Consecutive read-only probes before mutations are rejected (default: 3).
+    #[serde(default = "default_read_only_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// Consecutive writable probes before mutations are accepted again
+    /// (default: 2).
+    #[serde(default = "default_read_only_recovery_threshold")]
+    pub recovery_threshold: u32,
+
+    /// `Retry-After` sent with rejected mutations, in seconds (default: 10).
+    #[serde(default = "default_read_only_retry_after_secs")]
+    pub retry_after_secs: u32,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_read_only_probe_interval_secs() -> u64 {
+    5
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_read_only_failure_threshold() -> u32 {
+    3
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_read_only_recovery_threshold() -> u32 {
+    2
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_read_only_retry_after_secs() -> u32 {
+    10
+}
+
+impl Default for ReadOnlyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            probe_interval_secs: default_read_only_probe_interval_secs(),
+            failure_threshold: default_read_only_failure_threshold(),
+            recovery_threshold: default_read_only_recovery_threshold(),
+            retry_after_secs: default_read_only_retry_after_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GraphQLConfig {
     /// Enable GraphQL Playground UI at /graphql (GET).
     /// Default: false (disabled for security - exposes schema to potential attackers).
     /// Enable in development via `TC_GRAPHQL__PLAYGROUND_ENABLED=true`
     #[serde(default)]
     pub playground_enabled: bool,
+    /// Most complexity points a single operation may cost (default: 200).
+    /// Each selected field costs one point unless its resolver says otherwise.
+    #[serde(default = "default_graphql_max_complexity")]
+    pub max_complexity: usize,
+    /// Per-minute cost budgets for device-signed callers.
+    #[serde(default)]
+    pub cost_budgets: GraphQLCostBudgetConfig,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_graphql_max_complexity() -> usize {
+    200
+}
+
+impl Default for GraphQLConfig {
+    fn default() -> Self {
+        Self {
+            playground_enabled: false,
+            max_complexity: default_graphql_max_complexity(),
+            cost_budgets: GraphQLCostBudgetConfig::default(),
+        }
+    }
+}
+
+/// Complexity points a device-signed account may spend on GraphQL per
+/// minute, by reputation tier; see [`crate::graphql::cost`].
+///
+/// Set via `TC_GRAPHQL__COST_BUDGETS__*` environment variables or
+/// `graphql.cost_budgets.*` in config.yaml.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct GraphQLCostBudgetConfig {
+    /// Charge signed operations against the budgets (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Budget below the `established` tier (default: 300).
+    #[serde(default = "default_graphql_budget_new")]
+    pub new: u32,
+    /// Default: 600.
+    #[serde(default = "default_graphql_budget_established")]
+    pub established: u32,
+    /// Default: 1200.
+    #[serde(default = "default_graphql_budget_trusted")]
+    pub trusted: u32,
+    /// Default: 2400.
+    #[serde(default = "default_graphql_budget_exemplary")]
+    pub exemplary: u32,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_graphql_budget_new() -> u32 {
+    300
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_graphql_budget_established() -> u32 {
+    600
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_graphql_budget_trusted() -> u32 {
+    1200
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_graphql_budget_exemplary() -> u32 {
+    2400
+}
+
+impl Default for GraphQLCostBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            new: default_graphql_budget_new(),
+            established: default_graphql_budget_established(),
+            trusted: default_graphql_budget_trusted(),
+            exemplary: default_graphql_budget_exemplary(),
+        }
+    }
+}
+
+impl GraphQLCostBudgetConfig {
+    /// Points per minute for an account in `tier`.
+    #[must_use]
+    pub const fn budget(&self, tier: crate::reputation::ReputationTier) -> u32 {
+        use crate::reputation::ReputationTier;
+        match tier {
+            ReputationTier::New => self.new,
+            ReputationTier::Established => self.established,
+            ReputationTier::Trusted => self.trusted,
+            ReputationTier::Exemplary => self.exemplary,
+        }
+    }
 }
 
 /// ID.me OAuth 2.0 configuration.
@@ -400,6 +855,36 @@ pub struct SwaggerConfig {
     pub enabled: bool,
 }
 
+/// Access restrictions for the GraphQL Playground and Swagger UI.
+///
+/// The `graphql.playground_enabled` / `swagger.enabled` toggles decide whether
+/// the tools are served at all; this section decides who may reach them. With
+/// no CIDR ranges and `allow_admins` off (the default) enabled tools are
+/// public. Otherwise a request must come from an allowed CIDR range or, with
+/// `allow_admins`, be device-signed by an account in
+/// `accounts.admin_account_ids`; everything else gets a 404.
+///
+/// Set via `TC_DEV_TOOLS__*` environment variables or `dev_tools.*` in config.yaml.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DevToolsConfig {
+    /// Client IP ranges allowed through, e.g. `10.0.0.0/8` or `::1/128`.
+    /// Accepts either an array or comma-separated string.
+    #[serde(default, deserialize_with = "deserialize_string_list")]
+    pub allowed_cidrs: Vec<String>,
+    /// Let device-signed requests from `accounts.admin_account_ids` through.
+    #[serde(default)]
+    pub allow_admins: bool,
+    /// Take the client IP from the last `X-Forwarded-For` entry instead of the
+    /// socket peer. Only enable behind a proxy that sets the header. Also
+    /// decides the IP that signature failures are blocked by.
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+    /// Check every JSON response against the `OpenAPI` document and log
+    /// mismatches. For development and CI; it parses every response.
+    #[serde(default)]
+    pub validate_responses: bool,
+}
+
 /// Rate limiting configuration for unauthenticated auth endpoints.
 ///
 /// Set via `TC_RATE_LIMIT__*` environment variables or `rate_limit.*` in config.yaml.
@@ -420,6 +905,23 @@ pub struct RateLimitConfig {
     #[serde(default = "default_backup_per_minute")]
     pub backup_per_minute: u32,
 
+    /// Max public reputation badge requests per minute per IP (default: 60).
+    #[serde(default = "default_badge_per_minute")]
+    pub badge_per_minute: u32,
+
+    /// Max third-party verification requests per minute per IP (default: 30).
+    #[serde(default = "default_verify_per_minute")]
+    pub verify_per_minute: u32,
+
+    /// Max requests per minute per IP that send an SMS: step-up phone
+    /// enrollment and challenges (default: 5).
+    #[serde(default = "default_sms_per_minute")]
+    pub sms_per_minute: u32,
+
+    /// Max username availability checks per minute per IP (default: 30).
+    #[serde(default = "default_username_check_per_minute")]
+    pub username_check_per_minute: u32,
+
     /// Enable rate limiting (default: true). Set to false in tests.
     #[serde(default = "default_true")]
     pub enabled: bool,
@@ -440,49 +942,1309 @@ fn default_backup_per_minute() -> u32 {
     10
 }
 
+#[allow(clippy::missing_const_for_fn)]
+fn default_badge_per_minute() -> u32 {
+    60
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_verify_per_minute() -> u32 {
+    30
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_sms_per_minute() -> u32 {
+    5
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_username_check_per_minute() -> u32 {
+    30
+}
+
 impl Default for RateLimitConfig {
     fn default() -> Self {
         Self {
             signup_per_minute: default_signup_per_minute(),
             login_per_minute: default_login_per_minute(),
             backup_per_minute: default_backup_per_minute(),
+            badge_per_minute: default_badge_per_minute(),
+            verify_per_minute: default_verify_per_minute(),
+            sms_per_minute: default_sms_per_minute(),
+            username_check_per_minute: default_username_check_per_minute(),
             enabled: default_true(),
         }
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            database: DatabaseConfig {
-                host: default_db_host(),
-                port: default_db_port(),
-                name: default_db_name(),
-                user: String::new(),
-                password: String::new(),
-                max_connections: default_max_connections(),
-                migrations_dir: None,
-                auto_reset_on_migration_failure: false,
-            },
-            server: ServerConfig {
-                port: default_port(),
-                host: default_host(),
-            },
-            logging: LoggingConfig {
-                level: default_log_level(),
-            },
-            cors: CorsConfig::default(),
-            security_headers: SecurityHeadersConfig::default(),
-            graphql: GraphQLConfig::default(),
-            swagger: SwaggerConfig::default(),
-            synthetic_backup_key: String::new(),
-            idme: None,
-            verifiers: Vec::new(),
-            rate_limit: RateLimitConfig::default(),
-        }
-    }
-}
-
+/// Replay protection for signed requests.
+///
+/// Set via `TC_AUTH__*` environment variables or `auth.*` in config.yaml.
+///
+/// A request is accepted while its timestamp is within `max_clock_skew_secs`
+/// of the server clock, in either direction, so a nonce must be kept for at
+/// least twice that long to reject every replay. Deployments with
+/// well-synchronized clocks can tighten both.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct AuthConfig {
+    /// Max difference between a request timestamp and the server clock, in
+    /// seconds (default: 300). Also bounds login certificate timestamps.
+    #[serde(default = "default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: u64,
+
+    /// Seconds a recorded nonce is kept before the retention worker purges
+    /// it (default: 600). Must be at least `2 * max_clock_skew_secs`.
+    #[serde(default = "default_nonce_ttl_secs")]
+    pub nonce_ttl_secs: u64,
+
+    /// Accept legacy v1 request signatures, which do not sign the host,
+    /// headers, or algorithm (default: true). Turn off once every client
+    /// sends `X-Signature-Alg`.
+    #[serde(default = "default_true")]
+    pub accept_v1_signatures: bool,
+
+    /// Signature failures from one client IP, within `failure_window_secs`,
+    /// that trigger a temporary block (default: 10). The same count against
+    /// one device KID is logged but never blocks. 0 disables blocking.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// Sliding window for counting signature failures, in seconds
+    /// (default: 300).
+    #[serde(default = "default_failure_window_secs")]
+    pub failure_window_secs: u64,
+
+    /// First block length, in seconds (default: 30). Each further block on
+    /// the same IP doubles it.
+    #[serde(default = "default_failure_block_secs")]
+    pub failure_block_secs: u64,
+
+    /// Longest block, in seconds (default: 900).
+    #[serde(default = "default_failure_block_max_secs")]
+    pub failure_block_max_secs: u64,
+
+    /// Lifetime of a session token from `POST /auth/sessions`, in seconds
+    /// (default: 900, at most one day). 0 stops issuing sessions; tokens
+    /// already issued keep working until they expire.
+    #[serde(default = "default_session_ttl_secs")]
+    pub session_ttl_secs: u64,
+
+    /// Lifetime of a challenge from `GET /auth/challenge`, in seconds
+    /// (default: 60, at most 600). 0 stops issuing challenges.
+    #[serde(default = "default_challenge_ttl_secs")]
+    pub challenge_ttl_secs: u64,
+}
+
+/// Accepted range for `auth.max_clock_skew_secs`.
+pub const MAX_CLOCK_SKEW_SECS_RANGE: std::ops::RangeInclusive<u64> = 5..=3600;
+
+/// Upper bound for `auth.nonce_ttl_secs` (one day).
+pub const MAX_NONCE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Upper bound for `auth.session_ttl_secs` (one day).
+pub const MAX_SESSION_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Upper bound for `auth.challenge_ttl_secs` (ten minutes).
+pub const MAX_CHALLENGE_TTL_SECS: u64 = 10 * 60;
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_max_clock_skew_secs() -> u64 {
+    300
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_nonce_ttl_secs() -> u64 {
+    600
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_failure_threshold() -> u32 {
+    10
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_failure_window_secs() -> u64 {
+    300
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_failure_block_secs() -> u64 {
+    30
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_failure_block_max_secs() -> u64 {
+    900
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_session_ttl_secs() -> u64 {
+    900
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_challenge_ttl_secs() -> u64 {
+    60
+}
+
+impl AuthConfig {
+    /// Returns `true` when `timestamp` differs from `now` by more than
+    /// `max_clock_skew_secs`.
+    ///
+    /// Uses [`i64::abs_diff`] so extreme values (`i64::MIN`, `i64::MAX`)
+    /// cannot overflow.
+    #[must_use]
+    pub const fn timestamp_is_stale(&self, now: i64, timestamp: i64) -> bool {
+        now.abs_diff(timestamp) > self.max_clock_skew_secs
+    }
+
+    /// How long recorded nonces are kept.
+    #[must_use]
+    pub const fn nonce_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.nonce_ttl_secs)
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            max_clock_skew_secs: default_max_clock_skew_secs(),
+            nonce_ttl_secs: default_nonce_ttl_secs(),
+            accept_v1_signatures: default_true(),
+            failure_threshold: default_failure_threshold(),
+            failure_window_secs: default_failure_window_secs(),
+            failure_block_secs: default_failure_block_secs(),
+            failure_block_max_secs: default_failure_block_max_secs(),
+            session_ttl_secs: default_session_ttl_secs(),
+            challenge_ttl_secs: default_challenge_ttl_secs(),
+        }
+    }
+}
+
+/// Domain event publishing configuration.
+///
+/// When enabled, signups, device revocations, endorsements, and ballots are
+/// mirrored as versioned JSON envelopes to NATS on subjects of the form
+/// `{subject_prefix}.{event}.v{version}`.
+///
+/// Disabled by default. Set via `TC_EVENTS__*` environment variables or
+/// `events.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventsConfig {
+    /// Enable event publishing (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// NATS server URL, e.g. `nats://nats:4222`. Required when enabled.
+    #[serde(default)]
+    pub nats_url: String,
+
+    /// Subject prefix for published events (default: "tc.events").
+    #[serde(default = "default_events_subject_prefix")]
+    pub subject_prefix: String,
+
+    /// Events buffered in memory while the broker is slow or unreachable
+    /// (default: 1024). Further events are dropped until the buffer drains.
+    #[serde(default = "default_events_buffer_size")]
+    pub buffer_size: usize,
+}
+
+fn default_events_subject_prefix() -> String {
+    "tc.events".to_string()
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_events_buffer_size() -> usize {
+    1024
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            nats_url: String::new(),
+            subject_prefix: default_events_subject_prefix(),
+            buffer_size: default_events_buffer_size(),
+        }
+    }
+}
+
+/// Data retention configuration.
+///
+/// Each `*_days` field opts a table into purging; unset tables are kept
+/// forever. Expired nonces are always purged regardless of these settings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetentionConfig {
+    /// Log and report what would be purged without deleting anything
+    /// (default: false). Does not apply to nonce expiry.
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Seconds between purge runs (default: 60).
+    #[serde(default = "default_retention_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Days to keep completed or failed trust actions.
+    #[serde(default)]
+    pub action_log_days: Option<u32>,
+
+    /// Days to keep finished bot traces.
+    #[serde(default)]
+    pub bot_trace_days: Option<u32>,
+
+    /// Days to keep unaccepted invites after they expire.
+    #[serde(default)]
+    pub expired_invite_days: Option<u32>,
+
+    /// Days to keep notifications, counted from when they were read, or from
+    /// creation for unread ones.
+    #[serde(default)]
+    pub notification_days: Option<u32>,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_retention_interval_secs() -> u64 {
+    60
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            interval_secs: default_retention_interval_secs(),
+            action_log_days: None,
+            bot_trace_days: None,
+            expired_invite_days: None,
+            notification_days: None,
+        }
+    }
+}
+
+/// Error reporting configuration.
+///
+/// When enabled, service `ERROR` logs and panics are scrubbed and sent to a
+/// Sentry-compatible collector. Disabled by default.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ErrorReportingConfig {
+    /// Enable error reporting (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Collector DSN, e.g. `https://<key>@sentry.example.com/<project>`.
+    #[serde(default)]
+    pub dsn: String,
+
+    /// Environment tag attached to every report (default: "production").
+    #[serde(default = "default_error_reporting_environment")]
+    pub environment: String,
+}
+
+impl std::fmt::Debug for ErrorReportingConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorReportingConfig")
+            .field("enabled", &self.enabled)
+            .field("dsn", &"[REDACTED]")
+            .field("environment", &self.environment)
+            .finish()
+    }
+}
+
+fn default_error_reporting_environment() -> String {
+    "production".to_string()
+}
+
+impl Default for ErrorReportingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dsn: String::new(),
+            environment: default_error_reporting_environment(),
+        }
+    }
+}
+
+/// Instance metadata for the public discovery document.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstanceConfig {
+    /// Display name of this instance (default: `TinyCongress`).
+    #[serde(default = "default_instance_name")]
+    pub name: String,
+
+    /// Person or organization running the instance; omitted when empty.
+    #[serde(default)]
+    pub operator: String,
+
+    /// Public origin of the web app, e.g. `https://tinycongress.example`,
+    /// used to build invite deep links. Empty yields root-relative links.
+    #[serde(default)]
+    pub web_url: String,
+}
+
+fn default_instance_name() -> String {
+    "TinyCongress".to_string()
+}
+
+impl Default for InstanceConfig {
+    fn default() -> Self {
+        Self {
+            name: default_instance_name(),
+            operator: String::new(),
+            web_url: String::new(),
+        }
+    }
+}
+
+/// Signup policy and invite code quotas.
+///
+/// When `invite_only` is set, `POST /auth/signup` requires an unused invite
+/// code issued through `POST /auth/signup-invites`. Accounts may hold up to
+/// `invites_per_account` outstanding codes; `accounts.admin_account_ids` have
+/// no cap.
+///
+/// Set via `TC_SIGNUP__*` environment variables or `signup.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignupConfig {
+    /// Require an invite code to sign up (default: false).
+    #[serde(default)]
+    pub invite_only: bool,
+    /// Unredeemed, unexpired codes an account may hold (default: 5).
+    #[serde(default = "default_invites_per_account")]
+    pub invites_per_account: u32,
+    /// Days until an invite code expires (default: 14).
+    #[serde(default = "default_invite_ttl_days")]
+    pub invite_ttl_days: u32,
+}
+
+/// Longest allowed invite code lifetime.
+pub const MAX_INVITE_TTL_DAYS: u32 = 365;
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_invites_per_account() -> u32 {
+    5
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_invite_ttl_days() -> u32 {
+    14
+}
+
+impl Default for SignupConfig {
+    fn default() -> Self {
+        Self {
+            invite_only: false,
+            invites_per_account: default_invites_per_account(),
+            invite_ttl_days: default_invite_ttl_days(),
+        }
+    }
+}
+
+/// Character set a [`UsernameConfig`] accepts; see
+/// [`crate::identity::username`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsernamePack {
+    /// ASCII letters, digits, `-` and `_`.
+    #[default]
+    Ascii,
+    /// As `ascii`, plus `.` between other characters.
+    AsciiDots,
+    /// Letters and digits of any identifier script, `-` and `_`, with
+    /// mixed-script and look-alike checks.
+    Unicode,
+}
+
+/// Rules for new usernames and aliases.
+///
+/// Set via `TC_USERNAMES__*` environment variables or `usernames.*` in
+/// config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UsernameConfig {
+    /// Allowed character set (default: ascii).
+    #[serde(default)]
+    pub pack: UsernamePack,
+    /// Fewest characters in a username (default: 3).
+    #[serde(default = "default_username_min_length")]
+    pub min_length: usize,
+    /// Most characters in a username (default: 64, at most 255).
+    #[serde(default = "default_username_max_length")]
+    pub max_length: usize,
+    /// File of extra reserved names, one per line, added to the built-in
+    /// list. Blank lines and `#` comments are ignored.
+    #[serde(default)]
+    pub reserved_file: Option<String>,
+    /// Days a username or alias an account gave up stays unclaimable by
+    /// other accounts (default: 90, 0 to disable).
+    #[serde(default = "default_username_release_cooldown_days")]
+    pub release_cooldown_days: u32,
+    /// Days an account must wait between username changes (default: 30, 0
+    /// to disable).
+    #[serde(default = "default_username_change_cooldown_days")]
+    pub change_cooldown_days: u32,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_username_min_length() -> usize {
+    crate::identity::username::MIN_USERNAME_LEN
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_username_max_length() -> usize {
+    crate::identity::username::MAX_USERNAME_LEN
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_username_release_cooldown_days() -> u32 {
+    crate::identity::username::DEFAULT_RELEASE_COOLDOWN_DAYS
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_username_change_cooldown_days() -> u32 {
+    crate::identity::username::DEFAULT_CHANGE_COOLDOWN_DAYS
+}
+
+impl Default for UsernameConfig {
+    fn default() -> Self {
+        Self {
+            pack: UsernamePack::default(),
+            min_length: default_username_min_length(),
+            max_length: default_username_max_length(),
+            reserved_file: None,
+            release_cooldown_days: default_username_release_cooldown_days(),
+            change_cooldown_days: default_username_change_cooldown_days(),
+        }
+    }
+}
+
+/// Request quotas for API bearer tokens.
+///
+/// Each token may make up to `daily_quota` requests per UTC day and
+/// `monthly_quota` per UTC calendar month; `0` disables a limit. Accounts in
+/// `accounts.admin_account_ids` may view the top consumers at
+/// `GET /auth/tokens/usage`.
+///
+/// Set via `TC_API_TOKENS__*` environment variables or `api_tokens.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiTokenConfig {
+    /// Requests per token per UTC day (default: 10000).
+    #[serde(default = "default_api_token_daily_quota")]
+    pub daily_quota: u32,
+    /// Requests per token per UTC month (default: 200000).
+    #[serde(default = "default_api_token_monthly_quota")]
+    pub monthly_quota: u32,
+    /// Seconds between flushes of in-memory usage counters to Postgres (default: 10).
+    #[serde(default = "default_api_token_usage_flush_secs")]
+    pub usage_flush_secs: u64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_api_token_daily_quota() -> u32 {
+    10_000
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_api_token_monthly_quota() -> u32 {
+    200_000
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_api_token_usage_flush_secs() -> u64 {
+    10
+}
+
+impl Default for ApiTokenConfig {
+    fn default() -> Self {
+        Self {
+            daily_quota: default_api_token_daily_quota(),
+            monthly_quota: default_api_token_monthly_quota(),
+            usage_flush_secs: default_api_token_usage_flush_secs(),
+        }
+    }
+}
+
+/// Anonymized bulk exports for researchers.
+///
+/// Accounts in `researcher_account_ids` may request an export through
+/// `POST /research/exports`; an account in `accounts.admin_account_ids` other
+/// than the requester must approve it before it can be downloaded. Rows
+/// covering fewer than `k_anonymity` distinct accounts are suppressed. Both
+/// lists default to empty, so exports are unavailable until an operator opts
+/// in.
+///
+/// Set via `TC_RESEARCH__*` environment variables or `research.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResearchConfig {
+    /// IDs of the accounts that may request exports.
+    /// Accepts either an array or comma-separated string.
+    #[serde(default, deserialize_with = "deserialize_uuid_list")]
+    pub researcher_account_ids: Vec<Uuid>,
+    /// Minimum distinct accounts behind every exported row (default: 10).
+    #[serde(default = "default_research_k_anonymity")]
+    pub k_anonymity: u32,
+    /// Export requests a researcher may file per rolling 24 hours (default: 5).
+    #[serde(default = "default_research_requests_per_day")]
+    pub requests_per_day: u32,
+    /// Downloads allowed per approved export (default: 3).
+    #[serde(default = "default_research_downloads_per_export")]
+    pub downloads_per_export: u32,
+    /// Days an approval stays valid (default: 7).
+    #[serde(default = "default_research_approval_ttl_days")]
+    pub approval_ttl_days: u32,
+}
+
+/// Smallest allowed `research.k_anonymity`.
+pub const MIN_RESEARCH_K_ANONYMITY: u32 = 2;
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_research_k_anonymity() -> u32 {
+    10
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_research_requests_per_day() -> u32 {
+    5
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_research_downloads_per_export() -> u32 {
+    3
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_research_approval_ttl_days() -> u32 {
+    7
+}
+
+impl Default for ResearchConfig {
+    fn default() -> Self {
+        Self {
+            researcher_account_ids: Vec::new(),
+            k_anonymity: default_research_k_anonymity(),
+            requests_per_day: default_research_requests_per_day(),
+            downloads_per_export: default_research_downloads_per_export(),
+            approval_ttl_days: default_research_approval_ttl_days(),
+        }
+    }
+}
+
+impl ResearchConfig {
+    /// Whether `account_id` may request exports.
+    #[must_use]
+    pub fn is_researcher(&self, account_id: Uuid) -> bool {
+        self.researcher_account_ids.contains(&account_id)
+    }
+}
+
+/// Noise on publicly exposed aggregate counts; see [`crate::privacy`].
+///
+/// Disabled by default. When enabled, counts below `min_count` are reported
+/// as zero and the rest get Laplace noise with scale `1 / epsilon`. Accounts
+/// in `accounts.admin_account_ids` still see exact values.
+///
+/// Set via `TC_PRIVACY__*` environment variables or `privacy.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PrivacyConfig {
+    /// Apply noise to public aggregates (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+    /// Privacy budget per count; smaller is noisier (default: 1.0).
+    #[serde(default = "default_privacy_epsilon")]
+    pub epsilon: f64,
+    /// Counts below this are suppressed to zero (default: 5).
+    #[serde(default = "default_privacy_min_count")]
+    pub min_count: u32,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_privacy_epsilon() -> f64 {
+    1.0
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_privacy_min_count() -> u32 {
+    5
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            epsilon: default_privacy_epsilon(),
+            min_count: default_privacy_min_count(),
+        }
+    }
+}
+
+/// Reputation tiers; see [`crate::reputation::score`].
+///
+/// An account's score maps to the highest tier whose threshold it reaches;
+/// below `established_score` it is `new`. Thresholds must be positive and
+/// strictly increasing.
+///
+/// Set via `TC_REPUTATION__*` environment variables or `reputation.*` in
+/// config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReputationConfig {
+    /// Score at which an account becomes `established` (default: 20).
+    #[serde(default = "default_established_score")]
+    pub established_score: f64,
+    /// Score at which an account becomes `trusted` (default: 60).
+    #[serde(default = "default_trusted_score")]
+    pub trusted_score: f64,
+    /// Score at which an account becomes `exemplary` (default: 150).
+    #[serde(default = "default_exemplary_score")]
+    pub exemplary_score: f64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_established_score() -> f64 {
+    20.0
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_trusted_score() -> f64 {
+    60.0
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_exemplary_score() -> f64 {
+    150.0
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            established_score: default_established_score(),
+            trusted_score: default_trusted_score(),
+            exemplary_score: default_exemplary_score(),
+        }
+    }
+}
+
+/// How email and phone verification codes are delivered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationSenderKind {
+    /// Codes are never sent; email and phone verification return 503.
+    #[default]
+    Disabled,
+    /// Write each message, code included, to the service log. Local
+    /// development only.
+    Log,
+    /// POST each message as JSON to `sender_url`, an SMS/email gateway.
+    Http,
+}
+
+/// Account verification methods; see [`crate::reputation::verification`].
+///
+/// Email and phone verification send a one-time code through `sender`,
+/// which is disabled by default. An account is verified in person once
+/// `in_person_attestations_required` verified accounts attest to it. The
+/// ID verification webhook is off until `idv_webhook_secret` is set.
+///
+/// Set via `TC_VERIFICATION__*` environment variables or `verification.*`
+/// in config.yaml.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VerificationConfig {
+    /// Code delivery: `disabled` (default), `log` or `http`.
+    #[serde(default)]
+    pub sender: VerificationSenderKind,
+    /// Gateway URL for the `http` sender.
+    #[serde(default)]
+    pub sender_url: String,
+    /// Bearer token sent to the gateway; omitted when empty.
+    #[serde(default)]
+    pub sender_token: String,
+    /// Seconds a code stays valid (default: 600, at most one day).
+    #[serde(default = "default_verification_code_ttl_secs")]
+    pub code_ttl_secs: u64,
+    /// Wrong guesses allowed per code (default: 5).
+    #[serde(default = "default_verification_max_attempts")]
+    pub max_code_attempts: u32,
+    /// Seconds before a new code may be requested (default: 60).
+    #[serde(default = "default_verification_resend_secs")]
+    pub resend_cooldown_secs: u64,
+    /// Attestations from verified accounts needed for in-person
+    /// verification (default: 3).
+    #[serde(default = "default_in_person_attestations_required")]
+    pub in_person_attestations_required: u32,
+    /// HMAC-SHA256 key shared with the ID verification provider. Empty
+    /// disables `POST /verifications/idv/webhook`. At least 32 bytes.
+    #[serde(default)]
+    pub idv_webhook_secret: String,
+    /// Provider name recorded on government ID verifications (default: "idv").
+    #[serde(default = "default_idv_provider")]
+    pub idv_provider: String,
+}
+
+impl std::fmt::Debug for VerificationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerificationConfig")
+            .field("sender", &self.sender)
+            .field("sender_url", &self.sender_url)
+            .field("sender_token", &"[REDACTED]")
+            .field("code_ttl_secs", &self.code_ttl_secs)
+            .field("max_code_attempts", &self.max_code_attempts)
+            .field("resend_cooldown_secs", &self.resend_cooldown_secs)
+            .field(
+                "in_person_attestations_required",
+                &self.in_person_attestations_required,
+            )
+            .field("idv_webhook_secret", &"[REDACTED]")
+            .field("idv_provider", &self.idv_provider)
+            .finish()
+    }
+}
+
+/// Longest accepted `verification.code_ttl_secs` (one day).
+pub const MAX_VERIFICATION_CODE_TTL_SECS: u64 = 86_400;
+
+/// Shortest accepted `verification.idv_webhook_secret`.
+pub const MIN_IDV_WEBHOOK_SECRET_LEN: usize = 32;
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_verification_code_ttl_secs() -> u64 {
+    600
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_verification_max_attempts() -> u32 {
+    5
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_verification_resend_secs() -> u64 {
+    60
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_in_person_attestations_required() -> u32 {
+    3
+}
+
+fn default_idv_provider() -> String {
+    "idv".to_string()
+}
+
+impl Default for VerificationConfig {
+    fn default() -> Self {
+        Self {
+            sender: VerificationSenderKind::Disabled,
+            sender_url: String::new(),
+            sender_token: String::new(),
+            code_ttl_secs: default_verification_code_ttl_secs(),
+            max_code_attempts: default_verification_max_attempts(),
+            resend_cooldown_secs: default_verification_resend_secs(),
+            in_person_attestations_required: default_in_person_attestations_required(),
+            idv_webhook_secret: String::new(),
+            idv_provider: default_idv_provider(),
+        }
+    }
+}
+
+/// Which SMS provider delivers text messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmsProviderKind {
+    /// No SMS; phone enrollment returns 503 and step-up is never required.
+    #[default]
+    Disabled,
+    /// Keep messages in memory and write them to the service log. Local
+    /// development and tests only.
+    Mock,
+    /// Twilio Programmable Messaging.
+    Twilio,
+    /// Amazon SNS direct publish to a phone number.
+    Sns,
+}
+
+/// SMS delivery; see [`crate::sms`].
+///
+/// Disabled by default. Twilio needs `twilio_account_sid`,
+/// `twilio_auth_token` and `twilio_from`; SNS needs `sns_region`,
+/// `sns_access_key_id` and `sns_secret_access_key`.
+///
+/// Set via `TC_SMS__*` environment variables or `sms.*` in config.yaml.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct SmsConfig {
+    /// `disabled` (default), `mock`, `twilio` or `sns`.
+    #[serde(default)]
+    pub provider: SmsProviderKind,
+    #[serde(default)]
+    pub twilio_account_sid: String,
+    #[serde(default)]
+    pub twilio_auth_token: String,
+    /// Sending number or messaging service SID.
+    #[serde(default)]
+    pub twilio_from: String,
+    /// AWS region, e.g. `us-east-1`.
+    #[serde(default)]
+    pub sns_region: String,
+    #[serde(default)]
+    pub sns_access_key_id: String,
+    #[serde(default)]
+    pub sns_secret_access_key: String,
+}
+
+impl std::fmt::Debug for SmsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmsConfig")
+            .field("provider", &self.provider)
+            .field("twilio_account_sid", &self.twilio_account_sid)
+            .field("twilio_auth_token", &"[REDACTED]")
+            .field("twilio_from", &self.twilio_from)
+            .field("sns_region", &self.sns_region)
+            .field("sns_access_key_id", &self.sns_access_key_id)
+            .field("sns_secret_access_key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Step-up confirmation by SMS; see [`crate::identity::step_up`].
+///
+/// Once an account enrolls a phone, high-risk actions from a device added
+/// in the last `new_device_hours`, and removing the phone itself, need a
+/// code texted to that phone. Requires an SMS provider.
+///
+/// Set via `TC_STEP_UP__*` environment variables or `step_up.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StepUpConfig {
+    /// Seconds a texted code stays valid (default: 300, at most one day).
+    #[serde(default = "default_step_up_code_ttl_secs")]
+    pub code_ttl_secs: u64,
+    /// Wrong guesses allowed per code (default: 5).
+    #[serde(default = "default_step_up_max_attempts")]
+    pub max_code_attempts: u32,
+    /// Devices younger than this many hours are treated as new (default: 72).
+    #[serde(default = "default_step_up_new_device_hours")]
+    pub new_device_hours: u32,
+    /// Seconds a confirmed challenge may be used to retry the action
+    /// (default: 300, at most one day).
+    #[serde(default = "default_step_up_confirmation_ttl_secs")]
+    pub confirmation_ttl_secs: u64,
+}
+
+/// Longest accepted `step_up.code_ttl_secs` and
+/// `step_up.confirmation_ttl_secs` (one day).
+pub const MAX_STEP_UP_TTL_SECS: u64 = 86_400;
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_step_up_code_ttl_secs() -> u64 {
+    300
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_step_up_max_attempts() -> u32 {
+    5
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_step_up_new_device_hours() -> u32 {
+    72
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_step_up_confirmation_ttl_secs() -> u64 {
+    300
+}
+
+impl Default for StepUpConfig {
+    fn default() -> Self {
+        Self {
+            code_ttl_secs: default_step_up_code_ttl_secs(),
+            max_code_attempts: default_step_up_max_attempts(),
+            new_device_hours: default_step_up_new_device_hours(),
+            confirmation_ttl_secs: default_step_up_confirmation_ttl_secs(),
+        }
+    }
+}
+
+/// Feed precomputation; see [`crate::feed`].
+///
+/// Accounts with a large trust reach are the expensive ones to rank, so
+/// their feed is ranked in the background and served from the stored
+/// result while it is fresh.
+///
+/// Set via `TC_FEED__*` environment variables or `feed.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeedConfig {
+    /// Accounts with at least this many accounts within trust reach get a
+    /// precomputed feed (default: 200). 0 turns precomputation off.
+    #[serde(default = "default_feed_precompute_min_reach")]
+    pub precompute_min_reach: u32,
+    /// Seconds a precomputed feed is served before it is rebuilt
+    /// (default: 300).
+    #[serde(default = "default_feed_precompute_max_age_secs")]
+    pub precompute_max_age_secs: u64,
+    /// Seconds between precompute runs (default: 60).
+    #[serde(default = "default_feed_precompute_interval_secs")]
+    pub precompute_interval_secs: u64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_feed_precompute_min_reach() -> u32 {
+    200
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_feed_precompute_max_age_secs() -> u64 {
+    300
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_feed_precompute_interval_secs() -> u64 {
+    60
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            precompute_min_reach: default_feed_precompute_min_reach(),
+            precompute_max_age_secs: default_feed_precompute_max_age_secs(),
+            precompute_interval_secs: default_feed_precompute_interval_secs(),
+        }
+    }
+}
+
+/// Self-service account deletion; see [`crate::identity::deletion`].
+///
+/// `DELETE /auth/accounts/me` deactivates the account at once and schedules
+/// it for purging after `grace_days`; until then the owner can undo it with
+/// their root key.
+///
+/// Set via `TC_ACCOUNT_DELETION__*` environment variables or
+/// `account_deletion.*` in config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountDeletionConfig {
+    /// Days a deactivated account can still be restored (default: 30).
+    #[serde(default = "default_account_deletion_grace_days")]
+    pub grace_days: u32,
+    /// Seconds between purge runs (default: 3600).
+    #[serde(default = "default_account_deletion_sweep_interval_secs")]
+    pub sweep_interval_secs: u64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_account_deletion_grace_days() -> u32 {
+    30
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_account_deletion_sweep_interval_secs() -> u64 {
+    3600
+}
+
+impl Default for AccountDeletionConfig {
+    fn default() -> Self {
+        Self {
+            grace_days: default_account_deletion_grace_days(),
+            sweep_interval_secs: default_account_deletion_sweep_interval_secs(),
+        }
+    }
+}
+
+/// Background integrity sweep; see [`crate::integrity::worker`].
+///
+/// Set via `TC_INTEGRITY__*` environment variables or `integrity.*` in
+/// config.yaml.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IntegrityConfig {
+    /// Run the sweep in the background (default: true).
+    #[serde(default = "default_integrity_enabled")]
+    pub enabled: bool,
+    /// Seconds between sweeps (default: 86400).
+    #[serde(default = "default_integrity_sweep_interval_secs")]
+    pub sweep_interval_secs: u64,
+    /// Signed events (sigchain links, device revocations) from the last this
+    /// many days have their signatures re-checked (default: 7).
+    #[serde(default = "default_integrity_recent_days")]
+    pub recent_days: u32,
+    /// Milliseconds to wait between batches, to keep the sweep from
+    /// competing with live traffic (default: 250).
+    #[serde(default = "default_integrity_batch_pause_ms")]
+    pub batch_pause_ms: u64,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_integrity_enabled() -> bool {
+    true
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_integrity_sweep_interval_secs() -> u64 {
+    86_400
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_integrity_recent_days() -> u32 {
+    7
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_integrity_batch_pause_ms() -> u64 {
+    250
+}
+
+impl Default for IntegrityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_integrity_enabled(),
+            sweep_interval_secs: default_integrity_sweep_interval_secs(),
+            recent_days: default_integrity_recent_days(),
+            batch_pause_ms: default_integrity_batch_pause_ms(),
+        }
+    }
+}
+
+/// Where analytics exports are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsSinkKind {
+    /// No export.
+    #[default]
+    Disabled,
+    /// Files under `local_dir`, e.g. a mounted volume.
+    Local,
+    /// An S3-compatible bucket, written with signed `PutObject` requests.
+    S3,
+}
+
+/// Scheduled Parquet export for analytics; see [`crate::analytics`].
+///
+/// Disabled by default. The `local` sink needs `local_dir`; the `s3` sink
+/// needs `s3_bucket`, `s3_region`, `s3_access_key_id` and
+/// `s3_secret_access_key`, plus `s3_endpoint` for anything other than AWS.
+///
+/// Set via `TC_ANALYTICS_EXPORT__*` environment variables or
+/// `analytics_export.*` in config.yaml.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct AnalyticsExportConfig {
+    /// `disabled` (default), `local` or `s3`.
+    #[serde(default)]
+    pub sink: AnalyticsSinkKind,
+    /// Seconds between exports (default: 86400).
+    #[serde(default = "default_analytics_export_interval_secs")]
+    pub interval_secs: u64,
+    /// Rows fetched per query and written per Parquet row group
+    /// (default: 10000).
+    #[serde(default = "default_analytics_export_batch_rows")]
+    pub batch_rows: u32,
+    /// Key prefix under which each dataset gets its own directory
+    /// (default: `tinycongress`). Lowercase letters, digits, `-`, `_`, `.`
+    /// and `/` only.
+    #[serde(default = "default_analytics_export_prefix")]
+    pub prefix: String,
+    /// Root directory for the `local` sink.
+    #[serde(default)]
+    pub local_dir: String,
+    #[serde(default)]
+    pub s3_bucket: String,
+    /// AWS region, e.g. `us-east-1`; also used in request signatures for
+    /// other S3-compatible stores.
+    #[serde(default)]
+    pub s3_region: String,
+    /// Endpoint URL for S3-compatible stores, e.g. `https://minio:9000`.
+    /// Empty means `https://s3.<region>.amazonaws.com`.
+    #[serde(default)]
+    pub s3_endpoint: String,
+    #[serde(default)]
+    pub s3_access_key_id: String,
+    #[serde(default)]
+    pub s3_secret_access_key: String,
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_analytics_export_interval_secs() -> u64 {
+    86_400
+}
+
+#[allow(clippy::missing_const_for_fn)]
+fn default_analytics_export_batch_rows() -> u32 {
+    10_000
+}
+
+fn default_analytics_export_prefix() -> String {
+    "tinycongress".to_string()
+}
+
+impl Default for AnalyticsExportConfig {
+    fn default() -> Self {
+        Self {
+            sink: AnalyticsSinkKind::default(),
+            interval_secs: default_analytics_export_interval_secs(),
+            batch_rows: default_analytics_export_batch_rows(),
+            prefix: default_analytics_export_prefix(),
+            local_dir: String::new(),
+            s3_bucket: String::new(),
+            s3_region: String::new(),
+            s3_endpoint: String::new(),
+            s3_access_key_id: String::new(),
+            s3_secret_access_key: String::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for AnalyticsExportConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnalyticsExportConfig")
+            .field("sink", &self.sink)
+            .field("interval_secs", &self.interval_secs)
+            .field("batch_rows", &self.batch_rows)
+            .field("prefix", &self.prefix)
+            .field("local_dir", &self.local_dir)
+            .field("s3_bucket", &self.s3_bucket)
+            .field("s3_region", &self.s3_region)
+            .field("s3_endpoint", &self.s3_endpoint)
+            .field("s3_access_key_id", &self.s3_access_key_id)
+            .field("s3_secret_access_key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Instance admins and account moderation; see [`crate::identity::status`].
+///
+/// `admin_account_ids` is the one admin list for the instance. Admins may
+/// change other accounts' status through `PUT /accounts/{account_id}/status`,
+/// review endorsement disputes and strikes, manage reserved and released
+/// usernames, review integrity alerts, issue invite codes without a quota,
+/// view API token usage, approve research exports, see exact aggregates, and
+/// (with `dev_tools.allow_admins`) reach the developer tools. Empty by
+/// default, so only owners can lock or delete their own accounts until an
+/// operator opts in.
+///
+/// Admins are listed by account ID rather than username: usernames can be
+/// changed and released, and a released name must not carry admin rights to
+/// whoever claims it next.
+///
+/// Set via `TC_ACCOUNTS__*` environment variables or `accounts.*` in config.yaml.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AccountsConfig {
+    /// IDs of the instance's admin accounts.
+    /// Accepts either an array or comma-separated string.
+    #[serde(default, deserialize_with = "deserialize_uuid_list")]
+    pub admin_account_ids: Vec<Uuid>,
+}
+
+impl AccountsConfig {
+    /// Whether `account_id` is an instance admin.
+    #[must_use]
+    pub fn is_admin(&self, account_id: Uuid) -> bool {
+        self.admin_account_ids.contains(&account_id)
+    }
+}
+
+/// Minimum account age, activity and verification before an account may
+/// perform sensitive trust actions. All requirements default to zero (no gate).
+///
+/// Set via `TC_ACCOUNT_GATES__<ACTION>__*` environment variables or
+/// `account_gates.<action>.*` in config.yaml.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct AccountGatesConfig {
+    /// `POST /trust/endorse`
+    #[serde(default)]
+    pub endorse: ActionRequirement,
+    /// `POST /trust/denounce`
+    #[serde(default)]
+    pub denounce: ActionRequirement,
+    /// `POST /trust/invites`
+    #[serde(default)]
+    pub invite: ActionRequirement,
+}
+
+/// Requirements an account must meet for one gated action.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ActionRequirement {
+    /// Days since the account was created.
+    #[serde(default)]
+    pub min_account_age_days: u32,
+    /// Distinct polls the account has voted in.
+    #[serde(default)]
+    pub min_polls_voted: u32,
+    /// Weakest verification method that satisfies the gate, e.g. `phone`;
+    /// stronger methods also count.
+    #[serde(default)]
+    pub min_verification: Option<VerificationMethod>,
+}
+
+impl AccountGatesConfig {
+    /// Whether any action has a requirement configured.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        !self.endorse.is_unrestricted()
+            || !self.denounce.is_unrestricted()
+            || !self.invite.is_unrestricted()
+    }
+}
+
+impl ActionRequirement {
+    #[must_use]
+    pub const fn is_unrestricted(&self) -> bool {
+        self.min_account_age_days == 0
+            && self.min_polls_voted == 0
+            && self.min_verification.is_none()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            database: DatabaseConfig {
+                host: default_db_host(),
+                port: default_db_port(),
+                name: default_db_name(),
+                user: String::new(),
+                password: String::new(),
+                max_connections: default_max_connections(),
+                migrations_dir: None,
+                auto_reset_on_migration_failure: false,
+                startup_deadline_secs: default_db_startup_deadline_secs(),
+                wait_for_pgmq: false,
+                explain_queries: false,
+                reader_user: None,
+                reader_password: None,
+            },
+            server: ServerConfig {
+                port: default_port(),
+                host: default_host(),
+            },
+            logging: LoggingConfig {
+                level: default_log_level(),
+            },
+            cors: CorsConfig::default(),
+            security_headers: SecurityHeadersConfig::default(),
+            compression: CompressionConfig::default(),
+            concurrency: ConcurrencyConfig::default(),
+            read_only: ReadOnlyConfig::default(),
+            graphql: GraphQLConfig::default(),
+            swagger: SwaggerConfig::default(),
+            dev_tools: DevToolsConfig::default(),
+            synthetic_backup_key: String::new(),
+            idme: None,
+            verifiers: Vec::new(),
+            rate_limit: RateLimitConfig::default(),
+            auth: AuthConfig::default(),
+            events: EventsConfig::default(),
+            retention: RetentionConfig::default(),
+            error_reporting: ErrorReportingConfig::default(),
+            instance: InstanceConfig::default(),
+            signup: SignupConfig::default(),
+            usernames: UsernameConfig::default(),
+            account_gates: AccountGatesConfig::default(),
+            api_tokens: ApiTokenConfig::default(),
+            research: ResearchConfig::default(),
+            privacy: PrivacyConfig::default(),
+            accounts: AccountsConfig::default(),
+            reputation: ReputationConfig::default(),
+            verification: VerificationConfig::default(),
+            sms: SmsConfig::default(),
+            step_up: StepUpConfig::default(),
+            feed: FeedConfig::default(),
+            account_deletion: AccountDeletionConfig::default(),
+            integrity: IntegrityConfig::default(),
+            analytics_export: AnalyticsExportConfig::default(),
+        }
+    }
+}
+
 /// Configuration loading and validation errors.
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -511,15 +2273,19 @@ impl Config {
     /// # Errors
     /// Returns an error if configuration cannot be loaded or is invalid.
     pub fn load() -> Result<Self, ConfigError> {
-        let config: Self = Figment::new()
+        let config: Self = Self::figment().extract()?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The standard source chain used by [`Config::load`].
+    fn figment() -> Figment {
+        Figment::new()
             .merge(Serialized::defaults(Self::default()))
             .merge(Yaml::file("/etc/tc/config.yaml"))
             .merge(Yaml::file("config.yaml"))
             .merge(Env::prefixed("TC_").split("__"))
-            .extract()?;
-
-        config.validate()?;
-        Ok(config)
     }
 
     /// Load configuration with a custom YAML file path.
@@ -545,6 +2311,7 @@ impl Config {
     ///
     /// # Errors
     /// Returns an error if any configuration value is invalid.
+    #[allow(clippy::too_many_lines)]
     pub fn validate(&self) -> Result<(), ConfigError> {
         // Database user is required
         if self.database.user.is_empty() {
@@ -560,81 +2327,544 @@ impl Config {
             ));
         }
 
-        // Database port must be non-zero
-        if self.database.port == 0 {
-            return Err(ConfigError::Validation("database.port cannot be 0".into()));
+        // Database port must be non-zero
+        if self.database.port == 0 {
+            return Err(ConfigError::Validation("database.port cannot be 0".into()));
+        }
+
+        // Port must be non-zero
+        if self.server.port == 0 {
+            return Err(ConfigError::Validation("server.port cannot be 0".into()));
+        }
+
+        // Max connections must be at least 1
+        if self.database.max_connections == 0 {
+            return Err(ConfigError::Validation(
+                "database.max_connections cannot be 0".into(),
+            ));
+        }
+
+        if self.database.startup_deadline_secs == 0 {
+            return Err(ConfigError::Validation(
+                "database.startup_deadline_secs cannot be 0".into(),
+            ));
+        }
+
+        match (&self.database.reader_user, &self.database.reader_password) {
+            (None, None) => {}
+            (Some(user), Some(password)) if !user.is_empty() && !password.is_empty() => {
+                if *user == self.database.user {
+                    return Err(ConfigError::Validation(
+                        "database.reader_user must differ from database.user".into(),
+                    ));
+                }
+            }
+            _ => {
+                return Err(ConfigError::Validation(
+                    "database.reader_user and database.reader_password must be set together. Set TC_DATABASE__READER_USER and TC_DATABASE__READER_PASSWORD.".into(),
+                ));
+            }
+        }
+
+        self.validate_cors()?;
+        self.validate_concurrency()?;
+        self.validate_read_only()?;
+        self.validate_usernames()?;
+        self.validate_graphql()?;
+
+        // X-Frame-Options must be DENY or SAMEORIGIN
+        let frame_opts = self.security_headers.frame_options.to_uppercase();
+        if frame_opts != "DENY" && frame_opts != "SAMEORIGIN" {
+            return Err(ConfigError::Validation(format!(
+                "security_headers.frame_options must be 'DENY' or 'SAMEORIGIN', got: '{}'",
+                self.security_headers.frame_options
+            )));
+        }
+
+        // Synthetic backup HMAC key is required and must be at least 32 bytes
+        if self.synthetic_backup_key.is_empty() {
+            return Err(ConfigError::Validation(
+                "synthetic_backup_key is required. Set TC_SYNTHETIC_BACKUP_KEY environment variable or configure in config.yaml.".into(),
+            ));
+        }
+        if self.synthetic_backup_key.len() < 32 {
+            return Err(ConfigError::Validation(
+                "TC_SYNTHETIC_BACKUP_KEY must be at least 32 bytes".into(),
+            ));
+        }
+
+        // If IdMe is configured, validate all required fields
+        if let Some(ref idme) = self.idme {
+            if idme.client_id.is_empty() {
+                return Err(ConfigError::Validation(
+                    "idme.client_id is required when IdMe is enabled. Set TC_IDME__CLIENT_ID."
+                        .into(),
+                ));
+            }
+            if idme.client_secret.is_empty() {
+                return Err(ConfigError::Validation(
+                    "idme.client_secret is required when IdMe is enabled. Set TC_IDME__CLIENT_SECRET.".into(),
+                ));
+            }
+            if idme.redirect_uri.is_empty() {
+                return Err(ConfigError::Validation(
+                    "idme.redirect_uri is required when IdMe is enabled. Set TC_IDME__REDIRECT_URI.".into(),
+                ));
+            }
+            if idme.frontend_callback_url.is_empty() {
+                return Err(ConfigError::Validation(
+                    "idme.frontend_callback_url is required when IdMe is enabled. Set TC_IDME__FRONTEND_CALLBACK_URL.".into(),
+                ));
+            }
+            if idme.state_secret.len() < 32 {
+                return Err(ConfigError::Validation(
+                    "idme.state_secret must be at least 32 bytes. Set TC_IDME__STATE_SECRET."
+                        .into(),
+                ));
+            }
+        }
+
+        if self.events.enabled {
+            if self.events.nats_url.is_empty() {
+                return Err(ConfigError::Validation(
+                    "events.nats_url is required when events are enabled. Set TC_EVENTS__NATS_URL."
+                        .into(),
+                ));
+            }
+            if self.events.subject_prefix.is_empty() {
+                return Err(ConfigError::Validation(
+                    "events.subject_prefix cannot be empty".into(),
+                ));
+            }
+            if self.events.buffer_size == 0 {
+                return Err(ConfigError::Validation(
+                    "events.buffer_size cannot be 0".into(),
+                ));
+            }
+        }
+
+        self.validate_dev_tools()?;
+        self.validate_auth()?;
+        self.validate_retention()?;
+        self.validate_error_reporting()?;
+        self.validate_instance()?;
+        self.validate_signup()?;
+        self.validate_api_tokens()?;
+        self.validate_research()?;
+        self.validate_privacy()?;
+        self.validate_reputation()?;
+        self.validate_verification()?;
+        self.validate_sms()?;
+        self.validate_feed()?;
+        self.validate_account_deletion()?;
+        self.validate_integrity()?;
+        self.validate_analytics_export()
+    }
+
+    fn validate_cors(&self) -> Result<(), ConfigError> {
+        // CORS origins must be valid URLs or "*"
+        for (field, origins) in [
+            ("allowed_origins", &self.cors.allowed_origins),
+            ("public_origins", &self.cors.public_origins),
+            ("auth_origins", &self.cors.auth_origins),
+        ] {
+            for origin in origins {
+                if origin != "*"
+                    && !origin.starts_with("http://")
+                    && !origin.starts_with("https://")
+                {
+                    return Err(ConfigError::Validation(format!(
+                        "cors.{field} contains invalid origin '{origin}'. Must be '*' or start with http:// or https://"
+                    )));
+                }
+            }
+        }
+        // Auth routes must name their origins explicitly
+        if self.cors.auth_origins.iter().any(|o| o == "*") {
+            return Err(ConfigError::Validation(
+                "cors.auth_origins cannot be '*'; list the frontend origins explicitly".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_concurrency(&self) -> Result<(), ConfigError> {
+        if let Some(route) = self
+            .concurrency
+            .expensive_routes
+            .iter()
+            .find(|route| !route.starts_with('/'))
+        {
+            return Err(ConfigError::Validation(format!(
+                "concurrency.expensive_routes entry '{route}' must start with '/'"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_read_only(&self) -> Result<(), ConfigError> {
+        let read_only = &self.read_only;
+        if read_only.probe_interval_secs == 0 {
+            return Err(ConfigError::Validation(
+                "read_only.probe_interval_secs cannot be 0".into(),
+            ));
+        }
+        if read_only.failure_threshold == 0 || read_only.recovery_threshold == 0 {
+            return Err(ConfigError::Validation(
+                "read_only.failure_threshold and read_only.recovery_threshold must be at least 1"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_usernames(&self) -> Result<(), ConfigError> {
+        let usernames = &self.usernames;
+        if usernames.min_length == 0 || usernames.min_length > usernames.max_length {
+            return Err(ConfigError::Validation(
+                "usernames.min_length must be at least 1 and no more than usernames.max_length"
+                    .into(),
+            ));
+        }
+        if usernames.max_length > crate::identity::username::USERNAME_LENGTH_LIMIT {
+            return Err(ConfigError::Validation(format!(
+                "usernames.max_length cannot exceed {}",
+                crate::identity::username::USERNAME_LENGTH_LIMIT
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_graphql(&self) -> Result<(), ConfigError> {
+        let graphql = &self.graphql;
+        if graphql.max_complexity == 0 {
+            return Err(ConfigError::Validation(
+                "graphql.max_complexity cannot be 0".into(),
+            ));
+        }
+        let budgets = &graphql.cost_budgets;
+        let smallest = crate::reputation::ReputationTier::ALL
+            .into_iter()
+            .map(|tier| budgets.budget(tier))
+            .min()
+            .unwrap_or(0);
+        if budgets.enabled && (graphql.max_complexity as u64) > u64::from(smallest) {
+            return Err(ConfigError::Validation(format!(
+                "graphql.max_complexity ({}) cannot exceed the smallest graphql.cost_budgets entry ({smallest}); some accounts could never run the largest allowed query",
+                graphql.max_complexity
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_dev_tools(&self) -> Result<(), ConfigError> {
+        for cidr in &self.dev_tools.allowed_cidrs {
+            if cidr.parse::<crate::http::dev_tools::IpCidr>().is_err() {
+                return Err(ConfigError::Validation(format!(
+                    "dev_tools.allowed_cidrs contains invalid range '{cidr}'. Use ADDRESS/PREFIX, e.g. 10.0.0.0/8"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_auth(&self) -> Result<(), ConfigError> {
+        let skew = self.auth.max_clock_skew_secs;
+        if !MAX_CLOCK_SKEW_SECS_RANGE.contains(&skew) {
+            return Err(ConfigError::Validation(format!(
+                "auth.max_clock_skew_secs must be between {} and {}, got {skew}",
+                MAX_CLOCK_SKEW_SECS_RANGE.start(),
+                MAX_CLOCK_SKEW_SECS_RANGE.end()
+            )));
+        }
+        let ttl = self.auth.nonce_ttl_secs;
+        if ttl < 2 * skew {
+            return Err(ConfigError::Validation(format!(
+                "auth.nonce_ttl_secs ({ttl}) must be at least twice auth.max_clock_skew_secs ({skew}) or replays become possible"
+            )));
+        }
+        if ttl > MAX_NONCE_TTL_SECS {
+            return Err(ConfigError::Validation(format!(
+                "auth.nonce_ttl_secs cannot exceed {MAX_NONCE_TTL_SECS}"
+            )));
+        }
+        if self.auth.failure_threshold > 0
+            && (self.auth.failure_window_secs == 0 || self.auth.failure_block_secs == 0)
+        {
+            return Err(ConfigError::Validation(
+                "auth.failure_window_secs and auth.failure_block_secs cannot be 0 while auth.failure_threshold is set".into(),
+            ));
+        }
+        if self.auth.session_ttl_secs > MAX_SESSION_TTL_SECS {
+            return Err(ConfigError::Validation(format!(
+                "auth.session_ttl_secs cannot exceed {MAX_SESSION_TTL_SECS}"
+            )));
+        }
+        if self.auth.challenge_ttl_secs > MAX_CHALLENGE_TTL_SECS {
+            return Err(ConfigError::Validation(format!(
+                "auth.challenge_ttl_secs cannot exceed {MAX_CHALLENGE_TTL_SECS}"
+            )));
+        }
+        if self.auth.failure_block_max_secs < self.auth.failure_block_secs {
+            return Err(ConfigError::Validation(
+                "auth.failure_block_max_secs cannot be less than auth.failure_block_secs".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_error_reporting(&self) -> Result<(), ConfigError> {
+        if self.error_reporting.enabled {
+            crate::error_reporting::SentryDsn::parse(&self.error_reporting.dsn).map_err(|e| {
+                ConfigError::Validation(format!("error_reporting.dsn is invalid: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn validate_instance(&self) -> Result<(), ConfigError> {
+        if self.instance.name.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "instance.name cannot be empty".into(),
+            ));
+        }
+        if !self.instance.web_url.is_empty() {
+            let valid = reqwest::Url::parse(&self.instance.web_url)
+                .is_ok_and(|url| matches!(url.scheme(), "https" | "http") && url.has_host());
+            if !valid {
+                return Err(ConfigError::Validation(
+                    "instance.web_url must be an absolute http(s) URL".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_api_tokens(&self) -> Result<(), ConfigError> {
+        if self.api_tokens.usage_flush_secs == 0 {
+            return Err(ConfigError::Validation(
+                "api_tokens.usage_flush_secs must be greater than 0".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_privacy(&self) -> Result<(), ConfigError> {
+        let epsilon = self.privacy.epsilon;
+        if !epsilon.is_finite() || epsilon <= 0.0 {
+            return Err(ConfigError::Validation(format!(
+                "privacy.epsilon must be a positive number, got {epsilon}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_reputation(&self) -> Result<(), ConfigError> {
+        let r = &self.reputation;
+        let thresholds = [r.established_score, r.trusted_score, r.exemplary_score];
+        if thresholds.iter().any(|t| !t.is_finite() || *t <= 0.0)
+            || thresholds.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(ConfigError::Validation(format!(
+                "reputation thresholds must be positive and increasing, got \
+                 established={}, trusted={}, exemplary={}",
+                r.established_score, r.trusted_score, r.exemplary_score
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_verification(&self) -> Result<(), ConfigError> {
+        let v = &self.verification;
+        if v.sender == VerificationSenderKind::Http {
+            let valid = reqwest::Url::parse(&v.sender_url)
+                .is_ok_and(|url| matches!(url.scheme(), "https" | "http") && url.has_host());
+            if !valid {
+                return Err(ConfigError::Validation(
+                    "verification.sender_url must be an absolute http(s) URL when sender is http"
+                        .into(),
+                ));
+            }
+        }
+        if v.code_ttl_secs == 0
+            || v.max_code_attempts == 0
+            || v.in_person_attestations_required == 0
+        {
+            return Err(ConfigError::Validation(
+                "verification.code_ttl_secs, max_code_attempts and \
+                 in_person_attestations_required must be greater than 0"
+                    .into(),
+            ));
+        }
+        if !v.idv_webhook_secret.is_empty()
+            && v.idv_webhook_secret.len() < MIN_IDV_WEBHOOK_SECRET_LEN
+        {
+            return Err(ConfigError::Validation(format!(
+                "verification.idv_webhook_secret must be at least \
+                 {MIN_IDV_WEBHOOK_SECRET_LEN} bytes"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_sms(&self) -> Result<(), ConfigError> {
+        let sms = &self.sms;
+        let missing = match sms.provider {
+            SmsProviderKind::Disabled | SmsProviderKind::Mock => None,
+            SmsProviderKind::Twilio => [
+                ("twilio_account_sid", &sms.twilio_account_sid),
+                ("twilio_auth_token", &sms.twilio_auth_token),
+                ("twilio_from", &sms.twilio_from),
+            ]
+            .into_iter()
+            .find(|(_, value)| value.is_empty()),
+            SmsProviderKind::Sns => [
+                ("sns_region", &sms.sns_region),
+                ("sns_access_key_id", &sms.sns_access_key_id),
+                ("sns_secret_access_key", &sms.sns_secret_access_key),
+            ]
+            .into_iter()
+            .find(|(_, value)| value.is_empty()),
+        };
+        if let Some((field, _)) = missing {
+            return Err(ConfigError::Validation(format!(
+                "sms.{field} is required for the configured provider"
+            )));
+        }
+        let step_up = &self.step_up;
+        let ttls = [step_up.code_ttl_secs, step_up.confirmation_ttl_secs];
+        if ttls.iter().any(|&t| t == 0 || t > MAX_STEP_UP_TTL_SECS) {
+            return Err(ConfigError::Validation(format!(
+                "step_up.code_ttl_secs and confirmation_ttl_secs must be between 1 and \
+                 {MAX_STEP_UP_TTL_SECS}"
+            )));
+        }
+        if step_up.max_code_attempts == 0 {
+            return Err(ConfigError::Validation(
+                "step_up.max_code_attempts must be greater than 0".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_research(&self) -> Result<(), ConfigError> {
+        let research = &self.research;
+        if research.k_anonymity < MIN_RESEARCH_K_ANONYMITY {
+            return Err(ConfigError::Validation(format!(
+                "research.k_anonymity must be at least {MIN_RESEARCH_K_ANONYMITY}, got {}",
+                research.k_anonymity
+            )));
+        }
+        if research.downloads_per_export == 0 || research.approval_ttl_days == 0 {
+            return Err(ConfigError::Validation(
+                "research.downloads_per_export and research.approval_ttl_days must be greater than 0"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_signup(&self) -> Result<(), ConfigError> {
+        let ttl = self.signup.invite_ttl_days;
+        if ttl == 0 || ttl > MAX_INVITE_TTL_DAYS {
+            return Err(ConfigError::Validation(format!(
+                "signup.invite_ttl_days must be between 1 and {MAX_INVITE_TTL_DAYS}, got {ttl}"
+            )));
         }
+        Ok(())
+    }
 
-        // Port must be non-zero
-        if self.server.port == 0 {
-            return Err(ConfigError::Validation("server.port cannot be 0".into()));
+    fn validate_feed(&self) -> Result<(), ConfigError> {
+        if self.feed.precompute_max_age_secs == 0 || self.feed.precompute_interval_secs == 0 {
+            return Err(ConfigError::Validation(
+                "feed.precompute_max_age_secs and precompute_interval_secs cannot be 0".into(),
+            ));
         }
+        Ok(())
+    }
 
-        // Max connections must be at least 1
-        if self.database.max_connections == 0 {
+    fn validate_account_deletion(&self) -> Result<(), ConfigError> {
+        if self.account_deletion.grace_days == 0 || self.account_deletion.sweep_interval_secs == 0 {
             return Err(ConfigError::Validation(
-                "database.max_connections cannot be 0".into(),
+                "account_deletion.grace_days and sweep_interval_secs cannot be 0".into(),
             ));
         }
+        Ok(())
+    }
 
-        // CORS origins must be valid URLs or "*"
-        for origin in &self.cors.allowed_origins {
-            if origin != "*" && !origin.starts_with("http://") && !origin.starts_with("https://") {
-                return Err(ConfigError::Validation(format!(
-                    "cors.allowed_origins contains invalid origin '{origin}'. Must be '*' or start with http:// or https://"
-                )));
-            }
+    fn validate_integrity(&self) -> Result<(), ConfigError> {
+        if self.integrity.sweep_interval_secs == 0 || self.integrity.recent_days == 0 {
+            return Err(ConfigError::Validation(
+                "integrity.sweep_interval_secs and recent_days cannot be 0".into(),
+            ));
         }
+        Ok(())
+    }
 
-        // X-Frame-Options must be DENY or SAMEORIGIN
-        let frame_opts = self.security_headers.frame_options.to_uppercase();
-        if frame_opts != "DENY" && frame_opts != "SAMEORIGIN" {
+    fn validate_analytics_export(&self) -> Result<(), ConfigError> {
+        let export = &self.analytics_export;
+        let missing = match export.sink {
+            AnalyticsSinkKind::Disabled => return Ok(()),
+            AnalyticsSinkKind::Local => std::iter::once(("local_dir", &export.local_dir))
+                .find(|(_, value)| value.is_empty()),
+            AnalyticsSinkKind::S3 => [
+                ("s3_bucket", &export.s3_bucket),
+                ("s3_region", &export.s3_region),
+                ("s3_access_key_id", &export.s3_access_key_id),
+                ("s3_secret_access_key", &export.s3_secret_access_key),
+            ]
+            .into_iter()
+            .find(|(_, value)| value.is_empty()),
+        };
+        if let Some((field, _)) = missing {
             return Err(ConfigError::Validation(format!(
-                "security_headers.frame_options must be 'DENY' or 'SAMEORIGIN', got: '{}'",
-                self.security_headers.frame_options
+                "analytics_export.{field} is required for the configured sink"
             )));
         }
-
-        // Synthetic backup HMAC key is required and must be at least 32 bytes
-        if self.synthetic_backup_key.is_empty() {
+        if export.interval_secs == 0 || export.batch_rows == 0 {
             return Err(ConfigError::Validation(
-                "synthetic_backup_key is required. Set TC_SYNTHETIC_BACKUP_KEY environment variable or configure in config.yaml.".into(),
+                "analytics_export.interval_secs and batch_rows cannot be 0".into(),
             ));
         }
-        if self.synthetic_backup_key.len() < 32 {
+        let prefix_ok = export.prefix.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment.bytes().all(|b| {
+                    b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'-' | b'_' | b'.')
+                })
+        });
+        if !prefix_ok {
             return Err(ConfigError::Validation(
-                "TC_SYNTHETIC_BACKUP_KEY must be at least 32 bytes".into(),
+                "analytics_export.prefix must be `/`-separated segments of lowercase letters, \
+                 digits, `-`, `_` and `.`"
+                    .into(),
+            ));
+        }
+        if !export.s3_endpoint.is_empty() && reqwest::Url::parse(&export.s3_endpoint).is_err() {
+            return Err(ConfigError::Validation(
+                "analytics_export.s3_endpoint must be a URL".into(),
             ));
         }
+        Ok(())
+    }
 
-        // If IdMe is configured, validate all required fields
-        if let Some(ref idme) = self.idme {
-            if idme.client_id.is_empty() {
-                return Err(ConfigError::Validation(
-                    "idme.client_id is required when IdMe is enabled. Set TC_IDME__CLIENT_ID."
-                        .into(),
-                ));
-            }
-            if idme.client_secret.is_empty() {
-                return Err(ConfigError::Validation(
-                    "idme.client_secret is required when IdMe is enabled. Set TC_IDME__CLIENT_SECRET.".into(),
-                ));
-            }
-            if idme.redirect_uri.is_empty() {
-                return Err(ConfigError::Validation(
-                    "idme.redirect_uri is required when IdMe is enabled. Set TC_IDME__REDIRECT_URI.".into(),
-                ));
-            }
-            if idme.frontend_callback_url.is_empty() {
-                return Err(ConfigError::Validation(
-                    "idme.frontend_callback_url is required when IdMe is enabled. Set TC_IDME__FRONTEND_CALLBACK_URL.".into(),
-                ));
-            }
-            if idme.state_secret.len() < 32 {
-                return Err(ConfigError::Validation(
-                    "idme.state_secret must be at least 32 bytes. Set TC_IDME__STATE_SECRET."
-                        .into(),
-                ));
+    fn validate_retention(&self) -> Result<(), ConfigError> {
+        if self.retention.interval_secs == 0 {
+            return Err(ConfigError::Validation(
+                "retention.interval_secs cannot be 0".into(),
+            ));
+        }
+        for (name, days) in [
+            ("action_log_days", self.retention.action_log_days),
+            ("bot_trace_days", self.retention.bot_trace_days),
+            ("expired_invite_days", self.retention.expired_invite_days),
+            ("notification_days", self.retention.notification_days),
+        ] {
+            if days == Some(0) {
+                return Err(ConfigError::Validation(format!(
+                    "retention.{name} cannot be 0 (unset it to keep rows forever)"
+                )));
             }
         }
 
@@ -669,6 +2899,8 @@ mod tests {
         assert_eq!(config.rate_limit.signup_per_minute, 5);
         assert_eq!(config.rate_limit.login_per_minute, 10);
         assert_eq!(config.rate_limit.backup_per_minute, 10);
+        assert_eq!(config.rate_limit.badge_per_minute, 60);
+        assert_eq!(config.rate_limit.verify_per_minute, 30);
         assert!(config.rate_limit.enabled);
     }
 
@@ -689,6 +2921,11 @@ mod tests {
             max_connections: 10,
             migrations_dir: None,
             auto_reset_on_migration_failure: false,
+            startup_deadline_secs: default_db_startup_deadline_secs(),
+            wait_for_pgmq: false,
+            explain_queries: false,
+            reader_user: None,
+            reader_password: None,
         };
         let opts = config.connect_options();
         // PgConnectOptions exposes getters for host, port, and database
@@ -709,6 +2946,11 @@ mod tests {
             max_connections: 10,
             migrations_dir: None,
             auto_reset_on_migration_failure: false,
+            startup_deadline_secs: default_db_startup_deadline_secs(),
+            wait_for_pgmq: false,
+            explain_queries: false,
+            reader_user: None,
+            reader_password: None,
         };
         let opts = config.connect_options();
         // PgConnectOptions handles special chars without URL encoding issues.
@@ -740,6 +2982,20 @@ mod tests {
             .contains("database.password"));
     }
 
+    #[test]
+    fn test_validation_requires_complete_distinct_reader_credentials() {
+        let mut config = valid_config();
+        config.database.reader_user = Some("tc_api_reader".into());
+        assert!(config.validate().is_err());
+        config.database.reader_password = Some("reader-secret".into());
+        assert!(config.validate().is_ok());
+        config.database.reader_user = Some(config.database.user.clone());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("must differ"), "{err}");
+        config.database.reader_user = None;
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_validation_rejects_zero_database_port() {
         let mut config = valid_config();
@@ -781,6 +3037,23 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("invalid origin"));
     }
 
+    #[test]
+    fn test_cors_group_origins_validation() {
+        let mut config = valid_config();
+        config.cors.public_origins = vec!["*".into()];
+        config.cors.auth_origins = vec!["https://app.example.com".into()];
+        assert!(config.validate().is_ok());
+
+        config.cors.public_origins = vec!["ftp://x".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("cors.public_origins"));
+
+        config.cors.public_origins.clear();
+        config.cors.auth_origins = vec!["*".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("cors.auth_origins"));
+    }
+
     #[test]
     fn test_cors_deserialize_comma_separated_string() {
         // Simulate what figment does with env var
@@ -807,6 +3080,75 @@ mod tests {
         assert!(config.allowed_origins.is_empty());
     }
 
+    #[test]
+    fn test_compression_enabled_by_default() {
+        let config = CompressionConfig::default();
+        assert!(config.enabled && config.gzip && config.br);
+        assert_eq!(config.min_size_bytes, 1024);
+
+        let config: CompressionConfig =
+            serde_json::from_str(r#"{"br": false}"#).expect("should parse");
+        assert!(config.enabled && config.gzip && !config.br);
+    }
+
+    #[test]
+    fn test_concurrency_limits_on_by_default() {
+        let config = ConcurrencyConfig::default();
+        assert!(config.enabled);
+        assert_eq!((config.expensive_limit, config.default_limit), (8, 256));
+        assert!(config
+            .expensive_routes
+            .contains(&"/research/exports".to_string()));
+
+        let config: ConcurrencyConfig =
+            serde_json::from_str(r#"{"expensive_routes": "/a,/b/{id}/c"}"#).expect("should parse");
+        assert_eq!(config.expensive_routes, ["/a", "/b/{id}/c"]);
+
+        let mut config = valid_config();
+        config.concurrency.expensive_routes = vec!["research/exports".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("concurrency.expensive_routes"));
+    }
+
+    #[test]
+    fn test_read_only_mode_validation() {
+        let config = ReadOnlyConfig::default();
+        assert!(config.enabled);
+        assert_eq!(
+            (config.failure_threshold, config.recovery_threshold),
+            (3, 2)
+        );
+
+        let mut config = valid_config();
+        config.read_only.probe_interval_secs = 0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("read_only.probe_interval_secs"));
+
+        let mut config = valid_config();
+        config.read_only.recovery_threshold = 0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("read_only.recovery_threshold"));
+    }
+
+    #[test]
+    fn test_username_policy_validation() {
+        let config: UsernameConfig =
+            serde_json::from_str(r#"{"pack": "ascii_dots"}"#).expect("should parse");
+        assert_eq!(config.pack, UsernamePack::AsciiDots);
+        assert_eq!((config.min_length, config.max_length), (3, 64));
+
+        let mut config = valid_config();
+        config.usernames.min_length = 10;
+        config.usernames.max_length = 5;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("usernames.min_length"));
+
+        let mut config = valid_config();
+        config.usernames.max_length = 1000;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("usernames.max_length"));
+    }
+
     #[test]
     fn test_graphql_playground_disabled_by_default() {
         let config = GraphQLConfig::default();
@@ -818,6 +3160,19 @@ mod tests {
         let json = r#"{"playground_enabled": true}"#;
         let config: GraphQLConfig = serde_json::from_str(json).expect("should parse");
         assert!(config.playground_enabled);
+        assert_eq!(config.max_complexity, 200);
+        assert!(config.cost_budgets.enabled);
+    }
+
+    #[test]
+    fn test_graphql_complexity_must_fit_smallest_budget() {
+        let mut config = valid_config();
+        config.graphql.cost_budgets.new = 100;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("graphql.max_complexity"));
+
+        config.graphql.cost_budgets.enabled = false;
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -833,6 +3188,38 @@ mod tests {
         assert!(config.enabled);
     }
 
+    #[test]
+    fn test_dev_tools_lists_parse_from_comma_separated() {
+        let json = r#"{"allowed_cidrs": "10.0.0.0/8,::1"}"#;
+        let config: DevToolsConfig = serde_json::from_str(json).expect("should parse");
+        assert_eq!(config.allowed_cidrs, vec!["10.0.0.0/8", "::1"]);
+        assert!(!config.allow_admins);
+        assert!(!config.trust_forwarded_for);
+    }
+
+    #[test]
+    fn test_admin_account_ids_parse_from_comma_separated() {
+        let json = r#"{"admin_account_ids": "6f1c2a3e-0000-4000-8000-000000000001, 6f1c2a3e-0000-4000-8000-000000000002"}"#;
+        let config: AccountsConfig = serde_json::from_str(json).expect("should parse");
+        assert_eq!(config.admin_account_ids.len(), 2);
+        assert!(config.is_admin(
+            "6f1c2a3e-0000-4000-8000-000000000002"
+                .parse()
+                .expect("uuid")
+        ));
+
+        let json = r#"{"admin_account_ids": "alice"}"#;
+        assert!(serde_json::from_str::<AccountsConfig>(json).is_err());
+    }
+
+    #[test]
+    fn test_dev_tools_rejects_invalid_cidr() {
+        let mut config = valid_config();
+        config.dev_tools.allowed_cidrs = vec!["10.0.0.0/33".into()];
+        let err = config.validate().expect_err("should reject");
+        assert!(err.to_string().contains("dev_tools.allowed_cidrs"));
+    }
+
     // Table-driven boundary tests for validation rules
 
     #[test]
@@ -1008,6 +3395,11 @@ mod tests {
             max_connections: 10,
             migrations_dir: None,
             auto_reset_on_migration_failure: false,
+            startup_deadline_secs: default_db_startup_deadline_secs(),
+            wait_for_pgmq: false,
+            explain_queries: false,
+            reader_user: None,
+            reader_password: None,
         };
         let debug = format!("{config:?}");
         assert!(
@@ -1045,4 +3437,319 @@ mod tests {
             "non-secret client_id must still appear"
         );
     }
+
+    #[test]
+    fn test_events_disabled_by_default() {
+        let config = Config::default();
+        assert!(!config.events.enabled);
+        assert_eq!(config.events.subject_prefix, "tc.events");
+        assert!(valid_config().validate().is_ok());
+    }
+
+    #[test]
+    fn test_events_enabled_requires_nats_url() {
+        let mut config = valid_config();
+        config.events.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("events.nats_url"));
+
+        config.events.nats_url = "nats://localhost:4222".into();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_retention_defaults_keep_everything() {
+        let config = Config::default();
+        assert!(!config.retention.dry_run);
+        assert_eq!(config.retention.interval_secs, 60);
+        assert!(config.retention.action_log_days.is_none());
+        assert!(config.retention.bot_trace_days.is_none());
+        assert!(config.retention.expired_invite_days.is_none());
+        assert!(config.retention.notification_days.is_none());
+    }
+
+    #[test]
+    fn test_retention_rejects_zero_days() {
+        let mut config = valid_config();
+        config.retention.action_log_days = Some(0);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("retention.action_log_days"));
+
+        config.retention.action_log_days = Some(90);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_auth_defaults_cover_replay_window() {
+        let auth = Config::default().auth;
+        assert_eq!(auth.max_clock_skew_secs, 300);
+        assert_eq!(auth.nonce_ttl_secs, 600);
+        assert!(valid_config().validate().is_ok());
+    }
+
+    #[test]
+    fn test_auth_rejects_out_of_range_skew() {
+        let mut config = valid_config();
+        config.auth.max_clock_skew_secs = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.max_clock_skew_secs"));
+
+        config.auth.max_clock_skew_secs = 7200;
+        config.auth.nonce_ttl_secs = 14_400;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.max_clock_skew_secs"));
+
+        config.auth.max_clock_skew_secs = 30;
+        config.auth.nonce_ttl_secs = 60;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_auth_nonce_ttl_must_outlive_window() {
+        let mut config = valid_config();
+        config.auth.max_clock_skew_secs = 30;
+        config.auth.nonce_ttl_secs = 59;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.nonce_ttl_secs"));
+
+        config.auth.nonce_ttl_secs = MAX_NONCE_TTL_SECS + 1;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.nonce_ttl_secs"));
+    }
+
+    #[test]
+    fn test_auth_session_ttl_is_bounded() {
+        let mut config = valid_config();
+        assert_eq!(config.auth.session_ttl_secs, 900);
+        config.auth.session_ttl_secs = MAX_SESSION_TTL_SECS + 1;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.session_ttl_secs"));
+
+        config.auth.session_ttl_secs = 0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_auth_challenge_ttl_is_bounded() {
+        let mut config = valid_config();
+        assert_eq!(config.auth.challenge_ttl_secs, 60);
+        config.auth.challenge_ttl_secs = MAX_CHALLENGE_TTL_SECS + 1;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.challenge_ttl_secs"));
+
+        config.auth.challenge_ttl_secs = 0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_auth_failure_block_bounds() {
+        let mut config = valid_config();
+        config.auth.failure_block_secs = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.failure_block_secs"));
+
+        config.auth.failure_threshold = 0;
+        assert!(config.validate().is_ok());
+
+        config.auth.failure_block_secs = 60;
+        config.auth.failure_block_max_secs = 30;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.failure_block_max_secs"));
+    }
+
+    #[test]
+    fn test_auth_timestamp_is_stale() {
+        let auth = AuthConfig {
+            max_clock_skew_secs: 30,
+            nonce_ttl_secs: 60,
+            ..AuthConfig::default()
+        };
+        let now = 1_700_000_000;
+        assert!(!auth.timestamp_is_stale(now, now - 30));
+        assert!(!auth.timestamp_is_stale(now, now + 30));
+        assert!(auth.timestamp_is_stale(now, now - 31));
+        assert!(auth.timestamp_is_stale(now, now + 31));
+        assert!(auth.timestamp_is_stale(now, i64::MIN));
+        assert!(auth.timestamp_is_stale(now, i64::MAX));
+    }
+
+    #[test]
+    fn test_error_reporting_requires_valid_dsn_when_enabled() {
+        let mut config = valid_config();
+        config.error_reporting.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("error_reporting.dsn"));
+
+        config.error_reporting.dsn = "https://key@sentry.example.com/3".into();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_instance_name_required() {
+        let mut config = valid_config();
+        assert_eq!(config.instance.name, "TinyCongress");
+        config.instance.name = "  ".into();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("instance.name"));
+
+        config.instance.name = "TinyCongress".into();
+        config.instance.web_url = "tinycongress.example".into();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("instance.web_url"));
+        config.instance.web_url = "https://tinycongress.example".into();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_signup_defaults_and_ttl_bounds() {
+        let mut config = valid_config();
+        assert!(!config.signup.invite_only);
+        assert_eq!(config.signup.invites_per_account, 5);
+        config.signup.invite_ttl_days = 0;
+        assert!(config.validate().is_err());
+        config.signup.invite_ttl_days = MAX_INVITE_TTL_DAYS + 1;
+        assert!(config.validate().is_err());
+        config.signup.invite_ttl_days = MAX_INVITE_TTL_DAYS;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_feed_defaults_and_bounds() {
+        let mut config = valid_config();
+        assert_eq!(config.feed.precompute_min_reach, 200);
+        assert_eq!(config.feed.precompute_max_age_secs, 300);
+        config.feed.precompute_interval_secs = 0;
+        assert!(config.validate().is_err());
+        config.feed.precompute_interval_secs = 60;
+        config.feed.precompute_min_reach = 0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_account_deletion_defaults_and_bounds() {
+        let mut config = valid_config();
+        assert_eq!(config.account_deletion.grace_days, 30);
+        assert_eq!(config.account_deletion.sweep_interval_secs, 3600);
+        config.account_deletion.grace_days = 0;
+        assert!(config.validate().is_err());
+        config.account_deletion.grace_days = 1;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_integrity_defaults_and_bounds() {
+        let mut config = valid_config();
+        assert!(config.integrity.enabled);
+        assert_eq!(config.integrity.sweep_interval_secs, 86_400);
+        assert_eq!(config.integrity.recent_days, 7);
+        config.integrity.recent_days = 0;
+        assert!(config.validate().is_err());
+        config.integrity.recent_days = 1;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_analytics_export_sinks_require_their_settings() {
+        let mut config = valid_config();
+        assert_eq!(config.analytics_export.sink, AnalyticsSinkKind::Disabled);
+        assert_eq!(config.analytics_export.interval_secs, 86_400);
+
+        config.analytics_export.sink = AnalyticsSinkKind::Local;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("analytics_export.local_dir"));
+        config.analytics_export.local_dir = "/var/lib/tc/analytics".into();
+        assert!(config.validate().is_ok());
+
+        config.analytics_export.sink = AnalyticsSinkKind::S3;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("analytics_export.s3_bucket"));
+        config.analytics_export.s3_bucket = "tc-analytics".into();
+        config.analytics_export.s3_region = "us-east-1".into();
+        config.analytics_export.s3_access_key_id = "AKIDEXAMPLE".into();
+        config.analytics_export.s3_secret_access_key = "secret".into();
+        assert!(config.validate().is_ok());
+
+        for prefix in ["", "a//b", "../up", "Upper", "with space"] {
+            config.analytics_export.prefix = prefix.into();
+            assert!(config.validate().is_err(), "{prefix:?}");
+        }
+        config.analytics_export.prefix = "prod/tc-1.0".into();
+        config.analytics_export.s3_endpoint = "not a url".into();
+        assert!(config.validate().is_err());
+        config.analytics_export.s3_endpoint = "http://localhost:9000".into();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_research_defaults_and_bounds() {
+        let mut config = valid_config();
+        assert!(config.research.researcher_account_ids.is_empty());
+        assert_eq!(config.research.k_anonymity, 10);
+        config.research.k_anonymity = 1;
+        assert!(config.validate().is_err());
+        config.research.k_anonymity = MIN_RESEARCH_K_ANONYMITY;
+        config.research.downloads_per_export = 0;
+        assert!(config.validate().is_err());
+        config.research.downloads_per_export = 1;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_privacy_defaults_and_epsilon_bounds() {
+        let mut config = valid_config();
+        assert!(!config.privacy.enabled);
+        assert_eq!(config.privacy.min_count, 5);
+        config.privacy.epsilon = 0.0;
+        assert!(config.validate().is_err());
+        config.privacy.epsilon = f64::NAN;
+        assert!(config.validate().is_err());
+        config.privacy.epsilon = 0.5;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_reputation_thresholds_must_increase() {
+        let mut config = valid_config();
+        assert!(config.validate().is_ok());
+        config.reputation.trusted_score = config.reputation.exemplary_score;
+        assert!(config.validate().is_err());
+        config.reputation.trusted_score = 60.0;
+        config.reputation.established_score = 0.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_verification_defaults_and_validation() {
+        let mut config = valid_config();
+        assert_eq!(config.verification.sender, VerificationSenderKind::Disabled);
+        assert_eq!(config.verification.in_person_attestations_required, 3);
+        config.verification.sender = VerificationSenderKind::Http;
+        assert!(config.validate().is_err());
+        config.verification.sender_url = "https://sms.example.com/send".to_string();
+        assert!(config.validate().is_ok());
+        config.verification.idv_webhook_secret = "short".to_string();
+        assert!(config.validate().is_err());
+        config.verification.idv_webhook_secret = "x".repeat(MIN_IDV_WEBHOOK_SECRET_LEN);
+        assert!(config.validate().is_ok());
+        config.verification.in_person_attestations_required = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_sms_and_step_up_validation() {
+        let mut config = valid_config();
+        assert_eq!(config.sms.provider, SmsProviderKind::Disabled);
+        assert_eq!(config.step_up.new_device_hours, 72);
+        config.sms.provider = SmsProviderKind::Twilio;
+        config.sms.twilio_account_sid = "AC123".to_string();
+        config.sms.twilio_auth_token = "token".to_string();
+        assert!(config.validate().is_err());
+        config.sms.twilio_from = "+15550001111".to_string();
+        assert!(config.validate().is_ok());
+        config.sms.provider = SmsProviderKind::Sns;
+        assert!(config.validate().is_err());
+        config.sms.provider = SmsProviderKind::Mock;
+        config.step_up.confirmation_ttl_secs = MAX_STEP_UP_TTL_SECS + 1;
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,95 @@
+//! Shared shape of repository errors.
+//!
+//! Each repository keeps its own error enum so callers can match on the
+//! domain cases that matter to them (`DuplicateUsername`, `AlreadyRevoked`,
+//! ...). [`RepoError`] puts every one of those cases into a small set of
+//! [`RepoErrorKind`]s, so code that only needs the broad class — "was this
+//! a conflict or an outage?" — does not have to list every variant.
+//!
+//! [`map_unique_violation`] is the one place unique-constraint violations
+//! are recognised; repositories pass a closure naming the constraints they
+//! translate.
+
+/// Broad class of a repository failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoErrorKind {
+    /// The addressed row does not exist (or is not visible to the caller).
+    NotFound,
+    /// The write collides with existing state: a duplicate key or a row
+    /// already in the target state.
+    Conflict,
+    /// A per-account or per-day quota is exhausted.
+    LimitReached,
+    /// The request is well-formed but not allowed in the row's current state.
+    Rejected,
+    /// The database failed; not the caller's fault.
+    Database,
+}
+
+impl RepoErrorKind {
+    /// Label for logs and metrics.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::Conflict => "conflict",
+            Self::LimitReached => "limit_reached",
+            Self::Rejected => "rejected",
+            Self::Database => "database",
+        }
+    }
+}
+
+/// Implemented by every `*RepoError` enum.
+pub trait RepoError: std::error::Error {
+    /// Which broad class this error falls into.
+    fn kind(&self) -> RepoErrorKind;
+}
+
+/// Name of the unique constraint `e` violated, if it is a unique violation.
+#[must_use]
+pub fn unique_violation(e: &sqlx::Error) -> Option<&str> {
+    match e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => db_err.constraint(),
+        _ => None,
+    }
+}
+
+/// Translate a unique violation into a domain error.
+///
+/// `on_constraint` is given the violated constraint's name. Any other error,
+/// or a constraint the closure does not recognise, becomes the repository's
+/// database variant.
+pub fn map_unique_violation<E>(e: sqlx::Error, on_constraint: impl FnOnce(&str) -> Option<E>) -> E
+where
+    E: From<sqlx::Error>,
+{
+    unique_violation(&e)
+        .and_then(on_constraint)
+        .unwrap_or_else(|| E::from(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Duplicate,
+        Database,
+    }
+
+    impl From<sqlx::Error> for TestError {
+        fn from(_: sqlx::Error) -> Self {
+            Self::Database
+        }
+    }
+
+    #[test]
+    fn non_database_errors_are_not_unique_violations() {
+        assert_eq!(unique_violation(&sqlx::Error::RowNotFound), None);
+        let mapped =
+            map_unique_violation(sqlx::Error::PoolTimedOut, |_| Some(TestError::Duplicate));
+        assert_eq!(mapped, TestError::Database);
+    }
+}
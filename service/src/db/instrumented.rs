@@ -0,0 +1,281 @@
+//! Per-query timing, row counts, and optional query plans for sqlx calls.
+//!
+//! [`Instrumented`] wraps any Postgres executor and tags it with a stable
+//! query name. Run a query through it instead of the bare pool or connection:
+//!
+//! ```ignore
+//! sqlx::query("SELECT ...")
+//!     .bind(id)
+//!     .fetch_optional(instrument(&pool, "device_keys.by_kid"))
+//!     .await?;
+//! ```
+//!
+//! Every query records, labelled by name:
+//! - `tc_db_query_duration_seconds` — time until the last row (or the caller
+//!   dropped the result)
+//! - `tc_db_query_rows_total` — rows returned plus rows affected
+//! - `tc_db_query_errors_total` — queries that returned an error
+//!
+//! With `database.explain_queries` on, pool-backed queries first run under
+//! `EXPLAIN (ANALYZE, BUFFERS)` in a transaction that is rolled back, and the
+//! plan is logged. `ANALYZE` really executes the statement, so this doubles
+//! the work of every instrumented query and can wait on row locks; side
+//! effects outside the transaction (sequences, advisory session locks) are
+//! not undone. It is meant for local profiling, not production.
+//!
+//! With the `sql-audit` feature, query text is also checked for spliced
+//! request input before it runs; see [`super::sql_audit`].
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{FutureExt, StreamExt};
+use sqlx::{Describe, Either, Execute, Executor, PgPool, Postgres};
+use sqlx_postgres::{PgQueryResult, PgRow, PgStatement, PgTypeInfo};
+
+static EXPLAIN_QUERIES: AtomicBool = AtomicBool::new(false);
+
+/// Turn plan logging for pool-backed instrumented queries on or off.
+///
+/// Set once at startup from `database.explain_queries`.
+pub fn set_explain_queries(enabled: bool) {
+    if enabled {
+        tracing::warn!("database.explain_queries is on: instrumented queries run twice");
+    }
+    EXPLAIN_QUERIES.store(enabled, Ordering::Relaxed);
+}
+
+/// Executor that records metrics for the queries run through it.
+pub struct Instrumented<E> {
+    inner: E,
+    name: &'static str,
+    /// Pool to run `EXPLAIN ANALYZE` on; only set for pool-backed executors
+    /// while plan logging is enabled.
+    explain: Option<PgPool>,
+}
+
+impl<E> Instrumented<E> {
+    /// Wrap a connection or transaction. Plans are never logged for these,
+    /// since `EXPLAIN ANALYZE` would run inside the caller's transaction.
+    pub const fn new(inner: E, name: &'static str) -> Self {
+        Self {
+            inner,
+            name,
+            explain: None,
+        }
+    }
+}
+
+/// Wrap a pool, logging query plans when `database.explain_queries` is on.
+#[must_use]
+pub fn instrument<'p>(pool: &'p PgPool, name: &'static str) -> Instrumented<&'p PgPool> {
+    Instrumented {
+        inner: pool,
+        name,
+        explain: EXPLAIN_QUERIES
+            .load(Ordering::Relaxed)
+            .then(|| pool.clone()),
+    }
+}
+
+impl<E> fmt::Debug for Instrumented<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instrumented")
+            .field("name", &self.name)
+            .field("explain", &self.explain.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Records one query's metrics when dropped, so abandoned streams and
+/// `fetch_optional` early returns are still counted.
+struct QueryTimer {
+    name: &'static str,
+    started: Instant,
+    rows: u64,
+    failed: bool,
+}
+
+impl QueryTimer {
+    fn start(name: &'static str) -> Self {
+        Self {
+            name,
+            started: Instant::now(),
+            rows: 0,
+            failed: false,
+        }
+    }
+
+    fn observe<T>(&mut self, step: &Result<Either<PgQueryResult, T>, sqlx::Error>) {
+        match step {
+            Ok(Either::Left(result)) => self.rows += result.rows_affected(),
+            Ok(Either::Right(_)) => self.rows += 1,
+            Err(_) => self.failed = true,
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        let name = self.name;
+        metrics::histogram!("tc_db_query_duration_seconds", "query" => name)
+            .record(self.started.elapsed().as_secs_f64());
+        metrics::counter!("tc_db_query_rows_total", "query" => name).increment(self.rows);
+        if self.failed {
+            metrics::counter!("tc_db_query_errors_total", "query" => name).increment(1);
+        }
+    }
+}
+
+/// Run `sql` under `EXPLAIN ANALYZE` in a rolled-back transaction and log the
+/// plan. Failures are logged and otherwise ignored.
+async fn log_plan(
+    pool: PgPool,
+    name: &'static str,
+    sql: &str,
+    arguments: sqlx_postgres::PgArguments,
+) {
+    let explain = format!("EXPLAIN (ANALYZE, BUFFERS, FORMAT TEXT) {sql}");
+    let plan = async {
+        let mut tx = pool.begin().await?;
+        let lines: Vec<String> = sqlx::query_scalar_with(&explain, arguments)
+            .fetch_all(&mut *tx)
+            .await?;
+        tx.rollback().await?;
+        Ok::<_, sqlx::Error>(lines.join("\n"))
+    };
+    match plan.await {
+        Ok(plan) => tracing::info!(query = name, "Query plan:\n{plan}"),
+        Err(e) => tracing::warn!(query = name, error = %e, "EXPLAIN ANALYZE failed"),
+    }
+}
+
+impl<'c, E> Executor<'c> for Instrumented<E>
+where
+    E: Executor<'c, Database = Postgres>,
+{
+    type Database = Postgres;
+
+    fn fetch_many<'e, 'q: 'e, Q>(
+        self,
+        mut query: Q,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, sqlx::Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, Postgres>,
+    {
+        #[cfg(feature = "sql-audit")]
+        if let Err(e) = super::sql_audit::check(self.name, query.sql()) {
+            return stream::once(async { Err(e) }).boxed();
+        }
+
+        let mut timer = QueryTimer::start(self.name);
+        let Some(pool) = self.explain else {
+            return self
+                .inner
+                .fetch_many(query)
+                .map(move |step| {
+                    timer.observe(&step);
+                    step
+                })
+                .boxed();
+        };
+
+        // The arguments are needed twice, so rebuild the query around a copy
+        let sql = query.sql();
+        let persistent = query.persistent();
+        let arguments = match query.take_arguments() {
+            Ok(Some(arguments)) => arguments,
+            Ok(None) => {
+                // Plain SQL without bind parameters; run it as is
+                return self
+                    .inner
+                    .fetch_many(query)
+                    .map(move |step| {
+                        timer.observe(&step);
+                        step
+                    })
+                    .boxed();
+            }
+            Err(e) => return stream::once(async { Err(sqlx::Error::Encode(e)) }).boxed(),
+        };
+        // Pool streams are lazy, so this does not start until the plan is in
+        let rows = self
+            .inner
+            .fetch_many(sqlx::query_with(sql, arguments.clone()).persistent(persistent));
+        let name = self.name;
+        stream::once(async move {
+            log_plan(pool, name, sql, arguments).await;
+            // Start timing after the plan so the metric reflects the real query
+            timer.started = Instant::now();
+            rows.map(move |step| {
+                timer.observe(&step);
+                step
+            })
+        })
+        .flatten()
+        .boxed()
+    }
+
+    fn fetch_optional<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<PgRow>, sqlx::Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, Postgres>,
+    {
+        // Reuse the plan-logging path; the stream stops at the first row
+        let mut rows = self.fetch_many(query);
+        async move {
+            while let Some(step) = rows.next().await {
+                if let Either::Right(row) = step? {
+                    return Ok(Some(row));
+                }
+            }
+            Ok(None)
+        }
+        .boxed()
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [PgTypeInfo],
+    ) -> BoxFuture<'e, Result<PgStatement<'q>, sqlx::Error>>
+    where
+        'c: 'e,
+    {
+        self.inner.prepare_with(sql, parameters)
+    }
+
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Postgres>, sqlx::Error>>
+    where
+        'c: 'e,
+    {
+        self.inner.describe(sql)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_counts_rows_and_errors() {
+        let mut timer = QueryTimer::start("test.query");
+        timer.observe::<()>(&Ok(Either::Right(())));
+        timer.observe::<()>(&Ok(Either::Right(())));
+        timer.observe::<()>(&Ok(Either::Left(PgQueryResult::default())));
+        assert_eq!(timer.rows, 2);
+        assert!(!timer.failed);
+        timer.observe::<()>(&Err(sqlx::Error::RowNotFound));
+        assert!(timer.failed);
+    }
+}
@@ -0,0 +1,352 @@
+pub mod error;
+pub mod instrumented;
+pub mod read_only;
+pub mod reader;
+pub mod schema_check;
+#[cfg(feature = "sql-audit")]
+pub mod sql_audit;
+
+pub use error::{map_unique_violation, unique_violation, RepoError, RepoErrorKind};
+pub use instrumented::{instrument, set_explain_queries, Instrumented};
+pub use reader::{connect_reader, verify_read_only};
+
+use crate::config::DatabaseConfig;
+use sqlx::Connection;
+use sqlx_core::migrate::{MigrateError, Migrator};
+use sqlx_postgres::{PgConnectOptions, PgConnection, PgPool, PgPoolOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+/// Connect to the database and run migrations.
+///
+/// Startup is ordered so a briefly unavailable database does not crash the
+/// process: the connection is retried with exponential backoff (500ms to 5s)
+/// until `startup_deadline_secs` (default 120s) elapses, and with
+/// `wait_for_pgmq` enabled the same deadline covers waiting for the `pgmq`
+/// extension to become installable. The default deadline is well within the
+/// Kubernetes startup probe budget (600s), so the process still crashes fast
+/// enough for K8s to restart the pod without Helm timing out.
+///
+/// Errors that retrying cannot fix — bad credentials, a missing database, TLS
+/// or configuration problems — fail immediately instead of burning the
+/// deadline (see [`is_transient_error`]).
+///
+/// When `auto_reset_on_migration_failure` is enabled and migrations fail due
+/// to a version mismatch (not an SQL execution error), the database is dropped
+/// and recreated, then migrations are retried exactly once.
+///
+/// # Errors
+/// Returns an error if a fatal error occurs, if the database (or pgmq) is not
+/// ready within the deadline, or if migrations fail after a successful
+/// connection.
+pub async fn setup_database(config: &DatabaseConfig) -> Result<PgPool, anyhow::Error> {
+    let deadline = Instant::now() + Duration::from_secs(config.startup_deadline_secs);
+    let pool = connect_with_retry(config, deadline).await?;
+    if config.wait_for_pgmq {
+        wait_for_pgmq(&pool, deadline).await?;
+    }
+    let migrator = resolve_migrator(config).await?;
+
+    match migrator.run(&pool).await {
+        Ok(()) => {
+            info!("Migrations applied");
+            Ok(pool)
+        }
+        Err(err) if config.auto_reset_on_migration_failure && is_resettable_error(&err) => {
+            warn!(
+                error = %err,
+                "Migration failed with resettable error; resetting database"
+            );
+            pool.close().await;
+            reset_database(config).await?;
+            let deadline = Instant::now() + Duration::from_secs(config.startup_deadline_secs);
+            let pool = connect_with_retry(config, deadline).await?;
+            migrator.run(&pool).await?;
+            info!("Migrations applied after database reset");
+            Ok(pool)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Connect to the database without running migrations.
+///
+/// Retries transient failures like [`setup_database`], within the same
+/// `startup_deadline_secs`. For tools that must observe the schema as it is,
+/// such as `tc-ops check-schema`.
+///
+/// # Errors
+/// Returns an error on a fatal connection error, or if the database is not
+/// reachable before the deadline.
+pub async fn connect(config: &DatabaseConfig) -> Result<PgPool, anyhow::Error> {
+    let deadline = Instant::now() + Duration::from_secs(config.startup_deadline_secs);
+    connect_with_retry(config, deadline).await
+}
+
+/// Returns true for database errors that are worth retrying at startup.
+///
+/// Transient errors come from infrastructure that is not ready yet: network
+/// failures, the server starting up or shutting down, or connection slots
+/// exhausted. Errors that would recur on every attempt, such as
+/// authentication failure or an unknown database, are fatal.
+///
+/// `sqlx::Error` is `#[non_exhaustive]`, so unknown variants are treated as
+/// fatal.
+#[must_use]
+pub fn is_transient_error(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(db_err) => db_err.code().is_some_and(|code| {
+            // 08xxx connection exceptions, 57P0x operator intervention
+            // (admin/crash shutdown, cannot connect now), 53xxx insufficient
+            // resources (e.g. too many connections).
+            code.starts_with("08") || code.starts_with("57P0") || code.starts_with("53")
+        }),
+        _ => false,
+    }
+}
+
+/// Run `attempt` until it yields a value, backing off exponentially while it
+/// returns `None` or a transient error, and giving up at `deadline`.
+async fn retry_until<T, F, Fut>(
+    what: &str,
+    deadline: Instant,
+    mut attempt: F,
+) -> Result<T, anyhow::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<Option<T>, sqlx::Error>>,
+{
+    let max_interval = Duration::from_secs(5);
+    let mut delay = Duration::from_millis(500);
+
+    loop {
+        let err = match attempt().await {
+            Ok(Some(value)) => return Ok(value),
+            Ok(None) => None,
+            Err(err) if is_transient_error(&err) => Some(err),
+            Err(err) => {
+                error!(error = %err, "{what}: fatal error, not retrying");
+                return Err(anyhow::Error::new(err).context(format!("{what}: fatal error")));
+            }
+        };
+
+        let reason = err
+            .as_ref()
+            .map_or_else(|| "not available".to_string(), ToString::to_string);
+        if Instant::now() >= deadline {
+            warn!(error = %reason, "{what}: startup deadline exceeded");
+            return Err(anyhow::anyhow!(
+                "{what}: not ready before startup deadline: {reason}"
+            ));
+        }
+        warn!(error = %reason, "{what}: not ready yet; retrying in {:?}", delay);
+        sleep(delay).await;
+        delay = (delay.saturating_mul(2)).min(max_interval);
+    }
+}
+
+/// Connect to Postgres, retrying transient failures until `deadline`.
+async fn connect_with_retry(
+    config: &DatabaseConfig,
+    deadline: Instant,
+) -> Result<PgPool, anyhow::Error> {
+    pool_with_retry(config.connect_options(), config.max_connections, deadline).await
+}
+
+/// Open a pool with `options`, retrying transient failures until `deadline`.
+async fn pool_with_retry(
+    options: PgConnectOptions,
+    max_connections: u32,
+    deadline: Instant,
+) -> Result<PgPool, anyhow::Error> {
+    retry_until("Postgres connection", deadline, || async {
+        info!("Attempting to connect to Postgres...");
+        PgPoolOptions::new()
+            .max_connections(max_connections)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_with(options.clone())
+            .await
+            .map(Some)
+    })
+    .await
+}
+
+/// Wait until the `pgmq` extension is available to `CREATE EXTENSION`.
+async fn wait_for_pgmq(pool: &PgPool, deadline: Instant) -> Result<(), anyhow::Error> {
+    retry_until("pgmq extension", deadline, || async {
+        let available: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM pg_available_extensions WHERE name = 'pgmq')",
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(available.then_some(()))
+    })
+    .await?;
+    info!("pgmq extension available");
+    Ok(())
+}
+
+/// Resolve the migrations directory, trying multiple candidate paths.
+///
+/// Preference order:
+///  1. `config.migrations_dir` (from config file or `TC_DATABASE__MIGRATIONS_DIR` env)
+///  2. `./migrations` relative to the running binary
+///  3. The compile-time manifest directory for local `cargo run`
+pub(crate) async fn resolve_migrator(config: &DatabaseConfig) -> Result<Migrator, anyhow::Error> {
+    let candidate_dirs = [
+        config.migrations_dir.as_ref().map(PathBuf::from),
+        Some(PathBuf::from("./migrations")),
+        Some(PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/migrations"
+        ))),
+    ];
+
+    let mut last_error = None;
+
+    for dir in candidate_dirs.into_iter().flatten() {
+        match Migrator::new(Path::new(&dir)).await {
+            Ok(found) => {
+                info!("Using migrations from {}", dir.display());
+                return Ok(found);
+            }
+            Err(err) => {
+                last_error = Some((dir, err));
+            }
+        }
+    }
+
+    match last_error {
+        Some((dir, err)) => Err(anyhow::anyhow!(
+            "failed to load migrations from {}: {}",
+            dir.display(),
+            err
+        )),
+        None => Err(anyhow::anyhow!("failed to resolve migrations directory")),
+    }
+}
+
+/// Returns true for migration errors caused by version history divergence,
+/// which are fixed by a fresh database. Returns false for SQL execution
+/// errors which would recur on a clean DB.
+///
+/// `MigrateError` is `#[non_exhaustive]`, so the wildcard arm is required.
+const fn is_resettable_error(err: &MigrateError) -> bool {
+    matches!(
+        err,
+        MigrateError::VersionMissing(_) | MigrateError::VersionMismatch(_) | MigrateError::Dirty(_)
+    )
+}
+
+/// Drop and recreate the application database.
+///
+/// Connects to the `postgres` system database, terminates existing connections
+/// to the app database, then drops and recreates it. All operations are logged
+/// at `warn!` level since this is a destructive recovery path.
+async fn reset_database(config: &DatabaseConfig) -> Result<(), anyhow::Error> {
+    let db_name = &config.name;
+    warn!(database = %db_name, "Resetting database: terminating connections");
+
+    let mut conn: PgConnection = PgConnection::connect_with(&config.system_connect_options())
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to connect to system database: {e}"))?;
+
+    // Terminate all other connections to the target database.
+    sqlx::query(
+        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = $1 AND pid <> pg_backend_pid()"
+    )
+    .bind(db_name)
+    .execute(&mut conn)
+    .await
+    .map_err(|e| anyhow::anyhow!("failed to terminate connections to {db_name}: {e}"))?;
+
+    // Database identifiers cannot be parameterized — use quoting to prevent
+    // injection. The name comes from our own config, not user input.
+    //
+    // TEMPLATE template0 avoids collation version mismatches that break
+    // CREATE DATABASE after a major Postgres upgrade (e.g. PG 17→18).
+    // template1 inherits stale collation metadata from the old data dir;
+    // template0 is always safe.
+    let drop_sql = format!("DROP DATABASE IF EXISTS \"{db_name}\"");
+    let create_sql = format!("CREATE DATABASE \"{db_name}\" TEMPLATE template0");
+
+    warn!(database = %db_name, "Dropping database");
+    sqlx::query(&drop_sql)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to drop database {db_name}: {e}"))?;
+
+    warn!(database = %db_name, "Creating database");
+    sqlx::query(&create_sql)
+        .execute(&mut conn)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to create database {db_name}: {e}"))?;
+
+    warn!(database = %db_name, "Database reset complete");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use std::borrow::Cow;
+
+    #[derive(Debug)]
+    struct SqlState(&'static str);
+
+    impl std::fmt::Display for SqlState {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "SQLSTATE {}", self.0)
+        }
+    }
+
+    impl std::error::Error for SqlState {}
+
+    impl DatabaseError for SqlState {
+        fn message(&self) -> &str {
+            self.0
+        }
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.0))
+        }
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    fn db_error(code: &'static str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(SqlState(code)))
+    }
+
+    #[test]
+    fn infrastructure_errors_are_transient() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert!(is_transient_error(&sqlx::Error::Io(refused)));
+        assert!(is_transient_error(&sqlx::Error::PoolTimedOut));
+        assert!(is_transient_error(&db_error("57P03"))); // cannot_connect_now
+        assert!(is_transient_error(&db_error("08006"))); // connection_failure
+        assert!(is_transient_error(&db_error("53300"))); // too_many_connections
+    }
+
+    #[test]
+    fn configuration_errors_are_fatal() {
+        assert!(!is_transient_error(&db_error("28P01"))); // invalid_password
+        assert!(!is_transient_error(&db_error("3D000"))); // invalid_catalog_name
+        assert!(!is_transient_error(&sqlx::Error::Configuration(
+            "bad option".into()
+        )));
+    }
+}
@@ -0,0 +1,218 @@
+//! Emergency read-only mode while the database refuses writes.
+//!
+//! During a failover the node behind the connection string can keep
+//! answering queries while rejecting writes: a demoted primary, a replica
+//! that has not been promoted yet, or a primary fenced with
+//! `default_transaction_read_only`. Rather than fail every request,
+//! [`ReadOnlyMonitor`] probes the pool and flips the shared [`ReadOnlyMode`]
+//! flag; [`crate::http::read_only`] then rejects mutations with `503` and
+//! lets reads through.
+//!
+//! A database that cannot be reached at all does not enable read-only mode:
+//! reads would fail too, and `/ready` already reports it. Transitions are
+//! logged and exported as `tc_db_read_only` (0 or 1) and
+//! `tc_db_read_only_transitions_total`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::HeaderValue;
+use sqlx_postgres::PgPool;
+
+use crate::config::ReadOnlyConfig;
+
+/// How long a single probe may take before the database counts as
+/// unreachable.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Result of one write-availability probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteProbe {
+    /// The database accepts writes.
+    Writable,
+    /// The database answers queries but refuses writes.
+    ReadOnly,
+    /// The database could not be queried.
+    Unreachable,
+}
+
+/// Ask the database whether it would accept a write.
+pub async fn probe(pool: &PgPool) -> WriteProbe {
+    let query = sqlx::query_scalar::<_, bool>(
+        "SELECT pg_is_in_recovery() OR current_setting('transaction_read_only')::boolean",
+    )
+    .fetch_one(pool);
+    match tokio::time::timeout(PROBE_TIMEOUT, query).await {
+        Ok(Ok(false)) => WriteProbe::Writable,
+        Ok(Ok(true)) => WriteProbe::ReadOnly,
+        Ok(Err(e)) => {
+            tracing::debug!(error = %e, "Write-availability probe failed");
+            WriteProbe::Unreachable
+        }
+        Err(_) => WriteProbe::Unreachable,
+    }
+}
+
+/// Shared read-only flag, checked on every request.
+pub struct ReadOnlyMode {
+    active: AtomicBool,
+    retry_after: HeaderValue,
+}
+
+impl ReadOnlyMode {
+    #[must_use]
+    pub fn new(config: &ReadOnlyConfig) -> Self {
+        metrics::gauge!("tc_db_read_only").set(0.0);
+        Self {
+            active: AtomicBool::new(false),
+            retry_after: HeaderValue::from(config.retry_after_secs),
+        }
+    }
+
+    /// Whether mutations are currently rejected.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// `Retry-After` value for rejected mutations.
+    #[must_use]
+    pub const fn retry_after(&self) -> &HeaderValue {
+        &self.retry_after
+    }
+
+    /// Enter or leave read-only mode, logging and counting real transitions.
+    pub fn set(&self, active: bool) {
+        if self.active.swap(active, Ordering::Relaxed) == active {
+            return;
+        }
+        metrics::gauge!("tc_db_read_only").set(if active { 1.0 } else { 0.0 });
+        metrics::counter!(
+            "tc_db_read_only_transitions_total",
+            "to" => if active { "read_only" } else { "read_write" }
+        )
+        .increment(1);
+        if active {
+            tracing::error!("Database is refusing writes; entering read-only mode");
+        } else {
+            tracing::warn!("Database accepts writes again; leaving read-only mode");
+        }
+    }
+}
+
+/// Polls the database and drives [`ReadOnlyMode`].
+pub struct ReadOnlyMonitor {
+    pool: PgPool,
+    mode: Arc<ReadOnlyMode>,
+    interval: Duration,
+    failure_threshold: u32,
+    recovery_threshold: u32,
+    read_only_streak: u32,
+    writable_streak: u32,
+}
+
+impl ReadOnlyMonitor {
+    #[must_use]
+    pub const fn new(pool: PgPool, mode: Arc<ReadOnlyMode>, config: &ReadOnlyConfig) -> Self {
+        Self {
+            pool,
+            mode,
+            interval: Duration::from_secs(config.probe_interval_secs),
+            failure_threshold: config.failure_threshold,
+            recovery_threshold: config.recovery_threshold,
+            read_only_streak: 0,
+            writable_streak: 0,
+        }
+    }
+
+    /// Probe forever.
+    pub async fn run(mut self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let result = probe(&self.pool).await;
+            self.observe(result);
+        }
+    }
+
+    /// Count a probe result towards a transition and apply it once its
+    /// threshold is reached. An unreachable database breaks both streaks
+    /// and leaves the mode unchanged.
+    fn observe(&mut self, result: WriteProbe) {
+        match result {
+            WriteProbe::Writable => {
+                self.read_only_streak = 0;
+                self.writable_streak = self.writable_streak.saturating_add(1);
+                if self.writable_streak >= self.recovery_threshold {
+                    self.mode.set(false);
+                }
+            }
+            WriteProbe::ReadOnly => {
+                self.writable_streak = 0;
+                self.read_only_streak = self.read_only_streak.saturating_add(1);
+                if self.read_only_streak >= self.failure_threshold {
+                    self.mode.set(true);
+                }
+            }
+            WriteProbe::Unreachable => {
+                self.read_only_streak = 0;
+                self.writable_streak = 0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(failure_threshold: u32, recovery_threshold: u32) -> ReadOnlyMonitor {
+        let config = ReadOnlyConfig {
+            failure_threshold,
+            recovery_threshold,
+            ..ReadOnlyConfig::default()
+        };
+        let pool = sqlx_postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .expect("lazy pool");
+        ReadOnlyMonitor::new(pool, Arc::new(ReadOnlyMode::new(&config)), &config)
+    }
+
+    #[tokio::test]
+    async fn flips_after_consecutive_probes() {
+        let mut m = monitor(3, 2);
+        m.observe(WriteProbe::ReadOnly);
+        m.observe(WriteProbe::ReadOnly);
+        assert!(!m.mode.is_active());
+        m.observe(WriteProbe::ReadOnly);
+        assert!(m.mode.is_active());
+
+        m.observe(WriteProbe::Writable);
+        assert!(m.mode.is_active(), "one writable probe is not enough");
+        m.observe(WriteProbe::Writable);
+        assert!(!m.mode.is_active());
+    }
+
+    #[tokio::test]
+    async fn interrupted_streaks_do_not_flip() {
+        let mut m = monitor(2, 2);
+        for result in [
+            WriteProbe::ReadOnly,
+            WriteProbe::Writable,
+            WriteProbe::ReadOnly,
+            WriteProbe::Unreachable,
+            WriteProbe::ReadOnly,
+        ] {
+            m.observe(result);
+        }
+        assert!(!m.mode.is_active());
+
+        m.observe(WriteProbe::ReadOnly);
+        assert!(m.mode.is_active());
+        m.observe(WriteProbe::Unreachable);
+        m.observe(WriteProbe::Unreachable);
+        assert!(m.mode.is_active(), "an outage does not end read-only mode");
+    }
+}
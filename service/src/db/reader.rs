@@ -0,0 +1,87 @@
+//! Reader pool connected as a SELECT-only role.
+//!
+//! Read paths that never write (public stats, for now) can run on a second
+//! pool logged in as `database.reader_user`. Migrations create the NOLOGIN
+//! group role `tc_reader` with SELECT on every table; operators create a
+//! login role, `GRANT tc_reader TO` it, and configure its credentials. If a
+//! read handler is ever SQL-injected, the injected statement runs without
+//! write privileges.
+//!
+//! The role is checked once at startup by [`verify_read_only`]; a reader that
+//! holds any write privilege stops the service rather than silently widening
+//! the blast radius. Reader sessions also default to read-only transactions.
+
+use std::time::{Duration, Instant};
+
+use sqlx_postgres::PgPool;
+use tracing::info;
+
+use crate::config::DatabaseConfig;
+
+/// Open the reader pool, if `database.reader_user` is configured, and verify
+/// that its role cannot write.
+///
+/// Call after migrations so the `tc_reader` grants exist.
+///
+/// # Errors
+/// Returns an error if the reader cannot connect before the startup deadline
+/// or if [`verify_read_only`] fails.
+pub async fn connect_reader(config: &DatabaseConfig) -> Result<Option<PgPool>, anyhow::Error> {
+    let Some(options) = config.reader_connect_options() else {
+        return Ok(None);
+    };
+    let deadline = Instant::now() + Duration::from_secs(config.startup_deadline_secs);
+    let pool = super::pool_with_retry(options, config.max_connections, deadline).await?;
+    verify_read_only(&pool).await?;
+    info!(role = ?config.reader_user, "Reader pool connected with a read-only role");
+    Ok(Some(pool))
+}
+
+/// Fail unless the pool's role is unable to write.
+///
+/// Checks role privileges rather than attempting writes, so the result does
+/// not depend on `default_transaction_read_only`: the role must not be a
+/// superuser, must not be able to create objects in the database or any
+/// application schema, and must hold no INSERT, UPDATE, DELETE or TRUNCATE
+/// privilege on any application table.
+///
+/// # Errors
+/// Returns an error naming the offending privileges, or a database error.
+pub async fn verify_read_only(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let (role, superuser): (String, bool) = sqlx::query_as(
+        "SELECT current_user::text, rolsuper FROM pg_roles WHERE rolname = current_user",
+    )
+    .fetch_one(pool)
+    .await?;
+    if superuser {
+        anyhow::bail!("reader role {role} is a superuser");
+    }
+
+    let writable: Vec<String> = sqlx::query_scalar(
+        r"
+        SELECT format('CREATE on database %I', current_database())
+        WHERE has_database_privilege(current_database(), 'CREATE')
+        UNION ALL
+        SELECT format('CREATE on schema %I', n.nspname)
+        FROM pg_namespace n
+        WHERE n.nspname NOT LIKE 'pg\_%' AND n.nspname <> 'information_schema'
+          AND has_schema_privilege(n.oid, 'CREATE')
+        UNION ALL
+        SELECT format('writes to %I.%I', n.nspname, c.relname)
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE c.relkind IN ('r', 'p', 'v', 'm')
+          AND n.nspname NOT LIKE 'pg\_%' AND n.nspname <> 'information_schema'
+          AND has_table_privilege(c.oid, 'INSERT, UPDATE, DELETE, TRUNCATE')
+        ",
+    )
+    .fetch_all(pool)
+    .await?;
+    if !writable.is_empty() {
+        anyhow::bail!(
+            "reader role {role} can write ({}); grant it tc_reader only",
+            writable.join(", ")
+        );
+    }
+    Ok(())
+}
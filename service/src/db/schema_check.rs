@@ -0,0 +1,634 @@
+//! Schema drift detection for `tc-ops check-schema`.
+//!
+//! [`check_schema`] compares a live database with the migrations the service
+//! would apply, changing neither:
+//!
+//! - **Migration history**: every row in `_sqlx_migrations` must match a
+//!   migration on disk by version and checksum and must have succeeded, and
+//!   every migration on disk must have been applied.
+//! - **Schema objects**: the applied migrations are replayed into a scratch
+//!   database (`{name}_schema_check_{suffix}`, dropped afterwards) and the
+//!   tables, columns, indexes, constraints, views, functions, and triggers of
+//!   both `public` schemas are compared. Objects found only in the live
+//!   database, such as a hand-made hotfix index, count as drift too.
+//! - **Query metadata**: given a directory of sqlx offline metadata
+//!   (`query-*.json`), each recorded query is prepared against the live
+//!   database and its result columns compared by name and type.
+//!
+//! Pending migrations are also linted. Statements that drop or retype data
+//! are reported as warnings, which do not fail the check.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Column as _, Connection as _, Executor as _, Statement as _, TypeInfo as _};
+use sqlx_core::migrate::{Migrate as _, Migration};
+use sqlx_postgres::{PgConnection, PgPool};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{connect, resolve_migrator};
+use crate::config::DatabaseConfig;
+
+/// One difference between the live database and what its migrations define.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Drift {
+    /// Stable `snake_case` identifier of the failed check.
+    pub code: &'static str,
+    /// What differs: `migration:{version}`, `{kind}:{name}` for a schema
+    /// object, or `query:{file}`.
+    pub object: String,
+    pub detail: String,
+}
+
+impl Drift {
+    fn new(code: &'static str, object: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            code,
+            object: object.into(),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Result of a schema check.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SchemaReport {
+    pub migrations_on_disk: u64,
+    pub migrations_applied: u64,
+    pub objects_compared: u64,
+    pub queries_checked: u64,
+    pub drift: Vec<Drift>,
+    /// Lint findings for pending migrations; these do not fail the check.
+    pub warnings: Vec<Drift>,
+}
+
+impl SchemaReport {
+    /// Whether the live database matches its migrations.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.drift.is_empty()
+    }
+}
+
+/// Which checks [`check_schema`] runs beyond migration history.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaCheckOptions {
+    /// Skip the schema object comparison, which needs `CREATEDB` for its
+    /// scratch database.
+    pub skip_objects: bool,
+    /// Directory of sqlx offline query metadata to check.
+    pub sqlx_metadata: Option<PathBuf>,
+}
+
+/// A row of `_sqlx_migrations`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct AppliedMigrationRow {
+    version: i64,
+    description: String,
+    checksum: Vec<u8>,
+    success: bool,
+}
+
+/// Compare the live database with its migrations.
+///
+/// # Errors
+/// Returns an error if the migrations cannot be loaded, a database cannot be
+/// reached, a migration fails to replay into the scratch database, or the
+/// query metadata cannot be read. Drift itself is not an error; see
+/// [`SchemaReport::is_clean`].
+pub async fn check_schema(
+    config: &DatabaseConfig,
+    options: &SchemaCheckOptions,
+) -> Result<SchemaReport, anyhow::Error> {
+    let migrator = resolve_migrator(config).await?;
+    let on_disk: Vec<&Migration> = migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+        .collect();
+    let pool = connect(config).await?;
+    let applied = applied_migrations(&pool).await?;
+    let applied_versions: HashSet<i64> = applied.iter().map(|row| row.version).collect();
+
+    let mut report = SchemaReport {
+        migrations_on_disk: on_disk.len() as u64,
+        migrations_applied: applied.len() as u64,
+        drift: compare_migrations(&on_disk, &applied),
+        warnings: lint_pending(&on_disk, &applied_versions),
+        ..SchemaReport::default()
+    };
+
+    if !options.skip_objects {
+        let expected = reference_snapshot(config, &on_disk, &applied_versions).await?;
+        let live = snapshot(&pool).await?;
+        report.objects_compared = expected
+            .keys()
+            .chain(live.keys())
+            .collect::<BTreeSet<_>>()
+            .len() as u64;
+        report.drift.extend(compare_schemas(&expected, &live));
+    }
+
+    if let Some(dir) = &options.sqlx_metadata {
+        let (checked, drift) = check_query_metadata(&pool, dir).await?;
+        report.queries_checked = checked;
+        report.drift.extend(drift);
+    }
+
+    pool.close().await;
+    Ok(report)
+}
+
+async fn applied_migrations(pool: &PgPool) -> Result<Vec<AppliedMigrationRow>, sqlx::Error> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('public._sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as(
+        "SELECT version, description, checksum, success FROM _sqlx_migrations ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+fn compare_migrations(on_disk: &[&Migration], applied: &[AppliedMigrationRow]) -> Vec<Drift> {
+    let by_version: HashMap<i64, &Migration> = on_disk.iter().map(|m| (m.version, *m)).collect();
+    let mut drift = Vec::new();
+    let mut seen = HashSet::new();
+    for migration in on_disk {
+        if !seen.insert(migration.version) {
+            drift.push(Drift::new(
+                "migration_duplicate_version",
+                format!("migration:{}", migration.version),
+                format!("'{}' reuses an existing version", migration.description),
+            ));
+        }
+    }
+    for row in applied {
+        let object = format!("migration:{}", row.version);
+        match by_version.get(&row.version) {
+            None => drift.push(Drift::new(
+                "migration_unknown",
+                object,
+                format!("'{}' is applied but not on disk", row.description),
+            )),
+            Some(m) if *m.checksum != *row.checksum => drift.push(Drift::new(
+                "migration_checksum_mismatch",
+                object,
+                format!("'{}' was edited after it was applied", row.description),
+            )),
+            Some(_) if !row.success => drift.push(Drift::new(
+                "migration_failed",
+                object,
+                format!("'{}' did not complete", row.description),
+            )),
+            Some(_) => {}
+        }
+    }
+    let applied_versions: HashSet<i64> = applied.iter().map(|row| row.version).collect();
+    drift.extend(
+        on_disk
+            .iter()
+            .filter(|m| !applied_versions.contains(&m.version))
+            .map(|m| {
+                Drift::new(
+                    "migration_pending",
+                    format!("migration:{}", m.version),
+                    format!("'{}' is on disk but not applied", m.description),
+                )
+            }),
+    );
+    drift
+}
+
+/// Statements in a pending migration that lose or rewrite existing data.
+const DESTRUCTIVE_STATEMENTS: [&str; 4] = ["DROP TABLE", "DROP COLUMN", "TRUNCATE", "ALTER COLUMN"];
+
+fn lint_pending(on_disk: &[&Migration], applied_versions: &HashSet<i64>) -> Vec<Drift> {
+    on_disk
+        .iter()
+        .filter(|m| !applied_versions.contains(&m.version))
+        .flat_map(|m| {
+            let sql = m.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+            let sql = sql.to_uppercase();
+            DESTRUCTIVE_STATEMENTS
+                .iter()
+                .filter(move |statement| {
+                    sql.contains(*statement)
+                        && (**statement != "ALTER COLUMN" || sql.contains(" TYPE "))
+                })
+                .map(move |statement| {
+                    Drift::new(
+                        "migration_destructive",
+                        format!("migration:{}", m.version),
+                        format!("'{}' contains {statement}", m.description),
+                    )
+                })
+        })
+        .collect()
+}
+
+/// `kind:name` → definition for every object in the `public` schema, except
+/// the migration table and objects owned by extensions.
+async fn snapshot<'e, E>(executor: E) -> Result<BTreeMap<String, String>, sqlx::Error>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        r"
+        SELECT 'table', c.relname::text, c.relkind::text
+        FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = 'public' AND c.relkind IN ('r', 'p')
+          AND c.relname <> '_sqlx_migrations'
+        UNION ALL
+        SELECT 'column', c.relname || '.' || a.attname,
+               format_type(a.atttypid, a.atttypmod)
+               || CASE WHEN a.attnotnull THEN ' NOT NULL' ELSE '' END
+               || COALESCE(' DEFAULT ' || pg_get_expr(d.adbin, d.adrelid), '')
+        FROM pg_attribute a
+        JOIN pg_class c ON c.oid = a.attrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+        WHERE n.nspname = 'public' AND c.relkind IN ('r', 'p')
+          AND c.relname <> '_sqlx_migrations'
+          AND a.attnum > 0 AND NOT a.attisdropped
+        UNION ALL
+        SELECT 'index', indexname::text, indexdef
+        FROM pg_indexes
+        WHERE schemaname = 'public' AND tablename <> '_sqlx_migrations'
+        UNION ALL
+        SELECT 'constraint', c.relname || '.' || con.conname, pg_get_constraintdef(con.oid)
+        FROM pg_constraint con
+        JOIN pg_class c ON c.oid = con.conrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = 'public' AND c.relname <> '_sqlx_migrations'
+        UNION ALL
+        SELECT 'view', c.relname::text, pg_get_viewdef(c.oid)
+        FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = 'public' AND c.relkind IN ('v', 'm')
+        UNION ALL
+        SELECT 'function',
+               p.proname || '(' || pg_get_function_identity_arguments(p.oid) || ')',
+               pg_get_functiondef(p.oid)
+        FROM pg_proc p JOIN pg_namespace n ON n.oid = p.pronamespace
+        WHERE n.nspname = 'public' AND p.prokind IN ('f', 'p')
+          AND NOT EXISTS (
+              SELECT 1 FROM pg_depend dep WHERE dep.objid = p.oid AND dep.deptype = 'e'
+          )
+        UNION ALL
+        SELECT 'trigger', c.relname || '.' || t.tgname, pg_get_triggerdef(t.oid)
+        FROM pg_trigger t
+        JOIN pg_class c ON c.oid = t.tgrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = 'public' AND NOT t.tgisinternal
+        ",
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(kind, name, definition)| (format!("{kind}:{name}"), definition))
+        .collect())
+}
+
+/// Snapshot of the schema the applied migrations produce, built in a scratch
+/// database that is dropped afterwards.
+async fn reference_snapshot(
+    config: &DatabaseConfig,
+    on_disk: &[&Migration],
+    applied_versions: &HashSet<i64>,
+) -> Result<BTreeMap<String, String>, anyhow::Error> {
+    let suffix = Uuid::new_v4().simple().to_string();
+    let scratch = format!("{}_schema_check_{}", config.name, &suffix[..8]);
+    let mut admin = PgConnection::connect_with(&config.system_connect_options())
+        .await
+        .context("connecting to the system database")?;
+    // Identifiers cannot be parameterized; the name comes from our own
+    // config plus hex digits.
+    sqlx::query(&format!("CREATE DATABASE \"{scratch}\" TEMPLATE template0"))
+        .execute(&mut admin)
+        .await
+        .with_context(|| {
+            format!("creating scratch database {scratch} (needs CREATEDB; see --skip-objects)")
+        })?;
+
+    let result = replay_and_snapshot(config, &scratch, on_disk, applied_versions).await;
+
+    if let Err(e) = sqlx::query(&format!(
+        "DROP DATABASE IF EXISTS \"{scratch}\" WITH (FORCE)"
+    ))
+    .execute(&mut admin)
+    .await
+    {
+        warn!(database = %scratch, error = %e, "Failed to drop scratch database");
+    }
+    if let Err(e) = admin.close().await {
+        warn!(error = %e, "Failed to close system database connection");
+    }
+    result
+}
+
+async fn replay_and_snapshot(
+    config: &DatabaseConfig,
+    scratch: &str,
+    on_disk: &[&Migration],
+    applied_versions: &HashSet<i64>,
+) -> Result<BTreeMap<String, String>, anyhow::Error> {
+    let mut conn = PgConnection::connect_with(&config.connect_options().database(scratch))
+        .await
+        .with_context(|| format!("connecting to scratch database {scratch}"))?;
+    conn.ensure_migrations_table().await?;
+    for migration in on_disk
+        .iter()
+        .filter(|m| applied_versions.contains(&m.version))
+    {
+        conn.apply(migration)
+            .await
+            .with_context(|| format!("replaying migration {}", migration.version))?;
+    }
+    let snapshot = snapshot(&mut conn).await?;
+    conn.close().await?;
+    Ok(snapshot)
+}
+
+fn compare_schemas(
+    expected: &BTreeMap<String, String>,
+    live: &BTreeMap<String, String>,
+) -> Vec<Drift> {
+    let mut drift = Vec::new();
+    for (object, definition) in expected {
+        match live.get(object) {
+            None => drift.push(Drift::new(
+                "schema_object_missing",
+                object,
+                format!("expected `{definition}`"),
+            )),
+            Some(found) if found != definition => drift.push(Drift::new(
+                "schema_object_changed",
+                object,
+                format!("expected `{definition}`, found `{found}`"),
+            )),
+            Some(_) => {}
+        }
+    }
+    drift.extend(
+        live.iter()
+            .filter(|(object, _)| !expected.contains_key(*object))
+            .map(|(object, definition)| {
+                Drift::new(
+                    "schema_object_unexpected",
+                    object,
+                    format!("not created by any migration: `{definition}`"),
+                )
+            }),
+    );
+    drift
+}
+
+/// The parts of an sqlx offline metadata file that are checked.
+#[derive(Deserialize)]
+struct QueryMetadata {
+    query: String,
+    describe: QueryDescribe,
+}
+
+#[derive(Deserialize)]
+struct QueryDescribe {
+    columns: Vec<QueryColumn>,
+}
+
+#[derive(Deserialize)]
+struct QueryColumn {
+    name: String,
+    type_info: Value,
+}
+
+async fn check_query_metadata(
+    pool: &PgPool,
+    dir: &Path,
+) -> Result<(u64, Vec<Drift>), anyhow::Error> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading query metadata directory {}", dir.display()))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "json")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("query-"))
+        })
+        .collect();
+    files.sort();
+
+    let mut drift = Vec::new();
+    for path in &files {
+        let object = format!(
+            "query:{}",
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default()
+        );
+        let raw =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let metadata: QueryMetadata =
+            serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+        let recorded: Vec<(String, String)> = metadata
+            .describe
+            .columns
+            .iter()
+            .map(|c| {
+                (
+                    c.name.clone(),
+                    normalize_type(&recorded_type_name(&c.type_info)),
+                )
+            })
+            .collect();
+        match pool.prepare(&metadata.query).await {
+            Err(e) => drift.push(Drift::new("query_invalid", object, e.to_string())),
+            Ok(statement) => {
+                let live: Vec<(String, String)> = statement
+                    .columns()
+                    .iter()
+                    .map(|c| (c.name().to_string(), normalize_type(c.type_info().name())))
+                    .collect();
+                if live != recorded {
+                    drift.push(Drift::new(
+                        "query_columns_changed",
+                        object,
+                        format!(
+                            "recorded ({}), live ({})",
+                            describe_columns(&recorded),
+                            describe_columns(&live)
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    Ok((files.len() as u64, drift))
+}
+
+/// Type name from an offline `type_info`: a string such as `"Int4"`, or
+/// `{"Custom": {"name": ...}}` for user-defined types.
+fn recorded_type_name(type_info: &Value) -> String {
+    match type_info {
+        Value::String(name) => name.clone(),
+        Value::Object(map) => map
+            .get("Custom")
+            .and_then(|custom| custom.get("name"))
+            .or_else(|| map.get("DeclareWithName"))
+            .and_then(Value::as_str)
+            .map_or_else(|| type_info.to_string(), str::to_string),
+        _ => type_info.to_string(),
+    }
+}
+
+/// Make offline names (`TextArray`, `Bpchar`) and server names (`TEXT[]`,
+/// `CHAR`) comparable.
+fn normalize_type(name: &str) -> String {
+    let name = name.to_lowercase();
+    let (element, suffix) = name
+        .strip_suffix("[]")
+        .or_else(|| name.strip_prefix('_'))
+        .or_else(|| name.strip_suffix("array"))
+        .map_or((name.as_str(), ""), |element| (element, "array"));
+    let element = if element == "bpchar" { "char" } else { element };
+    format!("{element}{suffix}")
+}
+
+fn describe_columns(columns: &[(String, String)]) -> String {
+    columns
+        .iter()
+        .map(|(name, ty)| format!("{name} {ty}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx_core::migrate::MigrationType;
+
+    fn migration(version: i64, sql: &'static str) -> Migration {
+        Migration::new(
+            version,
+            format!("m{version}").into(),
+            MigrationType::Simple,
+            sql.into(),
+            false,
+        )
+    }
+
+    fn applied(m: &Migration, success: bool) -> AppliedMigrationRow {
+        AppliedMigrationRow {
+            version: m.version,
+            description: m.description.to_string(),
+            checksum: m.checksum.to_vec(),
+            success,
+        }
+    }
+
+    fn codes(drift: &[Drift]) -> Vec<(&'static str, &str)> {
+        drift.iter().map(|d| (d.code, d.object.as_str())).collect()
+    }
+
+    #[test]
+    fn migration_history_drift() {
+        let (one, two, three) = (
+            migration(1, "CREATE TABLE a ()"),
+            migration(2, "CREATE TABLE b ()"),
+            migration(3, "CREATE TABLE c ()"),
+        );
+        let mut edited = applied(&two, true);
+        edited.checksum = migration(2, "CREATE TABLE b (id int)").checksum.to_vec();
+        let removed = applied(&migration(9, "SELECT 1"), true);
+
+        let drift = compare_migrations(
+            &[&one, &two, &three],
+            &[applied(&one, false), edited, removed],
+        );
+        assert_eq!(
+            codes(&drift),
+            [
+                ("migration_failed", "migration:1"),
+                ("migration_checksum_mismatch", "migration:2"),
+                ("migration_unknown", "migration:9"),
+                ("migration_pending", "migration:3"),
+            ]
+        );
+
+        let clean = compare_migrations(&[&one, &two], &[applied(&one, true), applied(&two, true)]);
+        assert!(clean.is_empty());
+    }
+
+    #[test]
+    fn pending_destructive_statements_are_linted() {
+        let (old, drops, retypes, adds) = (
+            migration(1, "DROP TABLE legacy"),
+            migration(2, "ALTER TABLE t\n  DROP   COLUMN x"),
+            migration(3, "ALTER TABLE t ALTER COLUMN y TYPE bigint"),
+            migration(4, "ALTER TABLE t ALTER COLUMN y SET DEFAULT 0"),
+        );
+        let warnings = lint_pending(&[&old, &drops, &retypes, &adds], &HashSet::from([1]));
+        assert_eq!(
+            codes(&warnings),
+            [
+                ("migration_destructive", "migration:2"),
+                ("migration_destructive", "migration:3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_object_drift() {
+        let expected = BTreeMap::from([
+            ("column:t.a".to_string(), "integer NOT NULL".to_string()),
+            (
+                "index:t_pkey".to_string(),
+                "CREATE UNIQUE INDEX t_pkey".to_string(),
+            ),
+        ]);
+        let live = BTreeMap::from([
+            ("column:t.a".to_string(), "bigint NOT NULL".to_string()),
+            (
+                "index:t_hotfix".to_string(),
+                "CREATE INDEX t_hotfix".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            codes(&compare_schemas(&expected, &live)),
+            [
+                ("schema_object_changed", "column:t.a"),
+                ("schema_object_missing", "index:t_pkey"),
+                ("schema_object_unexpected", "index:t_hotfix"),
+            ]
+        );
+        assert!(compare_schemas(&expected, &expected).is_empty());
+    }
+
+    #[test]
+    fn offline_and_server_type_names_agree() {
+        for (recorded, live) in [
+            (serde_json::json!("Int4"), "INT4"),
+            (serde_json::json!("TextArray"), "TEXT[]"),
+            (serde_json::json!("Bpchar"), "CHAR"),
+            (
+                serde_json::json!({"Custom": {"name": "poll_status", "kind": "Simple"}}),
+                "poll_status",
+            ),
+        ] {
+            assert_eq!(
+                normalize_type(&recorded_type_name(&recorded)),
+                normalize_type(live)
+            );
+        }
+        assert_ne!(normalize_type("Int4"), normalize_type("INT8"));
+    }
+}
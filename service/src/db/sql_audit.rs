@@ -0,0 +1,260 @@
+//! SQL injection audit mode (`sql-audit` feature, debug builds and tests only).
+//!
+//! Repo queries must take request input through bind parameters. Anything
+//! spliced into the query text with `format!` or `push_str` is an injection
+//! risk, and the pattern is easy to reintroduce when a list endpoint grows a
+//! new filter or sort option.
+//!
+//! With the feature on, [`trace_request_inputs`] records the request's path
+//! segments, query-string values, and JSON body strings as *tainted* for the
+//! rest of that request. Every query run through
+//! [`Instrumented`](super::Instrumented) is then checked: if a tainted value
+//! appears literally in the SQL text, the query is refused with
+//! [`sqlx::Error::InvalidArgument`] before it reaches the database, logged,
+//! and recorded for [`take_violations`]. The integration tests enable the
+//! feature, so such a query fails the test that exercises it.
+//!
+//! Values shorter than [`MIN_TRACED_LEN`] bytes, and plain lowercase words
+//! such as `trust` or `public`, are not traced: they routinely appear in
+//! static SQL as column names or enum literals. Tests that probe a filter
+//! should use a distinctive value.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::Response,
+};
+
+/// Shortest request value that is traced into SQL text.
+pub const MIN_TRACED_LEN: usize = 8;
+
+/// Largest body that is buffered to collect its strings; the app-wide body
+/// limit is the same.
+const MAX_TRACED_BODY: usize = 1024 * 1024;
+
+tokio::task_local! {
+    static TAINTED: RefCell<Vec<String>>;
+}
+
+static VIOLATIONS: Mutex<Vec<Violation>> = Mutex::new(Vec::new());
+
+/// A query whose text contained a tainted request value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Instrumented query name, e.g. `endorsements.list_by_subject`
+    pub query: &'static str,
+    pub value: String,
+}
+
+/// Whether `value` is distinctive enough to trace.
+fn traceable(value: &str) -> bool {
+    value.len() >= MIN_TRACED_LEN && !value.bytes().all(|b| b.is_ascii_lowercase() || b == b'_')
+}
+
+/// Mark `value` as request-derived for the rest of the current audit scope.
+/// Outside a scope this does nothing.
+pub fn taint(value: &str) {
+    if traceable(value) {
+        // Err only outside a scope
+        TAINTED
+            .try_with(|tainted| tainted.borrow_mut().push(value.to_string()))
+            .ok();
+    }
+}
+
+/// Run `future` with its own, initially empty, taint set.
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    TAINTED.scope(RefCell::new(Vec::new()), future).await
+}
+
+/// Middleware: taint the request's inputs and run the rest of the stack in
+/// an audit scope.
+pub async fn trace_request_inputs(request: Request, next: Next) -> Response {
+    scope(async move {
+        let request = taint_request(request).await;
+        next.run(request).await
+    })
+    .await
+}
+
+async fn taint_request(request: Request) -> Request {
+    let uri = request.uri().clone();
+    for segment in uri.path().split('/') {
+        if let Ok(decoded) = urlencoding::decode(segment) {
+            taint(&decoded);
+        }
+    }
+    for pair in uri.query().unwrap_or_default().split('&') {
+        let value = pair.split_once('=').map_or("", |(_, v)| v);
+        if let Ok(decoded) = urlencoding::decode(&value.replace('+', " ")) {
+            taint(&decoded);
+        }
+    }
+
+    let is_json = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return request;
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, MAX_TRACED_BODY).await.unwrap_or_default();
+    if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+        taint_json(&value);
+    }
+    Request::from_parts(parts, Body::from(bytes))
+}
+
+fn taint_json(value: &serde_json::Value) {
+    match value {
+        serde_json::Value::String(s) => taint(s),
+        serde_json::Value::Array(items) => items.iter().for_each(taint_json),
+        serde_json::Value::Object(map) => {
+            for (key, item) in map {
+                taint(key);
+                taint_json(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Refuse `sql` if it contains a value tainted in the current scope.
+pub(crate) fn check(query: &'static str, sql: &str) -> Result<(), sqlx::Error> {
+    let found = TAINTED
+        .try_with(|tainted| {
+            tainted
+                .borrow()
+                .iter()
+                .find(|v| sql.contains(v.as_str()))
+                .cloned()
+        })
+        .ok()
+        .flatten();
+    let Some(value) = found else {
+        return Ok(());
+    };
+    tracing::error!(
+        query,
+        value,
+        "sql-audit: request input spliced into query text"
+    );
+    VIOLATIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Violation {
+            query,
+            value: value.clone(),
+        });
+    Err(sqlx::Error::InvalidArgument(format!(
+        "sql-audit: query {query} contains request input {value:?}; use a bind parameter"
+    )))
+}
+
+/// Drain the violations recorded so far, across all scopes.
+pub fn take_violations() -> Vec<Violation> {
+    std::mem::take(&mut *VIOLATIONS.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    const MARKER: &str = "x' OR 1=1 --";
+
+    #[tokio::test]
+    async fn spliced_values_are_refused_and_bound_ones_pass() {
+        scope(async {
+            taint(MARKER);
+            let spliced = format!("SELECT * FROM endorsements WHERE topic = '{MARKER}'");
+            assert!(matches!(
+                check("test.spliced", &spliced),
+                Err(sqlx::Error::InvalidArgument(_))
+            ));
+            assert!(check("test.bound", "SELECT * FROM endorsements WHERE topic = $1").is_ok());
+        })
+        .await;
+        let violations = take_violations();
+        assert!(violations.contains(&Violation {
+            query: "test.spliced",
+            value: MARKER.to_string(),
+        }));
+        assert!(violations.iter().all(|v| v.query != "test.bound"));
+    }
+
+    #[tokio::test]
+    async fn common_words_and_unscoped_queries_are_not_traced() {
+        scope(async {
+            taint("trust");
+            taint("endorsements");
+            assert!(check(
+                "test.words",
+                "SELECT * FROM endorsements WHERE topic = 'trust'"
+            )
+            .is_ok());
+        })
+        .await;
+        taint(MARKER);
+        assert!(check("test.unscoped", &format!("SELECT '{MARKER}'")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn middleware_taints_path_query_and_json_body() {
+        async fn probe(body: String) -> String {
+            let sql = format!("SELECT 1 WHERE a = 'dev-0042-kid' OR b = 'Gov%20Topic 7' {body}");
+            match check("test.middleware", &sql) {
+                Ok(()) => "clean".to_string(),
+                Err(e) => e.to_string(),
+            }
+        }
+        let app = Router::new()
+            .route("/items/{id}", get(probe).post(probe))
+            .layer(axum::middleware::from_fn(trace_request_inputs));
+
+        let from_path = app
+            .clone()
+            .oneshot(
+                Request::get("/items/dev-0042-kid")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        let body = to_bytes(from_path.into_body(), 1024).await.expect("body");
+        assert!(String::from_utf8_lossy(&body).contains("dev-0042-kid"));
+
+        let from_query = app
+            .clone()
+            .oneshot(
+                Request::get("/items/1?topic=Gov%2520Topic+7")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        let body = to_bytes(from_query.into_body(), 1024).await.expect("body");
+        assert!(String::from_utf8_lossy(&body).contains("Gov%20Topic 7"));
+
+        let from_body = app
+            .oneshot(
+                Request::post("/items/1")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"filter":"Robert'); DROP"}"#))
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        let body = to_bytes(from_body.into_body(), 1024).await.expect("body");
+        assert!(String::from_utf8_lossy(&body).contains("Robert'); DROP"));
+    }
+}
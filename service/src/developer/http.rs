@@ -0,0 +1,708 @@
+//! HTTP handlers for registering and managing OAuth clients, and the
+//! authorization server endpoints those clients call.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{rejection::FormRejection, Extension},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, patch, post},
+    Form, Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::repo::{
+    DeveloperRepo, DeveloperRepoError, NewAuthorizationCode, OAuthClientRecord, OAuthClientUpdate,
+};
+use super::{
+    generate_authorization_code, generate_client_id, generate_client_secret,
+    hash_authorization_code, hash_client_secret, parse_scope_param, parse_scopes,
+    pkce_verifier_matches, rotation_grace_deadline, secret_matches, validate_client_name,
+    validate_code_challenge, validate_redirect_uris, ACCESS_TOKEN_TTL_SECS,
+    AUTHORIZATION_CODE_TTL_SECS, PKCE_METHOD_S256,
+};
+use crate::config::ReputationConfig;
+use crate::http::{bad_request, conflict, internal_error, not_found, Path};
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::identity::http::token_auth::{generate_api_token, hash_api_token};
+use crate::reputation::repo::ReputationRepo;
+use crate::reputation::score::explain_account;
+use crate::reputation::ReputationTier;
+use crate::trust::repo::TrustRepo;
+
+// ─── Request / response types ──────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateClientRequest {
+    pub name: String,
+    /// Absolute `https` URLs (plain `http` only for loopback hosts)
+    pub redirect_uris: Vec<String>,
+    /// API token scopes the client may request, e.g. `trust:read`
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateClientRequest {
+    pub name: Option<String>,
+    /// Replaces the whole list when present
+    pub redirect_uris: Option<Vec<String>>,
+    /// Replaces the whole list when present
+    pub scopes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClientInfo {
+    pub client_id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    /// The secret issued before the last rotation stops working at this instant
+    pub previous_secret_expires_at: Option<String>,
+    pub secret_rotated_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<OAuthClientRecord> for ClientInfo {
+    fn from(r: OAuthClientRecord) -> Self {
+        Self {
+            client_id: r.client_id,
+            name: r.name,
+            redirect_uris: r.redirect_uris,
+            scopes: r.scopes,
+            previous_secret_expires_at: r
+                .previous_secret_expires_at
+                .filter(|t| *t > Utc::now())
+                .map(|t| t.to_rfc3339()),
+            secret_rotated_at: r.secret_rotated_at.map(|t| t.to_rfc3339()),
+            revoked_at: r.revoked_at.map(|t| t.to_rfc3339()),
+            created_at: r.created_at.to_rfc3339(),
+            updated_at: r.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// A client together with its plaintext secret, returned exactly once.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClientWithSecretResponse {
+    #[serde(flatten)]
+    pub client: ClientInfo,
+    /// Store this now; it cannot be retrieved again
+    pub client_secret: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClientListResponse {
+    /// Newest first, including revoked clients
+    pub clients: Vec<ClientInfo>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AuthorizeRequest {
+    /// Must be `code`
+    pub response_type: String,
+    pub client_id: String,
+    /// Must exactly match one of the client's registered redirect URIs
+    pub redirect_uri: String,
+    /// Space-separated scopes; defaults to every scope the client registered
+    pub scope: Option<String>,
+    /// Opaque value echoed back to the client
+    pub state: Option<String>,
+    /// Base64url SHA-256 of the client's code verifier
+    pub code_challenge: String,
+    /// Must be `S256`
+    pub code_challenge_method: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthorizeResponse {
+    pub code: String,
+    pub state: Option<String>,
+    /// Seconds until the code can no longer be exchanged
+    pub expires_in: i64,
+    /// The redirect URI with `code` and `state` appended; send the user here
+    pub redirect_to: String,
+}
+
+/// Form body of `POST /oauth/token` (`application/x-www-form-urlencoded`).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TokenRequest {
+    /// Must be `authorization_code`
+    pub grant_type: String,
+    pub code: String,
+    pub redirect_uri: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub code_verifier: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    /// Opaque bearer token for the read APIs covered by `scope`
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: String,
+    pub expires_in: i64,
+    /// Space-separated scopes granted
+    pub scope: String,
+    /// The account that approved the client
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub reputation_tier: ReputationTier,
+}
+
+/// Error body of the token endpoint, as defined by RFC 6749 §5.2.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OAuthErrorResponse {
+    /// e.g. `invalid_grant`, `invalid_client`
+    pub error: String,
+    pub error_description: String,
+}
+
+/// Everything the client owner may change, validated.
+#[allow(clippy::result_large_err)]
+fn validate_update(
+    body: &UpdateClientRequest,
+) -> Result<OAuthClientUpdate, axum::response::Response> {
+    let name = body
+        .name
+        .as_deref()
+        .map(validate_client_name)
+        .transpose()
+        .map_err(|e| bad_request(&e))?
+        .map(str::to_string);
+    let redirect_uris = body
+        .redirect_uris
+        .as_deref()
+        .map(validate_redirect_uris)
+        .transpose()
+        .map_err(|e| bad_request(&e))?;
+    let scopes = body
+        .scopes
+        .as_deref()
+        .map(parse_scopes)
+        .transpose()
+        .map_err(|e| bad_request(&e))?
+        .map(|scopes| scopes.iter().map(|s| s.as_str().to_string()).collect());
+    Ok(OAuthClientUpdate {
+        name,
+        redirect_uris,
+        scopes,
+    })
+}
+
+// ─── Router ────────────────────────────────────────────────────────────────
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/developer/clients", get(list_clients).post(create_client))
+        .route(
+            "/developer/clients/{client_id}",
+            patch(update_client).delete(revoke_client),
+        )
+        .route(
+            "/developer/clients/{client_id}/secret",
+            post(rotate_client_secret),
+        )
+        .route("/oauth/authorize", post(authorize))
+        .route("/oauth/token", post(token))
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// Register an OAuth client. The secret is returned only in this response.
+#[utoipa::path(
+    post,
+    path = "/developer/clients",
+    tag = "Developer",
+    request_body = CreateClientRequest,
+    responses(
+        (status = 201, description = "Client registered", body = ClientWithSecretResponse),
+        (status = 400, description = "Invalid name, redirect URI or scope"),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Maximum client limit reached"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn create_client(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: CreateClientRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let name = match validate_client_name(&body.name) {
+        Ok(n) => n,
+        Err(e) => return bad_request(&e),
+    };
+    let redirect_uris = match validate_redirect_uris(&body.redirect_uris) {
+        Ok(u) => u,
+        Err(e) => return bad_request(&e),
+    };
+    let scopes: Vec<String> = match parse_scopes(&body.scopes) {
+        Ok(s) => s.iter().map(|s| s.as_str().to_string()).collect(),
+        Err(e) => return bad_request(&e),
+    };
+
+    let secret = generate_client_secret();
+    match repo
+        .create_client(
+            auth.account_id,
+            &generate_client_id(),
+            name,
+            &redirect_uris,
+            &scopes,
+            &hash_client_secret(&secret),
+        )
+        .await
+    {
+        Ok(record) => {
+            tracing::info!(
+                account_id = %auth.account_id,
+                client_id = %record.client_id,
+                "OAuth client registered"
+            );
+            (
+                StatusCode::CREATED,
+                Json(ClientWithSecretResponse {
+                    client: record.into(),
+                    client_secret: secret,
+                }),
+            )
+                .into_response()
+        }
+        Err(ref e) => developer_repo_error_response(e),
+    }
+}
+
+/// List the caller's OAuth clients. Secrets are never returned.
+#[utoipa::path(
+    get,
+    path = "/developer/clients",
+    tag = "Developer",
+    responses(
+        (status = 200, description = "The caller's clients", body = ClientListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn list_clients(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.list_clients_by_owner(auth.account_id).await {
+        Ok(records) => (
+            StatusCode::OK,
+            Json(ClientListResponse {
+                clients: records.into_iter().map(ClientInfo::from).collect(),
+            }),
+        )
+            .into_response(),
+        Err(ref e) => developer_repo_error_response(e),
+    }
+}
+
+/// Change a client's name, redirect URIs or scopes.
+#[utoipa::path(
+    patch,
+    path = "/developer/clients/{client_id}",
+    tag = "Developer",
+    params(("client_id" = String, Path, description = "Public client ID")),
+    request_body = UpdateClientRequest,
+    responses(
+        (status = 200, description = "Client updated", body = ClientInfo),
+        (status = 400, description = "Invalid name, redirect URI or scope"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Client not found"),
+        (status = 409, description = "Client has been revoked"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn update_client(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    Path(client_id): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: UpdateClientRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let update = match validate_update(&body) {
+        Ok(u) => u,
+        Err(resp) => return resp,
+    };
+    match repo
+        .update_client(auth.account_id, &client_id, &update)
+        .await
+    {
+        Ok(record) => (StatusCode::OK, Json(ClientInfo::from(record))).into_response(),
+        Err(ref e) => developer_repo_error_response(e),
+    }
+}
+
+/// Issue a new client secret. The previous secret keeps working for a grace
+/// period so deployed clients can switch over.
+#[utoipa::path(
+    post,
+    path = "/developer/clients/{client_id}/secret",
+    tag = "Developer",
+    params(("client_id" = String, Path, description = "Public client ID")),
+    responses(
+        (status = 200, description = "New secret issued", body = ClientWithSecretResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Client not found"),
+        (status = 409, description = "Client has been revoked"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn rotate_client_secret(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    Path(client_id): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let secret = generate_client_secret();
+    match repo
+        .rotate_client_secret(
+            auth.account_id,
+            &client_id,
+            &hash_client_secret(&secret),
+            rotation_grace_deadline(Utc::now()),
+        )
+        .await
+    {
+        Ok(record) => {
+            tracing::info!(client_id = %record.client_id, "OAuth client secret rotated");
+            (
+                StatusCode::OK,
+                Json(ClientWithSecretResponse {
+                    client: record.into(),
+                    client_secret: secret,
+                }),
+            )
+                .into_response()
+        }
+        Err(ref e) => developer_repo_error_response(e),
+    }
+}
+
+/// Revoke a client. Both its current and previous secrets stop working, and
+/// every access token issued to it is revoked.
+#[utoipa::path(
+    delete,
+    path = "/developer/clients/{client_id}",
+    tag = "Developer",
+    params(("client_id" = String, Path, description = "Public client ID")),
+    responses(
+        (status = 204, description = "Client revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Client not found"),
+        (status = 409, description = "Client already revoked"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn revoke_client(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    Path(client_id): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.revoke_client(auth.account_id, &client_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(ref e) => developer_repo_error_response(e),
+    }
+}
+
+// ─── Authorization server ──────────────────────────────────────────────────
+
+/// Approve a client on behalf of the signed-in user and issue an
+/// authorization code.
+///
+/// The web client shows its consent screen, then calls this with the
+/// parameters the third-party app put in its authorization link and sends
+/// the user to `redirect_to`.
+#[utoipa::path(
+    post,
+    path = "/oauth/authorize",
+    tag = "Developer",
+    request_body = AuthorizeRequest,
+    responses(
+        (status = 200, description = "Code issued", body = AuthorizeResponse),
+        (status = 400, description = "Unknown client, unregistered redirect URI, bad scope or PKCE parameters"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account is not active"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn authorize(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: AuthorizeRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    if body.response_type != "code" {
+        return bad_request("response_type must be code");
+    }
+    if body.code_challenge_method != PKCE_METHOD_S256 {
+        return bad_request("code_challenge_method must be S256");
+    }
+    if let Err(e) = validate_code_challenge(&body.code_challenge) {
+        return bad_request(&e);
+    }
+
+    let client = match repo.get_client_by_client_id(&body.client_id).await {
+        Ok(c) if c.revoked_at.is_none() => c,
+        Ok(_) | Err(DeveloperRepoError::NotFound) => return bad_request("Unknown client"),
+        Err(ref e) => return developer_repo_error_response(e),
+    };
+    // Never redirect anywhere the client did not register
+    if !client.redirect_uris.contains(&body.redirect_uri) {
+        return bad_request("redirect_uri is not registered for this client");
+    }
+    let scopes: Vec<String> = match body.scope.as_deref() {
+        None => client.scopes.clone(),
+        Some(scope) => match parse_scope_param(scope) {
+            Ok(parsed) => parsed.iter().map(|s| s.as_str().to_string()).collect(),
+            Err(e) => return bad_request(&e),
+        },
+    };
+    if let Some(extra) = scopes.iter().find(|s| !client.scopes.contains(s)) {
+        return bad_request(&format!("client may not request scope: {extra}"));
+    }
+    let Ok(mut redirect_to) = Url::parse(&body.redirect_uri) else {
+        return bad_request("redirect_uri is not registered for this client");
+    };
+
+    let code = generate_authorization_code();
+    let code_hash = hash_authorization_code(&code);
+    let stored = repo
+        .create_authorization_code(&NewAuthorizationCode {
+            code_hash: &code_hash,
+            oauth_client_id: client.id,
+            account_id: auth.account_id,
+            redirect_uri: &body.redirect_uri,
+            scopes: &scopes,
+            code_challenge: &body.code_challenge,
+            expires_at: Utc::now() + Duration::seconds(AUTHORIZATION_CODE_TTL_SECS),
+        })
+        .await;
+    if let Err(ref e) = stored {
+        return developer_repo_error_response(e);
+    }
+
+    {
+        let mut query = redirect_to.query_pairs_mut();
+        query.append_pair("code", &code);
+        if let Some(state) = &body.state {
+            query.append_pair("state", state);
+        }
+    }
+    tracing::info!(
+        account_id = %auth.account_id,
+        client_id = %client.client_id,
+        "OAuth client authorized"
+    );
+    (
+        StatusCode::OK,
+        Json(AuthorizeResponse {
+            code,
+            state: body.state,
+            expires_in: AUTHORIZATION_CODE_TTL_SECS,
+            redirect_to: redirect_to.into(),
+        }),
+    )
+        .into_response()
+}
+
+/// Exchange an authorization code for an access token.
+///
+/// Clients authenticate with `client_id` and `client_secret` in the form
+/// body and prove possession of the PKCE verifier. Each code works once.
+#[utoipa::path(
+    post,
+    path = "/oauth/token",
+    tag = "Developer",
+    request_body(content = TokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Access token issued", body = TokenResponse),
+        (status = 400, description = "Invalid request or grant", body = OAuthErrorResponse),
+        (status = 401, description = "Client authentication failed", body = OAuthErrorResponse),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn token(
+    Extension(repo): Extension<Arc<dyn DeveloperRepo>>,
+    Extension(reputation): Extension<Arc<dyn ReputationRepo>>,
+    Extension(trust): Extension<Arc<dyn TrustRepo>>,
+    reputation_config: Option<Extension<ReputationConfig>>,
+    form: Result<Form<TokenRequest>, FormRejection>,
+) -> impl IntoResponse {
+    let Ok(Form(body)) = form else {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            "missing or malformed parameters",
+        );
+    };
+    if body.grant_type != "authorization_code" {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "unsupported_grant_type",
+            "only authorization_code is supported",
+        );
+    }
+
+    let now = Utc::now();
+    let client = match authenticate_client(repo.as_ref(), &body, now).await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let grant = match repo
+        .consume_authorization_code(&hash_authorization_code(&body.code))
+        .await
+    {
+        Ok(g) => g,
+        Err(DeveloperRepoError::NotFound) => return invalid_grant(),
+        Err(ref e) => return developer_repo_error_response(e),
+    };
+    if grant.oauth_client_id != client.id {
+        return invalid_grant();
+    }
+    if grant.redirect_uri != body.redirect_uri
+        || !pkce_verifier_matches(&body.code_verifier, &grant.code_challenge)
+    {
+        return invalid_grant();
+    }
+    // The client may have dropped scopes since the user approved it
+    let scopes: Vec<String> = grant
+        .scopes
+        .into_iter()
+        .filter(|s| client.scopes.contains(s))
+        .collect();
+    if scopes.is_empty() {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_scope",
+            "the client no longer holds any approved scope",
+        );
+    }
+
+    let reputation_config = reputation_config.map(|Extension(c)| c).unwrap_or_default();
+    let reputation_tier = match explain_account(
+        reputation.as_ref(),
+        trust.as_ref(),
+        &reputation_config,
+        grant.account_id,
+    )
+    .await
+    {
+        Ok(explanation) => explanation.tier,
+        Err(e) => {
+            tracing::error!("Failed to score account for OAuth token: {e}");
+            return internal_error();
+        }
+    };
+    let access_token = generate_api_token();
+    if let Err(ref e) = repo
+        .issue_access_token(
+            &client,
+            grant.account_id,
+            &hash_api_token(&access_token),
+            &scopes,
+            now + Duration::seconds(ACCESS_TOKEN_TTL_SECS),
+        )
+        .await
+    {
+        return developer_repo_error_response(e);
+    }
+
+    tracing::info!(
+        account_id = %grant.account_id,
+        client_id = %client.client_id,
+        "OAuth access token issued"
+    );
+    (
+        StatusCode::OK,
+        [(header::CACHE_CONTROL, "no-store")],
+        Json(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: ACCESS_TOKEN_TTL_SECS,
+            scope: scopes.join(" "),
+            account_id: grant.account_id,
+            reputation_tier,
+        }),
+    )
+        .into_response()
+}
+
+#[allow(clippy::result_large_err)]
+async fn authenticate_client(
+    repo: &dyn DeveloperRepo,
+    body: &TokenRequest,
+    now: DateTime<Utc>,
+) -> Result<OAuthClientRecord, axum::response::Response> {
+    match repo.get_client_by_client_id(&body.client_id).await {
+        Ok(c) if secret_matches(&c, &body.client_secret, now) => Ok(c),
+        Ok(_) | Err(DeveloperRepoError::NotFound) => Err(oauth_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "client authentication failed",
+        )),
+        Err(ref e) => Err(developer_repo_error_response(e)),
+    }
+}
+
+fn oauth_error(status: StatusCode, error: &str, description: &str) -> axum::response::Response {
+    (
+        status,
+        [(header::CACHE_CONTROL, "no-store")],
+        Json(OAuthErrorResponse {
+            error: error.to_string(),
+            error_description: description.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Same answer for unknown, expired, reused and mismatched codes.
+fn invalid_grant() -> axum::response::Response {
+    oauth_error(
+        StatusCode::BAD_REQUEST,
+        "invalid_grant",
+        "authorization code is invalid or expired",
+    )
+}
+
+fn developer_repo_error_response(e: &DeveloperRepoError) -> axum::response::Response {
+    match e {
+        DeveloperRepoError::NotFound => not_found("Client not found"),
+        DeveloperRepoError::AlreadyRevoked => conflict("Client has been revoked"),
+        DeveloperRepoError::MaxClientsReached => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(crate::http::ErrorResponse {
+                error: "Maximum client limit reached".to_string(),
+            }),
+        )
+            .into_response(),
+        DeveloperRepoError::DuplicateClientId => conflict("Client ID collision — please retry"),
+        DeveloperRepoError::Database(inner) => {
+            tracing::error!("Developer repo database error: {inner}");
+            internal_error()
+        }
+    }
+}
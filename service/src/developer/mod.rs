@@ -0,0 +1,315 @@
+//! Third-party OAuth client registration (the developer portal).
+//!
+//! Any signed-in account can register clients under `/developer/clients`.
+//! Each client gets a public `client_id` and a `client_secret` that is shown
+//! once; only its SHA-256 is stored. Clients declare the redirect URIs and
+//! [`ApiTokenScope`]s they may later request from the authorization server.
+//!
+//! Rotating a secret issues a new one immediately and keeps the previous one
+//! valid for [`SECRET_ROTATION_GRACE_HOURS`], so a deployed client can be
+//! updated without downtime. [`secret_matches`] is the check the
+//! authorization server uses at the token endpoint.
+//!
+//! The authorization server implements the code flow with PKCE (`S256`
+//! only). A signed-in user approves a client at `/oauth/authorize`, which
+//! returns a single-use code valid for [`AUTHORIZATION_CODE_TTL_SECS`]. The
+//! client exchanges it at `/oauth/token` for an opaque bearer token — an
+//! ordinary scoped API token tagged with the client — along with the user's
+//! account ID and [`ReputationTier`](crate::reputation::ReputationTier).
+
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use tc_crypto::encode_base64url;
+
+use crate::identity::http::token_auth::ApiTokenScope;
+
+pub mod http;
+pub mod repo;
+
+use repo::OAuthClientRecord;
+
+/// Prefix of every `client_id`.
+pub const CLIENT_ID_PREFIX: &str = "tcc_";
+
+/// Prefix of every client secret.
+pub const CLIENT_SECRET_PREFIX: &str = "tcs_";
+
+/// Hours the previous secret keeps working after a rotation.
+pub const SECRET_ROTATION_GRACE_HOURS: i64 = 24;
+
+/// Most redirect URIs one client may register.
+pub const MAX_REDIRECT_URIS: usize = 10;
+
+/// Longest accepted redirect URI, in bytes.
+pub const MAX_REDIRECT_URI_LEN: usize = 2000;
+
+/// Longest client name, in characters.
+pub const MAX_CLIENT_NAME_LENGTH: usize = 64;
+
+/// Prefix of every authorization code.
+pub const AUTHORIZATION_CODE_PREFIX: &str = "tca_";
+
+/// Seconds an authorization code may wait before being exchanged.
+pub const AUTHORIZATION_CODE_TTL_SECS: i64 = 600;
+
+/// Seconds an access token issued at the token endpoint stays valid.
+pub const ACCESS_TOKEN_TTL_SECS: i64 = 3600;
+
+/// The only PKCE challenge method accepted; `plain` offers no protection.
+pub const PKCE_METHOD_S256: &str = "S256";
+
+/// Generate a new public client identifier (128 random bits).
+#[must_use]
+pub fn generate_client_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{CLIENT_ID_PREFIX}{}", encode_base64url(&bytes))
+}
+
+/// Generate a new plaintext client secret (256 random bits).
+#[must_use]
+pub fn generate_client_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{CLIENT_SECRET_PREFIX}{}", encode_base64url(&bytes))
+}
+
+/// Hash a plaintext client secret for storage and comparison.
+#[must_use]
+pub fn hash_client_secret(secret: &str) -> Vec<u8> {
+    Sha256::digest(secret.as_bytes()).to_vec()
+}
+
+/// Generate a new plaintext authorization code (256 random bits).
+#[must_use]
+pub fn generate_authorization_code() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{AUTHORIZATION_CODE_PREFIX}{}", encode_base64url(&bytes))
+}
+
+/// Hash a plaintext authorization code for storage and lookup.
+#[must_use]
+pub fn hash_authorization_code(code: &str) -> Vec<u8> {
+    Sha256::digest(code.as_bytes()).to_vec()
+}
+
+/// Check a PKCE `S256` code challenge: 43-128 characters of base64url.
+///
+/// # Errors
+///
+/// Returns a message for a malformed challenge.
+pub fn validate_code_challenge(challenge: &str) -> Result<(), String> {
+    let valid_chars = challenge
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~'));
+    if !(43..=128).contains(&challenge.len()) || !valid_chars {
+        return Err("code_challenge must be 43-128 unreserved characters".to_string());
+    }
+    Ok(())
+}
+
+/// Whether `verifier` answers `challenge` under the `S256` method
+/// (RFC 7636 §4.6).
+#[must_use]
+pub fn pkce_verifier_matches(verifier: &str, challenge: &str) -> bool {
+    validate_code_challenge(verifier).is_ok()
+        && encode_base64url(&Sha256::digest(verifier.as_bytes())) == challenge
+}
+
+/// Parse a space-separated OAuth `scope` parameter into known scopes.
+///
+/// # Errors
+///
+/// Returns a message for an empty parameter or an unknown scope.
+pub fn parse_scope_param(scope: &str) -> Result<Vec<ApiTokenScope>, String> {
+    let scopes: Vec<String> = scope.split_whitespace().map(str::to_string).collect();
+    parse_scopes(&scopes)
+}
+
+/// When a secret rotated at `now` stops accepting the previous secret.
+#[must_use]
+pub fn rotation_grace_deadline(now: DateTime<Utc>) -> DateTime<Utc> {
+    now + Duration::hours(SECRET_ROTATION_GRACE_HOURS)
+}
+
+/// Whether `secret` authenticates `client` at `now`.
+///
+/// Accepts the current secret, or the previous one until its grace period
+/// ends. Revoked clients never match. Hashes are compared in constant time.
+#[must_use]
+pub fn secret_matches(client: &OAuthClientRecord, secret: &str, now: DateTime<Utc>) -> bool {
+    if client.revoked_at.is_some() {
+        return false;
+    }
+    let hash = hash_client_secret(secret);
+    if bool::from(hash.ct_eq(&client.secret_hash)) {
+        return true;
+    }
+    match (
+        &client.previous_secret_hash,
+        client.previous_secret_expires_at,
+    ) {
+        (Some(previous), Some(expires_at)) => now < expires_at && bool::from(hash.ct_eq(previous)),
+        _ => false,
+    }
+}
+
+/// Check a client name and return it trimmed.
+///
+/// # Errors
+///
+/// Returns a message when the name is empty or too long.
+pub fn validate_client_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_CLIENT_NAME_LENGTH {
+        return Err(format!(
+            "name must be 1-{MAX_CLIENT_NAME_LENGTH} characters"
+        ));
+    }
+    Ok(name)
+}
+
+/// Check redirect URIs and return them deduplicated in the order given.
+///
+/// Each must be an absolute `https` URL without a fragment; plain `http` is
+/// allowed only for loopback hosts, for native apps and local development.
+///
+/// # Errors
+///
+/// Returns a message naming the first invalid URI.
+pub fn validate_redirect_uris(uris: &[String]) -> Result<Vec<String>, String> {
+    if uris.is_empty() || uris.len() > MAX_REDIRECT_URIS {
+        return Err(format!(
+            "redirect_uris must list 1-{MAX_REDIRECT_URIS} URIs"
+        ));
+    }
+    let mut accepted: Vec<String> = Vec::with_capacity(uris.len());
+    for uri in uris {
+        let uri = uri.trim();
+        if uri.len() > MAX_REDIRECT_URI_LEN {
+            return Err(format!(
+                "redirect URI must be at most {MAX_REDIRECT_URI_LEN} bytes"
+            ));
+        }
+        let parsed = Url::parse(uri).map_err(|_| format!("invalid redirect URI: {uri}"))?;
+        if parsed.fragment().is_some() {
+            return Err(format!("redirect URI must not have a fragment: {uri}"));
+        }
+        let loopback = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        match parsed.scheme() {
+            "https" => {}
+            "http" if loopback => {}
+            _ => return Err(format!("redirect URI must use https: {uri}")),
+        }
+        if !accepted.iter().any(|u| u == uri) {
+            accepted.push(uri.to_string());
+        }
+    }
+    Ok(accepted)
+}
+
+/// Parse requested scopes, sorted and deduplicated.
+///
+/// # Errors
+///
+/// Returns a message for an empty list or an unknown scope.
+pub fn parse_scopes(scopes: &[String]) -> Result<Vec<ApiTokenScope>, String> {
+    if scopes.is_empty() {
+        return Err("at least one scope is required".to_string());
+    }
+    let mut parsed = scopes
+        .iter()
+        .map(|s| ApiTokenScope::parse(s).ok_or_else(|| format!("unknown scope: {s}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    parsed.sort_by_key(|s| s.as_str());
+    parsed.dedup();
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn client(secret: &str) -> OAuthClientRecord {
+        OAuthClientRecord {
+            id: Uuid::new_v4(),
+            owner_account_id: Uuid::new_v4(),
+            client_id: generate_client_id(),
+            name: "Example".to_string(),
+            redirect_uris: vec!["https://example.org/callback".to_string()],
+            scopes: vec!["trust:read".to_string()],
+            secret_hash: hash_client_secret(secret),
+            previous_secret_hash: None,
+            previous_secret_expires_at: None,
+            secret_rotated_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn previous_secret_works_until_grace_ends() {
+        let now = Utc::now();
+        let old = generate_client_secret();
+        let new = generate_client_secret();
+        let mut record = client(&new);
+        record.previous_secret_hash = Some(hash_client_secret(&old));
+        record.previous_secret_expires_at = Some(rotation_grace_deadline(now));
+
+        assert!(secret_matches(&record, &new, now));
+        assert!(secret_matches(&record, &old, now));
+        let later = now + Duration::hours(SECRET_ROTATION_GRACE_HOURS);
+        assert!(!secret_matches(&record, &old, later));
+        assert!(secret_matches(&record, &new, later));
+
+        record.revoked_at = Some(now);
+        assert!(!secret_matches(&record, &new, now));
+    }
+
+    #[test]
+    fn redirect_uris_require_https_except_loopback() {
+        let ok = validate_redirect_uris(&[
+            "https://app.example.org/cb".to_string(),
+            "http://127.0.0.1:8080/cb".to_string(),
+            "https://app.example.org/cb".to_string(),
+        ])
+        .expect("valid");
+        assert_eq!(ok.len(), 2);
+
+        for bad in [
+            "http://app.example.org/cb",
+            "https://app.example.org/cb#frag",
+            "/relative",
+            "javascript:alert(1)",
+        ] {
+            assert!(validate_redirect_uris(&[bad.to_string()]).is_err(), "{bad}");
+        }
+        assert!(validate_redirect_uris(&[]).is_err());
+    }
+
+    #[test]
+    fn pkce_s256_matches_rfc_7636_example() {
+        let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+        let challenge = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+        assert!(validate_code_challenge(challenge).is_ok());
+        assert!(pkce_verifier_matches(verifier, challenge));
+        assert!(!pkce_verifier_matches(challenge, challenge));
+        assert!(!pkce_verifier_matches("short", challenge));
+        assert!(validate_code_challenge("not a challenge").is_err());
+    }
+
+    #[test]
+    fn generated_credentials_are_prefixed_and_distinct() {
+        let secret = generate_client_secret();
+        assert!(secret.starts_with(CLIENT_SECRET_PREFIX));
+        assert_ne!(secret, generate_client_secret());
+        assert!(generate_client_id().starts_with(CLIENT_ID_PREFIX));
+        assert!(generate_authorization_code().starts_with(AUTHORIZATION_CODE_PREFIX));
+    }
+}
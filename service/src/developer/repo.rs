@@ -0,0 +1,628 @@
+//! Persistence for registered OAuth clients
+//!
+//! Every mutation includes `owner_account_id` in its WHERE clause, so the
+//! ownership check and the write happen atomically, as with API tokens.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{instrument, map_unique_violation, RepoError, RepoErrorKind};
+
+/// Most active (non-revoked) clients one account may register.
+pub const MAX_CLIENTS_PER_ACCOUNT: i64 = 10;
+
+/// One registered client.
+#[derive(Debug, Clone)]
+pub struct OAuthClientRecord {
+    pub id: Uuid,
+    pub owner_account_id: Uuid,
+    pub client_id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    pub secret_hash: Vec<u8>,
+    /// Accepted until `previous_secret_expires_at` after a rotation.
+    pub previous_secret_hash: Option<Vec<u8>>,
+    pub previous_secret_expires_at: Option<DateTime<Utc>>,
+    pub secret_rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields a client's owner may change. `None` leaves a field as is.
+#[derive(Debug, Clone, Default)]
+pub struct OAuthClientUpdate {
+    pub name: Option<String>,
+    pub redirect_uris: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeveloperRepoError {
+    #[error("OAuth client not found")]
+    NotFound,
+    #[error("OAuth client has been revoked")]
+    AlreadyRevoked,
+    #[error("maximum OAuth client limit reached")]
+    MaxClientsReached,
+    #[error("client ID already in use")]
+    DuplicateClientId,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for DeveloperRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::AlreadyRevoked | Self::DuplicateClientId => RepoErrorKind::Conflict,
+            Self::MaxClientsReached => RepoErrorKind::LimitReached,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OAuthClientRow {
+    id: Uuid,
+    owner_account_id: Uuid,
+    client_id: String,
+    name: String,
+    redirect_uris: Vec<String>,
+    scopes: Vec<String>,
+    secret_hash: Vec<u8>,
+    previous_secret_hash: Option<Vec<u8>>,
+    previous_secret_expires_at: Option<DateTime<Utc>>,
+    secret_rotated_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<OAuthClientRow> for OAuthClientRecord {
+    fn from(row: OAuthClientRow) -> Self {
+        Self {
+            id: row.id,
+            owner_account_id: row.owner_account_id,
+            client_id: row.client_id,
+            name: row.name,
+            redirect_uris: row.redirect_uris,
+            scopes: row.scopes,
+            secret_hash: row.secret_hash,
+            previous_secret_hash: row.previous_secret_hash,
+            previous_secret_expires_at: row.previous_secret_expires_at,
+            secret_rotated_at: row.secret_rotated_at,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const CLIENT_COLUMNS: &str = r"
+    id, owner_account_id, client_id, name, redirect_uris, scopes, secret_hash,
+    previous_secret_hash, previous_secret_expires_at, secret_rotated_at, revoked_at,
+    created_at, updated_at
+";
+
+/// Register a client.
+///
+/// Locks the owner's account row so concurrent registrations cannot exceed
+/// [`MAX_CLIENTS_PER_ACCOUNT`].
+///
+/// # Errors
+///
+/// Returns `NotFound` if the owner does not exist, `MaxClientsReached` at the
+/// limit, `DuplicateClientId` if the generated ID collides.
+pub async fn create_client(
+    pool: &PgPool,
+    owner_account_id: Uuid,
+    client_id: &str,
+    name: &str,
+    redirect_uris: &[String],
+    scopes: &[String],
+    secret_hash: &[u8],
+) -> Result<OAuthClientRecord, DeveloperRepoError> {
+    let mut tx = pool.begin().await?;
+    let locked = sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(owner_account_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if locked.is_none() {
+        return Err(DeveloperRepoError::NotFound);
+    }
+
+    let row = sqlx::query_as::<_, OAuthClientRow>(&format!(
+        r"
+        INSERT INTO oauth_clients
+            (owner_account_id, client_id, name, redirect_uris, scopes, secret_hash)
+        SELECT $1, $2, $3, $4, $5, $6
+        WHERE (SELECT COUNT(*) FROM oauth_clients
+               WHERE owner_account_id = $1 AND revoked_at IS NULL) < $7
+        RETURNING {CLIENT_COLUMNS}
+        "
+    ))
+    .bind(owner_account_id)
+    .bind(client_id)
+    .bind(name)
+    .bind(redirect_uris)
+    .bind(scopes)
+    .bind(secret_hash)
+    .bind(MAX_CLIENTS_PER_ACCOUNT)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        map_unique_violation(e, |constraint| match constraint {
+            "oauth_clients_client_id_key" | "oauth_clients_secret_hash_key" => {
+                Some(DeveloperRepoError::DuplicateClientId)
+            }
+            _ => None,
+        })
+    })?
+    .ok_or(DeveloperRepoError::MaxClientsReached)?;
+
+    tx.commit().await?;
+    Ok(row.into())
+}
+
+/// Every client the account registered, including revoked ones, newest first.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure.
+pub async fn list_clients_by_owner(
+    pool: &PgPool,
+    owner_account_id: Uuid,
+) -> Result<Vec<OAuthClientRecord>, DeveloperRepoError> {
+    let rows = sqlx::query_as::<_, OAuthClientRow>(&format!(
+        "SELECT {CLIENT_COLUMNS} FROM oauth_clients
+         WHERE owner_account_id = $1
+         ORDER BY created_at DESC, id DESC"
+    ))
+    .bind(owner_account_id)
+    .fetch_all(instrument(pool, "oauth_clients.list_by_owner"))
+    .await?;
+    Ok(rows.into_iter().map(OAuthClientRecord::from).collect())
+}
+
+/// Look up a client by its public `client_id`, for the authorization server.
+///
+/// # Errors
+///
+/// Returns `NotFound` for an unknown `client_id`.
+pub async fn get_client_by_client_id(
+    pool: &PgPool,
+    client_id: &str,
+) -> Result<OAuthClientRecord, DeveloperRepoError> {
+    sqlx::query_as::<_, OAuthClientRow>(&format!(
+        "SELECT {CLIENT_COLUMNS} FROM oauth_clients WHERE client_id = $1"
+    ))
+    .bind(client_id)
+    .fetch_optional(instrument(pool, "oauth_clients.by_client_id"))
+    .await?
+    .map(OAuthClientRecord::from)
+    .ok_or(DeveloperRepoError::NotFound)
+}
+
+/// Distinguish "not yours / missing" from "revoked" after an UPDATE matched
+/// no rows.
+async fn missing_or_revoked(
+    pool: &PgPool,
+    owner_account_id: Uuid,
+    client_id: &str,
+) -> DeveloperRepoError {
+    let revoked: Result<Option<bool>, _> = sqlx::query_scalar(
+        "SELECT revoked_at IS NOT NULL FROM oauth_clients
+         WHERE client_id = $1 AND owner_account_id = $2",
+    )
+    .bind(client_id)
+    .bind(owner_account_id)
+    .fetch_optional(pool)
+    .await;
+    match revoked {
+        Ok(Some(true)) => DeveloperRepoError::AlreadyRevoked,
+        Ok(_) => DeveloperRepoError::NotFound,
+        Err(e) => e.into(),
+    }
+}
+
+/// Change a client's name, redirect URIs or scopes.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the account owns no such client, `AlreadyRevoked`
+/// if it was revoked.
+pub async fn update_client(
+    pool: &PgPool,
+    owner_account_id: Uuid,
+    client_id: &str,
+    update: &OAuthClientUpdate,
+) -> Result<OAuthClientRecord, DeveloperRepoError> {
+    let row = sqlx::query_as::<_, OAuthClientRow>(&format!(
+        r"
+        UPDATE oauth_clients
+        SET name = COALESCE($3, name),
+            redirect_uris = COALESCE($4, redirect_uris),
+            scopes = COALESCE($5, scopes),
+            updated_at = now()
+        WHERE client_id = $1 AND owner_account_id = $2 AND revoked_at IS NULL
+        RETURNING {CLIENT_COLUMNS}
+        "
+    ))
+    .bind(client_id)
+    .bind(owner_account_id)
+    .bind(update.name.as_deref())
+    .bind(update.redirect_uris.as_deref())
+    .bind(update.scopes.as_deref())
+    .fetch_optional(instrument(pool, "oauth_clients.update"))
+    .await?;
+    match row {
+        Some(row) => Ok(row.into()),
+        None => Err(missing_or_revoked(pool, owner_account_id, client_id).await),
+    }
+}
+
+/// Replace a client's secret, keeping the current one valid until
+/// `previous_expires_at`.
+///
+/// A second rotation inside the grace period drops the oldest secret.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the account owns no such client, `AlreadyRevoked`
+/// if it was revoked.
+pub async fn rotate_client_secret(
+    pool: &PgPool,
+    owner_account_id: Uuid,
+    client_id: &str,
+    secret_hash: &[u8],
+    previous_expires_at: DateTime<Utc>,
+) -> Result<OAuthClientRecord, DeveloperRepoError> {
+    let row = sqlx::query_as::<_, OAuthClientRow>(&format!(
+        r"
+        UPDATE oauth_clients
+        SET previous_secret_hash = secret_hash,
+            previous_secret_expires_at = $4,
+            secret_hash = $3,
+            secret_rotated_at = now(),
+            updated_at = now()
+        WHERE client_id = $1 AND owner_account_id = $2 AND revoked_at IS NULL
+        RETURNING {CLIENT_COLUMNS}
+        "
+    ))
+    .bind(client_id)
+    .bind(owner_account_id)
+    .bind(secret_hash)
+    .bind(previous_expires_at)
+    .fetch_optional(instrument(pool, "oauth_clients.rotate_secret"))
+    .await?;
+    match row {
+        Some(row) => Ok(row.into()),
+        None => Err(missing_or_revoked(pool, owner_account_id, client_id).await),
+    }
+}
+
+/// Revoke a client. Neither secret works afterwards, and every access token
+/// issued to it is revoked in the same transaction.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the account owns no such client, `AlreadyRevoked`
+/// if it was already revoked.
+pub async fn revoke_client(
+    pool: &PgPool,
+    owner_account_id: Uuid,
+    client_id: &str,
+) -> Result<(), DeveloperRepoError> {
+    let mut tx = pool.begin().await?;
+    let revoked: Option<Uuid> = sqlx::query_scalar(
+        "UPDATE oauth_clients SET revoked_at = now(), updated_at = now()
+         WHERE client_id = $1 AND owner_account_id = $2 AND revoked_at IS NULL
+         RETURNING id",
+    )
+    .bind(client_id)
+    .bind(owner_account_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(id) = revoked else {
+        return Err(missing_or_revoked(pool, owner_account_id, client_id).await);
+    };
+    sqlx::query(
+        "UPDATE api_tokens SET revoked_at = now()
+         WHERE oauth_client_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+// ─── Authorization codes ───────────────────────────────────────────────────
+
+/// A code the user approved, as read back when it is exchanged.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AuthorizationCodeRecord {
+    /// Row ID of the client the code was issued to (not the public `client_id`)
+    pub oauth_client_id: Uuid,
+    pub account_id: Uuid,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    pub code_challenge: String,
+}
+
+/// A code to store when the user approves a client.
+#[derive(Debug, Clone, Copy)]
+pub struct NewAuthorizationCode<'a> {
+    pub code_hash: &'a [u8],
+    /// Row ID of the client
+    pub oauth_client_id: Uuid,
+    pub account_id: Uuid,
+    pub redirect_uri: &'a str,
+    pub scopes: &'a [String],
+    pub code_challenge: &'a str,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Store an approved authorization code.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure.
+pub async fn create_authorization_code(
+    pool: &PgPool,
+    code: &NewAuthorizationCode<'_>,
+) -> Result<(), DeveloperRepoError> {
+    sqlx::query(
+        "INSERT INTO oauth_authorization_codes
+             (code_hash, client_id, account_id, redirect_uri, scopes, code_challenge, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(code.code_hash)
+    .bind(code.oauth_client_id)
+    .bind(code.account_id)
+    .bind(code.redirect_uri)
+    .bind(code.scopes)
+    .bind(code.code_challenge)
+    .bind(code.expires_at)
+    .execute(instrument(pool, "oauth_authorization_codes.create"))
+    .await?;
+    Ok(())
+}
+
+/// Mark an unexpired, unused code as used and return it.
+///
+/// A code is consumed even if the exchange later fails, so each code can be
+/// tried at most once.
+///
+/// # Errors
+///
+/// Returns `NotFound` for an unknown, expired or already-used code.
+pub async fn consume_authorization_code(
+    pool: &PgPool,
+    code_hash: &[u8],
+) -> Result<AuthorizationCodeRecord, DeveloperRepoError> {
+    sqlx::query_as::<_, AuthorizationCodeRecord>(
+        "UPDATE oauth_authorization_codes SET used_at = now()
+         WHERE code_hash = $1 AND used_at IS NULL AND expires_at > now()
+         RETURNING client_id AS oauth_client_id, account_id, redirect_uri, scopes, code_challenge",
+    )
+    .bind(code_hash)
+    .fetch_optional(instrument(pool, "oauth_authorization_codes.consume"))
+    .await?
+    .ok_or(DeveloperRepoError::NotFound)
+}
+
+/// Store an access token issued to a client on behalf of `account_id`.
+///
+/// The token is an `api_tokens` row tagged with the client, so it is
+/// revoked along with the client and does not count against the account's
+/// personal token limit.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure.
+pub async fn issue_access_token(
+    pool: &PgPool,
+    client: &OAuthClientRecord,
+    account_id: Uuid,
+    token_hash: &[u8],
+    scopes: &[String],
+    expires_at: DateTime<Utc>,
+) -> Result<(), DeveloperRepoError> {
+    sqlx::query(
+        "INSERT INTO api_tokens (account_id, name, token_hash, scopes, expires_at, oauth_client_id)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(account_id)
+    .bind(format!("OAuth: {}", client.name))
+    .bind(token_hash)
+    .bind(scopes)
+    .bind(expires_at)
+    .bind(client.id)
+    .execute(instrument(pool, "api_tokens.issue_oauth"))
+    .await?;
+    Ok(())
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// OAuth client persistence for the developer portal and authorization
+/// server handlers.
+#[async_trait]
+pub trait DeveloperRepo: Send + Sync {
+    async fn create_client(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+        name: &str,
+        redirect_uris: &[String],
+        scopes: &[String],
+        secret_hash: &[u8],
+    ) -> Result<OAuthClientRecord, DeveloperRepoError>;
+
+    async fn list_clients_by_owner(
+        &self,
+        owner_account_id: Uuid,
+    ) -> Result<Vec<OAuthClientRecord>, DeveloperRepoError>;
+
+    async fn get_client_by_client_id(
+        &self,
+        client_id: &str,
+    ) -> Result<OAuthClientRecord, DeveloperRepoError>;
+
+    async fn update_client(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+        update: &OAuthClientUpdate,
+    ) -> Result<OAuthClientRecord, DeveloperRepoError>;
+
+    async fn rotate_client_secret(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+        secret_hash: &[u8],
+        previous_expires_at: DateTime<Utc>,
+    ) -> Result<OAuthClientRecord, DeveloperRepoError>;
+
+    async fn revoke_client(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+    ) -> Result<(), DeveloperRepoError>;
+
+    async fn create_authorization_code(
+        &self,
+        code: &NewAuthorizationCode<'_>,
+    ) -> Result<(), DeveloperRepoError>;
+
+    async fn consume_authorization_code(
+        &self,
+        code_hash: &[u8],
+    ) -> Result<AuthorizationCodeRecord, DeveloperRepoError>;
+
+    async fn issue_access_token(
+        &self,
+        client: &OAuthClientRecord,
+        account_id: Uuid,
+        token_hash: &[u8],
+        scopes: &[String],
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DeveloperRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`DeveloperRepo`].
+pub struct PgDeveloperRepo {
+    pool: PgPool,
+}
+
+impl PgDeveloperRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DeveloperRepo for PgDeveloperRepo {
+    async fn create_client(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+        name: &str,
+        redirect_uris: &[String],
+        scopes: &[String],
+        secret_hash: &[u8],
+    ) -> Result<OAuthClientRecord, DeveloperRepoError> {
+        create_client(
+            &self.pool,
+            owner_account_id,
+            client_id,
+            name,
+            redirect_uris,
+            scopes,
+            secret_hash,
+        )
+        .await
+    }
+
+    async fn list_clients_by_owner(
+        &self,
+        owner_account_id: Uuid,
+    ) -> Result<Vec<OAuthClientRecord>, DeveloperRepoError> {
+        list_clients_by_owner(&self.pool, owner_account_id).await
+    }
+
+    async fn get_client_by_client_id(
+        &self,
+        client_id: &str,
+    ) -> Result<OAuthClientRecord, DeveloperRepoError> {
+        get_client_by_client_id(&self.pool, client_id).await
+    }
+
+    async fn update_client(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+        update: &OAuthClientUpdate,
+    ) -> Result<OAuthClientRecord, DeveloperRepoError> {
+        update_client(&self.pool, owner_account_id, client_id, update).await
+    }
+
+    async fn rotate_client_secret(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+        secret_hash: &[u8],
+        previous_expires_at: DateTime<Utc>,
+    ) -> Result<OAuthClientRecord, DeveloperRepoError> {
+        rotate_client_secret(
+            &self.pool,
+            owner_account_id,
+            client_id,
+            secret_hash,
+            previous_expires_at,
+        )
+        .await
+    }
+
+    async fn revoke_client(
+        &self,
+        owner_account_id: Uuid,
+        client_id: &str,
+    ) -> Result<(), DeveloperRepoError> {
+        revoke_client(&self.pool, owner_account_id, client_id).await
+    }
+
+    async fn create_authorization_code(
+        &self,
+        code: &NewAuthorizationCode<'_>,
+    ) -> Result<(), DeveloperRepoError> {
+        create_authorization_code(&self.pool, code).await
+    }
+
+    async fn consume_authorization_code(
+        &self,
+        code_hash: &[u8],
+    ) -> Result<AuthorizationCodeRecord, DeveloperRepoError> {
+        consume_authorization_code(&self.pool, code_hash).await
+    }
+
+    async fn issue_access_token(
+        &self,
+        client: &OAuthClientRecord,
+        account_id: Uuid,
+        token_hash: &[u8],
+        scopes: &[String],
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DeveloperRepoError> {
+        issue_access_token(
+            &self.pool, client, account_id, token_hash, scopes, expires_at,
+        )
+        .await
+    }
+}
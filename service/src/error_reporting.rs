@@ -0,0 +1,565 @@
+//! Optional error reporting to a Sentry-compatible collector.
+//!
+//! Two sources feed the reporter:
+//!
+//! - [`ErrorReportingLayer`], a `tracing` layer that forwards every `ERROR`
+//!   event emitted by this service. Handlers already log the underlying error
+//!   before returning a 500, so this captures handler and worker failures
+//!   without touching each call site.
+//! - [`install_panic_hook`], which reports panics (including panics inside
+//!   spawned worker tasks) before the default hook runs.
+//!
+//! Every report passes through [`scrub_value`] before it leaves the process:
+//! fields whose names suggest key material, signatures, certificates or backup
+//! envelopes are replaced, and long base64/hex runs are redacted from free
+//! text. Reports are fingerprinted by call site so repeated failures group
+//! together regardless of the error text.
+//!
+//! Sending is fire-and-forget over a bounded channel, mirroring
+//! [`crate::events`]: a slow or unreachable collector never blocks a request.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use uuid::Uuid;
+
+use crate::build_info::BuildInfo;
+use crate::config::ErrorReportingConfig;
+
+/// Reports queued while the collector is slow. Further reports are dropped.
+const REPORT_BUFFER: usize = 256;
+
+/// Placeholder for redacted values.
+const SCRUBBED: &str = "[scrubbed]";
+
+/// Field-name fragments whose values are always redacted.
+const SENSITIVE_FIELD_FRAGMENTS: &[&str] = &[
+    "signature",
+    "certificate",
+    "pubkey",
+    "key",
+    "secret",
+    "password",
+    "token",
+    "envelope",
+    "backup",
+    "encrypted",
+    "nonce",
+    "authorization",
+    "cookie",
+];
+
+/// Base64/hex runs at least this long are redacted from free text. Long
+/// enough to keep 22-character KIDs readable, short enough to catch 32-byte
+/// public keys (43 chars) and everything larger.
+const MIN_SCRUB_RUN: usize = 40;
+
+/// Crate-name prefixes whose `ERROR` events are reported. Dependencies log
+/// their own errors at ERROR too, but those surface through our own logs.
+const REPORTED_TARGET_PREFIXES: &[&str] = &["tinycongress_api", "tc_"];
+
+// ─── Reports ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportLevel {
+    Error,
+    Fatal,
+}
+
+impl ReportLevel {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Fatal => "fatal",
+        }
+    }
+}
+
+/// A single error occurrence, before scrubbing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub level: ReportLevel,
+    /// Human-readable message (the log line or panic payload).
+    pub message: String,
+    /// Module or logger the report came from.
+    pub logger: String,
+    /// Grouping key; reports with the same fingerprint are one issue.
+    pub fingerprint: Vec<String>,
+    /// Structured context (log fields, panic location).
+    pub extra: Map<String, Value>,
+}
+
+/// Sink for error reports.
+pub trait ErrorReporter: Send + Sync {
+    /// Queue a report. Must not block.
+    fn capture(&self, report: ErrorReport);
+}
+
+/// Reporter used when error reporting is disabled.
+pub struct NoopErrorReporter;
+
+impl ErrorReporter for NoopErrorReporter {
+    fn capture(&self, _report: ErrorReport) {}
+}
+
+// ─── Scrubbing ─────────────────────────────────────────────────────────────
+
+fn is_sensitive_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_FIELD_FRAGMENTS.iter().any(|f| name.contains(f))
+}
+
+const fn is_encoded_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+' | '/' | '=')
+}
+
+fn is_uuid(run: &str) -> bool {
+    Uuid::parse_str(run).is_ok()
+}
+
+/// Redact long base64/base64url/hex runs (keys, signatures, envelopes) from
+/// free text. UUIDs are kept.
+#[must_use]
+pub fn scrub_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut run = String::new();
+    let flush = |run: &mut String, out: &mut String| {
+        if run.len() >= MIN_SCRUB_RUN && !is_uuid(run) {
+            out.push_str(SCRUBBED);
+        } else {
+            out.push_str(run);
+        }
+        run.clear();
+    };
+    for c in text.chars() {
+        if is_encoded_char(c) {
+            run.push(c);
+        } else {
+            flush(&mut run, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut run, &mut out);
+    out
+}
+
+/// Recursively scrub a JSON value: sensitive fields are replaced outright and
+/// all remaining strings pass through [`scrub_text`].
+pub fn scrub_value(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = scrub_text(s),
+        Value::Array(items) => items.iter_mut().for_each(scrub_value),
+        Value::Object(map) => {
+            for (name, v) in map.iter_mut() {
+                if is_sensitive_field(name) {
+                    *v = Value::String(SCRUBBED.to_string());
+                } else {
+                    scrub_value(v);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// ─── Sentry envelope ───────────────────────────────────────────────────────
+
+/// Parsed Sentry DSN (`https://<public_key>@<host>/<project_id>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentryDsn {
+    raw: String,
+    public_key: String,
+    envelope_url: String,
+}
+
+impl SentryDsn {
+    /// Parse a DSN.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the DSN is malformed.
+    pub fn parse(dsn: &str) -> Result<Self, String> {
+        let (scheme, rest) = dsn
+            .split_once("://")
+            .ok_or_else(|| "missing scheme".to_string())?;
+        if scheme != "https" && scheme != "http" {
+            return Err(format!("unsupported scheme '{scheme}'"));
+        }
+        let (public_key, location) = rest
+            .split_once('@')
+            .ok_or_else(|| "missing public key".to_string())?;
+        let public_key = public_key.split(':').next().unwrap_or_default();
+        let (host_and_path, project_id) = location
+            .trim_end_matches('/')
+            .rsplit_once('/')
+            .ok_or_else(|| "missing project id".to_string())?;
+        if public_key.is_empty() || host_and_path.is_empty() || project_id.is_empty() {
+            return Err("DSN must look like https://<key>@<host>/<project>".to_string());
+        }
+        Ok(Self {
+            raw: dsn.to_string(),
+            public_key: public_key.to_string(),
+            envelope_url: format!("{scheme}://{host_and_path}/api/{project_id}/envelope/"),
+        })
+    }
+
+    fn auth_header(&self, client: &str) -> String {
+        format!(
+            "Sentry sentry_version=7, sentry_key={}, sentry_client={client}",
+            self.public_key
+        )
+    }
+}
+
+/// Static context stamped on every event.
+#[derive(Debug, Clone)]
+pub struct ReportContext {
+    pub release: String,
+    pub environment: String,
+    pub git_sha: String,
+}
+
+impl ReportContext {
+    #[must_use]
+    pub fn new(build_info: &BuildInfo, environment: &str) -> Self {
+        Self {
+            release: format!("tinycongress-api@{}", build_info.version),
+            environment: environment.to_string(),
+            git_sha: build_info.git_sha.clone(),
+        }
+    }
+}
+
+/// Build the scrubbed Sentry event payload for a report.
+#[must_use]
+pub fn build_event(report: &ErrorReport, context: &ReportContext, event_id: Uuid) -> Value {
+    let mut event = json!({
+        "event_id": event_id.simple().to_string(),
+        "timestamp": Utc::now().to_rfc3339(),
+        "platform": "rust",
+        "level": report.level.as_str(),
+        "logger": report.logger,
+        "release": context.release,
+        "environment": context.environment,
+        "fingerprint": report.fingerprint,
+        "message": { "formatted": report.message },
+        "tags": { "git_sha": context.git_sha },
+        "extra": report.extra,
+    });
+    scrub_value(&mut event);
+    event
+}
+
+/// Serialize an event as a single-item Sentry envelope.
+#[must_use]
+pub fn build_envelope(dsn: &SentryDsn, event: &Value) -> String {
+    let payload = event.to_string();
+    let header = json!({
+        "event_id": event.get("event_id"),
+        "dsn": dsn.raw,
+        "sent_at": Utc::now().to_rfc3339(),
+    });
+    let item_header = json!({ "type": "event", "length": payload.len() });
+    format!("{header}\n{item_header}\n{payload}\n")
+}
+
+/// Reporter that posts scrubbed events to a Sentry-compatible collector.
+pub struct SentryReporter {
+    tx: mpsc::Sender<ErrorReport>,
+}
+
+impl SentryReporter {
+    /// Spawn the background sender. Must be called inside a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DSN is invalid or the HTTP client cannot be built.
+    pub fn spawn(config: &ErrorReportingConfig, build_info: &BuildInfo) -> anyhow::Result<Self> {
+        let dsn = SentryDsn::parse(&config.dsn)
+            .map_err(|e| anyhow::anyhow!("invalid error_reporting.dsn: {e}"))?;
+        let context = ReportContext::new(build_info, &config.environment);
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?;
+        let auth = dsn.auth_header(&format!("tinycongress-api/{}", build_info.version));
+        let (tx, mut rx) = mpsc::channel::<ErrorReport>(REPORT_BUFFER);
+
+        tokio::spawn(async move {
+            while let Some(report) = rx.recv().await {
+                let event = build_event(&report, &context, Uuid::new_v4());
+                let result = client
+                    .post(&dsn.envelope_url)
+                    .header("X-Sentry-Auth", &auth)
+                    .header("Content-Type", "application/x-sentry-envelope")
+                    .body(build_envelope(&dsn, &event))
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status);
+                // WARN, not ERROR: an ERROR here would be reported again.
+                if let Err(e) = result {
+                    tracing::warn!("Failed to send error report: {e}");
+                }
+            }
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+impl ErrorReporter for SentryReporter {
+    fn capture(&self, report: ErrorReport) {
+        if self.tx.try_send(report).is_err() {
+            tracing::warn!("Error report dropped: reporter queue full or closed");
+        }
+    }
+}
+
+/// Build the reporter selected by config.
+///
+/// # Errors
+///
+/// Returns an error if reporting is enabled but cannot be set up.
+pub fn build_reporter(
+    config: &ErrorReportingConfig,
+    build_info: &BuildInfo,
+) -> anyhow::Result<Arc<dyn ErrorReporter>> {
+    if config.enabled {
+        Ok(Arc::new(SentryReporter::spawn(config, build_info)?))
+    } else {
+        Ok(Arc::new(NoopErrorReporter))
+    }
+}
+
+// ─── Sources ───────────────────────────────────────────────────────────────
+
+/// Collects a tracing event's message and fields.
+#[derive(Default)]
+struct FieldCollector {
+    message: String,
+    fields: BTreeMap<String, Value>,
+}
+
+impl Visit for FieldCollector {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let rendered = format!("{value:?}");
+        if field.name() == "message" {
+            self.message = rendered;
+        } else {
+            self.fields
+                .insert(field.name().to_string(), Value::String(rendered));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields
+                .insert(field.name().to_string(), Value::String(value.to_string()));
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().to_string(), json!(value));
+    }
+}
+
+/// `tracing` layer that reports this service's `ERROR` events.
+pub struct ErrorReportingLayer {
+    reporter: Arc<dyn ErrorReporter>,
+}
+
+impl ErrorReportingLayer {
+    #[must_use]
+    pub fn new(reporter: Arc<dyn ErrorReporter>) -> Self {
+        Self { reporter }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ErrorReportingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() != Level::ERROR
+            || !REPORTED_TARGET_PREFIXES
+                .iter()
+                .any(|p| metadata.target().starts_with(p))
+        {
+            return;
+        }
+
+        let mut collector = FieldCollector::default();
+        event.record(&mut collector);
+
+        let site = match (metadata.file(), metadata.line()) {
+            (Some(file), Some(line)) => format!("{} {file}:{line}", metadata.target()),
+            _ => metadata.target().to_string(),
+        };
+
+        self.reporter.capture(ErrorReport {
+            level: ReportLevel::Error,
+            message: collector.message,
+            logger: metadata.target().to_string(),
+            fingerprint: vec!["log".to_string(), site],
+            extra: collector.fields.into_iter().collect(),
+        });
+    }
+}
+
+/// Report panics before delegating to the previously installed hook.
+pub fn install_panic_hook(reporter: Arc<dyn ErrorReporter>) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic with non-string payload".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_default();
+        let thread = std::thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .to_string();
+
+        let mut extra = Map::new();
+        extra.insert("location".to_string(), Value::String(location.clone()));
+        extra.insert("thread".to_string(), Value::String(thread));
+
+        reporter.capture(ErrorReport {
+            level: ReportLevel::Fatal,
+            message,
+            logger: "panic".to_string(),
+            fingerprint: vec!["panic".to_string(), location],
+            extra,
+        });
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Default)]
+    struct RecordingReporter(Mutex<Vec<ErrorReport>>);
+
+    impl ErrorReporter for RecordingReporter {
+        fn capture(&self, report: ErrorReport) {
+            self.0.lock().unwrap().push(report);
+        }
+    }
+
+    #[test]
+    fn dsn_parses_into_envelope_url() {
+        let dsn = SentryDsn::parse("https://abc123@o1.ingest.example.com/42").unwrap();
+        assert_eq!(
+            dsn.envelope_url,
+            "https://o1.ingest.example.com/api/42/envelope/"
+        );
+        assert!(dsn.auth_header("c/1").contains("sentry_key=abc123"));
+    }
+
+    #[test]
+    fn dsn_rejects_malformed_values() {
+        assert!(SentryDsn::parse("not a dsn").is_err());
+        assert!(SentryDsn::parse("https://example.com/42").is_err());
+        assert!(SentryDsn::parse("ftp://k@example.com/42").is_err());
+    }
+
+    #[test]
+    fn scrub_text_redacts_keys_but_keeps_kids_and_uuids() {
+        let pubkey = "A".repeat(43);
+        let kid = "abcdefghijklmnopqrstuv";
+        let id = "550e8400-e29b-41d4-a716-446655440000";
+        let scrubbed = scrub_text(&format!("bad key {pubkey} for {kid} on {id}"));
+        assert_eq!(scrubbed, format!("bad key [scrubbed] for {kid} on {id}"));
+    }
+
+    #[test]
+    fn scrub_value_redacts_sensitive_fields() {
+        let mut value = json!({
+            "device_kid": "abcdefghijklmnopqrstuv",
+            "certificate": "short",
+            "nested": { "root_pubkey": "x", "encrypted_backup": [1, 2, 3] },
+            "status": 500,
+        });
+        scrub_value(&mut value);
+        assert_eq!(value["device_kid"], "abcdefghijklmnopqrstuv");
+        assert_eq!(value["certificate"], SCRUBBED);
+        assert_eq!(value["nested"]["root_pubkey"], SCRUBBED);
+        assert_eq!(value["nested"]["encrypted_backup"], SCRUBBED);
+        assert_eq!(value["status"], 500);
+    }
+
+    #[test]
+    fn event_carries_release_and_fingerprint() {
+        let build_info = BuildInfo::from_lookup(|key| match key {
+            "APP_VERSION" => Some("1.2.3".to_string()),
+            "GIT_SHA" => Some("deadbeef".to_string()),
+            _ => None,
+        });
+        let context = ReportContext::new(&build_info, "staging");
+        let report = ErrorReport {
+            level: ReportLevel::Error,
+            message: format!("signature {} rejected", "B".repeat(86)),
+            logger: "tinycongress_api::identity".to_string(),
+            fingerprint: vec!["log".to_string(), "site".to_string()],
+            extra: Map::new(),
+        };
+        let event = build_event(&report, &context, Uuid::nil());
+        assert_eq!(event["release"], "tinycongress-api@1.2.3");
+        assert_eq!(event["environment"], "staging");
+        assert_eq!(event["tags"]["git_sha"], "deadbeef");
+        assert_eq!(event["fingerprint"][1], "site");
+        assert_eq!(
+            event["message"]["formatted"],
+            "signature [scrubbed] rejected"
+        );
+
+        let dsn = SentryDsn::parse("https://k@example.com/1").unwrap();
+        let envelope = build_envelope(&dsn, &event);
+        let lines: Vec<&str> = envelope.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let item: Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(item["length"], lines[2].len());
+    }
+
+    #[test]
+    fn layer_reports_only_service_errors() {
+        let reporter = Arc::new(RecordingReporter::default());
+        let subscriber =
+            tracing_subscriber::registry().with(ErrorReportingLayer::new(reporter.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::error!(target: "tinycongress_api::rooms", room = 7, "Failed to load room");
+            tracing::warn!(target: "tinycongress_api::rooms", "not reported");
+            tracing::error!(target: "hyper::proto", "not ours");
+        });
+
+        let reports = std::mem::take(&mut *reporter.0.lock().unwrap());
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].message, "Failed to load room");
+        assert_eq!(reports[0].extra["room"], 7);
+        assert!(reports[0].fingerprint[1].starts_with("tinycongress_api::rooms "));
+    }
+}
@@ -0,0 +1,246 @@
+//! Domain events mirrored to an external message bus.
+//!
+//! Handlers emit a [`DomainEvent`] after a state change has committed. The
+//! configured [`EventPublisher`] wraps it in a versioned [`EventEnvelope`] and
+//! forwards it to NATS so analytics and downstream services can consume
+//! activity without polling Postgres.
+//!
+//! Publishing is fire-and-forget: events are queued on a bounded channel and
+//! dropped (with a warning) if the bus falls behind. The request path never
+//! waits on the broker, and a broker outage never fails a user request.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tc_crypto::Kid;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::config::EventsConfig;
+use crate::reputation::EndorsementVisibility;
+
+/// Schema version stamped on every envelope. Bump when an existing event's
+/// payload changes incompatibly; adding a new event type does not require it.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// A state change worth telling the outside world about.
+///
+/// Payloads carry identifiers only — never request bodies, key material, or
+/// ballot contents.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum DomainEvent {
+    /// A new account was created via signup.
+    AccountCreated {
+        account_id: Uuid,
+        username: String,
+        root_kid: Kid,
+    },
+    /// A device key was revoked by its owner.
+    DeviceRevoked { account_id: Uuid, device_kid: Kid },
+    /// A trust endorsement was accepted into the action queue.
+    EndorsementQueued {
+        endorser_id: Uuid,
+        subject_id: Uuid,
+        weight: f32,
+    },
+    /// A ballot was cast (or updated) on a poll. The voter and values are
+    /// deliberately omitted.
+    BallotCast { room_id: Uuid, poll_id: Uuid },
+    /// A verifier issued (or re-issued) a non-trust endorsement.
+    EndorsementCreated {
+        endorsement_id: Uuid,
+        subject_id: Uuid,
+        topic: String,
+        issuer_id: Option<Uuid>,
+        visibility: EndorsementVisibility,
+    },
+    /// The subject of an endorsement disputed it. Sent to the issuer.
+    EndorsementDisputed {
+        endorsement_id: Uuid,
+        subject_id: Uuid,
+        issuer_id: Uuid,
+    },
+    /// Repeated signature failures blocked a client IP or flagged a device
+    /// KID. Devices are never blocked, so their `blocked_secs` is 0. The IP
+    /// itself is not published.
+    SignatureFailuresBlocked {
+        /// `device` or `ip`.
+        scope: &'static str,
+        device_kid: Option<Kid>,
+        blocked_secs: u64,
+    },
+}
+
+impl DomainEvent {
+    /// Stable, dot-free name used as the last subject token.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::AccountCreated { .. } => "account_created",
+            Self::DeviceRevoked { .. } => "device_revoked",
+            Self::EndorsementQueued { .. } => "endorsement_queued",
+            Self::BallotCast { .. } => "ballot_cast",
+            Self::EndorsementCreated { .. } => "endorsement_created",
+            Self::EndorsementDisputed { .. } => "endorsement_disputed",
+            Self::SignatureFailuresBlocked { .. } => "signature_failures_blocked",
+        }
+    }
+}
+
+/// Wire format for a published event.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventEnvelope {
+    /// Unique event ID, for consumer-side deduplication.
+    pub id: Uuid,
+    pub version: u32,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: DomainEvent,
+}
+
+impl EventEnvelope {
+    #[must_use]
+    pub fn new(event: DomainEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            version: EVENT_SCHEMA_VERSION,
+            occurred_at: Utc::now(),
+            event,
+        }
+    }
+
+    /// Subject this envelope is published on: `{prefix}.{event}.v{version}`.
+    #[must_use]
+    pub fn subject(&self, prefix: &str) -> String {
+        format!("{prefix}.{}.v{}", self.event.name(), self.version)
+    }
+}
+
+/// Sink for domain events. Implementations must not block the caller.
+pub trait EventPublisher: Send + Sync {
+    fn publish(&self, event: DomainEvent);
+}
+
+/// Publisher used when event mirroring is disabled.
+pub struct NoopEventPublisher;
+
+impl EventPublisher for NoopEventPublisher {
+    fn publish(&self, _event: DomainEvent) {}
+}
+
+/// Publishes envelopes to NATS from a background task.
+pub struct NatsEventPublisher {
+    tx: mpsc::Sender<EventEnvelope>,
+}
+
+impl NatsEventPublisher {
+    /// Connect to NATS and spawn the forwarding task.
+    ///
+    /// The initial connection is retried in the background, so an unavailable
+    /// broker does not block startup; events queued meanwhile are held up to
+    /// the configured buffer size.
+    ///
+    /// # Errors
+    /// Returns an error if the NATS URL cannot be parsed.
+    pub async fn connect(config: &EventsConfig) -> Result<Self, anyhow::Error> {
+        let client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect(config.nats_url.as_str())
+            .await?;
+
+        let (tx, mut rx) = mpsc::channel::<EventEnvelope>(config.buffer_size);
+        let prefix = config.subject_prefix.clone();
+
+        tokio::spawn(async move {
+            while let Some(envelope) = rx.recv().await {
+                let payload = match serde_json::to_vec(&envelope) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Failed to serialize domain event");
+                        continue;
+                    }
+                };
+                if let Err(e) = client
+                    .publish(envelope.subject(&prefix), payload.into())
+                    .await
+                {
+                    tracing::warn!(
+                        event = envelope.event.name(),
+                        error = %e,
+                        "Failed to publish domain event"
+                    );
+                }
+            }
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+impl EventPublisher for NatsEventPublisher {
+    fn publish(&self, event: DomainEvent) {
+        let envelope = EventEnvelope::new(event);
+        if let Err(e) = self.tx.try_send(envelope) {
+            tracing::warn!(error = %e, "Domain event buffer full or closed; dropping event");
+        }
+    }
+}
+
+/// Build the publisher selected by configuration.
+///
+/// # Errors
+/// Returns an error if events are enabled but the broker URL is invalid.
+pub async fn build_publisher(
+    config: &EventsConfig,
+) -> Result<Arc<dyn EventPublisher>, anyhow::Error> {
+    if !config.enabled {
+        tracing::info!("Domain event publishing disabled (enable via TC_EVENTS__ENABLED=true)");
+        return Ok(Arc::new(NoopEventPublisher));
+    }
+    tracing::info!(
+        subject_prefix = %config.subject_prefix,
+        "Domain event publishing enabled"
+    );
+    Ok(Arc::new(NatsEventPublisher::connect(config).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_serializes_type_and_data() {
+        let account_id = Uuid::nil();
+        let envelope = EventEnvelope::new(DomainEvent::DeviceRevoked {
+            account_id,
+            device_kid: Kid::derive(&[1u8; 32]),
+        });
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(json["type"], "device_revoked");
+        assert_eq!(json["data"]["account_id"], account_id.to_string());
+        assert!(json["data"]["device_kid"].is_string());
+    }
+
+    #[test]
+    fn subject_includes_event_name_and_version() {
+        let envelope = EventEnvelope::new(DomainEvent::BallotCast {
+            room_id: Uuid::nil(),
+            poll_id: Uuid::nil(),
+        });
+        assert_eq!(envelope.subject("tc.events"), "tc.events.ballot_cast.v1");
+    }
+
+    #[test]
+    fn ballot_event_omits_voter() {
+        let json = serde_json::to_value(EventEnvelope::new(DomainEvent::BallotCast {
+            room_id: Uuid::nil(),
+            poll_id: Uuid::nil(),
+        }))
+        .unwrap();
+        let data = json["data"].as_object().unwrap();
+        assert_eq!(data.len(), 2);
+    }
+}
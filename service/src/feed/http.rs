@@ -0,0 +1,82 @@
+//! HTTP handler for the ranked feed.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use chrono::Utc;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::service::{FeedQuery, FeedService};
+use super::RankedFeedItem;
+use crate::http::{bad_request, internal_error};
+use crate::identity::http::auth::AuthenticatedDevice;
+
+// ─── Response types ────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedResponse {
+    /// Best first
+    pub items: Vec<RankedFeedItem>,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+    /// Instant every page of this walk is ranked for
+    pub as_of: String,
+    /// Whether the page was served from a precomputed ranking
+    pub precomputed: bool,
+}
+
+// ─── Router ────────────────────────────────────────────────────────────────
+
+pub fn router() -> Router {
+    Router::new().route("/feed", get(get_feed))
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// The caller's feed: recent endorsements and newly opened polls, ranked by
+/// trust closeness, followed topics, and recency.
+#[utoipa::path(
+    get,
+    path = "/feed",
+    tag = "Feed",
+    params(FeedQuery),
+    responses(
+        (status = 200, description = "One page of the ranked feed", body = FeedResponse),
+        (status = 400, description = "Invalid limit or cursor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn get_feed(
+    Extension(service): Extension<Arc<FeedService>>,
+    Query(query): Query<FeedQuery>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let request = match query.resolve() {
+        Ok(request) => request,
+        Err(msg) => return bad_request(&msg),
+    };
+    match service.page(auth.account_id, &request, Utc::now()).await {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(FeedResponse {
+                items: page.items,
+                next_cursor: page.next_cursor,
+                as_of: page.as_of.to_rfc3339(),
+                precomputed: page.precomputed,
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Feed ranking failed: {e}");
+            internal_error()
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Personalized, trust-ranked activity feed.
+//!
+//! `GET /feed` ranks recent public activity for the caller: endorsements
+//! issued in the last [`CANDIDATE_WINDOW_DAYS`] days and polls opened in that
+//! window. Each item is scored by a [`score::FeedScorer`] from three signals:
+//!
+//! - how close the accounts involved are to the viewer in the trust graph,
+//!   read from the materialized distances in `trust__distances`
+//!   ([`crate::trust::distances`]);
+//! - whether the item's topic is one the viewer follows
+//!   ([`crate::topics`]);
+//! - how recent it is.
+//!
+//! Scores depend on the instant they are computed for, so a walk through the
+//! feed is pinned to the `as_of` of its first page: the cursor carries it,
+//! and later pages re-rank the same candidates for the same instant.
+//!
+//! Ranking a viewer with a large trust reach is the expensive case. Accounts
+//! with at least `feed.precompute_min_reach` accounts within reach have their
+//! ranking precomputed in the background and stored in `feed__snapshots`;
+//! first pages are served from a snapshot younger than
+//! `feed.precompute_max_age_secs`.
+//!
+//! Congress member activity is not a source yet: there is no member data in
+//! this service to draw from. [`FeedItem`] is tagged by `kind` so new sources
+//! can be added without breaking clients.
+
+pub mod http;
+pub mod repo;
+pub mod score;
+pub mod service;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How far back candidates are drawn from.
+pub const CANDIDATE_WINDOW_DAYS: i32 = 7;
+
+/// Most candidates of each kind ranked per request, newest first.
+pub const MAX_CANDIDATES: i64 = 500;
+
+/// Items kept in a precomputed snapshot; later pages fall back to live
+/// ranking.
+pub const SNAPSHOT_ITEMS: usize = 200;
+
+/// One piece of activity in the feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FeedItem {
+    /// A public endorsement was issued.
+    Endorsement {
+        #[schema(value_type = String, format = "uuid")]
+        id: Uuid,
+        topic: String,
+        #[schema(value_type = String, format = "uuid")]
+        subject_id: Uuid,
+        subject_username: String,
+        #[schema(value_type = Option<String>, format = "uuid")]
+        endorser_id: Option<Uuid>,
+        #[schema(value_type = String, format = DateTime)]
+        occurred_at: DateTime<Utc>,
+    },
+    /// A poll opened for voting.
+    PollOpened {
+        #[schema(value_type = String, format = "uuid")]
+        id: Uuid,
+        #[schema(value_type = String, format = "uuid")]
+        room_id: Uuid,
+        room_name: String,
+        question: String,
+        /// The room's eligibility topic
+        topic: String,
+        #[schema(value_type = String, format = DateTime)]
+        occurred_at: DateTime<Utc>,
+    },
+}
+
+impl FeedItem {
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        match self {
+            Self::Endorsement { id, .. } | Self::PollOpened { id, .. } => *id,
+        }
+    }
+
+    #[must_use]
+    pub const fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            Self::Endorsement { occurred_at, .. } | Self::PollOpened { occurred_at, .. } => {
+                *occurred_at
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn topic(&self) -> &str {
+        match self {
+            Self::Endorsement { topic, .. } | Self::PollOpened { topic, .. } => topic,
+        }
+    }
+
+    /// Accounts whose trust distance to the viewer counts toward the score.
+    #[must_use]
+    pub fn actors(&self) -> Vec<Uuid> {
+        match self {
+            Self::Endorsement {
+                subject_id,
+                endorser_id,
+                ..
+            } => std::iter::once(*subject_id).chain(*endorser_id).collect(),
+            Self::PollOpened { .. } => Vec::new(),
+        }
+    }
+}
+
+/// A feed item with its score for the viewer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RankedFeedItem {
+    /// Higher ranks first; only comparable within one viewer's feed
+    pub score: f64,
+    pub item: FeedItem,
+}
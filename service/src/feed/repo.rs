@@ -0,0 +1,323 @@
+//! Persistence for feed candidates, viewers, and precomputed snapshots
+
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::score::FeedViewer;
+use super::{FeedItem, RankedFeedItem, CANDIDATE_WINDOW_DAYS, MAX_CANDIDATES};
+use crate::db::{instrument, RepoError, RepoErrorKind};
+use crate::topics::TRUST_TOPIC;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// A viewer's ranking, computed ahead of their request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedSnapshot {
+    /// The instant the items were scored for
+    pub computed_at: DateTime<Utc>,
+    /// Best first
+    pub items: Vec<RankedFeedItem>,
+}
+
+#[derive(sqlx::FromRow)]
+struct EndorsementRow {
+    id: Uuid,
+    topic: String,
+    subject_id: Uuid,
+    subject_username: String,
+    endorser_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PollRow {
+    id: Uuid,
+    room_id: Uuid,
+    room_name: String,
+    question: String,
+    topic: String,
+    activated_at: DateTime<Utc>,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum FeedRepoError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("snapshot serialization error: {0}")]
+    Snapshot(#[from] serde_json::Error),
+}
+
+impl RepoError for FeedRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        RepoErrorKind::Database
+    }
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// The viewer's materialized trust distances and followed topics.
+///
+/// A viewer with no materialized distances yet is queued for the trust
+/// distance refresher and ranked without trust until it has run.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn load_viewer(pool: &PgPool, account_id: Uuid) -> Result<FeedViewer, FeedRepoError> {
+    let distances: Vec<(Uuid, f32)> =
+        sqlx::query_as("SELECT target_id, distance FROM trust__distances WHERE source_id = $1")
+            .bind(account_id)
+            .fetch_all(instrument(pool, "feed.viewer_distances"))
+            .await?;
+    if distances.is_empty() {
+        sqlx::query(
+            "INSERT INTO trust__distance_sources (source_id) VALUES ($1) \
+             ON CONFLICT (source_id) DO NOTHING",
+        )
+        .bind(account_id)
+        .execute(instrument(pool, "feed.queue_distances"))
+        .await?;
+    }
+    let topics: Vec<String> =
+        sqlx::query_scalar("SELECT topic FROM topic_subscriptions WHERE account_id = $1")
+            .bind(account_id)
+            .fetch_all(instrument(pool, "feed.viewer_topics"))
+            .await?;
+    Ok(FeedViewer {
+        account_id,
+        distances: distances.into_iter().collect::<HashMap<_, _>>(),
+        topics: topics.into_iter().collect::<HashSet<_>>(),
+    })
+}
+
+/// Activity in the [`CANDIDATE_WINDOW_DAYS`] days up to `as_of`, at most
+/// [`MAX_CANDIDATES`] of each kind, newest first within a kind.
+///
+/// Only public, unrevoked, non-trust endorsements are included.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_candidates(
+    pool: &PgPool,
+    as_of: DateTime<Utc>,
+) -> Result<Vec<FeedItem>, FeedRepoError> {
+    let endorsements = sqlx::query_as::<_, EndorsementRow>(
+        r"
+        SELECT e.id, e.topic, e.subject_id, a.username AS subject_username,
+               e.endorser_id, e.created_at
+        FROM reputation__endorsements e
+        JOIN accounts a ON a.id = e.subject_id
+        WHERE e.visibility = 'public'
+          AND e.topic <> $2
+          AND (e.revoked_at IS NULL OR e.revoked_at > $1)
+          AND e.created_at <= $1
+          AND e.created_at > $1 - make_interval(days => $3)
+        ORDER BY e.created_at DESC, e.id
+        LIMIT $4
+        ",
+    )
+    .bind(as_of)
+    .bind(TRUST_TOPIC)
+    .bind(CANDIDATE_WINDOW_DAYS)
+    .bind(MAX_CANDIDATES)
+    .fetch_all(instrument(pool, "feed.endorsement_candidates"))
+    .await?;
+
+    // COUPLING: rooms__polls is owned by the polling engine, as in
+    // rooms::repo::rooms_needing_content.
+    let polls = sqlx::query_as::<_, PollRow>(
+        r"
+        SELECT p.id, r.id AS room_id, r.name AS room_name, p.question,
+               r.eligibility_topic AS topic, p.activated_at
+        FROM rooms__polls p
+        JOIN rooms__rooms r ON r.id = p.room_id
+        WHERE p.status = 'active'
+          AND p.activated_at <= $1
+          AND p.activated_at > $1 - make_interval(days => $2)
+        ORDER BY p.activated_at DESC, p.id
+        LIMIT $3
+        ",
+    )
+    .bind(as_of)
+    .bind(CANDIDATE_WINDOW_DAYS)
+    .bind(MAX_CANDIDATES)
+    .fetch_all(instrument(pool, "feed.poll_candidates"))
+    .await?;
+
+    let endorsements = endorsements.into_iter().map(|r| FeedItem::Endorsement {
+        id: r.id,
+        topic: r.topic,
+        subject_id: r.subject_id,
+        subject_username: r.subject_username,
+        endorser_id: r.endorser_id,
+        occurred_at: r.created_at,
+    });
+    let polls = polls.into_iter().map(|r| FeedItem::PollOpened {
+        id: r.id,
+        room_id: r.room_id,
+        room_name: r.room_name,
+        question: r.question,
+        topic: r.topic,
+        occurred_at: r.activated_at,
+    });
+    Ok(endorsements.chain(polls).collect())
+}
+
+/// The account's stored snapshot, if any.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure, `Snapshot` if the stored items do not
+/// parse.
+pub async fn get_snapshot(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Option<FeedSnapshot>, FeedRepoError> {
+    let row: Option<(DateTime<Utc>, serde_json::Value)> =
+        sqlx::query_as("SELECT computed_at, items FROM feed__snapshots WHERE account_id = $1")
+            .bind(account_id)
+            .fetch_optional(instrument(pool, "feed.get_snapshot"))
+            .await?;
+    row.map(|(computed_at, items)| {
+        Ok(FeedSnapshot {
+            computed_at,
+            items: serde_json::from_value(items)?,
+        })
+    })
+    .transpose()
+}
+
+/// Replace the account's snapshot, unless a newer one is already stored.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure.
+pub async fn store_snapshot(
+    pool: &PgPool,
+    account_id: Uuid,
+    snapshot: &FeedSnapshot,
+) -> Result<(), FeedRepoError> {
+    sqlx::query(
+        r"
+        INSERT INTO feed__snapshots (account_id, computed_at, items)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (account_id) DO UPDATE
+            SET computed_at = EXCLUDED.computed_at, items = EXCLUDED.items
+            WHERE feed__snapshots.computed_at <= EXCLUDED.computed_at
+        ",
+    )
+    .bind(account_id)
+    .bind(snapshot.computed_at)
+    .bind(serde_json::to_value(&snapshot.items)?)
+    .execute(instrument(pool, "feed.store_snapshot"))
+    .await?;
+    Ok(())
+}
+
+/// Accounts with at least `min_reach` accounts within trust reach whose
+/// snapshot is missing or older than `stale_before`, stalest first.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure.
+pub async fn list_precompute_due(
+    pool: &PgPool,
+    min_reach: i64,
+    stale_before: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Uuid>, FeedRepoError> {
+    let accounts = sqlx::query_scalar(
+        r"
+        SELECT d.source_id
+        FROM trust__distances d
+        LEFT JOIN feed__snapshots s ON s.account_id = d.source_id
+        WHERE s.computed_at IS NULL OR s.computed_at < $2
+        GROUP BY d.source_id, s.computed_at
+        HAVING COUNT(*) >= $1
+        ORDER BY s.computed_at NULLS FIRST
+        LIMIT $3
+        ",
+    )
+    .bind(min_reach)
+    .bind(stale_before)
+    .bind(limit)
+    .fetch_all(instrument(pool, "feed.precompute_due"))
+    .await?;
+    Ok(accounts)
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// Feed persistence for the service and precompute job.
+#[async_trait]
+pub trait FeedRepo: Send + Sync {
+    async fn load_viewer(&self, account_id: Uuid) -> Result<FeedViewer, FeedRepoError>;
+
+    async fn list_candidates(&self, as_of: DateTime<Utc>) -> Result<Vec<FeedItem>, FeedRepoError>;
+
+    async fn get_snapshot(&self, account_id: Uuid) -> Result<Option<FeedSnapshot>, FeedRepoError>;
+
+    async fn store_snapshot(
+        &self,
+        account_id: Uuid,
+        snapshot: &FeedSnapshot,
+    ) -> Result<(), FeedRepoError>;
+
+    async fn list_precompute_due(
+        &self,
+        min_reach: i64,
+        stale_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, FeedRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`FeedRepo`].
+pub struct PgFeedRepo {
+    pool: PgPool,
+}
+
+impl PgFeedRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FeedRepo for PgFeedRepo {
+    async fn load_viewer(&self, account_id: Uuid) -> Result<FeedViewer, FeedRepoError> {
+        load_viewer(&self.pool, account_id).await
+    }
+
+    async fn list_candidates(&self, as_of: DateTime<Utc>) -> Result<Vec<FeedItem>, FeedRepoError> {
+        list_candidates(&self.pool, as_of).await
+    }
+
+    async fn get_snapshot(&self, account_id: Uuid) -> Result<Option<FeedSnapshot>, FeedRepoError> {
+        get_snapshot(&self.pool, account_id).await
+    }
+
+    async fn store_snapshot(
+        &self,
+        account_id: Uuid,
+        snapshot: &FeedSnapshot,
+    ) -> Result<(), FeedRepoError> {
+        store_snapshot(&self.pool, account_id, snapshot).await
+    }
+
+    async fn list_precompute_due(
+        &self,
+        min_reach: i64,
+        stale_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, FeedRepoError> {
+        list_precompute_due(&self.pool, min_reach, stale_before, limit).await
+    }
+}
@@ -0,0 +1,151 @@
+//! Feed scoring.
+//!
+//! [`FeedScorer`] is the extension point: the service ranks with whatever
+//! scorer it was built with, and [`TrustTopicScorer`] is the default.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::FeedItem;
+
+/// What the scorer knows about the viewer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedViewer {
+    pub account_id: Uuid,
+    /// Weighted trust distance to each account within reach
+    pub distances: HashMap<Uuid, f32>,
+    /// Topics the viewer follows
+    pub topics: HashSet<String>,
+}
+
+impl FeedViewer {
+    /// `1 / (1 + distance)`: 1 for the viewer, 0.5 for a full-weight direct
+    /// edge, 0 for an account out of reach.
+    #[must_use]
+    pub fn closeness(&self, account_id: Uuid) -> f64 {
+        if account_id == self.account_id {
+            return 1.0;
+        }
+        self.distances
+            .get(&account_id)
+            .map_or(0.0, |d| 1.0 / (1.0 + f64::from(*d)))
+    }
+}
+
+/// Scores one feed item for a viewer. Higher ranks first.
+///
+/// Pages are re-ranked on every request, so a scorer must return the same
+/// score for the same item, viewer, and `now`.
+pub trait FeedScorer: Send + Sync {
+    fn score(&self, item: &FeedItem, viewer: &FeedViewer, now: DateTime<Utc>) -> f64;
+}
+
+/// Recency decay, boosted by trust closeness and followed topics:
+///
+/// ```text
+/// score = 0.5^(age_hours / half_life_hours)
+///         × (1 + trust_weight × max closeness of the item's actors
+///              + topic_weight × [topic is followed])
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrustTopicScorer {
+    pub half_life_hours: f64,
+    pub trust_weight: f64,
+    pub topic_weight: f64,
+}
+
+impl Default for TrustTopicScorer {
+    fn default() -> Self {
+        Self {
+            half_life_hours: 24.0,
+            trust_weight: 2.0,
+            topic_weight: 1.0,
+        }
+    }
+}
+
+impl FeedScorer for TrustTopicScorer {
+    fn score(&self, item: &FeedItem, viewer: &FeedViewer, now: DateTime<Utc>) -> f64 {
+        #[allow(clippy::cast_precision_loss)] // seconds in the candidate window fit easily
+        let age_hours = (now - item.occurred_at()).num_seconds().max(0) as f64 / 3600.0;
+        let recency = 0.5_f64.powf(age_hours / self.half_life_hours);
+        let closeness = item
+            .actors()
+            .into_iter()
+            .map(|id| viewer.closeness(id))
+            .fold(0.0, f64::max);
+        let followed = if viewer.topics.contains(item.topic()) {
+            1.0
+        } else {
+            0.0
+        };
+        recency
+            * self
+                .topic_weight
+                .mul_add(followed, self.trust_weight.mul_add(closeness, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn endorsement(subject_id: Uuid, topic: &str, occurred_at: DateTime<Utc>) -> FeedItem {
+        FeedItem::Endorsement {
+            id: Uuid::new_v4(),
+            topic: topic.to_string(),
+            subject_id,
+            subject_username: "someone".to_string(),
+            endorser_id: None,
+            occurred_at,
+        }
+    }
+
+    #[test]
+    fn closer_and_followed_items_score_higher() {
+        let now = Utc::now();
+        let (near, far, stranger) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let viewer = FeedViewer {
+            account_id: Uuid::new_v4(),
+            distances: HashMap::from([(near, 1.0), (far, 3.0)]),
+            topics: HashSet::from(["policy".to_string()]),
+        };
+        let scorer = TrustTopicScorer::default();
+        let score = |item: &FeedItem| scorer.score(item, &viewer, now);
+
+        let near_score = score(&endorsement(near, "other", now));
+        let far_score = score(&endorsement(far, "other", now));
+        let stranger_score = score(&endorsement(stranger, "other", now));
+        assert!(near_score > far_score && far_score > stranger_score);
+        assert!((stranger_score - 1.0).abs() < 1e-9);
+        assert!((near_score - 2.0).abs() < 1e-9);
+
+        let followed = score(&endorsement(stranger, "policy", now));
+        assert!((followed - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scores_halve_every_half_life() {
+        let now = Utc::now();
+        let viewer = FeedViewer::default();
+        let scorer = TrustTopicScorer::default();
+        let subject = Uuid::new_v4();
+        let fresh = scorer.score(&endorsement(subject, "x", now), &viewer, now);
+        let day_old = scorer.score(
+            &endorsement(subject, "x", now - Duration::hours(24)),
+            &viewer,
+            now,
+        );
+        assert!((fresh / day_old - 2.0).abs() < 1e-9);
+        // Items stamped after `now` are not boosted past fresh ones
+        let future = scorer.score(
+            &endorsement(subject, "x", now + Duration::hours(1)),
+            &viewer,
+            now,
+        );
+        assert!((future - fresh).abs() < 1e-9);
+    }
+}
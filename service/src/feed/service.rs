@@ -0,0 +1,568 @@
+//! Ranking, pagination, and background precomputation of feeds.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use super::repo::{FeedRepo, FeedRepoError, FeedSnapshot};
+use super::score::{FeedScorer, FeedViewer};
+use super::{FeedItem, RankedFeedItem, SNAPSHOT_ITEMS};
+use crate::config::FeedConfig;
+use crate::pagination::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+
+/// Accounts precomputed per job run.
+const PRECOMPUTE_BATCH: i64 = 50;
+
+/// Query parameters for `GET /feed`.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct FeedQuery {
+    /// Page size (1–200, default 50).
+    pub limit: Option<u32>,
+    /// Opaque cursor from the previous page's `next_cursor`.
+    pub cursor: Option<String>,
+}
+
+/// Position after which a page starts: the last item of the previous page.
+pub type FeedAnchor = (f64, Uuid);
+
+/// A resolved feed page request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeedPageRequest {
+    /// Instant the walk is ranked for; `None` on the first page.
+    pub as_of: Option<DateTime<Utc>>,
+    pub after: Option<FeedAnchor>,
+    pub limit: u32,
+}
+
+impl FeedQuery {
+    /// Resolve the query into a page request.
+    ///
+    /// # Errors
+    ///
+    /// Returns a client-facing message if the limit is out of range or the
+    /// cursor is malformed.
+    pub fn resolve(&self) -> Result<FeedPageRequest, String> {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+            return Err(format!("limit must be between 1 and {MAX_PAGE_LIMIT}"));
+        }
+        let (as_of, after) = match &self.cursor {
+            Some(cursor) => {
+                let (as_of, anchor) = decode_cursor(cursor).ok_or("invalid cursor")?;
+                (Some(as_of), Some(anchor))
+            }
+            None => (None, None),
+        };
+        Ok(FeedPageRequest {
+            as_of,
+            after,
+            limit,
+        })
+    }
+}
+
+fn encode_cursor(as_of: DateTime<Utc>, (score, id): FeedAnchor) -> String {
+    tc_crypto::encode_base64url(
+        format!("{}:{:016x}:{id}", as_of.timestamp_micros(), score.to_bits()).as_bytes(),
+    )
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime<Utc>, FeedAnchor)> {
+    let bytes = tc_crypto::decode_base64url(cursor).ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let mut parts = text.splitn(3, ':');
+    let as_of = DateTime::from_timestamp_micros(parts.next()?.parse().ok()?)?;
+    let score = f64::from_bits(u64::from_str_radix(parts.next()?, 16).ok()?);
+    let id = parts.next()?.parse().ok()?;
+    score.is_finite().then_some((as_of, (score, id)))
+}
+
+/// `now` at the precision cursors and stored snapshots keep, so a walk's
+/// later pages rank for exactly the instant its first page did.
+fn pin(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.trunc_subsecs(6)
+}
+
+/// Feed order: score descending, then id ascending so ties are stable.
+fn feed_order(a: &RankedFeedItem, b: &RankedFeedItem) -> Ordering {
+    b.score
+        .total_cmp(&a.score)
+        .then_with(|| a.item.id().cmp(&b.item.id()))
+}
+
+/// Whether `item` comes after `anchor` in feed order.
+fn ranks_after(item: &RankedFeedItem, (score, id): FeedAnchor) -> bool {
+    match item.score.total_cmp(&score) {
+        Ordering::Less => true,
+        Ordering::Equal => item.item.id() > id,
+        Ordering::Greater => false,
+    }
+}
+
+/// Score every candidate for `viewer` at `now` and sort them best first.
+#[must_use]
+pub fn rank(
+    scorer: &dyn FeedScorer,
+    viewer: &FeedViewer,
+    candidates: Vec<FeedItem>,
+    now: DateTime<Utc>,
+) -> Vec<RankedFeedItem> {
+    let mut ranked: Vec<RankedFeedItem> = candidates
+        .into_iter()
+        .map(|item| RankedFeedItem {
+            score: scorer.score(&item, viewer, now),
+            item,
+        })
+        .collect();
+    ranked.sort_by(feed_order);
+    ranked
+}
+
+/// One page of a viewer's feed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedPage {
+    pub items: Vec<RankedFeedItem>,
+    pub next_cursor: Option<String>,
+    /// Instant the walk is ranked for
+    pub as_of: DateTime<Utc>,
+    /// Whether the page came from a precomputed snapshot
+    pub precomputed: bool,
+}
+
+/// Ranks feeds on request, or from a fresh precomputed snapshot.
+pub struct FeedService {
+    repo: Arc<dyn FeedRepo>,
+    scorer: Arc<dyn FeedScorer>,
+    config: FeedConfig,
+}
+
+impl FeedService {
+    #[must_use]
+    pub fn new(repo: Arc<dyn FeedRepo>, scorer: Arc<dyn FeedScorer>, config: FeedConfig) -> Self {
+        Self {
+            repo,
+            scorer,
+            config,
+        }
+    }
+
+    async fn rank_live(
+        &self,
+        account_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<Vec<RankedFeedItem>, FeedRepoError> {
+        let viewer = self.repo.load_viewer(account_id).await?;
+        let candidates = self.repo.list_candidates(as_of).await?;
+        Ok(rank(self.scorer.as_ref(), &viewer, candidates, as_of))
+    }
+
+    fn max_age(&self) -> Duration {
+        Duration::seconds(i64::try_from(self.config.precompute_max_age_secs).unwrap_or(i64::MAX))
+    }
+
+    /// A snapshot usable for this request: the one the walk started from,
+    /// or for a first page any snapshot younger than the max age.
+    async fn usable_snapshot(
+        &self,
+        account_id: Uuid,
+        request: &FeedPageRequest,
+        now: DateTime<Utc>,
+    ) -> Result<Option<FeedSnapshot>, FeedRepoError> {
+        if self.config.precompute_min_reach == 0 {
+            return Ok(None);
+        }
+        let snapshot = self.repo.get_snapshot(account_id).await?;
+        Ok(snapshot.filter(|s| {
+            request.as_of.map_or_else(
+                || now - s.computed_at <= self.max_age(),
+                |as_of| s.computed_at == as_of,
+            )
+        }))
+    }
+
+    /// One page of `account_id`'s feed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the failing repo call.
+    pub async fn page(
+        &self,
+        account_id: Uuid,
+        request: &FeedPageRequest,
+        now: DateTime<Utc>,
+    ) -> Result<FeedPage, FeedRepoError> {
+        let limit = usize::try_from(request.limit).unwrap_or(usize::MAX);
+        let start_of = |ranked: &[RankedFeedItem]| {
+            request.after.map_or(0, |anchor| {
+                ranked
+                    .iter()
+                    .position(|item| ranks_after(item, anchor))
+                    .unwrap_or(ranked.len())
+            })
+        };
+
+        let (as_of, ranked, precomputed) =
+            match self.usable_snapshot(account_id, request, now).await? {
+                // A truncated snapshot cannot tell whether more items follow
+                // its end; rank live for the snapshot's instant instead.
+                Some(s)
+                    if s.items.len() < SNAPSHOT_ITEMS
+                        || start_of(&s.items).saturating_add(limit) < s.items.len() =>
+                {
+                    (s.computed_at, s.items, true)
+                }
+                Some(s) => (
+                    s.computed_at,
+                    self.rank_live(account_id, s.computed_at).await?,
+                    false,
+                ),
+                None => {
+                    let as_of = request.as_of.unwrap_or_else(|| pin(now));
+                    (as_of, self.rank_live(account_id, as_of).await?, false)
+                }
+            };
+        metrics::counter!(
+            "tc_feed_pages_total",
+            "source" => if precomputed { "snapshot" } else { "live" }
+        )
+        .increment(1);
+
+        let start = start_of(&ranked);
+        let items: Vec<RankedFeedItem> = ranked.iter().skip(start).take(limit).cloned().collect();
+        let next_cursor = (start.saturating_add(limit) < ranked.len())
+            .then(|| items.last())
+            .flatten()
+            .map(|last| encode_cursor(as_of, (last.score, last.item.id())));
+        Ok(FeedPage {
+            items,
+            next_cursor,
+            as_of,
+            precomputed,
+        })
+    }
+
+    /// Rank `account_id`'s feed for `now` and store the top
+    /// [`SNAPSHOT_ITEMS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the failing repo call.
+    pub async fn precompute(
+        &self,
+        account_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<(), FeedRepoError> {
+        let now = pin(now);
+        let mut items = self.rank_live(account_id, now).await?;
+        items.truncate(SNAPSHOT_ITEMS);
+        self.repo
+            .store_snapshot(
+                account_id,
+                &FeedSnapshot {
+                    computed_at: now,
+                    items,
+                },
+            )
+            .await
+    }
+
+    /// Precompute one batch of heavy accounts whose snapshot is missing or
+    /// due. Snapshots are rebuilt one run interval before they expire, so a
+    /// heavy account's first page keeps hitting one.
+    ///
+    /// Returns the number of accounts precomputed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first failing repo call; accounts before it are stored.
+    pub async fn precompute_due(&self, now: DateTime<Utc>) -> Result<usize, FeedRepoError> {
+        if self.config.precompute_min_reach == 0 {
+            return Ok(0);
+        }
+        let lead = self
+            .config
+            .precompute_max_age_secs
+            .saturating_sub(self.config.precompute_interval_secs);
+        let stale_before = now - Duration::seconds(i64::try_from(lead).unwrap_or(i64::MAX));
+        let due = self
+            .repo
+            .list_precompute_due(
+                i64::from(self.config.precompute_min_reach),
+                stale_before,
+                PRECOMPUTE_BATCH,
+            )
+            .await?;
+        for &account_id in &due {
+            self.precompute(account_id, now).await?;
+        }
+        Ok(due.len())
+    }
+
+    /// Run [`Self::precompute_due`] every `feed.precompute_interval_secs`
+    /// for the life of the process. Does nothing if precomputation is off.
+    pub fn spawn_precompute_job(self: &Arc<Self>) {
+        if self.config.precompute_min_reach == 0 {
+            return;
+        }
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(StdDuration::from_secs(
+                service.config.precompute_interval_secs,
+            ));
+            loop {
+                interval.tick().await;
+                match service.precompute_due(Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::debug!(accounts = n, "Precomputed feeds"),
+                    Err(e) => tracing::warn!("Feed precompute failed: {e}"),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::score::TrustTopicScorer;
+    use async_trait::async_trait;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryFeedRepo {
+        viewer: FeedViewer,
+        candidates: Vec<FeedItem>,
+        snapshots: Mutex<HashMap<Uuid, FeedSnapshot>>,
+        candidate_loads: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl FeedRepo for MemoryFeedRepo {
+        async fn load_viewer(&self, _account_id: Uuid) -> Result<FeedViewer, FeedRepoError> {
+            Ok(self.viewer.clone())
+        }
+
+        async fn list_candidates(
+            &self,
+            as_of: DateTime<Utc>,
+        ) -> Result<Vec<FeedItem>, FeedRepoError> {
+            *self.candidate_loads.lock().unwrap() += 1;
+            Ok(self
+                .candidates
+                .iter()
+                .filter(|c| c.occurred_at() <= as_of)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_snapshot(
+            &self,
+            account_id: Uuid,
+        ) -> Result<Option<FeedSnapshot>, FeedRepoError> {
+            Ok(self.snapshots.lock().unwrap().get(&account_id).cloned())
+        }
+
+        async fn store_snapshot(
+            &self,
+            account_id: Uuid,
+            snapshot: &FeedSnapshot,
+        ) -> Result<(), FeedRepoError> {
+            self.snapshots
+                .lock()
+                .unwrap()
+                .insert(account_id, snapshot.clone());
+            Ok(())
+        }
+
+        async fn list_precompute_due(
+            &self,
+            _min_reach: i64,
+            stale_before: DateTime<Utc>,
+            _limit: i64,
+        ) -> Result<Vec<Uuid>, FeedRepoError> {
+            let fresh = self
+                .snapshots
+                .lock()
+                .unwrap()
+                .get(&self.viewer.account_id)
+                .is_some_and(|s| s.computed_at >= stale_before);
+            Ok(if fresh {
+                vec![]
+            } else {
+                vec![self.viewer.account_id]
+            })
+        }
+    }
+
+    fn endorsement(subject_id: Uuid, topic: &str, occurred_at: DateTime<Utc>) -> FeedItem {
+        FeedItem::Endorsement {
+            id: Uuid::new_v4(),
+            topic: topic.to_string(),
+            subject_id,
+            subject_username: "someone".to_string(),
+            endorser_id: None,
+            occurred_at,
+        }
+    }
+
+    fn service(repo: Arc<MemoryFeedRepo>) -> FeedService {
+        FeedService::new(
+            repo,
+            Arc::new(TrustTopicScorer::default()),
+            FeedConfig::default(),
+        )
+    }
+
+    fn fixture(now: DateTime<Utc>) -> Arc<MemoryFeedRepo> {
+        let friend = Uuid::new_v4();
+        let viewer = FeedViewer {
+            account_id: Uuid::new_v4(),
+            distances: HashMap::from([(friend, 0.5)]),
+            topics: HashSet::from(["policy".to_string()]),
+        };
+        let candidates = (0..5)
+            .map(|i| endorsement(Uuid::new_v4(), "other", now - Duration::hours(i)))
+            .chain([
+                endorsement(friend, "other", now - Duration::hours(6)),
+                FeedItem::PollOpened {
+                    id: Uuid::new_v4(),
+                    room_id: Uuid::new_v4(),
+                    room_name: "Budget".to_string(),
+                    question: "Fund the library?".to_string(),
+                    topic: "policy".to_string(),
+                    occurred_at: now - Duration::hours(6),
+                },
+            ])
+            .collect();
+        Arc::new(MemoryFeedRepo {
+            viewer,
+            candidates,
+            ..MemoryFeedRepo::default()
+        })
+    }
+
+    async fn walk(service: &FeedService, account_id: Uuid, now: DateTime<Utc>) -> Vec<FeedPage> {
+        let mut pages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let request = FeedQuery {
+                limit: Some(2),
+                cursor: cursor.clone(),
+            }
+            .resolve()
+            .unwrap();
+            let page = service.page(account_id, &request, now).await.unwrap();
+            cursor.clone_from(&page.next_cursor);
+            pages.push(page);
+            if cursor.is_none() {
+                return pages;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn trusted_and_followed_items_outrank_newer_strangers() {
+        // Database timestamps carry microseconds
+        let now = pin(Utc::now());
+        let repo = fixture(now);
+        let pages = walk(&service(Arc::clone(&repo)), repo.viewer.account_id, now).await;
+        let items: Vec<_> = pages.iter().flat_map(|p| p.items.clone()).collect();
+
+        assert_eq!(items.len(), 7);
+        assert!(items.windows(2).all(|w| w[0].score >= w[1].score));
+        let ids: HashSet<_> = items.iter().map(|i| i.item.id()).collect();
+        assert_eq!(ids.len(), 7, "no item repeats across pages");
+        // Six hours old, but from a trusted account
+        assert!(matches!(items[0].item, FeedItem::Endorsement { .. }));
+        assert!(items[0].item.occurred_at() < now - Duration::hours(5));
+        assert!(matches!(items[1].item, FeedItem::PollOpened { .. }));
+        assert!(pages.iter().all(|p| p.as_of == now && !p.precomputed));
+    }
+
+    #[tokio::test]
+    async fn later_pages_rank_for_the_first_pages_instant() {
+        let now = pin(Utc::now());
+        let repo = fixture(now);
+        let service = service(Arc::clone(&repo));
+        let account_id = repo.viewer.account_id;
+        let first = service
+            .page(account_id, &FeedQuery::default().resolve().unwrap(), now)
+            .await
+            .unwrap();
+        assert_eq!(first.items.len(), 7);
+
+        let request = FeedQuery {
+            limit: Some(3),
+            cursor: None,
+        }
+        .resolve()
+        .unwrap();
+        let page = service.page(account_id, &request, now).await.unwrap();
+        let request = FeedQuery {
+            limit: Some(3),
+            cursor: page.next_cursor,
+        }
+        .resolve()
+        .unwrap();
+        let later = service
+            .page(account_id, &request, now + Duration::hours(12))
+            .await
+            .unwrap();
+        assert_eq!(later.as_of, now);
+        assert_eq!(later.items, first.items[3..6]);
+    }
+
+    #[tokio::test]
+    async fn fresh_snapshots_serve_first_pages() {
+        let now = pin(Utc::now());
+        let repo = fixture(now);
+        let service = service(Arc::clone(&repo));
+        let account_id = repo.viewer.account_id;
+
+        assert_eq!(service.precompute_due(now).await.unwrap(), 1);
+        assert_eq!(service.precompute_due(now).await.unwrap(), 0);
+        let loads = *repo.candidate_loads.lock().unwrap();
+
+        let pages = walk(&service, account_id, now + Duration::seconds(30)).await;
+        assert!(pages.iter().all(|p| p.precomputed && p.as_of == now));
+        assert_eq!(*repo.candidate_loads.lock().unwrap(), loads);
+
+        // Past the max age the feed is ranked live again
+        let late = now + Duration::seconds(301);
+        let page = service
+            .page(account_id, &FeedQuery::default().resolve().unwrap(), late)
+            .await
+            .unwrap();
+        assert!(!page.precomputed);
+        assert_eq!(page.as_of, late);
+    }
+
+    #[test]
+    fn malformed_queries_are_rejected() {
+        let bad_limit = FeedQuery {
+            limit: Some(MAX_PAGE_LIMIT + 1),
+            cursor: None,
+        };
+        assert!(bad_limit.resolve().is_err());
+        let bad_cursor = FeedQuery {
+            limit: None,
+            cursor: Some("not-a-cursor".to_string()),
+        };
+        assert!(bad_cursor.resolve().is_err());
+        let nan = tc_crypto::encode_base64url(
+            format!("0:{:016x}:{}", f64::NAN.to_bits(), Uuid::nil()).as_bytes(),
+        );
+        assert!(FeedQuery {
+            limit: None,
+            cursor: Some(nan),
+        }
+        .resolve()
+        .is_err());
+    }
+}
@@ -0,0 +1,478 @@
+//! Reputation-scaled cost budgets for GraphQL operations.
+//!
+//! Every operation is scored by async-graphql's complexity analysis (one
+//! point per selected field unless a resolver declares otherwise) and
+//! refused outright above `graphql.max_complexity`. Device-signed operations
+//! are then charged against the caller's budget for the current minute,
+//! which grows with their [`ReputationTier`]. Unsigned queries have no
+//! account to charge; they are bounded by the complexity limit and the
+//! per-IP rate limits instead.
+//!
+//! Spend is kept in the `graphql_cost_budgets` table so every API instance
+//! sees the same total. An operation that would overdraw the budget is not
+//! executed or charged; it fails with a `THROTTLED` error whose extensions
+//! carry the budget and when it resets:
+//!
+//! ```json
+//! {"message": "GraphQL cost budget exhausted",
+//!  "extensions": {"code": "THROTTLED", "cost": 40, "budget": 300,
+//!                 "tier": "new", "resetAt": "2026-03-14T12:01:00+00:00",
+//!                 "retryAfter": 17}}
+//! ```
+//!
+//! Tier lookups are cached for [`TIER_CACHE_TTL`]. If a tier cannot be
+//! computed the caller gets the `new` budget; if the budget table cannot be
+//! reached the operation runs uncharged, matching the fail-open rate limits.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextValidation};
+use async_graphql::{ErrorExtensionValues, ServerError, ValidationResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::SignedCaller;
+use crate::config::{GraphQLCostBudgetConfig, ReputationConfig};
+use crate::reputation::repo::ReputationRepo;
+use crate::reputation::score::{explain_account, ExplainError};
+use crate::reputation::ReputationTier;
+use crate::trust::repo::TrustRepo;
+
+/// Length of a budget window.
+pub const WINDOW_SECS: i64 = 60;
+
+/// How long a caller's tier is reused before it is recomputed.
+pub const TIER_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Error code in the `extensions` of a throttled operation.
+pub const THROTTLED: &str = "THROTTLED";
+
+/// Start of the window containing `now`.
+#[must_use]
+pub fn window_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    let secs = now.timestamp();
+    DateTime::from_timestamp(secs - secs.rem_euclid(WINDOW_SECS), 0).unwrap_or(now)
+}
+
+// ─── Persistence ───────────────────────────────────────────────────────────
+
+/// Where per-account spend is counted.
+#[async_trait]
+pub trait GraphQLCostRepo: Send + Sync {
+    /// Add `cost` to `account_id`'s spend for the window starting at
+    /// `window_start`, unless that would take it past `budget`.
+    ///
+    /// Returns the new total, or `None` if the charge was refused.
+    async fn charge(
+        &self,
+        account_id: Uuid,
+        window_start: DateTime<Utc>,
+        cost: i32,
+        budget: i32,
+    ) -> Result<Option<i32>, sqlx::Error>;
+}
+
+pub struct PgGraphQLCostRepo {
+    pool: PgPool,
+}
+
+impl PgGraphQLCostRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GraphQLCostRepo for PgGraphQLCostRepo {
+    async fn charge(
+        &self,
+        account_id: Uuid,
+        window_start: DateTime<Utc>,
+        cost: i32,
+        budget: i32,
+    ) -> Result<Option<i32>, sqlx::Error> {
+        if cost > budget {
+            return Ok(None);
+        }
+        // A row from an earlier window is reset rather than added to
+        sqlx::query_scalar(
+            r"
+            INSERT INTO graphql_cost_budgets AS b (account_id, window_start, spent)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (account_id) DO UPDATE SET
+                spent = CASE WHEN b.window_start = EXCLUDED.window_start
+                             THEN b.spent + EXCLUDED.spent
+                             ELSE EXCLUDED.spent END,
+                window_start = EXCLUDED.window_start
+            WHERE b.window_start <> EXCLUDED.window_start OR b.spent + EXCLUDED.spent <= $4
+            RETURNING spent
+            ",
+        )
+        .bind(account_id)
+        .bind(window_start)
+        .bind(cost)
+        .bind(budget)
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
+
+// ─── Tiers ─────────────────────────────────────────────────────────────────
+
+/// Resolves the reputation tier a caller's budget is sized by.
+#[async_trait]
+pub trait TierSource: Send + Sync {
+    async fn tier(&self, account_id: Uuid) -> Result<ReputationTier, ExplainError>;
+}
+
+/// Computes tiers from the reputation model, as
+/// `GET /reputation/{account_id}/explanation` does.
+pub struct ReputationTierSource {
+    reputation: Arc<dyn ReputationRepo>,
+    trust: Arc<dyn TrustRepo>,
+    config: ReputationConfig,
+}
+
+impl ReputationTierSource {
+    #[must_use]
+    pub fn new(
+        reputation: Arc<dyn ReputationRepo>,
+        trust: Arc<dyn TrustRepo>,
+        config: ReputationConfig,
+    ) -> Self {
+        Self {
+            reputation,
+            trust,
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl TierSource for ReputationTierSource {
+    async fn tier(&self, account_id: Uuid) -> Result<ReputationTier, ExplainError> {
+        explain_account(
+            self.reputation.as_ref(),
+            self.trust.as_ref(),
+            &self.config,
+            account_id,
+        )
+        .await
+        .map(|explanation| explanation.tier)
+    }
+}
+
+// ─── Budgets ───────────────────────────────────────────────────────────────
+
+/// An operation refused for overdrawing its caller's budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Throttled {
+    pub tier: ReputationTier,
+    pub cost: i32,
+    pub budget: i32,
+    pub reset_at: DateTime<Utc>,
+}
+
+impl Throttled {
+    /// Render as the `THROTTLED` GraphQL error.
+    #[must_use]
+    pub fn into_server_error(self, now: DateTime<Utc>) -> ServerError {
+        let mut extensions = ErrorExtensionValues::default();
+        extensions.set("code", THROTTLED);
+        extensions.set("cost", self.cost);
+        extensions.set("budget", self.budget);
+        extensions.set("tier", self.tier.as_str());
+        extensions.set("resetAt", self.reset_at.to_rfc3339());
+        extensions.set("retryAfter", (self.reset_at - now).num_seconds().max(1));
+        let mut error = ServerError::new("GraphQL cost budget exhausted", None);
+        error.extensions = Some(extensions);
+        error
+    }
+}
+
+type TierCache = HashMap<Uuid, (ReputationTier, Instant)>;
+
+/// Per-account GraphQL budgets. Install on the schema with
+/// `.extension(CostBudgets::new(..))`; clones share the tier cache.
+#[derive(Clone)]
+pub struct CostBudgets {
+    config: GraphQLCostBudgetConfig,
+    repo: Arc<dyn GraphQLCostRepo>,
+    tiers: Arc<dyn TierSource>,
+    tier_cache: Arc<Mutex<TierCache>>,
+}
+
+impl CostBudgets {
+    #[must_use]
+    pub fn new(
+        config: GraphQLCostBudgetConfig,
+        repo: Arc<dyn GraphQLCostRepo>,
+        tiers: Arc<dyn TierSource>,
+    ) -> Self {
+        Self {
+            config,
+            repo,
+            tiers,
+            tier_cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The cache holds plain values; recover from poisoning.
+    fn cache(&self) -> MutexGuard<'_, TierCache> {
+        self.tier_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn tier(&self, account_id: Uuid) -> ReputationTier {
+        let cached = self.cache().get(&account_id).copied();
+        if let Some((tier, at)) = cached {
+            if at.elapsed() < TIER_CACHE_TTL {
+                return tier;
+            }
+        }
+        let tier = match self.tiers.tier(account_id).await {
+            Ok(tier) => tier,
+            Err(e) => {
+                tracing::warn!(%account_id, "Failed to resolve reputation tier for GraphQL budget: {e}");
+                return ReputationTier::New;
+            }
+        };
+        let mut cache = self.cache();
+        cache.retain(|_, (_, at)| at.elapsed() < TIER_CACHE_TTL);
+        cache.insert(account_id, (tier, Instant::now()));
+        drop(cache);
+        tier
+    }
+
+    /// Charge `complexity` to `account_id` at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Throttled`] if the charge would overdraw the caller's
+    /// budget for the current window; nothing is charged in that case.
+    pub async fn charge(
+        &self,
+        account_id: Uuid,
+        complexity: usize,
+        now: DateTime<Utc>,
+    ) -> Result<(), Throttled> {
+        let tier = self.tier(account_id).await;
+        let budget = i32::try_from(self.config.budget(tier)).unwrap_or(i32::MAX);
+        let cost = i32::try_from(complexity).unwrap_or(i32::MAX);
+        let window = window_start(now);
+        match self.repo.charge(account_id, window, cost, budget).await {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => {
+                metrics::counter!("tc_graphql_throttled_total", "tier" => tier.as_str())
+                    .increment(1);
+                Err(Throttled {
+                    tier,
+                    cost,
+                    budget,
+                    reset_at: window + chrono::Duration::seconds(WINDOW_SECS),
+                })
+            }
+            Err(e) => {
+                tracing::warn!("Failed to charge GraphQL cost budget: {e}");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExtensionFactory for CostBudgets {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(CostBudgetExtension(self.clone()))
+    }
+}
+
+/// Charges each validated operation to the signed caller, if any.
+struct CostBudgetExtension(CostBudgets);
+
+#[async_trait]
+impl Extension for CostBudgetExtension {
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let result = next.run(ctx).await?;
+        let Some(caller) = ctx.data_opt::<SignedCaller>() else {
+            return Ok(result);
+        };
+        let now = Utc::now();
+        self.0
+            .charge(caller.account_id, result.complexity, now)
+            .await
+            .map_err(|throttled| vec![throttled.into_server_error(now)])?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// In-memory stand-in for `graphql_cost_budgets`.
+    #[derive(Default)]
+    struct MemoryCostRepo {
+        spent: Mutex<HashMap<Uuid, (DateTime<Utc>, i32)>>,
+    }
+
+    #[async_trait]
+    impl GraphQLCostRepo for MemoryCostRepo {
+        async fn charge(
+            &self,
+            account_id: Uuid,
+            window_start: DateTime<Utc>,
+            cost: i32,
+            budget: i32,
+        ) -> Result<Option<i32>, sqlx::Error> {
+            let mut spent = self.spent.lock().unwrap();
+            let entry = spent.entry(account_id).or_insert((window_start, 0));
+            if entry.0 != window_start {
+                *entry = (window_start, 0);
+            }
+            if entry.1 + cost > budget {
+                return Ok(None);
+            }
+            entry.1 += cost;
+            Ok(Some(entry.1))
+        }
+    }
+
+    struct FixedTier(ReputationTier);
+
+    #[async_trait]
+    impl TierSource for FixedTier {
+        async fn tier(&self, _account_id: Uuid) -> Result<ReputationTier, ExplainError> {
+            Ok(self.0)
+        }
+    }
+
+    fn budgets(tier: ReputationTier) -> CostBudgets {
+        CostBudgets::new(
+            GraphQLCostBudgetConfig::default(),
+            Arc::new(MemoryCostRepo::default()),
+            Arc::new(FixedTier(tier)),
+        )
+    }
+
+    fn at(secs: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 14, 12, 0, secs).unwrap()
+    }
+
+    #[test]
+    fn windows_start_on_the_minute() {
+        assert_eq!(window_start(at(0)), at(0));
+        assert_eq!(window_start(at(59)), at(0));
+        assert_eq!(
+            window_start(at(59) + chrono::Duration::seconds(1)),
+            at(0) + chrono::Duration::minutes(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn budget_grows_with_tier_and_resets_each_window() {
+        let config = GraphQLCostBudgetConfig::default();
+        let account = Uuid::new_v4();
+        for tier in ReputationTier::ALL {
+            let budgets = budgets(tier);
+            let budget = config.budget(tier) as usize;
+            budgets
+                .charge(account, budget, at(5))
+                .await
+                .expect("whole budget");
+            let throttled = budgets
+                .charge(account, 1, at(30))
+                .await
+                .expect_err("budget spent");
+            assert_eq!(throttled.tier, tier);
+            assert_eq!(throttled.reset_at, at(0) + chrono::Duration::minutes(1));
+            budgets
+                .charge(account, 1, throttled.reset_at)
+                .await
+                .expect("next window");
+        }
+        assert!(
+            config.budget(ReputationTier::New) < config.budget(ReputationTier::Exemplary),
+            "reputation should buy a larger budget"
+        );
+    }
+
+    #[tokio::test]
+    async fn refused_operations_are_not_charged() {
+        let budgets = budgets(ReputationTier::New);
+        let account = Uuid::new_v4();
+        let budget = GraphQLCostBudgetConfig::default().budget(ReputationTier::New) as usize;
+        budgets
+            .charge(account, budget + 1, at(1))
+            .await
+            .expect_err("larger than the budget");
+        budgets
+            .charge(account, budget, at(2))
+            .await
+            .expect("nothing was spent");
+    }
+
+    #[tokio::test]
+    async fn extension_throttles_signed_operations_only() {
+        use super::super::{MutationRoot, QueryRoot};
+        use async_graphql::{EmptySubscription, Request, Schema};
+
+        let config = GraphQLCostBudgetConfig {
+            new: 2,
+            ..GraphQLCostBudgetConfig::default()
+        };
+        let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .extension(CostBudgets::new(
+                config,
+                Arc::new(MemoryCostRepo::default()),
+                Arc::new(FixedTier(ReputationTier::New)),
+            ))
+            .finish();
+        let caller = SignedCaller {
+            account_id: Uuid::new_v4(),
+            device_kid: tc_crypto::Kid::derive(&[7u8; 32]),
+        };
+        let echo = r#"mutation { echo(message: "x") }"#;
+        let signed = || Request::new(echo).data(caller.clone());
+
+        assert!(schema.execute(signed()).await.is_ok());
+        assert!(schema.execute(signed()).await.is_ok());
+        let response = schema.execute(signed()).await;
+        let extensions = response.errors[0].extensions.as_ref().expect("extensions");
+        assert_eq!(
+            extensions.get("code"),
+            Some(&async_graphql::Value::from(THROTTLED))
+        );
+        assert!(response.data == async_graphql::Value::Null);
+
+        for _ in 0..3 {
+            assert!(schema.execute(echo).await.is_ok());
+        }
+    }
+
+    #[test]
+    fn throttled_error_carries_code_and_reset() {
+        let error = Throttled {
+            tier: ReputationTier::Trusted,
+            cost: 40,
+            budget: 1000,
+            reset_at: at(0) + chrono::Duration::minutes(1),
+        }
+        .into_server_error(at(43));
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["extensions"]["code"], THROTTLED);
+        assert_eq!(json["extensions"]["tier"], "trusted");
+        assert_eq!(json["extensions"]["retryAfter"], 17);
+        assert_eq!(json["extensions"]["resetAt"], "2026-03-14T12:01:00+00:00");
+    }
+}
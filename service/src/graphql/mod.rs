@@ -0,0 +1,185 @@
+pub mod cost;
+
+use std::sync::Arc;
+
+use crate::build_info::BuildInfo;
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::notifications::repo::NotificationRepo;
+use crate::privacy::AggregateNoise;
+use crate::stats::http::{
+    add_noise, load_instance_stats, resolve_stats_days, InstanceStatsResponse,
+};
+use crate::stats::repo::StatsRepo;
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
+use async_graphql::parser::{
+    parse_query,
+    types::{DocumentOperations, OperationType},
+};
+use async_graphql::{BatchRequest, Context, EmptySubscription, Object, Result, Schema};
+use async_graphql_axum::GraphQLResponse;
+use axum::body::Body;
+use axum::extract::{Extension, FromRequest, Request};
+use axum::response::{Html, IntoResponse, Response};
+use tc_crypto::Kid;
+use uuid::Uuid;
+
+/// Maximum GraphQL request body size (1 MiB, the app-wide body limit).
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Device that signed the request.
+///
+/// Mutations require the same signed headers as authenticated REST routes
+/// (see [`AuthenticatedDevice`]); queries may optionally be signed to reach
+/// per-account fields. Resolvers read the caller with
+/// `ctx.data::<SignedCaller>()`, which is absent for unsigned queries.
+#[derive(Debug, Clone)]
+pub struct SignedCaller {
+    pub account_id: Uuid,
+    pub device_kid: Kid,
+}
+
+/// The schema type with Query and Mutation roots
+pub type ApiSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Query root for the GraphQL API
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Returns build metadata for the running service
+    #[allow(clippy::unused_async)]
+    async fn build_info(&self, ctx: &Context<'_>) -> Result<BuildInfo> {
+        Ok(ctx.data::<BuildInfo>()?.clone())
+    }
+
+    /// Daily instance-level statistics for public dashboards, newest first.
+    ///
+    /// `days` defaults to 30 and may not exceed 366. Counts carry
+    /// differential-privacy noise when `privacy.enabled` is set. Costs 10
+    /// complexity points plus its selections, as it aggregates every day.
+    #[graphql(complexity = "10 + child_complexity")]
+    async fn instance_stats(
+        &self,
+        ctx: &Context<'_>,
+        days: Option<u32>,
+    ) -> Result<InstanceStatsResponse> {
+        let days = resolve_stats_days(days)?;
+        let repo = ctx.data::<Arc<dyn StatsRepo>>()?;
+        let stats = load_instance_stats(repo.as_ref(), days)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load instance stats: {e}");
+                async_graphql::Error::new("internal error")
+            })?;
+        // Always the public view; admins read exact counts over REST
+        Ok(match ctx.data_opt::<Arc<AggregateNoise>>() {
+            Some(noise) => add_noise(stats, noise),
+            None => stats,
+        })
+    }
+
+    /// Number of unread notifications for the signed caller.
+    async fn notification_unread_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let Ok(caller) = ctx.data::<SignedCaller>() else {
+            return Err(async_graphql::Error::new("authentication required"));
+        };
+        let repo = ctx.data::<Arc<dyn NotificationRepo>>()?;
+        repo.count_unread(caller.account_id).await.map_err(|e| {
+            tracing::error!("Failed to count unread notifications: {e}");
+            async_graphql::Error::new("internal error")
+        })
+    }
+}
+
+/// Mutation root for the GraphQL API
+///
+/// Only executed for device-signed requests; see [`graphql_handler`].
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Placeholder mutation - returns the input string
+    ///
+    /// This exists because GraphQL requires at least one mutation.
+    /// Replace with actual mutations as features are implemented.
+    #[allow(clippy::unused_async)]
+    async fn echo(&self, _ctx: &Context<'_>, message: String) -> String {
+        message
+    }
+}
+
+/// GraphQL playground handler - serves the interactive GraphQL IDE
+#[allow(clippy::unused_async)]
+pub async fn graphql_playground() -> impl IntoResponse {
+    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+}
+
+/// Whether any operation in the request's document is a mutation.
+///
+/// Deliberately ignores `operationName`: a document that defines a mutation
+/// is treated as one. Unparseable documents are not mutations — execution
+/// rejects them without running any resolver.
+fn has_mutation(request: &async_graphql::Request) -> bool {
+    parse_query(&request.query).is_ok_and(|doc| match doc.operations {
+        DocumentOperations::Single(op) => op.node.ty == OperationType::Mutation,
+        DocumentOperations::Multiple(ops) => {
+            ops.values().any(|op| op.node.ty == OperationType::Mutation)
+        }
+    })
+}
+
+/// GraphQL request handler - executes GraphQL queries and mutations
+///
+/// Queries are public. A request (or batch) containing a mutation must carry
+/// valid device-signed headers over the raw body, exactly like authenticated
+/// REST routes, so GraphQL cannot become an unsigned side door for writes.
+/// A query that sends `X-Device-Kid` is verified the same way, so signed
+/// queries can read per-account fields. Signed operations are charged
+/// against the caller's per-minute cost budget; see [`cost`].
+pub async fn graphql_handler(Extension(schema): Extension<ApiSchema>, req: Request) -> Response {
+    let (parts, body) = req.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_SIZE).await else {
+        return crate::http::bad_request("Failed to read request body");
+    };
+    let batch: BatchRequest = match serde_json::from_slice(&bytes) {
+        Ok(batch) => batch,
+        Err(e) => return crate::http::bad_request(&format!("Invalid GraphQL request: {e}")),
+    };
+
+    let signed = batch.iter().any(has_mutation) || parts.headers.contains_key("X-Device-Kid");
+    let batch = if signed {
+        let req = Request::from_parts(parts, Body::from(bytes));
+        let device = match AuthenticatedDevice::from_request(req, &()).await {
+            Ok(device) => device,
+            Err(rejection) => return rejection,
+        };
+        batch.data(SignedCaller {
+            account_id: device.account_id,
+            device_kid: device.device_kid,
+        })
+    } else {
+        batch
+    };
+
+    GraphQLResponse::from(schema.execute_batch(batch).await).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(query: &str) -> async_graphql::Request {
+        async_graphql::Request::new(query)
+    }
+
+    #[test]
+    fn detects_mutations() {
+        assert!(has_mutation(&request("mutation { echo(message: \"x\") }")));
+        assert!(has_mutation(&request(
+            "query A { buildInfo { version } } mutation B { echo(message: \"x\") }"
+        )));
+        assert!(!has_mutation(&request("{ buildInfo { version } }")));
+        assert!(!has_mutation(&request("query { buildInfo { version } }")));
+        assert!(!has_mutation(&request("mutation {")));
+    }
+}
@@ -0,0 +1,22 @@
+//! Response compression layer.
+
+use tower_http::compression::{
+    predicate::{And, DefaultPredicate, SizeAbove},
+    CompressionLayer, Predicate,
+};
+
+use crate::config::CompressionConfig;
+
+/// Build the response compression layer from configuration.
+///
+/// Keeps tower-http's default exclusions (images, gRPC, event streams) and
+/// additionally skips bodies smaller than `min_size_bytes`.
+#[must_use]
+pub fn build_compression_layer(
+    config: &CompressionConfig,
+) -> CompressionLayer<And<DefaultPredicate, SizeAbove>> {
+    CompressionLayer::new()
+        .gzip(config.gzip)
+        .br(config.br)
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(config.min_size_bytes)))
+}
@@ -0,0 +1,258 @@
+//! In-flight request limits with load shedding, per route group.
+//!
+//! Routes fall into three groups (see [`ConcurrencyLimits::group_for`]):
+//! probes (`/health`, `/ready`, `/metrics`), which are never limited;
+//! expensive routes listed in `concurrency.expensive_routes`; and everything
+//! else. Each limited group holds a semaphore sized from config. A request
+//! that finds its group full is not queued: it gets `503` with `Retry-After`
+//! straight away, so a burst cannot pile up behind the database pool.
+//!
+//! The permit is held until the handler returns its response, not until the
+//! body finishes streaming. Shed requests are counted in
+//! `tc_http_requests_shed_total` and in-flight requests in
+//! `tc_http_requests_in_flight`, both labelled by group.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    http::{header::RETRY_AFTER, HeaderValue},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::ConcurrencyConfig;
+use crate::http::service_unavailable;
+
+/// Paths that are never limited, so orchestrators can always probe.
+const UNLIMITED_PATHS: [&str; 3] = ["/health", "/ready", "/metrics"];
+
+/// Prefix under which some routes are also mounted.
+const VERSION_PREFIX: &str = "/api/v1";
+
+/// Route group that picks which limit applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConcurrencyGroup {
+    /// Health, readiness, and metrics probes.
+    Unlimited,
+    /// Routes matching `concurrency.expensive_routes`.
+    Expensive,
+    /// Every other route.
+    Default,
+}
+
+impl ConcurrencyGroup {
+    /// Metric label for this group.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unlimited => "unlimited",
+            Self::Expensive => "expensive",
+            Self::Default => "default",
+        }
+    }
+}
+
+/// Semaphores and route patterns built from [`ConcurrencyConfig`].
+pub struct ConcurrencyLimits {
+    /// Segments of each expensive route prefix.
+    expensive_routes: Vec<Vec<String>>,
+    expensive: Option<Arc<Semaphore>>,
+    default: Option<Arc<Semaphore>>,
+    retry_after: HeaderValue,
+}
+
+impl ConcurrencyLimits {
+    /// Build the limits, or `None` when they are disabled.
+    #[must_use]
+    pub fn from_config(config: &ConcurrencyConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let semaphore = |limit: u32| (limit > 0).then(|| Arc::new(Semaphore::new(limit as usize)));
+        Some(Self {
+            expensive_routes: config
+                .expensive_routes
+                .iter()
+                .map(|route| segments(route))
+                .collect(),
+            expensive: semaphore(config.expensive_limit),
+            default: semaphore(config.default_limit),
+            retry_after: HeaderValue::from(config.retry_after_secs),
+        })
+    }
+
+    #[must_use]
+    pub fn group_for(&self, path: &str) -> ConcurrencyGroup {
+        if UNLIMITED_PATHS.contains(&path) {
+            return ConcurrencyGroup::Unlimited;
+        }
+        let request = segments(path.strip_prefix(VERSION_PREFIX).unwrap_or(path));
+        if self
+            .expensive_routes
+            .iter()
+            .any(|route| prefix_matches(route, &request))
+        {
+            ConcurrencyGroup::Expensive
+        } else {
+            ConcurrencyGroup::Default
+        }
+    }
+
+    const fn semaphore(&self, group: ConcurrencyGroup) -> Option<&Arc<Semaphore>> {
+        match group {
+            ConcurrencyGroup::Unlimited => None,
+            ConcurrencyGroup::Expensive => self.expensive.as_ref(),
+            ConcurrencyGroup::Default => self.default.as_ref(),
+        }
+    }
+}
+
+/// Holds a group's permit and keeps the in-flight gauge in step, including
+/// when the request future is dropped mid-flight.
+struct InFlight {
+    group: ConcurrencyGroup,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl InFlight {
+    fn new(group: ConcurrencyGroup, permit: OwnedSemaphorePermit) -> Self {
+        metrics::gauge!("tc_http_requests_in_flight", "group" => group.as_str()).increment(1.0);
+        Self {
+            group,
+            _permit: permit,
+        }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        metrics::gauge!("tc_http_requests_in_flight", "group" => self.group.as_str())
+            .decrement(1.0);
+    }
+}
+
+/// Shed requests beyond their route group's in-flight limit.
+pub async fn limit_concurrency(
+    Extension(limits): Extension<Arc<ConcurrencyLimits>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let group = limits.group_for(req.uri().path());
+    let Some(semaphore) = limits.semaphore(group) else {
+        return next.run(req).await;
+    };
+    let Ok(permit) = Arc::clone(semaphore).try_acquire_owned() else {
+        metrics::counter!("tc_http_requests_shed_total", "group" => group.as_str()).increment(1);
+        tracing::debug!(path = %req.uri().path(), group = group.as_str(), "Shedding request over concurrency limit");
+        let mut response = service_unavailable("Server is busy — please retry shortly");
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, limits.retry_after.clone());
+        return response;
+    };
+    let _in_flight = InFlight::new(group, permit);
+    next.run(req).await
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `route` matches the start of `request`, with `{param}` segments
+/// matching any value.
+fn prefix_matches(route: &[String], request: &[String]) -> bool {
+    route.len() <= request.len()
+        && route
+            .iter()
+            .zip(request)
+            .all(|(r, s)| (r.starts_with('{') && r.ends_with('}')) || r == s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn limits(expensive_limit: u32) -> ConcurrencyLimits {
+        ConcurrencyLimits::from_config(&ConcurrencyConfig {
+            expensive_limit,
+            ..ConcurrencyConfig::default()
+        })
+        .expect("enabled by default")
+    }
+
+    #[test]
+    fn paths_map_to_groups() {
+        let l = limits(8);
+        assert_eq!(l.group_for("/health"), ConcurrencyGroup::Unlimited);
+        assert_eq!(l.group_for("/metrics"), ConcurrencyGroup::Unlimited);
+        assert_eq!(
+            l.group_for("/research/exports/abc/download"),
+            ConcurrencyGroup::Expensive
+        );
+        assert_eq!(
+            l.group_for("/rooms/r1/polls/p1/results/distribution"),
+            ConcurrencyGroup::Expensive
+        );
+        assert_eq!(l.group_for("/rooms/r1/polls/p1"), ConcurrencyGroup::Default);
+        assert_eq!(l.group_for("/research"), ConcurrencyGroup::Default);
+        assert_eq!(l.group_for("/healthz"), ConcurrencyGroup::Default);
+    }
+
+    #[test]
+    fn disabled_config_returns_none() {
+        let config = ConcurrencyConfig {
+            enabled: false,
+            ..ConcurrencyConfig::default()
+        };
+        assert!(ConcurrencyLimits::from_config(&config).is_none());
+    }
+
+    #[tokio::test]
+    async fn full_group_sheds_with_retry_after() {
+        let limits = Arc::new(limits(1));
+        // Hold the only expensive permit, as a slow request would
+        let held = Arc::clone(limits.expensive.as_ref().expect("limited"))
+            .try_acquire_owned()
+            .expect("permit");
+        let app = Router::new()
+            .route("/research/exports", get(|| async { "ok" }))
+            .route("/rooms", get(|| async { "ok" }))
+            .route("/health", get(|| async { "ok" }))
+            .layer(middleware::from_fn(limit_concurrency))
+            .layer(Extension(limits));
+        let status = |path: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::get(path).body(Body::empty()).expect("request"))
+                    .await
+                    .expect("response");
+                (
+                    response.status(),
+                    response.headers().get(RETRY_AFTER).cloned(),
+                )
+            }
+        };
+
+        assert_eq!(
+            status("/research/exports").await,
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Some(HeaderValue::from_static("1"))
+            )
+        );
+        assert_eq!(status("/rooms").await, (StatusCode::OK, None));
+        assert_eq!(status("/health").await, (StatusCode::OK, None));
+
+        drop(held);
+        assert_eq!(status("/research/exports").await, (StatusCode::OK, None));
+    }
+}
@@ -0,0 +1,163 @@
+//! CORS layer with per-route-group origin lists.
+//!
+//! Routes fall into three groups (see [`CorsGroup::for_path`]): auth routes,
+//! public read-only embeds, and everything else. Each group takes its origins
+//! from `cors.auth_origins`, `cors.public_origins`, or `cors.allowed_origins`,
+//! and an empty group list falls back to `allowed_origins`.
+//!
+//! A single layer decides per request, because nested CORS layers do not
+//! compose: the outermost one answers preflights before the inner ones run.
+
+use axum::http::{HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use crate::config::CorsConfig;
+
+/// Path prefixes served under `cors.public_origins`.
+const PUBLIC_PREFIXES: [&str; 2] = ["/badges/", "/api/v1/stats"];
+
+/// Path prefix served under `cors.auth_origins`.
+const AUTH_PREFIX: &str = "/auth/";
+
+/// Route group that picks which origin list applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorsGroup {
+    /// `/auth/*`: signup, login, devices, tokens.
+    Auth,
+    /// Embeddable read-only endpoints: badges and instance stats.
+    Public,
+    /// Every other route.
+    Default,
+}
+
+impl CorsGroup {
+    #[must_use]
+    pub fn for_path(path: &str) -> Self {
+        if path.starts_with(AUTH_PREFIX) {
+            Self::Auth
+        } else if PUBLIC_PREFIXES.iter().any(|p| path.starts_with(p)) {
+            Self::Public
+        } else {
+            Self::Default
+        }
+    }
+}
+
+/// Parsed origin list for one group.
+#[derive(Debug, Clone)]
+enum Origins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+impl Origins {
+    fn parse(origins: &[String]) -> Self {
+        if origins.iter().any(|o| o == "*") {
+            return Self::Any;
+        }
+        Self::List(
+            origins
+                .iter()
+                .filter_map(|origin| match origin.parse() {
+                    Ok(v) => Some(v),
+                    Err(e) => {
+                        tracing::warn!(origin = %origin, error = %e, "Invalid CORS origin in config — skipping");
+                        None
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn allows(&self, origin: &HeaderValue) -> bool {
+        match self {
+            Self::Any => true,
+            Self::List(list) => list.contains(origin),
+        }
+    }
+
+    fn into_allow_origin(self) -> AllowOrigin {
+        match self {
+            Self::Any => AllowOrigin::any(),
+            Self::List(list) => AllowOrigin::list(list),
+        }
+    }
+}
+
+/// Build the CORS layer for the whole app from configuration.
+///
+/// Without group overrides this is a plain origin list (or `*`). With
+/// overrides, a wildcard group echoes the request's origin instead of `*`,
+/// which is equivalent for requests without credentials.
+pub fn build_cors_layer(config: &CorsConfig) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::DELETE,
+            Method::PATCH,
+            Method::OPTIONS,
+        ])
+        .allow_headers(Any);
+
+    let default = Origins::parse(&config.allowed_origins);
+    match default {
+        Origins::Any => {
+            tracing::warn!("CORS configured to allow any origin - not recommended for production");
+        }
+        Origins::List(ref list) if list.is_empty() => tracing::info!(
+            "CORS allowed origins not configured - cross-origin requests will be blocked"
+        ),
+        Origins::List(_) => {
+            tracing::info!(origins = ?config.allowed_origins, "CORS allowed origins configured");
+        }
+    }
+    if config.public_origins.is_empty() && config.auth_origins.is_empty() {
+        return layer.allow_origin(default.into_allow_origin());
+    }
+
+    let group_origins = |origins: &[String]| {
+        if origins.is_empty() {
+            default.clone()
+        } else {
+            Origins::parse(origins)
+        }
+    };
+    let public = group_origins(&config.public_origins);
+    let auth = group_origins(&config.auth_origins);
+    tracing::info!(
+        public = ?config.public_origins,
+        auth = ?config.auth_origins,
+        "Per-group CORS origins configured"
+    );
+    layer.allow_origin(AllowOrigin::predicate(
+        move |origin, parts| match CorsGroup::for_path(parts.uri.path()) {
+            CorsGroup::Auth => auth.allows(origin),
+            CorsGroup::Public => public.allows(origin),
+            CorsGroup::Default => default.allows(origin),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_map_to_groups() {
+        assert_eq!(CorsGroup::for_path("/auth/login"), CorsGroup::Auth);
+        assert_eq!(CorsGroup::for_path("/badges/alice.svg"), CorsGroup::Public);
+        assert_eq!(CorsGroup::for_path("/api/v1/stats"), CorsGroup::Public);
+        assert_eq!(CorsGroup::for_path("/authority"), CorsGroup::Default);
+        assert_eq!(CorsGroup::for_path("/graphql"), CorsGroup::Default);
+    }
+
+    #[test]
+    fn origins_match_exactly_or_by_wildcard() {
+        let list = Origins::parse(&["https://app.example.com".into(), "bad\norigin".into()]);
+        assert!(list.allows(&HeaderValue::from_static("https://app.example.com")));
+        assert!(!list.allows(&HeaderValue::from_static("https://evil.example.com")));
+        assert!(Origins::parse(&["*".into()]).allows(&HeaderValue::from_static("https://x.test")));
+    }
+}
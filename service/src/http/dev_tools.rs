@@ -0,0 +1,318 @@
+//! Access control for developer tooling (GraphQL Playground, Swagger UI).
+//!
+//! The `graphql.playground_enabled` / `swagger.enabled` toggles decide whether
+//! the tools are served; [`dev_tools_guard`] decides who reaches them, based
+//! on [`DevToolsConfig`]. With no restrictions configured the tools are public.
+//! Otherwise a request passes when:
+//!
+//! - the client IP falls in one of `allowed_cidrs`, or
+//! - `allow_admins` is set and it is device-signed (see
+//!   [`AuthenticatedDevice`]) by an account in `accounts.admin_account_ids`.
+//!
+//! Browsers cannot sign page loads, so interactive use goes through the IP
+//! allowlist; admin signatures suit scripted access such as fetching
+//! `/api-docs/openapi.json`. Rejected requests get a plain 404 so the tooling
+//! is not advertised.
+
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, FromRequest, Request},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+
+use uuid::Uuid;
+
+use crate::config::{AccountsConfig, DevToolsConfig};
+use crate::http::not_found;
+use crate::identity::http::auth::AuthenticatedDevice;
+
+/// Largest body buffered while checking an admin signature.
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// An IPv4 or IPv6 network in `ADDRESS/PREFIX` notation.
+///
+/// A bare address is a single-host range (`/32` or `/128`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+/// Error returned for a malformed CIDR range.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid CIDR range")]
+pub struct InvalidCidr;
+
+impl IpCidr {
+    /// Whether `ip` is inside this range. IPv4-mapped IPv6 addresses match
+    /// IPv4 ranges.
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => prefix_matches(
+                u128::from(net.to_bits()),
+                u128::from(ip.to_bits()),
+                self.prefix,
+                32,
+            ),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_matches(net.to_bits(), ip.to_bits(), self.prefix, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: u128, ip: u128, prefix: u8, width: u8) -> bool {
+    let host_bits = u32::from(width - prefix);
+    network.checked_shr(host_bits).unwrap_or(0) == ip.checked_shr(host_bits).unwrap_or(0)
+}
+
+impl FromStr for IpCidr {
+    type Err = InvalidCidr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = match s.trim().split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s.trim(), None),
+        };
+        let network: IpAddr = addr.parse().map_err(|_| InvalidCidr)?;
+        let width = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse::<u8>().map_err(|_| InvalidCidr)?,
+            None => width,
+        };
+        if prefix > width {
+            return Err(InvalidCidr);
+        }
+        Ok(Self { network, prefix })
+    }
+}
+
+/// Parsed [`DevToolsConfig`], shared with [`dev_tools_guard`] via `Extension`.
+#[derive(Debug, Clone, Default)]
+pub struct DevToolsAccess {
+    cidrs: Vec<IpCidr>,
+    admin_account_ids: Vec<Uuid>,
+    trust_forwarded_for: bool,
+}
+
+impl DevToolsAccess {
+    /// Admins come from `accounts` when `config.allow_admins` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidCidr`] if any allowed range does not parse.
+    pub fn from_config(
+        config: &DevToolsConfig,
+        accounts: &AccountsConfig,
+    ) -> Result<Self, InvalidCidr> {
+        Ok(Self {
+            cidrs: config
+                .allowed_cidrs
+                .iter()
+                .map(|c| c.parse())
+                .collect::<Result<_, _>>()?,
+            admin_account_ids: if config.allow_admins {
+                accounts.admin_account_ids.clone()
+            } else {
+                Vec::new()
+            },
+            trust_forwarded_for: config.trust_forwarded_for,
+        })
+    }
+
+    /// Whether any restriction is configured.
+    #[must_use]
+    pub fn is_restricted(&self) -> bool {
+        !self.cidrs.is_empty() || !self.admin_account_ids.is_empty()
+    }
+
+    fn allows_ip(&self, ip: IpAddr) -> bool {
+        self.cidrs.iter().any(|cidr| cidr.contains(ip))
+    }
+
+    fn is_admin(&self, account_id: Uuid) -> bool {
+        self.admin_account_ids.contains(&account_id)
+    }
+
+    fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        client_ip(req, self.trust_forwarded_for)
+    }
+}
+
+/// Client IP: the last `X-Forwarded-For` entry when `trust_forwarded_for` is
+/// set, else the socket peer from `ConnectInfo`.
+///
+/// Only the last entry is used because it is the one the trusted proxy
+/// appended; earlier entries come from the client and can be anything.
+pub fn client_ip(req: &Request, trust_forwarded_for: bool) -> Option<IpAddr> {
+    if trust_forwarded_for {
+        return req
+            .headers()
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse().ok());
+    }
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// Middleware that hides developer tooling from callers not allowed by
+/// [`DevToolsAccess`].
+///
+/// Expects `Extension<Arc<DevToolsAccess>>`; the admin check also needs the
+/// `Arc<dyn IdentityRepo>` extension used by [`AuthenticatedDevice`].
+pub async fn dev_tools_guard(
+    Extension(access): Extension<Arc<DevToolsAccess>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !access.is_restricted()
+        || access
+            .client_ip(&req)
+            .is_some_and(|ip| access.allows_ip(ip))
+    {
+        return next.run(req).await;
+    }
+    if access.admin_account_ids.is_empty() || !req.headers().contains_key("X-Device-Kid") {
+        return not_found("Not found");
+    }
+
+    let (parts, body) = req.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_SIZE).await else {
+        return not_found("Not found");
+    };
+    let mut auth_req = Request::new(Body::from(bytes.clone()));
+    *auth_req.method_mut() = parts.method.clone();
+    *auth_req.uri_mut() = parts.uri.clone();
+    *auth_req.headers_mut() = parts.headers.clone();
+    *auth_req.extensions_mut() = parts.extensions.clone();
+
+    match AuthenticatedDevice::from_request(auth_req, &()).await {
+        Ok(device) if access.is_admin(device.account_id) => {
+            next.run(Request::from_parts(parts, Body::from(bytes)))
+                .await
+        }
+        _ => not_found("Not found"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn cidr(s: &str) -> IpCidr {
+        s.parse().expect("valid CIDR")
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().expect("valid IP")
+    }
+
+    #[test]
+    fn cidr_parsing() {
+        assert_eq!(cidr("10.0.0.1"), cidr("10.0.0.1/32"));
+        assert_eq!(cidr("::1"), cidr("::1/128"));
+        for bad in [
+            "",
+            "10.0.0.0/33",
+            "::/129",
+            "10.0.0/8",
+            "10.0.0.0/x",
+            "host/8",
+        ] {
+            assert!(bad.parse::<IpCidr>().is_err(), "{bad:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn cidr_contains() {
+        assert!(cidr("10.0.0.0/8").contains(ip("10.255.1.2")));
+        assert!(!cidr("10.0.0.0/8").contains(ip("11.0.0.1")));
+        assert!(cidr("0.0.0.0/0").contains(ip("203.0.113.9")));
+        assert!(cidr("192.168.1.7").contains(ip("192.168.1.7")));
+        assert!(!cidr("192.168.1.7").contains(ip("192.168.1.8")));
+        assert!(cidr("fd00::/8").contains(ip("fd12::1")));
+        assert!(!cidr("fd00::/8").contains(ip("fe80::1")));
+        assert!(cidr("::/0").contains(ip("2001:db8::1")));
+        // IPv4-mapped IPv6 peers match IPv4 ranges, never IPv6 ranges
+        assert!(cidr("127.0.0.0/8").contains(ip("::ffff:127.0.0.1")));
+        assert!(!cidr("::/0").contains(ip("127.0.0.1")));
+    }
+
+    fn app(config: &DevToolsConfig) -> Router {
+        let accounts = AccountsConfig {
+            admin_account_ids: vec![Uuid::new_v4()],
+        };
+        let access = DevToolsAccess::from_config(config, &accounts).expect("valid config");
+        Router::new()
+            .route("/swagger-ui", get(|| async { "docs" }))
+            .layer(axum::middleware::from_fn(dev_tools_guard))
+            .layer(Extension(Arc::new(access)))
+    }
+
+    async fn status(app: Router, forwarded_for: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().uri("/swagger-ui");
+        if let Some(value) = forwarded_for {
+            req = req.header("X-Forwarded-For", value);
+        }
+        app.oneshot(req.body(Body::empty()).expect("request"))
+            .await
+            .expect("response")
+            .status()
+    }
+
+    #[tokio::test]
+    async fn unrestricted_is_public() {
+        let app = app(&DevToolsConfig::default());
+        assert_eq!(status(app, None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn allowlisted_ip_passes_and_others_are_hidden() {
+        let config = DevToolsConfig {
+            allowed_cidrs: vec!["10.0.0.0/8".into()],
+            trust_forwarded_for: true,
+            ..DevToolsConfig::default()
+        };
+        assert_eq!(status(app(&config), Some("10.1.2.3")).await, StatusCode::OK);
+        // Only the entry appended by the trusted proxy counts
+        assert_eq!(
+            status(app(&config), Some("10.1.2.3, 203.0.113.9")).await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(status(app(&config), None).await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn forwarded_for_ignored_unless_trusted() {
+        let config = DevToolsConfig {
+            allowed_cidrs: vec!["10.0.0.0/8".into()],
+            ..DevToolsConfig::default()
+        };
+        assert_eq!(
+            status(app(&config), Some("10.1.2.3")).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn unsigned_request_hidden_when_only_admins_allowed() {
+        let config = DevToolsConfig {
+            allow_admins: true,
+            ..DevToolsConfig::default()
+        };
+        assert_eq!(status(app(&config), None).await, StatusCode::NOT_FOUND);
+    }
+}
@@ -2,9 +2,20 @@
 //!
 //! This module provides shared HTTP functionality used by the application server.
 
+pub mod compression;
+pub mod concurrency;
+pub mod cors;
+pub mod dev_tools;
 pub mod rate_limit;
+pub mod read_only;
+pub mod response_check;
 pub mod security;
 
+pub use compression::build_compression_layer;
+pub use concurrency::{limit_concurrency, ConcurrencyLimits};
+pub use cors::build_cors_layer;
+pub use dev_tools::{dev_tools_guard, DevToolsAccess};
+pub use read_only::reject_writes_when_read_only;
 pub use security::{build_security_headers, security_headers_middleware};
 
 use axum::{
@@ -52,6 +63,25 @@ fn extract_param_name(rejection: &PathRejection) -> String {
     "parameter".to_string()
 }
 
+/// A drop-in replacement for [`axum::extract::Query`] that returns a JSON
+/// [`ErrorResponse`] on rejection instead of a plain-text response.
+pub struct Query<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    T: serde::de::DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = axum::response::Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Self(value)),
+            Err(rejection) => Err(bad_request(&rejection.body_text())),
+        }
+    }
+}
+
 /// Error response body shared by all HTTP handlers.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -126,6 +156,29 @@ pub fn forbidden(msg: &str) -> axum::response::Response {
         .into_response()
 }
 
+/// 403 Forbidden response whose body also carries a machine-readable `code`
+/// (e.g. `ACCOUNT_TOO_NEW`) so clients can explain the refusal.
+#[must_use]
+pub fn forbidden_with_code(code: &str, msg: &str) -> axum::response::Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(serde_json::json!({ "error": msg, "code": code })),
+    )
+        .into_response()
+}
+
+/// 413 Payload Too Large response with a JSON error body.
+#[must_use]
+pub fn payload_too_large(msg: &str) -> axum::response::Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(ErrorResponse {
+            error: msg.to_string(),
+        }),
+    )
+        .into_response()
+}
+
 /// 429 Too Many Requests response with a JSON error body.
 #[must_use]
 pub fn too_many_requests(msg: &str) -> axum::response::Response {
@@ -138,6 +191,29 @@ pub fn too_many_requests(msg: &str) -> axum::response::Response {
         .into_response()
 }
 
+/// 503 Service Unavailable response with a JSON error body.
+#[must_use]
+pub fn service_unavailable(msg: &str) -> axum::response::Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ErrorResponse {
+            error: msg.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// 503 Service Unavailable response whose body also carries a
+/// machine-readable `code`, like [`forbidden_with_code`].
+#[must_use]
+pub fn service_unavailable_with_code(code: &str, msg: &str) -> axum::response::Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({ "error": msg, "code": code })),
+    )
+        .into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
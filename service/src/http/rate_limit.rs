@@ -101,6 +101,10 @@ mod tests {
             signup_per_minute: 5,
             login_per_minute: 10,
             backup_per_minute: 10,
+            badge_per_minute: 60,
+            verify_per_minute: 30,
+            sms_per_minute: 5,
+            username_check_per_minute: 30,
         }
     }
 
@@ -0,0 +1,125 @@
+//! Reject mutations while the database is read-only.
+//!
+//! While [`ReadOnlyMode`] is active (see [`crate::db::read_only`]), requests
+//! that would write get `503` with code `READ_ONLY_MODE` and `Retry-After`
+//! instead of a database error partway through the handler. That covers
+//! every method other than `GET`, `HEAD` and `OPTIONS`, and also signed
+//! requests of any method, since verifying a device signature records its
+//! nonce. `POST /graphql` is let through because queries are sent as POST too.
+//! Probes are never rejected.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    http::{header::RETRY_AFTER, Method},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+
+use crate::db::read_only::ReadOnlyMode;
+use crate::http::service_unavailable_with_code;
+
+/// Error code clients can match to show a maintenance notice.
+pub const READ_ONLY_CODE: &str = "READ_ONLY_MODE";
+
+/// Paths that are never rejected.
+const ALWAYS_ALLOWED: [&str; 4] = ["/health", "/ready", "/metrics", "/graphql"];
+
+/// Whether `req` could write to the database.
+fn writes(req: &Request) -> bool {
+    if ALWAYS_ALLOWED.contains(&req.uri().path()) {
+        return false;
+    }
+    let safe = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    !safe || req.headers().contains_key("X-Device-Kid")
+}
+
+/// Answer mutations with `503 READ_ONLY_MODE` while read-only mode is active.
+pub async fn reject_writes_when_read_only(
+    Extension(mode): Extension<Arc<ReadOnlyMode>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !mode.is_active() || !writes(&req) {
+        return next.run(req).await;
+    }
+    metrics::counter!("tc_http_requests_read_only_rejected_total").increment(1);
+    let mut response = service_unavailable_with_code(
+        READ_ONLY_CODE,
+        "The service is temporarily read-only — please retry shortly",
+    );
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, mode.retry_after().clone());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ReadOnlyConfig;
+    use axum::{
+        body::{to_bytes, Body},
+        http::{HeaderValue, StatusCode},
+        middleware,
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn rejects_only_writes_while_active() {
+        let mode = Arc::new(ReadOnlyMode::new(&ReadOnlyConfig::default()));
+        let app = Router::new()
+            .route("/rooms", get(|| async { "ok" }).post(|| async { "ok" }))
+            .route("/graphql", get(|| async { "ok" }).post(|| async { "ok" }))
+            .layer(middleware::from_fn(reject_writes_when_read_only))
+            .layer(Extension(Arc::clone(&mode)));
+        let call = |method: Method, path: &'static str, signed: bool| {
+            let app = app.clone();
+            async move {
+                let mut builder = Request::builder().method(method).uri(path);
+                if signed {
+                    builder = builder.header("X-Device-Kid", "kid");
+                }
+                let response = app
+                    .oneshot(builder.body(Body::empty()).expect("request"))
+                    .await
+                    .expect("response");
+                response.status()
+            }
+        };
+
+        assert_eq!(call(Method::POST, "/rooms", false).await, StatusCode::OK);
+
+        mode.set(true);
+        assert_eq!(call(Method::GET, "/rooms", false).await, StatusCode::OK);
+        assert_eq!(call(Method::POST, "/graphql", false).await, StatusCode::OK);
+        assert_eq!(
+            call(Method::GET, "/rooms", true).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/rooms")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers().get(RETRY_AFTER),
+            Some(&HeaderValue::from_static("10"))
+        );
+        let body = to_bytes(response.into_body(), 1024).await.expect("body");
+        let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(json["code"], READ_ONLY_CODE);
+
+        mode.set(false);
+        assert_eq!(call(Method::POST, "/rooms", false).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,359 @@
+//! Development check that JSON responses match the `OpenAPI` document.
+//!
+//! With `dev_tools.validate_responses` on, [`validate_responses`] buffers
+//! every `application/json` response, looks up the documented schema for the
+//! route and status in [`ApiDoc`](crate::rest::ApiDoc), and logs a warning
+//! for each place the body disagrees: wrong type, missing required field,
+//! field the schema does not list, or value outside an `enum`. Responses are
+//! passed through unchanged either way.
+//!
+//! The checker covers the JSON Schema subset utoipa emits (`$ref`, `type`,
+//! `properties`, `required`, `items`, `enum`, `oneOf`/`anyOf`/`allOf`,
+//! `additionalProperties`). It is meant for local runs and CI, not
+//! production: it costs a full parse of every response.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{OriginalUri, Request},
+    http::{header::CONTENT_TYPE, Method},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use http_body::Body as _;
+use serde_json::Value;
+
+use crate::http::internal_error;
+
+/// Largest response body that is checked; bigger ones pass unchecked.
+const MAX_CHECKED_BODY: usize = 4 * 1024 * 1024;
+
+/// Deepest schema nesting followed, as a guard against recursive `$ref`s.
+const MAX_DEPTH: usize = 32;
+
+/// Prefix under which some documented paths are also mounted.
+const VERSION_PREFIX: &str = "/api/v1";
+
+/// Response schemas from an `OpenAPI` document, indexed for lookup by route.
+pub struct ResponseSchemas {
+    doc: Value,
+    /// `(documented path, path segments)` for matching request paths.
+    routes: Vec<(String, Vec<String>)>,
+}
+
+impl ResponseSchemas {
+    /// Index `doc`, an `OpenAPI` document serialized to JSON.
+    #[must_use]
+    pub fn new(doc: Value) -> Self {
+        let routes = doc["paths"]
+            .as_object()
+            .map(|paths| {
+                paths
+                    .keys()
+                    .map(|path| (path.clone(), segments(path)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { doc, routes }
+    }
+
+    /// The documented JSON schema for `method path` answering `status`, if
+    /// any. Falls back to the `default` response.
+    fn schema_for(&self, method: &Method, path: &str, status: u16) -> Option<&Value> {
+        let candidates = [Some(path), path.strip_prefix(VERSION_PREFIX)];
+        let documented = candidates.into_iter().flatten().find_map(|p| {
+            let request = segments(p);
+            self.routes
+                .iter()
+                .find(|(_, route)| route_matches(route, &request))
+        })?;
+        let operation = &self.doc["paths"][&documented.0][method.as_str().to_ascii_lowercase()];
+        let responses = operation.get("responses")?;
+        let response = responses
+            .get(status.to_string())
+            .or_else(|| responses.get("default"))?;
+        let response = self.resolve(response, 0)?;
+        response["content"]
+            .as_object()?
+            .iter()
+            .find(|(media, _)| media.starts_with("application/json"))
+            .and_then(|(_, content)| content.get("schema"))
+    }
+
+    /// Every mismatch between `body` and the documented schema, as
+    /// `"$.path: problem"` strings. Empty when the route is undocumented.
+    #[must_use]
+    pub fn check(&self, method: &Method, path: &str, status: u16, body: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(schema) = self.schema_for(method, path, status) {
+            self.validate(schema, body, "$", 0, &mut errors);
+        }
+        errors
+    }
+
+    fn resolve<'a>(&'a self, schema: &'a Value, depth: usize) -> Option<&'a Value> {
+        let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+            return Some(schema);
+        };
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let pointer = reference.strip_prefix('#')?;
+        self.resolve(self.doc.pointer(pointer)?, depth + 1)
+    }
+
+    fn validate(
+        &self,
+        schema: &Value,
+        value: &Value,
+        at: &str,
+        depth: usize,
+        errors: &mut Vec<String>,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let Some(schema) = self.resolve(schema, depth) else {
+            errors.push(format!("{at}: unresolvable schema reference"));
+            return;
+        };
+
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            for part in parts {
+                self.validate(part, value, at, depth + 1, errors);
+            }
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(options) = schema.get(key).and_then(Value::as_array) {
+                let matches = options.iter().any(|option| {
+                    let mut scratch = Vec::new();
+                    self.validate(option, value, at, depth + 1, &mut scratch);
+                    scratch.is_empty()
+                });
+                if !matches {
+                    errors.push(format!("{at}: matches none of the {key} variants"));
+                }
+            }
+        }
+
+        if let Some(types) = schema_types(schema) {
+            if !types.iter().any(|t| type_matches(t, value)) {
+                errors.push(format!(
+                    "{at}: expected {}, got {}",
+                    types.join(" or "),
+                    json_type(value)
+                ));
+                return;
+            }
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !value.is_null() && !allowed.contains(value) {
+                errors.push(format!("{at}: {value} is not one of the documented values"));
+            }
+        }
+
+        match value {
+            Value::Object(fields) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !fields.contains_key(name) {
+                            errors.push(format!("{at}: missing required field `{name}`"));
+                        }
+                    }
+                }
+                let extra = schema.get("additionalProperties");
+                for (name, field) in fields {
+                    let field_at = format!("{at}.{name}");
+                    if let Some(property) = properties.and_then(|p| p.get(name)) {
+                        self.validate(property, field, &field_at, depth + 1, errors);
+                    } else if let Some(extra) = extra.filter(|e| e.is_object()) {
+                        self.validate(extra, field, &field_at, depth + 1, errors);
+                    } else if properties.is_some() && extra != Some(&Value::Bool(true)) {
+                        errors.push(format!("{field_at}: field is not documented"));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items").filter(|i| i.is_object()) {
+                    for (i, item) in items.iter().enumerate() {
+                        self.validate(item_schema, item, &format!("{at}[{i}]"), depth + 1, errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Log every JSON response that does not match its documented schema.
+pub async fn validate_responses(
+    Extension(schemas): Extension<Arc<ResponseSchemas>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let path = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| req.uri().path().to_string(), |o| o.0.path().to_string());
+    let response = next.run(req).await;
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    // Only buffer bodies of known, bounded length; streams pass untouched
+    let bounded = body
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= MAX_CHECKED_BODY as u64);
+    if !bounded {
+        tracing::debug!(%method, %path, "Response not checked against the OpenAPI schema: unbounded body");
+        return Response::from_parts(parts, body);
+    }
+    let bytes = match axum::body::to_bytes(body, MAX_CHECKED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(%method, %path, "Failed to buffer response for schema check: {e}");
+            return internal_error();
+        }
+    };
+    match serde_json::from_slice::<Value>(&bytes) {
+        Ok(json) => {
+            for mismatch in schemas.check(&method, &path, parts.status.as_u16(), &json) {
+                tracing::warn!(
+                    %method,
+                    %path,
+                    status = parts.status.as_u16(),
+                    mismatch,
+                    "Response does not match OpenAPI schema"
+                );
+            }
+        }
+        Err(e) => tracing::warn!(%method, %path, "Response labelled JSON does not parse: {e}"),
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn route_matches(route: &[String], request: &[String]) -> bool {
+    route.len() == request.len()
+        && route
+            .iter()
+            .zip(request)
+            .all(|(r, s)| (r.starts_with('{') && r.ends_with('}')) || r == s)
+}
+
+fn schema_types(schema: &Value) -> Option<Vec<&str>> {
+    match schema.get("type")? {
+        Value::String(t) => Some(vec![t.as_str()]),
+        Value::Array(ts) => Some(ts.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+const fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schemas() -> ResponseSchemas {
+        ResponseSchemas::new(json!({
+            "paths": {
+                "/stats": {"get": {"responses": {"200": {"content": {"application/json": {
+                    "schema": {"$ref": "#/components/schemas/Stats"}
+                }}}}}},
+                "/items/{id}": {"get": {"responses": {"default": {"content": {"application/json": {
+                    "schema": {"type": "array", "items": {"type": "integer"}}
+                }}}}}}
+            },
+            "components": {"schemas": {"Stats": {
+                "type": "object",
+                "required": ["count", "status"],
+                "properties": {
+                    "count": {"type": "integer"},
+                    "status": {"type": "string", "enum": ["ok", "degraded"]},
+                    "note": {"type": ["string", "null"]}
+                }
+            }}}
+        }))
+    }
+
+    #[test]
+    fn matching_bodies_pass() {
+        let s = schemas();
+        let body = json!({"count": 3, "status": "ok", "note": null});
+        assert!(s.check(&Method::GET, "/stats", 200, &body).is_empty());
+        assert!(s
+            .check(&Method::GET, "/api/v1/stats", 200, &body)
+            .is_empty());
+        assert!(s
+            .check(&Method::GET, "/items/42", 404, &json!([1, 2]))
+            .is_empty());
+        // Undocumented routes and statuses are not checked
+        assert!(s.check(&Method::GET, "/other", 200, &json!(1)).is_empty());
+        assert!(s.check(&Method::POST, "/stats", 200, &json!(1)).is_empty());
+    }
+
+    #[test]
+    fn drift_is_reported() {
+        let s = schemas();
+        let mut errors = s.check(
+            &Method::GET,
+            "/stats",
+            200,
+            &json!({"count": "3", "status": "down", "extra": true}),
+        );
+        errors.sort();
+        assert_eq!(
+            errors,
+            [
+                "$.count: expected integer, got string",
+                "$.extra: field is not documented",
+                "$.status: \"down\" is not one of the documented values",
+            ]
+        );
+        let errors = s.check(&Method::GET, "/stats", 200, &json!({"count": 1}));
+        assert_eq!(errors, ["$: missing required field `status`"]);
+        let errors = s.check(&Method::GET, "/items/1", 200, &json!([1, "two"]));
+        assert_eq!(errors, ["$[1]: expected integer, got string"]);
+    }
+}
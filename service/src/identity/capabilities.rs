@@ -0,0 +1,290 @@
+//! Client capability negotiation for versioned envelope formats.
+//!
+//! Request signatures and device certificates each exist in more than one
+//! version. A client lists the versions it can produce as tokens such as
+//! `sig-v2, cert-v2`: in the `TC-Client-Caps` header on any signed request
+//! or login, or in `device.capabilities` at signup.
+//!
+//! The server records, per device, the highest version of each format the
+//! device has ever declared, and only ever raises it. From then on the
+//! device must use at least that version: a v1 request signature from a
+//! device that declared `sig-v2`, or a raw certificate added by one that
+//! declared `cert-v2`, is refused with 403 and code
+//! `SIGNATURE_UPGRADE_REQUIRED` or `CERTIFICATE_UPGRADE_REQUIRED` instead of
+//! being accepted in the weaker format. Devices that never declare anything
+//! keep working with every version the instance accepts.
+//!
+//! Versions newer than the server knows are negotiated down to its latest,
+//! and unknown formats are ignored, so clients can declare ahead of the
+//! server. The instance document lists what the server accepts.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Header carrying a client's capability tokens.
+pub const CLIENT_CAPS_HEADER: &str = "tc-client-caps";
+
+/// Most tokens accepted in one declaration.
+const MAX_TOKENS: usize = 16;
+
+/// An envelope format with more than one version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EnvelopeFormat {
+    /// Request signatures: v1 legacy, v2 with `X-Signature-Alg`
+    Signature,
+    /// Device certificates: v1 over the raw pubkey, v2 also over a timestamp
+    Certificate,
+}
+
+impl EnvelopeFormat {
+    /// Every format.
+    pub const ALL: [Self; 2] = [Self::Signature, Self::Certificate];
+
+    /// Token prefix, as in `sig-v2`.
+    #[must_use]
+    pub const fn token(self) -> &'static str {
+        match self {
+            Self::Signature => "sig",
+            Self::Certificate => "cert",
+        }
+    }
+
+    /// Newest version the server understands.
+    #[must_use]
+    pub const fn latest(self) -> u16 {
+        match self {
+            Self::Signature | Self::Certificate => 2,
+        }
+    }
+
+    /// Error code for a request in an older version than the device declared.
+    #[must_use]
+    pub const fn upgrade_code(self) -> &'static str {
+        match self {
+            Self::Signature => "SIGNATURE_UPGRADE_REQUIRED",
+            Self::Certificate => "CERTIFICATE_UPGRADE_REQUIRED",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.token() == token)
+    }
+}
+
+impl fmt::Display for EnvelopeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Signature => "request signature",
+            Self::Certificate => "device certificate",
+        })
+    }
+}
+
+/// Why a capability declaration was rejected.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CapsError {
+    #[error("malformed capability token {0:?}; expected <format>-v<version>")]
+    Malformed(String),
+    #[error("at most {MAX_TOKENS} capability tokens are accepted")]
+    TooMany,
+}
+
+/// A request used an older version than the device declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpgradeRequired {
+    pub format: EnvelopeFormat,
+    pub required: u16,
+    pub used: u16,
+}
+
+impl fmt::Display for UpgradeRequired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "This device declared {} version {}; version {} is no longer accepted from it",
+            self.format, self.required, self.used
+        )
+    }
+}
+
+/// The highest version of each known format a client can produce.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientCaps {
+    versions: BTreeMap<EnvelopeFormat, u16>,
+}
+
+impl ClientCaps {
+    /// Parse a declaration: tokens separated by commas or whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapsError`] for a token that is not `<format>-v<version>`
+    /// or for more than 16 tokens.
+    pub fn parse(declaration: &str) -> Result<Self, CapsError> {
+        Self::parse_tokens(
+            declaration
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|t| !t.is_empty()),
+        )
+    }
+
+    /// Parse a list of tokens, as sent in `device.capabilities`.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::parse`].
+    pub fn parse_tokens<'a>(tokens: impl IntoIterator<Item = &'a str>) -> Result<Self, CapsError> {
+        let mut caps = Self::default();
+        for (i, token) in tokens.into_iter().enumerate() {
+            if i == MAX_TOKENS {
+                return Err(CapsError::TooMany);
+            }
+            let (format, version) =
+                split_token(token.trim()).ok_or_else(|| CapsError::Malformed(token.to_string()))?;
+            if let Some(format) = EnvelopeFormat::from_token(format) {
+                caps.raise(format, version.min(format.latest()));
+            }
+        }
+        Ok(caps)
+    }
+
+    /// Caps as stored in `device_keys.client_caps`; unreadable tokens are
+    /// skipped.
+    #[must_use]
+    pub fn from_stored(tokens: &[String]) -> Self {
+        let mut caps = Self::default();
+        for (format, version) in tokens.iter().filter_map(|t| split_token(t)) {
+            if let Some(format) = EnvelopeFormat::from_token(format) {
+                caps.raise(format, version.min(format.latest()));
+            }
+        }
+        caps
+    }
+
+    /// One token per declared format, e.g. `["sig-v2", "cert-v2"]`.
+    #[must_use]
+    pub fn tokens(&self) -> Vec<String> {
+        self.versions
+            .iter()
+            .map(|(format, version)| format!("{}-v{version}", format.token()))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.versions.is_empty()
+    }
+
+    /// Highest declared version of `format`.
+    #[must_use]
+    pub fn highest(&self, format: EnvelopeFormat) -> Option<u16> {
+        self.versions.get(&format).copied()
+    }
+
+    /// The higher version of each format from either side.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut caps = self.clone();
+        for (&format, &version) in &other.versions {
+            caps.raise(format, version);
+        }
+        caps
+    }
+
+    /// Refuse `used` if the client declared a newer version of `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UpgradeRequired`] naming the version the client must use.
+    pub fn require(&self, format: EnvelopeFormat, used: u16) -> Result<(), UpgradeRequired> {
+        match self.highest(format) {
+            Some(required) if used < required => Err(UpgradeRequired {
+                format,
+                required,
+                used,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn raise(&mut self, format: EnvelopeFormat, version: u16) {
+        let entry = self.versions.entry(format).or_insert(version);
+        *entry = (*entry).max(version);
+    }
+}
+
+/// `("sig", 2)` for `sig-v2`.
+fn split_token(token: &str) -> Option<(&str, u16)> {
+    let (format, version) = token.rsplit_once("-v")?;
+    let version: u16 = version.parse().ok()?;
+    (!format.is_empty() && version > 0).then_some((format, version))
+}
+
+/// Every token the server accepts right now; v1 signatures only while
+/// `auth.accept_v1_signatures` is on.
+#[must_use]
+pub fn server_caps(accept_v1_signatures: bool) -> Vec<String> {
+    EnvelopeFormat::ALL
+        .into_iter()
+        .flat_map(|format| {
+            let oldest = if format == EnvelopeFormat::Signature && !accept_v1_signatures {
+                2
+            } else {
+                1
+            };
+            (oldest..=format.latest()).map(move |v| format!("{}-v{v}", format.token()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keeps_the_highest_known_version() {
+        let caps = ClientCaps::parse("sig-v1, sig-v2 cert-v1,future-v9").expect("parse");
+        assert_eq!(caps.highest(EnvelopeFormat::Signature), Some(2));
+        assert_eq!(caps.highest(EnvelopeFormat::Certificate), Some(1));
+        assert_eq!(caps.tokens(), ["sig-v2", "cert-v1"]);
+
+        // Versions from the future are negotiated down to ours
+        let ahead = ClientCaps::parse("sig-v7").expect("parse");
+        assert_eq!(ahead.highest(EnvelopeFormat::Signature), Some(2));
+
+        for bad in ["sig", "sig-v", "sig-v0", "-v2", "sig-vx"] {
+            assert!(ClientCaps::parse(bad).is_err(), "{bad}");
+        }
+        assert_eq!(
+            ClientCaps::parse(&vec!["sig-v2"; 17].join(",")),
+            Err(CapsError::TooMany)
+        );
+    }
+
+    #[test]
+    fn declared_versions_only_rise() {
+        let stored = ClientCaps::from_stored(&["sig-v2".to_string(), "junk".to_string()]);
+        let declared = ClientCaps::parse("sig-v1, cert-v2").expect("parse");
+        let caps = stored.union(&declared);
+        assert_eq!(caps.tokens(), ["sig-v2", "cert-v2"]);
+
+        assert!(caps.require(EnvelopeFormat::Signature, 2).is_ok());
+        let err = caps
+            .require(EnvelopeFormat::Signature, 1)
+            .expect_err("downgrade");
+        assert_eq!(err.required, 2);
+        assert_eq!(err.format.upgrade_code(), "SIGNATURE_UPGRADE_REQUIRED");
+        assert!(ClientCaps::default()
+            .require(EnvelopeFormat::Certificate, 1)
+            .is_ok());
+    }
+
+    #[test]
+    fn server_caps_follow_the_v1_switch() {
+        assert_eq!(
+            server_caps(true),
+            ["sig-v1", "sig-v2", "cert-v1", "cert-v2"]
+        );
+        assert_eq!(server_caps(false), ["sig-v2", "cert-v1", "cert-v2"]);
+    }
+}
@@ -0,0 +1,507 @@
+//! Device revocation lists for offline and federated verifiers.
+//!
+//! `GET /accounts/{account_id}/crl` publishes a [`DeviceCrl`]: every revoked
+//! device of an account with the time it was revoked. The server holds no
+//! signing key, so the list is signed entry by entry instead: each entry
+//! carries the revoking device's signed `DELETE /auth/devices/{kid}` request,
+//! and the document includes the root certificates of those devices. A key
+//! retired by rotation is its own revoker: its entry carries the signed
+//! `POST /auth/devices/{kid}/rotate` request made with the old key.
+//! [`verify_crl`] checks the whole chain — root key, signer certificates,
+//! revocation signatures — without contacting the server.
+//!
+//! Devices revoked before revocation requests were kept have no signature;
+//! [`verify_crl`] reports them as unsigned rather than failing.
+//!
+//! The document is regenerated after every revocation and stored in
+//! `account_crls`. `crl_number` is the count of revoked devices, so a stored
+//! document that trails the current count is stale and is rebuilt on read.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tc_crypto::{encode_base64url, verify_ed25519, Kid};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::repo::{
+    AccountRecord, AccountRepoError, CrlRepoError, DeviceKeyRecord, DeviceKeyRepoError,
+    IdentityRepo,
+};
+use super::service::{device_certificate_message, CertificateSignature, DevicePubkey, RootPubkey};
+use super::signed_request::SignedRequest;
+
+/// Version written to every CRL. Verifiers reject other versions.
+pub const CRL_FORMAT_VERSION: u32 = 1;
+
+// ─── Document ──────────────────────────────────────────────────────────────
+
+/// An account's published device revocation list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DeviceCrl {
+    pub format_version: u32,
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    #[schema(value_type = String)]
+    pub root_kid: Kid,
+    /// Base64url-encoded account root public key
+    pub root_pubkey: String,
+    /// Number of revoked devices; grows with every revocation
+    pub crl_number: i64,
+    #[schema(value_type = String, format = DateTime)]
+    pub generated_at: DateTime<Utc>,
+    /// Revoked devices, oldest revocation first
+    pub revoked: Vec<CrlEntry>,
+    /// Certificates of the devices that signed the revocations
+    pub signers: Vec<CrlSigner>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CrlEntry {
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    #[schema(value_type = String, format = DateTime)]
+    pub revoked_at: DateTime<Utc>,
+    #[schema(value_type = Option<String>)]
+    pub revoked_by_kid: Option<Kid>,
+    /// The revoking device's signed request; absent for older revocations
+    #[schema(value_type = Option<Object>)]
+    pub revocation: Option<SignedRequest>,
+}
+
+/// A device key certified by the account root, as needed to check its
+/// revocation signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CrlSigner {
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    pub device_pubkey: String,
+    /// Base64url-encoded root signature; see
+    /// [`device_certificate_message`](super::service::device_certificate_message)
+    pub certificate: String,
+    /// Login timestamp bound into a version 2 certificate
+    pub certificate_timestamp: Option<i64>,
+}
+
+/// Build the CRL for `account` from all of its devices.
+///
+/// A stored revocation request that no longer parses is dropped, leaving the
+/// entry unsigned.
+#[must_use]
+pub fn build_crl(
+    account: &AccountRecord,
+    devices: &[DeviceKeyRecord],
+    generated_at: DateTime<Utc>,
+) -> DeviceCrl {
+    let mut revoked: Vec<(&DeviceKeyRecord, DateTime<Utc>)> = devices
+        .iter()
+        .filter_map(|d| d.revoked_at.map(|at| (d, at)))
+        .collect();
+    revoked.sort_by(|(a, a_at), (b, b_at)| {
+        a_at.cmp(b_at)
+            .then_with(|| a.device_kid.as_str().cmp(b.device_kid.as_str()))
+    });
+
+    let entries: Vec<CrlEntry> = revoked
+        .iter()
+        .map(|(d, revoked_at)| CrlEntry {
+            device_kid: d.device_kid.clone(),
+            revoked_at: *revoked_at,
+            revoked_by_kid: d.revoked_by_kid.clone(),
+            revocation: d
+                .revocation_envelope
+                .clone()
+                .and_then(|v| serde_json::from_value(v).ok()),
+        })
+        .collect();
+
+    let signer_kids: HashSet<&Kid> = entries
+        .iter()
+        .filter_map(|e| e.revocation.as_ref().map(|r| &r.device_kid))
+        .collect();
+    let signers = devices
+        .iter()
+        .filter(|d| signer_kids.contains(&d.device_kid))
+        .map(|d| CrlSigner {
+            device_kid: d.device_kid.clone(),
+            device_pubkey: d.device_pubkey.clone(),
+            certificate: encode_base64url(&d.certificate),
+            certificate_timestamp: d.certificate_timestamp,
+        })
+        .collect();
+
+    DeviceCrl {
+        format_version: CRL_FORMAT_VERSION,
+        account_id: account.id,
+        root_kid: account.root_kid.clone(),
+        root_pubkey: account.root_pubkey.clone(),
+        crl_number: i64::try_from(entries.len()).unwrap_or(i64::MAX),
+        generated_at,
+        revoked: entries,
+        signers,
+    }
+}
+
+// ─── Verification ──────────────────────────────────────────────────────────
+
+/// Why a CRL failed verification.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CrlError {
+    #[error("unsupported format_version {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid root public key")]
+    InvalidRootKey,
+    #[error("root_kid does not match root public key")]
+    RootKidMismatch,
+    #[error("signer {0}: key or certificate does not verify against root key")]
+    InvalidSigner(Kid),
+    #[error("device {0}: revocation is not a valid signed revocation of this device")]
+    InvalidRevocation(Kid),
+}
+
+/// Revoked devices of a verified CRL, split by whether a signature backs the
+/// revocation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifiedCrl {
+    pub signed: Vec<Kid>,
+    /// Revoked before revocation requests were kept; only the server vouches
+    /// for these.
+    pub unsigned: Vec<Kid>,
+}
+
+/// Check a CRL offline against its own root key.
+///
+/// Every signer must be certified by the root, and every signed entry must be
+/// a `DELETE /auth/devices/{kid}` of that entry's device, signed by the
+/// device named in `revoked_by_kid`.
+///
+/// # Errors
+///
+/// Returns the first [`CrlError`] found.
+pub fn verify_crl(crl: &DeviceCrl) -> Result<VerifiedCrl, CrlError> {
+    if crl.format_version != CRL_FORMAT_VERSION {
+        return Err(CrlError::UnsupportedVersion(crl.format_version));
+    }
+    let root_pubkey =
+        RootPubkey::from_base64url(&crl.root_pubkey).map_err(|_| CrlError::InvalidRootKey)?;
+    if root_pubkey.kid() != crl.root_kid {
+        return Err(CrlError::RootKidMismatch);
+    }
+
+    let mut signers = Vec::with_capacity(crl.signers.len());
+    for signer in &crl.signers {
+        signers.push((
+            &signer.device_kid,
+            verify_signer(&root_pubkey, signer)
+                .ok_or_else(|| CrlError::InvalidSigner(signer.device_kid.clone()))?,
+        ));
+    }
+
+    let mut verified = VerifiedCrl::default();
+    for entry in &crl.revoked {
+        let Some(revocation) = &entry.revocation else {
+            verified.unsigned.push(entry.device_kid.clone());
+            continue;
+        };
+        let invalid = || CrlError::InvalidRevocation(entry.device_kid.clone());
+        let signer_kid = &revocation.device_kid;
+        if entry.revoked_by_kid.as_ref() != Some(signer_kid)
+            || !revokes_device(revocation, &entry.device_kid)
+        {
+            return Err(invalid());
+        }
+        let (_, pubkey) = signers
+            .iter()
+            .find(|(kid, _)| *kid == signer_kid)
+            .ok_or_else(invalid)?;
+        if !revocation.verify(pubkey) {
+            return Err(invalid());
+        }
+        verified.signed.push(entry.device_kid.clone());
+    }
+    Ok(verified)
+}
+
+fn verify_signer(root_pubkey: &RootPubkey, signer: &CrlSigner) -> Option<DevicePubkey> {
+    let device_pubkey = DevicePubkey::from_base64url(&signer.device_pubkey).ok()?;
+    if device_pubkey.kid() != signer.device_kid {
+        return None;
+    }
+    let cert = CertificateSignature::from_base64url(&signer.certificate).ok()?;
+    verify_ed25519(
+        root_pubkey.as_bytes(),
+        &device_certificate_message(device_pubkey.as_bytes(), signer.certificate_timestamp),
+        cert.as_bytes(),
+    )
+    .ok()?;
+    Some(device_pubkey)
+}
+
+/// Whether `request` is a `DELETE` of `/auth/devices/{device_kid}`, or the
+/// device's own `POST /auth/devices/{device_kid}/rotate`, under any path
+/// prefix the deployment mounts the API at.
+fn revokes_device(request: &SignedRequest, device_kid: &Kid) -> bool {
+    let path = request
+        .path_and_query
+        .split_once('?')
+        .map_or(request.path_and_query.as_str(), |(path, _)| path);
+    match request.method.as_str() {
+        "DELETE" => path.ends_with(&format!("/auth/devices/{device_kid}")),
+        "POST" => {
+            request.device_kid == *device_kid
+                && path.ends_with(&format!("/auth/devices/{device_kid}/rotate"))
+        }
+        _ => false,
+    }
+}
+
+// ─── Publishing ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum CrlPublishError {
+    #[error("account not found")]
+    AccountNotFound,
+    #[error("account lookup failed: {0}")]
+    Account(AccountRepoError),
+    #[error("device lookup failed: {0}")]
+    Devices(#[from] DeviceKeyRepoError),
+    #[error("CRL storage failed: {0}")]
+    Store(#[from] CrlRepoError),
+    #[error("serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+impl From<AccountRepoError> for CrlPublishError {
+    fn from(e: AccountRepoError) -> Self {
+        match e {
+            AccountRepoError::NotFound => Self::AccountNotFound,
+            e => Self::Account(e),
+        }
+    }
+}
+
+/// Rebuild and store an account's CRL. Called after every revocation.
+///
+/// # Errors
+///
+/// Returns `AccountNotFound` for an unknown account, or the failing lookup
+/// or write.
+pub async fn publish_account_crl(
+    repo: &dyn IdentityRepo,
+    account_id: Uuid,
+) -> Result<serde_json::Value, CrlPublishError> {
+    let account = repo.get_account_by_id(account_id).await?;
+    let devices = repo.list_device_keys_by_account(account_id).await?;
+    let crl = build_crl(&account, &devices, Utc::now());
+    let document = serde_json::to_value(&crl)?;
+    repo.store_account_crl(account_id, crl.crl_number, &document)
+        .await?;
+    Ok(document)
+}
+
+/// The account's current CRL document, rebuilt if none is stored or a
+/// device was revoked since it was generated.
+///
+/// # Errors
+///
+/// See [`publish_account_crl`].
+pub async fn current_account_crl(
+    repo: &dyn IdentityRepo,
+    account_id: Uuid,
+) -> Result<serde_json::Value, CrlPublishError> {
+    if let Some(stored) = repo.get_current_account_crl(account_id).await? {
+        return Ok(stored.document);
+    }
+    publish_account_crl(repo, account_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use tc_crypto::{Kid, RequestParts, SignatureAlg};
+
+    struct Fixture {
+        root: SigningKey,
+        account: AccountRecord,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let root = SigningKey::from_bytes(&[1; 32]);
+            let pubkey = root.verifying_key().to_bytes();
+            let account = AccountRecord {
+                id: Uuid::new_v4(),
+                username: "alice".to_string(),
+                root_pubkey: encode_base64url(&pubkey),
+                root_kid: Kid::derive(&pubkey),
+            };
+            Self { root, account }
+        }
+
+        fn device(&self, seed: u8) -> (SigningKey, DeviceKeyRecord) {
+            let key = SigningKey::from_bytes(&[seed; 32]);
+            let pubkey = key.verifying_key().to_bytes();
+            let record = DeviceKeyRecord {
+                id: Uuid::new_v4(),
+                account_id: self.account.id,
+                device_kid: Kid::derive(&pubkey),
+                device_pubkey: encode_base64url(&pubkey),
+                device_name: "Laptop".to_string(),
+                certificate: self.root.sign(&pubkey).to_bytes().to_vec(),
+                last_used_at: None,
+                revoked_at: None,
+                revoked_by_kid: None,
+                revocation_envelope: None,
+                certificate_version: Some(1),
+                certificate_timestamp: None,
+                rotated_from_kid: None,
+                rotation_signature: None,
+                client_caps: Vec::new(),
+                created_at: Utc::now(),
+            };
+            (key, record)
+        }
+    }
+
+    /// Mark `target` revoked by `by`, keeping `by`'s signed request for
+    /// `method` and `path`.
+    fn revoke(target: &mut DeviceKeyRecord, by: (&SigningKey, &Kid), method: &str, path: &str) {
+        let (key, kid) = by;
+        let kid_str = kid.to_string();
+        let canonical = RequestParts {
+            method,
+            host: "localhost",
+            path_and_query: path,
+            timestamp: 1_700_000_000,
+            nonce: "n",
+            content_type: "",
+            device_kid: &kid_str,
+            challenge: "",
+            body: b"",
+        }
+        .canonical_v2(SignatureAlg::Ed25519);
+        let request = SignedRequest {
+            alg: Some("ed25519".to_string()),
+            method: method.to_string(),
+            host: "localhost".to_string(),
+            path_and_query: path.to_string(),
+            timestamp: 1_700_000_000,
+            nonce: "n".to_string(),
+            content_type: String::new(),
+            device_kid: kid.clone(),
+            challenge: None,
+            body: String::new(),
+            signature: encode_base64url(&key.sign(canonical.as_bytes()).to_bytes()),
+        };
+        target.revoked_at = Some(Utc::now());
+        target.revoked_by_kid = Some(kid.clone());
+        target.revocation_envelope = Some(serde_json::to_value(request).expect("serialize"));
+    }
+
+    #[test]
+    fn signed_revocations_verify_offline() {
+        let fx = Fixture::new();
+        let (key, active) = fx.device(2);
+        let (_, mut lost) = fx.device(3);
+        let (_, mut legacy) = fx.device(4);
+        let path = format!("/api/v1/auth/devices/{}", lost.device_kid);
+        revoke(&mut lost, (&key, &active.device_kid), "DELETE", &path);
+        legacy.revoked_at = Some(Utc::now() - chrono::Duration::days(1));
+
+        let crl = build_crl(
+            &fx.account,
+            &[active.clone(), lost.clone(), legacy.clone()],
+            Utc::now(),
+        );
+        assert_eq!(crl.crl_number, 2);
+        assert_eq!(crl.revoked[0].device_kid, legacy.device_kid);
+        assert_eq!(crl.signers.len(), 1);
+        assert_eq!(crl.signers[0].device_kid, active.device_kid);
+
+        // Round-trips through the published JSON
+        let crl: DeviceCrl = serde_json::from_value(serde_json::to_value(&crl).expect("serialize"))
+            .expect("deserialize");
+        let verified = verify_crl(&crl).expect("verifies");
+        assert_eq!(verified.signed, [lost.device_kid.clone()]);
+        assert_eq!(verified.unsigned, [legacy.device_kid.clone()]);
+    }
+
+    #[test]
+    fn verify_crl_rejects_tampering() {
+        let fx = Fixture::new();
+        let (key, active) = fx.device(2);
+        let (_, mut lost) = fx.device(3);
+        let (_, other) = fx.device(5);
+        let path = format!("/auth/devices/{}", lost.device_kid);
+        revoke(&mut lost, (&key, &active.device_kid), "DELETE", &path);
+        let crl = build_crl(&fx.account, &[active, lost.clone()], Utc::now());
+
+        // Entry moved onto a device the signature does not name
+        let mut moved = crl.clone();
+        moved.revoked[0].device_kid = other.device_kid.clone();
+        assert_eq!(
+            verify_crl(&moved),
+            Err(CrlError::InvalidRevocation(other.device_kid.clone()))
+        );
+
+        // Signer not certified by this account's root
+        let mut forged = crl.clone();
+        forged.signers[0].certificate = encode_base64url(&[0u8; 64]);
+        assert!(matches!(
+            verify_crl(&forged),
+            Err(CrlError::InvalidSigner(_))
+        ));
+
+        let mut wrong_root = crl;
+        wrong_root.root_kid = Kid::derive(&[9u8; 32]);
+        assert_eq!(verify_crl(&wrong_root), Err(CrlError::RootKidMismatch));
+    }
+
+    #[test]
+    fn rotation_is_a_self_signed_revocation() {
+        let fx = Fixture::new();
+        let (key, mut rotated) = fx.device(2);
+        let (_, mut replacement) = fx.device(3);
+        let kid = rotated.device_kid.clone();
+        let path = format!("/auth/devices/{kid}/rotate");
+        revoke(&mut rotated, (&key, &kid), "POST", &path);
+        replacement.rotated_from_kid = Some(kid.clone());
+
+        let crl = build_crl(&fx.account, &[rotated, replacement.clone()], Utc::now());
+        assert_eq!(crl.signers.len(), 1);
+        assert_eq!(crl.signers[0].device_kid, kid);
+        let verified = verify_crl(&crl).expect("verifies");
+        assert_eq!(verified.signed, [kid.clone()]);
+
+        // Another device's rotation request does not revoke this one
+        let (other_key, mut other) = fx.device(4);
+        let path = format!("/auth/devices/{}/rotate", other.device_kid);
+        revoke(
+            &mut other,
+            (&other_key, &replacement.device_kid),
+            "POST",
+            &path,
+        );
+        let crl = build_crl(&fx.account, &[other.clone(), replacement], Utc::now());
+        assert_eq!(
+            verify_crl(&crl),
+            Err(CrlError::InvalidRevocation(other.device_kid.clone()))
+        );
+    }
+
+    #[test]
+    fn other_signed_requests_are_not_revocations() {
+        let fx = Fixture::new();
+        let (key, active) = fx.device(2);
+        let (_, mut lost) = fx.device(3);
+        let path = format!("/auth/devices/{}", lost.device_kid);
+        revoke(&mut lost, (&key, &active.device_kid), "PATCH", &path);
+        let crl = build_crl(&fx.account, &[active, lost.clone()], Utc::now());
+        assert_eq!(
+            verify_crl(&crl),
+            Err(CrlError::InvalidRevocation(lost.device_kid.clone()))
+        );
+    }
+}
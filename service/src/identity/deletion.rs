@@ -0,0 +1,144 @@
+//! Self-service account deletion with a grace period.
+//!
+//! `DELETE /auth/accounts/me`, signed by a device and countersigned by the
+//! root key, moves the account to [`AccountStatus::Deactivated`], revokes
+//! every device, and sets `purge_after` to `account_deletion.grace_days`
+//! from now. Until then `POST /auth/accounts/undelete`, signed by the root
+//! key alone, restores it; the devices stay revoked and the owner logs in
+//! again with the root key.
+//!
+//! Once the grace period ends the sweeper deletes the account row and
+//! everything that cascades from it; the username is quarantined like any
+//! released handle. An account still referenced by rows that do not cascade
+//! (rooms it owns, role assignments it made, research suggestions) is moved
+//! to [`AccountStatus::Deleted`] instead and kept, with the blocking
+//! constraint recorded as the reason.
+//!
+//! [`AccountStatus::Deactivated`]: super::status::AccountStatus::Deactivated
+//! [`AccountStatus::Deleted`]: super::status::AccountStatus::Deleted
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::repo::{AccountPurge, AccountRepoError, IdentityRepo};
+use crate::config::AccountDeletionConfig;
+
+/// Accounts purged per query.
+const PURGE_BATCH: i64 = 100;
+
+/// When an account deactivated at `now` is purged.
+#[must_use]
+pub fn purge_after(now: DateTime<Utc>, grace_days: u32) -> DateTime<Utc> {
+    now + Duration::days(i64::from(grace_days))
+}
+
+/// What one sweep did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeSummary {
+    pub purged: usize,
+    /// Moved to `deleted` because other rows still reference them
+    pub retained: usize,
+}
+
+/// Purge every deactivated account whose grace period ended by `now`.
+///
+/// # Errors
+///
+/// Returns the first repo failure; accounts handled before it stay purged.
+pub async fn purge_due_accounts(
+    repo: &dyn IdentityRepo,
+    now: DateTime<Utc>,
+) -> Result<PurgeSummary, AccountRepoError> {
+    let mut summary = PurgeSummary::default();
+    loop {
+        let due = repo.list_purge_due(now, PURGE_BATCH).await?;
+        for &account_id in &due {
+            match repo.purge_account(account_id, now).await? {
+                AccountPurge::Purged => {
+                    summary.purged += 1;
+                    metrics::counter!("tc_account_purges_total", "outcome" => "purged")
+                        .increment(1);
+                    tracing::info!(%account_id, "Purged deactivated account");
+                }
+                AccountPurge::Retained { constraint } => {
+                    summary.retained += 1;
+                    metrics::counter!("tc_account_purges_total", "outcome" => "retained")
+                        .increment(1);
+                    tracing::warn!(
+                        %account_id,
+                        constraint,
+                        "Deactivated account is still referenced; marked deleted instead"
+                    );
+                }
+                AccountPurge::NotDue => {}
+            }
+        }
+        if due.len() < usize::try_from(PURGE_BATCH).unwrap_or(usize::MAX) {
+            return Ok(summary);
+        }
+    }
+}
+
+/// Run [`purge_due_accounts`] every `sweep_interval_secs`.
+pub fn spawn_purge_sweeper(repo: Arc<dyn IdentityRepo>, config: &AccountDeletionConfig) {
+    let period = StdDuration::from_secs(config.sweep_interval_secs);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match purge_due_accounts(repo.as_ref(), Utc::now()).await {
+                Ok(PurgeSummary {
+                    purged: 0,
+                    retained: 0,
+                }) => {}
+                Ok(summary) => tracing::info!(
+                    purged = summary.purged,
+                    retained = summary.retained,
+                    "Account purge sweep finished"
+                ),
+                Err(e) => tracing::warn!("Account purge sweep failed: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use crate::identity::status::AccountStatus;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn sweep_purges_due_accounts_in_batches() {
+        let repo = MockIdentityRepo::new();
+        let due: Vec<Uuid> = (0..PURGE_BATCH + 5).map(|_| Uuid::new_v4()).collect();
+        repo.purge_due.lock().expect("lock").clone_from(&due);
+
+        let summary = purge_due_accounts(&repo, Utc::now()).await.expect("sweep");
+        assert_eq!(summary.purged, due.len());
+        assert_eq!(*repo.purged.lock().expect("lock"), due);
+    }
+
+    #[tokio::test]
+    async fn referenced_account_is_retained_as_deleted() {
+        let repo = MockIdentityRepo::new();
+        repo.set_account_status(AccountStatus::Deactivated);
+        repo.purge_due.lock().expect("lock").push(Uuid::new_v4());
+        *repo.purge_blocked_by.lock().expect("lock") = Some("rooms__rooms_owner_id_fkey".into());
+
+        let summary = purge_due_accounts(&repo, Utc::now()).await.expect("sweep");
+        assert_eq!(
+            summary,
+            PurgeSummary {
+                purged: 0,
+                retained: 1
+            }
+        );
+        assert_eq!(repo.account_status(), AccountStatus::Deleted);
+        assert!(repo.purged.lock().expect("lock").is_empty());
+    }
+}
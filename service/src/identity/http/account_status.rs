@@ -0,0 +1,250 @@
+//! Account status HTTP handlers
+//!
+//! Owners lock or delete their own account with a signed request; account
+//! admins suspend, restore and delete other accounts. The allowed transitions
+//! live in [`crate::identity::status`], and enforcement happens where
+//! requests are authenticated, not here.
+
+use std::sync::Arc;
+
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::Path;
+use crate::config::AccountsConfig;
+use crate::identity::repo::IdentityRepo;
+use crate::identity::status::{
+    change_account_status, AccountStatus, StatusActor, StatusChange, StatusChangeError,
+};
+
+/// Maximum length of a status change reason (bytes).
+const MAX_REASON_LENGTH: usize = 500;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeAccountStatusRequest {
+    pub status: AccountStatus,
+    /// Why the status is changing; kept in the audit trail
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountStatusResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub status: AccountStatus,
+    pub previous_status: AccountStatus,
+    pub changed_at: String,
+}
+
+impl From<StatusChange> for AccountStatusResponse {
+    fn from(change: StatusChange) -> Self {
+        Self {
+            account_id: change.account_id,
+            status: change.status,
+            previous_status: change.previous,
+            changed_at: change.changed_at.to_rfc3339(),
+        }
+    }
+}
+
+/// POST /auth/account/status — lock or delete the caller's own account
+///
+/// A locked account can only be restored by an account admin.
+#[utoipa::path(
+    post,
+    path = "/auth/account/status",
+    tag = "Identity",
+    request_body = ChangeAccountStatusRequest,
+    responses(
+        (status = 200, description = "Status changed", body = AccountStatusResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account is not active"),
+        (status = 409, description = "Transition not allowed from the current status"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn change_own_status(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: ChangeAccountStatusRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let reason = match validate_reason(body.reason.as_deref()) {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    change(
+        repo.as_ref(),
+        auth.account_id,
+        body.status,
+        StatusActor::Owner,
+        auth.account_id,
+        reason,
+    )
+    .await
+}
+
+/// PUT `/accounts/{account_id}/status` — change another account's status (admins only)
+#[utoipa::path(
+    put,
+    path = "/accounts/{account_id}/status",
+    tag = "Identity",
+    params(("account_id" = Uuid, Path, description = "Account to change")),
+    request_body = ChangeAccountStatusRequest,
+    responses(
+        (status = 200, description = "Status changed", body = AccountStatusResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin, or targets their own account"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "Transition not allowed from the current status"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn change_account_status_as_admin(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(account_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let body: ChangeAccountStatusRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let reason = match validate_reason(body.reason.as_deref()) {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+
+    if !accounts_config.is_admin(auth.account_id) {
+        return super::forbidden("Only account admins can change account status");
+    }
+    if account_id == auth.account_id {
+        return super::forbidden("Use POST /auth/account/status for your own account");
+    }
+
+    change(
+        repo.as_ref(),
+        account_id,
+        body.status,
+        StatusActor::Admin,
+        auth.account_id,
+        reason,
+    )
+    .await
+}
+
+#[allow(clippy::result_large_err)]
+pub(super) fn validate_reason(
+    reason: Option<&str>,
+) -> Result<Option<&str>, axum::response::Response> {
+    let reason = reason.map(str::trim).filter(|r| !r.is_empty());
+    if reason.is_some_and(|r| r.len() > MAX_REASON_LENGTH) {
+        return Err(super::bad_request(&format!(
+            "reason must be at most {MAX_REASON_LENGTH} bytes"
+        )));
+    }
+    Ok(reason)
+}
+
+async fn change(
+    repo: &dyn IdentityRepo,
+    account_id: Uuid,
+    to: AccountStatus,
+    actor: StatusActor,
+    actor_account_id: Uuid,
+    reason: Option<&str>,
+) -> axum::response::Response {
+    match change_account_status(repo, account_id, to, actor, actor_account_id, reason).await {
+        Ok(change) => (StatusCode::OK, Json(AccountStatusResponse::from(change))).into_response(),
+        Err(StatusChangeError::NotFound) => super::not_found("Account not found"),
+        Err(e @ (StatusChangeError::NotAllowed { .. } | StatusChangeError::Conflict)) => {
+            super::conflict(&e.to_string())
+        }
+        Err(StatusChangeError::Repo(e)) => {
+            tracing::error!(%account_id, "Account status change failed: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use axum::body::Bytes;
+    use tc_crypto::Kid;
+
+    const ADMIN: Uuid = Uuid::from_u128(1);
+
+    fn auth_with_body(account_id: Uuid, body: &serde_json::Value) -> AuthenticatedDevice {
+        AuthenticatedDevice::for_test(
+            account_id,
+            Kid::derive(&[0xAAu8; 32]),
+            Bytes::from(body.to_string()),
+        )
+    }
+
+    async fn admin_change(
+        caller: Uuid,
+        repo: Arc<MockIdentityRepo>,
+        status: &str,
+    ) -> axum::response::Response {
+        let config = AccountsConfig {
+            admin_account_ids: vec![ADMIN],
+        };
+        change_account_status_as_admin(
+            Extension(repo as Arc<dyn IdentityRepo>),
+            Some(Extension(config)),
+            Path(Uuid::new_v4()),
+            auth_with_body(caller, &serde_json::json!({ "status": status })),
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn admin_can_suspend_and_others_cannot() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let response = admin_change(Uuid::new_v4(), Arc::clone(&repo), "suspended").await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(repo.account_status(), AccountStatus::Active);
+
+        let response = admin_change(ADMIN, Arc::clone(&repo), "suspended").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(repo.account_status(), AccountStatus::Suspended);
+    }
+
+    #[tokio::test]
+    async fn owner_cannot_suspend_themselves() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let response = change_own_status(
+            Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+            auth_with_body(
+                Uuid::new_v4(),
+                &serde_json::json!({ "status": "suspended" }),
+            ),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = change_own_status(
+            Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+            auth_with_body(Uuid::new_v4(), &serde_json::json!({ "status": "locked" })),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(repo.account_status(), AccountStatus::Locked);
+    }
+}
@@ -0,0 +1,277 @@
+//! Account alias management HTTP handlers
+//!
+//! Aliases are secondary handles that lookup endpoints resolve to the
+//! account's canonical username. Users manage their own aliases here;
+//! reserved aliases (e.g. for verified organizations) are assigned by
+//! operators and cannot be removed through the API.
+
+use std::sync::Arc;
+
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::auth::AuthenticatedDevice;
+use super::{ErrorResponse, Path};
+use crate::identity::repo::{AccountAliasRecord, AliasRepoError, IdentityRepo};
+use crate::identity::username::UsernamePolicy;
+
+/// Alias returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AliasInfo {
+    pub alias: String,
+    /// Assigned by an operator; cannot be removed by the account holder
+    pub reserved: bool,
+    pub created_at: String,
+}
+
+impl From<AccountAliasRecord> for AliasInfo {
+    fn from(record: AccountAliasRecord) -> Self {
+        Self {
+            alias: record.alias,
+            reserved: record.reserved,
+            created_at: record.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AliasListResponse {
+    pub aliases: Vec<AliasInfo>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateAliasRequest {
+    /// Follows the username rules (3-64 letters, digits, `-`, `_`)
+    pub alias: String,
+}
+
+/// POST /auth/aliases — add an alias to the authenticated account
+#[utoipa::path(
+    post,
+    path = "/auth/aliases",
+    tag = "Identity",
+    request_body = CreateAliasRequest,
+    responses(
+        (status = 201, description = "Alias added", body = AliasInfo),
+        (status = 400, description = "Invalid alias"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Handle already taken by a username or alias, or recently given up by another account"),
+        (status = 422, description = "Maximum alias limit reached"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn create_alias(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let req: CreateAliasRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    let alias = req.alias.trim();
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    if let Err(e) = usernames.validate(alias) {
+        return super::bad_request(&e.to_string());
+    }
+    match usernames
+        .is_cooling_off(repo.as_ref(), alias, Some(auth.account_id))
+        .await
+    {
+        Ok(false) => {}
+        Ok(true) => return super::conflict("Handle already taken"),
+        Err(e) => {
+            tracing::error!("Failed to check released handles: {e}");
+            return super::internal_error();
+        }
+    }
+
+    match repo
+        .create_account_alias(auth.account_id, alias, false)
+        .await
+    {
+        Ok(record) => (StatusCode::CREATED, Json(AliasInfo::from(record))).into_response(),
+        Err(e) => alias_repo_error_response(&e),
+    }
+}
+
+/// GET /auth/aliases — list aliases of the authenticated account
+#[utoipa::path(
+    get,
+    path = "/auth/aliases",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Alias list", body = AliasListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_aliases(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.list_account_aliases(auth.account_id).await {
+        Ok(records) => {
+            let aliases = records.into_iter().map(AliasInfo::from).collect();
+            (StatusCode::OK, Json(AliasListResponse { aliases })).into_response()
+        }
+        Err(e) => alias_repo_error_response(&e),
+    }
+}
+
+/// DELETE /auth/aliases/:alias — remove an alias
+#[utoipa::path(
+    delete,
+    path = "/auth/aliases/{alias}",
+    tag = "Identity",
+    params(
+        ("alias" = String, Path, description = "Alias to remove")
+    ),
+    responses(
+        (status = 204, description = "Alias removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Alias is reserved"),
+        (status = 404, description = "Alias not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn delete_alias(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Path(alias): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.delete_account_alias(&alias, auth.account_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => alias_repo_error_response(&e),
+    }
+}
+
+fn alias_repo_error_response(e: &AliasRepoError) -> axum::response::Response {
+    match e {
+        AliasRepoError::DuplicateHandle => super::conflict("Handle already taken"),
+        AliasRepoError::NotFound => super::not_found("Alias not found"),
+        AliasRepoError::Reserved => super::forbidden("Reserved aliases cannot be removed"),
+        AliasRepoError::MaxAliasesReached => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                error: "Maximum alias limit reached".to_string(),
+            }),
+        )
+            .into_response(),
+        AliasRepoError::Database(db_err) => {
+            tracing::error!("Alias operation failed: {db_err}");
+            super::internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::{mock::MockIdentityRepo, ReleasedHandleRecord};
+    use axum::body::{to_bytes, Bytes};
+    use tc_crypto::Kid;
+    use uuid::Uuid;
+
+    fn auth_with_body(body: &serde_json::Value) -> AuthenticatedDevice {
+        AuthenticatedDevice::for_test(
+            Uuid::new_v4(),
+            Kid::derive(&[0xAAu8; 32]),
+            Bytes::from(body.to_string()),
+        )
+    }
+
+    async fn create(repo: MockIdentityRepo, body: &serde_json::Value) -> axum::response::Response {
+        create_alias(
+            Extension(Arc::new(repo) as Arc<dyn IdentityRepo>),
+            None,
+            auth_with_body(body),
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn create_trims_and_returns_alias() {
+        let response = create(
+            MockIdentityRepo::new(),
+            &serde_json::json!({ "alias": " civic-bob " }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["alias"], "civic-bob");
+        assert_eq!(payload["reserved"], false);
+    }
+
+    #[tokio::test]
+    async fn create_applies_username_rules() {
+        for alias in ["ab", "has space", "admin"] {
+            let response = create(
+                MockIdentityRepo::new(),
+                &serde_json::json!({ "alias": alias }),
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{alias}");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_refuses_recently_released_handles() {
+        let repo = MockIdentityRepo::new();
+        repo.released_handles
+            .lock()
+            .expect("lock")
+            .push(ReleasedHandleRecord {
+                handle: "civic-bob".to_string(),
+                account_id: Uuid::new_v4(),
+                released_at: chrono::Utc::now() - chrono::Duration::days(30),
+            });
+        let response = create(repo, &serde_json::json!({ "alias": "Civic-Bob" })).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let repo = MockIdentityRepo::new();
+        repo.released_handles
+            .lock()
+            .expect("lock")
+            .push(ReleasedHandleRecord {
+                handle: "civic-bob".to_string(),
+                account_id: Uuid::new_v4(),
+                released_at: chrono::Utc::now() - chrono::Duration::days(91),
+            });
+        let response = create(repo, &serde_json::json!({ "alias": "civic-bob" })).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn create_maps_conflicts_and_limits() {
+        let repo = MockIdentityRepo::new();
+        repo.set_create_alias_error(AliasRepoError::DuplicateHandle);
+        let response = create(repo, &serde_json::json!({ "alias": "taken" })).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let repo = MockIdentityRepo::new();
+        repo.set_create_alias_error(AliasRepoError::MaxAliasesReached);
+        let response = create(repo, &serde_json::json!({ "alias": "sixth" })).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn delete_reserved_alias_is_forbidden() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_delete_alias_result(Err(AliasRepoError::Reserved));
+        let response = delete_alias(
+            Extension(repo as Arc<dyn IdentityRepo>),
+            Path("civic-org".to_string()),
+            auth_with_body(&serde_json::Value::Null),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
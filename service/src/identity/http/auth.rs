@@ -1,46 +1,70 @@
 //! Request authentication via signed headers
 //!
 //! Device endpoints authenticate requests by verifying an Ed25519 signature
-//! over a canonical message built from request parts.
+//! over a canonical message built from request parts. The message formats
+//! are defined in [`tc_crypto::RequestParts`]:
 //!
-//! Canonical message format:
-//! ```text
-//! {METHOD}\n{PATH_AND_QUERY}\n{TIMESTAMP}\n{NONCE}\n{BODY_SHA256_HEX}
-//! ```
+//! - **v1** (legacy): `{METHOD}\n{PATH_AND_QUERY}\n{TIMESTAMP}\n{NONCE}\n{BODY_SHA256_HEX}`
+//! - **v2**: selected by `X-Signature-Alg`; also signs the algorithm, `Host`,
+//!   `Content-Type`, and `X-Device-Kid`
+//!
+//! v1 is accepted while `auth.accept_v1_signatures` is on, so clients can
+//! migrate before it is switched off.
 //!
 //! Required headers:
 //! - `X-Device-Kid`: 22-char base64url key identifier
 //! - `X-Signature`: base64url Ed25519 signature of the canonical message
 //! - `X-Timestamp`: Unix seconds
 //! - `X-Nonce`: unique per-request nonce (max 64 chars)
+//! - `X-Signature-Alg` (v2 only): `ed25519`
+//!
+//! A v2 request may also carry `X-Challenge` from `GET /auth/challenge` (see
+//! [`super::challenge`]). The challenge is signed, single-use, and replaces
+//! the timestamp window for that request.
+//!
+//! Any request may declare `TC-Client-Caps` (see
+//! [`crate::identity::capabilities`]); once a device has declared `sig-v2`,
+//! its v1 signatures are refused with `SIGNATURE_UPGRADE_REQUIRED`.
+//!
+//! `GET` and `HEAD` requests may instead carry a session token from
+//! `POST /auth/sessions` (see [`super::sessions`]).
 
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Instant;
 
 #[cfg(test)]
 use axum::http::StatusCode;
 use axum::{
     body::Bytes,
-    extract::{FromRequest, Request},
+    extract::{FromRequest, MatchedPath, OptionalFromRequest, OriginalUri, Request},
+    http::header::{CONTENT_TYPE, HOST},
     response::Response,
 };
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::body_hash::{read_hashed, BodyReadError, SignedBodyLimit};
+use super::challenge::{hash_challenge, validate_challenge};
+use super::device_usage::DeviceUsageRecorder;
+use super::sessions::{authenticate_session, session_token};
+use super::signature_guard::{blocked_response, FailureKey, SignatureFailureGuard};
+use crate::config::AuthConfig;
+use crate::events::EventPublisher;
+use crate::identity::capabilities::{ClientCaps, EnvelopeFormat};
 use crate::identity::repo::{DeviceKeyRepoError, IdentityRepo, NonceRepoError};
 use crate::identity::service::DevicePubkey;
-use tc_crypto::{decode_base64url, verify_ed25519, Kid};
-
-/// Maximum clock skew allowed for timestamps (seconds).
-///
-/// Also used as the TTL for nonce cleanup — nonces older than this are
-/// safe to delete because the timestamp check would reject them anyway.
-pub const MAX_TIMESTAMP_SKEW: i64 = 300;
+use crate::identity::signed_request::{canonical_message, SignedRequest};
+use tc_crypto::{
+    decode_base64url, encode_base64url, verify_ed25519, Kid, RequestParts, SignatureAlg,
+};
 
 /// Maximum request body size for authenticated device endpoints (64 KiB).
 ///
 /// Device management payloads (JSON with keys, names, certificates) are small;
 /// 64 KiB is generous. A tighter limit prevents abuse of the body-read step
-/// before signature verification.
+/// before signature verification. Routes that take larger signed uploads
+/// raise it with [`SignedBodyLimit`].
 const MAX_BODY_SIZE: usize = 64 * 1024;
 
 /// Maximum length of the X-Nonce header value (bytes).
@@ -58,6 +82,22 @@ pub struct AuthenticatedDevice {
     pub account_id: Uuid,
     pub device_kid: Kid,
     body_bytes: Bytes,
+    signed: SignedParts,
+    client_caps: ClientCaps,
+}
+
+/// Signed request fields other than the body and device KID.
+#[derive(Debug, Clone, Default)]
+struct SignedParts {
+    alg: Option<SignatureAlg>,
+    method: String,
+    host: String,
+    path_and_query: String,
+    timestamp: i64,
+    nonce: String,
+    content_type: String,
+    challenge: Option<String>,
+    signature: String,
 }
 
 impl AuthenticatedDevice {
@@ -72,6 +112,44 @@ impl AuthenticatedDevice {
             .map_err(|e| super::bad_request(&format!("Invalid JSON body: {e}")))
     }
 
+    /// The verified request as a [`SignedRequest`], for handlers that keep a
+    /// tamper-evident record of what the device asked for.
+    #[must_use]
+    pub fn signed_request(&self) -> SignedRequest {
+        SignedRequest {
+            alg: self.signed.alg.map(|alg| alg.as_str().to_string()),
+            method: self.signed.method.clone(),
+            host: self.signed.host.clone(),
+            path_and_query: self.signed.path_and_query.clone(),
+            timestamp: self.signed.timestamp,
+            nonce: self.signed.nonce.clone(),
+            content_type: self.signed.content_type.clone(),
+            device_kid: self.device_kid.clone(),
+            challenge: self.signed.challenge.clone(),
+            body: encode_base64url(&self.body_bytes),
+            signature: self.signed.signature.clone(),
+        }
+    }
+
+    /// Envelope versions this device has declared, including any raised by
+    /// this request; see [`crate::identity::capabilities`]. Empty for
+    /// session-authenticated reads.
+    #[must_use]
+    pub const fn client_caps(&self) -> &ClientCaps {
+        &self.client_caps
+    }
+
+    /// A read request authenticated by session token: no body, nothing signed.
+    fn from_session(account_id: Uuid, device_kid: Kid) -> Self {
+        Self {
+            account_id,
+            device_kid,
+            body_bytes: Bytes::new(),
+            signed: SignedParts::default(),
+            client_caps: ClientCaps::default(),
+        }
+    }
+
     /// Construct an `AuthenticatedDevice` for use in unit tests.
     ///
     /// Skips all authentication checks. For testing handler logic after
@@ -82,8 +160,18 @@ impl AuthenticatedDevice {
             account_id,
             device_kid,
             body_bytes: body,
+            signed: SignedParts::default(),
+            client_caps: ClientCaps::default(),
         }
     }
+
+    /// Set the declared capabilities of a test device.
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_client_caps(mut self, client_caps: ClientCaps) -> Self {
+        self.client_caps = client_caps;
+        self
+    }
 }
 
 /// Validate a nonce value from the X-Nonce header.
@@ -106,10 +194,45 @@ fn validate_nonce(nonce: &str) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Signature scheme requested by `X-Signature-Alg`: `Some` for v2, `None`
+/// for a v1 request (only while v1 is accepted).
+fn signature_alg(req: &Request, config: &AuthConfig) -> Result<Option<SignatureAlg>, &'static str> {
+    let Some(value) = req.headers().get("X-Signature-Alg") else {
+        return if config.accept_v1_signatures {
+            Ok(None)
+        } else {
+            Err("Missing X-Signature-Alg header")
+        };
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Some)
+        .ok_or("Unsupported signature algorithm")
+}
+
+/// `Host` header, falling back to the URI authority (HTTP/2).
+fn request_host(req: &Request) -> Option<String> {
+    req.headers()
+        .get(HOST)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| req.uri().authority().map(|a| a.as_str().to_string()))
+}
+
 fn auth_error(msg: &str) -> Response {
     super::unauthorized(msg)
 }
 
+/// Keys a signature failure counts against in [`SignatureFailureGuard`].
+fn failure_keys(kid: Option<&Kid>, ip: Option<IpAddr>) -> Vec<FailureKey> {
+    kid.map(|kid| FailureKey::Device(kid.clone()))
+        .into_iter()
+        .chain(ip.map(FailureKey::Ip))
+        .collect()
+}
+
 impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
     type Rejection = Response;
 
@@ -121,6 +244,29 @@ impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
             .get::<Arc<dyn IdentityRepo>>()
             .ok_or_else(|| auth_error("Server misconfiguration"))?
             .clone();
+        if !req.headers().contains_key("X-Device-Kid") {
+            if let Some(token) = session_token(req.headers()) {
+                let (account_id, device_kid) =
+                    authenticate_session(repo.as_ref(), req.method(), token).await?;
+                return Ok(Self::from_session(account_id, device_kid));
+            }
+        }
+        let usage = req.extensions().get::<Arc<DeviceUsageRecorder>>().cloned();
+        let guard = req
+            .extensions()
+            .get::<Arc<SignatureFailureGuard>>()
+            .cloned();
+        let publisher = req.extensions().get::<Arc<dyn EventPublisher>>().cloned();
+        let client_ip = guard.as_ref().and_then(|guard| guard.client_ip(&req));
+        let note_failure = |kid: Option<&Kid>| {
+            if let Some(guard) = &guard {
+                guard.note_failures(
+                    failure_keys(kid, client_ip),
+                    publisher.as_deref(),
+                    Instant::now(),
+                );
+            }
+        };
 
         // Extract headers
         let kid_str = req
@@ -159,18 +305,50 @@ impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
             return Err(auth_error(msg));
         }
 
+        let challenge = match req.headers().get("X-Challenge") {
+            None => None,
+            Some(value) => {
+                let value = value
+                    .to_str()
+                    .map_err(|_| auth_error("X-Challenge contains invalid characters"))?;
+                validate_challenge(value).map_err(auth_error)?;
+                Some(value.to_string())
+            }
+        };
+
         // Parse KID
         let kid: Kid = kid_str
             .parse()
             .map_err(|_| auth_error("Invalid KID format"))?;
 
+        // Refuse blocked callers before any lookup or signature check
+        if let Some(wait) = guard
+            .as_ref()
+            .and_then(|guard| guard.retry_after(&failure_keys(None, client_ip), Instant::now()))
+        {
+            return Err(blocked_response(wait));
+        }
+
         // Parse and validate timestamp
         let timestamp: i64 = timestamp_str
             .parse()
             .map_err(|_| auth_error("Invalid timestamp"))?;
 
+        // The window and accepted versions come from `Config::auth`; routers
+        // built without the extension (unit tests) fall back to defaults.
+        let auth_config = req
+            .extensions()
+            .get::<AuthConfig>()
+            .copied()
+            .unwrap_or_default();
+        let alg = signature_alg(&req, &auth_config).map_err(auth_error)?;
+        let declared_caps = super::declared_caps(req.headers())?;
+        if challenge.is_some() && alg.is_none() {
+            return Err(auth_error("X-Challenge requires a v2 signature"));
+        }
+        // A challenged request is bounded by the challenge's lifetime instead
         let now = chrono::Utc::now().timestamp();
-        if super::timestamp_is_stale(now, timestamp) {
+        if challenge.is_none() && auth_config.timestamp_is_stale(now, timestamp) {
             return Err(auth_error("Timestamp out of range"));
         }
 
@@ -185,30 +363,73 @@ impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
         // Capture method and path+query before consuming the request.
         // Include query string in the signed payload so future endpoints
         // with query parameters are protected against parameter injection.
+        // Nested routers (e.g. `/api/v1`) see a stripped URI; the client
+        // signed the one it sent.
         let method = req.method().to_string();
-        let path = req.uri().path_and_query().map_or_else(
-            || req.uri().path().to_string(),
-            |pq| pq.as_str().to_string(),
-        );
+        let uri = req
+            .extensions()
+            .get::<OriginalUri>()
+            .map_or_else(|| req.uri().clone(), |o| o.0.clone());
+        let path = uri
+            .path_and_query()
+            .map_or_else(|| uri.path().to_string(), |pq| pq.as_str().to_string());
+        // Route template for usage stats, so labels don't carry IDs
+        let route = req
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or_else(|| req.uri().path().to_string(), |m| m.as_str().to_string());
+        let host = request_host(&req);
+        if alg.is_some() && host.is_none() {
+            return Err(auth_error("Missing Host header"));
+        }
+        let content_type = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
 
-        // Read the body
-        let body_bytes = axum::body::to_bytes(req.into_body(), MAX_BODY_SIZE)
+        // Read and hash the body in one pass
+        let limit = req
+            .extensions()
+            .get::<SignedBodyLimit>()
+            .map_or(MAX_BODY_SIZE, |l| l.0);
+        let body = read_hashed(req.into_body(), limit)
             .await
-            .map_err(|_| auth_error("Failed to read request body"))?;
-
-        // Compute body hash
-        let body_hash = Sha256::digest(&body_bytes);
-        let body_hash_hex = format!("{body_hash:x}");
-
-        // Build canonical message
-        let canonical = format!("{method}\n{path}\n{timestamp}\n{nonce}\n{body_hash_hex}");
+            .map_err(|e| match e {
+                BodyReadError::TooLarge { .. } => {
+                    crate::http::payload_too_large("Request body too large")
+                }
+                BodyReadError::Read(_) => auth_error("Failed to read request body"),
+            })?;
+        let body_bytes = body.bytes;
+
+        let canonical = canonical_message(
+            alg,
+            &RequestParts {
+                method: &method,
+                host: host.as_deref().unwrap_or_default(),
+                path_and_query: &path,
+                timestamp,
+                nonce: &nonce,
+                content_type: &content_type,
+                device_kid: &kid_str,
+                challenge: challenge.as_deref().unwrap_or_default(),
+                body: &body_bytes,
+            },
+            &body.hash,
+        );
 
         // Look up device
         let device = repo
             .get_device_key_by_kid(&kid)
             .await
             .map_err(|e| match e {
-                DeviceKeyRepoError::NotFound => auth_error("Device not found"),
+                DeviceKeyRepoError::NotFound => {
+                    // Unknown KIDs count against the IP only
+                    note_failure(None);
+                    auth_error("Device not found")
+                }
                 DeviceKeyRepoError::Database(db_err) => {
                     tracing::error!("Auth device lookup failed: {db_err}");
                     auth_error("Authentication failed")
@@ -229,8 +450,19 @@ impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
         // If we checked revocation first, an unauthenticated caller who knows
         // a valid KID could distinguish revoked (403) from active (401) devices
         // without possessing the private key.
-        verify_ed25519(device_pubkey.as_bytes(), canonical.as_bytes(), &sig_arr)
-            .map_err(|_| auth_error("Invalid signature"))?;
+        match alg.unwrap_or(SignatureAlg::Ed25519) {
+            SignatureAlg::Ed25519 => {
+                verify_ed25519(device_pubkey.as_bytes(), canonical.as_bytes(), &sig_arr).map_err(
+                    |_| {
+                        note_failure(Some(&kid));
+                        auth_error("Invalid signature")
+                    },
+                )?;
+            }
+        }
+        if let Some(guard) = &guard {
+            guard.record_success(&kid);
+        }
 
         // Record nonce AFTER signature verification to prevent unauthenticated
         // callers from exhausting nonces for valid requests.
@@ -243,14 +475,63 @@ impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
                     tracing::error!("Nonce check failed: {db_err}");
                     auth_error("Authentication failed")
                 }
+                NonceRepoError::UnknownChallenge => {
+                    tracing::error!("Unexpected repo error during nonce check: {e}");
+                    auth_error("Authentication failed")
+                }
             })?;
 
+        // Spent only once the signature over it has verified, so a caller
+        // without the key cannot burn a device's challenges
+        if let Some(challenge) = &challenge {
+            repo.consume_auth_challenge(&kid, &hash_challenge(challenge))
+                .await
+                .map_err(|e| match e {
+                    NonceRepoError::UnknownChallenge => {
+                        auth_error("Challenge is unknown, expired, or already used")
+                    }
+                    NonceRepoError::Database(db_err) => {
+                        tracing::error!("Challenge check failed: {db_err}");
+                        auth_error("Authentication failed")
+                    }
+                    NonceRepoError::Replay => {
+                        tracing::error!("Unexpected repo error during challenge check: {e}");
+                        auth_error("Authentication failed")
+                    }
+                })?;
+        }
+
         // Check if revoked (after signature verification to avoid status oracle).
         // Must happen after nonce recording so a revoked device's valid request
         // doesn't allow the same nonce to be reused by another caller.
         if device.revoked_at.is_some() {
             return Err(super::forbidden("Device has been revoked"));
         }
+        super::require_active_account(repo.as_ref(), device.account_id).await?;
+
+        // A device that has declared v2 signatures may not fall back to v1.
+        // Declarations only add to what was recorded before.
+        let recorded_caps = ClientCaps::from_stored(&device.client_caps);
+        let client_caps = recorded_caps.union(&declared_caps);
+        client_caps
+            .require(EnvelopeFormat::Signature, if alg.is_some() { 2 } else { 1 })
+            .map_err(super::upgrade_required_response)?;
+        if client_caps != recorded_caps {
+            if let Err(e) = repo.record_device_caps(&kid, &client_caps.tokens()).await {
+                tracing::warn!("Failed to record capabilities for device {kid}: {e}");
+            }
+        }
+
+        // Tracks v1 usage so operators know when v1 can be switched off
+        metrics::counter!(
+            "tc_auth_signed_requests_total",
+            "version" => if alg.is_some() { "v2" } else { "v1" }
+        )
+        .increment(1);
+
+        if let Some(usage) = usage {
+            usage.record(&kid, &format!("{method} {route}"), chrono::Utc::now());
+        }
 
         // Touch last_used_at (fire-and-forget, don't fail the request)
         let touch_kid = kid.clone();
@@ -265,19 +546,50 @@ impl<S: Send + Sync> FromRequest<S> for AuthenticatedDevice {
             account_id: device.account_id,
             device_kid: kid,
             body_bytes,
+            signed: SignedParts {
+                alg,
+                method,
+                host: host.unwrap_or_default(),
+                path_and_query: path,
+                timestamp,
+                nonce,
+                content_type,
+                challenge,
+                signature: signature_str,
+            },
+            client_caps,
         })
     }
 }
 
+/// Optional device auth for public routes that show more to signed callers.
+///
+/// Requests without an `X-Device-Kid` header or session token extract as
+/// `None`; requests that carry either must verify, so a bad signature is
+/// still rejected rather than silently downgraded to anonymous.
+impl<S: Send + Sync> OptionalFromRequest<S> for AuthenticatedDevice {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Option<Self>, Self::Rejection> {
+        if !req.headers().contains_key("X-Device-Kid") && session_token(req.headers()).is_none() {
+            return Ok(None);
+        }
+        <Self as FromRequest<S>>::from_request(req, state)
+            .await
+            .map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::identity::capabilities::CLIENT_CAPS_HEADER;
     use crate::identity::repo::{mock::MockIdentityRepo, DeviceKeyRecord};
     use axum::{body::Body, http::Request, routing::get, Router};
     use chrono::Utc;
     use ed25519_dalek::{Signer, SigningKey};
     use rand::rngs::OsRng;
-    use tc_crypto::{encode_base64url, Kid};
+    use tc_crypto::{encode_base64url, BodyHash, Kid};
     use tower::ServiceExt;
     use uuid::Uuid;
 
@@ -304,6 +616,13 @@ mod tests {
             certificate: vec![],
             last_used_at: None,
             revoked_at: if revoked { Some(Utc::now()) } else { None },
+            revoked_by_kid: None,
+            revocation_envelope: None,
+            certificate_version: Some(1),
+            certificate_timestamp: None,
+            rotated_from_kid: None,
+            rotation_signature: None,
+            client_caps: Vec::new(),
             created_at: Utc::now(),
         }
     }
@@ -361,7 +680,8 @@ mod tests {
     async fn test_from_request_expired_timestamp_returns_unauthorized() {
         let repo = MockIdentityRepo::new();
         let app = make_auth_router(repo);
-        let expired = Utc::now().timestamp() - (MAX_TIMESTAMP_SKEW + 1);
+        let skew = i64::try_from(AuthConfig::default().max_clock_skew_secs).expect("skew fits");
+        let expired = Utc::now().timestamp() - (skew + 1);
         let response = app
             .oneshot(build_auth_request(
                 "cs1uhCLEB_ttCYaQ8RMLfQ",
@@ -374,6 +694,32 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[tokio::test]
+    async fn test_from_request_uses_configured_clock_skew() {
+        let app =
+            make_auth_router(MockIdentityRepo::new()).layer(axum::extract::Extension(AuthConfig {
+                max_clock_skew_secs: 10,
+                nonce_ttl_secs: 20,
+                ..AuthConfig::default()
+            }));
+        // Well inside the default window, outside the configured one
+        let response = app
+            .oneshot(build_auth_request(
+                "cs1uhCLEB_ttCYaQ8RMLfQ",
+                &encode_base64url(&[0u8; 64]),
+                Utc::now().timestamp() - 60,
+                "nonce",
+            ))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["error"], "Timestamp out of range");
+    }
+
     #[tokio::test]
     async fn test_from_request_invalid_signature_returns_unauthorized() {
         let signing_key = SigningKey::generate(&mut OsRng);
@@ -431,6 +777,153 @@ mod tests {
         assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
 
+    #[tokio::test]
+    async fn test_from_request_suspended_account_returns_forbidden() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let record = make_device_record(&signing_key.verifying_key().to_bytes(), false);
+        let kid = record.device_kid.clone();
+
+        let repo = MockIdentityRepo::new();
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        repo.set_account_status(crate::identity::status::AccountStatus::Suspended);
+        let app = make_auth_router(repo);
+
+        let timestamp = Utc::now().timestamp();
+        let nonce = "nonce-suspended-test";
+        let signature = sign_canonical(&signing_key, "GET", "/test", timestamp, nonce, b"");
+        let response = app
+            .oneshot(build_auth_request(
+                kid.as_str(),
+                &signature,
+                timestamp,
+                nonce,
+            ))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), 4096)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["code"], "ACCOUNT_SUSPENDED");
+    }
+
+    // ── Session tokens ───────────────────────────────────────────────────────
+
+    /// Issue a session on `repo`, let `adjust` age or revoke it, and send it
+    /// to `/test` with `method`.
+    async fn session_status(
+        method: &str,
+        adjust: impl FnOnce(&mut crate::identity::repo::DeviceSessionRecord),
+    ) -> StatusCode {
+        async fn ok_handler(_auth: AuthenticatedDevice) -> StatusCode {
+            StatusCode::OK
+        }
+        let repo = MockIdentityRepo::new();
+        let token = super::super::sessions::generate_session_token();
+        let kid = Kid::derive(&[9u8; 32]);
+        repo.create_device_session(
+            Uuid::new_v4(),
+            &kid,
+            &super::super::sessions::hash_session_token(&token),
+            Utc::now() + chrono::Duration::minutes(15),
+        )
+        .await
+        .expect("session");
+        adjust(&mut repo.device_sessions.lock().expect("lock")[0].1);
+        let app = Router::new()
+            .route("/test", get(ok_handler).post(ok_handler))
+            .layer(axum::extract::Extension(
+                Arc::new(repo) as Arc<dyn IdentityRepo>
+            ));
+        app.oneshot(
+            Request::builder()
+                .method(method)
+                .uri("/test")
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response")
+        .status()
+    }
+
+    #[tokio::test]
+    async fn test_session_token_authorizes_reads_only() {
+        assert_eq!(session_status("GET", |_| {}).await, StatusCode::OK);
+        assert_eq!(session_status("HEAD", |_| {}).await, StatusCode::OK);
+        assert_eq!(
+            session_status("POST", |_| {}).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_or_revoked_session_is_rejected() {
+        let past = Utc::now() - chrono::Duration::seconds(1);
+        assert_eq!(
+            session_status("GET", |s| s.expires_at = past).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            session_status("GET", |s| s.revoked_at = Some(past)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            session_status("GET", |s| s.device_revoked_at = Some(past)).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_session_token_is_rejected() {
+        let app = make_auth_router(MockIdentityRepo::new());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/test")
+                    .header("Authorization", "Bearer tcsess_unknown")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_from_request_body_limit_is_per_route() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let record = make_device_record(&signing_key.verifying_key().to_bytes(), false);
+        let kid = record.device_kid.clone();
+        let body = vec![b'x'; MAX_BODY_SIZE + 1];
+        let timestamp = Utc::now().timestamp();
+        let request = |nonce: &str| {
+            let signature = sign_canonical(&signing_key, "GET", "/test", timestamp, nonce, &body);
+            let mut req = build_auth_request(kid.as_str(), &signature, timestamp, nonce);
+            *req.body_mut() = Body::from(body.clone());
+            req
+        };
+
+        let repo = MockIdentityRepo::new();
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        let app = make_auth_router(repo);
+        let response = app
+            .clone()
+            .oneshot(request("nonce-large-1"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let raised = app.layer(axum::extract::Extension(SignedBodyLimit(MAX_BODY_SIZE * 2)));
+        let response = raised
+            .oneshot(request("nonce-large-2"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_from_request_valid_request_returns_ok() {
         let signing_key = SigningKey::generate(&mut OsRng);
@@ -522,46 +1015,501 @@ mod tests {
     }
 
     #[test]
-    fn test_timestamp_validation() {
-        use super::super::timestamp_is_stale;
-        let now = chrono::Utc::now().timestamp();
+    fn test_canonical_message_format() {
+        let parts = RequestParts {
+            method: "GET",
+            host: "api.example.com",
+            path_and_query: "/auth/devices",
+            timestamp: 1_700_000_000,
+            nonce: "test-nonce-abc",
+            content_type: "",
+            device_kid: "cs1uhCLEB_ttCYaQ8RMLfQ",
+            challenge: "",
+            body: b"",
+        };
+
+        let v1 = canonical_message(None, &parts, &BodyHash::of(b""));
+        assert!(v1.starts_with("GET\n/auth/devices\n1700000000\ntest-nonce-abc\n"));
+        // SHA-256 of empty body is well-known
+        assert!(v1.ends_with("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
 
-        // Within range
-        assert!(!timestamp_is_stale(now, now));
+        let v2 = canonical_message(Some(SignatureAlg::Ed25519), &parts, &BodyHash::of(b""));
+        assert!(v2.starts_with("tc-request-v2\ned25519\nGET\napi.example.com\n"));
+    }
+
+    #[test]
+    fn test_canonical_message_matches_vectors() {
+        let corpus = tc_crypto::test_vectors::corpus();
+        let device = tc_crypto::test_vectors::keypair("device")
+            .verifying_key()
+            .to_bytes();
+        let text =
+            |v: &serde_json::Value, key: &str| v[key].as_str().unwrap_or_default().to_string();
+        for (section, alg) in [
+            ("requests", None),
+            ("requests_v2", Some(SignatureAlg::Ed25519)),
+        ] {
+            for vector in corpus[section].as_array().expect("request vectors") {
+                let body = text(vector, "body");
+                let canonical = canonical_message(
+                    alg,
+                    &RequestParts {
+                        method: &text(vector, "method"),
+                        host: &text(vector, "host"),
+                        path_and_query: &text(vector, "path"),
+                        timestamp: vector["timestamp"].as_i64().expect("timestamp"),
+                        nonce: &text(vector, "nonce"),
+                        content_type: &text(vector, "content_type"),
+                        device_kid: &text(vector, "device_kid"),
+                        challenge: &text(vector, "challenge"),
+                        body: body.as_bytes(),
+                    },
+                    &BodyHash::of(body.as_bytes()),
+                );
+                assert_eq!(canonical, vector["canonical"], "{section}");
+
+                let signature = decode_base64url(&text(vector, "signature")).expect("base64url");
+                let signature: [u8; 64] = signature.try_into().expect("64 bytes");
+                assert!(verify_ed25519(&device, canonical.as_bytes(), &signature).is_ok());
+            }
+        }
+    }
 
-        // Outside range
-        let old = now - MAX_TIMESTAMP_SKEW - 1;
-        assert!(timestamp_is_stale(now, old));
+    // ── Signing v2 ─────────────────────────────────────────────────────────
 
-        let future = now + MAX_TIMESTAMP_SKEW + 1;
-        assert!(timestamp_is_stale(now, future));
+    const TEST_HOST: &str = "api.example.com";
+
+    fn sign_v2(signing_key: &SigningKey, kid: &Kid, host: &str, nonce: &str) -> Request<Body> {
+        let timestamp = Utc::now().timestamp();
+        let canonical = RequestParts {
+            method: "GET",
+            host,
+            path_and_query: "/test",
+            timestamp,
+            nonce,
+            content_type: "",
+            device_kid: kid.as_str(),
+            challenge: "",
+            body: b"",
+        }
+        .canonical_v2(SignatureAlg::Ed25519);
+        let signature = encode_base64url(&signing_key.sign(canonical.as_bytes()).to_bytes());
+        let mut request = build_auth_request(kid.as_str(), &signature, timestamp, nonce);
+        let headers = request.headers_mut();
+        headers.insert("X-Signature-Alg", "ed25519".parse().expect("header"));
+        headers.insert(HOST, TEST_HOST.parse().expect("header"));
+        request
     }
 
-    #[test]
-    fn test_timestamp_overflow_rejects() {
-        use super::super::timestamp_is_stale;
-        let now = chrono::Utc::now().timestamp();
+    fn v2_router(config: AuthConfig) -> (Router, SigningKey, Kid) {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let record = make_device_record(&signing_key.verifying_key().to_bytes(), false);
+        let kid = record.device_kid.clone();
+        let repo = MockIdentityRepo::new();
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        let app = make_auth_router(repo).layer(axum::extract::Extension(config));
+        (app, signing_key, kid)
+    }
 
-        // Crafted timestamps that would overflow plain (now - timestamp).abs()
-        // must still produce a large skew that gets rejected.
-        assert!(timestamp_is_stale(now, i64::MIN));
-        assert!(timestamp_is_stale(now, i64::MAX));
+    async fn error_of(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        payload["error"].as_str().unwrap_or_default().to_string()
     }
 
-    #[test]
-    fn test_canonical_message_format() {
-        let method = "GET";
-        let path = "/auth/devices";
-        let timestamp = 1700000000_i64;
-        let nonce = "test-nonce-abc";
-        let body_hash_hex = format!("{:x}", Sha256::digest(b""));
+    #[tokio::test]
+    async fn test_v2_valid_request_returns_ok() {
+        let (app, key, kid) = v2_router(AuthConfig::default());
+        let response = app
+            .oneshot(sign_v2(&key, &kid, TEST_HOST, "nonce-v2"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 
-        let canonical = format!("{method}\n{path}\n{timestamp}\n{nonce}\n{body_hash_hex}");
+    #[tokio::test]
+    async fn test_v2_signed_for_other_host_returns_unauthorized() {
+        let (app, key, kid) = v2_router(AuthConfig::default());
+        let response = app
+            .oneshot(sign_v2(&key, &kid, "other.example.com", "nonce-v2-host"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_of(response).await, "Invalid signature");
+    }
 
-        assert!(canonical.starts_with("GET\n/auth/devices\n1700000000\ntest-nonce-abc\n"));
-        // SHA-256 of empty body is well-known
-        assert!(
-            canonical.ends_with("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    #[tokio::test]
+    async fn test_repeated_failures_block_the_client_ip() {
+        let config = AuthConfig {
+            failure_threshold: 2,
+            ..AuthConfig::default()
+        };
+        let guard = SignatureFailureGuard::from_config(&config)
+            .expect("enabled")
+            .with_trust_forwarded_for(true);
+        let (app, key, kid) = v2_router(config);
+        let app = app.layer(axum::extract::Extension(Arc::new(guard)));
+        let from = |ip: &str, nonce: &str| {
+            let mut request = sign_v2(&key, &kid, TEST_HOST, nonce);
+            request
+                .headers_mut()
+                .insert("X-Forwarded-For", ip.parse().expect("header"));
+            request
+        };
+
+        // Bad signature, then an unknown device (the mock's record is used up)
+        let mut bad = from("203.0.113.7", "nonce-bf-1");
+        bad.headers_mut().insert(
+            "X-Signature",
+            encode_base64url(&[0u8; 64]).parse().expect("header"),
+        );
+        let response = app.clone().oneshot(bad).await.expect("response");
+        assert_eq!(error_of(response).await, "Invalid signature");
+        let response = app
+            .clone()
+            .oneshot(from("203.0.113.7", "nonce-bf-2"))
+            .await
+            .expect("response");
+        assert_eq!(error_of(response).await, "Device not found");
+
+        let response = app
+            .clone()
+            .oneshot(from("203.0.113.7", "nonce-bf-3"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers().get("retry-after").map(|v| v.as_bytes()),
+            Some(&b"30"[..])
+        );
+
+        // The block is per IP, so the same KID still works from elsewhere
+        let response = app
+            .oneshot(from("198.51.100.1", "nonce-bf-4"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_spoofed_forwarded_for_does_not_move_blocks() {
+        let config = AuthConfig {
+            failure_threshold: 2,
+            ..AuthConfig::default()
+        };
+        let guard = SignatureFailureGuard::from_config(&config).expect("enabled");
+        let (app, key, kid) = v2_router(config);
+        let app = app.layer(axum::extract::Extension(Arc::new(guard)));
+        let from = |peer: [u8; 4], forwarded: &str, nonce: &str| {
+            let mut request = sign_v2(&key, &kid, TEST_HOST, nonce);
+            request
+                .headers_mut()
+                .insert("X-Forwarded-For", forwarded.parse().expect("header"));
+            request.extensions_mut().insert(axum::extract::ConnectInfo(
+                std::net::SocketAddr::from((peer, 443)),
+            ));
+            request
+        };
+        let attacker = [203, 0, 113, 7];
+        let victim = [198, 51, 100, 1];
+
+        // Failures from the attacker's socket, each claiming to be the victim
+        let mut bad = from(attacker, "198.51.100.1", "nonce-xff-1");
+        bad.headers_mut().insert(
+            "X-Signature",
+            encode_base64url(&[0u8; 64]).parse().expect("header"),
+        );
+        let response = app.clone().oneshot(bad).await.expect("response");
+        assert_eq!(error_of(response).await, "Invalid signature");
+        let response = app
+            .clone()
+            .oneshot(from(attacker, "198.51.100.1", "nonce-xff-2"))
+            .await
+            .expect("response");
+        assert_eq!(error_of(response).await, "Device not found");
+
+        // A fresh forwarded address does not get the attacker out of the block
+        let response = app
+            .clone()
+            .oneshot(from(attacker, "192.0.2.99", "nonce-xff-3"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // and the victim's own address was never blocked
+        let response = app
+            .oneshot(from(victim, "198.51.100.1", "nonce-xff-4"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_of(response).await, "Device not found");
+    }
+
+    #[tokio::test]
+    async fn test_v2_unsupported_alg_returns_unauthorized() {
+        let (app, key, kid) = v2_router(AuthConfig::default());
+        let mut request = sign_v2(&key, &kid, TEST_HOST, "nonce-v2-alg");
+        request
+            .headers_mut()
+            .insert("X-Signature-Alg", "hmac-sha256".parse().expect("header"));
+        let response = app.oneshot(request).await.expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_of(response).await, "Unsupported signature algorithm");
+    }
+
+    #[tokio::test]
+    async fn test_v2_missing_host_returns_unauthorized() {
+        let (app, key, kid) = v2_router(AuthConfig::default());
+        let mut request = sign_v2(&key, &kid, TEST_HOST, "nonce-v2-nohost");
+        request.headers_mut().remove(HOST);
+        let response = app.oneshot(request).await.expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_of(response).await, "Missing Host header");
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_reverifies_and_detects_tampering() {
+        async fn echo_signed(auth: AuthenticatedDevice) -> axum::Json<SignedRequest> {
+            axum::Json(auth.signed_request())
+        }
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let pubkey_bytes = signing_key.verifying_key().to_bytes();
+        let record = make_device_record(&pubkey_bytes, false);
+        let kid = record.device_kid.clone();
+        let repo = MockIdentityRepo::new();
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        let app = Router::new()
+            .route("/test", get(echo_signed))
+            .layer(axum::extract::Extension(
+                Arc::new(repo) as Arc<dyn IdentityRepo>
+            ));
+
+        let response = app
+            .oneshot(sign_v2(&signing_key, &kid, TEST_HOST, "nonce-envelope"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), 8192)
+            .await
+            .expect("body");
+        let signed: SignedRequest = serde_json::from_slice(&body).expect("json");
+        let pubkey = DevicePubkey::from_base64url(&encode_base64url(&pubkey_bytes)).expect("key");
+        assert!(signed.verify(&pubkey));
+
+        let tampered = SignedRequest {
+            body: encode_base64url(br#"{"visibility":"public"}"#),
+            ..signed
+        };
+        assert!(!tampered.verify(&pubkey));
+    }
+
+    #[tokio::test]
+    async fn test_v1_rejected_after_migration_window() {
+        let config = AuthConfig {
+            accept_v1_signatures: false,
+            ..AuthConfig::default()
+        };
+        let (app, key, kid) = v2_router(config);
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_canonical(&key, "GET", "/test", timestamp, "nonce-v1", b"");
+        let response = app
+            .clone()
+            .oneshot(build_auth_request(
+                kid.as_str(),
+                &signature,
+                timestamp,
+                "nonce-v1",
+            ))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_of(response).await, "Missing X-Signature-Alg header");
+
+        let response = app
+            .oneshot(sign_v2(&key, &kid, TEST_HOST, "nonce-v2-only"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // ── Client capabilities ────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_declared_caps_are_recorded() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let record = make_device_record(&signing_key.verifying_key().to_bytes(), false);
+        let kid = record.device_kid.clone();
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        let app = Router::new()
+            .route(
+                "/test",
+                get(|_auth: AuthenticatedDevice| async { StatusCode::OK }),
+            )
+            .layer(axum::extract::Extension(
+                repo.clone() as Arc<dyn IdentityRepo>
+            ));
+
+        let mut request = sign_v2(&signing_key, &kid, TEST_HOST, "nonce-caps");
+        request.headers_mut().insert(
+            CLIENT_CAPS_HEADER,
+            "sig-v2, cert-v2".parse().expect("header"),
+        );
+        let response = app.oneshot(request).await.expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *repo.recorded_caps.lock().expect("lock"),
+            [(kid, vec!["sig-v2".to_string(), "cert-v2".to_string()])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_v1_refused_once_device_declared_v2() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let mut record = make_device_record(&signing_key.verifying_key().to_bytes(), false);
+        record.client_caps = vec!["sig-v2".to_string()];
+        let kid = record.device_kid.clone();
+        let repo = MockIdentityRepo::new();
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        let app = make_auth_router(repo);
+
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_canonical(&signing_key, "GET", "/test", timestamp, "nonce-v1", b"");
+        let response = app
+            .oneshot(build_auth_request(
+                kid.as_str(),
+                &signature,
+                timestamp,
+                "nonce-v1",
+            ))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["code"], "SIGNATURE_UPGRADE_REQUIRED");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_caps_header_returns_bad_request() {
+        let (app, key, kid) = v2_router(AuthConfig::default());
+        let mut request = sign_v2(&key, &kid, TEST_HOST, "nonce-bad-caps");
+        request
+            .headers_mut()
+            .insert(CLIENT_CAPS_HEADER, "sig2".parse().expect("header"));
+        let response = app.oneshot(request).await.expect("response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // ── Server-issued challenges ───────────────────────────────────────────
+
+    fn sign_v2_challenged(
+        signing_key: &SigningKey,
+        kid: &Kid,
+        timestamp: i64,
+        challenge: &str,
+    ) -> Request<Body> {
+        let nonce = "nonce-challenged";
+        let canonical = RequestParts {
+            method: "GET",
+            host: TEST_HOST,
+            path_and_query: "/test",
+            timestamp,
+            nonce,
+            content_type: "",
+            device_kid: kid.as_str(),
+            challenge,
+            body: b"",
+        }
+        .canonical_v2(SignatureAlg::Ed25519);
+        let signature = encode_base64url(&signing_key.sign(canonical.as_bytes()).to_bytes());
+        let mut request = build_auth_request(kid.as_str(), &signature, timestamp, nonce);
+        let headers = request.headers_mut();
+        headers.insert("X-Signature-Alg", "ed25519".parse().expect("header"));
+        headers.insert(HOST, TEST_HOST.parse().expect("header"));
+        headers.insert("X-Challenge", challenge.parse().expect("header"));
+        request
+    }
+
+    fn challenge_router(signing_key: &SigningKey) -> (Router, Arc<MockIdentityRepo>, Kid) {
+        let record = make_device_record(&signing_key.verifying_key().to_bytes(), false);
+        let kid = record.device_kid.clone();
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_get_device_key_by_kid_result(Ok(record));
+        let app = Router::new()
+            .route(
+                "/test",
+                get(|_auth: AuthenticatedDevice| async { StatusCode::OK }),
+            )
+            .layer(axum::extract::Extension(
+                Arc::clone(&repo) as Arc<dyn IdentityRepo>
+            ));
+        (app, repo, kid)
+    }
+
+    #[tokio::test]
+    async fn test_challenge_replaces_timestamp_window_and_is_consumed() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let (app, repo, kid) = challenge_router(&signing_key);
+        let challenge = super::super::challenge::generate_challenge();
+        repo.auth_challenges.lock().expect("lock poisoned").push((
+            kid.clone(),
+            hash_challenge(&challenge),
+            Utc::now() + chrono::Duration::seconds(60),
+        ));
+
+        // A day off the server clock, but answering a live challenge
+        let skewed = Utc::now().timestamp() - 86_400;
+        let response = app
+            .oneshot(sign_v2_challenged(&signing_key, &kid, skewed, &challenge))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(repo
+            .auth_challenges
+            .lock()
+            .expect("lock poisoned")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_challenge_returns_unauthorized() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let (app, _repo, kid) = challenge_router(&signing_key);
+        let response = app
+            .oneshot(sign_v2_challenged(
+                &signing_key,
+                &kid,
+                Utc::now().timestamp(),
+                &super::super::challenge::generate_challenge(),
+            ))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            error_of(response).await,
+            "Challenge is unknown, expired, or already used"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_challenge_requires_v2_signature() {
+        let (app, key, kid) = v2_router(AuthConfig::default());
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_canonical(&key, "GET", "/test", timestamp, "nonce-v1-chal", b"");
+        let mut request = build_auth_request(kid.as_str(), &signature, timestamp, "nonce-v1-chal");
+        request
+            .headers_mut()
+            .insert("X-Challenge", "abc".parse().expect("header"));
+        let response = app.oneshot(request).await.expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            error_of(response).await,
+            "X-Challenge requires a v2 signature"
         );
     }
 }
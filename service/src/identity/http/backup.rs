@@ -8,19 +8,43 @@
 //!
 //! Synthetic backups are keyed with a server-side HMAC secret so that external
 //! observers cannot precompute the expected response for a given username.
+//!
+//! Recovery on a new device uses `GET /auth/backups/{root_kid}` instead, which
+//! also returns the salt and KDF parameters. Root KIDs are public (profiles,
+//! CRLs, `/verify`), so unknown KIDs get a synthetic backup too.
+//!
+//! An account holder may require a recovery code for both endpoints via
+//! `PUT /auth/backups/recovery-code` (stored as Argon2id, see
+//! [`crate::identity::recovery_code`]). A missing or wrong code gets the same
+//! synthetic backup as an unknown account rather than an error, so the
+//! response never reveals whether an account, a backup, or a code exists.
+//!
+//! Setting or clearing the code needs a root key signature as well as a
+//! device signature, as deleting the account does: a stolen device alone
+//! cannot lock the owner out of recovery or lift the code before fetching
+//! the backup. The web client does not send `X-Recovery-Code` or manage
+//! codes yet, so accounts that set one need another client to recover.
 
 use std::sync::Arc;
 
 use crate::http::Path;
-use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::Extension,
+    http::{header::CACHE_CONTROL, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use hmac::{Hmac, Mac};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use tc_crypto::Kid;
+use tc_crypto::{verify_ed25519, BackupEnvelope, Kid};
 use utoipa::ToSchema;
 
-use crate::identity::repo::{AccountRepoError, BackupRepoError, IdentityRepo};
-use crate::identity::service::validate_username;
+use super::auth::AuthenticatedDevice;
+use crate::identity::recovery_code::{self, Verified};
+use crate::identity::repo::{AccountRepoError, BackupRecord, BackupRepoError, IdentityRepo};
+use crate::identity::service::{recovery_code_message, CertificateSignature};
+use crate::identity::username::UsernamePolicy;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -69,6 +93,47 @@ pub struct BackupResponse {
     pub root_kid: Kid,
 }
 
+/// Header carrying the recovery code for the backup endpoints.
+pub const RECOVERY_CODE_HEADER: &str = "x-recovery-code";
+
+/// Accepted recovery code length, in characters.
+pub const RECOVERY_CODE_LENGTH: std::ops::RangeInclusive<usize> = 16..=128;
+
+/// Argon2id parameters needed to re-derive the backup key
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BackupKdfParams {
+    /// Always `argon2id`
+    pub algorithm: String,
+    /// Memory cost in KiB
+    pub m_cost: u32,
+    /// Iterations
+    pub t_cost: u32,
+    /// Parallelism
+    pub p_cost: u32,
+}
+
+/// Everything a new device needs to restore the root key
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryBackupResponse {
+    #[schema(value_type = String)]
+    pub root_kid: Kid,
+    /// Base64url-encoded encrypted backup envelope
+    pub encrypted_backup: String,
+    /// Base64url-encoded Argon2id salt (also embedded in the envelope)
+    pub salt: String,
+    pub kdf: BackupKdfParams,
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetRecoveryCodeRequest {
+    /// New recovery code (16-128 characters), or `null` to stop requiring one
+    pub recovery_code: Option<String>,
+    /// Base64url root key signature over the recovery code message for this
+    /// account and the request's `X-Timestamp`
+    pub root_signature: String,
+}
+
 /// Compute HMAC-SHA256(key, message) and return the 32-byte tag.
 ///
 /// HMAC-SHA256 accepts keys of any length (RFC 2104 §2), so
@@ -138,12 +203,108 @@ fn synthetic_backup_response(username: &str, hmac_key: &[u8]) -> axum::response:
         .into_response()
 }
 
+/// Build the 200 OK recovery response for a KID with no backup available.
+///
+/// The envelope comes from [`synthetic_backup`] under a `kid:` subject, which
+/// cannot collide with a username, and echoes the requested KID back.
+fn synthetic_recovery_response(root_kid: Kid, hmac_key: &[u8]) -> axum::response::Response {
+    let (fake_backup, _) = synthetic_backup(&format!("kid:{root_kid}"), hmac_key);
+    let Ok(envelope) = BackupEnvelope::parse(fake_backup) else {
+        unreachable!("synthetic backups are valid envelopes")
+    };
+    recovery_response(root_kid, &envelope, 1)
+}
+
+fn recovery_response(
+    root_kid: Kid,
+    envelope: &BackupEnvelope,
+    version: i32,
+) -> axum::response::Response {
+    (
+        StatusCode::OK,
+        [(CACHE_CONTROL, "no-store")],
+        Json(RecoveryBackupResponse {
+            root_kid,
+            encrypted_backup: tc_crypto::encode_base64url(envelope.as_bytes()),
+            salt: tc_crypto::encode_base64url(envelope.salt()),
+            kdf: BackupKdfParams {
+                algorithm: "argon2id".to_string(),
+                m_cost: envelope.m_cost(),
+                t_cost: envelope.t_cost(),
+                p_cost: envelope.p_cost(),
+            },
+            version,
+        }),
+    )
+        .into_response()
+}
+
+/// Whether the request may receive `record`, given its recovery code.
+///
+/// Backups without a code are always released. A code presented for an
+/// unknown or code-less backup is still run through Argon2id so the response
+/// time does not reveal whether one is set. A legacy SHA-256 code is rehashed
+/// on a successful match.
+async fn recovery_code_accepted(
+    repo: &dyn IdentityRepo,
+    record: Option<&BackupRecord>,
+    headers: &HeaderMap,
+) -> bool {
+    let stored = record.and_then(|r| r.recovery_code_hash.clone());
+    let required = stored.is_some();
+    let Some(presented) = headers
+        .get(RECOVERY_CODE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+    else {
+        if required {
+            metrics::counter!("tc_backup_recovery_code_failures_total").increment(1);
+        }
+        return !required;
+    };
+
+    let checked = tokio::task::spawn_blocking(move || {
+        let verified = recovery_code::verify(stored.as_deref(), &presented);
+        let rehashed = (verified == Some(Verified::Legacy))
+            .then(|| recovery_code::hash(&presented).ok())
+            .flatten();
+        (verified, rehashed)
+    })
+    .await;
+    let (verified, rehashed) = match checked {
+        Ok(checked) => checked,
+        Err(e) => {
+            tracing::error!("Recovery code check failed: {e}");
+            (None, None)
+        }
+    };
+
+    if let (Some(record), Some(rehashed)) = (record, rehashed) {
+        if let Err(e) = repo
+            .set_backup_recovery_code(&record.kid, Some(&rehashed))
+            .await
+        {
+            tracing::warn!(kid = %record.kid, "Failed to rehash legacy recovery code: {e}");
+        }
+    }
+    if !required {
+        return true;
+    }
+    if verified.is_none() {
+        metrics::counter!("tc_backup_recovery_code_failures_total").increment(1);
+    }
+    verified.is_some()
+}
+
 /// GET /auth/backup/{username} -- fetch encrypted backup for login.
 ///
 /// Returns 200 with an encrypted backup envelope for both real and unknown
 /// usernames. Unknown usernames receive a deterministic synthetic backup
 /// to prevent username enumeration.
 ///
+/// If the owner set a recovery code, the request must present it in
+/// `X-Recovery-Code`; otherwise it gets the synthetic backup too.
+///
 /// To mitigate timing side-channels, both the account lookup and backup
 /// lookup are always performed regardless of whether the account exists.
 #[utoipa::path(
@@ -151,7 +312,8 @@ fn synthetic_backup_response(username: &str, hmac_key: &[u8]) -> axum::response:
     path = "/auth/backup/{username}",
     tag = "Identity",
     params(
-        ("username" = String, Path, description = "Username to fetch backup for")
+        ("username" = String, Path, description = "Username to fetch backup for"),
+        ("X-Recovery-Code" = Option<String>, Header, description = "Recovery code, if the account requires one")
     ),
     responses(
         (status = 200, description = "Encrypted backup (real or synthetic)", body = BackupResponse),
@@ -162,10 +324,13 @@ fn synthetic_backup_response(username: &str, hmac_key: &[u8]) -> axum::response:
 pub async fn get_backup(
     Extension(repo): Extension<Arc<dyn IdentityRepo>>,
     Extension(hmac_key): Extension<SyntheticBackupKey>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
     Path(username): Path<String>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let username = username.trim();
-    if let Err(e) = validate_username(username) {
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    if let Err(e) = usernames.check_handle(username) {
         return super::bad_request(&e.to_string());
     }
 
@@ -185,6 +350,8 @@ pub async fn get_backup(
     let account = match account_result {
         Ok(a) => a,
         Err(AccountRepoError::NotFound) => {
+            // Spend the same time on a presented code as for a real account
+            recovery_code_accepted(repo.as_ref(), None, &headers).await;
             return synthetic_backup_response(username, hmac_key.as_bytes());
         }
         Err(AccountRepoError::Database(e)) => {
@@ -199,6 +366,9 @@ pub async fn get_backup(
 
     match backup_result {
         Ok(record) => {
+            if !recovery_code_accepted(repo.as_ref(), Some(&record), &headers).await {
+                return synthetic_backup_response(username, hmac_key.as_bytes());
+            }
             let encrypted_backup = tc_crypto::encode_base64url(&record.encrypted_backup);
             (
                 StatusCode::OK,
@@ -212,6 +382,7 @@ pub async fn get_backup(
         Err(BackupRepoError::NotFound) => {
             // Account exists but has no backup — return synthetic to avoid
             // leaking that the account exists without a backup.
+            recovery_code_accepted(repo.as_ref(), None, &headers).await;
             synthetic_backup_response(username, hmac_key.as_bytes())
         }
         Err(BackupRepoError::Database(e)) => {
@@ -225,6 +396,137 @@ pub async fn get_backup(
     }
 }
 
+/// GET `/auth/backups/{root_kid}` -- fetch a backup to recover an account.
+///
+/// Returns the envelope together with its salt and Argon2id parameters. If
+/// the owner set a recovery code, the request must present it in
+/// `X-Recovery-Code`. Unknown KIDs, KIDs without a backup, and missing or
+/// wrong codes all get a deterministic synthetic backup, exactly like
+/// `GET /auth/backup/{username}`.
+#[utoipa::path(
+    get,
+    path = "/auth/backups/{root_kid}",
+    tag = "Identity",
+    params(
+        ("root_kid" = String, Path, description = "Root key ID of the account"),
+        ("X-Recovery-Code" = Option<String>, Header, description = "Recovery code, if the account requires one")
+    ),
+    responses(
+        (status = 200, description = "Encrypted backup and KDF parameters (real or synthetic)", body = RecoveryBackupResponse),
+        (status = 400, description = "Invalid KID"),
+        (status = 429, description = "Rate limit exceeded"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_recovery_backup(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Extension(hmac_key): Extension<SyntheticBackupKey>,
+    Path(root_kid): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let Ok(root_kid) = root_kid.parse::<Kid>() else {
+        return super::bad_request("Invalid KID format");
+    };
+    let record = match repo.get_backup_by_kid(&root_kid).await {
+        Ok(record) => Some(record),
+        Err(BackupRepoError::NotFound) => None,
+        Err(e) => {
+            tracing::error!("Failed to fetch backup for recovery: {e}");
+            return super::internal_error();
+        }
+    };
+
+    let accepted = recovery_code_accepted(repo.as_ref(), record.as_ref(), &headers).await;
+    let Some(record) = record.filter(|_| accepted) else {
+        return synthetic_recovery_response(root_kid, hmac_key.as_bytes());
+    };
+    match BackupEnvelope::parse(record.encrypted_backup) {
+        Ok(envelope) => recovery_response(record.kid, &envelope, record.version),
+        Err(e) => {
+            tracing::error!(kid = %root_kid, "Stored backup failed to parse: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+/// PUT /auth/backups/recovery-code -- require (or stop requiring) a recovery
+/// code to fetch the account's backup by root KID
+///
+/// Signed by a device and, in the body, by the root key over
+/// [`recovery_code_message`].
+#[utoipa::path(
+    put,
+    path = "/auth/backups/recovery-code",
+    tag = "Identity",
+    request_body = SetRecoveryCodeRequest,
+    responses(
+        (status = 204, description = "Recovery code updated"),
+        (status = 400, description = "Invalid recovery code or root signature"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Account has no backup"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn set_recovery_code(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let req: SetRecoveryCodeRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    if let Some(code) = &req.recovery_code {
+        if !RECOVERY_CODE_LENGTH.contains(&code.chars().count()) {
+            return super::bad_request("Recovery code must be 16-128 characters");
+        }
+    }
+    let signature = match CertificateSignature::from_base64url(&req.root_signature) {
+        Ok(sig) => sig,
+        Err(e) => return super::bad_request(&e.to_string()),
+    };
+    let account = match repo.get_account_by_id(auth.account_id).await {
+        Ok(account) => account,
+        Err(e) => {
+            tracing::error!("Failed to look up account: {e}");
+            return super::internal_error();
+        }
+    };
+    let root_pubkey = match super::decode_account_root_pubkey(&account) {
+        Ok(k) => k,
+        Err(resp) => return resp,
+    };
+    let message = recovery_code_message(auth.account_id, auth.signed_request().timestamp);
+    if verify_ed25519(&root_pubkey, &message, signature.as_bytes()).is_err() {
+        return super::bad_request("Invalid root signature");
+    }
+    let hash = match req.recovery_code {
+        Some(code) => match tokio::task::spawn_blocking(move || recovery_code::hash(&code)).await {
+            Ok(Ok(hash)) => Some(hash),
+            Ok(Err(e)) => {
+                tracing::error!("Failed to hash recovery code: {e}");
+                return super::internal_error();
+            }
+            Err(e) => {
+                tracing::error!("Recovery code hashing task failed: {e}");
+                return super::internal_error();
+            }
+        },
+        None => None,
+    };
+    match repo
+        .set_backup_recovery_code(&account.root_kid, hash.as_deref())
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(BackupRepoError::NotFound) => super::not_found("Account has no backup"),
+        Err(e) => {
+            tracing::error!("Failed to update recovery code: {e}");
+            super::internal_error()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +539,7 @@ mod tests {
         routing::get,
         Router,
     };
+    use ed25519_dalek::{Signer, SigningKey};
     use std::sync::Arc;
     use tc_crypto::{encode_base64url, Kid};
     use tower::ServiceExt;
@@ -485,6 +788,7 @@ mod tests {
             salt: vec![0; 16],
             version: 1,
             created_at: chrono::Utc::now(),
+            recovery_code_hash: None,
         }));
         let app = test_router(repo);
 
@@ -544,4 +848,286 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    // ── Recovery codes ─────────────────────────────────────────────────────
+
+    fn stored_backup(kid: &Kid, recovery_code: Option<&str>) -> BackupRecord {
+        let envelope =
+            BackupEnvelope::build([7u8; 16], 65536, 3, 1, [0u8; 12], &[0u8; 48]).expect("build");
+        BackupRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            kid: kid.clone(),
+            encrypted_backup: envelope.into_bytes(),
+            salt: vec![7u8; 16],
+            version: 1,
+            created_at: chrono::Utc::now(),
+            recovery_code_hash: recovery_code.map(|c| recovery_code::hash(c).expect("hash")),
+        }
+    }
+
+    async fn json_body<T: serde::de::DeserializeOwned>(response: axum::response::Response) -> T {
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        serde_json::from_slice(&body).expect("json")
+    }
+
+    #[tokio::test]
+    async fn get_backup_requires_recovery_code_when_set() {
+        let root_kid = Kid::derive(&[10u8; 32]);
+        let real = stored_backup(&root_kid, Some("correct-horse-battery"));
+        let real_backup = encode_base64url(&real.encrypted_backup);
+        for (code, expect_real) in [
+            (None, false),
+            (Some("wrong-code-0123456789"), false),
+            (Some("correct-horse-battery"), true),
+        ] {
+            let repo = MockIdentityRepo::new();
+            repo.set_account_by_username_result(Ok(AccountRecord {
+                id: Uuid::new_v4(),
+                username: "alice".to_string(),
+                root_pubkey: encode_base64url(&[10u8; 32]),
+                root_kid: root_kid.clone(),
+            }));
+            repo.set_get_backup_by_kid_result(Ok(real.clone()));
+            let mut request = backup_request("alice");
+            if let Some(code) = code {
+                request
+                    .headers_mut()
+                    .insert(RECOVERY_CODE_HEADER, code.parse().expect("header"));
+            }
+            let response = test_router(repo).oneshot(request).await.expect("response");
+            assert_eq!(response.status(), StatusCode::OK, "{code:?}");
+            let payload: serde_json::Value = json_body(response).await;
+            assert_eq!(
+                payload["encrypted_backup"] == real_backup.as_str(),
+                expect_real,
+                "{code:?}"
+            );
+            assert_eq!(
+                payload["root_kid"] == root_kid.as_str(),
+                expect_real,
+                "{code:?}"
+            );
+        }
+    }
+
+    // ── Recovery by root KID ───────────────────────────────────────────────
+
+    async fn recover(
+        repo: MockIdentityRepo,
+        kid: &str,
+        code: Option<&str>,
+    ) -> axum::response::Response {
+        let mut request = Request::builder().uri(format!("/auth/backups/{kid}"));
+        if let Some(code) = code {
+            request = request.header(RECOVERY_CODE_HEADER, code);
+        }
+        Router::new()
+            .route("/auth/backups/{root_kid}", get(get_recovery_backup))
+            .layer(axum::extract::Extension(
+                Arc::new(repo) as Arc<dyn crate::identity::repo::IdentityRepo>
+            ))
+            .layer(axum::extract::Extension(SyntheticBackupKey::new(
+                TEST_HMAC_KEY.to_vec(),
+            )))
+            .oneshot(request.body(Body::empty()).expect("request builder"))
+            .await
+            .expect("response")
+    }
+
+    #[tokio::test]
+    async fn recovery_returns_envelope_salt_and_kdf_params() {
+        let kid = Kid::derive(&[4u8; 32]);
+        let repo = MockIdentityRepo::new();
+        repo.set_get_backup_by_kid_result(Ok(stored_backup(&kid, None)));
+
+        let response = recover(repo, kid.as_str(), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+        let payload: RecoveryBackupResponse = json_body(response).await;
+        assert_eq!(payload.root_kid, kid);
+        assert_eq!(payload.salt, encode_base64url(&[7u8; 16]));
+        assert_eq!(
+            (payload.kdf.m_cost, payload.kdf.t_cost, payload.kdf.p_cost),
+            (65536, 3, 1)
+        );
+        assert_eq!(payload.kdf.algorithm, "argon2id");
+        assert_eq!(payload.version, 1);
+    }
+
+    /// Anti-enumeration: a missing or wrong code looks like an unknown KID.
+    #[tokio::test]
+    async fn recovery_without_matching_code_returns_synthetic_backup() {
+        let kid = Kid::derive(&[5u8; 32]);
+        let real = stored_backup(&kid, Some("correct-horse-battery"));
+        let unknown: RecoveryBackupResponse =
+            json_body(recover(MockIdentityRepo::new(), kid.as_str(), None).await).await;
+        assert_ne!(
+            unknown.encrypted_backup,
+            encode_base64url(&real.encrypted_backup)
+        );
+
+        for (code, expect_real) in [
+            (None, false),
+            (Some("wrong-code-0123456789"), false),
+            (Some("correct-horse-battery"), true),
+        ] {
+            let repo = MockIdentityRepo::new();
+            repo.set_get_backup_by_kid_result(Ok(real.clone()));
+            let response = recover(repo, kid.as_str(), code).await;
+            assert_eq!(response.status(), StatusCode::OK, "{code:?}");
+            let payload: RecoveryBackupResponse = json_body(response).await;
+            assert_eq!(payload.root_kid, kid);
+            if expect_real {
+                assert_eq!(
+                    payload.encrypted_backup,
+                    encode_base64url(&real.encrypted_backup)
+                );
+            } else {
+                assert_eq!(
+                    payload.encrypted_backup, unknown.encrypted_backup,
+                    "{code:?}"
+                );
+                assert_eq!(payload.salt, unknown.salt, "{code:?}");
+            }
+        }
+    }
+
+    /// Anti-enumeration: unknown KIDs get a stable, parseable synthetic backup.
+    #[tokio::test]
+    async fn recovery_unknown_kid_returns_deterministic_synthetic_backup() {
+        let response = recover(MockIdentityRepo::new(), "not-a-kid", None).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // backup lookup defaults to NotFound
+        let kid = Kid::derive(&[6u8; 32]);
+        let first = recover(MockIdentityRepo::new(), kid.as_str(), None).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        let first: RecoveryBackupResponse = json_body(first).await;
+        let second: RecoveryBackupResponse =
+            json_body(recover(MockIdentityRepo::new(), kid.as_str(), None).await).await;
+        assert_eq!(first.root_kid, kid);
+        assert_eq!(first.encrypted_backup, second.encrypted_backup);
+        assert_eq!(first.salt, second.salt);
+        let envelope = tc_crypto::decode_base64url(&first.encrypted_backup).expect("base64");
+        assert!(BackupEnvelope::parse(envelope).is_ok());
+
+        let other: RecoveryBackupResponse = json_body(
+            recover(
+                MockIdentityRepo::new(),
+                Kid::derive(&[7u8; 32]).as_str(),
+                None,
+            )
+            .await,
+        )
+        .await;
+        assert_ne!(first.encrypted_backup, other.encrypted_backup);
+    }
+
+    #[tokio::test]
+    async fn recovery_rehashes_legacy_code_on_match() {
+        let kid = Kid::derive(&[11u8; 32]);
+        let mut legacy = stored_backup(&kid, None);
+        legacy.recovery_code_hash = Some(recovery_code::from_legacy_digest(
+            &<Sha256 as sha2::Digest>::digest(b"correct-horse-battery"),
+        ));
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_get_backup_by_kid_result(Ok(legacy.clone()));
+
+        let response = get_recovery_backup(
+            Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+            Extension(SyntheticBackupKey::new(TEST_HMAC_KEY.to_vec())),
+            Path(kid.to_string()),
+            HeaderMap::from_iter([(
+                axum::http::HeaderName::from_static(RECOVERY_CODE_HEADER),
+                "correct-horse-battery".parse().expect("header"),
+            )]),
+        )
+        .await
+        .into_response();
+        let payload: RecoveryBackupResponse = json_body(response).await;
+        assert_eq!(
+            payload.encrypted_backup,
+            encode_base64url(&legacy.encrypted_backup)
+        );
+
+        let calls = repo.backup_recovery_codes.lock().expect("lock poisoned");
+        let [(rehashed_kid, Some(rehashed))] = calls.as_slice() else {
+            panic!("expected one rehash, got {calls:?}");
+        };
+        assert_eq!(rehashed_kid, &kid);
+        assert_eq!(
+            recovery_code::verify(Some(rehashed), "correct-horse-battery"),
+            Some(Verified::Current)
+        );
+    }
+
+    #[tokio::test]
+    async fn set_recovery_code_stores_salted_hash_for_root_kid() {
+        let root_key = SigningKey::from_bytes(&[8u8; 32]);
+        let root_pubkey = root_key.verifying_key().to_bytes();
+        let root_kid = Kid::derive(&root_pubkey);
+        let account_id = Uuid::new_v4();
+        // `for_test` requests carry timestamp 0
+        let signed = encode_base64url(
+            &root_key
+                .sign(&recovery_code_message(account_id, 0))
+                .to_bytes(),
+        );
+        let unsigned = encode_base64url(&[0u8; 64]);
+        let repo = Arc::new(MockIdentityRepo::new());
+        for (body, expected) in [
+            (
+                serde_json::json!({ "recovery_code": "too-short", "root_signature": signed }),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                serde_json::json!({
+                    "recovery_code": "correct-horse-battery",
+                    "root_signature": unsigned,
+                }),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                serde_json::json!({
+                    "recovery_code": "correct-horse-battery",
+                    "root_signature": signed,
+                }),
+                StatusCode::NO_CONTENT,
+            ),
+            (
+                serde_json::json!({ "recovery_code": null, "root_signature": signed }),
+                StatusCode::NO_CONTENT,
+            ),
+        ] {
+            repo.set_account_by_id_result(Ok(AccountRecord {
+                id: account_id,
+                username: "alice".to_string(),
+                root_pubkey: encode_base64url(&root_pubkey),
+                root_kid: root_kid.clone(),
+            }));
+            let response = set_recovery_code(
+                Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+                AuthenticatedDevice::for_test(
+                    account_id,
+                    Kid::derive(&[9u8; 32]),
+                    axum::body::Bytes::from(body.to_string()),
+                ),
+            )
+            .await
+            .into_response();
+            assert_eq!(response.status(), expected, "{body}");
+        }
+
+        let calls = repo.backup_recovery_codes.lock().expect("lock poisoned");
+        let [(set_kid, Some(stored)), (cleared_kid, None)] = calls.as_slice() else {
+            panic!("expected a set then a clear, got {calls:?}");
+        };
+        assert_eq!((set_kid, cleared_kid), (&root_kid, &root_kid));
+        assert!(stored.starts_with("$argon2id$"));
+        assert_eq!(
+            recovery_code::verify(Some(stored), "correct-horse-battery"),
+            Some(Verified::Current)
+        );
+    }
 }
@@ -0,0 +1,138 @@
+//! Streaming body reads for signed requests.
+//!
+//! [`read_hashed`] pulls the body one frame at a time, feeding each chunk to
+//! a [`BodyHasher`] and giving up as soon as the running total passes the
+//! limit. An oversized upload is never buffered past the limit, and the
+//! signature check reuses the hash instead of making a second pass over the
+//! bytes.
+//!
+//! Signed bodies are capped at 64 KiB by default. A route that accepts larger
+//! signed uploads raises its own cap with [`SignedBodyLimit`]:
+//!
+//! ```ignore
+//! .route("/uploads", post(upload).layer(Extension(SignedBodyLimit(2 * 1024 * 1024))))
+//! ```
+
+use std::pin::Pin;
+
+use axum::body::{Body, Bytes};
+use http_body::Body as _;
+use tc_crypto::{BodyHash, BodyHasher};
+
+/// Per-route cap, in bytes, on the body of a device-signed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedBodyLimit(pub usize);
+
+/// A fully read request body and its SHA-256.
+#[derive(Debug, Clone)]
+pub struct HashedBody {
+    pub bytes: Bytes,
+    pub hash: BodyHash,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BodyReadError {
+    #[error("request body exceeds {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("failed to read request body: {0}")]
+    Read(#[from] axum::Error),
+}
+
+/// Read `body` up to `limit` bytes, hashing it as it arrives.
+///
+/// # Errors
+///
+/// Returns `TooLarge` as soon as the declared or received length passes
+/// `limit`, and `Read` if the underlying stream fails.
+pub async fn read_hashed(mut body: Body, limit: usize) -> Result<HashedBody, BodyReadError> {
+    let declared = body.size_hint().lower();
+    if !usize::try_from(declared).is_ok_and(|len| len <= limit) {
+        return Err(BodyReadError::TooLarge { limit });
+    }
+
+    let mut hasher = BodyHasher::new();
+    let mut bytes = Vec::with_capacity(usize::try_from(declared).unwrap_or(0));
+    while let Some(frame) = std::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+        // Trailers are not part of the signed body
+        let Ok(chunk) = frame?.into_data() else {
+            continue;
+        };
+        if hasher.len().saturating_add(chunk.len()) > limit {
+            return Err(BodyReadError::TooLarge { limit });
+        }
+        hasher.update(&chunk);
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(HashedBody {
+        bytes: Bytes::from(bytes),
+        hash: hasher.finish(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::convert::Infallible;
+    use std::task::{Context, Poll};
+
+    use http_body::{Frame, SizeHint};
+
+    /// Body that yields its chunks one frame at a time with no length hint,
+    /// like a chunked upload.
+    struct Chunked(VecDeque<Bytes>);
+
+    impl http_body::Body for Chunked {
+        type Data = Bytes;
+        type Error = Infallible;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+            Poll::Ready(self.0.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+        }
+
+        fn size_hint(&self) -> SizeHint {
+            SizeHint::default()
+        }
+    }
+
+    fn chunked(chunks: &[&'static [u8]]) -> Body {
+        Body::new(Chunked(
+            chunks.iter().copied().map(Bytes::from_static).collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn chunked_body_is_hashed_like_a_buffered_one() {
+        let read = read_hashed(chunked(&[b"{\"a\":", b"1,", b"\"b\":2}"]), 64)
+            .await
+            .expect("read");
+        assert_eq!(&read.bytes[..], b"{\"a\":1,\"b\":2}");
+        assert_eq!(read.hash, BodyHash::of(b"{\"a\":1,\"b\":2}"));
+
+        let empty = read_hashed(Body::empty(), 0).await.expect("read");
+        assert_eq!(empty.hash, BodyHash::of(b""));
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_are_rejected() {
+        // Known length: rejected before reading
+        let err = read_hashed(Body::from(vec![0u8; 65]), 64)
+            .await
+            .expect_err("too large");
+        assert!(matches!(err, BodyReadError::TooLarge { limit: 64 }));
+
+        // Unknown length: rejected at the chunk that crosses the limit
+        let err = read_hashed(chunked(&[&[0u8; 40], &[0u8; 40]]), 64)
+            .await
+            .expect_err("too large");
+        assert!(matches!(err, BodyReadError::TooLarge { limit: 64 }));
+
+        let exact = read_hashed(chunked(&[&[0u8; 32], &[0u8; 32]]), 64)
+            .await
+            .expect("read");
+        assert_eq!(exact.bytes.len(), 64);
+    }
+}
@@ -0,0 +1,256 @@
+//! Server-issued challenges for signed requests
+//!
+//! A device that cannot trust its clock asks for a challenge first:
+//!
+//! - `GET /auth/challenge?kid=<KID>` returns a random value bound to that KID
+//! - the next v2 request sends it back in `X-Challenge`, which adds an
+//!   `x-challenge:` line to the signed canonical message
+//!
+//! [`AuthenticatedDevice`](super::auth::AuthenticatedDevice) skips the
+//! timestamp window for such a request and instead consumes the challenge:
+//! it must have been issued to the signing KID, be unexpired, and not used
+//! before. A signature over a challenge cannot be computed ahead of time.
+//! Challenges live for `auth.challenge_ttl_secs`; only their SHA-256 is
+//! stored.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::header::CACHE_CONTROL,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::config::AuthConfig;
+use crate::http::Query;
+use crate::identity::repo::{DeviceKeyRepoError, IdentityRepo};
+use tc_crypto::{encode_base64url, Kid};
+
+/// Random bytes per challenge (256 bits).
+const CHALLENGE_BYTES: usize = 32;
+
+/// Longest accepted `X-Challenge` value; issued challenges are 43 chars.
+pub const MAX_CHALLENGE_LENGTH: usize = 64;
+
+/// Generate a new plaintext challenge.
+#[must_use]
+pub fn generate_challenge() -> String {
+    let mut bytes = [0u8; CHALLENGE_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    encode_base64url(&bytes)
+}
+
+/// Hash a plaintext challenge for storage and lookup.
+#[must_use]
+pub fn hash_challenge(challenge: &str) -> Vec<u8> {
+    Sha256::digest(challenge.as_bytes()).to_vec()
+}
+
+/// Validate an `X-Challenge` value: non-empty, bounded, base64url only.
+///
+/// # Errors
+///
+/// Returns a message suitable for a 401 response.
+pub fn validate_challenge(challenge: &str) -> Result<(), &'static str> {
+    if challenge.is_empty() || challenge.len() > MAX_CHALLENGE_LENGTH {
+        return Err("Invalid X-Challenge length");
+    }
+    if !challenge
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    {
+        return Err("X-Challenge contains invalid characters");
+    }
+    Ok(())
+}
+
+/// Query parameters for `GET /auth/challenge`.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ChallengeQuery {
+    /// KID of the device that will sign the challenge
+    #[param(value_type = String)]
+    pub kid: Kid,
+}
+
+/// Issued challenge
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthChallengeResponse {
+    /// Value to send back in `X-Challenge`. Single use.
+    pub challenge: String,
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    pub expires_at: String,
+}
+
+/// Issue a single-use challenge for the next signed request from a device.
+///
+/// Unauthenticated and rate-limited like login. Challenges are only stored
+/// for registered devices, so unknown KIDs cannot grow the table.
+#[utoipa::path(
+    get,
+    path = "/auth/challenge",
+    tag = "Identity",
+    params(ChallengeQuery),
+    responses(
+        (status = 200, description = "Challenge issued", body = AuthChallengeResponse),
+        (status = 400, description = "Invalid KID"),
+        (status = 403, description = "Challenges are disabled"),
+        (status = 404, description = "Device not found"),
+        (status = 429, description = "Rate limit exceeded"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_challenge(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth_config: Option<Extension<AuthConfig>>,
+    Query(query): Query<ChallengeQuery>,
+) -> Response {
+    let auth_config = auth_config.map(|Extension(c)| c).unwrap_or_default();
+    if auth_config.challenge_ttl_secs == 0 {
+        return super::forbidden("Challenges are disabled on this server");
+    }
+    let kid = query.kid;
+    match repo.get_device_key_by_kid(&kid).await {
+        Ok(_) => {}
+        Err(DeviceKeyRepoError::NotFound) => return super::not_found("Device not found"),
+        Err(e) => {
+            tracing::error!("Challenge device lookup failed: {e}");
+            return super::internal_error();
+        }
+    }
+
+    let ttl = i64::try_from(auth_config.challenge_ttl_secs).unwrap_or(i64::MAX);
+    let expires_at = Utc::now() + Duration::seconds(ttl);
+    let challenge = generate_challenge();
+    if let Err(e) = repo
+        .create_auth_challenge(&kid, &hash_challenge(&challenge), expires_at)
+        .await
+    {
+        tracing::error!("Failed to store challenge: {e}");
+        return super::internal_error();
+    }
+    metrics::counter!("tc_auth_challenges_issued_total").increment(1);
+    (
+        [(CACHE_CONTROL, "no-store")],
+        Json(AuthChallengeResponse {
+            challenge,
+            device_kid: kid,
+            expires_at: expires_at.to_rfc3339(),
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use crate::identity::repo::DeviceKeyRecord;
+    use axum::{body::Body, http::Request, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn app(repo: Arc<MockIdentityRepo>) -> Router {
+        Router::new()
+            .route("/auth/challenge", get(get_challenge))
+            .layer(Extension(repo as Arc<dyn IdentityRepo>))
+    }
+
+    fn device(kid: &Kid) -> DeviceKeyRecord {
+        DeviceKeyRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            device_kid: kid.clone(),
+            device_pubkey: encode_base64url(&[7u8; 32]),
+            device_name: "Phone".to_string(),
+            certificate: vec![],
+            last_used_at: None,
+            revoked_at: None,
+            revoked_by_kid: None,
+            revocation_envelope: None,
+            certificate_version: Some(1),
+            certificate_timestamp: None,
+            rotated_from_kid: None,
+            rotation_signature: None,
+            client_caps: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn get_request(kid: &str) -> Request<Body> {
+        Request::builder()
+            .uri(format!("/auth/challenge?kid={kid}"))
+            .body(Body::empty())
+            .expect("request")
+    }
+
+    #[tokio::test]
+    async fn issues_and_stores_challenge_for_known_device() {
+        let kid = Kid::derive(&[7u8; 32]);
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_get_device_key_by_kid_result(Ok(device(&kid)));
+        let response = app(Arc::clone(&repo))
+            .oneshot(get_request(kid.as_str()))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+        let body = axum::body::to_bytes(response.into_body(), 4096)
+            .await
+            .expect("body");
+        let issued: AuthChallengeResponse = serde_json::from_slice(&body).expect("json");
+        assert_eq!(issued.device_kid, kid);
+
+        let stored = repo.auth_challenges.lock().expect("lock poisoned");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].0, kid);
+        assert_eq!(stored[0].1, hash_challenge(&issued.challenge));
+    }
+
+    #[tokio::test]
+    async fn unknown_device_gets_no_challenge() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_get_device_key_by_kid_result(Err(DeviceKeyRepoError::NotFound));
+        let response = app(Arc::clone(&repo))
+            .oneshot(get_request("cs1uhCLEB_ttCYaQ8RMLfQ"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(repo
+            .auth_challenges
+            .lock()
+            .expect("lock poisoned")
+            .is_empty());
+
+        let response = app(repo)
+            .oneshot(get_request("not-a-kid"))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn generated_challenges_validate() {
+        let a = generate_challenge();
+        let b = generate_challenge();
+        assert_eq!(a.len(), 43);
+        assert!(validate_challenge(&a).is_ok());
+        assert_ne!(a, b);
+        assert_ne!(hash_challenge(&a), hash_challenge(&b));
+    }
+
+    #[test]
+    fn malformed_challenges_are_rejected() {
+        assert!(validate_challenge("").is_err());
+        assert!(validate_challenge(&"a".repeat(MAX_CHALLENGE_LENGTH + 1)).is_err());
+        assert!(validate_challenge("abc\ndef").is_err());
+        assert!(validate_challenge("abc def").is_err());
+        assert!(validate_challenge("abc+/=").is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! Device revocation list endpoint; see [`crate::identity::crl`].
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::header::CACHE_CONTROL,
+    response::{IntoResponse, Response},
+    Json,
+};
+use uuid::Uuid;
+
+use super::Path;
+use crate::identity::crl::{current_account_crl, CrlPublishError};
+use crate::identity::repo::IdentityRepo;
+
+/// GET `/accounts/{account_id}/crl` — an account's device revocation list
+///
+/// Open to anyone. Each signed entry can be checked offline against the
+/// account's root key with [`crate::identity::crl::verify_crl`]. Served with
+/// `Cache-Control: no-cache` so caches revalidate after a revocation.
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/crl",
+    tag = "Identity",
+    params(("account_id" = Uuid, Path, description = "Account whose revoked devices to list")),
+    responses(
+        (status = 200, description = "Device revocation list", body = crate::identity::crl::DeviceCrl),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_account_crl(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Path(account_id): Path<Uuid>,
+) -> Response {
+    match current_account_crl(repo.as_ref(), account_id).await {
+        Ok(document) => ([(CACHE_CONTROL, "no-cache")], Json(document)).into_response(),
+        Err(CrlPublishError::AccountNotFound) => super::not_found("Account not found"),
+        Err(e) => {
+            tracing::error!(%account_id, "Failed to load device CRL: {e}");
+            super::internal_error()
+        }
+    }
+}
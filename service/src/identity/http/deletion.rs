@@ -0,0 +1,401 @@
+//! Account deletion and undeletion handlers
+//!
+//! `DELETE /auth/accounts/me` deactivates the account and schedules it for
+//! purging; `POST /auth/accounts/undelete` restores it within the grace
+//! period. Both need a root key signature, since deletion revokes every
+//! device and undeletion has none left to sign with. See
+//! [`crate::identity::deletion`].
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tc_crypto::verify_ed25519;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::account_status::validate_reason;
+use super::auth::AuthenticatedDevice;
+use super::devices::account_root_pubkey;
+use super::sigchain::sigchain_link;
+use super::ErrorResponse;
+use crate::config::{AccountDeletionConfig, AuthConfig};
+use crate::identity::crl::publish_account_crl;
+use crate::identity::deletion::purge_after;
+use crate::identity::repo::{AccountReactivation, AccountRepoError, IdentityRepo, NonceRepoError};
+use crate::identity::service::{
+    account_deletion_message, account_undeletion_message, CertificateSignature,
+};
+use crate::identity::sigchain::{NewSigchainLink, SigchainLinkType};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeleteAccountRequest {
+    /// Base64url root key signature over the account deletion message for
+    /// this account and the request's `X-Timestamp`
+    pub root_signature: String,
+    /// Why the account is being deleted; kept in the audit trail
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteAccountResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub deactivated_at: String,
+    /// Until this instant `POST /auth/accounts/undelete` restores the account
+    pub purge_after: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UndeleteAccountRequest {
+    pub username: String,
+    /// Unix seconds; must be within the server's clock skew window
+    pub timestamp: i64,
+    /// Base64url root key signature over the account undeletion message for
+    /// this account and `timestamp`
+    pub root_signature: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UndeleteAccountResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub reactivated_at: String,
+}
+
+/// DELETE /auth/accounts/me — deactivate the caller's account
+///
+/// Every device is revoked at once and the account is purged after
+/// `account_deletion.grace_days`, unless it is undeleted first.
+#[utoipa::path(
+    delete,
+    path = "/auth/accounts/me",
+    tag = "Identity",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 200, description = "Account deactivated", body = DeleteAccountResponse),
+        (status = 400, description = "Invalid request or root signature"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account is not active"),
+        (status = 409, description = "Account status changed concurrently"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn delete_account(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    deletion: Option<Extension<AccountDeletionConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let req: DeleteAccountRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    let reason = match validate_reason(req.reason.as_deref()) {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    let signature = match CertificateSignature::from_base64url(&req.root_signature) {
+        Ok(sig) => sig,
+        Err(e) => return super::bad_request(&e.to_string()),
+    };
+    let root_pubkey = match account_root_pubkey(repo.as_ref(), auth.account_id).await {
+        Ok(k) => k,
+        Err(resp) => return resp,
+    };
+    let envelope = auth.signed_request();
+    let message = account_deletion_message(auth.account_id, envelope.timestamp);
+    if verify_ed25519(&root_pubkey, &message, signature.as_bytes()).is_err() {
+        return super::bad_request("Invalid root signature");
+    }
+    let envelope = match serde_json::to_value(&envelope) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("Failed to serialize deletion envelope: {e}");
+            return super::internal_error();
+        }
+    };
+    let link = match sigchain_link(&auth, SigchainLinkType::AccountDeactivated) {
+        Ok(link) => link,
+        Err(resp) => return resp,
+    };
+
+    let grace_days = deletion.map_or_else(
+        || AccountDeletionConfig::default().grace_days,
+        |Extension(c)| c.grace_days,
+    );
+    let purge_after = purge_after(Utc::now(), grace_days);
+    match repo
+        .deactivate_account(
+            auth.account_id,
+            purge_after,
+            &auth.device_kid,
+            &envelope,
+            reason,
+            &link,
+        )
+        .await
+    {
+        Ok(Some(deactivation)) => {
+            metrics::counter!("tc_account_status_changes_total", "status" => "deactivated")
+                .increment(1);
+            tracing::info!(
+                account_id = %auth.account_id,
+                revoked_devices = deactivation.revoked_devices,
+                %purge_after,
+                "Account deactivated"
+            );
+            if let Err(e) = publish_account_crl(repo.as_ref(), auth.account_id).await {
+                tracing::warn!(account_id = %auth.account_id, "Failed to publish device CRL: {e}");
+            }
+            (
+                StatusCode::OK,
+                Json(DeleteAccountResponse {
+                    account_id: auth.account_id,
+                    deactivated_at: deactivation.deactivated_at.to_rfc3339(),
+                    purge_after: purge_after.to_rfc3339(),
+                }),
+            )
+                .into_response()
+        }
+        Ok(None) => super::conflict("Account status changed concurrently"),
+        Err(e) => {
+            tracing::error!("Failed to deactivate account: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+/// POST /auth/accounts/undelete — restore a deactivated account
+///
+/// Signed by the root key alone. Devices revoked by the deletion stay
+/// revoked; log in again to enroll one.
+#[utoipa::path(
+    post,
+    path = "/auth/accounts/undelete",
+    tag = "Identity",
+    request_body = UndeleteAccountRequest,
+    responses(
+        (status = 200, description = "Account restored", body = UndeleteAccountResponse),
+        (status = 400, description = "Invalid request, stale timestamp, or replay"),
+        (status = 401, description = "Invalid credentials"),
+        (status = 409, description = "Account is not scheduled for deletion"),
+        (status = 410, description = "Grace period has ended"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn undelete_account(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth_config: Option<Extension<AuthConfig>>,
+    Json(req): Json<UndeleteAccountRequest>,
+) -> impl IntoResponse {
+    let auth_config = auth_config.map(|Extension(c)| c).unwrap_or_default();
+    let now = Utc::now();
+    if auth_config.timestamp_is_stale(now.timestamp(), req.timestamp) {
+        return super::bad_request("Timestamp out of range");
+    }
+    let signature = match CertificateSignature::from_base64url(&req.root_signature) {
+        Ok(sig) => sig,
+        Err(e) => return super::bad_request(&e.to_string()),
+    };
+
+    // Unknown usernames and bad signatures look the same, as in login
+    let account = match repo.get_account_by_username(req.username.trim()).await {
+        Ok(a) => a,
+        Err(AccountRepoError::NotFound) => return super::unauthorized("Invalid credentials"),
+        Err(e) => {
+            tracing::error!("Undelete account lookup failed: {e}");
+            return super::internal_error();
+        }
+    };
+    let root_pubkey = match super::decode_account_root_pubkey(&account) {
+        Ok(k) => k,
+        Err(resp) => return resp,
+    };
+    let message = account_undeletion_message(account.id, req.timestamp);
+    if verify_ed25519(&root_pubkey, &message, signature.as_bytes()).is_err() {
+        return super::unauthorized("Invalid credentials");
+    }
+    let nonce_hash: [u8; 32] = Sha256::digest(signature.as_bytes()).into();
+    match repo.check_and_record_nonce(&nonce_hash).await {
+        Ok(()) => {}
+        Err(NonceRepoError::Replay) => return super::bad_request("Request replay detected"),
+        Err(e) => {
+            tracing::error!("Nonce check failed: {e}");
+            return super::internal_error();
+        }
+    }
+
+    // The account has no devices left, so the root-signed request is the
+    // link's envelope
+    let link = match serde_json::to_string(&req) {
+        Ok(envelope) => NewSigchainLink {
+            link_type: SigchainLinkType::AccountReactivated,
+            signer_kid: account.root_kid.clone(),
+            envelope,
+        },
+        Err(e) => {
+            tracing::error!("Failed to serialize undelete envelope: {e}");
+            return super::internal_error();
+        }
+    };
+    match repo.reactivate_account(account.id, now, &link).await {
+        Ok(AccountReactivation::Reactivated { reactivated_at }) => {
+            metrics::counter!("tc_account_status_changes_total", "status" => "active").increment(1);
+            tracing::info!(account_id = %account.id, "Account undeleted");
+            (
+                StatusCode::OK,
+                Json(UndeleteAccountResponse {
+                    account_id: account.id,
+                    reactivated_at: reactivated_at.to_rfc3339(),
+                }),
+            )
+                .into_response()
+        }
+        Ok(AccountReactivation::NotDeactivated) => {
+            super::conflict("Account is not scheduled for deletion")
+        }
+        Ok(AccountReactivation::GraceExpired { purge_after }) => grace_expired(purge_after),
+        Err(e) => {
+            tracing::error!("Failed to undelete account: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+fn grace_expired(purge_after: chrono::DateTime<Utc>) -> Response {
+    (
+        StatusCode::GONE,
+        Json(ErrorResponse {
+            error: format!(
+                "Grace period ended at {}; the account is being deleted",
+                purge_after.to_rfc3339()
+            ),
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::{mock::MockIdentityRepo, AccountRecord};
+    use crate::identity::status::AccountStatus;
+    use axum::body::Bytes;
+    use ed25519_dalek::{Signer, SigningKey};
+    use rand::rngs::OsRng;
+    use tc_crypto::{encode_base64url, Kid};
+
+    fn account_with_root() -> (AccountRecord, SigningKey) {
+        let root_key = SigningKey::generate(&mut OsRng);
+        let root_pubkey = root_key.verifying_key().to_bytes();
+        let account = AccountRecord {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            root_pubkey: encode_base64url(&root_pubkey),
+            root_kid: Kid::derive(&root_pubkey),
+        };
+        (account, root_key)
+    }
+
+    async fn call_delete(repo: Arc<MockIdentityRepo>, auth: AuthenticatedDevice) -> Response {
+        delete_account(Extension(repo as Arc<dyn IdentityRepo>), None, auth)
+            .await
+            .into_response()
+    }
+
+    async fn call_undelete(repo: Arc<MockIdentityRepo>, req: UndeleteAccountRequest) -> Response {
+        undelete_account(Extension(repo as Arc<dyn IdentityRepo>), None, Json(req))
+            .await
+            .into_response()
+    }
+
+    fn undelete_request(account: &AccountRecord, root_key: &SigningKey) -> UndeleteAccountRequest {
+        let timestamp = Utc::now().timestamp();
+        let message = account_undeletion_message(account.id, timestamp);
+        UndeleteAccountRequest {
+            username: account.username.clone(),
+            timestamp,
+            root_signature: encode_base64url(&root_key.sign(&message).to_bytes()),
+        }
+    }
+
+    #[tokio::test]
+    async fn root_signed_delete_deactivates_and_undelete_restores() {
+        let (account, root_key) = account_with_root();
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_account_by_id_result(Ok(account.clone()));
+        let device_kid = Kid::derive(&[7u8; 32]);
+        // `for_test` requests carry timestamp 0
+        let signature = root_key.sign(&account_deletion_message(account.id, 0));
+        let body = serde_json::json!({
+            "root_signature": encode_base64url(&signature.to_bytes()),
+            "reason": "leaving",
+        });
+        let auth = AuthenticatedDevice::for_test(
+            account.id,
+            device_kid.clone(),
+            Bytes::from(body.to_string()),
+        );
+
+        let response = call_delete(Arc::clone(&repo), auth).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(repo.account_status(), AccountStatus::Deactivated);
+        assert_eq!(
+            *repo.deactivations.lock().expect("lock"),
+            [(account.id, device_kid)]
+        );
+
+        repo.set_account_by_username_result(Ok(account.clone()));
+        let response =
+            call_undelete(Arc::clone(&repo), undelete_request(&account, &root_key)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(repo.account_status(), AccountStatus::Active);
+        let links = repo.sigchain_links.lock().expect("lock");
+        let types: Vec<&str> = links.iter().map(|l| l.link_type.as_str()).collect();
+        assert_eq!(types, ["account_deactivated", "account_reactivated"]);
+        assert_eq!(links[1].signer_kid, account.root_kid);
+    }
+
+    #[tokio::test]
+    async fn delete_without_root_signature_is_refused() {
+        let (account, _) = account_with_root();
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_account_by_id_result(Ok(account.clone()));
+        // Signed by a key that is not the account's root
+        let other = SigningKey::generate(&mut OsRng);
+        let signature = other.sign(&account_deletion_message(account.id, 0));
+        let body = serde_json::json!({ "root_signature": encode_base64url(&signature.to_bytes()) });
+        let auth = AuthenticatedDevice::for_test(
+            account.id,
+            Kid::derive(&[7u8; 32]),
+            Bytes::from(body.to_string()),
+        );
+
+        let response = call_delete(Arc::clone(&repo), auth).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(repo.account_status(), AccountStatus::Active);
+        assert!(repo.deactivations.lock().expect("lock").is_empty());
+    }
+
+    #[tokio::test]
+    async fn undelete_after_grace_period_is_gone() {
+        let (account, root_key) = account_with_root();
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_account_by_username_result(Ok(account.clone()));
+        repo.set_account_status(AccountStatus::Deactivated);
+        *repo.purge_after.lock().expect("lock") = Some(Utc::now() - chrono::Duration::hours(1));
+
+        let response =
+            call_undelete(Arc::clone(&repo), undelete_request(&account, &root_key)).await;
+        assert_eq!(response.status(), StatusCode::GONE);
+        assert_eq!(repo.account_status(), AccountStatus::Deactivated);
+    }
+}
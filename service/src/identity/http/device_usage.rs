@@ -0,0 +1,179 @@
+//! Per-device request counters for the device listing
+//!
+//! Every request authenticated by [`super::auth::AuthenticatedDevice`] is
+//! counted here in hourly buckets along with the route it hit. Counts live in
+//! memory and are written to `device_key_usage` in batches by
+//! [`DeviceUsageRecorder::spawn_flusher`], so the signed-request path never
+//! waits on an extra write. `GET /auth/devices` sums the buckets into 24h and
+//! 7d totals; they trail live traffic by up to one flush interval.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use tc_crypto::Kid;
+
+use crate::identity::repo::{DeviceKeyRepoError, DeviceUsageBatch, IdentityRepo};
+
+/// How often buffered counts are written to Postgres.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Endpoint labels longer than this are truncated.
+const MAX_ENDPOINT_LEN: usize = 128;
+
+#[derive(Debug, Default)]
+struct UsageState {
+    /// Requests not yet written, keyed by `(device_kid, hour bucket)`.
+    counts: HashMap<(Kid, DateTime<Utc>), i64>,
+    /// Latest endpoint per device since the last flush.
+    last_endpoints: HashMap<Kid, String>,
+}
+
+/// Buffers per-device request counts between flushes.
+#[derive(Debug, Default)]
+pub struct DeviceUsageRecorder {
+    state: Mutex<UsageState>,
+}
+
+impl DeviceUsageRecorder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, UsageState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Count one request from `device_kid` to `endpoint` at `now`.
+    ///
+    /// `endpoint` should be the route template (`GET /auth/devices/{kid}`),
+    /// not the concrete path, so labels don't leak identifiers.
+    pub fn record(&self, device_kid: &Kid, endpoint: &str, now: DateTime<Utc>) {
+        let hour = now.duration_trunc(TimeDelta::hours(1)).unwrap_or(now);
+        let endpoint = truncate(endpoint, MAX_ENDPOINT_LEN);
+
+        let mut state = self.state();
+        *state.counts.entry((device_kid.clone(), hour)).or_default() += 1;
+        state
+            .last_endpoints
+            .insert(device_kid.clone(), endpoint.to_string());
+        drop(state);
+    }
+
+    /// Write buffered counts to Postgres. Failed writes are kept for the next
+    /// flush.
+    ///
+    /// # Errors
+    ///
+    /// Returns the repo error if the batch could not be written.
+    pub async fn flush(&self, repo: &dyn IdentityRepo) -> Result<(), DeviceKeyRepoError> {
+        let pending = std::mem::take(&mut *self.state());
+        if pending.counts.is_empty() {
+            return Ok(());
+        }
+        let batch = DeviceUsageBatch {
+            counts: pending
+                .counts
+                .iter()
+                .map(|((kid, hour), &count)| (kid.clone(), *hour, count))
+                .collect(),
+            last_endpoints: pending
+                .last_endpoints
+                .iter()
+                .map(|(kid, endpoint)| (kid.clone(), endpoint.clone()))
+                .collect(),
+        };
+        if let Err(e) = repo.record_device_usage(&batch).await {
+            let mut state = self.state();
+            for (key, count) in pending.counts {
+                *state.counts.entry(key).or_default() += count;
+            }
+            for (kid, endpoint) in pending.last_endpoints {
+                // A newer request since the failed flush wins.
+                state.last_endpoints.entry(kid).or_insert(endpoint);
+            }
+            drop(state);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Flush every [`FLUSH_INTERVAL`] for the life of the process.
+    pub fn spawn_flusher(self: &Arc<Self>, repo: Arc<dyn IdentityRepo>) {
+        let recorder = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = recorder.flush(repo.as_ref()).await {
+                    tracing::warn!("Failed to flush device usage: {e}");
+                }
+            }
+        });
+    }
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use chrono::TimeZone;
+
+    fn kid(byte: u8) -> Kid {
+        Kid::derive(&[byte; 32])
+    }
+
+    #[tokio::test]
+    async fn flush_batches_hourly_counts_and_latest_endpoint() {
+        let recorder = DeviceUsageRecorder::new();
+        let repo = MockIdentityRepo::new();
+        let t = Utc.with_ymd_and_hms(2026, 3, 1, 10, 15, 0).unwrap();
+
+        recorder.record(&kid(1), "GET /auth/devices", t);
+        recorder.record(&kid(1), "POST /trust/endorse", t + TimeDelta::minutes(10));
+        recorder.record(&kid(1), "GET /auth/devices", t + TimeDelta::hours(1));
+        recorder.record(&kid(2), "GET /me/endorsements", t);
+        recorder.flush(&repo).await.unwrap();
+
+        let batches = repo.recorded_device_usage.lock().unwrap();
+        assert_eq!(batches.len(), 1);
+        let mut counts = batches[0].counts.clone();
+        counts.sort_by_key(|(k, h, _)| (k.to_string(), *h));
+        let hour = Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 0).unwrap();
+        let mut expected = vec![
+            (kid(1), hour, 2),
+            (kid(1), hour + TimeDelta::hours(1), 1),
+            (kid(2), hour, 1),
+        ];
+        expected.sort_by_key(|(k, h, _)| (k.to_string(), *h));
+        assert_eq!(counts, expected);
+        assert!(batches[0]
+            .last_endpoints
+            .contains(&(kid(1), "GET /auth/devices".to_string())));
+        drop(batches);
+
+        // Nothing buffered, nothing written
+        recorder.flush(&repo).await.unwrap();
+        assert_eq!(repo.recorded_device_usage.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn truncate_respects_char_boundaries() {
+        assert_eq!(truncate("GET /a", 128), "GET /a");
+        assert_eq!(truncate("ééé", 3), "é");
+    }
+}
@@ -1,21 +1,44 @@
 // lint-patterns:allow-no-utoipa — tracked by #906
 //! Device management HTTP handlers
 //!
-//! Endpoints for listing, adding, revoking, and renaming device keys.
-//! All endpoints require authentication via signed headers.
+//! Endpoints for listing, adding, revoking, renaming, and rotating device
+//! keys, and for inspecting the root delegation behind a device.
+//! All endpoints require authentication via signed headers; listing devices
+//! also accepts an API token with the `devices:read` scope.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Extension, Query},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::auth::AuthenticatedDevice;
+use super::sigchain::sigchain_link;
+use super::token_auth::{scope, ReadAuth};
 use super::{ErrorResponse, Path};
-use crate::identity::repo::{AccountRepoError, DeviceKeyRecord, DeviceKeyRepoError, IdentityRepo};
-use crate::identity::service::{CertificateSignature, DeviceName, DevicePubkey};
-use tc_crypto::{verify_ed25519, Kid};
+use crate::config::{AuthConfig, StepUpConfig};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::identity::capabilities::EnvelopeFormat;
+use crate::identity::crl::publish_account_crl;
+use crate::identity::repo::{
+    AccountRepoError, DeviceKeyRecord, DeviceKeyRepoError, DeviceRotation, DeviceUsageRecord,
+    IdentityRepo,
+};
+use crate::identity::service::{
+    device_certificate_message, device_rotation_message, CertificateSignature, DeviceName,
+    DevicePubkey, CERTIFICATE_VERSION_RAW, CERTIFICATE_VERSION_TIMESTAMPED,
+};
+use crate::identity::sigchain::SigchainLinkType;
+use crate::identity::step_up::StepUpAction;
+use crate::sms::SmsProvider;
+use tc_crypto::{encode_base64url, verify_ed25519, Kid};
 
 /// Device info returned in API responses (omits certificate and raw pubkey)
 #[derive(Debug, Serialize, ToSchema)]
@@ -26,6 +49,33 @@ pub struct DeviceInfo {
     pub created_at: String,
     pub last_used_at: Option<String>,
     pub revoked_at: Option<String>,
+    /// KID of the device that revoked this one, if recorded
+    #[schema(value_type = Option<String>)]
+    pub revoked_by_kid: Option<Kid>,
+    /// Certificate format: 1 = root signed the raw pubkey, 2 = root signed
+    /// pubkey and login timestamp; null for devices created before versioning
+    pub certificate_version: Option<i32>,
+    /// Signed requests in the last 24 hours (hourly granularity; may trail
+    /// live traffic by up to a minute)
+    pub requests_24h: i64,
+    /// Signed requests in the last 7 days
+    pub requests_7d: i64,
+    /// Route of the most recent signed request, e.g. `GET /auth/devices`
+    pub last_endpoint: Option<String>,
+    /// Key this device rotated from, if it was created by a rotation
+    #[schema(value_type = Option<String>)]
+    pub rotated_from_kid: Option<Kid>,
+}
+
+impl DeviceInfo {
+    fn with_usage(mut self, usage: Option<&DeviceUsageRecord>) -> Self {
+        if let Some(usage) = usage {
+            self.requests_24h = usage.requests_24h;
+            self.requests_7d = usage.requests_7d;
+            self.last_endpoint.clone_from(&usage.last_endpoint);
+        }
+        self
+    }
 }
 
 impl From<DeviceKeyRecord> for DeviceInfo {
@@ -36,10 +86,23 @@ impl From<DeviceKeyRecord> for DeviceInfo {
             created_at: record.created_at.to_rfc3339(),
             last_used_at: record.last_used_at.map(|t| t.to_rfc3339()),
             revoked_at: record.revoked_at.map(|t| t.to_rfc3339()),
+            revoked_by_kid: record.revoked_by_kid,
+            certificate_version: record.certificate_version,
+            rotated_from_kid: record.rotated_from_kid,
+            requests_24h: 0,
+            requests_7d: 0,
+            last_endpoint: None,
         }
     }
 }
 
+/// Query parameters for the device list endpoint.
+#[derive(Debug, Deserialize)]
+pub struct DeviceListQuery {
+    /// Include revoked devices (default `true`)
+    pub include_revoked: Option<bool>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DeviceListResponse {
     pub devices: Vec<DeviceInfo>,
@@ -50,8 +113,15 @@ pub struct AddDeviceRequest {
     /// Base64url-encoded Ed25519 public key
     pub pubkey: String,
     pub name: String,
-    /// Base64url-encoded certificate (root key's signature over device pubkey)
+    /// Base64url-encoded certificate: the root key's signature over the
+    /// device pubkey, or over `device_pubkey || certificate_timestamp` when
+    /// `certificate_timestamp` is set
     pub certificate: String,
+    /// Unix seconds bound into a version 2 certificate; must be within the
+    /// clock skew window. Required once the calling device has declared
+    /// `cert-v2`.
+    #[serde(default)]
+    pub certificate_timestamp: Option<i64>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -66,24 +136,158 @@ pub struct RenameDeviceRequest {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RotateDeviceRequest {
+    /// Base64url-encoded Ed25519 public key of the replacement key pair
+    pub new_pubkey: String,
+    /// Base64url-encoded root key's signature over the new public key, or
+    /// over `new_pubkey || certificate_timestamp` when that is set
+    pub certificate: String,
+    /// Unix seconds bound into a version 2 certificate; must be within the
+    /// clock skew window. Required once the rotating device has declared
+    /// `cert-v2`.
+    #[serde(default)]
+    pub certificate_timestamp: Option<i64>,
+    /// Base64url-encoded current device key's signature over the rotation
+    /// message (`tc-device-rotation-v1 || 0x00 || kid || 0x00 || new_pubkey`)
+    pub rotation_signature: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateDeviceResponse {
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    #[schema(value_type = String)]
+    pub rotated_from_kid: Kid,
+    pub created_at: String,
+}
+
+/// Scope reported for an active device. Device keys are not scope-restricted:
+/// an active device acts with the account's full authority.
+pub const DEVICE_SCOPE_ALL: &str = "*";
+
+/// Result of re-verifying a stored delegation against the current root key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegationStatus {
+    /// The certificate verifies against the account's current root key.
+    Valid,
+    /// The certificate does not verify against the account's current root key.
+    Invalid,
+    /// The certificate format was not recorded (device predates certificate
+    /// versioning) and it is not a raw-pubkey certificate, so it cannot be
+    /// re-verified.
+    UnknownFormat,
+}
+
+/// Stored delegation envelope for a device, with its verification status.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DelegationResponse {
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    /// KID of the account's current root key
+    #[schema(value_type = String)]
+    pub root_kid: Kid,
+    /// Base64url-encoded root signature certifying the device key
+    pub certificate: String,
+    /// 1 = root signed the raw pubkey, 2 = root signed pubkey and login
+    /// timestamp; null for devices created before versioning
+    pub certificate_version: Option<i32>,
+    /// Login timestamp (Unix seconds) bound into a version 2 certificate
+    pub certificate_timestamp: Option<i64>,
+    pub status: DelegationStatus,
+    pub revoked_at: Option<String>,
+    /// Capabilities the device currently holds: `["*"]` while active,
+    /// empty once revoked
+    pub scopes: Vec<String>,
+    /// Key this device rotated from, if it was created by a rotation
+    #[schema(value_type = Option<String>)]
+    pub rotated_from_kid: Option<Kid>,
+    /// Base64url-encoded signature by `rotated_from_kid` over the rotation
+    pub rotation_signature: Option<String>,
+}
+
+/// Re-verify a stored device certificate against a root public key.
+#[must_use]
+pub fn verify_delegation(root_pubkey: &[u8; 32], record: &DeviceKeyRecord) -> DelegationStatus {
+    let Ok(device_pubkey) = DevicePubkey::from_base64url(&record.device_pubkey) else {
+        return DelegationStatus::Invalid;
+    };
+    let Ok(signature) = <[u8; 64]>::try_from(record.certificate.as_slice()) else {
+        return DelegationStatus::Invalid;
+    };
+    let verifies = |timestamp: Option<i64>| {
+        verify_ed25519(
+            root_pubkey,
+            &device_certificate_message(device_pubkey.as_bytes(), timestamp),
+            &signature,
+        )
+        .is_ok()
+    };
+
+    let timestamp = match (record.certificate_version, record.certificate_timestamp) {
+        (Some(CERTIFICATE_VERSION_RAW), _) => None,
+        (Some(CERTIFICATE_VERSION_TIMESTAMPED), Some(timestamp)) => Some(timestamp),
+        (Some(CERTIFICATE_VERSION_TIMESTAMPED), None) => return DelegationStatus::Invalid,
+        // Pre-versioning rows: only raw-pubkey certificates can be checked
+        (None, _) if verifies(None) => return DelegationStatus::Valid,
+        (Some(_) | None, _) => return DelegationStatus::UnknownFormat,
+    };
+    if verifies(timestamp) {
+        DelegationStatus::Valid
+    } else {
+        DelegationStatus::Invalid
+    }
+}
+
 /// GET /auth/devices — list all devices for the authenticated account
+///
+/// Revoked devices are included by default, with the revoking device's KID,
+/// so the full device history can be audited; pass `include_revoked=false`
+/// for active devices only. Each device carries its request counts for the
+/// last 24 hours and 7 days and the last route it called, so dormant devices
+/// stand out. Also accepts an API token with the
+/// `devices:read` scope.
 #[utoipa::path(
     get,
     path = "/auth/devices",
     tag = "Identity",
+    params(
+        ("include_revoked" = Option<bool>, Query, description = "Include revoked devices (default true)")
+    ),
     responses(
         (status = 200, description = "Device list", body = DeviceListResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the devices:read scope"),
         (status = 500, description = "Internal server error")
-    )
+    ),
+    security(("device_auth" = []), ("api_token" = []))
 )]
 pub async fn list_devices(
     Extension(repo): Extension<Arc<dyn IdentityRepo>>,
-    auth: AuthenticatedDevice,
+    Query(params): Query<DeviceListQuery>,
+    auth: ReadAuth<scope::DevicesRead>,
 ) -> impl IntoResponse {
-    match repo.list_device_keys_by_account(auth.account_id).await {
-        Ok(records) => {
-            let devices: Vec<DeviceInfo> = records.into_iter().map(DeviceInfo::from).collect();
+    let include_revoked = params.include_revoked.unwrap_or(true);
+    let listed = match repo.list_device_keys_by_account(auth.account_id).await {
+        Ok(records) => repo
+            .list_device_usage_by_account(auth.account_id)
+            .await
+            .map(|usage| (records, usage)),
+        Err(e) => Err(e),
+    };
+    match listed {
+        Ok((records, usage)) => {
+            let usage: HashMap<&Kid, &DeviceUsageRecord> =
+                usage.iter().map(|u| (&u.device_kid, u)).collect();
+            let devices: Vec<DeviceInfo> = records
+                .into_iter()
+                .filter(|r| include_revoked || r.revoked_at.is_none())
+                .map(|r| {
+                    let device_usage = usage.get(&r.device_kid).copied();
+                    DeviceInfo::from(r).with_usage(device_usage)
+                })
+                .collect();
             (StatusCode::OK, Json(DeviceListResponse { devices })).into_response()
         }
         Err(e) => {
@@ -103,6 +307,7 @@ pub async fn list_devices(
         (status = 201, description = "Device added", body = AddDeviceResponse),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller declared cert-v2 but sent a version 1 certificate (CERTIFICATE_UPGRADE_REQUIRED)"),
         (status = 409, description = "Device key already registered"),
         (status = 422, description = "Maximum device limit reached"),
         (status = 500, description = "Internal server error")
@@ -110,6 +315,7 @@ pub async fn list_devices(
 )]
 pub async fn add_device(
     Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth_config: Option<Extension<AuthConfig>>,
     auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
     let req: AddDeviceRequest = match auth.json() {
@@ -117,10 +323,18 @@ pub async fn add_device(
         Err(resp) => return resp,
     };
 
+    if let Err(resp) = check_certificate_version(&auth, auth_config, req.certificate_timestamp) {
+        return resp;
+    }
+
     let validated = match validate_add_device_request(&*repo, auth.account_id, &req).await {
         Ok(v) => v,
         Err(resp) => return resp,
     };
+    let link = match sigchain_link(&auth, SigchainLinkType::DeviceAdded) {
+        Ok(link) => link,
+        Err(resp) => return resp,
+    };
 
     match repo
         .create_device_key(
@@ -129,6 +343,8 @@ pub async fn add_device(
             &req.pubkey,
             validated.device_name.as_str(),
             validated.cert.as_bytes(),
+            req.certificate_timestamp,
+            Some(&link),
         )
         .await
     {
@@ -167,28 +383,10 @@ async fn validate_add_device_request(
     let cert_sig = CertificateSignature::from_base64url(&req.certificate)
         .map_err(|e| super::bad_request(&e.to_string()))?;
 
-    // Look up the account to get the root pubkey for certificate verification
-    let account = match repo.get_account_by_id(account_id).await {
-        Ok(a) => a,
-        Err(AccountRepoError::NotFound) => {
-            tracing::error!("Authenticated device's account not found: {account_id}");
-            return Err(super::internal_error());
-        }
-        Err(e) => {
-            tracing::error!("Failed to look up account: {e}");
-            return Err(super::internal_error());
-        }
-    };
-
-    let root_pubkey_arr = super::decode_account_root_pubkey(&account)?;
+    let root_pubkey_arr = account_root_pubkey(repo, account_id).await?;
 
-    if verify_ed25519(
-        &root_pubkey_arr,
-        device_pubkey.as_bytes(),
-        cert_sig.as_bytes(),
-    )
-    .is_err()
-    {
+    let message = device_certificate_message(device_pubkey.as_bytes(), req.certificate_timestamp);
+    if verify_ed25519(&root_pubkey_arr, &message, cert_sig.as_bytes()).is_err() {
         return Err(super::bad_request("Invalid device certificate"));
     }
 
@@ -202,17 +400,23 @@ async fn validate_add_device_request(
 }
 
 /// DELETE /auth/devices/:kid — revoke a device key
+///
+/// A device added recently must present a confirmed `revoke_device`
+/// step-up challenge if the account has enrolled a phone; see
+/// [`crate::identity::step_up`].
 #[utoipa::path(
     delete,
     path = "/auth/devices/{kid}",
     tag = "Identity",
     params(
-        ("kid" = String, Path, description = "Key identifier of the device to revoke")
+        ("kid" = String, Path, description = "Key identifier of the device to revoke"),
+        ("x-step-up-challenge" = Option<String>, Header, description = "Confirmed revoke_device challenge")
     ),
     responses(
         (status = 204, description = "Device revoked"),
         (status = 400, description = "Invalid KID"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Step-up confirmation required (code STEP_UP_REQUIRED)"),
         (status = 404, description = "Device not found"),
         (status = 409, description = "Device already revoked"),
         (status = 422, description = "Cannot revoke the device making this request"),
@@ -221,7 +425,11 @@ async fn validate_add_device_request(
 )]
 pub async fn revoke_device(
     Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
+    sms: Option<Extension<Arc<dyn SmsProvider>>>,
+    step_up_config: Option<Extension<StepUpConfig>>,
     Path(kid_str): Path<String>,
+    headers: HeaderMap,
     auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
     let kid: Kid = match kid_str.parse() {
@@ -240,13 +448,138 @@ pub async fn revoke_device(
             .into_response();
     }
 
-    match repo.revoke_device_key(&kid, auth.account_id).await {
-        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    let step_up_config = step_up_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = super::step_up::require_step_up(
+        repo.as_ref(),
+        sms.is_some(),
+        &step_up_config,
+        &auth,
+        &headers,
+        StepUpAction::RevokeDevice,
+    )
+    .await
+    {
+        return resp;
+    }
+
+    // Keep the signed request so the revocation can be checked offline; see
+    // crate::identity::crl
+    let envelope = match serde_json::to_value(auth.signed_request()) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("Failed to serialize revocation envelope: {e}");
+            return super::internal_error();
+        }
+    };
+    let link = match sigchain_link(&auth, SigchainLinkType::DeviceRevoked) {
+        Ok(link) => link,
+        Err(resp) => return resp,
+    };
+    match repo
+        .revoke_device_key(
+            &kid,
+            auth.account_id,
+            &auth.device_kid,
+            Some(&envelope),
+            &link,
+        )
+        .await
+    {
+        Ok(()) => {
+            // A failure here only delays the CRL: a stale one is rebuilt on read
+            if let Err(e) = publish_account_crl(repo.as_ref(), auth.account_id).await {
+                tracing::warn!(account_id = %auth.account_id, "Failed to publish device CRL: {e}");
+            }
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::DeviceRevoked {
+                    account_id: auth.account_id,
+                    device_kid: kid,
+                });
+            }
+            StatusCode::NO_CONTENT.into_response()
+        }
         Err(DeviceKeyRepoError::AlreadyRevoked) => super::conflict("Device already revoked"),
         Err(e) => super::device_key_repo_error_response(&e),
     }
 }
 
+/// GET /auth/devices/:kid/delegation — inspect a device's root delegation
+///
+/// Returns the stored certificate, whether it still verifies against the
+/// account's current root key, and the scopes the device holds. Works for
+/// revoked devices too, so a client can see why a device is being refused.
+/// Also accepts an API token with the `devices:read` scope.
+#[utoipa::path(
+    get,
+    path = "/auth/devices/{kid}/delegation",
+    tag = "Identity",
+    params(
+        ("kid" = String, Path, description = "Key identifier of the device to inspect")
+    ),
+    responses(
+        (status = 200, description = "Delegation envelope", body = DelegationResponse),
+        (status = 400, description = "Invalid KID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the devices:read scope"),
+        (status = 404, description = "Device not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []), ("api_token" = []))
+)]
+pub async fn get_device_delegation(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Path(kid_str): Path<String>,
+    auth: ReadAuth<scope::DevicesRead>,
+) -> impl IntoResponse {
+    let kid: Kid = match kid_str.parse() {
+        Ok(k) => k,
+        Err(_) => return super::bad_request("Invalid KID format"),
+    };
+
+    let record = match repo.get_device_key_by_kid(&kid).await {
+        Ok(r) if r.account_id == auth.account_id => r,
+        // Another account's device is indistinguishable from a missing one
+        Ok(_) => return super::not_found("Device not found"),
+        Err(e) => return super::device_key_repo_error_response(&e),
+    };
+
+    let account = match repo.get_account_by_id(auth.account_id).await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to look up account for delegation: {e}");
+            return super::internal_error();
+        }
+    };
+    let root_pubkey = match super::decode_account_root_pubkey(&account) {
+        Ok(k) => k,
+        Err(resp) => return resp,
+    };
+
+    let status = verify_delegation(&root_pubkey, &record);
+    let scopes = if record.revoked_at.is_none() {
+        vec![DEVICE_SCOPE_ALL.to_string()]
+    } else {
+        vec![]
+    };
+
+    (
+        StatusCode::OK,
+        Json(DelegationResponse {
+            device_kid: record.device_kid,
+            root_kid: account.root_kid,
+            certificate: encode_base64url(&record.certificate),
+            certificate_version: record.certificate_version,
+            certificate_timestamp: record.certificate_timestamp,
+            status,
+            revoked_at: record.revoked_at.map(|t| t.to_rfc3339()),
+            scopes,
+            rotated_from_kid: record.rotated_from_kid,
+            rotation_signature: record.rotation_signature.as_deref().map(encode_base64url),
+        }),
+    )
+        .into_response()
+}
+
 /// PATCH /auth/devices/:kid — rename a device
 #[utoipa::path(
     patch,
@@ -285,8 +618,13 @@ pub async fn rename_device(
         Err(e) => return super::bad_request(&e.to_string()),
     };
 
+    let link = match sigchain_link(&auth, SigchainLinkType::DeviceRenamed) {
+        Ok(link) => link,
+        Err(resp) => return resp,
+    };
+
     match repo
-        .rename_device_key(&kid, auth.account_id, new_name.as_str())
+        .rename_device_key(&kid, auth.account_id, new_name.as_str(), &link)
         .await
     {
         Ok(()) => StatusCode::NO_CONTENT.into_response(),
@@ -297,9 +635,198 @@ pub async fn rename_device(
     }
 }
 
+/// POST /auth/devices/:kid/rotate — replace this device's key pair
+///
+/// Signed by the device being rotated. The new key must be certified by the
+/// root key and cross-signed by the current device key. The old KID is
+/// revoked and the new one registered under the same name, linked to it, in
+/// one transaction; the rotation request becomes the old key's revocation
+/// record in the CRL. The new key inherits the capabilities the old one
+/// declared, so a device that declared `cert-v2` must certify its new key
+/// with a version 2 certificate, and one that declared `sig-v2` cannot fall
+/// back to v1 signatures by rotating.
+#[utoipa::path(
+    post,
+    path = "/auth/devices/{kid}/rotate",
+    tag = "Identity",
+    params(
+        ("kid" = String, Path, description = "Key identifier of the signing device")
+    ),
+    request_body = RotateDeviceRequest,
+    responses(
+        (status = 201, description = "Device key rotated", body = RotateDeviceResponse),
+        (status = 400, description = "Invalid request, certificate, or rotation signature"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "A device can only rotate its own key, or it declared cert-v2 but sent a version 1 certificate (CERTIFICATE_UPGRADE_REQUIRED)"),
+        (status = 409, description = "New key already registered or device already revoked"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[allow(clippy::too_many_lines)]
+pub async fn rotate_device(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
+    auth_config: Option<Extension<AuthConfig>>,
+    Path(kid_str): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let kid: Kid = match kid_str.parse() {
+        Ok(k) => k,
+        Err(_) => return super::bad_request("Invalid KID format"),
+    };
+    if kid != auth.device_kid {
+        return super::forbidden("A device can only rotate its own key");
+    }
+    let req: RotateDeviceRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+
+    let new_pubkey = match DevicePubkey::from_base64url(&req.new_pubkey) {
+        Ok(k) => k,
+        Err(e) => return super::bad_request(&e.to_string()),
+    };
+    let new_kid = new_pubkey.kid();
+    if new_kid == kid {
+        return super::bad_request("New key must differ from the current key");
+    }
+    let (cert, rotation_sig) = match (
+        CertificateSignature::from_base64url(&req.certificate),
+        CertificateSignature::from_base64url(&req.rotation_signature),
+    ) {
+        (Ok(cert), Ok(sig)) => (cert, sig),
+        (Err(e), _) | (_, Err(e)) => return super::bad_request(&e.to_string()),
+    };
+
+    if let Err(resp) = check_certificate_version(&auth, auth_config, req.certificate_timestamp) {
+        return resp;
+    }
+
+    let root_pubkey = match account_root_pubkey(repo.as_ref(), auth.account_id).await {
+        Ok(k) => k,
+        Err(resp) => return resp,
+    };
+    let message = device_certificate_message(new_pubkey.as_bytes(), req.certificate_timestamp);
+    if verify_ed25519(&root_pubkey, &message, cert.as_bytes()).is_err() {
+        return super::bad_request("Invalid device certificate");
+    }
+
+    let current = match repo.get_device_key_by_kid(&kid).await {
+        Ok(record) => record,
+        Err(e) => return super::device_key_repo_error_response(&e),
+    };
+    let Ok(current_pubkey) = DevicePubkey::from_base64url(&current.device_pubkey) else {
+        tracing::error!(%kid, "Stored device pubkey is invalid");
+        return super::internal_error();
+    };
+    if verify_ed25519(
+        current_pubkey.as_bytes(),
+        &device_rotation_message(&kid, new_pubkey.as_bytes()),
+        rotation_sig.as_bytes(),
+    )
+    .is_err()
+    {
+        return super::bad_request("Invalid rotation signature");
+    }
+
+    let envelope = match serde_json::to_value(auth.signed_request()) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("Failed to serialize rotation envelope: {e}");
+            return super::internal_error();
+        }
+    };
+    let link = match sigchain_link(&auth, SigchainLinkType::DeviceRotated) {
+        Ok(link) => link,
+        Err(resp) => return resp,
+    };
+    let rotation = DeviceRotation {
+        account_id: auth.account_id,
+        old_kid: &kid,
+        new_kid: &new_kid,
+        new_pubkey: &req.new_pubkey,
+        certificate: cert.as_bytes(),
+        certificate_timestamp: req.certificate_timestamp,
+        rotation_signature: rotation_sig.as_bytes(),
+        revocation_envelope: &envelope,
+        link: &link,
+    };
+    match repo.rotate_device_key(&rotation).await {
+        Ok(created) => {
+            if let Err(e) = publish_account_crl(repo.as_ref(), auth.account_id).await {
+                tracing::warn!(account_id = %auth.account_id, "Failed to publish device CRL: {e}");
+            }
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::DeviceRevoked {
+                    account_id: auth.account_id,
+                    device_kid: kid.clone(),
+                });
+            }
+            (
+                StatusCode::CREATED,
+                Json(RotateDeviceResponse {
+                    device_kid: created.device_kid,
+                    rotated_from_kid: kid,
+                    created_at: created.created_at.to_rfc3339(),
+                }),
+            )
+                .into_response()
+        }
+        Err(DeviceKeyRepoError::AlreadyRevoked) => super::conflict("Device already revoked"),
+        Err(e) => super::device_key_repo_error_response(&e),
+    }
+}
+
+/// Check a device certificate's version against the signing device's
+/// declared capabilities: a device that declared `cert-v2` may not certify
+/// new keys the old way, and a version 2 timestamp must be fresh.
+#[allow(clippy::result_large_err)]
+fn check_certificate_version(
+    auth: &AuthenticatedDevice,
+    auth_config: Option<Extension<AuthConfig>>,
+    certificate_timestamp: Option<i64>,
+) -> Result<(), axum::response::Response> {
+    let used = if certificate_timestamp.is_some() {
+        2
+    } else {
+        1
+    };
+    auth.client_caps()
+        .require(EnvelopeFormat::Certificate, used)
+        .map_err(super::upgrade_required_response)?;
+    if let Some(timestamp) = certificate_timestamp {
+        let auth_config = auth_config.map(|Extension(c)| c).unwrap_or_default();
+        if auth_config.timestamp_is_stale(chrono::Utc::now().timestamp(), timestamp) {
+            return Err(super::bad_request("Certificate timestamp out of range"));
+        }
+    }
+    Ok(())
+}
+
+/// Look up the account's root public key for certificate verification.
+#[allow(clippy::result_large_err)]
+pub(super) async fn account_root_pubkey(
+    repo: &dyn IdentityRepo,
+    account_id: Uuid,
+) -> Result<[u8; 32], axum::response::Response> {
+    let account = match repo.get_account_by_id(account_id).await {
+        Ok(a) => a,
+        Err(AccountRepoError::NotFound) => {
+            tracing::error!("Authenticated device's account not found: {account_id}");
+            return Err(super::internal_error());
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up account: {e}");
+            return Err(super::internal_error());
+        }
+    };
+    super::decode_account_root_pubkey(&account)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::identity::capabilities::ClientCaps;
     use crate::identity::repo::mock::MockIdentityRepo;
     use crate::identity::repo::AccountRecord;
     use axum::http::StatusCode;
@@ -332,6 +859,7 @@ mod tests {
             pubkey: encode_base64url(&device_pubkey),
             name: "New Device".to_string(),
             certificate: encode_base64url(&sig.to_bytes()),
+            certificate_timestamp: None,
         };
 
         (req, account)
@@ -427,6 +955,19 @@ mod tests {
         assert_eq!(err.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn test_validate_add_device_request_timestamp_must_be_signed() {
+        // A raw certificate does not cover the timestamp claimed for v2
+        let (mut req, account) = make_valid_components();
+        req.certificate_timestamp = Some(Utc::now().timestamp());
+        let repo = mock_with_account(account.clone());
+        let err = validate_add_device_request(&repo, account.id, &req)
+            .await
+            .err()
+            .expect("expected error");
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+
     fn make_device_record(account_id: Uuid) -> DeviceKeyRecord {
         DeviceKeyRecord {
             id: Uuid::new_v4(),
@@ -437,6 +978,13 @@ mod tests {
             certificate: vec![],
             last_used_at: None,
             revoked_at: None,
+            revoked_by_kid: None,
+            revocation_envelope: None,
+            certificate_version: Some(1),
+            certificate_timestamp: None,
+            rotated_from_kid: None,
+            rotation_signature: None,
+            client_caps: Vec::new(),
             created_at: Utc::now(),
         }
     }
@@ -494,6 +1042,7 @@ mod tests {
 
         let response = add_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
             auth,
         )
         .await
@@ -524,6 +1073,7 @@ mod tests {
 
         let response = add_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
             auth,
         )
         .await
@@ -554,6 +1104,7 @@ mod tests {
 
         let response = add_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
             auth,
         )
         .await
@@ -562,6 +1113,42 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
+    #[tokio::test]
+    async fn test_add_device_raw_certificate_after_cert_v2_requires_upgrade() {
+        use axum::extract::Extension;
+        use axum::response::IntoResponse;
+
+        let (req, account) = make_valid_components();
+        let repo = std::sync::Arc::new(mock_with_account(account.clone()));
+
+        let body = axum::body::Bytes::from(
+            serde_json::json!({
+                "pubkey": req.pubkey,
+                "name": req.name,
+                "certificate": req.certificate,
+            })
+            .to_string(),
+        );
+        let caps = ClientCaps::parse("cert-v2").expect("caps");
+        let auth = AuthenticatedDevice::for_test(account.id, Kid::derive(&[0xAAu8; 32]), body)
+            .with_client_caps(caps);
+
+        let response = add_device(
+            Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
+            auth,
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["code"], "CERTIFICATE_UPGRADE_REQUIRED");
+    }
+
     // ── revoke_device error paths ────────────────────────────────────────────
 
     /// Invalid KID in the path must return 400 before any repo call.
@@ -582,7 +1169,11 @@ mod tests {
 
         let response = revoke_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
+            None,
+            None,
             Path("not-a-valid-kid!!!".to_string()),
+            HeaderMap::new(),
             auth,
         )
         .await
@@ -614,7 +1205,11 @@ mod tests {
 
         let response = revoke_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
+            None,
+            None,
             Path(auth_kid.as_str().to_string()),
+            HeaderMap::new(),
             auth,
         )
         .await
@@ -654,7 +1249,11 @@ mod tests {
 
         let response = revoke_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
+            None,
+            None,
             Path(target_kid.as_str().to_string()),
+            HeaderMap::new(),
             auth,
         )
         .await
@@ -680,7 +1279,11 @@ mod tests {
 
         let response = revoke_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
+            None,
+            None,
             Path(target_kid.as_str().to_string()),
+            HeaderMap::new(),
             auth,
         )
         .await
@@ -703,7 +1306,11 @@ mod tests {
 
         let response = revoke_device(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            None,
+            None,
+            None,
             Path(target_kid.as_str().to_string()),
+            HeaderMap::new(),
             auth,
         )
         .await
@@ -874,15 +1481,17 @@ mod tests {
 
         let account_id = Uuid::new_v4();
         let record = make_device_record(account_id);
-        let kid = record.device_kid.clone();
 
         let repo = std::sync::Arc::new(MockIdentityRepo::new());
         repo.set_list_device_keys_result(Ok(vec![record]));
 
-        let auth = AuthenticatedDevice::for_test(account_id, kid, axum::body::Bytes::new());
+        let auth = ReadAuth::for_test(account_id);
 
         let response = list_devices(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            Query(DeviceListQuery {
+                include_revoked: None,
+            }),
             auth,
         )
         .await
@@ -894,23 +1503,68 @@ mod tests {
         assert_eq!(payload["devices"].as_array().unwrap().len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_list_devices_can_exclude_revoked() {
+        use axum::response::IntoResponse;
+        use axum::{body::to_bytes, extract::Extension};
+
+        let account_id = Uuid::new_v4();
+        let active = make_device_record(account_id);
+        let mut revoked = make_device_record(account_id);
+        revoked.device_kid = Kid::derive(&[4u8; 32]);
+        revoked.revoked_at = Some(Utc::now());
+        revoked.revoked_by_kid = Some(active.device_kid.clone());
+
+        for (include_revoked, expected) in [(None, 2), (Some(true), 2), (Some(false), 1)] {
+            let repo = std::sync::Arc::new(MockIdentityRepo::new());
+            repo.set_list_device_keys_result(Ok(vec![active.clone(), revoked.clone()]));
+
+            let response = list_devices(
+                Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+                Query(DeviceListQuery { include_revoked }),
+                ReadAuth::for_test(account_id),
+            )
+            .await
+            .into_response();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), 4096).await.expect("body");
+            let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+            let devices = payload["devices"].as_array().expect("devices");
+            assert_eq!(
+                devices.len(),
+                expected,
+                "include_revoked={include_revoked:?}"
+            );
+            if expected == 2 {
+                assert_eq!(
+                    devices[1]["revoked_by_kid"].as_str(),
+                    Some(active.device_kid.as_str())
+                );
+                assert_eq!(devices[1]["certificate_version"], 1);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_list_devices_db_error_returns_internal() {
         use axum::extract::Extension;
         use axum::response::IntoResponse;
 
         let account_id = Uuid::new_v4();
-        let auth_kid = Kid::derive(&[0xAAu8; 32]);
 
         let repo = std::sync::Arc::new(MockIdentityRepo::new());
         repo.set_list_device_keys_result(Err(DeviceKeyRepoError::Database(sqlx::Error::Protocol(
             "db error".to_string(),
         ))));
 
-        let auth = AuthenticatedDevice::for_test(account_id, auth_kid, axum::body::Bytes::new());
+        let auth = ReadAuth::for_test(account_id);
 
         let response = list_devices(
             Extension(repo as std::sync::Arc<dyn crate::identity::repo::IdentityRepo>),
+            Query(DeviceListQuery {
+                include_revoked: None,
+            }),
             auth,
         )
         .await
@@ -918,4 +1572,302 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    // ── get_device_delegation ────────────────────────────────────────────────
+
+    /// Build a device record certified by `root` in the given format.
+    fn certified_record(
+        root: &SigningKey,
+        account_id: Uuid,
+        timestamp: Option<i64>,
+    ) -> DeviceKeyRecord {
+        let device_pubkey = SigningKey::generate(&mut OsRng).verifying_key().to_bytes();
+        let message = device_certificate_message(&device_pubkey, timestamp);
+        let mut record = make_device_record(account_id);
+        record.device_kid = Kid::derive(&device_pubkey);
+        record.device_pubkey = encode_base64url(&device_pubkey);
+        record.certificate = root.sign(&message).to_bytes().to_vec();
+        record.certificate_version = Some(if timestamp.is_some() {
+            CERTIFICATE_VERSION_TIMESTAMPED
+        } else {
+            CERTIFICATE_VERSION_RAW
+        });
+        record.certificate_timestamp = timestamp;
+        record
+    }
+
+    #[test]
+    fn test_verify_delegation_by_certificate_version() {
+        let root = SigningKey::generate(&mut OsRng);
+        let root_pubkey = root.verifying_key().to_bytes();
+        let account_id = Uuid::new_v4();
+
+        let raw = certified_record(&root, account_id, None);
+        assert_eq!(
+            verify_delegation(&root_pubkey, &raw),
+            DelegationStatus::Valid
+        );
+
+        let login = certified_record(&root, account_id, Some(1_700_000_000));
+        assert_eq!(
+            verify_delegation(&root_pubkey, &login),
+            DelegationStatus::Valid
+        );
+
+        let other_root = SigningKey::generate(&mut OsRng).verifying_key().to_bytes();
+        assert_eq!(
+            verify_delegation(&other_root, &raw),
+            DelegationStatus::Invalid
+        );
+
+        let mut missing_timestamp = login;
+        missing_timestamp.certificate_timestamp = None;
+        assert_eq!(
+            verify_delegation(&root_pubkey, &missing_timestamp),
+            DelegationStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn test_verify_delegation_legacy_rows() {
+        let root = SigningKey::generate(&mut OsRng);
+        let root_pubkey = root.verifying_key().to_bytes();
+        let account_id = Uuid::new_v4();
+
+        let mut raw = certified_record(&root, account_id, None);
+        raw.certificate_version = None;
+        assert_eq!(
+            verify_delegation(&root_pubkey, &raw),
+            DelegationStatus::Valid
+        );
+
+        // A pre-versioning login certificate has no stored timestamp
+        let mut login = certified_record(&root, account_id, Some(1_700_000_000));
+        login.certificate_version = None;
+        login.certificate_timestamp = None;
+        assert_eq!(
+            verify_delegation(&root_pubkey, &login),
+            DelegationStatus::UnknownFormat
+        );
+    }
+
+    async fn call_get_delegation(
+        repo: MockIdentityRepo,
+        kid: &Kid,
+        account_id: Uuid,
+    ) -> (StatusCode, serde_json::Value) {
+        use axum::response::IntoResponse;
+        use axum::{body::to_bytes, extract::Extension};
+
+        let response = get_device_delegation(
+            Extension(std::sync::Arc::new(repo) as std::sync::Arc<dyn IdentityRepo>),
+            Path(kid.as_str().to_string()),
+            ReadAuth::for_test(account_id),
+        )
+        .await
+        .into_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        (status, serde_json::from_slice(&body).expect("json"))
+    }
+
+    #[tokio::test]
+    async fn test_get_device_delegation_reports_status_and_scopes() {
+        let root = SigningKey::generate(&mut OsRng);
+        let root_pubkey = root.verifying_key().to_bytes();
+        let account = AccountRecord {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            root_pubkey: encode_base64url(&root_pubkey),
+            root_kid: Kid::derive(&root_pubkey),
+        };
+        let mut record = certified_record(&root, account.id, Some(1_700_000_000));
+
+        let repo = mock_with_account(account.clone());
+        repo.set_get_device_key_by_kid_result(Ok(record.clone()));
+        let (status, payload) = call_get_delegation(repo, &record.device_kid, account.id).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(payload["status"], "valid");
+        assert_eq!(payload["certificate_version"], 2);
+        assert_eq!(payload["certificate_timestamp"], 1_700_000_000);
+        assert_eq!(payload["root_kid"], account.root_kid.as_str());
+        assert_eq!(payload["scopes"], serde_json::json!([DEVICE_SCOPE_ALL]));
+
+        record.revoked_at = Some(Utc::now());
+        let repo = mock_with_account(account.clone());
+        repo.set_get_device_key_by_kid_result(Ok(record.clone()));
+        let (status, payload) = call_get_delegation(repo, &record.device_kid, account.id).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(payload["scopes"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_get_device_delegation_other_account_returns_404() {
+        let (_, account) = make_valid_components();
+        let record = make_device_record(Uuid::new_v4());
+
+        let repo = mock_with_account(account.clone());
+        repo.set_get_device_key_by_kid_result(Ok(record.clone()));
+        let (status, _) = call_get_delegation(repo, &record.device_kid, account.id).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    /// A signed rotation request from `old_key`, with the replacement key
+    /// certified by the account's root and cross-signed by `old_key`.
+    fn rotation_fixture() -> (
+        std::sync::Arc<MockIdentityRepo>,
+        AuthenticatedDevice,
+        Kid,
+        SigningKey,
+        SigningKey,
+    ) {
+        let root_key = SigningKey::generate(&mut OsRng);
+        let root_pubkey = root_key.verifying_key().to_bytes();
+        let account = AccountRecord {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            root_pubkey: encode_base64url(&root_pubkey),
+            root_kid: Kid::derive(&root_pubkey),
+        };
+        let old_key = SigningKey::generate(&mut OsRng);
+        let old_pubkey = old_key.verifying_key().to_bytes();
+        let old_kid = Kid::derive(&old_pubkey);
+        let mut current = make_device_record(account.id);
+        current.device_kid = old_kid.clone();
+        current.device_pubkey = encode_base64url(&old_pubkey);
+
+        let repo = std::sync::Arc::new(mock_with_account(account.clone()));
+        repo.set_get_device_key_by_kid_result(Ok(current));
+
+        let new_key = SigningKey::generate(&mut OsRng);
+        let new_pubkey = new_key.verifying_key().to_bytes();
+        let body = serde_json::json!({
+            "new_pubkey": encode_base64url(&new_pubkey),
+            "certificate": encode_base64url(&root_key.sign(&new_pubkey).to_bytes()),
+            "rotation_signature": encode_base64url(
+                &old_key
+                    .sign(&device_rotation_message(&old_kid, &new_pubkey))
+                    .to_bytes()
+            ),
+        });
+        let auth = AuthenticatedDevice::for_test(
+            account.id,
+            old_kid.clone(),
+            axum::body::Bytes::from(body.to_string()),
+        );
+        (repo, auth, old_kid, root_key, new_key)
+    }
+
+    async fn call_rotate(
+        repo: std::sync::Arc<MockIdentityRepo>,
+        kid: &Kid,
+        auth: AuthenticatedDevice,
+    ) -> axum::response::Response {
+        use axum::response::IntoResponse;
+
+        rotate_device(
+            Extension(repo as std::sync::Arc<dyn IdentityRepo>),
+            None,
+            None,
+            Path(kid.to_string()),
+            auth,
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn test_rotate_device_links_new_key_and_revokes_old() {
+        let (repo, auth, old_kid, _, new_key) = rotation_fixture();
+        let new_kid = Kid::derive(&new_key.verifying_key().to_bytes());
+
+        let response = call_rotate(std::sync::Arc::clone(&repo), &old_kid, auth).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["rotated_from_kid"], old_kid.as_str());
+        assert_eq!(
+            *repo.rotations.lock().expect("lock poisoned"),
+            [(old_kid, new_kid)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rotate_device_rejects_other_devices_kid() {
+        let (repo, auth, _, _, _) = rotation_fixture();
+        let other = Kid::derive(&[0xCCu8; 32]);
+
+        let response = call_rotate(std::sync::Arc::clone(&repo), &other, auth).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(repo.rotations.lock().expect("lock poisoned").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_device_rejects_bad_signatures() {
+        for cross_signed in [false, true] {
+            let (repo, auth, old_kid, root_key, new_key) = rotation_fixture();
+            let mut body: serde_json::Value = auth.json().expect("json");
+            if cross_signed {
+                // Valid cross-signature, but a certificate over a different key
+                body["certificate"] =
+                    encode_base64url(&root_key.sign(&[0u8; 32]).to_bytes()).into();
+            } else {
+                // Cross-signature made by the new key instead of the old one
+                let new_pubkey = new_key.verifying_key().to_bytes();
+                body["rotation_signature"] = encode_base64url(
+                    &new_key
+                        .sign(&device_rotation_message(&old_kid, &new_pubkey))
+                        .to_bytes(),
+                )
+                .into();
+            }
+            let auth = AuthenticatedDevice::for_test(
+                auth.account_id,
+                old_kid.clone(),
+                axum::body::Bytes::from(body.to_string()),
+            );
+            let response = call_rotate(std::sync::Arc::clone(&repo), &old_kid, auth).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert!(repo.rotations.lock().expect("lock poisoned").is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotate_device_after_cert_v2_requires_timestamped_certificate() {
+        let (repo, auth, old_kid, root_key, new_key) = rotation_fixture();
+        let caps = ClientCaps::parse("cert-v2").expect("caps");
+        let mut body: serde_json::Value = auth.json().expect("json");
+        let signed = |body: &serde_json::Value| {
+            AuthenticatedDevice::for_test(
+                auth.account_id,
+                old_kid.clone(),
+                axum::body::Bytes::from(body.to_string()),
+            )
+            .with_client_caps(caps.clone())
+        };
+
+        let response = call_rotate(std::sync::Arc::clone(&repo), &old_kid, signed(&body)).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let payload = axum::body::to_bytes(response.into_body(), 1024)
+            .await
+            .expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&payload).expect("json");
+        assert_eq!(payload["code"], "CERTIFICATE_UPGRADE_REQUIRED");
+        assert!(repo.rotations.lock().expect("lock poisoned").is_empty());
+
+        let timestamp = Utc::now().timestamp();
+        let new_pubkey = new_key.verifying_key().to_bytes();
+        body["certificate"] = encode_base64url(
+            &root_key
+                .sign(&device_certificate_message(&new_pubkey, Some(timestamp)))
+                .to_bytes(),
+        )
+        .into();
+        body["certificate_timestamp"] = timestamp.into();
+        let response = call_rotate(std::sync::Arc::clone(&repo), &old_kid, signed(&body)).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(repo.rotations.lock().expect("lock poisoned").len(), 1);
+    }
 }
@@ -7,29 +7,41 @@
 //! ## Replay protection
 //!
 //! The request includes a `timestamp` (Unix seconds) that must be within
-//! ±300 seconds of the server's clock. The certificate signs
+//! `auth.max_clock_skew_secs` (default ±300) of the server's clock. The certificate signs
 //! `device_pubkey || timestamp_le_i64_bytes`, binding the signature to a
 //! narrow time window. A SHA-256 hash of the certificate bytes is recorded
 //! as a nonce, so replaying the exact same request within the window is
 //! rejected.
+//!
+//! A `TC-Client-Caps` header is recorded for the new device; see
+//! [`crate::identity::capabilities`].
 
 use std::sync::Arc;
 
-use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::Extension,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::config::AuthConfig;
 use crate::identity::repo::{AccountRepoError, DeviceKeyRepoError, IdentityRepo, NonceRepoError};
-use crate::identity::service::{validate_username, CertificateSignature, DeviceName, DevicePubkey};
+use crate::identity::service::{
+    device_certificate_message, CertificateSignature, DeviceName, DevicePubkey,
+};
+use crate::identity::username::UsernamePolicy;
 use tc_crypto::{verify_ed25519, Kid};
 
 /// Login request payload
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub username: String,
-    /// Unix timestamp (seconds) — must be within ±300s of server time
+    /// Unix timestamp (seconds) — must be within the server's clock skew window (default ±300s)
     pub timestamp: i64,
     pub device: LoginDevice,
 }
@@ -82,9 +94,7 @@ fn validate_login_device(
         .map_err(|e| super::bad_request(&e.to_string()))?;
 
     // The certificate must sign device_pubkey || timestamp (LE i64 bytes)
-    let mut signed_payload = Vec::with_capacity(40);
-    signed_payload.extend_from_slice(device_pubkey.as_bytes());
-    signed_payload.extend_from_slice(&req.timestamp.to_le_bytes());
+    let signed_payload = device_certificate_message(device_pubkey.as_bytes(), Some(req.timestamp));
 
     if verify_ed25519(root_pubkey_arr, &signed_payload, cert_sig.as_bytes()).is_err() {
         // Return 401 with generic message — must be indistinguishable from
@@ -111,6 +121,7 @@ fn validate_login_device(
         (status = 201, description = "Login successful", body = LoginResponse),
         (status = 400, description = "Validation error or replay detected"),
         (status = 401, description = "Invalid credentials"),
+        (status = 403, description = "Account is suspended, locked or deleted"),
         (status = 409, description = "Device key already registered"),
         (status = 422, description = "Maximum device limit reached"),
         (status = 500, description = "Internal server error")
@@ -118,17 +129,27 @@ fn validate_login_device(
 )]
 pub async fn login(
     Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth_config: Option<Extension<AuthConfig>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    headers: HeaderMap,
     Json(req): Json<LoginRequest>,
 ) -> impl IntoResponse {
+    let declared_caps = match super::declared_caps(&headers) {
+        Ok(caps) => caps,
+        Err(resp) => return resp,
+    };
+
     // Validate timestamp
+    let auth_config = auth_config.map(|Extension(c)| c).unwrap_or_default();
     let now = chrono::Utc::now().timestamp();
-    if super::timestamp_is_stale(now, req.timestamp) {
+    if auth_config.timestamp_is_stale(now, req.timestamp) {
         return super::bad_request("Timestamp out of range");
     }
 
     // Validate username
     let username = req.username.trim();
-    if let Err(e) = validate_username(username) {
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    if let Err(e) = usernames.check_handle(username) {
         return super::bad_request(&e.to_string());
     }
 
@@ -156,9 +177,14 @@ pub async fn login(
         Err(resp) => return resp,
     };
 
+    // Checked after the certificate so the status is only revealed to the owner
+    if let Err(resp) = super::require_active_account(repo.as_ref(), account.id).await {
+        return resp;
+    }
+
     // Record nonce to prevent replay within the timestamp window.
-    // Nonce cleanup is handled by the background sweep in main.rs
-    // (spawn_nonce_cleanup), using MAX_TIMESTAMP_SKEW as the TTL.
+    // Nonce cleanup is handled by the retention worker
+    // (retention::spawn_retention_worker), using `auth.nonce_ttl_secs` as the TTL.
     let nonce_hash: [u8; 32] = Sha256::digest(validated.cert.as_bytes()).into();
     if let Err(e) = repo.check_and_record_nonce(&nonce_hash).await {
         return match e {
@@ -167,6 +193,10 @@ pub async fn login(
                 tracing::error!("Nonce check failed: {db_err}");
                 super::internal_error()
             }
+            NonceRepoError::UnknownChallenge => {
+                tracing::error!("Unexpected repo error during nonce check: {e}");
+                super::internal_error()
+            }
         };
     }
 
@@ -182,10 +212,23 @@ pub async fn login(
             &req.device.pubkey,
             validated.device_name.as_str(),
             validated.cert.as_bytes(),
+            Some(req.timestamp),
+            None,
         )
         .await
     {
         Ok(_created) => {
+            if !declared_caps.is_empty() {
+                if let Err(e) = repo
+                    .record_device_caps(&validated.device_kid, &declared_caps.tokens())
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to record capabilities for device {}: {e}",
+                        validated.device_kid
+                    );
+                }
+            }
             tracing::info!(
                 account_id = %account.id,
                 device_kid = %validated.device_kid,
@@ -218,7 +261,6 @@ pub async fn login(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::identity::http::auth::MAX_TIMESTAMP_SKEW;
     use crate::identity::repo::{
         mock::MockIdentityRepo, AccountRecord, DeviceKeyRepoError, NonceRepoError,
     };
@@ -552,7 +594,8 @@ mod tests {
         let repo = MockIdentityRepo::new();
         let app = test_login_router(repo);
 
-        let expired = chrono::Utc::now().timestamp() - (MAX_TIMESTAMP_SKEW + 1);
+        let skew = i64::try_from(AuthConfig::default().max_clock_skew_secs).expect("skew fits");
+        let expired = chrono::Utc::now().timestamp() - (skew + 1);
         let body = serde_json::json!({
             "username": "alice",
             "timestamp": expired,
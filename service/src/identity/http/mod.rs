@@ -1,10 +1,30 @@
 // lint-patterns:allow-no-utoipa — tracked by #906
 //! HTTP handlers for identity system
 
+pub mod account_status;
+pub mod aliases;
 pub mod auth;
 pub mod backup;
+pub mod body_hash;
+pub mod challenge;
+pub mod crl;
+pub mod deletion;
+pub mod device_usage;
 pub mod devices;
 pub mod login;
+pub mod profile;
+pub mod reserved_usernames;
+pub mod security;
+pub mod sessions;
+pub mod sigchain;
+pub mod signature_guard;
+pub mod signup_invites;
+pub mod step_up;
+pub mod token_auth;
+pub mod token_quota;
+pub mod tokens;
+pub mod username;
+pub mod verify;
 
 use std::sync::Arc;
 
@@ -12,21 +32,25 @@ use axum::{
     extract::{Extension, Query},
     http::StatusCode,
     response::IntoResponse,
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post, put, MethodRouter},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use super::service::{validate_username, IdentityService, RootPubkey, SignupError, SignupRequest};
+use super::capabilities::{ClientCaps, UpgradeRequired, CLIENT_CAPS_HEADER};
+use super::service::{IdentityService, RootPubkey, SignupError, SignupRequest};
+use super::username::UsernamePolicy;
 // Re-export shared error helpers so submodules and external callers can use them.
 use crate::config::RateLimitConfig;
+use crate::events::{DomainEvent, EventPublisher};
 use crate::http::rate_limit::make_governor_layer;
 pub use crate::http::{bad_request, internal_error, not_found, unauthorized, ErrorResponse, Path};
-pub(crate) use crate::http::{conflict, forbidden};
+pub(crate) use crate::http::{conflict, forbidden, forbidden_with_code};
 use crate::identity::http::auth::AuthenticatedDevice;
 use crate::identity::repo::{AccountRecord, AccountRepoError, DeviceKeyRepoError, IdentityRepo};
+use crate::identity::status::AccountStatus;
 use tc_crypto::Kid;
 
 /// Signup response
@@ -45,7 +69,10 @@ pub struct SignupResponse {
 pub struct AccountLookupResponse {
     #[schema(value_type = String, format = "uuid")]
     pub id: Uuid,
+    /// Canonical username, even when the lookup matched an alias
     pub username: String,
+    /// The alias that matched, if the lookup was not by username
+    pub alias: Option<String>,
 }
 
 /// Query parameters for the account lookup endpoint.
@@ -57,9 +84,11 @@ pub struct AccountLookupQuery {
 /// Create identity router.
 ///
 /// Unauthenticated endpoints (`/auth/signup`, `/auth/login`,
-/// `/auth/backup/{username}`) get individual rate-limit layers based on
-/// `rate_limit_config`. Authenticated device-management and lookup routes are
-/// not rate-limited here.
+/// `/auth/challenge`, `/auth/backup/{username}`,
+/// `/auth/usernames/{name}/availability`) get individual rate-limit
+/// layers based on `rate_limit_config`. Authenticated device-management and
+/// lookup routes are not rate-limited here.
+#[allow(clippy::too_many_lines)]
 pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
     // ── Unauthenticated routes — each gets its own governor layer ──────────
     //
@@ -90,8 +119,23 @@ pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
         }
     };
 
+    // Challenges are fetched right before a signed request, so they share
+    // the login budget rather than adding another knob
+    let challenge_router = {
+        let r = Router::new().route("/auth/challenge", get(challenge::get_challenge));
+        if let Some(layer) =
+            make_governor_layer(rate_limit_config.login_per_minute, rate_limit_config)
+        {
+            r.layer(layer)
+        } else {
+            r
+        }
+    };
+
     let backup_router = {
-        let r = Router::new().route("/auth/backup/{username}", get(backup::get_backup));
+        let r = Router::new()
+            .route("/auth/backup/{username}", get(backup::get_backup))
+            .route("/auth/backups/{root_kid}", get(backup::get_recovery_backup));
         if let Some(layer) =
             make_governor_layer(rate_limit_config.backup_per_minute, rate_limit_config)
         {
@@ -101,6 +145,43 @@ pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
         }
     };
 
+    // Undeletion is the login of a deactivated account, so it shares the
+    // login budget
+    let undelete_router = {
+        let r = Router::new().route("/auth/accounts/undelete", post(deletion::undelete_account));
+        if let Some(layer) =
+            make_governor_layer(rate_limit_config.login_per_minute, rate_limit_config)
+        {
+            r.layer(layer)
+        } else {
+            r
+        }
+    };
+
+    let username_check_router = {
+        let r = Router::new().route(
+            "/auth/usernames/{name}/availability",
+            get(username::check_username_availability),
+        );
+        if let Some(layer) = make_governor_layer(
+            rate_limit_config.username_check_per_minute,
+            rate_limit_config,
+        ) {
+            r.layer(layer)
+        } else {
+            r
+        }
+    };
+
+    // Authenticated routes that send an SMS share one per-IP limit.
+    let sms_limited = |route: MethodRouter| match make_governor_layer(
+        rate_limit_config.sms_per_minute,
+        rate_limit_config,
+    ) {
+        Some(layer) => route.layer(layer),
+        None => route,
+    };
+
     // ── Authenticated routes — no rate limiting ────────────────────────────
     let authenticated_router = Router::new()
         .route(
@@ -111,24 +192,112 @@ pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
             "/auth/devices/{kid}",
             delete(devices::revoke_device).patch(devices::rename_device),
         )
-        .route("/accounts/lookup", get(account_lookup));
+        .route(
+            "/auth/devices/{kid}/delegation",
+            get(devices::get_device_delegation),
+        )
+        .route("/auth/devices/{kid}/rotate", post(devices::rotate_device))
+        .route(
+            "/auth/tokens",
+            get(tokens::list_tokens).post(tokens::create_token),
+        )
+        .route("/auth/tokens/usage", get(tokens::top_token_consumers))
+        .route("/auth/tokens/{id}", delete(tokens::revoke_token))
+        .route(
+            "/auth/signup-invites",
+            get(signup_invites::list_signup_invites).post(signup_invites::create_signup_invite),
+        )
+        .route(
+            "/auth/aliases",
+            get(aliases::list_aliases).post(aliases::create_alias),
+        )
+        .route("/auth/aliases/{alias}", delete(aliases::delete_alias))
+        .route(
+            "/auth/reserved-usernames",
+            get(reserved_usernames::list_reserved_usernames)
+                .post(reserved_usernames::reserve_username),
+        )
+        .route(
+            "/auth/reserved-usernames/{name}",
+            delete(reserved_usernames::release_username),
+        )
+        .route(
+            "/auth/released-usernames",
+            get(reserved_usernames::list_released_usernames),
+        )
+        .route(
+            "/auth/released-usernames/{name}",
+            delete(reserved_usernames::lift_username_quarantine),
+        )
+        .route(
+            "/auth/sessions",
+            post(sessions::create_session).delete(sessions::revoke_sessions),
+        )
+        .route(
+            "/auth/backups/recovery-code",
+            put(backup::set_recovery_code),
+        )
+        .route("/auth/security/checkup", get(security::security_checkup))
+        .route(
+            "/auth/step-up/phone",
+            sms_limited(post(step_up::enroll_step_up_phone))
+                .get(step_up::get_step_up_phone)
+                .delete(step_up::remove_step_up_phone),
+        )
+        .route(
+            "/auth/step-up/challenges",
+            sms_limited(post(step_up::create_step_up_challenge)),
+        )
+        .route(
+            "/auth/step-up/challenges/{challenge_id}/confirm",
+            post(step_up::confirm_step_up_challenge),
+        )
+        .route(
+            "/auth/account/status",
+            post(account_status::change_own_status),
+        )
+        .route("/auth/accounts/me", delete(deletion::delete_account))
+        .route(
+            "/auth/accounts/me/username",
+            patch(username::change_username),
+        )
+        .route(
+            "/auth/accounts/{account_id}/sigchain",
+            get(sigchain::list_sigchain),
+        )
+        .route(
+            "/auth/accounts/{account_id}/sigchain/head",
+            get(sigchain::get_sigchain_head),
+        )
+        .route("/accounts/lookup", get(account_lookup))
+        .route("/accounts/{account_id}", get(profile::get_account_profile))
+        .route("/accounts/{account_id}/crl", get(crl::get_account_crl))
+        .route(
+            "/accounts/{account_id}/status",
+            put(account_status::change_account_status_as_admin),
+        );
 
     signup_router
         .merge(login_router)
+        .merge(challenge_router)
         .merge(backup_router)
+        .merge(undelete_router)
+        .merge(username_check_router)
         .merge(authenticated_router)
 }
 
-/// Look up an account by username.
+/// Look up an account by username or alias.
 ///
-/// Returns `{ id, username }` so the caller can use the UUID for trust actions.
+/// Returns `{ id, username, alias }` so the caller can use the UUID for trust
+/// actions. `username` is always canonical; `alias` is set when the handle
+/// was an alias, so clients can switch to the canonical name.
 /// Requires authentication — callers must have a valid device session.
 #[utoipa::path(
     get,
     path = "/accounts/lookup",
     tag = "Identity",
     params(
-        ("username" = String, Query, description = "Username to look up")
+        ("username" = String, Query, description = "Username or alias to look up")
     ),
     responses(
         (status = 200, description = "Account found", body = AccountLookupResponse),
@@ -139,6 +308,7 @@ pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
 )]
 async fn account_lookup(
     Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
     Query(params): Query<AccountLookupQuery>,
     _auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
@@ -146,16 +316,18 @@ async fn account_lookup(
     if username.is_empty() {
         return bad_request("username is required");
     }
-    if let Err(e) = validate_username(&username) {
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    if let Err(e) = usernames.check_handle(&username) {
         return bad_request(&e.to_string());
     }
 
-    match repo.get_account_by_username(&username).await {
-        Ok(account) => (
+    match repo.resolve_account_handle(&username).await {
+        Ok(resolved) => (
             StatusCode::OK,
             Json(AccountLookupResponse {
-                id: account.id,
-                username: account.username,
+                id: resolved.account.id,
+                username: resolved.account.username,
+                alias: resolved.alias,
             }),
         )
             .into_response(),
@@ -167,16 +339,6 @@ async fn account_lookup(
     }
 }
 
-// ── Shared timestamp helpers ─────────────────────────────────────────────────
-
-/// Returns `true` when `timestamp` differs from `now` by more than [`auth::MAX_TIMESTAMP_SKEW`].
-///
-/// Uses [`i64::abs_diff`] to compute the absolute difference without overflow on
-/// extreme values (`i64::MIN`, `i64::MAX`).
-pub(crate) const fn timestamp_is_stale(now: i64, timestamp: i64) -> bool {
-    now.abs_diff(timestamp) > auth::MAX_TIMESTAMP_SKEW as u64
-}
-
 // ── Shared error response helpers ───────────────────────────────────────────
 
 /// Map a [`DeviceKeyRepoError`] to an HTTP response.
@@ -220,6 +382,65 @@ pub(crate) fn decode_account_root_pubkey(
         })
 }
 
+/// Capabilities declared in the `TC-Client-Caps` header; empty if absent.
+///
+/// A malformed header is a 400 rather than being ignored, so a client bug
+/// cannot silently leave a device at the older version.
+#[allow(clippy::result_large_err)]
+pub(crate) fn declared_caps(
+    headers: &axum::http::HeaderMap,
+) -> Result<ClientCaps, axum::response::Response> {
+    let Some(value) = headers.get(CLIENT_CAPS_HEADER) else {
+        return Ok(ClientCaps::default());
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| bad_request("Invalid TC-Client-Caps header"))?;
+    ClientCaps::parse(value)
+        .map_err(|e| bad_request(&format!("Invalid TC-Client-Caps header: {e}")))
+}
+
+/// 403 with `SIGNATURE_UPGRADE_REQUIRED` or `CERTIFICATE_UPGRADE_REQUIRED`.
+pub(crate) fn upgrade_required_response(e: UpgradeRequired) -> axum::response::Response {
+    forbidden_with_code(e.format.upgrade_code(), &e.to_string())
+}
+
+/// Refuse requests from accounts that are not `active`.
+///
+/// Called by every authentication path (signed requests, API tokens, login)
+/// so a suspended, locked, deactivated or deleted account is blocked
+/// everywhere at once. The body carries `ACCOUNT_SUSPENDED`, `ACCOUNT_LOCKED`,
+/// `ACCOUNT_DEACTIVATED` or `ACCOUNT_DELETED`.
+#[allow(clippy::result_large_err)]
+pub(crate) async fn require_active_account(
+    repo: &dyn IdentityRepo,
+    account_id: Uuid,
+) -> Result<(), axum::response::Response> {
+    match repo.get_account_status(account_id).await {
+        Ok(AccountStatus::Active) => Ok(()),
+        Ok(AccountStatus::Suspended) => Err(forbidden_with_code(
+            "ACCOUNT_SUSPENDED",
+            "Account has been suspended",
+        )),
+        Ok(AccountStatus::Locked) => Err(forbidden_with_code(
+            "ACCOUNT_LOCKED",
+            "Account is locked by its owner",
+        )),
+        Ok(AccountStatus::Deleted) => Err(forbidden_with_code(
+            "ACCOUNT_DELETED",
+            "Account has been deleted",
+        )),
+        Ok(AccountStatus::Deactivated) => Err(forbidden_with_code(
+            "ACCOUNT_DEACTIVATED",
+            "Account is scheduled for deletion",
+        )),
+        Err(e) => {
+            tracing::error!(%account_id, "Account status lookup failed: {e}");
+            Err(internal_error())
+        }
+    }
+}
+
 /// Handle signup request — delegates validation and persistence to [`IdentityService`].
 #[utoipa::path(
     post,
@@ -229,6 +450,7 @@ pub(crate) fn decode_account_root_pubkey(
     responses(
         (status = 201, description = "Account created", body = SignupResponse),
         (status = 400, description = "Validation error"),
+        (status = 403, description = "Invite code missing or invalid on an invite-only instance"),
         (status = 409, description = "Username or key already registered"),
         (status = 422, description = "Maximum device limit reached"),
         (status = 500, description = "Internal server error")
@@ -236,6 +458,7 @@ pub(crate) fn decode_account_root_pubkey(
 )]
 async fn signup(
     Extension(service): Extension<Arc<dyn IdentityService>>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
     Json(req): Json<SignupRequest>,
 ) -> impl IntoResponse {
     match service.signup(&req).await {
@@ -245,6 +468,13 @@ async fn signup(
                 account_id = %result.account_id,
                 "User signed up"
             );
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::AccountCreated {
+                    account_id: result.account_id,
+                    username: req.username.clone(),
+                    root_kid: result.root_kid.clone(),
+                });
+            }
             (
                 StatusCode::CREATED,
                 Json(SignupResponse {
@@ -271,6 +501,7 @@ fn signup_error_response(e: SignupError) -> axum::response::Response {
             }),
         )
             .into_response(),
+        SignupError::InviteRequired | SignupError::InvalidInvite => forbidden(&e.to_string()),
         SignupError::Internal(ref msg) => {
             tracing::error!("Signup returned internal error: {msg}");
             internal_error()
@@ -0,0 +1,201 @@
+//! Account profile, redacted by who is asking; see [`crate::visibility`].
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use tc_crypto::Kid;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::Path;
+use crate::config::AccountsConfig;
+use crate::identity::repo::{AccountRepoError, DeviceKeyRecord, IdentityRepo};
+use crate::visibility::{Redact, Viewer};
+
+/// An account as others see it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountProfileResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub username: String,
+    #[schema(value_type = String)]
+    pub root_kid: Kid,
+    /// The account's devices, including revoked ones; signed-in viewers only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devices: Option<Vec<ProfileDevice>>,
+}
+
+/// A device in an account profile
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProfileDevice {
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    pub created_at: String,
+    pub revoked: bool,
+    /// Moderators and the owner only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    /// Moderators and the owner only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+    /// The owner only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<String>,
+}
+
+impl From<DeviceKeyRecord> for ProfileDevice {
+    fn from(record: DeviceKeyRecord) -> Self {
+        Self {
+            device_kid: record.device_kid,
+            created_at: record.created_at.to_rfc3339(),
+            revoked: record.revoked_at.is_some(),
+            device_name: Some(record.device_name),
+            revoked_at: record.revoked_at.map(|t| t.to_rfc3339()),
+            last_used_at: record.last_used_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+impl Redact for ProfileDevice {
+    fn redact(self, viewer: Viewer) -> Self {
+        Self {
+            device_name: viewer.reveal(Viewer::Moderator, self.device_name),
+            revoked_at: viewer.reveal(Viewer::Moderator, self.revoked_at),
+            last_used_at: viewer.reveal(Viewer::Owner, self.last_used_at),
+            ..self
+        }
+    }
+}
+
+impl Redact for AccountProfileResponse {
+    fn redact(self, viewer: Viewer) -> Self {
+        Self {
+            devices: viewer
+                .reveal(Viewer::Member, self.devices)
+                .map(|devices| devices.redact(viewer)),
+            ..self
+        }
+    }
+}
+
+/// GET `/accounts/{account_id}` — an account's profile
+///
+/// Open to anyone. A signed request reveals more: other members see the
+/// account's devices, moderators also see device names, and the owner sees
+/// everything.
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}",
+    tag = "Identity",
+    params(("account_id" = Uuid, Path, description = "Account to show")),
+    responses(
+        (status = 200, description = "Account profile", body = AccountProfileResponse),
+        (status = 401, description = "Signed request failed verification"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_account_profile(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(account_id): Path<Uuid>,
+    auth: Option<AuthenticatedDevice>,
+) -> Response {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let account = match repo.get_account_by_id(account_id).await {
+        Ok(a) => a,
+        Err(AccountRepoError::NotFound) => return super::not_found("Account not found"),
+        Err(e) => {
+            tracing::error!("Failed to load account profile: {e}");
+            return super::internal_error();
+        }
+    };
+    let viewer = Viewer::resolve(&accounts_config, auth.map(|a| a.account_id), account_id);
+
+    let devices = if viewer >= Viewer::Member {
+        match repo.list_device_keys_by_account(account_id).await {
+            Ok(records) => Some(records.into_iter().map(ProfileDevice::from).collect()),
+            Err(e) => {
+                tracing::error!("Failed to list devices for profile: {e}");
+                return super::internal_error();
+            }
+        }
+    } else {
+        None
+    };
+
+    let profile = AccountProfileResponse {
+        account_id: account.id,
+        username: account.username,
+        root_kid: account.root_kid,
+        devices,
+    };
+    (StatusCode::OK, Json(profile.redact(viewer))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn device() -> ProfileDevice {
+        let now = Utc::now();
+        ProfileDevice::from(DeviceKeyRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            device_kid: Kid::derive(&[7u8; 32]),
+            device_pubkey: String::new(),
+            device_name: "Work laptop".to_string(),
+            certificate: Vec::new(),
+            last_used_at: Some(now),
+            revoked_at: Some(now),
+            revoked_by_kid: None,
+            revocation_envelope: None,
+            certificate_version: Some(2),
+            certificate_timestamp: None,
+            rotated_from_kid: None,
+            rotation_signature: None,
+            client_caps: Vec::new(),
+            created_at: now,
+        })
+    }
+
+    fn profile() -> AccountProfileResponse {
+        AccountProfileResponse {
+            account_id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            root_kid: Kid::derive(&[1u8; 32]),
+            devices: Some(vec![device()]),
+        }
+    }
+
+    #[test]
+    fn device_fields_follow_viewer() {
+        let json = |viewer| serde_json::to_value(profile().redact(viewer)).expect("json");
+
+        let anonymous = json(Viewer::Anonymous);
+        assert_eq!(anonymous["username"], "alice");
+        assert!(anonymous.get("devices").is_none());
+
+        let member = json(Viewer::Member);
+        let device = &member["devices"][0];
+        assert_eq!(device["revoked"], true);
+        assert!(device.get("device_name").is_none());
+        assert!(device.get("revoked_at").is_none());
+        assert!(device.get("last_used_at").is_none());
+
+        let moderator = json(Viewer::Moderator);
+        assert_eq!(moderator["devices"][0]["device_name"], "Work laptop");
+        assert!(moderator["devices"][0].get("last_used_at").is_none());
+
+        let owner = json(Viewer::Owner);
+        assert!(owner["devices"][0]["last_used_at"].is_string());
+    }
+}
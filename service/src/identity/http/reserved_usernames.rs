@@ -0,0 +1,489 @@
+//! Reserved username HTTP handlers
+//!
+//! Account admins list, add and remove the names new accounts and aliases may
+//! not take. Changes apply on this server immediately and on the others within
+//! a minute; see [`crate::identity::username`]. Existing accounts are not
+//! affected.
+//!
+//! Admins can also see which given-up names are still quarantined and lift a
+//! quarantine early, e.g. to let a renamed organization reclaim its old name
+//! under a new account.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::Path;
+use crate::config::AccountsConfig;
+use crate::identity::repo::{
+    IdentityRepo, ReleasedHandleRecord, ReservedUsernameRecord, ReservedUsernameRepoError,
+};
+use crate::identity::username::{UsernamePolicy, USERNAME_LENGTH_LIMIT};
+
+/// Maximum length of a reservation reason (bytes).
+const MAX_REASON_LENGTH: usize = 500;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReserveUsernameRequest {
+    /// Name to reserve; matched case-insensitively
+    pub name: String,
+    /// Why the name is reserved, for other admins
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReservedUsernameInfo {
+    pub name: String,
+    pub reason: Option<String>,
+    /// Admin who reserved it; null for the built-in names
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub created_by: Option<Uuid>,
+    pub created_at: String,
+}
+
+impl From<ReservedUsernameRecord> for ReservedUsernameInfo {
+    fn from(record: ReservedUsernameRecord) -> Self {
+        Self {
+            name: record.name,
+            reason: record.reason,
+            created_by: record.created_by,
+            created_at: record.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReservedUsernamesResponse {
+    pub names: Vec<ReservedUsernameInfo>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReleasedUsernameInfo {
+    pub name: String,
+    /// Account that gave the name up
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub released_at: String,
+    /// When other accounts may claim the name
+    pub available_at: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReleasedUsernamesResponse {
+    pub names: Vec<ReleasedUsernameInfo>,
+}
+
+/// GET /auth/reserved-usernames — list reserved names (account admins only)
+#[utoipa::path(
+    get,
+    path = "/auth/reserved-usernames",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Reserved names, alphabetically", body = ReservedUsernamesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_reserved_usernames(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = require_admin(&accounts_config, auth.account_id) {
+        return resp;
+    }
+    match repo.list_reserved_usernames().await {
+        Ok(records) => (
+            StatusCode::OK,
+            Json(ReservedUsernamesResponse {
+                names: records
+                    .into_iter()
+                    .map(ReservedUsernameInfo::from)
+                    .collect(),
+            }),
+        )
+            .into_response(),
+        Err(e) => reserved_username_error_response(&e),
+    }
+}
+
+/// POST /auth/reserved-usernames — reserve a name (account admins only)
+#[utoipa::path(
+    post,
+    path = "/auth/reserved-usernames",
+    tag = "Identity",
+    request_body = ReserveUsernameRequest,
+    responses(
+        (status = 201, description = "Name reserved", body = ReservedUsernameInfo),
+        (status = 400, description = "Invalid name or reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 409, description = "Name already reserved"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn reserve_username(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let body: ReserveUsernameRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let name = match normalize_name(&body.name) {
+        Ok(n) => n,
+        Err(resp) => return resp,
+    };
+    let reason = body
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    if reason.is_some_and(|r| r.len() > MAX_REASON_LENGTH) {
+        return super::bad_request(&format!("reason must be at most {MAX_REASON_LENGTH} bytes"));
+    }
+    if let Err(resp) = require_admin(&accounts_config, auth.account_id) {
+        return resp;
+    }
+
+    match repo
+        .create_reserved_username(&name, reason, auth.account_id)
+        .await
+    {
+        Ok(record) => {
+            tracing::info!(name = %record.name, "Username reserved");
+            reload(repo.as_ref(), usernames).await;
+            (
+                StatusCode::CREATED,
+                Json(ReservedUsernameInfo::from(record)),
+            )
+                .into_response()
+        }
+        Err(e) => reserved_username_error_response(&e),
+    }
+}
+
+/// DELETE /auth/reserved-usernames/:name — release a reserved name (account admins only)
+#[utoipa::path(
+    delete,
+    path = "/auth/reserved-usernames/{name}",
+    tag = "Identity",
+    params(("name" = String, Path, description = "Reserved name to release")),
+    responses(
+        (status = 204, description = "Name released"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 404, description = "Name is not reserved"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn release_username(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    Path(name): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = require_admin(&accounts_config, auth.account_id) {
+        return resp;
+    }
+    match repo
+        .delete_reserved_username(&name.trim().to_lowercase())
+        .await
+    {
+        Ok(()) => {
+            tracing::info!(name = %name, "Reserved username released");
+            reload(repo.as_ref(), usernames).await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => reserved_username_error_response(&e),
+    }
+}
+
+/// GET /auth/released-usernames — list quarantined names (account admins only)
+#[utoipa::path(
+    get,
+    path = "/auth/released-usernames",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Names still cooling off, most recently released first", body = ReleasedUsernamesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_released_usernames(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    if let Err(resp) = require_admin(&accounts_config, auth.account_id) {
+        return resp;
+    }
+    let cutoff = usernames.release_cutoff();
+    match repo.list_released_handles(cutoff).await {
+        Ok(records) => {
+            let cooldown = chrono::Utc::now() - cutoff;
+            let names = records
+                .into_iter()
+                .map(|r: ReleasedHandleRecord| ReleasedUsernameInfo {
+                    available_at: (r.released_at + cooldown).to_rfc3339(),
+                    name: r.handle,
+                    account_id: r.account_id,
+                    released_at: r.released_at.to_rfc3339(),
+                })
+                .collect();
+            (StatusCode::OK, Json(ReleasedUsernamesResponse { names })).into_response()
+        }
+        Err(e) => reserved_username_error_response(&e),
+    }
+}
+
+/// DELETE /auth/released-usernames/:name — end a name's quarantine (account admins only)
+#[utoipa::path(
+    delete,
+    path = "/auth/released-usernames/{name}",
+    tag = "Identity",
+    params(("name" = String, Path, description = "Released name to make claimable")),
+    responses(
+        (status = 204, description = "Name is claimable again"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 404, description = "Name was never released"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn lift_username_quarantine(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(name): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = require_admin(&accounts_config, auth.account_id) {
+        return resp;
+    }
+    match repo.delete_released_handle(name.trim()).await {
+        Ok(()) => {
+            tracing::info!(name = %name, "Username quarantine lifted");
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(ReservedUsernameRepoError::NotFound) => super::not_found("Name was never released"),
+        Err(e) => reserved_username_error_response(&e),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn normalize_name(raw: &str) -> Result<String, Response> {
+    let name = raw.trim().to_lowercase();
+    if name.is_empty() {
+        return Err(super::bad_request("name cannot be empty"));
+    }
+    if name.chars().count() > USERNAME_LENGTH_LIMIT {
+        return Err(super::bad_request(&format!(
+            "name must be at most {USERNAME_LENGTH_LIMIT} characters"
+        )));
+    }
+    if name.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        return Err(super::bad_request(
+            "name cannot contain spaces or control characters",
+        ));
+    }
+    Ok(name)
+}
+
+#[allow(clippy::result_large_err)]
+fn require_admin(accounts_config: &AccountsConfig, account_id: Uuid) -> Result<(), Response> {
+    if accounts_config.is_admin(account_id) {
+        Ok(())
+    } else {
+        Err(super::forbidden(
+            "Only account admins can manage reserved and released usernames",
+        ))
+    }
+}
+
+async fn reload(repo: &dyn IdentityRepo, usernames: Option<Extension<Arc<UsernamePolicy>>>) {
+    if let Some(Extension(usernames)) = usernames {
+        if let Err(e) = usernames.reload_reserved(repo).await {
+            tracing::warn!("Failed to reload reserved usernames: {e}");
+        }
+    }
+}
+
+fn reserved_username_error_response(e: &ReservedUsernameRepoError) -> Response {
+    match e {
+        ReservedUsernameRepoError::Duplicate => super::conflict("Name already reserved"),
+        ReservedUsernameRepoError::NotFound => super::not_found("Name is not reserved"),
+        ReservedUsernameRepoError::Database(db_err) => {
+            tracing::error!("Reserved username operation failed: {db_err}");
+            super::internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use crate::identity::username::UsernameError;
+    use axum::body::Bytes;
+    use tc_crypto::Kid;
+
+    const ALICE: Uuid = Uuid::from_u128(1);
+
+    /// `alice` is the admin; anyone else is a fresh account.
+    fn caller(username: &str) -> AuthenticatedDevice {
+        let account_id = if username == "alice" {
+            ALICE
+        } else {
+            Uuid::new_v4()
+        };
+        AuthenticatedDevice::for_test(account_id, Kid::derive(&[0xAAu8; 32]), Bytes::new())
+    }
+
+    fn config() -> Option<Extension<AccountsConfig>> {
+        Some(Extension(AccountsConfig {
+            admin_account_ids: vec![ALICE],
+        }))
+    }
+
+    async fn reserve(
+        repo: &Arc<MockIdentityRepo>,
+        policy: &Arc<UsernamePolicy>,
+        username: &str,
+        body: &serde_json::Value,
+    ) -> StatusCode {
+        let auth = caller(username);
+        reserve_username(
+            Extension(repo.clone() as Arc<dyn IdentityRepo>),
+            config(),
+            Some(Extension(policy.clone())),
+            AuthenticatedDevice::for_test(
+                auth.account_id,
+                Kid::derive(&[0xAAu8; 32]),
+                Bytes::from(body.to_string()),
+            ),
+        )
+        .await
+        .into_response()
+        .status()
+    }
+
+    #[tokio::test]
+    async fn reserving_applies_to_validation_immediately() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let policy = Arc::new(UsernamePolicy::default());
+        assert!(policy.validate("ceo").is_ok());
+
+        let body = serde_json::json!({ "name": " CEO ", "reason": "impersonation" });
+        assert_eq!(
+            reserve(&repo, &policy, "alice", &body).await,
+            StatusCode::CREATED
+        );
+        assert_eq!(policy.validate("Ceo"), Err(UsernameError::Reserved));
+        assert_eq!(
+            reserve(&repo, &policy, "alice", &body).await,
+            StatusCode::CONFLICT
+        );
+
+        let response = release_username(
+            Extension(repo.clone() as Arc<dyn IdentityRepo>),
+            config(),
+            Some(Extension(policy.clone())),
+            Path("ceo".to_string()),
+            caller("alice"),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(policy.validate("ceo").is_ok());
+    }
+
+    #[tokio::test]
+    async fn lifting_a_quarantine_makes_the_name_claimable() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let policy = Arc::new(UsernamePolicy::default());
+        repo.released_handles
+            .lock()
+            .expect("lock")
+            .push(ReleasedHandleRecord {
+                handle: "bob".to_string(),
+                account_id: Uuid::new_v4(),
+                released_at: chrono::Utc::now(),
+            });
+        let cooling_off = || policy.is_cooling_off(repo.as_ref(), "bob", None);
+        assert!(cooling_off().await.expect("check"));
+
+        let response = list_released_usernames(
+            Extension(repo.clone() as Arc<dyn IdentityRepo>),
+            config(),
+            Some(Extension(policy.clone())),
+            caller("alice"),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let lift = |username: &str| {
+            lift_username_quarantine(
+                Extension(repo.clone() as Arc<dyn IdentityRepo>),
+                config(),
+                Path("Bob".to_string()),
+                caller(username),
+            )
+        };
+        assert_eq!(
+            lift("mallory").await.into_response().status(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            lift("alice").await.into_response().status(),
+            StatusCode::NO_CONTENT
+        );
+        assert!(!cooling_off().await.expect("check"));
+        assert_eq!(
+            lift("alice").await.into_response().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn only_admins_may_reserve_valid_names() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let policy = Arc::new(UsernamePolicy::default());
+        let body = serde_json::json!({ "name": "ceo" });
+        assert_eq!(
+            reserve(&repo, &policy, "mallory", &body).await,
+            StatusCode::FORBIDDEN
+        );
+        let body = serde_json::json!({ "name": "two words" });
+        assert_eq!(
+            reserve(&repo, &policy, "alice", &body).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
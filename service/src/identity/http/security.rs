@@ -0,0 +1,422 @@
+//! Account security checkup HTTP handler
+//!
+//! `GET /auth/security/checkup` summarizes an account's security posture for a
+//! client "security center" screen: device inventory, backup status, API
+//! tokens, recent device changes, and recommendations. Everything is derived
+//! from existing identity records; nothing new is stored.
+//!
+//! Requires a device signature — API tokens cannot read the checkup, since it
+//! reveals which devices and tokens could be attacked.
+
+use std::sync::Arc;
+
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::auth::AuthenticatedDevice;
+use crate::identity::repo::{
+    ApiTokenRecord, BackupRecord, BackupRepoError, DeviceKeyRecord, IdentityRepo,
+    MAX_DEVICES_PER_ACCOUNT,
+};
+use tc_crypto::Kid;
+
+/// Active devices unused for this long are flagged as stale.
+pub const STALE_DEVICE_DAYS: i64 = 90;
+
+/// Device additions and revocations within this window are listed as recent.
+pub const RECENT_EVENT_DAYS: i64 = 14;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DevicePosture {
+    pub active: usize,
+    pub revoked: usize,
+    /// Maximum active devices per account
+    pub limit: i64,
+    /// Age in days of the oldest active device
+    pub oldest_active_age_days: Option<i64>,
+    /// Active devices not used in the last `STALE_DEVICE_DAYS` days
+    #[schema(value_type = Vec<String>)]
+    pub stale: Vec<Kid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackupPosture {
+    /// Whether an encrypted root-key backup is stored for recovery
+    pub present: bool,
+    pub created_at: Option<String>,
+    pub age_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiTokenPosture {
+    pub active: usize,
+    /// Active tokens with no expiry
+    pub without_expiry: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityEventKind {
+    DeviceAdded,
+    DeviceRevoked,
+}
+
+/// A recent device change the account owner should recognize.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SecurityEvent {
+    pub kind: SecurityEventKind,
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    pub device_name: String,
+    pub at: String,
+    /// Device that performed a revocation, if recorded
+    #[schema(value_type = Option<String>)]
+    pub by_kid: Option<Kid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationCode {
+    NoBackup,
+    StaleDevices,
+    DeviceLimitNear,
+    NonExpiringTokens,
+    ReviewRecentChanges,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Recommendation {
+    pub code: RecommendationCode,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SecurityCheckupResponse {
+    pub devices: DevicePosture,
+    pub backup: BackupPosture,
+    pub api_tokens: ApiTokenPosture,
+    /// Device additions and revocations in the last `RECENT_EVENT_DAYS` days,
+    /// newest first
+    pub recent_events: Vec<SecurityEvent>,
+    pub recommendations: Vec<Recommendation>,
+}
+
+/// Assemble the checkup from an account's identity records.
+#[must_use]
+pub fn build_checkup(
+    now: DateTime<Utc>,
+    devices: &[DeviceKeyRecord],
+    backup: Option<&BackupRecord>,
+    tokens: &[ApiTokenRecord],
+) -> SecurityCheckupResponse {
+    let stale_cutoff = now - Duration::days(STALE_DEVICE_DAYS);
+
+    let active: Vec<&DeviceKeyRecord> = devices.iter().filter(|d| d.revoked_at.is_none()).collect();
+    let stale: Vec<Kid> = active
+        .iter()
+        .filter(|d| d.last_used_at.unwrap_or(d.created_at) < stale_cutoff)
+        .map(|d| d.device_kid.clone())
+        .collect();
+    let device_posture = DevicePosture {
+        active: active.len(),
+        revoked: devices.len() - active.len(),
+        limit: MAX_DEVICES_PER_ACCOUNT,
+        oldest_active_age_days: active
+            .iter()
+            .map(|d| d.created_at)
+            .min()
+            .map(|created| (now - created).num_days()),
+        stale,
+    };
+
+    let backup_posture = BackupPosture {
+        present: backup.is_some(),
+        created_at: backup.map(|b| b.created_at.to_rfc3339()),
+        age_days: backup.map(|b| (now - b.created_at).num_days()),
+    };
+
+    let active_tokens: Vec<&ApiTokenRecord> = tokens
+        .iter()
+        .filter(|t| t.revoked_at.is_none() && t.expires_at.is_none_or(|exp| exp > now))
+        .collect();
+    let token_posture = ApiTokenPosture {
+        active: active_tokens.len(),
+        without_expiry: active_tokens
+            .iter()
+            .filter(|t| t.expires_at.is_none())
+            .count(),
+    };
+
+    let recent_events = recent_device_events(devices, now - Duration::days(RECENT_EVENT_DAYS));
+    let recommendations = recommend(
+        &device_posture,
+        &backup_posture,
+        &token_posture,
+        !recent_events.is_empty(),
+    );
+
+    SecurityCheckupResponse {
+        devices: device_posture,
+        backup: backup_posture,
+        api_tokens: token_posture,
+        recent_events,
+        recommendations,
+    }
+}
+
+/// Device additions and revocations at or after `cutoff`, newest first.
+fn recent_device_events(devices: &[DeviceKeyRecord], cutoff: DateTime<Utc>) -> Vec<SecurityEvent> {
+    let mut recent: Vec<(DateTime<Utc>, SecurityEvent)> = Vec::new();
+    for d in devices {
+        if d.created_at >= cutoff {
+            recent.push((
+                d.created_at,
+                SecurityEvent {
+                    kind: SecurityEventKind::DeviceAdded,
+                    device_kid: d.device_kid.clone(),
+                    device_name: d.device_name.clone(),
+                    at: d.created_at.to_rfc3339(),
+                    by_kid: None,
+                },
+            ));
+        }
+        if let Some(revoked_at) = d.revoked_at.filter(|t| *t >= cutoff) {
+            recent.push((
+                revoked_at,
+                SecurityEvent {
+                    kind: SecurityEventKind::DeviceRevoked,
+                    device_kid: d.device_kid.clone(),
+                    device_name: d.device_name.clone(),
+                    at: revoked_at.to_rfc3339(),
+                    by_kid: d.revoked_by_kid.clone(),
+                },
+            ));
+        }
+    }
+    recent.sort_by_key(|(at, _)| std::cmp::Reverse(*at));
+    recent.into_iter().map(|(_, e)| e).collect()
+}
+
+fn recommend(
+    devices: &DevicePosture,
+    backup: &BackupPosture,
+    tokens: &ApiTokenPosture,
+    has_recent_events: bool,
+) -> Vec<Recommendation> {
+    let mut recommendations = Vec::new();
+    if !backup.present {
+        recommendations.push(Recommendation {
+            code: RecommendationCode::NoBackup,
+            message: "No encrypted backup is stored; losing every device would lock you out"
+                .to_string(),
+        });
+    }
+    if !devices.stale.is_empty() {
+        recommendations.push(Recommendation {
+            code: RecommendationCode::StaleDevices,
+            message: format!(
+                "{} device(s) unused for over {STALE_DEVICE_DAYS} days; revoke any you no longer have",
+                devices.stale.len()
+            ),
+        });
+    }
+    if i64::try_from(devices.active).unwrap_or(i64::MAX) >= MAX_DEVICES_PER_ACCOUNT - 1 {
+        recommendations.push(Recommendation {
+            code: RecommendationCode::DeviceLimitNear,
+            message: "You are at or near the device limit; revoke unused devices".to_string(),
+        });
+    }
+    if tokens.without_expiry > 0 {
+        recommendations.push(Recommendation {
+            code: RecommendationCode::NonExpiringTokens,
+            message: format!(
+                "{} API token(s) never expire; prefer tokens with an expiry",
+                tokens.without_expiry
+            ),
+        });
+    }
+    if has_recent_events {
+        recommendations.push(Recommendation {
+            code: RecommendationCode::ReviewRecentChanges,
+            message: format!(
+                "Devices changed in the last {RECENT_EVENT_DAYS} days; make sure you recognize them"
+            ),
+        });
+    }
+    recommendations
+}
+
+/// GET /auth/security/checkup — summarize the account's security posture
+#[utoipa::path(
+    get,
+    path = "/auth/security/checkup",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Security checkup", body = SecurityCheckupResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn security_checkup(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let account = match repo.get_account_by_id(auth.account_id).await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to look up account for security checkup: {e}");
+            return super::internal_error();
+        }
+    };
+    let devices = match repo.list_device_keys_by_account(auth.account_id).await {
+        Ok(d) => d,
+        Err(e) => {
+            tracing::error!("Failed to list devices for security checkup: {e}");
+            return super::internal_error();
+        }
+    };
+    let backup = match repo.get_backup_by_kid(&account.root_kid).await {
+        Ok(b) => Some(b),
+        Err(BackupRepoError::NotFound) => None,
+        Err(e) => {
+            tracing::error!("Failed to look up backup for security checkup: {e}");
+            return super::internal_error();
+        }
+    };
+    let tokens = match repo.list_api_tokens_by_account(auth.account_id).await {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!("Failed to list API tokens for security checkup: {e}");
+            return super::internal_error();
+        }
+    };
+
+    let checkup = build_checkup(Utc::now(), &devices, backup.as_ref(), &tokens);
+    (StatusCode::OK, Json(checkup)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn device(seed: u8, created_days_ago: i64, now: DateTime<Utc>) -> DeviceKeyRecord {
+        DeviceKeyRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            device_kid: Kid::derive(&[seed; 32]),
+            device_pubkey: String::new(),
+            device_name: format!("Device {seed}"),
+            certificate: vec![],
+            last_used_at: None,
+            revoked_at: None,
+            revoked_by_kid: None,
+            revocation_envelope: None,
+            certificate_version: Some(1),
+            certificate_timestamp: None,
+            rotated_from_kid: None,
+            rotation_signature: None,
+            client_caps: Vec::new(),
+            created_at: now - Duration::days(created_days_ago),
+        }
+    }
+
+    fn token(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> ApiTokenRecord {
+        ApiTokenRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            name: "ci".to_string(),
+            scopes: vec!["trust:read".to_string()],
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: now,
+        }
+    }
+
+    fn codes(checkup: &SecurityCheckupResponse) -> Vec<RecommendationCode> {
+        checkup.recommendations.iter().map(|r| r.code).collect()
+    }
+
+    #[test]
+    fn healthy_account_has_no_recommendations() {
+        let now = Utc::now();
+        let mut laptop = device(1, 200, now);
+        laptop.last_used_at = Some(now);
+        let backup = BackupRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            kid: Kid::derive(&[9u8; 32]),
+            encrypted_backup: vec![],
+            salt: vec![],
+            version: 1,
+            created_at: now - Duration::days(200),
+            recovery_code_hash: None,
+        };
+        let tokens = [token(Some(now + Duration::days(30)), now)];
+
+        let checkup = build_checkup(now, &[laptop], Some(&backup), &tokens);
+        assert_eq!(checkup.devices.active, 1);
+        assert_eq!(checkup.devices.oldest_active_age_days, Some(200));
+        assert!(checkup.devices.stale.is_empty());
+        assert_eq!(checkup.backup.age_days, Some(200));
+        assert_eq!(checkup.api_tokens.active, 1);
+        assert!(checkup.recent_events.is_empty());
+        assert!(codes(&checkup).is_empty());
+    }
+
+    #[test]
+    fn weak_spots_are_recommended() {
+        let now = Utc::now();
+        let stale = device(1, 200, now);
+        let mut revoked = device(2, 100, now);
+        revoked.revoked_at = Some(now - Duration::days(1));
+        revoked.revoked_by_kid = Some(stale.device_kid.clone());
+        let fresh = device(3, 2, now);
+        let tokens = [token(None, now), token(Some(now - Duration::days(1)), now)];
+
+        let checkup = build_checkup(now, &[stale.clone(), revoked, fresh], None, &tokens);
+        assert_eq!(checkup.devices.active, 2);
+        assert_eq!(checkup.devices.revoked, 1);
+        assert_eq!(checkup.devices.stale, vec![stale.device_kid.clone()]);
+        assert_eq!(checkup.api_tokens.active, 1);
+        assert_eq!(checkup.api_tokens.without_expiry, 1);
+
+        let kinds: Vec<SecurityEventKind> = checkup.recent_events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SecurityEventKind::DeviceRevoked,
+                SecurityEventKind::DeviceAdded
+            ]
+        );
+        assert_eq!(checkup.recent_events[0].by_kid, Some(stale.device_kid));
+
+        assert_eq!(
+            codes(&checkup),
+            [
+                RecommendationCode::NoBackup,
+                RecommendationCode::StaleDevices,
+                RecommendationCode::NonExpiringTokens,
+                RecommendationCode::ReviewRecentChanges,
+            ]
+        );
+    }
+
+    #[test]
+    fn device_limit_is_flagged() {
+        let now = Utc::now();
+        let devices: Vec<DeviceKeyRecord> = (0..9)
+            .map(|i| {
+                let mut d = device(i, 30, now);
+                d.last_used_at = Some(now);
+                d
+            })
+            .collect();
+        let checkup = build_checkup(now, &devices, None, &[]);
+        assert!(codes(&checkup).contains(&RecommendationCode::DeviceLimitNear));
+    }
+}
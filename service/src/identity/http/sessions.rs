@@ -0,0 +1,230 @@
+//! Short-lived session tokens for read requests
+//!
+//! A device trades one signed `POST /auth/sessions` for an opaque bearer
+//! token, so web clients can load pages without signing every `GET`:
+//!
+//! - `Authorization: Bearer tcsess_<base64url>`
+//!
+//! [`AuthenticatedDevice`] accepts a session token in place of the signed
+//! headers for `GET` and `HEAD` requests only. A session token is not bound
+//! to a body, nonce or timestamp, so anything that changes state still needs
+//! a signature. Sessions expire after `auth.session_ttl_secs`, die with their
+//! device, and are refused once the account is no longer active. Only the
+//! SHA-256 of each token is stored.
+//!
+//! Sessions live at `/auth/sessions` rather than `/auth/login` because
+//! `POST /auth/login` already registers a new device from a root-signed
+//! certificate (see [`super::login`]). The signed request itself is the
+//! challenge: its timestamp and nonce are checked by [`AuthenticatedDevice`],
+//! so there is no separate challenge round-trip.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::{header::AUTHORIZATION, HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use crate::config::AuthConfig;
+use crate::identity::repo::{DeviceSessionRepoError, IdentityRepo};
+use tc_crypto::{encode_base64url, Kid};
+
+/// Prefix on every session token; distinct from API tokens' `tc_` and
+/// OAuth client secrets' `tcs_`.
+pub const SESSION_TOKEN_PREFIX: &str = "tcsess_";
+
+/// Random bytes per token (256 bits).
+const SESSION_TOKEN_BYTES: usize = 32;
+
+/// Generate a new plaintext session token.
+#[must_use]
+pub fn generate_session_token() -> String {
+    let mut bytes = [0u8; SESSION_TOKEN_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{SESSION_TOKEN_PREFIX}{}", encode_base64url(&bytes))
+}
+
+/// Hash a plaintext session token for storage and lookup.
+#[must_use]
+pub fn hash_session_token(token: &str) -> Vec<u8> {
+    Sha256::digest(token.as_bytes()).to_vec()
+}
+
+/// The session token in `Authorization`, if the header carries one.
+///
+/// Other bearer tokens (API tokens) are left to their own extractor.
+#[must_use]
+pub fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| token.starts_with(SESSION_TOKEN_PREFIX))
+}
+
+fn auth_error(msg: &str) -> Response {
+    super::unauthorized(msg)
+}
+
+/// Resolve a session token to the device it was issued to.
+///
+/// Called by [`AuthenticatedDevice`] when a request carries a session token
+/// instead of signed headers.
+#[allow(clippy::result_large_err)]
+pub(super) async fn authenticate_session(
+    repo: &dyn IdentityRepo,
+    method: &Method,
+    token: &str,
+) -> Result<(Uuid, Kid), Response> {
+    if method != Method::GET && method != Method::HEAD {
+        return Err(auth_error(
+            "Session tokens only authorize GET and HEAD requests; sign this request",
+        ));
+    }
+    let session = repo
+        .get_device_session_by_hash(&hash_session_token(token))
+        .await
+        .map_err(|e| match e {
+            DeviceSessionRepoError::NotFound => auth_error("Invalid session token"),
+            DeviceSessionRepoError::Database(db_err) => {
+                tracing::error!("Session lookup failed: {db_err}");
+                auth_error("Authentication failed")
+            }
+        })?;
+    if !session.is_live(Utc::now()) {
+        return Err(auth_error("Session has expired"));
+    }
+    if session.device_revoked_at.is_some() {
+        return Err(super::forbidden("Device has been revoked"));
+    }
+    super::require_active_account(repo, session.account_id).await?;
+    metrics::counter!("tc_auth_session_requests_total").increment(1);
+    Ok((session.account_id, session.device_kid))
+}
+
+/// Issued session
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateSessionResponse {
+    /// The bearer token. Shown once; only its hash is stored.
+    pub token: String,
+    #[schema(value_type = String)]
+    pub device_kid: Kid,
+    pub expires_at: String,
+}
+
+/// Issue a session token to the signing device.
+///
+/// The signed request is the challenge: its timestamp and nonce make it
+/// single-use, so a captured request cannot mint further sessions.
+#[utoipa::path(
+    post,
+    path = "/auth/sessions",
+    tag = "Identity",
+    responses(
+        (status = 201, description = "Session issued", body = CreateSessionResponse),
+        (status = 401, description = "Unauthorized, or not a signed request"),
+        (status = 403, description = "Sessions are disabled, the device is revoked, or the account is not active"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn create_session(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth_config: Option<Extension<AuthConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let auth_config = auth_config.map(|Extension(c)| c).unwrap_or_default();
+    if auth_config.session_ttl_secs == 0 {
+        return super::forbidden("Session tokens are disabled on this server");
+    }
+    let ttl = i64::try_from(auth_config.session_ttl_secs).unwrap_or(i64::MAX);
+    let expires_at = Utc::now() + Duration::seconds(ttl);
+    let token = generate_session_token();
+    match repo
+        .create_device_session(
+            auth.account_id,
+            &auth.device_kid,
+            &hash_session_token(&token),
+            expires_at,
+        )
+        .await
+    {
+        Ok(session) => (
+            StatusCode::CREATED,
+            Json(CreateSessionResponse {
+                token,
+                device_kid: session.device_kid,
+                expires_at: session.expires_at.to_rfc3339(),
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to create session: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+/// Revoke every session issued to the signing device (log out).
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions",
+    tag = "Identity",
+    responses(
+        (status = 204, description = "Sessions revoked"),
+        (status = 401, description = "Unauthorized, or not a signed request"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn revoke_sessions(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.revoke_device_sessions(&auth.device_kid).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to revoke sessions: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn tokens_are_prefixed_and_unique() {
+        let a = generate_session_token();
+        let b = generate_session_token();
+        assert!(a.starts_with(SESSION_TOKEN_PREFIX));
+        assert_ne!(a, b);
+        assert_ne!(hash_session_token(&a), hash_session_token(&b));
+    }
+
+    #[test]
+    fn session_token_ignores_other_bearers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(session_token(&headers), None);
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer tc_abc"));
+        assert_eq!(session_token(&headers), None);
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer tcs_abc"));
+        assert_eq!(
+            session_token(&headers),
+            None,
+            "client secrets are not sessions"
+        );
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer tcsess_abc"));
+        assert_eq!(session_token(&headers), Some("tcsess_abc"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Sigchain read endpoints; see [`crate::identity::sigchain`].
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::header::CACHE_CONTROL,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tc_crypto::encode_base64url;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::Path;
+use crate::identity::repo::IdentityRepo;
+use crate::identity::sigchain::{NewSigchainLink, SigchainLink, SigchainLinkType};
+use crate::pagination::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+
+/// Query parameters for `GET /auth/accounts/{account_id}/sigchain`.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct SigchainQuery {
+    /// Return links after this seqno (default 0: from the start).
+    pub since_seqno: Option<i64>,
+    /// Page size (1–200, default 50).
+    pub limit: Option<u32>,
+}
+
+/// A page of sigchain links, oldest first.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SigchainPage {
+    pub links: Vec<SigchainLink>,
+    /// Pass as `since_seqno` for the next page; absent on the last page
+    pub next_since_seqno: Option<i64>,
+}
+
+/// The latest link of an account's sigchain.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SigchainHead {
+    /// 0 for an empty chain
+    pub seqno: i64,
+    /// Base64url hash of the latest link; absent for an empty chain
+    pub hash: Option<String>,
+}
+
+/// GET `/auth/accounts/{account_id}/sigchain` — the account's signed event
+/// history
+///
+/// Only the account's own devices may read it. Walk the chain by passing
+/// each page's `next_since_seqno` back; every link commits to the previous
+/// one, so [`crate::identity::sigchain::verify_links`] can check a page
+/// against the last link of the page before it.
+#[utoipa::path(
+    get,
+    path = "/auth/accounts/{account_id}/sigchain",
+    tag = "Identity",
+    params(
+        ("account_id" = Uuid, Path, description = "Account whose sigchain to read"),
+        SigchainQuery
+    ),
+    responses(
+        (status = 200, description = "Sigchain links", body = SigchainPage),
+        (status = 400, description = "Invalid since_seqno or limit"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not this account's device"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_sigchain(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<SigchainQuery>,
+    auth: AuthenticatedDevice,
+) -> Response {
+    if auth.account_id != account_id {
+        return super::forbidden("Devices can only read their own account's sigchain");
+    }
+    let since_seqno = query.since_seqno.unwrap_or(0);
+    if since_seqno < 0 {
+        return super::bad_request("since_seqno must not be negative");
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+        return super::bad_request(&format!("limit must be between 1 and {MAX_PAGE_LIMIT}"));
+    }
+
+    // One extra row tells whether another page follows
+    let mut links = match repo
+        .list_sigchain_links(account_id, since_seqno, i64::from(limit) + 1)
+        .await
+    {
+        Ok(links) => links,
+        Err(e) => {
+            tracing::error!(%account_id, "Failed to list sigchain: {e}");
+            return super::internal_error();
+        }
+    };
+    let more = links.len() > limit as usize;
+    links.truncate(limit as usize);
+    let next_since_seqno = more.then(|| links.last().map(|l| l.seqno)).flatten();
+    Json(SigchainPage {
+        links: links.into_iter().map(SigchainLink::from).collect(),
+        next_since_seqno,
+    })
+    .into_response()
+}
+
+/// GET `/auth/accounts/{account_id}/sigchain/head` — the latest link's hash
+///
+/// Clients compare it with the last hash they verified to tell whether the
+/// chain has grown, or was rewritten, since. Served with
+/// `Cache-Control: no-store`.
+#[utoipa::path(
+    get,
+    path = "/auth/accounts/{account_id}/sigchain/head",
+    tag = "Identity",
+    params(("account_id" = Uuid, Path, description = "Account whose sigchain head to read")),
+    responses(
+        (status = 200, description = "Sigchain head", body = SigchainHead),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not this account's device"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_sigchain_head(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Path(account_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> Response {
+    if auth.account_id != account_id {
+        return super::forbidden("Devices can only read their own account's sigchain");
+    }
+    match repo.get_sigchain_head(account_id).await {
+        Ok(head) => (
+            [(CACHE_CONTROL, "no-store")],
+            Json(SigchainHead {
+                seqno: head.as_ref().map_or(0, |h| h.seqno),
+                hash: head.map(|h| encode_base64url(&h.hash)),
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(%account_id, "Failed to load sigchain head: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+/// The sigchain link recording the authenticated request, to pass to the
+/// repository call that makes the change. See [`crate::identity::sigchain`].
+#[allow(clippy::result_large_err)]
+pub(crate) fn sigchain_link(
+    auth: &AuthenticatedDevice,
+    link_type: SigchainLinkType,
+) -> Result<NewSigchainLink, Response> {
+    NewSigchainLink::signed(link_type, &auth.signed_request()).map_err(|e| {
+        tracing::error!("Failed to serialize sigchain envelope: {e}");
+        super::internal_error()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use crate::identity::sigchain::verify_links;
+    use crate::identity::signed_request::SignedRequest;
+    use axum::{body::to_bytes, body::Bytes, http::StatusCode};
+    use tc_crypto::Kid;
+
+    fn repo_with_chain(account_id: Uuid, len: usize) -> Arc<MockIdentityRepo> {
+        let repo = Arc::new(MockIdentityRepo::new());
+        for i in 0..len {
+            let request = SignedRequest {
+                alg: None,
+                method: "POST".to_string(),
+                host: "api.example".to_string(),
+                path_and_query: "/auth/devices".to_string(),
+                timestamp: 1_700_000_000,
+                nonce: format!("nonce-{i}"),
+                content_type: "application/json".to_string(),
+                device_kid: Kid::derive(&[1u8; 32]),
+                challenge: None,
+                body: String::new(),
+                signature: String::new(),
+            };
+            let link =
+                NewSigchainLink::signed(SigchainLinkType::DeviceAdded, &request).expect("json");
+            repo.push_sigchain_link(account_id, &link);
+        }
+        repo
+    }
+
+    fn auth(account_id: Uuid) -> AuthenticatedDevice {
+        AuthenticatedDevice::for_test(account_id, Kid::derive(&[1u8; 32]), Bytes::new())
+    }
+
+    async fn page(repo: &Arc<MockIdentityRepo>, account_id: Uuid, since: i64) -> SigchainPage {
+        let response = list_sigchain(
+            Extension(Arc::clone(repo) as Arc<dyn IdentityRepo>),
+            Path(account_id),
+            Query(SigchainQuery {
+                since_seqno: Some(since),
+                limit: Some(2),
+            }),
+            auth(account_id),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), 65536).await.expect("body");
+        serde_json::from_slice(&body).expect("json")
+    }
+
+    #[tokio::test]
+    async fn pages_walk_the_chain_and_verify() {
+        let account_id = Uuid::new_v4();
+        let repo = repo_with_chain(account_id, 3);
+
+        let first = page(&repo, account_id, 0).await;
+        assert_eq!(first.links.len(), 2);
+        assert_eq!(first.next_since_seqno, Some(2));
+        verify_links(None, &first.links).expect("first page verifies");
+
+        let second = page(&repo, account_id, 2).await;
+        assert_eq!(second.links.len(), 1);
+        assert_eq!(second.next_since_seqno, None);
+        verify_links(first.links.last(), &second.links).expect("second page continues");
+
+        let response = get_sigchain_head(
+            Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+            Path(account_id),
+            auth(account_id),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        let head: SigchainHead = serde_json::from_slice(&body).expect("json");
+        assert_eq!(head.seqno, 3);
+        assert_eq!(head.hash.as_ref(), Some(&second.links[0].hash));
+    }
+
+    #[tokio::test]
+    async fn other_accounts_and_bad_queries_are_refused() {
+        let account_id = Uuid::new_v4();
+        let repo = repo_with_chain(account_id, 1);
+
+        let response = list_sigchain(
+            Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+            Path(account_id),
+            Query(SigchainQuery::default()),
+            auth(Uuid::new_v4()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = get_sigchain_head(
+            Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+            Path(account_id),
+            auth(Uuid::new_v4()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        for query in [
+            SigchainQuery {
+                since_seqno: Some(-1),
+                limit: None,
+            },
+            SigchainQuery {
+                since_seqno: None,
+                limit: Some(MAX_PAGE_LIMIT + 1),
+            },
+        ] {
+            let response = list_sigchain(
+                Extension(Arc::clone(&repo) as Arc<dyn IdentityRepo>),
+                Path(account_id),
+                Query(query),
+                auth(account_id),
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_chain_has_a_zero_head() {
+        let account_id = Uuid::new_v4();
+        let repo = Arc::new(MockIdentityRepo::new());
+        let response = get_sigchain_head(
+            Extension(repo as Arc<dyn IdentityRepo>),
+            Path(account_id),
+            auth(account_id),
+        )
+        .await;
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        let head: SigchainHead = serde_json::from_slice(&body).expect("json");
+        assert_eq!(head.seqno, 0);
+        assert_eq!(head.hash, None);
+    }
+}
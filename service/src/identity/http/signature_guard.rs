@@ -0,0 +1,321 @@
+//! Brute-force protection for signed requests
+//!
+//! [`super::auth::AuthenticatedDevice`] reports every signature that fails to
+//! verify against a known device, keyed by device KID and by client IP, and
+//! every unknown KID, keyed by IP. Once a key collects
+//! `auth.failure_threshold` failures within `auth.failure_window_secs`, it
+//! trips.
+//!
+//! Only client IPs are blocked, for `auth.failure_block_secs`, doubling with
+//! each further block on the same IP up to `auth.failure_block_max_secs`.
+//! Blocked requests get `429` with `Retry-After` before any database lookup
+//! or signature check. The IP is the socket peer, or the last
+//! `X-Forwarded-For` entry when `dev_tools.trust_forwarded_for` is set (see
+//! [`crate::http::dev_tools::client_ip`]), so a client cannot choose which IP
+//! its failures count against.
+//!
+//! A device KID is public — it travels in every signed request — so blocking
+//! by KID would let anyone lock a device out by sending forged signatures. A
+//! tripped KID is only flagged for audit.
+//!
+//! A valid signature clears its device's failures. IP counts are never
+//! cleared by success, so interleaving one good request cannot reset them.
+//!
+//! State lives in memory per replica. Each new block or flag is logged,
+//! counted in `tc_auth_signature_blocks_total`, and published as a
+//! [`DomainEvent::SignatureFailuresBlocked`].
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::Request,
+    http::{header::RETRY_AFTER, HeaderValue},
+    response::Response,
+};
+use tc_crypto::Kid;
+
+use crate::config::AuthConfig;
+use crate::events::{DomainEvent, EventPublisher};
+
+/// Keys tracked before idle entries are pruned.
+const MAX_TRACKED_KEYS: usize = 50_000;
+
+/// What a failure count is kept against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FailureKey {
+    Device(Kid),
+    Ip(IpAddr),
+}
+
+impl FailureKey {
+    /// Metric and event label.
+    #[must_use]
+    pub const fn scope(&self) -> &'static str {
+        match self {
+            Self::Device(_) => "device",
+            Self::Ip(_) => "ip",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// Failures inside the current window, oldest first.
+    failures: VecDeque<Instant>,
+    /// Blocks imposed so far; each one doubles the next.
+    strikes: u32,
+    blocked_until: Option<Instant>,
+    last_seen: Instant,
+}
+
+/// Sliding-window failure counts and temporary blocks.
+#[derive(Debug)]
+pub struct SignatureFailureGuard {
+    threshold: usize,
+    window: Duration,
+    block: Duration,
+    max_block: Duration,
+    trust_forwarded_for: bool,
+    entries: Mutex<HashMap<FailureKey, Entry>>,
+}
+
+impl SignatureFailureGuard {
+    /// Build the guard, or `None` when `failure_threshold` is 0.
+    #[must_use]
+    pub fn from_config(config: &AuthConfig) -> Option<Self> {
+        (config.failure_threshold > 0).then(|| Self {
+            threshold: config.failure_threshold as usize,
+            window: Duration::from_secs(config.failure_window_secs),
+            block: Duration::from_secs(config.failure_block_secs),
+            max_block: Duration::from_secs(config.failure_block_max_secs),
+            trust_forwarded_for: false,
+            entries: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Take client IPs from the last `X-Forwarded-For` entry instead of the
+    /// socket peer.
+    #[must_use]
+    pub const fn with_trust_forwarded_for(mut self, trust: bool) -> Self {
+        self.trust_forwarded_for = trust;
+        self
+    }
+
+    /// The IP a failure on `req` counts against.
+    #[must_use]
+    pub fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        crate::http::dev_tools::client_ip(req, self.trust_forwarded_for)
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<FailureKey, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Time left on the longest active block among `keys`, if any.
+    #[must_use]
+    pub fn retry_after(&self, keys: &[FailureKey], now: Instant) -> Option<Duration> {
+        let entries = self.entries();
+        keys.iter()
+            .filter_map(|key| entries.get(key)?.blocked_until)
+            .filter_map(|until| until.checked_duration_since(now))
+            .filter(|left| !left.is_zero())
+            .max()
+    }
+
+    /// Count one failure against `key`. Returns the block duration when this
+    /// failure trips the key; device keys trip with a zero duration, since
+    /// they are never blocked.
+    pub fn record_failure(&self, key: FailureKey, now: Instant) -> Option<Duration> {
+        let mut entries = self.entries();
+        if entries.len() >= MAX_TRACKED_KEYS {
+            let idle = self.window.max(self.max_block);
+            entries.retain(|_, e| {
+                now.saturating_duration_since(e.last_seen) < idle
+                    || e.blocked_until.is_some_and(|until| until > now)
+            });
+        }
+        let is_device = matches!(key, FailureKey::Device(_));
+        let entry = entries.entry(key).or_insert_with(|| Entry {
+            failures: VecDeque::new(),
+            strikes: 0,
+            blocked_until: None,
+            last_seen: now,
+        });
+        entry.last_seen = now;
+        while entry
+            .failures
+            .front()
+            .is_some_and(|&t| now.saturating_duration_since(t) >= self.window)
+        {
+            entry.failures.pop_front();
+        }
+        entry.failures.push_back(now);
+        if entry.failures.len() < self.threshold {
+            return None;
+        }
+        entry.failures.clear();
+        if is_device {
+            return Some(Duration::ZERO);
+        }
+        let duration = self
+            .block
+            .saturating_mul(2u32.saturating_pow(entry.strikes))
+            .min(self.max_block);
+        entry.strikes = entry.strikes.saturating_add(1);
+        entry.blocked_until = Some(now + duration);
+        drop(entries);
+        Some(duration)
+    }
+
+    /// Forget a device's failures after it signs a valid request.
+    pub fn record_success(&self, kid: &Kid) {
+        self.entries().remove(&FailureKey::Device(kid.clone()));
+    }
+
+    /// Count a failure against every key, reporting any new block.
+    pub fn note_failures(
+        &self,
+        keys: Vec<FailureKey>,
+        publisher: Option<&dyn EventPublisher>,
+        now: Instant,
+    ) {
+        for key in keys {
+            let Some(duration) = self.record_failure(key.clone(), now) else {
+                continue;
+            };
+            let scope = key.scope();
+            metrics::counter!("tc_auth_signature_blocks_total", "scope" => scope).increment(1);
+            let device_kid = match key {
+                FailureKey::Device(kid) => {
+                    tracing::warn!(%kid, "Repeated signature failures against device");
+                    Some(kid)
+                }
+                FailureKey::Ip(ip) => {
+                    tracing::warn!(%ip, secs = duration.as_secs(), "Blocking client IP after repeated signature failures");
+                    None
+                }
+            };
+            if let Some(publisher) = publisher {
+                publisher.publish(DomainEvent::SignatureFailuresBlocked {
+                    scope,
+                    device_kid,
+                    blocked_secs: duration.as_secs(),
+                });
+            }
+        }
+    }
+}
+
+/// 429 response for a blocked caller, with `Retry-After` in whole seconds.
+#[must_use]
+pub fn blocked_response(retry_after: Duration) -> Response {
+    let mut response =
+        crate::http::too_many_requests("Too many failed signatures — please retry later");
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard() -> SignatureFailureGuard {
+        SignatureFailureGuard::from_config(&AuthConfig {
+            failure_threshold: 3,
+            failure_window_secs: 60,
+            failure_block_secs: 10,
+            failure_block_max_secs: 25,
+            ..AuthConfig::default()
+        })
+        .expect("enabled")
+    }
+
+    fn kid() -> Kid {
+        "cs1uhCLEB_ttCYaQ8RMLfQ".parse().expect("kid")
+    }
+
+    #[test]
+    fn blocks_escalate_and_cap() {
+        let g = guard();
+        let key = FailureKey::Ip(IpAddr::from([203, 0, 113, 7]));
+        let t0 = Instant::now();
+        assert_eq!(g.record_failure(key.clone(), t0), None);
+        assert_eq!(g.record_failure(key.clone(), t0), None);
+        assert_eq!(
+            g.record_failure(key.clone(), t0),
+            Some(Duration::from_secs(10))
+        );
+        let keys = [key.clone()];
+        assert_eq!(g.retry_after(&keys, t0), Some(Duration::from_secs(10)));
+        assert_eq!(g.retry_after(&keys, t0 + Duration::from_secs(10)), None);
+
+        let t1 = t0 + Duration::from_secs(11);
+        g.record_failure(key.clone(), t1);
+        g.record_failure(key.clone(), t1);
+        assert_eq!(
+            g.record_failure(key.clone(), t1),
+            Some(Duration::from_secs(20))
+        );
+        let t2 = t1 + Duration::from_secs(21);
+        g.record_failure(key.clone(), t2);
+        g.record_failure(key.clone(), t2);
+        assert_eq!(g.record_failure(key, t2), Some(Duration::from_secs(25)));
+    }
+
+    #[test]
+    fn old_failures_leave_the_window() {
+        let g = guard();
+        let key = FailureKey::Ip(IpAddr::from([203, 0, 113, 7]));
+        let t0 = Instant::now();
+        g.record_failure(key.clone(), t0);
+        g.record_failure(key.clone(), t0);
+        assert_eq!(
+            g.record_failure(key.clone(), t0 + Duration::from_secs(60)),
+            None
+        );
+        assert!(g
+            .retry_after(&[key], t0 + Duration::from_secs(60))
+            .is_none());
+    }
+
+    #[test]
+    fn success_clears_device_but_not_ip() {
+        let g = guard();
+        let device = FailureKey::Device(kid());
+        let ip = FailureKey::Ip(IpAddr::from([203, 0, 113, 7]));
+        let now = Instant::now();
+        for _ in 0..2 {
+            g.record_failure(device.clone(), now);
+            g.record_failure(ip.clone(), now);
+        }
+        g.record_success(&kid());
+        assert_eq!(g.record_failure(device, now), None);
+        assert!(g.record_failure(ip, now).is_some());
+    }
+
+    #[test]
+    fn devices_are_flagged_but_never_blocked() {
+        let g = guard();
+        let key = FailureKey::Device(kid());
+        let now = Instant::now();
+        g.record_failure(key.clone(), now);
+        g.record_failure(key.clone(), now);
+        assert_eq!(g.record_failure(key.clone(), now), Some(Duration::ZERO));
+        assert!(g.retry_after(&[key], now).is_none());
+    }
+
+    #[test]
+    fn zero_threshold_disables() {
+        let config = AuthConfig {
+            failure_threshold: 0,
+            ..AuthConfig::default()
+        };
+        assert!(SignatureFailureGuard::from_config(&config).is_none());
+    }
+}
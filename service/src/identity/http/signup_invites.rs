@@ -0,0 +1,191 @@
+//! Signup invite HTTP handlers
+//!
+//! Endpoints for issuing and listing invite codes used by invite-only
+//! instances. Codes can be issued whether or not invite-only mode is on, so
+//! members can stock up before an operator switches it on. Only the code's
+//! SHA-256 is stored; the plaintext is returned once at creation.
+
+use std::sync::Arc;
+
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::ErrorResponse;
+use crate::config::{AccountsConfig, SignupConfig};
+use crate::identity::repo::{IdentityRepo, SignupInviteRecord, SignupInviteRepoError};
+use crate::identity::service::{generate_invite_code, hash_invite_code};
+
+/// Invite metadata returned in responses (never includes the code itself)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignupInviteInfo {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub created_at: String,
+    pub expires_at: String,
+    /// Account created with this code, if redeemed
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub redeemed_by: Option<Uuid>,
+    pub redeemed_at: Option<String>,
+}
+
+impl From<SignupInviteRecord> for SignupInviteInfo {
+    fn from(record: SignupInviteRecord) -> Self {
+        Self {
+            id: record.id,
+            created_at: record.created_at.to_rfc3339(),
+            expires_at: record.expires_at.to_rfc3339(),
+            redeemed_by: record.redeemed_by,
+            redeemed_at: record.redeemed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignupInviteListResponse {
+    pub invites: Vec<SignupInviteInfo>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateSignupInviteResponse {
+    /// The invite code. Shown once; only its hash is stored.
+    pub code: String,
+    #[serde(flatten)]
+    pub info: SignupInviteInfo,
+}
+
+/// POST /auth/signup-invites — issue a signup invite code
+#[utoipa::path(
+    post,
+    path = "/auth/signup-invites",
+    tag = "Identity",
+    responses(
+        (status = 201, description = "Invite issued", body = CreateSignupInviteResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Outstanding invite quota reached"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn create_signup_invite(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    signup_config: Option<Extension<SignupConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let signup_config = signup_config.map(|Extension(c)| c).unwrap_or_default();
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+
+    let max_outstanding = (!accounts_config.is_admin(auth.account_id))
+        .then(|| i64::from(signup_config.invites_per_account));
+    let expires_at = Utc::now() + Duration::days(i64::from(signup_config.invite_ttl_days));
+
+    let code = generate_invite_code();
+    match repo
+        .create_signup_invite(
+            auth.account_id,
+            &hash_invite_code(&code),
+            expires_at,
+            max_outstanding,
+        )
+        .await
+    {
+        Ok(record) => (
+            StatusCode::CREATED,
+            Json(CreateSignupInviteResponse {
+                code,
+                info: SignupInviteInfo::from(record),
+            }),
+        )
+            .into_response(),
+        Err(e) => signup_invite_repo_error_response(&e),
+    }
+}
+
+/// GET /auth/signup-invites — list invites issued by the authenticated account
+#[utoipa::path(
+    get,
+    path = "/auth/signup-invites",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Invite list", body = SignupInviteListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_signup_invites(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.list_signup_invites_by_account(auth.account_id).await {
+        Ok(records) => {
+            let invites = records.into_iter().map(SignupInviteInfo::from).collect();
+            (StatusCode::OK, Json(SignupInviteListResponse { invites })).into_response()
+        }
+        Err(e) => signup_invite_repo_error_response(&e),
+    }
+}
+
+fn signup_invite_repo_error_response(e: &SignupInviteRepoError) -> axum::response::Response {
+    match e {
+        SignupInviteRepoError::NotFound => super::not_found("Account not found"),
+        SignupInviteRepoError::QuotaReached => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                error: "Outstanding invite quota reached".to_string(),
+            }),
+        )
+            .into_response(),
+        SignupInviteRepoError::InvalidCode | SignupInviteRepoError::Database(_) => {
+            tracing::error!("Signup invite operation failed: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use axum::body::{to_bytes, Bytes};
+    use tc_crypto::Kid;
+
+    fn auth() -> AuthenticatedDevice {
+        AuthenticatedDevice::for_test(Uuid::new_v4(), Kid::derive(&[0xAAu8; 32]), Bytes::new())
+    }
+
+    async fn create(repo: MockIdentityRepo) -> axum::response::Response {
+        create_signup_invite(
+            Extension(Arc::new(repo) as Arc<dyn IdentityRepo>),
+            None,
+            None,
+            auth(),
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn create_returns_code_once() {
+        let response = create(MockIdentityRepo::new()).await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert!(payload["code"].as_str().unwrap().starts_with("tci_"));
+        assert!(payload["expires_at"].is_string());
+        assert!(payload["redeemed_by"].is_null());
+    }
+
+    #[tokio::test]
+    async fn create_at_quota_returns_422() {
+        let repo = MockIdentityRepo::new();
+        repo.set_create_signup_invite_error(SignupInviteRepoError::QuotaReached);
+        let response = create(repo).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,437 @@
+//! Step-up phone enrollment and challenges; see [`crate::identity::step_up`].
+//!
+//! Every route requires a device signature. Routes that text a code are
+//! rate limited by `rate_limit.sms_per_minute`.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use crate::config::{StepUpConfig, MAX_STEP_UP_TTL_SECS};
+use crate::http::Path;
+use crate::identity::repo::{
+    IdentityRepo, NewStepUpChallenge, StepUpChallengeRecord, StepUpRepoError,
+};
+use crate::identity::step_up::{
+    hash_step_up_code, phone_hint, step_up_required, RiskContext, StepUpAction, STEP_UP_HEADER,
+    STEP_UP_REQUIRED_CODE,
+};
+use crate::reputation::verification::generate_code;
+use crate::sms::{normalize_phone, SmsProvider};
+
+// ─── Request / response types ─────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StepUpPhoneResponse {
+    pub enrolled: bool,
+    /// Last four digits of the enrolled phone, e.g. `•••4567`
+    pub phone_hint: Option<String>,
+    pub enrolled_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EnrollStepUpPhoneRequest {
+    /// Phone number in international form, e.g. `+15551234567`
+    pub phone_number: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateStepUpChallengeRequest {
+    /// `revoke_device` or `remove_phone`
+    pub action: StepUpAction,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConfirmStepUpChallengeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StepUpChallengeResponse {
+    pub challenge_id: Uuid,
+    pub action: StepUpAction,
+    /// Phone the code was sent to
+    pub phone_hint: String,
+    pub expires_at: String,
+    /// Set once the code has been confirmed
+    pub confirmed_at: Option<String>,
+}
+
+impl From<StepUpChallengeRecord> for StepUpChallengeResponse {
+    fn from(record: StepUpChallengeRecord) -> Self {
+        Self {
+            challenge_id: record.id,
+            action: record.action,
+            phone_hint: phone_hint(&record.phone_number),
+            expires_at: record.expires_at.to_rfc3339(),
+            confirmed_at: record.confirmed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+// ─── Handlers ─────────────────────────────────────────────────────────────
+
+/// GET /auth/step-up/phone — the phone enrolled for step-up codes
+#[utoipa::path(
+    get,
+    path = "/auth/step-up/phone",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Enrolled phone, if any", body = StepUpPhoneResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_step_up_phone(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.get_step_up_phone(auth.account_id).await {
+        Ok(phone) => (
+            StatusCode::OK,
+            Json(StepUpPhoneResponse {
+                enrolled: phone.is_some(),
+                phone_hint: phone.as_ref().map(|p| phone_hint(&p.phone_number)),
+                enrolled_at: phone.map(|p| p.enrolled_at.to_rfc3339()),
+            }),
+        )
+            .into_response(),
+        Err(e) => step_up_error_response(&e),
+    }
+}
+
+/// POST /auth/step-up/phone — text a code to enroll a phone
+///
+/// Confirm the code with `POST /auth/step-up/challenges/{challenge_id}/confirm`
+/// to finish enrolling. Only one phone may be enrolled; remove it first to
+/// change numbers.
+#[utoipa::path(
+    post,
+    path = "/auth/step-up/phone",
+    tag = "Identity",
+    request_body = EnrollStepUpPhoneRequest,
+    responses(
+        (status = 202, description = "Code sent", body = StepUpChallengeResponse),
+        (status = 400, description = "Invalid phone number"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "A phone is already enrolled"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "SMS is not configured or sending failed")
+    )
+)]
+pub async fn enroll_step_up_phone(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    sms: Option<Extension<Arc<dyn SmsProvider>>>,
+    config: Option<Extension<StepUpConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let req: EnrollStepUpPhoneRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    let Some(phone_number) = normalize_phone(&req.phone_number) else {
+        return super::bad_request("phone_number must be in international form, e.g. +15551234567");
+    };
+    let Some(Extension(sms)) = sms else {
+        return crate::http::service_unavailable("SMS is not available on this instance");
+    };
+    match repo.get_step_up_phone(auth.account_id).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return super::conflict("A phone is already enrolled; remove it before adding another")
+        }
+        Err(e) => return step_up_error_response(&e),
+    }
+    send_challenge(
+        repo.as_ref(),
+        sms.as_ref(),
+        &config,
+        &auth,
+        StepUpAction::EnrollPhone,
+        &phone_number,
+    )
+    .await
+}
+
+/// DELETE /auth/step-up/phone — stop requiring step-up codes
+///
+/// Always requires a confirmed `remove_phone` challenge in the
+/// `X-Step-Up-Challenge` header.
+#[utoipa::path(
+    delete,
+    path = "/auth/step-up/phone",
+    tag = "Identity",
+    params(
+        ("x-step-up-challenge" = Option<String>, Header, description = "Confirmed remove_phone challenge")
+    ),
+    responses(
+        (status = 204, description = "Phone removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Step-up confirmation required (code STEP_UP_REQUIRED)"),
+        (status = 404, description = "No phone enrolled"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn remove_step_up_phone(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    sms: Option<Extension<Arc<dyn SmsProvider>>>,
+    config: Option<Extension<StepUpConfig>>,
+    headers: HeaderMap,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = require_step_up(
+        repo.as_ref(),
+        sms.is_some(),
+        &config,
+        &auth,
+        &headers,
+        StepUpAction::RemovePhone,
+    )
+    .await
+    {
+        return resp;
+    }
+    match repo.delete_step_up_phone(auth.account_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => step_up_error_response(&e),
+    }
+}
+
+/// POST /auth/step-up/challenges — text a code authorizing an action
+///
+/// Used after a request is refused with `STEP_UP_REQUIRED`. The challenge
+/// can only be confirmed and used by the device that created it.
+#[utoipa::path(
+    post,
+    path = "/auth/step-up/challenges",
+    tag = "Identity",
+    request_body = CreateStepUpChallengeRequest,
+    responses(
+        (status = 202, description = "Code sent", body = StepUpChallengeResponse),
+        (status = 400, description = "Action cannot be requested directly"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No phone enrolled"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "SMS is not configured or sending failed")
+    )
+)]
+pub async fn create_step_up_challenge(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    sms: Option<Extension<Arc<dyn SmsProvider>>>,
+    config: Option<Extension<StepUpConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let req: CreateStepUpChallengeRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    if req.action == StepUpAction::EnrollPhone {
+        return super::bad_request("Use POST /auth/step-up/phone to enroll a phone");
+    }
+    let Some(Extension(sms)) = sms else {
+        return crate::http::service_unavailable("SMS is not available on this instance");
+    };
+    let phone = match repo.get_step_up_phone(auth.account_id).await {
+        Ok(Some(phone)) => phone,
+        Ok(None) => return step_up_error_response(&StepUpRepoError::NoPhone),
+        Err(e) => return step_up_error_response(&e),
+    };
+    send_challenge(
+        repo.as_ref(),
+        sms.as_ref(),
+        &config,
+        &auth,
+        req.action,
+        &phone.phone_number,
+    )
+    .await
+}
+
+/// POST /auth/step-up/challenges/{challenge_id}/confirm — confirm a texted code
+///
+/// Confirming an `enroll_phone` challenge enrolls the phone. Any other
+/// confirmed challenge is passed in `X-Step-Up-Challenge` when retrying
+/// the action, within `step_up.confirmation_ttl_secs`.
+#[utoipa::path(
+    post,
+    path = "/auth/step-up/challenges/{challenge_id}/confirm",
+    tag = "Identity",
+    params(("challenge_id" = Uuid, Path, description = "Challenge to confirm")),
+    request_body = ConfirmStepUpChallengeRequest,
+    responses(
+        (status = 200, description = "Challenge confirmed", body = StepUpChallengeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Incorrect code"),
+        (status = 404, description = "Challenge not found or expired"),
+        (status = 409, description = "Challenge already confirmed"),
+        (status = 429, description = "Too many incorrect codes"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn confirm_step_up_challenge(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    config: Option<Extension<StepUpConfig>>,
+    Path(challenge_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let req: ConfirmStepUpChallengeRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    match repo
+        .confirm_step_up_challenge(
+            challenge_id,
+            auth.account_id,
+            &auth.device_kid,
+            &hash_step_up_code(challenge_id, &req.code),
+            i32::try_from(config.max_code_attempts).unwrap_or(i32::MAX),
+        )
+        .await
+    {
+        Ok(record) => (StatusCode::OK, Json(StepUpChallengeResponse::from(record))).into_response(),
+        Err(e) => step_up_error_response(&e),
+    }
+}
+
+// ─── Shared helpers ───────────────────────────────────────────────────────
+
+/// Refuse `action` unless the risk check passes or the request carries a
+/// confirmed challenge for it in [`STEP_UP_HEADER`], which is consumed.
+///
+/// Nothing is gated when SMS is disabled, since no challenge could be sent.
+#[allow(clippy::result_large_err)]
+pub(crate) async fn require_step_up(
+    repo: &dyn IdentityRepo,
+    sms_enabled: bool,
+    config: &StepUpConfig,
+    auth: &AuthenticatedDevice,
+    headers: &HeaderMap,
+    action: StepUpAction,
+) -> Result<(), Response> {
+    if !sms_enabled {
+        return Ok(());
+    }
+    let phone_enrolled = repo
+        .get_step_up_phone(auth.account_id)
+        .await
+        .map_err(|e| step_up_error_response(&e))?
+        .is_some();
+    let device = repo
+        .get_device_key_by_kid(&auth.device_kid)
+        .await
+        .map_err(|e| super::device_key_repo_error_response(&e))?;
+    let now = Utc::now();
+    let ctx = RiskContext {
+        phone_enrolled,
+        device_created_at: device.created_at,
+        now,
+    };
+    if !step_up_required(action, &ctx, config) {
+        return Ok(());
+    }
+
+    let refused = || {
+        crate::http::forbidden_with_code(
+            STEP_UP_REQUIRED_CODE,
+            &format!("Confirm a {action} step-up challenge and retry with {STEP_UP_HEADER}"),
+        )
+    };
+    let Some(challenge_id) = headers
+        .get(STEP_UP_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<Uuid>().ok())
+    else {
+        return Err(refused());
+    };
+    let ttl = config.confirmation_ttl_secs.min(MAX_STEP_UP_TTL_SECS);
+    let confirmed_after = now - Duration::seconds(i64::try_from(ttl).unwrap_or(0));
+    match repo
+        .consume_step_up_challenge(
+            challenge_id,
+            auth.account_id,
+            &auth.device_kid,
+            action,
+            confirmed_after,
+        )
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(StepUpRepoError::NotFound) => Err(refused()),
+        Err(e) => Err(step_up_error_response(&e)),
+    }
+}
+
+/// Store a challenge for `action` and text its code to `phone_number`.
+async fn send_challenge(
+    repo: &dyn IdentityRepo,
+    sms: &dyn SmsProvider,
+    config: &StepUpConfig,
+    auth: &AuthenticatedDevice,
+    action: StepUpAction,
+    phone_number: &str,
+) -> Response {
+    let id = Uuid::new_v4();
+    let code = generate_code();
+    let ttl_secs = i64::try_from(config.code_ttl_secs.min(MAX_STEP_UP_TTL_SECS)).unwrap_or(0);
+    let record = match repo
+        .create_step_up_challenge(&NewStepUpChallenge {
+            id,
+            account_id: auth.account_id,
+            device_kid: &auth.device_kid,
+            action,
+            phone_number,
+            code_hash: &hash_step_up_code(id, &code),
+            expires_at: Utc::now() + Duration::seconds(ttl_secs),
+        })
+        .await
+    {
+        Ok(record) => record,
+        Err(e) => return step_up_error_response(&e),
+    };
+
+    let message = format!(
+        "Your code to {} is {code}. It expires in {} minutes. \
+         If you did not ask for it, someone may have access to your account.",
+        action.describe(),
+        (ttl_secs + 59) / 60
+    );
+    if let Err(e) = sms.send(phone_number, &message).await {
+        tracing::error!(provider = sms.name(), %action, "Failed to send step-up code: {e}");
+        return crate::http::service_unavailable("Could not send the code");
+    }
+    (
+        StatusCode::ACCEPTED,
+        Json(StepUpChallengeResponse::from(record)),
+    )
+        .into_response()
+}
+
+fn step_up_error_response(e: &StepUpRepoError) -> Response {
+    match e {
+        StepUpRepoError::NotFound => super::not_found("Step-up challenge not found or expired"),
+        StepUpRepoError::NoPhone => super::not_found("No phone enrolled"),
+        StepUpRepoError::InvalidCode => super::forbidden("Incorrect code"),
+        StepUpRepoError::TooManyAttempts => {
+            crate::http::too_many_requests("Too many incorrect codes; request a new one")
+        }
+        StepUpRepoError::AlreadyConfirmed => super::conflict("Challenge already confirmed"),
+        StepUpRepoError::Database(db_err) => {
+            tracing::error!("Step-up repo database error: {db_err}");
+            super::internal_error()
+        }
+    }
+}
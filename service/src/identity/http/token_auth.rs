@@ -0,0 +1,383 @@
+//! Request authentication via API bearer tokens
+//!
+//! API tokens let server-to-server integrations call selected read endpoints
+//! without holding a device key. They are parallel to the signed-request
+//! layer in [`super::auth`], not a replacement: tokens are scoped, read-only,
+//! and cannot manage devices or other tokens.
+//!
+//! Required header:
+//! - `Authorization: Bearer tc_<base64url>`
+//!
+//! Endpoints that accept tokens use [`ReadAuth`], which falls back to
+//! [`AuthenticatedDevice`] when no `Authorization` header is present or it
+//! carries a device session token.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRequest, FromRequestParts, Request},
+    http::{header::AUTHORIZATION, request::Parts},
+    response::{IntoResponse, Response},
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::sessions::session_token;
+use super::token_quota::{ApiTokenQuotas, QuotaError};
+use crate::identity::repo::{ApiTokenRepoError, IdentityRepo};
+use tc_crypto::encode_base64url;
+
+/// Prefix on every issued token, so leaked tokens are easy to recognize.
+pub const API_TOKEN_PREFIX: &str = "tc_";
+
+/// Random bytes per token (256 bits).
+const API_TOKEN_BYTES: usize = 32;
+
+/// Permission granted to an API token. Each scope covers one read endpoint group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum ApiTokenScope {
+    /// `GET /auth/devices`, `GET /auth/devices/{kid}/delegation`
+    #[serde(rename = "devices:read")]
+    DevicesRead,
+    /// `GET /me/endorsements`
+    #[serde(rename = "endorsements:read")]
+    EndorsementsRead,
+    /// `GET /trust/scores/me`, `GET /trust/budget`
+    #[serde(rename = "trust:read")]
+    TrustRead,
+}
+
+impl ApiTokenScope {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::DevicesRead => "devices:read",
+            Self::EndorsementsRead => "endorsements:read",
+            Self::TrustRead => "trust:read",
+        }
+    }
+
+    /// Parse a stored scope string. Unknown scopes return `None`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        [Self::DevicesRead, Self::EndorsementsRead, Self::TrustRead]
+            .into_iter()
+            .find(|s| s.as_str() == value)
+    }
+}
+
+/// Generate a new plaintext API token.
+#[must_use]
+pub fn generate_api_token() -> String {
+    let mut bytes = [0u8; API_TOKEN_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{API_TOKEN_PREFIX}{}", encode_base64url(&bytes))
+}
+
+/// Hash a plaintext token for storage and lookup.
+#[must_use]
+pub fn hash_api_token(token: &str) -> Vec<u8> {
+    Sha256::digest(token.as_bytes()).to_vec()
+}
+
+fn auth_error(msg: &str) -> Response {
+    super::unauthorized(msg)
+}
+
+/// Account authenticated by an API bearer token.
+///
+/// Implements `FromRequestParts` — validates the token, its expiry, and its
+/// revocation status, then counts the request against the token's quota when
+/// an `Arc<ApiTokenQuotas>` extension is present. Scope checks are left to
+/// [`ReadAuth`].
+pub struct ApiTokenAuth {
+    pub account_id: Uuid,
+    pub token_id: Uuid,
+    pub scopes: Vec<ApiTokenScope>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ApiTokenAuth {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let repo = parts
+            .extensions
+            .get::<Arc<dyn IdentityRepo>>()
+            .ok_or_else(|| auth_error("Server misconfiguration"))?
+            .clone();
+
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or_else(|| auth_error("Authorization header must be 'Bearer <token>'"))?
+            .trim();
+        if !token.starts_with(API_TOKEN_PREFIX) {
+            return Err(auth_error("Invalid API token"));
+        }
+
+        let record = repo
+            .get_api_token_by_hash(&hash_api_token(token))
+            .await
+            .map_err(|e| match e {
+                ApiTokenRepoError::NotFound => auth_error("Invalid API token"),
+                ApiTokenRepoError::Database(db_err) => {
+                    tracing::error!("API token lookup failed: {db_err}");
+                    auth_error("Authentication failed")
+                }
+                ApiTokenRepoError::AlreadyRevoked | ApiTokenRepoError::MaxTokensReached => {
+                    tracing::error!("Unexpected repo error during token lookup: {e}");
+                    auth_error("Authentication failed")
+                }
+            })?;
+
+        if record.revoked_at.is_some() {
+            return Err(super::forbidden("API token has been revoked"));
+        }
+        if record
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+        {
+            return Err(auth_error("API token has expired"));
+        }
+        super::require_active_account(repo.as_ref(), record.account_id).await?;
+
+        if let Some(quotas) = parts.extensions.get::<Arc<ApiTokenQuotas>>() {
+            match quotas
+                .consume(repo.as_ref(), record.id, chrono::Utc::now())
+                .await
+            {
+                Ok(()) => {}
+                Err(QuotaError::Exceeded(exceeded)) => return Err(exceeded.into_response()),
+                Err(QuotaError::Repo(e)) => {
+                    tracing::error!("API token usage lookup failed: {e}");
+                    return Err(auth_error("Authentication failed"));
+                }
+            }
+        }
+
+        // Touch last_used_at (fire-and-forget, don't fail the request)
+        let token_id = record.id;
+        tokio::spawn(async move {
+            if let Err(e) = repo.touch_api_token(token_id).await {
+                tracing::warn!("Failed to touch API token {token_id}: {e}");
+            }
+        });
+
+        Ok(Self {
+            account_id: record.account_id,
+            token_id: record.id,
+            scopes: record
+                .scopes
+                .iter()
+                .filter_map(|s| ApiTokenScope::parse(s))
+                .collect(),
+        })
+    }
+}
+
+/// Scope marker for [`ReadAuth`].
+pub trait RequiredScope: Send + Sync + 'static {
+    const SCOPE: ApiTokenScope;
+}
+
+/// Scope markers, one per [`ApiTokenScope`] variant.
+pub mod scope {
+    use super::{ApiTokenScope, RequiredScope};
+
+    pub struct DevicesRead;
+    impl RequiredScope for DevicesRead {
+        const SCOPE: ApiTokenScope = ApiTokenScope::DevicesRead;
+    }
+
+    pub struct EndorsementsRead;
+    impl RequiredScope for EndorsementsRead {
+        const SCOPE: ApiTokenScope = ApiTokenScope::EndorsementsRead;
+    }
+
+    pub struct TrustRead;
+    impl RequiredScope for TrustRead {
+        const SCOPE: ApiTokenScope = ApiTokenScope::TrustRead;
+    }
+}
+
+/// Caller of a read endpoint that accepts either a device signature or an
+/// API token carrying scope `S`.
+///
+/// Requests with an `Authorization` header are authenticated as API tokens,
+/// unless it holds a session token; all others go through
+/// [`AuthenticatedDevice`].
+pub struct ReadAuth<S> {
+    pub account_id: Uuid,
+    /// Set when the caller authenticated with an API token.
+    pub token_id: Option<Uuid>,
+    _scope: PhantomData<S>,
+}
+
+impl<S> ReadAuth<S> {
+    /// Construct a `ReadAuth` for use in unit tests.
+    #[cfg(test)]
+    #[must_use]
+    pub const fn for_test(account_id: Uuid) -> Self {
+        Self {
+            account_id,
+            token_id: None,
+            _scope: PhantomData,
+        }
+    }
+}
+
+impl<St: Send + Sync, S: RequiredScope> FromRequest<St> for ReadAuth<S> {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &St) -> Result<Self, Self::Rejection> {
+        if !req.headers().contains_key(AUTHORIZATION) || session_token(req.headers()).is_some() {
+            let device = AuthenticatedDevice::from_request(req, state).await?;
+            return Ok(Self {
+                account_id: device.account_id,
+                token_id: None,
+                _scope: PhantomData,
+            });
+        }
+
+        let (mut parts, _body) = req.into_parts();
+        let token = ApiTokenAuth::from_request_parts(&mut parts, state).await?;
+        if !token.scopes.contains(&S::SCOPE) {
+            return Err(super::forbidden(&format!(
+                "API token lacks the '{}' scope",
+                S::SCOPE.as_str()
+            )));
+        }
+        Ok(Self {
+            account_id: token.account_id,
+            token_id: Some(token.token_id),
+            _scope: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::{mock::MockIdentityRepo, ApiTokenRecord};
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use chrono::{Duration, Utc};
+    use tower::ServiceExt;
+
+    fn make_router(repo: MockIdentityRepo) -> Router {
+        async fn ok_handler(auth: ReadAuth<scope::DevicesRead>) -> String {
+            auth.account_id.to_string()
+        }
+        Router::new()
+            .route("/test", get(ok_handler))
+            .layer(axum::extract::Extension(
+                Arc::new(repo) as Arc<dyn IdentityRepo>
+            ))
+    }
+
+    fn token_record(scopes: &[&str]) -> ApiTokenRecord {
+        ApiTokenRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            name: "ci".to_string(),
+            scopes: scopes.iter().map(ToString::to_string).collect(),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    async fn call(repo: MockIdentityRepo, authorization: Option<&str>) -> StatusCode {
+        let mut builder = Request::builder().uri("/test");
+        if let Some(value) = authorization {
+            builder = builder.header(AUTHORIZATION, value);
+        }
+        make_router(repo)
+            .oneshot(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[test]
+    fn generated_tokens_are_prefixed_and_unique() {
+        let a = generate_api_token();
+        let b = generate_api_token();
+        assert!(a.starts_with(API_TOKEN_PREFIX));
+        assert_eq!(a.len(), API_TOKEN_PREFIX.len() + 43);
+        assert_ne!(a, b);
+        assert_eq!(hash_api_token(&a).len(), 32);
+    }
+
+    #[test]
+    fn scope_strings_round_trip() {
+        for scope in [
+            ApiTokenScope::DevicesRead,
+            ApiTokenScope::EndorsementsRead,
+            ApiTokenScope::TrustRead,
+        ] {
+            assert_eq!(ApiTokenScope::parse(scope.as_str()), Some(scope));
+            let json = serde_json::to_string(&scope).unwrap();
+            assert_eq!(json, format!("\"{}\"", scope.as_str()));
+        }
+        assert_eq!(ApiTokenScope::parse("devices:write"), None);
+    }
+
+    #[tokio::test]
+    async fn valid_token_with_scope_is_accepted() {
+        let repo = MockIdentityRepo::new();
+        repo.set_api_token_by_hash_result(Ok(token_record(&["devices:read"])));
+        let status = call(repo, Some(&format!("Bearer {}", generate_api_token()))).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn token_without_scope_is_forbidden() {
+        let repo = MockIdentityRepo::new();
+        repo.set_api_token_by_hash_result(Ok(token_record(&["trust:read"])));
+        let status = call(repo, Some(&format!("Bearer {}", generate_api_token()))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn expired_token_is_rejected() {
+        let repo = MockIdentityRepo::new();
+        let mut record = token_record(&["devices:read"]);
+        record.expires_at = Some(Utc::now() - Duration::minutes(1));
+        repo.set_api_token_by_hash_result(Ok(record));
+        let status = call(repo, Some(&format!("Bearer {}", generate_api_token()))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn revoked_token_is_forbidden() {
+        let repo = MockIdentityRepo::new();
+        let mut record = token_record(&["devices:read"]);
+        record.revoked_at = Some(Utc::now());
+        repo.set_api_token_by_hash_result(Ok(record));
+        let status = call(repo, Some(&format!("Bearer {}", generate_api_token()))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn unknown_or_malformed_token_is_unauthorized() {
+        let status = call(MockIdentityRepo::new(), Some("Bearer tc_unknown")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let status = call(MockIdentityRepo::new(), Some("Basic dXNlcjpwYXNz")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn missing_authorization_falls_back_to_device_auth() {
+        // No device headers either: the signed-request extractor rejects it.
+        let status = call(MockIdentityRepo::new(), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
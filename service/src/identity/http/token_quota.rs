@@ -0,0 +1,356 @@
+//! Daily and monthly request quotas for API tokens
+//!
+//! Every request authenticated by [`super::token_auth::ApiTokenAuth`] is
+//! counted here. Counters live in memory and are flushed to the
+//! `api_token_usage` table in batches by [`ApiTokenQuotas::spawn_flusher`];
+//! a token's counter is seeded from Postgres the first time this instance
+//! sees it each day. With several API instances, each enforces against its own
+//! view plus what the others had flushed when it seeded, so a token can
+//! briefly overshoot by up to one flush interval of traffic per instance.
+//!
+//! Requests over quota get `429` with `X-Quota-Limit`, `X-Quota-Remaining`,
+//! `X-Quota-Reset` (Unix seconds), and `Retry-After` headers.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use axum::{
+    http::{header::RETRY_AFTER, HeaderName, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::ErrorResponse;
+use crate::config::ApiTokenConfig;
+use crate::identity::repo::{ApiTokenRepoError, IdentityRepo};
+
+const X_QUOTA_LIMIT: HeaderName = HeaderName::from_static("x-quota-limit");
+const X_QUOTA_REMAINING: HeaderName = HeaderName::from_static("x-quota-remaining");
+const X_QUOTA_RESET: HeaderName = HeaderName::from_static("x-quota-reset");
+
+/// A token's counts for `day` and the month containing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Counter {
+    day: NaiveDate,
+    daily: i64,
+    monthly: i64,
+}
+
+#[derive(Debug, Default)]
+struct QuotaState {
+    counters: HashMap<Uuid, Counter>,
+    /// Increments not yet written to Postgres, keyed by `(token_id, day)`.
+    pending: HashMap<(Uuid, NaiveDate), i64>,
+}
+
+/// Quota window that was exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaPeriod {
+    Daily,
+    Monthly,
+}
+
+/// Returned when a token is over quota; renders as the `429` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    pub period: QuotaPeriod,
+    pub limit: i64,
+    pub reset_at: DateTime<Utc>,
+}
+
+impl IntoResponse for QuotaExceeded {
+    fn into_response(self) -> Response {
+        let window = match self.period {
+            QuotaPeriod::Daily => "daily",
+            QuotaPeriod::Monthly => "monthly",
+        };
+        let retry_after = (self.reset_at - Utc::now()).num_seconds().max(1);
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [
+                (X_QUOTA_LIMIT, self.limit.to_string()),
+                (X_QUOTA_REMAINING, "0".to_string()),
+                (X_QUOTA_RESET, self.reset_at.timestamp().to_string()),
+                (RETRY_AFTER, retry_after.to_string()),
+            ],
+            Json(ErrorResponse {
+                error: format!("API token {window} quota exceeded"),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// Errors returned by [`ApiTokenQuotas::consume`].
+#[derive(Debug, thiserror::Error)]
+pub enum QuotaError {
+    #[error("quota exceeded")]
+    Exceeded(QuotaExceeded),
+    #[error("failed to load token usage: {0}")]
+    Repo(#[from] ApiTokenRepoError),
+}
+
+/// Per-token request counters, shared via `Extension<Arc<ApiTokenQuotas>>`.
+#[derive(Debug)]
+pub struct ApiTokenQuotas {
+    daily_limit: Option<i64>,
+    monthly_limit: Option<i64>,
+    flush_interval: Duration,
+    state: Mutex<QuotaState>,
+}
+
+fn limit(value: u32) -> Option<i64> {
+    (value > 0).then(|| i64::from(value))
+}
+
+fn same_month(a: NaiveDate, b: NaiveDate) -> bool {
+    a.year() == b.year() && a.month() == b.month()
+}
+
+fn next_day(day: NaiveDate) -> DateTime<Utc> {
+    day.succ_opt()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map_or(DateTime::<Utc>::MAX_UTC, |d| d.and_utc())
+}
+
+fn next_month(day: NaiveDate) -> DateTime<Utc> {
+    let (year, month) = if day.month() == 12 {
+        (day.year() + 1, 1)
+    } else {
+        (day.year(), day.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map_or(DateTime::<Utc>::MAX_UTC, |d| d.and_utc())
+}
+
+impl ApiTokenQuotas {
+    #[must_use]
+    pub fn from_config(config: &ApiTokenConfig) -> Self {
+        Self {
+            daily_limit: limit(config.daily_quota),
+            monthly_limit: limit(config.monthly_quota),
+            flush_interval: Duration::from_secs(config.usage_flush_secs),
+            state: Mutex::new(QuotaState::default()),
+        }
+    }
+
+    /// The state holds plain counters, so a panic mid-update leaves nothing
+    /// worth refusing requests over; recover from poisoning.
+    fn state(&self) -> MutexGuard<'_, QuotaState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Count one request for `token_id` at `now`, or reject it if the token
+    /// is over its daily or monthly quota. Rejected requests are not counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuotaError::Exceeded`] when over quota, or
+    /// [`QuotaError::Repo`] if the counter could not be seeded.
+    pub async fn consume(
+        &self,
+        repo: &dyn IdentityRepo,
+        token_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<(), QuotaError> {
+        let today = now.date_naive();
+        let cached = self.state().counters.get(&token_id).copied();
+        let seeded = match cached {
+            Some(c) if c.day == today => c,
+            Some(c) if same_month(c.day, today) => Counter {
+                day: today,
+                daily: 0,
+                monthly: c.monthly,
+            },
+            _ => {
+                let usage = repo.get_api_token_usage(token_id, today).await?;
+                Counter {
+                    day: today,
+                    daily: usage.daily,
+                    monthly: usage.monthly,
+                }
+            }
+        };
+
+        let mut state = self.state();
+        // Another request may have seeded the counter while we were loading
+        let counter = state
+            .counters
+            .entry(token_id)
+            .and_modify(|c| {
+                if c.day != today {
+                    *c = seeded;
+                }
+            })
+            .or_insert(seeded);
+
+        let over_daily = self.daily_limit.filter(|&l| counter.daily >= l);
+        let over_monthly = self.monthly_limit.filter(|&l| counter.monthly >= l);
+        let exceeded = match (over_daily, over_monthly) {
+            (Some(limit), _) => Some(QuotaExceeded {
+                period: QuotaPeriod::Daily,
+                limit,
+                reset_at: next_day(today),
+            }),
+            (None, Some(limit)) => Some(QuotaExceeded {
+                period: QuotaPeriod::Monthly,
+                limit,
+                reset_at: next_month(today),
+            }),
+            (None, None) => {
+                counter.daily += 1;
+                counter.monthly += 1;
+                *state.pending.entry((token_id, today)).or_default() += 1;
+                None
+            }
+        };
+        drop(state);
+
+        exceeded.map_or(Ok(()), |e| Err(QuotaError::Exceeded(e)))
+    }
+
+    /// Write pending increments to Postgres and forget counters from earlier
+    /// days. Failed writes are kept for the next flush.
+    ///
+    /// # Errors
+    ///
+    /// Returns the repo error if the batch could not be written.
+    pub async fn flush(&self, repo: &dyn IdentityRepo) -> Result<(), ApiTokenRepoError> {
+        let today = Utc::now().date_naive();
+        let pending = {
+            let mut state = self.state();
+            state.counters.retain(|_, c| c.day == today);
+            std::mem::take(&mut state.pending)
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let entries: Vec<(Uuid, NaiveDate, i64)> = pending
+            .iter()
+            .map(|(&(token_id, day), &count)| (token_id, day, count))
+            .collect();
+        if let Err(e) = repo.record_api_token_usage(&entries).await {
+            let mut state = self.state();
+            for (key, count) in pending {
+                *state.pending.entry(key).or_default() += count;
+            }
+            drop(state);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Flush counters every `usage_flush_secs` for the life of the process.
+    pub fn spawn_flusher(self: &Arc<Self>, repo: Arc<dyn IdentityRepo>) {
+        let quotas = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(quotas.flush_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = quotas.flush(repo.as_ref()).await {
+                    tracing::warn!("Failed to flush API token usage: {e}");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use crate::identity::repo::ApiTokenUsage;
+    use chrono::TimeZone;
+
+    fn quotas(daily: u32, monthly: u32) -> ApiTokenQuotas {
+        ApiTokenQuotas::from_config(&ApiTokenConfig {
+            daily_quota: daily,
+            monthly_quota: monthly,
+            ..ApiTokenConfig::default()
+        })
+    }
+
+    fn at(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn daily_limit_resets_next_day() {
+        let repo = MockIdentityRepo::new();
+        let quotas = quotas(2, 0);
+        let token = Uuid::new_v4();
+        let day = at(2026, 3, 14);
+
+        quotas.consume(&repo, token, day).await.expect("first");
+        quotas.consume(&repo, token, day).await.expect("second");
+        let Err(QuotaError::Exceeded(exceeded)) = quotas.consume(&repo, token, day).await else {
+            panic!("third request should be over quota");
+        };
+        assert_eq!(exceeded.period, QuotaPeriod::Daily);
+        assert_eq!(exceeded.reset_at, next_day(day.date_naive()));
+
+        quotas
+            .consume(&repo, token, at(2026, 3, 15))
+            .await
+            .expect("new day");
+    }
+
+    #[tokio::test]
+    async fn monthly_limit_seeded_from_recorded_usage() {
+        let repo = MockIdentityRepo::new();
+        repo.set_api_token_usage(ApiTokenUsage {
+            daily: 0,
+            monthly: 5,
+        });
+        let quotas = quotas(0, 5);
+        let result = quotas
+            .consume(&repo, Uuid::new_v4(), at(2026, 12, 31))
+            .await;
+        let Err(QuotaError::Exceeded(exceeded)) = result else {
+            panic!("seeded usage should exhaust the monthly quota");
+        };
+        assert_eq!(exceeded.period, QuotaPeriod::Monthly);
+        assert_eq!(
+            exceeded.reset_at,
+            at(2027, 1, 1) - chrono::Duration::hours(12)
+        );
+    }
+
+    #[tokio::test]
+    async fn flush_writes_pending_counts_once() {
+        let repo = MockIdentityRepo::new();
+        let quotas = quotas(0, 0);
+        let token = Uuid::new_v4();
+        for _ in 0..3 {
+            quotas
+                .consume(&repo, token, Utc::now())
+                .await
+                .expect("unlimited");
+        }
+        quotas.flush(&repo).await.expect("flush");
+        quotas.flush(&repo).await.expect("nothing pending");
+
+        let recorded = repo.recorded_api_token_usage.lock().unwrap().clone();
+        assert_eq!(recorded, vec![(token, Utc::now().date_naive(), 3)]);
+    }
+
+    #[test]
+    fn exceeded_response_carries_quota_headers() {
+        let response = QuotaExceeded {
+            period: QuotaPeriod::Daily,
+            limit: 100,
+            reset_at: Utc::now() + chrono::Duration::hours(1),
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["x-quota-limit"], "100");
+        assert_eq!(response.headers()["x-quota-remaining"], "0");
+        assert!(response.headers().contains_key("x-quota-reset"));
+        assert!(response.headers().contains_key("retry-after"));
+    }
+}
@@ -0,0 +1,456 @@
+//! API token management HTTP handlers
+//!
+//! Endpoints for issuing, listing, and revoking per-account API tokens.
+//! All endpoints require device authentication via signed headers — an API
+//! token can never be used to mint or revoke tokens.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::auth::AuthenticatedDevice;
+use super::token_auth::{generate_api_token, hash_api_token, ApiTokenScope};
+use super::token_quota::ApiTokenQuotas;
+use super::{ErrorResponse, Path};
+use crate::config::AccountsConfig;
+use crate::identity::repo::{
+    ApiTokenConsumerRecord, ApiTokenRecord, ApiTokenRepoError, IdentityRepo,
+};
+
+/// Maximum length of a token name (characters).
+const MAX_TOKEN_NAME_LENGTH: usize = 64;
+
+/// Maximum token lifetime when an expiry is requested (days).
+const MAX_TOKEN_LIFETIME_DAYS: u32 = 365;
+
+/// Longest window accepted by the top-consumers view (days).
+const MAX_USAGE_WINDOW_DAYS: u32 = 90;
+
+/// Most rows returned by the top-consumers view.
+const MAX_USAGE_LIMIT: u32 = 100;
+
+/// API token metadata returned in responses (never includes the token itself)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiTokenInfo {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+impl From<ApiTokenRecord> for ApiTokenInfo {
+    fn from(record: ApiTokenRecord) -> Self {
+        Self {
+            id: record.id,
+            name: record.name,
+            scopes: record.scopes,
+            created_at: record.created_at.to_rfc3339(),
+            expires_at: record.expires_at.map(|t| t.to_rfc3339()),
+            last_used_at: record.last_used_at.map(|t| t.to_rfc3339()),
+            revoked_at: record.revoked_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiTokenListResponse {
+    pub tokens: Vec<ApiTokenInfo>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    /// Read scopes granted to the token (at least one)
+    pub scopes: Vec<ApiTokenScope>,
+    /// Days until the token expires; omit for a non-expiring token
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateApiTokenResponse {
+    /// The bearer token. Shown once; only its hash is stored.
+    pub token: String,
+    #[serde(flatten)]
+    pub info: ApiTokenInfo,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TokenUsageQuery {
+    /// Days of usage to include, counting today (1–90, default 30)
+    pub days: Option<u32>,
+    /// Maximum tokens to return (1–100, default 20)
+    pub limit: Option<u32>,
+}
+
+/// Request count for one token over the requested window
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenUsageInfo {
+    #[schema(value_type = String, format = "uuid")]
+    pub token_id: Uuid,
+    pub token_name: String,
+    #[schema(value_type = String, format = "uuid")]
+    pub account_id: Uuid,
+    pub username: String,
+    pub requests: i64,
+}
+
+impl From<ApiTokenConsumerRecord> for TokenUsageInfo {
+    fn from(record: ApiTokenConsumerRecord) -> Self {
+        Self {
+            token_id: record.token_id,
+            token_name: record.token_name,
+            account_id: record.account_id,
+            username: record.username,
+            requests: record.requests,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenUsageResponse {
+    /// First UTC day included in the counts (YYYY-MM-DD)
+    pub since: String,
+    pub tokens: Vec<TokenUsageInfo>,
+}
+
+fn validate_create_request(req: &CreateApiTokenRequest) -> Result<(String, Vec<String>), String> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err("Token name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_TOKEN_NAME_LENGTH {
+        return Err(format!(
+            "Token name must be at most {MAX_TOKEN_NAME_LENGTH} characters"
+        ));
+    }
+    if req.scopes.is_empty() {
+        return Err("At least one scope is required".to_string());
+    }
+    if let Some(days) = req.expires_in_days {
+        if days == 0 || days > MAX_TOKEN_LIFETIME_DAYS {
+            return Err(format!(
+                "expires_in_days must be between 1 and {MAX_TOKEN_LIFETIME_DAYS}"
+            ));
+        }
+    }
+
+    let mut scopes: Vec<String> = req.scopes.iter().map(|s| s.as_str().to_string()).collect();
+    scopes.sort_unstable();
+    scopes.dedup();
+    Ok((name.to_string(), scopes))
+}
+
+/// POST /auth/tokens — issue a new API token
+#[utoipa::path(
+    post,
+    path = "/auth/tokens",
+    tag = "Identity",
+    request_body = CreateApiTokenRequest,
+    responses(
+        (status = 201, description = "Token issued", body = CreateApiTokenResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Maximum token limit reached"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn create_token(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let req: CreateApiTokenRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+
+    let (name, scopes) = match validate_create_request(&req) {
+        Ok(v) => v,
+        Err(msg) => return super::bad_request(&msg),
+    };
+    let expires_at = req
+        .expires_in_days
+        .map(|days| Utc::now() + Duration::days(i64::from(days)));
+
+    let token = generate_api_token();
+    match repo
+        .create_api_token(
+            auth.account_id,
+            &name,
+            &hash_api_token(&token),
+            &scopes,
+            expires_at,
+        )
+        .await
+    {
+        Ok(record) => (
+            StatusCode::CREATED,
+            Json(CreateApiTokenResponse {
+                token,
+                info: ApiTokenInfo::from(record),
+            }),
+        )
+            .into_response(),
+        Err(e) => api_token_repo_error_response(&e),
+    }
+}
+
+/// GET /auth/tokens — list API tokens for the authenticated account
+#[utoipa::path(
+    get,
+    path = "/auth/tokens",
+    tag = "Identity",
+    responses(
+        (status = 200, description = "Token list", body = ApiTokenListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_tokens(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.list_api_tokens_by_account(auth.account_id).await {
+        Ok(records) => {
+            let tokens = records.into_iter().map(ApiTokenInfo::from).collect();
+            (StatusCode::OK, Json(ApiTokenListResponse { tokens })).into_response()
+        }
+        Err(e) => api_token_repo_error_response(&e),
+    }
+}
+
+/// DELETE /auth/tokens/:id — revoke an API token
+#[utoipa::path(
+    delete,
+    path = "/auth/tokens/{id}",
+    tag = "Identity",
+    params(
+        ("id" = String, Path, description = "ID of the token to revoke")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Token not found"),
+        (status = 409, description = "Token already revoked"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn revoke_token(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Path(token_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.revoke_api_token(token_id, auth.account_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => api_token_repo_error_response(&e),
+    }
+}
+
+/// GET /auth/tokens/usage — busiest API tokens across all accounts (admins only)
+#[utoipa::path(
+    get,
+    path = "/auth/tokens/usage",
+    tag = "Identity",
+    params(TokenUsageQuery),
+    responses(
+        (status = 200, description = "Top consumers, busiest first", body = TokenUsageResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn top_token_consumers(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    quotas: Option<Extension<Arc<ApiTokenQuotas>>>,
+    Query(query): Query<TokenUsageQuery>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let days = query.days.unwrap_or(30);
+    let limit = query.limit.unwrap_or(20);
+    if days == 0 || days > MAX_USAGE_WINDOW_DAYS {
+        return super::bad_request(&format!(
+            "days must be between 1 and {MAX_USAGE_WINDOW_DAYS}"
+        ));
+    }
+    if limit == 0 || limit > MAX_USAGE_LIMIT {
+        return super::bad_request(&format!("limit must be between 1 and {MAX_USAGE_LIMIT}"));
+    }
+
+    if !accounts_config.is_admin(auth.account_id) {
+        return super::forbidden("Only admins can view token usage");
+    }
+
+    // Include this instance's unflushed counts
+    if let Some(Extension(quotas)) = quotas {
+        if let Err(e) = quotas.flush(repo.as_ref()).await {
+            tracing::warn!("Failed to flush API token usage: {e}");
+        }
+    }
+
+    let since = Utc::now().date_naive() - Duration::days(i64::from(days) - 1);
+    match repo.top_api_token_consumers(since, i64::from(limit)).await {
+        Ok(records) => (
+            StatusCode::OK,
+            Json(TokenUsageResponse {
+                since: since.to_string(),
+                tokens: records.into_iter().map(TokenUsageInfo::from).collect(),
+            }),
+        )
+            .into_response(),
+        Err(e) => api_token_repo_error_response(&e),
+    }
+}
+
+fn api_token_repo_error_response(e: &ApiTokenRepoError) -> axum::response::Response {
+    match e {
+        ApiTokenRepoError::NotFound => super::not_found("Token not found"),
+        ApiTokenRepoError::AlreadyRevoked => super::conflict("Token already revoked"),
+        ApiTokenRepoError::MaxTokensReached => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                error: "Maximum token limit reached".to_string(),
+            }),
+        )
+            .into_response(),
+        ApiTokenRepoError::Database(db_err) => {
+            tracing::error!("API token operation failed: {db_err}");
+            super::internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use axum::body::{to_bytes, Bytes};
+    use tc_crypto::Kid;
+
+    fn auth_with_body(account_id: Uuid, body: &serde_json::Value) -> AuthenticatedDevice {
+        AuthenticatedDevice::for_test(
+            account_id,
+            Kid::derive(&[0xAAu8; 32]),
+            Bytes::from(body.to_string()),
+        )
+    }
+
+    async fn create(repo: MockIdentityRepo, body: &serde_json::Value) -> axum::response::Response {
+        create_token(
+            Extension(Arc::new(repo) as Arc<dyn IdentityRepo>),
+            auth_with_body(Uuid::new_v4(), body),
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn create_returns_token_once_with_sorted_scopes() {
+        let response = create(
+            MockIdentityRepo::new(),
+            &serde_json::json!({
+                "name": "  CI exporter ",
+                "scopes": ["trust:read", "devices:read", "trust:read"],
+                "expires_in_days": 30
+            }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert!(payload["token"].as_str().unwrap().starts_with("tc_"));
+        assert_eq!(payload["name"], "CI exporter");
+        assert_eq!(
+            payload["scopes"],
+            serde_json::json!(["devices:read", "trust:read"])
+        );
+        assert!(payload["expires_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn create_rejects_invalid_requests() {
+        for body in [
+            serde_json::json!({ "name": " ", "scopes": ["devices:read"] }),
+            serde_json::json!({ "name": "ci", "scopes": [] }),
+            serde_json::json!({ "name": "ci", "scopes": ["devices:write"] }),
+            serde_json::json!({ "name": "ci", "scopes": ["devices:read"], "expires_in_days": 0 }),
+        ] {
+            let response = create(MockIdentityRepo::new(), &body).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_at_limit_returns_422() {
+        let repo = MockIdentityRepo::new();
+        repo.set_create_api_token_error(ApiTokenRepoError::MaxTokensReached);
+        let response = create(
+            repo,
+            &serde_json::json!({ "name": "ci", "scopes": ["devices:read"] }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn revoke_maps_repo_errors() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        repo.set_revoke_api_token_result(Err(ApiTokenRepoError::AlreadyRevoked));
+        let response = revoke_token(
+            Extension(repo as Arc<dyn IdentityRepo>),
+            Path(Uuid::new_v4()),
+            auth_with_body(Uuid::new_v4(), &serde_json::Value::Null),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    async fn usage_as(account_id: Uuid, admin: Uuid) -> axum::response::Response {
+        let repo = MockIdentityRepo::new();
+        let config = AccountsConfig {
+            admin_account_ids: vec![admin],
+        };
+        top_token_consumers(
+            Extension(Arc::new(repo) as Arc<dyn IdentityRepo>),
+            Some(Extension(config)),
+            None,
+            Query(TokenUsageQuery {
+                days: None,
+                limit: None,
+            }),
+            auth_with_body(account_id, &serde_json::Value::Null),
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn usage_view_is_admin_only() {
+        let admin = Uuid::new_v4();
+        assert_eq!(
+            usage_as(Uuid::new_v4(), admin).await.status(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(usage_as(admin, admin).await.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,373 @@
+//! Username availability and username change handlers
+//!
+//! Clients check a name with `GET /auth/usernames/{name}/availability` before
+//! signing up or renaming, instead of finding out from a 409. The check is
+//! unauthenticated, so it does not say whether a valid name is reserved,
+//! held by an account or quarantined; see [`UsernameUnavailableReason`]. An account
+//! renames itself with a signed `PATCH /auth/accounts/me/username`, at most
+//! once every `usernames.change_cooldown_days`; each change is recorded in the
+//! account's sigchain. See [`crate::identity::username`].
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::auth::AuthenticatedDevice;
+use super::sigchain::sigchain_link;
+use super::Path;
+use crate::identity::repo::{AccountRepoError, IdentityRepo, UsernameChange};
+use crate::identity::sigchain::SigchainLinkType;
+use crate::identity::username::{UsernameError, UsernamePolicy};
+
+/// Why a name cannot be claimed
+///
+/// Reserved, taken and recently released names all report `unavailable`, so
+/// an anonymous caller cannot tell a name held by an account from one the
+/// instance reserved or quarantined. That a valid name is unavailable at all
+/// is no more than signup's 409 reveals. The cost is that clients cannot tell
+/// the user why a valid name is refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UsernameUnavailableReason {
+    /// Breaks the instance's username rules; see `message`
+    Invalid,
+    /// Reserved, held by an account, or given up by one within
+    /// `usernames.release_cooldown_days`
+    Unavailable,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsernameAvailabilityResponse {
+    /// The name as checked, trimmed
+    pub name: String,
+    pub available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<UsernameUnavailableReason>,
+    /// The rule the name breaks, for `invalid`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeUsernameRequest {
+    /// Follows the username rules
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChangeUsernameResponse {
+    pub username: String,
+    /// Quarantined for other accounts for `usernames.release_cooldown_days`
+    pub previous_username: String,
+    pub changed_at: String,
+    /// Earliest time of the next change
+    pub next_change_at: String,
+}
+
+/// GET /auth/usernames/:name/availability — whether a new account could take a name
+#[utoipa::path(
+    get,
+    path = "/auth/usernames/{name}/availability",
+    tag = "Identity",
+    params(
+        ("name" = String, Path, description = "Username to check")
+    ),
+    responses(
+        (status = 200, description = "Availability of the name", body = UsernameAvailabilityResponse),
+        (status = 429, description = "Rate limited"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn check_username_availability(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    let name = name.trim().to_string();
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    let (reason, message) = match usernames.validate(&name) {
+        Ok(()) => match is_claimed(repo.as_ref(), &usernames, &name).await {
+            Ok(claimed) => (
+                claimed.then_some(UsernameUnavailableReason::Unavailable),
+                None,
+            ),
+            Err(resp) => return resp,
+        },
+        Err(UsernameError::Reserved) => (Some(UsernameUnavailableReason::Unavailable), None),
+        Err(e) => (
+            Some(UsernameUnavailableReason::Invalid),
+            Some(e.to_string()),
+        ),
+    };
+    (
+        StatusCode::OK,
+        Json(UsernameAvailabilityResponse {
+            name,
+            available: reason.is_none(),
+            reason,
+            message,
+        }),
+    )
+        .into_response()
+}
+
+/// Whether a valid name is held or quarantined.
+async fn is_claimed(
+    repo: &dyn IdentityRepo,
+    usernames: &UsernamePolicy,
+    name: &str,
+) -> Result<bool, Response> {
+    match repo.resolve_account_handle(name).await {
+        Ok(_) => return Ok(true),
+        Err(AccountRepoError::NotFound) => {}
+        Err(e) => {
+            tracing::error!("Failed to resolve handle: {e}");
+            return Err(super::internal_error());
+        }
+    }
+    usernames
+        .is_cooling_off(repo, name, None)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check released handles: {e}");
+            super::internal_error()
+        })
+}
+
+/// PATCH /auth/accounts/me/username — rename the authenticated account
+#[utoipa::path(
+    patch,
+    path = "/auth/accounts/me/username",
+    tag = "Identity",
+    request_body = ChangeUsernameRequest,
+    responses(
+        (status = 200, description = "Username changed", body = ChangeUsernameResponse),
+        (status = 400, description = "Invalid or unchanged username"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Name taken by another account, or recently given up by one"),
+        (status = 429, description = "Changed too recently; Retry-After gives the seconds left"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn change_username(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    usernames: Option<Extension<Arc<UsernamePolicy>>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let req: ChangeUsernameRequest = match auth.json() {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    let username = req.username.trim();
+    let usernames = usernames.map(|Extension(p)| p).unwrap_or_default();
+    if let Err(e) = usernames.validate(username) {
+        return super::bad_request(&e.to_string());
+    }
+    match usernames
+        .is_cooling_off(repo.as_ref(), username, Some(auth.account_id))
+        .await
+    {
+        Ok(false) => {}
+        Ok(true) => return super::conflict("Username already taken"),
+        Err(e) => {
+            tracing::error!("Failed to check released handles: {e}");
+            return super::internal_error();
+        }
+    }
+
+    let link = match sigchain_link(&auth, SigchainLinkType::UsernameChanged) {
+        Ok(link) => link,
+        Err(resp) => return resp,
+    };
+    let now = Utc::now();
+    let cooldown = usernames.change_cooldown();
+    match repo
+        .change_account_username(auth.account_id, username, now - cooldown, &link)
+        .await
+    {
+        Ok(UsernameChange::Changed {
+            previous,
+            changed_at,
+        }) => {
+            tracing::info!(
+                account_id = %auth.account_id,
+                previous = %previous,
+                username = %username,
+                "Username changed"
+            );
+            (
+                StatusCode::OK,
+                Json(ChangeUsernameResponse {
+                    username: username.to_string(),
+                    previous_username: previous,
+                    changed_at: changed_at.to_rfc3339(),
+                    next_change_at: (changed_at + cooldown).to_rfc3339(),
+                }),
+            )
+                .into_response()
+        }
+        Ok(UsernameChange::Unchanged) => super::bad_request("That is already your username"),
+        Ok(UsernameChange::TooSoon { last_changed_at }) => {
+            change_too_soon(last_changed_at + cooldown, now)
+        }
+        Err(AccountRepoError::DuplicateUsername) => super::conflict("Username already taken"),
+        Err(AccountRepoError::NotFound) => super::not_found("Account not found"),
+        Err(e) => {
+            tracing::error!("Failed to change username: {e}");
+            super::internal_error()
+        }
+    }
+}
+
+/// 429 until `next_change_at`, with `Retry-After` in whole seconds.
+fn change_too_soon(next_change_at: DateTime<Utc>, now: DateTime<Utc>) -> Response {
+    let mut response = crate::http::too_many_requests(&format!(
+        "Username was changed recently; it can be changed again at {}",
+        next_change_at.to_rfc3339()
+    ));
+    let secs = u64::try_from((next_change_at - now).num_seconds().max(1)).unwrap_or(1);
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::{mock::MockIdentityRepo, AccountRecord, ReleasedHandleRecord};
+    use axum::body::{to_bytes, Bytes};
+    use tc_crypto::Kid;
+    use uuid::Uuid;
+
+    async fn check(repo: MockIdentityRepo, name: &str) -> serde_json::Value {
+        let response = check_username_availability(
+            Extension(Arc::new(repo) as Arc<dyn IdentityRepo>),
+            None,
+            Path(name.to_string()),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        serde_json::from_slice(&body).expect("json")
+    }
+
+    async fn change(
+        repo: &Arc<MockIdentityRepo>,
+        account_id: Uuid,
+        username: &str,
+    ) -> axum::response::Response {
+        change_username(
+            Extension(Arc::clone(repo) as Arc<dyn IdentityRepo>),
+            None,
+            AuthenticatedDevice::for_test(
+                account_id,
+                Kid::derive(&[0xAAu8; 32]),
+                Bytes::from(serde_json::json!({ "username": username }).to_string()),
+            ),
+        )
+        .await
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn availability_hides_why_a_valid_name_is_unavailable() {
+        let free = check(MockIdentityRepo::new(), " civic-bob ").await;
+        assert_eq!(free["name"], "civic-bob");
+        assert_eq!(free["available"], true);
+        assert!(free.get("reason").is_none());
+
+        let invalid = check(MockIdentityRepo::new(), "ab").await;
+        assert_eq!(invalid["available"], false);
+        assert_eq!(invalid["reason"], "invalid");
+        assert!(invalid["message"].as_str().is_some());
+
+        let reserved = check(MockIdentityRepo::new(), "Admin").await;
+        assert_eq!(reserved["available"], false);
+        assert_eq!(reserved["reason"], "unavailable");
+        assert!(reserved.get("message").is_none());
+
+        let repo = MockIdentityRepo::new();
+        repo.set_account_by_username_result(Ok(AccountRecord {
+            id: Uuid::new_v4(),
+            username: "civic-bob".to_string(),
+            root_pubkey: String::new(),
+            root_kid: Kid::derive(&[1u8; 32]),
+        }));
+        assert_eq!(check(repo, "civic-bob").await["reason"], "unavailable");
+
+        let repo = MockIdentityRepo::new();
+        repo.released_handles
+            .lock()
+            .expect("lock")
+            .push(ReleasedHandleRecord {
+                handle: "civic-bob".to_string(),
+                account_id: Uuid::new_v4(),
+                released_at: Utc::now(),
+            });
+        assert_eq!(check(repo, "civic-bob").await["reason"], "unavailable");
+    }
+
+    #[tokio::test]
+    async fn change_records_a_sigchain_link_and_starts_the_cooldown() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let account_id = Uuid::new_v4();
+
+        let response = change(&repo, account_id, " civic-bob ").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), 4096).await.expect("body");
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("json");
+        assert_eq!(payload["username"], "civic-bob");
+        let links = repo.sigchain_links.lock().expect("lock").clone();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].link_type, "username_changed");
+        assert_eq!(links[0].account_id, account_id);
+
+        let response = change(&repo, account_id, "civic-bobby").await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: i64 = response.headers()[RETRY_AFTER]
+            .to_str()
+            .expect("header")
+            .parse()
+            .expect("seconds");
+        assert!(retry_after > 29 * 24 * 3600);
+
+        let response = change(&repo, account_id, "civic-bob").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(repo.sigchain_links.lock().expect("lock").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn change_refuses_invalid_and_taken_names() {
+        let repo = Arc::new(MockIdentityRepo::new());
+        let response = change(&repo, Uuid::new_v4(), "admin").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        *repo.change_username_error.lock().expect("lock") =
+            Some(AccountRepoError::DuplicateUsername);
+        let response = change(&repo, Uuid::new_v4(), "civic-bob").await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        repo.released_handles
+            .lock()
+            .expect("lock")
+            .push(ReleasedHandleRecord {
+                handle: "civic-alice".to_string(),
+                account_id: Uuid::new_v4(),
+                released_at: Utc::now(),
+            });
+        let response = change(&repo, Uuid::new_v4(), "civic-alice").await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert!(repo.sigchain_links.lock().expect("lock").is_empty());
+    }
+}
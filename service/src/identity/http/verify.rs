@@ -0,0 +1,258 @@
+//! Public account-control verification for third-party sites.
+//!
+//! A third party that wants proof a visitor controls a given account
+//! generates a random challenge, has the visitor's browser sign
+//! [`verification_message`] with one of their device keys, and calls
+//! `GET /api/v1/verify?kid=&challenge=&signature=`.
+//!
+//! The server never signs anything itself (see the trust boundary rules).
+//! The returned statement is backed by user-held keys only: the device's
+//! signature over the challenge, and the root-signed device certificate
+//! that binds the device KID to the account. Both are echoed back so the
+//! third party can re-check them without trusting this server's verdict.
+//!
+//! The route has its own CORS policy (any origin, `GET` only, no
+//! credentials) and a per-IP rate limit, and is mounted outside the global
+//! CORS layer in `main.rs`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::Extension,
+    http::{Method, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use tc_crypto::{decode_base64url, encode_base64url, verify_ed25519, Kid};
+use tower_http::cors::{Any, CorsLayer};
+use utoipa::{IntoParams, ToSchema};
+
+use super::devices::{verify_delegation, DelegationStatus};
+use super::{bad_request, internal_error, not_found};
+use crate::config::RateLimitConfig;
+use crate::http::rate_limit::make_governor_layer;
+use crate::http::Query;
+use crate::identity::repo::{DeviceKeyRepoError, IdentityRepo};
+use crate::identity::service::DevicePubkey;
+
+/// Domain-separation prefix for challenge signatures, so a verification
+/// signature can never be replayed as a request or certificate signature.
+pub const VERIFY_CHALLENGE_DOMAIN: &str = "tc-verify-challenge:v1\n";
+
+/// Shortest accepted challenge; shorter values are too guessable.
+pub const MIN_CHALLENGE_LEN: usize = 16;
+
+/// Longest accepted challenge.
+pub const MAX_CHALLENGE_LEN: usize = 256;
+
+/// Bytes a device signs to answer a verification challenge.
+#[must_use]
+pub fn verification_message(challenge: &str) -> Vec<u8> {
+    let mut message = Vec::with_capacity(VERIFY_CHALLENGE_DOMAIN.len() + challenge.len());
+    message.extend_from_slice(VERIFY_CHALLENGE_DOMAIN.as_bytes());
+    message.extend_from_slice(challenge.as_bytes());
+    message
+}
+
+/// Check a third-party challenge for length and charset (printable ASCII).
+///
+/// # Errors
+///
+/// Returns a human-readable message describing the violated rule.
+pub fn validate_challenge(challenge: &str) -> Result<(), String> {
+    if challenge.len() < MIN_CHALLENGE_LEN || challenge.len() > MAX_CHALLENGE_LEN {
+        return Err(format!(
+            "challenge must be {MIN_CHALLENGE_LEN}-{MAX_CHALLENGE_LEN} characters"
+        ));
+    }
+    if !challenge.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err("challenge must be printable ASCII without spaces".to_string());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyQuery {
+    /// Device KID that signed the challenge
+    #[param(value_type = String)]
+    pub kid: Kid,
+    /// Challenge issued by the third party (16-256 printable ASCII chars)
+    pub challenge: String,
+    /// base64url Ed25519 signature by the device over the challenge message
+    pub signature: String,
+}
+
+/// Whether the signing device is still delegated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceStatus {
+    Active,
+    Revoked,
+}
+
+/// Statement binding a device KID to an account, backed by user signatures.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationStatement {
+    #[schema(value_type = String)]
+    pub kid: Kid,
+    pub username: String,
+    #[schema(value_type = String)]
+    pub root_kid: Kid,
+    /// base64url account root public key
+    pub root_pubkey: String,
+    /// base64url device public key
+    pub device_pubkey: String,
+    /// base64url root signature over the device key (see the delegation endpoint)
+    pub certificate: String,
+    pub certificate_version: Option<i32>,
+    pub certificate_timestamp: Option<i64>,
+    pub delegation: DelegationStatus,
+    pub device_status: DeviceStatus,
+    /// Challenge exactly as supplied
+    pub challenge: String,
+    /// Whether `signature` verifies over the challenge message
+    pub signature_valid: bool,
+    /// `true` only when the signature is valid, the device is active, and the
+    /// delegation certificate verifies
+    pub verified: bool,
+}
+
+/// Router for `/api/v1/verify` with its own rate limit and CORS policy.
+///
+/// The caller must supply the `IdentityRepo` extension and mount this outside
+/// any stricter CORS layer, which would otherwise answer preflights first.
+pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
+    let r = Router::new().route("/api/v1/verify", get(verify));
+    let r = if let Some(layer) =
+        make_governor_layer(rate_limit_config.verify_per_minute, rate_limit_config)
+    {
+        r.layer(layer)
+    } else {
+        r
+    };
+    r.layer(
+        CorsLayer::new()
+            .allow_methods([Method::GET])
+            .allow_headers(Any)
+            .allow_origin(Any)
+            .max_age(Duration::from_secs(3600)),
+    )
+}
+
+/// Verify that a device of some account signed a third-party challenge.
+///
+/// Returns 200 with `verified: false` when the KID exists but the signature,
+/// device status, or delegation does not check out, so widgets can explain
+/// why verification failed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/verify",
+    tag = "Identity",
+    params(VerifyQuery),
+    responses(
+        (status = 200, description = "Verification statement", body = VerificationStatement),
+        (status = 400, description = "Malformed KID, challenge, or signature"),
+        (status = 404, description = "Unknown device KID"),
+        (status = 429, description = "Too many verification requests"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn verify(
+    Extension(repo): Extension<Arc<dyn IdentityRepo>>,
+    Query(query): Query<VerifyQuery>,
+) -> impl IntoResponse {
+    let kid = &query.kid;
+    if let Err(msg) = validate_challenge(&query.challenge) {
+        return bad_request(&msg);
+    }
+    let Some(signature) = decode_base64url(&query.signature)
+        .ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+    else {
+        return bad_request("signature must be a base64url Ed25519 signature");
+    };
+
+    let record = match repo.get_device_key_by_kid(kid).await {
+        Ok(r) => r,
+        Err(DeviceKeyRepoError::NotFound) => return not_found("Device not found"),
+        Err(e) => return super::device_key_repo_error_response(&e),
+    };
+    let account = match repo.get_account_by_id(record.account_id).await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to look up account for verification: {e}");
+            return internal_error();
+        }
+    };
+    let root_pubkey = match super::decode_account_root_pubkey(&account) {
+        Ok(k) => k,
+        Err(resp) => return resp,
+    };
+    let device_pubkey = match DevicePubkey::from_base64url(&record.device_pubkey) {
+        Ok(k) => k,
+        Err(e) => {
+            tracing::error!("Corrupted device pubkey for {kid}: {e}");
+            return internal_error();
+        }
+    };
+
+    let signature_valid = verify_ed25519(
+        device_pubkey.as_bytes(),
+        &verification_message(&query.challenge),
+        &signature,
+    )
+    .is_ok();
+    let delegation = verify_delegation(&root_pubkey, &record);
+    let device_status = if record.revoked_at.is_none() {
+        DeviceStatus::Active
+    } else {
+        DeviceStatus::Revoked
+    };
+
+    (
+        StatusCode::OK,
+        Json(VerificationStatement {
+            kid: record.device_kid,
+            username: account.username,
+            root_kid: account.root_kid,
+            root_pubkey: account.root_pubkey,
+            device_pubkey: record.device_pubkey,
+            certificate: encode_base64url(&record.certificate),
+            certificate_version: record.certificate_version,
+            certificate_timestamp: record.certificate_timestamp,
+            delegation,
+            device_status,
+            challenge: query.challenge,
+            signature_valid,
+            verified: signature_valid
+                && device_status == DeviceStatus::Active
+                && delegation == DelegationStatus::Valid,
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_is_domain_separated() {
+        let message = verification_message("0123456789abcdef");
+        assert!(message.starts_with(VERIFY_CHALLENGE_DOMAIN.as_bytes()));
+        assert!(message.ends_with(b"0123456789abcdef"));
+    }
+
+    #[test]
+    fn challenge_length_and_charset() {
+        assert!(validate_challenge("0123456789abcdef").is_ok());
+        assert!(validate_challenge("short").is_err());
+        assert!(validate_challenge(&"a".repeat(MAX_CHALLENGE_LEN + 1)).is_err());
+        assert!(validate_challenge("has spaces in the challenge").is_err());
+        assert!(validate_challenge("non-ascii-challenge-é").is_err());
+    }
+}
@@ -2,6 +2,16 @@
 //!
 //! Provides cryptographic identity management with Ed25519 keys.
 
+pub mod capabilities;
+pub mod crl;
+pub mod deletion;
 pub mod http;
+pub mod portability;
+pub mod recovery_code;
 pub mod repo;
 pub mod service;
+pub mod sigchain;
+pub mod signed_request;
+pub mod status;
+pub mod step_up;
+pub mod username;
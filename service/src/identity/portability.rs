@@ -0,0 +1,910 @@
+//! Identity data export and import for instance migration and restore drills.
+//!
+//! Accounts are written as JSON Lines, one [`AccountExport`] per line with its
+//! devices, aliases, and encrypted backup inlined. Import re-verifies every record before
+//! touching the database — root and device KIDs must match their public keys,
+//! device certificates must verify against the root key, rotated keys must be
+//! cross-signed by the key they replaced, and backups must parse
+//! as valid envelopes bound to the root KID — so a tampered or truncated dump is
+//! rejected as a whole rather than partially restored.
+//!
+//! Only public material and client-encrypted envelopes are exported; the server
+//! never holds private keys, so a dump contains nothing it could not already
+//! serve.
+
+use std::io::{BufRead, Write};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tc_crypto::{decode_base64url, encode_base64url, verify_ed25519, BackupEnvelope, Kid};
+use uuid::Uuid;
+
+use super::recovery_code;
+use super::service::{
+    device_certificate_message, device_rotation_message, CertificateSignature, DevicePubkey,
+    RootPubkey,
+};
+
+/// Version written to every exported line. Import rejects other versions.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Accounts read from the database per export batch.
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// One exported account with its devices and backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountExport {
+    pub format_version: u32,
+    pub id: Uuid,
+    pub username: String,
+    pub root_pubkey: String,
+    pub root_kid: String,
+    pub created_at: DateTime<Utc>,
+    pub devices: Vec<DeviceExport>,
+    #[serde(default)]
+    pub aliases: Vec<AliasExport>,
+    pub backup: Option<BackupExport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceExport {
+    pub id: Uuid,
+    pub device_kid: String,
+    pub device_pubkey: String,
+    pub device_name: String,
+    /// Base64url-encoded root signature over the device public key.
+    pub certificate: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub revoked_by_kid: Option<String>,
+    /// The revoking device's signed request, kept for the account's CRL.
+    #[serde(default)]
+    pub revocation_envelope: Option<serde_json::Value>,
+    #[serde(default)]
+    pub certificate_version: Option<i32>,
+    /// Login timestamp bound into a version 2 certificate.
+    #[serde(default)]
+    pub certificate_timestamp: Option<i64>,
+    /// Device whose key this one replaced, if created by a rotation.
+    #[serde(default)]
+    pub rotated_from_kid: Option<String>,
+    /// Base64url-encoded signature by `rotated_from_kid` over the rotation.
+    #[serde(default)]
+    pub rotation_signature: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasExport {
+    pub alias: String,
+    pub reserved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupExport {
+    pub id: Uuid,
+    pub kid: String,
+    /// Base64url-encoded encrypted backup envelope.
+    pub encrypted_backup: String,
+    pub created_at: DateTime<Utc>,
+    /// Stored recovery code hash, if one is required (see
+    /// `identity::recovery_code`). Exports written before Argon2id hashing
+    /// carry a bare base64url SHA-256 digest instead, which import accepts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_code_hash: Option<String>,
+}
+
+/// Counts reported by [`import_accounts`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Accounts inserted (or that would be inserted, in dry-run mode).
+    pub imported: usize,
+    /// Accounts skipped because an account with the same ID already exists.
+    pub skipped: usize,
+}
+
+// ─── Error types ───────────────────────────────────────────────────────────
+
+/// Integrity failure for a single exported account.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum IntegrityError {
+    #[error("unsupported format_version {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid root public key")]
+    InvalidRootKey,
+    #[error("root_kid does not match root public key")]
+    RootKidMismatch,
+    #[error("device {0}: invalid public key")]
+    InvalidDeviceKey(String),
+    #[error("device {0}: device_kid does not match public key")]
+    DeviceKidMismatch(String),
+    #[error("device {0}: certificate does not verify against root key")]
+    InvalidCertificate(String),
+    #[error("device {0}: rotation is not signed by the key it replaced")]
+    InvalidRotation(String),
+    #[error("backup kid does not match root_kid")]
+    BackupKidMismatch,
+    #[error("invalid backup envelope: {0}")]
+    InvalidBackup(String),
+}
+
+impl IntegrityError {
+    /// Stable `snake_case` identifier for machine-readable reports.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::UnsupportedVersion(_) => "unsupported_version",
+            Self::InvalidRootKey => "invalid_root_key",
+            Self::RootKidMismatch => "root_kid_mismatch",
+            Self::InvalidDeviceKey(_) => "invalid_device_key",
+            Self::DeviceKidMismatch(_) => "device_kid_mismatch",
+            Self::InvalidCertificate(_) => "invalid_certificate",
+            Self::InvalidRotation(_) => "invalid_rotation",
+            Self::BackupKidMismatch => "backup_kid_mismatch",
+            Self::InvalidBackup(_) => "invalid_backup",
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PortabilityError {
+    #[error("line {line}: malformed record: {source}")]
+    Malformed {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("line {line} (account {id}): {source}")]
+    Integrity {
+        line: usize,
+        id: Uuid,
+        source: IntegrityError,
+    },
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+// ─── Verification ──────────────────────────────────────────────────────────
+
+/// An exported account whose signatures and key bindings have been checked,
+/// with its binary fields already decoded for insertion.
+pub struct VerifiedAccount {
+    pub record: AccountExport,
+    certificates: Vec<[u8; 64]>,
+    rotation_signatures: Vec<Option<[u8; 64]>>,
+    backup: Option<BackupEnvelope>,
+}
+
+/// Re-verify the signatures and key bindings of an exported account.
+///
+/// # Errors
+///
+/// Returns the first [`IntegrityError`] found.
+pub fn verify_account(record: AccountExport) -> Result<VerifiedAccount, IntegrityError> {
+    if record.format_version != EXPORT_FORMAT_VERSION {
+        return Err(IntegrityError::UnsupportedVersion(record.format_version));
+    }
+
+    let root_pubkey = verify_root(&record)?;
+
+    let certificates = record
+        .devices
+        .iter()
+        .map(|device| verify_device(&root_pubkey, device))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rotation_signatures = record
+        .devices
+        .iter()
+        .map(|device| verify_rotation(&record.devices, device))
+        .collect::<Result<Vec<_>, _>>()?;
+    let backup = record
+        .backup
+        .as_ref()
+        .map(|backup| verify_backup(&record.root_kid, backup))
+        .transpose()?;
+
+    Ok(VerifiedAccount {
+        record,
+        certificates,
+        rotation_signatures,
+        backup,
+    })
+}
+
+/// Decode the root key and check it matches `root_kid`.
+///
+/// # Errors
+///
+/// Returns `InvalidRootKey` or `RootKidMismatch`.
+pub fn verify_root(record: &AccountExport) -> Result<RootPubkey, IntegrityError> {
+    let root_pubkey = RootPubkey::from_base64url(&record.root_pubkey)
+        .map_err(|_| IntegrityError::InvalidRootKey)?;
+    if root_pubkey.kid().as_str() != record.root_kid {
+        return Err(IntegrityError::RootKidMismatch);
+    }
+    Ok(root_pubkey)
+}
+
+/// Check a device's KID binding and its certificate under `root_pubkey`,
+/// returning the decoded certificate.
+///
+/// # Errors
+///
+/// Returns `InvalidDeviceKey`, `DeviceKidMismatch`, or `InvalidCertificate`.
+pub fn verify_device(
+    root_pubkey: &RootPubkey,
+    device: &DeviceExport,
+) -> Result<[u8; 64], IntegrityError> {
+    let device_pubkey = DevicePubkey::from_base64url(&device.device_pubkey)
+        .map_err(|_| IntegrityError::InvalidDeviceKey(device.device_kid.clone()))?;
+    if device_pubkey.kid().as_str() != device.device_kid {
+        return Err(IntegrityError::DeviceKidMismatch(device.device_kid.clone()));
+    }
+    let cert = CertificateSignature::from_base64url(&device.certificate)
+        .map_err(|_| IntegrityError::InvalidCertificate(device.device_kid.clone()))?;
+    verify_ed25519(
+        root_pubkey.as_bytes(),
+        &device_certificate_message(device_pubkey.as_bytes(), device.certificate_timestamp),
+        cert.as_bytes(),
+    )
+    .map_err(|_| IntegrityError::InvalidCertificate(device.device_kid.clone()))?;
+    Ok(*cert.as_bytes())
+}
+
+/// Check a rotated device's cross-signature against the device it replaced,
+/// which must be among `devices`. Returns the decoded signature, or `None`
+/// for a device that was not created by a rotation.
+///
+/// # Errors
+///
+/// Returns `InvalidRotation` if only one of the rotation fields is set, the
+/// predecessor is missing, or the signature does not verify.
+pub fn verify_rotation(
+    devices: &[DeviceExport],
+    device: &DeviceExport,
+) -> Result<Option<[u8; 64]>, IntegrityError> {
+    let invalid = || IntegrityError::InvalidRotation(device.device_kid.clone());
+    let (old_kid, signature) = match (&device.rotated_from_kid, &device.rotation_signature) {
+        (None, None) => return Ok(None),
+        (Some(kid), Some(sig)) => (kid, sig),
+        _ => return Err(invalid()),
+    };
+    let old_kid: Kid = old_kid.parse().map_err(|_| invalid())?;
+    let old_pubkey = devices
+        .iter()
+        .find(|d| d.device_kid == old_kid.as_str())
+        .and_then(|d| DevicePubkey::from_base64url(&d.device_pubkey).ok())
+        .ok_or_else(invalid)?;
+    let new_pubkey = DevicePubkey::from_base64url(&device.device_pubkey).map_err(|_| invalid())?;
+    let signature = CertificateSignature::from_base64url(signature).map_err(|_| invalid())?;
+    verify_ed25519(
+        old_pubkey.as_bytes(),
+        &device_rotation_message(&old_kid, new_pubkey.as_bytes()),
+        signature.as_bytes(),
+    )
+    .map_err(|_| invalid())?;
+    Ok(Some(*signature.as_bytes()))
+}
+
+/// Check a backup is bound to `root_kid` and parses as an envelope.
+///
+/// # Errors
+///
+/// Returns `BackupKidMismatch` or `InvalidBackup`.
+pub fn verify_backup(
+    root_kid: &str,
+    backup: &BackupExport,
+) -> Result<BackupEnvelope, IntegrityError> {
+    if backup.kid != root_kid {
+        return Err(IntegrityError::BackupKidMismatch);
+    }
+    if let Some(hash) = &backup.recovery_code_hash {
+        if stored_recovery_code(hash).is_none() {
+            return Err(IntegrityError::InvalidBackup(
+                "recovery_code_hash is not a recognized hash".to_string(),
+            ));
+        }
+    }
+    let bytes = decode_base64url(&backup.encrypted_backup)
+        .map_err(|e| IntegrityError::InvalidBackup(e.to_string()))?;
+    BackupEnvelope::parse(bytes).map_err(|e| IntegrityError::InvalidBackup(e.to_string()))
+}
+
+/// Normalize an exported recovery code hash to its stored form.
+fn stored_recovery_code(hash: &str) -> Option<String> {
+    if recovery_code::is_well_formed(hash) {
+        return Some(hash.to_string());
+    }
+    decode_base64url(hash)
+        .ok()
+        .filter(|digest| digest.len() == 32)
+        .map(|digest| recovery_code::from_legacy_digest(&digest))
+}
+
+// ─── SQL row types ─────────────────────────────────────────────────────────
+
+// KIDs stay `String` here rather than `Kid`: an export carries whatever the
+// database holds, so a malformed value is reported by the `verify_*` checks
+// instead of failing the whole page.
+
+#[derive(sqlx::FromRow)]
+struct AccountRow {
+    id: Uuid,
+    username: String,
+    root_pubkey: String,
+    root_kid: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DeviceRow {
+    id: Uuid,
+    account_id: Uuid,
+    device_kid: String,
+    device_pubkey: String,
+    device_name: String,
+    certificate: Vec<u8>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    revoked_by_kid: Option<String>,
+    revocation_envelope: Option<serde_json::Value>,
+    certificate_version: Option<i32>,
+    certificate_timestamp: Option<i64>,
+    rotated_from_kid: Option<String>,
+    rotation_signature: Option<Vec<u8>>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AliasRow {
+    account_id: Uuid,
+    alias: String,
+    reserved: bool,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct BackupRow {
+    id: Uuid,
+    account_id: Uuid,
+    kid: String,
+    encrypted_backup: Vec<u8>,
+    created_at: DateTime<Utc>,
+    recovery_code_hash: Option<String>,
+}
+
+impl From<&DeviceRow> for DeviceExport {
+    fn from(d: &DeviceRow) -> Self {
+        Self {
+            id: d.id,
+            device_kid: d.device_kid.clone(),
+            device_pubkey: d.device_pubkey.clone(),
+            device_name: d.device_name.clone(),
+            certificate: encode_base64url(&d.certificate),
+            last_used_at: d.last_used_at,
+            revoked_at: d.revoked_at,
+            revoked_by_kid: d.revoked_by_kid.clone(),
+            revocation_envelope: d.revocation_envelope.clone(),
+            certificate_version: d.certificate_version,
+            certificate_timestamp: d.certificate_timestamp,
+            rotated_from_kid: d.rotated_from_kid.clone(),
+            rotation_signature: d.rotation_signature.as_deref().map(encode_base64url),
+            created_at: d.created_at,
+        }
+    }
+}
+
+impl From<&AliasRow> for AliasExport {
+    fn from(a: &AliasRow) -> Self {
+        Self {
+            alias: a.alias.clone(),
+            reserved: a.reserved,
+            created_at: a.created_at,
+        }
+    }
+}
+
+impl From<&BackupRow> for BackupExport {
+    fn from(b: &BackupRow) -> Self {
+        Self {
+            id: b.id,
+            kid: b.kid.clone(),
+            encrypted_backup: encode_base64url(&b.encrypted_backup),
+            created_at: b.created_at,
+            recovery_code_hash: b.recovery_code_hash.clone(),
+        }
+    }
+}
+
+// ─── Export ────────────────────────────────────────────────────────────────
+
+/// Write every account as JSON Lines, oldest first. Returns the account count.
+///
+/// # Errors
+///
+/// Returns `Database` on query failure and `Io`/`Serialize` on write failure.
+pub async fn export_accounts<W: Write>(
+    pool: &PgPool,
+    out: &mut W,
+) -> Result<usize, PortabilityError> {
+    let mut cursor = None;
+    let mut total = 0;
+
+    loop {
+        let page = export_page(pool, cursor, EXPORT_BATCH_SIZE).await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        cursor = Some((last.created_at, last.id));
+
+        for record in &page {
+            serde_json::to_writer(&mut *out, record)?;
+            out.write_all(b"\n")?;
+            total += 1;
+        }
+    }
+
+    out.flush()?;
+    Ok(total)
+}
+
+/// Up to `limit` accounts created after `after` (`(created_at, id)` of the
+/// last account of the previous page), oldest first, with their devices,
+/// aliases, and backup.
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub async fn export_page(
+    pool: &PgPool,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
+) -> Result<Vec<AccountExport>, sqlx::Error> {
+    let accounts = sqlx::query_as::<_, AccountRow>(
+        r"
+        SELECT id, username, root_pubkey, root_kid, created_at
+        FROM accounts
+        WHERE $1::timestamptz IS NULL OR (created_at, id) > ($1::timestamptz, $2::uuid)
+        ORDER BY created_at, id
+        LIMIT $3
+        ",
+    )
+    .bind(after.map(|(at, _)| at))
+    .bind(after.map(|(_, id)| id))
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    if accounts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<Uuid> = accounts.iter().map(|a| a.id).collect();
+    let devices = sqlx::query_as::<_, DeviceRow>(
+        r"
+        SELECT id, account_id, device_kid, device_pubkey, device_name, certificate,
+               last_used_at, revoked_at, revoked_by_kid, revocation_envelope,
+               certificate_version, certificate_timestamp, rotated_from_kid,
+               rotation_signature, created_at
+        FROM device_keys
+        WHERE account_id = ANY($1)
+        ORDER BY created_at, id
+        ",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    let aliases = sqlx::query_as::<_, AliasRow>(
+        r"
+        SELECT account_id, alias, reserved, created_at
+        FROM account_aliases
+        WHERE account_id = ANY($1)
+        ORDER BY created_at, alias
+        ",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+    let backups = sqlx::query_as::<_, BackupRow>(
+        r"
+        SELECT id, account_id, kid, encrypted_backup, created_at, recovery_code_hash
+        FROM account_backups
+        WHERE account_id = ANY($1)
+        ",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(accounts
+        .into_iter()
+        .map(|account| AccountExport {
+            format_version: EXPORT_FORMAT_VERSION,
+            devices: devices
+                .iter()
+                .filter(|d| d.account_id == account.id)
+                .map(DeviceExport::from)
+                .collect(),
+            aliases: aliases
+                .iter()
+                .filter(|a| a.account_id == account.id)
+                .map(AliasExport::from)
+                .collect(),
+            backup: backups
+                .iter()
+                .find(|b| b.account_id == account.id)
+                .map(BackupExport::from),
+            id: account.id,
+            username: account.username,
+            root_pubkey: account.root_pubkey,
+            root_kid: account.root_kid,
+            created_at: account.created_at,
+        })
+        .collect())
+}
+
+// ─── Import ────────────────────────────────────────────────────────────────
+
+/// Parse and verify every line of a JSON Lines export.
+///
+/// # Errors
+///
+/// Returns `Malformed` or `Integrity` for the first bad line (1-based).
+pub fn read_export<R: BufRead>(input: R) -> Result<Vec<VerifiedAccount>, PortabilityError> {
+    let mut accounts = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let account: AccountExport =
+            serde_json::from_str(&line).map_err(|source| PortabilityError::Malformed {
+                line: line_no,
+                source,
+            })?;
+        let id = account.id;
+        let verified = verify_account(account).map_err(|source| PortabilityError::Integrity {
+            line: line_no,
+            id,
+            source,
+        })?;
+        accounts.push(verified);
+    }
+    Ok(accounts)
+}
+
+/// Verify and restore an export in a single transaction.
+///
+/// Every line is verified before anything is written. Accounts whose ID
+/// already exists are skipped along with their devices, aliases, and backup, so
+/// re-running an import is safe. Any other conflict (e.g. a username or KID
+/// registered to a different account) aborts the whole import.
+///
+/// With `dry_run`, verification and conflict checks run but the transaction
+/// is rolled back.
+///
+/// # Errors
+///
+/// Returns the first verification, I/O, or database error.
+pub async fn import_accounts<R: BufRead>(
+    pool: &PgPool,
+    input: R,
+    dry_run: bool,
+) -> Result<ImportSummary, PortabilityError> {
+    let accounts = read_export(input)?;
+    let mut summary = ImportSummary::default();
+    let mut tx = pool.begin().await?;
+
+    for verified in &accounts {
+        let account = &verified.record;
+        let inserted = sqlx::query(
+            r"
+            INSERT INTO accounts (id, username, root_pubkey, root_kid, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (id) DO NOTHING
+            ",
+        )
+        .bind(account.id)
+        .bind(&account.username)
+        .bind(&account.root_pubkey)
+        .bind(&account.root_kid)
+        .bind(account.created_at)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            summary.skipped += 1;
+            continue;
+        }
+
+        insert_devices(&mut tx, verified).await?;
+
+        for alias in &account.aliases {
+            sqlx::query(
+                r"
+                INSERT INTO account_aliases (alias, account_id, reserved, created_at)
+                VALUES ($1, $2, $3, $4)
+                ",
+            )
+            .bind(&alias.alias)
+            .bind(account.id)
+            .bind(alias.reserved)
+            .bind(alias.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let (Some(backup), Some(envelope)) = (&account.backup, &verified.backup) {
+            sqlx::query(
+                r"
+                INSERT INTO account_backups
+                    (id, account_id, kid, encrypted_backup, salt, version, created_at,
+                     recovery_code_hash)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ",
+            )
+            .bind(backup.id)
+            .bind(account.id)
+            .bind(&backup.kid)
+            .bind(envelope.as_bytes())
+            .bind(envelope.salt().as_slice())
+            .bind(envelope.version())
+            .bind(backup.created_at)
+            // Validated by `verify_backup`
+            .bind(
+                backup
+                    .recovery_code_hash
+                    .as_deref()
+                    .and_then(stored_recovery_code),
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        summary.imported += 1;
+    }
+
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+    Ok(summary)
+}
+
+/// Insert a verified account's devices in export order, so a rotated key's
+/// predecessor exists before the row that references it.
+async fn insert_devices(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    verified: &VerifiedAccount,
+) -> Result<(), sqlx::Error> {
+    let account = &verified.record;
+    for ((device, certificate), rotation_signature) in account
+        .devices
+        .iter()
+        .zip(&verified.certificates)
+        .zip(&verified.rotation_signatures)
+    {
+        sqlx::query(
+            r"
+            INSERT INTO device_keys (id, account_id, device_kid, device_pubkey, device_name,
+                                     certificate, last_used_at, revoked_at, revoked_by_kid,
+                                     revocation_envelope, certificate_version,
+                                     certificate_timestamp, rotated_from_kid,
+                                     rotation_signature, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ",
+        )
+        .bind(device.id)
+        .bind(account.id)
+        .bind(&device.device_kid)
+        .bind(&device.device_pubkey)
+        .bind(&device.device_name)
+        .bind(certificate.as_slice())
+        .bind(device.last_used_at)
+        .bind(device.revoked_at)
+        .bind(&device.revoked_by_kid)
+        .bind(&device.revocation_envelope)
+        .bind(device.certificate_version)
+        .bind(device.certificate_timestamp)
+        .bind(&device.rotated_from_kid)
+        .bind(rotation_signature.as_ref().map(<[u8; 64]>::as_slice))
+        .bind(device.created_at)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn sample_account() -> AccountExport {
+        let root = SigningKey::from_bytes(&[1u8; 32]);
+        let device = SigningKey::from_bytes(&[2u8; 32]);
+        let root_pub = root.verifying_key().to_bytes();
+        let device_pub = device.verifying_key().to_bytes();
+        let envelope =
+            BackupEnvelope::build([7u8; 16], 65536, 3, 1, [0u8; 12], &[0u8; 48]).unwrap();
+        let now = Utc::now();
+        AccountExport {
+            format_version: EXPORT_FORMAT_VERSION,
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            root_pubkey: encode_base64url(&root_pub),
+            root_kid: Kid::derive(&root_pub).to_string(),
+            created_at: now,
+            devices: vec![DeviceExport {
+                id: Uuid::new_v4(),
+                device_kid: Kid::derive(&device_pub).to_string(),
+                device_pubkey: encode_base64url(&device_pub),
+                device_name: "laptop".to_string(),
+                certificate: encode_base64url(&root.sign(&device_pub).to_bytes()),
+                last_used_at: None,
+                revoked_at: None,
+                revoked_by_kid: None,
+                revocation_envelope: None,
+                certificate_version: Some(1),
+                certificate_timestamp: None,
+                rotated_from_kid: None,
+                rotation_signature: None,
+                created_at: now,
+            }],
+            aliases: vec![],
+            backup: Some(BackupExport {
+                id: Uuid::new_v4(),
+                kid: Kid::derive(&root_pub).to_string(),
+                encrypted_backup: encode_base64url(envelope.as_bytes()),
+                created_at: now,
+                recovery_code_hash: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn valid_account_verifies() {
+        let verified = verify_account(sample_account()).unwrap();
+        assert_eq!(verified.certificates.len(), 1);
+        assert!(verified.backup.is_some());
+    }
+
+    #[test]
+    fn login_certificate_verifies_with_its_timestamp() {
+        let root = SigningKey::from_bytes(&[1u8; 32]);
+        let device_pub = SigningKey::from_bytes(&[2u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let message = device_certificate_message(&device_pub, Some(1_700_000_000));
+        let mut account = sample_account();
+        account.devices[0].certificate = encode_base64url(&root.sign(&message).to_bytes());
+        account.devices[0].certificate_version = Some(2);
+        account.devices[0].certificate_timestamp = Some(1_700_000_000);
+        assert!(verify_account(account.clone()).is_ok());
+
+        account.devices[0].certificate_timestamp = Some(1_700_000_001);
+        assert!(matches!(
+            verify_account(account).err(),
+            Some(IntegrityError::InvalidCertificate(_))
+        ));
+    }
+
+    #[test]
+    fn rotated_device_must_be_cross_signed_by_its_predecessor() {
+        let root = SigningKey::from_bytes(&[1u8; 32]);
+        let old_key = SigningKey::from_bytes(&[2u8; 32]);
+        let new_pub = SigningKey::from_bytes(&[4u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let mut account = sample_account();
+        let old_kid: Kid = account.devices[0].device_kid.parse().unwrap();
+        let mut rotated = account.devices[0].clone();
+        rotated.id = Uuid::new_v4();
+        rotated.device_kid = Kid::derive(&new_pub).to_string();
+        rotated.device_pubkey = encode_base64url(&new_pub);
+        rotated.certificate = encode_base64url(&root.sign(&new_pub).to_bytes());
+        rotated.rotated_from_kid = Some(old_kid.to_string());
+        rotated.rotation_signature = Some(encode_base64url(
+            &old_key
+                .sign(&device_rotation_message(&old_kid, &new_pub))
+                .to_bytes(),
+        ));
+        account.devices.push(rotated);
+        let verified = verify_account(account.clone()).unwrap();
+        assert_eq!(verified.rotation_signatures[0], None);
+        assert!(verified.rotation_signatures[1].is_some());
+
+        let mut forged = account.clone();
+        forged.devices[1].rotation_signature = Some(encode_base64url(&[0xAAu8; 64]));
+        assert!(matches!(
+            verify_account(forged).err(),
+            Some(IntegrityError::InvalidRotation(_))
+        ));
+
+        let mut orphaned = account;
+        orphaned.devices.remove(0);
+        assert!(matches!(
+            verify_account(orphaned).err(),
+            Some(IntegrityError::InvalidRotation(_))
+        ));
+    }
+
+    #[test]
+    fn tampered_certificate_is_rejected() {
+        let mut account = sample_account();
+        account.devices[0].certificate = encode_base64url(&[0xAAu8; 64]);
+        assert!(matches!(
+            verify_account(account).err(),
+            Some(IntegrityError::InvalidCertificate(_))
+        ));
+    }
+
+    #[test]
+    fn swapped_root_key_is_rejected() {
+        let mut account = sample_account();
+        account.root_pubkey = encode_base64url(&[9u8; 32]);
+        assert_eq!(
+            verify_account(account).err(),
+            Some(IntegrityError::RootKidMismatch)
+        );
+    }
+
+    #[test]
+    fn backup_for_other_key_is_rejected() {
+        let mut account = sample_account();
+        if let Some(backup) = account.backup.as_mut() {
+            backup.kid = Kid::derive(&[3u8; 32]).to_string();
+        }
+        assert_eq!(
+            verify_account(account).err(),
+            Some(IntegrityError::BackupKidMismatch)
+        );
+    }
+
+    #[test]
+    fn backup_recovery_code_hash_must_be_recognized() {
+        let mut account = sample_account();
+        if let Some(backup) = account.backup.as_mut() {
+            backup.recovery_code_hash = Some(encode_base64url(&[1u8; 32]));
+        }
+        assert!(verify_account(account.clone()).is_ok());
+        if let Some(backup) = account.backup.as_mut() {
+            backup.recovery_code_hash = Some(recovery_code::from_legacy_digest(&[1u8; 32]));
+        }
+        assert!(verify_account(account.clone()).is_ok());
+        if let Some(backup) = account.backup.as_mut() {
+            backup.recovery_code_hash = Some(encode_base64url(&[1u8; 8]));
+        }
+        assert!(matches!(
+            verify_account(account).err(),
+            Some(IntegrityError::InvalidBackup(_))
+        ));
+    }
+
+    #[test]
+    fn read_export_reports_bad_line_number() {
+        let good = serde_json::to_string(&sample_account()).unwrap();
+        let mut bad = sample_account();
+        bad.format_version = 99;
+        let bad = serde_json::to_string(&bad).unwrap();
+        let input = format!("{good}\n\n{bad}\n");
+
+        let err = read_export(input.as_bytes())
+            .err()
+            .expect("line 3 must be rejected");
+        assert!(
+            matches!(err, PortabilityError::Integrity { line: 3, .. }),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Recovery codes gating backup retrieval
+//!
+//! Recovery codes are chosen by the account holder, so they are stored as
+//! Argon2id PHC strings with a random per-code salt rather than a bare digest.
+//! Codes set before migration `66_recovery_code_kdf.sql` are kept as
+//! `sha256$<base64url digest>`; they still verify (compared in constant time)
+//! and callers rehash them with [`hash`] after the next successful check.
+
+use std::sync::LazyLock;
+
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, SaltString};
+use argon2::{Argon2, PasswordVerifier};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use tc_crypto::{decode_base64url, encode_base64url};
+
+/// Prefix of recovery codes stored as an unsalted SHA-256 digest.
+const LEGACY_PREFIX: &str = "sha256$";
+
+/// Hash used when there is no stored code to compare against, so that a
+/// request carrying a code takes the same time whether or not one is set.
+static DUMMY_HASH: LazyLock<Option<String>> = LazyLock::new(|| hash("tc-recovery-code-dummy").ok());
+
+/// Outcome of a successful [`verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verified {
+    /// Stored as Argon2id
+    Current,
+    /// Stored as a legacy digest; the caller should rehash it
+    Legacy,
+}
+
+/// Hash a recovery code for storage as an Argon2id PHC string.
+///
+/// # Errors
+///
+/// Returns an error if Argon2 rejects the input (it never does for codes
+/// within `RECOVERY_CODE_LENGTH`).
+pub fn hash(code: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default()
+        .hash_password(code.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// Check `presented` against a stored recovery code hash.
+///
+/// `stored = None` (no code set) never matches, but still runs one Argon2id
+/// verification so the response time does not reveal whether a code is set.
+/// This is CPU-bound; call it from a blocking task.
+#[must_use]
+pub fn verify(stored: Option<&str>, presented: &str) -> Option<Verified> {
+    let Some(stored) = stored else {
+        if let Some(dummy) = DUMMY_HASH.as_deref() {
+            let _ = verify_phc(dummy, presented);
+        }
+        return None;
+    };
+    if let Some(digest) = stored.strip_prefix(LEGACY_PREFIX) {
+        let expected = decode_base64url(digest).ok()?;
+        let actual = Sha256::digest(presented.as_bytes());
+        return bool::from(actual.as_slice().ct_eq(&expected)).then_some(Verified::Legacy);
+    }
+    verify_phc(stored, presented).then_some(Verified::Current)
+}
+
+fn verify_phc(stored: &str, presented: &str) -> bool {
+    // `verify_password` compares the derived output in constant time
+    PasswordHash::new(stored).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(presented.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+/// Whether `stored` is a hash this module can verify.
+#[must_use]
+pub fn is_well_formed(stored: &str) -> bool {
+    stored.strip_prefix(LEGACY_PREFIX).map_or_else(
+        || PasswordHash::new(stored).is_ok_and(|h| h.algorithm == argon2::ARGON2ID_IDENT),
+        |digest| decode_base64url(digest).is_ok_and(|d| d.len() == 32),
+    )
+}
+
+/// Stored form of a bare SHA-256 digest, as found in older account exports.
+#[must_use]
+pub fn from_legacy_digest(digest: &[u8]) -> String {
+    format!("{LEGACY_PREFIX}{}", encode_base64url(digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_salted_and_verifies() {
+        let a = hash("correct-horse-battery").expect("hash");
+        let b = hash("correct-horse-battery").expect("hash");
+        assert_ne!(a, b, "each hash must use a fresh salt");
+        assert!(a.starts_with("$argon2id$"));
+        assert!(is_well_formed(&a));
+        assert_eq!(
+            verify(Some(&a), "correct-horse-battery"),
+            Some(Verified::Current)
+        );
+        assert_eq!(verify(Some(&a), "wrong-code-0123456789"), None);
+    }
+
+    #[test]
+    fn legacy_digests_still_verify() {
+        let stored = from_legacy_digest(&Sha256::digest(b"correct-horse-battery"));
+        assert!(is_well_formed(&stored));
+        assert_eq!(
+            verify(Some(&stored), "correct-horse-battery"),
+            Some(Verified::Legacy)
+        );
+        assert_eq!(verify(Some(&stored), "wrong-code-0123456789"), None);
+    }
+
+    #[test]
+    fn missing_or_malformed_hashes_never_match() {
+        assert_eq!(verify(None, "correct-horse-battery"), None);
+        assert_eq!(verify(Some("sha256$!!"), "correct-horse-battery"), None);
+        assert!(!is_well_formed("sha256$AAAA"));
+        assert!(!is_well_formed("not-a-hash"));
+    }
+}
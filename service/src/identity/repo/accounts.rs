@@ -1,8 +1,14 @@
 //! Account repository for database operations
 
+use chrono::{DateTime, Utc};
 use tc_crypto::Kid;
 use uuid::Uuid;
 
+use super::sigchain::append_sigchain_link;
+use crate::db::{map_unique_violation, Instrumented, RepoError, RepoErrorKind};
+use crate::identity::sigchain::NewSigchainLink;
+use crate::identity::status::AccountStatus;
+
 /// Account creation result
 #[derive(Debug, Clone)]
 pub struct CreatedAccount {
@@ -32,6 +38,16 @@ pub enum AccountRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for AccountRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::DuplicateUsername | Self::DuplicateKey => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 /// Shared implementation for account creation that works with any executor.
 /// This allows tests to use transactions for isolation.
 async fn create_account<'e, E>(
@@ -55,8 +71,8 @@ where
     .bind(id)
     .bind(username)
     .bind(root_pubkey)
-    .bind(root_kid.as_str())
-    .execute(executor)
+    .bind(root_kid)
+    .execute(Instrumented::new(executor, "accounts.create"))
     .await;
 
     match result {
@@ -69,16 +85,12 @@ where
                 root_kid: root_kid.clone(),
             })
         }
-        Err(e) => {
-            if let sqlx::Error::Database(db_err) = &e {
-                if let Some(constraint) = db_err.constraint() {
-                    if constraint == "accounts_root_kid_key" {
-                        return Err(AccountRepoError::DuplicateKey);
-                    }
-                }
-            }
-            Err(AccountRepoError::Database(e))
-        }
+        Err(e) => Err(map_unique_violation(e, |constraint| match constraint {
+            "accounts_root_kid_key" => Some(AccountRepoError::DuplicateKey),
+            // The username is already another account's alias
+            super::aliases::HANDLE_UNIQUE_CONSTRAINT => Some(AccountRepoError::DuplicateUsername),
+            _ => None,
+        })),
     }
 }
 
@@ -102,13 +114,12 @@ where
 }
 
 /// Row shape for account queries — mirrors the SELECT columns.
-/// Uses `String` for `root_kid` because [`Kid`] doesn't implement `sqlx::Decode`.
 #[derive(sqlx::FromRow)]
 struct AccountRow {
     id: Uuid,
     username: String,
     root_pubkey: String,
-    root_kid: String,
+    root_kid: Kid,
 }
 
 /// Look up an account by its ID.
@@ -131,7 +142,7 @@ where
         ",
     )
     .bind(account_id)
-    .fetch_optional(executor)
+    .fetch_optional(Instrumented::new(executor, "accounts.by_id"))
     .await?;
 
     account_row_to_record(row)
@@ -157,7 +168,7 @@ where
         ",
     )
     .bind(username)
-    .fetch_optional(executor)
+    .fetch_optional(Instrumented::new(executor, "accounts.by_username"))
     .await?;
 
     account_row_to_record(row)
@@ -165,19 +176,418 @@ where
 
 /// Convert an optional `AccountRow` to an `AccountRecord`, returning `NotFound` if absent.
 fn account_row_to_record(row: Option<AccountRow>) -> Result<AccountRecord, AccountRepoError> {
-    match row {
-        Some(r) => {
-            let root_kid: Kid = r
-                .root_kid
-                .parse()
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
-            Ok(AccountRecord {
-                id: r.id,
-                username: r.username,
-                root_pubkey: r.root_pubkey,
-                root_kid,
-            })
-        }
-        None => Err(AccountRepoError::NotFound),
+    row.map(|r| AccountRecord {
+        id: r.id,
+        username: r.username,
+        root_pubkey: r.root_pubkey,
+        root_kid: r.root_kid,
+    })
+    .ok_or(AccountRepoError::NotFound)
+}
+
+/// Current lifecycle status of an account.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::NotFound` if no account matches.
+pub async fn get_account_status<'e, E>(
+    executor: E,
+    account_id: Uuid,
+) -> Result<AccountStatus, AccountRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let status: Option<String> = sqlx::query_scalar("SELECT status FROM accounts WHERE id = $1")
+        .bind(account_id)
+        .fetch_optional(Instrumented::new(executor, "accounts.status"))
+        .await?;
+    status
+        .ok_or(AccountRepoError::NotFound)?
+        .parse()
+        .map_err(|e| AccountRepoError::Database(sqlx::Error::Decode(Box::new(e))))
+}
+
+/// Move an account from `from` to `to` and record the change.
+///
+/// Returns `None` without writing anything if the account is no longer in
+/// `from`, so a caller that validated the transition against a stale read
+/// cannot apply it.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::Database` on query failure.
+pub async fn update_account_status(
+    pool: &sqlx::PgPool,
+    account_id: Uuid,
+    from: AccountStatus,
+    to: AccountStatus,
+    actor_account_id: Uuid,
+    reason: Option<&str>,
+) -> Result<Option<DateTime<Utc>>, AccountRepoError> {
+    let mut tx = pool.begin().await?;
+    let changed_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        r"
+        UPDATE accounts
+        SET status = $3, status_changed_at = now()
+        WHERE id = $1 AND status = $2
+        RETURNING status_changed_at
+        ",
+    )
+    .bind(account_id)
+    .bind(from.as_str())
+    .bind(to.as_str())
+    .fetch_optional(Instrumented::new(&mut *tx, "accounts.update_status"))
+    .await?;
+    let Some(changed_at) = changed_at else {
+        return Ok(None);
+    };
+    record_status_change(
+        &mut tx,
+        account_id,
+        from,
+        to,
+        Some(actor_account_id),
+        reason,
+        changed_at,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(Some(changed_at))
+}
+
+/// Outcome of [`change_account_username`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsernameChange {
+    Changed {
+        previous: String,
+        changed_at: DateTime<Utc>,
+    },
+    /// The account already has this username; nothing was written.
+    Unchanged,
+    /// The last change, at `last_changed_at`, is after the cooldown cutoff.
+    TooSoon { last_changed_at: DateTime<Utc> },
+}
+
+/// Rename an account and append `link` to its sigchain, unless it last
+/// changed its username after `cooldown_cutoff`.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::NotFound` if no account matches,
+/// `AccountRepoError::DuplicateUsername` if the name is another account's
+/// username or alias.
+pub async fn change_account_username(
+    pool: &sqlx::PgPool,
+    account_id: Uuid,
+    username: &str,
+    cooldown_cutoff: DateTime<Utc>,
+    link: &NewSigchainLink,
+) -> Result<UsernameChange, AccountRepoError> {
+    let mut tx = pool.begin().await?;
+    let current: Option<(String, Option<DateTime<Utc>>)> = sqlx::query_as(
+        "SELECT username, username_changed_at FROM accounts WHERE id = $1 FOR UPDATE",
+    )
+    .bind(account_id)
+    .fetch_optional(Instrumented::new(&mut *tx, "accounts.lock_username"))
+    .await?;
+    let (previous, last_changed_at) = current.ok_or(AccountRepoError::NotFound)?;
+    if previous == username {
+        return Ok(UsernameChange::Unchanged);
+    }
+    if let Some(last_changed_at) = last_changed_at.filter(|at| *at > cooldown_cutoff) {
+        return Ok(UsernameChange::TooSoon { last_changed_at });
+    }
+    let changed_at: DateTime<Utc> = sqlx::query_scalar(
+        r"
+        UPDATE accounts
+        SET username = $2, username_changed_at = now()
+        WHERE id = $1
+        RETURNING username_changed_at
+        ",
+    )
+    .bind(account_id)
+    .bind(username)
+    .fetch_one(Instrumented::new(&mut *tx, "accounts.change_username"))
+    .await
+    .map_err(|e| {
+        map_unique_violation(e, |constraint| match constraint {
+            "accounts_username_key" | super::aliases::HANDLE_UNIQUE_CONSTRAINT => {
+                Some(AccountRepoError::DuplicateUsername)
+            }
+            _ => None,
+        })
+    })?;
+    append_sigchain_link(&mut tx, account_id, link).await?;
+    tx.commit().await?;
+    Ok(UsernameChange::Changed {
+        previous,
+        changed_at,
+    })
+}
+
+/// A deactivation that was applied by [`deactivate_account`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDeactivation {
+    pub deactivated_at: DateTime<Utc>,
+    /// Devices that were still active and are now revoked
+    pub revoked_devices: u64,
+}
+
+/// Move an `active` account to `deactivated` until `purge_after`, revoking
+/// every device with `revocation_envelope` as the record and appending
+/// `link` to its sigchain, in one transaction.
+///
+/// Returns `None` without writing anything if the account is not `active`.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::Database` on query failure.
+pub async fn deactivate_account(
+    pool: &sqlx::PgPool,
+    account_id: Uuid,
+    purge_after: DateTime<Utc>,
+    revoked_by: &Kid,
+    revocation_envelope: &serde_json::Value,
+    reason: Option<&str>,
+    link: &NewSigchainLink,
+) -> Result<Option<AccountDeactivation>, AccountRepoError> {
+    let mut tx = pool.begin().await?;
+    let deactivated_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        r"
+        UPDATE accounts
+        SET status = $2, status_changed_at = now(), purge_after = $3
+        WHERE id = $1 AND status = $4
+        RETURNING status_changed_at
+        ",
+    )
+    .bind(account_id)
+    .bind(AccountStatus::Deactivated.as_str())
+    .bind(purge_after)
+    .bind(AccountStatus::Active.as_str())
+    .fetch_optional(Instrumented::new(&mut *tx, "accounts.deactivate"))
+    .await?;
+    let Some(deactivated_at) = deactivated_at else {
+        return Ok(None);
+    };
+    record_status_change(
+        &mut tx,
+        account_id,
+        AccountStatus::Active,
+        AccountStatus::Deactivated,
+        Some(account_id),
+        reason,
+        deactivated_at,
+    )
+    .await?;
+    let revoked = sqlx::query(
+        r"
+        UPDATE device_keys
+        SET revoked_at = $4, revoked_by_kid = $2, revocation_envelope = $3
+        WHERE account_id = $1 AND revoked_at IS NULL
+        ",
+    )
+    .bind(account_id)
+    .bind(revoked_by)
+    .bind(revocation_envelope)
+    .bind(deactivated_at)
+    .execute(Instrumented::new(&mut *tx, "accounts.deactivate_devices"))
+    .await?;
+    append_sigchain_link(&mut tx, account_id, link).await?;
+    tx.commit().await?;
+    Ok(Some(AccountDeactivation {
+        deactivated_at,
+        revoked_devices: revoked.rows_affected(),
+    }))
+}
+
+/// Outcome of [`reactivate_account`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountReactivation {
+    Reactivated {
+        reactivated_at: DateTime<Utc>,
+    },
+    /// The account is not `deactivated`; nothing was written.
+    NotDeactivated,
+    /// The grace period ended at `purge_after`; nothing was written.
+    GraceExpired {
+        purge_after: DateTime<Utc>,
+    },
+}
+
+/// Move a `deactivated` account back to `active` and append `link` to its
+/// sigchain, if its grace period has not ended by `now`. Its devices stay
+/// revoked.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::NotFound` if no account matches.
+pub async fn reactivate_account(
+    pool: &sqlx::PgPool,
+    account_id: Uuid,
+    now: DateTime<Utc>,
+    link: &NewSigchainLink,
+) -> Result<AccountReactivation, AccountRepoError> {
+    let mut tx = pool.begin().await?;
+    let current: Option<(String, Option<DateTime<Utc>>)> =
+        sqlx::query_as("SELECT status, purge_after FROM accounts WHERE id = $1 FOR UPDATE")
+            .bind(account_id)
+            .fetch_optional(Instrumented::new(&mut *tx, "accounts.lock_status"))
+            .await?;
+    let (status, purge_after) = current.ok_or(AccountRepoError::NotFound)?;
+    if status != AccountStatus::Deactivated.as_str() {
+        return Ok(AccountReactivation::NotDeactivated);
+    }
+    if let Some(purge_after) = purge_after.filter(|at| *at <= now) {
+        return Ok(AccountReactivation::GraceExpired { purge_after });
     }
+    let reactivated_at: DateTime<Utc> = sqlx::query_scalar(
+        r"
+        UPDATE accounts
+        SET status = $2, status_changed_at = now(), purge_after = NULL
+        WHERE id = $1
+        RETURNING status_changed_at
+        ",
+    )
+    .bind(account_id)
+    .bind(AccountStatus::Active.as_str())
+    .fetch_one(Instrumented::new(&mut *tx, "accounts.reactivate"))
+    .await?;
+    record_status_change(
+        &mut tx,
+        account_id,
+        AccountStatus::Deactivated,
+        AccountStatus::Active,
+        Some(account_id),
+        None,
+        reactivated_at,
+    )
+    .await?;
+    append_sigchain_link(&mut tx, account_id, link).await?;
+    tx.commit().await?;
+    Ok(AccountReactivation::Reactivated { reactivated_at })
+}
+
+/// Deactivated accounts whose grace period ended by `now`, oldest first.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::Database` on query failure.
+pub async fn list_purge_due(
+    pool: &sqlx::PgPool,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Uuid>, AccountRepoError> {
+    let ids = sqlx::query_scalar(
+        r"
+        SELECT id FROM accounts
+        WHERE status = $1 AND purge_after <= $2
+        ORDER BY purge_after
+        LIMIT $3
+        ",
+    )
+    .bind(AccountStatus::Deactivated.as_str())
+    .bind(now)
+    .bind(limit)
+    .fetch_all(Instrumented::new(pool, "accounts.purge_due"))
+    .await?;
+    Ok(ids)
+}
+
+/// Outcome of [`purge_account`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountPurge {
+    /// The row and everything cascading from it is gone.
+    Purged,
+    /// Rows without `ON DELETE` behavior still reference the account, so it
+    /// was moved to `deleted` instead and kept.
+    Retained { constraint: String },
+    /// The account was restored or is not due yet; nothing was written.
+    NotDue,
+}
+
+/// Hard-delete a `deactivated` account whose grace period ended by `now`.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::Database` on query failure.
+pub async fn purge_account(
+    pool: &sqlx::PgPool,
+    account_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<AccountPurge, AccountRepoError> {
+    let deleted =
+        sqlx::query("DELETE FROM accounts WHERE id = $1 AND status = $2 AND purge_after <= $3")
+            .bind(account_id)
+            .bind(AccountStatus::Deactivated.as_str())
+            .bind(now)
+            .execute(Instrumented::new(pool, "accounts.purge"))
+            .await;
+    let constraint = match deleted {
+        Ok(result) if result.rows_affected() == 0 => return Ok(AccountPurge::NotDue),
+        Ok(_) => return Ok(AccountPurge::Purged),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            e.constraint().unwrap_or("unknown").to_string()
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut tx = pool.begin().await?;
+    let changed_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        r"
+        UPDATE accounts
+        SET status = $2, status_changed_at = now(), purge_after = NULL
+        WHERE id = $1 AND status = $3
+        RETURNING status_changed_at
+        ",
+    )
+    .bind(account_id)
+    .bind(AccountStatus::Deleted.as_str())
+    .bind(AccountStatus::Deactivated.as_str())
+    .fetch_optional(Instrumented::new(&mut *tx, "accounts.retain_purged"))
+    .await?;
+    let Some(changed_at) = changed_at else {
+        return Ok(AccountPurge::NotDue);
+    };
+    record_status_change(
+        &mut tx,
+        account_id,
+        AccountStatus::Deactivated,
+        AccountStatus::Deleted,
+        None,
+        Some(&format!("purge blocked by {constraint}")),
+        changed_at,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(AccountPurge::Retained { constraint })
+}
+
+async fn record_status_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    account_id: Uuid,
+    from: AccountStatus,
+    to: AccountStatus,
+    actor_account_id: Option<Uuid>,
+    reason: Option<&str>,
+    changed_at: DateTime<Utc>,
+) -> Result<(), AccountRepoError> {
+    sqlx::query(
+        r"
+        INSERT INTO account_status_changes
+            (account_id, from_status, to_status, actor_account_id, reason, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ",
+    )
+    .bind(account_id)
+    .bind(from.as_str())
+    .bind(to.as_str())
+    .bind(actor_account_id)
+    .bind(reason)
+    .bind(changed_at)
+    .execute(Instrumented::new(
+        &mut **tx,
+        "accounts.record_status_change",
+    ))
+    .await?;
+    Ok(())
 }
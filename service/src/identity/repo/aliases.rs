@@ -0,0 +1,261 @@
+//! Account alias repository
+//!
+//! Aliases are secondary handles that resolve to an account. Handles are
+//! unique across usernames and aliases; migration `32_account_aliases.sql`
+//! enforces this with triggers that report `account_handles_unique`.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tc_crypto::Kid;
+use uuid::Uuid;
+
+use super::accounts::{AccountRecord, AccountRepoError};
+
+use crate::db::{unique_violation, RepoError, RepoErrorKind};
+
+/// Maximum number of aliases per account, reserved ones included
+pub const MAX_ALIASES_PER_ACCOUNT: i64 = 5;
+
+/// Constraint name raised by the handle-uniqueness triggers.
+pub(crate) const HANDLE_UNIQUE_CONSTRAINT: &str = "account_handles_unique";
+
+/// Record returned from alias queries
+#[derive(Debug, Clone)]
+pub struct AccountAliasRecord {
+    pub alias: String,
+    pub account_id: Uuid,
+    pub reserved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An account found by username or alias
+#[derive(Debug, Clone)]
+pub struct ResolvedHandle {
+    pub account: AccountRecord,
+    /// The alias that matched, or `None` if the handle was the username
+    pub alias: Option<String>,
+}
+
+/// Error types for alias operations
+#[derive(Debug, thiserror::Error)]
+pub enum AliasRepoError {
+    #[error("handle already taken")]
+    DuplicateHandle,
+    #[error("alias not found")]
+    NotFound,
+    #[error("alias is reserved")]
+    Reserved,
+    #[error("maximum alias limit reached")]
+    MaxAliasesReached,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for AliasRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::DuplicateHandle => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Reserved => RepoErrorKind::Rejected,
+            Self::MaxAliasesReached => RepoErrorKind::LimitReached,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+/// `true` if `e` is a unique violation on a username or alias.
+pub(crate) fn is_handle_conflict(e: &sqlx::Error) -> bool {
+    matches!(
+        unique_violation(e),
+        Some(HANDLE_UNIQUE_CONSTRAINT | "account_aliases_pkey")
+    )
+}
+
+#[derive(sqlx::FromRow)]
+struct AliasRow {
+    alias: String,
+    account_id: Uuid,
+    reserved: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AliasRow> for AccountAliasRecord {
+    fn from(row: AliasRow) -> Self {
+        Self {
+            alias: row.alias,
+            account_id: row.account_id,
+            reserved: row.reserved,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Add an alias to an account.
+///
+/// Locks the account row so concurrent creations cannot exceed
+/// [`MAX_ALIASES_PER_ACCOUNT`].
+///
+/// # Errors
+///
+/// Returns `AliasRepoError::NotFound` if the account does not exist.
+/// Returns `AliasRepoError::DuplicateHandle` if the alias is already a username or alias.
+/// Returns `AliasRepoError::MaxAliasesReached` if the account has reached the alias limit.
+pub(crate) async fn create_account_alias(
+    pool: &PgPool,
+    account_id: Uuid,
+    alias: &str,
+    reserved: bool,
+) -> Result<AccountAliasRecord, AliasRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let locked = sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(account_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if locked.is_none() {
+        return Err(AliasRepoError::NotFound);
+    }
+
+    let row = sqlx::query_as::<_, AliasRow>(
+        r"
+        INSERT INTO account_aliases (alias, account_id, reserved)
+        SELECT $1, $2, $3
+        WHERE (SELECT COUNT(*) FROM account_aliases WHERE account_id = $2) < $4
+        RETURNING alias, account_id, reserved, created_at
+        ",
+    )
+    .bind(alias)
+    .bind(account_id)
+    .bind(reserved)
+    .bind(MAX_ALIASES_PER_ACCOUNT)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        if is_handle_conflict(&e) {
+            AliasRepoError::DuplicateHandle
+        } else {
+            AliasRepoError::Database(e)
+        }
+    })?
+    .ok_or(AliasRepoError::MaxAliasesReached)?;
+
+    tx.commit().await?;
+    Ok(row.into())
+}
+
+/// List an account's aliases, oldest first.
+///
+/// # Errors
+///
+/// Returns `AliasRepoError::Database` on database failures.
+pub(crate) async fn list_account_aliases<'e, E>(
+    executor: E,
+    account_id: Uuid,
+) -> Result<Vec<AccountAliasRecord>, AliasRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query_as::<_, AliasRow>(
+        r"
+        SELECT alias, account_id, reserved, created_at
+        FROM account_aliases
+        WHERE account_id = $1
+        ORDER BY created_at, alias
+        ",
+    )
+    .bind(account_id)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Remove an alias owned by `account_id`.
+///
+/// # Errors
+///
+/// Returns `AliasRepoError::NotFound` if the account has no such alias.
+/// Returns `AliasRepoError::Reserved` if the alias was assigned by an operator.
+pub(crate) async fn delete_account_alias(
+    pool: &PgPool,
+    alias: &str,
+    account_id: Uuid,
+) -> Result<(), AliasRepoError> {
+    let reserved: Option<bool> = sqlx::query_scalar(
+        r"
+        DELETE FROM account_aliases
+        WHERE alias = $1 AND account_id = $2 AND NOT reserved
+        RETURNING reserved
+        ",
+    )
+    .bind(alias)
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await?;
+    if reserved.is_some() {
+        return Ok(());
+    }
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM account_aliases WHERE alias = $1 AND account_id = $2)",
+    )
+    .bind(alias)
+    .bind(account_id)
+    .fetch_one(pool)
+    .await?;
+    if exists {
+        Err(AliasRepoError::Reserved)
+    } else {
+        Err(AliasRepoError::NotFound)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ResolvedRow {
+    id: Uuid,
+    username: String,
+    root_pubkey: String,
+    root_kid: Kid,
+    alias: Option<String>,
+}
+
+/// Resolve a handle that may be either a username or an alias.
+///
+/// # Errors
+///
+/// Returns `AccountRepoError::NotFound` if no account has this handle.
+pub async fn resolve_account_handle<'e, E>(
+    executor: E,
+    handle: &str,
+) -> Result<ResolvedHandle, AccountRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let row = sqlx::query_as::<_, ResolvedRow>(
+        r"
+        SELECT id, username, root_pubkey, root_kid, NULL::text AS alias
+        FROM accounts
+        WHERE username = $1
+        UNION ALL
+        SELECT a.id, a.username, a.root_pubkey, a.root_kid, al.alias
+        FROM account_aliases al
+        JOIN accounts a ON a.id = al.account_id
+        WHERE al.alias = $1
+        LIMIT 1
+        ",
+    )
+    .bind(handle)
+    .fetch_optional(executor)
+    .await?
+    .ok_or(AccountRepoError::NotFound)?;
+
+    Ok(ResolvedHandle {
+        account: AccountRecord {
+            id: row.id,
+            username: row.username,
+            root_pubkey: row.root_pubkey,
+            root_kid: row.root_kid,
+        },
+        alias: row.alias,
+    })
+}
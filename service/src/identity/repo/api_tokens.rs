@@ -0,0 +1,344 @@
+//! API token repository for server-to-server integrations
+//!
+//! Only the SHA-256 of each token is stored. Lookups hash the presented token
+//! and match on `token_hash`, so the plaintext never touches the database.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{instrument, Instrumented, RepoError, RepoErrorKind};
+
+/// Record returned from API token queries
+#[derive(Debug, Clone)]
+pub struct ApiTokenRecord {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Error types for API token operations
+#[derive(Debug, thiserror::Error)]
+pub enum ApiTokenRepoError {
+    #[error("API token not found")]
+    NotFound,
+    #[error("API token has been revoked")]
+    AlreadyRevoked,
+    #[error("maximum API token limit reached")]
+    MaxTokensReached,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for ApiTokenRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::AlreadyRevoked => RepoErrorKind::Conflict,
+            Self::MaxTokensReached => RepoErrorKind::LimitReached,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+/// Maximum number of active (non-revoked) API tokens per account, not
+/// counting tokens issued to OAuth clients
+const MAX_TOKENS_PER_ACCOUNT: i64 = 20;
+
+/// Create an API token.
+///
+/// Locks the account row so concurrent creations cannot exceed
+/// `MAX_TOKENS_PER_ACCOUNT`.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::NotFound` if the account does not exist.
+/// Returns `ApiTokenRepoError::MaxTokensReached` if the account has reached the token limit.
+pub(crate) async fn create_api_token(
+    pool: &PgPool,
+    account_id: Uuid,
+    name: &str,
+    token_hash: &[u8],
+    scopes: &[String],
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<ApiTokenRecord, ApiTokenRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let locked = sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(account_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if locked.is_none() {
+        return Err(ApiTokenRepoError::NotFound);
+    }
+
+    let record = sqlx::query_as::<_, ApiTokenRow>(
+        r"
+        INSERT INTO api_tokens (account_id, name, token_hash, scopes, expires_at)
+        SELECT $1, $2, $3, $4, $5
+        WHERE (SELECT COUNT(*) FROM api_tokens
+               WHERE account_id = $1 AND revoked_at IS NULL AND oauth_client_id IS NULL) < $6
+        RETURNING id, account_id, name, scopes, expires_at, last_used_at, revoked_at, created_at
+        ",
+    )
+    .bind(account_id)
+    .bind(name)
+    .bind(token_hash)
+    .bind(scopes)
+    .bind(expires_at)
+    .bind(MAX_TOKENS_PER_ACCOUNT)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiTokenRepoError::MaxTokensReached)?;
+
+    tx.commit().await?;
+    Ok(record.into())
+}
+
+#[derive(sqlx::FromRow)]
+struct ApiTokenRow {
+    id: Uuid,
+    account_id: Uuid,
+    name: String,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ApiTokenRow> for ApiTokenRecord {
+    fn from(row: ApiTokenRow) -> Self {
+        Self {
+            id: row.id,
+            account_id: row.account_id,
+            name: row.name,
+            scopes: row.scopes,
+            expires_at: row.expires_at,
+            last_used_at: row.last_used_at,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// List all API tokens for an account (including revoked), newest first.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::Database` on database failures.
+pub(crate) async fn list_api_tokens_by_account<'e, E>(
+    executor: E,
+    account_id: Uuid,
+) -> Result<Vec<ApiTokenRecord>, ApiTokenRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query_as::<_, ApiTokenRow>(
+        r"
+        SELECT id, account_id, name, scopes, expires_at, last_used_at, revoked_at, created_at
+        FROM api_tokens
+        WHERE account_id = $1
+        ORDER BY created_at DESC
+        ",
+    )
+    .bind(account_id)
+    .fetch_all(Instrumented::new(executor, "api_tokens.list_by_account"))
+    .await?;
+
+    Ok(rows.into_iter().map(ApiTokenRecord::from).collect())
+}
+
+/// Get an API token by the SHA-256 of its plaintext.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::NotFound` if no token matches the hash.
+pub(crate) async fn get_api_token_by_hash<'e, E>(
+    executor: E,
+    token_hash: &[u8],
+) -> Result<ApiTokenRecord, ApiTokenRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query_as::<_, ApiTokenRow>(
+        r"
+        SELECT id, account_id, name, scopes, expires_at, last_used_at, revoked_at, created_at
+        FROM api_tokens
+        WHERE token_hash = $1
+        ",
+    )
+    .bind(token_hash)
+    .fetch_optional(Instrumented::new(executor, "api_tokens.by_hash"))
+    .await?
+    .map(ApiTokenRecord::from)
+    .ok_or(ApiTokenRepoError::NotFound)
+}
+
+/// Revoke an API token (sets `revoked_at`).
+///
+/// The `account_id` is part of the WHERE clause so the ownership check and the
+/// mutation happen atomically.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::NotFound` if no token matches the ID for this account.
+/// Returns `ApiTokenRepoError::AlreadyRevoked` if the token was already revoked.
+pub(crate) async fn revoke_api_token(
+    pool: &PgPool,
+    token_id: Uuid,
+    account_id: Uuid,
+) -> Result<(), ApiTokenRepoError> {
+    let result = sqlx::query(
+        "UPDATE api_tokens SET revoked_at = now() \
+         WHERE id = $1 AND account_id = $2 AND revoked_at IS NULL",
+    )
+    .bind(token_id)
+    .bind(account_id)
+    .execute(instrument(pool, "api_tokens.revoke"))
+    .await?;
+
+    if result.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    let revoked: Option<bool> = sqlx::query_scalar(
+        "SELECT revoked_at IS NOT NULL FROM api_tokens WHERE id = $1 AND account_id = $2",
+    )
+    .bind(token_id)
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match revoked {
+        Some(true) => Err(ApiTokenRepoError::AlreadyRevoked),
+        Some(false) | None => Err(ApiTokenRepoError::NotFound),
+    }
+}
+
+/// Update `last_used_at` timestamp.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::Database` on database failures.
+pub(crate) async fn touch_api_token(
+    pool: &PgPool,
+    token_id: Uuid,
+) -> Result<(), ApiTokenRepoError> {
+    sqlx::query("UPDATE api_tokens SET last_used_at = now() WHERE id = $1")
+        .bind(token_id)
+        .execute(instrument(pool, "api_tokens.touch"))
+        .await?;
+    Ok(())
+}
+
+/// Request counts for one token in the current day and month.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiTokenUsage {
+    pub daily: i64,
+    pub monthly: i64,
+}
+
+/// A token's request count over a window, for the top-consumers view.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ApiTokenConsumerRecord {
+    pub token_id: Uuid,
+    pub token_name: String,
+    pub account_id: Uuid,
+    pub username: String,
+    pub requests: i64,
+}
+
+/// Load a token's recorded requests for `day` and for the month containing it.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::Database` on database failures.
+pub(crate) async fn get_api_token_usage(
+    pool: &PgPool,
+    token_id: Uuid,
+    day: NaiveDate,
+) -> Result<ApiTokenUsage, ApiTokenRepoError> {
+    let (daily, monthly): (i64, i64) = sqlx::query_as(
+        r"
+        SELECT COALESCE(SUM(requests) FILTER (WHERE day = $2), 0)::BIGINT,
+               COALESCE(SUM(requests), 0)::BIGINT
+        FROM api_token_usage
+        WHERE token_id = $1
+          AND day >= date_trunc('month', $2::date)::date
+          AND day <= $2
+        ",
+    )
+    .bind(token_id)
+    .bind(day)
+    .fetch_one(pool)
+    .await?;
+    Ok(ApiTokenUsage { daily, monthly })
+}
+
+/// Add request counts in one statement. Each entry is `(token_id, day, requests)`.
+///
+/// Counts for tokens deleted since they were counted are dropped.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::Database` on database failures.
+pub(crate) async fn record_api_token_usage(
+    pool: &PgPool,
+    entries: &[(Uuid, NaiveDate, i64)],
+) -> Result<(), ApiTokenRepoError> {
+    let token_ids: Vec<Uuid> = entries.iter().map(|e| e.0).collect();
+    let days: Vec<NaiveDate> = entries.iter().map(|e| e.1).collect();
+    let counts: Vec<i64> = entries.iter().map(|e| e.2).collect();
+    sqlx::query(
+        r"
+        INSERT INTO api_token_usage (token_id, day, requests)
+        SELECT u.token_id, u.day, u.requests
+        FROM UNNEST($1::uuid[], $2::date[], $3::bigint[]) AS u(token_id, day, requests)
+        JOIN api_tokens t ON t.id = u.token_id
+        ON CONFLICT (token_id, day)
+        DO UPDATE SET requests = api_token_usage.requests + EXCLUDED.requests
+        ",
+    )
+    .bind(&token_ids)
+    .bind(&days)
+    .bind(&counts)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Tokens with the most recorded requests since `since`, busiest first.
+///
+/// # Errors
+///
+/// Returns `ApiTokenRepoError::Database` on database failures.
+pub(crate) async fn top_api_token_consumers(
+    pool: &PgPool,
+    since: NaiveDate,
+    limit: i64,
+) -> Result<Vec<ApiTokenConsumerRecord>, ApiTokenRepoError> {
+    let rows = sqlx::query_as::<_, ApiTokenConsumerRecord>(
+        r"
+        SELECT t.id AS token_id, t.name AS token_name, a.id AS account_id, a.username,
+               SUM(u.requests)::BIGINT AS requests
+        FROM api_token_usage u
+        JOIN api_tokens t ON t.id = u.token_id
+        JOIN accounts a ON a.id = t.account_id
+        WHERE u.day >= $1
+        GROUP BY t.id, t.name, a.id, a.username
+        ORDER BY requests DESC, t.id
+        LIMIT $2
+        ",
+    )
+    .bind(since)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
@@ -5,6 +5,8 @@ use sqlx::Row;
 use tc_crypto::Kid;
 use uuid::Uuid;
 
+use crate::db::{map_unique_violation, RepoError, RepoErrorKind};
+
 /// Record returned from backup queries
 #[derive(Debug, Clone)]
 pub struct BackupRecord {
@@ -15,6 +17,9 @@ pub struct BackupRecord {
     pub salt: Vec<u8>,
     pub version: i32,
     pub created_at: DateTime<Utc>,
+    /// Hash of the recovery code required to fetch this backup, if the owner
+    /// set one (see `identity::recovery_code`).
+    pub recovery_code_hash: Option<String>,
 }
 
 /// Result of creating a backup
@@ -38,6 +43,16 @@ pub enum BackupRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for BackupRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::DuplicateAccount | Self::DuplicateKid => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 async fn create_backup<'e, E>(
     executor: E,
     account_id: Uuid,
@@ -60,7 +75,7 @@ where
     )
     .bind(id)
     .bind(account_id)
-    .bind(kid.as_str())
+    .bind(kid)
     .bind(encrypted_backup)
     .bind(salt)
     .bind(version)
@@ -74,20 +89,11 @@ where
             kid: kid.clone(),
             created_at: now,
         }),
-        Err(e) => {
-            if let sqlx::Error::Database(db_err) = &e {
-                if let Some(constraint) = db_err.constraint() {
-                    match constraint {
-                        "uq_account_backups_account" => {
-                            return Err(BackupRepoError::DuplicateAccount)
-                        }
-                        "uq_account_backups_kid" => return Err(BackupRepoError::DuplicateKid),
-                        _ => {}
-                    }
-                }
-            }
-            Err(BackupRepoError::Database(e))
-        }
+        Err(e) => Err(map_unique_violation(e, |constraint| match constraint {
+            "uq_account_backups_account" => Some(BackupRepoError::DuplicateAccount),
+            "uq_account_backups_kid" => Some(BackupRepoError::DuplicateKid),
+            _ => None,
+        })),
     }
 }
 
@@ -126,35 +132,55 @@ where
 {
     let row = sqlx::query(
         r"
-        SELECT id, account_id, kid, encrypted_backup, salt, version, created_at
+        SELECT id, account_id, kid, encrypted_backup, salt, version, created_at,
+               recovery_code_hash
         FROM account_backups
         WHERE kid = $1
         ",
     )
-    .bind(kid.as_str())
+    .bind(kid)
     .fetch_optional(executor)
     .await?
     .ok_or(BackupRepoError::NotFound)?;
 
-    let raw_kid = row.get::<String, _>("kid");
-    let parsed_kid = raw_kid.parse().map_err(|_| {
-        tracing::error!(raw_kid = %raw_kid, "invalid KID in account_backups — data corruption");
-        BackupRepoError::Database(sqlx::Error::Decode(
-            "invalid KID value in account_backups".into(),
-        ))
-    })?;
-
     Ok(BackupRecord {
         id: row.get("id"),
         account_id: row.get("account_id"),
-        kid: parsed_kid,
+        kid: row.try_get("kid")?,
         encrypted_backup: row.get("encrypted_backup"),
         salt: row.get("salt"),
         version: row.get("version"),
         created_at: row.get("created_at"),
+        recovery_code_hash: row.get("recovery_code_hash"),
     })
 }
 
+/// Set or clear (`None`) the recovery code hash on the backup for `kid`.
+///
+/// # Errors
+///
+/// Returns `BackupRepoError::NotFound` if no backup exists for this KID.
+pub(crate) async fn set_backup_recovery_code<'e, E>(
+    executor: E,
+    kid: &Kid,
+    recovery_code_hash: Option<&str>,
+) -> Result<(), BackupRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let result = sqlx::query("UPDATE account_backups SET recovery_code_hash = $2 WHERE kid = $1")
+        .bind(kid)
+        .bind(recovery_code_hash)
+        .execute(executor)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(BackupRepoError::NotFound);
+    }
+
+    Ok(())
+}
+
 pub(crate) async fn delete_backup_by_kid<'e, E>(
     executor: E,
     kid: &Kid,
@@ -163,7 +189,7 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
     let result = sqlx::query("DELETE FROM account_backups WHERE kid = $1")
-        .bind(kid.as_str())
+        .bind(kid)
         .execute(executor)
         .await?;
 
@@ -0,0 +1,90 @@
+//! Published device revocation lists; see [`crate::identity::crl`].
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{instrument, RepoError, RepoErrorKind};
+
+/// Record returned from CRL queries
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AccountCrlRecord {
+    pub account_id: Uuid,
+    pub crl_number: i64,
+    /// The published [`DeviceCrl`](crate::identity::crl::DeviceCrl)
+    pub document: serde_json::Value,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Error types for CRL operations
+#[derive(Debug, thiserror::Error)]
+pub enum CrlRepoError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for CrlRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        RepoErrorKind::Database
+    }
+}
+
+/// Get an account's CRL, unless a device has been revoked since it was
+/// generated.
+///
+/// # Errors
+///
+/// Returns `CrlRepoError::Database` on database failures.
+pub(crate) async fn get_current_account_crl(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Option<AccountCrlRecord>, CrlRepoError> {
+    let record = sqlx::query_as::<_, AccountCrlRecord>(
+        r"
+        SELECT account_id, crl_number, document, generated_at
+        FROM account_crls
+        WHERE account_id = $1
+          AND crl_number = (
+              SELECT COUNT(*) FROM device_keys
+              WHERE account_id = $1 AND revoked_at IS NOT NULL
+          )
+        ",
+    )
+    .bind(account_id)
+    .fetch_optional(instrument(pool, "crls.get_current"))
+    .await?;
+    Ok(record)
+}
+
+/// Store a freshly generated CRL.
+///
+/// A document never replaces one with a higher `crl_number`, so a slow
+/// regeneration cannot roll back a newer revocation.
+///
+/// # Errors
+///
+/// Returns `CrlRepoError::Database` on database failures.
+pub(crate) async fn store_account_crl(
+    pool: &PgPool,
+    account_id: Uuid,
+    crl_number: i64,
+    document: &serde_json::Value,
+) -> Result<(), CrlRepoError> {
+    sqlx::query(
+        r"
+        INSERT INTO account_crls (account_id, crl_number, document)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (account_id) DO UPDATE
+            SET crl_number = EXCLUDED.crl_number,
+                document = EXCLUDED.document,
+                generated_at = now()
+            WHERE account_crls.crl_number <= EXCLUDED.crl_number
+        ",
+    )
+    .bind(account_id)
+    .bind(crl_number)
+    .bind(document)
+    .execute(instrument(pool, "crls.store"))
+    .await?;
+    Ok(())
+}
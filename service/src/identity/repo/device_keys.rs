@@ -6,6 +6,11 @@ use sqlx::Row;
 use tc_crypto::Kid;
 use uuid::Uuid;
 
+use super::sigchain::append_sigchain_link;
+use crate::db::{instrument, map_unique_violation, Instrumented, RepoError, RepoErrorKind};
+use crate::identity::service::{CERTIFICATE_VERSION_RAW, CERTIFICATE_VERSION_TIMESTAMPED};
+use crate::identity::sigchain::NewSigchainLink;
+
 /// Record returned from device key queries
 #[derive(Debug, Clone)]
 pub struct DeviceKeyRecord {
@@ -17,6 +22,24 @@ pub struct DeviceKeyRecord {
     pub certificate: Vec<u8>,
     pub last_used_at: Option<DateTime<Utc>>,
     pub revoked_at: Option<DateTime<Utc>>,
+    /// Device that performed the revocation, if recorded.
+    pub revoked_by_kid: Option<Kid>,
+    /// The revoking device's signed request, as a
+    /// [`SignedRequest`](crate::identity::signed_request::SignedRequest).
+    pub revocation_envelope: Option<serde_json::Value>,
+    /// What the root signed; `None` for devices created before versioning.
+    /// See [`crate::identity::service::device_certificate_message`].
+    pub certificate_version: Option<i32>,
+    /// Login timestamp bound into a version 2 certificate.
+    pub certificate_timestamp: Option<i64>,
+    /// Key this device rotated from, if it was created by a rotation.
+    pub rotated_from_kid: Option<Kid>,
+    /// The old key's signature over the rotation; see
+    /// [`crate::identity::service::device_rotation_message`].
+    pub rotation_signature: Option<Vec<u8>>,
+    /// Envelope versions the device has declared; see
+    /// [`crate::identity::capabilities`].
+    pub client_caps: Vec<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -43,8 +66,19 @@ pub enum DeviceKeyRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for DeviceKeyRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::DuplicateKid | Self::AlreadyRevoked => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::MaxDevicesReached => RepoErrorKind::LimitReached,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 /// Maximum number of devices per account
-const MAX_DEVICES_PER_ACCOUNT: i64 = 10;
+pub const MAX_DEVICES_PER_ACCOUNT: i64 = 10;
 
 async fn create_device_key<'e, E>(
     executor: E,
@@ -53,6 +87,7 @@ async fn create_device_key<'e, E>(
     device_pubkey: &str,
     device_name: &str,
     certificate: &[u8],
+    certificate_timestamp: Option<i64>,
 ) -> Result<CreatedDeviceKey, DeviceKeyRepoError>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
@@ -65,20 +100,27 @@ where
     // so the count is consistent with the insert.
     let result = sqlx::query(
         r"
-        INSERT INTO device_keys (id, account_id, device_kid, device_pubkey, device_name, certificate, created_at)
-        SELECT $1, $2, $3, $4, $5, $6, $7
-        WHERE (SELECT COUNT(*) FROM device_keys WHERE account_id = $2 AND revoked_at IS NULL) < $8
+        INSERT INTO device_keys (id, account_id, device_kid, device_pubkey, device_name, certificate,
+                                 certificate_version, certificate_timestamp, created_at)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9
+        WHERE (SELECT COUNT(*) FROM device_keys WHERE account_id = $2 AND revoked_at IS NULL) < $10
         ",
     )
     .bind(id)
     .bind(account_id)
-    .bind(device_kid.as_str())
+    .bind(device_kid)
     .bind(device_pubkey)
     .bind(device_name)
     .bind(certificate)
+    .bind(if certificate_timestamp.is_some() {
+        CERTIFICATE_VERSION_TIMESTAMPED
+    } else {
+        CERTIFICATE_VERSION_RAW
+    })
+    .bind(certificate_timestamp)
     .bind(now)
     .bind(MAX_DEVICES_PER_ACCOUNT)
-    .execute(executor)
+    .execute(Instrumented::new(executor, "device_keys.create"))
     .await;
 
     match result {
@@ -92,16 +134,9 @@ where
                 created_at: now,
             })
         }
-        Err(e) => {
-            if let sqlx::Error::Database(db_err) = &e {
-                if let Some(constraint) = db_err.constraint() {
-                    if constraint == "uq_device_keys_kid" {
-                        return Err(DeviceKeyRepoError::DuplicateKid);
-                    }
-                }
-            }
-            Err(DeviceKeyRepoError::Database(e))
-        }
+        Err(e) => Err(map_unique_violation(e, |constraint| {
+            (constraint == "uq_device_keys_kid").then_some(DeviceKeyRepoError::DuplicateKid)
+        })),
     }
 }
 
@@ -123,6 +158,7 @@ pub async fn create_device_key_with_executor(
     device_pubkey: &str,
     device_name: &str,
     certificate: &[u8],
+    certificate_timestamp: Option<i64>,
 ) -> Result<CreatedDeviceKey, DeviceKeyRepoError> {
     // Lock the account row to serialize concurrent device additions.
     // Fail explicitly if the account doesn't exist rather than letting the
@@ -142,29 +178,29 @@ pub async fn create_device_key_with_executor(
         device_pubkey,
         device_name,
         certificate,
+        certificate_timestamp,
     )
     .await
 }
 
 #[allow(clippy::needless_pass_by_value)]
 fn map_device_key_row(row: sqlx::postgres::PgRow) -> Result<DeviceKeyRecord, DeviceKeyRepoError> {
-    let raw_kid = row.get::<String, _>("device_kid");
-    let device_kid = raw_kid.parse().map_err(|_| {
-        tracing::error!(raw_kid = %raw_kid, "invalid KID in device_keys — data corruption");
-        DeviceKeyRepoError::Database(sqlx::Error::Decode(
-            "invalid KID value in device_keys".into(),
-        ))
-    })?;
-
     Ok(DeviceKeyRecord {
         id: row.get("id"),
         account_id: row.get("account_id"),
-        device_kid,
+        device_kid: row.try_get("device_kid")?,
         device_pubkey: row.get("device_pubkey"),
         device_name: row.get("device_name"),
         certificate: row.get("certificate"),
         last_used_at: row.get("last_used_at"),
         revoked_at: row.get("revoked_at"),
+        revoked_by_kid: row.try_get("revoked_by_kid")?,
+        revocation_envelope: row.get("revocation_envelope"),
+        certificate_version: row.get("certificate_version"),
+        certificate_timestamp: row.get("certificate_timestamp"),
+        rotated_from_kid: row.try_get("rotated_from_kid")?,
+        rotation_signature: row.get("rotation_signature"),
+        client_caps: row.get("client_caps"),
         created_at: row.get("created_at"),
     })
 }
@@ -184,14 +220,16 @@ where
     let rows = sqlx::query(
         r"
         SELECT id, account_id, device_kid, device_pubkey, device_name,
-               certificate, last_used_at, revoked_at, created_at
+               certificate, last_used_at, revoked_at, revoked_by_kid,
+               revocation_envelope, certificate_version, certificate_timestamp,
+               rotated_from_kid, rotation_signature, client_caps, created_at
         FROM device_keys
         WHERE account_id = $1
         ORDER BY created_at ASC
         ",
     )
     .bind(account_id)
-    .fetch_all(executor)
+    .fetch_all(Instrumented::new(executor, "device_keys.list_by_account"))
     .await?;
 
     rows.into_iter().map(map_device_key_row).collect()
@@ -212,13 +250,15 @@ where
     let row = sqlx::query(
         r"
         SELECT id, account_id, device_kid, device_pubkey, device_name,
-               certificate, last_used_at, revoked_at, created_at
+               certificate, last_used_at, revoked_at, revoked_by_kid,
+               revocation_envelope, certificate_version, certificate_timestamp,
+               rotated_from_kid, rotation_signature, client_caps, created_at
         FROM device_keys
         WHERE device_kid = $1
         ",
     )
-    .bind(device_kid.as_str())
-    .fetch_optional(executor)
+    .bind(device_kid)
+    .fetch_optional(Instrumented::new(executor, "device_keys.by_kid"))
     .await?
     .ok_or(DeviceKeyRepoError::NotFound)?;
 
@@ -231,8 +271,8 @@ async fn not_found_or_revoked(pool: &PgPool, device_kid: &Kid) -> DeviceKeyRepoE
     let exists = sqlx::query(
         "SELECT revoked_at IS NOT NULL AS is_revoked FROM device_keys WHERE device_kid = $1",
     )
-    .bind(device_kid.as_str())
-    .fetch_optional(pool)
+    .bind(device_kid)
+    .fetch_optional(instrument(pool, "device_keys.revoked_status"))
     .await;
 
     match exists {
@@ -255,7 +295,8 @@ async fn ensure_active_device_updated(
     Ok(())
 }
 
-/// Revoke a device key (sets `revoked_at`).
+/// Revoke a device key (sets `revoked_at` and records the revoking device
+/// and its signed request) and append `link` to the account's sigchain.
 ///
 /// The `account_id` is included in the WHERE clause so ownership check and
 /// mutation happen atomically in a single query, eliminating the TOCTOU race
@@ -270,20 +311,134 @@ pub(crate) async fn revoke_device_key(
     pool: &PgPool,
     device_kid: &Kid,
     account_id: Uuid,
+    revoked_by: &Kid,
+    revocation_envelope: Option<&serde_json::Value>,
+    link: &NewSigchainLink,
 ) -> Result<(), DeviceKeyRepoError> {
+    let mut tx = pool.begin().await?;
     let result = sqlx::query(
-        "UPDATE device_keys SET revoked_at = now() \
+        "UPDATE device_keys SET revoked_at = now(), revoked_by_kid = $3, revocation_envelope = $4 \
          WHERE device_kid = $1 AND account_id = $2 AND revoked_at IS NULL",
     )
-    .bind(device_kid.as_str())
+    .bind(device_kid)
     .bind(account_id)
-    .execute(pool)
+    .bind(revoked_by)
+    .bind(revocation_envelope)
+    .execute(Instrumented::new(&mut *tx, "device_keys.revoke"))
     .await?;
 
-    ensure_active_device_updated(pool, result, device_kid).await
+    ensure_active_device_updated(pool, result, device_kid).await?;
+    append_sigchain_link(&mut tx, account_id, link).await?;
+    tx.commit().await?;
+    Ok(())
 }
 
-/// Rename a device.
+/// A device replacing its own key pair.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceRotation<'a> {
+    pub account_id: Uuid,
+    pub old_kid: &'a Kid,
+    pub new_kid: &'a Kid,
+    pub new_pubkey: &'a str,
+    /// Root signature over the new public key (and timestamp, if set)
+    pub certificate: &'a [u8],
+    /// Unix seconds bound into a version 2 certificate
+    pub certificate_timestamp: Option<i64>,
+    /// Old key's signature over the rotation
+    pub rotation_signature: &'a [u8],
+    /// The old device's signed rotation request, kept for the account's CRL
+    pub revocation_envelope: &'a serde_json::Value,
+    /// Sigchain link recording the rotation
+    pub link: &'a NewSigchainLink,
+}
+
+/// Replace a device key: revoke the old KID, insert the new one, linked to
+/// it and keeping its name and declared capabilities, and append the
+/// rotation's sigchain link, in one transaction.
+///
+/// The old key is recorded as its own revoker, with the rotation request as
+/// the revocation envelope.
+///
+/// # Errors
+///
+/// Returns `DeviceKeyRepoError::NotFound` if the old key does not belong to
+/// the account, `AlreadyRevoked` if it is revoked (including by an earlier
+/// rotation), and `DuplicateKid` if the new KID is already registered.
+pub(crate) async fn rotate_device_key(
+    pool: &PgPool,
+    rotation: &DeviceRotation<'_>,
+) -> Result<CreatedDeviceKey, DeviceKeyRepoError> {
+    let mut tx = pool.begin().await?;
+
+    // Lock the old row so concurrent rotations of one device serialize
+    let old = sqlx::query(
+        "SELECT device_name, client_caps, revoked_at IS NOT NULL AS is_revoked FROM device_keys \
+         WHERE device_kid = $1 AND account_id = $2 FOR UPDATE",
+    )
+    .bind(rotation.old_kid)
+    .bind(rotation.account_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(DeviceKeyRepoError::NotFound)?;
+    if old.get::<bool, _>("is_revoked") {
+        return Err(DeviceKeyRepoError::AlreadyRevoked);
+    }
+    let device_name: String = old.get("device_name");
+    let client_caps: Vec<String> = old.get("client_caps");
+
+    sqlx::query(
+        "UPDATE device_keys SET revoked_at = now(), revoked_by_kid = $1, revocation_envelope = $2 \
+         WHERE device_kid = $1",
+    )
+    .bind(rotation.old_kid)
+    .bind(rotation.revocation_envelope)
+    .execute(&mut *tx)
+    .await?;
+
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+    sqlx::query(
+        r"
+        INSERT INTO device_keys (id, account_id, device_kid, device_pubkey, device_name, certificate,
+                                 certificate_version, certificate_timestamp, created_at,
+                                 rotated_from_kid, rotation_signature, client_caps)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ",
+    )
+    .bind(id)
+    .bind(rotation.account_id)
+    .bind(rotation.new_kid)
+    .bind(rotation.new_pubkey)
+    .bind(&device_name)
+    .bind(rotation.certificate)
+    .bind(if rotation.certificate_timestamp.is_some() {
+        CERTIFICATE_VERSION_TIMESTAMPED
+    } else {
+        CERTIFICATE_VERSION_RAW
+    })
+    .bind(rotation.certificate_timestamp)
+    .bind(now)
+    .bind(rotation.old_kid)
+    .bind(rotation.rotation_signature)
+    .bind(&client_caps)
+    .execute(Instrumented::new(&mut *tx, "device_keys.rotate"))
+    .await
+    .map_err(|e| {
+        map_unique_violation(e, |constraint| {
+            (constraint == "uq_device_keys_kid").then_some(DeviceKeyRepoError::DuplicateKid)
+        })
+    })?;
+
+    append_sigchain_link(&mut tx, rotation.account_id, rotation.link).await?;
+    tx.commit().await?;
+    Ok(CreatedDeviceKey {
+        id,
+        device_kid: rotation.new_kid.clone(),
+        created_at: now,
+    })
+}
+
+/// Rename a device and append `link` to the account's sigchain.
 ///
 /// The `account_id` is included in the WHERE clause so ownership check and
 /// mutation happen atomically in a single query, eliminating the TOCTOU race
@@ -299,18 +454,23 @@ pub(crate) async fn rename_device_key(
     device_kid: &Kid,
     account_id: Uuid,
     new_name: &str,
+    link: &NewSigchainLink,
 ) -> Result<(), DeviceKeyRepoError> {
+    let mut tx = pool.begin().await?;
     let result = sqlx::query(
         "UPDATE device_keys SET device_name = $1 \
          WHERE device_kid = $2 AND account_id = $3 AND revoked_at IS NULL",
     )
     .bind(new_name)
-    .bind(device_kid.as_str())
+    .bind(device_kid)
     .bind(account_id)
-    .execute(pool)
+    .execute(Instrumented::new(&mut *tx, "device_keys.rename"))
     .await?;
 
-    ensure_active_device_updated(pool, result, device_kid).await
+    ensure_active_device_updated(pool, result, device_kid).await?;
+    append_sigchain_link(&mut tx, account_id, link).await?;
+    tx.commit().await?;
+    Ok(())
 }
 
 /// Update `last_used_at` timestamp.
@@ -326,9 +486,158 @@ pub(crate) async fn touch_device_key(
     let result = sqlx::query(
         "UPDATE device_keys SET last_used_at = now() WHERE device_kid = $1 AND revoked_at IS NULL",
     )
-    .bind(device_kid.as_str())
-    .execute(pool)
+    .bind(device_kid)
+    .execute(instrument(pool, "device_keys.touch"))
     .await?;
 
     ensure_active_device_updated(pool, result, device_kid).await
 }
+
+/// Add capability tokens to a device's recorded set; tokens already
+/// recorded are kept, so declared versions never go down.
+///
+/// # Errors
+///
+/// Returns `DeviceKeyRepoError::NotFound` if no device key matches the given KID.
+pub(crate) async fn record_device_caps<'e, E>(
+    executor: E,
+    device_kid: &Kid,
+    tokens: &[String],
+) -> Result<(), DeviceKeyRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let result = sqlx::query(
+        r"
+        UPDATE device_keys
+        SET client_caps = ARRAY(
+            SELECT DISTINCT t FROM unnest(client_caps || $2::text[]) AS t ORDER BY t
+        )
+        WHERE device_kid = $1
+        ",
+    )
+    .bind(device_kid)
+    .bind(tokens)
+    .execute(Instrumented::new(executor, "device_keys.record_caps"))
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DeviceKeyRepoError::NotFound);
+    }
+    Ok(())
+}
+
+/// Recent request counts for one device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceUsageRecord {
+    pub device_kid: Kid,
+    pub requests_24h: i64,
+    pub requests_7d: i64,
+    pub last_endpoint: Option<String>,
+}
+
+/// Buffered per-device usage to write in one batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceUsageBatch {
+    /// `(device_kid, hour bucket, requests)`
+    pub counts: Vec<(Kid, DateTime<Utc>, i64)>,
+    /// `(device_kid, endpoint)` for each device's latest request
+    pub last_endpoints: Vec<(Kid, String)>,
+}
+
+/// Usage buckets older than this are deleted on each flush.
+const DEVICE_USAGE_RETENTION_DAYS: i32 = 8;
+
+/// Add buffered request counts, set last endpoints, and prune old buckets.
+///
+/// Counts for devices deleted since they were buffered are dropped.
+///
+/// # Errors
+///
+/// Returns `DeviceKeyRepoError::Database` on database failures.
+pub(crate) async fn record_device_usage(
+    pool: &PgPool,
+    batch: &DeviceUsageBatch,
+) -> Result<(), DeviceKeyRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let kids: Vec<&str> = batch.counts.iter().map(|c| c.0.as_str()).collect();
+    let hours: Vec<DateTime<Utc>> = batch.counts.iter().map(|c| c.1).collect();
+    let counts: Vec<i64> = batch.counts.iter().map(|c| c.2).collect();
+    sqlx::query(
+        r"
+        INSERT INTO device_key_usage (device_kid, hour, requests)
+        SELECT u.device_kid, u.hour, u.requests
+        FROM UNNEST($1::text[], $2::timestamptz[], $3::bigint[]) AS u(device_kid, hour, requests)
+        JOIN device_keys d ON d.device_kid = u.device_kid
+        ON CONFLICT (device_kid, hour)
+        DO UPDATE SET requests = device_key_usage.requests + EXCLUDED.requests
+        ",
+    )
+    .bind(&kids)
+    .bind(&hours)
+    .bind(&counts)
+    .execute(&mut *tx)
+    .await?;
+
+    let endpoint_kids: Vec<&str> = batch.last_endpoints.iter().map(|e| e.0.as_str()).collect();
+    let endpoints: Vec<&str> = batch.last_endpoints.iter().map(|e| e.1.as_str()).collect();
+    sqlx::query(
+        r"
+        UPDATE device_keys d SET last_endpoint = u.endpoint
+        FROM UNNEST($1::text[], $2::text[]) AS u(device_kid, endpoint)
+        WHERE d.device_kid = u.device_kid
+        ",
+    )
+    .bind(&endpoint_kids)
+    .bind(&endpoints)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM device_key_usage WHERE hour < now() - make_interval(days => $1)")
+        .bind(DEVICE_USAGE_RETENTION_DAYS)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Request counts over the last 24 hours and 7 days for each of an account's
+/// devices, at hourly granularity.
+///
+/// # Errors
+///
+/// Returns `DeviceKeyRepoError::Database` on database failures.
+pub(crate) async fn list_device_usage_by_account(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<DeviceUsageRecord>, DeviceKeyRepoError> {
+    let rows = sqlx::query(
+        r"
+        SELECT d.device_kid, d.last_endpoint,
+               COALESCE(SUM(u.requests) FILTER (WHERE u.hour > now() - interval '24 hours'), 0)::BIGINT
+                   AS requests_24h,
+               COALESCE(SUM(u.requests), 0)::BIGINT AS requests_7d
+        FROM device_keys d
+        LEFT JOIN device_key_usage u
+            ON u.device_kid = d.device_kid AND u.hour > now() - interval '7 days'
+        WHERE d.account_id = $1
+        GROUP BY d.device_kid, d.last_endpoint
+        ",
+    )
+    .bind(account_id)
+    .fetch_all(instrument(pool, "device_keys.usage_by_account"))
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(DeviceUsageRecord {
+                device_kid: row.try_get("device_kid")?,
+                requests_24h: row.get("requests_24h"),
+                requests_7d: row.get("requests_7d"),
+                last_endpoint: row.get("last_endpoint"),
+            })
+        })
+        .collect()
+}
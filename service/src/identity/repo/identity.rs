@@ -1,8 +1,9 @@
 //! Consolidated identity repository trait
 //!
 //! Provides a single [`IdentityRepo`] that combines all identity persistence
-//! operations (accounts, backups, device keys) plus a compound [`IdentityRepo::create_signup`]
-//! that wraps the three inserts in a single transaction.
+//! operations (accounts, aliases, backups, device keys, sessions, CRLs,
+//! sigchains, API tokens, signup invites) plus a compound [`IdentityRepo::create_signup`] that wraps the
+//! signup inserts in a single transaction.
 
 use async_trait::async_trait;
 use sqlx::PgPool;
@@ -10,19 +11,61 @@ use tc_crypto::Kid;
 use uuid::Uuid;
 
 use super::accounts::{
-    create_account_with_executor, get_account_by_id, get_account_by_username, AccountRecord,
-    AccountRepoError, CreatedAccount,
+    change_account_username, create_account_with_executor, deactivate_account, get_account_by_id,
+    get_account_by_username, get_account_status, list_purge_due, purge_account, reactivate_account,
+    update_account_status, AccountDeactivation, AccountPurge, AccountReactivation, AccountRecord,
+    AccountRepoError, CreatedAccount, UsernameChange,
+};
+use super::aliases::{
+    create_account_alias, delete_account_alias, list_account_aliases, resolve_account_handle,
+    AccountAliasRecord, AliasRepoError, ResolvedHandle,
+};
+use super::api_tokens::{
+    create_api_token, get_api_token_by_hash, get_api_token_usage, list_api_tokens_by_account,
+    record_api_token_usage, revoke_api_token, top_api_token_consumers, touch_api_token,
+    ApiTokenConsumerRecord, ApiTokenRecord, ApiTokenRepoError, ApiTokenUsage,
 };
 use super::backups::{
-    create_backup_with_executor, delete_backup_by_kid, get_backup_by_kid, BackupRecord,
-    BackupRepoError, CreatedBackup,
+    create_backup_with_executor, delete_backup_by_kid, get_backup_by_kid, set_backup_recovery_code,
+    BackupRecord, BackupRepoError, CreatedBackup,
 };
+use super::crls::{get_current_account_crl, store_account_crl, AccountCrlRecord, CrlRepoError};
 use super::device_keys::{
     create_device_key_with_executor, get_device_key_by_kid, list_device_keys_by_account,
-    rename_device_key, revoke_device_key, touch_device_key, CreatedDeviceKey, DeviceKeyRecord,
-    DeviceKeyRepoError,
+    list_device_usage_by_account, record_device_caps, record_device_usage, rename_device_key,
+    revoke_device_key, rotate_device_key, touch_device_key, CreatedDeviceKey, DeviceKeyRecord,
+    DeviceKeyRepoError, DeviceRotation, DeviceUsageBatch, DeviceUsageRecord,
+};
+use super::nonces::{
+    check_and_record_nonce, cleanup_expired_nonces, consume_auth_challenge, create_auth_challenge,
+    NonceRepoError,
+};
+use super::reserved_usernames::{
+    create_reserved_username, delete_released_handle, delete_reserved_username,
+    get_released_handle, list_released_handles, list_reserved_usernames, ReleasedHandleRecord,
+    ReservedUsernameRecord, ReservedUsernameRepoError,
+};
+use super::sessions::{
+    create_device_session, get_device_session_by_hash, revoke_device_sessions, DeviceSessionRecord,
+    DeviceSessionRepoError,
+};
+use super::sigchain::{
+    append_sigchain_link, get_sigchain_head, list_sigchain_links, SigchainLinkRecord,
+    SigchainRepoError,
+};
+use super::signup_invites::{
+    create_signup_invite, list_signup_invites_by_account, redeem_signup_invite_with_executor,
+    SignupInviteRecord, SignupInviteRepoError,
+};
+use super::step_up::{
+    confirm_step_up_challenge, consume_step_up_challenge, create_step_up_challenge,
+    delete_step_up_phone, get_step_up_phone, NewStepUpChallenge, StepUpChallengeRecord,
+    StepUpPhoneRecord, StepUpRepoError,
 };
-use super::nonces::{check_and_record_nonce, cleanup_expired_nonces, NonceRepoError};
+use crate::db::{RepoError, RepoErrorKind};
+use crate::identity::sigchain::NewSigchainLink;
+use crate::identity::status::AccountStatus;
+use crate::identity::step_up::StepUpAction;
 
 /// Validated signup data ready for persistence.
 ///
@@ -39,6 +82,10 @@ pub struct ValidatedSignup {
     pub(crate) device_kid: Kid,
     pub(crate) device_name: String,
     pub(crate) certificate: Vec<u8>,
+    /// Capability tokens the first device declared.
+    pub(crate) client_caps: Vec<String>,
+    /// SHA-256 of the invite code to redeem, on invite-only instances.
+    pub(crate) invite_code_hash: Option<Vec<u8>>,
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -73,6 +120,8 @@ impl ValidatedSignup {
             device_kid,
             device_name,
             certificate,
+            client_caps: Vec::new(),
+            invite_code_hash: None,
         }
     }
 }
@@ -94,10 +143,24 @@ pub enum CreateSignupError {
     Backup(BackupRepoError),
     #[error("device key error: {0}")]
     DeviceKey(DeviceKeyRepoError),
+    #[error("invite error: {0}")]
+    Invite(SignupInviteRepoError),
     #[error("transaction error: {0}")]
     Transaction(sqlx::Error),
 }
 
+impl RepoError for CreateSignupError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::Account(e) => e.kind(),
+            Self::Backup(e) => e.kind(),
+            Self::DeviceKey(e) => e.kind(),
+            Self::Invite(e) => e.kind(),
+            Self::Transaction(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 /// Consolidated repository trait for identity persistence.
 ///
 /// Combines account, backup, and device key operations into a single trait,
@@ -121,6 +184,65 @@ pub trait IdentityRepo: Send + Sync {
         username: &str,
     ) -> Result<AccountRecord, AccountRepoError>;
 
+    async fn get_account_status(&self, account_id: Uuid)
+        -> Result<AccountStatus, AccountRepoError>;
+
+    /// Compare-and-set the account status; `None` if it was no longer `from`.
+    async fn update_account_status(
+        &self,
+        account_id: Uuid,
+        from: AccountStatus,
+        to: AccountStatus,
+        actor_account_id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, AccountRepoError>;
+
+    /// Rename the account and append `link` to its sigchain, unless it last
+    /// did so after `cooldown_cutoff`.
+    async fn change_account_username(
+        &self,
+        account_id: Uuid,
+        username: &str,
+        cooldown_cutoff: chrono::DateTime<chrono::Utc>,
+        link: &NewSigchainLink,
+    ) -> Result<UsernameChange, AccountRepoError>;
+
+    /// Deactivate an `active` account until `purge_after`, revoke all its
+    /// devices and append `link` to its sigchain; `None` if it was not
+    /// `active`.
+    async fn deactivate_account(
+        &self,
+        account_id: Uuid,
+        purge_after: chrono::DateTime<chrono::Utc>,
+        revoked_by: &Kid,
+        revocation_envelope: &serde_json::Value,
+        reason: Option<&str>,
+        link: &NewSigchainLink,
+    ) -> Result<Option<AccountDeactivation>, AccountRepoError>;
+
+    /// Restore a `deactivated` account whose grace period has not ended and
+    /// append `link` to its sigchain.
+    async fn reactivate_account(
+        &self,
+        account_id: Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+        link: &NewSigchainLink,
+    ) -> Result<AccountReactivation, AccountRepoError>;
+
+    /// Deactivated accounts whose grace period ended by `now`.
+    async fn list_purge_due(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, AccountRepoError>;
+
+    /// Hard-delete a deactivated account whose grace period ended by `now`.
+    async fn purge_account(
+        &self,
+        account_id: Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<AccountPurge, AccountRepoError>;
+
     // Backup operations
 
     async fn create_backup(
@@ -136,8 +258,18 @@ pub trait IdentityRepo: Send + Sync {
 
     async fn delete_backup_by_kid(&self, kid: &Kid) -> Result<(), BackupRepoError>;
 
+    /// Set or clear the recovery code hash gating a backup.
+    async fn set_backup_recovery_code(
+        &self,
+        kid: &Kid,
+        recovery_code_hash: Option<&str>,
+    ) -> Result<(), BackupRepoError>;
+
     // Device key operations
 
+    /// Register a device, appending `link` to the account's sigchain when
+    /// the addition was signed by another device.
+    #[allow(clippy::too_many_arguments)]
     async fn create_device_key(
         &self,
         account_id: Uuid,
@@ -145,6 +277,8 @@ pub trait IdentityRepo: Send + Sync {
         device_pubkey: &str,
         device_name: &str,
         certificate: &[u8],
+        certificate_timestamp: Option<i64>,
+        link: Option<&NewSigchainLink>,
     ) -> Result<CreatedDeviceKey, DeviceKeyRepoError>;
 
     async fn list_device_keys_by_account(
@@ -161,6 +295,9 @@ pub trait IdentityRepo: Send + Sync {
         &self,
         device_kid: &Kid,
         account_id: Uuid,
+        revoked_by: &Kid,
+        revocation_envelope: Option<&serde_json::Value>,
+        link: &NewSigchainLink,
     ) -> Result<(), DeviceKeyRepoError>;
 
     async fn rename_device_key(
@@ -168,10 +305,225 @@ pub trait IdentityRepo: Send + Sync {
         device_kid: &Kid,
         account_id: Uuid,
         new_name: &str,
+        link: &NewSigchainLink,
     ) -> Result<(), DeviceKeyRepoError>;
 
+    /// Atomically revoke a device key, register its replacement and append
+    /// the rotation's sigchain link.
+    async fn rotate_device_key(
+        &self,
+        rotation: &DeviceRotation<'_>,
+    ) -> Result<CreatedDeviceKey, DeviceKeyRepoError>;
+
     async fn touch_device_key(&self, device_kid: &Kid) -> Result<(), DeviceKeyRepoError>;
 
+    /// Add capability tokens to the device's recorded set.
+    async fn record_device_caps(
+        &self,
+        device_kid: &Kid,
+        tokens: &[String],
+    ) -> Result<(), DeviceKeyRepoError>;
+
+    async fn record_device_usage(&self, batch: &DeviceUsageBatch)
+        -> Result<(), DeviceKeyRepoError>;
+
+    async fn list_device_usage_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<DeviceUsageRecord>, DeviceKeyRepoError>;
+
+    // Alias operations
+
+    /// Resolve a username or alias to its account.
+    async fn resolve_account_handle(
+        &self,
+        handle: &str,
+    ) -> Result<ResolvedHandle, AccountRepoError>;
+
+    async fn create_account_alias(
+        &self,
+        account_id: Uuid,
+        alias: &str,
+        reserved: bool,
+    ) -> Result<AccountAliasRecord, AliasRepoError>;
+
+    async fn list_account_aliases(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<AccountAliasRecord>, AliasRepoError>;
+
+    async fn delete_account_alias(
+        &self,
+        alias: &str,
+        account_id: Uuid,
+    ) -> Result<(), AliasRepoError>;
+
+    // API token operations
+
+    async fn create_api_token(
+        &self,
+        account_id: Uuid,
+        name: &str,
+        token_hash: &[u8],
+        scopes: &[String],
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<ApiTokenRecord, ApiTokenRepoError>;
+
+    async fn list_api_tokens_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<ApiTokenRecord>, ApiTokenRepoError>;
+
+    async fn get_api_token_by_hash(
+        &self,
+        token_hash: &[u8],
+    ) -> Result<ApiTokenRecord, ApiTokenRepoError>;
+
+    async fn revoke_api_token(
+        &self,
+        token_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<(), ApiTokenRepoError>;
+
+    async fn touch_api_token(&self, token_id: Uuid) -> Result<(), ApiTokenRepoError>;
+
+    async fn get_api_token_usage(
+        &self,
+        token_id: Uuid,
+        day: chrono::NaiveDate,
+    ) -> Result<ApiTokenUsage, ApiTokenRepoError>;
+
+    async fn record_api_token_usage(
+        &self,
+        entries: &[(Uuid, chrono::NaiveDate, i64)],
+    ) -> Result<(), ApiTokenRepoError>;
+
+    async fn top_api_token_consumers(
+        &self,
+        since: chrono::NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<ApiTokenConsumerRecord>, ApiTokenRepoError>;
+
+    // Signup invite operations
+
+    async fn create_signup_invite(
+        &self,
+        created_by: Uuid,
+        code_hash: &[u8],
+        expires_at: chrono::DateTime<chrono::Utc>,
+        max_outstanding: Option<i64>,
+    ) -> Result<SignupInviteRecord, SignupInviteRepoError>;
+
+    async fn list_signup_invites_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<SignupInviteRecord>, SignupInviteRepoError>;
+
+    // Reserved username operations
+
+    async fn list_reserved_usernames(
+        &self,
+    ) -> Result<Vec<ReservedUsernameRecord>, ReservedUsernameRepoError>;
+
+    async fn create_reserved_username(
+        &self,
+        name: &str,
+        reason: Option<&str>,
+        created_by: Uuid,
+    ) -> Result<ReservedUsernameRecord, ReservedUsernameRepoError>;
+
+    async fn delete_reserved_username(&self, name: &str) -> Result<(), ReservedUsernameRepoError>;
+
+    async fn get_released_handle(
+        &self,
+        handle: &str,
+    ) -> Result<Option<ReleasedHandleRecord>, ReservedUsernameRepoError>;
+
+    async fn list_released_handles(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<ReleasedHandleRecord>, ReservedUsernameRepoError>;
+
+    async fn delete_released_handle(&self, handle: &str) -> Result<(), ReservedUsernameRepoError>;
+
+    // Device revocation list operations
+
+    async fn get_current_account_crl(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<AccountCrlRecord>, CrlRepoError>;
+
+    async fn store_account_crl(
+        &self,
+        account_id: Uuid,
+        crl_number: i64,
+        document: &serde_json::Value,
+    ) -> Result<(), CrlRepoError>;
+
+    // Sigchain operations
+
+    async fn list_sigchain_links(
+        &self,
+        account_id: Uuid,
+        since_seqno: i64,
+        limit: i64,
+    ) -> Result<Vec<SigchainLinkRecord>, SigchainRepoError>;
+
+    async fn get_sigchain_head(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<SigchainLinkRecord>, SigchainRepoError>;
+
+    // Device session operations
+
+    async fn create_device_session(
+        &self,
+        account_id: Uuid,
+        device_kid: &Kid,
+        token_hash: &[u8],
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<DeviceSessionRecord, DeviceSessionRepoError>;
+
+    async fn get_device_session_by_hash(
+        &self,
+        token_hash: &[u8],
+    ) -> Result<DeviceSessionRecord, DeviceSessionRepoError>;
+
+    async fn revoke_device_sessions(&self, device_kid: &Kid)
+        -> Result<u64, DeviceSessionRepoError>;
+
+    // Step-up phone and challenge operations
+
+    async fn get_step_up_phone(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<StepUpPhoneRecord>, StepUpRepoError>;
+
+    async fn delete_step_up_phone(&self, account_id: Uuid) -> Result<(), StepUpRepoError>;
+
+    async fn create_step_up_challenge(
+        &self,
+        new: &NewStepUpChallenge<'_>,
+    ) -> Result<StepUpChallengeRecord, StepUpRepoError>;
+
+    async fn confirm_step_up_challenge(
+        &self,
+        challenge_id: Uuid,
+        account_id: Uuid,
+        device_kid: &Kid,
+        code_hash: &[u8],
+        max_attempts: i32,
+    ) -> Result<StepUpChallengeRecord, StepUpRepoError>;
+
+    async fn consume_step_up_challenge(
+        &self,
+        challenge_id: Uuid,
+        account_id: Uuid,
+        device_kid: &Kid,
+        action: StepUpAction,
+        confirmed_after: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), StepUpRepoError>;
+
     // Nonce operations (replay prevention)
 
     /// Record a nonce hash. Returns `NonceRepoError::Replay` if already seen.
@@ -180,6 +532,22 @@ pub trait IdentityRepo: Send + Sync {
     /// Delete nonces older than `max_age_secs`. Returns count of deleted rows.
     async fn cleanup_expired_nonces(&self, max_age_secs: i64) -> Result<u64, NonceRepoError>;
 
+    /// Store a server-issued challenge hash for a device.
+    async fn create_auth_challenge(
+        &self,
+        device_kid: &Kid,
+        challenge_hash: &[u8],
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), NonceRepoError>;
+
+    /// Use up a challenge. Returns `NonceRepoError::UnknownChallenge` unless
+    /// it was issued to `device_kid`, is unexpired, and was not used before.
+    async fn consume_auth_challenge(
+        &self,
+        device_kid: &Kid,
+        challenge_hash: &[u8],
+    ) -> Result<(), NonceRepoError>;
+
     // Compound: atomic signup (account + backup + device key in one transaction)
 
     async fn create_signup(
@@ -222,6 +590,80 @@ impl IdentityRepo for PgIdentityRepo {
         get_account_by_username(&self.pool, username).await
     }
 
+    async fn get_account_status(
+        &self,
+        account_id: Uuid,
+    ) -> Result<AccountStatus, AccountRepoError> {
+        get_account_status(&self.pool, account_id).await
+    }
+
+    async fn update_account_status(
+        &self,
+        account_id: Uuid,
+        from: AccountStatus,
+        to: AccountStatus,
+        actor_account_id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, AccountRepoError> {
+        update_account_status(&self.pool, account_id, from, to, actor_account_id, reason).await
+    }
+
+    async fn change_account_username(
+        &self,
+        account_id: Uuid,
+        username: &str,
+        cooldown_cutoff: chrono::DateTime<chrono::Utc>,
+        link: &NewSigchainLink,
+    ) -> Result<UsernameChange, AccountRepoError> {
+        change_account_username(&self.pool, account_id, username, cooldown_cutoff, link).await
+    }
+
+    async fn deactivate_account(
+        &self,
+        account_id: Uuid,
+        purge_after: chrono::DateTime<chrono::Utc>,
+        revoked_by: &Kid,
+        revocation_envelope: &serde_json::Value,
+        reason: Option<&str>,
+        link: &NewSigchainLink,
+    ) -> Result<Option<AccountDeactivation>, AccountRepoError> {
+        deactivate_account(
+            &self.pool,
+            account_id,
+            purge_after,
+            revoked_by,
+            revocation_envelope,
+            reason,
+            link,
+        )
+        .await
+    }
+
+    async fn reactivate_account(
+        &self,
+        account_id: Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+        link: &NewSigchainLink,
+    ) -> Result<AccountReactivation, AccountRepoError> {
+        reactivate_account(&self.pool, account_id, now, link).await
+    }
+
+    async fn list_purge_due(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, AccountRepoError> {
+        list_purge_due(&self.pool, now, limit).await
+    }
+
+    async fn purge_account(
+        &self,
+        account_id: Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<AccountPurge, AccountRepoError> {
+        purge_account(&self.pool, account_id, now).await
+    }
+
     async fn create_backup(
         &self,
         account_id: Uuid,
@@ -242,6 +684,14 @@ impl IdentityRepo for PgIdentityRepo {
         delete_backup_by_kid(&self.pool, kid).await
     }
 
+    async fn set_backup_recovery_code(
+        &self,
+        kid: &Kid,
+        recovery_code_hash: Option<&str>,
+    ) -> Result<(), BackupRepoError> {
+        set_backup_recovery_code(&self.pool, kid, recovery_code_hash).await
+    }
+
     async fn create_device_key(
         &self,
         account_id: Uuid,
@@ -249,6 +699,8 @@ impl IdentityRepo for PgIdentityRepo {
         device_pubkey: &str,
         device_name: &str,
         certificate: &[u8],
+        certificate_timestamp: Option<i64>,
+        link: Option<&NewSigchainLink>,
     ) -> Result<CreatedDeviceKey, DeviceKeyRepoError> {
         let mut tx = self
             .pool
@@ -262,8 +714,12 @@ impl IdentityRepo for PgIdentityRepo {
             device_pubkey,
             device_name,
             certificate,
+            certificate_timestamp,
         )
         .await?;
+        if let Some(link) = link {
+            append_sigchain_link(&mut tx, account_id, link).await?;
+        }
         tx.commit().await.map_err(DeviceKeyRepoError::Database)?;
         Ok(result)
     }
@@ -286,8 +742,19 @@ impl IdentityRepo for PgIdentityRepo {
         &self,
         device_kid: &Kid,
         account_id: Uuid,
+        revoked_by: &Kid,
+        revocation_envelope: Option<&serde_json::Value>,
+        link: &NewSigchainLink,
     ) -> Result<(), DeviceKeyRepoError> {
-        revoke_device_key(&self.pool, device_kid, account_id).await
+        revoke_device_key(
+            &self.pool,
+            device_kid,
+            account_id,
+            revoked_by,
+            revocation_envelope,
+            link,
+        )
+        .await
     }
 
     async fn rename_device_key(
@@ -295,89 +762,424 @@ impl IdentityRepo for PgIdentityRepo {
         device_kid: &Kid,
         account_id: Uuid,
         new_name: &str,
+        link: &NewSigchainLink,
     ) -> Result<(), DeviceKeyRepoError> {
-        rename_device_key(&self.pool, device_kid, account_id, new_name).await
+        rename_device_key(&self.pool, device_kid, account_id, new_name, link).await
+    }
+
+    async fn rotate_device_key(
+        &self,
+        rotation: &DeviceRotation<'_>,
+    ) -> Result<CreatedDeviceKey, DeviceKeyRepoError> {
+        rotate_device_key(&self.pool, rotation).await
     }
 
     async fn touch_device_key(&self, device_kid: &Kid) -> Result<(), DeviceKeyRepoError> {
         touch_device_key(&self.pool, device_kid).await
     }
 
-    async fn check_and_record_nonce(&self, nonce_hash: &[u8]) -> Result<(), NonceRepoError> {
-        check_and_record_nonce(&self.pool, nonce_hash).await
+    async fn record_device_caps(
+        &self,
+        device_kid: &Kid,
+        tokens: &[String],
+    ) -> Result<(), DeviceKeyRepoError> {
+        record_device_caps(&self.pool, device_kid, tokens).await
     }
 
-    async fn cleanup_expired_nonces(&self, max_age_secs: i64) -> Result<u64, NonceRepoError> {
-        cleanup_expired_nonces(&self.pool, max_age_secs).await
+    async fn record_device_usage(
+        &self,
+        batch: &DeviceUsageBatch,
+    ) -> Result<(), DeviceKeyRepoError> {
+        record_device_usage(&self.pool, batch).await
     }
 
-    async fn create_signup(
+    async fn list_device_usage_by_account(
         &self,
-        data: &ValidatedSignup,
-    ) -> Result<SignupResult, CreateSignupError> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(CreateSignupError::Transaction)?;
+        account_id: Uuid,
+    ) -> Result<Vec<DeviceUsageRecord>, DeviceKeyRepoError> {
+        list_device_usage_by_account(&self.pool, account_id).await
+    }
 
-        let account = create_account_with_executor(
-            &mut *tx,
-            &data.username,
-            &data.root_pubkey,
-            &data.root_kid,
-        )
-        .await
-        .map_err(CreateSignupError::Account)?;
+    async fn resolve_account_handle(
+        &self,
+        handle: &str,
+    ) -> Result<ResolvedHandle, AccountRepoError> {
+        resolve_account_handle(&self.pool, handle).await
+    }
 
-        create_backup_with_executor(
-            &mut *tx,
-            account.id,
-            &data.root_kid,
-            &data.backup_bytes,
-            &data.backup_salt,
-            data.backup_version,
-        )
-        .await
-        .map_err(CreateSignupError::Backup)?;
+    async fn create_account_alias(
+        &self,
+        account_id: Uuid,
+        alias: &str,
+        reserved: bool,
+    ) -> Result<AccountAliasRecord, AliasRepoError> {
+        create_account_alias(&self.pool, account_id, alias, reserved).await
+    }
 
-        let device = create_device_key_with_executor(
-            &mut tx,
-            account.id,
-            &data.device_kid,
-            &data.device_pubkey,
-            &data.device_name,
-            &data.certificate,
-        )
-        .await
-        .map_err(CreateSignupError::DeviceKey)?;
+    async fn list_account_aliases(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<AccountAliasRecord>, AliasRepoError> {
+        list_account_aliases(&self.pool, account_id).await
+    }
 
-        tx.commit().await.map_err(CreateSignupError::Transaction)?;
+    async fn delete_account_alias(
+        &self,
+        alias: &str,
+        account_id: Uuid,
+    ) -> Result<(), AliasRepoError> {
+        delete_account_alias(&self.pool, alias, account_id).await
+    }
 
-        Ok(SignupResult {
-            account_id: account.id,
-            root_kid: account.root_kid,
-            device_kid: device.device_kid,
-        })
+    async fn create_api_token(
+        &self,
+        account_id: Uuid,
+        name: &str,
+        token_hash: &[u8],
+        scopes: &[String],
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<ApiTokenRecord, ApiTokenRepoError> {
+        create_api_token(&self.pool, account_id, name, token_hash, scopes, expires_at).await
     }
-}
 
-#[cfg(any(test, feature = "test-utils"))]
-#[allow(clippy::expect_used)]
-pub mod mock {
-    //! Mock identity repo for unit testing.
-    //!
-    //! `create_signup` and `get_account_by_username` are configurable;
-    //! other methods return defaults since they are not exercised in
-    //! service-layer tests.
+    async fn list_api_tokens_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<ApiTokenRecord>, ApiTokenRepoError> {
+        list_api_tokens_by_account(&self.pool, account_id).await
+    }
+
+    async fn get_api_token_by_hash(
+        &self,
+        token_hash: &[u8],
+    ) -> Result<ApiTokenRecord, ApiTokenRepoError> {
+        get_api_token_by_hash(&self.pool, token_hash).await
+    }
+
+    async fn revoke_api_token(
+        &self,
+        token_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<(), ApiTokenRepoError> {
+        revoke_api_token(&self.pool, token_id, account_id).await
+    }
+
+    async fn touch_api_token(&self, token_id: Uuid) -> Result<(), ApiTokenRepoError> {
+        touch_api_token(&self.pool, token_id).await
+    }
+
+    async fn get_api_token_usage(
+        &self,
+        token_id: Uuid,
+        day: chrono::NaiveDate,
+    ) -> Result<ApiTokenUsage, ApiTokenRepoError> {
+        get_api_token_usage(&self.pool, token_id, day).await
+    }
+
+    async fn record_api_token_usage(
+        &self,
+        entries: &[(Uuid, chrono::NaiveDate, i64)],
+    ) -> Result<(), ApiTokenRepoError> {
+        record_api_token_usage(&self.pool, entries).await
+    }
+
+    async fn top_api_token_consumers(
+        &self,
+        since: chrono::NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<ApiTokenConsumerRecord>, ApiTokenRepoError> {
+        top_api_token_consumers(&self.pool, since, limit).await
+    }
+
+    async fn create_signup_invite(
+        &self,
+        created_by: Uuid,
+        code_hash: &[u8],
+        expires_at: chrono::DateTime<chrono::Utc>,
+        max_outstanding: Option<i64>,
+    ) -> Result<SignupInviteRecord, SignupInviteRepoError> {
+        create_signup_invite(
+            &self.pool,
+            created_by,
+            code_hash,
+            expires_at,
+            max_outstanding,
+        )
+        .await
+    }
+
+    async fn list_signup_invites_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<SignupInviteRecord>, SignupInviteRepoError> {
+        list_signup_invites_by_account(&self.pool, account_id).await
+    }
+
+    async fn list_reserved_usernames(
+        &self,
+    ) -> Result<Vec<ReservedUsernameRecord>, ReservedUsernameRepoError> {
+        list_reserved_usernames(&self.pool).await
+    }
+
+    async fn create_reserved_username(
+        &self,
+        name: &str,
+        reason: Option<&str>,
+        created_by: Uuid,
+    ) -> Result<ReservedUsernameRecord, ReservedUsernameRepoError> {
+        create_reserved_username(&self.pool, name, reason, created_by).await
+    }
+
+    async fn delete_reserved_username(&self, name: &str) -> Result<(), ReservedUsernameRepoError> {
+        delete_reserved_username(&self.pool, name).await
+    }
+
+    async fn get_released_handle(
+        &self,
+        handle: &str,
+    ) -> Result<Option<ReleasedHandleRecord>, ReservedUsernameRepoError> {
+        get_released_handle(&self.pool, handle).await
+    }
+
+    async fn list_released_handles(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<ReleasedHandleRecord>, ReservedUsernameRepoError> {
+        list_released_handles(&self.pool, since).await
+    }
+
+    async fn delete_released_handle(&self, handle: &str) -> Result<(), ReservedUsernameRepoError> {
+        delete_released_handle(&self.pool, handle).await
+    }
+
+    async fn get_current_account_crl(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<AccountCrlRecord>, CrlRepoError> {
+        get_current_account_crl(&self.pool, account_id).await
+    }
+
+    async fn store_account_crl(
+        &self,
+        account_id: Uuid,
+        crl_number: i64,
+        document: &serde_json::Value,
+    ) -> Result<(), CrlRepoError> {
+        store_account_crl(&self.pool, account_id, crl_number, document).await
+    }
+
+    async fn list_sigchain_links(
+        &self,
+        account_id: Uuid,
+        since_seqno: i64,
+        limit: i64,
+    ) -> Result<Vec<SigchainLinkRecord>, SigchainRepoError> {
+        list_sigchain_links(&self.pool, account_id, since_seqno, limit).await
+    }
+
+    async fn get_sigchain_head(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<SigchainLinkRecord>, SigchainRepoError> {
+        get_sigchain_head(&self.pool, account_id).await
+    }
+
+    async fn create_device_session(
+        &self,
+        account_id: Uuid,
+        device_kid: &Kid,
+        token_hash: &[u8],
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<DeviceSessionRecord, DeviceSessionRepoError> {
+        create_device_session(&self.pool, account_id, device_kid, token_hash, expires_at).await
+    }
+
+    async fn get_device_session_by_hash(
+        &self,
+        token_hash: &[u8],
+    ) -> Result<DeviceSessionRecord, DeviceSessionRepoError> {
+        get_device_session_by_hash(&self.pool, token_hash).await
+    }
+
+    async fn revoke_device_sessions(
+        &self,
+        device_kid: &Kid,
+    ) -> Result<u64, DeviceSessionRepoError> {
+        revoke_device_sessions(&self.pool, device_kid).await
+    }
+
+    async fn get_step_up_phone(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<StepUpPhoneRecord>, StepUpRepoError> {
+        get_step_up_phone(&self.pool, account_id).await
+    }
+
+    async fn delete_step_up_phone(&self, account_id: Uuid) -> Result<(), StepUpRepoError> {
+        delete_step_up_phone(&self.pool, account_id).await
+    }
+
+    async fn create_step_up_challenge(
+        &self,
+        new: &NewStepUpChallenge<'_>,
+    ) -> Result<StepUpChallengeRecord, StepUpRepoError> {
+        create_step_up_challenge(&self.pool, new).await
+    }
+
+    async fn confirm_step_up_challenge(
+        &self,
+        challenge_id: Uuid,
+        account_id: Uuid,
+        device_kid: &Kid,
+        code_hash: &[u8],
+        max_attempts: i32,
+    ) -> Result<StepUpChallengeRecord, StepUpRepoError> {
+        confirm_step_up_challenge(
+            &self.pool,
+            challenge_id,
+            account_id,
+            device_kid,
+            code_hash,
+            max_attempts,
+        )
+        .await
+    }
+
+    async fn consume_step_up_challenge(
+        &self,
+        challenge_id: Uuid,
+        account_id: Uuid,
+        device_kid: &Kid,
+        action: StepUpAction,
+        confirmed_after: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), StepUpRepoError> {
+        consume_step_up_challenge(
+            &self.pool,
+            challenge_id,
+            account_id,
+            device_kid,
+            action,
+            confirmed_after,
+        )
+        .await
+    }
+
+    async fn check_and_record_nonce(&self, nonce_hash: &[u8]) -> Result<(), NonceRepoError> {
+        check_and_record_nonce(&self.pool, nonce_hash).await
+    }
+
+    async fn cleanup_expired_nonces(&self, max_age_secs: i64) -> Result<u64, NonceRepoError> {
+        cleanup_expired_nonces(&self.pool, max_age_secs).await
+    }
+
+    async fn create_auth_challenge(
+        &self,
+        device_kid: &Kid,
+        challenge_hash: &[u8],
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), NonceRepoError> {
+        create_auth_challenge(&self.pool, device_kid, challenge_hash, expires_at).await
+    }
+
+    async fn consume_auth_challenge(
+        &self,
+        device_kid: &Kid,
+        challenge_hash: &[u8],
+    ) -> Result<(), NonceRepoError> {
+        consume_auth_challenge(&self.pool, device_kid, challenge_hash).await
+    }
+
+    async fn create_signup(
+        &self,
+        data: &ValidatedSignup,
+    ) -> Result<SignupResult, CreateSignupError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(CreateSignupError::Transaction)?;
+
+        let account = create_account_with_executor(
+            &mut *tx,
+            &data.username,
+            &data.root_pubkey,
+            &data.root_kid,
+        )
+        .await
+        .map_err(CreateSignupError::Account)?;
+
+        create_backup_with_executor(
+            &mut *tx,
+            account.id,
+            &data.root_kid,
+            &data.backup_bytes,
+            &data.backup_salt,
+            data.backup_version,
+        )
+        .await
+        .map_err(CreateSignupError::Backup)?;
+
+        let device = create_device_key_with_executor(
+            &mut tx,
+            account.id,
+            &data.device_kid,
+            &data.device_pubkey,
+            &data.device_name,
+            &data.certificate,
+            None,
+        )
+        .await
+        .map_err(CreateSignupError::DeviceKey)?;
+
+        if !data.client_caps.is_empty() {
+            record_device_caps(&mut *tx, &data.device_kid, &data.client_caps)
+                .await
+                .map_err(CreateSignupError::DeviceKey)?;
+        }
+
+        if let Some(ref code_hash) = data.invite_code_hash {
+            redeem_signup_invite_with_executor(&mut *tx, code_hash, account.id)
+                .await
+                .map_err(CreateSignupError::Invite)?;
+        }
+
+        tx.commit().await.map_err(CreateSignupError::Transaction)?;
+
+        Ok(SignupResult {
+            account_id: account.id,
+            root_kid: account.root_kid,
+            device_kid: device.device_kid,
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+#[allow(clippy::expect_used)]
+pub mod mock {
+    //! Mock identity repo for unit testing.
+    //!
+    //! `create_signup` and `get_account_by_username` are configurable;
+    //! other methods return defaults since they are not exercised in
+    //! service-layer tests.
 
     use super::{
-        async_trait, AccountRecord, AccountRepoError, BackupRecord, BackupRepoError,
-        CreateSignupError, CreatedAccount, CreatedBackup, CreatedDeviceKey, DeviceKeyRecord,
-        DeviceKeyRepoError, IdentityRepo, Kid, NonceRepoError, SignupResult, Uuid, ValidatedSignup,
+        async_trait, AccountAliasRecord, AccountCrlRecord, AccountDeactivation, AccountPurge,
+        AccountReactivation, AccountRecord, AccountRepoError, AccountStatus, AliasRepoError,
+        ApiTokenConsumerRecord, ApiTokenRecord, ApiTokenRepoError, ApiTokenUsage, BackupRecord,
+        BackupRepoError, CreateSignupError, CreatedAccount, CreatedBackup, CreatedDeviceKey,
+        CrlRepoError, DeviceKeyRecord, DeviceKeyRepoError, DeviceRotation, DeviceSessionRecord,
+        DeviceSessionRepoError, DeviceUsageBatch, DeviceUsageRecord, IdentityRepo, Kid,
+        NewSigchainLink, NewStepUpChallenge, NonceRepoError, ReleasedHandleRecord,
+        ReservedUsernameRecord, ReservedUsernameRepoError, ResolvedHandle, SigchainLinkRecord,
+        SigchainRepoError, SignupInviteRecord, SignupInviteRepoError, SignupResult, StepUpAction,
+        StepUpChallengeRecord, StepUpPhoneRecord, StepUpRepoError, UsernameChange, Uuid,
+        ValidatedSignup,
     };
     use std::sync::Mutex;
 
+    /// An issued challenge as (KID, hash, expiry).
+    pub type IssuedChallenge = (Kid, Vec<u8>, chrono::DateTime<chrono::Utc>);
+
     /// Mock identity repo with configurable results.
     pub struct MockIdentityRepo {
         pub signup_result: Mutex<Option<Result<SignupResult, CreateSignupError>>>,
@@ -389,9 +1191,53 @@ pub mod mock {
         pub list_device_keys_result:
             Mutex<Option<Result<Vec<DeviceKeyRecord>, DeviceKeyRepoError>>>,
         pub get_backup_by_kid_result: Mutex<Option<Result<BackupRecord, BackupRepoError>>>,
+        /// Calls to [`IdentityRepo::set_backup_recovery_code`], in order.
+        pub backup_recovery_codes: Mutex<Vec<(Kid, Option<String>)>>,
         pub nonce_result: Mutex<Option<Result<(), NonceRepoError>>>,
         pub revoke_device_key_result: Mutex<Option<Result<(), DeviceKeyRepoError>>>,
         pub rename_device_key_result: Mutex<Option<Result<(), DeviceKeyRepoError>>>,
+        pub rotate_device_key_error: Mutex<Option<DeviceKeyRepoError>>,
+        /// `(old_kid, new_kid)` of each successful rotation, in order.
+        pub rotations: Mutex<Vec<(Kid, Kid)>>,
+        pub create_api_token_error: Mutex<Option<ApiTokenRepoError>>,
+        pub api_token_by_hash_result: Mutex<Option<Result<ApiTokenRecord, ApiTokenRepoError>>>,
+        pub list_api_tokens_result: Mutex<Option<Result<Vec<ApiTokenRecord>, ApiTokenRepoError>>>,
+        pub revoke_api_token_result: Mutex<Option<Result<(), ApiTokenRepoError>>>,
+        pub create_alias_error: Mutex<Option<AliasRepoError>>,
+        pub list_aliases_result: Mutex<Option<Result<Vec<AccountAliasRecord>, AliasRepoError>>>,
+        pub delete_alias_result: Mutex<Option<Result<(), AliasRepoError>>>,
+        pub create_signup_invite_error: Mutex<Option<SignupInviteRepoError>>,
+        pub api_token_usage: Mutex<ApiTokenUsage>,
+        pub recorded_api_token_usage: Mutex<Vec<(Uuid, chrono::NaiveDate, i64)>>,
+        pub device_usage: Mutex<Vec<DeviceUsageRecord>>,
+        pub recorded_device_usage: Mutex<Vec<DeviceUsageBatch>>,
+        /// `(device_kid, tokens)` of each capability declaration recorded
+        pub recorded_caps: Mutex<Vec<(Kid, Vec<String>)>>,
+        pub account_status: Mutex<AccountStatus>,
+        pub change_username_error: Mutex<Option<AccountRepoError>>,
+        /// `(account_id, new_username, changed_at)` of each username change,
+        /// in order; an account's first change reports an empty `previous`.
+        pub username_changes: Mutex<Vec<(Uuid, String, chrono::DateTime<chrono::Utc>)>>,
+        /// When a deactivated account is purged; shared by every account
+        pub purge_after: Mutex<Option<chrono::DateTime<chrono::Utc>>>,
+        /// `(account_id, revoked_by)` of each deactivation, in order.
+        pub deactivations: Mutex<Vec<(Uuid, Kid)>>,
+        /// Returned by [`IdentityRepo::list_purge_due`].
+        pub purge_due: Mutex<Vec<Uuid>>,
+        /// Makes [`IdentityRepo::purge_account`] retain the account as if
+        /// this constraint blocked the delete.
+        pub purge_blocked_by: Mutex<Option<String>>,
+        /// Accounts removed by [`IdentityRepo::purge_account`], in order.
+        pub purged: Mutex<Vec<Uuid>>,
+        pub reserved_usernames: Mutex<Vec<ReservedUsernameRecord>>,
+        pub released_handles: Mutex<Vec<ReleasedHandleRecord>>,
+        /// Sessions keyed by token hash
+        pub device_sessions: Mutex<Vec<(Vec<u8>, DeviceSessionRecord)>>,
+        /// Stored CRLs; always treated as current
+        pub account_crls: Mutex<Vec<AccountCrlRecord>>,
+        /// Sigchain links of every account, in append order
+        pub sigchain_links: Mutex<Vec<SigchainLinkRecord>>,
+        pub auth_challenges: Mutex<Vec<IssuedChallenge>>,
     }
 
     impl MockIdentityRepo {
@@ -405,9 +1251,42 @@ pub mod mock {
                 get_device_key_by_kid_result: Mutex::new(None),
                 list_device_keys_result: Mutex::new(None),
                 get_backup_by_kid_result: Mutex::new(None),
+                backup_recovery_codes: Mutex::new(Vec::new()),
                 nonce_result: Mutex::new(None),
                 revoke_device_key_result: Mutex::new(None),
                 rename_device_key_result: Mutex::new(None),
+                rotate_device_key_error: Mutex::new(None),
+                rotations: Mutex::new(Vec::new()),
+                create_api_token_error: Mutex::new(None),
+                api_token_by_hash_result: Mutex::new(None),
+                list_api_tokens_result: Mutex::new(None),
+                revoke_api_token_result: Mutex::new(None),
+                create_alias_error: Mutex::new(None),
+                list_aliases_result: Mutex::new(None),
+                delete_alias_result: Mutex::new(None),
+                create_signup_invite_error: Mutex::new(None),
+                api_token_usage: Mutex::new(ApiTokenUsage {
+                    daily: 0,
+                    monthly: 0,
+                }),
+                recorded_api_token_usage: Mutex::new(Vec::new()),
+                device_usage: Mutex::new(Vec::new()),
+                recorded_device_usage: Mutex::new(Vec::new()),
+                recorded_caps: Mutex::new(Vec::new()),
+                account_status: Mutex::new(AccountStatus::Active),
+                change_username_error: Mutex::new(None),
+                username_changes: Mutex::new(Vec::new()),
+                purge_after: Mutex::new(None),
+                deactivations: Mutex::new(Vec::new()),
+                purge_due: Mutex::new(Vec::new()),
+                purge_blocked_by: Mutex::new(None),
+                purged: Mutex::new(Vec::new()),
+                reserved_usernames: Mutex::new(Vec::new()),
+                released_handles: Mutex::new(Vec::new()),
+                device_sessions: Mutex::new(Vec::new()),
+                account_crls: Mutex::new(Vec::new()),
+                sigchain_links: Mutex::new(Vec::new()),
+                auth_challenges: Mutex::new(Vec::new()),
             }
         }
 
@@ -420,6 +1299,36 @@ pub mod mock {
             *self.signup_result.lock().expect("lock poisoned") = Some(result);
         }
 
+        /// Append `link` to the account's sigchain, as the mutating methods
+        /// do on success.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn push_sigchain_link(&self, account_id: Uuid, link: &NewSigchainLink) {
+            let mut links = self.sigchain_links.lock().expect("lock poisoned");
+            let prev = links.iter().rev().find(|l| l.account_id == account_id);
+            let seqno = prev.map_or(1, |l| l.seqno + 1);
+            let prev_hash = prev.map(|l| l.hash.clone());
+            let link_type = link.link_type.as_str();
+            let hash = crate::identity::sigchain::link_hash(
+                seqno,
+                prev_hash.as_deref(),
+                link_type,
+                &link.envelope,
+            );
+            links.push(SigchainLinkRecord {
+                account_id,
+                seqno,
+                link_type: link_type.to_string(),
+                signer_kid: link.signer_kid.clone(),
+                envelope: link.envelope.clone(),
+                prev_hash,
+                hash: hash.to_vec(),
+                created_at: chrono::Utc::now(),
+            });
+        }
+
         /// Set the result that [`IdentityRepo::get_account_by_username`] will return.
         ///
         /// # Panics
@@ -435,6 +1344,25 @@ pub mod mock {
                 .expect("lock poisoned") = Some(result);
         }
 
+        /// Set the status every account reports.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_account_status(&self, status: AccountStatus) {
+            *self.account_status.lock().expect("lock poisoned") = status;
+        }
+
+        /// The status last set or written through the repo.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        #[must_use]
+        pub fn account_status(&self) -> AccountStatus {
+            *self.account_status.lock().expect("lock poisoned")
+        }
+
         /// Set the result that [`IdentityRepo::get_account_by_id`] will return.
         ///
         /// # Panics
@@ -515,54 +1443,292 @@ pub mod mock {
         pub fn set_rename_device_key_result(&self, result: Result<(), DeviceKeyRepoError>) {
             *self.rename_device_key_result.lock().expect("lock poisoned") = Some(result);
         }
-    }
 
-    impl Default for MockIdentityRepo {
-        fn default() -> Self {
-            Self::new()
+        /// Set an error that [`IdentityRepo::create_api_token`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_create_api_token_error(&self, error: ApiTokenRepoError) {
+            *self.create_api_token_error.lock().expect("lock poisoned") = Some(error);
         }
-    }
 
-    #[async_trait]
-    impl IdentityRepo for MockIdentityRepo {
-        async fn create_account(
-            &self,
-            _username: &str,
-            _root_pubkey: &str,
-            root_kid: &Kid,
-        ) -> Result<CreatedAccount, AccountRepoError> {
-            Ok(CreatedAccount {
-                id: Uuid::new_v4(),
-                root_kid: root_kid.clone(),
-            })
+        /// Set an error that [`IdentityRepo::create_signup_invite`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_create_signup_invite_error(&self, error: SignupInviteRepoError) {
+            *self
+                .create_signup_invite_error
+                .lock()
+                .expect("lock poisoned") = Some(error);
         }
 
-        async fn get_account_by_id(
-            &self,
-            _account_id: Uuid,
-        ) -> Result<AccountRecord, AccountRepoError> {
-            self.account_by_id_result
-                .lock()
-                .expect("lock poisoned")
-                .take()
-                .unwrap_or(Err(AccountRepoError::NotFound))
+        /// Set the usage that [`IdentityRepo::list_device_usage_by_account`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_device_usage(&self, usage: Vec<DeviceUsageRecord>) {
+            *self.device_usage.lock().expect("lock poisoned") = usage;
         }
 
-        async fn get_account_by_username(
-            &self,
-            _username: &str,
-        ) -> Result<AccountRecord, AccountRepoError> {
-            self.account_by_username_result
-                .lock()
-                .expect("lock poisoned")
-                .take()
-                .unwrap_or(Err(AccountRepoError::NotFound))
+        /// Set the usage that [`IdentityRepo::get_api_token_usage`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_api_token_usage(&self, usage: ApiTokenUsage) {
+            *self.api_token_usage.lock().expect("lock poisoned") = usage;
         }
 
-        async fn create_backup(
-            &self,
-            _account_id: Uuid,
-            kid: &Kid,
+        /// Set the result that [`IdentityRepo::get_api_token_by_hash`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_api_token_by_hash_result(
+            &self,
+            result: Result<ApiTokenRecord, ApiTokenRepoError>,
+        ) {
+            *self.api_token_by_hash_result.lock().expect("lock poisoned") = Some(result);
+        }
+
+        /// Set the result that [`IdentityRepo::list_api_tokens_by_account`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_list_api_tokens_result(
+            &self,
+            result: Result<Vec<ApiTokenRecord>, ApiTokenRepoError>,
+        ) {
+            *self.list_api_tokens_result.lock().expect("lock poisoned") = Some(result);
+        }
+
+        /// Set the result that [`IdentityRepo::revoke_api_token`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_revoke_api_token_result(&self, result: Result<(), ApiTokenRepoError>) {
+            *self.revoke_api_token_result.lock().expect("lock poisoned") = Some(result);
+        }
+
+        /// Set an error that [`IdentityRepo::create_account_alias`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_create_alias_error(&self, error: AliasRepoError) {
+            *self.create_alias_error.lock().expect("lock poisoned") = Some(error);
+        }
+
+        /// Set the result that [`IdentityRepo::list_account_aliases`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_list_aliases_result(
+            &self,
+            result: Result<Vec<AccountAliasRecord>, AliasRepoError>,
+        ) {
+            *self.list_aliases_result.lock().expect("lock poisoned") = Some(result);
+        }
+
+        /// Set the result that [`IdentityRepo::delete_account_alias`] will return.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the internal mutex is poisoned.
+        pub fn set_delete_alias_result(&self, result: Result<(), AliasRepoError>) {
+            *self.delete_alias_result.lock().expect("lock poisoned") = Some(result);
+        }
+    }
+
+    impl Default for MockIdentityRepo {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[async_trait]
+    impl IdentityRepo for MockIdentityRepo {
+        async fn create_account(
+            &self,
+            _username: &str,
+            _root_pubkey: &str,
+            root_kid: &Kid,
+        ) -> Result<CreatedAccount, AccountRepoError> {
+            Ok(CreatedAccount {
+                id: Uuid::new_v4(),
+                root_kid: root_kid.clone(),
+            })
+        }
+
+        async fn get_account_by_id(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<AccountRecord, AccountRepoError> {
+            self.account_by_id_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Err(AccountRepoError::NotFound))
+        }
+
+        async fn get_account_by_username(
+            &self,
+            _username: &str,
+        ) -> Result<AccountRecord, AccountRepoError> {
+            self.account_by_username_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Err(AccountRepoError::NotFound))
+        }
+
+        async fn get_account_status(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<AccountStatus, AccountRepoError> {
+            Ok(self.account_status())
+        }
+
+        async fn update_account_status(
+            &self,
+            _account_id: Uuid,
+            from: AccountStatus,
+            to: AccountStatus,
+            _actor_account_id: Uuid,
+            _reason: Option<&str>,
+        ) -> Result<Option<chrono::DateTime<chrono::Utc>>, AccountRepoError> {
+            let mut status = self.account_status.lock().expect("lock poisoned");
+            if *status != from {
+                return Ok(None);
+            }
+            *status = to;
+            drop(status);
+            Ok(Some(chrono::Utc::now()))
+        }
+
+        async fn change_account_username(
+            &self,
+            account_id: Uuid,
+            username: &str,
+            cooldown_cutoff: chrono::DateTime<chrono::Utc>,
+            link: &NewSigchainLink,
+        ) -> Result<UsernameChange, AccountRepoError> {
+            let maybe_err = self
+                .change_username_error
+                .lock()
+                .expect("lock poisoned")
+                .take();
+            if let Some(e) = maybe_err {
+                return Err(e);
+            }
+            let mut changes = self.username_changes.lock().expect("lock poisoned");
+            let last = changes.iter().rev().find(|(id, _, _)| *id == account_id);
+            if let Some((_, previous, last_changed_at)) = last {
+                if previous == username {
+                    return Ok(UsernameChange::Unchanged);
+                }
+                if *last_changed_at > cooldown_cutoff {
+                    return Ok(UsernameChange::TooSoon {
+                        last_changed_at: *last_changed_at,
+                    });
+                }
+            }
+            let previous = last.map(|(_, name, _)| name.clone()).unwrap_or_default();
+            let changed_at = chrono::Utc::now();
+            changes.push((account_id, username.to_string(), changed_at));
+            drop(changes);
+            self.push_sigchain_link(account_id, link);
+            Ok(UsernameChange::Changed {
+                previous,
+                changed_at,
+            })
+        }
+
+        async fn deactivate_account(
+            &self,
+            account_id: Uuid,
+            purge_after: chrono::DateTime<chrono::Utc>,
+            revoked_by: &Kid,
+            _revocation_envelope: &serde_json::Value,
+            _reason: Option<&str>,
+            link: &NewSigchainLink,
+        ) -> Result<Option<AccountDeactivation>, AccountRepoError> {
+            let mut status = self.account_status.lock().expect("lock poisoned");
+            if *status != AccountStatus::Active {
+                return Ok(None);
+            }
+            *status = AccountStatus::Deactivated;
+            drop(status);
+            *self.purge_after.lock().expect("lock poisoned") = Some(purge_after);
+            self.deactivations
+                .lock()
+                .expect("lock poisoned")
+                .push((account_id, revoked_by.clone()));
+            self.push_sigchain_link(account_id, link);
+            Ok(Some(AccountDeactivation {
+                deactivated_at: chrono::Utc::now(),
+                revoked_devices: 1,
+            }))
+        }
+
+        async fn reactivate_account(
+            &self,
+            account_id: Uuid,
+            now: chrono::DateTime<chrono::Utc>,
+            link: &NewSigchainLink,
+        ) -> Result<AccountReactivation, AccountRepoError> {
+            let mut status = self.account_status.lock().expect("lock poisoned");
+            if *status != AccountStatus::Deactivated {
+                return Ok(AccountReactivation::NotDeactivated);
+            }
+            let mut purge_after = self.purge_after.lock().expect("lock poisoned");
+            if let Some(at) = purge_after.filter(|at| *at <= now) {
+                return Ok(AccountReactivation::GraceExpired { purge_after: at });
+            }
+            *status = AccountStatus::Active;
+            *purge_after = None;
+            drop((status, purge_after));
+            self.push_sigchain_link(account_id, link);
+            Ok(AccountReactivation::Reactivated {
+                reactivated_at: chrono::Utc::now(),
+            })
+        }
+
+        async fn list_purge_due(
+            &self,
+            _now: chrono::DateTime<chrono::Utc>,
+            limit: i64,
+        ) -> Result<Vec<Uuid>, AccountRepoError> {
+            let mut due = self.purge_due.lock().expect("lock poisoned");
+            let n = due.len().min(usize::try_from(limit).unwrap_or(0));
+            Ok(due.drain(..n).collect())
+        }
+
+        async fn purge_account(
+            &self,
+            account_id: Uuid,
+            _now: chrono::DateTime<chrono::Utc>,
+        ) -> Result<AccountPurge, AccountRepoError> {
+            let blocked_by = self.purge_blocked_by.lock().expect("lock poisoned").clone();
+            if let Some(constraint) = blocked_by {
+                self.set_account_status(AccountStatus::Deleted);
+                return Ok(AccountPurge::Retained { constraint });
+            }
+            self.purged.lock().expect("lock poisoned").push(account_id);
+            Ok(AccountPurge::Purged)
+        }
+
+        async fn create_backup(
+            &self,
+            _account_id: Uuid,
+            kid: &Kid,
             _encrypted_backup: &[u8],
             _salt: &[u8],
             _version: i32,
@@ -586,13 +1752,27 @@ pub mod mock {
             Ok(())
         }
 
+        async fn set_backup_recovery_code(
+            &self,
+            kid: &Kid,
+            recovery_code_hash: Option<&str>,
+        ) -> Result<(), BackupRepoError> {
+            self.backup_recovery_codes
+                .lock()
+                .expect("lock poisoned")
+                .push((kid.clone(), recovery_code_hash.map(str::to_string)));
+            Ok(())
+        }
+
         async fn create_device_key(
             &self,
-            _account_id: Uuid,
+            account_id: Uuid,
             device_kid: &Kid,
             _device_pubkey: &str,
             _device_name: &str,
             _certificate: &[u8],
+            _certificate_timestamp: Option<i64>,
+            link: Option<&NewSigchainLink>,
         ) -> Result<CreatedDeviceKey, DeviceKeyRepoError> {
             let maybe_err = self
                 .create_device_key_error
@@ -602,6 +1782,9 @@ pub mod mock {
             if let Some(err) = maybe_err {
                 return Err(err);
             }
+            if let Some(link) = link {
+                self.push_sigchain_link(account_id, link);
+            }
             Ok(CreatedDeviceKey {
                 id: Uuid::new_v4(),
                 device_kid: device_kid.clone(),
@@ -634,32 +1817,534 @@ pub mod mock {
         async fn revoke_device_key(
             &self,
             _device_kid: &Kid,
-            _account_id: Uuid,
+            account_id: Uuid,
+            _revoked_by: &Kid,
+            _revocation_envelope: Option<&serde_json::Value>,
+            link: &NewSigchainLink,
         ) -> Result<(), DeviceKeyRepoError> {
-            self.revoke_device_key_result
+            let result = self
+                .revoke_device_key_result
                 .lock()
                 .expect("lock poisoned")
                 .take()
-                .unwrap_or(Ok(()))
+                .unwrap_or(Ok(()));
+            if result.is_ok() {
+                self.push_sigchain_link(account_id, link);
+            }
+            result
         }
 
         async fn rename_device_key(
             &self,
             _device_kid: &Kid,
-            _account_id: Uuid,
+            account_id: Uuid,
             _new_name: &str,
+            link: &NewSigchainLink,
         ) -> Result<(), DeviceKeyRepoError> {
-            self.rename_device_key_result
+            let result = self
+                .rename_device_key_result
                 .lock()
                 .expect("lock poisoned")
                 .take()
-                .unwrap_or(Ok(()))
+                .unwrap_or(Ok(()));
+            if result.is_ok() {
+                self.push_sigchain_link(account_id, link);
+            }
+            result
+        }
+
+        async fn rotate_device_key(
+            &self,
+            rotation: &DeviceRotation<'_>,
+        ) -> Result<CreatedDeviceKey, DeviceKeyRepoError> {
+            let error = self
+                .rotate_device_key_error
+                .lock()
+                .expect("lock poisoned")
+                .take();
+            if let Some(e) = error {
+                return Err(e);
+            }
+            self.rotations
+                .lock()
+                .expect("lock poisoned")
+                .push((rotation.old_kid.clone(), rotation.new_kid.clone()));
+            self.push_sigchain_link(rotation.account_id, rotation.link);
+            Ok(CreatedDeviceKey {
+                id: Uuid::new_v4(),
+                device_kid: rotation.new_kid.clone(),
+                created_at: chrono::Utc::now(),
+            })
         }
 
         async fn touch_device_key(&self, _device_kid: &Kid) -> Result<(), DeviceKeyRepoError> {
             Ok(())
         }
 
+        async fn record_device_caps(
+            &self,
+            device_kid: &Kid,
+            tokens: &[String],
+        ) -> Result<(), DeviceKeyRepoError> {
+            self.recorded_caps
+                .lock()
+                .expect("lock poisoned")
+                .push((device_kid.clone(), tokens.to_vec()));
+            Ok(())
+        }
+
+        async fn record_device_usage(
+            &self,
+            batch: &DeviceUsageBatch,
+        ) -> Result<(), DeviceKeyRepoError> {
+            self.recorded_device_usage
+                .lock()
+                .expect("lock poisoned")
+                .push(batch.clone());
+            Ok(())
+        }
+
+        async fn list_device_usage_by_account(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<DeviceUsageRecord>, DeviceKeyRepoError> {
+            Ok(self.device_usage.lock().expect("lock poisoned").clone())
+        }
+
+        async fn create_api_token(
+            &self,
+            account_id: Uuid,
+            name: &str,
+            _token_hash: &[u8],
+            scopes: &[String],
+            expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<ApiTokenRecord, ApiTokenRepoError> {
+            let maybe_err = self
+                .create_api_token_error
+                .lock()
+                .expect("lock poisoned")
+                .take();
+            if let Some(err) = maybe_err {
+                return Err(err);
+            }
+            Ok(ApiTokenRecord {
+                id: Uuid::new_v4(),
+                account_id,
+                name: name.to_string(),
+                scopes: scopes.to_vec(),
+                expires_at,
+                last_used_at: None,
+                revoked_at: None,
+                created_at: chrono::Utc::now(),
+            })
+        }
+
+        async fn list_api_tokens_by_account(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<ApiTokenRecord>, ApiTokenRepoError> {
+            self.list_api_tokens_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Ok(vec![]))
+        }
+
+        async fn get_api_token_by_hash(
+            &self,
+            _token_hash: &[u8],
+        ) -> Result<ApiTokenRecord, ApiTokenRepoError> {
+            self.api_token_by_hash_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Err(ApiTokenRepoError::NotFound))
+        }
+
+        async fn revoke_api_token(
+            &self,
+            _token_id: Uuid,
+            _account_id: Uuid,
+        ) -> Result<(), ApiTokenRepoError> {
+            self.revoke_api_token_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Ok(()))
+        }
+
+        async fn touch_api_token(&self, _token_id: Uuid) -> Result<(), ApiTokenRepoError> {
+            Ok(())
+        }
+
+        async fn get_api_token_usage(
+            &self,
+            _token_id: Uuid,
+            _day: chrono::NaiveDate,
+        ) -> Result<ApiTokenUsage, ApiTokenRepoError> {
+            Ok(*self.api_token_usage.lock().expect("lock poisoned"))
+        }
+
+        async fn record_api_token_usage(
+            &self,
+            entries: &[(Uuid, chrono::NaiveDate, i64)],
+        ) -> Result<(), ApiTokenRepoError> {
+            self.recorded_api_token_usage
+                .lock()
+                .expect("lock poisoned")
+                .extend_from_slice(entries);
+            Ok(())
+        }
+
+        async fn top_api_token_consumers(
+            &self,
+            _since: chrono::NaiveDate,
+            _limit: i64,
+        ) -> Result<Vec<ApiTokenConsumerRecord>, ApiTokenRepoError> {
+            Ok(vec![])
+        }
+
+        async fn create_signup_invite(
+            &self,
+            created_by: Uuid,
+            _code_hash: &[u8],
+            expires_at: chrono::DateTime<chrono::Utc>,
+            _max_outstanding: Option<i64>,
+        ) -> Result<SignupInviteRecord, SignupInviteRepoError> {
+            let maybe_err = self
+                .create_signup_invite_error
+                .lock()
+                .expect("lock poisoned")
+                .take();
+            if let Some(err) = maybe_err {
+                return Err(err);
+            }
+            Ok(SignupInviteRecord {
+                id: Uuid::new_v4(),
+                created_by,
+                created_at: chrono::Utc::now(),
+                expires_at,
+                redeemed_by: None,
+                redeemed_at: None,
+            })
+        }
+
+        async fn list_signup_invites_by_account(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<SignupInviteRecord>, SignupInviteRepoError> {
+            Ok(vec![])
+        }
+
+        /// Resolves through the `get_account_by_username` result, as a username match.
+        async fn resolve_account_handle(
+            &self,
+            handle: &str,
+        ) -> Result<ResolvedHandle, AccountRepoError> {
+            self.get_account_by_username(handle)
+                .await
+                .map(|account| ResolvedHandle {
+                    account,
+                    alias: None,
+                })
+        }
+
+        async fn create_account_alias(
+            &self,
+            account_id: Uuid,
+            alias: &str,
+            reserved: bool,
+        ) -> Result<AccountAliasRecord, AliasRepoError> {
+            let maybe_err = self
+                .create_alias_error
+                .lock()
+                .expect("lock poisoned")
+                .take();
+            if let Some(err) = maybe_err {
+                return Err(err);
+            }
+            Ok(AccountAliasRecord {
+                alias: alias.to_string(),
+                account_id,
+                reserved,
+                created_at: chrono::Utc::now(),
+            })
+        }
+
+        async fn list_account_aliases(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<AccountAliasRecord>, AliasRepoError> {
+            self.list_aliases_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Ok(vec![]))
+        }
+
+        async fn delete_account_alias(
+            &self,
+            _alias: &str,
+            _account_id: Uuid,
+        ) -> Result<(), AliasRepoError> {
+            self.delete_alias_result
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or(Ok(()))
+        }
+
+        async fn list_reserved_usernames(
+            &self,
+        ) -> Result<Vec<ReservedUsernameRecord>, ReservedUsernameRepoError> {
+            let mut names = self
+                .reserved_usernames
+                .lock()
+                .expect("lock poisoned")
+                .clone();
+            names.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(names)
+        }
+
+        async fn create_reserved_username(
+            &self,
+            name: &str,
+            reason: Option<&str>,
+            created_by: Uuid,
+        ) -> Result<ReservedUsernameRecord, ReservedUsernameRepoError> {
+            let record = ReservedUsernameRecord {
+                name: name.to_string(),
+                reason: reason.map(str::to_string),
+                created_by: Some(created_by),
+                created_at: chrono::Utc::now(),
+            };
+            let mut names = self.reserved_usernames.lock().expect("lock poisoned");
+            if names.iter().any(|r| r.name == name) {
+                return Err(ReservedUsernameRepoError::Duplicate);
+            }
+            names.push(record.clone());
+            drop(names);
+            Ok(record)
+        }
+
+        async fn delete_reserved_username(
+            &self,
+            name: &str,
+        ) -> Result<(), ReservedUsernameRepoError> {
+            let mut names = self.reserved_usernames.lock().expect("lock poisoned");
+            let before = names.len();
+            names.retain(|r| r.name != name);
+            let removed = names.len() < before;
+            drop(names);
+            if removed {
+                Ok(())
+            } else {
+                Err(ReservedUsernameRepoError::NotFound)
+            }
+        }
+
+        async fn get_released_handle(
+            &self,
+            handle: &str,
+        ) -> Result<Option<ReleasedHandleRecord>, ReservedUsernameRepoError> {
+            let handle = handle.to_lowercase();
+            Ok(self
+                .released_handles
+                .lock()
+                .expect("lock poisoned")
+                .iter()
+                .find(|r| r.handle == handle)
+                .cloned())
+        }
+
+        async fn list_released_handles(
+            &self,
+            since: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<ReleasedHandleRecord>, ReservedUsernameRepoError> {
+            let mut handles: Vec<_> = self
+                .released_handles
+                .lock()
+                .expect("lock poisoned")
+                .iter()
+                .filter(|r| r.released_at >= since)
+                .cloned()
+                .collect();
+            handles.sort_by_key(|r| std::cmp::Reverse(r.released_at));
+            Ok(handles)
+        }
+
+        async fn delete_released_handle(
+            &self,
+            handle: &str,
+        ) -> Result<(), ReservedUsernameRepoError> {
+            let handle = handle.to_lowercase();
+            let mut handles = self.released_handles.lock().expect("lock poisoned");
+            let before = handles.len();
+            handles.retain(|r| r.handle != handle);
+            let removed = handles.len() < before;
+            drop(handles);
+            if removed {
+                Ok(())
+            } else {
+                Err(ReservedUsernameRepoError::NotFound)
+            }
+        }
+
+        async fn get_current_account_crl(
+            &self,
+            account_id: Uuid,
+        ) -> Result<Option<AccountCrlRecord>, CrlRepoError> {
+            Ok(self
+                .account_crls
+                .lock()
+                .expect("lock poisoned")
+                .iter()
+                .find(|crl| crl.account_id == account_id)
+                .cloned())
+        }
+
+        async fn store_account_crl(
+            &self,
+            account_id: Uuid,
+            crl_number: i64,
+            document: &serde_json::Value,
+        ) -> Result<(), CrlRepoError> {
+            let mut crls = self.account_crls.lock().expect("lock poisoned");
+            crls.retain(|crl| crl.account_id != account_id);
+            crls.push(AccountCrlRecord {
+                account_id,
+                crl_number,
+                document: document.clone(),
+                generated_at: chrono::Utc::now(),
+            });
+            drop(crls);
+            Ok(())
+        }
+
+        async fn list_sigchain_links(
+            &self,
+            account_id: Uuid,
+            since_seqno: i64,
+            limit: i64,
+        ) -> Result<Vec<SigchainLinkRecord>, SigchainRepoError> {
+            Ok(self
+                .sigchain_links
+                .lock()
+                .expect("lock poisoned")
+                .iter()
+                .filter(|l| l.account_id == account_id && l.seqno > since_seqno)
+                .take(usize::try_from(limit).unwrap_or(0))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_sigchain_head(
+            &self,
+            account_id: Uuid,
+        ) -> Result<Option<SigchainLinkRecord>, SigchainRepoError> {
+            Ok(self
+                .sigchain_links
+                .lock()
+                .expect("lock poisoned")
+                .iter()
+                .rev()
+                .find(|l| l.account_id == account_id)
+                .cloned())
+        }
+
+        async fn create_device_session(
+            &self,
+            account_id: Uuid,
+            device_kid: &Kid,
+            token_hash: &[u8],
+            expires_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<DeviceSessionRecord, DeviceSessionRepoError> {
+            let record = DeviceSessionRecord {
+                id: Uuid::new_v4(),
+                account_id,
+                device_kid: device_kid.clone(),
+                created_at: chrono::Utc::now(),
+                expires_at,
+                revoked_at: None,
+                device_revoked_at: None,
+            };
+            self.device_sessions
+                .lock()
+                .expect("lock poisoned")
+                .push((token_hash.to_vec(), record.clone()));
+            Ok(record)
+        }
+
+        async fn get_device_session_by_hash(
+            &self,
+            token_hash: &[u8],
+        ) -> Result<DeviceSessionRecord, DeviceSessionRepoError> {
+            self.device_sessions
+                .lock()
+                .expect("lock poisoned")
+                .iter()
+                .find(|(hash, _)| hash == token_hash)
+                .map(|(_, record)| record.clone())
+                .ok_or(DeviceSessionRepoError::NotFound)
+        }
+
+        async fn revoke_device_sessions(
+            &self,
+            device_kid: &Kid,
+        ) -> Result<u64, DeviceSessionRepoError> {
+            let now = chrono::Utc::now();
+            let mut revoked = 0;
+            let mut sessions = self.device_sessions.lock().expect("lock poisoned");
+            for (_, record) in sessions.iter_mut() {
+                if record.device_kid == *device_kid && record.is_live(now) {
+                    record.revoked_at = Some(now);
+                    revoked += 1;
+                }
+            }
+            drop(sessions);
+            Ok(revoked)
+        }
+
+        async fn get_step_up_phone(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Option<StepUpPhoneRecord>, StepUpRepoError> {
+            Ok(None)
+        }
+
+        async fn delete_step_up_phone(&self, _account_id: Uuid) -> Result<(), StepUpRepoError> {
+            Err(StepUpRepoError::NoPhone)
+        }
+
+        async fn create_step_up_challenge(
+            &self,
+            _new: &NewStepUpChallenge<'_>,
+        ) -> Result<StepUpChallengeRecord, StepUpRepoError> {
+            Err(StepUpRepoError::NoPhone)
+        }
+
+        async fn confirm_step_up_challenge(
+            &self,
+            _challenge_id: Uuid,
+            _account_id: Uuid,
+            _device_kid: &Kid,
+            _code_hash: &[u8],
+            _max_attempts: i32,
+        ) -> Result<StepUpChallengeRecord, StepUpRepoError> {
+            Err(StepUpRepoError::NotFound)
+        }
+
+        async fn consume_step_up_challenge(
+            &self,
+            _challenge_id: Uuid,
+            _account_id: Uuid,
+            _device_kid: &Kid,
+            _action: StepUpAction,
+            _confirmed_after: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), StepUpRepoError> {
+            Err(StepUpRepoError::NotFound)
+        }
+
         async fn check_and_record_nonce(&self, _nonce_hash: &[u8]) -> Result<(), NonceRepoError> {
             self.nonce_result
                 .lock()
@@ -672,6 +2357,38 @@ pub mod mock {
             Ok(0)
         }
 
+        async fn create_auth_challenge(
+            &self,
+            device_kid: &Kid,
+            challenge_hash: &[u8],
+            expires_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), NonceRepoError> {
+            self.auth_challenges.lock().expect("lock poisoned").push((
+                device_kid.clone(),
+                challenge_hash.to_vec(),
+                expires_at,
+            ));
+            Ok(())
+        }
+
+        async fn consume_auth_challenge(
+            &self,
+            device_kid: &Kid,
+            challenge_hash: &[u8],
+        ) -> Result<(), NonceRepoError> {
+            let now = chrono::Utc::now();
+            let mut challenges = self.auth_challenges.lock().expect("lock poisoned");
+            let position = challenges
+                .iter()
+                .position(|(kid, hash, expires_at)| {
+                    kid == device_kid && hash == challenge_hash && *expires_at > now
+                })
+                .ok_or(NonceRepoError::UnknownChallenge)?;
+            challenges.remove(position);
+            drop(challenges);
+            Ok(())
+        }
+
         async fn create_signup(
             &self,
             _data: &ValidatedSignup,
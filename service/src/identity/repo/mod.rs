@@ -1,23 +1,49 @@
 //! Repository layer for identity persistence
 
 pub mod accounts;
+pub mod aliases;
+pub mod api_tokens;
 pub mod backups;
+pub mod crls;
 pub mod device_keys;
 pub mod identity;
 pub mod nonces;
+pub mod reserved_usernames;
+pub mod sessions;
+pub mod sigchain;
+pub mod signup_invites;
+pub mod step_up;
 
 pub use accounts::{
-    create_account_with_executor, get_account_by_id, get_account_by_username, AccountRecord,
-    AccountRepoError, CreatedAccount,
+    create_account_with_executor, get_account_by_id, get_account_by_username, get_account_status,
+    AccountDeactivation, AccountPurge, AccountReactivation, AccountRecord, AccountRepoError,
+    CreatedAccount, UsernameChange,
 };
+pub use aliases::{
+    resolve_account_handle, AccountAliasRecord, AliasRepoError, ResolvedHandle,
+    MAX_ALIASES_PER_ACCOUNT,
+};
+pub use api_tokens::{ApiTokenConsumerRecord, ApiTokenRecord, ApiTokenRepoError, ApiTokenUsage};
 pub use backups::{create_backup_with_executor, BackupRecord, BackupRepoError, CreatedBackup};
+pub use crls::{AccountCrlRecord, CrlRepoError};
 pub use device_keys::{
     create_device_key_with_executor, CreatedDeviceKey, DeviceKeyRecord, DeviceKeyRepoError,
+    DeviceRotation, DeviceUsageBatch, DeviceUsageRecord, MAX_DEVICES_PER_ACCOUNT,
 };
 pub use identity::{
     CreateSignupError, IdentityRepo, PgIdentityRepo, SignupResult, ValidatedSignup,
 };
-pub use nonces::{check_and_record_nonce, cleanup_expired_nonces, NonceRepoError};
+pub use nonces::{
+    check_and_record_nonce, cleanup_expired_nonces, consume_auth_challenge, create_auth_challenge,
+    maintain_nonce_partitions, NonceRepoError, PartitionChanges,
+};
+pub use reserved_usernames::{
+    ReleasedHandleRecord, ReservedUsernameRecord, ReservedUsernameRepoError,
+};
+pub use sessions::{DeviceSessionRecord, DeviceSessionRepoError};
+pub use sigchain::{SigchainLinkRecord, SigchainRepoError};
+pub use signup_invites::{SignupInviteRecord, SignupInviteRepoError};
+pub use step_up::{NewStepUpChallenge, StepUpChallengeRecord, StepUpPhoneRecord, StepUpRepoError};
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod mock {
@@ -3,18 +3,50 @@
 //! Stores SHA-256 hashes of request nonces in postgres. A nonce that has
 //! already been recorded is rejected as a replay. A background task
 //! periodically deletes entries older than the timestamp skew window.
+//!
+//! `request_nonces` is range-partitioned by `created_at` into hourly
+//! partitions, which the retention worker creates ahead of time and drops
+//! once expired ([`maintain_nonce_partitions`]). Rows outside every hourly
+//! partition fall into `request_nonces_default`.
+//!
+//! Server-issued challenges (`GET /auth/challenge`) live in
+//! `auth_challenges`: bound to one device KID, consumed on first use, and
+//! purged by the retention worker once expired.
 
+use chrono::{DateTime, DurationRound, NaiveDateTime, TimeDelta, Utc};
 use sqlx::PgPool;
+use tc_crypto::Kid;
+
+use crate::db::{instrument, Instrumented, RepoError, RepoErrorKind};
+
+/// Hourly partitions kept ready beyond the current hour.
+pub const NONCE_PARTITIONS_AHEAD: i64 = 3;
+
+/// Prefix of hourly partition names; the suffix is the start hour as
+/// `YYYYMMDDHH` (UTC).
+const PARTITION_PREFIX: &str = "request_nonces_p";
 
 /// Errors from nonce operations.
 #[derive(Debug, thiserror::Error)]
 pub enum NonceRepoError {
     #[error("request replay detected")]
     Replay,
+    #[error("challenge not found, expired, or already used")]
+    UnknownChallenge,
     #[error("database error: {0}")]
     Database(sqlx::Error),
 }
 
+impl RepoError for NonceRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::Replay => RepoErrorKind::Conflict,
+            Self::UnknownChallenge => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 /// Record a nonce hash. Returns `NonceRepoError::Replay` if already seen.
 ///
 /// # Errors
@@ -25,17 +57,31 @@ pub async fn check_and_record_nonce(
     pool: &PgPool,
     nonce_hash: &[u8],
 ) -> Result<(), NonceRepoError> {
-    let result =
-        sqlx::query("INSERT INTO request_nonces (nonce_hash) VALUES ($1) ON CONFLICT DO NOTHING")
+    // The partitioned table has no unique index on nonce_hash, so concurrent
+    // uses of one nonce are serialized on an advisory lock instead
+    let mut tx = pool.begin().await.map_err(NonceRepoError::Database)?;
+    sqlx::query(
+        "SELECT pg_advisory_xact_lock(hashtextextended('request_nonce:' || encode($1, 'hex'), 0))",
+    )
+    .bind(nonce_hash)
+    .execute(Instrumented::new(&mut *tx, "nonces.lock"))
+    .await
+    .map_err(NonceRepoError::Database)?;
+    let seen: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM request_nonces WHERE nonce_hash = $1)")
             .bind(nonce_hash)
-            .execute(pool)
+            .fetch_one(Instrumented::new(&mut *tx, "nonces.exists"))
             .await
             .map_err(NonceRepoError::Database)?;
-
-    if result.rows_affected() == 0 {
+    if seen {
         return Err(NonceRepoError::Replay);
     }
-    Ok(())
+    sqlx::query("INSERT INTO request_nonces (nonce_hash) VALUES ($1)")
+        .bind(nonce_hash)
+        .execute(Instrumented::new(&mut *tx, "nonces.insert"))
+        .await
+        .map_err(NonceRepoError::Database)?;
+    tx.commit().await.map_err(NonceRepoError::Database)
 }
 
 /// Delete nonces older than `max_age_secs`. Returns count of deleted rows.
@@ -51,9 +97,167 @@ pub async fn cleanup_expired_nonces(
         "DELETE FROM request_nonces WHERE created_at < now() - make_interval(secs => $1::float8)",
     )
     .bind(max_age_secs)
-    .execute(pool)
+    .execute(instrument(pool, "nonces.cleanup"))
     .await
     .map_err(NonceRepoError::Database)?;
 
     Ok(result.rows_affected())
 }
+
+/// Store a challenge hash for `device_kid`, valid until `expires_at`.
+///
+/// # Errors
+///
+/// Returns [`NonceRepoError::Database`] on connection or query failure.
+pub async fn create_auth_challenge(
+    pool: &PgPool,
+    device_kid: &Kid,
+    challenge_hash: &[u8],
+    expires_at: DateTime<Utc>,
+) -> Result<(), NonceRepoError> {
+    sqlx::query(
+        "INSERT INTO auth_challenges (challenge_hash, device_kid, expires_at) VALUES ($1, $2, $3)",
+    )
+    .bind(challenge_hash)
+    .bind(device_kid)
+    .bind(expires_at)
+    .execute(instrument(pool, "nonces.create_challenge"))
+    .await
+    .map_err(NonceRepoError::Database)?;
+    Ok(())
+}
+
+/// Use up a challenge issued to `device_kid`.
+///
+/// The row is deleted in the same statement that checks it, so concurrent
+/// requests cannot both spend one challenge.
+///
+/// # Errors
+///
+/// - [`NonceRepoError::UnknownChallenge`] if no unexpired challenge with this
+///   hash was issued to `device_kid`
+/// - [`NonceRepoError::Database`] on connection or query failure
+pub async fn consume_auth_challenge(
+    pool: &PgPool,
+    device_kid: &Kid,
+    challenge_hash: &[u8],
+) -> Result<(), NonceRepoError> {
+    let result = sqlx::query(
+        "DELETE FROM auth_challenges \
+         WHERE challenge_hash = $1 AND device_kid = $2 AND expires_at > now()",
+    )
+    .bind(challenge_hash)
+    .bind(device_kid)
+    .execute(instrument(pool, "nonces.consume_challenge"))
+    .await
+    .map_err(NonceRepoError::Database)?;
+    if result.rows_affected() == 0 {
+        return Err(NonceRepoError::UnknownChallenge);
+    }
+    Ok(())
+}
+
+/// Hourly partitions created and dropped by one maintenance pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartitionChanges {
+    pub created: Vec<String>,
+    pub dropped: Vec<String>,
+}
+
+fn partition_name(start: DateTime<Utc>) -> String {
+    format!("{PARTITION_PREFIX}{}", start.format("%Y%m%d%H"))
+}
+
+/// Start hour of an hourly partition, parsed back from its name.
+fn partition_start(name: &str) -> Option<DateTime<Utc>> {
+    let suffix = name.strip_prefix(PARTITION_PREFIX)?;
+    if suffix.len() != 10 || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(&format!("{suffix}00"), "%Y%m%d%H%M")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// Create the hourly partitions for the next [`NONCE_PARTITIONS_AHEAD`]
+/// hours and drop those that end at or before `cutoff`.
+///
+/// The current hour is never created here: rows for it may already be in
+/// the default partition, which would make the new range overlap them.
+///
+/// # Errors
+///
+/// Returns [`NonceRepoError::Database`] on connection or query failure.
+pub async fn maintain_nonce_partitions(
+    pool: &PgPool,
+    now: DateTime<Utc>,
+    cutoff: DateTime<Utc>,
+) -> Result<PartitionChanges, NonceRepoError> {
+    let mut changes = PartitionChanges::default();
+    let hour = now.duration_trunc(TimeDelta::hours(1)).unwrap_or(now);
+    for ahead in 1..=NONCE_PARTITIONS_AHEAD {
+        let start = hour + TimeDelta::hours(ahead);
+        let name = partition_name(start);
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+            .bind(&name)
+            .fetch_one(pool)
+            .await
+            .map_err(NonceRepoError::Database)?;
+        if exists {
+            continue;
+        }
+        // Names and bounds are generated here, never taken from input
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {name} PARTITION OF request_nonces \
+             FOR VALUES FROM ('{}') TO ('{}')",
+            start.to_rfc3339(),
+            (start + TimeDelta::hours(1)).to_rfc3339()
+        );
+        sqlx::query(&sql)
+            .execute(pool)
+            .await
+            .map_err(NonceRepoError::Database)?;
+        changes.created.push(name);
+    }
+
+    let partitions: Vec<String> = sqlx::query_scalar(
+        "SELECT c.relname::text FROM pg_inherits i \
+         JOIN pg_class c ON c.oid = i.inhrelid \
+         JOIN pg_class p ON p.oid = i.inhparent \
+         WHERE p.relname = 'request_nonces'",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(NonceRepoError::Database)?;
+    for start in partitions.iter().filter_map(|name| partition_start(name)) {
+        if start + TimeDelta::hours(1) > cutoff {
+            continue;
+        }
+        let name = partition_name(start);
+        sqlx::query(&format!("DROP TABLE IF EXISTS {name}"))
+            .execute(pool)
+            .await
+            .map_err(NonceRepoError::Database)?;
+        changes.dropped.push(name);
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_names_round_trip() {
+        let start = DateTime::parse_from_rfc3339("2025-03-10T07:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+        let name = partition_name(start);
+        assert_eq!(name, "request_nonces_p2025031007");
+        assert_eq!(partition_start(&name), Some(start));
+
+        assert_eq!(partition_start("request_nonces_default"), None);
+        assert_eq!(partition_start("request_nonces_p2025031007; DROP"), None);
+        assert_eq!(partition_start("request_nonces_p2025133107"), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! Reserved username repository
+//!
+//! Names are stored lowercased; [`crate::identity::username::UsernamePolicy`]
+//! keeps an in-memory copy that validation reads.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{map_unique_violation, RepoError, RepoErrorKind};
+
+/// Record returned from reserved username queries
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReservedUsernameRecord {
+    pub name: String,
+    pub reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Error types for reserved username operations
+#[derive(Debug, thiserror::Error)]
+pub enum ReservedUsernameRepoError {
+    #[error("name is already reserved")]
+    Duplicate,
+    #[error("reserved name not found")]
+    NotFound,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for ReservedUsernameRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::Duplicate => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+/// List every reserved name, alphabetically.
+///
+/// # Errors
+///
+/// Returns `ReservedUsernameRepoError::Database` on database failures.
+pub(crate) async fn list_reserved_usernames(
+    pool: &PgPool,
+) -> Result<Vec<ReservedUsernameRecord>, ReservedUsernameRepoError> {
+    let rows = sqlx::query_as::<_, ReservedUsernameRecord>(
+        "SELECT name, reason, created_by, created_at FROM reserved_usernames ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Reserve `name`, which must already be lowercased.
+///
+/// # Errors
+///
+/// Returns `ReservedUsernameRepoError::Duplicate` if the name is already reserved.
+pub(crate) async fn create_reserved_username(
+    pool: &PgPool,
+    name: &str,
+    reason: Option<&str>,
+    created_by: Uuid,
+) -> Result<ReservedUsernameRecord, ReservedUsernameRepoError> {
+    sqlx::query_as::<_, ReservedUsernameRecord>(
+        r"
+        INSERT INTO reserved_usernames (name, reason, created_by)
+        VALUES ($1, $2, $3)
+        RETURNING name, reason, created_by, created_at
+        ",
+    )
+    .bind(name)
+    .bind(reason)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        map_unique_violation(e, |constraint| {
+            (constraint == "reserved_usernames_pkey")
+                .then_some(ReservedUsernameRepoError::Duplicate)
+        })
+    })
+}
+
+/// Release a reserved name.
+///
+/// # Errors
+///
+/// Returns `ReservedUsernameRepoError::NotFound` if the name is not reserved.
+pub(crate) async fn delete_reserved_username(
+    pool: &PgPool,
+    name: &str,
+) -> Result<(), ReservedUsernameRepoError> {
+    let result = sqlx::query("DELETE FROM reserved_usernames WHERE name = $1")
+        .bind(name)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(ReservedUsernameRepoError::NotFound);
+    }
+    Ok(())
+}
+
+/// A username or alias that an account gave up; see migration
+/// `52_released_handles.sql`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReleasedHandleRecord {
+    pub handle: String,
+    /// Account that held the handle
+    pub account_id: Uuid,
+    pub released_at: DateTime<Utc>,
+}
+
+/// Look up when `handle` was last released, matching case-insensitively.
+///
+/// # Errors
+///
+/// Returns `ReservedUsernameRepoError::Database` on database failures.
+pub(crate) async fn get_released_handle(
+    pool: &PgPool,
+    handle: &str,
+) -> Result<Option<ReleasedHandleRecord>, ReservedUsernameRepoError> {
+    let row = sqlx::query_as::<_, ReleasedHandleRecord>(
+        "SELECT handle, account_id, released_at FROM released_handles WHERE handle = lower($1)",
+    )
+    .bind(handle)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row)
+}
+
+/// List handles released at or after `since`, newest first.
+///
+/// # Errors
+///
+/// Returns `ReservedUsernameRepoError::Database` on database failures.
+pub(crate) async fn list_released_handles(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+) -> Result<Vec<ReleasedHandleRecord>, ReservedUsernameRepoError> {
+    let rows = sqlx::query_as::<_, ReleasedHandleRecord>(
+        r"
+        SELECT handle, account_id, released_at
+        FROM released_handles
+        WHERE released_at >= $1
+        ORDER BY released_at DESC, handle
+        ",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Forget that `handle` was released, making it claimable right away.
+///
+/// # Errors
+///
+/// Returns `ReservedUsernameRepoError::NotFound` if the handle was never released.
+pub(crate) async fn delete_released_handle(
+    pool: &PgPool,
+    handle: &str,
+) -> Result<(), ReservedUsernameRepoError> {
+    let result = sqlx::query("DELETE FROM released_handles WHERE handle = lower($1)")
+        .bind(handle)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(ReservedUsernameRepoError::NotFound);
+    }
+    Ok(())
+}
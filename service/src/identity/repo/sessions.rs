@@ -0,0 +1,139 @@
+//! Device session repository; see [`crate::identity::http::sessions`].
+//!
+//! Only the SHA-256 of each session token is stored, as for API tokens.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tc_crypto::Kid;
+use uuid::Uuid;
+
+use crate::db::{instrument, RepoError, RepoErrorKind};
+
+/// Record returned from device session queries
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DeviceSessionRecord {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub device_kid: Kid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// When the issuing device was revoked, if it was
+    pub device_revoked_at: Option<DateTime<Utc>>,
+}
+
+impl DeviceSessionRecord {
+    /// Whether the session itself still authorizes requests at `now`; the
+    /// device's revocation is checked separately.
+    #[must_use]
+    pub fn is_live(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+/// Error types for device session operations
+#[derive(Debug, thiserror::Error)]
+pub enum DeviceSessionRepoError {
+    #[error("session not found")]
+    NotFound,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for DeviceSessionRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+/// Store a new session for `device_kid`.
+///
+/// The device's expired and revoked sessions are deleted first, so the table
+/// holds at most the live sessions plus those that lapsed since the device
+/// last logged in.
+///
+/// # Errors
+///
+/// Returns `DeviceSessionRepoError::Database` on database failures.
+pub(crate) async fn create_device_session(
+    pool: &PgPool,
+    account_id: Uuid,
+    device_kid: &Kid,
+    token_hash: &[u8],
+    expires_at: DateTime<Utc>,
+) -> Result<DeviceSessionRecord, DeviceSessionRepoError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r"
+        DELETE FROM device_sessions
+        WHERE device_kid = $1 AND (expires_at <= now() OR revoked_at IS NOT NULL)
+        ",
+    )
+    .bind(device_kid)
+    .execute(&mut *tx)
+    .await?;
+    let record = sqlx::query_as::<_, DeviceSessionRecord>(
+        r"
+        INSERT INTO device_sessions (account_id, device_kid, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, account_id, device_kid, created_at, expires_at, revoked_at,
+                  NULL::timestamptz AS device_revoked_at
+        ",
+    )
+    .bind(account_id)
+    .bind(device_kid)
+    .bind(token_hash)
+    .bind(expires_at)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Look up a session by token hash, with its device's revocation time.
+///
+/// # Errors
+///
+/// Returns `DeviceSessionRepoError::NotFound` if no session has this hash.
+pub(crate) async fn get_device_session_by_hash(
+    pool: &PgPool,
+    token_hash: &[u8],
+) -> Result<DeviceSessionRecord, DeviceSessionRepoError> {
+    sqlx::query_as::<_, DeviceSessionRecord>(
+        r"
+        SELECT s.id, s.account_id, s.device_kid, s.created_at, s.expires_at, s.revoked_at,
+               d.revoked_at AS device_revoked_at
+        FROM device_sessions s
+        JOIN device_keys d ON d.device_kid = s.device_kid
+        WHERE s.token_hash = $1
+        ",
+    )
+    .bind(token_hash)
+    .fetch_optional(instrument(pool, "sessions.get_by_hash"))
+    .await?
+    .ok_or(DeviceSessionRepoError::NotFound)
+}
+
+/// Revoke every live session issued to `device_kid`.
+///
+/// # Errors
+///
+/// Returns `DeviceSessionRepoError::Database` on database failures.
+pub(crate) async fn revoke_device_sessions(
+    pool: &PgPool,
+    device_kid: &Kid,
+) -> Result<u64, DeviceSessionRepoError> {
+    let result = sqlx::query(
+        r"
+        UPDATE device_sessions SET revoked_at = now()
+        WHERE device_kid = $1 AND revoked_at IS NULL AND expires_at > now()
+        ",
+    )
+    .bind(device_kid)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
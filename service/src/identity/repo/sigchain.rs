@@ -0,0 +1,143 @@
+//! Account sigchain links; see [`crate::identity::sigchain`].
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use tc_crypto::Kid;
+use uuid::Uuid;
+
+use crate::db::{instrument, Instrumented, RepoError, RepoErrorKind};
+use crate::identity::sigchain::{link_hash, NewSigchainLink};
+
+/// Record returned from sigchain queries
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SigchainLinkRecord {
+    pub account_id: Uuid,
+    pub seqno: i64,
+    pub link_type: String,
+    pub signer_kid: Kid,
+    /// JSON text of the signer's [`SignedRequest`](crate::identity::signed_request::SignedRequest),
+    /// or of the root-signed undelete request for `account_reactivated`
+    pub envelope: String,
+    pub prev_hash: Option<Vec<u8>>,
+    pub hash: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Error types for sigchain operations
+#[derive(Debug, thiserror::Error)]
+pub enum SigchainRepoError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for SigchainRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+/// Append `link` after the account's current head, inside the caller's
+/// transaction so the link commits or rolls back with the change it records.
+///
+/// The account row is locked so concurrent appends take consecutive seqnos.
+///
+/// # Errors
+///
+/// Returns the database error, including `RowNotFound` for an unknown
+/// account.
+pub(crate) async fn append_sigchain_link(
+    conn: &mut PgConnection,
+    account_id: Uuid,
+    link: &NewSigchainLink,
+) -> Result<SigchainLinkRecord, sqlx::Error> {
+    sqlx::query("SELECT 1 FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(account_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let head: Option<(i64, Vec<u8>)> = sqlx::query_as(
+        "SELECT seqno, hash FROM account_sigchain WHERE account_id = $1 \
+         ORDER BY seqno DESC LIMIT 1",
+    )
+    .bind(account_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let (seqno, prev_hash) = match head {
+        Some((seqno, hash)) => (seqno + 1, Some(hash)),
+        None => (1, None),
+    };
+    let link_type = link.link_type.as_str();
+    let hash = link_hash(seqno, prev_hash.as_deref(), link_type, &link.envelope);
+
+    sqlx::query_as::<_, SigchainLinkRecord>(
+        r"
+        INSERT INTO account_sigchain (account_id, seqno, link_type, signer_kid, envelope,
+                                      prev_hash, hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING account_id, seqno, link_type, signer_kid, envelope, prev_hash, hash, created_at
+        ",
+    )
+    .bind(account_id)
+    .bind(seqno)
+    .bind(link_type)
+    .bind(link.signer_kid.as_str())
+    .bind(&link.envelope)
+    .bind(prev_hash)
+    .bind(hash.as_slice())
+    .fetch_one(Instrumented::new(&mut *conn, "sigchain.append"))
+    .await
+}
+
+/// Up to `limit` links with seqno above `since_seqno`, oldest first.
+///
+/// # Errors
+///
+/// Returns `SigchainRepoError::Database` on database failures.
+pub(crate) async fn list_sigchain_links(
+    pool: &PgPool,
+    account_id: Uuid,
+    since_seqno: i64,
+    limit: i64,
+) -> Result<Vec<SigchainLinkRecord>, SigchainRepoError> {
+    let links = sqlx::query_as::<_, SigchainLinkRecord>(
+        r"
+        SELECT account_id, seqno, link_type, signer_kid, envelope, prev_hash, hash, created_at
+        FROM account_sigchain
+        WHERE account_id = $1 AND seqno > $2
+        ORDER BY seqno
+        LIMIT $3
+        ",
+    )
+    .bind(account_id)
+    .bind(since_seqno)
+    .bind(limit)
+    .fetch_all(instrument(pool, "sigchain.list"))
+    .await?;
+    Ok(links)
+}
+
+/// The account's latest link, if any.
+///
+/// # Errors
+///
+/// Returns `SigchainRepoError::Database` on database failures.
+pub(crate) async fn get_sigchain_head(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Option<SigchainLinkRecord>, SigchainRepoError> {
+    let head = sqlx::query_as::<_, SigchainLinkRecord>(
+        r"
+        SELECT account_id, seqno, link_type, signer_kid, envelope, prev_hash, hash, created_at
+        FROM account_sigchain
+        WHERE account_id = $1
+        ORDER BY seqno DESC
+        LIMIT 1
+        ",
+    )
+    .bind(account_id)
+    .fetch_optional(instrument(pool, "sigchain.head"))
+    .await?;
+    Ok(head)
+}
@@ -0,0 +1,151 @@
+//! Signup invite repository for invite-only instances
+//!
+//! Only the SHA-256 of each invite code is stored, like API tokens.
+//! Redemption runs inside the signup transaction so a code is consumed only
+//! if the account is actually created.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{RepoError, RepoErrorKind};
+
+/// Record returned from signup invite queries
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SignupInviteRecord {
+    pub id: Uuid,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub redeemed_by: Option<Uuid>,
+    pub redeemed_at: Option<DateTime<Utc>>,
+}
+
+/// Error types for signup invite operations
+#[derive(Debug, thiserror::Error)]
+pub enum SignupInviteRepoError {
+    #[error("account not found")]
+    NotFound,
+    #[error("invite quota reached")]
+    QuotaReached,
+    #[error("invite code is unknown, expired, or already used")]
+    InvalidCode,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for SignupInviteRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::QuotaReached => RepoErrorKind::LimitReached,
+            Self::InvalidCode => RepoErrorKind::Rejected,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+/// Create a signup invite.
+///
+/// `max_outstanding` caps the issuer's unredeemed, unexpired invites; `None`
+/// means no cap. The account row is locked so concurrent creations cannot
+/// exceed it.
+///
+/// # Errors
+///
+/// Returns `SignupInviteRepoError::NotFound` if the account does not exist.
+/// Returns `SignupInviteRepoError::QuotaReached` if the account is at its cap.
+pub(crate) async fn create_signup_invite(
+    pool: &PgPool,
+    created_by: Uuid,
+    code_hash: &[u8],
+    expires_at: DateTime<Utc>,
+    max_outstanding: Option<i64>,
+) -> Result<SignupInviteRecord, SignupInviteRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let locked = sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(created_by)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if locked.is_none() {
+        return Err(SignupInviteRepoError::NotFound);
+    }
+
+    let record = sqlx::query_as::<_, SignupInviteRecord>(
+        r"
+        INSERT INTO signup_invites (created_by, code_hash, expires_at)
+        SELECT $1, $2, $3
+        WHERE $4::bigint IS NULL OR (
+            SELECT COUNT(*) FROM signup_invites
+            WHERE created_by = $1 AND redeemed_at IS NULL AND expires_at > now()
+        ) < $4
+        RETURNING id, created_by, created_at, expires_at, redeemed_by, redeemed_at
+        ",
+    )
+    .bind(created_by)
+    .bind(code_hash)
+    .bind(expires_at)
+    .bind(max_outstanding)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(SignupInviteRepoError::QuotaReached)?;
+
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// List all signup invites issued by an account, newest first.
+///
+/// # Errors
+///
+/// Returns `SignupInviteRepoError::Database` on database failures.
+pub(crate) async fn list_signup_invites_by_account<'e, E>(
+    executor: E,
+    account_id: Uuid,
+) -> Result<Vec<SignupInviteRecord>, SignupInviteRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query_as::<_, SignupInviteRecord>(
+        r"
+        SELECT id, created_by, created_at, expires_at, redeemed_by, redeemed_at
+        FROM signup_invites
+        WHERE created_by = $1
+        ORDER BY created_at DESC
+        ",
+    )
+    .bind(account_id)
+    .fetch_all(executor)
+    .await?;
+    Ok(rows)
+}
+
+/// Mark the invite matching `code_hash` as redeemed by `account_id`.
+///
+/// # Errors
+///
+/// Returns `SignupInviteRepoError::InvalidCode` if no unredeemed, unexpired
+/// invite matches.
+pub(crate) async fn redeem_signup_invite_with_executor<'e, E>(
+    executor: E,
+    code_hash: &[u8],
+    account_id: Uuid,
+) -> Result<Uuid, SignupInviteRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query_scalar(
+        r"
+        UPDATE signup_invites
+        SET redeemed_by = $2, redeemed_at = now()
+        WHERE code_hash = $1 AND redeemed_at IS NULL AND expires_at > now()
+        RETURNING id
+        ",
+    )
+    .bind(code_hash)
+    .bind(account_id)
+    .fetch_optional(executor)
+    .await?
+    .ok_or(SignupInviteRepoError::InvalidCode)
+}
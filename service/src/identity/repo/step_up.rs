@@ -0,0 +1,321 @@
+//! Step-up phone enrollment and challenge persistence; see
+//! [`crate::identity::step_up`].
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tc_crypto::Kid;
+use uuid::Uuid;
+
+use crate::db::{instrument, RepoError, RepoErrorKind};
+use crate::identity::step_up::StepUpAction;
+
+/// A phone enrolled for step-up codes.
+#[derive(Debug, Clone)]
+pub struct StepUpPhoneRecord {
+    pub account_id: Uuid,
+    pub phone_number: String,
+    pub enrolled_at: DateTime<Utc>,
+}
+
+/// A texted step-up code.
+#[derive(Debug, Clone)]
+pub struct StepUpChallengeRecord {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub device_kid: Kid,
+    pub action: StepUpAction,
+    pub phone_number: String,
+    pub expires_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+/// A challenge to store before its code is texted.
+pub struct NewStepUpChallenge<'a> {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub device_kid: &'a Kid,
+    pub action: StepUpAction,
+    pub phone_number: &'a str,
+    pub code_hash: &'a [u8],
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Error types for step-up operations
+#[derive(Debug, thiserror::Error)]
+pub enum StepUpRepoError {
+    #[error("step-up challenge not found or expired")]
+    NotFound,
+    #[error("no phone enrolled")]
+    NoPhone,
+    #[error("incorrect code")]
+    InvalidCode,
+    #[error("too many incorrect codes")]
+    TooManyAttempts,
+    #[error("step-up challenge already confirmed")]
+    AlreadyConfirmed,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for StepUpRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound | Self::NoPhone => RepoErrorKind::NotFound,
+            Self::InvalidCode => RepoErrorKind::Rejected,
+            Self::TooManyAttempts => RepoErrorKind::LimitReached,
+            Self::AlreadyConfirmed => RepoErrorKind::Conflict,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StepUpChallengeRow {
+    id: Uuid,
+    account_id: Uuid,
+    device_kid: Kid,
+    action: String,
+    phone_number: String,
+    code_hash: Vec<u8>,
+    attempts: i32,
+    expires_at: DateTime<Utc>,
+    confirmed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<StepUpChallengeRow> for StepUpChallengeRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: StepUpChallengeRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            account_id: row.account_id,
+            device_kid: row.device_kid,
+            action: row
+                .action
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            phone_number: row.phone_number,
+            expires_at: row.expires_at,
+            confirmed_at: row.confirmed_at,
+        })
+    }
+}
+
+/// Get an account's enrolled phone, if any.
+///
+/// # Errors
+///
+/// Returns `StepUpRepoError::Database` on database failures.
+pub(crate) async fn get_step_up_phone(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Option<StepUpPhoneRecord>, StepUpRepoError> {
+    let row: Option<(Uuid, String, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT account_id, phone_number, enrolled_at FROM step_up_phones WHERE account_id = $1",
+    )
+    .bind(account_id)
+    .fetch_optional(instrument(pool, "step_up.get_phone"))
+    .await?;
+    Ok(row.map(
+        |(account_id, phone_number, enrolled_at)| StepUpPhoneRecord {
+            account_id,
+            phone_number,
+            enrolled_at,
+        },
+    ))
+}
+
+/// Remove an account's enrolled phone and its outstanding challenges.
+///
+/// # Errors
+///
+/// Returns `StepUpRepoError::NoPhone` if no phone is enrolled.
+pub(crate) async fn delete_step_up_phone(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<(), StepUpRepoError> {
+    let mut tx = pool.begin().await?;
+    let deleted = sqlx::query("DELETE FROM step_up_phones WHERE account_id = $1")
+        .bind(account_id)
+        .execute(&mut *tx)
+        .await?;
+    if deleted.rows_affected() == 0 {
+        return Err(StepUpRepoError::NoPhone);
+    }
+    sqlx::query("DELETE FROM step_up_challenges WHERE account_id = $1")
+        .bind(account_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Store a new challenge.
+///
+/// Unused challenges from the same device for the same action are replaced,
+/// so only the most recently texted code works.
+///
+/// # Errors
+///
+/// Returns `StepUpRepoError::Database` on database failures.
+pub(crate) async fn create_step_up_challenge(
+    pool: &PgPool,
+    new: &NewStepUpChallenge<'_>,
+) -> Result<StepUpChallengeRecord, StepUpRepoError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r"
+        DELETE FROM step_up_challenges
+        WHERE account_id = $1
+          AND (expires_at <= now()
+               OR (device_kid = $2 AND action = $3 AND consumed_at IS NULL))
+        ",
+    )
+    .bind(new.account_id)
+    .bind(new.device_kid)
+    .bind(new.action.as_str())
+    .execute(&mut *tx)
+    .await?;
+    let row = sqlx::query_as::<_, StepUpChallengeRow>(
+        r"
+        INSERT INTO step_up_challenges
+            (id, account_id, device_kid, action, phone_number, code_hash, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, account_id, device_kid, action, phone_number, code_hash, attempts,
+                  expires_at, confirmed_at
+        ",
+    )
+    .bind(new.id)
+    .bind(new.account_id)
+    .bind(new.device_kid)
+    .bind(new.action.as_str())
+    .bind(new.phone_number)
+    .bind(new.code_hash)
+    .bind(new.expires_at)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(row.try_into()?)
+}
+
+/// Confirm a challenge with the texted code.
+///
+/// A wrong code counts against `max_attempts`. Confirming an `enroll_phone`
+/// challenge enrolls its phone and consumes the challenge.
+///
+/// # Errors
+///
+/// Returns `StepUpRepoError::NotFound` if the challenge does not exist for
+/// this account and device, or has expired.
+/// Returns `StepUpRepoError::AlreadyConfirmed` if it was already confirmed.
+/// Returns `StepUpRepoError::TooManyAttempts` once `max_attempts` wrong
+/// codes have been entered.
+/// Returns `StepUpRepoError::InvalidCode` if the code does not match.
+pub(crate) async fn confirm_step_up_challenge(
+    pool: &PgPool,
+    challenge_id: Uuid,
+    account_id: Uuid,
+    device_kid: &Kid,
+    code_hash: &[u8],
+    max_attempts: i32,
+) -> Result<StepUpChallengeRecord, StepUpRepoError> {
+    let mut tx = pool.begin().await?;
+    let row = sqlx::query_as::<_, StepUpChallengeRow>(
+        r"
+        SELECT id, account_id, device_kid, action, phone_number, code_hash, attempts,
+               expires_at, confirmed_at
+        FROM step_up_challenges
+        WHERE id = $1 AND account_id = $2 AND device_kid = $3 AND expires_at > now()
+        FOR UPDATE
+        ",
+    )
+    .bind(challenge_id)
+    .bind(account_id)
+    .bind(device_kid)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(StepUpRepoError::NotFound)?;
+
+    if row.confirmed_at.is_some() {
+        return Err(StepUpRepoError::AlreadyConfirmed);
+    }
+    if row.attempts >= max_attempts {
+        return Err(StepUpRepoError::TooManyAttempts);
+    }
+    if row.code_hash != code_hash {
+        sqlx::query("UPDATE step_up_challenges SET attempts = attempts + 1 WHERE id = $1")
+            .bind(challenge_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        return Err(StepUpRepoError::InvalidCode);
+    }
+
+    let mut record = StepUpChallengeRecord::try_from(row)?;
+    let enrolls = record.action == StepUpAction::EnrollPhone;
+    let confirmed_at: DateTime<Utc> = sqlx::query_scalar(
+        r"
+        UPDATE step_up_challenges
+        SET confirmed_at = now(), consumed_at = CASE WHEN $2 THEN now() END
+        WHERE id = $1
+        RETURNING confirmed_at
+        ",
+    )
+    .bind(challenge_id)
+    .bind(enrolls)
+    .fetch_one(&mut *tx)
+    .await?;
+    if enrolls {
+        sqlx::query(
+            r"
+            INSERT INTO step_up_phones (account_id, phone_number)
+            VALUES ($1, $2)
+            ON CONFLICT (account_id)
+            DO UPDATE SET phone_number = EXCLUDED.phone_number, enrolled_at = now()
+            ",
+        )
+        .bind(account_id)
+        .bind(&record.phone_number)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    record.confirmed_at = Some(confirmed_at);
+    Ok(record)
+}
+
+/// Use up a confirmed challenge to authorize `action`.
+///
+/// # Errors
+///
+/// Returns `StepUpRepoError::NotFound` unless the challenge belongs to this
+/// account and device, is for `action`, was confirmed after
+/// `confirmed_after`, and has not been used.
+pub(crate) async fn consume_step_up_challenge(
+    pool: &PgPool,
+    challenge_id: Uuid,
+    account_id: Uuid,
+    device_kid: &Kid,
+    action: StepUpAction,
+    confirmed_after: DateTime<Utc>,
+) -> Result<(), StepUpRepoError> {
+    let consumed = sqlx::query(
+        r"
+        UPDATE step_up_challenges
+        SET consumed_at = now()
+        WHERE id = $1 AND account_id = $2 AND device_kid = $3 AND action = $4
+          AND confirmed_at > $5 AND consumed_at IS NULL
+        ",
+    )
+    .bind(challenge_id)
+    .bind(account_id)
+    .bind(device_kid)
+    .bind(action.as_str())
+    .bind(confirmed_after)
+    .execute(instrument(pool, "step_up.consume_challenge"))
+    .await?;
+    if consumed.rows_affected() == 0 {
+        return Err(StepUpRepoError::NotFound);
+    }
+    Ok(())
+}
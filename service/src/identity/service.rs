@@ -6,14 +6,20 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rand::RngCore;
 use serde::Deserialize;
-use tc_crypto::{decode_base64url, verify_ed25519, BackupEnvelope, Kid};
+use sha2::{Digest, Sha256};
+use tc_crypto::{decode_base64url, encode_base64url, verify_ed25519, BackupEnvelope, Kid};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
+use super::capabilities::ClientCaps;
 use super::repo::{
     AccountRepoError, BackupRepoError, CreateSignupError, DeviceKeyRepoError, IdentityRepo,
-    ValidatedSignup,
+    SignupInviteRepoError, ValidatedSignup,
 };
+use super::username::UsernamePolicy;
+use crate::db::RepoError;
 
 // Re-export repo's SignupResult — the service adds no extra fields today.
 // If the service later needs its own fields (e.g., session tokens), fork it then.
@@ -37,6 +43,10 @@ pub struct SignupDevice {
     pub name: String,
     /// Base64url-encoded certificate (root key's signature over canonical cert message)
     pub certificate: String,
+    /// Envelope versions this client can produce, e.g. `["sig-v2", "cert-v2"]`;
+    /// see [`crate::identity::capabilities`]
+    #[serde(default)]
+    pub capabilities: Vec<String>,
 }
 
 /// Signup request payload — atomic creation of account + backup + first device
@@ -47,6 +57,9 @@ pub struct SignupRequest {
     pub root_pubkey: String,
     pub backup: SignupBackup,
     pub device: SignupDevice,
+    /// Invite code; required when the instance is invite-only
+    #[serde(default)]
+    pub invite_code: Option<String>,
 }
 
 // ─── Domain error type ──────────────────────────────────────────────────────
@@ -62,80 +75,14 @@ pub enum SignupError {
     DuplicateKey,
     #[error("Maximum device limit reached")]
     MaxDevicesReached,
+    #[error("This instance is invite-only; an invite code is required")]
+    InviteRequired,
+    #[error("Invite code is invalid, expired, or already used")]
+    InvalidInvite,
     #[error("internal error: {0}")]
     Internal(String),
 }
 
-// ─── Validation helpers ──────────────────────────────────────────────────────
-
-/// Maximum byte length of a valid username.
-///
-/// Used both in signup validation and at the login/backup endpoints to reject
-/// obviously invalid input before hitting the database.
-pub const MAX_USERNAME_LEN: usize = 64;
-
-const RESERVED_USERNAMES: &[&str] = &[
-    "admin",
-    "administrator",
-    "root",
-    "system",
-    "mod",
-    "moderator",
-    "support",
-    "help",
-    "api",
-    "graphql",
-    "auth",
-    "signup",
-    "login",
-    "null",
-    "undefined",
-    "anonymous",
-];
-
-/// Structured error type for username validation failures.
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-pub enum UsernameError {
-    #[error("Username cannot be empty")]
-    Empty,
-    #[error("Username must be at least 3 characters")]
-    TooShort,
-    #[error("Username too long")]
-    TooLong,
-    #[error("Username may only contain letters, numbers, hyphens, and underscores")]
-    InvalidCharacters,
-    #[error("This username is reserved")]
-    Reserved,
-}
-
-/// Validate a username, returning a structured error if invalid.
-///
-/// # Errors
-///
-/// Returns [`UsernameError`] if the username is empty, too short/long,
-/// contains invalid characters, or is reserved.
-pub fn validate_username(username: &str) -> Result<(), UsernameError> {
-    if username.is_empty() {
-        return Err(UsernameError::Empty);
-    }
-    if username.len() < 3 {
-        return Err(UsernameError::TooShort);
-    }
-    if username.len() > MAX_USERNAME_LEN {
-        return Err(UsernameError::TooLong);
-    }
-    if !username
-        .chars()
-        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
-    {
-        return Err(UsernameError::InvalidCharacters);
-    }
-    if RESERVED_USERNAMES.contains(&username.to_ascii_lowercase().as_str()) {
-        return Err(UsernameError::Reserved);
-    }
-    Ok(())
-}
-
 // ─── DeviceName type ─────────────────────────────────────────────────────────
 
 /// A validated, trimmed device name (1–128 Unicode scalars).
@@ -310,6 +257,115 @@ impl CertificateSignature {
     }
 }
 
+/// Certificate version for a root signature over the raw device pubkey.
+pub const CERTIFICATE_VERSION_RAW: i32 = 1;
+
+/// Certificate version for a root signature over `device_pubkey || timestamp`.
+pub const CERTIFICATE_VERSION_TIMESTAMPED: i32 = 2;
+
+/// Bytes the root key signs to certify a device key.
+///
+/// Signup and `POST /auth/devices` certify the raw public key; login also
+/// binds the request timestamp (little-endian `i64`).
+#[must_use]
+pub fn device_certificate_message(device_pubkey: &[u8; 32], timestamp: Option<i64>) -> Vec<u8> {
+    let mut message = Vec::with_capacity(40);
+    message.extend_from_slice(device_pubkey);
+    if let Some(timestamp) = timestamp {
+        message.extend_from_slice(&timestamp.to_le_bytes());
+    }
+    message
+}
+
+/// Domain separator for device rotation signatures.
+const DEVICE_ROTATION_CONTEXT: &[u8] = b"tc-device-rotation-v1";
+
+/// Bytes the old device key signs to hand its identity to a new key pair
+/// (`POST /auth/devices/{kid}/rotate`).
+///
+/// `context || 0x00 || old KID || 0x00 || new pubkey`. The context keeps the
+/// signature from doubling as a request signature or certificate.
+#[must_use]
+pub fn device_rotation_message(old_kid: &Kid, new_pubkey: &[u8; 32]) -> Vec<u8> {
+    let mut message =
+        Vec::with_capacity(DEVICE_ROTATION_CONTEXT.len() + old_kid.as_str().len() + 34);
+    message.extend_from_slice(DEVICE_ROTATION_CONTEXT);
+    message.push(0);
+    message.extend_from_slice(old_kid.as_str().as_bytes());
+    message.push(0);
+    message.extend_from_slice(new_pubkey);
+    message
+}
+
+/// Domain separator for account deletion signatures.
+const ACCOUNT_DELETION_CONTEXT: &[u8] = b"tc-account-deletion-v1";
+
+/// Domain separator for account undeletion signatures.
+const ACCOUNT_UNDELETION_CONTEXT: &[u8] = b"tc-account-undeletion-v1";
+
+/// Domain separator for recovery code change signatures.
+const RECOVERY_CODE_CONTEXT: &[u8] = b"tc-recovery-code-v1";
+
+/// Bytes the root key signs to deactivate an account
+/// (`DELETE /auth/accounts/me`).
+///
+/// `context || 0x00 || account ID || 0x00 || timestamp (LE i64)`, where the
+/// timestamp is the signed request's, so a signature cannot be replayed once
+/// the request falls out of the clock skew window.
+#[must_use]
+pub fn account_deletion_message(account_id: Uuid, timestamp: i64) -> Vec<u8> {
+    account_lifecycle_message(ACCOUNT_DELETION_CONTEXT, account_id, timestamp)
+}
+
+/// Bytes the root key signs to restore a deactivated account
+/// (`POST /auth/accounts/undelete`). Same layout as
+/// [`account_deletion_message`] under its own context.
+#[must_use]
+pub fn account_undeletion_message(account_id: Uuid, timestamp: i64) -> Vec<u8> {
+    account_lifecycle_message(ACCOUNT_UNDELETION_CONTEXT, account_id, timestamp)
+}
+
+/// Bytes the root key signs to set or clear the backup recovery code
+/// (`PUT /auth/backups/recovery-code`). Same layout as
+/// [`account_deletion_message`] under its own context.
+#[must_use]
+pub fn recovery_code_message(account_id: Uuid, timestamp: i64) -> Vec<u8> {
+    account_lifecycle_message(RECOVERY_CODE_CONTEXT, account_id, timestamp)
+}
+
+fn account_lifecycle_message(context: &[u8], account_id: Uuid, timestamp: i64) -> Vec<u8> {
+    let account_id = account_id.to_string();
+    let mut message = Vec::with_capacity(context.len() + account_id.len() + 10);
+    message.extend_from_slice(context);
+    message.push(0);
+    message.extend_from_slice(account_id.as_bytes());
+    message.push(0);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+// ─── Signup invite codes ─────────────────────────────────────────────────────
+
+/// Prefix of every signup invite code.
+pub const INVITE_CODE_PREFIX: &str = "tci_";
+
+/// Random bytes per invite code.
+const INVITE_CODE_BYTES: usize = 16;
+
+/// Generate a new plaintext signup invite code.
+#[must_use]
+pub fn generate_invite_code() -> String {
+    let mut bytes = [0u8; INVITE_CODE_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{INVITE_CODE_PREFIX}{}", encode_base64url(&bytes))
+}
+
+/// Hash a plaintext invite code for storage and lookup.
+#[must_use]
+pub fn hash_invite_code(code: &str) -> Vec<u8> {
+    Sha256::digest(code.trim().as_bytes()).to_vec()
+}
+
 // ─── Service trait and implementation ────────────────────────────────────────
 
 /// Orchestrates identity operations: validation + atomic persistence.
@@ -324,12 +380,32 @@ pub trait IdentityService: Send + Sync {
 /// Production implementation — validates all fields then delegates to [`IdentityRepo`].
 pub struct DefaultIdentityService {
     repo: Arc<dyn IdentityRepo>,
+    invite_only: bool,
+    usernames: Arc<UsernamePolicy>,
 }
 
 impl DefaultIdentityService {
     #[must_use]
     pub fn new(repo: Arc<dyn IdentityRepo>) -> Self {
-        Self { repo }
+        Self {
+            repo,
+            invite_only: false,
+            usernames: Arc::default(),
+        }
+    }
+
+    /// Require and redeem an invite code on every signup.
+    #[must_use]
+    pub const fn with_invite_only(mut self, invite_only: bool) -> Self {
+        self.invite_only = invite_only;
+        self
+    }
+
+    /// Validate new usernames against `policy` instead of the default pack.
+    #[must_use]
+    pub fn with_username_policy(mut self, policy: Arc<UsernamePolicy>) -> Self {
+        self.usernames = policy;
+        self
     }
 }
 
@@ -340,50 +416,18 @@ fn map_signup_error(e: CreateSignupError) -> SignupError {
             SignupError::DuplicateUsername
         }
         CreateSignupError::Account(AccountRepoError::DuplicateKey)
+        | CreateSignupError::Backup(BackupRepoError::DuplicateKid)
         | CreateSignupError::DeviceKey(DeviceKeyRepoError::DuplicateKid) => {
             SignupError::DuplicateKey
         }
         CreateSignupError::DeviceKey(DeviceKeyRepoError::MaxDevicesReached) => {
             SignupError::MaxDevicesReached
         }
-        CreateSignupError::Account(AccountRepoError::NotFound) => {
-            // Unreachable from create path — indicates a programming error
-            tracing::error!("Unexpected NotFound from account create during signup");
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::Account(AccountRepoError::Database(e)) => {
-            tracing::error!("Signup failed (account): {e}");
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::Backup(BackupRepoError::DuplicateKid) => SignupError::DuplicateKey,
-        CreateSignupError::Backup(BackupRepoError::DuplicateAccount) => {
-            tracing::error!(
-                "Unexpected DuplicateAccount on backup create during signup — account was just inserted"
-            );
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::Backup(BackupRepoError::Database(e)) => {
-            tracing::error!("Signup failed (backup): {e}");
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::Backup(BackupRepoError::NotFound) => {
-            tracing::error!("Unexpected NotFound from backup create during signup");
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::DeviceKey(
-            DeviceKeyRepoError::NotFound | DeviceKeyRepoError::AlreadyRevoked,
-        ) => {
-            tracing::error!(
-                "Unexpected NotFound/AlreadyRevoked from device key create during signup"
-            );
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::DeviceKey(DeviceKeyRepoError::Database(e)) => {
-            tracing::error!("Signup failed (device key): {e}");
-            SignupError::Internal("Internal server error".to_string())
-        }
-        CreateSignupError::Transaction(e) => {
-            tracing::error!("Signup transaction failed: {e}");
+        CreateSignupError::Invite(SignupInviteRepoError::InvalidCode) => SignupError::InvalidInvite,
+        // Database failures, plus cases the create path cannot produce
+        // (NotFound, AlreadyRevoked, a second backup for a new account)
+        e => {
+            tracing::error!(kind = e.kind().as_str(), "Signup failed: {e}");
             SignupError::Internal("Internal server error".to_string())
         }
     }
@@ -392,9 +436,34 @@ fn map_signup_error(e: CreateSignupError) -> SignupError {
 #[async_trait]
 impl IdentityService for DefaultIdentityService {
     async fn signup(&self, req: &SignupRequest) -> Result<SignupResult, SignupError> {
+        // Invite-only instances reject signups without a code up front; the
+        // code itself is redeemed inside the signup transaction.
+        let invite_code_hash = if self.invite_only {
+            match req.invite_code.as_deref().map(str::trim) {
+                Some(code) if !code.is_empty() => Some(hash_invite_code(code)),
+                _ => return Err(SignupError::InviteRequired),
+            }
+        } else {
+            None
+        };
+
         // Validate username
         let username = req.username.trim().to_string();
-        validate_username(&username).map_err(|e| SignupError::Validation(e.to_string()))?;
+        self.usernames
+            .validate(&username)
+            .map_err(|e| SignupError::Validation(e.to_string()))?;
+        match self
+            .usernames
+            .is_cooling_off(self.repo.as_ref(), &username, None)
+            .await
+        {
+            Ok(false) => {}
+            Ok(true) => return Err(SignupError::DuplicateUsername),
+            Err(e) => {
+                tracing::error!("Failed to check released usernames: {e}");
+                return Err(SignupError::Internal("Internal server error".to_string()));
+            }
+        }
 
         // Decode and validate root public key
         let root_pubkey = RootPubkey::from_base64url(&req.root_pubkey)
@@ -436,6 +505,10 @@ impl IdentityService for DefaultIdentityService {
         )
         .map_err(|_| SignupError::Validation("Invalid device certificate".to_string()))?;
 
+        let client_caps =
+            ClientCaps::parse_tokens(req.device.capabilities.iter().map(String::as_str))
+                .map_err(|e| SignupError::Validation(e.to_string()))?;
+
         // Build validated signup data and delegate to repo
         let validated = ValidatedSignup {
             username,
@@ -448,6 +521,8 @@ impl IdentityService for DefaultIdentityService {
             device_kid,
             device_name: device_name.as_str().to_string(),
             certificate: cert_sig.as_bytes().to_vec(),
+            client_caps: client_caps.tokens(),
+            invite_code_hash,
         };
 
         self.repo
@@ -570,7 +645,9 @@ mod tests {
                 pubkey: encode_base64url(&device_pubkey_bytes),
                 name: "Test Device".to_string(),
                 certificate: encode_base64url(&certificate_sig.to_bytes()),
+                capabilities: Vec::new(),
             },
+            invite_code: None,
         }
     }
 
@@ -714,76 +791,6 @@ mod tests {
         assert_eq!(key.kid(), Kid::derive(&bytes));
     }
 
-    // ── Username validation (direct function tests) ────────────────────────
-
-    #[test]
-    fn test_validate_username_empty() {
-        assert_eq!(validate_username(""), Err(UsernameError::Empty));
-    }
-
-    #[test]
-    fn test_validate_username_too_short() {
-        assert_eq!(validate_username("ab"), Err(UsernameError::TooShort));
-        assert_eq!(validate_username("a"), Err(UsernameError::TooShort));
-    }
-
-    #[test]
-    fn test_validate_username_min_valid_length() {
-        assert!(validate_username("abc").is_ok());
-    }
-
-    #[test]
-    fn test_validate_username_too_long() {
-        let long = "a".repeat(65);
-        assert_eq!(validate_username(&long), Err(UsernameError::TooLong));
-    }
-
-    #[test]
-    fn test_validate_username_max_valid_length() {
-        let max = "a".repeat(64);
-        assert!(validate_username(&max).is_ok());
-    }
-
-    #[test]
-    fn test_validate_username_invalid_chars() {
-        assert_eq!(
-            validate_username("al!ce"),
-            Err(UsernameError::InvalidCharacters)
-        );
-    }
-
-    #[test]
-    fn test_validate_username_unicode_rejected() {
-        assert_eq!(
-            validate_username("álice"),
-            Err(UsernameError::InvalidCharacters)
-        );
-    }
-
-    #[test]
-    fn test_validate_username_spaces_rejected() {
-        assert_eq!(
-            validate_username("al ice"),
-            Err(UsernameError::InvalidCharacters)
-        );
-    }
-
-    #[test]
-    fn test_validate_username_hyphens_underscores_valid() {
-        assert!(validate_username("a-b_c").is_ok());
-    }
-
-    #[test]
-    fn test_validate_username_reserved() {
-        assert_eq!(validate_username("admin"), Err(UsernameError::Reserved));
-    }
-
-    #[test]
-    fn test_validate_username_reserved_case_insensitive() {
-        assert_eq!(validate_username("Admin"), Err(UsernameError::Reserved));
-        assert_eq!(validate_username("ROOT"), Err(UsernameError::Reserved));
-    }
-
     // ── DeviceName validation (direct function tests) ─────────────────────
 
     #[test]
@@ -835,7 +842,7 @@ mod tests {
     async fn test_signup_whitespace_only_username() {
         // The service trims the username before validating. A whitespace-only
         // username should produce "empty" (not "invalid characters") because
-        // trim collapses it to "" before validate_username runs.
+        // trim collapses it to "" before validation runs.
         let svc = service_with_mock_repo();
         let mut req = valid_signup_request();
         req.username = "   ".to_string();
@@ -930,6 +937,18 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_signup_malformed_capability() {
+        let svc = service_with_mock_repo();
+        let mut req = valid_signup_request();
+        req.device.capabilities = vec!["sig-v2".to_string(), "cert2".to_string()];
+        let err = svc.signup(&req).await.unwrap_err();
+        match &err {
+            SignupError::Validation(msg) => assert!(msg.contains("capability")),
+            other => panic!("expected Validation, got: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_signup_valid_request_succeeds() {
         let svc = service_with_mock_repo();
@@ -938,6 +957,57 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_signup_uses_configured_username_policy() {
+        let mut req = valid_signup_request();
+        req.username = "jane.doe".to_string();
+        let err = service_with_mock_repo().signup(&req).await.unwrap_err();
+        assert!(matches!(err, SignupError::Validation(_)));
+
+        let dots = UsernamePolicy::new(crate::config::UsernamePack::AsciiDots, 3, 64, &[]);
+        let svc = service_with_mock_repo().with_username_policy(Arc::new(dots));
+        assert!(svc.signup(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_invite_only_requires_code() {
+        let svc = service_with_mock_repo().with_invite_only(true);
+        let mut req = valid_signup_request();
+        let err = svc.signup(&req).await.unwrap_err();
+        assert!(matches!(err, SignupError::InviteRequired));
+
+        req.invite_code = Some("  ".to_string());
+        let err = svc.signup(&req).await.unwrap_err();
+        assert!(matches!(err, SignupError::InviteRequired));
+
+        req.invite_code = Some(generate_invite_code());
+        assert!(svc.signup(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_invite_maps_correctly() {
+        let repo = MockIdentityRepo::new();
+        repo.set_signup_result(Err(CreateSignupError::Invite(
+            SignupInviteRepoError::InvalidCode,
+        )));
+        let svc = DefaultIdentityService::new(Arc::new(repo)).with_invite_only(true);
+        let mut req = valid_signup_request();
+        req.invite_code = Some(generate_invite_code());
+        let err = svc.signup(&req).await.unwrap_err();
+        assert!(matches!(err, SignupError::InvalidInvite));
+    }
+
+    #[test]
+    fn test_invite_codes_are_prefixed_and_hashed_trimmed() {
+        let code = generate_invite_code();
+        assert!(code.starts_with(INVITE_CODE_PREFIX));
+        assert_ne!(code, generate_invite_code());
+        assert_eq!(
+            hash_invite_code(&code),
+            hash_invite_code(&format!(" {code}\n"))
+        );
+    }
+
     // ── Error mapping from repo → service ──────────────────────────────────
 
     #[tokio::test]
@@ -1112,4 +1182,26 @@ mod tests {
             other => panic!("expected Internal, got: {other:?}"),
         }
     }
+
+    #[test]
+    fn test_device_certificate_message_matches_vectors() {
+        use tc_crypto::{decode_base64url, test_vectors, verify_ed25519};
+
+        let root = test_vectors::keypair("root").verifying_key().to_bytes();
+        for vector in test_vectors::corpus()["certificates"]
+            .as_array()
+            .expect("certificates")
+        {
+            let subject = test_vectors::keypair(vector["subject"].as_str().expect("subject"))
+                .verifying_key()
+                .to_bytes();
+            let message = device_certificate_message(&subject, vector["timestamp"].as_i64());
+            assert_eq!(encode_base64url(&message), vector["message"]);
+
+            let signature = decode_base64url(vector["signature"].as_str().expect("signature"))
+                .expect("base64url");
+            let signature: [u8; 64] = signature.try_into().expect("64 bytes");
+            assert!(verify_ed25519(&root, &message, &signature).is_ok());
+        }
+    }
 }
@@ -0,0 +1,299 @@
+//! Per-account sigchain: a hash-linked log of signed account events.
+//!
+//! Every change to an account's device set — adding, renaming, revoking, or
+//! rotating a device — every username change, and deactivating the account
+//! appends a [`SigchainLink`] holding the device's signed request (a
+//! [`SignedRequest`] serialized as JSON). Links are numbered from 1 and each one commits to its predecessor:
+//!
+//! ```text
+//! hash = SHA-256("tc-sigchain-v1" || 0x00 || seqno (u64 BE)
+//!                || prev_hash (32 zero bytes for seqno 1)
+//!                || link_type || 0x00 || envelope)
+//! ```
+//!
+//! `envelope` is hashed as the exact text served, so a client can recompute
+//! every hash with [`verify_links`], check each envelope's signature with
+//! [`SignedRequest::verify`] against its device keys, and compare the last
+//! hash with `GET /auth/accounts/{id}/sigchain/head` to detect a rewritten or
+//! truncated history.
+//!
+//! The one exception is `account_reactivated`: a deactivated account has no
+//! devices left, so undoing the deletion is signed by the root key alone.
+//! Its envelope is the undelete request, signed by `signer_kid` (the root
+//! KID) over [`super::service::account_undeletion_message`].
+//!
+//! A link is appended in the same transaction as the change it records
+//! (see [`NewSigchainLink`]). If the append fails the change rolls back and
+//! the request fails, so the chain holds every event and only events that
+//! happened.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tc_crypto::{decode_base64url, encode_base64url, Kid};
+use utoipa::ToSchema;
+
+use super::repo::SigchainLinkRecord;
+use super::signed_request::SignedRequest;
+
+/// Domain separator hashed into every link.
+pub const SIGCHAIN_HASH_DOMAIN: &[u8] = b"tc-sigchain-v1";
+
+/// What a link records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigchainLinkType {
+    DeviceAdded,
+    DeviceRenamed,
+    DeviceRevoked,
+    DeviceRotated,
+    UsernameChanged,
+    AccountDeactivated,
+    AccountReactivated,
+}
+
+impl SigchainLinkType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::DeviceAdded => "device_added",
+            Self::DeviceRenamed => "device_renamed",
+            Self::DeviceRevoked => "device_revoked",
+            Self::DeviceRotated => "device_rotated",
+            Self::UsernameChanged => "username_changed",
+            Self::AccountDeactivated => "account_deactivated",
+            Self::AccountReactivated => "account_reactivated",
+        }
+    }
+}
+
+/// Hash of a link; see the module docs for the layout.
+#[must_use]
+pub fn link_hash(
+    seqno: i64,
+    prev_hash: Option<&[u8]>,
+    link_type: &str,
+    envelope: &str,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SIGCHAIN_HASH_DOMAIN);
+    hasher.update([0]);
+    hasher.update(seqno.to_be_bytes());
+    hasher.update(prev_hash.unwrap_or(&[0; 32]));
+    hasher.update(link_type.as_bytes());
+    hasher.update([0]);
+    hasher.update(envelope.as_bytes());
+    hasher.finalize().into()
+}
+
+// ─── Wire format ───────────────────────────────────────────────────────────
+
+/// One link of an account's sigchain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SigchainLink {
+    pub seqno: i64,
+    pub link_type: String,
+    #[schema(value_type = String)]
+    pub signer_kid: Kid,
+    /// JSON text of the signer's signed request, hashed byte for byte
+    pub envelope: String,
+    /// Base64url hash of the previous link; absent for seqno 1
+    pub prev_hash: Option<String>,
+    /// Base64url link hash
+    pub hash: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SigchainLinkRecord> for SigchainLink {
+    fn from(record: SigchainLinkRecord) -> Self {
+        Self {
+            seqno: record.seqno,
+            link_type: record.link_type,
+            signer_kid: record.signer_kid,
+            envelope: record.envelope,
+            prev_hash: record.prev_hash.as_deref().map(encode_base64url),
+            hash: encode_base64url(&record.hash),
+            created_at: record.created_at,
+        }
+    }
+}
+
+// ─── Verification ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SigchainError {
+    #[error("expected seqno {expected}, found {found}")]
+    Gap { expected: i64, found: i64 },
+    #[error("link {seqno} does not point at the previous link")]
+    BrokenLink { seqno: i64 },
+    #[error("link {seqno} hash does not match its contents")]
+    HashMismatch { seqno: i64 },
+}
+
+/// Check that `links` continue the chain after `prev`, or start it.
+///
+/// Seqnos must be consecutive, each `prev_hash` must equal the previous
+/// link's hash, and every hash must match the link's contents.
+///
+/// Envelope signatures are not checked here; that needs the account's
+/// device keys.
+///
+/// # Errors
+///
+/// Returns the first inconsistency found.
+pub fn verify_links(
+    prev: Option<&SigchainLink>,
+    links: &[SigchainLink],
+) -> Result<(), SigchainError> {
+    let first_seqno = prev.map_or(1, |p| p.seqno + 1);
+    let mut expected_prev = prev.map(|p| p.hash.clone());
+    for (expected_seqno, link) in (first_seqno..).zip(links) {
+        if link.seqno != expected_seqno {
+            return Err(SigchainError::Gap {
+                expected: expected_seqno,
+                found: link.seqno,
+            });
+        }
+        if link.prev_hash != expected_prev {
+            return Err(SigchainError::BrokenLink { seqno: link.seqno });
+        }
+        let prev_bytes = match link.prev_hash.as_deref().map(decode_base64url) {
+            None => None,
+            Some(Ok(bytes)) => Some(bytes),
+            Some(Err(_)) => return Err(SigchainError::BrokenLink { seqno: link.seqno }),
+        };
+        let hash = link_hash(
+            link.seqno,
+            prev_bytes.as_deref(),
+            &link.link_type,
+            &link.envelope,
+        );
+        if encode_base64url(&hash) != link.hash {
+            return Err(SigchainError::HashMismatch { seqno: link.seqno });
+        }
+        expected_prev = Some(link.hash.clone());
+    }
+    Ok(())
+}
+
+// ─── Appending ─────────────────────────────────────────────────────────────
+
+/// A link to append alongside the change it records.
+///
+/// Repository methods that change an account's devices, username or status
+/// take one and append it in their transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewSigchainLink {
+    pub link_type: SigchainLinkType,
+    pub signer_kid: Kid,
+    /// JSON text stored and hashed as the link's envelope
+    pub envelope: String,
+}
+
+impl NewSigchainLink {
+    /// A link whose envelope is the device's signed `request`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serialization failure.
+    pub fn signed(
+        link_type: SigchainLinkType,
+        request: &SignedRequest,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            link_type,
+            signer_kid: request.device_kid.clone(),
+            envelope: serde_json::to_string(request)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use crate::identity::repo::IdentityRepo;
+    use uuid::Uuid;
+
+    fn request(nonce: &str) -> SignedRequest {
+        SignedRequest {
+            alg: None,
+            method: "POST".to_string(),
+            host: "api.example".to_string(),
+            path_and_query: "/auth/devices".to_string(),
+            timestamp: 1_700_000_000,
+            nonce: nonce.to_string(),
+            content_type: "application/json".to_string(),
+            device_kid: Kid::derive(&[3u8; 32]),
+            challenge: None,
+            body: String::new(),
+            signature: String::new(),
+        }
+    }
+
+    async fn chain(len: usize) -> Vec<SigchainLink> {
+        let repo = MockIdentityRepo::new();
+        let account_id = Uuid::new_v4();
+        for i in 0..len {
+            let link = NewSigchainLink::signed(
+                SigchainLinkType::DeviceAdded,
+                &request(&format!("nonce-{i}")),
+            )
+            .expect("json");
+            repo.push_sigchain_link(account_id, &link);
+        }
+        repo.list_sigchain_links(account_id, 0, 100)
+            .await
+            .expect("list")
+            .into_iter()
+            .map(SigchainLink::from)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn appended_links_form_a_verifiable_chain() {
+        let links = chain(3).await;
+        assert_eq!(links.iter().map(|l| l.seqno).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(links[0].prev_hash, None);
+        assert_eq!(verify_links(None, &links), Ok(()));
+        // A later page verifies against the last link of the previous one
+        assert_eq!(verify_links(Some(&links[0]), &links[1..]), Ok(()));
+        let envelope: SignedRequest = serde_json::from_str(&links[2].envelope).expect("json");
+        assert_eq!(envelope.nonce, "nonce-2");
+    }
+
+    #[tokio::test]
+    async fn tampering_is_detected() {
+        let links = chain(3).await;
+
+        let mut edited = links.clone();
+        edited[1].envelope = edited[1].envelope.replace("nonce-1", "nonce-9");
+        assert_eq!(
+            verify_links(None, &edited),
+            Err(SigchainError::HashMismatch { seqno: 2 })
+        );
+
+        let mut retyped = links.clone();
+        retyped[0].link_type = "device_revoked".to_string();
+        assert_eq!(
+            verify_links(None, &retyped),
+            Err(SigchainError::HashMismatch { seqno: 1 })
+        );
+
+        let dropped = [links[0].clone(), links[2].clone()];
+        assert_eq!(
+            verify_links(None, &dropped),
+            Err(SigchainError::Gap {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let mut relinked = links;
+        relinked[2].prev_hash = relinked[0].prev_hash.clone();
+        assert_eq!(
+            verify_links(None, &relinked),
+            Err(SigchainError::BrokenLink { seqno: 3 })
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Device-signed requests kept as evidence
+//!
+//! Handlers that record what a device asked for — sigchain links, CRLs,
+//! endorsement envelopes — store the request as a [`SignedRequest`]: the
+//! inputs to the canonical message defined in [`tc_crypto::RequestParts`]
+//! plus the device's signature. Anyone holding the device's public key can
+//! re-check it with [`SignedRequest::verify`]. The HTTP extractor that builds
+//! one is [`super::http::auth::AuthenticatedDevice`].
+
+use serde::{Deserialize, Serialize};
+use tc_crypto::{decode_base64url, verify_ed25519, BodyHash, Kid, RequestParts, SignatureAlg};
+
+use super::service::DevicePubkey;
+
+/// A device-signed request, stored so anyone holding the device's public key
+/// can later check that the body is exactly what the device sent.
+///
+/// The server never signs anything here: it only keeps the inputs to the
+/// canonical message and the client's signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRequest {
+    /// `X-Signature-Alg`; absent for a v1 signature.
+    pub alg: Option<String>,
+    pub method: String,
+    pub host: String,
+    pub path_and_query: String,
+    pub timestamp: i64,
+    pub nonce: String,
+    pub content_type: String,
+    pub device_kid: Kid,
+    /// `X-Challenge`, if the request answered a server-issued challenge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+    /// base64url-encoded request body
+    pub body: String,
+    /// base64url-encoded Ed25519 signature
+    pub signature: String,
+}
+
+impl SignedRequest {
+    /// Decoded request body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `body` is not valid base64url.
+    pub fn body_bytes(&self) -> Result<Vec<u8>, tc_crypto::DecodeError> {
+        decode_base64url(&self.body)
+    }
+
+    /// Whether `signature` verifies against `pubkey` over the canonical
+    /// message rebuilt from these fields.
+    #[must_use]
+    pub fn verify(&self, pubkey: &DevicePubkey) -> bool {
+        let alg = match self.alg.as_deref().map(str::parse::<SignatureAlg>) {
+            None => None,
+            Some(Ok(alg)) => Some(alg),
+            Some(Err(_)) => return false,
+        };
+        let (Ok(body), Ok(sig)) = (self.body_bytes(), decode_base64url(&self.signature)) else {
+            return false;
+        };
+        let Ok(sig) = <[u8; 64]>::try_from(sig.as_slice()) else {
+            return false;
+        };
+        let device_kid = self.device_kid.to_string();
+        let canonical = canonical_message(
+            alg,
+            &RequestParts {
+                method: &self.method,
+                host: &self.host,
+                path_and_query: &self.path_and_query,
+                timestamp: self.timestamp,
+                nonce: &self.nonce,
+                content_type: &self.content_type,
+                device_kid: &device_kid,
+                challenge: self.challenge.as_deref().unwrap_or_default(),
+                body: &body,
+            },
+            &BodyHash::of(&body),
+        );
+        verify_ed25519(pubkey.as_bytes(), canonical.as_bytes(), &sig).is_ok()
+    }
+}
+
+/// Build the message a device signs: v2 for `Some(alg)`, v1 otherwise.
+pub(crate) fn canonical_message(
+    alg: Option<SignatureAlg>,
+    parts: &RequestParts<'_>,
+    body_hash: &BodyHash,
+) -> String {
+    alg.map_or_else(
+        || parts.canonical_v1_prehashed(body_hash),
+        |alg| parts.canonical_v2_prehashed(alg, body_hash),
+    )
+}
@@ -0,0 +1,256 @@
+//! Account lifecycle status and the transitions between states.
+//!
+//! | From            | To              | Who                    |
+//! |-----------------|-----------------|------------------------|
+//! | `active`        | `locked`        | the owner              |
+//! | `active`        | `suspended`     | an account admin       |
+//! | `active`        | `deleted`       | the owner or an admin  |
+//! | `locked`        | `active`        | an account admin       |
+//! | `suspended`     | `active`        | an account admin       |
+//! | `locked`        | `deleted`       | an account admin       |
+//! | `suspended`     | `deleted`       | an account admin       |
+//! | `active`        | `deactivated`   | the owner's root key   |
+//! | `deactivated`   | `active`        | the owner's root key   |
+//! | `deactivated`   | `deleted`       | an account admin       |
+//!
+//! `locked` is the owner's emergency stop, e.g. after losing a device: every
+//! device and API token stops working, and since the owner can no longer
+//! sign anything, only an admin can restore it. `deleted` is terminal; rows
+//! are kept for the audit trail and the retention policy.
+//!
+//! `deactivated` is a self-service deletion in its grace period; see
+//! [`super::deletion`]. Only the root key can request or undo it, because it
+//! revokes every device.
+//!
+//! Only `active` accounts may authenticate. The signed-request and API token
+//! extractors and login refuse every other status through
+//! [`super::http::require_active_account`], so handlers never check status
+//! themselves. Every change is recorded in `account_status_changes`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::identity::repo::{AccountRepoError, IdentityRepo};
+
+/// Stored in `accounts.status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountStatus {
+    #[default]
+    Active,
+    Suspended,
+    Locked,
+    Deleted,
+    Deactivated,
+}
+
+impl AccountStatus {
+    /// Every status.
+    pub const ALL: [Self; 5] = [
+        Self::Active,
+        Self::Suspended,
+        Self::Locked,
+        Self::Deleted,
+        Self::Deactivated,
+    ];
+
+    /// Value stored in `accounts.status`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Suspended => "suspended",
+            Self::Locked => "locked",
+            Self::Deleted => "deleted",
+            Self::Deactivated => "deactivated",
+        }
+    }
+
+    /// Whether `actor` may move an account from `self` to `to`.
+    #[must_use]
+    pub const fn can_transition(self, to: Self, actor: StatusActor) -> bool {
+        matches!(
+            (self, to, actor),
+            (
+                Self::Active,
+                Self::Locked | Self::Deleted,
+                StatusActor::Owner
+            ) | (
+                Self::Active,
+                Self::Suspended | Self::Deleted,
+                StatusActor::Admin
+            ) | (
+                Self::Locked | Self::Suspended,
+                Self::Active | Self::Deleted,
+                StatusActor::Admin
+            ) | (Self::Active, Self::Deactivated, StatusActor::Root)
+                | (Self::Deactivated, Self::Active, StatusActor::Root)
+                | (Self::Deactivated, Self::Deleted, StatusActor::Admin)
+        )
+    }
+}
+
+impl fmt::Display for AccountStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown account status.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown account status")]
+pub struct UnknownAccountStatus;
+
+impl FromStr for AccountStatus {
+    type Err = UnknownAccountStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownAccountStatus)
+    }
+}
+
+/// Who is asking for a status change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusActor {
+    /// A device of the account being changed.
+    Owner,
+    /// An account listed in `accounts.admin_account_ids`.
+    Admin,
+    /// The account's root key.
+    Root,
+}
+
+/// A status change that was applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    pub account_id: Uuid,
+    pub previous: AccountStatus,
+    pub status: AccountStatus,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Why a status change was refused.
+#[derive(Debug, thiserror::Error)]
+pub enum StatusChangeError {
+    #[error("account not found")]
+    NotFound,
+    #[error("cannot change account status from {from} to {to}")]
+    NotAllowed {
+        from: AccountStatus,
+        to: AccountStatus,
+    },
+    #[error("account status changed concurrently")]
+    Conflict,
+    #[error("database error: {0}")]
+    Repo(AccountRepoError),
+}
+
+/// Move `account_id` to `to` on behalf of `actor`, recording who did it.
+///
+/// The update only applies if the status is still the one the transition was
+/// checked against, so two concurrent changes cannot both succeed.
+///
+/// # Errors
+///
+/// [`StatusChangeError::NotAllowed`] if the state machine forbids the
+/// transition for `actor`, [`StatusChangeError::Conflict`] if the status
+/// changed in the meantime.
+pub async fn change_account_status(
+    repo: &dyn IdentityRepo,
+    account_id: Uuid,
+    to: AccountStatus,
+    actor: StatusActor,
+    actor_account_id: Uuid,
+    reason: Option<&str>,
+) -> Result<StatusChange, StatusChangeError> {
+    let from = match repo.get_account_status(account_id).await {
+        Ok(status) => status,
+        Err(AccountRepoError::NotFound) => return Err(StatusChangeError::NotFound),
+        Err(e) => return Err(StatusChangeError::Repo(e)),
+    };
+    if !from.can_transition(to, actor) {
+        return Err(StatusChangeError::NotAllowed { from, to });
+    }
+    let changed_at = repo
+        .update_account_status(account_id, from, to, actor_account_id, reason)
+        .await
+        .map_err(StatusChangeError::Repo)?
+        .ok_or(StatusChangeError::Conflict)?;
+    metrics::counter!("tc_account_status_changes_total", "status" => to.as_str()).increment(1);
+    tracing::info!(%account_id, %from, %to, actor = ?actor, "Account status changed");
+    Ok(StatusChange {
+        account_id,
+        previous: from,
+        status: to,
+        changed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+
+    #[test]
+    fn transitions_follow_the_table() {
+        use AccountStatus::{Active, Deactivated, Deleted, Locked, Suspended};
+        use StatusActor::{Admin, Owner, Root};
+
+        assert!(Active.can_transition(Locked, Owner));
+        assert!(Active.can_transition(Deleted, Owner));
+        assert!(!Active.can_transition(Suspended, Owner));
+        assert!(!Locked.can_transition(Active, Owner));
+
+        assert!(Active.can_transition(Suspended, Admin));
+        assert!(Suspended.can_transition(Active, Admin));
+        assert!(Locked.can_transition(Active, Admin));
+        assert!(!Active.can_transition(Locked, Admin));
+        assert!(!Active.can_transition(Active, Admin));
+
+        assert!(Active.can_transition(Deactivated, Root));
+        assert!(Deactivated.can_transition(Active, Root));
+        assert!(Deactivated.can_transition(Deleted, Admin));
+        assert!(!Active.can_transition(Deactivated, Owner));
+        assert!(!Deactivated.can_transition(Active, Admin));
+
+        for to in AccountStatus::ALL {
+            assert!(!Deleted.can_transition(to, Admin));
+            assert!(!Deleted.can_transition(to, Owner));
+            assert!(!Deleted.can_transition(to, Root));
+        }
+    }
+
+    #[test]
+    fn status_round_trips_through_str() {
+        for status in AccountStatus::ALL {
+            assert_eq!(status.as_str().parse::<AccountStatus>().ok(), Some(status));
+        }
+        assert!("banned".parse::<AccountStatus>().is_err());
+    }
+
+    #[tokio::test]
+    async fn refused_transition_does_not_write() {
+        let repo = MockIdentityRepo::new();
+        repo.set_account_status(AccountStatus::Suspended);
+        let err = change_account_status(
+            &repo,
+            Uuid::new_v4(),
+            AccountStatus::Locked,
+            StatusActor::Owner,
+            Uuid::new_v4(),
+            None,
+        )
+        .await
+        .expect_err("owner cannot lock a suspended account");
+        assert!(matches!(err, StatusChangeError::NotAllowed { .. }));
+        assert_eq!(repo.account_status(), AccountStatus::Suspended);
+    }
+}
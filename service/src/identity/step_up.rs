@@ -0,0 +1,192 @@
+//! Step-up confirmation of high-risk actions by SMS.
+//!
+//! An account may enroll a phone number by confirming a code texted to it.
+//! Once enrolled, [`step_up_required`] decides which requests must also
+//! present a confirmed challenge:
+//!
+//! | Action          | Step-up required when                              |
+//! |-----------------|----------------------------------------------------|
+//! | `revoke_device` | the requesting device is newer than `new_device_hours` |
+//! | `remove_phone`  | always                                             |
+//!
+//! A stolen device can sign requests, but a device added after the theft
+//! cannot evict the owner's other devices, or drop the phone check, without
+//! the phone.
+//!
+//! The client answers a `STEP_UP_REQUIRED` 403 by creating a challenge for
+//! the action (`POST /auth/step-up/challenges`), confirming the texted code,
+//! and retrying with the challenge ID in [`STEP_UP_HEADER`]. A challenge is
+//! bound to the device that created it and is consumed by the retry.
+//!
+//! Step-up needs an SMS provider; with SMS disabled no phone can be
+//! enrolled and nothing is gated.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::StepUpConfig;
+
+/// Header carrying a confirmed challenge ID when retrying a gated request.
+pub const STEP_UP_HEADER: &str = "x-step-up-challenge";
+
+/// Error code returned with the 403 when a request needs step-up.
+pub const STEP_UP_REQUIRED_CODE: &str = "STEP_UP_REQUIRED";
+
+/// What a step-up challenge confirms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StepUpAction {
+    /// Enrolling the phone the code was sent to
+    EnrollPhone,
+    /// `DELETE /auth/devices/{kid}`
+    RevokeDevice,
+    /// `DELETE /auth/step-up/phone`
+    RemovePhone,
+}
+
+impl StepUpAction {
+    pub const ALL: [Self; 3] = [Self::EnrollPhone, Self::RevokeDevice, Self::RemovePhone];
+
+    /// Value stored in `step_up_challenges.action`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::EnrollPhone => "enroll_phone",
+            Self::RevokeDevice => "revoke_device",
+            Self::RemovePhone => "remove_phone",
+        }
+    }
+
+    /// Wording for the texted message.
+    #[must_use]
+    pub const fn describe(self) -> &'static str {
+        match self {
+            Self::EnrollPhone => "add this phone to your account",
+            Self::RevokeDevice => "remove a device from your account",
+            Self::RemovePhone => "remove this phone from your account",
+        }
+    }
+}
+
+impl fmt::Display for StepUpAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown step-up action.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown step-up action")]
+pub struct UnknownStepUpAction;
+
+impl FromStr for StepUpAction {
+    type Err = UnknownStepUpAction;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.as_str() == s)
+            .ok_or(UnknownStepUpAction)
+    }
+}
+
+/// What the risk check knows about a request.
+#[derive(Debug, Clone, Copy)]
+pub struct RiskContext {
+    /// Whether the account has an enrolled phone
+    pub phone_enrolled: bool,
+    /// When the requesting device was added
+    pub device_created_at: DateTime<Utc>,
+    pub now: DateTime<Utc>,
+}
+
+/// Whether `action` needs a confirmed step-up challenge; see the module docs.
+#[must_use]
+pub fn step_up_required(action: StepUpAction, ctx: &RiskContext, config: &StepUpConfig) -> bool {
+    if !ctx.phone_enrolled {
+        return false;
+    }
+    match action {
+        StepUpAction::EnrollPhone => false,
+        StepUpAction::RevokeDevice => {
+            ctx.now - ctx.device_created_at < Duration::hours(i64::from(config.new_device_hours))
+        }
+        StepUpAction::RemovePhone => true,
+    }
+}
+
+/// Hash a texted code for storage, bound to its challenge.
+#[must_use]
+pub fn hash_step_up_code(challenge_id: Uuid, code: &str) -> Vec<u8> {
+    Sha256::digest(format!("{challenge_id}:{}", code.trim()).as_bytes()).to_vec()
+}
+
+/// Phone number with all but the last four digits masked, e.g. `•••4567`.
+#[must_use]
+pub fn phone_hint(phone_number: &str) -> String {
+    let digits: Vec<char> = phone_number.chars().filter(char::is_ascii_digit).collect();
+    let last: String = digits[digits.len().saturating_sub(4)..].iter().collect();
+    format!("•••{last}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(phone_enrolled: bool, device_age_hours: i64) -> RiskContext {
+        let now = Utc::now();
+        RiskContext {
+            phone_enrolled,
+            device_created_at: now - Duration::hours(device_age_hours),
+            now,
+        }
+    }
+
+    #[test]
+    fn actions_round_trip() {
+        for action in StepUpAction::ALL {
+            assert_eq!(action.as_str().parse::<StepUpAction>().ok(), Some(action));
+        }
+        assert!("rotate_root".parse::<StepUpAction>().is_err());
+    }
+
+    #[test]
+    fn new_devices_need_step_up_to_revoke() {
+        let config = StepUpConfig::default();
+        assert!(step_up_required(
+            StepUpAction::RevokeDevice,
+            &ctx(true, 1),
+            &config
+        ));
+        assert!(!step_up_required(
+            StepUpAction::RevokeDevice,
+            &ctx(true, 24 * 30),
+            &config
+        ));
+        assert!(step_up_required(
+            StepUpAction::RemovePhone,
+            &ctx(true, 24 * 30),
+            &config
+        ));
+    }
+
+    #[test]
+    fn nothing_is_gated_without_a_phone() {
+        let config = StepUpConfig::default();
+        for action in StepUpAction::ALL {
+            assert!(!step_up_required(action, &ctx(false, 0), &config));
+        }
+    }
+
+    #[test]
+    fn phone_hint_keeps_last_four_digits() {
+        assert_eq!(phone_hint("+15551234567"), "•••4567");
+        assert_eq!(phone_hint("+12"), "•••12");
+    }
+}
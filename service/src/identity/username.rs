@@ -0,0 +1,482 @@
+//! Username policy
+//!
+//! Which usernames (and aliases) an instance accepts is a [`UsernamePolicy`]
+//! built once at startup from [`UsernameConfig`]. A policy is a pack — the
+//! allowed character set — plus length bounds and the reserved names.
+//!
+//! | Pack | Characters |
+//! |---|---|
+//! | `ascii` (default) | ASCII letters, digits, `-` and `_` |
+//! | `ascii_dots` | as `ascii`, plus `.` between other characters |
+//! | `unicode` | letters and digits of any script allowed in identifiers, `-` and `_` |
+//!
+//! Unicode names must be NFC-normalized and may not mix scripts beyond the
+//! usual CJK combinations (UTS #39 "highly restrictive"), which rules out
+//! homographs like a Cyrillic `а` inside a Latin name. Reserved names are
+//! matched by their confusable skeleton, so a look-alike spelled in another
+//! script is reserved too.
+//! Uniqueness is still by exact name: two names written entirely in
+//! different, look-alike scripts can both be registered.
+//!
+//! Reserved names live in the `reserved_usernames` table, managed by account
+//! admins through `/auth/reserved-usernames`. Each server keeps an in-memory
+//! copy, reloaded every minute and right after its own changes; names from
+//! `usernames.reserved_file` are always added on top.
+//!
+//! A username or alias that an account gives up stays unclaimable by other
+//! accounts for `usernames.release_cooldown_days`, so nobody can pick up a
+//! deleted account's name and pass as its former owner. The database records
+//! every release (see migration `52_released_handles.sql`); account admins
+//! can lift a quarantine early through `/auth/released-usernames`.
+//!
+//! An account may change its username once every
+//! `usernames.change_cooldown_days` (`PATCH /auth/accounts/me/username`);
+//! the old name is then released like any other. Clients can check a name
+//! with `GET /auth/usernames/{name}/availability` before signing up or
+//! changing to it.
+//!
+//! The policy only gates new names. Login, backup download and lookup use
+//! [`UsernamePolicy::check_handle`], which accepts any name that might exist,
+//! so tightening the pack never locks out existing accounts.
+
+use std::collections::HashSet;
+use std::io;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use chrono::Utc;
+use unicode_security::{GeneralSecurityProfile, RestrictionLevel, RestrictionLevelDetection};
+use uuid::Uuid;
+
+use crate::config::{UsernameConfig, UsernamePack};
+use crate::identity::repo::{IdentityRepo, ReservedUsernameRepoError};
+
+/// Default upper bound on username length, in characters.
+pub const MAX_USERNAME_LEN: usize = 64;
+
+/// Default days a released username or alias stays quarantined.
+pub const DEFAULT_RELEASE_COOLDOWN_DAYS: u32 = 90;
+
+/// Default days an account waits between username changes.
+pub const DEFAULT_CHANGE_COOLDOWN_DAYS: u32 = 30;
+
+/// Default lower bound on username length, in characters.
+pub const MIN_USERNAME_LEN: usize = 3;
+
+/// Hard cap on `usernames.max_length`.
+pub const USERNAME_LENGTH_LIMIT: usize = 255;
+
+/// How often each server reloads the reserved list from the database.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Reserved until the list is first loaded from the database; the migration
+/// that creates `reserved_usernames` seeds it with the same names.
+pub const DEFAULT_RESERVED_USERNAMES: &[&str] = &[
+    "admin",
+    "administrator",
+    "root",
+    "system",
+    "mod",
+    "moderator",
+    "support",
+    "help",
+    "api",
+    "graphql",
+    "auth",
+    "signup",
+    "login",
+    "null",
+    "undefined",
+    "anonymous",
+];
+
+/// Structured error type for username validation failures.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum UsernameError {
+    #[error("Username cannot be empty")]
+    Empty,
+    #[error("Username must be at least {0} characters")]
+    TooShort(usize),
+    #[error("Username too long")]
+    TooLong,
+    #[error("Username may only contain {0}")]
+    InvalidCharacters(&'static str),
+    #[error("Username cannot start or end with a dot or contain two dots in a row")]
+    MisplacedDot,
+    #[error("Username must be in Unicode normalization form C")]
+    NotNormalized,
+    #[error("Username cannot mix characters from different scripts")]
+    MixedScript,
+    #[error("This username is reserved")]
+    Reserved,
+}
+
+/// Username rules for this instance; see the module docs.
+#[derive(Debug)]
+pub struct UsernamePolicy {
+    pack: UsernamePack,
+    min_length: usize,
+    max_length: usize,
+    /// Names from `usernames.reserved_file`, reserved whatever the table says.
+    file_reserved: Vec<String>,
+    /// Lowercased reserved names, or their skeletons for the `unicode` pack.
+    reserved: RwLock<HashSet<String>>,
+    /// How long a released handle stays quarantined; zero disables it.
+    release_cooldown: chrono::Duration,
+    /// Minimum time between an account's username changes.
+    change_cooldown: chrono::Duration,
+}
+
+impl Default for UsernamePolicy {
+    fn default() -> Self {
+        Self::new(UsernamePack::Ascii, MIN_USERNAME_LEN, MAX_USERNAME_LEN, &[])
+    }
+}
+
+impl UsernamePolicy {
+    /// Build a policy; `extra_reserved` is always reserved, on top of
+    /// [`DEFAULT_RESERVED_USERNAMES`] until the first [`Self::reload_reserved`].
+    #[must_use]
+    pub fn new(
+        pack: UsernamePack,
+        min_length: usize,
+        max_length: usize,
+        extra_reserved: &[String],
+    ) -> Self {
+        let policy = Self {
+            pack,
+            min_length,
+            max_length,
+            file_reserved: extra_reserved.to_vec(),
+            reserved: RwLock::default(),
+            release_cooldown: chrono::Duration::days(i64::from(DEFAULT_RELEASE_COOLDOWN_DAYS)),
+            change_cooldown: chrono::Duration::days(i64::from(DEFAULT_CHANGE_COOLDOWN_DAYS)),
+        };
+        policy.set_reserved(DEFAULT_RESERVED_USERNAMES.iter().copied());
+        policy
+    }
+
+    /// Build the policy from config, reading `reserved_file` if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the reserved list cannot be read.
+    pub fn from_config(config: &UsernameConfig) -> io::Result<Self> {
+        let extra = match &config.reserved_file {
+            Some(path) => parse_reserved_list(&std::fs::read_to_string(path)?),
+            None => Vec::new(),
+        };
+        Ok(
+            Self::new(config.pack, config.min_length, config.max_length, &extra)
+                .with_release_cooldown_days(config.release_cooldown_days)
+                .with_change_cooldown_days(config.change_cooldown_days),
+        )
+    }
+
+    /// Quarantine released handles for `days` instead of the default.
+    #[must_use]
+    pub fn with_release_cooldown_days(mut self, days: u32) -> Self {
+        self.release_cooldown = chrono::Duration::days(i64::from(days));
+        self
+    }
+
+    /// Require `days` between username changes instead of the default.
+    #[must_use]
+    pub fn with_change_cooldown_days(mut self, days: u32) -> Self {
+        self.change_cooldown = chrono::Duration::days(i64::from(days));
+        self
+    }
+
+    /// Minimum time between an account's username changes.
+    #[must_use]
+    pub const fn change_cooldown(&self) -> chrono::Duration {
+        self.change_cooldown
+    }
+
+    /// Start of the current quarantine window: handles released since then
+    /// are still cooling off.
+    #[must_use]
+    pub fn release_cutoff(&self) -> chrono::DateTime<Utc> {
+        Utc::now() - self.release_cooldown
+    }
+
+    /// Whether `handle` was given up by an account other than `claimant`
+    /// too recently to be claimed. An account may always take back its own
+    /// handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns the repository error.
+    pub async fn is_cooling_off(
+        &self,
+        repo: &dyn IdentityRepo,
+        handle: &str,
+        claimant: Option<Uuid>,
+    ) -> Result<bool, ReservedUsernameRepoError> {
+        if self.release_cooldown.is_zero() {
+            return Ok(false);
+        }
+        Ok(repo.get_released_handle(handle).await?.is_some_and(|r| {
+            Some(r.account_id) != claimant && r.released_at >= self.release_cutoff()
+        }))
+    }
+
+    /// Validate a new username or alias.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UsernameError`] if the name is empty, too short/long,
+    /// contains characters the pack does not allow, or is reserved.
+    pub fn validate(&self, username: &str) -> Result<(), UsernameError> {
+        if username.is_empty() {
+            return Err(UsernameError::Empty);
+        }
+        let length = username.chars().count();
+        if length < self.min_length {
+            return Err(UsernameError::TooShort(self.min_length));
+        }
+        if length > self.max_length {
+            return Err(UsernameError::TooLong);
+        }
+        match self.pack {
+            UsernamePack::Ascii => check_ascii(username, false)?,
+            UsernamePack::AsciiDots => check_ascii(username, true)?,
+            UsernamePack::Unicode => check_unicode(username)?,
+        }
+        if self.is_reserved(username) {
+            return Err(UsernameError::Reserved);
+        }
+        Ok(())
+    }
+
+    fn is_reserved(&self, username: &str) -> bool {
+        self.reserved
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&reserved_key(self.pack, username))
+    }
+
+    /// Replace the reserved names with `names` plus the file's.
+    pub fn set_reserved<'a>(&self, names: impl Iterator<Item = &'a str>) {
+        let mut keys: HashSet<String> = names.map(|name| reserved_key(self.pack, name)).collect();
+        keys.extend(
+            self.file_reserved
+                .iter()
+                .map(|name| reserved_key(self.pack, name)),
+        );
+        *self
+            .reserved
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = keys;
+    }
+
+    /// Load the reserved names from the database, returning how many there are.
+    ///
+    /// # Errors
+    ///
+    /// Returns the repository error; the current list is kept.
+    pub async fn reload_reserved(
+        &self,
+        repo: &dyn IdentityRepo,
+    ) -> Result<usize, ReservedUsernameRepoError> {
+        let records = repo.list_reserved_usernames().await?;
+        self.set_reserved(records.iter().map(|r| r.name.as_str()));
+        Ok(records.len())
+    }
+
+    /// Reload the reserved names now and then every minute.
+    pub fn spawn_reloader(self: &Arc<Self>, repo: Arc<dyn IdentityRepo>) {
+        let policy = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = policy.reload_reserved(repo.as_ref()).await {
+                    tracing::warn!("Failed to reload reserved usernames: {e}");
+                }
+            }
+        });
+    }
+
+    /// Reject handles that cannot belong to any account before looking them
+    /// up. Deliberately looser than [`Self::validate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UsernameError`] if the handle is empty, longer than any
+    /// name the instance can hold, or contains control characters.
+    pub fn check_handle(&self, handle: &str) -> Result<(), UsernameError> {
+        if handle.is_empty() {
+            return Err(UsernameError::Empty);
+        }
+        if handle.chars().count() > self.max_length.max(MAX_USERNAME_LEN) {
+            return Err(UsernameError::TooLong);
+        }
+        if handle.chars().any(char::is_control) {
+            return Err(UsernameError::InvalidCharacters(self.pack.allowed()));
+        }
+        Ok(())
+    }
+}
+
+impl UsernamePack {
+    /// The allowed characters, for error messages.
+    const fn allowed(self) -> &'static str {
+        match self {
+            Self::Ascii | Self::Unicode => "letters, numbers, hyphens, and underscores",
+            Self::AsciiDots => "letters, numbers, dots, hyphens, and underscores",
+        }
+    }
+}
+
+/// Key a reserved name is stored and looked up under.
+fn reserved_key(pack: UsernamePack, name: &str) -> String {
+    match pack {
+        UsernamePack::Ascii | UsernamePack::AsciiDots => name.to_ascii_lowercase(),
+        UsernamePack::Unicode => unicode_security::skeleton(&name.to_lowercase()).collect(),
+    }
+}
+
+fn check_ascii(username: &str, dots: bool) -> Result<(), UsernameError> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-' || (dots && c == '.');
+    if !username.chars().all(allowed) {
+        let pack = if dots {
+            UsernamePack::AsciiDots
+        } else {
+            UsernamePack::Ascii
+        };
+        return Err(UsernameError::InvalidCharacters(pack.allowed()));
+    }
+    if dots && (username.starts_with('.') || username.ends_with('.') || username.contains("..")) {
+        return Err(UsernameError::MisplacedDot);
+    }
+    Ok(())
+}
+
+fn check_unicode(username: &str) -> Result<(), UsernameError> {
+    if !unicode_normalization::is_nfc(username) {
+        return Err(UsernameError::NotNormalized);
+    }
+    let allowed = |c: char| {
+        (c.is_alphanumeric() || c == '_' || c == '-')
+            && GeneralSecurityProfile::identifier_allowed(c)
+    };
+    if !username.chars().all(allowed) {
+        return Err(UsernameError::InvalidCharacters(
+            UsernamePack::Unicode.allowed(),
+        ));
+    }
+    if !username.check_restriction_level(RestrictionLevel::HighlyRestrictive) {
+        return Err(UsernameError::MixedScript);
+    }
+    Ok(())
+}
+
+/// One name per line; blank lines and `#` comments are skipped.
+fn parse_reserved_list(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::repo::mock::MockIdentityRepo;
+    use uuid::Uuid;
+
+    fn policy(pack: UsernamePack) -> UsernamePolicy {
+        let extra = ["council".to_string(), "ace".to_string()];
+        UsernamePolicy::new(pack, MIN_USERNAME_LEN, MAX_USERNAME_LEN, &extra)
+    }
+
+    #[test]
+    fn default_pack_keeps_original_rules() {
+        let p = UsernamePolicy::default();
+        assert_eq!(p.validate(""), Err(UsernameError::Empty));
+        assert_eq!(p.validate("ab"), Err(UsernameError::TooShort(3)));
+        assert!(p.validate("abc").is_ok());
+        assert!(p.validate(&"a".repeat(64)).is_ok());
+        assert_eq!(p.validate(&"a".repeat(65)), Err(UsernameError::TooLong));
+        assert!(p.validate("a-b_c").is_ok());
+        for bad in ["al!ce", "álice", "al ice", "a.b"] {
+            assert!(
+                matches!(p.validate(bad), Err(UsernameError::InvalidCharacters(_))),
+                "{bad} should be rejected"
+            );
+        }
+        assert_eq!(p.validate("admin"), Err(UsernameError::Reserved));
+        assert_eq!(p.validate("ROOT"), Err(UsernameError::Reserved));
+        assert_eq!(
+            UsernameError::InvalidCharacters(UsernamePack::Ascii.allowed()).to_string(),
+            "Username may only contain letters, numbers, hyphens, and underscores"
+        );
+    }
+
+    #[test]
+    fn dots_pack_allows_inner_dots() {
+        let p = policy(UsernamePack::AsciiDots);
+        assert!(p.validate("jane.doe").is_ok());
+        for bad in [".jane", "jane.", "jane..doe"] {
+            assert_eq!(p.validate(bad), Err(UsernameError::MisplacedDot), "{bad}");
+        }
+        assert_eq!(p.validate("Council"), Err(UsernameError::Reserved));
+    }
+
+    #[test]
+    fn unicode_pack_rejects_mixed_scripts_and_lookalikes() {
+        let p = policy(UsernamePack::Unicode);
+        assert!(p.validate("álice").is_ok());
+        assert!(p.validate("Дмитрий").is_ok());
+        assert!(p.validate("山田_taro").is_ok());
+        // "a" + combining acute is the NFD form of "á"
+        assert_eq!(
+            p.validate("a\u{301}lice"),
+            Err(UsernameError::NotNormalized)
+        );
+        // Cyrillic "а" and "с" among Latin letters
+        assert_eq!(p.validate("pаypal"), Err(UsernameError::MixedScript));
+        assert_eq!(p.validate("сouncil"), Err(UsernameError::MixedScript));
+        // All-Cyrillic look-alike of a reserved name
+        assert_eq!(p.validate("асе"), Err(UsernameError::Reserved));
+        assert!(matches!(
+            p.validate("al ice"),
+            Err(UsernameError::InvalidCharacters(_))
+        ));
+    }
+
+    #[test]
+    fn handles_are_checked_loosely() {
+        let p = UsernamePolicy::default();
+        assert!(p.check_handle("al.ice").is_ok());
+        assert!(p.check_handle("admin").is_ok());
+        assert_eq!(p.check_handle(""), Err(UsernameError::Empty));
+        assert_eq!(p.check_handle(&"a".repeat(65)), Err(UsernameError::TooLong));
+        assert!(p.check_handle("a\nb").is_err());
+    }
+
+    #[tokio::test]
+    async fn reload_replaces_defaults_but_keeps_file_names() {
+        let repo = MockIdentityRepo::new();
+        repo.create_reserved_username("ceo", None, Uuid::new_v4())
+            .await
+            .expect("reserve");
+        let p = policy(UsernamePack::Ascii);
+        assert!(p.validate("ceo").is_ok());
+
+        assert_eq!(p.reload_reserved(&repo).await.expect("reload"), 1);
+        assert_eq!(p.validate("CEO"), Err(UsernameError::Reserved));
+        assert_eq!(p.validate("council"), Err(UsernameError::Reserved));
+        assert!(p.validate("admin").is_ok());
+    }
+
+    #[test]
+    fn reserved_list_skips_comments_and_blanks() {
+        let names = parse_reserved_list("# staff\nsecretary\n\n  clerk  # front desk\n");
+        assert_eq!(names, ["secretary", "clerk"]);
+    }
+}
@@ -0,0 +1,207 @@
+//! Public instance discovery document at `/.well-known/tinycongress.json`.
+//!
+//! Describes this deployment so clients and other instances can find its
+//! endpoints and policies without out-of-band configuration. The document is
+//! built once at startup from [`Config`] and [`BuildInfo`].
+//!
+//! The server holds no signing keys of its own (keys are generated and used
+//! in the browser only), so the only public keys listed are those of the
+//! platform verifiers configured in `TC_VERIFIERS`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::Extension,
+    http::{header::CACHE_CONTROL, Method},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use tower_http::cors::{Any, CorsLayer};
+use utoipa::ToSchema;
+
+use crate::build_info::BuildInfo;
+use crate::config::Config;
+use crate::identity::capabilities::server_caps;
+
+/// Path of the discovery document.
+pub const WELL_KNOWN_PATH: &str = "/.well-known/tinycongress.json";
+
+/// Version of the document layout. Bump on breaking changes.
+pub const DOCUMENT_VERSION: u32 = 1;
+
+/// REST API versions served by this build.
+const API_VERSIONS: [&str; 1] = ["v1"];
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InstanceDocument {
+    pub document_version: u32,
+    pub name: String,
+    /// Person or organization running the instance
+    pub operator: Option<String>,
+    pub software: SoftwareInfo,
+    /// Supported REST API versions, e.g. `v1` for `/api/v1`
+    pub api_versions: Vec<String>,
+    /// `open` or `invite_only`
+    pub signup_policy: String,
+    /// Envelope versions accepted from clients, e.g. `sig-v2`; declare yours
+    /// in `TC-Client-Caps`
+    pub envelope_versions: Vec<String>,
+    pub endpoints: InstanceEndpoints,
+    /// Root keys of the platform verifiers this instance trusts
+    pub verifier_keys: Vec<VerifierKey>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SoftwareInfo {
+    pub name: String,
+    pub version: String,
+}
+
+/// Paths relative to the instance origin.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InstanceEndpoints {
+    pub rest: String,
+    pub graphql: String,
+    pub verify: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VerifierKey {
+    pub name: String,
+    /// Base64url Ed25519 public key
+    pub public_key: String,
+}
+
+impl InstanceDocument {
+    #[must_use]
+    pub fn from_config(config: &Config, build_info: &BuildInfo) -> Self {
+        let operator = config.instance.operator.trim();
+        Self {
+            document_version: DOCUMENT_VERSION,
+            name: config.instance.name.clone(),
+            operator: (!operator.is_empty()).then(|| operator.to_string()),
+            software: SoftwareInfo {
+                name: "tinycongress".to_string(),
+                version: build_info.version.clone(),
+            },
+            api_versions: API_VERSIONS.iter().map(ToString::to_string).collect(),
+            signup_policy: if config.signup.invite_only {
+                "invite_only"
+            } else {
+                "open"
+            }
+            .to_string(),
+            envelope_versions: server_caps(config.auth.accept_v1_signatures),
+            endpoints: InstanceEndpoints {
+                rest: "/api/v1".to_string(),
+                graphql: "/graphql".to_string(),
+                verify: "/api/v1/verify".to_string(),
+            },
+            verifier_keys: config
+                .verifiers
+                .iter()
+                .map(|v| VerifierKey {
+                    name: v.name.clone(),
+                    public_key: v.public_key.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Discovery routes. Readable from any origin, like `/api/v1/verify`, so the
+/// router carries its own CORS layer and is merged outside the global one.
+///
+/// Expects `Extension<Arc<InstanceDocument>>`.
+pub fn router() -> Router {
+    Router::new()
+        .route(WELL_KNOWN_PATH, get(get_instance_document))
+        .layer(
+            CorsLayer::new()
+                .allow_methods([Method::GET])
+                .allow_headers(Any)
+                .allow_origin(Any)
+                .max_age(Duration::from_secs(3600)),
+        )
+}
+
+/// Describe this instance: name, operator, endpoints, and policies.
+#[utoipa::path(
+    get,
+    path = "/.well-known/tinycongress.json",
+    tag = "System",
+    responses(
+        (status = 200, description = "Instance discovery document", body = InstanceDocument)
+    )
+)]
+#[allow(clippy::unused_async)] // Required for Axum handler signature
+pub async fn get_instance_document(
+    Extension(document): Extension<Arc<InstanceDocument>>,
+) -> impl IntoResponse {
+    (
+        [(CACHE_CONTROL, "public, max-age=300")],
+        Json(document.as_ref().clone()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VerifierConfig;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use tower::ServiceExt;
+
+    fn build_info() -> BuildInfo {
+        BuildInfo {
+            version: "1.2.3".into(),
+            git_sha: "abc".into(),
+            build_time: "unknown".into(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn document_reflects_config() {
+        let mut config = Config::default();
+        config.instance.name = "Springfield".into();
+        config.verifiers = vec![VerifierConfig {
+            name: "idme".into(),
+            public_key: "pk".into(),
+        }];
+
+        let doc = InstanceDocument::from_config(&config, &build_info());
+        assert_eq!(doc.name, "Springfield");
+        assert_eq!(doc.operator, None);
+        assert_eq!(doc.software.version, "1.2.3");
+        assert_eq!(doc.api_versions, ["v1"]);
+        assert_eq!(doc.signup_policy, "open");
+        assert!(doc.envelope_versions.contains(&"cert-v2".to_string()));
+        assert_eq!(doc.verifier_keys.len(), 1);
+        assert_eq!(doc.verifier_keys[0].public_key, "pk");
+
+        config.signup.invite_only = true;
+        let doc = InstanceDocument::from_config(&config, &build_info());
+        assert_eq!(doc.signup_policy, "invite_only");
+    }
+
+    #[tokio::test]
+    async fn served_with_any_origin_cors() {
+        let doc = InstanceDocument::from_config(&Config::default(), &build_info());
+        let app = router().layer(Extension(Arc::new(doc)));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(WELL_KNOWN_PATH)
+                    .header("Origin", "https://other.example")
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    }
+}
@@ -0,0 +1,179 @@
+//! HTTP handlers for reviewing integrity alerts (account admins only).
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::repo::{IntegrityAlertRecord, IntegrityRepo, IntegrityRepoError};
+use crate::config::AccountsConfig;
+use crate::http::{forbidden, internal_error, not_found, Path};
+use crate::identity::http::auth::AuthenticatedDevice;
+
+// ─── Request / response types ──────────────────────────────────────────────
+
+/// Query parameters for the alert list.
+#[derive(Debug, Deserialize)]
+pub struct AlertListQuery {
+    /// Include resolved alerts (default `false`)
+    pub include_resolved: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityAlertResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    /// Failed check, e.g. `invalid_certificate` or `broken_sigchain`
+    pub code: String,
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub account_id: Option<Uuid>,
+    /// Failing record, e.g. `device:{kid}`, `sigchain:{seqno}`, or
+    /// `endorsement:{id}`
+    pub record: String,
+    pub detail: String,
+    pub first_seen_at: String,
+    /// Last sweep that still found the failure
+    pub last_seen_at: String,
+    pub resolved_at: Option<String>,
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub resolved_by: Option<Uuid>,
+}
+
+impl From<IntegrityAlertRecord> for IntegrityAlertResponse {
+    fn from(r: IntegrityAlertRecord) -> Self {
+        Self {
+            id: r.id,
+            code: r.code,
+            account_id: r.account_id,
+            record: r.record,
+            detail: r.detail,
+            first_seen_at: r.first_seen_at.to_rfc3339(),
+            last_seen_at: r.last_seen_at.to_rfc3339(),
+            resolved_at: r.resolved_at.map(|t| t.to_rfc3339()),
+            resolved_by: r.resolved_by,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityAlertsResponse {
+    /// Most recently seen first
+    pub alerts: Vec<IntegrityAlertResponse>,
+}
+
+// ─── Router ────────────────────────────────────────────────────────────────
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/integrity/alerts", get(list_alerts))
+        .route("/integrity/alerts/{id}/resolve", post(resolve_alert))
+}
+
+#[allow(clippy::result_large_err)]
+fn require_admin(
+    config: &AccountsConfig,
+    account_id: Uuid,
+) -> Result<(), axum::response::Response> {
+    if config.is_admin(account_id) {
+        Ok(())
+    } else {
+        Err(forbidden("Only account admins can review integrity alerts"))
+    }
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// List records the background integrity sweep found failing, open ones
+/// only unless `include_resolved` is set.
+#[utoipa::path(
+    get,
+    path = "/integrity/alerts",
+    tag = "Integrity",
+    params(
+        ("include_resolved" = Option<bool>, Query, description = "Include resolved alerts (default false)")
+    ),
+    responses(
+        (status = 200, description = "Integrity alerts", body = IntegrityAlertsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn list_alerts(
+    Extension(repo): Extension<Arc<dyn IntegrityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Query(query): Query<AlertListQuery>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(response) = require_admin(&accounts_config, auth.account_id) {
+        return response;
+    }
+    match repo
+        .list_alerts(query.include_resolved.unwrap_or(false))
+        .await
+    {
+        Ok(alerts) => (
+            StatusCode::OK,
+            Json(IntegrityAlertsResponse {
+                alerts: alerts.into_iter().map(Into::into).collect(),
+            }),
+        )
+            .into_response(),
+        Err(ref e) => integrity_repo_error_response(e),
+    }
+}
+
+/// Mark an alert resolved once the record has been repaired or explained.
+/// The next sweep reopens it if the record still fails.
+#[utoipa::path(
+    post,
+    path = "/integrity/alerts/{id}/resolve",
+    tag = "Integrity",
+    params(("id" = Uuid, Path, description = "Integrity alert ID")),
+    responses(
+        (status = 200, description = "Alert resolved", body = IntegrityAlertResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account admin"),
+        (status = 404, description = "Integrity alert not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+async fn resolve_alert(
+    Extension(repo): Extension<Arc<dyn IntegrityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(response) = require_admin(&accounts_config, auth.account_id) {
+        return response;
+    }
+    match repo.resolve_alert(id, auth.account_id).await {
+        Ok(alert) => {
+            tracing::info!(alert_id = %id, code = alert.code, "Integrity alert resolved");
+            (StatusCode::OK, Json(IntegrityAlertResponse::from(alert))).into_response()
+        }
+        Err(ref e) => integrity_repo_error_response(e),
+    }
+}
+
+fn integrity_repo_error_response(e: &IntegrityRepoError) -> axum::response::Response {
+    match e {
+        IntegrityRepoError::NotFound => not_found("Integrity alert not found"),
+        IntegrityRepoError::Database(inner) => {
+            tracing::error!("Integrity repo database error: {inner}");
+            internal_error()
+        }
+    }
+}
@@ -0,0 +1,776 @@
+//! Integrity sweep over stored signed material.
+//!
+//! [`sweep`] walks every account and re-checks what the server stored on the
+//! strength of a signature: root and device KID bindings, device certificates
+//! under the root key, backup envelopes, and the issuance envelope of every
+//! verifier-issued endorsement. For endorsements it also cross-checks the
+//! materialized row against what was signed — the signing device must belong
+//! to the recorded issuer, and the signed body must name the row's subject,
+//! topic, and visibility.
+//!
+//! Signed account events are re-checked too: every sigchain is re-hashed end
+//! to end, and the signatures on sigchain links and device revocation
+//! envelopes are verified against the account's current keys. With
+//! [`SweepOptions::events_since`] set, only events from that time on have
+//! their signatures re-checked.
+//!
+//! Nothing is repaired. Each failure becomes a [`Discrepancy`] in the
+//! [`IntegrityReport`], which `tc-ops verify-chains` prints as JSON for
+//! operators and [`worker`] records as integrity alerts.
+
+pub mod http;
+pub mod repo;
+pub mod worker;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use tc_crypto::verify_ed25519;
+use uuid::Uuid;
+
+use crate::identity::http::deletion::UndeleteAccountRequest;
+use crate::identity::portability::{
+    export_page, verify_backup, verify_device, verify_root, verify_rotation, AccountExport,
+};
+use crate::identity::repo::SigchainLinkRecord;
+use crate::identity::service::{
+    account_undeletion_message, CertificateSignature, DevicePubkey, RootPubkey,
+};
+use crate::identity::sigchain::{verify_links, SigchainLink, SigchainLinkType};
+use crate::identity::signed_request::SignedRequest;
+use crate::reputation::EndorsementVisibility;
+
+/// Rows read per query while sweeping.
+const SWEEP_BATCH_SIZE: i64 = 500;
+
+/// One stored record that failed a check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Discrepancy {
+    /// Stable `snake_case` identifier of the failed check.
+    pub code: &'static str,
+    /// Account the record belongs to (the issuer, for endorsements).
+    pub account_id: Option<Uuid>,
+    /// The record that failed: `account`, `device:{kid}`, `backup`,
+    /// `revocation:{kid}`, `sigchain`, `sigchain:{seqno}`, or
+    /// `endorsement:{id}`.
+    pub record: String,
+    pub detail: String,
+}
+
+/// Result of a full sweep.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IntegrityReport {
+    pub accounts_checked: u64,
+    pub devices_checked: u64,
+    pub endorsements_checked: u64,
+    /// Revocation envelopes and sigchain links whose signatures were checked.
+    pub events_checked: u64,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// How much of the event history a sweep re-checks, and how gently.
+#[derive(Debug, Clone, Default)]
+pub struct SweepOptions {
+    /// Only re-verify event signatures from this time on; `None` checks all
+    /// of them. Sigchain hashes are always checked in full.
+    pub events_since: Option<DateTime<Utc>>,
+    /// Sleep between batches so a background sweep yields to request traffic.
+    pub batch_pause: Duration,
+}
+
+impl IntegrityReport {
+    /// Whether every check passed.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Every failed check for one account, its devices, and its backup.
+///
+/// Unlike [`verify_account`](crate::identity::portability::verify_account),
+/// this keeps going after the first failure. Device certificates are not
+/// checked when the root key itself is unusable.
+#[must_use]
+pub fn check_account(record: &AccountExport) -> Vec<Discrepancy> {
+    let discrepancy =
+        |record_name: String, e: &crate::identity::portability::IntegrityError| Discrepancy {
+            code: e.code(),
+            account_id: Some(record.id),
+            record: record_name,
+            detail: e.to_string(),
+        };
+
+    let mut found = Vec::new();
+    match verify_root(record) {
+        Ok(root_pubkey) => {
+            for device in &record.devices {
+                if let Err(e) = verify_device(&root_pubkey, device)
+                    .and_then(|_| verify_rotation(&record.devices, device))
+                {
+                    found.push(discrepancy(format!("device:{}", device.device_kid), &e));
+                }
+            }
+        }
+        Err(e) => found.push(discrepancy("account".to_string(), &e)),
+    }
+    if let Some(backup) = &record.backup {
+        if let Err(e) = verify_backup(&record.root_kid, backup) {
+            found.push(discrepancy("backup".to_string(), &e));
+        }
+    }
+    found
+}
+
+fn in_window(at: DateTime<Utc>, since: Option<DateTime<Utc>>) -> bool {
+    since.is_none_or(|since| at >= since)
+}
+
+/// The first failed check for an event envelope signed by one of the
+/// account's devices, if any.
+///
+/// `expected_signer` is the KID stored alongside the envelope; the envelope
+/// must name the same device.
+fn check_device_envelope(
+    record: &AccountExport,
+    record_name: &str,
+    envelope: &SignedRequest,
+    expected_signer: Option<&str>,
+) -> Option<Discrepancy> {
+    let fail = |code: &'static str, detail: String| {
+        Some(Discrepancy {
+            code,
+            account_id: Some(record.id),
+            record: record_name.to_string(),
+            detail,
+        })
+    };
+
+    let signer = envelope.device_kid.to_string();
+    if let Some(expected) = expected_signer {
+        if expected != signer {
+            return fail(
+                "envelope_signer_mismatch",
+                format!("signed by {signer}, recorded as {expected}"),
+            );
+        }
+    }
+    let Some(device) = record.devices.iter().find(|d| d.device_kid == signer) else {
+        return fail(
+            "unknown_envelope_device",
+            format!("device {signer} is not one of the account's devices"),
+        );
+    };
+    let Ok(pubkey) = DevicePubkey::from_base64url(&device.device_pubkey) else {
+        return fail(
+            "invalid_device_key",
+            format!("device {signer} has an unusable public key"),
+        );
+    };
+    if !envelope.verify(&pubkey) {
+        return fail(
+            "invalid_envelope_signature",
+            "signature does not verify against the signing device".to_string(),
+        );
+    }
+    None
+}
+
+/// Every failed check for the revocation envelopes of devices revoked at or
+/// after `since`.
+#[must_use]
+pub fn check_revocations(record: &AccountExport, since: Option<DateTime<Utc>>) -> Vec<Discrepancy> {
+    record
+        .devices
+        .iter()
+        .filter(|d| d.revoked_at.is_some_and(|at| in_window(at, since)))
+        .filter_map(|device| {
+            let envelope = device.revocation_envelope.as_ref()?;
+            let record_name = format!("revocation:{}", device.device_kid);
+            match serde_json::from_value::<SignedRequest>(envelope.clone()) {
+                Ok(envelope) => check_device_envelope(
+                    record,
+                    &record_name,
+                    &envelope,
+                    device.revoked_by_kid.as_deref(),
+                ),
+                Err(e) => Some(Discrepancy {
+                    code: "malformed_envelope",
+                    account_id: Some(record.id),
+                    record: record_name,
+                    detail: e.to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// The first failed check for a root-signed `account_reactivated` link.
+fn check_reactivation(record: &AccountExport, link: &SigchainLink) -> Option<Discrepancy> {
+    let fail = |code: &'static str, detail: String| {
+        Some(Discrepancy {
+            code,
+            account_id: Some(record.id),
+            record: format!("sigchain:{}", link.seqno),
+            detail,
+        })
+    };
+
+    if link.signer_kid.as_str() != record.root_kid {
+        return fail(
+            "envelope_signer_mismatch",
+            format!("signed by {}, not the root key", link.signer_kid),
+        );
+    }
+    // An unusable root key is already reported against `account`
+    let root_pubkey = RootPubkey::from_base64url(&record.root_pubkey).ok()?;
+    let request: UndeleteAccountRequest = match serde_json::from_str(&link.envelope) {
+        Ok(request) => request,
+        Err(e) => return fail("malformed_envelope", e.to_string()),
+    };
+    let signature = match CertificateSignature::from_base64url(&request.root_signature) {
+        Ok(signature) => signature,
+        Err(e) => return fail("malformed_envelope", e.to_string()),
+    };
+    let message = account_undeletion_message(record.id, request.timestamp);
+    if verify_ed25519(root_pubkey.as_bytes(), &message, signature.as_bytes()).is_err() {
+        return fail(
+            "invalid_envelope_signature",
+            "signature does not verify against the root key".to_string(),
+        );
+    }
+    None
+}
+
+/// Every failed check for one account's sigchain: the hash chain as a whole,
+/// then the signature of each link created at or after `since`.
+#[must_use]
+pub fn check_sigchain(
+    record: &AccountExport,
+    links: &[SigchainLink],
+    since: Option<DateTime<Utc>>,
+) -> Vec<Discrepancy> {
+    let mut found = Vec::new();
+    if let Err(e) = verify_links(None, links) {
+        found.push(Discrepancy {
+            code: "broken_sigchain",
+            account_id: Some(record.id),
+            record: "sigchain".to_string(),
+            detail: e.to_string(),
+        });
+    }
+    for link in links.iter().filter(|l| in_window(l.created_at, since)) {
+        let discrepancy = if link.link_type == SigchainLinkType::AccountReactivated.as_str() {
+            check_reactivation(record, link)
+        } else {
+            let record_name = format!("sigchain:{}", link.seqno);
+            match serde_json::from_str::<SignedRequest>(&link.envelope) {
+                Ok(envelope) => check_device_envelope(
+                    record,
+                    &record_name,
+                    &envelope,
+                    Some(link.signer_kid.as_str()),
+                ),
+                Err(e) => Some(Discrepancy {
+                    code: "malformed_envelope",
+                    account_id: Some(record.id),
+                    record: record_name,
+                    detail: e.to_string(),
+                }),
+            }
+        };
+        found.extend(discrepancy);
+    }
+    found
+}
+
+/// A stored endorsement with an issuance envelope, joined with the signing
+/// device and the subject's names.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct EnvelopeRow {
+    pub id: Uuid,
+    pub endorser_id: Option<Uuid>,
+    pub topic: String,
+    pub visibility: String,
+    pub issuance_envelope: Value,
+    /// Subject's username and aliases; the signed body may use any of them.
+    pub subject_names: Vec<String>,
+    /// Account owning the envelope's device, if that device exists.
+    pub device_account_id: Option<Uuid>,
+    pub device_pubkey: Option<String>,
+}
+
+/// The first failed check for one endorsement's issuance envelope, if any.
+#[must_use]
+pub fn check_endorsement(row: &EnvelopeRow) -> Option<Discrepancy> {
+    let fail = |code: &'static str, detail: String| {
+        Some(Discrepancy {
+            code,
+            account_id: row.endorser_id,
+            record: format!("endorsement:{}", row.id),
+            detail,
+        })
+    };
+
+    let envelope: SignedRequest = match serde_json::from_value(row.issuance_envelope.clone()) {
+        Ok(envelope) => envelope,
+        Err(e) => return fail("malformed_envelope", e.to_string()),
+    };
+    let (Some(device_account_id), Some(device_pubkey)) =
+        (row.device_account_id, row.device_pubkey.as_deref())
+    else {
+        return fail(
+            "unknown_envelope_device",
+            format!("device {} is not on record", envelope.device_kid),
+        );
+    };
+    if row.endorser_id != Some(device_account_id) {
+        return fail(
+            "envelope_issuer_mismatch",
+            format!(
+                "device {} belongs to {device_account_id}, not the recorded issuer",
+                envelope.device_kid
+            ),
+        );
+    }
+    let Ok(pubkey) = DevicePubkey::from_base64url(device_pubkey) else {
+        return fail(
+            "invalid_device_key",
+            format!("device {} has an unusable public key", envelope.device_kid),
+        );
+    };
+    if !envelope.verify(&pubkey) {
+        return fail(
+            "invalid_envelope_signature",
+            "signature does not verify against the signing device".to_string(),
+        );
+    }
+
+    let body: Value = match envelope
+        .body_bytes()
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+    {
+        Ok(body) => body,
+        Err(e) => return fail("malformed_envelope", format!("signed body: {e}")),
+    };
+    let signed_username = body["username"].as_str().unwrap_or_default();
+    if !row
+        .subject_names
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(signed_username))
+    {
+        return fail(
+            "envelope_body_mismatch",
+            format!("signed for subject {signed_username:?}"),
+        );
+    }
+    if body["topic"].as_str() != Some(row.topic.as_str()) {
+        return fail(
+            "envelope_body_mismatch",
+            format!("signed for topic {}", body["topic"]),
+        );
+    }
+    let signed_visibility = body["visibility"]
+        .as_str()
+        .unwrap_or(EndorsementVisibility::Public.as_str());
+    if signed_visibility != row.visibility {
+        return fail(
+            "envelope_body_mismatch",
+            format!(
+                "signed visibility {signed_visibility}, stored {}",
+                row.visibility
+            ),
+        );
+    }
+    None
+}
+
+/// Check every account, sigchain, and enveloped endorsement, re-verifying
+/// every event signature.
+///
+/// # Errors
+///
+/// Returns an error if a query fails; discrepancies are not errors.
+pub async fn sweep(pool: &PgPool) -> Result<IntegrityReport, sqlx::Error> {
+    sweep_with(pool, &SweepOptions::default()).await
+}
+
+/// [`sweep`] with a window for event signatures and a pause between batches.
+///
+/// # Errors
+///
+/// Returns an error if a query fails; discrepancies are not errors.
+pub async fn sweep_with(
+    pool: &PgPool,
+    options: &SweepOptions,
+) -> Result<IntegrityReport, sqlx::Error> {
+    let mut report = IntegrityReport::default();
+    let since = options.events_since;
+
+    let mut after = None;
+    loop {
+        let page = export_page(pool, after, SWEEP_BATCH_SIZE).await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after = Some((last.created_at, last.id));
+        let ids: Vec<Uuid> = page.iter().map(|record| record.id).collect();
+        let links = sqlx::query_as::<_, SigchainLinkRecord>(
+            r"
+            SELECT account_id, seqno, link_type, signer_kid, envelope, prev_hash, hash, created_at
+            FROM account_sigchain
+            WHERE account_id = ANY($1)
+            ORDER BY account_id, seqno
+            ",
+        )
+        .bind(&ids)
+        .fetch_all(pool)
+        .await?;
+        for record in &page {
+            let chain: Vec<SigchainLink> = links
+                .iter()
+                .filter(|link| link.account_id == record.id)
+                .cloned()
+                .map(SigchainLink::from)
+                .collect();
+            report.accounts_checked += 1;
+            report.devices_checked += record.devices.len() as u64;
+            report.events_checked += record
+                .devices
+                .iter()
+                .filter(|d| d.revocation_envelope.is_some())
+                .filter(|d| d.revoked_at.is_some_and(|at| in_window(at, since)))
+                .count() as u64;
+            report.events_checked += chain
+                .iter()
+                .filter(|link| in_window(link.created_at, since))
+                .count() as u64;
+            report.discrepancies.extend(check_account(record));
+            report
+                .discrepancies
+                .extend(check_revocations(record, since));
+            report
+                .discrepancies
+                .extend(check_sigchain(record, &chain, since));
+        }
+        tokio::time::sleep(options.batch_pause).await;
+    }
+
+    let mut after: Option<Uuid> = None;
+    loop {
+        let rows = sqlx::query_as::<_, EnvelopeRow>(
+            r"
+            SELECT e.id, e.endorser_id, e.topic, e.visibility, e.issuance_envelope,
+                   ARRAY[s.username] || ARRAY(
+                       SELECT alias FROM account_aliases WHERE account_id = s.id
+                   ) AS subject_names,
+                   d.account_id AS device_account_id, d.device_pubkey
+            FROM reputation__endorsements e
+            JOIN accounts s ON s.id = e.subject_id
+            LEFT JOIN device_keys d ON d.device_kid = e.issuance_envelope->>'device_kid'
+            WHERE e.issuance_envelope IS NOT NULL
+              AND ($1::uuid IS NULL OR e.id > $1)
+            ORDER BY e.id
+            LIMIT $2
+            ",
+        )
+        .bind(after)
+        .bind(SWEEP_BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+        let Some(last) = rows.last() else {
+            break;
+        };
+        after = Some(last.id);
+        for row in &rows {
+            report.endorsements_checked += 1;
+            report.discrepancies.extend(check_endorsement(row));
+        }
+        tokio::time::sleep(options.batch_pause).await;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::portability::{BackupExport, DeviceExport, EXPORT_FORMAT_VERSION};
+    use crate::identity::sigchain::link_hash;
+    use ed25519_dalek::{Signer, SigningKey};
+    use tc_crypto::{decode_base64url, encode_base64url, Kid, RequestParts, SignatureAlg};
+
+    fn device(root: &SigningKey, seed: u8) -> (SigningKey, DeviceExport) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let pubkey = key.verifying_key().to_bytes();
+        let export = DeviceExport {
+            id: Uuid::new_v4(),
+            device_kid: Kid::derive(&pubkey).to_string(),
+            device_pubkey: encode_base64url(&pubkey),
+            device_name: "Laptop".to_string(),
+            certificate: encode_base64url(&root.sign(&pubkey).to_bytes()),
+            last_used_at: None,
+            revoked_at: None,
+            revoked_by_kid: None,
+            revocation_envelope: None,
+            certificate_version: Some(1),
+            certificate_timestamp: None,
+            rotated_from_kid: None,
+            rotation_signature: None,
+            created_at: Utc::now(),
+        };
+        (key, export)
+    }
+
+    fn account(root: &SigningKey, devices: Vec<DeviceExport>) -> AccountExport {
+        let pubkey = root.verifying_key().to_bytes();
+        AccountExport {
+            format_version: EXPORT_FORMAT_VERSION,
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            root_pubkey: encode_base64url(&pubkey),
+            root_kid: Kid::derive(&pubkey).to_string(),
+            created_at: Utc::now(),
+            devices,
+            aliases: Vec::new(),
+            backup: None,
+        }
+    }
+
+    #[test]
+    fn check_account_reports_every_failure() {
+        let root = SigningKey::from_bytes(&[1; 32]);
+        let (_, good) = device(&root, 2);
+        let (_, mut forged) = device(&SigningKey::from_bytes(&[9; 32]), 3);
+        forged.device_name = "Forged".to_string();
+        let (_, mut mislabeled) = device(&root, 4);
+        mislabeled.device_kid = good.device_kid.clone();
+        let mut record = account(&root, vec![good, forged.clone(), mislabeled]);
+        record.backup = Some(BackupExport {
+            id: Uuid::new_v4(),
+            kid: "someone-else".to_string(),
+            encrypted_backup: String::new(),
+            created_at: Utc::now(),
+            recovery_code_hash: None,
+        });
+
+        let codes: Vec<_> = check_account(&record).iter().map(|d| d.code).collect();
+        assert_eq!(
+            codes,
+            [
+                "invalid_certificate",
+                "device_kid_mismatch",
+                "backup_kid_mismatch"
+            ]
+        );
+
+        record.root_kid = "wrong".to_string();
+        let found = check_account(&record);
+        assert_eq!(found[0].code, "root_kid_mismatch");
+        assert_eq!(found[0].record, "account");
+    }
+
+    fn signed(device_key: &SigningKey, path: &str, body: &str) -> SignedRequest {
+        let kid = Kid::derive(&device_key.verifying_key().to_bytes());
+        let kid_str = kid.to_string();
+        let canonical = RequestParts {
+            method: "POST",
+            host: "localhost",
+            path_and_query: path,
+            timestamp: 1_700_000_000,
+            nonce: "n",
+            content_type: "application/json",
+            device_kid: &kid_str,
+            challenge: "",
+            body: body.as_bytes(),
+        }
+        .canonical_v2(SignatureAlg::Ed25519);
+        SignedRequest {
+            alg: Some("ed25519".to_string()),
+            method: "POST".to_string(),
+            host: "localhost".to_string(),
+            path_and_query: path.to_string(),
+            timestamp: 1_700_000_000,
+            nonce: "n".to_string(),
+            content_type: "application/json".to_string(),
+            device_kid: kid,
+            challenge: None,
+            body: encode_base64url(body.as_bytes()),
+            signature: encode_base64url(&device_key.sign(canonical.as_bytes()).to_bytes()),
+        }
+    }
+
+    fn envelope_row(issuer: Uuid, device_key: &SigningKey, body: &Value) -> EnvelopeRow {
+        let pubkey = device_key.verifying_key().to_bytes();
+        let envelope = signed(device_key, "/verifiers/endorsements", &body.to_string());
+        EnvelopeRow {
+            id: Uuid::new_v4(),
+            endorser_id: Some(issuer),
+            topic: "clearance".to_string(),
+            visibility: "private".to_string(),
+            issuance_envelope: serde_json::to_value(envelope).expect("serialize"),
+            subject_names: vec!["Bob".to_string(), "bobby".to_string()],
+            device_account_id: Some(issuer),
+            device_pubkey: Some(encode_base64url(&pubkey)),
+        }
+    }
+
+    #[test]
+    fn check_endorsement_cross_checks_the_row() {
+        let issuer = Uuid::new_v4();
+        let key = SigningKey::from_bytes(&[5; 32]);
+        let body = serde_json::json!({
+            "username": "bobby",
+            "topic": "clearance",
+            "visibility": "private",
+        });
+        let row = envelope_row(issuer, &key, &body);
+        assert_eq!(check_endorsement(&row), None);
+
+        // Row edited after signing
+        let mut widened = row.clone();
+        widened.visibility = "public".to_string();
+        assert_eq!(
+            check_endorsement(&widened).map(|d| d.code),
+            Some("envelope_body_mismatch")
+        );
+
+        let mut reassigned = row.clone();
+        reassigned.endorser_id = Some(Uuid::new_v4());
+        assert_eq!(
+            check_endorsement(&reassigned).map(|d| d.code),
+            Some("envelope_issuer_mismatch")
+        );
+
+        let mut rekeyed = row.clone();
+        rekeyed.device_pubkey = Some(encode_base64url(
+            &SigningKey::from_bytes(&[6; 32]).verifying_key().to_bytes(),
+        ));
+        assert_eq!(
+            check_endorsement(&rekeyed).map(|d| d.code),
+            Some("invalid_envelope_signature")
+        );
+
+        let mut orphaned = row;
+        orphaned.device_account_id = None;
+        orphaned.device_pubkey = None;
+        assert_eq!(
+            check_endorsement(&orphaned).map(|d| d.code),
+            Some("unknown_envelope_device")
+        );
+    }
+
+    fn link(
+        prev: Option<&SigchainLink>,
+        link_type: &str,
+        signer_kid: &str,
+        envelope: String,
+    ) -> SigchainLink {
+        let seqno = prev.map_or(1, |p| p.seqno + 1);
+        let prev_hash = prev.map(|p| p.hash.clone());
+        let prev_bytes = prev_hash
+            .as_deref()
+            .map(|h| decode_base64url(h).expect("decode"));
+        let hash = link_hash(seqno, prev_bytes.as_deref(), link_type, &envelope);
+        SigchainLink {
+            seqno,
+            link_type: link_type.to_string(),
+            signer_kid: signer_kid.parse().expect("kid"),
+            envelope,
+            prev_hash,
+            hash: encode_base64url(&hash),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn check_revocations_verifies_the_recorded_signer() {
+        let root = SigningKey::from_bytes(&[1; 32]);
+        let (key, laptop) = device(&root, 2);
+        let (_, mut phone) = device(&root, 3);
+        phone.revoked_at = Some(Utc::now());
+        phone.revoked_by_kid = Some(laptop.device_kid.clone());
+        phone.revocation_envelope = Some(
+            serde_json::to_value(signed(
+                &key,
+                &format!("/auth/devices/{}", phone.device_kid),
+                "",
+            ))
+            .expect("serialize"),
+        );
+        let mut record = account(&root, vec![laptop.clone(), phone]);
+        assert_eq!(check_revocations(&record, None), []);
+
+        record.devices[1].revoked_by_kid = Some(record.devices[1].device_kid.clone());
+        let codes: Vec<_> = check_revocations(&record, None)
+            .iter()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, ["envelope_signer_mismatch"]);
+
+        // Outside the window the envelope is not re-checked
+        let later = Utc::now() + chrono::Duration::hours(1);
+        assert_eq!(check_revocations(&record, Some(later)), []);
+
+        record.devices.retain(|d| d.device_kid != laptop.device_kid);
+        record.devices[0].revoked_by_kid = Some(laptop.device_kid);
+        let codes: Vec<_> = check_revocations(&record, None)
+            .iter()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(codes, ["unknown_envelope_device"]);
+    }
+
+    #[test]
+    fn check_sigchain_rehashes_and_reverifies_links() {
+        let root = SigningKey::from_bytes(&[1; 32]);
+        let (key, laptop) = device(&root, 2);
+        let record = account(&root, vec![laptop.clone()]);
+        let undelete = |account_id: Uuid| {
+            let message = account_undeletion_message(account_id, 1_700_000_000);
+            serde_json::to_string(&UndeleteAccountRequest {
+                username: "alice".to_string(),
+                timestamp: 1_700_000_000,
+                root_signature: encode_base64url(&root.sign(&message).to_bytes()),
+            })
+            .expect("serialize")
+        };
+
+        let added = link(
+            None,
+            "device_added",
+            &laptop.device_kid,
+            serde_json::to_string(&signed(&key, "/auth/devices", "{}")).expect("serialize"),
+        );
+        let reactivated = link(
+            Some(&added),
+            "account_reactivated",
+            &record.root_kid,
+            undelete(record.id),
+        );
+        let mut chain = vec![added.clone(), reactivated];
+        assert_eq!(check_sigchain(&record, &chain, None), []);
+
+        chain[1].hash = added.hash.clone();
+        let found = check_sigchain(&record, &chain, None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].code, "broken_sigchain");
+        assert_eq!(found[0].record, "sigchain");
+
+        // Undelete signed for another account
+        let replayed = link(
+            Some(&added),
+            "account_reactivated",
+            &record.root_kid,
+            undelete(Uuid::new_v4()),
+        );
+        let found = check_sigchain(&record, &[added, replayed], None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].code, "invalid_envelope_signature");
+        assert_eq!(found[0].record, "sigchain:2");
+    }
+}
@@ -0,0 +1,220 @@
+//! Persistence for integrity alerts raised by the background sweep
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::Discrepancy;
+
+use crate::db::{RepoError, RepoErrorKind};
+
+/// Most alerts returned by one listing, most recently seen first.
+pub const MAX_LISTED_ALERTS: i64 = 500;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// A stored record that failed an integrity check.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct IntegrityAlertRecord {
+    pub id: Uuid,
+    /// [`Discrepancy::code`] of the failed check.
+    pub code: String,
+    pub account_id: Option<Uuid>,
+    /// [`Discrepancy::record`] naming the failing row.
+    pub record: String,
+    /// Detail from the most recent sweep that found it.
+    pub detail: String,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum IntegrityRepoError {
+    #[error("integrity alert not found")]
+    NotFound,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for IntegrityRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+const SELECT_ALERT: &str = r"
+    SELECT id, code, account_id, record, detail, first_seen_at, last_seen_at,
+           resolved_at, resolved_by
+    FROM integrity_alerts";
+
+/// Upsert one alert per discrepancy, seen at `seen_at`.
+///
+/// A known alert gets the new detail and `last_seen_at`, and is reopened if
+/// it had been resolved: the record still fails. Returns how many alerts
+/// were new.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn record_alerts(
+    pool: &PgPool,
+    discrepancies: &[Discrepancy],
+    seen_at: DateTime<Utc>,
+) -> Result<u64, IntegrityRepoError> {
+    if discrepancies.is_empty() {
+        return Ok(0);
+    }
+    let codes: Vec<&str> = discrepancies.iter().map(|d| d.code).collect();
+    let account_ids: Vec<Option<Uuid>> = discrepancies.iter().map(|d| d.account_id).collect();
+    let records: Vec<&str> = discrepancies.iter().map(|d| d.record.as_str()).collect();
+    let details: Vec<&str> = discrepancies.iter().map(|d| d.detail.as_str()).collect();
+    // Existing accounts only: one purged mid-sweep has nothing left to flag
+    let inserted: i64 = sqlx::query_scalar(
+        r"
+        WITH upserted AS (
+            INSERT INTO integrity_alerts (code, account_id, record, detail, first_seen_at, last_seen_at)
+            SELECT DISTINCT ON (d.code, d.account_id, d.record)
+                   d.code, d.account_id, d.record, d.detail, $5, $5
+            FROM UNNEST($1::text[], $2::uuid[], $3::text[], $4::text[])
+                AS d(code, account_id, record, detail)
+            WHERE d.account_id IS NULL
+               OR EXISTS (SELECT 1 FROM accounts a WHERE a.id = d.account_id)
+            ON CONFLICT (code, COALESCE(account_id, '00000000-0000-0000-0000-000000000000'::uuid), record)
+            DO UPDATE SET detail = EXCLUDED.detail,
+                          last_seen_at = EXCLUDED.last_seen_at,
+                          resolved_at = NULL,
+                          resolved_by = NULL
+            RETURNING (xmax = 0) AS inserted
+        )
+        SELECT COUNT(*) FILTER (WHERE inserted) FROM upserted
+        ",
+    )
+    .bind(&codes)
+    .bind(&account_ids)
+    .bind(&records)
+    .bind(&details)
+    .bind(seen_at)
+    .fetch_one(pool)
+    .await?;
+    Ok(u64::try_from(inserted).unwrap_or_default())
+}
+
+/// Alerts, most recently seen first, limited to [`MAX_LISTED_ALERTS`].
+/// Resolved alerts are only included with `include_resolved`.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_alerts(
+    pool: &PgPool,
+    include_resolved: bool,
+) -> Result<Vec<IntegrityAlertRecord>, IntegrityRepoError> {
+    let alerts = sqlx::query_as::<_, IntegrityAlertRecord>(&format!(
+        "{SELECT_ALERT} WHERE ($1 OR resolved_at IS NULL)
+         ORDER BY last_seen_at DESC, id LIMIT $2"
+    ))
+    .bind(include_resolved)
+    .bind(MAX_LISTED_ALERTS)
+    .fetch_all(pool)
+    .await?;
+    Ok(alerts)
+}
+
+/// Mark an alert resolved by `resolved_by`. Resolving twice keeps the first
+/// resolution.
+///
+/// # Errors
+///
+/// Returns `NotFound` for an unknown alert, `Database` on failure.
+pub async fn resolve_alert(
+    pool: &PgPool,
+    id: Uuid,
+    resolved_by: Uuid,
+) -> Result<IntegrityAlertRecord, IntegrityRepoError> {
+    sqlx::query_as::<_, IntegrityAlertRecord>(
+        r"
+        UPDATE integrity_alerts
+        SET resolved_at = COALESCE(resolved_at, now()),
+            resolved_by = CASE WHEN resolved_at IS NULL THEN $2 ELSE resolved_by END
+        WHERE id = $1
+        RETURNING id, code, account_id, record, detail, first_seen_at, last_seen_at,
+                  resolved_at, resolved_by
+        ",
+    )
+    .bind(id)
+    .bind(resolved_by)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(IntegrityRepoError::NotFound)
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// Integrity alert persistence for the sweep worker and admin handlers.
+#[async_trait]
+pub trait IntegrityRepo: Send + Sync {
+    async fn record_alerts(
+        &self,
+        discrepancies: &[Discrepancy],
+        seen_at: DateTime<Utc>,
+    ) -> Result<u64, IntegrityRepoError>;
+
+    async fn list_alerts(
+        &self,
+        include_resolved: bool,
+    ) -> Result<Vec<IntegrityAlertRecord>, IntegrityRepoError>;
+
+    async fn resolve_alert(
+        &self,
+        id: Uuid,
+        resolved_by: Uuid,
+    ) -> Result<IntegrityAlertRecord, IntegrityRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`IntegrityRepo`].
+pub struct PgIntegrityRepo {
+    pool: PgPool,
+}
+
+impl PgIntegrityRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IntegrityRepo for PgIntegrityRepo {
+    async fn record_alerts(
+        &self,
+        discrepancies: &[Discrepancy],
+        seen_at: DateTime<Utc>,
+    ) -> Result<u64, IntegrityRepoError> {
+        record_alerts(&self.pool, discrepancies, seen_at).await
+    }
+
+    async fn list_alerts(
+        &self,
+        include_resolved: bool,
+    ) -> Result<Vec<IntegrityAlertRecord>, IntegrityRepoError> {
+        list_alerts(&self.pool, include_resolved).await
+    }
+
+    async fn resolve_alert(
+        &self,
+        id: Uuid,
+        resolved_by: Uuid,
+    ) -> Result<IntegrityAlertRecord, IntegrityRepoError> {
+        resolve_alert(&self.pool, id, resolved_by).await
+    }
+}
@@ -0,0 +1,109 @@
+//! Background integrity sweep — re-verifies stored signed material on the
+//! reader pool and records every failure in `integrity_alerts`.
+//!
+//! Runs every `integrity.sweep_interval_secs`, pausing
+//! `integrity.batch_pause_ms` between batches so it stays out of the way of
+//! request traffic. Device certificates, backups, and endorsement envelopes
+//! are checked in full each time; event signatures only for the last
+//! `integrity.recent_days`, since older events were checked by earlier
+//! sweeps. Admins review the alerts at `GET /integrity/alerts`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use super::repo::IntegrityRepo;
+use super::{sweep_with, SweepOptions};
+use crate::config::IntegrityConfig;
+
+/// Sweep options for a run starting at `now`.
+#[must_use]
+pub fn sweep_options(config: &IntegrityConfig, now: DateTime<Utc>) -> SweepOptions {
+    SweepOptions {
+        events_since: Some(now - chrono::Duration::days(i64::from(config.recent_days))),
+        batch_pause: Duration::from_millis(config.batch_pause_ms),
+    }
+}
+
+/// Run the sweep against `reader` and record what it finds through `repo`.
+///
+/// # Errors
+///
+/// Returns a description of the first query or repo failure.
+pub async fn run_integrity_sweep(
+    reader: &PgPool,
+    repo: &dyn IntegrityRepo,
+    config: &IntegrityConfig,
+) -> Result<(), String> {
+    let now = Utc::now();
+    let report = sweep_with(reader, &sweep_options(config, now))
+        .await
+        .map_err(|e| e.to_string())?;
+    for discrepancy in &report.discrepancies {
+        metrics::counter!("tc_integrity_discrepancies_total", "code" => discrepancy.code)
+            .increment(1);
+    }
+    let new_alerts = repo
+        .record_alerts(&report.discrepancies, now)
+        .await
+        .map_err(|e| e.to_string())?;
+    if report.is_clean() {
+        tracing::info!(
+            accounts = report.accounts_checked,
+            devices = report.devices_checked,
+            events = report.events_checked,
+            endorsements = report.endorsements_checked,
+            "Integrity sweep found no discrepancies"
+        );
+    } else {
+        tracing::warn!(
+            discrepancies = report.discrepancies.len(),
+            new_alerts,
+            "Integrity sweep found discrepancies; see GET /integrity/alerts"
+        );
+    }
+    Ok(())
+}
+
+/// Run [`run_integrity_sweep`] every `sweep_interval_secs`, unless disabled.
+pub fn spawn_integrity_sweeper(
+    reader: PgPool,
+    repo: Arc<dyn IntegrityRepo>,
+    config: &IntegrityConfig,
+) {
+    if !config.enabled {
+        tracing::info!("Integrity sweep disabled (enable via TC_INTEGRITY__ENABLED=true)");
+        return;
+    }
+    let config = config.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.sweep_interval_secs));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_integrity_sweep(&reader, repo.as_ref(), &config).await {
+                tracing::warn!("Integrity sweep failed: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_cover_the_recent_window() {
+        let config = IntegrityConfig {
+            recent_days: 3,
+            batch_pause_ms: 40,
+            ..IntegrityConfig::default()
+        };
+        let now = Utc::now();
+        let options = sweep_options(&config, now);
+        assert_eq!(options.events_since, Some(now - chrono::Duration::days(3)));
+        assert_eq!(options.batch_pause, Duration::from_millis(40));
+    }
+}
@@ -7,15 +7,33 @@
     clippy::unwrap_used
 )]
 
+pub mod analytics;
 pub mod build_info;
+pub mod changelog;
 pub mod config;
 pub mod db;
+pub mod developer;
 pub mod engine_registry;
+pub mod error_reporting;
+pub mod events;
+pub mod feed;
 pub mod graphql;
 pub mod http;
 pub mod identity;
+pub mod instance;
+pub mod integrity;
+pub mod notifications;
+pub mod pagination;
+pub mod privacy;
 pub mod reputation;
+pub mod research;
 pub mod rest;
+pub mod retention;
 pub mod rooms;
 pub mod sim;
+pub mod sms;
+pub mod stats;
+pub mod subject;
+pub mod topics;
 pub mod trust;
+pub mod visibility;
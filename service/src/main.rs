@@ -9,12 +9,8 @@
 
 use async_graphql::{EmptySubscription, Schema};
 use axum::{
-    extract::DefaultBodyLimit,
-    http::{header::HeaderValue, Method, StatusCode},
-    middleware,
-    response::IntoResponse,
-    routing::get,
-    Extension, Router,
+    extract::DefaultBodyLimit, handler::Handler, http::StatusCode, middleware,
+    response::IntoResponse, routing::get, Extension, Router,
 };
 use axum_prometheus::PrometheusMetricLayer;
 use sqlx::PgPool;
@@ -26,31 +22,84 @@ use tc_engine_api::engine::{EngineContext, EngineRegistry};
 use tc_engine_polling::engine::PollingEngine;
 use tc_engine_polling::service::{DefaultPollingService, PollingService};
 use tinycongress_api::{
+    analytics,
     build_info::BuildInfo,
+    changelog,
     config::Config,
-    db::setup_database,
-    engine_registry,
-    graphql::{graphql_handler, graphql_playground, MutationRoot, QueryRoot},
-    http::{build_security_headers, security_headers_middleware},
+    db::{
+        self,
+        read_only::{ReadOnlyMode, ReadOnlyMonitor},
+        setup_database,
+    },
+    developer::{
+        self,
+        repo::{DeveloperRepo, PgDeveloperRepo},
+    },
+    engine_registry, error_reporting,
+    events::{self, EventPublisher},
+    feed::{self, repo::PgFeedRepo, score::TrustTopicScorer, service::FeedService},
+    graphql::{
+        cost::{CostBudgets, PgGraphQLCostRepo, ReputationTierSource},
+        graphql_handler, graphql_playground, ApiSchema, MutationRoot, QueryRoot,
+    },
+    http::{
+        build_compression_layer, build_cors_layer, build_security_headers, dev_tools_guard,
+        limit_concurrency, reject_writes_when_read_only,
+        response_check::{validate_responses, ResponseSchemas},
+        security_headers_middleware, ConcurrencyLimits, DevToolsAccess,
+    },
     identity::{
         self,
+        http::{
+            device_usage::DeviceUsageRecorder, signature_guard::SignatureFailureGuard,
+            token_quota::ApiTokenQuotas,
+        },
         repo::{IdentityRepo, PgIdentityRepo},
         service::{DefaultIdentityService, IdentityService},
+        username::UsernamePolicy,
     },
+    instance::{self, InstanceDocument},
+    integrity::{
+        self,
+        repo::{IntegrityRepo, PgIntegrityRepo},
+    },
+    notifications::{
+        self,
+        projector::NotificationProjector,
+        repo::{NotificationRepo, PgNotificationRepo},
+    },
+    privacy::AggregateNoise,
     reputation::{
         self,
         repo::{PgReputationRepo, ReputationRepo},
         service::{DefaultEndorsementService, EndorsementService},
     },
+    research::{
+        self,
+        repo::{PgResearchRepo, ResearchRepo},
+    },
     rest::{self, ApiDoc},
+    retention,
     rooms::{
         self,
         content_filter::{ContentFilter, NoopFilter},
         repo::{PgRoomsRepo, RoomsRepo},
         service::{DefaultRoomsService, RoomsService},
     },
+    sms,
+    stats::{
+        self,
+        repo::{PgStatsRepo, StatsRepo},
+    },
+    topics::{
+        self,
+        projector::TopicFeedProjector,
+        repo::{PgTopicRepo, TopicRepo},
+    },
     trust::{
         self,
+        account_gates::{AccountGates, PgAccountStandingReader},
+        distances::TrustDistances,
         engine::TrustEngine,
         graph_reader::TrustRepoGraphReader,
         repo::{PgTrustRepo, TrustRepo},
@@ -58,7 +107,8 @@ use tinycongress_api::{
         worker::TrustWorker,
     },
 };
-use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -109,64 +159,43 @@ async fn readiness_check(pool: Option<Extension<PgPool>>) -> impl IntoResponse {
     }
 }
 
-fn build_cors_origin(origins: &[String]) -> AllowOrigin {
-    if origins.iter().any(|o| o == "*") {
-        tracing::warn!("CORS configured to allow any origin - not recommended for production");
-        AllowOrigin::any()
-    } else if origins.is_empty() {
-        tracing::info!(
-            "CORS allowed origins not configured - cross-origin requests will be blocked"
-        );
-        AllowOrigin::list(Vec::<HeaderValue>::new())
-    } else {
-        let mut header_values: Vec<HeaderValue> = Vec::with_capacity(origins.len());
-        for origin in origins {
-            match origin.parse() {
-                Ok(v) => header_values.push(v),
-                Err(e) => {
-                    tracing::warn!(origin = %origin, error = %e, "Invalid CORS origin in config — skipping");
-                }
-            }
-        }
-        tracing::info!(origins = ?origins, "CORS allowed origins configured");
-        AllowOrigin::list(header_values)
-    }
-}
-
-/// Spawn a background task that periodically deletes expired nonces.
-///
-/// TTL matches [`identity::http::auth::MAX_TIMESTAMP_SKEW`] so nonces
-/// outlive the timestamp validation window.
-fn spawn_nonce_cleanup(pool: sqlx::PgPool) {
-    tokio::spawn(async move {
-        let ttl = identity::http::auth::MAX_TIMESTAMP_SKEW;
-        let mut interval = tokio::time::interval(Duration::from_secs(60));
-        loop {
-            interval.tick().await;
-            match identity::repo::cleanup_expired_nonces(&pool, ttl).await {
-                Ok(0) => {}
-                Ok(n) => tracing::debug!(count = n, "Cleaned up expired nonces"),
-                Err(e) => tracing::warn!("Nonce cleanup failed: {e}"),
-            }
-        }
-    });
-}
-
 /// Build the Axum router with all service layers wired up.
 #[allow(clippy::too_many_lines)]
 async fn build_app(
     config: &Config,
     pool: PgPool,
+    reader_pool: PgPool,
     build_info: BuildInfo,
     schema: Schema<QueryRoot, MutationRoot, EmptySubscription>,
-    allow_origin: AllowOrigin,
+    aggregate_noise: Arc<AggregateNoise>,
 ) -> Result<(Router, PgPool), anyhow::Error> {
-    let rest_v1 = Router::new().route("/build-info", get(rest::get_build_info));
+    let instance_document = Arc::new(InstanceDocument::from_config(config, &build_info));
+
+    let changelog = Arc::new(changelog::Changelog::embedded()?);
+    let rest_v1 = Router::new()
+        .route("/build-info", get(rest::get_build_info))
+        .route("/changelog", get(changelog::get_changelog))
+        .route("/stats", get(stats::http::get_stats));
 
     // Identity wiring
     let repo = Arc::new(PgIdentityRepo::new(pool.clone()));
-    let service = Arc::new(DefaultIdentityService::new(repo.clone())) as Arc<dyn IdentityService>;
+    let usernames = Arc::new(
+        UsernamePolicy::from_config(&config.usernames)
+            .map_err(|e| anyhow::anyhow!("Failed to load usernames.reserved_file: {e}"))?,
+    );
+    let service = Arc::new(
+        DefaultIdentityService::new(repo.clone())
+            .with_invite_only(config.signup.invite_only)
+            .with_username_policy(usernames.clone()),
+    ) as Arc<dyn IdentityService>;
     let repo_ext = repo as Arc<dyn IdentityRepo>;
+    usernames.spawn_reloader(repo_ext.clone());
+    identity::deletion::spawn_purge_sweeper(repo_ext.clone(), &config.account_deletion);
+    let api_token_quotas = Arc::new(ApiTokenQuotas::from_config(&config.api_tokens));
+    api_token_quotas.spawn_flusher(repo_ext.clone());
+    let device_usage = Arc::new(DeviceUsageRecorder::new());
+    device_usage.spawn_flusher(repo_ext.clone());
+    let verify_repo = repo_ext.clone();
 
     let synthetic_backup_key = identity::http::backup::SyntheticBackupKey::new(
         config.synthetic_backup_key.as_bytes().to_vec(),
@@ -203,12 +232,18 @@ async fn build_app(
 
     // Trust engine and service
     let trust_engine = Arc::new(TrustEngine::new(pool.clone()));
+    let trust_distances = Arc::new(TrustDistances::new(pool.clone(), trust_engine.clone()));
+    trust_distances.spawn_refresher();
     let reputation_repo_for_trust =
         Arc::new(PgReputationRepo::new(pool.clone())) as Arc<dyn ReputationRepo>;
-    let trust_service: Arc<dyn TrustService> = Arc::new(DefaultTrustService::new(
-        trust_repo_for_service,
-        reputation_repo_for_trust,
+    let account_gates = Arc::new(AccountGates::new(
+        config.account_gates,
+        Arc::new(PgAccountStandingReader::new(pool.clone())),
     ));
+    let trust_service: Arc<dyn TrustService> = Arc::new(
+        DefaultTrustService::new(trust_repo_for_service, reputation_repo_for_trust)
+            .with_account_gates(account_gates.clone()),
+    );
 
     // Engine plugin infrastructure
     let trust_graph_reader = Arc::new(TrustRepoGraphReader::new(trust_repo.clone()))
@@ -263,14 +298,59 @@ async fn build_app(
     let polling_service = Arc::new(DefaultPollingService::new(pool.clone(), trust_graph_reader))
         as Arc<dyn PollingService>;
 
+    // Notification inbox, fed from domain events
+    let notification_repo =
+        Arc::new(PgNotificationRepo::new(pool.clone())) as Arc<dyn NotificationRepo>;
+
+    // Topic subscriptions, with feeds fanned out from endorsement events
+    let topic_repo = Arc::new(PgTopicRepo::new(pool.clone())) as Arc<dyn TopicRepo>;
+
+    // Trust-ranked feed, precomputed in the background for heavy accounts
+    let feed_service = Arc::new(FeedService::new(
+        Arc::new(PgFeedRepo::new(pool.clone())),
+        Arc::new(TrustTopicScorer::default()),
+        config.feed.clone(),
+    ));
+    feed_service.spawn_precompute_job();
+
+    // Anonymized research exports, gated by `research` config roles
+    let research_repo = Arc::new(PgResearchRepo::new(pool.clone())) as Arc<dyn ResearchRepo>;
+    let integrity_repo = Arc::new(PgIntegrityRepo::new(pool.clone())) as Arc<dyn IntegrityRepo>;
+    integrity::worker::spawn_integrity_sweeper(
+        reader_pool.clone(),
+        integrity_repo.clone(),
+        &config.integrity,
+    );
+    analytics::worker::spawn_analytics_export(reader_pool.clone(), &config.analytics_export)?;
+
+    // Third-party OAuth client registration
+    let developer_repo = Arc::new(PgDeveloperRepo::new(pool.clone())) as Arc<dyn DeveloperRepo>;
+
+    // Domain event mirroring (no-op unless TC_EVENTS__ENABLED=true)
+    let event_publisher = Arc::new(TopicFeedProjector::spawn(
+        Arc::new(NotificationProjector::spawn(
+            events::build_publisher(&config.events).await?,
+            notification_repo.clone(),
+        )),
+        topic_repo.clone(),
+    )) as Arc<dyn EventPublisher>;
+
     let (prometheus_layer, metric_handle) = PrometheusMetricLayer::pair();
 
-    let app = Router::new()
+    let dev_tools_access = Arc::new(DevToolsAccess::from_config(
+        &config.dev_tools,
+        &config.accounts,
+    )?);
+    if dev_tools_access.is_restricted() {
+        tracing::info!("Developer tools restricted to allowlisted IPs and admin accounts");
+    }
+
+    let mut app = Router::new()
         .route("/graphql", {
             let route = axum::routing::post(graphql_handler);
             if config.graphql.playground_enabled {
                 tracing::info!("GraphQL Playground enabled at /graphql");
-                route.get(graphql_playground)
+                route.get(graphql_playground.layer(middleware::from_fn(dev_tools_guard)))
             } else {
                 tracing::info!(
                     "GraphQL Playground disabled (enable via TC_GRAPHQL__PLAYGROUND_ENABLED=true)"
@@ -283,11 +363,34 @@ async fn build_app(
         .merge(reputation::http::router(&config.rate_limit))
         .merge(rooms::http::router())
         .merge(trust::http::trust_router())
+        .merge(notifications::http::router())
+        .merge(topics::http::router())
+        .merge(feed::http::router())
+        .merge(research::http::router())
+        .merge(integrity::http::router())
+        .merge(developer::http::router())
         .nest("/api/v1", engine_registry::engines_router())
         .route("/health", get(health_check))
         .route("/ready", get(readiness_check))
-        .route("/metrics", get(|| async move { metric_handle.render() }))
+        .route("/metrics", get(|| async move { metric_handle.render() }));
+
+    // Swagger UI (disabled by default for security). Merged beneath the
+    // extension layers so the dev-tools guard can authenticate admins.
+    if config.swagger.enabled {
+        tracing::info!("Swagger UI enabled at /swagger-ui");
+        app = app.merge(
+            Router::from(
+                SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()),
+            )
+            .layer(middleware::from_fn(dev_tools_guard)),
+        );
+    } else {
+        tracing::info!("Swagger UI disabled (enable via TC_SWAGGER__ENABLED=true)");
+    }
+
+    let app = app
         .layer(Extension(schema))
+        .layer(Extension(dev_tools_access))
         .layer(Extension(service))
         .layer(Extension(repo_ext))
         .layer(Extension(endorsement_service))
@@ -295,13 +398,40 @@ async fn build_app(
         .layer(Extension(rooms_service))
         .layer(Extension(polling_service))
         .layer(Extension(trust_service))
+        .layer(Extension(trust_distances.clone()))
         .layer(Extension(trust_repo_for_http))
         .layer(Extension(trust_engine.clone()))
+        .layer(Extension(account_gates))
         .layer(Extension(synthetic_backup_key))
+        .layer(Extension(config.auth))
+        .layer(Extension(config.signup.clone()))
+        .layer(Extension(usernames))
+        .layer(Extension(config.api_tokens.clone()))
+        .layer(Extension(config.research.clone()))
+        .layer(Extension(config.accounts.clone()))
+        .layer(Extension(config.reputation.clone()))
+        .layer(Extension(config.verification.clone()))
+        .layer(Extension(config.step_up.clone()))
+        .layer(Extension(config.account_deletion.clone()))
+        .layer(Extension(config.instance.clone()))
+        .layer(Extension(aggregate_noise))
+        .layer(Extension(api_token_quotas))
+        .layer(Extension(device_usage))
         .layer(Extension(build_info))
+        .layer(Extension(changelog))
         .layer(Extension(pool.clone()))
         .layer(Extension(engine_registry))
         .layer(Extension(engine_ctx))
+        .layer(Extension(event_publisher))
+        .layer(Extension(notification_repo))
+        .layer(Extension(topic_repo))
+        .layer(Extension(feed_service))
+        .layer(Extension(research_repo))
+        .layer(Extension(integrity_repo))
+        .layer(Extension(developer_repo))
+        .layer(Extension(
+            Arc::new(PgStatsRepo::new(reader_pool)) as Arc<dyn StatsRepo>
+        ))
         .layer(Extension(Arc::new(NoopFilter) as Arc<dyn ContentFilter>));
 
     // Add ID.me config extension if configured
@@ -319,19 +449,36 @@ async fn build_app(
         app
     };
 
-    let app = app.layer(
-        CorsLayer::new()
-            .allow_methods([
-                Method::GET,
-                Method::POST,
-                Method::PUT,
-                Method::DELETE,
-                Method::PATCH,
-                Method::OPTIONS,
-            ])
-            .allow_headers(Any)
-            .allow_origin(allow_origin),
-    );
+    // Email and phone code delivery (disabled unless TC_VERIFICATION__SENDER is set)
+    let app = match reputation::verification::build_sender(&config.verification) {
+        Some(sender) => app.layer(Extension(sender)),
+        None => app,
+    };
+
+    // SMS for step-up confirmation (disabled unless TC_SMS__PROVIDER is set)
+    let app = match sms::build_provider(&config.sms) {
+        Some(provider) => app.layer(Extension(provider)),
+        None => app,
+    };
+
+    // Blocks callers that keep failing signature checks; off when
+    // auth.failure_threshold is 0
+    let app = if let Some(guard) = SignatureFailureGuard::from_config(&config.auth) {
+        let guard = guard.with_trust_forwarded_for(config.dev_tools.trust_forwarded_for);
+        app.layer(Extension(Arc::new(guard)))
+    } else {
+        tracing::warn!("Signature failure blocking disabled (auth.failure_threshold = 0)");
+        app
+    };
+
+    let app = app.layer(build_cors_layer(&config.cors));
+
+    // Third-party verification and the discovery document carry their own
+    // any-origin CORS policy, so they are merged after the global CORS layer
+    // rather than beneath it.
+    let app =
+        app.merge(identity::http::verify::router(&config.rate_limit).layer(Extension(verify_repo)));
+    let app = app.merge(instance::router().layer(Extension(instance_document)));
 
     // Apply a global body size cap before any route handler reads the body.
     // Unauthenticated endpoints (signup, login, GraphQL) previously had no
@@ -341,32 +488,105 @@ async fn build_app(
     // apply the tighter 64 KiB cap inside AuthenticatedDevice::from_request.
     let app = app.layer(DefaultBodyLimit::max(1024 * 1024));
 
+    #[cfg(feature = "sql-audit")]
+    let app = {
+        tracing::warn!("sql-audit enabled: queries containing request input are refused");
+        app.layer(middleware::from_fn(db::sql_audit::trace_request_inputs))
+    };
+
+    // Answer mutations with 503 while a failover leaves the database
+    // refusing writes, rather than failing them inside handlers.
+    let app = if config.read_only.enabled {
+        let mode = Arc::new(ReadOnlyMode::new(&config.read_only));
+        let monitor = ReadOnlyMonitor::new(pool.clone(), Arc::clone(&mode), &config.read_only);
+        tokio::spawn(monitor.run());
+        app.layer(middleware::from_fn(reject_writes_when_read_only))
+            .layer(Extension(mode))
+    } else {
+        tracing::info!("Read-only failover mode disabled");
+        app
+    };
+
+    // Shed bursts before they queue on the database pool. Inside the
+    // Prometheus layer so shed 503s still show up in request metrics.
+    let app = if let Some(limits) = ConcurrencyLimits::from_config(&config.concurrency) {
+        tracing::info!(
+            expensive_limit = config.concurrency.expensive_limit,
+            default_limit = config.concurrency.default_limit,
+            "Concurrency limits enabled"
+        );
+        app.layer(middleware::from_fn(limit_concurrency))
+            .layer(Extension(Arc::new(limits)))
+    } else {
+        tracing::info!("Concurrency limits disabled");
+        app
+    };
+
     let app = app.layer(prometheus_layer);
 
     // Spawn trust background worker
-    let trust_worker = Arc::new(TrustWorker::new(
-        pool.clone(),
-        trust_repo_for_worker,
-        reputation_repo_for_worker,
-        trust_engine,
-    ));
+    let trust_worker = Arc::new(
+        TrustWorker::new(
+            pool.clone(),
+            trust_repo_for_worker,
+            reputation_repo_for_worker,
+            trust_engine,
+        )
+        .with_distances(trust_distances),
+    );
     tokio::spawn(async move { trust_worker.run().await });
 
     Ok((app, pool))
 }
 
+/// Create the GraphQL schema, with cost budgets unless disabled.
+fn build_schema(
+    config: &Config,
+    pool: &PgPool,
+    reader_pool: &PgPool,
+    build_info: &BuildInfo,
+    aggregate_noise: &Arc<AggregateNoise>,
+) -> ApiSchema {
+    let mut schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(pool.clone()) // Pass the database pool to the schema
+        .data(build_info.clone())
+        .data(Arc::new(PgStatsRepo::new(reader_pool.clone())) as Arc<dyn StatsRepo>)
+        .data(Arc::new(PgNotificationRepo::new(pool.clone())) as Arc<dyn NotificationRepo>)
+        .data(Arc::clone(aggregate_noise))
+        .limit_complexity(config.graphql.max_complexity);
+    if config.graphql.cost_budgets.enabled {
+        schema = schema.extension(CostBudgets::new(
+            config.graphql.cost_budgets,
+            Arc::new(PgGraphQLCostRepo::new(pool.clone())),
+            Arc::new(ReputationTierSource::new(
+                Arc::new(PgReputationRepo::new(pool.clone())),
+                Arc::new(PgTrustRepo::new(pool.clone())),
+                config.reputation.clone(),
+            )),
+        ));
+    } else {
+        tracing::info!("GraphQL cost budgets disabled (TC_GRAPHQL__COST_BUDGETS__ENABLED=false)");
+    }
+    schema.finish()
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     // Load and validate configuration first (fail-fast)
     let config = Config::load().map_err(|e| anyhow::anyhow!("{e}"))?;
 
+    // Error reporting is optional; the no-op reporter is used when disabled.
+    let build_info = BuildInfo::from_env();
+    let reporter = error_reporting::build_reporter(&config.error_reporting, &build_info)?;
+    error_reporting::install_panic_hook(reporter.clone());
+
     // Set up logging from config
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_new(&config.logging.level).map_err(|e| {
-                anyhow::anyhow!("invalid log level '{}': {e}", config.logging.level)
-            })?,
-        )
+    let env_filter = tracing_subscriber::EnvFilter::try_new(&config.logging.level)
+        .map_err(|e| anyhow::anyhow!("invalid log level '{}': {e}", config.logging.level))?;
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(error_reporting::ErrorReportingLayer::new(reporter))
         .init();
 
     // Init banner so container logs clearly show startup
@@ -378,22 +598,28 @@ async fn main() -> Result<(), anyhow::Error> {
     // Database connection
     tracing::info!("Connecting to database...");
     let pool = setup_database(&config.database).await?;
+    db::set_explain_queries(config.database.explain_queries);
+    // Read-only paths use the SELECT-only reader role when one is configured
+    let reader_pool = db::connect_reader(&config.database)
+        .await?
+        .unwrap_or_else(|| pool.clone());
 
-    let build_info = BuildInfo::from_env();
     tracing::info!(
+        error_reporting = config.error_reporting.enabled,
         version = %build_info.version,
         git_sha = %build_info.git_sha,
         build_time = %build_info.build_time,
         "resolved build metadata"
     );
 
-    // Create the GraphQL schema
-    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
-        .data(pool.clone()) // Pass the database pool to the schema
-        .data(build_info.clone())
-        .finish();
+    // Noise for public aggregates (no-op unless TC_PRIVACY__ENABLED=true)
+    let aggregate_noise = Arc::new(AggregateNoise::from_config(
+        &config.privacy,
+        &config.accounts,
+        config.synthetic_backup_key.as_bytes(),
+    ));
 
-    let allow_origin = build_cors_origin(&config.cors.allowed_origins);
+    let schema = build_schema(&config, &pool, &reader_pool, &build_info, &aggregate_noise);
 
     // Build security headers layer if enabled
     let security_headers = if config.security_headers.enabled {
@@ -406,21 +632,41 @@ async fn main() -> Result<(), anyhow::Error> {
 
     // Service wiring (engine background tasks — including the lifecycle
     // consumer — are started inside build_app via PollingEngine::start())
-    let (app, pool_for_cleanup) =
-        build_app(&config, pool.clone(), build_info, schema, allow_origin).await?;
+    let (app, pool_for_cleanup) = build_app(
+        &config,
+        pool.clone(),
+        reader_pool,
+        build_info,
+        schema,
+        aggregate_noise,
+    )
+    .await?;
     let mut app = app;
 
-    spawn_nonce_cleanup(pool_for_cleanup);
-
-    // Add Swagger UI if enabled (disabled by default for security).
-    // Must be merged before the security headers layer so swagger routes are
-    // covered by the same CSP / X-Frame-Options headers as all other routes.
-    if config.swagger.enabled {
-        tracing::info!("Swagger UI enabled at /swagger-ui");
+    // Check JSON responses against the OpenAPI document (development only).
+    // Sits inside compression so it sees uncompressed bodies.
+    if config.dev_tools.validate_responses {
+        tracing::warn!("Response schema validation enabled - not for production");
+        let doc = serde_json::to_value(ApiDoc::openapi())?;
         app = app
-            .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()));
+            .layer(middleware::from_fn(validate_responses))
+            .layer(Extension(Arc::new(ResponseSchemas::new(doc))));
+    }
+
+    stats::worker::spawn_rollup_job(pool_for_cleanup.clone());
+    retention::spawn_retention_worker(pool_for_cleanup, &config.retention, &config.auth);
+
+    // Compress responses for clients that ask for it (swagger included).
+    if config.compression.enabled {
+        tracing::info!(
+            gzip = config.compression.gzip,
+            br = config.compression.br,
+            min_size_bytes = config.compression.min_size_bytes,
+            "Response compression enabled"
+        );
+        app = app.layer(build_compression_layer(&config.compression));
     } else {
-        tracing::info!("Swagger UI disabled (enable via TC_SWAGGER__ENABLED=true)");
+        tracing::info!("Response compression disabled");
     }
 
     // Add security headers middleware if enabled (outermost layer — applies to
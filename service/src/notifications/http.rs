@@ -0,0 +1,274 @@
+//! HTTP handlers for the notification inbox and preferences.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::repo::{NotificationRecord, NotificationRepo, NotificationRepoError};
+use super::NotificationCategory;
+use crate::http::{bad_request, internal_error, not_found, Path};
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::pagination::{finish_page, PageQuery};
+
+// ─── Request types ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationFilter {
+    /// Only return unread notifications (default false).
+    pub unread_only: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreference {
+    pub category: NotificationCategory,
+    pub enabled: bool,
+}
+
+/// Preferences to change; categories not listed are left as they are.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePreferencesRequest {
+    pub preferences: Vec<NotificationPreference>,
+}
+
+// ─── Response types ────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub category: String,
+    pub kind: String,
+    /// Kind-specific identifiers (e.g. `device_kid`, `endorser_id`)
+    pub payload: serde_json::Value,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+impl From<NotificationRecord> for NotificationResponse {
+    fn from(r: NotificationRecord) -> Self {
+        Self {
+            id: r.id,
+            category: r.category,
+            kind: r.kind,
+            payload: r.payload,
+            created_at: r.created_at.to_rfc3339(),
+            read_at: r.read_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationsResponse {
+    /// Newest first
+    pub notifications: Vec<NotificationResponse>,
+    /// Unread notifications across the whole inbox, not just this page
+    pub unread_count: i64,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+    /// Snapshot instant shared by every page of this walk
+    pub as_of: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarkReadResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub read_at: String,
+}
+
+/// Every category with its effective setting.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreferencesResponse {
+    pub preferences: Vec<NotificationPreference>,
+}
+
+// ─── Router ────────────────────────────────────────────────────────────────
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/notifications", get(list_notifications))
+        .route("/notifications/{id}/read", post(mark_read))
+        .route(
+            "/notifications/preferences",
+            get(get_preferences).put(update_preferences),
+        )
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// List the caller's notifications with keyset pagination.
+#[utoipa::path(
+    get,
+    path = "/notifications",
+    tag = "Notifications",
+    params(PageQuery, NotificationFilter),
+    responses(
+        (status = 200, description = "One page of notifications, newest first", body = NotificationsResponse),
+        (status = 400, description = "Invalid pagination parameters"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn list_notifications(
+    Extension(repo): Extension<Arc<dyn NotificationRepo>>,
+    Query(query): Query<PageQuery>,
+    Query(filter): Query<NotificationFilter>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let page = match query.resolve(Utc::now()) {
+        Ok(page) => page,
+        Err(msg) => return bad_request(&msg),
+    };
+    let rows = match repo
+        .list_notifications_page(auth.account_id, filter.unread_only.unwrap_or(false), &page)
+        .await
+    {
+        Ok(rows) => rows,
+        Err(ref e) => return notification_repo_error_response(e),
+    };
+    let unread_count = match repo.count_unread(auth.account_id).await {
+        Ok(count) => count,
+        Err(ref e) => return notification_repo_error_response(e),
+    };
+    let (rows, next_cursor) = finish_page(&page, rows, |r| (r.created_at, r.id));
+    (
+        StatusCode::OK,
+        Json(NotificationsResponse {
+            notifications: rows.into_iter().map(NotificationResponse::from).collect(),
+            unread_count,
+            next_cursor,
+            as_of: page.as_of.to_rfc3339(),
+        }),
+    )
+        .into_response()
+}
+
+/// Mark one of the caller's notifications as read. Idempotent.
+#[utoipa::path(
+    post,
+    path = "/notifications/{id}/read",
+    tag = "Notifications",
+    params(("id" = Uuid, Path, description = "Notification ID")),
+    responses(
+        (status = 200, description = "Notification marked read", body = MarkReadResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn mark_read(
+    Extension(repo): Extension<Arc<dyn NotificationRepo>>,
+    Path(id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.mark_read(auth.account_id, id).await {
+        Ok(read_at) => (
+            StatusCode::OK,
+            Json(MarkReadResponse {
+                id,
+                read_at: read_at.to_rfc3339(),
+            }),
+        )
+            .into_response(),
+        Err(ref e) => notification_repo_error_response(e),
+    }
+}
+
+/// Per-category notification settings for the caller.
+#[utoipa::path(
+    get,
+    path = "/notifications/preferences",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Notification preferences", body = NotificationPreferencesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn get_preferences(
+    Extension(repo): Extension<Arc<dyn NotificationRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    preferences_response(repo.as_ref(), auth.account_id).await
+}
+
+/// Enable or disable notification categories for the caller.
+///
+/// Disabling a category stops new notifications of that category; existing
+/// ones stay in the inbox.
+#[utoipa::path(
+    put,
+    path = "/notifications/preferences",
+    tag = "Notifications",
+    request_body = UpdatePreferencesRequest,
+    responses(
+        (status = 200, description = "Updated notification preferences", body = NotificationPreferencesResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn update_preferences(
+    Extension(repo): Extension<Arc<dyn NotificationRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: UpdatePreferencesRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    for pref in body.preferences {
+        if let Err(ref e) = repo
+            .set_preference(auth.account_id, pref.category, pref.enabled)
+            .await
+        {
+            return notification_repo_error_response(e);
+        }
+    }
+    preferences_response(repo.as_ref(), auth.account_id).await
+}
+
+async fn preferences_response(
+    repo: &dyn NotificationRepo,
+    account_id: Uuid,
+) -> axum::response::Response {
+    let stored = match repo.list_preferences(account_id).await {
+        Ok(rows) => rows,
+        Err(ref e) => return notification_repo_error_response(e),
+    };
+    let preferences = NotificationCategory::ALL
+        .into_iter()
+        .map(|category| NotificationPreference {
+            category,
+            enabled: stored
+                .iter()
+                .find(|(name, _)| name == category.as_str())
+                .is_none_or(|(_, enabled)| *enabled),
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(NotificationPreferencesResponse { preferences }),
+    )
+        .into_response()
+}
+
+fn notification_repo_error_response(e: &NotificationRepoError) -> axum::response::Response {
+    match e {
+        NotificationRepoError::NotFound => not_found("Notification not found"),
+        NotificationRepoError::Database(inner) => {
+            tracing::error!("Notification repo database error: {inner}");
+            internal_error()
+        }
+    }
+}
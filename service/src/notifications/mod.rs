@@ -0,0 +1,82 @@
+//! Per-account notification inbox.
+//!
+//! The [`projector::NotificationProjector`] turns selected domain events into
+//! inbox rows for the affected account. Users page through their inbox via
+//! `GET /notifications`, mark entries read, and opt out of whole categories;
+//! GraphQL exposes the unread count for signed callers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub mod http;
+pub mod projector;
+pub mod repo;
+
+/// Notification category, the unit of per-user preferences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// Changes to the account's devices and keys.
+    Security,
+    /// Endorsements and other trust-graph activity involving the account.
+    Trust,
+}
+
+impl NotificationCategory {
+    /// Every category, in display order.
+    pub const ALL: [Self; 2] = [Self::Security, Self::Trust];
+
+    /// Value stored in the `category` columns.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Security => "security",
+            Self::Trust => "trust",
+        }
+    }
+}
+
+impl fmt::Display for NotificationCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown category name.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown notification category")]
+pub struct UnknownCategory;
+
+impl FromStr for NotificationCategory {
+    type Err = UnknownCategory;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.as_str() == s)
+            .ok_or(UnknownCategory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_round_trips_through_str() {
+        for category in NotificationCategory::ALL {
+            assert_eq!(
+                category.as_str().parse::<NotificationCategory>().ok(),
+                Some(category)
+            );
+            assert_eq!(
+                serde_json::to_value(category).unwrap(),
+                serde_json::json!(category.as_str())
+            );
+        }
+        assert!("marketing".parse::<NotificationCategory>().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Projects domain events into notification inboxes.
+//!
+//! [`NotificationProjector`] wraps the configured [`EventPublisher`]: every
+//! event is still forwarded to it unchanged, and events that concern a
+//! specific account are also queued for a background task that writes the
+//! inbox row. Like event publishing, this never blocks or fails the request
+//! path; if the queue is full the notification is dropped with a warning.
+
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use super::repo::{NewNotification, NotificationRepo};
+use super::NotificationCategory;
+use crate::events::{DomainEvent, EventPublisher};
+
+/// Notifications buffered between the request path and the writer task.
+pub const PROJECTOR_BUFFER: usize = 1024;
+
+/// The inbox entry an event produces, if any.
+///
+/// Payloads follow the [`DomainEvent`] rule: identifiers only.
+#[must_use]
+pub fn notification_for(event: &DomainEvent) -> Option<NewNotification> {
+    match event {
+        DomainEvent::DeviceRevoked {
+            account_id,
+            device_kid,
+        } => Some(NewNotification {
+            account_id: *account_id,
+            category: NotificationCategory::Security,
+            kind: "device_revoked",
+            payload: json!({ "device_kid": device_kid.to_string() }),
+        }),
+        DomainEvent::EndorsementQueued {
+            endorser_id,
+            subject_id,
+            weight,
+        } => Some(NewNotification {
+            account_id: *subject_id,
+            category: NotificationCategory::Trust,
+            kind: "endorsement_received",
+            payload: json!({ "endorser_id": endorser_id, "weight": weight }),
+        }),
+        DomainEvent::EndorsementDisputed {
+            endorsement_id,
+            subject_id,
+            issuer_id,
+        } => Some(NewNotification {
+            account_id: *issuer_id,
+            category: NotificationCategory::Trust,
+            kind: "endorsement_disputed",
+            payload: json!({ "endorsement_id": endorsement_id, "subject_id": subject_id }),
+        }),
+        DomainEvent::AccountCreated { .. }
+        | DomainEvent::BallotCast { .. }
+        | DomainEvent::EndorsementCreated { .. }
+        | DomainEvent::SignatureFailuresBlocked { .. } => None,
+    }
+}
+
+/// [`EventPublisher`] that also writes inbox notifications.
+pub struct NotificationProjector {
+    inner: Arc<dyn EventPublisher>,
+    tx: mpsc::Sender<NewNotification>,
+}
+
+impl NotificationProjector {
+    /// Wrap `inner` and spawn the task that stores notifications via `repo`.
+    #[must_use]
+    pub fn spawn(inner: Arc<dyn EventPublisher>, repo: Arc<dyn NotificationRepo>) -> Self {
+        let (tx, mut rx) = mpsc::channel::<NewNotification>(PROJECTOR_BUFFER);
+        tokio::spawn(async move {
+            while let Some(notification) = rx.recv().await {
+                if let Err(e) = repo.insert_notification(&notification).await {
+                    tracing::warn!(
+                        kind = notification.kind,
+                        error = %e,
+                        "Failed to store notification"
+                    );
+                }
+            }
+        });
+        Self { inner, tx }
+    }
+}
+
+impl EventPublisher for NotificationProjector {
+    fn publish(&self, event: DomainEvent) {
+        if let Some(notification) = notification_for(&event) {
+            if let Err(e) = self.tx.try_send(notification) {
+                tracing::warn!(error = %e, "Notification buffer full or closed; dropping notification");
+            }
+        }
+        self.inner.publish(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tc_crypto::Kid;
+    use uuid::Uuid;
+
+    #[test]
+    fn events_notify_the_affected_account() {
+        let account_id = Uuid::new_v4();
+        let revoked = notification_for(&DomainEvent::DeviceRevoked {
+            account_id,
+            device_kid: Kid::derive(&[1u8; 32]),
+        })
+        .unwrap();
+        assert_eq!(revoked.account_id, account_id);
+        assert_eq!(revoked.category, NotificationCategory::Security);
+
+        let endorser_id = Uuid::new_v4();
+        let endorsed = notification_for(&DomainEvent::EndorsementQueued {
+            endorser_id,
+            subject_id: account_id,
+            weight: 1.0,
+        })
+        .unwrap();
+        assert_eq!(endorsed.account_id, account_id);
+        assert_eq!(endorsed.category, NotificationCategory::Trust);
+        assert_eq!(endorsed.payload["endorser_id"], endorser_id.to_string());
+
+        let disputed = notification_for(&DomainEvent::EndorsementDisputed {
+            endorsement_id: Uuid::new_v4(),
+            subject_id: account_id,
+            issuer_id: endorser_id,
+        })
+        .unwrap();
+        assert_eq!(disputed.account_id, endorser_id);
+        assert_eq!(disputed.kind, "endorsement_disputed");
+    }
+
+    #[test]
+    fn events_without_a_recipient_are_skipped() {
+        assert!(notification_for(&DomainEvent::BallotCast {
+            room_id: Uuid::nil(),
+            poll_id: Uuid::nil(),
+        })
+        .is_none());
+    }
+}
@@ -0,0 +1,308 @@
+//! Persistence for notifications and per-category preferences
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::NotificationCategory;
+use crate::db::{RepoError, RepoErrorKind};
+use crate::pagination::KeysetPage;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// One inbox entry.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct NotificationRecord {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub category: String,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+/// A notification to deliver, before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewNotification {
+    pub account_id: Uuid,
+    pub category: NotificationCategory,
+    pub kind: &'static str,
+    pub payload: serde_json::Value,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum NotificationRepoError {
+    #[error("notification not found")]
+    NotFound,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for NotificationRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// Store `notification` unless its recipient has disabled the category.
+///
+/// Returns the new row's ID, or `None` when the category is disabled.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn insert_notification(
+    pool: &PgPool,
+    notification: &NewNotification,
+) -> Result<Option<Uuid>, NotificationRepoError> {
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r"
+        INSERT INTO notifications (account_id, category, kind, payload)
+        SELECT $1, $2, $3, $4
+        WHERE NOT EXISTS (
+            SELECT 1 FROM notification_preferences
+            WHERE account_id = $1 AND category = $2 AND NOT enabled
+        )
+        RETURNING id
+        ",
+    )
+    .bind(notification.account_id)
+    .bind(notification.category.as_str())
+    .bind(notification.kind)
+    .bind(&notification.payload)
+    .fetch_optional(pool)
+    .await?;
+    Ok(id)
+}
+
+/// One keyset page of an account's notifications, newest first.
+///
+/// Returns up to `page.fetch_limit()` rows; see
+/// [`crate::pagination::finish_page`].
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_notifications_page(
+    pool: &PgPool,
+    account_id: Uuid,
+    unread_only: bool,
+    page: &KeysetPage,
+) -> Result<Vec<NotificationRecord>, NotificationRepoError> {
+    let (after_created_at, after_id) = page.after.unzip();
+    let rows = sqlx::query_as::<_, NotificationRecord>(
+        r"
+        SELECT id, account_id, category, kind, payload, created_at, read_at
+        FROM notifications
+        WHERE account_id = $1
+          AND created_at <= $2
+          AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+          AND (NOT $5 OR read_at IS NULL)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $6
+        ",
+    )
+    .bind(account_id)
+    .bind(page.as_of)
+    .bind(after_created_at)
+    .bind(after_id)
+    .bind(unread_only)
+    .bind(page.fetch_limit())
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Number of unread notifications for an account.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn count_unread(pool: &PgPool, account_id: Uuid) -> Result<i64, NotificationRepoError> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE account_id = $1 AND read_at IS NULL",
+    )
+    .bind(account_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+/// Mark one of `account_id`'s notifications read. Idempotent: an already-read
+/// notification keeps its original `read_at`.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the notification does not exist or belongs to
+/// another account, `Database` on query failure.
+pub async fn mark_read(
+    pool: &PgPool,
+    account_id: Uuid,
+    notification_id: Uuid,
+) -> Result<DateTime<Utc>, NotificationRepoError> {
+    sqlx::query_scalar::<_, DateTime<Utc>>(
+        r"
+        UPDATE notifications
+        SET read_at = COALESCE(read_at, now())
+        WHERE id = $1 AND account_id = $2
+        RETURNING read_at
+        ",
+    )
+    .bind(notification_id)
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(NotificationRepoError::NotFound)
+}
+
+/// Categories the account has explicitly configured, as `(category, enabled)`.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_preferences(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<(String, bool)>, NotificationRepoError> {
+    let rows = sqlx::query_as::<_, (String, bool)>(
+        "SELECT category, enabled FROM notification_preferences \
+         WHERE account_id = $1 ORDER BY category",
+    )
+    .bind(account_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Enable or disable a category for an account.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn set_preference(
+    pool: &PgPool,
+    account_id: Uuid,
+    category: NotificationCategory,
+    enabled: bool,
+) -> Result<(), NotificationRepoError> {
+    sqlx::query(
+        r"
+        INSERT INTO notification_preferences (account_id, category, enabled)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (account_id, category)
+            DO UPDATE SET enabled = EXCLUDED.enabled, updated_at = now()
+        ",
+    )
+    .bind(account_id)
+    .bind(category.as_str())
+    .bind(enabled)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// Notification persistence for the projector and HTTP/GraphQL handlers.
+#[async_trait]
+pub trait NotificationRepo: Send + Sync {
+    async fn insert_notification(
+        &self,
+        notification: &NewNotification,
+    ) -> Result<Option<Uuid>, NotificationRepoError>;
+
+    async fn list_notifications_page(
+        &self,
+        account_id: Uuid,
+        unread_only: bool,
+        page: &KeysetPage,
+    ) -> Result<Vec<NotificationRecord>, NotificationRepoError>;
+
+    async fn count_unread(&self, account_id: Uuid) -> Result<i64, NotificationRepoError>;
+
+    async fn mark_read(
+        &self,
+        account_id: Uuid,
+        notification_id: Uuid,
+    ) -> Result<DateTime<Utc>, NotificationRepoError>;
+
+    async fn list_preferences(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<(String, bool)>, NotificationRepoError>;
+
+    async fn set_preference(
+        &self,
+        account_id: Uuid,
+        category: NotificationCategory,
+        enabled: bool,
+    ) -> Result<(), NotificationRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`NotificationRepo`].
+pub struct PgNotificationRepo {
+    pool: PgPool,
+}
+
+impl PgNotificationRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepo for PgNotificationRepo {
+    async fn insert_notification(
+        &self,
+        notification: &NewNotification,
+    ) -> Result<Option<Uuid>, NotificationRepoError> {
+        insert_notification(&self.pool, notification).await
+    }
+
+    async fn list_notifications_page(
+        &self,
+        account_id: Uuid,
+        unread_only: bool,
+        page: &KeysetPage,
+    ) -> Result<Vec<NotificationRecord>, NotificationRepoError> {
+        list_notifications_page(&self.pool, account_id, unread_only, page).await
+    }
+
+    async fn count_unread(&self, account_id: Uuid) -> Result<i64, NotificationRepoError> {
+        count_unread(&self.pool, account_id).await
+    }
+
+    async fn mark_read(
+        &self,
+        account_id: Uuid,
+        notification_id: Uuid,
+    ) -> Result<DateTime<Utc>, NotificationRepoError> {
+        mark_read(&self.pool, account_id, notification_id).await
+    }
+
+    async fn list_preferences(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<(String, bool)>, NotificationRepoError> {
+        list_preferences(&self.pool, account_id).await
+    }
+
+    async fn set_preference(
+        &self,
+        account_id: Uuid,
+        category: NotificationCategory,
+        enabled: bool,
+    ) -> Result<(), NotificationRepoError> {
+        set_preference(&self.pool, account_id, category, enabled).await
+    }
+}
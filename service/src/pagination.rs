@@ -0,0 +1,253 @@
+//! Keyset pagination with snapshot-consistent cursors.
+//!
+//! Offset pagination skips or repeats rows when new rows arrive between page
+//! requests. Pages here are anchored on `(created_at, id)` instead, newest
+//! first, and every page of a walk is read "as of" the same instant: rows
+//! created after `as_of` are excluded and revocations after `as_of` are not
+//! yet visible. The cursor carries `as_of`, so clients only need to pass it
+//! back unchanged.
+
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+/// Page size used when `limit` is omitted.
+pub const DEFAULT_PAGE_LIMIT: u32 = 50;
+
+/// Largest page a client may request.
+pub const MAX_PAGE_LIMIT: u32 = 200;
+
+/// Query parameters accepted by paginated list endpoints.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct PageQuery {
+    /// Page size (1–200, default 50).
+    pub limit: Option<u32>,
+    /// Opaque cursor from the previous page's `next_cursor`.
+    pub cursor: Option<String>,
+    /// RFC 3339 snapshot instant for the first page (default: now).
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+impl PageQuery {
+    /// `true` if the client asked for pagination at all.
+    #[must_use]
+    pub const fn is_paginated(&self) -> bool {
+        self.limit.is_some() || self.cursor.is_some() || self.as_of.is_some()
+    }
+
+    /// Resolve the query into a concrete page request.
+    ///
+    /// # Errors
+    ///
+    /// Returns a client-facing message if the limit is out of range, the
+    /// cursor is malformed, `as_of` is in the future, or both `cursor` and
+    /// `as_of` are supplied.
+    pub fn resolve(&self, now: DateTime<Utc>) -> Result<KeysetPage, String> {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+            return Err(format!("limit must be between 1 and {MAX_PAGE_LIMIT}"));
+        }
+
+        match (&self.cursor, self.as_of) {
+            (Some(_), Some(_)) => Err("cursor already carries as_of; send only one".to_string()),
+            (Some(cursor), None) => {
+                let (as_of, after) = decode_cursor(cursor).ok_or("invalid cursor")?;
+                Ok(KeysetPage {
+                    as_of,
+                    after: Some(after),
+                    limit,
+                })
+            }
+            (None, as_of) => {
+                let as_of = as_of.unwrap_or(now);
+                if as_of > now {
+                    return Err("as_of must not be in the future".to_string());
+                }
+                // Cursors and Postgres keep microseconds; truncate so every
+                // page of a walk reports the same as_of.
+                Ok(KeysetPage {
+                    as_of: as_of.trunc_subsecs(6),
+                    after: None,
+                    limit,
+                })
+            }
+        }
+    }
+}
+
+/// `(created_at, id)` key of the last row on the previous page.
+pub type KeysetAnchor = (DateTime<Utc>, Uuid);
+
+/// A resolved keyset page request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeysetPage {
+    /// Snapshot instant shared by every page of the walk.
+    pub as_of: DateTime<Utc>,
+    /// Exclusive `(created_at, id)` anchor; `None` for the first page.
+    pub after: Option<KeysetAnchor>,
+    pub limit: u32,
+}
+
+impl KeysetPage {
+    /// Rows to fetch: one past `limit` so callers can tell whether more exist.
+    #[must_use]
+    pub fn fetch_limit(&self) -> i64 {
+        i64::from(self.limit) + 1
+    }
+
+    /// Cursor for the page following a row with the given key.
+    #[must_use]
+    pub fn next_cursor(&self, created_at: DateTime<Utc>, id: Uuid) -> String {
+        tc_crypto::encode_base64url(
+            format!(
+                "{}:{}:{id}",
+                self.as_of.timestamp_micros(),
+                created_at.timestamp_micros()
+            )
+            .as_bytes(),
+        )
+    }
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime<Utc>, KeysetAnchor)> {
+    let bytes = tc_crypto::decode_base64url(cursor).ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let mut parts = text.splitn(3, ':');
+    let as_of = DateTime::from_timestamp_micros(parts.next()?.parse().ok()?)?;
+    let created_at = DateTime::from_timestamp_micros(parts.next()?.parse().ok()?)?;
+    let id = parts.next()?.parse().ok()?;
+    Some((as_of, (created_at, id)))
+}
+
+/// Trim a `fetch_limit()`-sized result to `limit` rows and build the cursor
+/// for the next page, if there is one.
+pub fn finish_page<T>(
+    page: &KeysetPage,
+    mut rows: Vec<T>,
+    key: impl Fn(&T) -> KeysetAnchor,
+) -> (Vec<T>, Option<String>) {
+    let limit = usize::try_from(page.limit).unwrap_or(usize::MAX);
+    if rows.len() <= limit {
+        return (rows, None);
+    }
+    rows.truncate(limit);
+    let next = rows.last().map(|row| {
+        let (created_at, id) = key(row);
+        page.next_cursor(created_at, id)
+    });
+    (rows, next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap_or_default()
+    }
+
+    #[test]
+    fn unpaginated_query_is_detected() {
+        assert!(!PageQuery::default().is_paginated());
+        let query = PageQuery {
+            limit: Some(10),
+            ..PageQuery::default()
+        };
+        assert!(query.is_paginated());
+    }
+
+    #[test]
+    fn first_page_defaults_as_of_to_now() {
+        let page = PageQuery::default().resolve(at(100)).expect("resolve");
+        assert_eq!(page.as_of, at(100));
+        assert_eq!(page.after, None);
+        assert_eq!(page.limit, DEFAULT_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn as_of_survives_the_cursor_unchanged() {
+        let now = DateTime::from_timestamp(100, 123_456_789).unwrap_or_default();
+        let first = PageQuery::default().resolve(now).expect("resolve");
+        let next = PageQuery {
+            cursor: Some(first.next_cursor(at(40), Uuid::new_v4())),
+            ..PageQuery::default()
+        }
+        .resolve(now)
+        .expect("resolve");
+        assert_eq!(next.as_of, first.as_of);
+    }
+
+    #[test]
+    fn cursor_round_trips_as_of_and_anchor() {
+        let first = PageQuery {
+            as_of: Some(at(50)),
+            limit: Some(2),
+            ..PageQuery::default()
+        }
+        .resolve(at(100))
+        .expect("resolve");
+        let id = Uuid::new_v4();
+        let cursor = first.next_cursor(at(40), id);
+
+        let next = PageQuery {
+            cursor: Some(cursor),
+            limit: Some(2),
+            ..PageQuery::default()
+        }
+        .resolve(at(200))
+        .expect("resolve");
+        assert_eq!(next.as_of, at(50));
+        assert_eq!(next.after, Some((at(40), id)));
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        let now = at(100);
+        for query in [
+            PageQuery {
+                limit: Some(0),
+                ..PageQuery::default()
+            },
+            PageQuery {
+                limit: Some(MAX_PAGE_LIMIT + 1),
+                ..PageQuery::default()
+            },
+            PageQuery {
+                cursor: Some("not-a-cursor".to_string()),
+                ..PageQuery::default()
+            },
+            PageQuery {
+                as_of: Some(at(101)),
+                ..PageQuery::default()
+            },
+            PageQuery {
+                cursor: Some(tc_crypto::encode_base64url(b"1:2:x")),
+                as_of: Some(at(1)),
+                ..PageQuery::default()
+            },
+        ] {
+            assert!(query.resolve(now).is_err(), "{query:?}");
+        }
+    }
+
+    #[test]
+    fn finish_page_emits_cursor_only_when_more_rows_exist() {
+        let page = PageQuery {
+            limit: Some(2),
+            ..PageQuery::default()
+        }
+        .resolve(at(100))
+        .expect("resolve");
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let key = |id: &Uuid| (at(10), *id);
+
+        let (rows, next) = finish_page(&page, ids.clone(), key);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(next, Some(page.next_cursor(at(10), ids[1])));
+
+        let (rows, next) = finish_page(&page, ids[..2].to_vec(), key);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(next, None);
+    }
+}
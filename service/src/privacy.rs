@@ -0,0 +1,162 @@
+//! Differential-privacy noise for publicly exposed aggregate counts.
+//!
+//! When `privacy.enabled` is set, counts served to the public (instance
+//! stats, topic movements, badges) go through [`AggregateNoise::count`]:
+//! counts below `min_count` are suppressed to zero, and the rest get Laplace
+//! noise with scale `1 / epsilon` (each count has sensitivity 1).
+//!
+//! Noise is derived from a keyed hash of the count's label and exact value
+//! rather than sampled per request, so asking the same question repeatedly
+//! returns the same answer and averaging reveals nothing. The key is derived
+//! from `synthetic_backup_key`, which is already secret and stable.
+//!
+//! Exact values stay available to admins (signed `GET /api/v1/stats`
+//! and `GET /topics/feed`) and to the subject of a badge (signed
+//! `GET /badges/{username}.json`).
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::config::{AccountsConfig, PrivacyConfig};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separator for deriving the noise key from `synthetic_backup_key`.
+const NOISE_KEY_LABEL: &[u8] = b"tc-aggregate-noise-v1";
+
+/// Noise applied to public aggregate counts.
+pub struct AggregateNoise {
+    enabled: bool,
+    scale: f64,
+    min_count: i64,
+    key: [u8; 32],
+    admin_account_ids: Vec<Uuid>,
+}
+
+impl AggregateNoise {
+    /// Build from config, deriving the noise key from `secret`. Admins in
+    /// `accounts` see exact values.
+    #[must_use]
+    pub fn from_config(config: &PrivacyConfig, accounts: &AccountsConfig, secret: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        if let Ok(mut mac) = HmacSha256::new_from_slice(secret) {
+            mac.update(NOISE_KEY_LABEL);
+            key.copy_from_slice(&mac.finalize().into_bytes());
+        }
+        Self {
+            enabled: config.enabled,
+            scale: 1.0 / config.epsilon,
+            min_count: i64::from(config.min_count),
+            key,
+            admin_account_ids: accounts.admin_account_ids.clone(),
+        }
+    }
+
+    /// Whether noise is applied at all.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether `account_id` sees exact aggregates.
+    #[must_use]
+    pub fn is_admin(&self, account_id: Uuid) -> bool {
+        self.admin_account_ids.contains(&account_id)
+    }
+
+    /// The public value of the count `exact` identified by `label`.
+    ///
+    /// `label` must name the aggregate uniquely (e.g. `stats:2026-01-02:signups`)
+    /// so that unrelated counts get independent noise. Returns `exact`
+    /// unchanged when noise is disabled; never returns a negative count.
+    #[must_use]
+    pub fn count(&self, label: &str, exact: i64) -> i64 {
+        if !self.enabled {
+            return exact;
+        }
+        if exact < self.min_count {
+            return 0;
+        }
+        let Ok(mut mac) = HmacSha256::new_from_slice(&self.key) else {
+            return 0;
+        };
+        mac.update(label.as_bytes());
+        mac.update(&[0]);
+        mac.update(&exact.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+        let mut bits = [0u8; 8];
+        bits.copy_from_slice(&digest[..8]);
+        let noise = laplace(self.scale, uniform_open(u64::from_be_bytes(bits)));
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        let noisy = (exact as f64 + noise).round() as i64;
+        noisy.max(0)
+    }
+}
+
+/// Map 64 random bits to the open interval (0, 1).
+#[allow(clippy::cast_precision_loss)]
+fn uniform_open(bits: u64) -> f64 {
+    ((bits >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+/// Inverse CDF of the zero-centred Laplace distribution with scale `b`.
+fn laplace(b: f64, u: f64) -> f64 {
+    let centred = u - 0.5;
+    -b * centred.signum() * 2.0f64.mul_add(-centred.abs(), 1.0).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: Uuid = Uuid::from_u128(1);
+
+    fn noise(enabled: bool, epsilon: f64, min_count: u32) -> AggregateNoise {
+        AggregateNoise::from_config(
+            &PrivacyConfig {
+                enabled,
+                epsilon,
+                min_count,
+            },
+            &AccountsConfig {
+                admin_account_ids: vec![ALICE],
+            },
+            b"test-secret-at-least-thirty-two-bytes",
+        )
+    }
+
+    #[test]
+    fn disabled_noise_passes_counts_through() {
+        let n = noise(false, 1.0, 5);
+        assert_eq!(n.count("x", 3), 3);
+        assert!(n.is_admin(ALICE));
+        assert!(!n.is_admin(Uuid::from_u128(2)));
+    }
+
+    #[test]
+    fn small_counts_are_suppressed_and_noise_is_stable() {
+        let n = noise(true, 1.0, 5);
+        assert_eq!(n.count("stats:signups", 4), 0);
+        let first = n.count("stats:signups", 100);
+        assert_eq!(n.count("stats:signups", 100), first);
+        assert!(first >= 0);
+        // Different labels get independent noise
+        let spread: std::collections::HashSet<i64> = (0..50)
+            .map(|i| n.count(&format!("topic:{i}"), 100))
+            .collect();
+        assert!(spread.len() > 1);
+    }
+
+    #[test]
+    fn noise_shrinks_as_epsilon_grows() {
+        let total_error = |epsilon: f64| -> i64 {
+            let n = noise(true, epsilon, 0);
+            (0..200)
+                .map(|i| (n.count(&format!("c:{i}"), 1000) - 1000).abs())
+                .sum()
+        };
+        assert!(total_error(10.0) < total_error(0.1));
+        assert!(laplace(1.0, 0.5).abs() < f64::EPSILON);
+    }
+}
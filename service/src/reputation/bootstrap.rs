@@ -55,7 +55,7 @@ async fn ensure_verifier_account(
     let existing = sqlx::query_scalar::<_, Uuid>(
         "SELECT id FROM accounts WHERE root_kid = $1 OR username = $2",
     )
-    .bind(kid.as_str())
+    .bind(&kid)
     .bind(name)
     .fetch_optional(&mut *conn)
     .await?;
@@ -74,7 +74,7 @@ async fn ensure_verifier_account(
     .bind(id)
     .bind(name)
     .bind(public_key)
-    .bind(kid.as_str())
+    .bind(&kid)
     .fetch_one(&mut *conn)
     .await?;
 
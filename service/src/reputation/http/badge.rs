@@ -0,0 +1,424 @@
+//! Public reputation badges for embedding on external sites.
+//!
+//! `GET /badges/{username}.svg` renders a small shields-style image and
+//! `GET /badges/{username}.json` returns the same summary as JSON. Both are
+//! computed from aggregate endorsement counts and the account's verification
+//! level only — no endorser identities, evidence or verified addresses are
+//! exposed — and are served with public caching headers and
+//! an `ETag` so CDNs and browsers absorb most of the traffic. The route is
+//! rate-limited per IP by `rate_limit.badge_per_minute`.
+//!
+//! With `privacy.enabled` the counts carry differential-privacy noise. A
+//! request device-signed by the badge's subject (or an admin) gets the
+//! exact counts instead, marked `Cache-Control: private`.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use crate::http::{internal_error, not_found, Path};
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::identity::repo::{AccountRepoError, IdentityRepo};
+use crate::privacy::AggregateNoise;
+use crate::reputation::repo::{ReputationRepo, TopicCount};
+use crate::reputation::service::EndorsementService;
+use crate::reputation::verification::VerificationMethod;
+use crate::reputation::VERIFIED_TOPIC;
+
+/// Seconds a badge may be served from a shared cache before revalidation.
+pub const BADGE_MAX_AGE_SECS: u32 = 300;
+
+/// Badge label shown on the left-hand side of the SVG.
+const BADGE_LABEL: &str = "tiny congress";
+
+/// Requested badge representation, taken from the path suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeFormat {
+    Svg,
+    Json,
+}
+
+impl BadgeFormat {
+    const fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Split `alice.svg` / `alice.json` into the username and format.
+#[must_use]
+pub fn parse_badge_path(file: &str) -> Option<(&str, BadgeFormat)> {
+    let (username, format) = if let Some(name) = file.strip_suffix(".svg") {
+        (name, BadgeFormat::Svg)
+    } else if let Some(name) = file.strip_suffix(".json") {
+        (name, BadgeFormat::Json)
+    } else {
+        return None;
+    };
+    (!username.is_empty()).then_some((username, format))
+}
+
+/// Aggregate reputation summary rendered by the badge endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct BadgeResponse {
+    pub username: String,
+    /// Whether the account holds an active `identity_verified` endorsement.
+    pub verified: bool,
+    /// Active endorsements across all topics.
+    pub endorsement_count: i64,
+    /// Distinct topics with at least one active endorsement.
+    pub topic_count: i64,
+    /// Strongest verification method the account has completed.
+    pub verification_level: Option<VerificationMethod>,
+}
+
+impl BadgeResponse {
+    /// Build a summary from per-topic endorsement counts.
+    #[must_use]
+    pub fn from_topic_counts(username: &str, counts: &[TopicCount]) -> Self {
+        let topic_count = i64::try_from(counts.len()).unwrap_or(i64::MAX);
+        Self {
+            username: username.to_string(),
+            verified: counts
+                .iter()
+                .any(|c| c.topic == VERIFIED_TOPIC && c.count > 0),
+            endorsement_count: counts.iter().map(|c| c.count).sum(),
+            topic_count,
+            verification_level: None,
+        }
+    }
+
+    /// Right-hand text of the SVG badge.
+    #[must_use]
+    pub fn message(&self) -> String {
+        let endorsements = match self.endorsement_count {
+            0 => "no endorsements".to_string(),
+            1 => "1 endorsement".to_string(),
+            n => format!("{n} endorsements"),
+        };
+        match (self.verified, self.verification_level) {
+            (true, _) => format!("verified · {endorsements}"),
+            (false, Some(level)) => format!("{} verified · {endorsements}", level.label()),
+            (false, None) => endorsements,
+        }
+    }
+}
+
+/// Escape text for inclusion in SVG character data and attribute values.
+fn xml_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Approximate rendered width of `text` in 11px Verdana, plus padding.
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+/// Render the summary as a flat shields-style SVG.
+#[must_use]
+pub fn render_svg(badge: &BadgeResponse) -> String {
+    let message = badge.message();
+    let color = if badge.verified { "#2e7d32" } else { "#1565c0" };
+    let left = text_width(BADGE_LABEL);
+    let right = text_width(&message);
+    let width = left + right;
+    let title = xml_escape(&format!("@{}: {message}", badge.username));
+    let message = xml_escape(&message);
+    let label_x = left / 2;
+    let message_x = left + right / 2;
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" \
+         role=\"img\" aria-label=\"{title}\"><title>{title}</title>\
+         <rect width=\"{left}\" height=\"20\" fill=\"#555\"/>\
+         <rect x=\"{left}\" width=\"{right}\" height=\"20\" fill=\"{color}\"/>\
+         <g fill=\"#fff\" text-anchor=\"middle\" \
+         font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">\
+         <text x=\"{label_x}\" y=\"14\">{BADGE_LABEL}</text>\
+         <text x=\"{message_x}\" y=\"14\">{message}</text></g></svg>"
+    )
+}
+
+/// Strong validator derived from the response body.
+fn etag_for(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("\"{}\"", tc_crypto::encode_base64url(&digest[..16]))
+}
+
+/// `true` if an `If-None-Match` header already names `etag`.
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+        })
+}
+
+/// Wrap a rendered body with caching headers, or answer 304 when the
+/// client's cached copy is still current.
+fn cached_response(
+    headers: &HeaderMap,
+    content_type: &'static str,
+    body: Vec<u8>,
+    private: bool,
+) -> Response {
+    let etag = etag_for(&body);
+    let cache_control = if private {
+        "private, no-cache".to_string()
+    } else {
+        format!(
+            "public, max-age={BADGE_MAX_AGE_SECS}, stale-while-revalidate={}",
+            BADGE_MAX_AGE_SECS * 12
+        )
+    };
+    let mut response = if etag_matches(headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, HeaderValue::from_static(content_type))],
+            body,
+        )
+            .into_response()
+    };
+    let out = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        out.insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&cache_control) {
+        out.insert(header::CACHE_CONTROL, value);
+    }
+    // A standalone SVG must never run script, even when the global security
+    // header layer (which overwrites this with the site policy) is disabled.
+    out.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static("default-src 'none'; style-src 'unsafe-inline'"),
+    );
+    response
+}
+
+/// Permanent redirect from an alias badge to the canonical one. Cached for
+/// the same time as a badge, since the alias may later be removed.
+fn canonical_redirect(username: &str, format: BadgeFormat) -> Response {
+    let location = format!("/badges/{username}.{}", format.extension());
+    let Ok(location) = HeaderValue::from_str(&location) else {
+        return internal_error();
+    };
+    let cache_control = format!("public, max-age={BADGE_MAX_AGE_SECS}");
+    let mut response = StatusCode::MOVED_PERMANENTLY.into_response();
+    let out = response.headers_mut();
+    out.insert(header::LOCATION, location);
+    if let Ok(value) = HeaderValue::from_str(&cache_control) {
+        out.insert(header::CACHE_CONTROL, value);
+    }
+    response
+}
+
+/// Public reputation badge for an account.
+///
+/// The path segment is `{username}.svg` or `{username}.json`. An account
+/// alias redirects (301) to the badge for the canonical username. Responses
+/// carry `Cache-Control: public` and an `ETag`; a matching `If-None-Match`
+/// returns 304 without a body. When privacy noise is on, a request signed by
+/// the subject or an admin gets exact counts, cached privately.
+#[utoipa::path(
+    get,
+    path = "/badges/{file}",
+    tag = "reputation",
+    params(
+        ("file" = String, Path, description = "Username or alias followed by `.svg` or `.json`")
+    ),
+    responses(
+        (status = 200, description = "Badge rendered as SVG or JSON", body = BadgeResponse),
+        (status = 301, description = "Alias; redirects to the canonical username's badge"),
+        (status = 304, description = "Cached badge is still current"),
+        (status = 401, description = "Signed request failed verification"),
+        (status = 404, description = "Unknown account or badge format"),
+        (status = 429, description = "Too many badge requests"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_badge(
+    Extension(identity_repo): Extension<Arc<dyn IdentityRepo>>,
+    Extension(endorsements): Extension<Arc<dyn EndorsementService>>,
+    Extension(reputation): Extension<Arc<dyn ReputationRepo>>,
+    noise: Option<Extension<Arc<AggregateNoise>>>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+    auth: Option<AuthenticatedDevice>,
+) -> Response {
+    let Some((username, format)) = parse_badge_path(&file) else {
+        return not_found("Badge must end in .svg or .json");
+    };
+
+    let account = match identity_repo.resolve_account_handle(username).await {
+        Ok(resolved) if resolved.alias.is_some() => {
+            return canonical_redirect(&resolved.account.username, format);
+        }
+        Ok(resolved) => resolved.account,
+        Err(AccountRepoError::NotFound) => return not_found("Account not found"),
+        Err(e) => {
+            tracing::error!("Badge account lookup failed: {e}");
+            return internal_error();
+        }
+    };
+
+    let counts = match endorsements.endorsement_summary(account.id).await {
+        Ok(counts) => counts,
+        Err(e) => {
+            tracing::error!("Badge endorsement summary failed: {e}");
+            return internal_error();
+        }
+    };
+
+    let mut badge = BadgeResponse::from_topic_counts(&account.username, &counts);
+    badge.verification_level = match reputation.list_verifications(account.id).await {
+        Ok(verifications) => verifications.iter().map(|v| v.method).max(),
+        Err(e) => {
+            tracing::error!("Badge verification lookup failed: {e}");
+            return internal_error();
+        }
+    };
+    let noise = noise
+        .map(|Extension(noise)| noise)
+        .filter(|noise| noise.is_enabled());
+    let private = match (noise, auth) {
+        (None, _) => false,
+        (Some(_), Some(auth)) if auth.account_id == account.id => true,
+        (Some(noise), Some(auth)) if noise.is_admin(auth.account_id) => true,
+        (Some(noise), _) => {
+            badge.endorsement_count = noise.count(
+                &format!("badge:{}:endorsements", account.id),
+                badge.endorsement_count,
+            );
+            badge.topic_count =
+                noise.count(&format!("badge:{}:topics", account.id), badge.topic_count);
+            false
+        }
+    };
+    match format {
+        BadgeFormat::Svg => cached_response(
+            &headers,
+            "image/svg+xml; charset=utf-8",
+            render_svg(&badge).into_bytes(),
+            private,
+        ),
+        BadgeFormat::Json => match serde_json::to_vec(&badge) {
+            Ok(body) => cached_response(&headers, "application/json", body, private),
+            Err(e) => {
+                tracing::error!("Badge serialization failed: {e}");
+                internal_error()
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(pairs: &[(&str, i64)]) -> Vec<TopicCount> {
+        pairs
+            .iter()
+            .map(|(topic, count)| TopicCount {
+                topic: (*topic).to_string(),
+                count: *count,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_svg_and_json_suffixes() {
+        assert_eq!(
+            parse_badge_path("alice.svg"),
+            Some(("alice", BadgeFormat::Svg))
+        );
+        assert_eq!(
+            parse_badge_path("alice.json"),
+            Some(("alice", BadgeFormat::Json))
+        );
+        assert_eq!(parse_badge_path("alice.png"), None);
+        assert_eq!(parse_badge_path("alice"), None);
+        assert_eq!(parse_badge_path(".svg"), None);
+    }
+
+    #[test]
+    fn summary_aggregates_topic_counts() {
+        let badge = BadgeResponse::from_topic_counts(
+            "alice",
+            &counts(&[("identity_verified", 1), ("trust", 4)]),
+        );
+        assert!(badge.verified);
+        assert_eq!(badge.endorsement_count, 5);
+        assert_eq!(badge.topic_count, 2);
+        assert_eq!(badge.message(), "verified · 5 endorsements");
+    }
+
+    #[test]
+    fn unendorsed_account_message() {
+        let badge = BadgeResponse::from_topic_counts("bob", &[]);
+        assert!(!badge.verified);
+        assert_eq!(badge.message(), "no endorsements");
+        let one = BadgeResponse::from_topic_counts("bob", &counts(&[("trust", 1)]));
+        assert_eq!(one.message(), "1 endorsement");
+    }
+
+    #[test]
+    fn verification_level_shows_without_identity_endorsement() {
+        let mut badge = BadgeResponse::from_topic_counts("carol", &counts(&[("trust", 2)]));
+        badge.verification_level = Some(VerificationMethod::Phone);
+        assert_eq!(badge.message(), "phone verified · 2 endorsements");
+    }
+
+    #[test]
+    fn svg_escapes_username() {
+        let badge = BadgeResponse::from_topic_counts("<a&b>", &[]);
+        let svg = render_svg(&badge);
+        assert!(svg.contains("@&lt;a&amp;b&gt;"));
+        assert!(!svg.contains("<a&b>"));
+    }
+
+    #[test]
+    fn alias_redirects_to_canonical_badge() {
+        let response = canonical_redirect("alice", BadgeFormat::Json);
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers()[header::LOCATION], "/badges/alice.json");
+    }
+
+    #[test]
+    fn if_none_match_is_honoured() {
+        let etag = etag_for(b"badge");
+        let mut headers = HeaderMap::new();
+        assert!(!etag_matches(&headers, &etag));
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"other\", W/{etag}")).unwrap(),
+        );
+        assert!(etag_matches(&headers, &etag));
+        let response = cached_response(&headers, "application/json", b"badge".to_vec(), false);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(response.headers().contains_key(header::ETAG));
+    }
+}
@@ -0,0 +1,275 @@
+//! Endorsement disputes.
+//!
+//! The subject of an endorsement can contest it with a signed statement. The
+//! issuer is notified, and the subject may ask for the endorsement to be left
+//! out of public aggregates until an account moderator (see
+//! [`AccountsConfig`]) upholds or dismisses the dispute.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::AccountsConfig;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::reputation::repo::{DisputeRecord, DisputeRepoError, NewDispute, ReputationRepo};
+use crate::reputation::DisputeStatus;
+
+/// Longest accepted dispute statement or resolution note, in bytes.
+pub const MAX_DISPUTE_STATEMENT_LENGTH: usize = 2000;
+
+// ─── Request / response types ─────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DisputeEndorsementRequest {
+    /// Why the subject contests the endorsement.
+    pub statement: String,
+    /// Leave the endorsement out of public aggregates pending review
+    /// (default `false`).
+    #[serde(default)]
+    pub pause_aggregates: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveDisputeRequest {
+    /// `upheld` revokes the endorsement; `dismissed` keeps it.
+    pub outcome: DisputeStatus,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DisputeResponse {
+    pub id: Uuid,
+    pub endorsement_id: Uuid,
+    pub subject_id: Uuid,
+    pub issuer_id: Option<Uuid>,
+    pub topic: String,
+    pub statement: String,
+    /// The subject's signed request. Verify it against the subject's device
+    /// key to confirm the statement.
+    pub envelope: serde_json::Value,
+    pub pauses_aggregates: bool,
+    pub status: DisputeStatus,
+    pub resolved_by: Option<Uuid>,
+    pub resolution_note: Option<String>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
+
+impl From<DisputeRecord> for DisputeResponse {
+    fn from(record: DisputeRecord) -> Self {
+        Self {
+            id: record.id,
+            endorsement_id: record.endorsement_id,
+            subject_id: record.subject_id,
+            issuer_id: record.issuer_id,
+            topic: record.topic,
+            statement: record.statement,
+            envelope: record.envelope,
+            pauses_aggregates: record.pauses_aggregates,
+            status: record.status,
+            resolved_by: record.resolved_by,
+            resolution_note: record.resolution_note,
+            created_at: record.created_at.to_rfc3339(),
+            resolved_at: record.resolved_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DisputesListResponse {
+    pub disputes: Vec<DisputeResponse>,
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// Dispute an endorsement of the authenticated account.
+///
+/// The signed request is stored with the dispute and the issuer is notified.
+#[utoipa::path(
+    post,
+    path = "/endorsements/{endorsement_id}/dispute",
+    tag = "reputation",
+    params(("endorsement_id" = Uuid, Path, description = "Endorsement to dispute")),
+    request_body = DisputeEndorsementRequest,
+    responses(
+        (status = 201, description = "Dispute opened", body = DisputeResponse),
+        (status = 400, description = "Invalid statement"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No active endorsement of the caller with this ID"),
+        (status = 409, description = "Endorsement already disputed"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn dispute_endorsement(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
+    Path(endorsement_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: DisputeEndorsementRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let statement = body.statement.trim();
+    if statement.is_empty() {
+        return crate::http::bad_request("statement is required");
+    }
+    if statement.len() > MAX_DISPUTE_STATEMENT_LENGTH {
+        return crate::http::bad_request(&format!(
+            "statement must be at most {MAX_DISPUTE_STATEMENT_LENGTH} bytes"
+        ));
+    }
+    let envelope = match serde_json::to_value(auth.signed_request()) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("Failed to serialize dispute envelope: {e}");
+            return crate::http::internal_error();
+        }
+    };
+
+    match repo
+        .open_dispute(&NewDispute {
+            endorsement_id,
+            subject_id: auth.account_id,
+            statement,
+            envelope: &envelope,
+            pause_aggregates: body.pause_aggregates,
+        })
+        .await
+    {
+        Ok(dispute) => {
+            if let (Some(Extension(events)), Some(issuer_id)) = (events, dispute.issuer_id) {
+                events.publish(DomainEvent::EndorsementDisputed {
+                    endorsement_id,
+                    subject_id: dispute.subject_id,
+                    issuer_id,
+                });
+            }
+            (StatusCode::CREATED, Json(DisputeResponse::from(dispute))).into_response()
+        }
+        Err(e) => dispute_error_response(&e),
+    }
+}
+
+/// List open disputes awaiting review (account moderators only).
+#[utoipa::path(
+    get,
+    path = "/endorsements/disputes",
+    tag = "reputation",
+    responses(
+        (status = 200, description = "Open disputes, oldest first", body = DisputesListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account moderator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_open_disputes(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = super::require_moderator(&accounts_config, auth.account_id) {
+        return resp;
+    }
+
+    match repo.list_open_disputes().await {
+        Ok(disputes) => (
+            StatusCode::OK,
+            Json(DisputesListResponse {
+                disputes: disputes.into_iter().map(DisputeResponse::from).collect(),
+            }),
+        )
+            .into_response(),
+        Err(e) => dispute_error_response(&e),
+    }
+}
+
+/// Uphold or dismiss the open dispute on an endorsement (account moderators
+/// only).
+///
+/// Upholding revokes the endorsement. Either outcome returns it to public
+/// aggregates if it was paused.
+#[utoipa::path(
+    post,
+    path = "/endorsements/{endorsement_id}/dispute/resolve",
+    tag = "reputation",
+    params(("endorsement_id" = Uuid, Path, description = "Disputed endorsement")),
+    request_body = ResolveDisputeRequest,
+    responses(
+        (status = 200, description = "Dispute resolved", body = DisputeResponse),
+        (status = 400, description = "Invalid outcome or note"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account moderator"),
+        (status = 404, description = "Endorsement has no dispute"),
+        (status = 409, description = "Dispute already resolved"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn resolve_dispute(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(endorsement_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let body: ResolveDisputeRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    if body.outcome == DisputeStatus::Open {
+        return crate::http::bad_request("outcome must be upheld or dismissed");
+    }
+    let note = body
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    if note.is_some_and(|n| n.len() > MAX_DISPUTE_STATEMENT_LENGTH) {
+        return crate::http::bad_request(&format!(
+            "note must be at most {MAX_DISPUTE_STATEMENT_LENGTH} bytes"
+        ));
+    }
+    if let Err(resp) = super::require_moderator(&accounts_config, auth.account_id) {
+        return resp;
+    }
+
+    match repo
+        .resolve_dispute(endorsement_id, body.outcome, auth.account_id, note)
+        .await
+    {
+        Ok(dispute) => (StatusCode::OK, Json(DisputeResponse::from(dispute))).into_response(),
+        Err(e) => dispute_error_response(&e),
+    }
+}
+
+// ─── Error mapping ─────────────────────────────────────────────────────────
+
+fn dispute_error_response(e: &DisputeRepoError) -> axum::response::Response {
+    match e {
+        DisputeRepoError::NotFound => crate::http::not_found("Endorsement not found"),
+        DisputeRepoError::AlreadyDisputed => {
+            crate::http::conflict("Endorsement has already been disputed")
+        }
+        DisputeRepoError::AlreadyResolved => {
+            crate::http::conflict("Dispute has already been resolved")
+        }
+        DisputeRepoError::Database(err) => {
+            tracing::error!("Dispute query failed: {err}");
+            crate::http::internal_error()
+        }
+    }
+}
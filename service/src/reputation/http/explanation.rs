@@ -0,0 +1,78 @@
+//! Reputation score explanation.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::config::{AccountsConfig, ReputationConfig};
+use crate::identity::http::token_auth::{scope, ReadAuth};
+use crate::identity::repo::{AccountRepoError, IdentityRepo};
+use crate::reputation::repo::ReputationRepo;
+use crate::reputation::score::{explain_account, ReputationExplanation};
+use crate::trust::repo::TrustRepo;
+use crate::visibility::{Redact, Viewer};
+
+/// Itemize the aggregates, trust signals and penalties behind an account's
+/// reputation score and tier.
+///
+/// Private endorsements never count toward the score, so they are not
+/// revealed here. Strike reasons are shown only to the account itself and
+/// to moderators.
+#[utoipa::path(
+    get,
+    path = "/reputation/{account_id}/explanation",
+    tag = "reputation",
+    params(("account_id" = Uuid, Path, description = "Account to explain")),
+    responses(
+        (status = 200, description = "Score, tier and the components that produced them", body = ReputationExplanation),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the trust:read scope"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []), ("api_token" = []))
+)]
+pub async fn get_explanation(
+    Extension(identity): Extension<Arc<dyn IdentityRepo>>,
+    Extension(reputation): Extension<Arc<dyn ReputationRepo>>,
+    Extension(trust): Extension<Arc<dyn TrustRepo>>,
+    reputation_config: Option<Extension<ReputationConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(account_id): Path<Uuid>,
+    auth: ReadAuth<scope::TrustRead>,
+) -> impl IntoResponse {
+    let reputation_config = reputation_config.map(|Extension(c)| c).unwrap_or_default();
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    match identity.get_account_by_id(account_id).await {
+        Ok(_) => {}
+        Err(AccountRepoError::NotFound) => return crate::http::not_found("Account not found"),
+        Err(e) => {
+            tracing::error!("Failed to look up account for explanation: {e}");
+            return crate::http::internal_error();
+        }
+    }
+
+    match explain_account(
+        reputation.as_ref(),
+        trust.as_ref(),
+        &reputation_config,
+        account_id,
+    )
+    .await
+    {
+        Ok(explanation) => {
+            let viewer = Viewer::resolve(&accounts_config, Some(auth.account_id), account_id);
+            (StatusCode::OK, Json(explanation.redact(viewer))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to explain reputation: {e}");
+            crate::http::internal_error()
+        }
+    }
+}
@@ -20,9 +20,11 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::config::IdMeConfig;
+use crate::events::{DomainEvent, EventPublisher};
 use crate::identity::http::auth::AuthenticatedDevice;
-use crate::reputation::repo::ReputationRepo;
+use crate::reputation::repo::{CreatedEndorsement, IssuedEndorsement, ReputationRepo};
 use crate::reputation::service::EndorsementService;
+use crate::reputation::EndorsementVisibility;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -189,6 +191,7 @@ pub async fn callback(
     Extension(endorsement_service): Extension<Arc<dyn EndorsementService>>,
     Extension(repo): Extension<Arc<dyn ReputationRepo>>,
     Extension(verifier_id): Extension<IdMeVerifierAccountId>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
     Query(query): Query<CallbackQuery>,
 ) -> impl IntoResponse {
     let frontend_url = &config.frontend_callback_url;
@@ -202,7 +205,18 @@ pub async fn callback(
     )
     .await
     {
-        Ok(()) => redirect_to_frontend(frontend_url, "success", ""),
+        Ok(created) => {
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::EndorsementCreated {
+                    endorsement_id: created.id,
+                    subject_id: created.subject_id,
+                    topic: created.topic,
+                    issuer_id: Some(verifier_id.0),
+                    visibility: EndorsementVisibility::Public,
+                });
+            }
+            redirect_to_frontend(frontend_url, "success", "")
+        }
         Err(msg) => redirect_to_frontend(frontend_url, "error", &msg),
     }
 }
@@ -215,7 +229,7 @@ async fn process_callback(
     repo: &dyn ReputationRepo,
     verifier_account_id: Uuid,
     query: &CallbackQuery,
-) -> Result<(), String> {
+) -> Result<CreatedEndorsement, String> {
     // Handle errors from ID.me
     if let Some(ref error) = query.error {
         let desc = query
@@ -346,19 +360,21 @@ async fn create_verification_endorsement(
     account_id: Uuid,
     verifier_account_id: Uuid,
     idme_sub: &str,
-) -> Result<(), String> {
+) -> Result<CreatedEndorsement, String> {
     match service
-        .create_endorsement(
-            account_id,
-            "identity_verified",
-            Some(verifier_account_id),
-            None,
-        )
+        .create_endorsement(&IssuedEndorsement {
+            subject_id: account_id,
+            topic: "identity_verified",
+            issuer_id: Some(verifier_account_id),
+            evidence: None,
+            visibility: EndorsementVisibility::Public,
+            envelope: None,
+        })
         .await
     {
-        Ok(_) => {
+        Ok(created) => {
             tracing::info!(account_id = %account_id, idme_sub = %idme_sub, "ID.me verification successful");
-            Ok(())
+            Ok(created)
         }
         Err(e) => {
             tracing::error!("Failed to create endorsement: {e}");
@@ -1,6 +1,11 @@
 //! HTTP handlers for reputation system
 
+pub mod badge;
+pub mod disputes;
+pub mod explanation;
 pub mod idme;
+pub mod strikes;
+pub mod verifications;
 
 use std::sync::Arc;
 
@@ -11,16 +16,23 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::repo::IssuedEndorsement;
 use super::service::{EndorsementError, EndorsementService};
-use crate::config::RateLimitConfig;
+use super::{DisputeStatus, EndorsementVisibility};
+use crate::config::{AccountsConfig, RateLimitConfig};
+use crate::events::{DomainEvent, EventPublisher};
 use crate::http::rate_limit::make_governor_layer;
-use crate::http::ErrorResponse;
+use crate::http::{bad_request, ErrorResponse};
 use crate::identity::http::auth::AuthenticatedDevice;
+use crate::identity::http::token_auth::{scope, ReadAuth};
 use crate::identity::repo::{AccountRepoError, IdentityRepo};
+use crate::pagination::PageQuery;
+use crate::subject::SubjectRef;
 
 // ─── Response types ────────────────────────────────────────────────────────
 
@@ -32,11 +44,24 @@ pub struct EndorsementResponse {
     pub issuer_id: Option<Uuid>,
     pub created_at: String,
     pub revoked: bool,
+    pub visibility: EndorsementVisibility,
+    /// The issuer's signed request, when issued through one. Verify it
+    /// against the issuing device's public key to confirm `visibility`.
+    pub issuance_envelope: Option<serde_json::Value>,
+    /// Status of the subject's dispute, if one was raised.
+    pub dispute_status: Option<DisputeStatus>,
+    /// True while the endorsement is left out of public aggregates pending
+    /// dispute review.
+    pub aggregates_paused: bool,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct EndorsementsListResponse {
     pub endorsements: Vec<EndorsementResponse>,
+    /// Cursor for the next page; null on the last page or when unpaginated.
+    pub next_cursor: Option<String>,
+    /// Snapshot instant of a paginated walk; null when unpaginated.
+    pub as_of: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -52,6 +77,9 @@ pub struct CreateEndorsementRequest {
     pub topic: String,
     #[serde(default)]
     pub evidence: Option<serde_json::Value>,
+    /// Who may see the endorsement (default `public`)
+    #[serde(default)]
+    pub visibility: EndorsementVisibility,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -61,6 +89,7 @@ pub struct CreatedEndorsementResponse {
     pub topic: String,
     pub issuer_id: Uuid,
     pub created_at: String,
+    pub visibility: EndorsementVisibility,
 }
 
 // ─── Query types ───────────────────────────────────────────────────────────
@@ -68,6 +97,8 @@ pub struct CreatedEndorsementResponse {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct EndorsementQuery {
     pub subject_id: Option<Uuid>,
+    /// Canonical subject reference; see [`crate::subject::SubjectRef`]
+    pub subject: Option<String>,
     pub topic: Option<String>,
 }
 
@@ -89,36 +120,133 @@ pub fn router(rate_limit_config: &RateLimitConfig) -> Router {
         }
     };
 
+    // Badges are public and embeddable, so they get their own per-IP limit.
+    let badge_router = {
+        let r = Router::new().route("/badges/{file}", get(badge::get_badge));
+        if let Some(layer) =
+            make_governor_layer(rate_limit_config.badge_per_minute, rate_limit_config)
+        {
+            r.layer(layer)
+        } else {
+            r
+        }
+    };
+
+    // The ID verification webhook is unauthenticated apart from its HMAC
+    // signature, so it shares the generic auth-flow limit.
+    let idv_router = {
+        let r = Router::new().route(
+            "/verifications/idv/webhook",
+            post(verifications::idv_webhook),
+        );
+        if let Some(layer) =
+            make_governor_layer(rate_limit_config.backup_per_minute, rate_limit_config)
+        {
+            r.layer(layer)
+        } else {
+            r
+        }
+    };
+
     Router::new()
         .route("/me/endorsements", get(my_endorsements))
+        .route("/me/verifications", get(verifications::my_verifications))
+        .route(
+            "/me/verifications/{method}/start",
+            post(verifications::start_verification),
+        )
+        .route(
+            "/me/verifications/{method}/confirm",
+            post(verifications::confirm_verification),
+        )
+        .route(
+            "/accounts/{account_id}/verifications",
+            get(verifications::account_verifications),
+        )
+        .route(
+            "/accounts/{account_id}/verifications/in-person",
+            post(verifications::attest_in_person),
+        )
+        .route("/me/strikes", get(strikes::my_strikes))
+        .route(
+            "/me/strikes/{strike_id}/appeal",
+            post(strikes::appeal_strike),
+        )
+        .route(
+            "/accounts/{account_id}/strikes",
+            post(strikes::issue_strike),
+        )
+        .route("/strikes/appeals", get(strikes::list_pending_appeals))
+        .route(
+            "/strikes/{strike_id}/appeal/resolve",
+            post(strikes::resolve_appeal),
+        )
         .route("/endorsements/check", get(check_endorsement))
+        .route("/endorsements/disputes", get(disputes::list_open_disputes))
+        .route(
+            "/endorsements/{endorsement_id}/dispute",
+            post(disputes::dispute_endorsement),
+        )
+        .route(
+            "/endorsements/{endorsement_id}/dispute/resolve",
+            post(disputes::resolve_dispute),
+        )
+        .route(
+            "/reputation/{account_id}/explanation",
+            get(explanation::get_explanation),
+        )
         .route(
             "/verifiers/endorsements",
             post(create_endorsement_as_verifier),
         )
         .merge(idme_router)
+        .merge(idv_router)
+        .merge(badge_router)
 }
 
 // ─── Handlers ──────────────────────────────────────────────────────────────
 
 /// List endorsements for the authenticated user.
+///
+/// Without query parameters every endorsement is returned. Passing `limit`,
+/// `cursor` or `as_of` switches to keyset pagination over a fixed snapshot.
 #[utoipa::path(
     get,
     path = "/me/endorsements",
     tag = "reputation",
+    params(PageQuery),
     responses(
         (status = 200, description = "List of endorsements for the authenticated user", body = EndorsementsListResponse),
+        (status = 400, description = "Invalid pagination parameters"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the endorsements:read scope"),
         (status = 500, description = "Internal server error")
     ),
-    security(("device_auth" = []))
+    security(("device_auth" = []), ("api_token" = []))
 )]
 async fn my_endorsements(
     Extension(service): Extension<Arc<dyn EndorsementService>>,
-    auth: AuthenticatedDevice,
+    Query(query): Query<PageQuery>,
+    auth: ReadAuth<scope::EndorsementsRead>,
 ) -> impl IntoResponse {
-    match service.list_endorsements(auth.account_id).await {
-        Ok(endorsements) => {
+    let result = if query.is_paginated() {
+        let page = match query.resolve(Utc::now()) {
+            Ok(page) => page,
+            Err(msg) => return bad_request(&msg),
+        };
+        service
+            .list_endorsements_page(auth.account_id, &page)
+            .await
+            .map(|(rows, next_cursor)| (rows, next_cursor, Some(page.as_of.to_rfc3339())))
+    } else {
+        service
+            .list_endorsements(auth.account_id)
+            .await
+            .map(|rows| (rows, None, None))
+    };
+
+    match result {
+        Ok((endorsements, next_cursor, as_of)) => {
             let response = EndorsementsListResponse {
                 endorsements: endorsements
                     .into_iter()
@@ -129,8 +257,14 @@ async fn my_endorsements(
                         issuer_id: e.endorser_id,
                         created_at: e.created_at.to_rfc3339(),
                         revoked: e.revoked_at.is_some(),
+                        visibility: e.visibility,
+                        issuance_envelope: e.issuance_envelope,
+                        dispute_status: e.dispute_status,
+                        aggregates_paused: e.aggregates_paused,
                     })
                     .collect(),
+                next_cursor,
+                as_of,
             };
             (StatusCode::OK, Json(response)).into_response()
         }
@@ -145,11 +279,12 @@ async fn my_endorsements(
     tag = "reputation",
     params(
         ("subject_id" = Option<Uuid>, Query, description = "Account UUID to check endorsement for"),
+        ("subject" = Option<String>, Query, description = "Canonical subject instead of subject_id, e.g. `account:<uuid>`, `member:A000360`, `bill:hr-1234-118`"),
         ("topic" = Option<String>, Query, description = "Endorsement topic to check")
     ),
     responses(
         (status = 200, description = "Endorsement check result", body = HasEndorsementResponse),
-        (status = 400, description = "Missing required query parameters"),
+        (status = 400, description = "Missing, conflicting, or malformed query parameters"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -157,19 +292,39 @@ async fn check_endorsement(
     Extension(service): Extension<Arc<dyn EndorsementService>>,
     Query(query): Query<EndorsementQuery>,
 ) -> impl IntoResponse {
-    let Some(subject_id) = query.subject_id else {
-        return crate::http::bad_request("subject_id query parameter is required");
+    let subject = match (query.subject_id, query.subject.as_deref()) {
+        (Some(_), Some(_)) => {
+            return crate::http::bad_request("send either subject or subject_id, not both")
+        }
+        (Some(id), None) => SubjectRef::Account(id),
+        (None, Some(s)) => match s.parse::<SubjectRef>() {
+            Ok(subject) => subject,
+            Err(e) => return crate::http::bad_request(&e.to_string()),
+        },
+        (None, None) => return crate::http::bad_request("subject_id query parameter is required"),
     };
 
     let Some(ref topic) = query.topic else {
         return crate::http::bad_request("topic query parameter is required");
     };
 
-    match service.has_endorsement(subject_id, topic).await {
-        Ok(has) => (
+    // Only accounts can be endorsed so far
+    let Some(subject_id) = subject.account_id() else {
+        return (
             StatusCode::OK,
             Json(HasEndorsementResponse {
-                has_endorsement: has,
+                has_endorsement: false,
+            }),
+        )
+            .into_response();
+    };
+
+    // Public view: private endorsements don't count
+    match service.endorsement_summary(subject_id).await {
+        Ok(counts) => (
+            StatusCode::OK,
+            Json(HasEndorsementResponse {
+                has_endorsement: counts.iter().any(|c| &c.topic == topic && c.count > 0),
             }),
         )
             .into_response(),
@@ -203,6 +358,7 @@ async fn check_endorsement(
 async fn create_endorsement_as_verifier(
     Extension(endorsement_service): Extension<Arc<dyn EndorsementService>>,
     Extension(identity_repo): Extension<Arc<dyn IdentityRepo>>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
     auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
     // Parse body from AuthenticatedDevice (which already consumed it for signing)
@@ -234,31 +390,70 @@ async fn create_endorsement_as_verifier(
         }
     };
 
-    // 3. Create endorsement
+    // 3. Create endorsement, keeping the signed request so the chosen
+    //    visibility is tamper-evident
+    let envelope = match serde_json::to_value(auth.signed_request()) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("Failed to serialize issuance envelope: {e}");
+            return crate::http::internal_error();
+        }
+    };
     match endorsement_service
-        .create_endorsement(
-            subject.id,
-            &body.topic,
-            Some(auth.account_id),
-            body.evidence.as_ref(),
-        )
+        .create_endorsement(&IssuedEndorsement {
+            subject_id: subject.id,
+            topic: &body.topic,
+            issuer_id: Some(auth.account_id),
+            evidence: body.evidence.as_ref(),
+            visibility: body.visibility,
+            envelope: Some(&envelope),
+        })
         .await
     {
-        Ok(created) => (
-            StatusCode::CREATED,
-            Json(CreatedEndorsementResponse {
-                id: created.id,
-                subject_id: created.subject_id,
-                topic: created.topic,
-                issuer_id: auth.account_id,
-                created_at: chrono::Utc::now().to_rfc3339(),
-            }),
-        )
-            .into_response(),
+        Ok(created) => {
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::EndorsementCreated {
+                    endorsement_id: created.id,
+                    subject_id: created.subject_id,
+                    topic: created.topic.clone(),
+                    issuer_id: Some(auth.account_id),
+                    visibility: body.visibility,
+                });
+            }
+            (
+                StatusCode::CREATED,
+                Json(CreatedEndorsementResponse {
+                    id: created.id,
+                    subject_id: created.subject_id,
+                    topic: created.topic,
+                    issuer_id: auth.account_id,
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                    visibility: body.visibility,
+                }),
+            )
+                .into_response()
+        }
         Err(e) => endorsement_error_response(e),
     }
 }
 
+// ─── Helpers ───────────────────────────────────────────────────────────────
+
+/// Refuse callers not listed in [`AccountsConfig::admin_account_ids`].
+#[allow(clippy::result_large_err)]
+pub(super) fn require_moderator(
+    config: &AccountsConfig,
+    account_id: Uuid,
+) -> Result<(), axum::response::Response> {
+    if config.is_admin(account_id) {
+        Ok(())
+    } else {
+        Err(crate::http::forbidden(
+            "Only account moderators can do this",
+        ))
+    }
+}
+
 // ─── Error mapping ─────────────────────────────────────────────────────────
 
 fn endorsement_error_response(e: EndorsementError) -> axum::response::Response {
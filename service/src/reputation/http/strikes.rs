@@ -0,0 +1,399 @@
+//! Moderator strikes and appeals; see [`crate::reputation::strikes`].
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::AccountsConfig;
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::identity::repo::{AccountRepoError, IdentityRepo};
+use crate::reputation::repo::{
+    NewStrike, ReputationRepo, StrikeEventRecord, StrikeRecord, StrikeRepoError,
+};
+use crate::reputation::strikes::{
+    decayed_penalty, strike_expiry, AppealStatus, StrikeAction, StrikeReason,
+};
+
+/// Longest accepted note or appeal statement, in bytes.
+pub const MAX_STRIKE_TEXT_LENGTH: usize = 2000;
+
+// ─── Request / response types ─────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct IssueStrikeRequest {
+    pub reason_code: StrikeReason,
+    /// Shown to the struck account
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Days until the penalty has fully decayed (default depends on
+    /// `reason_code`; at most 365)
+    #[serde(default)]
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AppealStrikeRequest {
+    pub statement: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveAppealRequest {
+    /// `granted` overturns the strike; `denied` keeps it.
+    pub outcome: AppealStatus,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StrikeEventResponse {
+    pub action: StrikeAction,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StrikeResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub reason_code: StrikeReason,
+    pub note: Option<String>,
+    /// Points deducted when issued
+    pub penalty: f64,
+    /// Points deducted now, after decay; zero once expired or overturned
+    pub current_penalty: f64,
+    pub issued_at: String,
+    pub expires_at: String,
+    pub appeal_status: AppealStatus,
+    pub appeal_statement: Option<String>,
+    /// Audit trail, oldest first. Only included for the struck account.
+    pub history: Vec<StrikeEventResponse>,
+}
+
+impl StrikeResponse {
+    fn new(record: StrikeRecord, events: &[StrikeEventRecord]) -> Self {
+        let current_penalty = if record.appeal_status == AppealStatus::Granted {
+            0.0
+        } else {
+            decayed_penalty(
+                record.penalty,
+                record.issued_at,
+                record.expires_at,
+                Utc::now(),
+            )
+        };
+        Self {
+            id: record.id,
+            account_id: record.account_id,
+            reason_code: record.reason,
+            note: record.note,
+            penalty: record.penalty,
+            current_penalty,
+            issued_at: record.issued_at.to_rfc3339(),
+            expires_at: record.expires_at.to_rfc3339(),
+            appeal_status: record.appeal_status,
+            appeal_statement: record.appeal_statement,
+            history: events
+                .iter()
+                .filter(|e| e.strike_id == record.id)
+                .map(|e| StrikeEventResponse {
+                    action: e.action,
+                    note: e.note.clone(),
+                    created_at: e.created_at.to_rfc3339(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StrikesListResponse {
+    pub strikes: Vec<StrikeResponse>,
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// Strike an account (account moderators only).
+///
+/// The reason sets the penalty and, unless `expires_in_days` is given, how
+/// long it takes to decay.
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/strikes",
+    tag = "reputation",
+    params(("account_id" = Uuid, Path, description = "Account to strike")),
+    request_body = IssueStrikeRequest,
+    responses(
+        (status = 201, description = "Strike issued", body = StrikeResponse),
+        (status = 400, description = "Invalid note or duration"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account moderator, or targets their own account"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn issue_strike(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    Extension(identity): Extension<Arc<dyn IdentityRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(account_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let body: IssueStrikeRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let note = match validate_text("note", body.note.as_deref()) {
+        Ok(n) => n,
+        Err(resp) => return resp,
+    };
+    let issued_at = Utc::now();
+    let expires_at = match strike_expiry(body.reason_code, body.expires_in_days, issued_at) {
+        Ok(t) => t,
+        Err(msg) => return crate::http::bad_request(&msg),
+    };
+    if let Err(resp) = super::require_moderator(&accounts_config, auth.account_id) {
+        return resp;
+    }
+    if account_id == auth.account_id {
+        return crate::http::forbidden("Moderators cannot strike their own account");
+    }
+    match identity.get_account_by_id(account_id).await {
+        Ok(_) => {}
+        Err(AccountRepoError::NotFound) => return crate::http::not_found("Account not found"),
+        Err(e) => {
+            tracing::error!("Failed to look up strike target: {e}");
+            return crate::http::internal_error();
+        }
+    }
+
+    match repo
+        .issue_strike(&NewStrike {
+            account_id,
+            issued_by: auth.account_id,
+            reason: body.reason_code,
+            note,
+            penalty: body.reason_code.penalty(),
+            issued_at,
+            expires_at,
+        })
+        .await
+    {
+        Ok(strike) => (StatusCode::CREATED, Json(StrikeResponse::new(strike, &[]))).into_response(),
+        Err(e) => strike_error_response(&e),
+    }
+}
+
+/// List strikes against the authenticated account, with their history.
+#[utoipa::path(
+    get,
+    path = "/me/strikes",
+    tag = "reputation",
+    responses(
+        (status = 200, description = "Strikes, newest first", body = StrikesListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn my_strikes(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let strikes = match repo.list_strikes_for_account(auth.account_id).await {
+        Ok(s) => s,
+        Err(e) => return strike_error_response(&e),
+    };
+    let events = match repo.list_strike_events_for_account(auth.account_id).await {
+        Ok(e) => e,
+        Err(e) => return strike_error_response(&e),
+    };
+    (
+        StatusCode::OK,
+        Json(StrikesListResponse {
+            strikes: strikes
+                .into_iter()
+                .map(|s| StrikeResponse::new(s, &events))
+                .collect(),
+        }),
+    )
+        .into_response()
+}
+
+/// Appeal a strike against the authenticated account.
+///
+/// Each strike can be appealed once, before it expires. The penalty still
+/// applies while the appeal is pending.
+#[utoipa::path(
+    post,
+    path = "/me/strikes/{strike_id}/appeal",
+    tag = "reputation",
+    params(("strike_id" = Uuid, Path, description = "Strike to appeal")),
+    request_body = AppealStrikeRequest,
+    responses(
+        (status = 200, description = "Appeal filed", body = StrikeResponse),
+        (status = 400, description = "Invalid statement"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No strike against the caller with this ID"),
+        (status = 409, description = "Strike already appealed or expired"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn appeal_strike(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    Path(strike_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: AppealStrikeRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let statement = match validate_text("statement", Some(&body.statement)) {
+        Ok(Some(s)) => s,
+        Ok(None) => return crate::http::bad_request("statement is required"),
+        Err(resp) => return resp,
+    };
+
+    match repo
+        .appeal_strike(strike_id, auth.account_id, statement)
+        .await
+    {
+        Ok(strike) => (StatusCode::OK, Json(StrikeResponse::new(strike, &[]))).into_response(),
+        Err(e) => strike_error_response(&e),
+    }
+}
+
+/// List strikes with a pending appeal (account moderators only).
+#[utoipa::path(
+    get,
+    path = "/strikes/appeals",
+    tag = "reputation",
+    responses(
+        (status = 200, description = "Pending appeals, oldest first", body = StrikesListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account moderator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn list_pending_appeals(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = super::require_moderator(&accounts_config, auth.account_id) {
+        return resp;
+    }
+
+    match repo.list_pending_appeals().await {
+        Ok(strikes) => (
+            StatusCode::OK,
+            Json(StrikesListResponse {
+                strikes: strikes
+                    .into_iter()
+                    .map(|s| StrikeResponse::new(s, &[]))
+                    .collect(),
+            }),
+        )
+            .into_response(),
+        Err(e) => strike_error_response(&e),
+    }
+}
+
+/// Grant or deny a pending appeal (account moderators only).
+///
+/// Granting overturns the strike, removing its penalty. Moderators cannot
+/// review appeals of strikes against themselves.
+#[utoipa::path(
+    post,
+    path = "/strikes/{strike_id}/appeal/resolve",
+    tag = "reputation",
+    params(("strike_id" = Uuid, Path, description = "Appealed strike")),
+    request_body = ResolveAppealRequest,
+    responses(
+        (status = 200, description = "Appeal resolved", body = StrikeResponse),
+        (status = 400, description = "Invalid outcome or note"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an account moderator, or was struck"),
+        (status = 404, description = "Strike not found"),
+        (status = 409, description = "Strike has no pending appeal"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn resolve_appeal(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(strike_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    let body: ResolveAppealRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    if !matches!(body.outcome, AppealStatus::Granted | AppealStatus::Denied) {
+        return crate::http::bad_request("outcome must be granted or denied");
+    }
+    let note = match validate_text("note", body.note.as_deref()) {
+        Ok(n) => n,
+        Err(resp) => return resp,
+    };
+    if let Err(resp) = super::require_moderator(&accounts_config, auth.account_id) {
+        return resp;
+    }
+
+    match repo
+        .resolve_appeal(strike_id, body.outcome, auth.account_id, note)
+        .await
+    {
+        Ok(strike) => (StatusCode::OK, Json(StrikeResponse::new(strike, &[]))).into_response(),
+        Err(e) => strike_error_response(&e),
+    }
+}
+
+// ─── Helpers ───────────────────────────────────────────────────────────────
+
+/// Trim optional free text, treating blank as absent.
+#[allow(clippy::result_large_err)]
+fn validate_text<'a>(
+    field: &str,
+    text: Option<&'a str>,
+) -> Result<Option<&'a str>, axum::response::Response> {
+    let text = text.map(str::trim).filter(|t| !t.is_empty());
+    if text.is_some_and(|t| t.len() > MAX_STRIKE_TEXT_LENGTH) {
+        return Err(crate::http::bad_request(&format!(
+            "{field} must be at most {MAX_STRIKE_TEXT_LENGTH} bytes"
+        )));
+    }
+    Ok(text)
+}
+
+fn strike_error_response(e: &StrikeRepoError) -> axum::response::Response {
+    match e {
+        StrikeRepoError::NotFound => crate::http::not_found("Strike not found"),
+        StrikeRepoError::AppealNotAllowed => {
+            crate::http::conflict("Strike has already been appealed or has expired")
+        }
+        StrikeRepoError::NoPendingAppeal => crate::http::conflict("Strike has no pending appeal"),
+        StrikeRepoError::OwnStrike => {
+            crate::http::forbidden("Moderators cannot review appeals of their own strikes")
+        }
+        StrikeRepoError::Database(err) => {
+            tracing::error!("Strike query failed: {err}");
+            crate::http::internal_error()
+        }
+    }
+}
@@ -0,0 +1,528 @@
+//! Account verification methods; see [`crate::reputation::verification`].
+
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::Extension,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::{AccountsConfig, VerificationConfig, MAX_VERIFICATION_CODE_TTL_SECS};
+use crate::http::Path;
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::identity::http::token_auth::{scope, ReadAuth};
+use crate::identity::repo::{AccountRepoError, IdentityRepo};
+use crate::reputation::repo::{
+    NewChallenge, ReputationRepo, VerificationRecord, VerificationRepoError, CODE_PROVIDER,
+};
+use crate::reputation::verification::{
+    generate_code, hash_code, hash_destination, normalize_destination, verify_webhook_signature,
+    VerificationMethod, VerificationSender, IDV_SIGNATURE_HEADER,
+};
+use crate::visibility::{Redact, Viewer};
+
+/// Status an ID verification provider reports for a passed check.
+pub const IDV_STATUS_VERIFIED: &str = "verified";
+
+// ─── Request / response types ─────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationResponse {
+    pub method: VerificationMethod,
+    /// Moderators and the owner only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Moderators and the owner only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<String>,
+}
+
+impl From<VerificationRecord> for VerificationResponse {
+    fn from(record: VerificationRecord) -> Self {
+        Self {
+            method: record.method,
+            provider: Some(record.provider),
+            verified_at: Some(record.verified_at.to_rfc3339()),
+        }
+    }
+}
+
+impl Redact for VerificationResponse {
+    fn redact(self, viewer: Viewer) -> Self {
+        Self {
+            provider: viewer.reveal(Viewer::Moderator, self.provider),
+            verified_at: viewer.reveal(Viewer::Moderator, self.verified_at),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationsResponse {
+    pub account_id: Uuid,
+    /// Strongest completed method; null if none
+    pub level: Option<VerificationMethod>,
+    /// Completed methods, weakest first
+    pub verifications: Vec<VerificationResponse>,
+    /// Accounts that have attested to meeting this one in person
+    pub in_person_attestations: i64,
+    /// Attestations needed for in-person verification
+    pub in_person_attestations_required: u32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartVerificationRequest {
+    /// Email address, or phone number in international form
+    pub destination: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StartVerificationResponse {
+    pub method: VerificationMethod,
+    /// When the code stops being accepted
+    pub expires_at: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConfirmVerificationRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InPersonAttestationResponse {
+    pub subject_id: Uuid,
+    pub attestations: i64,
+    pub required: u32,
+    /// Whether the subject is now verified in person
+    pub verified: bool,
+}
+
+/// Result reported by an ID verification provider.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct IdvWebhookRequest {
+    /// Account the check was started for
+    pub account_id: Uuid,
+    /// `verified` records a government ID verification; anything else is
+    /// acknowledged and ignored
+    pub status: String,
+    /// Provider's identifier for the verified person, used to keep one ID
+    /// from verifying several accounts
+    pub reference: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IdvWebhookResponse {
+    /// Whether a verification was recorded
+    pub recorded: bool,
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// List the authenticated account's verifications.
+#[utoipa::path(
+    get,
+    path = "/me/verifications",
+    tag = "reputation",
+    responses(
+        (status = 200, description = "Completed verification methods", body = VerificationsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn my_verifications(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    config: Option<Extension<VerificationConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    verifications_response(repo.as_ref(), &config, auth.account_id, Viewer::Owner).await
+}
+
+/// List an account's verifications, as shown on its badge.
+///
+/// Providers and dates are shown only to the account itself and to
+/// moderators; see [`crate::visibility`].
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/verifications",
+    tag = "reputation",
+    params(("account_id" = Uuid, Path, description = "Account to look up")),
+    responses(
+        (status = 200, description = "Completed verification methods", body = VerificationsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the trust:read scope"),
+        (status = 404, description = "Account not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []), ("api_token" = []))
+)]
+pub async fn account_verifications(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    Extension(identity): Extension<Arc<dyn IdentityRepo>>,
+    config: Option<Extension<VerificationConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(account_id): Path<Uuid>,
+    auth: ReadAuth<scope::TrustRead>,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if let Err(resp) = require_account(identity.as_ref(), account_id).await {
+        return resp;
+    }
+    let viewer = Viewer::resolve(&accounts_config, Some(auth.account_id), account_id);
+    verifications_response(repo.as_ref(), &config, account_id, viewer).await
+}
+
+/// Send a one-time code to an email address or phone number.
+///
+/// Requesting a new code replaces the previous one. Codes can be
+/// re-requested after `verification.resend_cooldown_secs`.
+#[utoipa::path(
+    post,
+    path = "/me/verifications/{method}/start",
+    tag = "reputation",
+    params(("method" = VerificationMethod, Path, description = "`email` or `phone`")),
+    request_body = StartVerificationRequest,
+    responses(
+        (status = 202, description = "Code sent", body = StartVerificationResponse),
+        (status = 400, description = "Method does not use a code, or invalid destination"),
+        (status = 401, description = "Unauthorized"),
+        (status = 429, description = "A code was sent recently"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Code delivery is not configured or failed")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn start_verification(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    sender: Option<Extension<Arc<dyn VerificationSender>>>,
+    config: Option<Extension<VerificationConfig>>,
+    Path(method): Path<VerificationMethod>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let body: StartVerificationRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let destination = match normalize_destination(method, &body.destination) {
+        Ok(d) => d,
+        Err(msg) => return crate::http::bad_request(&msg),
+    };
+    let Some(Extension(sender)) = sender else {
+        return crate::http::service_unavailable(&format!(
+            "{method} verification is not available on this instance"
+        ));
+    };
+
+    let code = generate_code();
+    let ttl_secs =
+        i64::try_from(config.code_ttl_secs.min(MAX_VERIFICATION_CODE_TTL_SECS)).unwrap_or(0);
+    let expires_at = Utc::now() + Duration::seconds(ttl_secs);
+    let stored = repo
+        .start_verification_challenge(&NewChallenge {
+            account_id: auth.account_id,
+            method,
+            destination_hash: &hash_destination(method, &destination),
+            code_hash: &hash_code(auth.account_id, &code),
+            expires_at,
+            resend_cooldown_secs: i64::try_from(config.resend_cooldown_secs).unwrap_or(i64::MAX),
+        })
+        .await;
+    if let Err(e) = stored {
+        return verification_error_response(&e);
+    }
+
+    let message = format!(
+        "Your verification code is {code}. It expires in {} minutes.",
+        (ttl_secs + 59) / 60
+    );
+    if let Err(e) = sender.send(method, &destination, &message).await {
+        tracing::error!(%method, "Failed to send verification code: {e}");
+        return crate::http::service_unavailable("Could not send the verification code");
+    }
+    (
+        StatusCode::ACCEPTED,
+        Json(StartVerificationResponse {
+            method,
+            expires_at: expires_at.to_rfc3339(),
+        }),
+    )
+        .into_response()
+}
+
+/// Confirm a code sent by `POST /me/verifications/{method}/start`.
+#[utoipa::path(
+    post,
+    path = "/me/verifications/{method}/confirm",
+    tag = "reputation",
+    params(("method" = VerificationMethod, Path, description = "`email` or `phone`")),
+    request_body = ConfirmVerificationRequest,
+    responses(
+        (status = 200, description = "Verification recorded", body = VerificationResponse),
+        (status = 400, description = "Method does not use a code"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Incorrect code"),
+        (status = 404, description = "No pending code"),
+        (status = 409, description = "The destination already verifies another account"),
+        (status = 429, description = "Too many incorrect codes"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn confirm_verification(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    config: Option<Extension<VerificationConfig>>,
+    Path(method): Path<VerificationMethod>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let body: ConfirmVerificationRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    if !method.uses_code() {
+        return crate::http::bad_request(&format!("{method} verification does not use a code"));
+    }
+    match repo
+        .confirm_verification_challenge(
+            auth.account_id,
+            method,
+            &hash_code(auth.account_id, &body.code),
+            i32::try_from(config.max_code_attempts).unwrap_or(i32::MAX),
+            CODE_PROVIDER,
+        )
+        .await
+    {
+        Ok(record) => (StatusCode::OK, Json(VerificationResponse::from(record))).into_response(),
+        Err(e) => verification_error_response(&e),
+    }
+}
+
+/// Attest to having met an account in person.
+///
+/// Only accounts verified in person or by government ID, and account
+/// moderators, may attest. The subject is verified in person once
+/// `verification.in_person_attestations_required` accounts have attested.
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/verifications/in-person",
+    tag = "reputation",
+    params(("account_id" = Uuid, Path, description = "Account the caller met")),
+    responses(
+        (status = 200, description = "Attestation recorded", body = InPersonAttestationResponse),
+        (status = 400, description = "Cannot attest to your own account"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not verified in person or by ID"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "Already attested to this account"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("device_auth" = []))
+)]
+pub async fn attest_in_person(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    Extension(identity): Extension<Arc<dyn IdentityRepo>>,
+    config: Option<Extension<VerificationConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(account_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let accounts_config = accounts_config.map(|Extension(c)| c).unwrap_or_default();
+    if account_id == auth.account_id {
+        return crate::http::bad_request("Cannot attest to your own account");
+    }
+    let attester = match repo.list_verifications(auth.account_id).await {
+        Ok(v) => v,
+        Err(e) => return verification_error_response(&e),
+    };
+    if !attester
+        .iter()
+        .any(|v| v.method >= VerificationMethod::InPerson)
+    {
+        match super::require_moderator(&accounts_config, auth.account_id) {
+            Ok(()) => {}
+            Err(resp) if resp.status() == StatusCode::FORBIDDEN => {
+                return crate::http::forbidden(
+                    "Only accounts verified in person or by ID can attest",
+                );
+            }
+            Err(resp) => return resp,
+        }
+    }
+    if let Err(resp) = require_account(identity.as_ref(), account_id).await {
+        return resp;
+    }
+
+    match repo
+        .attest_in_person(
+            account_id,
+            auth.account_id,
+            i64::from(config.in_person_attestations_required),
+        )
+        .await
+    {
+        Ok(outcome) => (
+            StatusCode::OK,
+            Json(InPersonAttestationResponse {
+                subject_id: account_id,
+                attestations: outcome.attestations,
+                required: config.in_person_attestations_required,
+                verified: outcome.verification.is_some(),
+            }),
+        )
+            .into_response(),
+        Err(e) => verification_error_response(&e),
+    }
+}
+
+/// Receive a result from the external ID verification provider.
+///
+/// The raw body must be signed with `verification.idv_webhook_secret` and
+/// the signature sent as `X-TC-Signature: sha256=<hex HMAC-SHA256>`. Not
+/// found unless the secret is configured.
+#[utoipa::path(
+    post,
+    path = "/verifications/idv/webhook",
+    tag = "reputation",
+    request_body = IdvWebhookRequest,
+    responses(
+        (status = 200, description = "Result acknowledged", body = IdvWebhookResponse),
+        (status = 400, description = "Malformed body"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 404, description = "Webhook disabled, or account not found"),
+        (status = 409, description = "The ID already verifies another account"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn idv_webhook(
+    Extension(repo): Extension<Arc<dyn ReputationRepo>>,
+    Extension(identity): Extension<Arc<dyn IdentityRepo>>,
+    config: Option<Extension<VerificationConfig>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    if config.idv_webhook_secret.is_empty() {
+        return crate::http::not_found("Not found");
+    }
+    let signature = headers
+        .get(IDV_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !verify_webhook_signature(config.idv_webhook_secret.as_bytes(), &body, signature) {
+        return crate::http::unauthorized("Invalid webhook signature");
+    }
+    let payload: IdvWebhookRequest = match serde_json::from_slice(&body) {
+        Ok(p) => p,
+        Err(e) => return crate::http::bad_request(&format!("Invalid webhook body: {e}")),
+    };
+    if payload.status != IDV_STATUS_VERIFIED {
+        tracing::info!(status = %payload.status, "Ignoring unverified IDV result");
+        return (StatusCode::OK, Json(IdvWebhookResponse { recorded: false })).into_response();
+    }
+    let reference = payload.reference.trim();
+    if reference.is_empty() {
+        return crate::http::bad_request("reference is required");
+    }
+    if let Err(resp) = require_account(identity.as_ref(), payload.account_id).await {
+        return resp;
+    }
+
+    let reference_hash = hash_destination(
+        VerificationMethod::GovernmentId,
+        &format!("{}:{reference}", config.idv_provider),
+    );
+    match repo
+        .record_verification(
+            payload.account_id,
+            VerificationMethod::GovernmentId,
+            &config.idv_provider,
+            Some(&reference_hash),
+        )
+        .await
+    {
+        Ok(_) => (StatusCode::OK, Json(IdvWebhookResponse { recorded: true })).into_response(),
+        Err(e) => verification_error_response(&e),
+    }
+}
+
+// ─── Helpers ───────────────────────────────────────────────────────────────
+
+#[allow(clippy::result_large_err)]
+async fn require_account(
+    identity: &dyn IdentityRepo,
+    account_id: Uuid,
+) -> Result<(), axum::response::Response> {
+    match identity.get_account_by_id(account_id).await {
+        Ok(_) => Ok(()),
+        Err(AccountRepoError::NotFound) => Err(crate::http::not_found("Account not found")),
+        Err(e) => {
+            tracing::error!("Failed to look up account for verification: {e}");
+            Err(crate::http::internal_error())
+        }
+    }
+}
+
+async fn verifications_response(
+    repo: &dyn ReputationRepo,
+    config: &VerificationConfig,
+    account_id: Uuid,
+    viewer: Viewer,
+) -> axum::response::Response {
+    let verifications = match repo.list_verifications(account_id).await {
+        Ok(v) => v,
+        Err(e) => return verification_error_response(&e),
+    };
+    let in_person_attestations = match repo.count_in_person_attestations(account_id).await {
+        Ok(n) => n,
+        Err(e) => return verification_error_response(&e),
+    };
+    (
+        StatusCode::OK,
+        Json(VerificationsResponse {
+            account_id,
+            level: verifications.iter().map(|v| v.method).max(),
+            verifications: verifications
+                .into_iter()
+                .map(|v| VerificationResponse::from(v).redact(viewer))
+                .collect(),
+            in_person_attestations,
+            in_person_attestations_required: config.in_person_attestations_required,
+        }),
+    )
+        .into_response()
+}
+
+fn verification_error_response(e: &VerificationRepoError) -> axum::response::Response {
+    match e {
+        VerificationRepoError::NoChallenge => {
+            crate::http::not_found("No pending verification code; request a new one")
+        }
+        VerificationRepoError::InvalidCode => {
+            crate::http::forbidden("Verification code is incorrect")
+        }
+        VerificationRepoError::TooManyAttempts | VerificationRepoError::TooSoon => {
+            crate::http::too_many_requests(&e.to_string())
+        }
+        VerificationRepoError::DestinationTaken => {
+            crate::http::conflict("This already verifies another account")
+        }
+        VerificationRepoError::AlreadyAttested => {
+            crate::http::conflict("Already attested to this account")
+        }
+        VerificationRepoError::Database(err) => {
+            tracing::error!("Verification query failed: {err}");
+            crate::http::internal_error()
+        }
+    }
+}
@@ -3,8 +3,266 @@
 //! Provides the endorsement system that gates room participation.
 //! Verifier service accounts issue endorsements to users for specific topics
 //! (e.g., `identity_verified`).
+//!
+//! Each endorsement carries an [`EndorsementVisibility`] chosen by its issuer.
+//! Visibility only governs what other people are shown; eligibility checks
+//! (room gates, verifier authorization) see every active endorsement.
+//!
+//! The subject of an endorsement can dispute it with a signed statement. A
+//! dispute may pause the endorsement's inclusion in public aggregates until
+//! a moderator resolves it; see [`DisputeStatus`].
+//!
+//! Endorsements, trust signals and penalties add up to a reputation score
+//! that maps onto a [`ReputationTier`]; [`score`] itemizes how. Moderators
+//! can issue [`strikes`], whose penalties decay over time. Completed
+//! [`verification`] methods add to the score and set the account's
+//! verification level.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 pub mod bootstrap;
 pub mod http;
 pub mod repo;
+pub mod score;
 pub mod service;
+pub mod strikes;
+pub mod verification;
+
+use crate::config::ReputationConfig;
+
+/// Topic that marks an account as identity-verified (issued via ID.me or an
+/// authorized verifier).
+pub const VERIFIED_TOPIC: &str = "identity_verified";
+
+/// Who may see an endorsement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EndorsementVisibility {
+    /// Listed to anyone (topic feeds) and counted in public aggregates
+    /// (badges, `/endorsements/check`, topic movements, instance stats).
+    #[default]
+    Public,
+    /// Listed only to the subject, but still counted in public aggregates.
+    SubjectOnly,
+    /// Listed only to the subject and left out of every public aggregate.
+    Private,
+}
+
+impl EndorsementVisibility {
+    /// Every visibility, most open first.
+    pub const ALL: [Self; 3] = [Self::Public, Self::SubjectOnly, Self::Private];
+
+    /// Value stored in `reputation__endorsements.visibility`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::SubjectOnly => "subject_only",
+            Self::Private => "private",
+        }
+    }
+}
+
+impl fmt::Display for EndorsementVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown visibility name.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown endorsement visibility")]
+pub struct UnknownVisibility;
+
+impl FromStr for EndorsementVisibility {
+    type Err = UnknownVisibility;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownVisibility)
+    }
+}
+
+/// Lifecycle of an endorsement dispute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeStatus {
+    /// Awaiting moderator review
+    Open,
+    /// The moderator agreed with the subject; the endorsement was revoked
+    Upheld,
+    /// The moderator kept the endorsement
+    Dismissed,
+}
+
+impl DisputeStatus {
+    pub const ALL: [Self; 3] = [Self::Open, Self::Upheld, Self::Dismissed];
+
+    /// Value stored in `reputation__endorsement_disputes.status`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Upheld => "upheld",
+            Self::Dismissed => "dismissed",
+        }
+    }
+}
+
+impl fmt::Display for DisputeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown dispute status.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown dispute status")]
+pub struct UnknownDisputeStatus;
+
+impl FromStr for DisputeStatus {
+    type Err = UnknownDisputeStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownDisputeStatus)
+    }
+}
+
+/// Named standing of an account, derived from its reputation score.
+///
+/// Thresholds come from [`ReputationConfig`]; see [`score::explain`] for how
+/// the score itself is computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationTier {
+    /// Below the `established` threshold
+    New,
+    Established,
+    Trusted,
+    Exemplary,
+}
+
+impl ReputationTier {
+    pub const ALL: [Self; 4] = [Self::New, Self::Established, Self::Trusted, Self::Exemplary];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Established => "established",
+            Self::Trusted => "trusted",
+            Self::Exemplary => "exemplary",
+        }
+    }
+
+    /// Score needed to reach this tier.
+    #[must_use]
+    pub const fn threshold(self, config: &ReputationConfig) -> f64 {
+        match self {
+            Self::New => 0.0,
+            Self::Established => config.established_score,
+            Self::Trusted => config.trusted_score,
+            Self::Exemplary => config.exemplary_score,
+        }
+    }
+
+    /// Highest tier whose threshold `score` reaches.
+    #[must_use]
+    pub fn from_score(score: f64, config: &ReputationConfig) -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|tier| score >= tier.threshold(config))
+            .unwrap_or(Self::New)
+    }
+
+    /// The tier above this one, if any.
+    #[must_use]
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            Self::New => Some(Self::Established),
+            Self::Established => Some(Self::Trusted),
+            Self::Trusted => Some(Self::Exemplary),
+            Self::Exemplary => None,
+        }
+    }
+}
+
+impl fmt::Display for ReputationTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visibility_round_trips_through_str() {
+        for visibility in EndorsementVisibility::ALL {
+            assert_eq!(
+                visibility.as_str().parse::<EndorsementVisibility>().ok(),
+                Some(visibility)
+            );
+            assert_eq!(
+                serde_json::to_value(visibility).unwrap(),
+                serde_json::json!(visibility.as_str())
+            );
+        }
+        assert!("friends".parse::<EndorsementVisibility>().is_err());
+        assert_eq!(
+            EndorsementVisibility::default(),
+            EndorsementVisibility::Public
+        );
+    }
+
+    #[test]
+    fn dispute_status_round_trips_through_str() {
+        for status in DisputeStatus::ALL {
+            assert_eq!(status.as_str().parse::<DisputeStatus>().ok(), Some(status));
+            assert_eq!(
+                serde_json::to_value(status).unwrap(),
+                serde_json::json!(status.as_str())
+            );
+        }
+        assert!("closed".parse::<DisputeStatus>().is_err());
+    }
+
+    #[test]
+    fn tier_follows_configured_thresholds() {
+        let config = ReputationConfig::default();
+        assert_eq!(
+            ReputationTier::from_score(0.0, &config),
+            ReputationTier::New
+        );
+        assert_eq!(
+            ReputationTier::from_score(config.established_score, &config),
+            ReputationTier::Established
+        );
+        assert_eq!(
+            ReputationTier::from_score(config.exemplary_score - 0.1, &config),
+            ReputationTier::Trusted
+        );
+        assert_eq!(
+            ReputationTier::from_score(1e6, &config),
+            ReputationTier::Exemplary
+        );
+        assert_eq!(ReputationTier::Exemplary.next(), None);
+        for tier in ReputationTier::ALL {
+            assert_eq!(
+                serde_json::to_value(tier).unwrap(),
+                serde_json::json!(tier.as_str())
+            );
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Endorsement dispute persistence operations
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{map_unique_violation, RepoError, RepoErrorKind};
+use crate::reputation::DisputeStatus;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct DisputeRecord {
+    pub id: Uuid,
+    pub endorsement_id: Uuid,
+    pub subject_id: Uuid,
+    /// `None` for genesis endorsements, which have no issuer to notify.
+    pub issuer_id: Option<Uuid>,
+    pub topic: String,
+    pub statement: String,
+    /// The subject's signed request that opened the dispute.
+    pub envelope: serde_json::Value,
+    pub pauses_aggregates: bool,
+    pub status: DisputeStatus,
+    pub resolved_by: Option<Uuid>,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A dispute to open on behalf of an endorsement's subject.
+#[derive(Debug, Clone, Copy)]
+pub struct NewDispute<'a> {
+    pub endorsement_id: Uuid,
+    pub subject_id: Uuid,
+    pub statement: &'a str,
+    pub envelope: &'a serde_json::Value,
+    /// Leave the endorsement out of public aggregates until resolved.
+    pub pause_aggregates: bool,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum DisputeRepoError {
+    #[error("endorsement or dispute not found")]
+    NotFound,
+    #[error("endorsement has already been disputed")]
+    AlreadyDisputed,
+    #[error("dispute has already been resolved")]
+    AlreadyResolved,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for DisputeRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::AlreadyDisputed | Self::AlreadyResolved => RepoErrorKind::Conflict,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL row types ─────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct DisputeRow {
+    id: Uuid,
+    endorsement_id: Uuid,
+    subject_id: Uuid,
+    issuer_id: Option<Uuid>,
+    topic: String,
+    statement: String,
+    envelope: serde_json::Value,
+    pauses_aggregates: bool,
+    status: String,
+    resolved_by: Option<Uuid>,
+    resolution_note: Option<String>,
+    created_at: DateTime<Utc>,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<DisputeRow> for DisputeRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: DisputeRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            endorsement_id: row.endorsement_id,
+            subject_id: row.subject_id,
+            issuer_id: row.issuer_id,
+            topic: row.topic,
+            statement: row.statement,
+            envelope: row.envelope,
+            pauses_aggregates: row.pauses_aggregates,
+            status: row
+                .status
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            resolved_by: row.resolved_by,
+            resolution_note: row.resolution_note,
+            created_at: row.created_at,
+            resolved_at: row.resolved_at,
+        })
+    }
+}
+
+const DISPUTE_SELECT: &str = r"
+    SELECT d.id, d.endorsement_id, d.subject_id, d.issuer_id, e.topic, d.statement,
+           d.envelope, d.pauses_aggregates, d.status, d.resolved_by, d.resolution_note,
+           d.created_at, d.resolved_at
+    FROM reputation__endorsement_disputes d
+    JOIN reputation__endorsements e ON e.id = d.endorsement_id
+";
+
+async fn fetch_dispute<'e, E>(
+    executor: E,
+    dispute_id: Uuid,
+) -> Result<DisputeRecord, DisputeRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let row = sqlx::query_as::<_, DisputeRow>(&format!("{DISPUTE_SELECT} WHERE d.id = $1"))
+        .bind(dispute_id)
+        .fetch_one(executor)
+        .await?;
+    Ok(row.try_into()?)
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// Open a dispute on an active endorsement of `dispute.subject_id`, pausing
+/// its inclusion in public aggregates if asked.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the endorsement does not exist, is revoked, or is
+/// about someone else; `AlreadyDisputed` if it was disputed before.
+pub async fn open_dispute(
+    pool: &PgPool,
+    dispute: &NewDispute<'_>,
+) -> Result<DisputeRecord, DisputeRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let issuer: Option<Option<Uuid>> = sqlx::query_scalar(
+        r"
+        SELECT endorser_id FROM reputation__endorsements
+        WHERE id = $1 AND subject_id = $2 AND revoked_at IS NULL
+        FOR UPDATE
+        ",
+    )
+    .bind(dispute.endorsement_id)
+    .bind(dispute.subject_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let issuer_id = issuer.ok_or(DisputeRepoError::NotFound)?;
+
+    let id: Uuid = sqlx::query_scalar(
+        r"
+        INSERT INTO reputation__endorsement_disputes
+            (endorsement_id, subject_id, issuer_id, statement, envelope, pauses_aggregates)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        ",
+    )
+    .bind(dispute.endorsement_id)
+    .bind(dispute.subject_id)
+    .bind(issuer_id)
+    .bind(dispute.statement)
+    .bind(dispute.envelope)
+    .bind(dispute.pause_aggregates)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        map_unique_violation(e, |constraint| {
+            (constraint == "reputation__endorsement_disputes_endorsement_id_key")
+                .then_some(DisputeRepoError::AlreadyDisputed)
+        })
+    })?;
+
+    if dispute.pause_aggregates {
+        sqlx::query("UPDATE reputation__endorsements SET aggregates_paused = true WHERE id = $1")
+            .bind(dispute.endorsement_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let record = fetch_dispute(&mut *tx, id).await?;
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Open disputes awaiting review, oldest first.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_open_disputes(pool: &PgPool) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+    let rows = sqlx::query_as::<_, DisputeRow>(&format!(
+        "{DISPUTE_SELECT} WHERE d.status = 'open' ORDER BY d.created_at, d.id"
+    ))
+    .fetch_all(pool)
+    .await?;
+    rows.into_iter()
+        .map(|row| DisputeRecord::try_from(row).map_err(DisputeRepoError::from))
+        .collect()
+}
+
+/// Close the open dispute on `endorsement_id`.
+///
+/// Upholding revokes the endorsement; either outcome lifts any pause on
+/// aggregates.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the endorsement was never disputed and
+/// `AlreadyResolved` if its dispute is closed.
+pub async fn resolve_dispute(
+    pool: &PgPool,
+    endorsement_id: Uuid,
+    outcome: DisputeStatus,
+    resolved_by: Uuid,
+    note: Option<&str>,
+) -> Result<DisputeRecord, DisputeRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let resolved: Option<Uuid> = sqlx::query_scalar(
+        r"
+        UPDATE reputation__endorsement_disputes
+        SET status = $2, resolved_by = $3, resolution_note = $4, resolved_at = now()
+        WHERE endorsement_id = $1 AND status = 'open'
+        RETURNING id
+        ",
+    )
+    .bind(endorsement_id)
+    .bind(outcome.as_str())
+    .bind(resolved_by)
+    .bind(note)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(id) = resolved else {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM reputation__endorsement_disputes WHERE endorsement_id = $1)",
+        )
+        .bind(endorsement_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        return Err(if exists {
+            DisputeRepoError::AlreadyResolved
+        } else {
+            DisputeRepoError::NotFound
+        });
+    };
+
+    sqlx::query(
+        r"
+        UPDATE reputation__endorsements
+        SET aggregates_paused = false,
+            revoked_at = CASE WHEN $2 THEN COALESCE(revoked_at, now()) ELSE revoked_at END
+        WHERE id = $1
+        ",
+    )
+    .bind(endorsement_id)
+    .bind(outcome == DisputeStatus::Upheld)
+    .execute(&mut *tx)
+    .await?;
+
+    let record = fetch_dispute(&mut *tx, id).await?;
+    tx.commit().await?;
+    Ok(record)
+}
@@ -3,6 +3,10 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::db::{Instrumented, RepoError, RepoErrorKind};
+use crate::pagination::KeysetPage;
+use crate::reputation::{DisputeStatus, EndorsementVisibility};
+
 // ─── Record types ──────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -12,8 +16,37 @@ pub struct EndorsementRecord {
     pub topic: String,
     pub endorser_id: Option<Uuid>,
     pub evidence: Option<serde_json::Value>,
+    pub visibility: EndorsementVisibility,
+    /// Issuer's signed request; see [`IssuedEndorsement::envelope`].
+    pub issuance_envelope: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub revoked_at: Option<DateTime<Utc>>,
+    /// Status of the subject's dispute, if one was raised.
+    pub dispute_status: Option<DisputeStatus>,
+    /// Left out of public aggregates while a dispute is reviewed.
+    pub aggregates_paused: bool,
+}
+
+/// Active endorsement count for one topic, as aggregated for a subject.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct TopicCount {
+    pub topic: String,
+    pub count: i64,
+}
+
+/// A verifier-issued endorsement to create (or re-issue).
+#[derive(Debug, Clone, Copy)]
+pub struct IssuedEndorsement<'a> {
+    pub subject_id: Uuid,
+    pub topic: &'a str,
+    /// `None` for genesis endorsements.
+    pub issuer_id: Option<Uuid>,
+    pub evidence: Option<&'a serde_json::Value>,
+    pub visibility: EndorsementVisibility,
+    /// The issuer's device-signed request, as a serialized
+    /// [`SignedRequest`](crate::identity::signed_request::SignedRequest). Its body
+    /// names `visibility`, which makes the column tamper-evident.
+    pub envelope: Option<&'a serde_json::Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +66,15 @@ pub enum EndorsementRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for EndorsementRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 // ─── SQL row types ─────────────────────────────────────────────────────────
 
 #[derive(sqlx::FromRow)]
@@ -42,8 +84,12 @@ struct EndorsementRow {
     topic: String,
     endorser_id: Option<Uuid>,
     evidence: Option<serde_json::Value>,
+    visibility: String,
+    issuance_envelope: Option<serde_json::Value>,
     created_at: DateTime<Utc>,
     revoked_at: Option<DateTime<Utc>>,
+    dispute_status: Option<String>,
+    aggregates_paused: bool,
 }
 
 fn row_to_record(row: EndorsementRow) -> EndorsementRecord {
@@ -53,8 +99,14 @@ fn row_to_record(row: EndorsementRow) -> EndorsementRecord {
         topic: row.topic,
         endorser_id: row.endorser_id,
         evidence: row.evidence,
+        // The column's CHECK constraint only admits known values
+        visibility: row.visibility.parse().unwrap_or_default(),
+        issuance_envelope: row.issuance_envelope,
         created_at: row.created_at,
         revoked_at: row.revoked_at,
+        // Likewise constrained to known statuses
+        dispute_status: row.dispute_status.and_then(|s| s.parse().ok()),
+        aggregates_paused: row.aggregates_paused,
     }
 }
 
@@ -71,6 +123,9 @@ fn row_to_record(row: EndorsementRow) -> EndorsementRecord {
 /// returning an error. This idempotency applies only to non-genesis endorsements
 /// (`endorser_id IS NOT NULL`). Genesis endorsements (`endorser_id = None`) use a
 /// separate partial index and do not participate in this upsert path.
+///
+/// Re-endorsing after a revocation reactivates the row: `revoked_at` is
+/// cleared and `last_confirmed_at` restarts, since the edge is new again.
 #[allow(clippy::too_many_arguments)]
 pub async fn create_endorsement<'e, E>(
     executor: E,
@@ -94,7 +149,13 @@ where
         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         ON CONFLICT (subject_id, topic, endorser_id)
             DO UPDATE SET weight = EXCLUDED.weight, attestation = EXCLUDED.attestation,
-                          in_slot = EXCLUDED.in_slot
+                          in_slot = EXCLUDED.in_slot,
+                          last_confirmed_at = CASE
+                              WHEN reputation__endorsements.revoked_at IS NULL
+                                  THEN reputation__endorsements.last_confirmed_at
+                              ELSE now()
+                          END,
+                          revoked_at = NULL
         RETURNING id
         ",
     )
@@ -106,7 +167,7 @@ where
     .bind(weight)
     .bind(attestation)
     .bind(in_slot)
-    .fetch_one(executor)
+    .fetch_one(Instrumented::new(executor, "endorsements.create"))
     .await
     .map_err(EndorsementRepoError::Database)?;
 
@@ -117,6 +178,51 @@ where
     })
 }
 
+/// Create a verifier-issued endorsement, or re-issue an existing one from
+/// the same issuer.
+///
+/// Re-issuing replaces the evidence, visibility, and envelope, so the stored
+/// visibility always matches the most recent signed request.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn create_issued_endorsement<'e, E>(
+    executor: E,
+    endorsement: &IssuedEndorsement<'_>,
+) -> Result<CreatedEndorsement, EndorsementRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let id: Uuid = sqlx::query_scalar(
+        r"
+        INSERT INTO reputation__endorsements
+            (id, subject_id, topic, endorser_id, evidence, weight, in_slot,
+             visibility, issuance_envelope)
+        VALUES ($1, $2, $3, $4, $5, 1.0, true, $6, $7)
+        ON CONFLICT (subject_id, topic, endorser_id)
+            DO UPDATE SET evidence = EXCLUDED.evidence, visibility = EXCLUDED.visibility,
+                          issuance_envelope = EXCLUDED.issuance_envelope
+        RETURNING id
+        ",
+    )
+    .bind(Uuid::new_v4())
+    .bind(endorsement.subject_id)
+    .bind(endorsement.topic)
+    .bind(endorsement.issuer_id)
+    .bind(endorsement.evidence)
+    .bind(endorsement.visibility.as_str())
+    .bind(endorsement.envelope)
+    .fetch_one(Instrumented::new(executor, "endorsements.create_issued"))
+    .await?;
+
+    Ok(CreatedEndorsement {
+        id,
+        subject_id: endorsement.subject_id,
+        topic: endorsement.topic.to_string(),
+    })
+}
+
 /// # Errors
 ///
 /// Returns `Database` on connection or query failure.
@@ -138,7 +244,7 @@ where
     )
     .bind(subject_id)
     .bind(topic)
-    .fetch_one(executor)
+    .fetch_one(Instrumented::new(executor, "endorsements.has"))
     .await?;
 
     Ok(exists)
@@ -156,14 +262,66 @@ where
 {
     let rows = sqlx::query_as::<_, EndorsementRow>(
         r"
-        SELECT id, subject_id, topic, endorser_id, evidence, created_at, revoked_at
-        FROM reputation__endorsements
-        WHERE subject_id = $1
-        ORDER BY created_at DESC
+        SELECT e.id, e.subject_id, e.topic, e.endorser_id, e.evidence, e.visibility,
+               e.issuance_envelope, e.created_at, e.revoked_at,
+               d.status AS dispute_status, e.aggregates_paused
+        FROM reputation__endorsements e
+        LEFT JOIN reputation__endorsement_disputes d ON d.endorsement_id = e.id
+        WHERE e.subject_id = $1
+        ORDER BY e.created_at DESC
         ",
     )
     .bind(subject_id)
-    .fetch_all(executor)
+    .fetch_all(Instrumented::new(executor, "endorsements.list_by_subject"))
+    .await?;
+
+    Ok(rows.into_iter().map(row_to_record).collect())
+}
+
+/// One keyset page of a subject's endorsements, newest first, as of
+/// `page.as_of`.
+///
+/// Endorsements created after `as_of` are excluded and revocations after
+/// `as_of` are reported as not yet revoked, so every page of a walk sees the
+/// same snapshot. Returns up to `page.fetch_limit()` rows; see
+/// [`crate::pagination::finish_page`].
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_endorsements_by_subject_page<'e, E>(
+    executor: E,
+    subject_id: Uuid,
+    page: &KeysetPage,
+) -> Result<Vec<EndorsementRecord>, EndorsementRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let (after_created_at, after_id) = page.after.unzip();
+    let rows = sqlx::query_as::<_, EndorsementRow>(
+        r"
+        SELECT e.id, e.subject_id, e.topic, e.endorser_id, e.evidence, e.visibility,
+               e.issuance_envelope, e.created_at,
+               CASE WHEN e.revoked_at <= $2 THEN e.revoked_at END AS revoked_at,
+               d.status AS dispute_status, e.aggregates_paused
+        FROM reputation__endorsements e
+        LEFT JOIN reputation__endorsement_disputes d ON d.endorsement_id = e.id
+        WHERE e.subject_id = $1
+          AND e.created_at <= $2
+          AND ($3::timestamptz IS NULL OR (e.created_at, e.id) < ($3, $4))
+        ORDER BY e.created_at DESC, e.id DESC
+        LIMIT $5
+        ",
+    )
+    .bind(subject_id)
+    .bind(page.as_of)
+    .bind(after_created_at)
+    .bind(after_id)
+    .bind(page.fetch_limit())
+    .fetch_all(Instrumented::new(
+        executor,
+        "endorsements.list_by_subject_page",
+    ))
     .await?;
 
     Ok(rows.into_iter().map(row_to_record).collect())
@@ -195,7 +353,7 @@ where
     .bind(endorser_id)
     .bind(subject_id)
     .bind(topic)
-    .execute(executor)
+    .execute(Instrumented::new(executor, "endorsements.revoke"))
     .await?;
     Ok(())
 }
@@ -225,6 +383,40 @@ where
     Ok(count)
 }
 
+/// Count a subject's active (non-revoked) endorsements per topic, for public
+/// display.
+///
+/// Private endorsements and those paused by a dispute are left out. Topics are
+/// returned in alphabetical order; topics with no counted endorsements are
+/// omitted.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn count_active_endorsements_by_topic<'e, E>(
+    executor: E,
+    subject_id: Uuid,
+) -> Result<Vec<TopicCount>, EndorsementRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query_as::<_, TopicCount>(
+        r"
+        SELECT topic, COUNT(*) AS count
+        FROM reputation__endorsements
+        WHERE subject_id = $1 AND revoked_at IS NULL AND visibility <> 'private'
+          AND NOT aggregates_paused
+        GROUP BY topic
+        ORDER BY topic
+        ",
+    )
+    .bind(subject_id)
+    .fetch_all(Instrumented::new(executor, "endorsements.count_by_topic"))
+    .await?;
+
+    Ok(rows)
+}
+
 /// Count ALL active trust endorsements (including out-of-slot).
 ///
 /// # Errors
@@ -264,14 +456,20 @@ where
 {
     let row = sqlx::query_as::<_, EndorsementRow>(
         r"
-        SELECT id, subject_id, topic, endorser_id, evidence, created_at, revoked_at
-        FROM reputation__endorsements
-        WHERE subject_id = $1 AND topic = $2
+        SELECT e.id, e.subject_id, e.topic, e.endorser_id, e.evidence, e.visibility,
+               e.issuance_envelope, e.created_at, e.revoked_at,
+               d.status AS dispute_status, e.aggregates_paused
+        FROM reputation__endorsements e
+        LEFT JOIN reputation__endorsement_disputes d ON d.endorsement_id = e.id
+        WHERE e.subject_id = $1 AND e.topic = $2
         ",
     )
     .bind(subject_id)
     .bind(topic)
-    .fetch_optional(executor)
+    .fetch_optional(Instrumented::new(
+        executor,
+        "endorsements.by_subject_and_topic",
+    ))
     .await?;
 
     row.map_or_else(
@@ -3,6 +3,8 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::db::{map_unique_violation, RepoError, RepoErrorKind};
+
 // ─── Record types ──────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -26,6 +28,16 @@ pub enum ExternalIdentityRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for ExternalIdentityRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::AlreadyLinked => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 // ─── SQL row types ─────────────────────────────────────────────────────────
 
 #[derive(sqlx::FromRow)]
@@ -80,16 +92,10 @@ where
 
     match result {
         Ok(row) => Ok(row_to_record(row)),
-        Err(e) => {
-            if let sqlx::Error::Database(ref db_err) = e {
-                if let Some(constraint) = db_err.constraint() {
-                    if constraint == "uq_external_identities_provider_subject" {
-                        return Err(ExternalIdentityRepoError::AlreadyLinked);
-                    }
-                }
-            }
-            Err(ExternalIdentityRepoError::Database(e))
-        }
+        Err(e) => Err(map_unique_violation(e, |constraint| {
+            (constraint == "uq_external_identities_provider_subject")
+                .then_some(ExternalIdentityRepoError::AlreadyLinked)
+        })),
     }
 }
 
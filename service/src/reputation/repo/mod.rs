@@ -1,22 +1,45 @@
 //! Repository layer for reputation persistence
 
+pub mod disputes;
 pub mod endorsements;
 pub mod external_identities;
+pub mod strikes;
+pub mod verifications;
 
+pub use disputes::{
+    list_open_disputes, open_dispute, resolve_dispute, DisputeRecord, DisputeRepoError, NewDispute,
+};
 pub use endorsements::{
-    count_active_trust_endorsements_by, count_all_active_trust_endorsements_by, create_endorsement,
-    has_endorsement, list_endorsements_by_subject, revoke_endorsement, CreatedEndorsement,
-    EndorsementRecord, EndorsementRepoError,
+    count_active_endorsements_by_topic, count_active_trust_endorsements_by,
+    count_all_active_trust_endorsements_by, create_endorsement, create_issued_endorsement,
+    has_endorsement, list_endorsements_by_subject, list_endorsements_by_subject_page,
+    revoke_endorsement, CreatedEndorsement, EndorsementRecord, EndorsementRepoError,
+    IssuedEndorsement, TopicCount,
 };
 pub use external_identities::{
     get_external_identity_by_provider, link_external_identity, ExternalIdentityRecord,
     ExternalIdentityRepoError,
 };
+pub use strikes::{
+    appeal_strike, issue_strike, list_pending_appeals, list_strike_events_for_account,
+    list_strikes_for_account, resolve_appeal, NewStrike, StrikeEventRecord, StrikeRecord,
+    StrikeRepoError,
+};
+pub use verifications::{
+    attest_in_person, confirm_challenge, count_in_person_attestations, list_verifications,
+    record_verification, start_challenge, InPersonAttestation, NewChallenge, VerificationRecord,
+    VerificationRepoError, CODE_PROVIDER, IN_PERSON_PROVIDER,
+};
 
 use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::pagination::KeysetPage;
+use crate::reputation::strikes::AppealStatus;
+use crate::reputation::verification::VerificationMethod;
+use crate::reputation::DisputeStatus;
+
 /// Consolidated repository trait for reputation persistence.
 #[async_trait]
 pub trait ReputationRepo: Send + Sync {
@@ -34,6 +57,13 @@ pub trait ReputationRepo: Send + Sync {
         in_slot: bool,
     ) -> Result<CreatedEndorsement, EndorsementRepoError>;
 
+    /// Create or re-issue a verifier endorsement with its visibility and
+    /// signed issuance envelope.
+    async fn create_issued_endorsement(
+        &self,
+        endorsement: &IssuedEndorsement<'_>,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError>;
+
     async fn count_all_active_trust_endorsements_by(
         &self,
         endorser_id: Uuid,
@@ -50,6 +80,20 @@ pub trait ReputationRepo: Send + Sync {
         subject_id: Uuid,
     ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError>;
 
+    /// One keyset page of a subject's endorsements as of `page.as_of`.
+    async fn list_endorsements_by_subject_page(
+        &self,
+        subject_id: Uuid,
+        page: &KeysetPage,
+    ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError>;
+
+    /// Active endorsement counts per topic for a subject, excluding private
+    /// endorsements.
+    async fn count_active_endorsements_by_topic(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementRepoError>;
+
     async fn revoke_endorsement(
         &self,
         endorser_id: Uuid,
@@ -62,6 +106,95 @@ pub trait ReputationRepo: Send + Sync {
         endorser_id: Uuid,
     ) -> Result<i64, EndorsementRepoError>;
 
+    // Dispute operations
+
+    async fn open_dispute(
+        &self,
+        dispute: &NewDispute<'_>,
+    ) -> Result<DisputeRecord, DisputeRepoError>;
+
+    async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError>;
+
+    async fn resolve_dispute(
+        &self,
+        endorsement_id: Uuid,
+        outcome: DisputeStatus,
+        resolved_by: Uuid,
+        note: Option<&str>,
+    ) -> Result<DisputeRecord, DisputeRepoError>;
+
+    // Strike operations
+
+    async fn issue_strike(&self, strike: &NewStrike<'_>) -> Result<StrikeRecord, StrikeRepoError>;
+
+    async fn list_strikes_for_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<StrikeRecord>, StrikeRepoError>;
+
+    async fn list_strike_events_for_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError>;
+
+    async fn appeal_strike(
+        &self,
+        strike_id: Uuid,
+        account_id: Uuid,
+        statement: &str,
+    ) -> Result<StrikeRecord, StrikeRepoError>;
+
+    async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError>;
+
+    async fn resolve_appeal(
+        &self,
+        strike_id: Uuid,
+        outcome: AppealStatus,
+        resolved_by: Uuid,
+        note: Option<&str>,
+    ) -> Result<StrikeRecord, StrikeRepoError>;
+
+    // Verification operations
+
+    async fn list_verifications(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<VerificationRecord>, VerificationRepoError>;
+
+    async fn start_verification_challenge(
+        &self,
+        challenge: &NewChallenge<'_>,
+    ) -> Result<(), VerificationRepoError>;
+
+    async fn confirm_verification_challenge(
+        &self,
+        account_id: Uuid,
+        method: VerificationMethod,
+        code_hash: &[u8],
+        max_attempts: i32,
+        provider: &str,
+    ) -> Result<VerificationRecord, VerificationRepoError>;
+
+    async fn record_verification(
+        &self,
+        account_id: Uuid,
+        method: VerificationMethod,
+        provider: &str,
+        destination_hash: Option<&[u8]>,
+    ) -> Result<VerificationRecord, VerificationRepoError>;
+
+    async fn attest_in_person(
+        &self,
+        subject_id: Uuid,
+        attester_id: Uuid,
+        required: i64,
+    ) -> Result<InPersonAttestation, VerificationRepoError>;
+
+    async fn count_in_person_attestations(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<i64, VerificationRepoError>;
+
     // External identity operations
 
     async fn link_external_identity(
@@ -116,6 +249,13 @@ impl ReputationRepo for PgReputationRepo {
         .await
     }
 
+    async fn create_issued_endorsement(
+        &self,
+        endorsement: &IssuedEndorsement<'_>,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError> {
+        endorsements::create_issued_endorsement(&self.pool, endorsement).await
+    }
+
     async fn count_all_active_trust_endorsements_by(
         &self,
         endorser_id: Uuid,
@@ -138,6 +278,21 @@ impl ReputationRepo for PgReputationRepo {
         endorsements::list_endorsements_by_subject(&self.pool, subject_id).await
     }
 
+    async fn list_endorsements_by_subject_page(
+        &self,
+        subject_id: Uuid,
+        page: &KeysetPage,
+    ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
+        endorsements::list_endorsements_by_subject_page(&self.pool, subject_id, page).await
+    }
+
+    async fn count_active_endorsements_by_topic(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementRepoError> {
+        endorsements::count_active_endorsements_by_topic(&self.pool, subject_id).await
+    }
+
     async fn revoke_endorsement(
         &self,
         endorser_id: Uuid,
@@ -154,6 +309,134 @@ impl ReputationRepo for PgReputationRepo {
         endorsements::count_active_trust_endorsements_by(&self.pool, endorser_id).await
     }
 
+    async fn open_dispute(
+        &self,
+        dispute: &NewDispute<'_>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        disputes::open_dispute(&self.pool, dispute).await
+    }
+
+    async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+        disputes::list_open_disputes(&self.pool).await
+    }
+
+    async fn resolve_dispute(
+        &self,
+        endorsement_id: Uuid,
+        outcome: DisputeStatus,
+        resolved_by: Uuid,
+        note: Option<&str>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        disputes::resolve_dispute(&self.pool, endorsement_id, outcome, resolved_by, note).await
+    }
+
+    async fn issue_strike(&self, strike: &NewStrike<'_>) -> Result<StrikeRecord, StrikeRepoError> {
+        strikes::issue_strike(&self.pool, strike).await
+    }
+
+    async fn list_strikes_for_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        strikes::list_strikes_for_account(&self.pool, account_id).await
+    }
+
+    async fn list_strike_events_for_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+        strikes::list_strike_events_for_account(&self.pool, account_id).await
+    }
+
+    async fn appeal_strike(
+        &self,
+        strike_id: Uuid,
+        account_id: Uuid,
+        statement: &str,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        strikes::appeal_strike(&self.pool, strike_id, account_id, statement).await
+    }
+
+    async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        strikes::list_pending_appeals(&self.pool).await
+    }
+
+    async fn resolve_appeal(
+        &self,
+        strike_id: Uuid,
+        outcome: AppealStatus,
+        resolved_by: Uuid,
+        note: Option<&str>,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        strikes::resolve_appeal(&self.pool, strike_id, outcome, resolved_by, note).await
+    }
+
+    async fn list_verifications(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+        verifications::list_verifications(&self.pool, account_id).await
+    }
+
+    async fn start_verification_challenge(
+        &self,
+        challenge: &NewChallenge<'_>,
+    ) -> Result<(), VerificationRepoError> {
+        verifications::start_challenge(&self.pool, challenge).await
+    }
+
+    async fn confirm_verification_challenge(
+        &self,
+        account_id: Uuid,
+        method: VerificationMethod,
+        code_hash: &[u8],
+        max_attempts: i32,
+        provider: &str,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        verifications::confirm_challenge(
+            &self.pool,
+            account_id,
+            method,
+            code_hash,
+            max_attempts,
+            provider,
+        )
+        .await
+    }
+
+    async fn record_verification(
+        &self,
+        account_id: Uuid,
+        method: VerificationMethod,
+        provider: &str,
+        destination_hash: Option<&[u8]>,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        verifications::record_verification(
+            &self.pool,
+            account_id,
+            method,
+            provider,
+            destination_hash,
+        )
+        .await
+    }
+
+    async fn attest_in_person(
+        &self,
+        subject_id: Uuid,
+        attester_id: Uuid,
+        required: i64,
+    ) -> Result<InPersonAttestation, VerificationRepoError> {
+        verifications::attest_in_person(&self.pool, subject_id, attester_id, required).await
+    }
+
+    async fn count_in_person_attestations(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<i64, VerificationRepoError> {
+        verifications::count_in_person_attestations(&self.pool, subject_id).await
+    }
+
     async fn link_external_identity(
         &self,
         account_id: Uuid,
@@ -0,0 +1,388 @@
+//! Strike persistence operations
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::{RepoError, RepoErrorKind};
+use crate::reputation::strikes::{AppealStatus, StrikeAction, StrikeReason};
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct StrikeRecord {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// `None` once the issuing moderator's account is deleted.
+    pub issued_by: Option<Uuid>,
+    pub reason: StrikeReason,
+    pub note: Option<String>,
+    /// Full penalty at issue; see
+    /// [`decayed_penalty`](crate::reputation::strikes::decayed_penalty).
+    pub penalty: f64,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub appeal_status: AppealStatus,
+    pub appeal_statement: Option<String>,
+    pub appealed_at: Option<DateTime<Utc>>,
+    pub appeal_resolved_by: Option<Uuid>,
+    pub appeal_resolved_at: Option<DateTime<Utc>>,
+}
+
+/// One entry in a strike's audit trail.
+#[derive(Debug, Clone)]
+pub struct StrikeEventRecord {
+    pub strike_id: Uuid,
+    pub action: StrikeAction,
+    pub actor_account_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NewStrike<'a> {
+    pub account_id: Uuid,
+    pub issued_by: Uuid,
+    pub reason: StrikeReason,
+    pub note: Option<&'a str>,
+    pub penalty: f64,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum StrikeRepoError {
+    #[error("strike not found")]
+    NotFound,
+    #[error("strike has already been appealed or has expired")]
+    AppealNotAllowed,
+    #[error("strike has no pending appeal")]
+    NoPendingAppeal,
+    #[error("moderators cannot review appeals of their own strikes")]
+    OwnStrike,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for StrikeRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::AppealNotAllowed | Self::NoPendingAppeal => RepoErrorKind::Conflict,
+            Self::OwnStrike => RepoErrorKind::Rejected,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL row types ─────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct StrikeRow {
+    id: Uuid,
+    account_id: Uuid,
+    issued_by: Option<Uuid>,
+    reason_code: String,
+    note: Option<String>,
+    penalty: f64,
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    appeal_status: String,
+    appeal_statement: Option<String>,
+    appealed_at: Option<DateTime<Utc>>,
+    appeal_resolved_by: Option<Uuid>,
+    appeal_resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<StrikeRow> for StrikeRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: StrikeRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            account_id: row.account_id,
+            issued_by: row.issued_by,
+            reason: row
+                .reason_code
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            note: row.note,
+            penalty: row.penalty,
+            issued_at: row.issued_at,
+            expires_at: row.expires_at,
+            appeal_status: row
+                .appeal_status
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            appeal_statement: row.appeal_statement,
+            appealed_at: row.appealed_at,
+            appeal_resolved_by: row.appeal_resolved_by,
+            appeal_resolved_at: row.appeal_resolved_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StrikeEventRow {
+    strike_id: Uuid,
+    action: String,
+    actor_account_id: Option<Uuid>,
+    note: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<StrikeEventRow> for StrikeEventRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: StrikeEventRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            strike_id: row.strike_id,
+            action: row
+                .action
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            actor_account_id: row.actor_account_id,
+            note: row.note,
+            created_at: row.created_at,
+        })
+    }
+}
+
+const STRIKE_COLUMNS: &str = "id, account_id, issued_by, reason_code, note, penalty, issued_at, \
+                              expires_at, appeal_status, appeal_statement, appealed_at, \
+                              appeal_resolved_by, appeal_resolved_at";
+
+fn into_records(rows: Vec<StrikeRow>) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+    rows.into_iter()
+        .map(|row| StrikeRecord::try_from(row).map_err(StrikeRepoError::from))
+        .collect()
+}
+
+async fn record_event(
+    tx: &mut Transaction<'_, Postgres>,
+    strike_id: Uuid,
+    action: StrikeAction,
+    actor_account_id: Uuid,
+    note: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r"
+        INSERT INTO reputation__strike_events (strike_id, action, actor_account_id, note)
+        VALUES ($1, $2, $3, $4)
+        ",
+    )
+    .bind(strike_id)
+    .bind(action.as_str())
+    .bind(actor_account_id)
+    .bind(note)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+// ─── Queries ───────────────────────────────────────────────────────────────
+
+/// Record a strike and its `issued` audit event.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn issue_strike(
+    pool: &PgPool,
+    strike: &NewStrike<'_>,
+) -> Result<StrikeRecord, StrikeRepoError> {
+    let mut tx = pool.begin().await?;
+    let row = sqlx::query_as::<_, StrikeRow>(&format!(
+        r"
+        INSERT INTO reputation__strikes
+            (account_id, issued_by, reason_code, note, penalty, issued_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING {STRIKE_COLUMNS}
+        "
+    ))
+    .bind(strike.account_id)
+    .bind(strike.issued_by)
+    .bind(strike.reason.as_str())
+    .bind(strike.note)
+    .bind(strike.penalty)
+    .bind(strike.issued_at)
+    .bind(strike.expires_at)
+    .fetch_one(&mut *tx)
+    .await?;
+    record_event(
+        &mut tx,
+        row.id,
+        StrikeAction::Issued,
+        strike.issued_by,
+        strike.note,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(row.try_into()?)
+}
+
+/// Every strike against `account_id`, newest first.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_strikes_for_account(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+    let rows = sqlx::query_as::<_, StrikeRow>(&format!(
+        "SELECT {STRIKE_COLUMNS} FROM reputation__strikes \
+         WHERE account_id = $1 ORDER BY issued_at DESC, id"
+    ))
+    .bind(account_id)
+    .fetch_all(pool)
+    .await?;
+    into_records(rows)
+}
+
+/// Audit events for every strike against `account_id`, oldest first.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_strike_events_for_account(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+    let rows = sqlx::query_as::<_, StrikeEventRow>(
+        r"
+        SELECT ev.strike_id, ev.action, ev.actor_account_id, ev.note, ev.created_at
+        FROM reputation__strike_events ev
+        JOIN reputation__strikes s ON s.id = ev.strike_id
+        WHERE s.account_id = $1
+        ORDER BY ev.created_at, ev.id
+        ",
+    )
+    .bind(account_id)
+    .fetch_all(pool)
+    .await?;
+    rows.into_iter()
+        .map(|row| StrikeEventRecord::try_from(row).map_err(StrikeRepoError::from))
+        .collect()
+}
+
+/// Appeal an unexpired, not-yet-appealed strike against `account_id`.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the strike does not exist or is against someone
+/// else, and `AppealNotAllowed` if it was already appealed or has expired.
+pub async fn appeal_strike(
+    pool: &PgPool,
+    strike_id: Uuid,
+    account_id: Uuid,
+    statement: &str,
+) -> Result<StrikeRecord, StrikeRepoError> {
+    let mut tx = pool.begin().await?;
+    let row = sqlx::query_as::<_, StrikeRow>(&format!(
+        r"
+        UPDATE reputation__strikes
+        SET appeal_status = 'pending', appeal_statement = $3, appealed_at = now()
+        WHERE id = $1 AND account_id = $2 AND appeal_status = 'none' AND expires_at > now()
+        RETURNING {STRIKE_COLUMNS}
+        "
+    ))
+    .bind(strike_id)
+    .bind(account_id)
+    .bind(statement)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(row) = row else {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM reputation__strikes WHERE id = $1 AND account_id = $2)",
+        )
+        .bind(strike_id)
+        .bind(account_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        return Err(if exists {
+            StrikeRepoError::AppealNotAllowed
+        } else {
+            StrikeRepoError::NotFound
+        });
+    };
+    record_event(
+        &mut tx,
+        strike_id,
+        StrikeAction::Appealed,
+        account_id,
+        Some(statement),
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(row.try_into()?)
+}
+
+/// Strikes with a pending appeal, oldest appeal first.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_pending_appeals(pool: &PgPool) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+    let rows = sqlx::query_as::<_, StrikeRow>(&format!(
+        "SELECT {STRIKE_COLUMNS} FROM reputation__strikes \
+         WHERE appeal_status = 'pending' ORDER BY appealed_at, id"
+    ))
+    .fetch_all(pool)
+    .await?;
+    into_records(rows)
+}
+
+/// Grant or deny the pending appeal on `strike_id`.
+///
+/// `outcome` must be [`AppealStatus::Granted`] or [`AppealStatus::Denied`].
+///
+/// # Errors
+///
+/// Returns `NotFound` if the strike does not exist, `OwnStrike` if it is
+/// against `resolved_by`, and `NoPendingAppeal` if it has no appeal awaiting
+/// review.
+pub async fn resolve_appeal(
+    pool: &PgPool,
+    strike_id: Uuid,
+    outcome: AppealStatus,
+    resolved_by: Uuid,
+    note: Option<&str>,
+) -> Result<StrikeRecord, StrikeRepoError> {
+    let action = if outcome == AppealStatus::Granted {
+        StrikeAction::AppealGranted
+    } else {
+        StrikeAction::AppealDenied
+    };
+    let mut tx = pool.begin().await?;
+    let row = sqlx::query_as::<_, StrikeRow>(&format!(
+        r"
+        UPDATE reputation__strikes
+        SET appeal_status = $2, appeal_resolved_by = $3, appeal_resolved_at = now()
+        WHERE id = $1 AND appeal_status = 'pending' AND account_id <> $3
+        RETURNING {STRIKE_COLUMNS}
+        "
+    ))
+    .bind(strike_id)
+    .bind(outcome.as_str())
+    .bind(resolved_by)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(row) = row else {
+        let struck: Option<Uuid> =
+            sqlx::query_scalar("SELECT account_id FROM reputation__strikes WHERE id = $1")
+                .bind(strike_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        return Err(match struck {
+            None => StrikeRepoError::NotFound,
+            Some(account_id) if account_id == resolved_by => StrikeRepoError::OwnStrike,
+            Some(_) => StrikeRepoError::NoPendingAppeal,
+        });
+    };
+    record_event(&mut tx, strike_id, action, resolved_by, note).await?;
+    tx.commit().await?;
+    Ok(row.try_into()?)
+}
@@ -0,0 +1,385 @@
+//! Account verification persistence operations
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::{map_unique_violation, RepoError, RepoErrorKind};
+use crate::reputation::verification::VerificationMethod;
+
+/// Provider recorded on email and phone verifications.
+pub const CODE_PROVIDER: &str = "code";
+
+/// Provider recorded on in-person verifications.
+pub const IN_PERSON_PROVIDER: &str = "attestation";
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct VerificationRecord {
+    pub account_id: Uuid,
+    pub method: VerificationMethod,
+    /// Who performed the check: [`CODE_PROVIDER`], [`IN_PERSON_PROVIDER`],
+    /// or the configured ID verification provider.
+    pub provider: String,
+    pub verified_at: DateTime<Utc>,
+}
+
+/// A freshly sent email or phone code.
+#[derive(Debug, Clone, Copy)]
+pub struct NewChallenge<'a> {
+    pub account_id: Uuid,
+    pub method: VerificationMethod,
+    pub destination_hash: &'a [u8],
+    pub code_hash: &'a [u8],
+    pub expires_at: DateTime<Utc>,
+    /// Seconds that must pass before the account's previous code for the
+    /// same method may be replaced.
+    pub resend_cooldown_secs: i64,
+}
+
+/// Result of one in-person attestation.
+#[derive(Debug, Clone)]
+pub struct InPersonAttestation {
+    /// Attestations the subject now has.
+    pub attestations: i64,
+    /// Set once the subject has reached the threshold.
+    pub verification: Option<VerificationRecord>,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationRepoError {
+    #[error("no pending verification code")]
+    NoChallenge,
+    #[error("verification code is incorrect")]
+    InvalidCode,
+    #[error("too many incorrect codes; request a new one")]
+    TooManyAttempts,
+    #[error("a code was sent recently; wait before requesting another")]
+    TooSoon,
+    #[error("this destination already verifies another account")]
+    DestinationTaken,
+    #[error("already attested to this account")]
+    AlreadyAttested,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for VerificationRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NoChallenge => RepoErrorKind::NotFound,
+            Self::InvalidCode => RepoErrorKind::Rejected,
+            Self::TooManyAttempts | Self::TooSoon => RepoErrorKind::LimitReached,
+            Self::DestinationTaken | Self::AlreadyAttested => RepoErrorKind::Conflict,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL row types ─────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct VerificationRow {
+    account_id: Uuid,
+    method: String,
+    provider: String,
+    verified_at: DateTime<Utc>,
+}
+
+impl TryFrom<VerificationRow> for VerificationRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: VerificationRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_id: row.account_id,
+            method: row
+                .method
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            provider: row.provider,
+            verified_at: row.verified_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ChallengeRow {
+    destination_hash: Vec<u8>,
+    code_hash: Vec<u8>,
+    attempts: i32,
+    expires_at: DateTime<Utc>,
+}
+
+fn map_destination_taken(e: sqlx::Error) -> VerificationRepoError {
+    map_unique_violation(e, |constraint| {
+        (constraint == "uq_reputation_verifications_destination")
+            .then_some(VerificationRepoError::DestinationTaken)
+    })
+}
+
+async fn upsert_verification(
+    tx: &mut Transaction<'_, Postgres>,
+    account_id: Uuid,
+    method: VerificationMethod,
+    provider: &str,
+    destination_hash: Option<&[u8]>,
+) -> Result<VerificationRecord, VerificationRepoError> {
+    let row = sqlx::query_as::<_, VerificationRow>(
+        r"
+        INSERT INTO reputation__verifications (account_id, method, provider, destination_hash)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (account_id, method) DO UPDATE
+        SET provider = EXCLUDED.provider,
+            destination_hash = EXCLUDED.destination_hash,
+            verified_at = now()
+        RETURNING account_id, method, provider, verified_at
+        ",
+    )
+    .bind(account_id)
+    .bind(method.as_str())
+    .bind(provider)
+    .bind(destination_hash)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(map_destination_taken)?;
+    Ok(row.try_into()?)
+}
+
+// ─── Queries ───────────────────────────────────────────────────────────────
+
+/// Every method `account_id` has completed, weakest first.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_verifications(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+    let rows = sqlx::query_as::<_, VerificationRow>(
+        r"
+        SELECT account_id, method, provider, verified_at
+        FROM reputation__verifications
+        WHERE account_id = $1
+        ",
+    )
+    .bind(account_id)
+    .fetch_all(pool)
+    .await?;
+    let mut records = rows
+        .into_iter()
+        .map(VerificationRecord::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    records.sort_by_key(|r| r.method);
+    Ok(records)
+}
+
+/// Store a newly sent code, replacing the account's previous code for the
+/// same method.
+///
+/// Whether another account already verified the destination is only
+/// revealed on confirmation, to someone who controls it.
+///
+/// # Errors
+///
+/// Returns `TooSoon` if the previous code is younger than the resend
+/// cooldown.
+pub async fn start_challenge(
+    pool: &PgPool,
+    challenge: &NewChallenge<'_>,
+) -> Result<(), VerificationRepoError> {
+    let stored: Option<i32> = sqlx::query_scalar(
+        r"
+        INSERT INTO reputation__verification_challenges
+            (account_id, method, destination_hash, code_hash, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (account_id, method) DO UPDATE
+        SET destination_hash = EXCLUDED.destination_hash,
+            code_hash = EXCLUDED.code_hash,
+            attempts = 0,
+            created_at = now(),
+            expires_at = EXCLUDED.expires_at
+        WHERE reputation__verification_challenges.created_at
+              <= now() - make_interval(secs => $6::float8)
+        RETURNING attempts
+        ",
+    )
+    .bind(challenge.account_id)
+    .bind(challenge.method.as_str())
+    .bind(challenge.destination_hash)
+    .bind(challenge.code_hash)
+    .bind(challenge.expires_at)
+    .bind(challenge.resend_cooldown_secs)
+    .fetch_optional(pool)
+    .await?;
+    stored.map(|_| ()).ok_or(VerificationRepoError::TooSoon)
+}
+
+/// Check a code against the account's pending challenge and, if it
+/// matches, record the verification.
+///
+/// A wrong code counts as an attempt; after `max_attempts` the challenge
+/// accepts nothing until a new code is requested.
+///
+/// # Errors
+///
+/// Returns `NoChallenge` if no unexpired code is pending, `TooManyAttempts`
+/// once attempts are used up, `InvalidCode` for a wrong code, and
+/// `DestinationTaken` if another account verified the destination first.
+pub async fn confirm_challenge(
+    pool: &PgPool,
+    account_id: Uuid,
+    method: VerificationMethod,
+    code_hash: &[u8],
+    max_attempts: i32,
+    provider: &str,
+) -> Result<VerificationRecord, VerificationRepoError> {
+    let mut tx = pool.begin().await?;
+    let challenge = sqlx::query_as::<_, ChallengeRow>(
+        r"
+        SELECT destination_hash, code_hash, attempts, expires_at
+        FROM reputation__verification_challenges
+        WHERE account_id = $1 AND method = $2
+        FOR UPDATE
+        ",
+    )
+    .bind(account_id)
+    .bind(method.as_str())
+    .fetch_optional(&mut *tx)
+    .await?
+    .filter(|c| c.expires_at > Utc::now())
+    .ok_or(VerificationRepoError::NoChallenge)?;
+    if challenge.attempts >= max_attempts {
+        return Err(VerificationRepoError::TooManyAttempts);
+    }
+    if challenge.code_hash != code_hash {
+        sqlx::query(
+            r"
+            UPDATE reputation__verification_challenges
+            SET attempts = attempts + 1
+            WHERE account_id = $1 AND method = $2
+            ",
+        )
+        .bind(account_id)
+        .bind(method.as_str())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        return Err(VerificationRepoError::InvalidCode);
+    }
+
+    sqlx::query(
+        "DELETE FROM reputation__verification_challenges WHERE account_id = $1 AND method = $2",
+    )
+    .bind(account_id)
+    .bind(method.as_str())
+    .execute(&mut *tx)
+    .await?;
+    let record = upsert_verification(
+        &mut tx,
+        account_id,
+        method,
+        provider,
+        Some(&challenge.destination_hash),
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Record a verification completed outside the code flow, e.g. by an ID
+/// verification provider.
+///
+/// # Errors
+///
+/// Returns `DestinationTaken` if `destination_hash` already verifies
+/// another account.
+pub async fn record_verification(
+    pool: &PgPool,
+    account_id: Uuid,
+    method: VerificationMethod,
+    provider: &str,
+    destination_hash: Option<&[u8]>,
+) -> Result<VerificationRecord, VerificationRepoError> {
+    let mut tx = pool.begin().await?;
+    let record =
+        upsert_verification(&mut tx, account_id, method, provider, destination_hash).await?;
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Record that `attester_id` met `subject_id` in person, and verify the
+/// subject once it has `required` attestations.
+///
+/// # Errors
+///
+/// Returns `AlreadyAttested` if `attester_id` has already attested to
+/// `subject_id`.
+pub async fn attest_in_person(
+    pool: &PgPool,
+    subject_id: Uuid,
+    attester_id: Uuid,
+    required: i64,
+) -> Result<InPersonAttestation, VerificationRepoError> {
+    let mut tx = pool.begin().await?;
+    let inserted = sqlx::query(
+        r"
+        INSERT INTO reputation__in_person_attestations (subject_id, attester_id)
+        VALUES ($1, $2)
+        ON CONFLICT DO NOTHING
+        ",
+    )
+    .bind(subject_id)
+    .bind(attester_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if inserted == 0 {
+        return Err(VerificationRepoError::AlreadyAttested);
+    }
+    let attestations: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM reputation__in_person_attestations WHERE subject_id = $1",
+    )
+    .bind(subject_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let verification = if attestations >= required {
+        Some(
+            upsert_verification(
+                &mut tx,
+                subject_id,
+                VerificationMethod::InPerson,
+                IN_PERSON_PROVIDER,
+                None,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    tx.commit().await?;
+    Ok(InPersonAttestation {
+        attestations,
+        verification,
+    })
+}
+
+/// How many accounts have attested to meeting `subject_id` in person.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn count_in_person_attestations(
+    pool: &PgPool,
+    subject_id: Uuid,
+) -> Result<i64, VerificationRepoError> {
+    Ok(sqlx::query_scalar(
+        "SELECT COUNT(*) FROM reputation__in_person_attestations WHERE subject_id = $1",
+    )
+    .bind(subject_id)
+    .fetch_one(pool)
+    .await?)
+}
@@ -0,0 +1,430 @@
+//! Reputation scores and their explanation.
+//!
+//! A score is the sum of itemized [`ScoreComponent`]s from four sources:
+//!
+//! - **Aggregates** — active public endorsements, counted per topic up to
+//!   [`MAX_COUNTED_PER_TOPIC`], plus a flat bonus for [`VERIFIED_TOPIC`].
+//!   Private endorsements and those paused by a dispute never count.
+//! - **Trust signals** — the account's global trust snapshot: path
+//!   diversity, eigenvector centrality and distance from the anchors.
+//! - **Verifications** — each completed
+//!   [`VerificationMethod`](super::verification::VerificationMethod) adds
+//!   its [`points`](super::verification::VerificationMethod::points).
+//! - **Penalties** — open denouncements against the account, and
+//!   moderator [strikes](super::strikes) whose penalty decays to zero at
+//!   expiry. Strikes overturned on appeal do not count.
+//!
+//! The total is floored at zero and mapped to a [`ReputationTier`] using the
+//! configured thresholds. Every component is returned alongside the score so
+//! users can see exactly why they hold the tier they do.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::repo::{
+    EndorsementRepoError, ReputationRepo, StrikeRecord, StrikeRepoError, TopicCount,
+    VerificationRepoError,
+};
+use super::strikes::{decayed_penalty, AppealStatus};
+use super::verification::VerificationMethod;
+use super::{ReputationTier, VERIFIED_TOPIC};
+use crate::config::ReputationConfig;
+use crate::trust::repo::{ScoreSnapshot, TrustRepo, TrustRepoError};
+use crate::visibility::{Redact, Viewer};
+
+/// Points per active public endorsement.
+pub const ENDORSEMENT_POINTS: f64 = 5.0;
+/// Endorsements counted per topic; more add nothing.
+pub const MAX_COUNTED_PER_TOPIC: i64 = 5;
+/// Flat bonus for holding an active [`VERIFIED_TOPIC`] endorsement.
+pub const VERIFIED_POINTS: f64 = 30.0;
+/// Points per independent trust path.
+pub const PATH_DIVERSITY_POINTS: f64 = 4.0;
+/// Trust paths counted; more add nothing.
+pub const MAX_COUNTED_PATHS: i32 = 10;
+/// Points for an eigenvector centrality of 1.0, scaled linearly.
+pub const CENTRALITY_POINTS: f64 = 50.0;
+/// Points for sitting one hop from an anchor; each further hop costs
+/// [`DISTANCE_STEP_POINTS`].
+pub const PROXIMITY_POINTS: f64 = 20.0;
+pub const DISTANCE_STEP_POINTS: f64 = 4.0;
+/// Points deducted per open denouncement.
+pub const DENOUNCEMENT_PENALTY: f64 = 15.0;
+
+/// Which part of the model a component comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreComponentKind {
+    Aggregate,
+    TrustSignal,
+    Verification,
+    Penalty,
+}
+
+/// One itemized contribution to a score.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ScoreComponent {
+    pub kind: ScoreComponentKind,
+    /// What was measured, e.g. `endorsements:local_resident`,
+    /// `path_diversity`, `verification:phone` or `strike:spam`.
+    pub source: String,
+    /// The raw measurement (a count, centrality, distance, or a strike's
+    /// full penalty).
+    pub value: f64,
+    /// Points this component adds; negative for penalties.
+    pub points: f64,
+}
+
+/// Everything that produced an account's score and tier.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ReputationExplanation {
+    pub score: f64,
+    pub tier: ReputationTier,
+    pub components: Vec<ScoreComponent>,
+    /// The tier above the current one; null at the top tier.
+    pub next_tier: Option<ReputationTier>,
+    /// Points still needed to reach `next_tier`.
+    pub points_to_next_tier: Option<f64>,
+}
+
+impl Redact for ReputationExplanation {
+    /// Strike reasons are shown to the owner and moderators only; everyone
+    /// else sees a bare `strike` source with the same points.
+    fn redact(mut self, viewer: Viewer) -> Self {
+        if viewer < Viewer::Moderator {
+            for component in &mut self.components {
+                if component.source.starts_with("strike:") {
+                    component.source = "strike".to_string();
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Raw measurements for one account.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreInputs<'a> {
+    /// Public endorsement counts, as from
+    /// [`count_active_endorsements_by_topic`](super::repo::count_active_endorsements_by_topic).
+    pub topic_counts: &'a [TopicCount],
+    /// Global trust snapshot; `None` until the trust engine has scored the
+    /// account.
+    pub trust: Option<&'a ScoreSnapshot>,
+    pub open_denouncements: usize,
+    /// Completed verification methods.
+    pub verifications: &'a [VerificationMethod],
+    /// Every strike against the account; expired and overturned ones are
+    /// skipped.
+    pub strikes: &'a [StrikeRecord],
+    /// Instant strike penalties are decayed to.
+    pub as_of: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExplainError {
+    #[error("endorsement lookup failed: {0}")]
+    Endorsements(#[from] EndorsementRepoError),
+    #[error("trust lookup failed: {0}")]
+    Trust(#[from] TrustRepoError),
+    #[error("strike lookup failed: {0}")]
+    Strikes(#[from] StrikeRepoError),
+    #[error("verification lookup failed: {0}")]
+    Verifications(#[from] VerificationRepoError),
+}
+
+/// Score `inputs` and map the result onto a tier.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn explain(inputs: &ScoreInputs<'_>, config: &ReputationConfig) -> ReputationExplanation {
+    let mut components = Vec::new();
+
+    for count in inputs.topic_counts.iter().filter(|c| c.count > 0) {
+        let counted = count.count.min(MAX_COUNTED_PER_TOPIC);
+        components.push(ScoreComponent {
+            kind: ScoreComponentKind::Aggregate,
+            source: format!("endorsements:{}", count.topic),
+            value: count.count as f64,
+            points: counted as f64 * ENDORSEMENT_POINTS,
+        });
+        if count.topic == VERIFIED_TOPIC {
+            components.push(ScoreComponent {
+                kind: ScoreComponentKind::Aggregate,
+                source: VERIFIED_TOPIC.to_string(),
+                value: 1.0,
+                points: VERIFIED_POINTS,
+            });
+        }
+    }
+
+    if let Some(trust) = inputs.trust {
+        if let Some(diversity) = trust.path_diversity.filter(|d| *d > 0) {
+            components.push(ScoreComponent {
+                kind: ScoreComponentKind::TrustSignal,
+                source: "path_diversity".to_string(),
+                value: f64::from(diversity),
+                points: f64::from(diversity.min(MAX_COUNTED_PATHS)) * PATH_DIVERSITY_POINTS,
+            });
+        }
+        if let Some(centrality) = trust.eigenvector_centrality.filter(|c| *c > 0.0) {
+            let centrality = f64::from(centrality);
+            components.push(ScoreComponent {
+                kind: ScoreComponentKind::TrustSignal,
+                source: "eigenvector_centrality".to_string(),
+                value: centrality,
+                points: centrality.min(1.0) * CENTRALITY_POINTS,
+            });
+        }
+        if let Some(distance) = trust.trust_distance {
+            let distance = f64::from(distance);
+            let points = DISTANCE_STEP_POINTS
+                .mul_add(-(distance - 1.0).max(0.0), PROXIMITY_POINTS)
+                .max(0.0);
+            if points > 0.0 {
+                components.push(ScoreComponent {
+                    kind: ScoreComponentKind::TrustSignal,
+                    source: "trust_distance".to_string(),
+                    value: distance,
+                    points,
+                });
+            }
+        }
+    }
+
+    for method in inputs.verifications {
+        components.push(ScoreComponent {
+            kind: ScoreComponentKind::Verification,
+            source: format!("verification:{method}"),
+            value: 1.0,
+            points: method.points(),
+        });
+    }
+
+    if inputs.open_denouncements > 0 {
+        let count = inputs.open_denouncements as f64;
+        components.push(ScoreComponent {
+            kind: ScoreComponentKind::Penalty,
+            source: "denouncements".to_string(),
+            value: count,
+            points: -count * DENOUNCEMENT_PENALTY,
+        });
+    }
+
+    for strike in inputs
+        .strikes
+        .iter()
+        .filter(|s| s.appeal_status != AppealStatus::Granted)
+    {
+        let points = decayed_penalty(
+            strike.penalty,
+            strike.issued_at,
+            strike.expires_at,
+            inputs.as_of,
+        );
+        if points > 0.0 {
+            components.push(ScoreComponent {
+                kind: ScoreComponentKind::Penalty,
+                source: format!("strike:{}", strike.reason),
+                value: strike.penalty,
+                points: -points,
+            });
+        }
+    }
+
+    let score = components.iter().map(|c| c.points).sum::<f64>().max(0.0);
+    let tier = ReputationTier::from_score(score, config);
+    let next_tier = tier.next();
+    ReputationExplanation {
+        score,
+        tier,
+        components,
+        next_tier,
+        points_to_next_tier: next_tier.map(|t| t.threshold(config) - score),
+    }
+}
+
+/// Load `account_id`'s measurements and [`explain`] them.
+///
+/// # Errors
+///
+/// Returns an error if any of the lookups fail.
+pub async fn explain_account(
+    reputation: &dyn ReputationRepo,
+    trust: &dyn TrustRepo,
+    config: &ReputationConfig,
+    account_id: Uuid,
+) -> Result<ReputationExplanation, ExplainError> {
+    let topic_counts = reputation
+        .count_active_endorsements_by_topic(account_id)
+        .await?;
+    let snapshot = trust.get_score(account_id, None).await?;
+    let open_denouncements = trust
+        .list_denouncements_against(account_id)
+        .await?
+        .iter()
+        .filter(|d| d.resolved_at.is_none())
+        .count();
+    let strikes = reputation.list_strikes_for_account(account_id).await?;
+    let verifications: Vec<VerificationMethod> = reputation
+        .list_verifications(account_id)
+        .await?
+        .into_iter()
+        .map(|v| v.method)
+        .collect();
+    Ok(explain(
+        &ScoreInputs {
+            topic_counts: &topic_counts,
+            trust: snapshot.as_ref(),
+            open_denouncements,
+            verifications: &verifications,
+            strikes: &strikes,
+            as_of: Utc::now(),
+        },
+        config,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reputation::strikes::StrikeReason;
+    use chrono::Duration;
+
+    fn count(topic: &str, count: i64) -> TopicCount {
+        TopicCount {
+            topic: topic.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn empty_account_is_new() {
+        let explanation = explain(
+            &ScoreInputs {
+                topic_counts: &[],
+                trust: None,
+                open_denouncements: 0,
+                verifications: &[],
+                strikes: &[],
+                as_of: Utc::now(),
+            },
+            &ReputationConfig::default(),
+        );
+        assert!(explanation.components.is_empty());
+        assert_eq!(explanation.tier, ReputationTier::New);
+        assert_eq!(explanation.next_tier, Some(ReputationTier::Established));
+        assert_eq!(explanation.points_to_next_tier, Some(20.0));
+    }
+
+    #[test]
+    fn components_add_up_and_penalties_subtract() {
+        let counts = [count("local_resident", 9), count(VERIFIED_TOPIC, 1)];
+        let snapshot = ScoreSnapshot {
+            user_id: Uuid::nil(),
+            context_user_id: None,
+            trust_distance: Some(2.0),
+            path_diversity: Some(3),
+            eigenvector_centrality: Some(0.2),
+            computed_at: Utc::now(),
+        };
+        let explanation = explain(
+            &ScoreInputs {
+                topic_counts: &counts,
+                trust: Some(&snapshot),
+                open_denouncements: 1,
+                verifications: &[VerificationMethod::Phone],
+                strikes: &[],
+                as_of: Utc::now(),
+            },
+            &ReputationConfig::default(),
+        );
+        let points = |source: &str| {
+            explanation
+                .components
+                .iter()
+                .find(|c| c.source == source)
+                .map(|c| c.points)
+        };
+        // Capped at five endorsements per topic
+        assert_eq!(points("endorsements:local_resident"), Some(25.0));
+        assert_eq!(points(VERIFIED_TOPIC), Some(VERIFIED_POINTS));
+        assert_eq!(points("path_diversity"), Some(12.0));
+        assert_eq!(points("trust_distance"), Some(16.0));
+        assert_eq!(points("verification:phone"), Some(5.0));
+        assert_eq!(points("denouncements"), Some(-DENOUNCEMENT_PENALTY));
+        let total: f64 = explanation.components.iter().map(|c| c.points).sum();
+        assert!((explanation.score - total).abs() < 1e-9);
+        assert_eq!(explanation.tier, ReputationTier::Trusted);
+    }
+
+    #[test]
+    fn score_never_goes_negative() {
+        let explanation = explain(
+            &ScoreInputs {
+                topic_counts: &[],
+                trust: None,
+                open_denouncements: 4,
+                verifications: &[],
+                strikes: &[],
+                as_of: Utc::now(),
+            },
+            &ReputationConfig::default(),
+        );
+        assert!(explanation.score.abs() < f64::EPSILON);
+        assert_eq!(explanation.tier, ReputationTier::New);
+    }
+
+    #[test]
+    fn strikes_decay_and_overturned_ones_do_not_count() {
+        let issued_at = Utc::now() - Duration::days(15);
+        let strike = |appeal_status| StrikeRecord {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            issued_by: None,
+            reason: StrikeReason::Harassment,
+            note: None,
+            penalty: 30.0,
+            issued_at,
+            expires_at: issued_at + Duration::days(30),
+            appeal_status,
+            appeal_statement: None,
+            appealed_at: None,
+            appeal_resolved_by: None,
+            appeal_resolved_at: None,
+        };
+        let counts = [count("local_resident", 10)];
+        let strikes = [strike(AppealStatus::Pending), strike(AppealStatus::Granted)];
+        let explanation = explain(
+            &ScoreInputs {
+                topic_counts: &counts,
+                trust: None,
+                open_denouncements: 0,
+                verifications: &[],
+                strikes: &strikes,
+                as_of: issued_at + Duration::days(15),
+            },
+            &ReputationConfig::default(),
+        );
+        let penalties: Vec<&ScoreComponent> = explanation
+            .components
+            .iter()
+            .filter(|c| c.kind == ScoreComponentKind::Penalty)
+            .collect();
+        assert_eq!(penalties.len(), 1);
+        assert_eq!(penalties[0].source, "strike:harassment");
+        // Halfway to expiry, half the penalty remains
+        assert!((penalties[0].points + 15.0).abs() < 1e-6);
+        assert!((explanation.score - 10.0).abs() < 1e-6);
+
+        let moderator = explanation.clone().redact(Viewer::Moderator);
+        assert_eq!(moderator, explanation);
+        let public = explanation.redact(Viewer::Member);
+        assert!(public.components.iter().any(|c| c.source == "strike"));
+        assert!(!public
+            .components
+            .iter()
+            .any(|c| c.source.starts_with("strike:")));
+    }
+}
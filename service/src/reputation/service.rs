@@ -8,7 +8,11 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use super::repo::{CreatedEndorsement, EndorsementRecord, EndorsementRepoError, ReputationRepo};
+use super::repo::{
+    CreatedEndorsement, EndorsementRecord, EndorsementRepoError, IssuedEndorsement, ReputationRepo,
+    TopicCount,
+};
+use crate::pagination::{finish_page, KeysetPage};
 
 // ─── Domain error type ─────────────────────────────────────────────────────
 
@@ -30,10 +34,7 @@ pub trait EndorsementService: Send + Sync {
     /// or `Some(account_id)` for verifier-issued endorsements.
     async fn create_endorsement(
         &self,
-        subject_id: Uuid,
-        topic: &str,
-        endorser_id: Option<Uuid>,
-        evidence: Option<&serde_json::Value>,
+        endorsement: &IssuedEndorsement<'_>,
     ) -> Result<CreatedEndorsement, EndorsementError>;
 
     /// Check if a subject has an active (non-revoked) endorsement for a topic.
@@ -48,6 +49,20 @@ pub trait EndorsementService: Send + Sync {
         &self,
         subject_id: Uuid,
     ) -> Result<Vec<EndorsementRecord>, EndorsementError>;
+
+    /// List one keyset page of a subject's endorsements, returning the rows
+    /// and the cursor for the next page (if any).
+    async fn list_endorsements_page(
+        &self,
+        subject_id: Uuid,
+        page: &KeysetPage,
+    ) -> Result<(Vec<EndorsementRecord>, Option<String>), EndorsementError>;
+
+    /// Active endorsement counts per topic for a subject, alphabetical by topic.
+    async fn endorsement_summary(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementError>;
 }
 
 // ─── Implementation ────────────────────────────────────────────────────────
@@ -67,19 +82,16 @@ impl DefaultEndorsementService {
 impl EndorsementService for DefaultEndorsementService {
     async fn create_endorsement(
         &self,
-        subject_id: Uuid,
-        topic: &str,
-        endorser_id: Option<Uuid>,
-        evidence: Option<&serde_json::Value>,
+        endorsement: &IssuedEndorsement<'_>,
     ) -> Result<CreatedEndorsement, EndorsementError> {
-        if topic.is_empty() {
+        if endorsement.topic.is_empty() {
             return Err(EndorsementError::Validation(
                 "Topic cannot be empty".to_string(),
             ));
         }
 
         self.repo
-            .create_endorsement(subject_id, topic, endorser_id, evidence, 1.0, None, true)
+            .create_issued_endorsement(endorsement)
             .await
             .map_err(|e| match e {
                 EndorsementRepoError::NotFound => {
@@ -129,4 +141,43 @@ impl EndorsementService for DefaultEndorsementService {
                 }
             })
     }
+
+    async fn list_endorsements_page(
+        &self,
+        subject_id: Uuid,
+        page: &KeysetPage,
+    ) -> Result<(Vec<EndorsementRecord>, Option<String>), EndorsementError> {
+        let rows = self
+            .repo
+            .list_endorsements_by_subject_page(subject_id, page)
+            .await
+            .map_err(|e| match e {
+                EndorsementRepoError::Database(e) => {
+                    tracing::error!("Endorsement page failed: {e}");
+                    EndorsementError::Internal("Internal server error".to_string())
+                }
+                EndorsementRepoError::NotFound => {
+                    EndorsementError::Internal("Internal server error".to_string())
+                }
+            })?;
+        Ok(finish_page(page, rows, |e| (e.created_at, e.id)))
+    }
+
+    async fn endorsement_summary(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementError> {
+        self.repo
+            .count_active_endorsements_by_topic(subject_id)
+            .await
+            .map_err(|e| match e {
+                EndorsementRepoError::Database(e) => {
+                    tracing::error!("Endorsement summary failed: {e}");
+                    EndorsementError::Internal("Internal server error".to_string())
+                }
+                EndorsementRepoError::NotFound => {
+                    EndorsementError::Internal("Internal server error".to_string())
+                }
+            })
+    }
 }
@@ -0,0 +1,281 @@
+//! Moderator-issued strikes and their decaying reputation penalty.
+//!
+//! An account moderator (see [`AccountsConfig`](crate::config::AccountsConfig))
+//! strikes an account with a [`StrikeReason`], which sets the penalty and how
+//! long it lasts. The penalty shrinks linearly from its full value when
+//! issued to zero at expiry; see [`decayed_penalty`]. The struck account can
+//! see its strikes and their history and may appeal each one once. A pending
+//! appeal leaves the penalty in place; a granted appeal removes it.
+//!
+//! Every action is recorded in `reputation__strike_events`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest a moderator may make a strike last.
+pub const MAX_STRIKE_DAYS: i64 = 365;
+
+/// Why a strike was issued. Stored in `reputation__strikes.reason_code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrikeReason {
+    Spam,
+    Harassment,
+    /// Endorsed someone for something untrue
+    FalseEndorsement,
+    Impersonation,
+    VoteManipulation,
+    Other,
+}
+
+impl StrikeReason {
+    pub const ALL: [Self; 6] = [
+        Self::Spam,
+        Self::Harassment,
+        Self::FalseEndorsement,
+        Self::Impersonation,
+        Self::VoteManipulation,
+        Self::Other,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Spam => "spam",
+            Self::Harassment => "harassment",
+            Self::FalseEndorsement => "false_endorsement",
+            Self::Impersonation => "impersonation",
+            Self::VoteManipulation => "vote_manipulation",
+            Self::Other => "other",
+        }
+    }
+
+    /// Score points deducted when the strike is issued.
+    #[must_use]
+    pub const fn penalty(self) -> f64 {
+        match self {
+            Self::Spam | Self::Other => 10.0,
+            Self::FalseEndorsement => 20.0,
+            Self::Harassment => 25.0,
+            Self::Impersonation | Self::VoteManipulation => 40.0,
+        }
+    }
+
+    /// Days until the penalty has fully decayed, unless the moderator picks
+    /// another duration.
+    #[must_use]
+    pub const fn default_days(self) -> i64 {
+        match self {
+            Self::Spam | Self::Other => 30,
+            Self::FalseEndorsement | Self::Harassment => 90,
+            Self::Impersonation | Self::VoteManipulation => 180,
+        }
+    }
+}
+
+impl fmt::Display for StrikeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown strike reason.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown strike reason")]
+pub struct UnknownStrikeReason;
+
+impl FromStr for StrikeReason {
+    type Err = UnknownStrikeReason;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownStrikeReason)
+    }
+}
+
+/// Where a strike's appeal stands. Stored in
+/// `reputation__strikes.appeal_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AppealStatus {
+    /// Not appealed
+    None,
+    /// Awaiting moderator review; the penalty still applies
+    Pending,
+    /// The strike was overturned and no longer counts
+    Granted,
+    /// The strike stands
+    Denied,
+}
+
+impl AppealStatus {
+    pub const ALL: [Self; 4] = [Self::None, Self::Pending, Self::Granted, Self::Denied];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Pending => "pending",
+            Self::Granted => "granted",
+            Self::Denied => "denied",
+        }
+    }
+}
+
+impl fmt::Display for AppealStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown appeal status.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown appeal status")]
+pub struct UnknownAppealStatus;
+
+impl FromStr for AppealStatus {
+    type Err = UnknownAppealStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownAppealStatus)
+    }
+}
+
+/// An entry in a strike's audit trail. Stored in
+/// `reputation__strike_events.action`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrikeAction {
+    Issued,
+    Appealed,
+    AppealGranted,
+    AppealDenied,
+}
+
+impl StrikeAction {
+    pub const ALL: [Self; 4] = [
+        Self::Issued,
+        Self::Appealed,
+        Self::AppealGranted,
+        Self::AppealDenied,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Issued => "issued",
+            Self::Appealed => "appealed",
+            Self::AppealGranted => "appeal_granted",
+            Self::AppealDenied => "appeal_denied",
+        }
+    }
+}
+
+/// Error returned for an unknown strike action.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown strike action")]
+pub struct UnknownStrikeAction;
+
+impl FromStr for StrikeAction {
+    type Err = UnknownStrikeAction;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownStrikeAction)
+    }
+}
+
+/// What is left of `penalty` at `now` for a strike issued at `issued_at`
+/// that expires at `expires_at`.
+///
+/// Full before issue, zero from expiry on, and linear in between.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn decayed_penalty(
+    penalty: f64,
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let total = (expires_at - issued_at).num_seconds();
+    if total <= 0 || now >= expires_at {
+        return 0.0;
+    }
+    let remaining = (expires_at - now).num_seconds().min(total);
+    penalty * remaining as f64 / total as f64
+}
+
+/// Expiry for a strike issued at `issued_at`, using the reason's default
+/// when `days` is `None`.
+///
+/// # Errors
+///
+/// Returns a message if `days` is outside `1..=MAX_STRIKE_DAYS`.
+pub fn strike_expiry(
+    reason: StrikeReason,
+    days: Option<i64>,
+    issued_at: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let days = days.unwrap_or_else(|| reason.default_days());
+    if !(1..=MAX_STRIKE_DAYS).contains(&days) {
+        return Err(format!(
+            "expires_in_days must be between 1 and {MAX_STRIKE_DAYS}"
+        ));
+    }
+    Ok(issued_at + Duration::days(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strike_enums_round_trip_through_str() {
+        for reason in StrikeReason::ALL {
+            assert_eq!(reason.as_str().parse::<StrikeReason>().ok(), Some(reason));
+            assert!(reason.penalty() > 0.0);
+        }
+        for status in AppealStatus::ALL {
+            assert_eq!(status.as_str().parse::<AppealStatus>().ok(), Some(status));
+        }
+        for action in StrikeAction::ALL {
+            assert_eq!(action.as_str().parse::<StrikeAction>().ok(), Some(action));
+        }
+        assert!("rudeness".parse::<StrikeReason>().is_err());
+    }
+
+    #[test]
+    fn penalty_decays_linearly_to_zero() {
+        let issued = Utc::now();
+        let expires = issued + Duration::days(10);
+        assert!((decayed_penalty(20.0, issued, expires, issued) - 20.0).abs() < 1e-9);
+        let halfway = issued + Duration::days(5);
+        assert!((decayed_penalty(20.0, issued, expires, halfway) - 10.0).abs() < 1e-9);
+        assert!(decayed_penalty(20.0, issued, expires, expires).abs() < f64::EPSILON);
+        // Clock skew before issue never inflates the penalty
+        let before = issued - Duration::days(1);
+        assert!((decayed_penalty(20.0, issued, expires, before) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expiry_defaults_by_reason_and_is_bounded() {
+        let now = Utc::now();
+        assert_eq!(
+            strike_expiry(StrikeReason::Spam, None, now),
+            Ok(now + Duration::days(30))
+        );
+        assert!(strike_expiry(StrikeReason::Spam, Some(0), now).is_err());
+        assert!(strike_expiry(StrikeReason::Spam, Some(MAX_STRIKE_DAYS + 1), now).is_err());
+    }
+}
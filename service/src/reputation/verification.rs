@@ -0,0 +1,368 @@
+//! Account verification methods and code delivery.
+//!
+//! An account completes zero or more [`VerificationMethod`]s, recorded in
+//! `reputation__verifications`:
+//!
+//! - **Email** and **phone**: the account asks for a one-time code, which a
+//!   [`VerificationSender`] delivers to the address or number, and confirms
+//!   it. Only a hash of the normalized destination is stored, so the same
+//!   address or number cannot verify two accounts.
+//! - **In person**: verified accounts attest that they met the account; it
+//!   is verified once `verification.in_person_attestations_required`
+//!   attestations accumulate.
+//! - **Government ID**: an external identity verification provider reports
+//!   the result to a webhook signed with `verification.idv_webhook_secret`.
+//!
+//! Methods are ordered by strength. The strongest completed method is the
+//! account's verification level, shown on its badge and checked by account
+//! gates; each method also adds points to the reputation score.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::{VerificationConfig, VerificationSenderKind};
+
+/// Digits in an email or phone verification code.
+pub const CODE_LEN: usize = 6;
+
+/// Header carrying the ID verification webhook signature,
+/// `sha256=<hex HMAC of the raw body>`.
+pub const IDV_SIGNATURE_HEADER: &str = "x-tc-signature";
+
+/// A way of verifying an account, weakest first.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationMethod {
+    /// Confirmed a code sent to an email address
+    Email,
+    /// Confirmed a code sent by SMS
+    Phone,
+    /// Attested to by enough verified accounts
+    InPerson,
+    /// Verified by an external ID verification provider
+    GovernmentId,
+}
+
+impl VerificationMethod {
+    pub const ALL: [Self; 4] = [Self::Email, Self::Phone, Self::InPerson, Self::GovernmentId];
+
+    /// Value stored in `reputation__verifications.method`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Phone => "phone",
+            Self::InPerson => "in_person",
+            Self::GovernmentId => "government_id",
+        }
+    }
+
+    /// Short label for badges.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Phone => "phone",
+            Self::InPerson => "in-person",
+            Self::GovernmentId => "ID",
+        }
+    }
+
+    /// Reputation score points for completing the method.
+    #[must_use]
+    pub const fn points(self) -> f64 {
+        match self {
+            Self::Email => 2.0,
+            Self::Phone => 5.0,
+            Self::InPerson => 15.0,
+            Self::GovernmentId => 20.0,
+        }
+    }
+
+    /// Whether the method is completed by confirming a delivered code.
+    #[must_use]
+    pub const fn uses_code(self) -> bool {
+        matches!(self, Self::Email | Self::Phone)
+    }
+}
+
+impl fmt::Display for VerificationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown verification method.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown verification method")]
+pub struct UnknownVerificationMethod;
+
+impl FromStr for VerificationMethod {
+    type Err = UnknownVerificationMethod;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownVerificationMethod)
+    }
+}
+
+/// Canonical form of an email address or phone number.
+///
+/// Email addresses are trimmed and lowercased. Phone numbers are normalized
+/// by [`crate::sms::normalize_phone`].
+///
+/// # Errors
+///
+/// Returns a message if `raw` is not a plausible destination for `method`.
+pub fn normalize_destination(method: VerificationMethod, raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    match method {
+        VerificationMethod::Email => {
+            let email = raw.to_lowercase();
+            let valid = email.len() <= 254
+                && !email.chars().any(char::is_whitespace)
+                && email.split_once('@').is_some_and(|(local, domain)| {
+                    !local.is_empty() && domain.contains('.') && !domain.contains('@')
+                });
+            if valid {
+                Ok(email)
+            } else {
+                Err("destination must be an email address".to_string())
+            }
+        }
+        VerificationMethod::Phone => crate::sms::normalize_phone(raw).ok_or_else(|| {
+            "destination must be a phone number in international form, e.g. +15551234567"
+                .to_string()
+        }),
+        VerificationMethod::InPerson | VerificationMethod::GovernmentId => {
+            Err(format!("{method} verification does not use a code"))
+        }
+    }
+}
+
+/// Hash of a normalized destination, stored to keep one address or number
+/// from verifying several accounts.
+#[must_use]
+pub fn hash_destination(method: VerificationMethod, normalized: &str) -> Vec<u8> {
+    Sha256::digest(format!("{method}:{normalized}").as_bytes()).to_vec()
+}
+
+/// Generate a numeric verification code.
+#[must_use]
+pub fn generate_code() -> String {
+    let mut rng = rand::rngs::OsRng;
+    (0..CODE_LEN)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10u8)))
+        .collect()
+}
+
+/// Hash a code for storage, bound to the account it was sent for.
+#[must_use]
+pub fn hash_code(account_id: Uuid, code: &str) -> Vec<u8> {
+    Sha256::digest(format!("{account_id}:{}", code.trim()).as_bytes()).to_vec()
+}
+
+/// Check an ID verification webhook signature header against the raw body.
+#[must_use]
+pub fn verify_webhook_signature(secret: &[u8], body: &[u8], header: &str) -> bool {
+    let Some(signature) = header.trim().strip_prefix("sha256=").and_then(decode_hex) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// ─── Code delivery ─────────────────────────────────────────────────────────
+
+/// Errors returned by a [`VerificationSender`].
+#[derive(Debug, thiserror::Error)]
+pub enum SendError {
+    #[error("gateway request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("gateway returned {0}")]
+    Status(reqwest::StatusCode),
+}
+
+/// Delivers verification messages by email or SMS.
+#[async_trait]
+pub trait VerificationSender: Send + Sync {
+    async fn send(
+        &self,
+        method: VerificationMethod,
+        destination: &str,
+        message: &str,
+    ) -> Result<(), SendError>;
+}
+
+/// Writes messages to the service log instead of delivering them.
+pub struct LogVerificationSender;
+
+#[async_trait]
+impl VerificationSender for LogVerificationSender {
+    async fn send(
+        &self,
+        method: VerificationMethod,
+        destination: &str,
+        message: &str,
+    ) -> Result<(), SendError> {
+        tracing::info!(%method, destination, "Verification message: {message}");
+        Ok(())
+    }
+}
+
+/// POSTs `{"channel", "to", "message"}` to an SMS/email gateway.
+pub struct HttpVerificationSender {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+}
+
+impl HttpVerificationSender {
+    #[must_use]
+    pub fn new(url: &str, token: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            token: token.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl VerificationSender for HttpVerificationSender {
+    async fn send(
+        &self,
+        method: VerificationMethod,
+        destination: &str,
+        message: &str,
+    ) -> Result<(), SendError> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .timeout(std::time::Duration::from_secs(10))
+            .json(&serde_json::json!({
+                "channel": method.as_str(),
+                "to": destination,
+                "message": message,
+            }));
+        if !self.token.is_empty() {
+            request = request.bearer_auth(&self.token);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(SendError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Build the configured sender, or `None` when code delivery is disabled.
+#[must_use]
+pub fn build_sender(config: &VerificationConfig) -> Option<Arc<dyn VerificationSender>> {
+    match config.sender {
+        VerificationSenderKind::Disabled => {
+            tracing::info!(
+                "Email and phone verification disabled (enable via TC_VERIFICATION__SENDER)"
+            );
+            None
+        }
+        VerificationSenderKind::Log => {
+            tracing::warn!("Verification codes are written to the log; do not use in production");
+            Some(Arc::new(LogVerificationSender))
+        }
+        VerificationSenderKind::Http => Some(Arc::new(HttpVerificationSender::new(
+            &config.sender_url,
+            &config.sender_token,
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn methods_round_trip_and_order_by_strength() {
+        for method in VerificationMethod::ALL {
+            assert_eq!(
+                method.as_str().parse::<VerificationMethod>().ok(),
+                Some(method)
+            );
+        }
+        assert!(VerificationMethod::Email < VerificationMethod::Phone);
+        assert!(VerificationMethod::InPerson < VerificationMethod::GovernmentId);
+        assert!("passport".parse::<VerificationMethod>().is_err());
+    }
+
+    #[test]
+    fn destinations_are_normalized() {
+        assert_eq!(
+            normalize_destination(VerificationMethod::Email, " Alice@Example.ORG "),
+            Ok("alice@example.org".to_string())
+        );
+        assert!(normalize_destination(VerificationMethod::Email, "alice@localhost").is_err());
+        assert!(normalize_destination(VerificationMethod::Email, "a b@example.org").is_err());
+        assert_eq!(
+            normalize_destination(VerificationMethod::Phone, "+1 (555) 123-4567"),
+            Ok("+15551234567".to_string())
+        );
+        assert!(normalize_destination(VerificationMethod::Phone, "555-1234").is_err());
+        assert!(normalize_destination(VerificationMethod::InPerson, "x").is_err());
+    }
+
+    #[test]
+    fn codes_are_numeric_and_bound_to_the_account() {
+        let code = generate_code();
+        assert_eq!(code.len(), CODE_LEN);
+        assert!(code.bytes().all(|b| b.is_ascii_digit()));
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(hash_code(a, &code), hash_code(a, &format!(" {code} ")));
+        assert_ne!(hash_code(a, &code), hash_code(b, &code));
+    }
+
+    #[test]
+    fn webhook_signature_is_checked_against_the_body() {
+        let secret = b"0123456789abcdef0123456789abcdef";
+        let body = br#"{"status":"verified"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(body);
+        let hex: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        let header = format!("sha256={hex}");
+        assert!(verify_webhook_signature(secret, body, &header));
+        assert!(!verify_webhook_signature(secret, b"{}", &header));
+        assert!(!verify_webhook_signature(secret, body, &hex));
+        assert!(!verify_webhook_signature(secret, body, "sha256=zz"));
+    }
+}
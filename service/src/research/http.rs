@@ -0,0 +1,376 @@
+//! HTTP handlers for research export requests, approvals, and downloads.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Extension,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::repo::{ExportRequestRecord, ResearchRepo, ResearchRepoError};
+use super::{push_csv_record, ExportStatus, ResearchDataset, MAX_PURPOSE_LEN};
+use crate::config::{AccountsConfig, ResearchConfig};
+use crate::http::{
+    bad_request, conflict, forbidden, internal_error, not_found, too_many_requests, Path,
+};
+use crate::identity::http::auth::AuthenticatedDevice;
+
+// ─── Request / response types ──────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateExportRequest {
+    pub dataset: ResearchDataset,
+    /// What the data will be used for; shown to the approving admin
+    pub purpose: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExportRequestResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub requester_username: String,
+    pub dataset: ResearchDataset,
+    pub purpose: String,
+    pub status: ExportStatus,
+    pub decided_at: Option<String>,
+    /// Downloads stop after this instant; set once approved
+    pub expires_at: Option<String>,
+    pub downloads_remaining: u32,
+    pub created_at: String,
+}
+
+impl ExportRequestResponse {
+    fn from_record(r: ExportRequestRecord, downloads_per_export: u32) -> Self {
+        let used = u32::try_from(r.download_count).unwrap_or(0);
+        Self {
+            id: r.id,
+            requester_username: r.requester_username,
+            dataset: r.dataset,
+            purpose: r.purpose,
+            status: r.status,
+            decided_at: r.decided_at.map(|t| t.to_rfc3339()),
+            expires_at: r.expires_at.map(|t| t.to_rfc3339()),
+            downloads_remaining: downloads_per_export.saturating_sub(used),
+            created_at: r.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExportRequestsResponse {
+    /// Newest first; every request for admins, otherwise the caller's own
+    pub requests: Vec<ExportRequestResponse>,
+}
+
+// ─── Router ────────────────────────────────────────────────────────────────
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/research/exports", get(list_exports).post(create_export))
+        .route("/research/exports/{id}/approve", post(approve_export))
+        .route("/research/exports/{id}/reject", post(reject_export))
+        .route("/research/exports/{id}/download", get(download_export))
+}
+
+/// The caller's research roles, as `(researcher, admin)`.
+fn caller_roles(
+    config: &ResearchConfig,
+    accounts: Option<Extension<AccountsConfig>>,
+    account_id: Uuid,
+) -> (bool, bool) {
+    let accounts = accounts.map(|Extension(c)| c).unwrap_or_default();
+    (
+        config.is_researcher(account_id),
+        accounts.is_admin(account_id),
+    )
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// Request an anonymized export. Only configured researchers may ask, and
+/// the request must be approved by a research admin before download.
+#[utoipa::path(
+    post,
+    path = "/research/exports",
+    tag = "Research",
+    request_body = CreateExportRequest,
+    responses(
+        (status = 201, description = "Request filed, pending approval", body = ExportRequestResponse),
+        (status = 400, description = "Invalid dataset or purpose"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not a researcher"),
+        (status = 429, description = "Daily request limit reached"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn create_export(
+    Extension(repo): Extension<Arc<dyn ResearchRepo>>,
+    config: Option<Extension<ResearchConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let body: CreateExportRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    let purpose = body.purpose.trim();
+    if purpose.is_empty() || purpose.len() > MAX_PURPOSE_LEN {
+        return bad_request(&format!("purpose must be 1-{MAX_PURPOSE_LEN} bytes"));
+    }
+    if !caller_roles(&config, accounts_config, auth.account_id).0 {
+        return forbidden("Only researchers can request exports");
+    }
+    match repo
+        .create_request(
+            auth.account_id,
+            body.dataset,
+            purpose,
+            config.requests_per_day,
+        )
+        .await
+    {
+        Ok(record) => (
+            StatusCode::CREATED,
+            Json(ExportRequestResponse::from_record(
+                record,
+                config.downloads_per_export,
+            )),
+        )
+            .into_response(),
+        Err(ref e) => research_repo_error_response(e),
+    }
+}
+
+/// List export requests: every request for research admins, otherwise the
+/// caller's own.
+#[utoipa::path(
+    get,
+    path = "/research/exports",
+    tag = "Research",
+    responses(
+        (status = 200, description = "Export requests", body = ExportRequestsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn list_exports(
+    Extension(repo): Extension<Arc<dyn ResearchRepo>>,
+    config: Option<Extension<ResearchConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let (_, is_admin) = caller_roles(&config, accounts_config, auth.account_id);
+    let scope = if is_admin {
+        None
+    } else {
+        Some(auth.account_id)
+    };
+    match repo.list_requests(scope).await {
+        Ok(rows) => (
+            StatusCode::OK,
+            Json(ExportRequestsResponse {
+                requests: rows
+                    .into_iter()
+                    .map(|r| ExportRequestResponse::from_record(r, config.downloads_per_export))
+                    .collect(),
+            }),
+        )
+            .into_response(),
+        Err(ref e) => research_repo_error_response(e),
+    }
+}
+
+/// Approve a pending export. Admins cannot approve their own requests.
+#[utoipa::path(
+    post,
+    path = "/research/exports/{id}/approve",
+    tag = "Research",
+    params(("id" = Uuid, Path, description = "Export request ID")),
+    responses(
+        (status = 200, description = "Approved", body = ExportRequestResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not a research admin, or filed the request"),
+        (status = 404, description = "Export request not found"),
+        (status = 409, description = "Request already decided"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn approve_export(
+    Extension(repo): Extension<Arc<dyn ResearchRepo>>,
+    config: Option<Extension<ResearchConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    decide(repo, config, accounts_config, id, auth, true).await
+}
+
+/// Reject a pending export. Admins cannot reject their own requests.
+#[utoipa::path(
+    post,
+    path = "/research/exports/{id}/reject",
+    tag = "Research",
+    params(("id" = Uuid, Path, description = "Export request ID")),
+    responses(
+        (status = 200, description = "Rejected", body = ExportRequestResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not a research admin, or filed the request"),
+        (status = 404, description = "Export request not found"),
+        (status = 409, description = "Request already decided"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn reject_export(
+    Extension(repo): Extension<Arc<dyn ResearchRepo>>,
+    config: Option<Extension<ResearchConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    Path(id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    decide(repo, config, accounts_config, id, auth, false).await
+}
+
+async fn decide(
+    repo: Arc<dyn ResearchRepo>,
+    config: Option<Extension<ResearchConfig>>,
+    accounts_config: Option<Extension<AccountsConfig>>,
+    id: Uuid,
+    auth: AuthenticatedDevice,
+    approve: bool,
+) -> axum::response::Response {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    if !caller_roles(&config, accounts_config, auth.account_id).1 {
+        return forbidden("Only research admins can decide exports");
+    }
+    match repo
+        .decide_request(id, auth.account_id, approve, config.approval_ttl_days)
+        .await
+    {
+        Ok(record) => {
+            tracing::info!(
+                export_id = %id,
+                dataset = %record.dataset,
+                status = record.status.as_str(),
+                "Research export decided"
+            );
+            (
+                StatusCode::OK,
+                Json(ExportRequestResponse::from_record(
+                    record,
+                    config.downloads_per_export,
+                )),
+            )
+                .into_response()
+        }
+        Err(ref e) => research_repo_error_response(e),
+    }
+}
+
+/// Download an approved export as CSV. Only the requester may download, a
+/// limited number of times before the approval expires. Rows covering fewer
+/// than the configured k accounts are omitted.
+#[utoipa::path(
+    get,
+    path = "/research/exports/{id}/download",
+    tag = "Research",
+    params(("id" = Uuid, Path, description = "Export request ID")),
+    responses(
+        (status = 200, description = "CSV export", body = String, content_type = "text/csv"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Export not approved or approval expired"),
+        (status = 404, description = "Export request not found"),
+        (status = 429, description = "Download limit reached"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn download_export(
+    Extension(repo): Extension<Arc<dyn ResearchRepo>>,
+    config: Option<Extension<ResearchConfig>>,
+    Path(id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let config = config.map(|Extension(c)| c).unwrap_or_default();
+    let dataset = match repo
+        .claim_download(id, auth.account_id, config.downloads_per_export)
+        .await
+    {
+        Ok(dataset) => dataset,
+        Err(ref e) => return research_repo_error_response(e),
+    };
+
+    let mut csv = String::new();
+    push_csv_record(&mut csv, dataset.columns());
+    let result = match dataset {
+        ResearchDataset::EndorsementAggregates => repo
+            .endorsement_aggregates(config.k_anonymity)
+            .await
+            .map(|rows| {
+                for r in rows {
+                    push_csv_record(
+                        &mut csv,
+                        &[
+                            r.week.to_string(),
+                            r.topic,
+                            r.endorsements.to_string(),
+                            r.subjects.to_string(),
+                            r.issuers.to_string(),
+                        ],
+                    );
+                }
+            }),
+        ResearchDataset::TrustDegrees => repo.trust_degrees(config.k_anonymity).await.map(|rows| {
+            for r in rows {
+                push_csv_record(
+                    &mut csv,
+                    &[r.direction, r.degree.to_string(), r.accounts.to_string()],
+                );
+            }
+        }),
+    };
+    if let Err(ref e) = result {
+        return research_repo_error_response(e);
+    }
+
+    tracing::info!(export_id = %id, dataset = %dataset, "Research export downloaded");
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{dataset}-{id}.csv\""),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        csv,
+    )
+        .into_response()
+}
+
+fn research_repo_error_response(e: &ResearchRepoError) -> axum::response::Response {
+    match e {
+        ResearchRepoError::NotFound => not_found("Export request not found"),
+        ResearchRepoError::DailyLimitReached => {
+            too_many_requests("Daily export request limit reached")
+        }
+        ResearchRepoError::SelfDecision => forbidden("Cannot decide your own export request"),
+        ResearchRepoError::AlreadyDecided => conflict("Export request already decided"),
+        ResearchRepoError::NotApproved => forbidden("Export has not been approved"),
+        ResearchRepoError::Expired => forbidden("Export approval has expired"),
+        ResearchRepoError::DownloadLimitReached => {
+            too_many_requests("Export download limit reached")
+        }
+        ResearchRepoError::Database(inner) => {
+            tracing::error!("Research repo database error: {inner}");
+            internal_error()
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Anonymized bulk exports for civic research.
+//!
+//! A researcher (see [`crate::config::ResearchConfig`]) files a request for
+//! one [`ResearchDataset`] with `POST /research/exports`, stating its purpose.
+//! An admin other than the requester approves or rejects it; an approved
+//! export can be downloaded as CSV a limited number of times before it
+//! expires.
+//!
+//! Exports contain aggregates only — never account IDs, usernames, or
+//! individual edges — and every row must cover at least `k_anonymity`
+//! distinct accounts or it is left out. Private endorsements are excluded,
+//! matching the other public aggregates.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub mod http;
+pub mod repo;
+
+/// Longest accepted `purpose` statement, in bytes.
+pub const MAX_PURPOSE_LEN: usize = 2000;
+
+/// A dataset that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResearchDataset {
+    /// Endorsements issued per topic per UTC week, with distinct subject and
+    /// issuer counts. Trust edges are reported by `TrustDegrees` instead.
+    EndorsementAggregates,
+    /// How many accounts have each in- and out-degree in the trust graph.
+    TrustDegrees,
+}
+
+impl ResearchDataset {
+    /// Every dataset.
+    pub const ALL: [Self; 2] = [Self::EndorsementAggregates, Self::TrustDegrees];
+
+    /// Value stored in `research_exports.dataset`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::EndorsementAggregates => "endorsement_aggregates",
+            Self::TrustDegrees => "trust_degrees",
+        }
+    }
+
+    /// CSV header row.
+    #[must_use]
+    pub const fn columns(self) -> &'static [&'static str] {
+        match self {
+            Self::EndorsementAggregates => {
+                &["week", "topic", "endorsements", "subjects", "issuers"]
+            }
+            Self::TrustDegrees => &["direction", "degree", "accounts"],
+        }
+    }
+}
+
+impl fmt::Display for ResearchDataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned for an unknown dataset name.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown research dataset")]
+pub struct UnknownDataset;
+
+impl FromStr for ResearchDataset {
+    type Err = UnknownDataset;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|d| d.as_str() == s)
+            .ok_or(UnknownDataset)
+    }
+}
+
+/// Where an export request stands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ExportStatus {
+    /// Every status, in workflow order.
+    pub const ALL: [Self; 3] = [Self::Pending, Self::Approved, Self::Rejected];
+
+    /// Value stored in `research_exports.status`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// Error returned for an unknown export status.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown export status")]
+pub struct UnknownStatus;
+
+impl FromStr for ExportStatus {
+    type Err = UnknownStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or(UnknownStatus)
+    }
+}
+
+/// Append one CSV record to `out`, quoting fields that need it (RFC 4180).
+pub fn push_csv_record<S: AsRef<str>>(out: &mut String, fields: &[S]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let field = field.as_ref();
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enums_round_trip_through_str() {
+        for dataset in ResearchDataset::ALL {
+            assert_eq!(
+                dataset.as_str().parse::<ResearchDataset>().ok(),
+                Some(dataset)
+            );
+            assert_eq!(
+                serde_json::to_value(dataset).unwrap(),
+                serde_json::json!(dataset.as_str())
+            );
+        }
+        for status in ExportStatus::ALL {
+            assert_eq!(status.as_str().parse::<ExportStatus>().ok(), Some(status));
+        }
+        assert!("raw_accounts".parse::<ResearchDataset>().is_err());
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        let mut out = String::new();
+        push_csv_record(&mut out, &["week", "topic"]);
+        push_csv_record(&mut out, &["2026-01-05", "a,b \"c\""]);
+        assert_eq!(out, "week,topic\r\n2026-01-05,\"a,b \"\"c\"\"\"\r\n");
+    }
+}
@@ -0,0 +1,498 @@
+//! Persistence for research export requests and the exported aggregates
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{ExportStatus, ResearchDataset};
+
+use crate::db::{RepoError, RepoErrorKind};
+
+/// Most requests returned by one listing, newest first.
+pub const MAX_LISTED_REQUESTS: i64 = 200;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// One export request and where it stands.
+#[derive(Debug, Clone)]
+pub struct ExportRequestRecord {
+    pub id: Uuid,
+    pub requester_id: Uuid,
+    pub requester_username: String,
+    pub dataset: ResearchDataset,
+    pub purpose: String,
+    pub status: ExportStatus,
+    pub decided_by: Option<Uuid>,
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Set on approval; downloads stop after this instant.
+    pub expires_at: Option<DateTime<Utc>>,
+    pub download_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Endorsements issued in one topic during one UTC week.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct EndorsementAggregateRow {
+    /// Monday the week starts on.
+    pub week: NaiveDate,
+    pub topic: String,
+    pub endorsements: i64,
+    /// Distinct endorsed accounts; always at least `k`.
+    pub subjects: i64,
+    pub issuers: i64,
+}
+
+/// Number of accounts with a given trust-graph degree.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct DegreeBucketRow {
+    /// `in` (trust received) or `out` (trust given).
+    pub direction: String,
+    pub degree: i64,
+    /// Always at least `k`.
+    pub accounts: i64,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResearchRepoError {
+    #[error("export request not found")]
+    NotFound,
+    #[error("daily export request limit reached")]
+    DailyLimitReached,
+    #[error("requesters cannot decide their own exports")]
+    SelfDecision,
+    #[error("export request already decided")]
+    AlreadyDecided,
+    #[error("export is not approved")]
+    NotApproved,
+    #[error("export approval has expired")]
+    Expired,
+    #[error("export download limit reached")]
+    DownloadLimitReached,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for ResearchRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::AlreadyDecided => RepoErrorKind::Conflict,
+            Self::DailyLimitReached | Self::DownloadLimitReached => RepoErrorKind::LimitReached,
+            Self::SelfDecision | Self::NotApproved | Self::Expired => RepoErrorKind::Rejected,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL row types ─────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct ExportRequestRow {
+    id: Uuid,
+    requester_id: Uuid,
+    requester_username: String,
+    dataset: String,
+    purpose: String,
+    status: String,
+    decided_by: Option<Uuid>,
+    decided_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    download_count: i32,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<ExportRequestRow> for ExportRequestRecord {
+    type Error = ResearchRepoError;
+
+    fn try_from(row: ExportRequestRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            requester_id: row.requester_id,
+            requester_username: row.requester_username,
+            dataset: row
+                .dataset
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            purpose: row.purpose,
+            status: row
+                .status
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            decided_by: row.decided_by,
+            decided_at: row.decided_at,
+            expires_at: row.expires_at,
+            download_count: row.download_count,
+            created_at: row.created_at,
+        })
+    }
+}
+
+const SELECT_REQUEST: &str = r"
+    SELECT r.id, r.requester_id, a.username AS requester_username, r.dataset, r.purpose,
+           r.status, r.decided_by, r.decided_at, r.expires_at, r.download_count, r.created_at
+    FROM research_exports r
+    JOIN accounts a ON a.id = r.requester_id
+";
+
+async fn fetch_request<'e, E>(
+    executor: E,
+    id: Uuid,
+) -> Result<ExportRequestRecord, ResearchRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let row = sqlx::query_as::<_, ExportRequestRow>(&format!("{SELECT_REQUEST} WHERE r.id = $1"))
+        .bind(id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or(ResearchRepoError::NotFound)?;
+    row.try_into()
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// File a pending export request.
+///
+/// # Errors
+///
+/// Returns `DailyLimitReached` if `requester_id` already filed `daily_limit`
+/// requests in the last 24 hours, `Database` on failure.
+pub async fn create_request(
+    pool: &PgPool,
+    requester_id: Uuid,
+    dataset: ResearchDataset,
+    purpose: &str,
+    daily_limit: u32,
+) -> Result<ExportRequestRecord, ResearchRepoError> {
+    let mut tx = pool.begin().await?;
+    // Serialize concurrent requests per account so the limit holds.
+    sqlx::query("SELECT 1 FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(requester_id)
+        .execute(&mut *tx)
+        .await?;
+    let recent: i64 = sqlx::query_scalar(
+        r"
+        SELECT COUNT(*) FROM research_exports
+        WHERE requester_id = $1 AND created_at > now() - interval '1 day'
+        ",
+    )
+    .bind(requester_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if recent >= i64::from(daily_limit) {
+        return Err(ResearchRepoError::DailyLimitReached);
+    }
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO research_exports (requester_id, dataset, purpose) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(requester_id)
+    .bind(dataset.as_str())
+    .bind(purpose)
+    .fetch_one(&mut *tx)
+    .await?;
+    let record = fetch_request(&mut *tx, id).await?;
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Export requests, newest first, limited to [`MAX_LISTED_REQUESTS`].
+/// With `requester_id` only that account's requests are returned.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_requests(
+    pool: &PgPool,
+    requester_id: Option<Uuid>,
+) -> Result<Vec<ExportRequestRecord>, ResearchRepoError> {
+    let rows = sqlx::query_as::<_, ExportRequestRow>(&format!(
+        "{SELECT_REQUEST} WHERE ($1::uuid IS NULL OR r.requester_id = $1)
+         ORDER BY r.created_at DESC, r.id DESC LIMIT $2"
+    ))
+    .bind(requester_id)
+    .bind(MAX_LISTED_REQUESTS)
+    .fetch_all(pool)
+    .await?;
+    rows.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Approve or reject a pending request. Approvals expire after `ttl_days`.
+///
+/// # Errors
+///
+/// Returns `NotFound` for an unknown request, `SelfDecision` if `decider_id`
+/// filed it, `AlreadyDecided` if it is no longer pending, `Database` on
+/// failure.
+pub async fn decide_request(
+    pool: &PgPool,
+    id: Uuid,
+    decider_id: Uuid,
+    approve: bool,
+    ttl_days: u32,
+) -> Result<ExportRequestRecord, ResearchRepoError> {
+    let mut tx = pool.begin().await?;
+    let current: Option<(Uuid, String)> = sqlx::query_as(
+        "SELECT requester_id, status FROM research_exports WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((requester_id, status)) = current else {
+        return Err(ResearchRepoError::NotFound);
+    };
+    if requester_id == decider_id {
+        return Err(ResearchRepoError::SelfDecision);
+    }
+    if status != ExportStatus::Pending.as_str() {
+        return Err(ResearchRepoError::AlreadyDecided);
+    }
+    let (status, ttl_days) = if approve {
+        (
+            ExportStatus::Approved,
+            Some(i32::try_from(ttl_days).unwrap_or(i32::MAX)),
+        )
+    } else {
+        (ExportStatus::Rejected, None)
+    };
+    sqlx::query(
+        r"
+        UPDATE research_exports
+        SET status = $2, decided_by = $3, decided_at = now(),
+            expires_at = now() + make_interval(days => $4)
+        WHERE id = $1
+        ",
+    )
+    .bind(id)
+    .bind(status.as_str())
+    .bind(decider_id)
+    .bind(ttl_days)
+    .execute(&mut *tx)
+    .await?;
+    let record = fetch_request(&mut *tx, id).await?;
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Count one download of an approved export by its requester and return the
+/// dataset to produce.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the request does not exist or belongs to someone
+/// else, `NotApproved`, `Expired`, or `DownloadLimitReached` if it cannot be
+/// downloaded, `Database` on failure.
+pub async fn claim_download(
+    pool: &PgPool,
+    id: Uuid,
+    requester_id: Uuid,
+    max_downloads: u32,
+) -> Result<ResearchDataset, ResearchRepoError> {
+    let mut tx = pool.begin().await?;
+    let current: Option<(String, String, Option<DateTime<Utc>>, i32)> = sqlx::query_as(
+        r"
+        SELECT dataset, status, expires_at, download_count
+        FROM research_exports
+        WHERE id = $1 AND requester_id = $2
+        FOR UPDATE
+        ",
+    )
+    .bind(id)
+    .bind(requester_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((dataset, status, expires_at, downloads)) = current else {
+        return Err(ResearchRepoError::NotFound);
+    };
+    if status != ExportStatus::Approved.as_str() {
+        return Err(ResearchRepoError::NotApproved);
+    }
+    if expires_at.is_none_or(|at| at <= Utc::now()) {
+        return Err(ResearchRepoError::Expired);
+    }
+    if i64::from(downloads) >= i64::from(max_downloads) {
+        return Err(ResearchRepoError::DownloadLimitReached);
+    }
+    sqlx::query("UPDATE research_exports SET download_count = download_count + 1 WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(dataset
+        .parse()
+        .map_err(|e| sqlx::Error::Decode(Box::new(e)))?)
+}
+
+/// Non-private, non-trust endorsements per topic per UTC week. Weeks with
+/// fewer than `k` distinct subjects in a topic are left out.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn endorsement_aggregates(
+    pool: &PgPool,
+    k: u32,
+) -> Result<Vec<EndorsementAggregateRow>, ResearchRepoError> {
+    let rows = sqlx::query_as::<_, EndorsementAggregateRow>(
+        r"
+        SELECT date_trunc('week', created_at AT TIME ZONE 'UTC')::date AS week,
+               topic,
+               COUNT(*) AS endorsements,
+               COUNT(DISTINCT subject_id) AS subjects,
+               COUNT(DISTINCT endorser_id) AS issuers
+        FROM reputation__endorsements
+        WHERE topic <> 'trust' AND visibility <> 'private' AND NOT aggregates_paused
+        GROUP BY 1, 2
+        HAVING COUNT(DISTINCT subject_id) >= $1
+        ORDER BY 1, 2
+        ",
+    )
+    .bind(i64::from(k))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Distribution of in- and out-degree over active trust edges, counting
+/// every account (degree 0 included). Degrees held by fewer than `k`
+/// accounts are left out.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn trust_degrees(
+    pool: &PgPool,
+    k: u32,
+) -> Result<Vec<DegreeBucketRow>, ResearchRepoError> {
+    let rows = sqlx::query_as::<_, DegreeBucketRow>(
+        r"
+        WITH edges AS (
+            SELECT endorser_id, subject_id
+            FROM reputation__endorsements
+            WHERE topic = 'trust' AND revoked_at IS NULL AND endorser_id IS NOT NULL
+              AND visibility <> 'private' AND NOT aggregates_paused
+        ),
+        degrees AS (
+            SELECT a.id,
+                   (SELECT COUNT(*) FROM edges e WHERE e.subject_id = a.id) AS in_degree,
+                   (SELECT COUNT(*) FROM edges e WHERE e.endorser_id = a.id) AS out_degree
+            FROM accounts a
+        )
+        SELECT 'in' AS direction, in_degree AS degree, COUNT(*) AS accounts
+        FROM degrees GROUP BY in_degree HAVING COUNT(*) >= $1
+        UNION ALL
+        SELECT 'out', out_degree, COUNT(*)
+        FROM degrees GROUP BY out_degree HAVING COUNT(*) >= $1
+        ORDER BY 1, 2
+        ",
+    )
+    .bind(i64::from(k))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// Research export persistence for the HTTP handlers.
+#[async_trait]
+pub trait ResearchRepo: Send + Sync {
+    async fn create_request(
+        &self,
+        requester_id: Uuid,
+        dataset: ResearchDataset,
+        purpose: &str,
+        daily_limit: u32,
+    ) -> Result<ExportRequestRecord, ResearchRepoError>;
+
+    async fn list_requests(
+        &self,
+        requester_id: Option<Uuid>,
+    ) -> Result<Vec<ExportRequestRecord>, ResearchRepoError>;
+
+    async fn decide_request(
+        &self,
+        id: Uuid,
+        decider_id: Uuid,
+        approve: bool,
+        ttl_days: u32,
+    ) -> Result<ExportRequestRecord, ResearchRepoError>;
+
+    async fn claim_download(
+        &self,
+        id: Uuid,
+        requester_id: Uuid,
+        max_downloads: u32,
+    ) -> Result<ResearchDataset, ResearchRepoError>;
+
+    async fn endorsement_aggregates(
+        &self,
+        k: u32,
+    ) -> Result<Vec<EndorsementAggregateRow>, ResearchRepoError>;
+
+    async fn trust_degrees(&self, k: u32) -> Result<Vec<DegreeBucketRow>, ResearchRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`ResearchRepo`].
+pub struct PgResearchRepo {
+    pool: PgPool,
+}
+
+impl PgResearchRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ResearchRepo for PgResearchRepo {
+    async fn create_request(
+        &self,
+        requester_id: Uuid,
+        dataset: ResearchDataset,
+        purpose: &str,
+        daily_limit: u32,
+    ) -> Result<ExportRequestRecord, ResearchRepoError> {
+        create_request(&self.pool, requester_id, dataset, purpose, daily_limit).await
+    }
+
+    async fn list_requests(
+        &self,
+        requester_id: Option<Uuid>,
+    ) -> Result<Vec<ExportRequestRecord>, ResearchRepoError> {
+        list_requests(&self.pool, requester_id).await
+    }
+
+    async fn decide_request(
+        &self,
+        id: Uuid,
+        decider_id: Uuid,
+        approve: bool,
+        ttl_days: u32,
+    ) -> Result<ExportRequestRecord, ResearchRepoError> {
+        decide_request(&self.pool, id, decider_id, approve, ttl_days).await
+    }
+
+    async fn claim_download(
+        &self,
+        id: Uuid,
+        requester_id: Uuid,
+        max_downloads: u32,
+    ) -> Result<ResearchDataset, ResearchRepoError> {
+        claim_download(&self.pool, id, requester_id, max_downloads).await
+    }
+
+    async fn endorsement_aggregates(
+        &self,
+        k: u32,
+    ) -> Result<Vec<EndorsementAggregateRow>, ResearchRepoError> {
+        endorsement_aggregates(&self.pool, k).await
+    }
+
+    async fn trust_degrees(&self, k: u32) -> Result<Vec<DegreeBucketRow>, ResearchRepoError> {
+        trust_degrees(&self.pool, k).await
+    }
+}
@@ -79,7 +79,7 @@ impl IntoResponse for ProblemDetails {
 #[openapi(
     info(
         title = "TinyCongress API",
-        version = "1.0.0",
+        version = "1.16.0",
         description = "REST API for TinyCongress",
         license(name = "MIT")
     ),
@@ -88,28 +88,122 @@ impl IntoResponse for ProblemDetails {
     ),
     paths(
         get_build_info,
+        crate::changelog::get_changelog,
+        crate::instance::get_instance_document,
+        crate::stats::http::get_stats,
         crate::reputation::http::my_endorsements,
+        crate::reputation::http::badge::get_badge,
+        crate::identity::http::verify::verify,
         crate::reputation::http::check_endorsement,
         crate::reputation::http::create_endorsement_as_verifier,
+        crate::reputation::http::disputes::dispute_endorsement,
+        crate::reputation::http::disputes::list_open_disputes,
+        crate::reputation::http::disputes::resolve_dispute,
+        crate::reputation::http::explanation::get_explanation,
+        crate::reputation::http::strikes::issue_strike,
+        crate::reputation::http::strikes::my_strikes,
+        crate::reputation::http::strikes::appeal_strike,
+        crate::reputation::http::strikes::list_pending_appeals,
+        crate::reputation::http::strikes::resolve_appeal,
+        crate::reputation::http::verifications::my_verifications,
+        crate::reputation::http::verifications::account_verifications,
+        crate::reputation::http::verifications::start_verification,
+        crate::reputation::http::verifications::confirm_verification,
+        crate::reputation::http::verifications::attest_in_person,
+        crate::reputation::http::verifications::idv_webhook,
         crate::reputation::http::idme::authorize,
         crate::reputation::http::idme::callback,
         crate::trust::http::budget_handler,
         crate::trust::http::endorse_handler,
         crate::trust::http::revoke_handler,
         crate::trust::http::scores_me_handler,
+        crate::trust::http::distance_handler,
         crate::trust::http::create_invite_handler,
         crate::trust::http::list_invites_handler,
+        crate::trust::http::invite_quota_handler,
+        crate::trust::http::invite_tree_handler,
         crate::trust::http::accept_invite_handler,
+        crate::trust::http::create_pairing_code_handler,
+        crate::trust::http::invite_link_handler,
+        crate::trust::http::resolve_pairing_code_handler,
         crate::trust::http::denounce_handler,
         crate::trust::http::list_my_denouncements_handler,
+        crate::trust::http::reconfirmations_handler,
+        crate::trust::http::reconfirm_handler,
+        // Notifications
+        crate::notifications::http::list_notifications,
+        crate::notifications::http::mark_read,
+        crate::notifications::http::get_preferences,
+        crate::notifications::http::update_preferences,
+        // Topics
+        crate::topics::http::subscribe,
+        crate::topics::http::unsubscribe,
+        crate::topics::http::list_subscriptions,
+        crate::topics::http::get_feed,
+        // Feed
+        crate::feed::http::get_feed,
+        // Research exports
+        crate::research::http::create_export,
+        crate::research::http::list_exports,
+        crate::research::http::approve_export,
+        crate::research::http::reject_export,
+        crate::research::http::download_export,
+        // Integrity alerts
+        crate::integrity::http::list_alerts,
+        crate::integrity::http::resolve_alert,
+        // Developer portal
+        crate::developer::http::create_client,
+        crate::developer::http::list_clients,
+        crate::developer::http::update_client,
+        crate::developer::http::rotate_client_secret,
+        crate::developer::http::revoke_client,
+        crate::developer::http::authorize,
+        crate::developer::http::token,
         // Identity
         crate::identity::http::signup,
         crate::identity::http::account_lookup,
+        crate::identity::http::profile::get_account_profile,
         crate::identity::http::backup::get_backup,
+        crate::identity::http::backup::get_recovery_backup,
+        crate::identity::http::backup::set_recovery_code,
         crate::identity::http::devices::list_devices,
         crate::identity::http::devices::add_device,
         crate::identity::http::devices::revoke_device,
         crate::identity::http::devices::rename_device,
+        crate::identity::http::devices::get_device_delegation,
+        crate::identity::http::devices::rotate_device,
+        crate::identity::http::security::security_checkup,
+        crate::identity::http::crl::get_account_crl,
+        crate::identity::http::sigchain::list_sigchain,
+        crate::identity::http::sigchain::get_sigchain_head,
+        crate::identity::http::username::check_username_availability,
+        crate::identity::http::username::change_username,
+        crate::identity::http::deletion::delete_account,
+        crate::identity::http::deletion::undelete_account,
+        crate::identity::http::sessions::create_session,
+        crate::identity::http::challenge::get_challenge,
+        crate::identity::http::sessions::revoke_sessions,
+        crate::identity::http::step_up::get_step_up_phone,
+        crate::identity::http::step_up::enroll_step_up_phone,
+        crate::identity::http::step_up::remove_step_up_phone,
+        crate::identity::http::step_up::create_step_up_challenge,
+        crate::identity::http::step_up::confirm_step_up_challenge,
+        crate::identity::http::tokens::create_token,
+        crate::identity::http::tokens::list_tokens,
+        crate::identity::http::tokens::revoke_token,
+        crate::identity::http::tokens::top_token_consumers,
+        crate::identity::http::account_status::change_own_status,
+        crate::identity::http::account_status::change_account_status_as_admin,
+        crate::identity::http::signup_invites::create_signup_invite,
+        crate::identity::http::signup_invites::list_signup_invites,
+        crate::identity::http::aliases::create_alias,
+        crate::identity::http::aliases::list_aliases,
+        crate::identity::http::aliases::delete_alias,
+        crate::identity::http::reserved_usernames::list_reserved_usernames,
+        crate::identity::http::reserved_usernames::reserve_username,
+        crate::identity::http::reserved_usernames::release_username,
+        crate::identity::http::reserved_usernames::list_released_usernames,
+        crate::identity::http::reserved_usernames::lift_username_quarantine,
         crate::identity::http::login::login,
         // Rooms (platform)
         crate::rooms::http::platform::list_rooms,
@@ -138,28 +232,113 @@ impl IntoResponse for ProblemDetails {
     ),
     components(schemas(
         BuildInfo,
+        crate::changelog::ChangelogResponse,
+        crate::changelog::ChangelogRelease,
+        crate::changelog::ChangelogChange,
+        crate::changelog::ChangeKind,
         ProblemDetails,
         ProblemExtensions,
+        crate::instance::InstanceDocument,
+        crate::instance::SoftwareInfo,
+        crate::instance::InstanceEndpoints,
+        crate::instance::VerifierKey,
+        crate::stats::http::InstanceStatsResponse,
+        crate::stats::http::DailyStats,
+        crate::stats::http::TopicEndorsementCount,
         crate::reputation::http::EndorsementResponse,
         crate::reputation::http::EndorsementsListResponse,
+        crate::reputation::http::badge::BadgeResponse,
+        crate::identity::http::verify::VerificationStatement,
+        crate::identity::http::verify::DeviceStatus,
         crate::reputation::http::HasEndorsementResponse,
         crate::reputation::http::EndorsementQuery,
         crate::reputation::http::CreateEndorsementRequest,
         crate::reputation::http::CreatedEndorsementResponse,
+        crate::reputation::EndorsementVisibility,
+        crate::reputation::DisputeStatus,
+        crate::reputation::http::disputes::DisputeEndorsementRequest,
+        crate::reputation::http::disputes::ResolveDisputeRequest,
+        crate::reputation::http::disputes::DisputeResponse,
+        crate::reputation::http::disputes::DisputesListResponse,
         crate::reputation::http::idme::AuthorizeResponse,
         crate::reputation::http::idme::CallbackQuery,
         crate::trust::http::BudgetResponse,
         crate::trust::http::ScoreSnapshotResponse,
         crate::trust::http::ScoresResponse,
+        crate::trust::http::TrustDistanceResponse,
+        crate::trust::distances::DistanceOrigin,
         crate::trust::http::CreateInviteResponse,
         crate::trust::http::InviteResponse,
         crate::trust::http::InvitesResponse,
+        crate::trust::http::InviteTreeEdgeResponse,
+        crate::trust::http::InviteTreeResponse,
+        crate::trust::invite_quota::InviteQuota,
         crate::trust::http::AcceptInviteResponse,
+        crate::trust::http::PairingCodeResponse,
+        crate::trust::http::InviteLinkResponse,
         crate::trust::http::DenouncementResponse,
         crate::trust::http::MessageResponse,
         crate::trust::http::EndorseRequest,
         crate::trust::http::RevokeRequest,
         crate::trust::http::DenounceRequest,
+        crate::trust::http::ReconfirmRequest,
+        crate::trust::http::ReconfirmResponse,
+        crate::trust::http::StaleEdgeResponse,
+        crate::trust::http::ReconfirmationsResponse,
+        crate::notifications::NotificationCategory,
+        crate::notifications::http::NotificationResponse,
+        crate::notifications::http::NotificationsResponse,
+        crate::notifications::http::MarkReadResponse,
+        crate::notifications::http::NotificationPreference,
+        crate::notifications::http::NotificationPreferencesResponse,
+        crate::notifications::http::UpdatePreferencesRequest,
+        crate::topics::http::TopicSubscriptionResponse,
+        crate::topics::http::TopicSubscriptionsResponse,
+        crate::topics::http::TopicFeedItemResponse,
+        crate::topics::http::TopicMovementResponse,
+        crate::topics::http::TopicFeedResponse,
+        crate::feed::FeedItem,
+        crate::feed::RankedFeedItem,
+        crate::feed::http::FeedResponse,
+        crate::research::ResearchDataset,
+        crate::research::ExportStatus,
+        crate::research::http::CreateExportRequest,
+        crate::research::http::ExportRequestResponse,
+        crate::research::http::ExportRequestsResponse,
+        crate::integrity::http::IntegrityAlertResponse,
+        crate::integrity::http::IntegrityAlertsResponse,
+        crate::developer::http::CreateClientRequest,
+        crate::developer::http::UpdateClientRequest,
+        crate::developer::http::ClientInfo,
+        crate::developer::http::ClientWithSecretResponse,
+        crate::developer::http::ClientListResponse,
+        crate::developer::http::AuthorizeRequest,
+        crate::developer::http::AuthorizeResponse,
+        crate::developer::http::TokenRequest,
+        crate::developer::http::TokenResponse,
+        crate::developer::http::OAuthErrorResponse,
+        crate::reputation::ReputationTier,
+        crate::reputation::score::ReputationExplanation,
+        crate::reputation::score::ScoreComponent,
+        crate::reputation::score::ScoreComponentKind,
+        crate::reputation::strikes::StrikeReason,
+        crate::reputation::strikes::AppealStatus,
+        crate::reputation::strikes::StrikeAction,
+        crate::reputation::http::strikes::IssueStrikeRequest,
+        crate::reputation::http::strikes::AppealStrikeRequest,
+        crate::reputation::http::strikes::ResolveAppealRequest,
+        crate::reputation::http::strikes::StrikeEventResponse,
+        crate::reputation::http::strikes::StrikeResponse,
+        crate::reputation::http::strikes::StrikesListResponse,
+        crate::reputation::verification::VerificationMethod,
+        crate::reputation::http::verifications::VerificationResponse,
+        crate::reputation::http::verifications::VerificationsResponse,
+        crate::reputation::http::verifications::StartVerificationRequest,
+        crate::reputation::http::verifications::StartVerificationResponse,
+        crate::reputation::http::verifications::ConfirmVerificationRequest,
+        crate::reputation::http::verifications::InPersonAttestationResponse,
+        crate::reputation::http::verifications::IdvWebhookRequest,
+        crate::reputation::http::verifications::IdvWebhookResponse,
         crate::trust::http::CreateInviteRequest,
         // Identity schemas
         crate::identity::service::SignupRequest,
@@ -167,12 +346,72 @@ impl IntoResponse for ProblemDetails {
         crate::identity::service::SignupDevice,
         crate::identity::http::SignupResponse,
         crate::identity::http::AccountLookupResponse,
+        crate::identity::http::profile::AccountProfileResponse,
+        crate::identity::http::profile::ProfileDevice,
         crate::identity::http::backup::BackupResponse,
+        crate::identity::http::backup::RecoveryBackupResponse,
+        crate::identity::http::backup::BackupKdfParams,
+        crate::identity::http::backup::SetRecoveryCodeRequest,
         crate::identity::http::devices::DeviceInfo,
         crate::identity::http::devices::DeviceListResponse,
         crate::identity::http::devices::AddDeviceRequest,
         crate::identity::http::devices::AddDeviceResponse,
         crate::identity::http::devices::RenameDeviceRequest,
+        crate::identity::http::devices::RotateDeviceRequest,
+        crate::identity::http::devices::RotateDeviceResponse,
+        crate::identity::http::devices::DelegationStatus,
+        crate::identity::http::devices::DelegationResponse,
+        crate::identity::http::security::SecurityCheckupResponse,
+        crate::identity::http::sessions::CreateSessionResponse,
+        crate::identity::http::challenge::AuthChallengeResponse,
+        crate::identity::crl::DeviceCrl,
+        crate::identity::crl::CrlEntry,
+        crate::identity::crl::CrlSigner,
+        crate::identity::sigchain::SigchainLink,
+        crate::identity::http::sigchain::SigchainPage,
+        crate::identity::http::sigchain::SigchainHead,
+        crate::identity::http::username::UsernameUnavailableReason,
+        crate::identity::http::username::UsernameAvailabilityResponse,
+        crate::identity::http::username::ChangeUsernameRequest,
+        crate::identity::http::username::ChangeUsernameResponse,
+        crate::identity::http::deletion::DeleteAccountRequest,
+        crate::identity::http::deletion::DeleteAccountResponse,
+        crate::identity::http::deletion::UndeleteAccountRequest,
+        crate::identity::http::deletion::UndeleteAccountResponse,
+        crate::identity::http::security::DevicePosture,
+        crate::identity::http::security::BackupPosture,
+        crate::identity::http::security::ApiTokenPosture,
+        crate::identity::http::security::SecurityEvent,
+        crate::identity::http::security::SecurityEventKind,
+        crate::identity::http::security::Recommendation,
+        crate::identity::http::security::RecommendationCode,
+        crate::identity::http::step_up::StepUpPhoneResponse,
+        crate::identity::http::step_up::EnrollStepUpPhoneRequest,
+        crate::identity::http::step_up::CreateStepUpChallengeRequest,
+        crate::identity::http::step_up::ConfirmStepUpChallengeRequest,
+        crate::identity::http::step_up::StepUpChallengeResponse,
+        crate::identity::step_up::StepUpAction,
+        crate::identity::http::token_auth::ApiTokenScope,
+        crate::identity::http::tokens::ApiTokenInfo,
+        crate::identity::http::tokens::ApiTokenListResponse,
+        crate::identity::http::tokens::CreateApiTokenRequest,
+        crate::identity::http::tokens::CreateApiTokenResponse,
+        crate::identity::http::tokens::TokenUsageInfo,
+        crate::identity::http::tokens::TokenUsageResponse,
+        crate::identity::http::account_status::ChangeAccountStatusRequest,
+        crate::identity::http::account_status::AccountStatusResponse,
+        crate::identity::status::AccountStatus,
+        crate::identity::http::signup_invites::SignupInviteInfo,
+        crate::identity::http::signup_invites::SignupInviteListResponse,
+        crate::identity::http::signup_invites::CreateSignupInviteResponse,
+        crate::identity::http::aliases::AliasInfo,
+        crate::identity::http::aliases::AliasListResponse,
+        crate::identity::http::aliases::CreateAliasRequest,
+        crate::identity::http::reserved_usernames::ReserveUsernameRequest,
+        crate::identity::http::reserved_usernames::ReservedUsernameInfo,
+        crate::identity::http::reserved_usernames::ReservedUsernamesResponse,
+        crate::identity::http::reserved_usernames::ReleasedUsernameInfo,
+        crate::identity::http::reserved_usernames::ReleasedUsernamesResponse,
         crate::identity::http::login::LoginRequest,
         crate::identity::http::login::LoginDevice,
         crate::identity::http::login::LoginResponse,
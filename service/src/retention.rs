@@ -0,0 +1,306 @@
+//! Data retention — periodic purging of rows that have outlived their purpose.
+//!
+//! Each [`RetentionPolicy`] names a table, the predicate that marks a row as
+//! finished (an unaccepted invite, a processed action), and how long finished
+//! rows are kept. Operators opt tables in via [`RetentionConfig`]; tables
+//! without a configured age are kept forever.
+//!
+//! Nonce expiry is the one mandatory policy: `request_nonces` only needs to
+//! outlive the signed-request timestamp window, so it is always purged after
+//! `auth.nonce_ttl_secs` and is not affected by dry-run mode. Each run also
+//! creates upcoming hourly nonce partitions and drops expired ones before
+//! purging whatever is left. Expired `auth_challenges` are likewise always
+//! purged.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::config::{AuthConfig, RetentionConfig};
+use crate::identity::repo::maintain_nonce_partitions;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A single table's retention rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Table the policy applies to (also the `table` metric label).
+    pub table: &'static str,
+    /// SQL predicate selecting purgeable rows. `$1` is the cutoff timestamp.
+    predicate: &'static str,
+    /// Rows older than this (per the predicate) are purged.
+    pub max_age: Duration,
+    /// Mandatory policies ignore dry-run mode.
+    pub mandatory: bool,
+}
+
+impl RetentionPolicy {
+    const fn nonces(ttl: Duration) -> Self {
+        Self {
+            table: "request_nonces",
+            predicate: "created_at < $1",
+            max_age: ttl,
+            mandatory: true,
+        }
+    }
+
+    const fn challenges() -> Self {
+        Self {
+            table: "auth_challenges",
+            predicate: "expires_at < $1",
+            max_age: Duration::ZERO,
+            mandatory: true,
+        }
+    }
+
+    const fn days(
+        table: &'static str,
+        predicate: &'static str,
+        days: u32,
+        mandatory: bool,
+    ) -> Self {
+        Self {
+            table,
+            predicate,
+            max_age: Duration::from_secs(days as u64 * SECS_PER_DAY),
+            mandatory,
+        }
+    }
+
+    /// Oldest timestamp that is still retained as of `now`.
+    #[must_use]
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        chrono::Duration::from_std(self.max_age)
+            .ok()
+            .and_then(|age| now.checked_sub_signed(age))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+    }
+}
+
+/// Build the active policy list from config. Nonce and challenge expiry are
+/// always first.
+#[must_use]
+pub fn policies(config: &RetentionConfig, auth: &AuthConfig) -> Vec<RetentionPolicy> {
+    let mut policies = vec![
+        RetentionPolicy::nonces(auth.nonce_ttl()),
+        RetentionPolicy::challenges(),
+    ];
+    if let Some(days) = config.action_log_days {
+        policies.push(RetentionPolicy::days(
+            "trust__action_log",
+            "status IN ('completed', 'failed') AND COALESCE(processed_at, created_at) < $1",
+            days,
+            false,
+        ));
+    }
+    if let Some(days) = config.bot_trace_days {
+        policies.push(RetentionPolicy::days(
+            "rooms__bot_traces",
+            "status <> 'running' AND created_at < $1",
+            days,
+            false,
+        ));
+    }
+    if let Some(days) = config.expired_invite_days {
+        policies.push(RetentionPolicy::days(
+            "trust__invites",
+            "accepted_at IS NULL AND expires_at < $1",
+            days,
+            false,
+        ));
+    }
+    if let Some(days) = config.notification_days {
+        policies.push(RetentionPolicy::days(
+            "notifications",
+            "COALESCE(read_at, created_at) < $1",
+            days,
+            false,
+        ));
+    }
+    policies
+}
+
+/// Outcome of applying one policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurgeOutcome {
+    pub table: &'static str,
+    /// Rows deleted, or rows that would have been deleted in dry-run mode.
+    pub rows: u64,
+    pub dry_run: bool,
+}
+
+/// Apply one policy as of `now`. In dry-run mode, count instead of delete.
+///
+/// # Errors
+///
+/// Returns the underlying database error.
+pub async fn apply_policy(
+    pool: &PgPool,
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<PurgeOutcome, sqlx::Error> {
+    let dry_run = dry_run && !policy.mandatory;
+    let cutoff = policy.cutoff(now);
+    let rows = if dry_run {
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            policy.table, policy.predicate
+        );
+        let count: i64 = sqlx::query_scalar(&sql)
+            .bind(cutoff)
+            .fetch_one(pool)
+            .await?;
+        count.unsigned_abs()
+    } else {
+        let sql = format!("DELETE FROM {} WHERE {}", policy.table, policy.predicate);
+        sqlx::query(&sql)
+            .bind(cutoff)
+            .execute(pool)
+            .await?
+            .rows_affected()
+    };
+    Ok(PurgeOutcome {
+        table: policy.table,
+        rows,
+        dry_run,
+    })
+}
+
+/// Apply every policy once, logging and recording metrics per table.
+///
+/// A failing policy is logged and skipped so one table cannot block the rest.
+pub async fn run_retention(
+    pool: &PgPool,
+    policies: &[RetentionPolicy],
+    dry_run: bool,
+) -> Vec<PurgeOutcome> {
+    let now = Utc::now();
+    if let Some(nonces) = policies.iter().find(|p| p.table == "request_nonces") {
+        match maintain_nonce_partitions(pool, now, nonces.cutoff(now)).await {
+            Ok(changes) => {
+                if !changes.created.is_empty() || !changes.dropped.is_empty() {
+                    tracing::debug!(?changes.created, ?changes.dropped, "Maintained nonce partitions");
+                }
+            }
+            Err(e) => tracing::warn!("Nonce partition maintenance failed: {e}"),
+        }
+    }
+    let mut outcomes = Vec::with_capacity(policies.len());
+    for policy in policies {
+        match apply_policy(pool, policy, now, dry_run).await {
+            Ok(outcome) => {
+                record_outcome(&outcome);
+                outcomes.push(outcome);
+            }
+            Err(e) => tracing::warn!(table = policy.table, "Retention purge failed: {e}"),
+        }
+    }
+    outcomes
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn record_outcome(outcome: &PurgeOutcome) {
+    if outcome.dry_run {
+        metrics::gauge!("tc_retention_eligible_rows", "table" => outcome.table)
+            .set(outcome.rows as f64);
+        if outcome.rows > 0 {
+            tracing::info!(
+                table = outcome.table,
+                count = outcome.rows,
+                "Retention dry run: rows eligible for purge"
+            );
+        }
+    } else {
+        metrics::counter!("tc_retention_purged_rows_total", "table" => outcome.table)
+            .increment(outcome.rows);
+        if outcome.rows > 0 {
+            tracing::debug!(
+                table = outcome.table,
+                count = outcome.rows,
+                "Purged expired rows"
+            );
+        }
+    }
+}
+
+/// Spawn the background retention worker.
+pub fn spawn_retention_worker(pool: PgPool, config: &RetentionConfig, auth: &AuthConfig) {
+    let policies = policies(config, auth);
+    let dry_run = config.dry_run;
+    let period = Duration::from_secs(config.interval_secs);
+    tracing::info!(
+        tables = ?policies.iter().map(|p| p.table).collect::<Vec<_>>(),
+        dry_run,
+        "Retention worker configured"
+    );
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            run_retention(&pool, &policies, dry_run).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nonce_policy_is_always_present_and_mandatory() {
+        let policies = policies(&RetentionConfig::default(), &AuthConfig::default());
+        assert_eq!(policies.len(), 2);
+        assert_eq!(policies[0].table, "request_nonces");
+        assert!(policies[0].mandatory);
+        assert_eq!(policies[0].max_age, Duration::from_secs(600));
+        assert_eq!(policies[1].table, "auth_challenges");
+        assert!(policies[1].mandatory);
+        assert_eq!(policies[1].max_age, Duration::ZERO);
+    }
+
+    #[test]
+    fn nonce_policy_follows_configured_ttl() {
+        let auth = AuthConfig {
+            max_clock_skew_secs: 30,
+            nonce_ttl_secs: 90,
+            ..AuthConfig::default()
+        };
+        let policies = policies(&RetentionConfig::default(), &auth);
+        assert_eq!(policies[0].max_age, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn configured_tables_are_opted_in() {
+        let config = RetentionConfig {
+            action_log_days: Some(90),
+            expired_invite_days: Some(7),
+            notification_days: Some(30),
+            ..RetentionConfig::default()
+        };
+        let tables: Vec<_> = policies(&config, &AuthConfig::default())
+            .iter()
+            .map(|p| p.table)
+            .collect();
+        assert_eq!(
+            tables,
+            vec![
+                "request_nonces",
+                "auth_challenges",
+                "trust__action_log",
+                "trust__invites",
+                "notifications"
+            ]
+        );
+    }
+
+    #[test]
+    fn cutoff_subtracts_max_age() {
+        let policy = RetentionPolicy::days("t", "true", 2, false);
+        let now = DateTime::parse_from_rfc3339("2025-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(policy.cutoff(now).to_rfc3339(), "2025-03-08T12:00:00+00:00");
+    }
+}
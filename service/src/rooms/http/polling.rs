@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::events::{DomainEvent, EventPublisher};
 use crate::http::{internal_error, not_found, ErrorResponse, Path};
 use crate::identity::http::auth::AuthenticatedDevice;
 use crate::rooms::service::{
@@ -537,7 +538,8 @@ pub async fn reset_poll(
 )]
 pub async fn cast_vote(
     Extension(polling): Extension<Arc<dyn PollingService>>,
-    Path((_room_id, poll_id)): Path<(Uuid, Uuid)>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
+    Path((room_id, poll_id)): Path<(Uuid, Uuid)>,
     auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
     let req: CastVoteRequest = match auth.json() {
@@ -554,6 +556,9 @@ pub async fn cast_vote(
                 poll_id = %poll_id,
                 "Vote cast"
             );
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::BallotCast { room_id, poll_id });
+            }
             let votes: Vec<_> = votes
                 .into_iter()
                 .map(|v| VoteResponse {
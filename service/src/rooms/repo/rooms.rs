@@ -3,6 +3,8 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::db::{map_unique_violation, RepoError, RepoErrorKind};
+
 #[derive(Debug, Clone)]
 pub struct RoomRecord {
     pub id: Uuid,
@@ -30,6 +32,16 @@ pub enum RoomRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for RoomRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::DuplicateName => RepoErrorKind::Conflict,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct RoomRow {
     id: Uuid,
@@ -103,14 +115,9 @@ where
 
     match result {
         Ok(row) => Ok(row_to_record(row)),
-        Err(e) => {
-            if let sqlx::Error::Database(ref db_err) = e {
-                if db_err.constraint() == Some("uq_rooms_name") {
-                    return Err(RoomRepoError::DuplicateName);
-                }
-            }
-            Err(RoomRepoError::Database(e))
-        }
+        Err(e) => Err(map_unique_violation(e, |constraint| {
+            (constraint == "uq_rooms_name").then_some(RoomRepoError::DuplicateName)
+        })),
     }
 }
 
@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::db::{RepoError, RepoErrorKind};
+
 #[derive(Debug, Clone)]
 pub struct SuggestionRecord {
     pub id: Uuid,
@@ -26,6 +28,15 @@ pub enum SuggestionRepoError {
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for SuggestionRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct SuggestionRow {
     id: Uuid,
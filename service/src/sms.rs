@@ -0,0 +1,355 @@
+//! Text message delivery.
+//!
+//! [`SmsProvider`] sends a single SMS to an international (`+` and digits)
+//! phone number. The provider is chosen by `sms.provider`:
+//!
+//! - [`TwilioSmsProvider`]: Twilio Programmable Messaging REST API.
+//! - [`SnsSmsProvider`]: Amazon SNS `Publish` to a phone number, signed with
+//!   AWS Signature Version 4.
+//! - [`MockSmsProvider`]: keeps messages in memory and logs them, for local
+//!   development and tests.
+//!
+//! Used for step-up confirmation codes; see [`crate::identity::step_up`].
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::config::{SmsConfig, SmsProviderKind};
+
+/// How long to wait for a provider before giving up.
+const PROVIDER_TIMEOUT_SECS: u64 = 10;
+
+/// Errors returned by an [`SmsProvider`].
+#[derive(Debug, thiserror::Error)]
+pub enum SmsError {
+    #[error("provider request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("provider returned {0}")]
+    Status(reqwest::StatusCode),
+}
+
+/// Sends text messages.
+#[async_trait]
+pub trait SmsProvider: Send + Sync {
+    /// Provider name, for logs.
+    fn name(&self) -> &'static str;
+
+    /// Send `body` to `to`, a phone number in international form.
+    async fn send(&self, to: &str, body: &str) -> Result<(), SmsError>;
+}
+
+/// Canonical international form of a phone number: `+` and 8-15 digits.
+///
+/// Spaces, dashes, dots and parentheses are dropped. Returns `None` for
+/// anything else, including numbers without a leading `+`.
+#[must_use]
+pub fn normalize_phone(raw: &str) -> Option<String> {
+    let digits: String = raw
+        .trim()
+        .strip_prefix('+')?
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+    ((8..=15).contains(&digits.len()) && digits.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| format!("+{digits}"))
+}
+
+// ─── Twilio ────────────────────────────────────────────────────────────────
+
+/// Twilio Programmable Messaging.
+pub struct TwilioSmsProvider {
+    client: reqwest::Client,
+    account_sid: String,
+    auth_token: String,
+    from: String,
+}
+
+impl TwilioSmsProvider {
+    /// `from` is a sending number, or a messaging service SID (`MG…`).
+    #[must_use]
+    pub fn new(account_sid: &str, auth_token: &str, from: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            account_sid: account_sid.to_string(),
+            auth_token: auth_token.to_string(),
+            from: from.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl SmsProvider for TwilioSmsProvider {
+    fn name(&self) -> &'static str {
+        "twilio"
+    }
+
+    async fn send(&self, to: &str, body: &str) -> Result<(), SmsError> {
+        let url = format!(
+            "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
+            self.account_sid
+        );
+        let from_field = if self.from.starts_with("MG") {
+            "MessagingServiceSid"
+        } else {
+            "From"
+        };
+        let response = self
+            .client
+            .post(url)
+            .timeout(std::time::Duration::from_secs(PROVIDER_TIMEOUT_SECS))
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+            .form(&[("To", to), (from_field, &self.from), ("Body", body)])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(SmsError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
+
+// ─── Amazon SNS ────────────────────────────────────────────────────────────
+
+/// Amazon SNS direct publish to a phone number.
+pub struct SnsSmsProvider {
+    client: reqwest::Client,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl SnsSmsProvider {
+    #[must_use]
+    pub fn new(region: &str, access_key_id: &str, secret_access_key: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            region: region.to_string(),
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+        }
+    }
+
+    fn host(&self) -> String {
+        format!("sns.{}.amazonaws.com", self.region)
+    }
+
+    /// `Authorization` header for a form-encoded `POST /` to SNS.
+    fn authorization(&self, now: DateTime<Utc>, body: &str) -> String {
+        const SIGNED_HEADERS: &str = "content-type;host;x-amz-date";
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{date}/{}/sns/aws4_request", self.region);
+        let canonical_request = format!(
+            "POST\n/\n\ncontent-type:{SNS_CONTENT_TYPE}\nhost:{}\nx-amz-date:{amz_date}\n\n\
+             {SIGNED_HEADERS}\n{}",
+            self.host(),
+            to_hex(&Sha256::digest(body.as_bytes()))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            to_hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = sigv4_signing_key(&self.secret_access_key, &date, &self.region, "sns");
+        let signature = to_hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, \
+             Signature={signature}",
+            self.access_key_id
+        )
+    }
+}
+
+const SNS_CONTENT_TYPE: &str = "application/x-www-form-urlencoded; charset=utf-8";
+
+#[async_trait]
+impl SmsProvider for SnsSmsProvider {
+    fn name(&self) -> &'static str {
+        "sns"
+    }
+
+    async fn send(&self, to: &str, body: &str) -> Result<(), SmsError> {
+        let form = format!(
+            "Action=Publish&Message={}&PhoneNumber={}&Version=2010-03-31",
+            urlencoding::encode(body),
+            urlencoding::encode(to)
+        );
+        let now = Utc::now();
+        let response = self
+            .client
+            .post(format!("https://{}/", self.host()))
+            .timeout(std::time::Duration::from_secs(PROVIDER_TIMEOUT_SECS))
+            .header("content-type", SNS_CONTENT_TYPE)
+            .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string())
+            .header("authorization", self.authorization(now, &form))
+            .body(form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(SmsError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length, so this never fails.
+    Hmac::<Sha256>::new_from_slice(key).map_or_else(
+        |_| Vec::new(),
+        |mut mac| {
+            mac.update(data);
+            mac.finalize().into_bytes().to_vec()
+        },
+    )
+}
+
+/// Derive the AWS Signature Version 4 signing key for one day, region and
+/// service.
+fn sigv4_signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{secret}").as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    bytes
+        .iter()
+        .flat_map(|b| [DIGITS[usize::from(b >> 4)], DIGITS[usize::from(b & 0x0f)]])
+        .map(char::from)
+        .collect()
+}
+
+// ─── Mock ──────────────────────────────────────────────────────────────────
+
+/// A message accepted by [`MockSmsProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentSms {
+    pub to: String,
+    pub body: String,
+}
+
+/// Keeps messages in memory and logs them instead of sending them.
+#[derive(Default)]
+pub struct MockSmsProvider {
+    sent: Mutex<Vec<SentSms>>,
+}
+
+impl MockSmsProvider {
+    /// Messages sent so far, oldest first.
+    #[must_use]
+    pub fn sent(&self) -> Vec<SentSms> {
+        self.sent
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl SmsProvider for MockSmsProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn send(&self, to: &str, body: &str) -> Result<(), SmsError> {
+        tracing::info!(to, "SMS (mock): {body}");
+        self.sent
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(SentSms {
+                to: to.to_string(),
+                body: body.to_string(),
+            });
+        Ok(())
+    }
+}
+
+/// Build the configured provider, or `None` when SMS is disabled.
+#[must_use]
+pub fn build_provider(config: &SmsConfig) -> Option<Arc<dyn SmsProvider>> {
+    match config.provider {
+        SmsProviderKind::Disabled => {
+            tracing::info!(
+                "SMS disabled; step-up confirmation is off (enable via TC_SMS__PROVIDER)"
+            );
+            None
+        }
+        SmsProviderKind::Mock => {
+            tracing::warn!("SMS messages are written to the log; do not use in production");
+            Some(Arc::new(MockSmsProvider::default()))
+        }
+        SmsProviderKind::Twilio => Some(Arc::new(TwilioSmsProvider::new(
+            &config.twilio_account_sid,
+            &config.twilio_auth_token,
+            &config.twilio_from,
+        ))),
+        SmsProviderKind::Sns => Some(Arc::new(SnsSmsProvider::new(
+            &config.sns_region,
+            &config.sns_access_key_id,
+            &config.sns_secret_access_key,
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn sigv4_signing_key_matches_aws_example() {
+        // From the AWS Signature Version 4 documentation.
+        let key = sigv4_signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            to_hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn sns_authorization_names_credential_scope() {
+        let provider = SnsSmsProvider::new("eu-west-1", "AKIDEXAMPLE", "secret");
+        let now = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+        let header = provider.authorization(now, "Action=Publish");
+        assert!(header.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260102/eu-west-1/sns/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date, Signature="
+        ));
+        assert_ne!(header, provider.authorization(now, "Action=Other"));
+    }
+
+    #[test]
+    fn phone_numbers_are_normalized() {
+        assert_eq!(
+            normalize_phone(" +44 (20) 7946-0018 ").as_deref(),
+            Some("+442079460018")
+        );
+        assert_eq!(normalize_phone("5551234567"), None);
+        assert_eq!(normalize_phone("+1234"), None);
+        assert_eq!(normalize_phone("+1555abc4567"), None);
+    }
+
+    #[tokio::test]
+    async fn mock_provider_records_messages() {
+        let provider = MockSmsProvider::default();
+        provider.send("+15551234567", "hello").await.unwrap();
+        assert_eq!(
+            provider.sent(),
+            vec![SentSms {
+                to: "+15551234567".to_string(),
+                body: "hello".to_string(),
+            }]
+        );
+    }
+}
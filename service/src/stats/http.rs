@@ -0,0 +1,215 @@
+//! HTTP handler for public instance stats.
+
+use std::sync::Arc;
+
+use async_graphql::SimpleObject;
+use axum::{
+    extract::{Extension, Query},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Days, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::repo::{DailyStatsRecord, StatsRepo, StatsRepoError};
+use crate::http::{bad_request, internal_error};
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::privacy::AggregateNoise;
+
+/// Days returned when the caller does not ask for a specific window.
+pub const DEFAULT_STATS_DAYS: u32 = 30;
+
+/// Largest window a caller may request.
+pub const MAX_STATS_DAYS: u32 = 366;
+
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct StatsQuery {
+    /// Number of most recent days to return (default 30, max 366).
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, SimpleObject, ToSchema)]
+pub struct TopicEndorsementCount {
+    pub topic: String,
+    pub endorsements: i64,
+}
+
+#[derive(Debug, Clone, Serialize, SimpleObject, ToSchema)]
+pub struct DailyStats {
+    /// UTC calendar day (`YYYY-MM-DD`).
+    pub date: String,
+    pub signups: i64,
+    /// Non-revoked devices used within the 30 days ending on `date`.
+    pub active_devices: i64,
+    /// Distinct (poll, voter) pairs with a vote cast or updated that day.
+    pub ballots_cast: i64,
+    /// Distinct accounts that voted that day.
+    pub voters: i64,
+    pub endorsements_by_topic: Vec<TopicEndorsementCount>,
+}
+
+#[derive(Debug, Clone, Serialize, SimpleObject, ToSchema)]
+pub struct InstanceStatsResponse {
+    /// Completed days, newest first. Today is never included.
+    pub days: Vec<DailyStats>,
+}
+
+impl From<DailyStatsRecord> for DailyStats {
+    fn from(r: DailyStatsRecord) -> Self {
+        Self {
+            date: r.day.to_string(),
+            signups: r.signups,
+            active_devices: r.active_devices,
+            ballots_cast: r.ballots_cast,
+            voters: r.voters,
+            endorsements_by_topic: r
+                .endorsements_by_topic
+                .into_iter()
+                .map(|(topic, endorsements)| TopicEndorsementCount {
+                    topic,
+                    endorsements,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Load the most recent `days` of rollups. Shared by REST and GraphQL.
+///
+/// # Errors
+///
+/// Returns `StatsRepoError` if the rollups cannot be read.
+pub async fn load_instance_stats(
+    repo: &dyn StatsRepo,
+    days: u32,
+) -> Result<InstanceStatsResponse, StatsRepoError> {
+    let since = Utc::now()
+        .date_naive()
+        .checked_sub_days(Days::new(u64::from(days)))
+        .ok_or(StatsRepoError::DayOutOfRange)?;
+    let records = repo.list_daily_stats(since).await?;
+    Ok(InstanceStatsResponse {
+        days: records.into_iter().map(DailyStats::from).collect(),
+    })
+}
+
+/// The public view of `stats`: every count passed through `noise`, and
+/// topics whose count is suppressed to zero dropped.
+#[must_use]
+pub fn add_noise(stats: InstanceStatsResponse, noise: &AggregateNoise) -> InstanceStatsResponse {
+    InstanceStatsResponse {
+        days: stats
+            .days
+            .into_iter()
+            .map(|d| {
+                let label = |field: &str| format!("stats:{}:{field}", d.date);
+                DailyStats {
+                    signups: noise.count(&label("signups"), d.signups),
+                    active_devices: noise.count(&label("active_devices"), d.active_devices),
+                    ballots_cast: noise.count(&label("ballots_cast"), d.ballots_cast),
+                    voters: noise.count(&label("voters"), d.voters),
+                    endorsements_by_topic: d
+                        .endorsements_by_topic
+                        .iter()
+                        .map(|t| TopicEndorsementCount {
+                            topic: t.topic.clone(),
+                            endorsements: noise
+                                .count(&label(&format!("topic:{}", t.topic)), t.endorsements),
+                        })
+                        .filter(|t| t.endorsements > 0)
+                        .collect(),
+                    date: d.date,
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Resolve and bound the requested window.
+///
+/// # Errors
+///
+/// Returns a message suitable for a 400 response when out of range.
+pub fn resolve_stats_days(days: Option<u32>) -> Result<u32, String> {
+    match days.unwrap_or(DEFAULT_STATS_DAYS) {
+        0 => Err("days must be at least 1".to_string()),
+        d if d > MAX_STATS_DAYS => Err(format!("days must not exceed {MAX_STATS_DAYS}")),
+        d => Ok(d),
+    }
+}
+
+/// GET /api/v1/stats — public instance statistics
+///
+/// With `privacy.enabled`, counts carry differential-privacy noise unless the
+/// request is device-signed by an admin.
+#[utoipa::path(
+    get,
+    path = "/stats",
+    tag = "System",
+    params(StatsQuery),
+    responses(
+        (status = 200, description = "Daily instance statistics", body = InstanceStatsResponse),
+        (status = 400, description = "Invalid window"),
+        (status = 401, description = "Signed request failed verification"),
+        (status = 500, description = "Internal server error")
+    ),
+    security((), ("device_auth" = []))
+)]
+pub async fn get_stats(
+    Extension(repo): Extension<Arc<dyn StatsRepo>>,
+    noise: Option<Extension<Arc<AggregateNoise>>>,
+    Query(query): Query<StatsQuery>,
+    auth: Option<AuthenticatedDevice>,
+) -> impl IntoResponse {
+    let days = match resolve_stats_days(query.days) {
+        Ok(d) => d,
+        Err(msg) => return bad_request(&msg),
+    };
+    let noise = noise
+        .map(|Extension(noise)| noise)
+        .filter(|noise| noise.is_enabled());
+    let noise = match (noise, auth) {
+        (Some(noise), Some(auth)) if noise.is_admin(auth.account_id) => None,
+        (noise, _) => noise,
+    };
+    match load_instance_stats(repo.as_ref(), days).await {
+        Ok(stats) => match noise {
+            Some(noise) => Json(add_noise(stats, &noise)).into_response(),
+            None => Json(stats).into_response(),
+        },
+        Err(e) => {
+            tracing::error!("Failed to load instance stats: {e}");
+            internal_error()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_stats_days_defaults_and_bounds() {
+        assert_eq!(resolve_stats_days(None), Ok(DEFAULT_STATS_DAYS));
+        assert_eq!(resolve_stats_days(Some(1)), Ok(1));
+        assert_eq!(resolve_stats_days(Some(MAX_STATS_DAYS)), Ok(MAX_STATS_DAYS));
+        assert!(resolve_stats_days(Some(0)).is_err());
+        assert!(resolve_stats_days(Some(MAX_STATS_DAYS + 1)).is_err());
+    }
+
+    #[test]
+    fn daily_stats_formats_date() {
+        let record = DailyStatsRecord {
+            day: chrono::NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+            signups: 3,
+            active_devices: 4,
+            ballots_cast: 5,
+            voters: 2,
+            endorsements_by_topic: vec![("trust".to_string(), 7)],
+        };
+        let stats = DailyStats::from(record);
+        assert_eq!(stats.date, "2025-01-02");
+        assert_eq!(stats.endorsements_by_topic[0].topic, "trust");
+    }
+}
@@ -0,0 +1,10 @@
+//! Instance-level statistics for public dashboards.
+//!
+//! A nightly job rolls raw activity (signups, device usage, endorsements,
+//! ballots) into per-day summary tables, which are served read-only via
+//! `GET /api/v1/stats` and the GraphQL `instanceStats` query. Only aggregate
+//! counts are stored; nothing here identifies an account.
+
+pub mod http;
+pub mod repo;
+pub mod worker;
@@ -0,0 +1,259 @@
+//! Rollup persistence for instance-level stats
+
+use async_trait::async_trait;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use sqlx::PgPool;
+
+use crate::db::{RepoError, RepoErrorKind};
+
+/// How far back `active_devices` looks from the end of the rollup day.
+const ACTIVE_DEVICE_WINDOW_DAYS: i32 = 30;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// One day of rolled-up instance stats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyStatsRecord {
+    pub day: NaiveDate,
+    pub signups: i64,
+    pub active_devices: i64,
+    pub ballots_cast: i64,
+    pub voters: i64,
+    /// `(topic, count)` pairs, sorted by topic.
+    pub endorsements_by_topic: Vec<(String, i64)>,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum StatsRepoError {
+    #[error("day out of range")]
+    DayOutOfRange,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for StatsRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::DayOutOfRange => RepoErrorKind::Rejected,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL row types ─────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct RollupRow {
+    day: NaiveDate,
+    signups: i64,
+    active_devices: i64,
+    ballots_cast: i64,
+    voters: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct TopicRow {
+    day: NaiveDate,
+    topic: String,
+    endorsements: i64,
+}
+
+/// UTC `[start, end)` bounds of a calendar day.
+fn day_bounds(day: NaiveDate) -> Result<(DateTime<Utc>, DateTime<Utc>), StatsRepoError> {
+    let next = day
+        .checked_add_days(Days::new(1))
+        .ok_or(StatsRepoError::DayOutOfRange)?;
+    let start = day
+        .and_hms_opt(0, 0, 0)
+        .ok_or(StatsRepoError::DayOutOfRange)?
+        .and_utc();
+    let end = next
+        .and_hms_opt(0, 0, 0)
+        .ok_or(StatsRepoError::DayOutOfRange)?
+        .and_utc();
+    Ok((start, end))
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// Compute (or recompute) the rollup rows for a single UTC day.
+///
+/// Runs in one transaction so readers never see a day's summary row without
+/// its per-topic rows.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn compute_daily_rollup(pool: &PgPool, day: NaiveDate) -> Result<(), StatsRepoError> {
+    let (start, end) = day_bounds(day)?;
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r"
+        INSERT INTO stats__daily_rollups (day, signups, active_devices, ballots_cast, voters, computed_at)
+        SELECT
+            $1,
+            (SELECT COUNT(*) FROM accounts WHERE created_at >= $2 AND created_at < $3),
+            (SELECT COUNT(*) FROM device_keys
+              WHERE revoked_at IS NULL
+                AND last_used_at >= $3 - make_interval(days => $4)
+                AND last_used_at < $3),
+            (SELECT COUNT(*) FROM (
+                SELECT DISTINCT poll_id, user_id FROM rooms__votes
+                WHERE updated_at >= $2 AND updated_at < $3
+            ) b),
+            (SELECT COUNT(DISTINCT user_id) FROM rooms__votes
+              WHERE updated_at >= $2 AND updated_at < $3),
+            now()
+        ON CONFLICT (day) DO UPDATE SET
+            signups = EXCLUDED.signups,
+            active_devices = EXCLUDED.active_devices,
+            ballots_cast = EXCLUDED.ballots_cast,
+            voters = EXCLUDED.voters,
+            computed_at = EXCLUDED.computed_at
+        ",
+    )
+    .bind(day)
+    .bind(start)
+    .bind(end)
+    .bind(ACTIVE_DEVICE_WINDOW_DAYS)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM stats__daily_topic_endorsements WHERE day = $1")
+        .bind(day)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r"
+        INSERT INTO stats__daily_topic_endorsements (day, topic, endorsements)
+        SELECT $1, topic, COUNT(*)
+        FROM reputation__endorsements
+        WHERE created_at >= $2 AND created_at < $3 AND visibility <> 'private'
+          AND NOT aggregates_paused
+        GROUP BY topic
+        ",
+    )
+    .bind(day)
+    .bind(start)
+    .bind(end)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Most recent day with a computed rollup, if any.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn latest_rollup_day<'e, E>(executor: E) -> Result<Option<NaiveDate>, StatsRepoError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let day: Option<NaiveDate> = sqlx::query_scalar("SELECT MAX(day) FROM stats__daily_rollups")
+        .fetch_one(executor)
+        .await?;
+    Ok(day)
+}
+
+/// List rollups for days on or after `since`, newest first.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_daily_stats(
+    pool: &PgPool,
+    since: NaiveDate,
+) -> Result<Vec<DailyStatsRecord>, StatsRepoError> {
+    let rollups = sqlx::query_as::<_, RollupRow>(
+        r"
+        SELECT day, signups, active_devices, ballots_cast, voters
+        FROM stats__daily_rollups
+        WHERE day >= $1
+        ORDER BY day DESC
+        ",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    let topics = sqlx::query_as::<_, TopicRow>(
+        r"
+        SELECT day, topic, endorsements
+        FROM stats__daily_topic_endorsements
+        WHERE day >= $1
+        ORDER BY day, topic
+        ",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rollups
+        .into_iter()
+        .map(|r| DailyStatsRecord {
+            day: r.day,
+            signups: r.signups,
+            active_devices: r.active_devices,
+            ballots_cast: r.ballots_cast,
+            voters: r.voters,
+            endorsements_by_topic: topics
+                .iter()
+                .filter(|t| t.day == r.day)
+                .map(|t| (t.topic.clone(), t.endorsements))
+                .collect(),
+        })
+        .collect())
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// Read access to rolled-up stats for HTTP and GraphQL handlers.
+#[async_trait]
+pub trait StatsRepo: Send + Sync {
+    async fn list_daily_stats(
+        &self,
+        since: NaiveDate,
+    ) -> Result<Vec<DailyStatsRecord>, StatsRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`StatsRepo`].
+pub struct PgStatsRepo {
+    pool: PgPool,
+}
+
+impl PgStatsRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StatsRepo for PgStatsRepo {
+    async fn list_daily_stats(
+        &self,
+        since: NaiveDate,
+    ) -> Result<Vec<DailyStatsRecord>, StatsRepoError> {
+        list_daily_stats(&self.pool, since).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_bounds_span_one_utc_day() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 9).unwrap();
+        let (start, end) = day_bounds(day).unwrap();
+        assert_eq!(start.to_rfc3339(), "2025-03-09T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2025-03-10T00:00:00+00:00");
+    }
+}
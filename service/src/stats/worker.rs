@@ -0,0 +1,101 @@
+//! Nightly rollup job — fills `stats__daily_rollups` for each completed UTC day.
+
+use std::time::Duration;
+
+use chrono::{Days, NaiveDate, Utc};
+use sqlx::PgPool;
+
+use super::repo::{compute_daily_rollup, latest_rollup_day, StatsRepoError};
+
+/// Days of history computed when the rollup table is empty (or has fallen
+/// further behind than this).
+const BACKFILL_DAYS: u64 = 30;
+
+/// How often the job checks for a newly completed day. Rollups only run once
+/// per day; the short interval just bounds how late after midnight that is.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Completed days that still need a rollup, oldest first.
+///
+/// Today is never included: its numbers are still moving.
+#[must_use]
+pub fn pending_days(latest: Option<NaiveDate>, today: NaiveDate) -> Vec<NaiveDate> {
+    let floor = today
+        .checked_sub_days(Days::new(BACKFILL_DAYS))
+        .unwrap_or(today);
+    let mut day = latest
+        .and_then(|d| d.checked_add_days(Days::new(1)))
+        .map_or(floor, |d| d.max(floor));
+
+    let mut days = Vec::new();
+    while day < today {
+        days.push(day);
+        match day.checked_add_days(Days::new(1)) {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+    days
+}
+
+/// Compute rollups for every pending day. Returns the number of days computed.
+///
+/// # Errors
+///
+/// Returns the first repo error; days before it remain committed.
+pub async fn run_pending_rollups(pool: &PgPool, today: NaiveDate) -> Result<usize, StatsRepoError> {
+    let days = pending_days(latest_rollup_day(pool).await?, today);
+    for day in &days {
+        compute_daily_rollup(pool, *day).await?;
+    }
+    Ok(days.len())
+}
+
+/// Spawn the background rollup job.
+pub fn spawn_rollup_job(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            match run_pending_rollups(&pool, Utc::now().date_naive()).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!(days = n, "Computed stats rollups"),
+                Err(e) => tracing::warn!("Stats rollup failed: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn empty_table_backfills_window_excluding_today() {
+        let days = pending_days(None, d(2025, 6, 30));
+        assert_eq!(days.len(), 30);
+        assert_eq!(days.first(), Some(&d(2025, 5, 31)));
+        assert_eq!(days.last(), Some(&d(2025, 6, 29)));
+    }
+
+    #[test]
+    fn up_to_date_table_has_nothing_pending() {
+        assert!(pending_days(Some(d(2025, 6, 29)), d(2025, 6, 30)).is_empty());
+    }
+
+    #[test]
+    fn resumes_after_latest_day() {
+        let days = pending_days(Some(d(2025, 6, 27)), d(2025, 6, 30));
+        assert_eq!(days, vec![d(2025, 6, 28), d(2025, 6, 29)]);
+    }
+
+    #[test]
+    fn stale_table_is_capped_at_backfill_window() {
+        let days = pending_days(Some(d(2024, 1, 1)), d(2025, 6, 30));
+        assert_eq!(days.len(), 30);
+    }
+}
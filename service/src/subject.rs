@@ -0,0 +1,299 @@
+//! Canonical references to the things endorsements and other records are
+//! about.
+//!
+//! A subject is written `<kind>:<id>` with exactly one spelling per subject,
+//! so the same thing can never be counted under two keys:
+//!
+//! | Kind | Example | Id |
+//! |---|---|---|
+//! | `account` | `account:5f0c…` | lowercase hyphenated UUID |
+//! | `member` | `member:A000360` | Bioguide ID: one uppercase letter, six digits |
+//! | `bill` | `bill:hr-1234-118` | `{type}-{number}-{congress}`, see [`BillType`] |
+//!
+//! [`SubjectRef::from_str`] rejects anything else, including other spellings
+//! of a valid id (an uppercase UUID, `HR-1234-118`, a leading zero), rather
+//! than normalizing it. Handlers parse subjects at the edge and pass the typed
+//! value inward.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+/// Highest Congress accepted in a bill reference.
+pub const MAX_CONGRESS: u16 = 200;
+
+/// Highest bill number accepted in a bill reference.
+pub const MAX_BILL_NUMBER: u32 = 99_999;
+
+/// Why a subject reference was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubjectRefError {
+    #[error("subject must be written <kind>:<id>")]
+    MissingKind,
+    #[error("unknown subject kind {0:?}; expected account, member, or bill")]
+    UnknownKind(String),
+    #[error("account subject must be a lowercase hyphenated UUID")]
+    InvalidAccount,
+    #[error("member subject must be a Bioguide ID like A000360")]
+    InvalidMember,
+    #[error("bill subject must look like hr-1234-118")]
+    InvalidBill,
+}
+
+/// A Bioguide ID such as `A000360`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemberId(String);
+
+impl MemberId {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for MemberId {
+    type Err = SubjectRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        if bytes.len() == 7
+            && bytes[0].is_ascii_uppercase()
+            && bytes[1..].iter().all(u8::is_ascii_digit)
+        {
+            Ok(Self(s.to_string()))
+        } else {
+            Err(SubjectRefError::InvalidMember)
+        }
+    }
+}
+
+/// Bill and resolution types, by their congress.gov abbreviation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BillType {
+    Hr,
+    S,
+    Hjres,
+    Sjres,
+    Hconres,
+    Sconres,
+    Hres,
+    Sres,
+}
+
+impl BillType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hr => "hr",
+            Self::S => "s",
+            Self::Hjres => "hjres",
+            Self::Sjres => "sjres",
+            Self::Hconres => "hconres",
+            Self::Sconres => "sconres",
+            Self::Hres => "hres",
+            Self::Sres => "sres",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "hr" => Self::Hr,
+            "s" => Self::S,
+            "hjres" => Self::Hjres,
+            "sjres" => Self::Sjres,
+            "hconres" => Self::Hconres,
+            "sconres" => Self::Sconres,
+            "hres" => Self::Hres,
+            "sres" => Self::Sres,
+            _ => return None,
+        })
+    }
+}
+
+/// A bill in a given Congress, e.g. `hr-1234-118`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BillId {
+    pub bill_type: BillType,
+    pub number: u32,
+    pub congress: u16,
+}
+
+/// Parse a positive decimal without sign or leading zeros.
+fn parse_canonical_number<T: FromStr>(s: &str) -> Option<T> {
+    if s.is_empty() || s.starts_with('0') || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl FromStr for BillId {
+    type Err = SubjectRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        let (Some(bill_type), Some(number), Some(congress), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(SubjectRefError::InvalidBill);
+        };
+        let bill_type = BillType::parse(bill_type).ok_or(SubjectRefError::InvalidBill)?;
+        let number = parse_canonical_number::<u32>(number)
+            .filter(|n| *n <= MAX_BILL_NUMBER)
+            .ok_or(SubjectRefError::InvalidBill)?;
+        let congress = parse_canonical_number::<u16>(congress)
+            .filter(|c| *c <= MAX_CONGRESS)
+            .ok_or(SubjectRefError::InvalidBill)?;
+        Ok(Self {
+            bill_type,
+            number,
+            congress,
+        })
+    }
+}
+
+impl fmt::Display for BillId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{}",
+            self.bill_type.as_str(),
+            self.number,
+            self.congress
+        )
+    }
+}
+
+/// What a record is about, in its canonical `<kind>:<id>` form.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SubjectRef {
+    Account(Uuid),
+    Member(MemberId),
+    Bill(BillId),
+}
+
+impl SubjectRef {
+    /// The `<kind>` prefix.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Account(_) => "account",
+            Self::Member(_) => "member",
+            Self::Bill(_) => "bill",
+        }
+    }
+
+    /// The account id, for account subjects.
+    #[must_use]
+    pub const fn account_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Account(id) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for SubjectRef {
+    type Err = SubjectRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s.split_once(':').ok_or(SubjectRefError::MissingKind)?;
+        match kind {
+            "account" => {
+                let uuid = Uuid::parse_str(id).map_err(|_| SubjectRefError::InvalidAccount)?;
+                // Uuid::parse_str also takes uppercase, braced, and URN forms
+                if uuid.hyphenated().to_string() != id {
+                    return Err(SubjectRefError::InvalidAccount);
+                }
+                Ok(Self::Account(uuid))
+            }
+            "member" => id.parse().map(Self::Member),
+            "bill" => id.parse().map(Self::Bill),
+            other => Err(SubjectRefError::UnknownKind(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SubjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Account(id) => write!(f, "account:{}", id.hyphenated()),
+            Self::Member(id) => write!(f, "member:{}", id.as_str()),
+            Self::Bill(id) => write!(f, "bill:{id}"),
+        }
+    }
+}
+
+impl Serialize for SubjectRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SubjectRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_forms_round_trip() {
+        for s in [
+            "account:5f0c2a3e-8d1b-4c7a-9e2f-1a2b3c4d5e6f",
+            "member:A000360",
+            "bill:hr-1234-118",
+            "bill:sconres-7-1",
+        ] {
+            let subject: SubjectRef = s.parse().unwrap();
+            assert_eq!(subject.to_string(), s);
+            let json = serde_json::to_string(&subject).unwrap();
+            assert_eq!(serde_json::from_str::<SubjectRef>(&json).unwrap(), subject);
+        }
+        let id = Uuid::new_v4();
+        let subject: SubjectRef = format!("account:{id}").parse().unwrap();
+        assert_eq!(subject.account_id(), Some(id));
+        assert_eq!(subject.kind(), "account");
+    }
+
+    #[test]
+    fn other_spellings_are_rejected() {
+        let cases = [
+            (
+                "5f0c2a3e-8d1b-4c7a-9e2f-1a2b3c4d5e6f",
+                SubjectRefError::MissingKind,
+            ),
+            (
+                "account:5F0C2A3E-8D1B-4C7A-9E2F-1A2B3C4D5E6F",
+                SubjectRefError::InvalidAccount,
+            ),
+            (
+                "account:5f0c2a3e8d1b4c7a9e2f1a2b3c4d5e6f",
+                SubjectRefError::InvalidAccount,
+            ),
+            (
+                "Account:5f0c2a3e-8d1b-4c7a-9e2f-1a2b3c4d5e6f",
+                SubjectRefError::UnknownKind("Account".to_string()),
+            ),
+            ("member:a000360", SubjectRefError::InvalidMember),
+            ("member:A00036", SubjectRefError::InvalidMember),
+            ("bill:HR-1234-118", SubjectRefError::InvalidBill),
+            ("bill:hr-01234-118", SubjectRefError::InvalidBill),
+            ("bill:hr-1234", SubjectRefError::InvalidBill),
+            ("bill:hr-1234-118-x", SubjectRefError::InvalidBill),
+            ("bill:hr-0-118", SubjectRefError::InvalidBill),
+            ("bill:hr-1234-201", SubjectRefError::InvalidBill),
+            ("bill:xr-1234-118", SubjectRefError::InvalidBill),
+            ("bill:hr-+1-118", SubjectRefError::InvalidBill),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<SubjectRef>(), Err(expected), "{input}");
+        }
+        assert!(serde_json::from_str::<SubjectRef>(r#""member:nope""#).is_err());
+    }
+}
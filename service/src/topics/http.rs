@@ -0,0 +1,296 @@
+//! HTTP handlers for topic subscriptions and the topic feed.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::repo::{FeedItemRecord, TopicMovementRecord, TopicRepo, TopicRepoError};
+use super::{validate_topic, MAX_SUBSCRIPTIONS_PER_ACCOUNT};
+use crate::http::{bad_request, conflict, internal_error, not_found, Path};
+use crate::identity::http::auth::AuthenticatedDevice;
+use crate::pagination::{finish_page, PageQuery};
+use crate::privacy::AggregateNoise;
+
+// ─── Response types ────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopicSubscriptionResponse {
+    pub topic: String,
+    pub subscribed_at: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopicSubscriptionsResponse {
+    /// Alphabetical by topic
+    pub subscriptions: Vec<TopicSubscriptionResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopicFeedItemResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub topic: String,
+    #[schema(value_type = String, format = "uuid")]
+    pub endorsement_id: Uuid,
+    #[schema(value_type = String, format = "uuid")]
+    pub subject_id: Uuid,
+    pub subject_username: String,
+    /// Verifier account that issued the endorsement, if any
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub issuer_id: Option<Uuid>,
+    /// Whether the endorsement has been revoked since it was issued
+    pub revoked: bool,
+    pub created_at: String,
+}
+
+impl From<FeedItemRecord> for TopicFeedItemResponse {
+    fn from(r: FeedItemRecord) -> Self {
+        Self {
+            id: r.id,
+            topic: r.topic,
+            endorsement_id: r.endorsement_id,
+            subject_id: r.subject_id,
+            subject_username: r.subject_username,
+            issuer_id: r.issuer_id,
+            revoked: r.revoked,
+            created_at: r.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Endorsement counts for one subscribed topic.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopicMovementResponse {
+    pub topic: String,
+    /// Active endorsements now
+    pub active: i64,
+    /// Endorsements issued in the last 7 days
+    pub added: i64,
+    /// Endorsements revoked in the last 7 days
+    pub revoked: i64,
+}
+
+impl From<TopicMovementRecord> for TopicMovementResponse {
+    fn from(r: TopicMovementRecord) -> Self {
+        Self {
+            topic: r.topic,
+            active: r.active,
+            added: r.added,
+            revoked: r.revoked,
+        }
+    }
+}
+
+impl TopicMovementResponse {
+    /// The public view: each count passed through `noise`.
+    fn with_noise(self, noise: &AggregateNoise) -> Self {
+        let label = |field: &str| format!("movement:{}:{field}", self.topic);
+        Self {
+            active: noise.count(&label("active"), self.active),
+            added: noise.count(&label("added"), self.added),
+            revoked: noise.count(&label("revoked"), self.revoked),
+            topic: self.topic.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopicFeedResponse {
+    /// Endorsements in subscribed topics, newest first
+    pub items: Vec<TopicFeedItemResponse>,
+    /// One entry per subscribed topic, alphabetical
+    pub movements: Vec<TopicMovementResponse>,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+    /// Snapshot instant shared by every page of this walk
+    pub as_of: String,
+}
+
+// ─── Router ────────────────────────────────────────────────────────────────
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/topics/subscriptions", get(list_subscriptions))
+        .route("/topics/feed", get(get_feed))
+        .route(
+            "/topics/{topic}/subscribe",
+            post(subscribe).delete(unsubscribe),
+        )
+}
+
+// ─── Handlers ──────────────────────────────────────────────────────────────
+
+/// Subscribe the caller to an endorsement topic. Idempotent.
+///
+/// New endorsements in the topic appear in `GET /topics/feed` from now on.
+#[utoipa::path(
+    post,
+    path = "/topics/{topic}/subscribe",
+    tag = "Topics",
+    params(("topic" = String, Path, description = "Endorsement topic")),
+    responses(
+        (status = 200, description = "Subscribed", body = TopicSubscriptionResponse),
+        (status = 400, description = "Invalid or unsubscribable topic"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Subscription limit reached"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn subscribe(
+    Extension(repo): Extension<Arc<dyn TopicRepo>>,
+    Path(topic): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    if let Err(msg) = validate_topic(&topic) {
+        return bad_request(msg);
+    }
+    match repo.subscribe(auth.account_id, &topic).await {
+        Ok(subscribed_at) => (
+            StatusCode::OK,
+            Json(TopicSubscriptionResponse {
+                topic,
+                subscribed_at: subscribed_at.to_rfc3339(),
+            }),
+        )
+            .into_response(),
+        Err(ref e) => topic_repo_error_response(e),
+    }
+}
+
+/// Unsubscribe the caller from a topic. Items already in the feed stay.
+#[utoipa::path(
+    delete,
+    path = "/topics/{topic}/subscribe",
+    tag = "Topics",
+    params(("topic" = String, Path, description = "Endorsement topic")),
+    responses(
+        (status = 204, description = "Unsubscribed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not subscribed to this topic"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn unsubscribe(
+    Extension(repo): Extension<Arc<dyn TopicRepo>>,
+    Path(topic): Path<String>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.unsubscribe(auth.account_id, &topic).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(ref e) => topic_repo_error_response(e),
+    }
+}
+
+/// Topics the caller follows.
+#[utoipa::path(
+    get,
+    path = "/topics/subscriptions",
+    tag = "Topics",
+    responses(
+        (status = 200, description = "Subscribed topics", body = TopicSubscriptionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn list_subscriptions(
+    Extension(repo): Extension<Arc<dyn TopicRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match repo.list_subscriptions(auth.account_id).await {
+        Ok(rows) => (
+            StatusCode::OK,
+            Json(TopicSubscriptionsResponse {
+                subscriptions: rows
+                    .into_iter()
+                    .map(|(topic, at)| TopicSubscriptionResponse {
+                        topic,
+                        subscribed_at: at.to_rfc3339(),
+                    })
+                    .collect(),
+            }),
+        )
+            .into_response(),
+        Err(ref e) => topic_repo_error_response(e),
+    }
+}
+
+/// Recent endorsements in the caller's subscribed topics, with keyset
+/// pagination, plus per-topic movement counts.
+///
+/// Movement counts carry differential-privacy noise when `privacy.enabled`
+/// is set, unless the caller is an admin.
+#[utoipa::path(
+    get,
+    path = "/topics/feed",
+    tag = "Topics",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "One page of the topic feed", body = TopicFeedResponse),
+        (status = 400, description = "Invalid pagination parameters"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn get_feed(
+    Extension(repo): Extension<Arc<dyn TopicRepo>>,
+    noise: Option<Extension<Arc<AggregateNoise>>>,
+    Query(query): Query<PageQuery>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let page = match query.resolve(Utc::now()) {
+        Ok(page) => page,
+        Err(msg) => return bad_request(&msg),
+    };
+    let rows = match repo.list_feed_page(auth.account_id, &page).await {
+        Ok(rows) => rows,
+        Err(ref e) => return topic_repo_error_response(e),
+    };
+    let movements = match repo.list_movements(auth.account_id).await {
+        Ok(rows) => rows,
+        Err(ref e) => return topic_repo_error_response(e),
+    };
+    let noise = noise
+        .map(|Extension(noise)| noise)
+        .filter(|noise| noise.is_enabled() && !noise.is_admin(auth.account_id));
+    let (rows, next_cursor) = finish_page(&page, rows, |r| (r.created_at, r.id));
+    (
+        StatusCode::OK,
+        Json(TopicFeedResponse {
+            items: rows.into_iter().map(TopicFeedItemResponse::from).collect(),
+            movements: movements
+                .into_iter()
+                .map(TopicMovementResponse::from)
+                .map(|m| match noise {
+                    Some(ref noise) => m.with_noise(noise),
+                    None => m,
+                })
+                .collect(),
+            next_cursor,
+            as_of: page.as_of.to_rfc3339(),
+        }),
+    )
+        .into_response()
+}
+
+fn topic_repo_error_response(e: &TopicRepoError) -> axum::response::Response {
+    match e {
+        TopicRepoError::SubscriptionLimitReached => conflict(&format!(
+            "Cannot follow more than {MAX_SUBSCRIPTIONS_PER_ACCOUNT} topics"
+        )),
+        TopicRepoError::NotFound => not_found("Not subscribed to this topic"),
+        TopicRepoError::Database(inner) => {
+            tracing::error!("Topic repo database error: {inner}");
+            internal_error()
+        }
+    }
+}
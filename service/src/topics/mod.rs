@@ -0,0 +1,64 @@
+//! Endorsement topic subscriptions and feeds.
+//!
+//! Accounts subscribe to endorsement topics (e.g. `identity_verified`) with
+//! `POST /topics/{topic}/subscribe`. The [`projector::TopicFeedProjector`]
+//! fans each new public endorsement out to the feeds of its topic's
+//! subscribers, and `GET /topics/feed` pages through that feed alongside
+//! per-topic movement counts — a "what's changing in areas I care about" view.
+//!
+//! The `trust` topic is excluded: trust edges are only visible to the two
+//! accounts involved.
+
+pub mod http;
+pub mod projector;
+pub mod repo;
+
+/// Topic that cannot be subscribed to; see the module docs.
+pub const TRUST_TOPIC: &str = "trust";
+
+/// Longest topic name accepted.
+pub const MAX_TOPIC_LEN: usize = 64;
+
+/// Most topics one account may follow.
+pub const MAX_SUBSCRIPTIONS_PER_ACCOUNT: i64 = 50;
+
+/// Window for the movement counts in the feed.
+pub const MOVEMENT_WINDOW_DAYS: i32 = 7;
+
+/// Check that `topic` is a subscribable topic name: 1–64 characters of
+/// `[a-z0-9_]`, and not `trust`.
+///
+/// # Errors
+///
+/// Returns a client-facing message describing the problem.
+pub fn validate_topic(topic: &str) -> Result<(), &'static str> {
+    if topic.is_empty() || topic.len() > MAX_TOPIC_LEN {
+        return Err("topic must be 1-64 characters");
+    }
+    if !topic
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+    {
+        return Err("topic may only contain lowercase letters, digits, and underscores");
+    }
+    if topic == TRUST_TOPIC {
+        return Err("trust endorsements are not published to topic feeds");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_topic_rules() {
+        assert!(validate_topic("identity_verified").is_ok());
+        assert!(validate_topic("policy_2026").is_ok());
+        assert!(validate_topic("").is_err());
+        assert!(validate_topic(&"a".repeat(MAX_TOPIC_LEN + 1)).is_err());
+        assert!(validate_topic("Identity").is_err());
+        assert!(validate_topic("a/b").is_err());
+        assert!(validate_topic("trust").is_err());
+    }
+}
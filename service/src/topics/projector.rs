@@ -0,0 +1,122 @@
+//! Fans endorsement events out to topic subscribers' feeds.
+//!
+//! [`TopicFeedProjector`] wraps the next [`EventPublisher`] in the chain the
+//! same way [`crate::notifications::projector::NotificationProjector`] does:
+//! every event is forwarded unchanged, and endorsement events are also queued
+//! for a background task that writes one feed row per subscriber. A full
+//! queue drops the fan-out with a warning rather than blocking the request.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use super::repo::{NewFeedItem, TopicRepo};
+use super::TRUST_TOPIC;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::reputation::EndorsementVisibility;
+
+/// Fan-outs buffered between the request path and the writer task.
+pub const PROJECTOR_BUFFER: usize = 1024;
+
+/// The feed item an event produces, if any. Only public, non-trust
+/// endorsements do.
+#[must_use]
+pub fn feed_item_for(event: &DomainEvent) -> Option<NewFeedItem> {
+    match event {
+        DomainEvent::EndorsementCreated {
+            endorsement_id,
+            subject_id,
+            topic,
+            issuer_id,
+            visibility: EndorsementVisibility::Public,
+        } if topic != TRUST_TOPIC => Some(NewFeedItem {
+            topic: topic.clone(),
+            endorsement_id: *endorsement_id,
+            subject_id: *subject_id,
+            issuer_id: *issuer_id,
+        }),
+        _ => None,
+    }
+}
+
+/// [`EventPublisher`] that also writes topic feed items.
+pub struct TopicFeedProjector {
+    inner: Arc<dyn EventPublisher>,
+    tx: mpsc::Sender<NewFeedItem>,
+}
+
+impl TopicFeedProjector {
+    /// Wrap `inner` and spawn the task that fans items out via `repo`.
+    #[must_use]
+    pub fn spawn(inner: Arc<dyn EventPublisher>, repo: Arc<dyn TopicRepo>) -> Self {
+        let (tx, mut rx) = mpsc::channel::<NewFeedItem>(PROJECTOR_BUFFER);
+        tokio::spawn(async move {
+            while let Some(item) = rx.recv().await {
+                match repo.fan_out(&item).await {
+                    Ok(delivered) => {
+                        tracing::debug!(topic = %item.topic, delivered, "Fanned out topic feed item");
+                    }
+                    Err(e) => {
+                        tracing::warn!(topic = %item.topic, error = %e, "Failed to fan out topic feed item");
+                    }
+                }
+            }
+        });
+        Self { inner, tx }
+    }
+}
+
+impl EventPublisher for TopicFeedProjector {
+    fn publish(&self, event: DomainEvent) {
+        if let Some(item) = feed_item_for(&event) {
+            if let Err(e) = self.tx.try_send(item) {
+                tracing::warn!(error = %e, "Topic feed buffer full or closed; dropping fan-out");
+            }
+        }
+        self.inner.publish(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn created(topic: &str, visibility: EndorsementVisibility) -> DomainEvent {
+        DomainEvent::EndorsementCreated {
+            endorsement_id: Uuid::new_v4(),
+            subject_id: Uuid::new_v4(),
+            topic: topic.to_string(),
+            issuer_id: Some(Uuid::new_v4()),
+            visibility,
+        }
+    }
+
+    #[test]
+    fn endorsements_become_feed_items() {
+        let item =
+            feed_item_for(&created("identity_verified", EndorsementVisibility::Public)).unwrap();
+        assert_eq!(item.topic, "identity_verified");
+    }
+
+    #[test]
+    fn trust_hidden_and_unrelated_events_are_skipped() {
+        assert!(feed_item_for(&created("trust", EndorsementVisibility::Public)).is_none());
+        assert!(feed_item_for(&created(
+            "identity_verified",
+            EndorsementVisibility::SubjectOnly
+        ))
+        .is_none());
+        assert!(feed_item_for(&created(
+            "identity_verified",
+            EndorsementVisibility::Private
+        ))
+        .is_none());
+        assert!(feed_item_for(&DomainEvent::EndorsementQueued {
+            endorser_id: Uuid::new_v4(),
+            subject_id: Uuid::new_v4(),
+            weight: 1.0,
+        })
+        .is_none());
+    }
+}
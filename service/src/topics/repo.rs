@@ -0,0 +1,347 @@
+//! Persistence for topic subscriptions and feeds
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{MAX_SUBSCRIPTIONS_PER_ACCOUNT, MOVEMENT_WINDOW_DAYS};
+use crate::db::{instrument, Instrumented, RepoError, RepoErrorKind};
+use crate::pagination::KeysetPage;
+
+// ─── Record types ──────────────────────────────────────────────────────────
+
+/// One endorsement in a subscriber's feed.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FeedItemRecord {
+    pub id: Uuid,
+    pub topic: String,
+    pub endorsement_id: Uuid,
+    pub subject_id: Uuid,
+    pub subject_username: String,
+    pub issuer_id: Option<Uuid>,
+    /// Whether the endorsement has since been revoked.
+    pub revoked: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Endorsement counts for one subscribed topic.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct TopicMovementRecord {
+    pub topic: String,
+    /// Active endorsements now.
+    pub active: i64,
+    /// Endorsements created in the last [`MOVEMENT_WINDOW_DAYS`] days.
+    pub added: i64,
+    /// Endorsements revoked in the last [`MOVEMENT_WINDOW_DAYS`] days.
+    pub revoked: i64,
+}
+
+/// A new endorsement to fan out to its topic's subscribers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewFeedItem {
+    pub topic: String,
+    pub endorsement_id: Uuid,
+    pub subject_id: Uuid,
+    pub issuer_id: Option<Uuid>,
+}
+
+// ─── Error type ────────────────────────────────────────────────────────────
+
+#[derive(Debug, thiserror::Error)]
+pub enum TopicRepoError {
+    #[error("subscription limit reached")]
+    SubscriptionLimitReached,
+    #[error("subscription not found")]
+    NotFound,
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl RepoError for TopicRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::SubscriptionLimitReached => RepoErrorKind::LimitReached,
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
+// ─── SQL operations ────────────────────────────────────────────────────────
+
+/// Subscribe `account_id` to `topic`. Idempotent.
+///
+/// # Errors
+///
+/// Returns `SubscriptionLimitReached` if the account already follows
+/// [`MAX_SUBSCRIPTIONS_PER_ACCOUNT`] other topics, `Database` on failure.
+pub async fn subscribe(
+    pool: &PgPool,
+    account_id: Uuid,
+    topic: &str,
+) -> Result<DateTime<Utc>, TopicRepoError> {
+    let mut tx = pool.begin().await?;
+    // Serialize concurrent subscribes per account so the limit holds.
+    sqlx::query("SELECT 1 FROM accounts WHERE id = $1 FOR UPDATE")
+        .bind(account_id)
+        .execute(Instrumented::new(&mut *tx, "topics.subscribe"))
+        .await?;
+    let existing: Option<DateTime<Utc>> = sqlx::query_scalar(
+        "SELECT created_at FROM topic_subscriptions WHERE account_id = $1 AND topic = $2",
+    )
+    .bind(account_id)
+    .bind(topic)
+    .fetch_optional(Instrumented::new(&mut *tx, "topics.subscribe"))
+    .await?;
+    if let Some(created_at) = existing {
+        return Ok(created_at);
+    }
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM topic_subscriptions WHERE account_id = $1")
+            .bind(account_id)
+            .fetch_one(Instrumented::new(&mut *tx, "topics.subscribe"))
+            .await?;
+    if count >= MAX_SUBSCRIPTIONS_PER_ACCOUNT {
+        return Err(TopicRepoError::SubscriptionLimitReached);
+    }
+    let created_at = sqlx::query_scalar(
+        "INSERT INTO topic_subscriptions (account_id, topic) VALUES ($1, $2) RETURNING created_at",
+    )
+    .bind(account_id)
+    .bind(topic)
+    .fetch_one(Instrumented::new(&mut *tx, "topics.subscribe"))
+    .await?;
+    tx.commit().await?;
+    Ok(created_at)
+}
+
+/// Unsubscribe `account_id` from `topic`. Items already in the feed stay.
+///
+/// # Errors
+///
+/// Returns `NotFound` if the account was not subscribed, `Database` on failure.
+pub async fn unsubscribe(
+    pool: &PgPool,
+    account_id: Uuid,
+    topic: &str,
+) -> Result<(), TopicRepoError> {
+    let result =
+        sqlx::query("DELETE FROM topic_subscriptions WHERE account_id = $1 AND topic = $2")
+            .bind(account_id)
+            .bind(topic)
+            .execute(instrument(pool, "topics.unsubscribe"))
+            .await?;
+    if result.rows_affected() == 0 {
+        return Err(TopicRepoError::NotFound);
+    }
+    Ok(())
+}
+
+/// Topics `account_id` follows, alphabetically.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_subscriptions(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<(String, DateTime<Utc>)>, TopicRepoError> {
+    let rows = sqlx::query_as(
+        "SELECT topic, created_at FROM topic_subscriptions WHERE account_id = $1 ORDER BY topic",
+    )
+    .bind(account_id)
+    .fetch_all(instrument(pool, "topics.list_subscriptions"))
+    .await?;
+    Ok(rows)
+}
+
+/// Copy `item` into the feed of every subscriber of its topic.
+///
+/// Returns the number of feeds written. Re-delivering the same endorsement is
+/// a no-op.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn fan_out(pool: &PgPool, item: &NewFeedItem) -> Result<u64, TopicRepoError> {
+    let result = sqlx::query(
+        r"
+        INSERT INTO topic_feed_items (account_id, topic, endorsement_id, subject_id, issuer_id)
+        SELECT account_id, topic, $2, $3, $4
+        FROM topic_subscriptions
+        WHERE topic = $1
+        ON CONFLICT (account_id, endorsement_id) DO NOTHING
+        ",
+    )
+    .bind(&item.topic)
+    .bind(item.endorsement_id)
+    .bind(item.subject_id)
+    .bind(item.issuer_id)
+    .execute(instrument(pool, "topics.fan_out"))
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// One keyset page of an account's feed, newest first.
+///
+/// Items whose endorsement has since been re-issued as non-public are hidden.
+///
+/// Returns up to `page.fetch_limit()` rows; see
+/// [`crate::pagination::finish_page`].
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_feed_page(
+    pool: &PgPool,
+    account_id: Uuid,
+    page: &KeysetPage,
+) -> Result<Vec<FeedItemRecord>, TopicRepoError> {
+    let (after_created_at, after_id) = page.after.unzip();
+    let rows = sqlx::query_as::<_, FeedItemRecord>(
+        r"
+        SELECT f.id, f.topic, f.endorsement_id, f.subject_id,
+               a.username AS subject_username, f.issuer_id,
+               (e.revoked_at IS NOT NULL AND e.revoked_at <= $2) AS revoked,
+               f.created_at
+        FROM topic_feed_items f
+        JOIN accounts a ON a.id = f.subject_id
+        JOIN reputation__endorsements e ON e.id = f.endorsement_id
+        WHERE f.account_id = $1
+          AND e.visibility = 'public'
+          AND f.created_at <= $2
+          AND ($3::timestamptz IS NULL OR (f.created_at, f.id) < ($3, $4))
+        ORDER BY f.created_at DESC, f.id DESC
+        LIMIT $5
+        ",
+    )
+    .bind(account_id)
+    .bind(page.as_of)
+    .bind(after_created_at)
+    .bind(after_id)
+    .bind(page.fetch_limit())
+    .fetch_all(instrument(pool, "topics.feed_page"))
+    .await?;
+    Ok(rows)
+}
+
+/// Movement counts for each topic `account_id` follows, alphabetically.
+/// Private endorsements are not counted.
+///
+/// # Errors
+///
+/// Returns `Database` on connection or query failure.
+pub async fn list_movements(
+    pool: &PgPool,
+    account_id: Uuid,
+) -> Result<Vec<TopicMovementRecord>, TopicRepoError> {
+    let rows = sqlx::query_as::<_, TopicMovementRecord>(
+        r"
+        SELECT s.topic,
+               COUNT(e.id) FILTER (WHERE e.revoked_at IS NULL) AS active,
+               COUNT(e.id) FILTER (
+                   WHERE e.created_at > now() - make_interval(days => $2)
+               ) AS added,
+               COUNT(e.id) FILTER (
+                   WHERE e.revoked_at > now() - make_interval(days => $2)
+               ) AS revoked
+        FROM topic_subscriptions s
+        LEFT JOIN reputation__endorsements e
+            ON e.topic = s.topic AND e.visibility <> 'private' AND NOT e.aggregates_paused
+        WHERE s.account_id = $1
+        GROUP BY s.topic
+        ORDER BY s.topic
+        ",
+    )
+    .bind(account_id)
+    .bind(MOVEMENT_WINDOW_DAYS)
+    .fetch_all(instrument(pool, "topics.movements"))
+    .await?;
+    Ok(rows)
+}
+
+// ─── Repo trait ────────────────────────────────────────────────────────────
+
+/// Topic subscription persistence for the projector and HTTP handlers.
+#[async_trait]
+pub trait TopicRepo: Send + Sync {
+    async fn subscribe(
+        &self,
+        account_id: Uuid,
+        topic: &str,
+    ) -> Result<DateTime<Utc>, TopicRepoError>;
+
+    async fn unsubscribe(&self, account_id: Uuid, topic: &str) -> Result<(), TopicRepoError>;
+
+    async fn list_subscriptions(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<(String, DateTime<Utc>)>, TopicRepoError>;
+
+    async fn fan_out(&self, item: &NewFeedItem) -> Result<u64, TopicRepoError>;
+
+    async fn list_feed_page(
+        &self,
+        account_id: Uuid,
+        page: &KeysetPage,
+    ) -> Result<Vec<FeedItemRecord>, TopicRepoError>;
+
+    async fn list_movements(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<TopicMovementRecord>, TopicRepoError>;
+}
+
+/// `PostgreSQL` implementation of [`TopicRepo`].
+pub struct PgTopicRepo {
+    pool: PgPool,
+}
+
+impl PgTopicRepo {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TopicRepo for PgTopicRepo {
+    async fn subscribe(
+        &self,
+        account_id: Uuid,
+        topic: &str,
+    ) -> Result<DateTime<Utc>, TopicRepoError> {
+        subscribe(&self.pool, account_id, topic).await
+    }
+
+    async fn unsubscribe(&self, account_id: Uuid, topic: &str) -> Result<(), TopicRepoError> {
+        unsubscribe(&self.pool, account_id, topic).await
+    }
+
+    async fn list_subscriptions(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<(String, DateTime<Utc>)>, TopicRepoError> {
+        list_subscriptions(&self.pool, account_id).await
+    }
+
+    async fn fan_out(&self, item: &NewFeedItem) -> Result<u64, TopicRepoError> {
+        fan_out(&self.pool, item).await
+    }
+
+    async fn list_feed_page(
+        &self,
+        account_id: Uuid,
+        page: &KeysetPage,
+    ) -> Result<Vec<FeedItemRecord>, TopicRepoError> {
+        list_feed_page(&self.pool, account_id, page).await
+    }
+
+    async fn list_movements(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<TopicMovementRecord>, TopicRepoError> {
+        list_movements(&self.pool, account_id).await
+    }
+}
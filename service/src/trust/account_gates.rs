@@ -0,0 +1,289 @@
+//! Account gates — minimum age, activity and verification for sensitive
+//! trust actions.
+//!
+//! New accounts are cheap, so an operator can require an account to be a
+//! few days old, to have voted in a few polls, or to have completed a
+//! [`VerificationMethod`] before it may endorse, denounce, or issue invites. Requirements are configured per action in
+//! [`AccountGatesConfig`] and checked by [`AccountGates`]; the trust service
+//! and the invite handler both go through it so the rules live in one place.
+//!
+//! The policy ([`evaluate`]) is pure; [`AccountStandingReader`] supplies the
+//! inputs.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::{AccountGatesConfig, ActionRequirement};
+use crate::reputation::verification::VerificationMethod;
+
+/// Trust actions that can be gated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatedAction {
+    Endorse,
+    Denounce,
+    Invite,
+}
+
+/// Inputs to the gate policy for one account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountStanding {
+    pub created_at: DateTime<Utc>,
+    /// Distinct polls the account has voted in.
+    pub polls_voted: i64,
+    /// Strongest verification method the account has completed.
+    pub verification: Option<VerificationMethod>,
+}
+
+/// Why an account may not perform a gated action yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum GateViolation {
+    #[error("account must be at least {min_days} days old")]
+    AccountTooNew { min_days: u32 },
+
+    #[error("account must have voted in at least {min_polls} polls")]
+    InsufficientActivity { min_polls: u32 },
+
+    #[error("account must complete {required} verification or stronger")]
+    VerificationRequired { required: VerificationMethod },
+}
+
+impl GateViolation {
+    /// Stable machine-readable code returned alongside the 403.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::AccountTooNew { .. } => "ACCOUNT_TOO_NEW",
+            Self::InsufficientActivity { .. } => "INSUFFICIENT_ACTIVITY",
+            Self::VerificationRequired { .. } => "VERIFICATION_REQUIRED",
+        }
+    }
+}
+
+/// Errors returned by [`AccountGates::check`].
+#[derive(Debug, thiserror::Error)]
+pub enum AccountGateError {
+    #[error(transparent)]
+    Violation(#[from] GateViolation),
+
+    #[error("account not found")]
+    AccountNotFound,
+
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Check `standing` against `requirement` at `now`. Age is checked first,
+/// then activity, then verification.
+///
+/// # Errors
+///
+/// Returns the first [`GateViolation`] the account does not meet.
+pub fn evaluate(
+    requirement: ActionRequirement,
+    standing: AccountStanding,
+    now: DateTime<Utc>,
+) -> Result<(), GateViolation> {
+    let age_days = (now - standing.created_at).num_days();
+    if age_days < i64::from(requirement.min_account_age_days) {
+        return Err(GateViolation::AccountTooNew {
+            min_days: requirement.min_account_age_days,
+        });
+    }
+    if standing.polls_voted < i64::from(requirement.min_polls_voted) {
+        return Err(GateViolation::InsufficientActivity {
+            min_polls: requirement.min_polls_voted,
+        });
+    }
+    if let Some(required) = requirement.min_verification {
+        if standing.verification.is_none_or(|v| v < required) {
+            return Err(GateViolation::VerificationRequired { required });
+        }
+    }
+    Ok(())
+}
+
+/// Loads the [`AccountStanding`] for an account.
+#[async_trait]
+pub trait AccountStandingReader: Send + Sync {
+    /// Returns `None` if the account does not exist.
+    async fn standing(&self, account_id: Uuid) -> Result<Option<AccountStanding>, sqlx::Error>;
+}
+
+/// [`AccountStandingReader`] backed by the accounts and votes tables.
+pub struct PgAccountStandingReader {
+    pool: PgPool,
+}
+
+impl PgAccountStandingReader {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AccountStandingReader for PgAccountStandingReader {
+    async fn standing(&self, account_id: Uuid) -> Result<Option<AccountStanding>, sqlx::Error> {
+        let row: Option<(DateTime<Utc>, i64, Vec<String>)> = sqlx::query_as(
+            r"
+            SELECT a.created_at,
+                   (SELECT COUNT(DISTINCT v.poll_id) FROM rooms__votes v WHERE v.user_id = a.id),
+                   ARRAY(SELECT rv.method FROM reputation__verifications rv
+                         WHERE rv.account_id = a.id)
+            FROM accounts a
+            WHERE a.id = $1
+            ",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(
+            row.map(|(created_at, polls_voted, methods)| AccountStanding {
+                created_at,
+                polls_voted,
+                verification: methods.iter().filter_map(|m| m.parse().ok()).max(),
+            }),
+        )
+    }
+}
+
+/// Per-action requirements plus the reader used to enforce them.
+pub struct AccountGates {
+    config: AccountGatesConfig,
+    reader: Arc<dyn AccountStandingReader>,
+}
+
+impl AccountGates {
+    #[must_use]
+    pub fn new(config: AccountGatesConfig, reader: Arc<dyn AccountStandingReader>) -> Self {
+        Self { config, reader }
+    }
+
+    const fn requirement(&self, action: GatedAction) -> ActionRequirement {
+        match action {
+            GatedAction::Endorse => self.config.endorse,
+            GatedAction::Denounce => self.config.denounce,
+            GatedAction::Invite => self.config.invite,
+        }
+    }
+
+    /// Check whether `account_id` may perform `action` now. Skips the
+    /// database when the action has no requirement.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AccountGateError::Violation`] if a requirement is not met.
+    pub async fn check(
+        &self,
+        action: GatedAction,
+        account_id: Uuid,
+    ) -> Result<(), AccountGateError> {
+        let requirement = self.requirement(action);
+        if requirement.is_unrestricted() {
+            return Ok(());
+        }
+        let standing = self
+            .reader
+            .standing(account_id)
+            .await?
+            .ok_or(AccountGateError::AccountNotFound)?;
+        evaluate(requirement, standing, Utc::now())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn standing(age_days: i64, polls_voted: i64) -> AccountStanding {
+        AccountStanding {
+            created_at: Utc::now() - Duration::days(age_days),
+            polls_voted,
+            verification: None,
+        }
+    }
+
+    const fn requirement(min_account_age_days: u32, min_polls_voted: u32) -> ActionRequirement {
+        ActionRequirement {
+            min_account_age_days,
+            min_polls_voted,
+            min_verification: None,
+        }
+    }
+
+    #[test]
+    fn evaluate_checks_age_then_activity() {
+        let now = Utc::now();
+        assert_eq!(
+            evaluate(requirement(7, 3), standing(2, 0), now),
+            Err(GateViolation::AccountTooNew { min_days: 7 })
+        );
+        assert_eq!(
+            evaluate(requirement(7, 3), standing(8, 2), now),
+            Err(GateViolation::InsufficientActivity { min_polls: 3 })
+        );
+        assert_eq!(evaluate(requirement(7, 3), standing(8, 3), now), Ok(()));
+        assert_eq!(evaluate(requirement(0, 0), standing(0, 0), now), Ok(()));
+    }
+
+    #[test]
+    fn evaluate_accepts_stronger_verification() {
+        let now = Utc::now();
+        let phone = ActionRequirement {
+            min_verification: Some(VerificationMethod::Phone),
+            ..requirement(0, 0)
+        };
+        let verified = |verification| AccountStanding {
+            verification,
+            ..standing(0, 0)
+        };
+        assert_eq!(
+            evaluate(phone, verified(None), now),
+            Err(GateViolation::VerificationRequired {
+                required: VerificationMethod::Phone
+            })
+        );
+        assert!(evaluate(phone, verified(Some(VerificationMethod::Email)), now).is_err());
+        assert_eq!(
+            evaluate(phone, verified(Some(VerificationMethod::Phone)), now),
+            Ok(())
+        );
+        assert_eq!(
+            evaluate(phone, verified(Some(VerificationMethod::GovernmentId)), now),
+            Ok(())
+        );
+    }
+
+    struct FixedReader(Option<AccountStanding>);
+
+    #[async_trait]
+    impl AccountStandingReader for FixedReader {
+        async fn standing(&self, _: Uuid) -> Result<Option<AccountStanding>, sqlx::Error> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn check_applies_the_actions_requirement() {
+        let config = AccountGatesConfig {
+            denounce: requirement(30, 0),
+            ..AccountGatesConfig::default()
+        };
+        let gates = AccountGates::new(config, Arc::new(FixedReader(Some(standing(1, 0)))));
+        let account = Uuid::new_v4();
+
+        assert!(gates.check(GatedAction::Endorse, account).await.is_ok());
+        assert!(matches!(
+            gates.check(GatedAction::Denounce, account).await,
+            Err(AccountGateError::Violation(GateViolation::AccountTooNew {
+                min_days: 30
+            }))
+        ));
+    }
+}
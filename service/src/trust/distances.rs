@@ -0,0 +1,256 @@
+//! Precomputed trust distances between accounts.
+//!
+//! Trust path lookups are hot, and the recursive CTE in
+//! [`TrustEngine::compute_distances_from`] walks the whole reachable graph on
+//! every call. [`TrustDistances`] keeps a table of each active account's
+//! distances to everyone within [`MAX_HOPS`] edges (`trust__distances`) and
+//! answers from it when it can.
+//!
+//! The table is refreshed incrementally: when a trust edge out of `X`
+//! changes, every source that reaches `X` in fewer than [`MAX_HOPS`] hops —
+//! plus `X` itself — is marked dirty ([`TrustDistances::mark_edge_changed`]).
+//! A background job ([`TrustDistances::spawn_refresher`]) recomputes dirty
+//! sources, and sources older than [`MAX_AGE_HOURS`] so that edge decay is
+//! picked up without an edge change.
+//!
+//! [`TrustDistances::distance`] falls back to a live traversal when the source
+//! is dirty or has never been materialized, or when the pair has no row (the
+//! target is more than [`MAX_HOPS`] away or unreachable). Cold sources are
+//! queued so later lookups hit the table.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::trust::engine::TrustEngine;
+
+/// Longest path, in edges, that is materialized.
+pub const MAX_HOPS: i32 = 4;
+
+/// Materialized sources older than this are recomputed even if no edge changed.
+pub const MAX_AGE_HOURS: i32 = 24;
+
+/// How often the refresher looks for dirty or stale sources.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Sources recomputed per refresher tick.
+const REFRESH_BATCH: i64 = 50;
+
+/// Where a distance answer came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceOrigin {
+    /// Read from `trust__distances`.
+    Materialized,
+    /// Computed by a live traversal.
+    Live,
+}
+
+/// Distance from one account to another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrustDistance {
+    /// Minimum weighted distance; `None` if the target is unreachable.
+    pub distance: Option<f32>,
+    pub origin: DistanceOrigin,
+}
+
+/// Materialized trust distances with live fallback.
+pub struct TrustDistances {
+    pool: PgPool,
+    engine: Arc<TrustEngine>,
+}
+
+impl TrustDistances {
+    #[must_use]
+    pub const fn new(pool: PgPool, engine: Arc<TrustEngine>) -> Self {
+        Self { pool, engine }
+    }
+
+    /// Mark every source whose materialized distances may depend on trust
+    /// edges out of `endorser_id` as dirty.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`sqlx::Error`] if the update fails.
+    pub async fn mark_edge_changed(&self, endorser_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r"
+            INSERT INTO trust__distance_sources (source_id)
+            SELECT $1::uuid
+            UNION
+            SELECT source_id FROM trust__distances WHERE target_id = $1 AND hops < $2
+            ON CONFLICT (source_id) DO UPDATE SET dirty = true
+            ",
+        )
+        .bind(endorser_id)
+        .bind(MAX_HOPS)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Recompute up to `limit` dirty or stale sources, oldest first.
+    ///
+    /// A source is marked clean when claimed, so an edge change during the
+    /// recompute marks it dirty again and it is picked up on the next pass.
+    ///
+    /// Returns the number of sources refreshed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`sqlx::Error`] if claiming or writing fails. Sources that
+    /// fail to write are left dirty.
+    pub async fn refresh(&self, limit: i64) -> Result<usize, sqlx::Error> {
+        let claimed: Vec<Uuid> = sqlx::query_scalar(
+            r"
+            UPDATE trust__distance_sources SET dirty = false
+            WHERE source_id IN (
+                SELECT source_id FROM trust__distance_sources
+                WHERE dirty
+                   OR refreshed_at IS NULL
+                   OR refreshed_at < now() - make_interval(hours => $2)
+                ORDER BY refreshed_at NULLS FIRST
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING source_id
+            ",
+        )
+        .bind(limit)
+        .bind(MAX_AGE_HOURS)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for &source_id in &claimed {
+            if let Err(e) = self.refresh_source(source_id).await {
+                sqlx::query("UPDATE trust__distance_sources SET dirty = true WHERE source_id = $1")
+                    .bind(source_id)
+                    .execute(&self.pool)
+                    .await?;
+                return Err(e);
+            }
+        }
+        Ok(claimed.len())
+    }
+
+    async fn refresh_source(&self, source_id: Uuid) -> Result<(), sqlx::Error> {
+        let rows = self
+            .engine
+            .compute_bounded_distances_from(source_id, MAX_HOPS)
+            .await?;
+        let targets: Vec<Uuid> = rows.iter().map(|r| r.account_id).collect();
+        let distances: Vec<f32> = rows.iter().map(|r| r.trust_distance).collect();
+        let hops: Vec<i32> = rows.iter().map(|r| r.hops).collect();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM trust__distances WHERE source_id = $1")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r"
+            INSERT INTO trust__distances (source_id, target_id, distance, hops)
+            SELECT $1, t.target_id, t.distance, t.hops
+            FROM UNNEST($2::uuid[], $3::real[], $4::int[]) AS t(target_id, distance, hops)
+            ",
+        )
+        .bind(source_id)
+        .bind(&targets)
+        .bind(&distances)
+        .bind(&hops)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE trust__distance_sources SET refreshed_at = now() WHERE source_id = $1")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    /// Distance from `source_id` to `target_id`, from the table when the
+    /// source is materialized and clean, otherwise by live traversal.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`sqlx::Error`] if a query fails.
+    pub async fn distance(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+    ) -> Result<TrustDistance, sqlx::Error> {
+        if source_id == target_id {
+            return Ok(TrustDistance {
+                distance: Some(0.0),
+                origin: DistanceOrigin::Materialized,
+            });
+        }
+
+        let cached: Option<(bool, Option<f32>)> = sqlx::query_as(
+            r"
+            SELECT s.dirty OR s.refreshed_at IS NULL, d.distance
+            FROM trust__distance_sources s
+            LEFT JOIN trust__distances d
+                ON d.source_id = s.source_id AND d.target_id = $2
+            WHERE s.source_id = $1
+            ",
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match cached {
+            Some((false, Some(distance))) => {
+                return Ok(TrustDistance {
+                    distance: Some(distance),
+                    origin: DistanceOrigin::Materialized,
+                });
+            }
+            Some(_) => {}
+            None => {
+                // Queue the source so the next lookup can hit the table.
+                sqlx::query(
+                    "INSERT INTO trust__distance_sources (source_id) VALUES ($1) \
+                     ON CONFLICT (source_id) DO NOTHING",
+                )
+                .bind(source_id)
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        let distance = self
+            .engine
+            .compute_distances_from(source_id)
+            .await?
+            .into_iter()
+            .find(|s| s.account_id == target_id)
+            .and_then(|s| s.trust_distance);
+        Ok(TrustDistance {
+            distance,
+            origin: DistanceOrigin::Live,
+        })
+    }
+
+    /// Refresh dirty and stale sources every few seconds for the life of the
+    /// process.
+    pub fn spawn_refresher(self: &Arc<Self>) {
+        let distances = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                match distances.refresh(REFRESH_BATCH).await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::debug!(sources = n, "refreshed trust distances"),
+                    Err(e) => tracing::warn!("Failed to refresh trust distances: {e}"),
+                }
+            }
+        });
+    }
+}
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::trust::max_flow::FlowGraph;
 use crate::trust::repo::{TrustRepo, TrustRepoError};
+use crate::trust::weight::{DECAY_EXPIRY_DAYS, DECAY_STEP_DAYS};
 
 /// Errors returned by [`TrustEngine::recompute_from_anchor`].
 #[derive(Debug, thiserror::Error)]
@@ -37,6 +38,16 @@ struct DistanceRow {
     trust_distance: f32,
 }
 
+/// Shortest weighted distance to one account within a hop bound.
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::FromRow)]
+pub struct BoundedDistance {
+    pub account_id: Uuid,
+    /// Minimum weighted distance over paths of at most the bound's length.
+    pub trust_distance: f32,
+    /// Fewest hops on any such path (not necessarily the shortest-distance one).
+    pub hops: i32,
+}
+
 /// Intermediate row type for edge loading (diversity computation).
 #[derive(sqlx::FromRow)]
 struct EdgeRow {
@@ -58,9 +69,14 @@ impl TrustEngine {
     /// Compute the minimum weighted hop-count distance from `anchor_id` to every
     /// reachable user using a recursive CTE (TRD Section 3.1.1).
     ///
-    /// A lower-weight edge is treated as a higher cost: an edge with `weight w`
-    /// contributes `1.0 / w` to the running distance. The traversal stops when
-    /// accumulated distance exceeds 10.0 or a cycle would be revisited.
+    /// A lower-weight edge is treated as a higher cost: an edge with effective
+    /// weight `w` contributes `1.0 / w` to the running distance. The effective
+    /// weight is the stored weight × [`decay_factor`] of the time since the edge
+    /// was last confirmed (ADR-025); fully decayed edges are left out. The
+    /// traversal stops when accumulated distance exceeds 10.0 or a cycle would
+    /// be revisited.
+    ///
+    /// [`decay_factor`]: crate::trust::weight::decay_factor
     ///
     /// # Errors
     ///
@@ -71,18 +87,30 @@ impl TrustEngine {
     ) -> Result<Vec<ComputedScore>, sqlx::Error> {
         let rows: Vec<DistanceRow> = sqlx::query_as(
             r"
-WITH RECURSIVE trust_graph AS (
+WITH RECURSIVE edges AS (
+    -- Active trust edges with decayed (effective) weights; see weight::decay_factor
+    SELECT
+        endorser_id,
+        subject_id,
+        CASE
+            WHEN last_confirmed_at > now() - make_interval(days => $2::int) THEN weight
+            ELSE weight * 0.5
+        END AS weight
+    FROM reputation__endorsements
+    WHERE revoked_at IS NULL
+      AND endorser_id IS NOT NULL
+      AND topic = 'trust'
+      AND in_slot = true
+      AND last_confirmed_at > now() - make_interval(days => $3::int)
+),
+trust_graph AS (
     -- Base: direct endorsements from the anchor
     SELECT
         e.subject_id                        AS account_id,
         (1.0 / e.weight)::real              AS distance,
         ARRAY[e.endorser_id, e.subject_id]  AS path
-    FROM reputation__endorsements e
+    FROM edges e
     WHERE e.endorser_id = $1
-      AND e.revoked_at IS NULL
-      AND e.endorser_id IS NOT NULL
-      AND e.topic = 'trust'
-      AND e.in_slot = true
 
     UNION ALL
 
@@ -91,14 +119,10 @@ WITH RECURSIVE trust_graph AS (
         e.subject_id,
         (tg.distance + 1.0 / e.weight)::real,
         tg.path || e.subject_id
-    FROM reputation__endorsements e
+    FROM edges e
     JOIN trust_graph tg ON e.endorser_id = tg.account_id
     WHERE tg.distance < 10.0
       AND NOT (e.subject_id = ANY(tg.path))
-      AND e.revoked_at IS NULL
-      AND e.endorser_id IS NOT NULL
-      AND e.topic = 'trust'
-      AND e.in_slot = true
 )
 SELECT account_id, MIN(distance) AS trust_distance
 FROM trust_graph
@@ -106,6 +130,8 @@ GROUP BY account_id
             ",
         )
         .bind(anchor_id)
+        .bind(DECAY_STEP_DAYS)
+        .bind(DECAY_EXPIRY_DAYS)
         .fetch_all(&self.pool)
         .await?;
 
@@ -133,6 +159,72 @@ GROUP BY account_id
         Ok(scores)
     }
 
+    /// Like [`compute_distances_from`](Self::compute_distances_from), but only
+    /// follows paths of at most `max_hops` edges, and also reports the fewest
+    /// hops to each account. The anchor itself is not included.
+    ///
+    /// Used to materialize `trust__distances`; see [`crate::trust::distances`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`sqlx::Error`] if the database query fails.
+    pub async fn compute_bounded_distances_from(
+        &self,
+        anchor_id: Uuid,
+        max_hops: i32,
+    ) -> Result<Vec<BoundedDistance>, sqlx::Error> {
+        sqlx::query_as(
+            r"
+WITH RECURSIVE edges AS (
+    SELECT
+        endorser_id,
+        subject_id,
+        CASE
+            WHEN last_confirmed_at > now() - make_interval(days => $2::int) THEN weight
+            ELSE weight * 0.5
+        END AS weight
+    FROM reputation__endorsements
+    WHERE revoked_at IS NULL
+      AND endorser_id IS NOT NULL
+      AND topic = 'trust'
+      AND in_slot = true
+      AND last_confirmed_at > now() - make_interval(days => $3::int)
+),
+trust_graph AS (
+    SELECT
+        e.subject_id                        AS account_id,
+        (1.0 / e.weight)::real              AS distance,
+        1                                   AS hops,
+        ARRAY[e.endorser_id, e.subject_id]  AS path
+    FROM edges e
+    WHERE e.endorser_id = $1
+
+    UNION ALL
+
+    SELECT
+        e.subject_id,
+        (tg.distance + 1.0 / e.weight)::real,
+        tg.hops + 1,
+        tg.path || e.subject_id
+    FROM edges e
+    JOIN trust_graph tg ON e.endorser_id = tg.account_id
+    WHERE tg.distance < 10.0
+      AND tg.hops < $4
+      AND NOT (e.subject_id = ANY(tg.path))
+)
+SELECT account_id, MIN(distance) AS trust_distance, MIN(hops) AS hops
+FROM trust_graph
+GROUP BY account_id
+            ",
+        )
+        .bind(anchor_id)
+        .bind(DECAY_STEP_DAYS)
+        .bind(DECAY_EXPIRY_DAYS)
+        .bind(max_hops)
+        .fetch_all(&self.pool)
+        .await
+    }
+
     /// Compute vertex connectivity (exact node-disjoint path count via Edmonds-Karp max-flow)
     /// for each user reachable from `anchor_id`.
     ///
@@ -179,7 +271,8 @@ GROUP BY account_id
             .collect();
         let n = reachable.len();
 
-        // Step 2: load all edges within the reachable subgraph.
+        // Step 2: load all edges within the reachable subgraph, skipping fully
+        // decayed ones (ADR-025).
         let edges: Vec<EdgeRow> = sqlx::query_as(
             r"
 SELECT endorser_id, subject_id
@@ -188,11 +281,13 @@ WHERE revoked_at IS NULL
   AND endorser_id IS NOT NULL
   AND topic = 'trust'
   AND in_slot = true
+  AND last_confirmed_at > now() - make_interval(days => $2::int)
   AND endorser_id = ANY($1)
   AND subject_id = ANY($1)
             ",
         )
         .bind(reachable)
+        .bind(DECAY_EXPIRY_DAYS)
         .fetch_all(&self.pool)
         .await?;
 
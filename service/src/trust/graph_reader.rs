@@ -113,9 +113,10 @@ mod tests {
     use std::sync::{Arc, Mutex};
 
     use super::*;
+    use crate::trust::invite_quota::InviteQuota;
     use crate::trust::repo::{
         ActionRecord, DenouncementRecord, DenouncementWithUsername, InfluenceRecord, InviteRecord,
-        ScoreSnapshot, TrustRepo, TrustRepoError,
+        InviteTreeEdge, PairingCodeRecord, ScoreSnapshot, StaleEdge, TrustRepo, TrustRepoError,
     };
     use crate::trust::service::ActionType;
     use crate::trust::weight::{DeliveryMethod, RelationshipDepth};
@@ -322,6 +323,43 @@ mod tests {
         ) -> Result<Vec<InviteRecord>, TrustRepoError> {
             unimplemented!()
         }
+        async fn get_invite_quota(&self, _: Uuid) -> Result<InviteQuota, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn get_invite_tree(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn create_pairing_code(
+            &self,
+            _: Uuid,
+            _: Uuid,
+            _: &str,
+            _: chrono::DateTime<chrono::Utc>,
+        ) -> Result<PairingCodeRecord, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn resolve_pairing_code(&self, _: &str) -> Result<Uuid, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn list_stale_edges(
+            &self,
+            _: Uuid,
+            _: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn reconfirm_edge(
+            &self,
+            _: Uuid,
+            _: Uuid,
+            _: &serde_json::Value,
+        ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+            unimplemented!()
+        }
         async fn upsert_score(
             &self,
             _: Uuid,
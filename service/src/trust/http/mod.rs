@@ -4,7 +4,7 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::Extension,
+    extract::{Extension, Query},
     http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
@@ -12,10 +12,17 @@ use axum::{
 };
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use tc_crypto::{decode_base64url, verify_ed25519, Kid};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use super::repo::{TrustRepo, TrustRepoError};
+use super::account_gates::{AccountGates, GatedAction};
+use super::distances::{DistanceOrigin, TrustDistances};
+use super::invite_quota::{InviteQuota, INVITE_QUOTA_WINDOW_DAYS};
+use super::pairing::{
+    generate_pairing_code, invite_deep_link, normalize_pairing_code, PAIRING_CODE_TTL_MINUTES,
+};
+use super::repo::{InviteTreeEdge, TrustRepo, TrustRepoError};
 use super::service::{
     is_valid_endorsement_weight, is_valid_reason, TrustService, TrustServiceError,
     DENOUNCEMENT_REASON_MAX_LEN, DENOUNCEMENT_SLOT_LIMIT, ENDORSEMENT_SLOT_LIMIT,
@@ -36,9 +43,31 @@ fn is_attestation_within_size_limit(att: &serde_json::Value) -> bool {
 const fn is_envelope_within_size_limit(bytes: &[u8]) -> bool {
     !bytes.is_empty() && bytes.len() <= 4096
 }
-use super::weight::{compute_endorsement_weight, DeliveryMethod, RelationshipDepth};
-use crate::http::{bad_request, conflict, internal_error, not_found, too_many_requests, Path};
+
+/// Domain-separation prefix for trust edge re-confirmation signatures, so a
+/// co-signature can never be replayed as a request or certificate signature.
+pub const RECONFIRM_DOMAIN: &str = "tc-trust-reconfirm:v1\n";
+
+/// Bytes the subject's device signs to re-confirm the edge
+/// `endorser_id → subject_id`:
+/// `RECONFIRM_DOMAIN || "{endorser_id}\n{subject_id}\n{timestamp}"`.
+#[must_use]
+pub fn reconfirmation_message(endorser_id: Uuid, subject_id: Uuid, timestamp: i64) -> Vec<u8> {
+    format!("{RECONFIRM_DOMAIN}{endorser_id}\n{subject_id}\n{timestamp}").into_bytes()
+}
+use super::weight::{
+    compute_endorsement_weight, decay_factor, DeliveryMethod, RelationshipDepth,
+    RECONFIRM_AFTER_DAYS,
+};
+use crate::config::{AuthConfig, InstanceConfig};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::http::{
+    bad_request, conflict, forbidden_with_code, internal_error, not_found, too_many_requests, Path,
+};
 use crate::identity::http::auth::AuthenticatedDevice;
+use crate::identity::http::token_auth::{scope, ReadAuth};
+use crate::identity::repo::{AccountRepoError, DeviceKeyRepoError, IdentityRepo};
+use crate::identity::service::DevicePubkey;
 use crate::reputation::repo::ReputationRepo;
 
 // ─── Request types ─────────────────────────────────────────────────────────
@@ -81,6 +110,30 @@ pub struct CreateInviteRequest {
     pub attestation: serde_json::Value,
 }
 
+/// Deepest invite tree the HTTP endpoint will walk.
+pub const MAX_INVITE_TREE_DEPTH: i32 = 5;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct InviteTreeQuery {
+    /// Hops to follow from the caller (1–5, default 5).
+    pub max_depth: Option<i32>,
+}
+
+/// Re-confirmation of the caller's trust edge to `subject_id`, co-signed by
+/// one of the subject's devices over [`reconfirmation_message`].
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReconfirmRequest {
+    #[schema(value_type = String, format = "uuid")]
+    pub subject_id: Uuid,
+    /// KID of the subject device that produced `subject_signature`
+    #[schema(value_type = String)]
+    pub subject_kid: Kid,
+    /// Unix seconds the subject signed at; must be within the clock-skew window
+    pub timestamp: i64,
+    /// base64url Ed25519 signature by the subject device
+    pub subject_signature: String,
+}
+
 // ─── Response types ────────────────────────────────────────────────────────
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -103,6 +156,16 @@ pub struct ScoresResponse {
     pub scores: Vec<ScoreSnapshotResponse>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrustDistanceResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub target_id: Uuid,
+    /// Minimum weighted trust distance from the caller; null if unreachable
+    pub trust_distance: Option<f32>,
+    /// Whether the answer came from the precomputed table or a live traversal
+    pub origin: DistanceOrigin,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct BudgetResponse {
     pub slots_total: u32,
@@ -137,6 +200,80 @@ pub struct InvitesResponse {
     pub invites: Vec<InviteResponse>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InviteTreeEdgeResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub invite_id: Uuid,
+    #[schema(value_type = String, format = "uuid")]
+    pub inviter_id: Uuid,
+    #[schema(value_type = String, format = "uuid")]
+    pub invitee_id: Uuid,
+    pub invitee_username: String,
+    pub accepted_at: Option<String>,
+    pub depth: i32,
+}
+
+/// Accepted invites downstream of `root_id`, with a per-depth summary.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InviteTreeResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub root_id: Uuid,
+    pub max_depth: i32,
+    pub total_invitees: usize,
+    /// Number of invitees at each depth; index 0 is depth 1.
+    pub invitees_per_depth: Vec<usize>,
+    pub edges: Vec<InviteTreeEdgeResponse>,
+}
+
+impl InviteTreeResponse {
+    #[must_use]
+    pub fn from_edges(root_id: Uuid, max_depth: i32, edges: Vec<InviteTreeEdge>) -> Self {
+        let mut invitees_per_depth = vec![0; usize::try_from(max_depth).unwrap_or(0)];
+        for edge in &edges {
+            if let Some(slot) = usize::try_from(edge.depth - 1)
+                .ok()
+                .and_then(|i| invitees_per_depth.get_mut(i))
+            {
+                *slot += 1;
+            }
+        }
+        let edges: Vec<_> = edges
+            .into_iter()
+            .map(|e| InviteTreeEdgeResponse {
+                invite_id: e.invite_id,
+                inviter_id: e.inviter_id,
+                invitee_id: e.invitee_id,
+                invitee_username: e.invitee_username,
+                accepted_at: e.accepted_at.map(|t| t.to_rfc3339()),
+                depth: e.depth,
+            })
+            .collect();
+        Self {
+            root_id,
+            max_depth,
+            total_invitees: edges.len(),
+            invitees_per_depth,
+            edges,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PairingCodeResponse {
+    /// Short code the invitee types in instead of scanning the QR code
+    pub code: String,
+    pub expires_at: String,
+    /// Link that opens the invite directly, same as the QR payload
+    pub deep_link: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InviteLinkResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub invite_id: Uuid,
+    pub deep_link: String,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AcceptInviteResponse {
     #[schema(value_type = String, format = "uuid")]
@@ -155,6 +292,35 @@ pub struct DenouncementResponse {
     pub created_at: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReconfirmResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub subject_id: Uuid,
+    pub last_confirmed_at: String,
+}
+
+/// A trust edge touching the caller that is due for re-confirmation.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StaleEdgeResponse {
+    #[schema(value_type = String, format = "uuid")]
+    pub endorser_id: Uuid,
+    pub endorser_username: String,
+    #[schema(value_type = String, format = "uuid")]
+    pub subject_id: Uuid,
+    pub subject_username: String,
+    pub weight: f32,
+    pub last_confirmed_at: String,
+    /// Multiplier currently applied to `weight` in trust computations
+    pub decay_factor: f64,
+    pub effective_weight: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReconfirmationsResponse {
+    pub reconfirm_after_days: i64,
+    pub edges: Vec<StaleEdgeResponse>,
+}
+
 // ─── Router ────────────────────────────────────────────────────────────────
 
 pub fn trust_router() -> Router {
@@ -167,10 +333,24 @@ pub fn trust_router() -> Router {
             get(list_my_denouncements_handler),
         )
         .route("/trust/scores/me", get(scores_me_handler))
+        .route("/trust/distance/{account_id}", get(distance_handler))
         .route("/trust/budget", get(budget_handler))
         .route("/trust/invites", post(create_invite_handler))
         .route("/trust/invites/mine", get(list_invites_handler))
+        .route("/trust/invites/quota", get(invite_quota_handler))
+        .route("/trust/invites/tree", get(invite_tree_handler))
         .route("/trust/invites/{id}/accept", post(accept_invite_handler))
+        .route(
+            "/trust/invites/{id}/pairing-code",
+            post(create_pairing_code_handler),
+        )
+        .route("/trust/invites/{id}/link", get(invite_link_handler))
+        .route(
+            "/trust/invites/pairing/{code}",
+            get(resolve_pairing_code_handler),
+        )
+        .route("/trust/reconfirmations", get(reconfirmations_handler))
+        .route("/trust/reconfirm", post(reconfirm_handler))
 }
 
 // ─── Handlers ──────────────────────────────────────────────────────────────
@@ -184,11 +364,13 @@ pub fn trust_router() -> Router {
         (status = 202, description = "Endorsement queued", body = MessageResponse),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account too new or not active enough (`ACCOUNT_TOO_NEW`, `INSUFFICIENT_ACTIVITY`)"),
         (status = 429, description = "Quota exceeded"),
     )
 )]
 async fn endorse_handler(
     Extension(trust_service): Extension<Arc<dyn TrustService>>,
+    events: Option<Extension<Arc<dyn EventPublisher>>>,
     auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
     let body: EndorseRequest = match auth.json() {
@@ -221,6 +403,13 @@ async fn endorse_handler(
                 subject_id = %body.subject_id,
                 "Endorsement queued"
             );
+            if let Some(Extension(events)) = events {
+                events.publish(DomainEvent::EndorsementQueued {
+                    endorser_id: auth.account_id,
+                    subject_id: body.subject_id,
+                    weight: body.weight,
+                });
+            }
             (
                 StatusCode::ACCEPTED,
                 Json(MessageResponse {
@@ -285,6 +474,7 @@ async fn revoke_handler(
         (status = 202, description = "Denouncement queued", body = MessageResponse),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account too new or not active enough (`ACCOUNT_TOO_NEW`, `INSUFFICIENT_ACTIVITY`)"),
         (status = 429, description = "Quota exceeded"),
     )
 )]
@@ -366,11 +556,13 @@ async fn list_my_denouncements_handler(
     responses(
         (status = 200, description = "Trust scores for the authenticated user", body = ScoresResponse),
         (status = 401, description = "Unauthorized"),
-    )
+        (status = 403, description = "API token lacks the trust:read scope"),
+    ),
+    security(("device_auth" = []), ("api_token" = []))
 )]
 async fn scores_me_handler(
     Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
-    auth: AuthenticatedDevice,
+    auth: ReadAuth<scope::TrustRead>,
 ) -> impl IntoResponse {
     match trust_repo.get_all_scores(auth.account_id).await {
         Ok(snapshots) => {
@@ -390,6 +582,52 @@ async fn scores_me_handler(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/trust/distance/{account_id}",
+    tag = "Trust",
+    params(
+        ("account_id" = String, Path, description = "Target account ID", format = "uuid")
+    ),
+    responses(
+        (status = 200, description = "Trust distance from the authenticated user to the target", body = TrustDistanceResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the trust:read scope"),
+        (status = 404, description = "Account not found"),
+    ),
+    security(("device_auth" = []), ("api_token" = []))
+)]
+async fn distance_handler(
+    Extension(identity_repo): Extension<Arc<dyn IdentityRepo>>,
+    Extension(distances): Extension<Arc<TrustDistances>>,
+    Path(target_id): Path<Uuid>,
+    auth: ReadAuth<scope::TrustRead>,
+) -> impl IntoResponse {
+    match identity_repo.get_account_by_id(target_id).await {
+        Ok(_) => {}
+        Err(AccountRepoError::NotFound) => return not_found("Account not found"),
+        Err(e) => {
+            tracing::error!("Failed to look up distance target: {e}");
+            return internal_error();
+        }
+    }
+    match distances.distance(auth.account_id, target_id).await {
+        Ok(d) => (
+            StatusCode::OK,
+            Json(TrustDistanceResponse {
+                target_id,
+                trust_distance: d.distance,
+                origin: d.origin,
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Trust distance lookup failed: {e}");
+            internal_error()
+        }
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/trust/budget",
@@ -397,12 +635,14 @@ async fn scores_me_handler(
     responses(
         (status = 200, description = "Endorsement and denouncement budget for the authenticated user", body = BudgetResponse),
         (status = 401, description = "Unauthorized"),
-    )
+        (status = 403, description = "API token lacks the trust:read scope"),
+    ),
+    security(("device_auth" = []), ("api_token" = []))
 )]
 async fn budget_handler(
     Extension(reputation_repo): Extension<Arc<dyn ReputationRepo>>,
     Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
-    auth: AuthenticatedDevice,
+    auth: ReadAuth<scope::TrustRead>,
 ) -> impl IntoResponse {
     let endorsements_used = match reputation_repo
         .count_active_trust_endorsements_by(auth.account_id)
@@ -467,10 +707,13 @@ async fn budget_handler(
         (status = 201, description = "Invite created", body = CreateInviteResponse),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Account too new or not active enough (`ACCOUNT_TOO_NEW`, `INSUFFICIENT_ACTIVITY`)"),
+        (status = 429, description = "Invite quota exhausted for the current window"),
     )
 )]
 async fn create_invite_handler(
     Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    account_gates: Option<Extension<Arc<AccountGates>>>,
     auth: AuthenticatedDevice,
 ) -> impl IntoResponse {
     let body: CreateInviteRequest = match auth.json() {
@@ -498,6 +741,12 @@ async fn create_invite_handler(
         return bad_request("weight must be in range (0.0, 1.0]");
     }
 
+    if let Some(Extension(gates)) = account_gates {
+        if let Err(e) = gates.check(GatedAction::Invite, auth.account_id).await {
+            return trust_service_error_response(&TrustServiceError::from(e));
+        }
+    }
+
     let expires_at = Utc::now() + Duration::days(7);
 
     match trust_repo
@@ -555,6 +804,186 @@ async fn list_invites_handler(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/trust/invites/quota",
+    tag = "Trust",
+    responses(
+        (status = 200, description = "Invite quota for the current window", body = InviteQuota),
+        (status = 401, description = "Unauthorized"),
+    )
+)]
+async fn invite_quota_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match trust_repo.get_invite_quota(auth.account_id).await {
+        Ok(quota) => (StatusCode::OK, Json(quota)).into_response(),
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/trust/invites/tree",
+    tag = "Trust",
+    params(InviteTreeQuery),
+    responses(
+        (status = 200, description = "Accepted invites downstream of the authenticated user", body = InviteTreeResponse),
+        (status = 400, description = "Invalid max_depth"),
+        (status = 401, description = "Unauthorized"),
+    )
+)]
+async fn invite_tree_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    Query(query): Query<InviteTreeQuery>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let max_depth = query.max_depth.unwrap_or(MAX_INVITE_TREE_DEPTH);
+    if !(1..=MAX_INVITE_TREE_DEPTH).contains(&max_depth) {
+        return bad_request(&format!(
+            "max_depth must be between 1 and {MAX_INVITE_TREE_DEPTH}"
+        ));
+    }
+
+    match trust_repo.get_invite_tree(auth.account_id, max_depth).await {
+        Ok(edges) => (
+            StatusCode::OK,
+            Json(InviteTreeResponse::from_edges(
+                auth.account_id,
+                max_depth,
+                edges,
+            )),
+        )
+            .into_response(),
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
+/// Issue a short pairing code for one of the caller's open invites, for
+/// invitees who cannot scan the QR code.
+#[utoipa::path(
+    post,
+    path = "/trust/invites/{id}/pairing-code",
+    tag = "Trust",
+    params(
+        ("id" = String, Path, description = "Invite ID", format = "uuid")
+    ),
+    responses(
+        (status = 201, description = "Pairing code issued", body = PairingCodeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No open invite with this ID belongs to the caller"),
+        (status = 429, description = "Hourly pairing code limit reached"),
+    )
+)]
+async fn create_pairing_code_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    instance: Option<Extension<InstanceConfig>>,
+    Path(invite_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let expires_at = Utc::now() + Duration::minutes(PAIRING_CODE_TTL_MINUTES);
+    match trust_repo
+        .create_pairing_code(
+            auth.account_id,
+            invite_id,
+            &generate_pairing_code(),
+            expires_at,
+        )
+        .await
+    {
+        Ok(record) => (
+            StatusCode::CREATED,
+            Json(PairingCodeResponse {
+                code: record.code,
+                expires_at: record.expires_at.to_rfc3339(),
+                deep_link: invite_deep_link(&web_url(instance), record.invite_id),
+            }),
+        )
+            .into_response(),
+        Err(TrustRepoError::NotFound) => not_found("Invite not found or no longer open"),
+        Err(TrustRepoError::Duplicate) => conflict("Pairing code collision — please retry"),
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
+/// Deep link for one of the caller's invites, for sharing where a QR code
+/// cannot be shown.
+#[utoipa::path(
+    get,
+    path = "/trust/invites/{id}/link",
+    tag = "Trust",
+    params(
+        ("id" = String, Path, description = "Invite ID", format = "uuid")
+    ),
+    responses(
+        (status = 200, description = "Invite deep link", body = InviteLinkResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Invite not found"),
+    )
+)]
+async fn invite_link_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    instance: Option<Extension<InstanceConfig>>,
+    Path(invite_id): Path<Uuid>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    match trust_repo.get_invite(invite_id).await {
+        Ok(invite) if invite.endorser_id == auth.account_id => (
+            StatusCode::OK,
+            Json(InviteLinkResponse {
+                invite_id,
+                deep_link: invite_deep_link(&web_url(instance), invite_id),
+            }),
+        )
+            .into_response(),
+        Ok(_) | Err(TrustRepoError::NotFound) => not_found("Invite not found"),
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
+/// Look up the invite behind a typed pairing code. Dashes, spaces and case
+/// are ignored. Accept it with `POST /trust/invites/{id}/accept`.
+#[utoipa::path(
+    get,
+    path = "/trust/invites/pairing/{code}",
+    tag = "Trust",
+    params(
+        ("code" = String, Path, description = "Pairing code")
+    ),
+    responses(
+        (status = 200, description = "Invite the code points at", body = InviteLinkResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Unknown or expired code, or the invite is no longer open"),
+    )
+)]
+async fn resolve_pairing_code_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    instance: Option<Extension<InstanceConfig>>,
+    Path(code): Path<String>,
+    _auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let Some(code) = normalize_pairing_code(&code) else {
+        return not_found("Pairing code not found");
+    };
+    match trust_repo.resolve_pairing_code(&code).await {
+        Ok(invite_id) => (
+            StatusCode::OK,
+            Json(InviteLinkResponse {
+                invite_id,
+                deep_link: invite_deep_link(&web_url(instance), invite_id),
+            }),
+        )
+            .into_response(),
+        Err(TrustRepoError::NotFound) => not_found("Pairing code not found"),
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
+fn web_url(instance: Option<Extension<InstanceConfig>>) -> String {
+    instance.map(|Extension(c)| c.web_url).unwrap_or_default()
+}
+
 #[utoipa::path(
     post,
     path = "/trust/invites/{id}/accept",
@@ -643,6 +1072,9 @@ fn trust_service_error_response(e: &TrustServiceError) -> axum::response::Respon
             conflict("Cannot endorse a user you have denounced")
         }
         TrustServiceError::AlreadyDenounced => conflict("Already denounced this user"),
+        TrustServiceError::AccountGate(violation) => {
+            forbidden_with_code(violation.code(), &violation.to_string())
+        }
         TrustServiceError::Repo(ref inner) => {
             tracing::error!("Trust service repo error: {inner}");
             internal_error()
@@ -654,10 +1086,163 @@ fn trust_service_error_response(e: &TrustServiceError) -> axum::response::Respon
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/trust/reconfirmations",
+    tag = "Trust",
+    responses(
+        (status = 200, description = "Trust edges due for re-confirmation, oldest first", body = ReconfirmationsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API token lacks the trust:read scope"),
+    ),
+    security(("device_auth" = []), ("api_token" = []))
+)]
+async fn reconfirmations_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    auth: ReadAuth<scope::TrustRead>,
+) -> impl IntoResponse {
+    let now = Utc::now();
+    let cutoff = now - Duration::days(RECONFIRM_AFTER_DAYS);
+    match trust_repo.list_stale_edges(auth.account_id, cutoff).await {
+        Ok(rows) => {
+            let edges = rows
+                .into_iter()
+                .map(|e| {
+                    let decay = decay_factor((now - e.last_confirmed_at).num_days());
+                    StaleEdgeResponse {
+                        endorser_id: e.endorser_id,
+                        endorser_username: e.endorser_username,
+                        subject_id: e.subject_id,
+                        subject_username: e.subject_username,
+                        weight: e.weight,
+                        last_confirmed_at: e.last_confirmed_at.to_rfc3339(),
+                        decay_factor: decay,
+                        effective_weight: f64::from(e.weight) * decay,
+                    }
+                })
+                .collect();
+            (
+                StatusCode::OK,
+                Json(ReconfirmationsResponse {
+                    reconfirm_after_days: RECONFIRM_AFTER_DAYS,
+                    edges,
+                }),
+            )
+                .into_response()
+        }
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
+/// Reset the decay clock on the caller's trust edge to `subject_id`.
+///
+/// The request is signed by the endorser's device as usual; the body carries
+/// the subject's co-signature over [`reconfirmation_message`], so neither side
+/// can refresh an edge alone.
+#[utoipa::path(
+    post,
+    path = "/trust/reconfirm",
+    tag = "Trust",
+    request_body = ReconfirmRequest,
+    responses(
+        (status = 200, description = "Edge re-confirmed", body = ReconfirmResponse),
+        (status = 400, description = "Invalid request, stale timestamp, or bad co-signature"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No active trust edge to the subject"),
+    )
+)]
+async fn reconfirm_handler(
+    Extension(trust_repo): Extension<Arc<dyn TrustRepo>>,
+    Extension(identity_repo): Extension<Arc<dyn IdentityRepo>>,
+    auth_config: Option<Extension<AuthConfig>>,
+    distances: Option<Extension<Arc<TrustDistances>>>,
+    auth: AuthenticatedDevice,
+) -> impl IntoResponse {
+    let body: ReconfirmRequest = match auth.json() {
+        Ok(b) => b,
+        Err(e) => return e,
+    };
+    if body.subject_id == auth.account_id {
+        return bad_request("Cannot re-confirm an edge to yourself");
+    }
+    let auth_config = auth_config.map(|Extension(c)| c).unwrap_or_default();
+    if auth_config.timestamp_is_stale(Utc::now().timestamp(), body.timestamp) {
+        return bad_request("Timestamp out of range");
+    }
+    let Some(signature) = decode_base64url(&body.subject_signature)
+        .ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+    else {
+        return bad_request("subject_signature must be a base64url Ed25519 signature");
+    };
+
+    let device = match identity_repo.get_device_key_by_kid(&body.subject_kid).await {
+        Ok(d) if d.account_id == body.subject_id && d.revoked_at.is_none() => d,
+        Ok(_) | Err(DeviceKeyRepoError::NotFound) => {
+            return bad_request("subject_kid is not an active device of the subject");
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up subject device: {e}");
+            return internal_error();
+        }
+    };
+    let pubkey = match DevicePubkey::from_base64url(&device.device_pubkey) {
+        Ok(k) => k,
+        Err(e) => {
+            tracing::error!("Corrupted device pubkey for {}: {e}", body.subject_kid);
+            return internal_error();
+        }
+    };
+    let message = reconfirmation_message(auth.account_id, body.subject_id, body.timestamp);
+    if verify_ed25519(pubkey.as_bytes(), &message, &signature).is_err() {
+        return bad_request("Invalid subject signature");
+    }
+
+    let reconfirmation = serde_json::json!({
+        "subject_kid": body.subject_kid.to_string(),
+        "subject_signature": body.subject_signature,
+        "timestamp": body.timestamp,
+    });
+    match trust_repo
+        .reconfirm_edge(auth.account_id, body.subject_id, &reconfirmation)
+        .await
+    {
+        Ok(last_confirmed_at) => {
+            tracing::info!(
+                endorser_id = %auth.account_id,
+                subject_id = %body.subject_id,
+                "Trust edge re-confirmed"
+            );
+            // Re-confirmation resets the edge's decay, changing its weight
+            if let Some(Extension(distances)) = distances {
+                if let Err(e) = distances.mark_edge_changed(auth.account_id).await {
+                    tracing::warn!("Failed to mark trust distances dirty: {e}");
+                }
+            }
+            (
+                StatusCode::OK,
+                Json(ReconfirmResponse {
+                    subject_id: body.subject_id,
+                    last_confirmed_at: last_confirmed_at.to_rfc3339(),
+                }),
+            )
+                .into_response()
+        }
+        Err(TrustRepoError::NotFound) => not_found("No active trust edge to this subject"),
+        Err(ref e) => trust_repo_error_response(e),
+    }
+}
+
 fn trust_repo_error_response(e: &TrustRepoError) -> axum::response::Response {
     match e {
         TrustRepoError::NotFound => not_found("Not found"),
         TrustRepoError::Duplicate => conflict("Duplicate entry"),
+        TrustRepoError::InviteQuotaExhausted { allowance } => too_many_requests(&format!(
+            "Invite quota exhausted ({allowance} per {INVITE_QUOTA_WINDOW_DAYS} days)"
+        )),
+        TrustRepoError::PairingCodeLimit { limit } => {
+            too_many_requests(&format!("Pairing code limit reached ({limit} per hour)"))
+        }
         TrustRepoError::Database(ref inner) => {
             tracing::error!("Trust repo database error: {inner}");
             internal_error()
@@ -763,6 +1348,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn account_gate_maps_to_403() {
+        use crate::trust::account_gates::GateViolation;
+        assert_eq!(
+            service_status(&TrustServiceError::AccountGate(
+                GateViolation::InsufficientActivity { min_polls: 3 }
+            )),
+            StatusCode::FORBIDDEN
+        );
+    }
+
     #[test]
     fn quota_exceeded_maps_to_429() {
         assert_eq!(
@@ -861,6 +1457,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn repo_invite_quota_exhausted_maps_to_429() {
+        assert_eq!(
+            repo_status(&TrustRepoError::InviteQuotaExhausted { allowance: 2 }),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[test]
+    fn repo_pairing_code_limit_maps_to_429() {
+        assert_eq!(
+            repo_status(&TrustRepoError::PairingCodeLimit { limit: 10 }),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[test]
+    fn invite_tree_response_counts_invitees_per_depth() {
+        let root = Uuid::new_v4();
+        let edge = |inviter_id, depth| InviteTreeEdge {
+            invite_id: Uuid::new_v4(),
+            inviter_id,
+            invitee_id: Uuid::new_v4(),
+            invitee_username: "invitee".to_string(),
+            accepted_at: None,
+            depth,
+        };
+        let response = InviteTreeResponse::from_edges(
+            root,
+            3,
+            vec![edge(root, 1), edge(root, 1), edge(root, 2)],
+        );
+        assert_eq!(response.total_invitees, 3);
+        assert_eq!(response.invitees_per_depth, vec![2, 1, 0]);
+    }
+
     #[test]
     fn repo_database_error_maps_to_500() {
         assert_eq!(
@@ -868,4 +1500,18 @@ mod tests {
             StatusCode::INTERNAL_SERVER_ERROR
         );
     }
+
+    #[test]
+    fn reconfirmation_message_binds_both_parties() {
+        let a = Uuid::from_u128(1);
+        let b = Uuid::from_u128(2);
+        assert_eq!(
+            reconfirmation_message(a, b, 1_700_000_000),
+            format!("tc-trust-reconfirm:v1\n{a}\n{b}\n1700000000").into_bytes()
+        );
+        assert_ne!(
+            reconfirmation_message(a, b, 1_700_000_000),
+            reconfirmation_message(b, a, 1_700_000_000)
+        );
+    }
 }
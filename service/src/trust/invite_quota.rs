@@ -0,0 +1,102 @@
+//! Invite quotas — how many invites an account may issue.
+//!
+//! Invites are the cheapest way to grow a sybil cluster, so each account gets
+//! a small allowance per rolling window. The allowance grows with account age
+//! and with the number of active trust endorsements the account has received,
+//! and it replenishes as old invites fall out of the window.
+//!
+//! The policy here is pure; enforcement happens in the invite repo, inside the
+//! same transaction as the insert.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Length of the rolling window that invites count against.
+pub const INVITE_QUOTA_WINDOW_DAYS: i32 = 30;
+
+/// Invites every account may issue per window.
+pub const BASE_INVITE_ALLOWANCE: u32 = 2;
+
+/// Account age that earns one extra invite per window.
+const AGE_BONUS_PERIOD_DAYS: i64 = 30;
+
+/// Cap on the account-age bonus.
+pub const MAX_AGE_BONUS: u32 = 3;
+
+/// Cap on the reputation bonus (one per active trust endorsement received).
+pub const MAX_REPUTATION_BONUS: u32 = 3;
+
+/// Invites allowed per window for an account of the given age and standing.
+#[must_use]
+pub fn invite_allowance(account_age_days: i64, trust_endorsements_received: i64) -> u32 {
+    let age_bonus = u32::try_from(account_age_days.max(0) / AGE_BONUS_PERIOD_DAYS)
+        .unwrap_or(u32::MAX)
+        .min(MAX_AGE_BONUS);
+    let reputation_bonus = u32::try_from(trust_endorsements_received.max(0))
+        .unwrap_or(u32::MAX)
+        .min(MAX_REPUTATION_BONUS);
+    BASE_INVITE_ALLOWANCE + age_bonus + reputation_bonus
+}
+
+/// An account's invite quota for the current window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct InviteQuota {
+    /// Invites allowed in the current window.
+    pub allowance: u32,
+    /// Invites issued in the current window.
+    pub used: u32,
+    /// Invites still available.
+    pub remaining: u32,
+    /// Length of the rolling window in days.
+    pub window_days: i32,
+}
+
+impl InviteQuota {
+    #[must_use]
+    pub const fn new(allowance: u32, used: u32) -> Self {
+        Self {
+            allowance,
+            used,
+            remaining: allowance.saturating_sub(used),
+            window_days: INVITE_QUOTA_WINDOW_DAYS,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_accounts_get_the_base_allowance() {
+        assert_eq!(invite_allowance(0, 0), BASE_INVITE_ALLOWANCE);
+        assert_eq!(invite_allowance(29, 0), BASE_INVITE_ALLOWANCE);
+    }
+
+    #[test]
+    fn allowance_grows_with_age_and_reputation_up_to_caps() {
+        assert_eq!(invite_allowance(60, 1), BASE_INVITE_ALLOWANCE + 2 + 1);
+        assert_eq!(
+            invite_allowance(10_000, 50),
+            BASE_INVITE_ALLOWANCE + MAX_AGE_BONUS + MAX_REPUTATION_BONUS
+        );
+    }
+
+    #[test]
+    fn negative_inputs_are_treated_as_zero() {
+        assert_eq!(invite_allowance(-5, -1), BASE_INVITE_ALLOWANCE);
+    }
+
+    #[test]
+    fn quota_remaining_saturates() {
+        let quota = InviteQuota::new(2, 3);
+        assert_eq!(quota.remaining, 0);
+        assert!(quota.is_exhausted());
+        assert!(!InviteQuota::new(3, 2).is_exhausted());
+    }
+}
@@ -1,8 +1,12 @@
+pub mod account_gates;
 pub mod constraints;
+pub mod distances;
 pub mod engine;
 pub mod graph_reader;
 pub mod http;
+pub mod invite_quota;
 pub mod max_flow;
+pub mod pairing;
 pub mod repo;
 pub mod service;
 pub mod weight;
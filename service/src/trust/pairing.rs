@@ -0,0 +1,91 @@
+//! Short pairing codes and deep links for trust invites.
+//!
+//! A QR code carries the invite link, but in-app browsers often have no
+//! camera. As a fallback the endorser can show a short code the invitee
+//! types in. Codes use an alphabet without look-alike characters, live for
+//! [`PAIRING_CODE_TTL_MINUTES`], and each endorser may generate at most
+//! [`MAX_PAIRING_CODES_PER_HOUR`]. The repo enforces the limit and caps the
+//! lifetime at the invite's own expiry.
+
+use rand::Rng;
+use uuid::Uuid;
+
+/// Uppercase letters and digits without `0`, `1`, `I`, `L`, `O` and `U`.
+pub const PAIRING_CODE_ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Characters in a pairing code (30^8 ≈ 6.6 × 10^11 codes).
+pub const PAIRING_CODE_LEN: usize = 8;
+
+/// Minutes a pairing code stays valid.
+pub const PAIRING_CODE_TTL_MINUTES: i64 = 10;
+
+/// Pairing codes one endorser may generate per rolling hour.
+pub const MAX_PAIRING_CODES_PER_HOUR: i64 = 10;
+
+/// Generate a random pairing code.
+#[must_use]
+pub fn generate_pairing_code() -> String {
+    let mut rng = rand::rngs::OsRng;
+    (0..PAIRING_CODE_LEN)
+        .map(|_| char::from(PAIRING_CODE_ALPHABET[rng.gen_range(0..PAIRING_CODE_ALPHABET.len())]))
+        .collect()
+}
+
+/// Canonical form of a typed code: uppercase, with spaces and dashes
+/// removed. `None` if it cannot be a pairing code.
+#[must_use]
+pub fn normalize_pairing_code(input: &str) -> Option<String> {
+    let code: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let valid =
+        code.len() == PAIRING_CODE_LEN && code.bytes().all(|b| PAIRING_CODE_ALPHABET.contains(&b));
+    valid.then_some(code)
+}
+
+/// Link that opens the invite in the web app, matching what the QR code
+/// encodes. Root-relative when `web_url` is empty.
+#[must_use]
+pub fn invite_deep_link(web_url: &str, invite_id: Uuid) -> String {
+    format!(
+        "{}/endorse?invite={invite_id}",
+        web_url.trim_end_matches('/')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_codes_survive_normalization() {
+        let code = generate_pairing_code();
+        assert_eq!(code.len(), PAIRING_CODE_LEN);
+        assert_eq!(
+            normalize_pairing_code(&code).as_deref(),
+            Some(code.as_str())
+        );
+
+        let typed = format!("{}-{}", &code[..4], &code[4..]).to_ascii_lowercase();
+        assert_eq!(normalize_pairing_code(&typed), Some(code));
+    }
+
+    #[test]
+    fn look_alike_and_short_codes_are_rejected() {
+        assert!(normalize_pairing_code("ABCD-EFG").is_none());
+        assert!(normalize_pairing_code("ABCD-EFG0").is_none());
+        assert!(normalize_pairing_code("ABCDEFGHJ").is_none());
+    }
+
+    #[test]
+    fn deep_link_matches_qr_payload() {
+        let id = Uuid::nil();
+        assert_eq!(
+            invite_deep_link("https://tc.example/", id),
+            format!("https://tc.example/endorse?invite={id}")
+        );
+        assert_eq!(invite_deep_link("", id), format!("/endorse?invite={id}"));
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{StaleEdge, TrustRepoError};
+
+/// Most stale edges returned per account, oldest confirmation first.
+const STALE_EDGE_LIMIT: i64 = 100;
+
+pub(super) async fn list_stale_edges(
+    pool: &PgPool,
+    account_id: Uuid,
+    confirmed_before: DateTime<Utc>,
+) -> Result<Vec<StaleEdge>, TrustRepoError> {
+    let rows = sqlx::query_as::<_, StaleEdge>(
+        "SELECT e.endorser_id, ea.username AS endorser_username, \
+                e.subject_id, sa.username AS subject_username, \
+                e.weight, e.last_confirmed_at \
+         FROM reputation__endorsements e \
+         JOIN accounts ea ON ea.id = e.endorser_id \
+         JOIN accounts sa ON sa.id = e.subject_id \
+         WHERE e.topic = 'trust' \
+           AND e.revoked_at IS NULL \
+           AND e.in_slot = true \
+           AND (e.endorser_id = $1 OR e.subject_id = $1) \
+           AND e.last_confirmed_at < $2 \
+         ORDER BY e.last_confirmed_at ASC \
+         LIMIT $3",
+    )
+    .bind(account_id)
+    .bind(confirmed_before)
+    .bind(STALE_EDGE_LIMIT)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Reset `last_confirmed_at` on the active trust edge `endorser_id → subject_id`.
+///
+/// The original attestation is kept; `reconfirmation` replaces the previous
+/// re-confirmation entry under the `reconfirmation` key.
+pub(super) async fn reconfirm_edge(
+    pool: &PgPool,
+    endorser_id: Uuid,
+    subject_id: Uuid,
+    reconfirmation: &serde_json::Value,
+) -> Result<DateTime<Utc>, TrustRepoError> {
+    sqlx::query_scalar::<_, DateTime<Utc>>(
+        "UPDATE reputation__endorsements \
+         SET last_confirmed_at = now(), \
+             attestation = COALESCE(attestation, '{}'::jsonb) \
+                 || jsonb_build_object('reconfirmation', $3::jsonb) \
+         WHERE endorser_id = $1 AND subject_id = $2 \
+           AND topic = 'trust' AND revoked_at IS NULL \
+         RETURNING last_confirmed_at",
+    )
+    .bind(endorser_id)
+    .bind(subject_id)
+    .bind(reconfirmation)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(TrustRepoError::NotFound)
+}
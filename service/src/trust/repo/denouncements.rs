@@ -2,6 +2,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use super::{DenouncementRecord, TrustRepoError};
+use crate::db::map_unique_violation;
 
 pub(super) async fn create_denouncement<'e, E>(
     executor: E,
@@ -23,12 +24,9 @@ where
     .fetch_one(executor)
     .await
     .map_err(|e| {
-        if let sqlx::Error::Database(ref db_err) = e {
-            if db_err.constraint() == Some("uq_denouncement_accuser_target") {
-                return TrustRepoError::Duplicate;
-            }
-        }
-        TrustRepoError::Database(e)
+        map_unique_violation(e, |constraint| {
+            (constraint == "uq_denouncement_accuser_target").then_some(TrustRepoError::Duplicate)
+        })
     })
 }
 
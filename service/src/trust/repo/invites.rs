@@ -1,9 +1,58 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use super::{InviteRecord, TrustRepoError};
+use super::{InviteRecord, InviteTreeEdge, PairingCodeRecord, TrustRepoError};
+use crate::db::map_unique_violation;
+use crate::trust::invite_quota::{invite_allowance, InviteQuota, INVITE_QUOTA_WINDOW_DAYS};
+use crate::trust::pairing::MAX_PAIRING_CODES_PER_HOUR;
 use crate::trust::weight::{DeliveryMethod, RelationshipDepth};
 
+#[derive(sqlx::FromRow)]
+struct InviteQuotaRow {
+    age_days: i64,
+    endorsements_received: i64,
+    used: i64,
+}
+
+impl From<InviteQuotaRow> for InviteQuota {
+    fn from(row: InviteQuotaRow) -> Self {
+        Self::new(
+            invite_allowance(row.age_days, row.endorsements_received),
+            u32::try_from(row.used).unwrap_or(u32::MAX),
+        )
+    }
+}
+
+const INVITE_QUOTA_SQL: &str = "\
+    SELECT \
+      EXTRACT(DAY FROM now() - a.created_at)::BIGINT AS age_days, \
+      (SELECT COUNT(*) FROM reputation__endorsements e \
+        WHERE e.subject_id = a.id AND e.topic = 'trust' AND e.revoked_at IS NULL) \
+        AS endorsements_received, \
+      (SELECT COUNT(*) FROM trust__invites i \
+        WHERE i.endorser_id = a.id AND i.created_at > now() - make_interval(days => $2)) \
+        AS used \
+    FROM accounts a \
+    WHERE a.id = $1";
+
+/// Current invite quota for `user_id`.
+pub(super) async fn get_invite_quota(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<InviteQuota, TrustRepoError> {
+    sqlx::query_as::<_, InviteQuotaRow>(INVITE_QUOTA_SQL)
+        .bind(user_id)
+        .bind(INVITE_QUOTA_WINDOW_DAYS)
+        .fetch_optional(pool)
+        .await?
+        .map(InviteQuota::from)
+        .ok_or(TrustRepoError::NotFound)
+}
+
+/// Create an invite, consuming one unit of the endorser's invite quota.
+///
+/// The endorser's account row is locked for the duration of the transaction
+/// so concurrent requests cannot both spend the last remaining invite.
 #[allow(clippy::too_many_arguments)]
 pub(super) async fn create_invite(
     pool: &PgPool,
@@ -15,6 +64,22 @@ pub(super) async fn create_invite(
     attestation: &serde_json::Value,
     expires_at: chrono::DateTime<chrono::Utc>,
 ) -> Result<InviteRecord, TrustRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let quota: InviteQuota =
+        sqlx::query_as::<_, InviteQuotaRow>(&format!("{INVITE_QUOTA_SQL} FOR UPDATE OF a"))
+            .bind(endorser_id)
+            .bind(INVITE_QUOTA_WINDOW_DAYS)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(TrustRepoError::NotFound)?
+            .into();
+    if quota.is_exhausted() {
+        return Err(TrustRepoError::InviteQuotaExhausted {
+            allowance: quota.allowance,
+        });
+    }
+
     let record = sqlx::query_as::<_, InviteRecord>(
         "INSERT INTO trust__invites \
          (endorser_id, envelope, delivery_method, relationship_depth, weight, attestation, expires_at) \
@@ -28,9 +93,10 @@ pub(super) async fn create_invite(
     .bind(weight)
     .bind(attestation)
     .bind(expires_at)
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    tx.commit().await?;
     Ok(record)
 }
 
@@ -80,3 +146,110 @@ pub(super) async fn list_invites_by_endorser(
 
     Ok(records)
 }
+
+/// Accepted invites reachable downstream of `root_id`, breadth-first.
+///
+/// Each edge is an accepted invite; `depth` is 1 for invites issued by the
+/// root itself. Accounts already visited are not expanded again.
+pub(super) async fn get_invite_tree(
+    pool: &PgPool,
+    root_id: Uuid,
+    max_depth: i32,
+) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+    let edges = sqlx::query_as::<_, InviteTreeEdge>(
+        "WITH RECURSIVE tree AS ( \
+           SELECT i.id AS invite_id, i.endorser_id AS inviter_id, i.accepted_by AS invitee_id, \
+                  i.accepted_at, 1 AS depth, ARRAY[i.endorser_id, i.accepted_by] AS path \
+           FROM trust__invites i \
+           WHERE i.endorser_id = $1 AND i.accepted_by IS NOT NULL \
+           UNION ALL \
+           SELECT i.id, i.endorser_id, i.accepted_by, i.accepted_at, t.depth + 1, \
+                  t.path || i.accepted_by \
+           FROM trust__invites i \
+           JOIN tree t ON i.endorser_id = t.invitee_id \
+           WHERE i.accepted_by IS NOT NULL \
+             AND t.depth < $2 \
+             AND NOT i.accepted_by = ANY(t.path) \
+         ) \
+         SELECT t.invite_id, t.inviter_id, t.invitee_id, a.username AS invitee_username, \
+                t.accepted_at, t.depth \
+         FROM tree t \
+         JOIN accounts a ON a.id = t.invitee_id \
+         ORDER BY t.depth, t.accepted_at",
+    )
+    .bind(root_id)
+    .bind(max_depth)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(edges)
+}
+
+/// Issue a pairing code for an open invite.
+///
+/// The endorser's account row is locked so concurrent requests cannot
+/// exceed [`MAX_PAIRING_CODES_PER_HOUR`].
+pub(super) async fn create_pairing_code(
+    pool: &PgPool,
+    endorser_id: Uuid,
+    invite_id: Uuid,
+    code: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> Result<PairingCodeRecord, TrustRepoError> {
+    let mut tx = pool.begin().await?;
+
+    let issued: Option<i64> = sqlx::query_scalar(
+        "SELECT (SELECT COUNT(*) FROM trust__invite_pairing_codes \
+                 WHERE endorser_id = a.id AND created_at > now() - interval '1 hour') \
+         FROM accounts a WHERE a.id = $1 FOR UPDATE OF a",
+    )
+    .bind(endorser_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if issued.ok_or(TrustRepoError::NotFound)? >= MAX_PAIRING_CODES_PER_HOUR {
+        return Err(TrustRepoError::PairingCodeLimit {
+            limit: MAX_PAIRING_CODES_PER_HOUR,
+        });
+    }
+
+    let record = sqlx::query_as::<_, PairingCodeRecord>(
+        "INSERT INTO trust__invite_pairing_codes (code, invite_id, endorser_id, expires_at) \
+         SELECT $1, i.id, i.endorser_id, LEAST($4, i.expires_at) \
+         FROM trust__invites i \
+         WHERE i.id = $2 AND i.endorser_id = $3 \
+           AND i.accepted_by IS NULL AND i.expires_at > now() \
+         RETURNING code, invite_id, expires_at",
+    )
+    .bind(code)
+    .bind(invite_id)
+    .bind(endorser_id)
+    .bind(expires_at)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        map_unique_violation(e, |constraint| {
+            (constraint == "trust__invite_pairing_codes_pkey").then_some(TrustRepoError::Duplicate)
+        })
+    })?
+    .ok_or(TrustRepoError::NotFound)?;
+
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// The open invite an unexpired pairing code points at.
+pub(super) async fn resolve_pairing_code(
+    pool: &PgPool,
+    code: &str,
+) -> Result<Uuid, TrustRepoError> {
+    sqlx::query_scalar(
+        "SELECT c.invite_id FROM trust__invite_pairing_codes c \
+         JOIN trust__invites i ON i.id = c.invite_id \
+         WHERE c.code = $1 AND c.expires_at > now() \
+           AND i.accepted_by IS NULL AND i.expires_at > now()",
+    )
+    .bind(code)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(TrustRepoError::NotFound)
+}
@@ -1,6 +1,7 @@
 //! Repository layer for trust persistence
 
 pub mod action_queue;
+pub mod confirmations;
 pub mod denouncements;
 pub mod influence;
 pub mod invites;
@@ -10,9 +11,12 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::invite_quota::InviteQuota;
 use super::service::ActionType;
 use super::weight::{DeliveryMethod, RelationshipDepth};
 
+use crate::db::{RepoError, RepoErrorKind};
+
 /// Error type for trust repository operations.
 #[derive(Debug, thiserror::Error)]
 pub enum TrustRepoError {
@@ -20,10 +24,27 @@ pub enum TrustRepoError {
     NotFound,
     #[error("duplicate")]
     Duplicate,
+    #[error("invite quota exhausted ({allowance} per window)")]
+    InviteQuotaExhausted { allowance: u32 },
+    #[error("pairing code limit reached ({limit} per hour)")]
+    PairingCodeLimit { limit: i64 },
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
 }
 
+impl RepoError for TrustRepoError {
+    fn kind(&self) -> RepoErrorKind {
+        match self {
+            Self::NotFound => RepoErrorKind::NotFound,
+            Self::Duplicate => RepoErrorKind::Conflict,
+            Self::InviteQuotaExhausted { .. } | Self::PairingCodeLimit { .. } => {
+                RepoErrorKind::LimitReached
+            }
+            Self::Database(_) => RepoErrorKind::Database,
+        }
+    }
+}
+
 /// Influence balance for a user.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct InfluenceRecord {
@@ -87,6 +108,26 @@ pub struct InviteRecord {
     pub weight: f32,
 }
 
+/// A short code standing in for an invite link.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PairingCodeRecord {
+    pub code: String,
+    pub invite_id: Uuid,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// One accepted invite in an inviter→invitee tree.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct InviteTreeEdge {
+    pub invite_id: Uuid,
+    pub inviter_id: Uuid,
+    pub invitee_id: Uuid,
+    pub invitee_username: String,
+    pub accepted_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Hops from the tree root; 1 for invites the root issued directly.
+    pub depth: i32,
+}
+
 /// A cached trust score snapshot for a user.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct ScoreSnapshot {
@@ -98,6 +139,17 @@ pub struct ScoreSnapshot {
     pub computed_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// An active trust edge due for re-confirmation.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StaleEdge {
+    pub endorser_id: Uuid,
+    pub endorser_username: String,
+    pub subject_id: Uuid,
+    pub subject_username: String,
+    pub weight: f32,
+    pub last_confirmed_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Consolidated repository trait for trust persistence.
 #[allow(clippy::too_many_arguments)]
 #[async_trait]
@@ -202,6 +254,51 @@ pub trait TrustRepo: Send + Sync {
         endorser_id: Uuid,
     ) -> Result<Vec<InviteRecord>, TrustRepoError>;
 
+    /// Current invite quota for `user_id`. `create_invite` enforces the same
+    /// quota transactionally and fails with `InviteQuotaExhausted`.
+    async fn get_invite_quota(&self, user_id: Uuid) -> Result<InviteQuota, TrustRepoError>;
+
+    /// Accepted invites downstream of `root_id`, up to `max_depth` hops.
+    async fn get_invite_tree(
+        &self,
+        root_id: Uuid,
+        max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError>;
+
+    /// Issue a pairing code for an open invite owned by `endorser_id`. It
+    /// expires at `expires_at` or with the invite, whichever comes first;
+    /// fails with `PairingCodeLimit` past the hourly limit.
+    async fn create_pairing_code(
+        &self,
+        endorser_id: Uuid,
+        invite_id: Uuid,
+        code: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError>;
+
+    /// The still-open invite an unexpired pairing code points at.
+    async fn resolve_pairing_code(&self, code: &str) -> Result<Uuid, TrustRepoError>;
+
+    // Edge confirmation operations
+
+    /// Active in-slot trust edges touching `account_id` (as endorser or
+    /// subject) last confirmed before `confirmed_before`, oldest first.
+    async fn list_stale_edges(
+        &self,
+        account_id: Uuid,
+        confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError>;
+
+    /// Mark the active trust edge `endorser_id → subject_id` as confirmed now,
+    /// recording `reconfirmation` in its attestation. Returns the new
+    /// `last_confirmed_at`, or `NotFound` if no such edge is active.
+    async fn reconfirm_edge(
+        &self,
+        endorser_id: Uuid,
+        subject_id: Uuid,
+        reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError>;
+
     // Score snapshot operations
 
     #[allow(clippy::too_many_arguments)]
@@ -376,6 +473,49 @@ impl TrustRepo for PgTrustRepo {
         invites::list_invites_by_endorser(&self.pool, endorser_id).await
     }
 
+    async fn get_invite_quota(&self, user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        invites::get_invite_quota(&self.pool, user_id).await
+    }
+
+    async fn get_invite_tree(
+        &self,
+        root_id: Uuid,
+        max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        invites::get_invite_tree(&self.pool, root_id, max_depth).await
+    }
+
+    async fn create_pairing_code(
+        &self,
+        endorser_id: Uuid,
+        invite_id: Uuid,
+        code: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        invites::create_pairing_code(&self.pool, endorser_id, invite_id, code, expires_at).await
+    }
+
+    async fn resolve_pairing_code(&self, code: &str) -> Result<Uuid, TrustRepoError> {
+        invites::resolve_pairing_code(&self.pool, code).await
+    }
+
+    async fn list_stale_edges(
+        &self,
+        account_id: Uuid,
+        confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        confirmations::list_stale_edges(&self.pool, account_id, confirmed_before).await
+    }
+
+    async fn reconfirm_edge(
+        &self,
+        endorser_id: Uuid,
+        subject_id: Uuid,
+        reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        confirmations::reconfirm_edge(&self.pool, endorser_id, subject_id, reconfirmation).await
+    }
+
     async fn upsert_score(
         &self,
         user_id: Uuid,
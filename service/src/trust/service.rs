@@ -7,6 +7,7 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::reputation::repo::ReputationRepo;
+use crate::trust::account_gates::{AccountGateError, AccountGates, GateViolation, GatedAction};
 use crate::trust::repo::{TrustRepo, TrustRepoError};
 /// Demo endorsement slot limit per user (k=3).
 pub const ENDORSEMENT_SLOT_LIMIT: u32 = 3;
@@ -92,6 +93,9 @@ pub enum TrustServiceError {
     #[error("reason must be between 1 and {max} characters")]
     InvalidReason { max: usize },
 
+    #[error(transparent)]
+    AccountGate(#[from] GateViolation),
+
     #[error("repository error: {0}")]
     Repo(#[from] TrustRepoError),
 
@@ -99,6 +103,16 @@ pub enum TrustServiceError {
     EndorsementRepo(#[from] crate::reputation::repo::EndorsementRepoError),
 }
 
+impl From<AccountGateError> for TrustServiceError {
+    fn from(e: AccountGateError) -> Self {
+        match e {
+            AccountGateError::Violation(v) => Self::AccountGate(v),
+            AccountGateError::AccountNotFound => Self::Repo(TrustRepoError::NotFound),
+            AccountGateError::Database(db) => Self::Repo(TrustRepoError::Database(db)),
+        }
+    }
+}
+
 /// Service trait for trust action orchestration.
 #[async_trait]
 pub trait TrustService: Send + Sync {
@@ -137,6 +151,8 @@ pub struct DefaultTrustService {
     max_denouncement_slots: u32, // d=2
     /// Max actions per day (resets at midnight UTC)
     daily_quota: i64, // 5
+    /// Minimum account age and activity per action; `None` disables gating
+    account_gates: Option<Arc<AccountGates>>,
 }
 
 #[cfg(test)]
@@ -293,13 +309,21 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use uuid::Uuid;
 
+    use crate::pagination::KeysetPage;
     use crate::reputation::repo::{
-        CreatedEndorsement, EndorsementRecord, EndorsementRepoError, ExternalIdentityRecord,
-        ExternalIdentityRepoError, ReputationRepo,
+        CreatedEndorsement, DisputeRecord, DisputeRepoError, EndorsementRecord,
+        EndorsementRepoError, ExternalIdentityRecord, ExternalIdentityRepoError,
+        InPersonAttestation, IssuedEndorsement, NewChallenge, NewDispute, NewStrike,
+        ReputationRepo, StrikeEventRecord, StrikeRecord, StrikeRepoError, TopicCount,
+        VerificationRecord, VerificationRepoError,
     };
+    use crate::reputation::strikes::AppealStatus;
+    use crate::reputation::verification::VerificationMethod;
+    use crate::reputation::DisputeStatus;
+    use crate::trust::invite_quota::InviteQuota;
     use crate::trust::repo::{
         ActionRecord, DenouncementRecord, DenouncementWithUsername, InfluenceRecord, InviteRecord,
-        ScoreSnapshot, TrustRepo, TrustRepoError,
+        InviteTreeEdge, PairingCodeRecord, ScoreSnapshot, StaleEdge, TrustRepo, TrustRepoError,
     };
     use crate::trust::weight::{DeliveryMethod, RelationshipDepth};
 
@@ -408,6 +432,12 @@ mod tests {
         ) -> Result<CreatedEndorsement, EndorsementRepoError> {
             unimplemented!()
         }
+        async fn create_issued_endorsement(
+            &self,
+            _: &IssuedEndorsement<'_>,
+        ) -> Result<CreatedEndorsement, EndorsementRepoError> {
+            unimplemented!()
+        }
         async fn count_all_active_trust_endorsements_by(
             &self,
             _: Uuid,
@@ -420,6 +450,19 @@ mod tests {
         ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
             unimplemented!()
         }
+        async fn list_endorsements_by_subject_page(
+            &self,
+            _: Uuid,
+            _: &KeysetPage,
+        ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
+            unimplemented!()
+        }
+        async fn count_active_endorsements_by_topic(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<TopicCount>, EndorsementRepoError> {
+            unimplemented!()
+        }
         async fn revoke_endorsement(
             &self,
             _: Uuid,
@@ -428,6 +471,107 @@ mod tests {
         ) -> Result<(), EndorsementRepoError> {
             unimplemented!()
         }
+        async fn open_dispute(
+            &self,
+            _dispute: &NewDispute<'_>,
+        ) -> Result<DisputeRecord, DisputeRepoError> {
+            unimplemented!()
+        }
+        async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+            unimplemented!()
+        }
+        async fn resolve_dispute(
+            &self,
+            _endorsement_id: Uuid,
+            _outcome: DisputeStatus,
+            _resolved_by: Uuid,
+            _note: Option<&str>,
+        ) -> Result<DisputeRecord, DisputeRepoError> {
+            unimplemented!()
+        }
+        async fn issue_strike(
+            &self,
+            _strike: &NewStrike<'_>,
+        ) -> Result<StrikeRecord, StrikeRepoError> {
+            unimplemented!()
+        }
+        async fn list_strikes_for_account(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+            unimplemented!()
+        }
+        async fn list_strike_events_for_account(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+            unimplemented!()
+        }
+        async fn appeal_strike(
+            &self,
+            _strike_id: Uuid,
+            _account_id: Uuid,
+            _statement: &str,
+        ) -> Result<StrikeRecord, StrikeRepoError> {
+            unimplemented!()
+        }
+        async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+            unimplemented!()
+        }
+        async fn resolve_appeal(
+            &self,
+            _strike_id: Uuid,
+            _outcome: AppealStatus,
+            _resolved_by: Uuid,
+            _note: Option<&str>,
+        ) -> Result<StrikeRecord, StrikeRepoError> {
+            unimplemented!()
+        }
+        async fn list_verifications(
+            &self,
+            _account_id: Uuid,
+        ) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+            unimplemented!()
+        }
+        async fn start_verification_challenge(
+            &self,
+            _challenge: &NewChallenge<'_>,
+        ) -> Result<(), VerificationRepoError> {
+            unimplemented!()
+        }
+        async fn confirm_verification_challenge(
+            &self,
+            _account_id: Uuid,
+            _method: VerificationMethod,
+            _code_hash: &[u8],
+            _max_attempts: i32,
+            _provider: &str,
+        ) -> Result<VerificationRecord, VerificationRepoError> {
+            unimplemented!()
+        }
+        async fn record_verification(
+            &self,
+            _account_id: Uuid,
+            _method: VerificationMethod,
+            _provider: &str,
+            _destination_hash: Option<&[u8]>,
+        ) -> Result<VerificationRecord, VerificationRepoError> {
+            unimplemented!()
+        }
+        async fn attest_in_person(
+            &self,
+            _subject_id: Uuid,
+            _attester_id: Uuid,
+            _required: i64,
+        ) -> Result<InPersonAttestation, VerificationRepoError> {
+            unimplemented!()
+        }
+        async fn count_in_person_attestations(
+            &self,
+            _subject_id: Uuid,
+        ) -> Result<i64, VerificationRepoError> {
+            unimplemented!()
+        }
         async fn link_external_identity(
             &self,
             _: Uuid,
@@ -700,6 +844,43 @@ mod tests {
         ) -> Result<Vec<InviteRecord>, TrustRepoError> {
             unimplemented!()
         }
+        async fn get_invite_quota(&self, _: Uuid) -> Result<InviteQuota, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn get_invite_tree(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn create_pairing_code(
+            &self,
+            _: Uuid,
+            _: Uuid,
+            _: &str,
+            _: chrono::DateTime<chrono::Utc>,
+        ) -> Result<PairingCodeRecord, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn resolve_pairing_code(&self, _: &str) -> Result<Uuid, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn list_stale_edges(
+            &self,
+            _: Uuid,
+            _: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+            unimplemented!()
+        }
+        async fn reconfirm_edge(
+            &self,
+            _: Uuid,
+            _: Uuid,
+            _: &serde_json::Value,
+        ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+            unimplemented!()
+        }
         async fn upsert_score(
             &self,
             _: Uuid,
@@ -762,6 +943,45 @@ mod tests {
         assert!(matches!(err, TrustServiceError::SelfAction));
     }
 
+    #[tokio::test]
+    async fn endorse_rejected_for_account_below_age_gate() {
+        use crate::config::{AccountGatesConfig, ActionRequirement};
+        use crate::trust::account_gates::{AccountStanding, AccountStandingReader};
+
+        struct NewAccount;
+
+        #[async_trait]
+        impl AccountStandingReader for NewAccount {
+            async fn standing(&self, _: Uuid) -> Result<Option<AccountStanding>, sqlx::Error> {
+                Ok(Some(AccountStanding {
+                    created_at: chrono::Utc::now(),
+                    polls_voted: 0,
+                    verification: None,
+                }))
+            }
+        }
+
+        let config = AccountGatesConfig {
+            endorse: ActionRequirement {
+                min_account_age_days: 7,
+                min_polls_voted: 0,
+                min_verification: None,
+            },
+            ..AccountGatesConfig::default()
+        };
+        // The stub repo panics on quota and slot lookups, so the gate must run first
+        let svc = make_service()
+            .with_account_gates(Arc::new(AccountGates::new(config, Arc::new(NewAccount))));
+        let err = svc
+            .endorse(Uuid::new_v4(), Uuid::new_v4(), 0.5, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            TrustServiceError::AccountGate(GateViolation::AccountTooNew { min_days: 7 })
+        ));
+    }
+
     #[tokio::test]
     async fn endorse_returns_invalid_weight_for_zero() {
         let a = Uuid::new_v4();
@@ -2170,6 +2390,25 @@ impl DefaultTrustService {
             endorsement_slots: ENDORSEMENT_SLOT_LIMIT,
             max_denouncement_slots: DENOUNCEMENT_SLOT_LIMIT,
             daily_quota: DAILY_ACTION_QUOTA,
+            account_gates: None,
+        }
+    }
+
+    /// Enforce per-action account age and activity requirements.
+    #[must_use]
+    pub fn with_account_gates(mut self, gates: Arc<AccountGates>) -> Self {
+        self.account_gates = Some(gates);
+        self
+    }
+
+    async fn check_account_gate(
+        &self,
+        action: GatedAction,
+        actor_id: Uuid,
+    ) -> Result<(), TrustServiceError> {
+        match &self.account_gates {
+            Some(gates) => Ok(gates.check(action, actor_id).await?),
+            None => Ok(()),
         }
     }
 
@@ -2204,6 +2443,8 @@ impl TrustService for DefaultTrustService {
             return Err(TrustServiceError::InvalidWeight);
         }
 
+        self.check_account_gate(GatedAction::Endorse, endorser_id)
+            .await?;
         self.check_daily_quota(endorser_id).await?;
 
         // Denouncement and endorsement are mutually exclusive: cannot endorse
@@ -2280,6 +2521,9 @@ impl TrustService for DefaultTrustService {
             });
         }
 
+        self.check_account_gate(GatedAction::Denounce, accuser_id)
+            .await?;
+
         // Cannot file a denouncement against someone already denounced. This mirrors
         // the DenouncementConflict check on the endorse path and prevents the user
         // from wasting their daily quota on an action the worker will silently reject.
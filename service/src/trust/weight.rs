@@ -2,6 +2,9 @@
 //!
 //! Final weight = `base_weight(delivery_method)` × `depth_multiplier(relationship_depth)`,
 //! clamped to (0, 1.0].
+//!
+//! Trust-weighted computations use the *effective* weight: the stored weight
+//! × [`decay_factor`] of the time since the edge was last confirmed.
 
 /// Delivery method for a trust invite.
 ///
@@ -84,6 +87,34 @@ pub fn compute_endorsement_weight(
     raw.clamp(f32::MIN_POSITIVE, 1.0)
 }
 
+/// Days without re-confirmation before an edge's effective weight halves
+/// (ADR-025).
+pub const DECAY_STEP_DAYS: i64 = 365;
+
+/// Days without re-confirmation after which an edge drops out of trust
+/// computations entirely.
+pub const DECAY_EXPIRY_DAYS: i64 = 2 * DECAY_STEP_DAYS;
+
+/// Edges are surfaced for re-confirmation this long after their last
+/// confirmation, a month ahead of the first decay step.
+pub const RECONFIRM_AFTER_DAYS: i64 = DECAY_STEP_DAYS - 30;
+
+/// Decay multiplier for an edge last confirmed `age_days` whole days ago:
+/// 1.0 for the first year, 0.5 for the second, then 0.0.
+///
+/// Mirrors the SQL in
+/// [`crate::trust::engine::TrustEngine::compute_distances_from`].
+#[must_use]
+pub const fn decay_factor(age_days: i64) -> f64 {
+    if age_days < DECAY_STEP_DAYS {
+        1.0
+    } else if age_days < DECAY_EXPIRY_DAYS {
+        0.5
+    } else {
+        0.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +181,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decay_steps_down_yearly() {
+        assert!((decay_factor(-1) - 1.0).abs() < f64::EPSILON);
+        assert!((decay_factor(DECAY_STEP_DAYS - 1) - 1.0).abs() < f64::EPSILON);
+        assert!((decay_factor(DECAY_STEP_DAYS) - 0.5).abs() < f64::EPSILON);
+        assert!((decay_factor(DECAY_EXPIRY_DAYS - 1) - 0.5).abs() < f64::EPSILON);
+        assert!(decay_factor(DECAY_EXPIRY_DAYS).abs() < f64::EPSILON);
+        assert!((decay_factor(RECONFIRM_AFTER_DAYS) - 1.0).abs() < f64::EPSILON);
+    }
+
     /// Verify the DB-facing string representation for each `DeliveryMethod` variant.
     ///
     /// These strings must match the `trust_invites.delivery_method` CHECK constraint.
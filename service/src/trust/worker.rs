@@ -10,6 +10,7 @@ use tc_engine_polling::repo::pgmq;
 
 use crate::reputation::repo::EndorsementRepoError;
 use crate::reputation::repo::ReputationRepo;
+use crate::trust::distances::TrustDistances;
 use crate::trust::engine::TrustEngine;
 use crate::trust::engine::TrustEngineError;
 use crate::trust::repo::action_queue::QUEUE_NAME;
@@ -56,6 +57,7 @@ pub struct TrustWorker {
     trust_repo: Arc<dyn TrustRepo>,
     reputation_repo: Arc<dyn ReputationRepo>,
     trust_engine: Arc<TrustEngine>,
+    distances: Option<Arc<TrustDistances>>,
 }
 
 impl TrustWorker {
@@ -72,9 +74,17 @@ impl TrustWorker {
             trust_repo,
             reputation_repo,
             trust_engine,
+            distances: None,
         }
     }
 
+    /// Mark materialized trust distances dirty after each processed action.
+    #[must_use]
+    pub fn with_distances(mut self, distances: Arc<TrustDistances>) -> Self {
+        self.distances = Some(distances);
+        self
+    }
+
     /// Enqueue pgmq messages for any pre-migration `status = 'pending'` rows.
     ///
     /// Run once at startup to recover actions that were inserted before the
@@ -282,6 +292,18 @@ impl TrustWorker {
             }
         }
 
+        // Every action changes an edge out of the actor. The edge is already
+        // written, so a failure here only delays the distance refresh until
+        // the source ages out; don't fail the action over it.
+        if let Some(distances) = &self.distances {
+            if let Err(e) = distances.mark_edge_changed(action.actor_id).await {
+                tracing::warn!(
+                    actor_id = %action.actor_id,
+                    "trust worker: failed to mark trust distances dirty: {e}"
+                );
+            }
+        }
+
         Ok(())
     }
 }
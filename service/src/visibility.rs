@@ -0,0 +1,103 @@
+//! Per-field visibility of account data by the viewer's relationship to it.
+//!
+//! Responses about an account show more to its owner than to anyone else.
+//! A handler resolves the caller's [`Viewer`] once and calls
+//! [`Redact::redact`] on the response before serializing it. Each response
+//! type decides in its `Redact` impl which fields each relationship may see;
+//! hidden fields become `None` and are left out of the JSON.
+//!
+//! Current rules:
+//!
+//! | Response | Field | Visible to |
+//! |---|---|---|
+//! | `GET /accounts/{id}` | `devices` | members |
+//! | | device `device_name`, `revoked_at` | moderators |
+//! | | device `last_used_at` | owner |
+//! | `GET /accounts/{id}/verifications` | `provider`, `verified_at` | moderators |
+//! | `GET /reputation/{id}/explanation` | strike reason in `source` | moderators |
+//!
+//! Moderators are the accounts in `accounts.admin_account_ids`; anything a
+//! moderator sees, the owner sees too.
+
+use uuid::Uuid;
+
+use crate::config::AccountsConfig;
+
+/// How the caller relates to the account a response describes, from least
+/// to most privileged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Viewer {
+    /// No authenticated caller.
+    Anonymous,
+    /// Another signed-in account.
+    Member,
+    /// An account moderator looking at someone else's account.
+    Moderator,
+    /// The account itself.
+    Owner,
+}
+
+impl Viewer {
+    /// Relationship of `caller` to `subject`.
+    #[must_use]
+    pub fn resolve(accounts: &AccountsConfig, caller: Option<Uuid>, subject: Uuid) -> Self {
+        match caller {
+            None => Self::Anonymous,
+            Some(caller) if caller == subject => Self::Owner,
+            Some(caller) if accounts.is_admin(caller) => Self::Moderator,
+            Some(_) => Self::Member,
+        }
+    }
+
+    /// `value` if this viewer is at least `min`, otherwise `None`.
+    #[must_use]
+    pub fn reveal<T>(self, min: Self, value: Option<T>) -> Option<T> {
+        value.filter(|_| self >= min)
+    }
+}
+
+/// A response with fields that depend on who is looking.
+pub trait Redact {
+    /// Drop whatever `viewer` may not see.
+    #[must_use]
+    fn redact(self, viewer: Viewer) -> Self;
+}
+
+impl<T: Redact> Redact for Vec<T> {
+    fn redact(self, viewer: Viewer) -> Self {
+        self.into_iter().map(|item| item.redact(viewer)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveal_respects_privilege_order() {
+        assert_eq!(Viewer::Owner.reveal(Viewer::Moderator, Some(1)), Some(1));
+        assert_eq!(
+            Viewer::Moderator.reveal(Viewer::Moderator, Some(1)),
+            Some(1)
+        );
+        assert_eq!(Viewer::Member.reveal(Viewer::Moderator, Some(1)), None);
+        assert_eq!(Viewer::Anonymous.reveal(Viewer::Member, Some(1)), None);
+        assert_eq!(Viewer::Owner.reveal::<i32>(Viewer::Member, None), None);
+    }
+
+    #[test]
+    fn resolve_ranks_moderators_below_owners() {
+        let (me, you) = (Uuid::new_v4(), Uuid::new_v4());
+        let accounts = AccountsConfig {
+            admin_account_ids: vec![you],
+        };
+        assert_eq!(Viewer::resolve(&accounts, None, me), Viewer::Anonymous);
+        assert_eq!(Viewer::resolve(&accounts, Some(me), me), Viewer::Owner);
+        assert_eq!(Viewer::resolve(&accounts, Some(you), me), Viewer::Moderator);
+        assert_eq!(Viewer::resolve(&accounts, Some(you), you), Viewer::Owner);
+        assert_eq!(
+            Viewer::resolve(&AccountsConfig::default(), Some(you), me),
+            Viewer::Member
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Integration tests for the scheduled Parquet analytics export.
+
+mod common;
+
+use arrow_array::cast::AsArray;
+use axum::body::Bytes;
+use common::factories::AccountFactory;
+use common::test_db::isolated_db;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::analytics::sink::MemorySink;
+use tinycongress_api::analytics::worker::run_export;
+use tinycongress_api::analytics::ExportDataset;
+use tinycongress_api::config::AnalyticsExportConfig;
+use uuid::Uuid;
+
+/// Every value of a string column in an exported Parquet file.
+fn read_column(body: &[u8], column: &str) -> Vec<String> {
+    let builder =
+        ParquetRecordBatchReaderBuilder::try_new(Bytes::copy_from_slice(body)).expect("reader");
+    let index = builder.schema().index_of(column).expect("column");
+    builder
+        .build()
+        .expect("reader")
+        .flat_map(|batch| {
+            batch
+                .expect("batch")
+                .column(index)
+                .as_string::<i32>()
+                .iter()
+                .map(|v| v.unwrap_or_default().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[shared_runtime_test]
+async fn test_export_writes_every_dataset_in_pages() {
+    let db = isolated_db().await;
+    let pool = db.pool().clone();
+    let alice = AccountFactory::new()
+        .with_seed(200)
+        .create(&pool)
+        .await
+        .expect("create alice");
+    let bob = AccountFactory::new()
+        .with_seed(201)
+        .create(&pool)
+        .await
+        .expect("create bob");
+
+    let mut exported = Vec::new();
+    for (endorser, subject, topic, visibility, revoked) in [
+        (alice.id, bob.id, "trust", "public", false),
+        (bob.id, alice.id, "civics", "subject_only", true),
+        (alice.id, bob.id, "secret", "private", false),
+    ] {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO reputation__endorsements \
+                 (endorser_id, subject_id, topic, weight, visibility, revoked_at) \
+             VALUES ($1, $2, $3, 1.0, $4, CASE WHEN $5 THEN now() END) RETURNING id",
+        )
+        .bind(endorser)
+        .bind(subject)
+        .bind(topic)
+        .bind(visibility)
+        .bind(revoked)
+        .fetch_one(&pool)
+        .await
+        .expect("insert endorsement");
+        if visibility != "private" {
+            exported.push(id.to_string());
+        }
+    }
+    for (account, seqno) in [(alice.id, 1_i64), (alice.id, 2), (bob.id, 1)] {
+        sqlx::query(
+            "INSERT INTO account_sigchain \
+                 (account_id, seqno, link_type, signer_kid, envelope, prev_hash, hash) \
+             VALUES ($1, $2, 'device_added', 'cs1uhCLEB_ttCYaQ8RMLfQ', '{}', \
+                     CASE WHEN $2 > 1 THEN decode(repeat('00', 32), 'hex') END, \
+                     decode(repeat('11', 32), 'hex'))",
+        )
+        .bind(account)
+        .bind(seqno)
+        .execute(&pool)
+        .await
+        .expect("insert link");
+    }
+
+    let config = AnalyticsExportConfig {
+        batch_rows: 1,
+        prefix: "test/tc".into(),
+        ..AnalyticsExportConfig::default()
+    };
+    let sink = MemorySink::default();
+    let outcomes = run_export(&pool, &sink, &config).await;
+
+    let rows: Vec<_> = outcomes.iter().map(|o| (o.dataset, o.rows)).collect();
+    assert_eq!(
+        rows,
+        vec![
+            (ExportDataset::Endorsements, 2),
+            (ExportDataset::SigchainLinks, 3)
+        ]
+    );
+    let objects = sink.objects();
+    assert_eq!(objects.len(), 2);
+    assert!(objects[0].0.starts_with("test/tc/endorsements/v1/"));
+    assert!(objects[1].0.starts_with("test/tc/sigchain_links/v1/"));
+
+    let mut ids = read_column(&objects[0].1, "id");
+    ids.sort();
+    exported.sort();
+    assert_eq!(ids, exported, "private endorsements are left out");
+    assert_eq!(
+        read_column(&objects[1].1, "link_type"),
+        vec!["device_added"; 3]
+    );
+}
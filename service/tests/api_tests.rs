@@ -159,18 +159,35 @@ async fn test_graphql_build_info_query() {
 }
 
 #[tokio::test]
-async fn test_graphql_echo_mutation() {
+async fn test_graphql_unsigned_mutation_rejected() {
     let (status, json) = graphql_post(r#"mutation { echo(message: "test message") }"#).await;
 
-    assert_eq!(status, StatusCode::OK);
-    assert_no_errors(&json);
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(json.get("data").is_none(), "resolver must not run: {json}");
+}
 
-    let data = extract_data(&json).expect("Response should have data");
-    assert_eq!(
-        data["echo"].as_str().expect("echo should be string"),
-        "test message",
-        "echo should return the exact input"
-    );
+#[tokio::test]
+async fn test_graphql_batch_with_unsigned_mutation_rejected() {
+    let app = TestAppBuilder::graphql_only().build();
+    let body = serde_json::json!([
+        { "query": "{ buildInfo { version } }" },
+        { "query": "mutation { echo(message: \"smuggled\") }" },
+    ])
+    .to_string();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/graphql")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
 // ============================================================================
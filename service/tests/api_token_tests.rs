@@ -0,0 +1,216 @@
+//! API token integration tests.
+//!
+//! Tests issuing, using, listing, and revoking API tokens (`/auth/tokens`)
+//! with real database connections.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, Method, Request, StatusCode},
+    Extension, Router,
+};
+use common::factories::{
+    account_id_by_username, build_authed_request, signup_user, signup_user_in_pool, SignupKeys,
+};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::{AccountsConfig, ApiTokenConfig};
+use tinycongress_api::identity::http::token_quota::ApiTokenQuotas;
+use tower::ServiceExt;
+
+async fn json_body(response: axum::response::Response) -> serde_json::Value {
+    let body = to_bytes(response.into_body(), 64 * 1024)
+        .await
+        .expect("body");
+    serde_json::from_slice(&body).expect("json")
+}
+
+async fn issue_token(app: &Router, keys: &SignupKeys, scopes: &[&str]) -> serde_json::Value {
+    let body = serde_json::json!({ "name": "integration", "scopes": scopes }).to_string();
+    let req = build_authed_request(
+        Method::POST,
+        "/auth/tokens",
+        &body,
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    json_body(response).await
+}
+
+fn bearer_get(path: &str, token: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::GET)
+        .uri(path)
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::empty())
+        .expect("request")
+}
+
+#[shared_runtime_test]
+async fn test_token_reads_scoped_endpoint() {
+    let (app, keys, _db) = signup_user("tokenread").await;
+    let issued = issue_token(&app, &keys, &["devices:read"]).await;
+    let token = issued["token"].as_str().expect("token");
+
+    let response = app
+        .clone()
+        .oneshot(bearer_get("/auth/devices", token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let payload = json_body(response).await;
+    assert_eq!(payload["devices"].as_array().expect("devices").len(), 1);
+
+    // A token without the endpoint's scope is forbidden.
+    let other = issue_token(&app, &keys, &["trust:read"]).await;
+    let response = app
+        .clone()
+        .oneshot(bearer_get(
+            "/auth/devices",
+            other["token"].as_str().expect("token"),
+        ))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[shared_runtime_test]
+async fn test_token_cannot_manage_tokens() {
+    let (app, keys, _db) = signup_user("tokenwrite").await;
+    let issued = issue_token(&app, &keys, &["devices:read"]).await;
+    let token = issued["token"].as_str().expect("token");
+
+    // Token management requires a device signature.
+    let response = app
+        .clone()
+        .oneshot(bearer_get("/auth/tokens", token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[shared_runtime_test]
+async fn test_list_omits_secret_and_revoke_disables_token() {
+    let (app, keys, _db) = signup_user("tokenrevoke").await;
+    let issued = issue_token(&app, &keys, &["devices:read"]).await;
+    let token = issued["token"].as_str().expect("token").to_string();
+    let id = issued["id"].as_str().expect("id").to_string();
+
+    let req = build_authed_request(
+        Method::GET,
+        "/auth/tokens",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed = json_body(response).await;
+    let tokens = listed["tokens"].as_array().expect("tokens");
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0]["id"], id);
+    assert!(tokens[0].get("token").is_none());
+
+    let path = format!("/auth/tokens/{id}");
+    let req = build_authed_request(
+        Method::DELETE,
+        &path,
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .clone()
+        .oneshot(bearer_get("/auth/devices", &token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Second revoke conflicts.
+    let req = build_authed_request(
+        Method::DELETE,
+        &path,
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[shared_runtime_test]
+async fn test_other_account_cannot_revoke_token() {
+    let (app, keys, db) = signup_user("tokenowner").await;
+    let issued = issue_token(&app, &keys, &["devices:read"]).await;
+    let id = issued["id"].as_str().expect("id");
+
+    let (other_app, other_keys) = signup_user_in_pool("tokenother", db.pool()).await;
+    let req = build_authed_request(
+        Method::DELETE,
+        &format!("/auth/tokens/{id}"),
+        "",
+        &other_keys.device_signing_key,
+        &other_keys.device_kid,
+    );
+    let response = other_app.oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[shared_runtime_test]
+async fn test_daily_quota_and_usage_view() {
+    let (app, keys, db) = signup_user("tokenquota").await;
+    let admin_id = account_id_by_username("tokenquota", db.pool()).await;
+    let config = ApiTokenConfig {
+        daily_quota: 2,
+        ..ApiTokenConfig::default()
+    };
+    let app = app
+        .layer(Extension(Arc::new(ApiTokenQuotas::from_config(&config))))
+        .layer(Extension(config))
+        .layer(Extension(AccountsConfig {
+            admin_account_ids: vec![admin_id],
+        }));
+    let issued = issue_token(&app, &keys, &["devices:read"]).await;
+    let token = issued["token"].as_str().expect("token");
+
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(bearer_get("/auth/devices", token))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = app
+        .clone()
+        .oneshot(bearer_get("/auth/devices", token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["x-quota-limit"], "2");
+    assert_eq!(response.headers()["x-quota-remaining"], "0");
+    assert!(response.headers().contains_key("retry-after"));
+
+    let req = build_authed_request(
+        Method::GET,
+        "/auth/tokens/usage",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let usage = json_body(response).await;
+    let tokens = usage["tokens"].as_array().expect("tokens");
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0]["token_id"], issued["id"]);
+    assert_eq!(tokens[0]["username"], "tokenquota");
+    assert_eq!(tokens[0]["requests"], 2);
+}
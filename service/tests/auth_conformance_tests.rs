@@ -0,0 +1,186 @@
+//! Device-auth conformance for every signed route.
+//!
+//! Each route below is run through
+//! [`common::auth_conformance::assert_device_auth_conformance`], which covers
+//! missing headers, malformed and unknown KIDs, bad signatures, signed
+//! method/path/query/body mismatches, stale timestamps, invalid nonces, and
+//! replay. When adding an authenticated route, add it to the matching table
+//! here instead of hand-writing those cases.
+//!
+//! Routes are targeted so that the one correctly signed request leaves the
+//! caller's device usable (e.g. revoking a KID that does not exist).
+
+mod common;
+
+use axum::http::Method;
+use common::app_builder::TestAppBuilder;
+use common::auth_conformance::{assert_device_auth_conformance, SignedRoute};
+use common::factories::{signup_user_in_pool, signup_user_transactional};
+use tc_crypto::Kid;
+use tc_test_macros::shared_runtime_test;
+use uuid::Uuid;
+
+#[shared_runtime_test]
+async fn test_identity_routes_conform() {
+    let (app, keys, _pool) = signup_user_transactional("conformid").await;
+    let unknown_kid = Kid::derive(&[0x42u8; 32]);
+
+    let routes = [
+        SignedRoute::get("/auth/devices"),
+        SignedRoute::post("/auth/devices", "{}"),
+        SignedRoute::new(
+            Method::PATCH,
+            format!("/auth/devices/{unknown_kid}"),
+            r#"{"name":"Renamed"}"#,
+        ),
+        SignedRoute::new(Method::DELETE, format!("/auth/devices/{unknown_kid}"), ""),
+        SignedRoute::get(format!("/auth/devices/{}/delegation", keys.device_kid)),
+        SignedRoute::get("/auth/tokens"),
+        SignedRoute::get("/auth/tokens/usage"),
+        SignedRoute::new(
+            Method::DELETE,
+            format!("/auth/tokens/{}", Uuid::new_v4()),
+            "",
+        ),
+        SignedRoute::get("/auth/aliases"),
+        SignedRoute::post("/auth/aliases", r#"{"alias":"conform-alias"}"#),
+        SignedRoute::new(Method::DELETE, "/auth/aliases/no-such-alias", ""),
+        SignedRoute::get("/auth/security/checkup"),
+        SignedRoute::get("/auth/signup-invites"),
+        SignedRoute::post("/auth/signup-invites", ""),
+        SignedRoute::get("/accounts/lookup?username=conformid"),
+    ];
+
+    for route in &routes {
+        assert_device_auth_conformance(&app, route, &keys).await;
+    }
+}
+
+#[shared_runtime_test]
+async fn test_trust_routes_conform() {
+    let (app, keys, _pool) = signup_user_transactional("conformtrust").await;
+
+    let routes = [
+        SignedRoute::post("/trust/endorse", "{}"),
+        SignedRoute::post("/trust/revoke", "{}"),
+        SignedRoute::post("/trust/denounce", "{}"),
+        SignedRoute::get("/trust/denouncements/mine"),
+        SignedRoute::get("/trust/scores/me"),
+        SignedRoute::get("/trust/budget"),
+        SignedRoute::get(format!("/trust/distance/{}", Uuid::new_v4())),
+        SignedRoute::post("/trust/invites", "{}"),
+        SignedRoute::get("/trust/invites/mine"),
+        SignedRoute::get("/trust/invites/quota"),
+        SignedRoute::get("/trust/invites/tree?max_depth=2"),
+        SignedRoute::post(format!("/trust/invites/{}/accept", Uuid::new_v4()), ""),
+        SignedRoute::get("/trust/reconfirmations"),
+        SignedRoute::post("/trust/reconfirm", "{}"),
+    ];
+
+    for route in &routes {
+        assert_device_auth_conformance(&app, route, &keys).await;
+    }
+}
+
+#[shared_runtime_test]
+async fn test_rooms_and_reputation_routes_conform() {
+    let (app, keys, _pool) = signup_user_transactional("conformrooms").await;
+    let room = format!("/rooms/{}", Uuid::new_v4());
+    let poll = format!("{room}/polls/{}", Uuid::new_v4());
+
+    let routes = [
+        SignedRoute::post("/rooms", "{}"),
+        SignedRoute::get(format!("{room}/my-capabilities")),
+        SignedRoute::post(format!("{room}/roles"), "{}"),
+        SignedRoute::post(format!("{room}/polls"), "{}"),
+        SignedRoute::post(format!("{poll}/suggestions"), "{}"),
+        SignedRoute::post(format!("{poll}/status"), "{}"),
+        SignedRoute::post(format!("{poll}/dimensions"), "{}"),
+        SignedRoute::post(
+            format!("{poll}/dimensions/{}/evidence", Uuid::new_v4()),
+            "{}",
+        ),
+        SignedRoute::new(Method::DELETE, format!("{poll}/evidence"), ""),
+        SignedRoute::post(format!("{poll}/vote"), "{}"),
+        SignedRoute::get(format!("{poll}/my-votes")),
+        SignedRoute::get("/me/endorsements?limit=10"),
+    ];
+
+    for route in &routes {
+        assert_device_auth_conformance(&app, route, &keys).await;
+    }
+}
+
+#[shared_runtime_test]
+async fn test_notification_routes_conform() {
+    let (app, keys, _pool) = signup_user_transactional("conformnotify").await;
+
+    let routes = [
+        SignedRoute::get("/notifications?limit=10"),
+        SignedRoute::post(format!("/notifications/{}/read", Uuid::new_v4()), ""),
+        SignedRoute::get("/notifications/preferences"),
+        SignedRoute::new(
+            Method::PUT,
+            "/notifications/preferences",
+            r#"{"preferences":[]}"#,
+        ),
+    ];
+
+    for route in &routes {
+        assert_device_auth_conformance(&app, route, &keys).await;
+    }
+}
+
+#[shared_runtime_test]
+async fn test_topic_routes_conform() {
+    let (app, keys, _pool) = signup_user_transactional("conformtopics").await;
+
+    let routes = [
+        SignedRoute::post("/topics/identity_verified/subscribe", ""),
+        SignedRoute::new(Method::DELETE, "/topics/identity_verified/subscribe", ""),
+        SignedRoute::get("/topics/subscriptions"),
+        SignedRoute::get("/topics/feed?limit=10"),
+    ];
+
+    for route in &routes {
+        assert_device_auth_conformance(&app, route, &keys).await;
+    }
+}
+
+#[shared_runtime_test]
+async fn test_research_routes_conform() {
+    let (app, keys, _pool) = signup_user_transactional("conformresearch").await;
+    let unknown = Uuid::new_v4();
+
+    let routes = [
+        SignedRoute::get("/research/exports"),
+        SignedRoute::post(
+            "/research/exports",
+            r#"{"dataset":"trust_degrees","purpose":"conformance"}"#,
+        ),
+        SignedRoute::post(format!("/research/exports/{unknown}/approve"), ""),
+        SignedRoute::post(format!("/research/exports/{unknown}/reject"), ""),
+        SignedRoute::get(format!("/research/exports/{unknown}/download")),
+    ];
+
+    for route in &routes {
+        assert_device_auth_conformance(&app, route, &keys).await;
+    }
+}
+
+#[shared_runtime_test]
+async fn test_graphql_mutations_conform() {
+    let builder = TestAppBuilder::new()
+        .with_transactional_pool()
+        .await
+        .with_graphql();
+    let pool = builder.pool().expect("transactional pool").clone();
+    let app = builder.build();
+    let (_, keys) = signup_user_in_pool("conformgql", &pool).await;
+
+    let route = SignedRoute::post(
+        "/graphql",
+        r#"{"query":"mutation { echo(message: \"signed\") }"}"#,
+    );
+    assert_device_auth_conformance(&app, &route, &keys).await;
+}
@@ -26,12 +26,15 @@
 //! - [`TestAppBuilder::minimal()`] - Health check only
 //! - [`TestAppBuilder::graphql_only()`] - GraphQL without identity/CORS
 //! - [`TestAppBuilder::with_mocks()`] - Full app with lazy pool (no real DB)
+//! - [`TestAppBuilder::with_transactional_pool()`] - Real DB routes on the shared
+//!   database, rolled back on drop
 
 use std::sync::Arc;
 
 use async_graphql::{EmptySubscription, Schema};
 use axum::{
-    http::{header::HeaderValue, Method, StatusCode},
+    handler::Handler,
+    http::{header::HeaderValue, StatusCode},
     middleware,
     response::IntoResponse,
     routing::get,
@@ -46,20 +49,39 @@ use tc_engine_polling::engine::PollingEngine;
 use tc_engine_polling::service::{DefaultPollingService, PollingService};
 use tinycongress_api::{
     build_info::BuildInfo,
-    config::SecurityHeadersConfig,
+    changelog,
+    config::{
+        AccountsConfig, CompressionConfig, CorsConfig, DevToolsConfig, SecurityHeadersConfig,
+    },
+    db,
+    developer::{
+        self,
+        repo::{DeveloperRepo, PgDeveloperRepo},
+    },
     graphql::{graphql_handler, graphql_playground, MutationRoot, QueryRoot},
-    http::{build_security_headers, security_headers_middleware},
+    http::{
+        build_compression_layer, build_cors_layer, build_security_headers, dev_tools_guard,
+        security_headers_middleware, DevToolsAccess,
+    },
     identity::{
         self,
         http::backup::SyntheticBackupKey,
         repo::{IdentityRepo, PgIdentityRepo},
         service::{DefaultIdentityService, IdentityService},
     },
+    notifications::{
+        self,
+        repo::{NotificationRepo, PgNotificationRepo},
+    },
     reputation::{
         self,
         repo::{PgReputationRepo, ReputationRepo},
         service::{DefaultEndorsementService, EndorsementService},
     },
+    research::{
+        self,
+        repo::{PgResearchRepo, ResearchRepo},
+    },
     rest::{self, ApiDoc},
     rooms::{
         self,
@@ -67,17 +89,25 @@ use tinycongress_api::{
         repo::{PgRoomsRepo, RoomsRepo},
         service::{DefaultRoomsService, RoomsService},
     },
+    stats::{self, repo::StatsRepo},
+    topics::{
+        self,
+        repo::{PgTopicRepo, TopicRepo},
+    },
     trust::{
         self,
+        distances::TrustDistances,
+        engine::TrustEngine,
         graph_reader::TrustRepoGraphReader,
         repo::{PgTrustRepo, TrustRepo},
         service::{DefaultTrustService, TrustService},
     },
 };
-use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+use super::test_db::transactional_pool;
+
 /// Minimal [`RoomLifecycle`] stub for test wiring — does not hit the database.
 struct StubRoomLifecycle;
 
@@ -136,6 +166,10 @@ pub struct TestAppBuilder {
     include_health: bool,
     /// Whether to include Swagger UI
     include_swagger: bool,
+    /// Access restrictions for the playground and Swagger UI (default: public)
+    dev_tools: DevToolsConfig,
+    /// Instance admins (None leaves `AccountsConfig` unset)
+    accounts: Option<AccountsConfig>,
     /// Custom build info provider (None uses from_env())
     build_info: Option<BuildInfo>,
     /// Database pool — only set by `with_identity_pool()` for integration tests
@@ -157,16 +191,31 @@ pub struct TestAppBuilder {
     trust_service: Option<Arc<dyn TrustService>>,
     /// Trust repo for trust routes
     trust_repo: Option<Arc<dyn TrustRepo>>,
+    /// Materialized trust distances for `/trust/distance/{account_id}`
+    trust_distances: Option<Arc<TrustDistances>>,
     /// Engine registry for room engine dispatch
     engine_registry: Option<Arc<EngineRegistry>>,
     /// Engine context for room engine hooks
     engine_ctx: Option<EngineContext>,
     /// Content filter for suggestion endpoints (None means no filter extension added)
     content_filter: Option<Arc<dyn ContentFilter>>,
+    /// Stats repo for `/api/v1/stats` and the GraphQL `instanceStats` query
+    stats_repo: Option<Arc<dyn StatsRepo>>,
+    /// Notification repo; enables `/notifications` routes and the GraphQL
+    /// `notificationUnreadCount` query
+    notification_repo: Option<Arc<dyn NotificationRepo>>,
+    /// Topic repo; enables `/topics` routes
+    topic_repo: Option<Arc<dyn TopicRepo>>,
+    /// Research export repo; enables `/research/exports` routes
+    research_repo: Option<Arc<dyn ResearchRepo>>,
+    /// OAuth client repo; enables `/developer/clients` routes
+    developer_repo: Option<Arc<dyn DeveloperRepo>>,
     /// CORS allowed origins (None means no CORS layer)
-    cors_origins: Option<Vec<String>>,
+    cors: Option<CorsConfig>,
     /// Security headers config (None means disabled)
     security_headers: Option<SecurityHeadersConfig>,
+    /// Response compression config (None means disabled)
+    compression: Option<CompressionConfig>,
 }
 
 impl Default for TestAppBuilder {
@@ -188,6 +237,8 @@ impl TestAppBuilder {
             include_trust: false,
             include_health: false,
             include_swagger: false,
+            dev_tools: DevToolsConfig::default(),
+            accounts: None,
             build_info: None,
             pool: None,
             identity_service: None,
@@ -198,11 +249,18 @@ impl TestAppBuilder {
             polling_service: None,
             trust_service: None,
             trust_repo: None,
+            trust_distances: None,
             engine_registry: None,
             engine_ctx: None,
             content_filter: None,
-            cors_origins: None,
+            stats_repo: None,
+            notification_repo: None,
+            topic_repo: None,
+            research_repo: None,
+            developer_repo: None,
+            cors: None,
             security_headers: None,
+            compression: None,
         }
     }
 
@@ -243,6 +301,7 @@ impl TestAppBuilder {
             .with_health()
             .with_swagger()
             .with_cors(&["http://localhost:3000"])
+            .with_compression(CompressionConfig::default())
             .with_security_headers_default()
     }
 
@@ -293,6 +352,21 @@ impl TestAppBuilder {
         self
     }
 
+    /// Require invite codes at signup, as with `TC_SIGNUP__INVITE_ONLY=true`.
+    ///
+    /// Call after the method that wires the identity repo.
+    #[must_use]
+    pub fn with_invite_only_signup(mut self) -> Self {
+        let repo = self
+            .identity_repo
+            .clone()
+            .expect("with_invite_only_signup needs an identity repo");
+        self.identity_service = Some(Arc::new(
+            DefaultIdentityService::new(repo).with_invite_only(true),
+        ) as Arc<dyn IdentityService>);
+        self
+    }
+
     /// Include rooms and reputation routes with a real database pool.
     ///
     /// This wires up the full rooms + polling + endorsement stack, matching main.rs.
@@ -378,11 +452,100 @@ impl TestAppBuilder {
         self.trust_repo = Some(trust_repo);
         self.trust_service = Some(trust_service);
         self.reputation_repo = Some(reputation_repo);
+        self.trust_distances = Some(Arc::new(TrustDistances::new(
+            pool.clone(),
+            Arc::new(TrustEngine::new(pool.clone())),
+        )));
+
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Include notification and topic feed routes with a real database pool.
+    ///
+    /// Also enables identity routes, which both need for auth.
+    #[must_use]
+    pub fn with_notifications_pool(mut self, pool: PgPool) -> Self {
+        self.include_identity = true;
+        if self.identity_repo.is_none() {
+            let identity_repo = Arc::new(PgIdentityRepo::new(pool.clone()));
+            self.identity_repo = Some(Arc::clone(&identity_repo) as Arc<dyn IdentityRepo>);
+            self.identity_service =
+                Some(Arc::new(DefaultIdentityService::new(identity_repo))
+                    as Arc<dyn IdentityService>);
+        }
+        self.notification_repo =
+            Some(Arc::new(PgNotificationRepo::new(pool.clone())) as Arc<dyn NotificationRepo>);
+        self.topic_repo = Some(Arc::new(PgTopicRepo::new(pool.clone())) as Arc<dyn TopicRepo>);
+        self.pool = Some(pool);
+        self
+    }
 
+    /// Include research export routes with a real database pool.
+    ///
+    /// Also enables identity routes for auth. Layer a
+    /// [`ResearchConfig`](tinycongress_api::config::ResearchConfig) onto the
+    /// built router to grant researcher and admin roles.
+    #[must_use]
+    pub fn with_research_pool(mut self, pool: PgPool) -> Self {
+        self.include_identity = true;
+        if self.identity_repo.is_none() {
+            let identity_repo = Arc::new(PgIdentityRepo::new(pool.clone()));
+            self.identity_repo = Some(Arc::clone(&identity_repo) as Arc<dyn IdentityRepo>);
+            self.identity_service =
+                Some(Arc::new(DefaultIdentityService::new(identity_repo))
+                    as Arc<dyn IdentityService>);
+        }
+        self.research_repo =
+            Some(Arc::new(PgResearchRepo::new(pool.clone())) as Arc<dyn ResearchRepo>);
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Include developer portal routes with a real database pool.
+    ///
+    /// Also enables identity routes for auth.
+    #[must_use]
+    pub fn with_developer_pool(mut self, pool: PgPool) -> Self {
+        self.include_identity = true;
+        if self.identity_repo.is_none() {
+            let identity_repo = Arc::new(PgIdentityRepo::new(pool.clone()));
+            self.identity_repo = Some(Arc::clone(&identity_repo) as Arc<dyn IdentityRepo>);
+            self.identity_service =
+                Some(Arc::new(DefaultIdentityService::new(identity_repo))
+                    as Arc<dyn IdentityService>);
+        }
+        self.developer_repo =
+            Some(Arc::new(PgDeveloperRepo::new(pool.clone())) as Arc<dyn DeveloperRepo>);
         self.pool = Some(pool);
         self
     }
 
+    /// Include identity, rooms, reputation, trust, notification, research, and developer routes
+    /// backed by a [`transactional_pool`] on the shared test database.
+    ///
+    /// Everything the app writes is rolled back when the built router (and any
+    /// clone of [`pool`](Self::pool)) is dropped. Prefer this over
+    /// `isolated_db()` unless the test needs real commits or concurrent
+    /// connections; see [`transactional_pool`] for the limits.
+    pub async fn with_transactional_pool(self) -> Self {
+        let pool = transactional_pool().await;
+        self.with_rooms_pool(pool.clone())
+            .with_trust_pool(pool.clone())
+            .with_notifications_pool(pool.clone())
+            .with_research_pool(pool.clone())
+            .with_developer_pool(pool)
+    }
+
+    /// The database pool the app will be built with, if any.
+    ///
+    /// Use with [`with_transactional_pool`](Self::with_transactional_pool) to
+    /// seed or inspect rows inside the same rolled-back transaction.
+    #[must_use]
+    pub fn pool(&self) -> Option<&PgPool> {
+        self.pool.as_ref()
+    }
+
     /// Inject a pre-built stub [`TrustRepo`] without wiring up a database pool.
     ///
     /// Enables the trust routes and registers the supplied repo as the
@@ -418,6 +581,13 @@ impl TestAppBuilder {
         self
     }
 
+    /// Inject a stats repo for `/api/v1/stats` and the GraphQL `instanceStats` query.
+    #[must_use]
+    pub fn with_stats_repo(mut self, repo: Arc<dyn StatsRepo>) -> Self {
+        self.stats_repo = Some(repo);
+        self
+    }
+
     /// Add a database pool as an Extension (for health check testing).
     ///
     /// Unlike [`with_identity_pool()`], this does NOT enable identity routes.
@@ -442,20 +612,44 @@ impl TestAppBuilder {
         self
     }
 
+    /// Restrict the playground and Swagger UI like `TC_DEV_TOOLS__*` does.
+    #[must_use]
+    pub fn with_dev_tools(mut self, config: DevToolsConfig) -> Self {
+        self.dev_tools = config;
+        self
+    }
+
+    /// Set the instance admins, for dev tools and admin-only routes.
+    #[must_use]
+    pub fn with_accounts(mut self, config: AccountsConfig) -> Self {
+        self.accounts = Some(config);
+        self
+    }
+
     /// Configure CORS with specific allowed origins.
     ///
     /// Pass an empty slice to block all cross-origin requests.
     /// Pass `&["*"]` to allow any origin.
     #[must_use]
     pub fn with_cors(mut self, origins: &[&str]) -> Self {
-        self.cors_origins = Some(origins.iter().map(|s| (*s).to_string()).collect());
+        self.cors = Some(CorsConfig {
+            allowed_origins: origins.iter().map(|s| (*s).to_string()).collect(),
+            ..CorsConfig::default()
+        });
+        self
+    }
+
+    /// Configure CORS with per-group origins, as in production config.
+    #[must_use]
+    pub fn with_cors_config(mut self, config: CorsConfig) -> Self {
+        self.cors = Some(config);
         self
     }
 
     /// Disable CORS layer entirely.
     #[must_use]
     pub fn without_cors(mut self) -> Self {
-        self.cors_origins = None;
+        self.cors = None;
         self
     }
 
@@ -473,6 +667,13 @@ impl TestAppBuilder {
         self
     }
 
+    /// Enable response compression with the given configuration.
+    #[must_use]
+    pub fn with_compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
     /// Use a custom build info provider.
     #[must_use]
     pub fn with_build_info(mut self, provider: BuildInfo) -> Self {
@@ -490,32 +691,49 @@ impl TestAppBuilder {
     /// 1. Routes (GraphQL, REST, Identity, Health, Swagger)
     /// 2. Extensions (schema, pool, repo, build_info)
     /// 3. CORS layer
-    /// 4. Security headers middleware (outermost)
+    /// 4. Response compression
+    /// 5. Security headers middleware (outermost)
     #[must_use]
     pub fn build(self) -> Router {
         let build_info = self.build_info.unwrap_or_else(BuildInfo::from_env);
 
         // Build GraphQL schema
-        let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
-            .data(build_info.clone())
-            .finish();
+        let mut schema =
+            Schema::build(QueryRoot, MutationRoot, EmptySubscription).data(build_info.clone());
+        if let Some(ref repo) = self.stats_repo {
+            schema = schema.data(Arc::clone(repo));
+        }
+        if let Some(ref repo) = self.notification_repo {
+            schema = schema.data(Arc::clone(repo));
+        }
+        let schema = schema.finish();
 
         // Start building the router
         let mut app = Router::new();
 
         // Add routes
         if self.include_graphql {
-            app = app.route("/graphql", get(graphql_playground).post(graphql_handler));
+            app = app.route(
+                "/graphql",
+                get(graphql_playground.layer(middleware::from_fn(dev_tools_guard)))
+                    .post(graphql_handler),
+            );
         }
 
         if self.include_rest {
-            let rest_v1 = Router::new().route("/build-info", get(rest::get_build_info));
+            let rest_v1 = Router::new()
+                .route("/build-info", get(rest::get_build_info))
+                .route("/changelog", get(changelog::get_changelog))
+                .route("/stats", get(stats::http::get_stats));
             app = app.nest("/api/v1", rest_v1);
         }
 
         if self.include_swagger {
             app = app.merge(
-                SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()),
+                Router::from(
+                    SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()),
+                )
+                .layer(middleware::from_fn(dev_tools_guard)),
             );
         }
 
@@ -526,6 +744,7 @@ impl TestAppBuilder {
                 ..Default::default()
             };
             app = app.merge(identity::http::router(&rl));
+            app = app.merge(identity::http::verify::router(&rl));
         }
 
         if self.include_reputation {
@@ -544,6 +763,22 @@ impl TestAppBuilder {
             app = app.merge(trust::http::trust_router());
         }
 
+        if self.notification_repo.is_some() {
+            app = app.merge(notifications::http::router());
+        }
+
+        if self.topic_repo.is_some() {
+            app = app.merge(topics::http::router());
+        }
+
+        if self.research_repo.is_some() {
+            app = app.merge(research::http::router());
+        }
+
+        if self.developer_repo.is_some() {
+            app = app.merge(developer::http::router());
+        }
+
         if self.include_health {
             app = app
                 .route("/health", get(health_check))
@@ -551,7 +786,22 @@ impl TestAppBuilder {
         }
 
         // Add extensions
-        app = app.layer(Extension(schema)).layer(Extension(build_info));
+        let dev_tools = DevToolsAccess::from_config(
+            &self.dev_tools,
+            &self.accounts.clone().unwrap_or_default(),
+        )
+        .expect("valid dev tools config");
+        app = app
+            .layer(Extension(schema))
+            .layer(Extension(build_info))
+            .layer(Extension(Arc::new(
+                changelog::Changelog::embedded().expect("embedded changelog"),
+            )))
+            .layer(Extension(Arc::new(dev_tools)));
+
+        if let Some(accounts) = self.accounts {
+            app = app.layer(Extension(accounts));
+        }
 
         if let Some(pool) = self.pool {
             app = app.layer(Extension(pool));
@@ -589,6 +839,10 @@ impl TestAppBuilder {
             app = app.layer(Extension(repo));
         }
 
+        if let Some(distances) = self.trust_distances {
+            app = app.layer(Extension(distances));
+        }
+
         if let Some(registry) = self.engine_registry {
             app = app.layer(Extension(registry));
         }
@@ -601,6 +855,26 @@ impl TestAppBuilder {
             app = app.layer(Extension(filter));
         }
 
+        if let Some(repo) = self.stats_repo {
+            app = app.layer(Extension(repo));
+        }
+
+        if let Some(repo) = self.notification_repo {
+            app = app.layer(Extension(repo));
+        }
+
+        if let Some(repo) = self.topic_repo {
+            app = app.layer(Extension(repo));
+        }
+
+        if let Some(repo) = self.research_repo {
+            app = app.layer(Extension(repo));
+        }
+
+        if let Some(repo) = self.developer_repo {
+            app = app.layer(Extension(repo));
+        }
+
         // Always provide a synthetic backup HMAC key when identity routes are active
         if self.include_identity {
             app = app.layer(Extension(SyntheticBackupKey::new(
@@ -608,33 +882,20 @@ impl TestAppBuilder {
             )));
         }
 
+        // Refuse any instrumented query that splices in request input; the
+        // dev-dependency on this crate turns on `sql-audit` for every test
+        app = app.layer(middleware::from_fn(db::sql_audit::trace_request_inputs));
+
         // Add CORS layer if configured
-        if let Some(origins) = self.cors_origins {
-            let allow_origin: AllowOrigin = if origins.iter().any(|o| o == "*") {
-                AllowOrigin::any()
-            } else if origins.is_empty() {
-                AllowOrigin::list(Vec::<HeaderValue>::new())
-            } else {
-                let header_values: Vec<HeaderValue> = origins
-                    .iter()
-                    .filter_map(|origin| origin.parse().ok())
-                    .collect();
-                AllowOrigin::list(header_values)
-            };
+        if let Some(config) = self.cors {
+            app = app.layer(build_cors_layer(&config));
+        }
 
-            app = app.layer(
-                CorsLayer::new()
-                    .allow_methods([
-                        Method::GET,
-                        Method::POST,
-                        Method::PUT,
-                        Method::DELETE,
-                        Method::PATCH,
-                        Method::OPTIONS,
-                    ])
-                    .allow_headers(Any)
-                    .allow_origin(allow_origin),
-            );
+        // Add response compression if configured
+        if let Some(config) = self.compression {
+            if config.enabled {
+                app = app.layer(build_compression_layer(&config));
+            }
         }
 
         // Add security headers middleware if configured
@@ -0,0 +1,242 @@
+//! Device-auth conformance suite for signed routes.
+//!
+//! [`assert_device_auth_conformance`] runs the full matrix of header,
+//! timestamp, signature, KID, and nonce violations against any route that
+//! authenticates with [`AuthenticatedDevice`] (directly or through
+//! `ReadAuth`), then checks that a correctly signed request gets past auth
+//! and that replaying it is rejected.
+//!
+//! Every authenticated route should be listed in
+//! `tests/auth_conformance_tests.rs`; adding a row there gives the route the
+//! same adversarial coverage as every other signed endpoint.
+//!
+//! ```ignore
+//! let (app, keys, _pool) = signup_user_transactional("alice").await;
+//! assert_device_auth_conformance(&app, &SignedRoute::get("/auth/devices"), &keys).await;
+//! ```
+//!
+//! [`AuthenticatedDevice`]: tinycongress_api::identity::http::auth::AuthenticatedDevice
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Router,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use tc_crypto::{encode_base64url, Kid};
+use tower::ServiceExt;
+
+use crate::common::factories::SignupKeys;
+
+/// Skew beyond the server's ±300 second timestamp window.
+const SKEW_SECS: i64 = 600;
+
+/// One past the server's nonce length limit.
+const OVERLONG_NONCE_LEN: usize = 65;
+
+/// A request to an authenticated route, as the client intends to send it.
+#[derive(Debug, Clone)]
+pub struct SignedRoute {
+    pub method: Method,
+    /// Path including any query string, exactly as signed.
+    pub path: String,
+    pub body: String,
+}
+
+impl SignedRoute {
+    pub fn new(method: Method, path: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: body.into(),
+        }
+    }
+
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::GET, path, "")
+    }
+
+    pub fn post(path: impl Into<String>, body: impl Into<String>) -> Self {
+        Self::new(Method::POST, path, body)
+    }
+}
+
+/// Everything that goes into the auth headers of one request.
+///
+/// Violations mutate a valid instance; the request itself always carries the
+/// route's real method, path, and body.
+struct Signed {
+    kid: String,
+    key: SigningKey,
+    timestamp: String,
+    nonce: String,
+    method: String,
+    path: String,
+    body: Vec<u8>,
+    /// Sent instead of the computed signature when set.
+    signature: Option<String>,
+    /// Header left off the request.
+    omit: Option<&'static str>,
+}
+
+impl Signed {
+    fn valid(route: &SignedRoute, keys: &SignupKeys) -> Self {
+        Self {
+            kid: keys.device_kid.to_string(),
+            key: keys.device_signing_key.clone(),
+            timestamp: chrono::Utc::now().timestamp().to_string(),
+            nonce: uuid::Uuid::new_v4().to_string(),
+            method: route.method.to_string(),
+            path: route.path.clone(),
+            body: route.body.as_bytes().to_vec(),
+            signature: None,
+            omit: None,
+        }
+    }
+
+    fn headers(&self) -> Vec<(&'static str, String)> {
+        let signature = self.signature.clone().unwrap_or_else(|| {
+            let body_hash = Sha256::digest(&self.body);
+            let canonical = format!(
+                "{}\n{}\n{}\n{}\n{body_hash:x}",
+                self.method, self.path, self.timestamp, self.nonce
+            );
+            encode_base64url(&self.key.sign(canonical.as_bytes()).to_bytes())
+        });
+        [
+            ("X-Device-Kid", self.kid.clone()),
+            ("X-Signature", signature),
+            ("X-Timestamp", self.timestamp.clone()),
+            ("X-Nonce", self.nonce.clone()),
+        ]
+        .into_iter()
+        .filter(|(name, _)| Some(*name) != self.omit)
+        .collect()
+    }
+
+    fn request(&self, route: &SignedRoute) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method(route.method.clone())
+            .uri(&route.path);
+        for (name, value) in self.headers() {
+            builder = builder.header(name, value);
+        }
+        if !route.body.is_empty() {
+            builder = builder.header(CONTENT_TYPE, "application/json");
+        }
+        builder
+            .body(Body::from(route.body.clone()))
+            .expect("request")
+    }
+}
+
+type Violation = (&'static str, fn(&mut Signed));
+
+fn shift_timestamp(signed: &mut Signed, secs: i64) {
+    let timestamp: i64 = signed.timestamp.parse().expect("numeric timestamp");
+    signed.timestamp = (timestamp + secs).to_string();
+}
+
+/// Every mutation that must be rejected with 401.
+fn violations() -> Vec<Violation> {
+    vec![
+        ("missing X-Device-Kid", |s| s.omit = Some("X-Device-Kid")),
+        ("missing X-Signature", |s| s.omit = Some("X-Signature")),
+        ("missing X-Timestamp", |s| s.omit = Some("X-Timestamp")),
+        ("missing X-Nonce", |s| s.omit = Some("X-Nonce")),
+        ("malformed KID", |s| s.kid = "not-a-kid".to_string()),
+        ("unregistered KID", |s| {
+            let key = SigningKey::generate(&mut OsRng);
+            s.kid = Kid::derive(key.verifying_key().as_bytes()).to_string();
+            s.key = key;
+        }),
+        ("signed by another key", |s| {
+            s.key = SigningKey::generate(&mut OsRng);
+        }),
+        ("signature not base64url", |s| {
+            s.signature = Some("!!!not-base64!!!".to_string());
+        }),
+        ("signature wrong length", |s| {
+            s.signature = Some(encode_base64url(&[0u8; 32]));
+        }),
+        ("empty signature", |s| s.signature = Some(String::new())),
+        ("method differs from signed", |s| {
+            s.method = if s.method == "GET" { "POST" } else { "GET" }.to_string();
+        }),
+        ("path differs from signed", |s| {
+            s.path = format!("{}-tampered", s.path.split('?').next().unwrap_or("/"));
+        }),
+        ("query differs from signed", |s| {
+            let sep = if s.path.contains('?') { '&' } else { '?' };
+            s.path = format!("{}{sep}tampered=1", s.path);
+        }),
+        ("body differs from signed", |s| {
+            s.body.extend_from_slice(b" ")
+        }),
+        ("timestamp in the past", |s| shift_timestamp(s, -SKEW_SECS)),
+        ("timestamp in the future", |s| shift_timestamp(s, SKEW_SECS)),
+        ("timestamp not numeric", |s| {
+            s.timestamp = "yesterday".to_string()
+        }),
+        ("empty nonce", |s| s.nonce = String::new()),
+        ("nonce too long", |s| {
+            s.nonce = "n".repeat(OVERLONG_NONCE_LEN)
+        }),
+        ("nonce with control character", |s| {
+            s.nonce = "bad\tnonce".to_string();
+        }),
+    ]
+}
+
+/// Run the device-auth conformance matrix against `route`.
+///
+/// Each violation must produce 401. A correctly signed request must then get
+/// past auth (any status other than 401/403), and replaying it must produce
+/// 401. All failures are collected and reported together.
+///
+/// The valid request is really executed, so pick a route target that leaves
+/// the caller's device usable (e.g. revoke a random KID, not `keys.device_kid`).
+///
+/// # Panics
+///
+/// Panics listing every case whose status did not match.
+pub async fn assert_device_auth_conformance(app: &Router, route: &SignedRoute, keys: &SignupKeys) {
+    let mut failures = Vec::new();
+
+    for (name, mutate) in violations() {
+        let mut signed = Signed::valid(route, keys);
+        mutate(&mut signed);
+        let status = send(app, signed.request(route)).await;
+        if status != StatusCode::UNAUTHORIZED {
+            failures.push(format!("{name}: expected 401, got {status}"));
+        }
+    }
+
+    let signed = Signed::valid(route, keys);
+    let status = send(app, signed.request(route)).await;
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        failures.push(format!("valid signature: rejected with {status}"));
+    }
+    let status = send(app, signed.request(route)).await;
+    if status != StatusCode::UNAUTHORIZED {
+        failures.push(format!("replayed nonce: expected 401, got {status}"));
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} {} failed device-auth conformance:\n  {}",
+        route.method,
+        route.path,
+        failures.join("\n  ")
+    );
+}
+
+async fn send(app: &Router, request: Request<Body>) -> StatusCode {
+    app.clone()
+        .oneshot(request)
+        .await
+        .expect("response")
+        .status()
+}
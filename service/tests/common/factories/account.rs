@@ -1,6 +1,7 @@
 //! Account factory for test data creation.
 
 use super::next_id;
+use ed25519_dalek::SigningKey;
 use tc_crypto::{encode_base64url, Kid};
 use tinycongress_api::identity::repo::{
     create_account_with_executor, AccountRepoError, CreatedAccount,
@@ -23,6 +24,7 @@ use tinycongress_api::identity::repo::{
 pub struct AccountFactory {
     username: Option<String>,
     seed: Option<u8>,
+    root_pubkey: Option<[u8; 32]>,
 }
 
 impl AccountFactory {
@@ -32,6 +34,7 @@ impl AccountFactory {
         Self {
             username: None,
             seed: None,
+            root_pubkey: None,
         }
     }
 
@@ -50,6 +53,14 @@ impl AccountFactory {
         self
     }
 
+    /// Use the public half of a real root key instead of a seeded fake one.
+    /// Needed when the test signs device certificates with this account.
+    #[must_use]
+    pub fn with_root_key(mut self, root_key: &SigningKey) -> Self {
+        self.root_pubkey = Some(root_key.verifying_key().to_bytes());
+        self
+    }
+
     /// Create the account in the database.
     ///
     /// # Errors
@@ -65,7 +76,10 @@ impl AccountFactory {
         #[allow(clippy::cast_possible_truncation)]
         let seed = self.seed.unwrap_or((id % 256) as u8);
 
-        let (root_pubkey, root_kid) = generate_test_keys(seed);
+        let (root_pubkey, root_kid) = self.root_pubkey.map_or_else(
+            || generate_test_keys(seed),
+            |pubkey| (encode_base64url(&pubkey), Kid::derive(&pubkey)),
+        );
 
         create_account_with_executor(executor, &username, &root_pubkey, &root_kid).await
     }
@@ -0,0 +1,134 @@
+//! Device key factory for test data creation.
+
+use super::next_id;
+use ed25519_dalek::{Signer, SigningKey};
+use rand::rngs::OsRng;
+use tc_crypto::{encode_base64url, Kid};
+use tinycongress_api::identity::repo::{create_device_key_with_executor, DeviceKeyRepoError};
+use tinycongress_api::identity::service::device_certificate_message;
+use uuid::Uuid;
+
+/// A device key stored in the database, with the private half kept for
+/// signing requests in the test.
+pub struct TestDevice {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub kid: Kid,
+    pub signing_key: SigningKey,
+}
+
+/// Builder for device keys certified by the account's root key.
+///
+/// # Examples
+///
+/// ```rust
+/// let root_key = SigningKey::generate(&mut OsRng);
+/// let account = AccountFactory::new().with_root_key(&root_key).create(&mut *tx).await?;
+/// let device = DeviceFactory::new()
+///     .with_name("Phone")
+///     .create(&mut tx, account.id, &root_key)
+///     .await?;
+/// let req = build_authed_request(Method::GET, "/auth/devices", "", &device.signing_key, &device.kid);
+/// ```
+pub struct DeviceFactory {
+    name: Option<String>,
+    signing_key: Option<SigningKey>,
+    certificate_timestamp: Option<i64>,
+    revoked: bool,
+}
+
+impl DeviceFactory {
+    /// Create a new factory with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            signing_key: None,
+            certificate_timestamp: None,
+            revoked: false,
+        }
+    }
+
+    /// Set a specific device name.
+    #[must_use]
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Use a specific device key instead of a freshly generated one.
+    #[must_use]
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Certify the device with a timestamped certificate.
+    #[must_use]
+    pub const fn with_certificate_timestamp(mut self, timestamp: i64) -> Self {
+        self.certificate_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Mark the device revoked after creating it.
+    #[must_use]
+    pub const fn revoked(mut self) -> Self {
+        self.revoked = true;
+        self
+    }
+
+    /// Create the device key, certified by `root_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the insert fails (e.g., unknown account, duplicate
+    /// kid, or device limit reached).
+    pub async fn create(
+        self,
+        conn: &mut sqlx::PgConnection,
+        account_id: Uuid,
+        root_key: &SigningKey,
+    ) -> Result<TestDevice, DeviceKeyRepoError> {
+        let name = self.name.unwrap_or_else(|| format!("Device {}", next_id()));
+        let signing_key = self
+            .signing_key
+            .unwrap_or_else(|| SigningKey::generate(&mut OsRng));
+        let pubkey = signing_key.verifying_key().to_bytes();
+        let kid = Kid::derive(&pubkey);
+        let certificate = root_key.sign(&device_certificate_message(
+            &pubkey,
+            self.certificate_timestamp,
+        ));
+
+        let created = create_device_key_with_executor(
+            &mut *conn,
+            account_id,
+            &kid,
+            &encode_base64url(&pubkey),
+            &name,
+            &certificate.to_bytes(),
+            self.certificate_timestamp,
+        )
+        .await?;
+
+        if self.revoked {
+            sqlx::query("UPDATE device_keys SET revoked_at = now() WHERE id = $1")
+                .bind(created.id)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        Ok(TestDevice {
+            id: created.id,
+            account_id,
+            kid,
+            signing_key,
+        })
+    }
+}
+
+impl Default for DeviceFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
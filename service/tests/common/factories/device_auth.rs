@@ -5,11 +5,14 @@
 
 use axum::{
     body::Body,
-    http::{header::CONTENT_TYPE, Method, Request},
+    http::{
+        header::{CONTENT_TYPE, HOST},
+        Method, Request,
+    },
 };
 use ed25519_dalek::{Signer, SigningKey};
 use sha2::{Digest, Sha256};
-use tc_crypto::{encode_base64url, Kid};
+use tc_crypto::{encode_base64url, Kid, RequestParts, SignatureAlg};
 
 /// Build the auth headers for a device-authenticated request.
 ///
@@ -80,3 +83,49 @@ pub fn build_authed_request(
 
     builder.body(Body::from(body.to_string())).expect("request")
 }
+
+/// Build a complete v2-signed request (`X-Signature-Alg: ed25519`) for a
+/// device endpoint, sent to `Host: localhost`.
+pub fn build_authed_request_v2(
+    method: Method,
+    path: &str,
+    body: &str,
+    signing_key: &SigningKey,
+    kid: &Kid,
+) -> Request<Body> {
+    let alg = SignatureAlg::Ed25519;
+    let timestamp = chrono::Utc::now().timestamp();
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let content_type = if body.is_empty() {
+        ""
+    } else {
+        "application/json"
+    };
+    let canonical = RequestParts {
+        method: method.as_str(),
+        host: "localhost",
+        path_and_query: path,
+        timestamp,
+        nonce: &nonce,
+        content_type,
+        device_kid: kid.as_str(),
+        challenge: "",
+        body: body.as_bytes(),
+    }
+    .canonical_v2(alg);
+    let signature = signing_key.sign(canonical.as_bytes());
+
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(path)
+        .header(HOST, "localhost")
+        .header("X-Signature-Alg", alg.to_string())
+        .header("X-Device-Kid", kid.to_string())
+        .header("X-Signature", encode_base64url(&signature.to_bytes()))
+        .header("X-Timestamp", timestamp.to_string())
+        .header("X-Nonce", nonce);
+    if !content_type.is_empty() {
+        builder = builder.header(CONTENT_TYPE, content_type);
+    }
+    builder.body(Body::from(body.to_string())).expect("request")
+}
@@ -1,6 +1,20 @@
-//! Endorsement helpers for test setup — bypass the action queue to insert edges directly.
+//! Endorsement factories and helpers for test setup.
+//!
+//! Everything here bypasses the action queue and the HTTP layer to insert
+//! rows directly, but the rows carry the same signed material the real
+//! paths would store.
 
+use super::device::TestDevice;
+use ed25519_dalek::Signer;
 use sqlx::PgPool;
+use tc_crypto::{encode_base64url, RequestParts, SignatureAlg};
+use tinycongress_api::identity::signed_request::SignedRequest;
+use tinycongress_api::reputation::repo::{
+    create_endorsement, create_issued_endorsement, CreatedEndorsement, EndorsementRepoError,
+    IssuedEndorsement,
+};
+use tinycongress_api::reputation::EndorsementVisibility;
+use tinycongress_api::trust::http::reconfirmation_message;
 use uuid::Uuid;
 
 /// Insert an active endorsement directly into the DB (bypass the action queue for test setup).
@@ -30,3 +44,280 @@ pub async fn insert_revoked_endorsement(pool: &PgPool, endorser: Uuid, subject:
     .await
     .unwrap();
 }
+
+/// Builder for verifier-issued endorsements.
+///
+/// With an issuer, the row carries a v2 device-signed envelope for
+/// `POST /verifiers/endorsements`, exactly as the handler stores it, so
+/// [`SignedRequest::verify`] succeeds against the issuer's device key.
+/// Without one, it is a genesis endorsement with no envelope.
+///
+/// # Examples
+///
+/// ```rust
+/// let endorsement = EndorsementFactory::new(subject.id, "alice")
+///     .with_topic("clearance")
+///     .issued_by(&verifier_device)
+///     .with_visibility(EndorsementVisibility::Private)
+///     .create(db.pool())
+///     .await?;
+/// ```
+pub struct EndorsementFactory<'a> {
+    subject_id: Uuid,
+    subject_username: String,
+    topic: String,
+    issuer: Option<&'a TestDevice>,
+    evidence: Option<serde_json::Value>,
+    visibility: EndorsementVisibility,
+}
+
+impl<'a> EndorsementFactory<'a> {
+    /// Endorse `subject_id`, whose username goes into the signed body.
+    #[must_use]
+    pub fn new(subject_id: Uuid, subject_username: &str) -> Self {
+        Self {
+            subject_id,
+            subject_username: subject_username.to_string(),
+            topic: "identity_verified".to_string(),
+            issuer: None,
+            evidence: None,
+            visibility: EndorsementVisibility::Public,
+        }
+    }
+
+    /// Set the topic (default `identity_verified`).
+    #[must_use]
+    pub fn with_topic(mut self, topic: &str) -> Self {
+        self.topic = topic.to_string();
+        self
+    }
+
+    /// Issue from `device`'s account, signing the envelope with its key.
+    #[must_use]
+    pub const fn issued_by(mut self, device: &'a TestDevice) -> Self {
+        self.issuer = Some(device);
+        self
+    }
+
+    /// Attach evidence JSON.
+    #[must_use]
+    pub fn with_evidence(mut self, evidence: serde_json::Value) -> Self {
+        self.evidence = Some(evidence);
+        self
+    }
+
+    /// Set who may see the endorsement (default public).
+    #[must_use]
+    pub const fn with_visibility(mut self, visibility: EndorsementVisibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// The signed issuance request this endorsement is stored with, if it
+    /// has an issuer.
+    #[must_use]
+    pub fn envelope(&self) -> Option<SignedRequest> {
+        let device = self.issuer?;
+        let mut body = serde_json::json!({
+            "username": self.subject_username,
+            "topic": self.topic,
+            "visibility": self.visibility,
+        });
+        if let Some(evidence) = &self.evidence {
+            body["evidence"] = evidence.clone();
+        }
+        Some(sign_envelope(
+            device,
+            "POST",
+            "/verifiers/endorsements",
+            body.to_string().as_bytes(),
+        ))
+    }
+
+    /// Create the endorsement in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database insert fails.
+    pub async fn create<'e, E>(
+        self,
+        executor: E,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    {
+        let envelope = self
+            .envelope()
+            .map(|e| serde_json::to_value(e).expect("serialize envelope"));
+        create_issued_endorsement(
+            executor,
+            &IssuedEndorsement {
+                subject_id: self.subject_id,
+                topic: &self.topic,
+                issuer_id: self.issuer.map(|d| d.account_id),
+                evidence: self.evidence.as_ref(),
+                visibility: self.visibility,
+                envelope: envelope.as_ref(),
+            },
+        )
+        .await
+    }
+}
+
+/// Builder for trust edges (`topic = 'trust'` endorsements).
+///
+/// # Examples
+///
+/// ```rust
+/// TrustEdgeFactory::new(alice.id, bob.id)
+///     .with_weight(0.5)
+///     .reconfirmed_by(&bob_device)
+///     .create(db.pool())
+///     .await?;
+/// ```
+pub struct TrustEdgeFactory<'a> {
+    endorser_id: Uuid,
+    subject_id: Uuid,
+    weight: f32,
+    attestation: Option<serde_json::Value>,
+    in_slot: bool,
+    revoked: bool,
+    reconfirmed_by: Option<&'a TestDevice>,
+}
+
+impl<'a> TrustEdgeFactory<'a> {
+    /// An active, in-slot edge `endorser_id → subject_id` with weight 1.0.
+    #[must_use]
+    pub const fn new(endorser_id: Uuid, subject_id: Uuid) -> Self {
+        Self {
+            endorser_id,
+            subject_id,
+            weight: 1.0,
+            attestation: None,
+            in_slot: true,
+            revoked: false,
+            reconfirmed_by: None,
+        }
+    }
+
+    /// Set the edge weight.
+    #[must_use]
+    pub const fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Attach attestation JSON.
+    #[must_use]
+    pub fn with_attestation(mut self, attestation: serde_json::Value) -> Self {
+        self.attestation = Some(attestation);
+        self
+    }
+
+    /// Create the edge outside the endorser's slot budget.
+    #[must_use]
+    pub const fn out_of_slot(mut self) -> Self {
+        self.in_slot = false;
+        self
+    }
+
+    /// Revoke the edge after creating it.
+    #[must_use]
+    pub const fn revoked(mut self) -> Self {
+        self.revoked = true;
+        self
+    }
+
+    /// Record a re-confirmation signed by the subject's `device`.
+    #[must_use]
+    pub const fn reconfirmed_by(mut self, device: &'a TestDevice) -> Self {
+        self.reconfirmed_by = Some(device);
+        self
+    }
+
+    /// Create the edge in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database statement fails.
+    pub async fn create(self, pool: &PgPool) -> Result<CreatedEndorsement, EndorsementRepoError> {
+        let created = create_endorsement(
+            pool,
+            self.subject_id,
+            "trust",
+            Some(self.endorser_id),
+            None,
+            self.weight,
+            self.attestation.as_ref(),
+            self.in_slot,
+        )
+        .await?;
+
+        if let Some(device) = self.reconfirmed_by {
+            let timestamp = chrono::Utc::now().timestamp();
+            let signature = device.signing_key.sign(&reconfirmation_message(
+                self.endorser_id,
+                self.subject_id,
+                timestamp,
+            ));
+            let reconfirmation = serde_json::json!({
+                "subject_kid": device.kid.to_string(),
+                "subject_signature": encode_base64url(&signature.to_bytes()),
+                "timestamp": timestamp,
+            });
+            sqlx::query(
+                "UPDATE reputation__endorsements \
+                 SET last_confirmed_at = now(), \
+                     attestation = COALESCE(attestation, '{}'::jsonb) \
+                         || jsonb_build_object('reconfirmation', $2::jsonb) \
+                 WHERE id = $1",
+            )
+            .bind(created.id)
+            .bind(reconfirmation)
+            .execute(pool)
+            .await?;
+        }
+        if self.revoked {
+            sqlx::query("UPDATE reputation__endorsements SET revoked_at = now() WHERE id = $1")
+                .bind(created.id)
+                .execute(pool)
+                .await?;
+        }
+        Ok(created)
+    }
+}
+
+/// Build a v2 device-signed request for `method path` carrying a JSON `body`.
+pub fn sign_envelope(device: &TestDevice, method: &str, path: &str, body: &[u8]) -> SignedRequest {
+    let alg = SignatureAlg::Ed25519;
+    let timestamp = chrono::Utc::now().timestamp();
+    let nonce = Uuid::new_v4().to_string();
+    let device_kid = device.kid.to_string();
+    let canonical = RequestParts {
+        method,
+        host: "localhost",
+        path_and_query: path,
+        timestamp,
+        nonce: &nonce,
+        content_type: "application/json",
+        device_kid: &device_kid,
+        challenge: "",
+        body,
+    }
+    .canonical_v2(alg);
+    let signature = device.signing_key.sign(canonical.as_bytes());
+
+    SignedRequest {
+        alg: Some(alg.to_string()),
+        method: method.to_string(),
+        host: "localhost".to_string(),
+        path_and_query: path.to_string(),
+        timestamp,
+        nonce,
+        content_type: "application/json".to_string(),
+        device_kid: device.kid.clone(),
+        challenge: None,
+        body: encode_base64url(body),
+        signature: encode_base64url(&signature.to_bytes()),
+    }
+}
@@ -9,8 +9,13 @@
 //! let account = AccountFactory::new().with_username("alice").create(&mut *tx).await;
 //! let item = TestItemFactory::new().with_name("test item").create(&mut *tx).await;
 //! ```
+//!
+//! Factories that need signatures (devices, issued endorsements, trust edge
+//! re-confirmations) sign with real Ed25519 keys via `tc-crypto`, so the rows
+//! they create verify exactly like ones written through the API.
 
 mod account;
+mod device;
 mod device_auth;
 mod endorsement;
 mod signup;
@@ -18,10 +23,18 @@ mod signup_fixture;
 mod test_item;
 
 pub use account::{generate_test_keys, AccountFactory};
-pub use device_auth::{build_authed_request, sign_request, sign_request_at_timestamp};
-pub use endorsement::{insert_endorsement, insert_revoked_endorsement};
+pub use device::{DeviceFactory, TestDevice};
+pub use device_auth::{
+    build_authed_request, build_authed_request_v2, sign_request, sign_request_at_timestamp,
+};
+pub use endorsement::{
+    insert_endorsement, insert_revoked_endorsement, sign_envelope, EndorsementFactory,
+    TrustEdgeFactory,
+};
 pub use signup::{valid_signup_json, valid_signup_with_keys, SignupKeys};
-pub use signup_fixture::{signup_user, signup_user_in_pool};
+pub use signup_fixture::{
+    account_id_by_username, signup_user, signup_user_in_pool, signup_user_transactional,
+};
 pub use test_item::TestItemFactory;
 
 use std::sync::atomic::{AtomicU64, Ordering};
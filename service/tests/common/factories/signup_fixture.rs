@@ -1,8 +1,8 @@
 //! Signup integration helpers for tests that need a fully registered user.
 //!
-//! These combine [`isolated_db`], [`TestAppBuilder`], and [`valid_signup_with_keys`]
-//! into a single call that returns an app router, signing keys, and (optionally)
-//! the isolated database handle.
+//! These combine a database ([`isolated_db`] or a transactional pool),
+//! [`TestAppBuilder`], and [`valid_signup_with_keys`] into a single call that
+//! returns an app router, signing keys, and (optionally) the database handle.
 
 use axum::{
     body::Body,
@@ -45,6 +45,35 @@ pub async fn signup_user(username: &str) -> (axum::Router, SignupKeys, IsolatedD
     (app, keys, db)
 }
 
+/// Sign up a new user on a [`TestAppBuilder::with_transactional_pool`] app.
+///
+/// Faster than [`signup_user`]: runs on the shared database and everything is
+/// rolled back once the router and pool are dropped.
+pub async fn signup_user_transactional(username: &str) -> (axum::Router, SignupKeys, PgPool) {
+    let builder = TestAppBuilder::new().with_transactional_pool().await;
+    let pool = builder.pool().expect("transactional pool").clone();
+    let app = builder.build();
+
+    let (json, keys) = valid_signup_with_keys(username);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    (app, keys, pool)
+}
+
 /// Sign up a user into an existing database pool.
 ///
 /// Use when you need multiple users in the same database (e.g., cross-account
@@ -73,3 +102,13 @@ pub async fn signup_user_in_pool(username: &str, pool: &PgPool) -> (axum::Router
 
     (app, keys)
 }
+
+/// The ID of the account registered as `username`, for configuring admins
+/// and other per-account settings after signup.
+pub async fn account_id_by_username(username: &str, pool: &PgPool) -> uuid::Uuid {
+    sqlx::query_scalar("SELECT id FROM accounts WHERE username = $1")
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .expect("account exists")
+}
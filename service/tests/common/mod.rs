@@ -6,6 +6,9 @@
 //! - [`app_builder::TestAppBuilder`] - Build test Axum apps that mirror main.rs wiring
 //! - [`test_db`] - Shared PostgreSQL container for database integration tests
 //! - [`graphql`] - GraphQL response helpers for testing schema behavior
+//! - [`auth_conformance`] - Device-auth violation matrix for signed routes
+//! - [`scenario`] - Multi-step end-to-end scenarios replayed over HTTP
+//! - [`protocol_sim`] - Randomized multi-client protocol simulation with fault injection
 //!
 //! # App Builder Usage
 //!
@@ -45,6 +48,22 @@
 //! }
 //! ```
 //!
+//! ### `transactional_pool()` - Handler tests that need a `PgPool`
+//! - Handlers and repos take a pool and commit; this pool holds one
+//!   connection inside a transaction that is never committed
+//! - Use via [`app_builder::TestAppBuilder::with_transactional_pool()`]
+//! - Same shared database as `test_transaction()`, so just as fast
+//!
+//! ```ignore
+//! #[shared_runtime_test]
+//! async fn test_handler() {
+//!     let builder = TestAppBuilder::new().with_transactional_pool().await;
+//!     let pool = builder.pool().expect("pool").clone();
+//!     let app = builder.build();
+//!     // Requests and `pool` see each other's writes; all rolled back on drop
+//! }
+//! ```
+//!
 //! ### `isolated_db()` - Specialized tests requiring full DB isolation
 //! - Migration testing (rollback, idempotency)
 //! - Concurrent transaction behavior (SELECT FOR UPDATE, isolation levels)
@@ -97,9 +116,12 @@
 //! - `/tmp/tc-test-postgres.json`: Shared container state file. Delete to force a fresh container.
 
 pub mod app_builder;
+pub mod auth_conformance;
 pub mod factories;
 pub mod graphql;
 pub mod migration_helpers;
+pub mod protocol_sim;
+pub mod scenario;
 pub mod simulation;
 
 pub mod test_db {
@@ -294,6 +316,71 @@ pub mod test_db {
         TestTransaction::new().await
     }
 
+    /// Savepoint wrapping each checkout from a [`transactional_pool`].
+    const CHECKOUT_SAVEPOINT: &str = "tc_test_checkout";
+
+    /// Create a single-connection pool whose work is rolled back when the
+    /// pool is dropped.
+    ///
+    /// The connection opens a transaction as soon as it is established, so
+    /// `pool.begin()`/`commit()` inside repos become savepoints and nothing is
+    /// ever committed to the shared database. Each checkout also runs inside
+    /// its own savepoint, so a failed statement (e.g. a unique violation the
+    /// handler maps to 409) does not abort the outer transaction. This lets
+    /// handler tests that need a `PgPool` skip `isolated_db()`.
+    ///
+    /// Not suitable for code that holds one pool connection while acquiring
+    /// another (it would wait out the acquire timeout), or for tests that need
+    /// real commits, concurrent transactions, or advisory-lock contention — use
+    /// `isolated_db()` for those. Uncommitted rows still take row locks, so
+    /// concurrent tests inserting the same unique value will block each other;
+    /// use distinct usernames.
+    #[allow(clippy::expect_used)]
+    pub async fn transactional_pool() -> PgPool {
+        use sqlx::{Database, Executor, Postgres, TransactionManager};
+
+        let db = get_test_db().await;
+        PgPoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .acquire_timeout(Duration::from_secs(5))
+            // Replacing the connection would silently drop the open transaction
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .after_connect(|conn, _meta| {
+                Box::pin(async move {
+                    <Postgres as Database>::TransactionManager::begin(conn, None).await?;
+                    conn.execute(format!("SAVEPOINT {CHECKOUT_SAVEPOINT}").as_str())
+                        .await?;
+                    Ok(())
+                })
+            })
+            .before_acquire(|conn, _meta| {
+                Box::pin(async move {
+                    conn.execute(format!("SAVEPOINT {CHECKOUT_SAVEPOINT}").as_str())
+                        .await?;
+                    Ok(true)
+                })
+            })
+            .after_release(|conn, _meta| {
+                Box::pin(async move {
+                    let release = format!("RELEASE SAVEPOINT {CHECKOUT_SAVEPOINT}");
+                    if conn.execute(release.as_str()).await.is_err() {
+                        // The checkout failed mid-statement; discard just its work
+                        conn.execute(
+                            format!("ROLLBACK TO SAVEPOINT {CHECKOUT_SAVEPOINT}").as_str(),
+                        )
+                        .await?;
+                        conn.execute(release.as_str()).await?;
+                    }
+                    Ok(true)
+                })
+            })
+            .connect(db.database_url())
+            .await
+            .expect("Failed to create transactional test pool")
+    }
+
     /// RAII guard holding both the pool and container.
     /// Container is kept alive as long as the pool exists.
     pub struct TestDb {
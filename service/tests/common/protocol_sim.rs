@@ -0,0 +1,785 @@
+//! Randomized protocol simulation: many virtual clients, one in-process app.
+//!
+//! Each virtual client holds its own root and device keys and speaks the
+//! protocol the way the web client does — signing requests with `tc-crypto`
+//! primitives and the canonical device-auth message — but requests go
+//! straight into the [`Router`] with `oneshot`, never over a socket.
+//!
+//! A [`Simulation`] plays `days` simulated days. Each day clients sign up,
+//! add, revoke and rotate devices, endorse and un-endorse each other, and
+//! recover accounts after losing every device. Faults are mixed in: replayed
+//! and tampered requests, stale timestamps, forged signatures, and add-device
+//! responses that never reach the client. Every action asserts the status
+//! the client's own model predicts, and at the end of each day the global
+//! invariants are checked:
+//!
+//! - no revoked device ever authenticates;
+//! - a replayed, tampered, stale or forged request never succeeds;
+//! - each account's active devices on the server are exactly the ones its
+//!   client holds, and never more than the device limit;
+//! - each account's active trust edges are exactly the ones its client made.
+//!
+//! Simulated days are steps, not wall-clock time: signed requests carry the
+//! real time, so time-based expiry is out of scope. The one clock the harness
+//! does advance is the daily trust-action quota, by ageing
+//! `trust__action_log.quota_date` at the end of each day. The operation sequence
+//! comes from a seeded RNG, and failures name the seed and day; rerun with
+//! `TC_SIM_SEED=<seed>` to replay the same sequence.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Router,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tc_crypto::{encode_base64url, Kid};
+use tinycongress_api::identity::repo::MAX_DEVICES_PER_ACCOUNT;
+use tinycongress_api::identity::service::device_certificate_message;
+use tinycongress_api::reputation::repo::PgReputationRepo;
+use tinycongress_api::trust::engine::TrustEngine;
+use tinycongress_api::trust::repo::PgTrustRepo;
+use tinycongress_api::trust::service::DAILY_ACTION_QUOTA;
+use tinycongress_api::trust::worker::TrustWorker;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::common::app_builder::TestAppBuilder;
+use crate::common::factories::{sign_request, sign_request_at_timestamp, valid_signup_with_keys};
+
+/// Knobs for one simulation run.
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub seed: u64,
+    pub days: u32,
+    pub initial_clients: usize,
+    pub actions_per_day: usize,
+    /// Chance that an action is preceded by an injected fault.
+    pub fault_rate: f64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0x7c_5eed,
+            days: 21,
+            initial_clients: 6,
+            actions_per_day: 12,
+            fault_rate: 0.2,
+        }
+    }
+}
+
+impl SimConfig {
+    /// Defaults, with `TC_SIM_SEED` and `TC_SIM_DAYS` overriding the seed and
+    /// length.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(seed) = std::env::var("TC_SIM_SEED")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.seed = seed;
+        }
+        if let Some(days) = std::env::var("TC_SIM_DAYS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            config.days = days;
+        }
+        config
+    }
+}
+
+/// What a client knows about one of its device keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeviceState {
+    Active,
+    /// Added on the server, but the response never reached the client.
+    Unconfirmed,
+    /// The client lost the key; the server still accepts it until revoked.
+    Lost,
+    Revoked,
+}
+
+struct SimDevice {
+    signing_key: SigningKey,
+    kid: Kid,
+    state: DeviceState,
+}
+
+struct SimClient {
+    username: String,
+    account_id: Uuid,
+    root_key: SigningKey,
+    devices: Vec<SimDevice>,
+    /// Accounts this client currently endorses.
+    endorsing: HashSet<Uuid>,
+    /// Trust actions queued this simulated day, for the daily quota.
+    trust_actions: i64,
+}
+
+impl SimClient {
+    fn active(&self) -> impl Iterator<Item = (usize, &SimDevice)> {
+        self.devices
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.state == DeviceState::Active)
+    }
+
+    /// Devices the server should still accept.
+    fn server_active(&self) -> usize {
+        self.devices
+            .iter()
+            .filter(|d| d.state != DeviceState::Revoked)
+            .count()
+    }
+}
+
+/// Counts of what a run did.
+#[derive(Debug, Default)]
+pub struct SimReport {
+    pub seed: u64,
+    pub days: u32,
+    pub clients: usize,
+    pub actions: BTreeMap<&'static str, usize>,
+    pub faults: BTreeMap<&'static str, usize>,
+    /// Revoked-device authentication attempts, all refused.
+    pub revoked_probes: usize,
+    /// Days whose end-of-day invariant check ran to completion.
+    pub days_checked: u32,
+}
+
+impl std::fmt::Display for SimReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "seed {} | {} days | {} clients | {} revoked-device probes",
+            self.seed, self.days, self.clients, self.revoked_probes
+        )?;
+        writeln!(f, "actions: {:?}", self.actions)?;
+        write!(f, "faults:  {:?}", self.faults)
+    }
+}
+
+/// A running simulation; see the module docs.
+pub struct Simulation {
+    config: SimConfig,
+    rng: StdRng,
+    app: Router,
+    pool: PgPool,
+    worker: TrustWorker,
+    clients: Vec<SimClient>,
+    day: u32,
+    report: SimReport,
+}
+
+impl Simulation {
+    /// Start a simulation against a fresh transactional app.
+    pub async fn new(config: SimConfig) -> Self {
+        let builder = TestAppBuilder::new().with_transactional_pool().await;
+        let pool = builder.pool().expect("transactional pool").clone();
+        let app = builder.build();
+        let worker = TrustWorker::new(
+            pool.clone(),
+            Arc::new(PgTrustRepo::new(pool.clone())),
+            Arc::new(PgReputationRepo::new(pool.clone())),
+            Arc::new(TrustEngine::new(pool.clone())),
+        );
+        Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            report: SimReport {
+                seed: config.seed,
+                days: config.days,
+                ..SimReport::default()
+            },
+            config,
+            app,
+            pool,
+            worker,
+            clients: Vec::new(),
+            day: 0,
+        }
+    }
+
+    /// Play every day, checking invariants at the end of each, and return
+    /// what happened.
+    pub async fn run(mut self) -> SimReport {
+        for _ in 0..self.config.initial_clients {
+            self.signup().await;
+        }
+        for day in 0..self.config.days {
+            self.day = day;
+            for _ in 0..self.config.actions_per_day {
+                if self.rng.gen_bool(self.config.fault_rate) {
+                    self.inject_fault().await;
+                }
+                self.act().await;
+            }
+            self.check_invariants().await;
+            self.report.days_checked += 1;
+            self.end_day().await;
+        }
+        self.report.clients = self.clients.len();
+        self.report
+    }
+
+    fn context(&self) -> String {
+        format!("[seed {} day {}]", self.config.seed, self.day)
+    }
+
+    fn count(map: &mut BTreeMap<&'static str, usize>, key: &'static str) {
+        *map.entry(key).or_default() += 1;
+    }
+
+    // ─── Transport ──────────────────────────────────────────────────────────
+
+    async fn send(&self, request: Request<Body>) -> (StatusCode, Value) {
+        let response = self.app.clone().oneshot(request).await.expect("response");
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .expect("body");
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    fn build(
+        method: &Method,
+        path: &str,
+        body: &str,
+        headers: &[(&'static str, String)],
+    ) -> Request<Body> {
+        let mut builder = Request::builder().method(method.clone()).uri(path);
+        for (name, value) in headers {
+            builder = builder.header(*name, value);
+        }
+        if !body.is_empty() {
+            builder = builder.header(CONTENT_TYPE, "application/json");
+        }
+        builder.body(Body::from(body.to_string())).expect("request")
+    }
+
+    async fn signed(
+        &self,
+        client: usize,
+        device: usize,
+        method: Method,
+        path: &str,
+        body: &str,
+    ) -> (StatusCode, Value) {
+        let device = &self.clients[client].devices[device];
+        let headers = sign_request(
+            method.as_str(),
+            path,
+            body.as_bytes(),
+            &device.signing_key,
+            &device.kid,
+        );
+        self.send(Self::build(&method, path, body, &headers)).await
+    }
+
+    fn expect(&self, client: usize, action: &str, got: StatusCode, want: StatusCode, body: &Value) {
+        assert_eq!(
+            got,
+            want,
+            "{} {} {action}: {body}",
+            self.context(),
+            self.clients[client].username
+        );
+    }
+
+    // ─── Choosing ───────────────────────────────────────────────────────────
+
+    fn pick_client(&mut self) -> usize {
+        self.rng.gen_range(0..self.clients.len())
+    }
+
+    /// A random active device of `client`, other than `except`.
+    fn pick_active(&mut self, client: usize, except: Option<usize>) -> Option<usize> {
+        let candidates: Vec<usize> = self.clients[client]
+            .active()
+            .map(|(i, _)| i)
+            .filter(|i| Some(*i) != except)
+            .collect();
+        if candidates.is_empty() {
+            None
+        } else {
+            Some(candidates[self.rng.gen_range(0..candidates.len())])
+        }
+    }
+
+    async fn act(&mut self) {
+        let client = self.pick_client();
+        self.reconcile(client).await;
+        match self.rng.gen_range(0..15) {
+            0 => self.signup().await,
+            1..=3 => {
+                self.add_device(client, false).await;
+            }
+            4..=5 => self.revoke_device(client).await,
+            6..=7 => self.rotate_device(client).await,
+            8..=10 => self.endorse(client).await,
+            11..=12 => self.revoke_endorsement(client).await,
+            _ => self.recover(client).await,
+        }
+    }
+
+    // ─── Actions ────────────────────────────────────────────────────────────
+
+    async fn signup(&mut self) {
+        let username = format!("sim-{}-{}", self.config.seed % 10_000, self.clients.len());
+        let (json, keys) = valid_signup_with_keys(&username);
+        let (status, body) = self
+            .send(Self::build(&Method::POST, "/auth/signup", &json, &[]))
+            .await;
+        assert_eq!(
+            status,
+            StatusCode::CREATED,
+            "{} signup {username}: {body}",
+            self.context()
+        );
+        let account_id = body["account_id"]
+            .as_str()
+            .and_then(|id| id.parse().ok())
+            .expect("account_id");
+        self.clients.push(SimClient {
+            username,
+            account_id,
+            root_key: keys.root_signing_key,
+            devices: vec![SimDevice {
+                signing_key: keys.device_signing_key,
+                kid: keys.device_kid,
+                state: DeviceState::Active,
+            }],
+            endorsing: HashSet::new(),
+            trust_actions: 0,
+        });
+        Self::count(&mut self.report.actions, "signup");
+    }
+
+    /// Add a device certified by the root key. With `lose_response`, the
+    /// server's answer is checked but withheld from the client.
+    async fn add_device(&mut self, client: usize, lose_response: bool) -> Option<usize> {
+        let signer = self.pick_active(client, None)?;
+        let signing_key = SigningKey::generate(&mut self.rng);
+        let pubkey = signing_key.verifying_key().to_bytes();
+        let certificate = self.clients[client].root_key.sign(&pubkey);
+        let body = json!({
+            "pubkey": encode_base64url(&pubkey),
+            "name": format!("Sim device {}", self.clients[client].devices.len() + 1),
+            "certificate": encode_base64url(&certificate.to_bytes()),
+        })
+        .to_string();
+        let at_limit = self.clients[client].server_active() as i64 >= MAX_DEVICES_PER_ACCOUNT;
+        let (status, response) = self
+            .signed(client, signer, Method::POST, "/auth/devices", &body)
+            .await;
+        if at_limit {
+            self.expect(
+                client,
+                "add_device at limit",
+                status,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                &response,
+            );
+            Self::count(&mut self.report.actions, "add_device_refused");
+            return None;
+        }
+        self.expect(client, "add_device", status, StatusCode::CREATED, &response);
+        self.clients[client].devices.push(SimDevice {
+            signing_key,
+            kid: Kid::derive(&pubkey),
+            state: if lose_response {
+                DeviceState::Unconfirmed
+            } else {
+                DeviceState::Active
+            },
+        });
+        Self::count(&mut self.report.actions, "add_device");
+        Some(self.clients[client].devices.len() - 1)
+    }
+
+    async fn revoke(&mut self, client: usize, signer: usize, target: usize) {
+        let path = format!("/auth/devices/{}", self.clients[client].devices[target].kid);
+        let (status, response) = self.signed(client, signer, Method::DELETE, &path, "").await;
+        self.expect(
+            client,
+            "revoke_device",
+            status,
+            StatusCode::NO_CONTENT,
+            &response,
+        );
+        self.clients[client].devices[target].state = DeviceState::Revoked;
+    }
+
+    async fn revoke_device(&mut self, client: usize) {
+        let Some(target) = self.pick_active(client, None) else {
+            return;
+        };
+        let Some(signer) = self.pick_active(client, Some(target)) else {
+            return;
+        };
+        self.revoke(client, signer, target).await;
+        Self::count(&mut self.report.actions, "revoke_device");
+    }
+
+    /// Replace a device: add a new one, then revoke the old one from it.
+    async fn rotate_device(&mut self, client: usize) {
+        let Some(old) = self.pick_active(client, None) else {
+            return;
+        };
+        let Some(new) = self.add_device(client, false).await else {
+            return;
+        };
+        self.revoke(client, new, old).await;
+        Self::count(&mut self.report.actions, "rotate_device");
+    }
+
+    async fn trust_action(&mut self, client: usize, path: &str, body: &str) -> bool {
+        let signer = self
+            .pick_active(client, None)
+            .expect("client has an active device");
+        let (status, response) = self.signed(client, signer, Method::POST, path, body).await;
+        if self.clients[client].trust_actions >= DAILY_ACTION_QUOTA {
+            self.expect(
+                client,
+                path,
+                status,
+                StatusCode::TOO_MANY_REQUESTS,
+                &response,
+            );
+            Self::count(&mut self.report.actions, "trust_action_over_quota");
+            return false;
+        }
+        self.expect(client, path, status, StatusCode::ACCEPTED, &response);
+        self.clients[client].trust_actions += 1;
+        assert!(
+            self.worker
+                .process_one()
+                .await
+                .expect("process trust action"),
+            "{} {path}: nothing queued",
+            self.context()
+        );
+        true
+    }
+
+    async fn endorse(&mut self, client: usize) {
+        let me = self.clients[client].account_id;
+        let candidates: Vec<Uuid> = self
+            .clients
+            .iter()
+            .map(|c| c.account_id)
+            .filter(|id| *id != me && !self.clients[client].endorsing.contains(id))
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        let subject = candidates[self.rng.gen_range(0..candidates.len())];
+        let weight = f64::from(self.rng.gen_range(1..=10_u8)) / 10.0;
+        let body = json!({ "subject_id": subject, "weight": weight }).to_string();
+        if self.trust_action(client, "/trust/endorse", &body).await {
+            self.clients[client].endorsing.insert(subject);
+            Self::count(&mut self.report.actions, "endorse");
+        }
+    }
+
+    async fn revoke_endorsement(&mut self, client: usize) {
+        let Some(subject) = self.clients[client].endorsing.iter().next().copied() else {
+            return;
+        };
+        let body = json!({ "subject_id": subject }).to_string();
+        if self.trust_action(client, "/trust/revoke", &body).await {
+            self.clients[client].endorsing.remove(&subject);
+            Self::count(&mut self.report.actions, "revoke_endorsement");
+        }
+    }
+
+    /// The client loses every device, fetches its backup, logs in with the
+    /// root key as a new device, and revokes the lost ones.
+    async fn recover(&mut self, client: usize) {
+        if self.clients[client].server_active() as i64 >= MAX_DEVICES_PER_ACCOUNT {
+            return;
+        }
+        for device in &mut self.clients[client].devices {
+            if device.state == DeviceState::Active {
+                device.state = DeviceState::Lost;
+            }
+        }
+
+        let username = self.clients[client].username.clone();
+        let (status, response) = self
+            .send(Self::build(
+                &Method::GET,
+                &format!("/auth/backup/{username}"),
+                "",
+                &[],
+            ))
+            .await;
+        self.expect(client, "fetch backup", status, StatusCode::OK, &response);
+
+        let signing_key = SigningKey::generate(&mut self.rng);
+        let pubkey = signing_key.verifying_key().to_bytes();
+        let timestamp = chrono::Utc::now().timestamp();
+        let certificate = self.clients[client]
+            .root_key
+            .sign(&device_certificate_message(&pubkey, Some(timestamp)));
+        let body = json!({
+            "username": username,
+            "timestamp": timestamp,
+            "device": {
+                "pubkey": encode_base64url(&pubkey),
+                "name": "Recovered device",
+                "certificate": encode_base64url(&certificate.to_bytes()),
+            },
+        })
+        .to_string();
+        let (status, response) = self
+            .send(Self::build(&Method::POST, "/auth/login", &body, &[]))
+            .await;
+        self.expect(
+            client,
+            "recovery login",
+            status,
+            StatusCode::CREATED,
+            &response,
+        );
+        self.clients[client].devices.push(SimDevice {
+            signing_key,
+            kid: Kid::derive(&pubkey),
+            state: DeviceState::Active,
+        });
+        let recovered = self.clients[client].devices.len() - 1;
+
+        let lost: Vec<usize> = self.clients[client]
+            .devices
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.state == DeviceState::Lost)
+            .map(|(i, _)| i)
+            .collect();
+        for target in lost {
+            self.revoke(client, recovered, target).await;
+        }
+        Self::count(&mut self.report.actions, "recover");
+    }
+
+    /// Adopt devices whose add-device response was lost, by listing the
+    /// account's devices the way a client would after a timeout.
+    async fn reconcile(&mut self, client: usize) {
+        if !self.clients[client]
+            .devices
+            .iter()
+            .any(|d| d.state == DeviceState::Unconfirmed)
+        {
+            return;
+        }
+        let kids = self.server_devices(client).await;
+        let context = self.context();
+        for device in &mut self.clients[client].devices {
+            if device.state == DeviceState::Unconfirmed {
+                assert!(
+                    kids.contains(&device.kid.to_string()),
+                    "{context} device whose add response was lost is missing on the server"
+                );
+                device.state = DeviceState::Active;
+            }
+        }
+    }
+
+    // ─── Faults ─────────────────────────────────────────────────────────────
+
+    async fn inject_fault(&mut self) {
+        let client = self.pick_client();
+        let Some(device) = self.pick_active(client, None) else {
+            return;
+        };
+        let (key, kid) = {
+            let d = &self.clients[client].devices[device];
+            (d.signing_key.clone(), d.kid.clone())
+        };
+        let fault = match self.rng.gen_range(0..5) {
+            0 => {
+                // The same signed request, sent twice
+                let headers = sign_request("GET", "/auth/devices", b"", &key, &kid);
+                let request = || Self::build(&Method::GET, "/auth/devices", "", &headers);
+                let (status, body) = self.send(request()).await;
+                self.expect(client, "original of replay", status, StatusCode::OK, &body);
+                let (status, body) = self.send(request()).await;
+                self.expect(client, "replay", status, StatusCode::UNAUTHORIZED, &body);
+                "replay"
+            }
+            1 => {
+                // Signature over one body, sent with another
+                let signed_body = json!({ "name": "harmless" }).to_string();
+                let sent_body = json!({
+                    "pubkey": encode_base64url(&[7u8; 32]),
+                    "name": "injected",
+                    "certificate": encode_base64url(&[7u8; 64]),
+                })
+                .to_string();
+                let headers =
+                    sign_request("POST", "/auth/devices", signed_body.as_bytes(), &key, &kid);
+                let (status, body) = self
+                    .send(Self::build(
+                        &Method::POST,
+                        "/auth/devices",
+                        &sent_body,
+                        &headers,
+                    ))
+                    .await;
+                self.expect(
+                    client,
+                    "tampered body",
+                    status,
+                    StatusCode::UNAUTHORIZED,
+                    &body,
+                );
+                "tampered_body"
+            }
+            2 => {
+                let stale = chrono::Utc::now().timestamp() - 3600;
+                let nonce = Uuid::new_v4().to_string();
+                let headers = sign_request_at_timestamp(
+                    "GET",
+                    "/auth/devices",
+                    b"",
+                    &key,
+                    &kid,
+                    stale,
+                    &nonce,
+                );
+                let (status, body) = self
+                    .send(Self::build(&Method::GET, "/auth/devices", "", &headers))
+                    .await;
+                self.expect(
+                    client,
+                    "stale timestamp",
+                    status,
+                    StatusCode::UNAUTHORIZED,
+                    &body,
+                );
+                "stale_timestamp"
+            }
+            3 => {
+                // Another client's device key claiming this device's KID
+                let other = self.pick_client();
+                let forger = self.clients[other].root_key.clone();
+                let headers = sign_request("GET", "/auth/devices", b"", &forger, &kid);
+                let (status, body) = self
+                    .send(Self::build(&Method::GET, "/auth/devices", "", &headers))
+                    .await;
+                self.expect(
+                    client,
+                    "forged signature",
+                    status,
+                    StatusCode::UNAUTHORIZED,
+                    &body,
+                );
+                "forged_signature"
+            }
+            _ => {
+                if self.add_device(client, true).await.is_none() {
+                    return;
+                }
+                "lost_response"
+            }
+        };
+        Self::count(&mut self.report.faults, fault);
+    }
+
+    /// Move today's trust actions to yesterday so the quota starts over.
+    async fn end_day(&mut self) {
+        sqlx::query("UPDATE trust__action_log SET quota_date = quota_date - 1")
+            .execute(&self.pool)
+            .await
+            .expect("age trust action quota");
+        for client in &mut self.clients {
+            client.trust_actions = 0;
+        }
+    }
+
+    // ─── Invariants ─────────────────────────────────────────────────────────
+
+    /// KIDs of `client`'s unrevoked devices, as the server lists them.
+    async fn server_devices(&self, client: usize) -> HashSet<String> {
+        let device = self.clients[client]
+            .active()
+            .map(|(i, _)| i)
+            .next()
+            .expect("client has an active device");
+        let (status, body) = self
+            .signed(client, device, Method::GET, "/auth/devices", "")
+            .await;
+        self.expect(client, "list devices", status, StatusCode::OK, &body);
+        body["devices"]
+            .as_array()
+            .expect("devices")
+            .iter()
+            .filter(|d| d["revoked_at"].is_null())
+            .map(|d| d["device_kid"].as_str().expect("device_kid").to_string())
+            .collect()
+    }
+
+    async fn check_invariants(&mut self) {
+        for client in 0..self.clients.len() {
+            self.reconcile(client).await;
+
+            let expected: HashSet<String> = self.clients[client]
+                .devices
+                .iter()
+                .filter(|d| d.state != DeviceState::Revoked)
+                .map(|d| d.kid.to_string())
+                .collect();
+            let actual = self.server_devices(client).await;
+            assert_eq!(
+                actual,
+                expected,
+                "{} {}: server and client disagree on active devices",
+                self.context(),
+                self.clients[client].username
+            );
+            assert!(actual.len() as i64 <= MAX_DEVICES_PER_ACCOUNT);
+
+            for device in 0..self.clients[client].devices.len() {
+                if self.clients[client].devices[device].state != DeviceState::Revoked {
+                    continue;
+                }
+                let (status, body) = self
+                    .signed(client, device, Method::GET, "/auth/devices", "")
+                    .await;
+                self.expect(
+                    client,
+                    "revoked device probe",
+                    status,
+                    StatusCode::FORBIDDEN,
+                    &body,
+                );
+                self.report.revoked_probes += 1;
+            }
+
+            let edges: Vec<Uuid> = sqlx::query_scalar(
+                "SELECT subject_id FROM reputation__endorsements \
+                 WHERE endorser_id = $1 AND topic = 'trust' AND revoked_at IS NULL",
+            )
+            .bind(self.clients[client].account_id)
+            .fetch_all(&self.pool)
+            .await
+            .expect("trust edges");
+            assert_eq!(
+                edges.into_iter().collect::<HashSet<_>>(),
+                self.clients[client].endorsing,
+                "{} {}: server and client disagree on trust edges",
+                self.context(),
+                self.clients[client].username
+            );
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Scenario DSL for multi-step end-to-end tests.
+//!
+//! A [`Scenario`] is a list of user-level steps (sign up, add a device,
+//! endorse someone, ...) that [`Scenario::run`] replays over HTTP against a
+//! [`TestAppBuilder::with_transactional_pool`] app, asserting that each step
+//! succeeds. Steps act as the *current* user: the last one signed up, or the
+//! one picked with [`Scenario::as_user`]. Trust actions are processed by a
+//! [`TrustWorker`] as soon as they are queued, so later steps see their
+//! effects.
+//!
+//! The returned [`ScenarioRun`] keeps the app, pool, and every user's keys,
+//! for follow-up requests and assertions:
+//!
+//! ```ignore
+//! let run = Scenario::new()
+//!     .signup("bob")
+//!     .signup("alice")
+//!     .add_device()
+//!     .endorse("bob", 0.8)
+//!     .revoke_device()
+//!     .run()
+//!     .await;
+//!
+//! let (status, _) = run.request_with_device("alice", 1, Method::GET, "/auth/devices", "").await;
+//! assert_eq!(status, StatusCode::UNAUTHORIZED);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Router,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::rngs::OsRng;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tc_crypto::{encode_base64url, Kid};
+use tinycongress_api::reputation::repo::PgReputationRepo;
+use tinycongress_api::trust::engine::TrustEngine;
+use tinycongress_api::trust::repo::PgTrustRepo;
+use tinycongress_api::trust::worker::TrustWorker;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::common::app_builder::TestAppBuilder;
+use crate::common::factories::{build_authed_request, valid_signup_with_keys};
+
+/// One user-level action in a scenario.
+#[derive(Debug, Clone)]
+enum Step {
+    Signup(String),
+    AsUser(String),
+    AddDevice,
+    RevokeDevice,
+    Endorse { subject: String, weight: f32 },
+    RevokeEndorsement { subject: String },
+}
+
+/// Builder for a multi-step scenario; see the module docs.
+#[derive(Debug, Default)]
+pub struct Scenario {
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sign up `username` with fresh root and device keys and make them the
+    /// current user.
+    #[must_use]
+    pub fn signup(mut self, username: &str) -> Self {
+        self.steps.push(Step::Signup(username.to_string()));
+        self
+    }
+
+    /// Make an already signed-up user the current one.
+    #[must_use]
+    pub fn as_user(mut self, username: &str) -> Self {
+        self.steps.push(Step::AsUser(username.to_string()));
+        self
+    }
+
+    /// Add a new device to the current user, certified by their root key.
+    #[must_use]
+    pub fn add_device(mut self) -> Self {
+        self.steps.push(Step::AddDevice);
+        self
+    }
+
+    /// Revoke the current user's most recently added device, signing with
+    /// their first device.
+    #[must_use]
+    pub fn revoke_device(mut self) -> Self {
+        self.steps.push(Step::RevokeDevice);
+        self
+    }
+
+    /// The current user endorses `subject` in the trust graph with `weight`.
+    #[must_use]
+    pub fn endorse(mut self, subject: &str, weight: f32) -> Self {
+        self.steps.push(Step::Endorse {
+            subject: subject.to_string(),
+            weight,
+        });
+        self
+    }
+
+    /// The current user revokes their trust endorsement of `subject`.
+    #[must_use]
+    pub fn revoke_endorsement(mut self, subject: &str) -> Self {
+        self.steps.push(Step::RevokeEndorsement {
+            subject: subject.to_string(),
+        });
+        self
+    }
+
+    /// Replay every step on a fresh transactional app, panicking with the
+    /// step and response if one fails.
+    pub async fn run(self) -> ScenarioRun {
+        let builder = TestAppBuilder::new().with_transactional_pool().await;
+        let pool = builder.pool().expect("transactional pool").clone();
+        let app = builder.build();
+        let worker = TrustWorker::new(
+            pool.clone(),
+            Arc::new(PgTrustRepo::new(pool.clone())),
+            Arc::new(PgReputationRepo::new(pool.clone())),
+            Arc::new(TrustEngine::new(pool.clone())),
+        );
+        let mut run = ScenarioRun {
+            app,
+            pool,
+            users: HashMap::new(),
+            current: None,
+        };
+        for step in self.steps {
+            run.apply(&step, &worker).await;
+        }
+        run
+    }
+}
+
+/// A device key held by a scenario user.
+pub struct ScenarioDevice {
+    pub signing_key: SigningKey,
+    pub kid: Kid,
+    pub revoked: bool,
+}
+
+/// A signed-up scenario user.
+pub struct ScenarioUser {
+    pub account_id: Uuid,
+    pub root_signing_key: SigningKey,
+    /// Every device ever added, in order; index 0 is the signup device.
+    pub devices: Vec<ScenarioDevice>,
+}
+
+impl ScenarioUser {
+    /// The first device that has not been revoked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every device is revoked.
+    pub fn active_device(&self) -> &ScenarioDevice {
+        self.devices
+            .iter()
+            .find(|d| !d.revoked)
+            .expect("user has an active device")
+    }
+}
+
+/// State left after a scenario ran: the app, its pool, and every user.
+pub struct ScenarioRun {
+    pub app: Router,
+    pub pool: PgPool,
+    users: HashMap<String, ScenarioUser>,
+    current: Option<String>,
+}
+
+impl ScenarioRun {
+    /// The user signed up as `username`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no such user was signed up.
+    pub fn user(&self, username: &str) -> &ScenarioUser {
+        self.users
+            .get(username)
+            .unwrap_or_else(|| panic!("scenario has no user {username}"))
+    }
+
+    /// Send a request signed by `username`'s first active device.
+    pub async fn request(
+        &self,
+        username: &str,
+        method: Method,
+        path: &str,
+        body: &str,
+    ) -> (StatusCode, Value) {
+        let device = self.user(username).active_device();
+        self.send(build_authed_request(
+            method,
+            path,
+            body,
+            &device.signing_key,
+            &device.kid,
+        ))
+        .await
+    }
+
+    /// Send a request signed by `username`'s device at `index`, revoked or not.
+    pub async fn request_with_device(
+        &self,
+        username: &str,
+        index: usize,
+        method: Method,
+        path: &str,
+        body: &str,
+    ) -> (StatusCode, Value) {
+        let device = &self.user(username).devices[index];
+        self.send(build_authed_request(
+            method,
+            path,
+            body,
+            &device.signing_key,
+            &device.kid,
+        ))
+        .await
+    }
+
+    async fn send(&self, request: Request<Body>) -> (StatusCode, Value) {
+        let response = self.app.clone().oneshot(request).await.expect("response");
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .expect("body");
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    fn current(&self) -> &str {
+        self.current
+            .as_deref()
+            .expect("scenario step needs a current user; sign someone up first")
+    }
+
+    async fn apply(&mut self, step: &Step, worker: &TrustWorker) {
+        match step {
+            Step::Signup(username) => self.signup(username).await,
+            Step::AsUser(username) => {
+                self.user(username);
+                self.current = Some(username.clone());
+            }
+            Step::AddDevice => self.add_device().await,
+            Step::RevokeDevice => self.revoke_device().await,
+            Step::Endorse { subject, weight } => {
+                let body = json!({
+                    "subject_id": self.user(subject).account_id,
+                    "weight": weight,
+                })
+                .to_string();
+                self.trust_action(step, "/trust/endorse", &body, worker)
+                    .await;
+            }
+            Step::RevokeEndorsement { subject } => {
+                let body = json!({ "subject_id": self.user(subject).account_id }).to_string();
+                self.trust_action(step, "/trust/revoke", &body, worker)
+                    .await;
+            }
+        }
+    }
+
+    async fn signup(&mut self, username: &str) {
+        let (json, keys) = valid_signup_with_keys(username);
+        let (status, body) = self
+            .send(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/auth/signup")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(json))
+                    .expect("request"),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "signup {username}: {body}");
+        let account_id = body["account_id"]
+            .as_str()
+            .and_then(|id| id.parse().ok())
+            .expect("account_id");
+        self.users.insert(
+            username.to_string(),
+            ScenarioUser {
+                account_id,
+                root_signing_key: keys.root_signing_key,
+                devices: vec![ScenarioDevice {
+                    signing_key: keys.device_signing_key,
+                    kid: keys.device_kid,
+                    revoked: false,
+                }],
+            },
+        );
+        self.current = Some(username.to_string());
+    }
+
+    async fn add_device(&mut self) {
+        let username = self.current().to_string();
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let pubkey = signing_key.verifying_key().to_bytes();
+        let certificate = self.user(&username).root_signing_key.sign(&pubkey);
+        let body = json!({
+            "pubkey": encode_base64url(&pubkey),
+            "name": format!("Device {}", self.user(&username).devices.len() + 1),
+            "certificate": encode_base64url(&certificate.to_bytes()),
+        })
+        .to_string();
+        let (status, response) = self
+            .request(&username, Method::POST, "/auth/devices", &body)
+            .await;
+        assert_eq!(
+            status,
+            StatusCode::CREATED,
+            "{username} add_device: {response}"
+        );
+        self.users
+            .get_mut(&username)
+            .expect("current user")
+            .devices
+            .push(ScenarioDevice {
+                signing_key,
+                kid: Kid::derive(&pubkey),
+                revoked: false,
+            });
+    }
+
+    async fn revoke_device(&mut self) {
+        let username = self.current().to_string();
+        let user = self.user(&username);
+        let (index, target) = user
+            .devices
+            .iter()
+            .enumerate()
+            .skip(1)
+            .rev()
+            .find(|(_, d)| !d.revoked)
+            .expect("revoke_device needs an active device besides the signup one");
+        let path = format!("/auth/devices/{}", target.kid);
+        let (status, response) = self
+            .request_with_device(&username, 0, Method::DELETE, &path, "")
+            .await;
+        assert_eq!(
+            status,
+            StatusCode::NO_CONTENT,
+            "{username} revoke_device: {response}"
+        );
+        self.users.get_mut(&username).expect("current user").devices[index].revoked = true;
+    }
+
+    async fn trust_action(&self, step: &Step, path: &str, body: &str, worker: &TrustWorker) {
+        let username = self.current();
+        let (status, response) = self.request(username, Method::POST, path, body).await;
+        assert_eq!(
+            status,
+            StatusCode::ACCEPTED,
+            "{username} {step:?}: {response}"
+        );
+        assert!(
+            worker.process_one().await.expect("process trust action"),
+            "{username} {step:?}: no queued action to process"
+        );
+    }
+}
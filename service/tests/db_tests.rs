@@ -451,3 +451,52 @@ async fn test_isolated_dbs_are_independent() {
         .expect("Failed to count in db2");
     assert_eq!(count_db2, 0, "Item should NOT exist in db2");
 }
+
+/// A login granted only `tc_reader` passes the startup check and can read;
+/// the migration superuser does not pass it.
+#[shared_runtime_test]
+async fn test_reader_role_is_select_only() {
+    use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+    use std::str::FromStr;
+    use tinycongress_api::db::verify_read_only;
+
+    let db = isolated_db().await;
+    let login = format!("tc_reader_test_{}", uuid::Uuid::new_v4().simple());
+    query(&format!(
+        "CREATE ROLE {login} LOGIN PASSWORD 'reader' IN ROLE tc_reader"
+    ))
+    .execute(db.pool())
+    .await
+    .expect("create reader login");
+
+    let options = PgConnectOptions::from_str(db.database_url())
+        .expect("database url")
+        .username(&login)
+        .password("reader");
+    let reader = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("connect as reader");
+
+    verify_read_only(&reader)
+        .await
+        .expect("tc_reader member must pass the read-only check");
+    let _: i64 = query_scalar("SELECT COUNT(*) FROM accounts")
+        .fetch_one(&reader)
+        .await
+        .expect("reader can SELECT");
+    let write = query("DELETE FROM test_items").execute(&reader).await;
+    assert!(write.is_err(), "reader must not be able to write");
+
+    let err = verify_read_only(db.pool())
+        .await
+        .expect_err("superuser must fail the read-only check");
+    assert!(err.to_string().contains("superuser"), "{err}");
+
+    reader.close().await;
+    query(&format!("DROP ROLE {login}"))
+        .execute(db.pool())
+        .await
+        .expect("drop reader login");
+}
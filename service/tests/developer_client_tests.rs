@@ -0,0 +1,288 @@
+//! Integration tests for OAuth client registration under `/developer/clients`
+//! and the `/oauth` authorization code flow.
+
+mod common;
+
+use axum::{
+    body::Body,
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        Method, Request, StatusCode,
+    },
+    Router,
+};
+use chrono::Utc;
+use serde_json::Value;
+use tower::ServiceExt;
+
+use common::factories::{
+    build_authed_request, signup_user_transactional, valid_signup_with_keys, SignupKeys,
+};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::developer::{
+    repo::{get_client_by_client_id, list_clients_by_owner},
+    secret_matches,
+};
+
+async fn signup(app: &Router, username: &str) -> SignupKeys {
+    let (json, keys) = valid_signup_with_keys(username);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    keys
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &SignupKeys,
+) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            method,
+            path,
+            body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[shared_runtime_test]
+async fn test_client_registration_rotation_and_revocation() {
+    let (app, dev, pool) = signup_user_transactional("oauthdev").await;
+    let other = signup(&app, "oauthother").await;
+
+    let request = r#"{"name":"Civic dashboard","redirect_uris":["https://dash.example.org/cb"],"scopes":["trust:read","devices:read"]}"#;
+    let (status, created) = send(&app, Method::POST, "/developer/clients", request, &dev).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let client_id = created["client_id"]
+        .as_str()
+        .expect("client_id")
+        .to_string();
+    let first_secret = created["client_secret"]
+        .as_str()
+        .expect("secret")
+        .to_string();
+    assert_eq!(
+        created["scopes"],
+        serde_json::json!(["devices:read", "trust:read"])
+    );
+
+    // The secret is stored hashed and never listed
+    let (status, listed) = send(&app, Method::GET, "/developer/clients", "", &dev).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["clients"].as_array().map(Vec::len), Some(1));
+    assert!(listed["clients"][0].get("client_secret").is_none());
+    let record = get_client_by_client_id(&pool, &client_id)
+        .await
+        .expect("client");
+    assert_ne!(record.secret_hash, first_secret.as_bytes());
+    assert!(secret_matches(&record, &first_secret, Utc::now()));
+
+    // Other accounts cannot see or manage it
+    let (_, listed) = send(&app, Method::GET, "/developer/clients", "", &other).await;
+    assert_eq!(listed["clients"].as_array().map(Vec::len), Some(0));
+    let rotate = format!("/developer/clients/{client_id}/secret");
+    let (status, _) = send(&app, Method::POST, &rotate, "", &other).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // After rotation both secrets work until the grace period ends
+    let (status, rotated) = send(&app, Method::POST, &rotate, "", &dev).await;
+    assert_eq!(status, StatusCode::OK);
+    let second_secret = rotated["client_secret"]
+        .as_str()
+        .expect("secret")
+        .to_string();
+    assert!(rotated["previous_secret_expires_at"].is_string());
+    let record = get_client_by_client_id(&pool, &client_id)
+        .await
+        .expect("client");
+    assert!(secret_matches(&record, &first_secret, Utc::now()));
+    assert!(secret_matches(&record, &second_secret, Utc::now()));
+
+    let path = format!("/developer/clients/{client_id}");
+    let (status, _) = send(
+        &app,
+        Method::PATCH,
+        &path,
+        r#"{"redirect_uris":["http://evil.example.org/cb"]}"#,
+        &dev,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, updated) = send(&app, Method::PATCH, &path, r#"{"name":"Dashboard"}"#, &dev).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["name"], "Dashboard");
+
+    let (status, _) = send(&app, Method::DELETE, &path, "", &dev).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, Method::DELETE, &path, "", &dev).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let record = get_client_by_client_id(&pool, &client_id)
+        .await
+        .expect("client");
+    assert!(!secret_matches(&record, &second_secret, Utc::now()));
+    let owned = list_clients_by_owner(&pool, record.owner_account_id)
+        .await
+        .expect("list");
+    assert!(owned[0].revoked_at.is_some());
+}
+
+async fn exchange(app: &Router, form: &str) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/oauth/token")
+                .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(form.to_string()))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[shared_runtime_test]
+async fn test_authorization_code_flow_with_pkce() {
+    let (app, dev, _pool) = signup_user_transactional("oauthapp").await;
+    let user = signup(&app, "oauthuser").await;
+
+    let request = r#"{"name":"Civic app","redirect_uris":["https://civic.example.org/cb"],"scopes":["devices:read","trust:read"]}"#;
+    let (_, created) = send(&app, Method::POST, "/developer/clients", request, &dev).await;
+    let client_id = created["client_id"]
+        .as_str()
+        .expect("client_id")
+        .to_string();
+    let secret = created["client_secret"]
+        .as_str()
+        .expect("secret")
+        .to_string();
+
+    // RFC 7636 appendix B verifier and challenge
+    let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    let challenge = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+    let authorize = |redirect_uri: &str| {
+        serde_json::json!({
+            "response_type": "code",
+            "client_id": client_id,
+            "redirect_uri": redirect_uri,
+            "scope": "devices:read",
+            "state": "xyz",
+            "code_challenge": challenge,
+            "code_challenge_method": "S256",
+        })
+        .to_string()
+    };
+
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        "/oauth/authorize",
+        &authorize("https://evil.example.org/cb"),
+        &user,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, approved) = send(
+        &app,
+        Method::POST,
+        "/oauth/authorize",
+        &authorize("https://civic.example.org/cb"),
+        &user,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let code = approved["code"].as_str().expect("code").to_string();
+    let redirect_to = approved["redirect_to"].as_str().expect("redirect_to");
+    assert!(redirect_to.starts_with("https://civic.example.org/cb?code="));
+    assert!(redirect_to.ends_with("&state=xyz"));
+
+    let form = |verifier: &str| {
+        format!(
+            "grant_type=authorization_code&code={code}&redirect_uri=https%3A%2F%2Fcivic.example.org%2Fcb\
+             &client_id={client_id}&client_secret={secret}&code_verifier={verifier}"
+        )
+    };
+
+    // A wrong verifier burns the code
+    let (status, error) = exchange(&app, &form("x".repeat(43).as_str())).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_grant");
+    let (status, _) = exchange(&app, &form(verifier)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, approved) = send(
+        &app,
+        Method::POST,
+        "/oauth/authorize",
+        &authorize("https://civic.example.org/cb"),
+        &user,
+    )
+    .await;
+    let code = approved["code"].as_str().expect("code").to_string();
+    let form = format!(
+        "grant_type=authorization_code&code={code}&redirect_uri=https%3A%2F%2Fcivic.example.org%2Fcb\
+         &client_id={client_id}&client_secret={secret}&code_verifier={verifier}"
+    );
+    let (status, granted) = exchange(&app, &form).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(granted["token_type"], "Bearer");
+    assert_eq!(granted["scope"], "devices:read");
+    assert_eq!(granted["reputation_tier"], "new");
+    assert!(granted["account_id"].is_string());
+    let access_token = granted["access_token"].as_str().expect("token").to_string();
+
+    // The token reads the approving user's data within its scope
+    let devices = Request::builder()
+        .uri("/auth/devices")
+        .header(AUTHORIZATION, format!("Bearer {access_token}"))
+        .body(Body::empty())
+        .expect("request");
+    let response = app.clone().oneshot(devices).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Revoking the client revokes its tokens
+    let path = format!("/developer/clients/{client_id}");
+    let (status, _) = send(&app, Method::DELETE, &path, "", &dev).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let devices = Request::builder()
+        .uri("/auth/devices")
+        .header(AUTHORIZATION, format!("Bearer {access_token}"))
+        .body(Body::empty())
+        .expect("request");
+    let response = app.clone().oneshot(devices).await.expect("response");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -1,17 +1,24 @@
 //! Device management handler integration tests.
 //!
 //! Tests the authenticated device endpoints (GET/POST/DELETE/PATCH /auth/devices)
-//! with real database connections.
+//! session tokens, the security checkup, and third-party verification with real database
+//! connections. Most tests run on a transactional pool over the shared
+//! database; those that build a second app on the same database use
+//! `isolated_db()`.
 
 mod common;
 
+use std::sync::Arc;
+
 use axum::{
     body::{to_bytes, Body},
     http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Extension,
 };
 use common::app_builder::TestAppBuilder;
 use common::factories::{
-    build_authed_request, signup_user, signup_user_in_pool, valid_signup_with_keys,
+    build_authed_request, build_authed_request_v2, signup_user, signup_user_in_pool,
+    signup_user_transactional, valid_signup_with_keys,
 };
 use common::test_db::isolated_db;
 use ed25519_dalek::{Signer, SigningKey};
@@ -19,6 +26,11 @@ use rand::rngs::OsRng;
 use sha2::{Digest, Sha256};
 use tc_crypto::{encode_base64url, Kid};
 use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::AuthConfig;
+use tinycongress_api::identity::http::device_usage::DeviceUsageRecorder;
+use tinycongress_api::identity::http::signature_guard::SignatureFailureGuard;
+use tinycongress_api::identity::repo::PgIdentityRepo;
+use tinycongress_api::identity::service::{device_certificate_message, device_rotation_message};
 use tower::ServiceExt;
 
 // =========================================================================
@@ -27,7 +39,7 @@ use tower::ServiceExt;
 
 #[shared_runtime_test]
 async fn test_list_devices_success() {
-    let (app, keys, _db) = signup_user("listdev").await;
+    let (app, keys, _pool) = signup_user_transactional("listdev").await;
 
     let req = build_authed_request(
         Method::GET,
@@ -49,6 +61,92 @@ async fn test_list_devices_success() {
     assert_eq!(devices[0]["device_name"], "Test Device");
 }
 
+#[shared_runtime_test]
+async fn test_list_devices_includes_usage_counts() {
+    let (app, keys, db) = signup_user("devusage").await;
+    let recorder = Arc::new(DeviceUsageRecorder::new());
+    let app = app.layer(Extension(recorder.clone()));
+
+    for _ in 0..3 {
+        let req = build_authed_request(
+            Method::GET,
+            "/auth/devices",
+            "",
+            &keys.device_signing_key,
+            &keys.device_kid,
+        );
+        let response = app.clone().oneshot(req).await.expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let repo = PgIdentityRepo::new(db.pool().clone());
+    recorder.flush(&repo).await.expect("flush");
+
+    let req = build_authed_request(
+        Method::GET,
+        "/auth/devices",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(req).await.expect("response");
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    let device = &json["devices"][0];
+    assert_eq!(device["requests_24h"], 3);
+    assert_eq!(device["requests_7d"], 3);
+    assert_eq!(device["last_endpoint"], "GET /auth/devices");
+}
+
+#[shared_runtime_test]
+async fn test_forged_signatures_do_not_block_owner_device() {
+    let (app, keys, _pool) = signup_user_transactional("forgedkid").await;
+    let guard = SignatureFailureGuard::from_config(&AuthConfig {
+        failure_threshold: 3,
+        ..AuthConfig::default()
+    })
+    .expect("enabled")
+    .with_trust_forwarded_for(true);
+    let app = app.layer(Extension(Arc::new(guard)));
+    let from = |ip: &str, signing_key: &SigningKey| {
+        let mut req = build_authed_request(
+            Method::GET,
+            "/auth/devices",
+            "",
+            signing_key,
+            &keys.device_kid,
+        );
+        req.headers_mut()
+            .insert("X-Forwarded-For", ip.parse().expect("header"));
+        req
+    };
+
+    // An attacker who only knows the public KID forges signatures for it
+    let forger = SigningKey::generate(&mut OsRng);
+    for _ in 0..3 {
+        let response = app
+            .clone()
+            .oneshot(from("203.0.113.7", &forger))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+    let response = app
+        .clone()
+        .oneshot(from("203.0.113.7", &forger))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The owner, from another IP, is not held to the attacker's count
+    let response = app
+        .oneshot(from("198.51.100.1", &keys.device_signing_key))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 #[shared_runtime_test]
 async fn test_list_devices_no_auth() {
     let (_app, _keys, db) = signup_user("noauthlist").await;
@@ -68,7 +166,7 @@ async fn test_list_devices_no_auth() {
 
 #[shared_runtime_test]
 async fn test_list_devices_invalid_signature() {
-    let (app, keys, _db) = signup_user("badsiglist").await;
+    let (app, keys, _pool) = signup_user_transactional("badsiglist").await;
 
     // Sign with a different key
     let wrong_key = SigningKey::generate(&mut OsRng);
@@ -118,7 +216,7 @@ async fn test_list_devices_expired_timestamp() {
 
 #[shared_runtime_test]
 async fn test_add_device_success() {
-    let (app, keys, _db) = signup_user("adddev").await;
+    let (app, keys, _pool) = signup_user_transactional("adddev").await;
 
     // Generate a new device key and certificate
     let new_device_key = SigningKey::generate(&mut OsRng);
@@ -153,7 +251,7 @@ async fn test_add_device_success() {
 
 #[shared_runtime_test]
 async fn test_add_device_invalid_certificate() {
-    let (app, keys, _db) = signup_user("badcert").await;
+    let (app, keys, _pool) = signup_user_transactional("badcert").await;
 
     // Generate a new device key but sign its pubkey with a random key (not the root)
     let new_device_key = SigningKey::generate(&mut OsRng);
@@ -182,7 +280,7 @@ async fn test_add_device_invalid_certificate() {
 
 #[shared_runtime_test]
 async fn test_add_device_duplicate_returns_conflict() {
-    let (app, keys, _db) = signup_user("dupdev").await;
+    let (app, keys, _pool) = signup_user_transactional("dupdev").await;
 
     // Generate a new device and add it
     let new_device_key = SigningKey::generate(&mut OsRng);
@@ -227,13 +325,215 @@ async fn test_add_device_duplicate_returns_conflict() {
     assert_eq!(response2.status(), StatusCode::CONFLICT);
 }
 
+// =========================================================================
+// POST /auth/devices/:kid/rotate
+// =========================================================================
+
+#[shared_runtime_test]
+async fn test_rotated_device_keeps_declared_capabilities() {
+    let (app, keys, pool) = signup_user_transactional("rotatecaps").await;
+    sqlx::query("UPDATE device_keys SET client_caps = '{cert-v2,sig-v2}' WHERE device_kid = $1")
+        .bind(keys.device_kid.as_str())
+        .execute(&pool)
+        .await
+        .expect("declare caps");
+
+    let new_device_key = SigningKey::generate(&mut OsRng);
+    let new_pubkey = new_device_key.verifying_key().to_bytes();
+    let new_kid = Kid::derive(&new_pubkey);
+    let certificate_timestamp = chrono::Utc::now().timestamp();
+    let cert = keys.root_signing_key.sign(&device_certificate_message(
+        &new_pubkey,
+        Some(certificate_timestamp),
+    ));
+    let rotation_sig = keys
+        .device_signing_key
+        .sign(&device_rotation_message(&keys.device_kid, &new_pubkey));
+    let body = serde_json::json!({
+        "new_pubkey": encode_base64url(&new_pubkey),
+        "certificate": encode_base64url(&cert.to_bytes()),
+        "certificate_timestamp": certificate_timestamp,
+        "rotation_signature": encode_base64url(&rotation_sig.to_bytes()),
+    })
+    .to_string();
+    let path = format!("/auth/devices/{}/rotate", keys.device_kid);
+
+    let response = app
+        .clone()
+        .oneshot(build_authed_request_v2(
+            Method::POST,
+            &path,
+            &body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // The new key cannot fall back to v1 signatures
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            Method::GET,
+            "/auth/devices",
+            "",
+            &new_device_key,
+            &new_kid,
+        ))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["code"], "SIGNATURE_UPGRADE_REQUIRED");
+
+    let response = app
+        .oneshot(build_authed_request_v2(
+            Method::GET,
+            "/auth/devices",
+            "",
+            &new_device_key,
+            &new_kid,
+        ))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// =========================================================================
+// GET /auth/devices/:kid/delegation
+// =========================================================================
+
+#[shared_runtime_test]
+async fn test_get_device_delegation_for_signup_device() {
+    let (app, keys, _pool) = signup_user_transactional("delegation").await;
+
+    let path = format!("/auth/devices/{}/delegation", keys.device_kid);
+    let req = build_authed_request(
+        Method::GET,
+        &path,
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["device_kid"], keys.device_kid.as_str());
+    assert_eq!(json["status"], "valid");
+    assert_eq!(json["certificate_version"], 1);
+    assert_eq!(json["scopes"], serde_json::json!(["*"]));
+
+    // Unknown devices are 404
+    let unknown = Kid::derive(&[0x42u8; 32]);
+    let path = format!("/auth/devices/{unknown}/delegation");
+    let req = build_authed_request(
+        Method::GET,
+        &path,
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+// =========================================================================
+// GET /api/v1/verify
+// =========================================================================
+
+#[shared_runtime_test]
+async fn test_verify_challenge_signed_by_device() {
+    use tinycongress_api::identity::http::verify::verification_message;
+
+    let (app, keys, _pool) = signup_user_transactional("verifyme").await;
+    let challenge = "third-party-challenge-0001";
+    let signature = keys
+        .device_signing_key
+        .sign(&verification_message(challenge));
+
+    let get = |uri: String| {
+        Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .header("origin", "https://widget.example")
+            .body(Body::empty())
+            .expect("request")
+    };
+
+    let uri = format!(
+        "/api/v1/verify?kid={}&challenge={challenge}&signature={}",
+        keys.device_kid,
+        encode_base64url(&signature.to_bytes())
+    );
+    let response = app.clone().oneshot(get(uri)).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "*",
+        "verify route must allow any origin"
+    );
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["username"], "verifyme");
+    assert_eq!(json["delegation"], "valid");
+    assert_eq!(json["device_status"], "active");
+    assert_eq!(json["verified"], true);
+
+    // A signature over a different challenge does not verify
+    let uri = format!(
+        "/api/v1/verify?kid={}&challenge=another-challenge-0002&signature={}",
+        keys.device_kid,
+        encode_base64url(&signature.to_bytes())
+    );
+    let response = app.clone().oneshot(get(uri)).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["signature_valid"], false);
+    assert_eq!(json["verified"], false);
+
+    // Malformed KIDs are rejected with a JSON error
+    let uri = format!(
+        "/api/v1/verify?kid=not-a-kid&challenge={challenge}&signature={}",
+        encode_base64url(&signature.to_bytes())
+    );
+    let response = app.clone().oneshot(get(uri)).await.expect("response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert!(json["error"].is_string());
+
+    // Unknown devices are 404
+    let unknown = Kid::derive(&[0x42u8; 32]);
+    let uri = format!(
+        "/api/v1/verify?kid={unknown}&challenge={challenge}&signature={}",
+        encode_base64url(&signature.to_bytes())
+    );
+    let response = app.oneshot(get(uri)).await.expect("response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
 // =========================================================================
 // DELETE /auth/devices/:kid
 // =========================================================================
 
 #[shared_runtime_test]
 async fn test_revoke_device_success() {
-    let (app, keys, _db) = signup_user("revokedev").await;
+    let (app, keys, _pool) = signup_user_transactional("revokedev").await;
 
     // First add a second device
     let new_device_key = SigningKey::generate(&mut OsRng);
@@ -267,8 +567,46 @@ async fn test_revoke_device_success() {
         &keys.device_signing_key,
         &keys.device_kid,
     );
-    let response = app.oneshot(req).await.expect("response");
+    let response = app.clone().oneshot(req).await.expect("response");
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // The history records who revoked it; active-only listings omit it
+    let req = build_authed_request(
+        Method::GET,
+        "/auth/devices?include_revoked=true",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    let revoked = json["devices"]
+        .as_array()
+        .expect("devices array")
+        .iter()
+        .find(|d| d["device_kid"] == new_device_kid.as_str())
+        .expect("revoked device listed");
+    assert!(revoked["revoked_at"].is_string());
+    assert_eq!(revoked["revoked_by_kid"], keys.device_kid.as_str());
+    assert_eq!(revoked["certificate_version"], 1);
+
+    let req = build_authed_request(
+        Method::GET,
+        "/auth/devices?include_revoked=false",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(req).await.expect("response");
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["devices"].as_array().expect("devices array").len(), 1);
 }
 
 #[shared_runtime_test]
@@ -327,7 +665,7 @@ async fn test_revoke_device_wrong_account() {
 
 #[shared_runtime_test]
 async fn test_revoke_device_self_revocation_rejected() {
-    let (app, keys, _db) = signup_user("selfrevoke").await;
+    let (app, keys, _pool) = signup_user_transactional("selfrevoke").await;
 
     // Try to revoke own device — should get 422
     let path = format!("/auth/devices/{}", keys.device_kid);
@@ -348,7 +686,7 @@ async fn test_revoke_device_self_revocation_rejected() {
 
 #[shared_runtime_test]
 async fn test_rename_device_success() {
-    let (app, keys, _db) = signup_user("renamedev").await;
+    let (app, keys, _pool) = signup_user_transactional("renamedev").await;
 
     let path = format!("/auth/devices/{}", keys.device_kid);
     let body = serde_json::json!({ "name": "Renamed Device" }).to_string();
@@ -382,7 +720,7 @@ async fn test_rename_device_success() {
 
 #[shared_runtime_test]
 async fn test_rename_device_empty_name_fails() {
-    let (app, keys, _db) = signup_user("renamebad").await;
+    let (app, keys, _pool) = signup_user_transactional("renamebad").await;
 
     let path = format!("/auth/devices/{}", keys.device_kid);
     let body = serde_json::json!({ "name": "   " }).to_string();
@@ -405,7 +743,7 @@ async fn test_rename_device_empty_name_fails() {
 
 #[shared_runtime_test]
 async fn test_nonce_replay_rejected() {
-    let (app, keys, _db) = signup_user("noncereplay").await;
+    let (app, keys, _pool) = signup_user_transactional("noncereplay").await;
 
     // Build a request with a specific nonce
     let nonce = "fixed-nonce-for-replay-test";
@@ -442,7 +780,7 @@ async fn test_nonce_replay_rejected() {
 
 #[shared_runtime_test]
 async fn test_auth_with_revoked_device() {
-    let (app, keys, _db) = signup_user("revokedauth").await;
+    let (app, keys, _pool) = signup_user_transactional("revokedauth").await;
 
     // Add a second device
     let new_device_key = SigningKey::generate(&mut OsRng);
@@ -514,3 +852,179 @@ async fn test_cannot_rename_other_accounts_device() {
     let response = app.oneshot(req).await.expect("response");
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+// =========================================================================
+// GET /auth/security/checkup
+// =========================================================================
+
+#[shared_runtime_test]
+async fn test_security_checkup_after_signup() {
+    let (app, keys, _pool) = signup_user_transactional("checkup").await;
+
+    let req = build_authed_request(
+        Method::GET,
+        "/auth/security/checkup",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(req).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["devices"]["active"], 1);
+    assert_eq!(json["backup"]["present"], true);
+    assert_eq!(json["api_tokens"]["active"], 0);
+    assert_eq!(json["recent_events"][0]["kind"], "device_added");
+    assert_eq!(
+        json["recent_events"][0]["device_kid"],
+        keys.device_kid.as_str()
+    );
+    let codes: Vec<&str> = json["recommendations"]
+        .as_array()
+        .expect("recommendations")
+        .iter()
+        .map(|r| r["code"].as_str().expect("code"))
+        .collect();
+    assert_eq!(codes, ["review_recent_changes"]);
+}
+
+// =========================================================================
+// GET /accounts/:id
+// =========================================================================
+
+#[shared_runtime_test]
+async fn test_account_profile_redacts_devices_by_viewer() {
+    let (app, owner_keys, pool) = signup_user_transactional("profowner").await;
+    let owner_id: uuid::Uuid =
+        sqlx::query_scalar("SELECT id FROM accounts WHERE username = 'profowner'")
+            .fetch_one(&pool)
+            .await
+            .expect("owner id");
+    let (json, other_keys) = valid_signup_with_keys("profother");
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let path = format!("/accounts/{owner_id}");
+    let profile = |req: Request<Body>| {
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(req).await.expect("response");
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), 1024 * 1024)
+                .await
+                .expect("body");
+            serde_json::from_slice::<serde_json::Value>(&body).expect("json")
+        }
+    };
+
+    let anonymous = profile(
+        Request::builder()
+            .uri(&path)
+            .body(Body::empty())
+            .expect("request"),
+    )
+    .await;
+    assert_eq!(anonymous["username"], "profowner");
+    assert!(anonymous.get("devices").is_none());
+
+    let member = profile(build_authed_request(
+        Method::GET,
+        &path,
+        "",
+        &other_keys.device_signing_key,
+        &other_keys.device_kid,
+    ))
+    .await;
+    assert_eq!(
+        member["devices"][0]["device_kid"],
+        owner_keys.device_kid.as_str()
+    );
+    assert!(member["devices"][0].get("device_name").is_none());
+
+    let owner = profile(build_authed_request(
+        Method::GET,
+        &path,
+        "",
+        &owner_keys.device_signing_key,
+        &owner_keys.device_kid,
+    ))
+    .await;
+    assert_eq!(owner["devices"][0]["device_name"], "Test Device");
+}
+
+// =========================================================================
+// POST/DELETE /auth/sessions
+// =========================================================================
+
+#[shared_runtime_test]
+async fn test_session_token_reads_until_revoked() {
+    let (app, keys, _pool) = signup_user_transactional("sessionread").await;
+    let signed = |method: Method, path: &str| {
+        build_authed_request(method, path, "", &keys.device_signing_key, &keys.device_kid)
+    };
+    let with_token = |method: Method, token: &str| {
+        Request::builder()
+            .method(method)
+            .uri("/auth/devices")
+            .header("Authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .expect("request")
+    };
+
+    let response = app
+        .clone()
+        .oneshot(signed(Method::POST, "/auth/sessions"))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    let token = json["token"].as_str().expect("token").to_string();
+    assert!(token.starts_with("tcsess_"));
+    assert_eq!(json["device_kid"], keys.device_kid.as_str());
+
+    let response = app
+        .clone()
+        .oneshot(with_token(Method::GET, &token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Writes still need a signature
+    let response = app
+        .clone()
+        .oneshot(with_token(Method::POST, &token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(signed(Method::DELETE, "/auth/sessions"))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .oneshot(with_token(Method::GET, &token))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
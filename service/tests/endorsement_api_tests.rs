@@ -1,4 +1,6 @@
-//! Integration tests for POST /verifiers/endorsements endpoint.
+//! Integration tests for POST /verifiers/endorsements, GET /me/endorsements,
+//! endorsement disputes, reputation explanations and the public badge
+//! endpoints.
 
 mod common;
 
@@ -10,10 +12,22 @@ use serde_json::{json, Value};
 use tower::ServiceExt;
 
 use common::app_builder::TestAppBuilder;
-use common::factories::{build_authed_request, valid_signup_with_keys};
+use common::factories::{
+    build_authed_request, valid_signup_with_keys, AccountFactory, DeviceFactory, EndorsementFactory,
+};
 use common::test_db::isolated_db;
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+use tc_crypto::encode_base64url;
 use tc_test_macros::shared_runtime_test;
-use tinycongress_api::reputation::repo::{create_endorsement, has_endorsement};
+use tinycongress_api::config::AccountsConfig;
+use tinycongress_api::db::sql_audit;
+use tinycongress_api::identity::service::DevicePubkey;
+use tinycongress_api::identity::signed_request::SignedRequest;
+use tinycongress_api::reputation::repo::{
+    count_active_endorsements_by_topic, create_endorsement, has_endorsement,
+};
+use tinycongress_api::reputation::EndorsementVisibility;
 
 /// Helper: sign up a user and return (keys, account_id).
 async fn signup_user(
@@ -96,6 +110,48 @@ async fn test_verifier_can_create_endorsement() {
     assert!(has);
 }
 
+/// Request values reach the endorsement queries as bind parameters: under the
+/// `sql-audit` feature a spliced topic would be refused and recorded.
+#[shared_runtime_test]
+async fn test_endorsement_topic_is_bound_not_spliced() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let (verifier_keys, verifier_id) = signup_user(&app, "audit-verifier").await;
+    let (_user_keys, user_id) = signup_user(&app, "audit-target").await;
+    create_endorsement(
+        db.pool(),
+        verifier_id,
+        "authorized_verifier",
+        None,
+        None,
+        1.0,
+        None,
+        true,
+    )
+    .await
+    .expect("bootstrap");
+
+    let topic = "x' OR '1'='1 --";
+    let body = json!({ "username": "audit-target", "topic": topic }).to_string();
+    let request = build_authed_request(
+        Method::POST,
+        "/verifiers/endorsements",
+        &body,
+        &verifier_keys.device_signing_key,
+        &verifier_keys.device_kid,
+    );
+    let response = app.clone().oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(has_endorsement(db.pool(), user_id, topic)
+        .await
+        .expect("check"));
+    assert!(sql_audit::take_violations()
+        .iter()
+        .all(|v| v.value != topic));
+}
+
 #[shared_runtime_test]
 async fn test_non_verifier_gets_403() {
     let db = isolated_db().await;
@@ -216,3 +272,521 @@ async fn test_duplicate_endorsement_is_idempotent() {
     let response = app.clone().oneshot(request).await.expect("response");
     assert_eq!(response.status(), StatusCode::CREATED);
 }
+
+// A private endorsement is listed to its subject, hidden from the public
+// check, and carries the verifier's signed request naming its visibility.
+#[shared_runtime_test]
+async fn test_private_endorsement_hidden_and_envelope_verifies() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let (verifier_keys, verifier_id) = signup_user(&app, "private-verifier").await;
+    let (user_keys, user_id) = signup_user(&app, "private-target").await;
+    create_endorsement(
+        db.pool(),
+        verifier_id,
+        "authorized_verifier",
+        None,
+        None,
+        1.0,
+        None,
+        true,
+    )
+    .await
+    .expect("bootstrap");
+
+    let body = json!({
+        "username": "private-target",
+        "topic": "clearance",
+        "visibility": "private"
+    })
+    .to_string();
+    let request = build_authed_request(
+        Method::POST,
+        "/verifiers/endorsements",
+        &body,
+        &verifier_keys.device_signing_key,
+        &verifier_keys.device_kid,
+    );
+    let response = app.clone().oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Eligibility still sees it; the public check does not
+    assert!(has_endorsement(db.pool(), user_id, "clearance")
+        .await
+        .expect("check"));
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/endorsements/check?subject_id={user_id}&topic=clearance"
+                ))
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    let bytes = axum::body::to_bytes(response.into_body(), 1024)
+        .await
+        .expect("body");
+    let check: Value = serde_json::from_slice(&bytes).expect("json");
+    assert_eq!(check["has_endorsement"], false);
+
+    let (status, mine) = get_my_endorsements(&app, &user_keys, "/me/endorsements").await;
+    assert_eq!(status, StatusCode::OK);
+    let endorsement = &mine["endorsements"][0];
+    assert_eq!(endorsement["visibility"], "private");
+
+    let envelope: SignedRequest =
+        serde_json::from_value(endorsement["issuance_envelope"].clone()).expect("envelope");
+    let pubkey = DevicePubkey::from_base64url(&encode_base64url(
+        &verifier_keys.device_signing_key.verifying_key().to_bytes(),
+    ))
+    .expect("pubkey");
+    assert!(envelope.verify(&pubkey));
+    let signed_body: Value =
+        serde_json::from_slice(&envelope.body_bytes().expect("body")).expect("json");
+    assert_eq!(signed_body["visibility"], "private");
+}
+
+async fn check(app: &axum::Router, query: &str) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/endorsements/check?{query}"))
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), 1024)
+        .await
+        .expect("body");
+    (status, serde_json::from_slice(&bytes).expect("json"))
+}
+
+#[shared_runtime_test]
+async fn test_check_accepts_canonical_subjects_only() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let (_keys, user_id) = signup_user(&app, "subject-target").await;
+    create_endorsement(db.pool(), user_id, "clearance", None, None, 1.0, None, true)
+        .await
+        .expect("endorse");
+
+    let (status, body) = check(&app, &format!("subject=account:{user_id}&topic=clearance")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["has_endorsement"], true);
+
+    // Well-formed but not endorsable yet
+    let (status, body) = check(&app, "subject=bill:hr-1234-118&topic=clearance").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["has_endorsement"], false);
+
+    let upper = user_id.to_string().to_uppercase();
+    for query in [
+        format!("subject=account:{upper}&topic=clearance"),
+        "subject=member:a000360&topic=clearance".to_string(),
+        "subject=senator-smith&topic=clearance".to_string(),
+        format!("subject_id={user_id}&subject=account:{user_id}&topic=clearance"),
+    ] {
+        let (status, _) = check(&app, &query).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+    }
+}
+
+#[shared_runtime_test]
+async fn test_factory_issued_endorsement_envelope_verifies() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let (user_keys, user_id) = signup_user(&app, "factory-target").await;
+
+    let root_key = SigningKey::generate(&mut OsRng);
+    let verifier = AccountFactory::new()
+        .with_root_key(&root_key)
+        .create(db.pool())
+        .await
+        .expect("verifier account");
+    let mut conn = db.pool().acquire().await.expect("connection");
+    let device = DeviceFactory::new()
+        .create(&mut conn, verifier.id, &root_key)
+        .await
+        .expect("verifier device");
+    EndorsementFactory::new(user_id, "factory-target")
+        .with_topic("clearance")
+        .issued_by(&device)
+        .with_visibility(EndorsementVisibility::SubjectOnly)
+        .with_evidence(json!({"method": "in_person"}))
+        .create(db.pool())
+        .await
+        .expect("endorse");
+
+    let (status, mine) = get_my_endorsements(&app, &user_keys, "/me/endorsements").await;
+    assert_eq!(status, StatusCode::OK);
+    let endorsement = &mine["endorsements"][0];
+    assert_eq!(endorsement["visibility"], "subject_only");
+    let envelope: SignedRequest =
+        serde_json::from_value(endorsement["issuance_envelope"].clone()).expect("envelope");
+    let pubkey = DevicePubkey::from_base64url(&encode_base64url(
+        &device.signing_key.verifying_key().to_bytes(),
+    ))
+    .expect("pubkey");
+    assert!(envelope.verify(&pubkey));
+    let signed_body: Value =
+        serde_json::from_slice(&envelope.body_bytes().expect("body")).expect("json");
+    assert_eq!(signed_body["username"], "factory-target");
+    assert_eq!(signed_body["evidence"]["method"], "in_person");
+}
+
+async fn get_my_endorsements(
+    app: &axum::Router,
+    keys: &common::factories::SignupKeys,
+    path: &str,
+) -> (StatusCode, Value) {
+    let request = build_authed_request(
+        Method::GET,
+        path,
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[shared_runtime_test]
+async fn test_my_endorsements_paginates_over_a_snapshot() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let (keys, user_id) = signup_user(&app, "paged-user").await;
+
+    for topic in ["topic_a", "topic_b", "topic_c"] {
+        create_endorsement(db.pool(), user_id, topic, None, None, 1.0, None, true)
+            .await
+            .expect("endorse");
+    }
+
+    let (status, first) = get_my_endorsements(&app, &keys, "/me/endorsements?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["endorsements"].as_array().expect("array").len(), 2);
+    assert!(first["as_of"].is_string());
+    let cursor = first["next_cursor"].as_str().expect("next_cursor");
+
+    // Rows created after the snapshot must not leak into later pages.
+    create_endorsement(db.pool(), user_id, "topic_d", None, None, 1.0, None, true)
+        .await
+        .expect("endorse");
+
+    let (status, second) = get_my_endorsements(
+        &app,
+        &keys,
+        &format!("/me/endorsements?limit=2&cursor={cursor}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let rest = second["endorsements"].as_array().expect("array");
+    assert_eq!(rest.len(), 1);
+    assert!(second["next_cursor"].is_null());
+    assert_eq!(second["as_of"], first["as_of"]);
+
+    let mut topics: Vec<&str> = first["endorsements"]
+        .as_array()
+        .expect("array")
+        .iter()
+        .chain(rest)
+        .map(|e| e["topic"].as_str().expect("topic"))
+        .collect();
+    topics.sort_unstable();
+    assert_eq!(topics, ["topic_a", "topic_b", "topic_c"]);
+
+    // The unpaginated form still returns everything.
+    let (_, all) = get_my_endorsements(&app, &keys, "/me/endorsements").await;
+    assert_eq!(all["endorsements"].as_array().expect("array").len(), 4);
+    assert!(all["next_cursor"].is_null());
+}
+
+#[shared_runtime_test]
+async fn test_my_endorsements_rejects_bad_page_params() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let (keys, _) = signup_user(&app, "paged-user").await;
+
+    for path in [
+        "/me/endorsements?limit=0",
+        "/me/endorsements?cursor=garbage",
+        "/me/endorsements?as_of=2999-01-01T00:00:00Z",
+    ] {
+        let (status, _) = get_my_endorsements(&app, &keys, path).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{path}");
+    }
+}
+
+async fn get_badge(app: &axum::Router, path: &str, etag: Option<&str>) -> axum::response::Response {
+    let mut builder = Request::builder().method(Method::GET).uri(path);
+    if let Some(etag) = etag {
+        builder = builder.header(axum::http::header::IF_NONE_MATCH, etag);
+    }
+    app.clone()
+        .oneshot(builder.body(Body::empty()).expect("request"))
+        .await
+        .expect("response")
+}
+
+#[shared_runtime_test]
+async fn test_badge_summarizes_active_endorsements() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+
+    let (_keys, user_id) = signup_user(&app, "badge-user").await;
+    for topic in ["identity_verified", "trust"] {
+        create_endorsement(db.pool(), user_id, topic, None, None, 1.0, None, true)
+            .await
+            .expect("endorse");
+    }
+
+    let response = get_badge(&app, "/badges/badge-user.json", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let cache_control = response.headers()[axum::http::header::CACHE_CONTROL]
+        .to_str()
+        .expect("cache-control");
+    assert!(cache_control.starts_with("public"));
+    let etag = response.headers()[axum::http::header::ETAG]
+        .to_str()
+        .expect("etag")
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["username"], "badge-user");
+    assert_eq!(json["verified"], true);
+    assert_eq!(json["endorsement_count"], 2);
+    assert_eq!(json["topic_count"], 2);
+
+    let cached = get_badge(&app, "/badges/badge-user.json", Some(&etag)).await;
+    assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+
+    let svg = get_badge(&app, "/badges/badge-user.svg", None).await;
+    assert_eq!(svg.status(), StatusCode::OK);
+    assert!(svg.headers()[CONTENT_TYPE]
+        .to_str()
+        .expect("content-type")
+        .starts_with("image/svg+xml"));
+}
+
+#[shared_runtime_test]
+async fn test_badge_unknown_user_or_format_returns_404() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+    let _ = signup_user(&app, "badge-user").await;
+
+    let missing = get_badge(&app, "/badges/nobody.svg", None).await;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    let bad_format = get_badge(&app, "/badges/badge-user.png", None).await;
+    assert_eq!(bad_format.status(), StatusCode::NOT_FOUND);
+}
+
+async fn send(
+    app: &axum::Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &common::factories::SignupKeys,
+) -> (StatusCode, Value) {
+    let request = build_authed_request(
+        method,
+        path,
+        body,
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[shared_runtime_test]
+async fn test_subject_disputes_endorsement_and_moderator_resolves() {
+    let db = isolated_db().await;
+    let app = TestAppBuilder::new()
+        .with_rooms_pool(db.pool().clone())
+        .build();
+
+    let (_issuer_keys, issuer_id) = signup_user(&app, "dispute-issuer").await;
+    let (subject_keys, subject_id) = signup_user(&app, "dispute-subject").await;
+    let (mod_keys, mod_id) = signup_user(&app, "dispute-mod").await;
+    let app = app.layer(axum::Extension(AccountsConfig {
+        admin_account_ids: vec![mod_id],
+    }));
+    let endorsement = create_endorsement(
+        db.pool(),
+        subject_id,
+        "local_resident",
+        Some(issuer_id),
+        None,
+        1.0,
+        None,
+        true,
+    )
+    .await
+    .expect("endorsement");
+    let path = format!("/endorsements/{}/dispute", endorsement.id);
+
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        &path,
+        r#"{"statement":"  "}"#,
+        &subject_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    // Only the subject can dispute
+    let body = r#"{"statement":"I have never lived here","pause_aggregates":true}"#;
+    let (status, _) = send(&app, Method::POST, &path, body, &mod_keys).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, dispute) = send(&app, Method::POST, &path, body, &subject_keys).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(dispute["status"], "open");
+    assert_eq!(dispute["issuer_id"], issuer_id.to_string());
+    let envelope: SignedRequest =
+        serde_json::from_value(dispute["envelope"].clone()).expect("envelope");
+    let pubkey = DevicePubkey::from_base64url(&encode_base64url(
+        &subject_keys.device_signing_key.verifying_key().to_bytes(),
+    ))
+    .expect("pubkey");
+    assert!(envelope.verify(&pubkey));
+    let (status, _) = send(&app, Method::POST, &path, body, &subject_keys).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Paused endorsements leave public aggregates but stay listed
+    let counts = count_active_endorsements_by_topic(db.pool(), subject_id)
+        .await
+        .expect("counts");
+    assert!(counts.is_empty());
+    let (_, listed) = get_my_endorsements(&app, &subject_keys, "/me/endorsements").await;
+    assert_eq!(listed["endorsements"][0]["dispute_status"], "open");
+    assert_eq!(listed["endorsements"][0]["aggregates_paused"], true);
+
+    // Review is limited to moderators
+    let (status, _) = send(
+        &app,
+        Method::GET,
+        "/endorsements/disputes",
+        "",
+        &subject_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, open) = send(&app, Method::GET, "/endorsements/disputes", "", &mod_keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(open["disputes"].as_array().map(Vec::len), Some(1));
+
+    let resolve = format!("{path}/resolve");
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        &resolve,
+        r#"{"outcome":"open"}"#,
+        &mod_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, resolved) = send(
+        &app,
+        Method::POST,
+        &resolve,
+        r#"{"outcome":"dismissed","note":"Residency confirmed"}"#,
+        &mod_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resolved["status"], "dismissed");
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        &resolve,
+        r#"{"outcome":"upheld"}"#,
+        &mod_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Dismissal restores the endorsement to aggregates
+    let counts = count_active_endorsements_by_topic(db.pool(), subject_id)
+        .await
+        .expect("counts");
+    assert_eq!(counts.len(), 1);
+    let (_, listed) = get_my_endorsements(&app, &subject_keys, "/me/endorsements").await;
+    assert_eq!(listed["endorsements"][0]["dispute_status"], "dismissed");
+    assert_eq!(listed["endorsements"][0]["aggregates_paused"], false);
+}
+
+#[shared_runtime_test]
+async fn test_reputation_explanation_itemizes_score() {
+    let builder = TestAppBuilder::new().with_transactional_pool().await;
+    let pool = builder.pool().expect("pool").clone();
+    let app = builder.build();
+    let (keys, account_id) = signup_user(&app, "explained-user").await;
+
+    let path = format!("/reputation/{account_id}/explanation");
+    let (status, explanation) = send(&app, Method::GET, &path, "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(explanation["tier"], "new");
+    assert_eq!(explanation["components"].as_array().map(Vec::len), Some(0));
+    assert_eq!(explanation["next_tier"], "established");
+
+    create_endorsement(
+        &pool,
+        account_id,
+        "identity_verified",
+        None,
+        None,
+        1.0,
+        None,
+        true,
+    )
+    .await
+    .expect("endorsement");
+    let (_, explanation) = send(&app, Method::GET, &path, "", &keys).await;
+    assert_eq!(explanation["tier"], "established");
+    let sources: Vec<&str> = explanation["components"]
+        .as_array()
+        .expect("components")
+        .iter()
+        .filter_map(|c| c["source"].as_str())
+        .collect();
+    assert_eq!(
+        sources,
+        ["endorsements:identity_verified", "identity_verified"]
+    );
+    assert_eq!(explanation["score"], 35.0);
+
+    let unknown = format!("/reputation/{}/explanation", uuid::Uuid::new_v4());
+    let (status, _) = send(&app, Method::GET, &unknown, "", &keys).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -6,7 +6,10 @@
 
 mod common;
 
+use common::factories::AccountFactory;
 use common::graphql::{assert_has_errors, assert_no_errors, execute_query, extract_data};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::graphql::cost::{window_start, GraphQLCostRepo, PgGraphQLCostRepo};
 
 // ============================================================================
 // Basic Query/Mutation Tests
@@ -336,3 +339,35 @@ async fn test_build_info_type_introspection() {
         "BuildInfo should have buildTime field"
     );
 }
+
+// ============================================================================
+// Cost Budget Tests
+// ============================================================================
+
+#[shared_runtime_test]
+async fn test_cost_budget_charges_within_window_and_resets() {
+    let pool = common::test_db::transactional_pool().await;
+    let account = AccountFactory::new()
+        .create(&pool)
+        .await
+        .expect("create account");
+    let repo = PgGraphQLCostRepo::new(pool);
+    let window = window_start(chrono::Utc::now());
+
+    let charge = |at, cost| repo.charge(account.id, at, cost, 10);
+    assert_eq!(charge(window, 6).await.expect("charge"), Some(6));
+    assert_eq!(charge(window, 4).await.expect("charge"), Some(10));
+    assert_eq!(
+        charge(window, 1).await.expect("charge"),
+        None,
+        "budget is spent for this window"
+    );
+    assert_eq!(
+        charge(window, 11).await.expect("charge"),
+        None,
+        "larger than the whole budget"
+    );
+
+    let next = window + chrono::Duration::seconds(60);
+    assert_eq!(charge(next, 3).await.expect("charge"), Some(3));
+}
@@ -10,18 +10,28 @@ use axum::{
     body::{to_bytes, Body},
     http::{
         header::{
-            ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_SECURITY_POLICY,
-            CONTENT_TYPE, ORIGIN, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS, X_XSS_PROTECTION,
+            ACCEPT_ENCODING, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+            CONTENT_ENCODING, CONTENT_SECURITY_POLICY, CONTENT_TYPE, ORIGIN,
+            X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS, X_XSS_PROTECTION,
         },
         HeaderValue, Method, Request, StatusCode,
     },
+    Router,
 };
 use common::app_builder::TestAppBuilder;
-use common::factories::valid_signup_json;
+use common::factories::{
+    account_id_by_username, build_authed_request, signup_user_in_pool, valid_signup_json,
+    SignupKeys,
+};
 use tc_crypto::{encode_base64url, BackupEnvelope};
 use tc_test_macros::shared_runtime_test;
-use tinycongress_api::config::SecurityHeadersConfig;
+use tinycongress_api::config::{
+    AccountsConfig, CompressionConfig, CorsConfig, DevToolsConfig, SecurityHeadersConfig,
+};
+use tinycongress_api::http::response_check::ResponseSchemas;
+use tinycongress_api::rest::ApiDoc;
 use tower::ServiceExt;
+use utoipa::OpenApi;
 
 // =============================================================================
 // Health Check Tests
@@ -307,6 +317,64 @@ async fn test_cors_allows_configured_methods() {
     );
 }
 
+#[tokio::test]
+async fn test_cors_route_groups_use_their_own_origins() {
+    let app = TestAppBuilder::minimal()
+        .with_cors_config(CorsConfig {
+            allowed_origins: vec!["http://localhost:3000".into()],
+            public_origins: vec!["*".into()],
+            auth_origins: vec!["https://app.example.com".into()],
+        })
+        .build();
+    let preflight = |path: &str, origin: &str| {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri(path)
+            .header(ORIGIN, origin)
+            .header("Access-Control-Request-Method", "GET")
+            .body(Body::empty())
+            .expect("request")
+    };
+    let allowed = |response: axum::response::Response| {
+        response
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .map(|v| v.to_str().unwrap_or_default().to_string())
+    };
+
+    // Badges are embeddable anywhere
+    let response = app
+        .clone()
+        .oneshot(preflight("/badges/alice.svg", "https://blog.example"))
+        .await
+        .expect("response");
+    assert_eq!(allowed(response).as_deref(), Some("https://blog.example"));
+
+    // Auth routes only accept their own list, not the default one
+    let response = app
+        .clone()
+        .oneshot(preflight("/auth/login", "https://app.example.com"))
+        .await
+        .expect("response");
+    assert_eq!(
+        allowed(response).as_deref(),
+        Some("https://app.example.com")
+    );
+    let response = app
+        .clone()
+        .oneshot(preflight("/auth/login", "http://localhost:3000"))
+        .await
+        .expect("response");
+    assert_eq!(allowed(response), None);
+
+    // Other routes keep the default list
+    let response = app
+        .oneshot(preflight("/health", "https://blog.example"))
+        .await
+        .expect("response");
+    assert_eq!(allowed(response), None);
+}
+
 // =============================================================================
 // Security Headers Tests
 // =============================================================================
@@ -401,6 +469,158 @@ async fn test_security_headers_disabled() {
     assert!(response.headers().get(X_CONTENT_TYPE_OPTIONS).is_none());
 }
 
+// =============================================================================
+// Compression Tests
+// =============================================================================
+
+async fn get_with_encoding(app: axum::Router, uri: &str, encoding: &str) -> Option<HeaderValue> {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(ACCEPT_ENCODING, encoding)
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    response.headers().get(CONTENT_ENCODING).cloned()
+}
+
+#[tokio::test]
+async fn test_compression_applies_negotiated_encoding() {
+    let app = TestAppBuilder::minimal()
+        .with_swagger()
+        .with_compression(CompressionConfig::default())
+        .build();
+
+    let gzip = get_with_encoding(app.clone(), "/api-docs/openapi.json", "gzip").await;
+    assert_eq!(gzip, Some(HeaderValue::from_static("gzip")));
+
+    let br = get_with_encoding(app, "/api-docs/openapi.json", "br").await;
+    assert_eq!(br, Some(HeaderValue::from_static("br")));
+}
+
+#[tokio::test]
+async fn test_compression_skips_small_bodies() {
+    let app = TestAppBuilder::minimal()
+        .with_compression(CompressionConfig::default())
+        .build();
+
+    assert_eq!(get_with_encoding(app, "/health", "gzip").await, None);
+}
+
+#[tokio::test]
+async fn test_compression_respects_disabled_encodings() {
+    let app = TestAppBuilder::minimal()
+        .with_swagger()
+        .with_compression(CompressionConfig {
+            gzip: false,
+            ..CompressionConfig::default()
+        })
+        .build();
+
+    assert_eq!(
+        get_with_encoding(app, "/api-docs/openapi.json", "gzip").await,
+        None
+    );
+}
+
+// =============================================================================
+// Developer Tooling Access Tests
+// =============================================================================
+
+async fn get_status(app: Router, uri: &str, forwarded_for: &str) -> StatusCode {
+    app.oneshot(
+        Request::builder()
+            .uri(uri)
+            .header("X-Forwarded-For", forwarded_for)
+            .body(Body::empty())
+            .expect("request"),
+    )
+    .await
+    .expect("response")
+    .status()
+}
+
+#[tokio::test]
+async fn test_dev_tools_restricted_to_allowed_cidrs() {
+    let app = TestAppBuilder::graphql_only()
+        .with_swagger()
+        .with_dev_tools(DevToolsConfig {
+            allowed_cidrs: vec!["10.0.0.0/8".into()],
+            trust_forwarded_for: true,
+            ..DevToolsConfig::default()
+        })
+        .build();
+
+    for uri in ["/swagger-ui/", "/api-docs/openapi.json", "/graphql"] {
+        assert_eq!(
+            get_status(app.clone(), uri, "10.2.3.4").await,
+            StatusCode::OK,
+            "{uri} from allowed range"
+        );
+        assert_eq!(
+            get_status(app.clone(), uri, "203.0.113.9").await,
+            StatusCode::NOT_FOUND,
+            "{uri} from outside the range"
+        );
+    }
+
+    // The guard covers the tooling only, not GraphQL queries
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/graphql")
+                .header(CONTENT_TYPE, "application/json")
+                .header("X-Forwarded-For", "203.0.113.9")
+                .body(Body::from(r#"{"query": "{ buildInfo { version } }"}"#))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[shared_runtime_test]
+async fn test_dev_tools_allow_signed_admin() {
+    let pool = common::test_db::transactional_pool().await;
+    let (_, admin) = signup_user_in_pool("toolsadmin", &pool).await;
+    let (_, member) = signup_user_in_pool("toolsmember", &pool).await;
+    let admin_id = account_id_by_username("toolsadmin", &pool).await;
+    let app = TestAppBuilder::new()
+        .with_identity_pool(pool)
+        .with_swagger()
+        .with_dev_tools(DevToolsConfig {
+            allow_admins: true,
+            ..DevToolsConfig::default()
+        })
+        .with_accounts(AccountsConfig {
+            admin_account_ids: vec![admin_id],
+        })
+        .build();
+
+    let status = |keys: &SignupKeys| {
+        app.clone().oneshot(build_authed_request(
+            Method::GET,
+            "/api-docs/openapi.json",
+            "",
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+    };
+    assert_eq!(
+        status(&admin).await.expect("response").status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&member).await.expect("response").status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
 // =============================================================================
 // Identity Routes Tests (with mocks)
 // =============================================================================
@@ -544,7 +764,7 @@ async fn test_graphql_query_success() {
 }
 
 #[tokio::test]
-async fn test_graphql_mutation_success() {
+async fn test_graphql_unsigned_mutation_rejected() {
     let app = TestAppBuilder::graphql_only().build();
 
     let mutation = r#"{"query": "mutation { echo(message: \"hello world\") }"}"#;
@@ -561,13 +781,8 @@ async fn test_graphql_mutation_success() {
         .await
         .expect("response");
 
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let body = to_bytes(response.into_body(), 1024 * 1024)
-        .await
-        .expect("body");
-    let body_str = String::from_utf8(body.to_vec()).expect("utf8");
-    assert!(body_str.contains("hello world"));
+    // Mutations require device-signed headers; see graphql_handler
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
 #[tokio::test]
@@ -655,6 +870,84 @@ async fn test_rest_build_info_endpoint() {
     assert!(body_str.contains("gitSha"));
 }
 
+#[tokio::test]
+async fn test_rest_changelog_filters_by_client_version() {
+    let app = TestAppBuilder::new().with_rest().build();
+    let get = |uri: &'static str| {
+        app.clone().oneshot(
+            Request::builder()
+                .uri(uri)
+                .body(Body::empty())
+                .expect("request"),
+        )
+    };
+
+    let response = get("/api/v1/changelog").await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    let schemas =
+        ResponseSchemas::new(serde_json::to_value(ApiDoc::openapi()).expect("openapi json"));
+    let mismatches = schemas.check(&Method::GET, "/api/v1/changelog", 200, &json);
+    assert!(mismatches.is_empty(), "{mismatches:?}");
+    let current = json["current_version"].as_str().expect("current_version");
+    assert_eq!(json["releases"][0]["version"], current);
+
+    let response = get("/api/v1/changelog?since=1.0.0")
+        .await
+        .expect("response");
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert!(json["releases"]
+        .as_array()
+        .expect("releases")
+        .iter()
+        .all(|r| r["version"] != "1.0.0"));
+    assert_eq!(json["breaking"], true);
+
+    let response = get("/api/v1/changelog?since=latest")
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_rest_build_info_matches_openapi_schema() {
+    let app = TestAppBuilder::new().with_rest().with_health().build();
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/build-info")
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    let status = response.status().as_u16();
+    let body = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+
+    let schemas =
+        ResponseSchemas::new(serde_json::to_value(ApiDoc::openapi()).expect("openapi json"));
+    let mismatches = schemas.check(&Method::GET, "/api/v1/build-info", status, &json);
+    assert!(mismatches.is_empty(), "{mismatches:?}");
+
+    // A body that has drifted is caught
+    let mismatches = schemas.check(
+        &Method::GET,
+        "/api/v1/build-info",
+        status,
+        &serde_json::json!({}),
+    );
+    assert!(!mismatches.is_empty());
+}
+
 // =============================================================================
 // Full Stack Integration Tests
 // =============================================================================
@@ -0,0 +1,93 @@
+//! Integration tests for identity export/import (`tc-ops export-accounts` / `import-accounts`).
+
+mod common;
+
+use common::factories::signup_user_in_pool;
+use common::test_db::isolated_db;
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::identity::portability::{
+    export_accounts, import_accounts, AccountExport, PortabilityError,
+};
+
+async fn export_to_string(pool: &sqlx::PgPool) -> (usize, String) {
+    let mut out = Vec::new();
+    let count = export_accounts(pool, &mut out).await.expect("export");
+    (count, String::from_utf8(out).expect("utf8"))
+}
+
+#[shared_runtime_test]
+async fn test_export_import_round_trip() {
+    let source = isolated_db().await;
+    let (_app, _keys) = signup_user_in_pool("alice", source.pool()).await;
+    let (_app, _keys) = signup_user_in_pool("bob", source.pool()).await;
+
+    let (count, dump) = export_to_string(source.pool()).await;
+    assert_eq!(count, 2);
+    assert_eq!(dump.lines().count(), 2);
+
+    let target = isolated_db().await;
+    let summary = import_accounts(target.pool(), dump.as_bytes(), false)
+        .await
+        .expect("import");
+    assert_eq!(summary.imported, 2);
+    assert_eq!(summary.skipped, 0);
+
+    let (_, restored) = export_to_string(target.pool()).await;
+    assert_eq!(restored, dump);
+
+    // Re-running the import is a no-op.
+    let summary = import_accounts(target.pool(), dump.as_bytes(), false)
+        .await
+        .expect("re-import");
+    assert_eq!(summary.imported, 0);
+    assert_eq!(summary.skipped, 2);
+}
+
+#[shared_runtime_test]
+async fn test_import_rejects_tampered_dump_without_writing() {
+    let source = isolated_db().await;
+    let (_app, _keys) = signup_user_in_pool("alice", source.pool()).await;
+    let (_app, _keys) = signup_user_in_pool("bob", source.pool()).await;
+    let (_, dump) = export_to_string(source.pool()).await;
+
+    // Point bob's device at alice's device key: the certificate no longer verifies.
+    let mut accounts: Vec<AccountExport> = dump
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("parse"))
+        .collect();
+    let alice_device = accounts[0].devices[0].clone();
+    accounts[1].devices[0].device_pubkey = alice_device.device_pubkey;
+    accounts[1].devices[0].device_kid = alice_device.device_kid;
+    let tampered: String = accounts
+        .iter()
+        .map(|a| serde_json::to_string(a).expect("serialize") + "\n")
+        .collect();
+
+    let target = isolated_db().await;
+    let err = import_accounts(target.pool(), tampered.as_bytes(), false)
+        .await
+        .expect_err("tampered dump must be rejected");
+    assert!(
+        matches!(err, PortabilityError::Integrity { line: 2, .. }),
+        "{err}"
+    );
+
+    let (count, _) = export_to_string(target.pool()).await;
+    assert_eq!(count, 0, "nothing should be written");
+}
+
+#[shared_runtime_test]
+async fn test_import_dry_run_rolls_back() {
+    let source = isolated_db().await;
+    let (_app, _keys) = signup_user_in_pool("alice", source.pool()).await;
+    let (_, dump) = export_to_string(source.pool()).await;
+
+    let target = isolated_db().await;
+    let summary = import_accounts(target.pool(), dump.as_bytes(), true)
+        .await
+        .expect("dry run");
+    assert_eq!(summary.imported, 1);
+
+    let (count, _) = export_to_string(target.pool()).await;
+    assert_eq!(count, 0);
+}
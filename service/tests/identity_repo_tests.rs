@@ -1,19 +1,21 @@
-//! Identity repo integration tests -- account, backup, and device key repositories.
+//! Identity repo integration tests -- account, alias, backup, and device key repositories.
 
 mod common;
 
-use common::factories::{generate_test_keys, AccountFactory};
+use common::factories::{generate_test_keys, AccountFactory, DeviceFactory};
 use common::test_db::{isolated_db, test_transaction};
 use ed25519_dalek::{Signer, SigningKey};
 use rand::rngs::OsRng;
 use sqlx::query_scalar;
-use tc_crypto::{encode_base64url, BackupEnvelope, Kid};
+use tc_crypto::{encode_base64url, verify_ed25519, BackupEnvelope, Kid};
 use tc_test_macros::shared_runtime_test;
 use tinycongress_api::identity::repo::{
     create_account_with_executor, create_backup_with_executor, create_device_key_with_executor,
-    AccountRepoError, BackupRepoError, CreateSignupError, DeviceKeyRepoError, IdentityRepo,
-    PgIdentityRepo, ValidatedSignup,
+    get_account_by_id, AccountRepoError, AliasRepoError, BackupRepoError, CreateSignupError,
+    DeviceKeyRepoError, IdentityRepo, PgIdentityRepo, ValidatedSignup, MAX_ALIASES_PER_ACCOUNT,
 };
+use tinycongress_api::identity::service::device_certificate_message;
+use tinycongress_api::identity::sigchain::{NewSigchainLink, SigchainLinkType};
 
 /// Build a [`ValidatedSignup`] with real Ed25519 keys and a valid certificate.
 ///
@@ -92,6 +94,37 @@ async fn test_accounts_repo_inserts_account() {
     assert_eq!(account.root_kid, expected_kid);
 }
 
+/// KIDs decode straight from text columns, and a malformed stored value is a
+/// database error rather than a panic or an unchecked `Kid`.
+#[shared_runtime_test]
+async fn test_accounts_repo_decodes_root_kid() {
+    let mut tx = test_transaction().await;
+
+    let account = AccountFactory::new()
+        .with_username("kiddecode")
+        .with_seed(43)
+        .create(&mut *tx)
+        .await
+        .expect("create account");
+
+    let stored: Kid = query_scalar("SELECT root_kid FROM accounts WHERE id = $1")
+        .bind(account.id)
+        .fetch_one(&mut *tx)
+        .await
+        .expect("decode root_kid");
+    assert_eq!(stored, account.root_kid);
+
+    sqlx::query("UPDATE accounts SET root_kid = 'not-a-kid' WHERE id = $1")
+        .bind(account.id)
+        .execute(&mut *tx)
+        .await
+        .expect("corrupt root_kid");
+    let err = get_account_by_id(&mut *tx, account.id)
+        .await
+        .expect_err("malformed root_kid should not decode");
+    assert!(matches!(err, AccountRepoError::Database(_)));
+}
+
 /// Test unique constraints: duplicate username should be rejected.
 #[shared_runtime_test]
 async fn test_accounts_repo_rejects_duplicate_username() {
@@ -284,6 +317,7 @@ async fn test_device_key_repo_creates_key() {
         "device-pubkey-b64",
         "My Laptop",
         &certificate,
+        None,
     )
     .await
     .expect("create device key");
@@ -319,6 +353,7 @@ async fn test_device_key_repo_rejects_duplicate_kid() {
         "pubkey-1",
         "Device A",
         &certificate,
+        None,
     )
     .await
     .expect("create first device key");
@@ -330,6 +365,7 @@ async fn test_device_key_repo_rejects_duplicate_kid() {
         "pubkey-2",
         "Device B",
         &certificate,
+        None,
     )
     .await
     .expect_err("duplicate kid should fail");
@@ -359,6 +395,7 @@ async fn test_device_key_repo_enforces_max_devices() {
             &format!("pubkey-{i}"),
             &format!("Device {i}"),
             &certificate,
+            None,
         )
         .await
         .unwrap_or_else(|_| panic!("create device key {i}"));
@@ -373,6 +410,7 @@ async fn test_device_key_repo_enforces_max_devices() {
         "pubkey-overflow",
         "Device Overflow",
         &certificate,
+        None,
     )
     .await
     .expect_err("11th device key should fail");
@@ -380,6 +418,100 @@ async fn test_device_key_repo_enforces_max_devices() {
     assert!(matches!(err, DeviceKeyRepoError::MaxDevicesReached));
 }
 
+#[shared_runtime_test]
+async fn test_device_factory_certifies_with_root_key() {
+    let mut tx = test_transaction().await;
+
+    let root_key = SigningKey::generate(&mut OsRng);
+    let account = AccountFactory::new()
+        .with_root_key(&root_key)
+        .create(&mut *tx)
+        .await
+        .expect("create account");
+    let device = DeviceFactory::new()
+        .with_certificate_timestamp(1_700_000_000)
+        .create(&mut tx, account.id, &root_key)
+        .await
+        .expect("create device");
+    let revoked = DeviceFactory::new()
+        .revoked()
+        .create(&mut tx, account.id, &root_key)
+        .await
+        .expect("create revoked device");
+
+    let (certificate, revoked_at): (Vec<u8>, Option<chrono::DateTime<chrono::Utc>>) =
+        sqlx::query_as("SELECT certificate, revoked_at FROM device_keys WHERE id = $1")
+            .bind(device.id)
+            .fetch_one(&mut *tx)
+            .await
+            .expect("fetch device key");
+    let message = device_certificate_message(
+        &device.signing_key.verifying_key().to_bytes(),
+        Some(1_700_000_000),
+    );
+    let certificate: [u8; 64] = certificate.try_into().expect("64-byte certificate");
+    verify_ed25519(&root_key.verifying_key().to_bytes(), &message, &certificate)
+        .expect("certificate verifies under the root key");
+    assert!(revoked_at.is_none());
+
+    let revoked_at: Option<chrono::DateTime<chrono::Utc>> =
+        query_scalar("SELECT revoked_at FROM device_keys WHERE id = $1")
+            .bind(revoked.id)
+            .fetch_one(&mut *tx)
+            .await
+            .expect("fetch revoked device key");
+    assert!(revoked_at.is_some());
+}
+
+/// A device change and its sigchain link commit together; a change that
+/// fails leaves no link behind.
+#[shared_runtime_test]
+async fn test_device_changes_append_sigchain_links_atomically() {
+    let db = isolated_db().await;
+    let repo = PgIdentityRepo::new(db.pool().clone());
+
+    let root_key = SigningKey::generate(&mut OsRng);
+    let account = AccountFactory::new()
+        .with_root_key(&root_key)
+        .create(db.pool())
+        .await
+        .expect("create account");
+    let mut conn = db.pool().acquire().await.expect("connection");
+    let device = DeviceFactory::new()
+        .create(&mut conn, account.id, &root_key)
+        .await
+        .expect("create device");
+    let revoked = DeviceFactory::new()
+        .revoked()
+        .create(&mut conn, account.id, &root_key)
+        .await
+        .expect("create revoked device");
+    drop(conn);
+
+    let link = |envelope: &str| NewSigchainLink {
+        link_type: SigchainLinkType::DeviceRenamed,
+        signer_kid: device.kid.clone(),
+        envelope: envelope.to_string(),
+    };
+    repo.rename_device_key(&device.kid, account.id, "Laptop", &link("{\"n\":1}"))
+        .await
+        .expect("rename");
+    let err = repo
+        .rename_device_key(&revoked.kid, account.id, "Old", &link("{\"n\":2}"))
+        .await
+        .expect_err("revoked device");
+    assert!(matches!(err, DeviceKeyRepoError::AlreadyRevoked));
+
+    let links = repo
+        .list_sigchain_links(account.id, 0, 10)
+        .await
+        .expect("list");
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].seqno, 1);
+    assert_eq!(links[0].link_type, "device_renamed");
+    assert_eq!(links[0].envelope, "{\"n\":1}");
+}
+
 // ============================================================================
 // PgIdentityRepo — compound create_signup tests
 // ============================================================================
@@ -469,3 +601,194 @@ async fn test_create_signup_rolls_back_on_duplicate_username() {
         .expect("count first account");
     assert_eq!(surviving_account, 1);
 }
+
+// ============================================================================
+// Alias Repo Tests
+// ============================================================================
+
+/// Handles are unique across usernames and aliases, in both directions.
+#[shared_runtime_test]
+async fn test_alias_repo_enforces_handle_uniqueness() {
+    let db = isolated_db().await;
+    let repo = PgIdentityRepo::new(db.pool().clone());
+
+    let alice = AccountFactory::new()
+        .with_username("alice")
+        .with_seed(10)
+        .create(db.pool())
+        .await
+        .expect("create alice");
+    let bob = AccountFactory::new()
+        .with_username("bob")
+        .with_seed(11)
+        .create(db.pool())
+        .await
+        .expect("create bob");
+
+    repo.create_account_alias(alice.id, "civic-alice", false)
+        .await
+        .expect("create alias");
+
+    // Alias colliding with another account's username
+    let err = repo
+        .create_account_alias(alice.id, "bob", false)
+        .await
+        .expect_err("alias equal to a username should error");
+    assert!(matches!(err, AliasRepoError::DuplicateHandle));
+
+    // Alias colliding with another alias
+    let err = repo
+        .create_account_alias(bob.id, "civic-alice", false)
+        .await
+        .expect_err("duplicate alias should error");
+    assert!(matches!(err, AliasRepoError::DuplicateHandle));
+
+    // Username colliding with an existing alias
+    let (pubkey, kid) = generate_test_keys(12);
+    let err = create_account_with_executor(db.pool(), "civic-alice", &pubkey, &kid)
+        .await
+        .expect_err("username equal to an alias should error");
+    assert!(matches!(err, AccountRepoError::DuplicateUsername));
+}
+
+#[shared_runtime_test]
+async fn test_alias_repo_resolves_username_and_alias() {
+    let db = isolated_db().await;
+    let repo = PgIdentityRepo::new(db.pool().clone());
+
+    let alice = AccountFactory::new()
+        .with_username("alice")
+        .with_seed(13)
+        .create(db.pool())
+        .await
+        .expect("create alice");
+    repo.create_account_alias(alice.id, "civic-alice", false)
+        .await
+        .expect("create alias");
+
+    let by_username = repo
+        .resolve_account_handle("alice")
+        .await
+        .expect("resolve username");
+    assert_eq!(by_username.account.id, alice.id);
+    assert_eq!(by_username.alias, None);
+
+    let by_alias = repo
+        .resolve_account_handle("civic-alice")
+        .await
+        .expect("resolve alias");
+    assert_eq!(by_alias.account.id, alice.id);
+    assert_eq!(by_alias.account.username, "alice");
+    assert_eq!(by_alias.alias.as_deref(), Some("civic-alice"));
+
+    let err = repo
+        .resolve_account_handle("nobody")
+        .await
+        .expect_err("unknown handle should error");
+    assert!(matches!(err, AccountRepoError::NotFound));
+}
+
+#[shared_runtime_test]
+async fn test_alias_repo_protects_reserved_and_enforces_limit() {
+    let db = isolated_db().await;
+    let repo = PgIdentityRepo::new(db.pool().clone());
+
+    let org = AccountFactory::new()
+        .with_username("civic-org")
+        .with_seed(14)
+        .create(db.pool())
+        .await
+        .expect("create org");
+
+    repo.create_account_alias(org.id, "civic", true)
+        .await
+        .expect("create reserved alias");
+    let err = repo
+        .delete_account_alias("civic", org.id)
+        .await
+        .expect_err("reserved alias should not be deletable");
+    assert!(matches!(err, AliasRepoError::Reserved));
+
+    for i in 1..MAX_ALIASES_PER_ACCOUNT {
+        repo.create_account_alias(org.id, &format!("civic-{i}"), false)
+            .await
+            .expect("create alias under limit");
+    }
+    let err = repo
+        .create_account_alias(org.id, "civic-extra", false)
+        .await
+        .expect_err("alias over limit should error");
+    assert!(matches!(err, AliasRepoError::MaxAliasesReached));
+
+    repo.delete_account_alias("civic-1", org.id)
+        .await
+        .expect("delete alias");
+    let err = repo
+        .delete_account_alias("civic-1", org.id)
+        .await
+        .expect_err("deleted alias should be gone");
+    assert!(matches!(err, AliasRepoError::NotFound));
+
+    let aliases = repo.list_account_aliases(org.id).await.expect("list");
+    assert_eq!(aliases.len(), (MAX_ALIASES_PER_ACCOUNT - 1) as usize);
+}
+
+/// Removing an alias or purging an account records the freed handles.
+#[shared_runtime_test]
+async fn test_released_handles_recorded_on_alias_and_account_removal() {
+    let db = isolated_db().await;
+    let repo = PgIdentityRepo::new(db.pool().clone());
+
+    let dana = AccountFactory::new()
+        .with_username("Dana")
+        .with_seed(15)
+        .create(db.pool())
+        .await
+        .expect("create dana");
+    repo.create_account_alias(dana.id, "civic-dana", false)
+        .await
+        .expect("create alias");
+    repo.create_account_alias(dana.id, "dana-org", false)
+        .await
+        .expect("create alias");
+
+    repo.delete_account_alias("civic-dana", dana.id)
+        .await
+        .expect("delete alias");
+    let released = repo
+        .get_released_handle("CIVIC-DANA")
+        .await
+        .expect("lookup")
+        .expect("alias should be recorded");
+    assert_eq!(released.handle, "civic-dana");
+    assert_eq!(released.account_id, dana.id);
+
+    sqlx::query("DELETE FROM accounts WHERE id = $1")
+        .bind(dana.id)
+        .execute(db.pool())
+        .await
+        .expect("purge account");
+    for handle in ["dana", "dana-org"] {
+        assert!(
+            repo.get_released_handle(handle)
+                .await
+                .expect("lookup")
+                .is_some(),
+            "{handle} should be recorded"
+        );
+    }
+    let recent = repo
+        .list_released_handles(chrono::Utc::now() - chrono::Duration::hours(1))
+        .await
+        .expect("list");
+    assert_eq!(recent.len(), 3);
+
+    repo.delete_released_handle("Dana")
+        .await
+        .expect("lift quarantine");
+    assert!(repo
+        .get_released_handle("dana")
+        .await
+        .expect("lookup")
+        .is_none());
+}
@@ -0,0 +1,214 @@
+//! Integration tests for the notification inbox endpoints and projector.
+
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use common::factories::{
+    build_authed_request, signup_user_transactional, AccountFactory, SignupKeys,
+};
+use tc_crypto::Kid;
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::events::DomainEvent;
+use tinycongress_api::notifications::projector::notification_for;
+use tinycongress_api::notifications::repo::insert_notification;
+
+async fn account_id(pool: &PgPool, username: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM accounts WHERE username = $1")
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .expect("account exists")
+}
+
+/// Project a `DeviceRevoked` event for `account_id`; returns the new row ID.
+async fn notify_device_revoked(pool: &PgPool, account_id: Uuid) -> Option<Uuid> {
+    let event = DomainEvent::DeviceRevoked {
+        account_id,
+        device_kid: Kid::derive(&[0x24u8; 32]),
+    };
+    let notification = notification_for(&event).expect("device revocation notifies");
+    insert_notification(pool, &notification)
+        .await
+        .expect("insert notification")
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &SignupKeys,
+) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            method,
+            path,
+            body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+#[shared_runtime_test]
+async fn test_list_and_mark_read() {
+    let (app, keys, pool) = signup_user_transactional("notifyalice").await;
+    let alice = account_id(&pool, "notifyalice").await;
+    let first = notify_device_revoked(&pool, alice).await.expect("inserted");
+    notify_device_revoked(&pool, alice).await.expect("inserted");
+
+    let (status, body) = send(&app, Method::GET, "/notifications", "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["unread_count"], 2);
+    assert_eq!(body["notifications"].as_array().map(Vec::len), Some(2));
+    assert_eq!(body["notifications"][0]["kind"], "device_revoked");
+    assert_eq!(body["notifications"][0]["category"], "security");
+
+    let path = format!("/notifications/{first}/read");
+    let (status, body) = send(&app, Method::POST, &path, "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    let read_at = body["read_at"].clone();
+
+    // Marking again keeps the original timestamp
+    let (status, body) = send(&app, Method::POST, &path, "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["read_at"], read_at);
+
+    let (status, body) = send(
+        &app,
+        Method::GET,
+        "/notifications?unread_only=true",
+        "",
+        &keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["unread_count"], 1);
+    assert_eq!(body["notifications"].as_array().map(Vec::len), Some(1));
+}
+
+#[shared_runtime_test]
+async fn test_pagination_walks_inbox() {
+    let (app, keys, pool) = signup_user_transactional("notifypager").await;
+    let account = account_id(&pool, "notifypager").await;
+    for _ in 0..3 {
+        notify_device_revoked(&pool, account)
+            .await
+            .expect("inserted");
+    }
+
+    let (status, page1) = send(&app, Method::GET, "/notifications?limit=2", "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page1["notifications"].as_array().map(Vec::len), Some(2));
+    let cursor = page1["next_cursor"].as_str().expect("more pages");
+
+    let path = format!(
+        "/notifications?limit=2&cursor={}",
+        urlencoding::encode(cursor)
+    );
+    let (status, page2) = send(&app, Method::GET, &path, "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page2["notifications"].as_array().map(Vec::len), Some(1));
+    assert!(page2["next_cursor"].is_null());
+}
+
+#[shared_runtime_test]
+async fn test_cannot_mark_another_accounts_notification() {
+    let (app, keys, pool) = signup_user_transactional("notifybob").await;
+    let other = AccountFactory::new()
+        .create(&pool)
+        .await
+        .expect("create account");
+    let id = notify_device_revoked(&pool, other.id)
+        .await
+        .expect("inserted");
+
+    let path = format!("/notifications/{id}/read");
+    let (status, _) = send(&app, Method::POST, &path, "", &keys).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[shared_runtime_test]
+async fn test_disabled_category_is_not_delivered() {
+    let (app, keys, pool) = signup_user_transactional("notifyquiet").await;
+    let account = account_id(&pool, "notifyquiet").await;
+
+    let (status, body) = send(&app, Method::GET, "/notifications/preferences", "", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["preferences"]
+        .as_array()
+        .expect("preferences")
+        .iter()
+        .all(|p| p["enabled"] == true));
+
+    let update = json!({ "preferences": [{ "category": "security", "enabled": false }] });
+    let (status, body) = send(
+        &app,
+        Method::PUT,
+        "/notifications/preferences",
+        &update.to_string(),
+        &keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["preferences"]
+        .as_array()
+        .expect("preferences")
+        .contains(&json!({ "category": "security", "enabled": false })));
+
+    assert_eq!(notify_device_revoked(&pool, account).await, None);
+    let (_, body) = send(&app, Method::GET, "/notifications", "", &keys).await;
+    assert_eq!(body["unread_count"], 0);
+}
+
+#[shared_runtime_test]
+async fn test_graphql_unread_count_requires_signature() {
+    let (_, keys, pool) = signup_user_transactional("notifygql").await;
+    let account = account_id(&pool, "notifygql").await;
+    notify_device_revoked(&pool, account)
+        .await
+        .expect("inserted");
+    let app = common::app_builder::TestAppBuilder::new()
+        .with_notifications_pool(pool)
+        .with_graphql()
+        .build();
+    let query = r#"{"query":"{ notificationUnreadCount }"}"#;
+
+    let (status, body) = send(&app, Method::POST, "/graphql", query, &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["notificationUnreadCount"], 1);
+
+    let response = app
+        .oneshot(
+            axum::http::Request::builder()
+                .method(Method::POST)
+                .uri("/graphql")
+                .header("content-type", "application/json")
+                .body(Body::from(query))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let body: Value = serde_json::from_slice(&bytes).expect("json");
+    assert_eq!(body["errors"][0]["message"], "authentication required");
+}
@@ -0,0 +1,75 @@
+//! Weeks of simulated multi-client activity against one in-process app,
+//! checking protocol invariants every day. See
+//! [`protocol_sim`](common::protocol_sim) for what is simulated and checked.
+//!
+//! Set `TC_SIM_SEED` to replay a failing run, and `TC_SIM_DAYS` for a longer
+//! soak.
+//!
+//! The harness lives with the service's integration tests rather than in a
+//! separate workspace crate: it drives the app through `tests/common`
+//! (`TestAppBuilder`'s transactional pool and the signing factories), which
+//! a separate crate could only reach if the service exported its test
+//! helpers.
+
+mod common;
+
+use common::protocol_sim::{SimConfig, Simulation};
+use tc_test_macros::shared_runtime_test;
+
+#[shared_runtime_test]
+async fn test_protocol_invariants_hold_over_simulated_weeks() {
+    let config = SimConfig::from_env();
+    let report = Simulation::new(config.clone()).await.run().await;
+    assert_eq!(
+        report.days_checked, config.days,
+        "days unchecked in {report}"
+    );
+    assert!(
+        report.clients > config.initial_clients,
+        "no client signed up during the run: {report}"
+    );
+
+    // The run exercised every kind of action and fault at least once
+    for action in [
+        "signup",
+        "add_device",
+        "revoke_device",
+        "rotate_device",
+        "endorse",
+        "revoke_endorsement",
+        "recover",
+    ] {
+        assert!(
+            report.actions.contains_key(action),
+            "no {action} in {report}"
+        );
+    }
+    for fault in [
+        "replay",
+        "tampered_body",
+        "stale_timestamp",
+        "forged_signature",
+        "lost_response",
+    ] {
+        assert!(report.faults.contains_key(fault), "no {fault} in {report}");
+    }
+    assert!(report.revoked_probes > 0, "no revoked device was probed");
+}
+
+#[shared_runtime_test]
+async fn test_protocol_invariants_hold_for_other_seeds() {
+    for seed in [1, 2, 3] {
+        let config = SimConfig {
+            seed,
+            days: 7,
+            ..SimConfig::default()
+        };
+        let report = Simulation::new(config).await.run().await;
+        assert_eq!(report.days_checked, 7, "days unchecked in {report}");
+        assert!(!report.faults.is_empty(), "no faults injected: {report}");
+        assert!(
+            report.actions.contains_key("signup"),
+            "no signups: {report}"
+        );
+    }
+}
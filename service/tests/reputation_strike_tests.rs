@@ -0,0 +1,161 @@
+//! Integration tests for moderator strikes, appeals and their effect on the
+//! reputation score.
+
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Extension, Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use common::app_builder::TestAppBuilder;
+use common::factories::{build_authed_request, valid_signup_with_keys, SignupKeys};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::AccountsConfig;
+use tinycongress_api::reputation::repo::create_endorsement;
+
+/// Helper: sign up a user and return (keys, account_id).
+async fn signup(app: &Router, username: &str) -> (SignupKeys, Uuid) {
+    let (json, keys) = valid_signup_with_keys(username);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let bytes = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let body: Value = serde_json::from_slice(&bytes).expect("json");
+    let account_id = body["account_id"]
+        .as_str()
+        .expect("account_id")
+        .parse()
+        .expect("uuid");
+    (keys, account_id)
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &SignupKeys,
+) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            method,
+            path,
+            body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[shared_runtime_test]
+async fn test_strike_lowers_score_until_appeal_granted() {
+    let builder = TestAppBuilder::new().with_transactional_pool().await;
+    let pool = builder.pool().expect("pool").clone();
+    let app = builder.build();
+    let (mod_keys, mod_id) = signup(&app, "strike-mod").await;
+    let (mod2_keys, mod2_id) = signup(&app, "strike-mod2").await;
+    let (user_keys, user_id) = signup(&app, "strike-user").await;
+    let app = app.layer(Extension(AccountsConfig {
+        admin_account_ids: vec![mod_id, mod2_id],
+    }));
+    create_endorsement(
+        &pool,
+        user_id,
+        "identity_verified",
+        None,
+        None,
+        1.0,
+        None,
+        true,
+    )
+    .await
+    .expect("endorsement");
+
+    let issue = format!("/accounts/{user_id}/strikes");
+    let body = r#"{"reason_code":"spam","note":"Repeated link posts"}"#;
+    let (status, _) = send(&app, Method::POST, &issue, body, &user_keys).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, strike) = send(&app, Method::POST, &issue, body, &mod_keys).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(strike["penalty"], 10.0);
+    let strike_id = strike["id"].as_str().expect("id").to_string();
+
+    // The penalty shows up in the explanation
+    let explain = format!("/reputation/{user_id}/explanation");
+    let (_, explanation) = send(&app, Method::GET, &explain, "", &user_keys).await;
+    let components = explanation["components"].as_array().expect("components");
+    assert!(components
+        .iter()
+        .any(|c| c["source"] == "strike:spam" && c["kind"] == "penalty"));
+    assert!(explanation["score"].as_f64().expect("score") < 35.0);
+
+    // The struck account sees the strike and its history
+    let (status, mine) = send(&app, Method::GET, "/me/strikes", "", &user_keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(mine["strikes"][0]["note"], "Repeated link posts");
+    assert_eq!(mine["strikes"][0]["history"][0]["action"], "issued");
+
+    let appeal = format!("/me/strikes/{strike_id}/appeal");
+    let statement = r#"{"statement":"Those were links to my own city council minutes"}"#;
+    let (status, _) = send(&app, Method::POST, &appeal, statement, &mod_keys).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, appealed) = send(&app, Method::POST, &appeal, statement, &user_keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(appealed["appeal_status"], "pending");
+    let (status, _) = send(&app, Method::POST, &appeal, statement, &user_keys).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, pending) = send(&app, Method::GET, "/strikes/appeals", "", &mod2_keys).await;
+    assert_eq!(pending["strikes"].as_array().map(Vec::len), Some(1));
+
+    let resolve = format!("/strikes/{strike_id}/appeal/resolve");
+    let (status, resolved) = send(
+        &app,
+        Method::POST,
+        &resolve,
+        r#"{"outcome":"granted","note":"Links were legitimate"}"#,
+        &mod2_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resolved["appeal_status"], "granted");
+    assert_eq!(resolved["current_penalty"], 0.0);
+
+    let (_, explanation) = send(&app, Method::GET, &explain, "", &user_keys).await;
+    assert_eq!(explanation["score"], 35.0);
+    let (_, mine) = send(&app, Method::GET, "/me/strikes", "", &user_keys).await;
+    let actions: Vec<&str> = mine["strikes"][0]["history"]
+        .as_array()
+        .expect("history")
+        .iter()
+        .filter_map(|e| e["action"].as_str())
+        .collect();
+    assert_eq!(actions, ["issued", "appealed", "appeal_granted"]);
+}
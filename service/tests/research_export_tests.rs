@@ -0,0 +1,192 @@
+//! Integration tests for research export requests, approval, and CSV download.
+
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Extension, Router,
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use common::factories::{
+    account_id_by_username, build_authed_request, signup_user_transactional,
+    valid_signup_with_keys, AccountFactory, SignupKeys,
+};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::{AccountsConfig, ResearchConfig};
+use tinycongress_api::reputation::repo::create_endorsement;
+
+async fn signup(app: &Router, username: &str) -> SignupKeys {
+    let (json, keys) = valid_signup_with_keys(username);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    keys
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &SignupKeys,
+) -> (StatusCode, String) {
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            method,
+            path,
+            body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    (status, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn json(body: &str) -> Value {
+    serde_json::from_str(body).unwrap_or(Value::Null)
+}
+
+/// Endorse one new account per seed in `topic`.
+async fn endorse_subjects(pool: &PgPool, topic: &str, seeds: std::ops::RangeInclusive<u8>) {
+    for seed in seeds {
+        let subject = AccountFactory::new()
+            .with_seed(seed)
+            .create(pool)
+            .await
+            .expect("create subject");
+        create_endorsement(pool, subject.id, topic, None, None, 1.0, None, false)
+            .await
+            .expect("create endorsement");
+    }
+}
+
+#[shared_runtime_test]
+async fn test_export_approval_workflow() {
+    let (app, carol, pool) = signup_user_transactional("researchcarol").await;
+    let alice = signup(&app, "researchalice").await;
+    let carol_id = account_id_by_username("researchcarol", &pool).await;
+    let alice_id = account_id_by_username("researchalice", &pool).await;
+    let app = app
+        .layer(Extension(ResearchConfig {
+            researcher_account_ids: vec![carol_id],
+            k_anonymity: 3,
+            downloads_per_export: 1,
+            ..ResearchConfig::default()
+        }))
+        .layer(Extension(AccountsConfig {
+            admin_account_ids: vec![alice_id, carol_id],
+        }));
+
+    let request = r#"{"dataset":"endorsement_aggregates","purpose":"Study verification uptake"}"#;
+    let (status, _) = send(&app, Method::POST, "/research/exports", request, &alice).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) = send(&app, Method::POST, "/research/exports", request, &carol).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let created = json(&body);
+    assert_eq!(created["status"], "pending");
+    let id = created["id"].as_str().expect("id").to_string();
+
+    // Not downloadable until approved, and not by the requester's own approval
+    let download = format!("/research/exports/{id}/download");
+    let (status, _) = send(&app, Method::GET, &download, "", &carol).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let approve = format!("/research/exports/{id}/approve");
+    let (status, _) = send(&app, Method::POST, &approve, "", &carol).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(&app, Method::POST, &approve, "", &alice).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json(&body)["status"], "approved");
+    assert!(json(&body)["expires_at"].is_string());
+    let reject = format!("/research/exports/{id}/reject");
+    let (status, _) = send(&app, Method::POST, &reject, "", &alice).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Only the topic with at least k subjects is exported
+    endorse_subjects(&pool, "research_common", 1..=3).await;
+    endorse_subjects(&pool, "research_rare", 4..=5).await;
+
+    let (status, _) = send(&app, Method::GET, &download, "", &alice).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, csv) = send(&app, Method::GET, &download, "", &carol).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("week,topic,endorsements,subjects,issuers")
+    );
+    let common_row = csv
+        .lines()
+        .find(|l| l.contains(",research_common,"))
+        .expect("common topic exported");
+    assert!(common_row.ends_with(",3,3,0"), "row: {common_row}");
+    assert!(!csv.contains("research_rare"));
+
+    let (status, _) = send(&app, Method::GET, &download, "", &carol).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    // Admins see every request; the requester sees their own
+    let (_, body) = send(&app, Method::GET, "/research/exports", "", &alice).await;
+    let listed = json(&body);
+    assert!(listed["requests"]
+        .as_array()
+        .expect("requests")
+        .iter()
+        .any(|r| r["id"] == id.as_str() && r["downloads_remaining"] == 0));
+}
+
+#[shared_runtime_test]
+async fn test_export_request_limit_and_unknown_ids() {
+    let (app, keys, pool) = signup_user_transactional("researchdave").await;
+    let dave_id = account_id_by_username("researchdave", &pool).await;
+    let app = app
+        .layer(Extension(ResearchConfig {
+            researcher_account_ids: vec![dave_id],
+            requests_per_day: 1,
+            ..ResearchConfig::default()
+        }))
+        .layer(Extension(AccountsConfig {
+            admin_account_ids: vec![dave_id],
+        }));
+
+    let request = r#"{"dataset":"trust_degrees","purpose":"Graph shape"}"#;
+    let (status, _) = send(&app, Method::POST, "/research/exports", request, &keys).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, Method::POST, "/research/exports", request, &keys).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        "/research/exports",
+        r#"{"dataset":"trust_degrees","purpose":"   "}"#,
+        &keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let path = format!("/research/exports/{}/approve", Uuid::new_v4());
+    let (status, _) = send(&app, Method::POST, &path, "", &keys).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,260 @@
+//! Integration tests for the data retention purge.
+
+mod common;
+
+use common::factories::AccountFactory;
+use common::test_db::isolated_db;
+use sqlx::PgPool;
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::{AuthConfig, RetentionConfig};
+use tinycongress_api::identity::repo::{
+    check_and_record_nonce, consume_auth_challenge, create_auth_challenge, NonceRepoError,
+};
+use tinycongress_api::retention::{policies, run_retention};
+
+async fn count(pool: &PgPool, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+        .fetch_one(pool)
+        .await
+        .expect("count")
+}
+
+async fn insert_action(pool: &PgPool, actor_id: uuid::Uuid, status: &str, age_days: i32) {
+    sqlx::query(
+        "INSERT INTO trust__action_log (actor_id, action_type, payload, status, created_at, processed_at) \
+         VALUES ($1, 'endorse', '{}'::jsonb, $2, now() - make_interval(days => $3), \
+                 CASE WHEN $2 = 'pending' THEN NULL ELSE now() - make_interval(days => $3) END)",
+    )
+    .bind(actor_id)
+    .bind(status)
+    .bind(age_days)
+    .execute(pool)
+    .await
+    .expect("insert action");
+}
+
+#[shared_runtime_test]
+async fn test_retention_purges_expired_nonces_by_default() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+
+    sqlx::query(
+        "INSERT INTO request_nonces (nonce_hash, created_at) VALUES \
+         ('\\x01', now() - interval '1 hour'), ('\\x02', now())",
+    )
+    .execute(pool)
+    .await
+    .expect("insert nonces");
+
+    let outcomes = run_retention(
+        pool,
+        &policies(&RetentionConfig::default(), &AuthConfig::default()),
+        false,
+    )
+    .await;
+
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes[0].table, "request_nonces");
+    assert_eq!(outcomes[0].rows, 1);
+    assert_eq!(count(pool, "request_nonces").await, 1);
+}
+
+#[shared_runtime_test]
+async fn test_auth_challenges_are_single_use_and_purged_once_expired() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+    let kid: tc_crypto::Kid = "IKn0gXXhyRumNLQ-rmSR5Q".parse().expect("kid");
+    let other: tc_crypto::Kid = "cs1uhCLEB_ttCYaQ8RMLfQ".parse().expect("kid");
+    let now = chrono::Utc::now();
+
+    create_auth_challenge(pool, &kid, b"live", now + chrono::Duration::seconds(60))
+        .await
+        .expect("live challenge");
+    create_auth_challenge(pool, &kid, b"stale", now - chrono::Duration::seconds(1))
+        .await
+        .expect("stale challenge");
+
+    // Bound to the KID it was issued to
+    assert!(matches!(
+        consume_auth_challenge(pool, &other, b"live").await,
+        Err(NonceRepoError::UnknownChallenge)
+    ));
+    assert!(matches!(
+        consume_auth_challenge(pool, &kid, b"stale").await,
+        Err(NonceRepoError::UnknownChallenge)
+    ));
+    consume_auth_challenge(pool, &kid, b"live")
+        .await
+        .expect("first use");
+    assert!(matches!(
+        consume_auth_challenge(pool, &kid, b"live").await,
+        Err(NonceRepoError::UnknownChallenge)
+    ));
+
+    let outcomes = run_retention(
+        pool,
+        &policies(&RetentionConfig::default(), &AuthConfig::default()),
+        false,
+    )
+    .await;
+    let challenges = outcomes
+        .iter()
+        .find(|o| o.table == "auth_challenges")
+        .expect("challenge outcome");
+    assert_eq!(challenges.rows, 1);
+    assert_eq!(count(pool, "auth_challenges").await, 0);
+}
+
+async fn nonce_partitions(pool: &PgPool) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT c.relname::text FROM pg_inherits i \
+         JOIN pg_class c ON c.oid = i.inhrelid \
+         JOIN pg_class p ON p.oid = i.inhparent \
+         WHERE p.relname = 'request_nonces' ORDER BY 1",
+    )
+    .fetch_all(pool)
+    .await
+    .expect("partitions")
+}
+
+#[shared_runtime_test]
+async fn test_retention_maintains_hourly_nonce_partitions() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+
+    // Left over from an earlier run and long expired
+    sqlx::query(
+        "CREATE TABLE request_nonces_p2000010100 PARTITION OF request_nonces \
+         FOR VALUES FROM ('2000-01-01T00:00:00Z') TO ('2000-01-01T01:00:00Z')",
+    )
+    .execute(pool)
+    .await
+    .expect("old partition");
+
+    let policies = policies(&RetentionConfig::default(), &AuthConfig::default());
+    run_retention(pool, &policies, false).await;
+    let partitions = nonce_partitions(pool).await;
+    assert!(!partitions.contains(&"request_nonces_p2000010100".to_string()));
+    assert!(partitions.contains(&"request_nonces_default".to_string()));
+    assert_eq!(
+        partitions
+            .iter()
+            .filter(|p| p.starts_with("request_nonces_p"))
+            .count(),
+        3
+    );
+
+    // Idempotent
+    run_retention(pool, &policies, false).await;
+    assert_eq!(nonce_partitions(pool).await, partitions);
+}
+
+#[shared_runtime_test]
+async fn test_nonce_replay_detected_without_unique_index() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+
+    check_and_record_nonce(pool, b"nonce-a")
+        .await
+        .expect("first use");
+    assert!(matches!(
+        check_and_record_nonce(pool, b"nonce-a").await,
+        Err(NonceRepoError::Replay)
+    ));
+    check_and_record_nonce(pool, b"nonce-b")
+        .await
+        .expect("other nonce");
+}
+
+#[shared_runtime_test]
+async fn test_retention_purges_only_finished_actions_past_cutoff() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+    let account = AccountFactory::new().create(pool).await.expect("account");
+
+    insert_action(pool, account.id, "completed", 100).await;
+    insert_action(pool, account.id, "failed", 100).await;
+    insert_action(pool, account.id, "pending", 100).await;
+    insert_action(pool, account.id, "completed", 1).await;
+
+    let config = RetentionConfig {
+        action_log_days: Some(90),
+        ..RetentionConfig::default()
+    };
+    let outcomes = run_retention(pool, &policies(&config, &AuthConfig::default()), false).await;
+
+    let action_log = outcomes
+        .iter()
+        .find(|o| o.table == "trust__action_log")
+        .expect("action log outcome");
+    assert_eq!(action_log.rows, 2);
+    assert_eq!(count(pool, "trust__action_log").await, 2);
+}
+
+#[shared_runtime_test]
+async fn test_retention_purges_notifications_past_cutoff() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+    let account = AccountFactory::new().create(pool).await.expect("account");
+
+    // Read long ago, unread but old, read recently though created long ago, fresh.
+    sqlx::query(
+        "INSERT INTO notifications (account_id, category, kind, created_at, read_at) VALUES \
+         ($1, 'security', 'device_revoked', now() - interval '60 days', now() - interval '45 days'), \
+         ($1, 'security', 'device_revoked', now() - interval '60 days', NULL), \
+         ($1, 'security', 'device_revoked', now() - interval '60 days', now() - interval '1 day'), \
+         ($1, 'security', 'device_revoked', now(), NULL)",
+    )
+    .bind(account.id)
+    .execute(pool)
+    .await
+    .expect("insert notifications");
+
+    let config = RetentionConfig {
+        notification_days: Some(30),
+        ..RetentionConfig::default()
+    };
+    let outcomes = run_retention(pool, &policies(&config, &AuthConfig::default()), false).await;
+
+    let notifications = outcomes
+        .iter()
+        .find(|o| o.table == "notifications")
+        .expect("notifications outcome");
+    assert_eq!(notifications.rows, 2);
+    assert_eq!(count(pool, "notifications").await, 2);
+}
+
+#[shared_runtime_test]
+async fn test_retention_dry_run_counts_without_deleting() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+    let account = AccountFactory::new().create(pool).await.expect("account");
+    insert_action(pool, account.id, "completed", 100).await;
+
+    let config = RetentionConfig {
+        dry_run: true,
+        action_log_days: Some(30),
+        ..RetentionConfig::default()
+    };
+    let outcomes = run_retention(
+        pool,
+        &policies(&config, &AuthConfig::default()),
+        config.dry_run,
+    )
+    .await;
+
+    let action_log = outcomes
+        .iter()
+        .find(|o| o.table == "trust__action_log")
+        .expect("action log outcome");
+    assert!(action_log.dry_run);
+    assert_eq!(action_log.rows, 1);
+    assert_eq!(count(pool, "trust__action_log").await, 1);
+
+    // Nonce expiry is mandatory and never runs as a dry run.
+    let nonces = outcomes
+        .iter()
+        .find(|o| o.table == "request_nonces")
+        .expect("nonce outcome");
+    assert!(!nonces.dry_run);
+}
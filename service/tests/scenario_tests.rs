@@ -0,0 +1,73 @@
+//! Golden-path scenarios that cross feature boundaries, written with the
+//! [`Scenario`](common::scenario::Scenario) DSL.
+
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::scenario::Scenario;
+use tc_test_macros::shared_runtime_test;
+
+/// Count `endorser → subject` trust edges, active ones first.
+async fn trust_edges(pool: &sqlx::PgPool, endorser: uuid::Uuid, subject: uuid::Uuid) -> (i64, i64) {
+    sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE revoked_at IS NULL), COUNT(*) \
+         FROM reputation__endorsements \
+         WHERE endorser_id = $1 AND subject_id = $2 AND topic = 'trust'",
+    )
+    .bind(endorser)
+    .bind(subject)
+    .fetch_one(pool)
+    .await
+    .expect("count trust edges")
+}
+
+#[shared_runtime_test]
+async fn test_revoked_device_loses_access_but_its_endorsement_stands() {
+    let run = Scenario::new()
+        .signup("scn-bob")
+        .signup("scn-alice")
+        .add_device()
+        .endorse("scn-bob", 0.8)
+        .revoke_device()
+        .run()
+        .await;
+
+    let (status, _) = run
+        .request_with_device("scn-alice", 1, Method::GET, "/auth/devices", "")
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, devices) = run
+        .request("scn-alice", Method::GET, "/auth/devices", "")
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let devices = devices["devices"].as_array().expect("devices");
+    assert_eq!(devices.len(), 2);
+    assert_eq!(
+        devices.iter().filter(|d| d["revoked_at"].is_null()).count(),
+        1
+    );
+
+    // The endorsement was signed by the signup device, which is still active
+    let alice = run.user("scn-alice").account_id;
+    let bob = run.user("scn-bob").account_id;
+    assert_eq!(trust_edges(&run.pool, alice, bob).await, (1, 1));
+}
+
+#[shared_runtime_test]
+async fn test_endorse_then_revoke_leaves_a_revoked_edge() {
+    let run = Scenario::new()
+        .signup("scn-subject")
+        .signup("scn-endorser")
+        .endorse("scn-subject", 1.0)
+        .revoke_endorsement("scn-subject")
+        .as_user("scn-subject")
+        .add_device()
+        .run()
+        .await;
+
+    let endorser = run.user("scn-endorser").account_id;
+    let subject = run.user("scn-subject").account_id;
+    assert_eq!(trust_edges(&run.pool, endorser, subject).await, (0, 1));
+    assert_eq!(run.user("scn-subject").devices.len(), 2);
+}
@@ -0,0 +1,106 @@
+//! Invite-only signup integration tests.
+//!
+//! Tests issuing invite codes (`/auth/signup-invites`) and redeeming them at
+//! `/auth/signup` with real database connections.
+
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Router,
+};
+use common::app_builder::TestAppBuilder;
+use common::factories::{
+    build_authed_request, signup_user_transactional, valid_signup_with_keys, SignupKeys,
+};
+use tc_test_macros::shared_runtime_test;
+use tower::ServiceExt;
+
+async fn json_body(response: axum::response::Response) -> serde_json::Value {
+    let body = to_bytes(response.into_body(), 64 * 1024)
+        .await
+        .expect("body");
+    serde_json::from_slice(&body).expect("json")
+}
+
+async fn signed(app: &Router, method: Method, keys: &SignupKeys) -> axum::response::Response {
+    let req = build_authed_request(
+        method,
+        "/auth/signup-invites",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    app.clone().oneshot(req).await.expect("response")
+}
+
+async fn signup_with_code(app: &Router, username: &str, code: Option<&str>) -> StatusCode {
+    let (json, _keys) = valid_signup_with_keys(username);
+    let mut body: serde_json::Value = serde_json::from_str(&json).expect("signup json");
+    if let Some(code) = code {
+        body["invite_code"] = code.into();
+    }
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .expect("request"),
+        )
+        .await
+        .expect("response")
+        .status()
+}
+
+#[shared_runtime_test]
+async fn test_invite_code_is_single_use() {
+    let (app, keys, pool) = signup_user_transactional("inviter").await;
+    let response = signed(&app, Method::POST, &keys).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let issued = json_body(response).await;
+    let code = issued["code"].as_str().expect("code").to_string();
+
+    let invite_only = TestAppBuilder::new()
+        .with_identity_pool(pool)
+        .with_invite_only_signup()
+        .build();
+
+    assert_eq!(
+        signup_with_code(&invite_only, "uninvited", None).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        signup_with_code(&invite_only, "badcode", Some("tci_unknown")).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        signup_with_code(&invite_only, "invitee", Some(&code)).await,
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        signup_with_code(&invite_only, "invitee2", Some(&code)).await,
+        StatusCode::FORBIDDEN
+    );
+
+    let response = signed(&app, Method::GET, &keys).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed = json_body(response).await;
+    let invites = listed["invites"].as_array().expect("invites");
+    assert_eq!(invites.len(), 1);
+    assert_eq!(invites[0]["id"], issued["id"]);
+    assert!(invites[0]["redeemed_by"].is_string());
+}
+
+#[shared_runtime_test]
+async fn test_outstanding_invites_are_capped() {
+    let (app, keys, _pool) = signup_user_transactional("invitehoarder").await;
+    for _ in 0..5 {
+        let response = signed(&app, Method::POST, &keys).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    let response = signed(&app, Method::POST, &keys).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
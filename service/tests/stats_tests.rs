@@ -0,0 +1,251 @@
+//! Tests for instance stats rollups, `GET /api/v1/stats`, and GraphQL `instanceStats`.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Extension,
+};
+use chrono::{NaiveDate, Utc};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use common::app_builder::TestAppBuilder;
+use common::factories::{
+    build_authed_request, insert_endorsement, valid_signup_with_keys, AccountFactory,
+};
+use common::test_db::isolated_db;
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::{AccountsConfig, PrivacyConfig};
+use tinycongress_api::privacy::AggregateNoise;
+use tinycongress_api::stats::repo::{
+    compute_daily_rollup, list_daily_stats, DailyStatsRecord, StatsRepo, StatsRepoError,
+};
+use tinycongress_api::stats::worker::run_pending_rollups;
+
+/// Stub repo returning a fixed set of rollups regardless of window.
+struct StubStatsRepo(Vec<DailyStatsRecord>);
+
+#[async_trait::async_trait]
+impl StatsRepo for StubStatsRepo {
+    async fn list_daily_stats(
+        &self,
+        _since: NaiveDate,
+    ) -> Result<Vec<DailyStatsRecord>, StatsRepoError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn stub_repo() -> Arc<dyn StatsRepo> {
+    Arc::new(StubStatsRepo(vec![DailyStatsRecord {
+        day: NaiveDate::from_ymd_opt(2025, 1, 2).expect("date"),
+        signups: 3,
+        active_devices: 5,
+        ballots_cast: 4,
+        voters: 2,
+        endorsements_by_topic: vec![("trust".to_string(), 6)],
+    }]))
+}
+
+async fn json_body(response: axum::response::Response) -> Value {
+    let bytes = to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    serde_json::from_slice(&bytes).expect("json")
+}
+
+#[tokio::test]
+async fn test_stats_endpoint_returns_rollups() {
+    let app = TestAppBuilder::new()
+        .with_rest()
+        .with_stats_repo(stub_repo())
+        .build();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/stats?days=7")
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = json_body(response).await;
+    assert_eq!(json["days"][0]["date"], "2025-01-02");
+    assert_eq!(json["days"][0]["signups"], 3);
+    assert_eq!(json["days"][0]["ballots_cast"], 4);
+    assert_eq!(
+        json["days"][0]["endorsements_by_topic"][0]["topic"],
+        "trust"
+    );
+}
+
+#[tokio::test]
+async fn test_stats_endpoint_rejects_out_of_range_window() {
+    let app = TestAppBuilder::new()
+        .with_rest()
+        .with_stats_repo(stub_repo())
+        .build();
+
+    for days in ["0", "10000"] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/v1/stats?days={days}"))
+                    .body(Body::empty())
+                    .expect("request"),
+            )
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "days={days}");
+    }
+}
+
+#[shared_runtime_test]
+async fn test_stats_noise_suppresses_small_counts_except_for_admins() {
+    let app = TestAppBuilder::new()
+        .with_transactional_pool()
+        .await
+        .with_rest()
+        .with_stats_repo(stub_repo())
+        .build();
+    let (signup, keys) = valid_signup_with_keys("statsadmin");
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(signup))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let admin_id = json_body(response).await["account_id"]
+        .as_str()
+        .expect("account_id")
+        .parse()
+        .expect("uuid");
+
+    let noise = AggregateNoise::from_config(
+        &PrivacyConfig {
+            enabled: true,
+            ..PrivacyConfig::default()
+        },
+        &AccountsConfig {
+            admin_account_ids: vec![admin_id],
+        },
+        b"stats-noise-test-key-at-least-32-bytes",
+    );
+    let app = app.layer(Extension(Arc::new(noise)));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/stats?days=7")
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = json_body(response).await;
+    assert_eq!(json["days"][0]["signups"], 0);
+    assert_eq!(json["days"][0]["voters"], 0);
+
+    // Signed by an admin: exact counts
+    let response = app
+        .oneshot(build_authed_request(
+            Method::GET,
+            "/api/v1/stats?days=7",
+            "",
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = json_body(response).await;
+    assert_eq!(json["days"][0]["signups"], 3);
+    assert_eq!(json["days"][0]["voters"], 2);
+}
+
+#[tokio::test]
+async fn test_graphql_instance_stats_query() {
+    let app = TestAppBuilder::graphql_only()
+        .with_stats_repo(stub_repo())
+        .build();
+    let body = serde_json::json!({
+        "query": "{ instanceStats(days: 7) { days { date voters activeDevices endorsementsByTopic { topic endorsements } } } }"
+    })
+    .to_string();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/graphql")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = json_body(response).await;
+    let day = &json["data"]["instanceStats"]["days"][0];
+    assert_eq!(day["date"], "2025-01-02");
+    assert_eq!(day["voters"], 2);
+    assert_eq!(day["activeDevices"], 5);
+    assert_eq!(day["endorsementsByTopic"][0]["endorsements"], 6);
+}
+
+#[shared_runtime_test]
+async fn test_compute_daily_rollup_counts_signups_and_endorsements() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+
+    let alice = AccountFactory::new().create(pool).await.expect("alice");
+    let bob = AccountFactory::new().create(pool).await.expect("bob");
+    insert_endorsement(pool, alice.id, bob.id, 1.0).await;
+
+    let today = Utc::now().date_naive();
+    compute_daily_rollup(pool, today).await.expect("rollup");
+    // Recomputing the same day overwrites rather than duplicating.
+    compute_daily_rollup(pool, today)
+        .await
+        .expect("rollup again");
+
+    let stats = list_daily_stats(pool, today).await.expect("list");
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].signups, 2);
+    assert_eq!(
+        stats[0].endorsements_by_topic,
+        vec![("trust".to_string(), 1)]
+    );
+}
+
+#[shared_runtime_test]
+async fn test_run_pending_rollups_skips_today_and_is_idempotent() {
+    let db = isolated_db().await;
+    let pool = db.pool();
+    let today = Utc::now().date_naive();
+
+    let computed = run_pending_rollups(pool, today).await.expect("first run");
+    assert_eq!(computed, 30);
+    let computed = run_pending_rollups(pool, today).await.expect("second run");
+    assert_eq!(computed, 0);
+
+    let stats = list_daily_stats(pool, today).await.expect("list");
+    assert!(stats.is_empty(), "today must not be rolled up");
+}
@@ -0,0 +1,257 @@
+//! Integration tests for SMS step-up: phone enrollment, challenges, and the
+//! step-up gate on device revocation and phone removal.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, HeaderValue, Method, Request, StatusCode},
+    Extension, Router,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::rngs::OsRng;
+use serde_json::Value;
+use tc_crypto::{encode_base64url, Kid};
+use tower::ServiceExt;
+
+use common::app_builder::TestAppBuilder;
+use common::factories::{build_authed_request, valid_signup_with_keys, SignupKeys};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::identity::step_up::STEP_UP_HEADER;
+use tinycongress_api::sms::{MockSmsProvider, SmsProvider};
+
+async fn signup(app: &Router, username: &str) -> SignupKeys {
+    let (json, keys) = valid_signup_with_keys(username);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    keys
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &SignupKeys,
+    challenge: Option<&str>,
+) -> (StatusCode, Value) {
+    let mut request = build_authed_request(
+        method,
+        path,
+        body,
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    if let Some(challenge) = challenge {
+        request.headers_mut().insert(
+            STEP_UP_HEADER,
+            HeaderValue::from_str(challenge).expect("header"),
+        );
+    }
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+fn last_code(sms: &MockSmsProvider) -> String {
+    let sent = sms.sent();
+    let body = &sent.last().expect("an SMS was sent").body;
+    body.split(|c: char| !c.is_ascii_digit())
+        .find(|word| word.len() == 6)
+        .expect("code in message")
+        .to_string()
+}
+
+/// Request a challenge for `action`, confirm it, and return its ID.
+async fn confirmed_challenge(
+    app: &Router,
+    sms: &MockSmsProvider,
+    keys: &SignupKeys,
+    action: &str,
+) -> String {
+    let (status, challenge) = send(
+        app,
+        Method::POST,
+        "/auth/step-up/challenges",
+        &format!(r#"{{"action":"{action}"}}"#),
+        keys,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let id = challenge["challenge_id"].as_str().expect("id").to_string();
+    let (status, confirmed) = send(
+        app,
+        Method::POST,
+        &format!("/auth/step-up/challenges/{id}/confirm"),
+        &format!(r#"{{"code":"{}"}}"#, last_code(sms)),
+        keys,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(confirmed["confirmed_at"].is_string());
+    id
+}
+
+#[shared_runtime_test]
+async fn test_step_up_gates_revocation_from_new_device() {
+    let builder = TestAppBuilder::new().with_transactional_pool().await;
+    let sms = Arc::new(MockSmsProvider::default());
+    let app = builder
+        .build()
+        .layer(Extension(sms.clone() as Arc<dyn SmsProvider>));
+    let keys = signup(&app, "stepup-user").await;
+
+    // Add a second device to revoke later
+    let other = SigningKey::generate(&mut OsRng);
+    let other_pubkey = other.verifying_key().to_bytes();
+    let other_kid = Kid::derive(&other_pubkey);
+    let body = serde_json::json!({
+        "pubkey": encode_base64url(&other_pubkey),
+        "name": "Other",
+        "certificate": encode_base64url(&keys.root_signing_key.sign(&other_pubkey).to_bytes()),
+    })
+    .to_string();
+    let (status, _) = send(&app, Method::POST, "/auth/devices", &body, &keys, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Enroll a phone
+    let phone = "/auth/step-up/phone";
+    let (_, status_body) = send(&app, Method::GET, phone, "", &keys, None).await;
+    assert_eq!(status_body["enrolled"], false);
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        phone,
+        r#"{"phone_number":"5551234567"}"#,
+        &keys,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let number = r#"{"phone_number":"+1 555 123 4567"}"#;
+    let (status, enrollment) = send(&app, Method::POST, phone, number, &keys, None).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(enrollment["action"], "enroll_phone");
+    assert_eq!(enrollment["phone_hint"], "•••4567");
+    assert_eq!(sms.sent()[0].to, "+15551234567");
+
+    let code = last_code(&sms);
+    let wrong = if code == "000000" { "111111" } else { "000000" };
+    let confirm = format!(
+        "/auth/step-up/challenges/{}/confirm",
+        enrollment["challenge_id"].as_str().expect("id")
+    );
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        &confirm,
+        &format!(r#"{{"code":"{wrong}"}}"#),
+        &keys,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        &confirm,
+        &format!(r#"{{"code":"{code}"}}"#),
+        &keys,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, status_body) = send(&app, Method::GET, phone, "", &keys, None).await;
+    assert_eq!(status_body["enrolled"], true);
+    assert_eq!(status_body["phone_hint"], "•••4567");
+    let (status, _) = send(&app, Method::POST, phone, number, &keys, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // The signup device is brand new, so revoking another device needs step-up
+    let revoke = format!("/auth/devices/{other_kid}");
+    let (status, refused) = send(&app, Method::DELETE, &revoke, "", &keys, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(refused["code"], "STEP_UP_REQUIRED");
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        "/auth/step-up/challenges",
+        r#"{"action":"enroll_phone"}"#,
+        &keys,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let challenge = confirmed_challenge(&app, &sms, &keys, "revoke_device").await;
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        phone,
+        "",
+        &keys,
+        Some(challenge.as_str()),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::FORBIDDEN,
+        "challenge is bound to its action"
+    );
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        &revoke,
+        "",
+        &keys,
+        Some(challenge.as_str()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Removing the phone always needs step-up, and a challenge works once
+    let challenge = confirmed_challenge(&app, &sms, &keys, "remove_phone").await;
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        phone,
+        "",
+        &keys,
+        Some(challenge.as_str()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, status_body) = send(&app, Method::GET, phone, "", &keys, None).await;
+    assert_eq!(status_body["enrolled"], false);
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        phone,
+        "",
+        &keys,
+        Some(challenge.as_str()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
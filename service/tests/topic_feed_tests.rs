@@ -0,0 +1,175 @@
+//! Integration tests for topic subscriptions, feed fan-out, and the feed endpoint.
+
+mod common;
+
+use axum::{
+    http::{Method, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use common::factories::{
+    build_authed_request, signup_user_transactional, AccountFactory, SignupKeys,
+};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::events::DomainEvent;
+use tinycongress_api::reputation::repo::create_endorsement;
+use tinycongress_api::reputation::EndorsementVisibility;
+use tinycongress_api::topics::projector::feed_item_for;
+use tinycongress_api::topics::repo::fan_out;
+
+async fn account_id(pool: &PgPool, username: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM accounts WHERE username = $1")
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .expect("account exists")
+}
+
+/// Create a `topic` endorsement of `subject_id` and fan it out as the
+/// projector would; returns the endorsement ID and the feeds written.
+async fn endorse_and_fan_out(pool: &PgPool, subject_id: Uuid, topic: &str) -> (Uuid, u64) {
+    let created = create_endorsement(pool, subject_id, topic, None, None, 1.0, None, false)
+        .await
+        .expect("create endorsement");
+    let event = DomainEvent::EndorsementCreated {
+        endorsement_id: created.id,
+        subject_id,
+        topic: topic.to_string(),
+        issuer_id: None,
+        visibility: EndorsementVisibility::Public,
+    };
+    let item = feed_item_for(&event).expect("endorsement produces a feed item");
+    let delivered = fan_out(pool, &item).await.expect("fan out");
+    (created.id, delivered)
+}
+
+async fn send(app: &Router, method: Method, path: &str, keys: &SignupKeys) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            method,
+            path,
+            "",
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+#[shared_runtime_test]
+async fn test_subscribe_and_feed() {
+    let (app, keys, pool) = signup_user_transactional("topicalice").await;
+    let alice = account_id(&pool, "topicalice").await;
+
+    let (status, body) = send(&app, Method::POST, "/topics/policy_review/subscribe", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["topic"], "policy_review");
+
+    // Subscribing again is a no-op
+    let (status, again) = send(&app, Method::POST, "/topics/policy_review/subscribe", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(again["subscribed_at"], body["subscribed_at"]);
+
+    let (endorsement_id, delivered) = endorse_and_fan_out(&pool, alice, "policy_review").await;
+    assert_eq!(delivered, 1);
+    // Redelivery is a no-op
+    let item = feed_item_for(&DomainEvent::EndorsementCreated {
+        endorsement_id,
+        subject_id: alice,
+        topic: "policy_review".to_string(),
+        issuer_id: None,
+        visibility: EndorsementVisibility::Public,
+    })
+    .expect("feed item");
+    assert_eq!(fan_out(&pool, &item).await.expect("fan out"), 0);
+    // Unsubscribed topics are not delivered
+    let (_, delivered) = endorse_and_fan_out(&pool, alice, "other_topic").await;
+    assert_eq!(delivered, 0);
+
+    let (status, feed) = send(&app, Method::GET, "/topics/feed", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = feed["items"].as_array().expect("items");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["endorsement_id"], endorsement_id.to_string());
+    assert_eq!(items[0]["subject_username"], "topicalice");
+    assert_eq!(items[0]["revoked"], false);
+    assert_eq!(feed["movements"][0]["topic"], "policy_review");
+    assert_eq!(feed["movements"][0]["active"], 1);
+    assert_eq!(feed["movements"][0]["added"], 1);
+    assert_eq!(feed["movements"][0]["revoked"], 0);
+
+    let (status, subs) = send(&app, Method::GET, "/topics/subscriptions", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(subs["subscriptions"].as_array().map(Vec::len), Some(1));
+
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        "/topics/policy_review/subscribe",
+        &keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(
+        &app,
+        Method::DELETE,
+        "/topics/policy_review/subscribe",
+        &keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Existing items stay after unsubscribing
+    let (_, feed) = send(&app, Method::GET, "/topics/feed", &keys).await;
+    assert_eq!(feed["items"].as_array().map(Vec::len), Some(1));
+    assert_eq!(feed["movements"].as_array().map(Vec::len), Some(0));
+}
+
+#[shared_runtime_test]
+async fn test_trust_and_invalid_topics_rejected() {
+    let (app, keys, _pool) = signup_user_transactional("topicbob").await;
+
+    let (status, _) = send(&app, Method::POST, "/topics/trust/subscribe", &keys).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, Method::POST, "/topics/Not-Valid/subscribe", &keys).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[shared_runtime_test]
+async fn test_feed_pagination() {
+    let (app, keys, pool) = signup_user_transactional("topicpager").await;
+    send(&app, Method::POST, "/topics/policy_review/subscribe", &keys).await;
+    for seed in 1..=3 {
+        let subject = AccountFactory::new()
+            .with_seed(seed)
+            .create(&pool)
+            .await
+            .expect("create subject");
+        endorse_and_fan_out(&pool, subject.id, "policy_review").await;
+    }
+
+    let (status, page1) = send(&app, Method::GET, "/topics/feed?limit=2", &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page1["items"].as_array().map(Vec::len), Some(2));
+    let cursor = page1["next_cursor"].as_str().expect("more pages");
+
+    let path = format!(
+        "/topics/feed?limit=2&cursor={}",
+        urlencoding::encode(cursor)
+    );
+    let (status, page2) = send(&app, Method::GET, &path, &keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page2["items"].as_array().map(Vec::len), Some(1));
+    assert!(page2["next_cursor"].is_null());
+}
@@ -0,0 +1,95 @@
+//! Integration tests for materialized trust distances — bounded refresh,
+//! incremental invalidation, and live fallback.
+
+mod common;
+
+use std::sync::Arc;
+
+use common::factories::{insert_endorsement, AccountFactory};
+use common::test_db::isolated_db;
+use sqlx::PgPool;
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::trust::distances::{DistanceOrigin, TrustDistances, MAX_HOPS};
+use tinycongress_api::trust::engine::TrustEngine;
+use uuid::Uuid;
+
+async fn chain(pool: &PgPool, len: u8) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    for seed in 1..=len {
+        let account = AccountFactory::new()
+            .with_seed(seed)
+            .create(pool)
+            .await
+            .expect("create account");
+        ids.push(account.id);
+    }
+    for pair in ids.windows(2) {
+        insert_endorsement(pool, pair[0], pair[1], 1.0).await;
+    }
+    ids
+}
+
+fn distances(pool: &PgPool) -> TrustDistances {
+    TrustDistances::new(pool.clone(), Arc::new(TrustEngine::new(pool.clone())))
+}
+
+// Seed → 1 → 2 → 3 → 4 → 5: pairs within MAX_HOPS come from the table, the
+// rest from a live traversal.
+#[shared_runtime_test]
+async fn test_bounded_pairs_materialized_and_far_pairs_live() {
+    let db = isolated_db().await;
+    let pool = db.pool().clone();
+    let ids = chain(&pool, 6).await;
+    let store = distances(&pool);
+
+    // Cold source: live, and queued for refresh
+    let cold = store.distance(ids[0], ids[2]).await.expect("distance");
+    assert_eq!(cold.origin, DistanceOrigin::Live);
+    assert_eq!(cold.distance, Some(2.0));
+
+    assert_eq!(store.refresh(10).await.expect("refresh"), 1);
+
+    let near = store.distance(ids[0], ids[4]).await.expect("distance");
+    assert_eq!(near.origin, DistanceOrigin::Materialized);
+    assert_eq!(near.distance, Some(4.0));
+
+    assert_eq!(MAX_HOPS, 4);
+    let far = store.distance(ids[0], ids[5]).await.expect("distance");
+    assert_eq!(far.origin, DistanceOrigin::Live);
+    assert_eq!(far.distance, Some(5.0));
+
+    // Nothing dirty or stale left
+    assert_eq!(store.refresh(10).await.expect("refresh"), 0);
+}
+
+// A new edge out of an account marks upstream sources dirty so lookups go
+// live until the next refresh.
+#[shared_runtime_test]
+async fn test_edge_change_invalidates_upstream_sources() {
+    let db = isolated_db().await;
+    let pool = db.pool().clone();
+    let ids = chain(&pool, 3).await;
+    let store = distances(&pool);
+
+    store.mark_edge_changed(ids[0]).await.expect("mark");
+    store.refresh(10).await.expect("refresh");
+    let before = store.distance(ids[0], ids[2]).await.expect("distance");
+    assert_eq!(before.origin, DistanceOrigin::Materialized);
+
+    let newcomer = AccountFactory::new()
+        .with_seed(9)
+        .create(&pool)
+        .await
+        .expect("create newcomer");
+    insert_endorsement(&pool, ids[2], newcomer.id, 1.0).await;
+    store.mark_edge_changed(ids[2]).await.expect("mark");
+
+    let stale = store.distance(ids[0], ids[2]).await.expect("distance");
+    assert_eq!(stale.origin, DistanceOrigin::Live);
+
+    // The endorser itself plus the upstream source
+    assert_eq!(store.refresh(10).await.expect("refresh"), 2);
+    let after = store.distance(ids[0], newcomer.id).await.expect("distance");
+    assert_eq!(after.origin, DistanceOrigin::Materialized);
+    assert_eq!(after.distance, Some(3.0));
+}
@@ -5,13 +5,15 @@
 mod common;
 
 use async_trait::async_trait;
-use common::factories::{insert_endorsement, insert_revoked_endorsement, AccountFactory};
+use common::factories::{insert_endorsement, AccountFactory, TrustEdgeFactory};
 use common::test_db::isolated_db;
 use tc_test_macros::shared_runtime_test;
 use tinycongress_api::trust::engine::{TrustEngine, TrustEngineError};
+use tinycongress_api::trust::invite_quota::InviteQuota;
 use tinycongress_api::trust::repo::{
     ActionRecord, DenouncementRecord, DenouncementWithUsername, InfluenceRecord, InviteRecord,
-    PgTrustRepo, ScoreSnapshot, TrustRepo, TrustRepoError,
+    InviteTreeEdge, PairingCodeRecord, PgTrustRepo, ScoreSnapshot, StaleEdge, TrustRepo,
+    TrustRepoError,
 };
 use tinycongress_api::trust::service::ActionType;
 use tinycongress_api::trust::weight::{DeliveryMethod, RelationshipDepth};
@@ -265,7 +267,11 @@ async fn test_revoked_edge_exclusion() {
 
     insert_endorsement(&pool, seed.id, a.id, 1.0).await;
     // Insert the A→B edge as revoked — B should be unreachable
-    insert_revoked_endorsement(&pool, a.id, b.id, 1.0).await;
+    TrustEdgeFactory::new(a.id, b.id)
+        .revoked()
+        .create(&pool)
+        .await
+        .expect("create revoked edge");
 
     let engine = TrustEngine::new(pool);
     let scores = engine
@@ -686,6 +692,43 @@ impl TrustRepo for FailingUpsertRepo {
     async fn list_invites_by_endorser(&self, _: Uuid) -> Result<Vec<InviteRecord>, TrustRepoError> {
         unimplemented!()
     }
+    async fn get_invite_quota(&self, _: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!()
+    }
+    async fn get_invite_tree(
+        &self,
+        _: Uuid,
+        _: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!()
+    }
+    async fn create_pairing_code(
+        &self,
+        _: Uuid,
+        _: Uuid,
+        _: &str,
+        _: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!()
+    }
+    async fn resolve_pairing_code(&self, _: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!()
+    }
+    async fn list_stale_edges(
+        &self,
+        _: Uuid,
+        _: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!()
+    }
+    async fn reconfirm_edge(
+        &self,
+        _: Uuid,
+        _: Uuid,
+        _: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!()
+    }
     async fn upsert_score(
         &self,
         _: Uuid,
@@ -16,16 +16,25 @@ use tower::ServiceExt;
 use uuid::Uuid;
 
 use common::app_builder::TestAppBuilder;
-use common::factories::{build_authed_request, valid_signup_with_keys};
+use common::factories::{build_authed_request, insert_endorsement, valid_signup_with_keys};
 use common::test_db::isolated_db;
+use ed25519_dalek::Signer;
 use tc_test_macros::shared_runtime_test;
+use tinycongress_api::pagination::KeysetPage;
 use tinycongress_api::reputation::repo::{
-    CreatedEndorsement, EndorsementRecord, EndorsementRepoError, ExternalIdentityRecord,
-    ExternalIdentityRepoError, ReputationRepo,
+    CreatedEndorsement, DisputeRecord, DisputeRepoError, EndorsementRecord, EndorsementRepoError,
+    ExternalIdentityRecord, ExternalIdentityRepoError, InPersonAttestation, IssuedEndorsement,
+    NewChallenge, NewDispute, NewStrike, ReputationRepo, StrikeEventRecord, StrikeRecord,
+    StrikeRepoError, TopicCount, VerificationRecord, VerificationRepoError,
 };
+use tinycongress_api::reputation::strikes::AppealStatus;
+use tinycongress_api::reputation::verification::VerificationMethod;
+use tinycongress_api::reputation::DisputeStatus;
+use tinycongress_api::trust::http::reconfirmation_message;
+use tinycongress_api::trust::invite_quota::InviteQuota;
 use tinycongress_api::trust::repo::{
     ActionRecord, DenouncementRecord, DenouncementWithUsername, InfluenceRecord, InviteRecord,
-    ScoreSnapshot, TrustRepo, TrustRepoError,
+    InviteTreeEdge, PairingCodeRecord, ScoreSnapshot, StaleEdge, TrustRepo, TrustRepoError,
 };
 use tinycongress_api::trust::service::{ActionType, TrustService, TrustServiceError};
 use tinycongress_api::trust::weight::{DeliveryMethod, RelationshipDepth};
@@ -159,6 +168,43 @@ impl TrustRepo for StubAcceptInviteNullTimestamp {
     ) -> Result<Vec<InviteRecord>, TrustRepoError> {
         unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
     }
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
+    }
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteNullTimestamp: not needed for this test")
+    }
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -669,6 +715,227 @@ async fn test_create_invite_returns_201() {
     assert!(json["expires_at"].is_string());
 }
 
+/// Helper: create an invite via the API and return the response.
+async fn post_invite(
+    app: &axum::Router,
+    keys: &common::factories::SignupKeys,
+) -> axum::http::Response<Body> {
+    let body = serde_json::json!({
+        "envelope": tc_crypto::encode_base64url(b"quota-envelope"),
+        "delivery_method": "email",
+        "attestation": {}
+    })
+    .to_string();
+    let request = build_authed_request(
+        Method::POST,
+        "/trust/invites",
+        &body,
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    app.clone().oneshot(request).await.expect("response")
+}
+
+#[shared_runtime_test]
+async fn test_create_invite_enforces_quota() {
+    let db = isolated_db().await;
+    let (app, keys, _account_id) = signup_and_get_account("quotainviter", db.pool()).await;
+
+    // A brand-new account with no endorsements gets the base allowance of 2.
+    for _ in 0..2 {
+        assert_eq!(post_invite(&app, &keys).await.status(), StatusCode::CREATED);
+    }
+    assert_eq!(
+        post_invite(&app, &keys).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    let request = build_authed_request(
+        Method::GET,
+        "/trust/invites/quota",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = json_body(response).await;
+    assert_eq!(json["allowance"], 2);
+    assert_eq!(json["used"], 2);
+    assert_eq!(json["remaining"], 0);
+}
+
+#[shared_runtime_test]
+async fn test_invite_tree_follows_accepted_invites() {
+    let db = isolated_db().await;
+    let (app, root_keys, root_id) = signup_and_get_account("treeroot", db.pool()).await;
+    let (_, child_keys, child_id) = signup_and_get_account("treechild", db.pool()).await;
+    let (_, grandchild_keys, grandchild_id) =
+        signup_and_get_account("treegrandchild", db.pool()).await;
+
+    for (inviter, invitee) in [(&root_keys, &child_keys), (&child_keys, &grandchild_keys)] {
+        let created = json_body(post_invite(&app, inviter).await).await;
+        let invite_id = created["id"].as_str().expect("invite id");
+        let request = build_authed_request(
+            Method::POST,
+            &format!("/trust/invites/{invite_id}/accept"),
+            "",
+            &invitee.device_signing_key,
+            &invitee.device_kid,
+        );
+        let response = app.clone().oneshot(request).await.expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let request = build_authed_request(
+        Method::GET,
+        "/trust/invites/tree",
+        "",
+        &root_keys.device_signing_key,
+        &root_keys.device_kid,
+    );
+    let response = app.clone().oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = json_body(response).await;
+    assert_eq!(json["root_id"], root_id.to_string());
+    assert_eq!(json["total_invitees"], 2);
+    let edges = json["edges"].as_array().expect("edges");
+    assert_eq!(edges[0]["invitee_id"], child_id.to_string());
+    assert_eq!(edges[0]["depth"], 1);
+    assert_eq!(edges[1]["inviter_id"], child_id.to_string());
+    assert_eq!(edges[1]["invitee_id"], grandchild_id.to_string());
+    assert_eq!(edges[1]["invitee_username"], "treegrandchild");
+    assert_eq!(edges[1]["depth"], 2);
+
+    let request = build_authed_request(
+        Method::GET,
+        "/trust/invites/tree?max_depth=1",
+        "",
+        &root_keys.device_signing_key,
+        &root_keys.device_kid,
+    );
+    let json = json_body(app.clone().oneshot(request).await.expect("response")).await;
+    assert_eq!(json["total_invitees"], 1);
+
+    let request = build_authed_request(
+        Method::GET,
+        "/trust/invites/tree?max_depth=6",
+        "",
+        &root_keys.device_signing_key,
+        &root_keys.device_kid,
+    );
+    let response = app.oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// ─── Re-confirmation ──────────────────────────────────────────────────────────
+
+async fn list_reconfirmations(app: axum::Router, keys: &common::factories::SignupKeys) -> Value {
+    let request = build_authed_request(
+        Method::GET,
+        "/trust/reconfirmations",
+        "",
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+    let response = app.oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    json_body(response).await
+}
+
+#[shared_runtime_test]
+async fn test_reconfirm_resets_decay_with_subject_cosignature() {
+    let db = isolated_db().await;
+    let (app, keys, endorser_id) = signup_and_get_account("reconfendorser", db.pool()).await;
+    let (_, subject_keys, subject_id) = signup_and_get_account("reconfsubject", db.pool()).await;
+    insert_endorsement(db.pool(), endorser_id, subject_id, 1.0).await;
+    sqlx::query(
+        "UPDATE reputation__endorsements SET last_confirmed_at = now() - interval '400 days' \
+         WHERE endorser_id = $1 AND subject_id = $2",
+    )
+    .bind(endorser_id)
+    .bind(subject_id)
+    .execute(db.pool())
+    .await
+    .expect("backdate edge");
+
+    let json = list_reconfirmations(app.clone(), &keys).await;
+    let edges = json["edges"].as_array().expect("edges");
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0]["subject_username"], "reconfsubject");
+    assert_eq!(edges[0]["decay_factor"], 0.5);
+
+    let timestamp = chrono::Utc::now().timestamp();
+    let message = reconfirmation_message(endorser_id, subject_id, timestamp);
+    let reconfirm = |signer: &ed25519_dalek::SigningKey| {
+        let body = serde_json::json!({
+            "subject_id": subject_id,
+            "subject_kid": subject_keys.device_kid.to_string(),
+            "timestamp": timestamp,
+            "subject_signature": tc_crypto::encode_base64url(&signer.sign(&message).to_bytes()),
+        })
+        .to_string();
+        build_authed_request(
+            Method::POST,
+            "/trust/reconfirm",
+            &body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        )
+    };
+
+    // The endorser cannot co-sign on the subject's behalf
+    let response = app
+        .clone()
+        .oneshot(reconfirm(&keys.device_signing_key))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(reconfirm(&subject_keys.device_signing_key))
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        json_body(response).await["subject_id"],
+        subject_id.to_string()
+    );
+
+    let json = list_reconfirmations(app, &keys).await;
+    assert!(json["edges"].as_array().expect("edges").is_empty());
+}
+
+#[shared_runtime_test]
+async fn test_reconfirm_without_edge_returns_404() {
+    let db = isolated_db().await;
+    let (app, keys, endorser_id) = signup_and_get_account("reconfnoedge", db.pool()).await;
+    let (_, subject_keys, subject_id) = signup_and_get_account("reconfstranger", db.pool()).await;
+
+    let timestamp = chrono::Utc::now().timestamp();
+    let message = reconfirmation_message(endorser_id, subject_id, timestamp);
+    let body = serde_json::json!({
+        "subject_id": subject_id,
+        "subject_kid": subject_keys.device_kid.to_string(),
+        "timestamp": timestamp,
+        "subject_signature": tc_crypto::encode_base64url(
+            &subject_keys.device_signing_key.sign(&message).to_bytes()
+        ),
+    })
+    .to_string();
+    let request = build_authed_request(
+        Method::POST,
+        "/trust/reconfirm",
+        &body,
+        &keys.device_signing_key,
+        &keys.device_kid,
+    );
+
+    let response = app.oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
 // ─── Endorse self-action validation ──────────────────────────────────────────
 
 #[shared_runtime_test]
@@ -2569,6 +2836,13 @@ impl ReputationRepo for StubBudgetReputationRepoReturnsError {
         unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
     }
 
+    async fn create_issued_endorsement(
+        &self,
+        _endorsement: &IssuedEndorsement<'_>,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError> {
+        unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
+    }
+
     async fn has_endorsement(
         &self,
         _subject_id: Uuid,
@@ -2584,6 +2858,21 @@ impl ReputationRepo for StubBudgetReputationRepoReturnsError {
         unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
     }
 
+    async fn list_endorsements_by_subject_page(
+        &self,
+        _subject_id: Uuid,
+        _page: &KeysetPage,
+    ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
+        unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
+    }
+
+    async fn count_active_endorsements_by_topic(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementRepoError> {
+        unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
+    }
+
     async fn revoke_endorsement(
         &self,
         _endorser_id: Uuid,
@@ -2593,6 +2882,104 @@ impl ReputationRepo for StubBudgetReputationRepoReturnsError {
         unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
     }
 
+    async fn open_dispute(
+        &self,
+        _dispute: &NewDispute<'_>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
+    }
+    async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+        unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
+    }
+    async fn resolve_dispute(
+        &self,
+        _endorsement_id: Uuid,
+        _outcome: DisputeStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        unimplemented!("StubBudgetReputationRepoReturnsError: not needed for this test")
+    }
+    async fn issue_strike(&self, _strike: &NewStrike<'_>) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strikes_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strike_events_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn appeal_strike(
+        &self,
+        _strike_id: Uuid,
+        _account_id: Uuid,
+        _statement: &str,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn resolve_appeal(
+        &self,
+        _strike_id: Uuid,
+        _outcome: AppealStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_verifications(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn start_verification_challenge(
+        &self,
+        _challenge: &NewChallenge<'_>,
+    ) -> Result<(), VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn confirm_verification_challenge(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _code_hash: &[u8],
+        _max_attempts: i32,
+        _provider: &str,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn record_verification(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _provider: &str,
+        _destination_hash: Option<&[u8]>,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn attest_in_person(
+        &self,
+        _subject_id: Uuid,
+        _attester_id: Uuid,
+        _required: i64,
+    ) -> Result<InPersonAttestation, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn count_in_person_attestations(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<i64, VerificationRepoError> {
+        unimplemented!()
+    }
     async fn link_external_identity(
         &self,
         _account_id: Uuid,
@@ -2735,6 +3122,45 @@ impl TrustRepo for PanickingTrustRepo {
         unimplemented!("PanickingTrustRepo: must not be called in this test")
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("PanickingTrustRepo: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("PanickingTrustRepo: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("PanickingTrustRepo: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("PanickingTrustRepo: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("PanickingTrustRepo: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("PanickingTrustRepo: must not be called in this test")
+    }
+
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -2835,6 +3261,13 @@ impl ReputationRepo for StubBudgetAllEndorsementsReturnsError {
         unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
     }
 
+    async fn create_issued_endorsement(
+        &self,
+        _endorsement: &IssuedEndorsement<'_>,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError> {
+        unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
+    }
+
     async fn has_endorsement(
         &self,
         _subject_id: Uuid,
@@ -2850,6 +3283,21 @@ impl ReputationRepo for StubBudgetAllEndorsementsReturnsError {
         unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
     }
 
+    async fn list_endorsements_by_subject_page(
+        &self,
+        _subject_id: Uuid,
+        _page: &KeysetPage,
+    ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
+        unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
+    }
+
+    async fn count_active_endorsements_by_topic(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementRepoError> {
+        unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
+    }
+
     async fn revoke_endorsement(
         &self,
         _endorser_id: Uuid,
@@ -2859,38 +3307,136 @@ impl ReputationRepo for StubBudgetAllEndorsementsReturnsError {
         unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
     }
 
-    async fn link_external_identity(
+    async fn open_dispute(
         &self,
-        _account_id: Uuid,
-        _provider: &str,
-        _provider_subject: &str,
-    ) -> Result<ExternalIdentityRecord, ExternalIdentityRepoError> {
+        _dispute: &NewDispute<'_>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
         unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
     }
-
-    async fn get_external_identity_by_provider(
+    async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+        unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
+    }
+    async fn resolve_dispute(
         &self,
-        _provider: &str,
-        _provider_subject: &str,
-    ) -> Result<ExternalIdentityRecord, ExternalIdentityRepoError> {
+        _endorsement_id: Uuid,
+        _outcome: DisputeStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
         unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
     }
-}
-
-/// When `count_all_active_trust_endorsements_by` returns an error,
-/// `budget_handler` must return 500 Internal Server Error before reaching the
-/// `TrustRepo` call.
-///
-/// The first reputation-repo call succeeds (returns 0); the second fails.
-#[shared_runtime_test]
-async fn budget_returns_500_when_all_endorsements_count_fails() {
-    let db = isolated_db().await;
-    let (_, keys, _) = signup_and_get_account("budgetallerror", db.pool()).await;
-
-    let app = TestAppBuilder::new()
-        .with_identity_pool(db.pool().clone())
-        .with_stub_trust_repo(Arc::new(PanickingTrustRepo))
-        .with_stub_reputation_repo(Arc::new(StubBudgetAllEndorsementsReturnsError))
+    async fn issue_strike(&self, _strike: &NewStrike<'_>) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strikes_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strike_events_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn appeal_strike(
+        &self,
+        _strike_id: Uuid,
+        _account_id: Uuid,
+        _statement: &str,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn resolve_appeal(
+        &self,
+        _strike_id: Uuid,
+        _outcome: AppealStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_verifications(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn start_verification_challenge(
+        &self,
+        _challenge: &NewChallenge<'_>,
+    ) -> Result<(), VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn confirm_verification_challenge(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _code_hash: &[u8],
+        _max_attempts: i32,
+        _provider: &str,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn record_verification(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _provider: &str,
+        _destination_hash: Option<&[u8]>,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn attest_in_person(
+        &self,
+        _subject_id: Uuid,
+        _attester_id: Uuid,
+        _required: i64,
+    ) -> Result<InPersonAttestation, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn count_in_person_attestations(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<i64, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn link_external_identity(
+        &self,
+        _account_id: Uuid,
+        _provider: &str,
+        _provider_subject: &str,
+    ) -> Result<ExternalIdentityRecord, ExternalIdentityRepoError> {
+        unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
+    }
+
+    async fn get_external_identity_by_provider(
+        &self,
+        _provider: &str,
+        _provider_subject: &str,
+    ) -> Result<ExternalIdentityRecord, ExternalIdentityRepoError> {
+        unimplemented!("StubBudgetAllEndorsementsReturnsError: not needed for this test")
+    }
+}
+
+/// When `count_all_active_trust_endorsements_by` returns an error,
+/// `budget_handler` must return 500 Internal Server Error before reaching the
+/// `TrustRepo` call.
+///
+/// The first reputation-repo call succeeds (returns 0); the second fails.
+#[shared_runtime_test]
+async fn budget_returns_500_when_all_endorsements_count_fails() {
+    let db = isolated_db().await;
+    let (_, keys, _) = signup_and_get_account("budgetallerror", db.pool()).await;
+
+    let app = TestAppBuilder::new()
+        .with_identity_pool(db.pool().clone())
+        .with_stub_trust_repo(Arc::new(PanickingTrustRepo))
+        .with_stub_reputation_repo(Arc::new(StubBudgetAllEndorsementsReturnsError))
         .build();
 
     let request = build_authed_request(
@@ -2946,6 +3492,13 @@ impl ReputationRepo for StubBudgetBothEndorsementsSucceed {
         unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
     }
 
+    async fn create_issued_endorsement(
+        &self,
+        _endorsement: &IssuedEndorsement<'_>,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError> {
+        unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
+    }
+
     async fn has_endorsement(
         &self,
         _subject_id: Uuid,
@@ -2961,6 +3514,21 @@ impl ReputationRepo for StubBudgetBothEndorsementsSucceed {
         unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
     }
 
+    async fn list_endorsements_by_subject_page(
+        &self,
+        _subject_id: Uuid,
+        _page: &KeysetPage,
+    ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
+        unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
+    }
+
+    async fn count_active_endorsements_by_topic(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementRepoError> {
+        unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
+    }
+
     async fn revoke_endorsement(
         &self,
         _endorser_id: Uuid,
@@ -2970,6 +3538,104 @@ impl ReputationRepo for StubBudgetBothEndorsementsSucceed {
         unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
     }
 
+    async fn open_dispute(
+        &self,
+        _dispute: &NewDispute<'_>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
+    }
+    async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+        unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
+    }
+    async fn resolve_dispute(
+        &self,
+        _endorsement_id: Uuid,
+        _outcome: DisputeStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        unimplemented!("StubBudgetBothEndorsementsSucceed: not needed for this test")
+    }
+    async fn issue_strike(&self, _strike: &NewStrike<'_>) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strikes_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strike_events_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn appeal_strike(
+        &self,
+        _strike_id: Uuid,
+        _account_id: Uuid,
+        _statement: &str,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn resolve_appeal(
+        &self,
+        _strike_id: Uuid,
+        _outcome: AppealStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_verifications(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn start_verification_challenge(
+        &self,
+        _challenge: &NewChallenge<'_>,
+    ) -> Result<(), VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn confirm_verification_challenge(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _code_hash: &[u8],
+        _max_attempts: i32,
+        _provider: &str,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn record_verification(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _provider: &str,
+        _destination_hash: Option<&[u8]>,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn attest_in_person(
+        &self,
+        _subject_id: Uuid,
+        _attester_id: Uuid,
+        _required: i64,
+    ) -> Result<InPersonAttestation, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn count_in_person_attestations(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<i64, VerificationRepoError> {
+        unimplemented!()
+    }
     async fn link_external_identity(
         &self,
         _account_id: Uuid,
@@ -3112,6 +3778,45 @@ impl TrustRepo for StubBudgetTrustRepoDenouncementsError {
         unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoDenouncementsError: must not be called in this test")
+    }
+
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -3306,6 +4011,45 @@ impl TrustRepo for StubScoresMeReturnsError {
         unimplemented!("StubScoresMeReturnsError: must not be called in this test")
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubScoresMeReturnsError: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubScoresMeReturnsError: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubScoresMeReturnsError: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubScoresMeReturnsError: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubScoresMeReturnsError: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubScoresMeReturnsError: must not be called in this test")
+    }
+
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -3444,6 +4188,44 @@ impl TrustRepo for StubListDenouncementsReturnsError {
     ) -> Result<Vec<InviteRecord>, TrustRepoError> {
         unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
     }
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubListDenouncementsReturnsError: must not be called in this test")
+    }
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -3553,6 +4335,45 @@ impl TrustRepo for StubListInvitesReturnsError {
         Err(TrustRepoError::Database(sqlx::Error::RowNotFound))
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubListInvitesReturnsError: not needed for this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubListInvitesReturnsError: not needed for this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubListInvitesReturnsError: not needed for this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubListInvitesReturnsError: not needed for this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubListInvitesReturnsError: not needed for this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubListInvitesReturnsError: not needed for this test")
+    }
+
     async fn get_or_create_influence(
         &self,
         _user_id: Uuid,
@@ -3849,6 +4670,45 @@ impl TrustRepo for StubCreateInviteReturnsError {
         unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubCreateInviteReturnsError: must not be called in this test")
+    }
+
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -4059,6 +4919,45 @@ impl TrustRepo for StubAcceptInviteAcceptReturnsError {
         unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteAcceptReturnsError: must not be called in this test")
+    }
+
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -4279,6 +5178,44 @@ impl TrustRepo for NeverCalledTrustRepo {
     ) -> Result<Vec<InviteRecord>, TrustRepoError> {
         unimplemented!("NeverCalledTrustRepo: must not be called in this test")
     }
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("NeverCalledTrustRepo: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("NeverCalledTrustRepo: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("NeverCalledTrustRepo: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("NeverCalledTrustRepo: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("NeverCalledTrustRepo: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("NeverCalledTrustRepo: must not be called in this test")
+    }
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -4671,6 +5608,44 @@ impl TrustRepo for StubAcceptInviteSuccessRepo {
     ) -> Result<Vec<InviteRecord>, TrustRepoError> {
         unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
     }
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubAcceptInviteSuccessRepo: must not be called in this test")
+    }
     async fn upsert_score(
         &self,
         _user_id: Uuid,
@@ -4825,6 +5800,13 @@ impl ReputationRepo for StubBudgetRepoConcurrentRevocation {
         unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
     }
 
+    async fn create_issued_endorsement(
+        &self,
+        _endorsement: &IssuedEndorsement<'_>,
+    ) -> Result<CreatedEndorsement, EndorsementRepoError> {
+        unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
+    }
+
     async fn has_endorsement(
         &self,
         _subject_id: Uuid,
@@ -4840,6 +5822,21 @@ impl ReputationRepo for StubBudgetRepoConcurrentRevocation {
         unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
     }
 
+    async fn list_endorsements_by_subject_page(
+        &self,
+        _subject_id: Uuid,
+        _page: &KeysetPage,
+    ) -> Result<Vec<EndorsementRecord>, EndorsementRepoError> {
+        unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
+    }
+
+    async fn count_active_endorsements_by_topic(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<Vec<TopicCount>, EndorsementRepoError> {
+        unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
+    }
+
     async fn revoke_endorsement(
         &self,
         _endorser_id: Uuid,
@@ -4849,6 +5846,104 @@ impl ReputationRepo for StubBudgetRepoConcurrentRevocation {
         unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
     }
 
+    async fn open_dispute(
+        &self,
+        _dispute: &NewDispute<'_>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
+    }
+    async fn list_open_disputes(&self) -> Result<Vec<DisputeRecord>, DisputeRepoError> {
+        unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
+    }
+    async fn resolve_dispute(
+        &self,
+        _endorsement_id: Uuid,
+        _outcome: DisputeStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<DisputeRecord, DisputeRepoError> {
+        unimplemented!("StubBudgetRepoConcurrentRevocation: not needed for this test")
+    }
+    async fn issue_strike(&self, _strike: &NewStrike<'_>) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strikes_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_strike_events_for_account(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<StrikeEventRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn appeal_strike(
+        &self,
+        _strike_id: Uuid,
+        _account_id: Uuid,
+        _statement: &str,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_pending_appeals(&self) -> Result<Vec<StrikeRecord>, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn resolve_appeal(
+        &self,
+        _strike_id: Uuid,
+        _outcome: AppealStatus,
+        _resolved_by: Uuid,
+        _note: Option<&str>,
+    ) -> Result<StrikeRecord, StrikeRepoError> {
+        unimplemented!()
+    }
+    async fn list_verifications(
+        &self,
+        _account_id: Uuid,
+    ) -> Result<Vec<VerificationRecord>, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn start_verification_challenge(
+        &self,
+        _challenge: &NewChallenge<'_>,
+    ) -> Result<(), VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn confirm_verification_challenge(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _code_hash: &[u8],
+        _max_attempts: i32,
+        _provider: &str,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn record_verification(
+        &self,
+        _account_id: Uuid,
+        _method: VerificationMethod,
+        _provider: &str,
+        _destination_hash: Option<&[u8]>,
+    ) -> Result<VerificationRecord, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn attest_in_person(
+        &self,
+        _subject_id: Uuid,
+        _attester_id: Uuid,
+        _required: i64,
+    ) -> Result<InPersonAttestation, VerificationRepoError> {
+        unimplemented!()
+    }
+    async fn count_in_person_attestations(
+        &self,
+        _subject_id: Uuid,
+    ) -> Result<i64, VerificationRepoError> {
+        unimplemented!()
+    }
     async fn link_external_identity(
         &self,
         _account_id: Uuid,
@@ -5022,6 +6117,45 @@ impl TrustRepo for StubBudgetTrustRepoZeroDenouncementsSucceed {
         )
     }
 
+    async fn get_invite_quota(&self, _user_id: Uuid) -> Result<InviteQuota, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoZeroDenouncementsSucceed: not needed for this test")
+    }
+
+    async fn get_invite_tree(
+        &self,
+        _root_id: Uuid,
+        _max_depth: i32,
+    ) -> Result<Vec<InviteTreeEdge>, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoZeroDenouncementsSucceed: not needed for this test")
+    }
+    async fn create_pairing_code(
+        &self,
+        _endorser_id: Uuid,
+        _invite_id: Uuid,
+        _code: &str,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<PairingCodeRecord, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoZeroDenouncementsSucceed: not needed for this test")
+    }
+    async fn resolve_pairing_code(&self, _code: &str) -> Result<Uuid, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoZeroDenouncementsSucceed: not needed for this test")
+    }
+    async fn list_stale_edges(
+        &self,
+        _account_id: Uuid,
+        _confirmed_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<StaleEdge>, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoZeroDenouncementsSucceed: not needed for this test")
+    }
+    async fn reconfirm_edge(
+        &self,
+        _endorser_id: Uuid,
+        _subject_id: Uuid,
+        _reconfirmation: &serde_json::Value,
+    ) -> Result<chrono::DateTime<chrono::Utc>, TrustRepoError> {
+        unimplemented!("StubBudgetTrustRepoZeroDenouncementsSucceed: not needed for this test")
+    }
+
     async fn upsert_score(
         &self,
         _user_id: Uuid,
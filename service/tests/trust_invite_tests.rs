@@ -338,3 +338,74 @@ async fn test_invite_weight_defaults_to_one() {
     );
     assert!(invite.relationship_depth.is_none());
 }
+
+#[shared_runtime_test]
+async fn test_pairing_codes_resolve_until_invite_closes() {
+    let db = isolated_db().await;
+    let pool = db.pool().clone();
+
+    let endorser = AccountFactory::new()
+        .with_seed(71)
+        .create(&pool)
+        .await
+        .expect("create endorser");
+    let other = AccountFactory::new()
+        .with_seed(72)
+        .create(&pool)
+        .await
+        .expect("create other");
+
+    let repo = PgTrustRepo::new(pool);
+    let invite_expires_at = chrono::Utc::now() + Duration::minutes(5);
+    let invite = repo
+        .create_invite(
+            endorser.id,
+            &[0u8],
+            DeliveryMethod::Qr,
+            None,
+            1.0,
+            &serde_json::json!({}),
+            invite_expires_at,
+        )
+        .await
+        .expect("create_invite");
+
+    // A code never outlives its invite
+    let later = chrono::Utc::now() + Duration::minutes(10);
+    let record = repo
+        .create_pairing_code(endorser.id, invite.id, "ABCD2345", later)
+        .await
+        .expect("create_pairing_code");
+    assert!(record.expires_at <= invite.expires_at);
+    assert_eq!(
+        repo.resolve_pairing_code("ABCD2345")
+            .await
+            .expect("resolve"),
+        invite.id
+    );
+
+    // Only the endorser can issue codes for the invite
+    let result = repo
+        .create_pairing_code(other.id, invite.id, "ABCD2346", later)
+        .await;
+    assert!(matches!(result, Err(TrustRepoError::NotFound)));
+
+    for c in "ABCDEFGHJ".chars() {
+        repo.create_pairing_code(endorser.id, invite.id, &format!("WXYZ234{c}"), later)
+            .await
+            .expect("within hourly limit");
+    }
+    let result = repo
+        .create_pairing_code(endorser.id, invite.id, "WXYZ234K", later)
+        .await;
+    assert!(matches!(
+        result,
+        Err(TrustRepoError::PairingCodeLimit { .. })
+    ));
+
+    repo.accept_invite(invite.id, other.id)
+        .await
+        .expect("accept_invite");
+    let result = repo.resolve_pairing_code("ABCD2345").await;
+    assert!(matches!(result, Err(TrustRepoError::NotFound)));
+}
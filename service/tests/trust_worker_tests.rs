@@ -166,6 +166,57 @@ async fn test_process_batch_revoke_action() {
     );
 }
 
+// ---------------------------------------------------------------------------
+// Test: endorsing again after a revoke reactivates the endorsement
+// ---------------------------------------------------------------------------
+#[shared_runtime_test]
+async fn test_process_batch_endorse_after_revoke_reactivates() {
+    let db = isolated_db().await;
+    let pool = db.pool().clone();
+
+    let actor = AccountFactory::new()
+        .with_seed(1)
+        .create(&pool)
+        .await
+        .expect("create actor");
+
+    let subject = AccountFactory::new()
+        .with_seed(2)
+        .create(&pool)
+        .await
+        .expect("create subject");
+
+    let trust_repo = PgTrustRepo::new(pool.clone());
+    let worker = make_worker(pool.clone());
+    let endorse =
+        json!({ "subject_id": subject.id, "weight": 0.5, "attestation": null, "in_slot": true });
+    for (action_type, payload) in [
+        (ActionType::Endorse, endorse.clone()),
+        (ActionType::Revoke, json!({ "subject_id": subject.id })),
+        (ActionType::Endorse, endorse),
+    ] {
+        trust_repo
+            .enqueue_action(actor.id, action_type, &payload)
+            .await
+            .expect("enqueue action");
+        assert!(worker.process_one().await.expect("process_one"));
+    }
+
+    let revoked_at: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+        "SELECT revoked_at FROM reputation__endorsements \
+         WHERE endorser_id = $1 AND subject_id = $2",
+    )
+    .bind(actor.id)
+    .bind(subject.id)
+    .fetch_one(&pool)
+    .await
+    .expect("fetch endorsement");
+    assert!(
+        revoked_at.is_none(),
+        "re-endorsing should clear the earlier revocation"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Test 3: denounce action — creates a denouncement row
 // ---------------------------------------------------------------------------
@@ -0,0 +1,278 @@
+//! Integration tests for account verification: email/phone codes, in-person
+//! attestations and the ID verification webhook.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    Extension, Router,
+};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use common::app_builder::TestAppBuilder;
+use common::factories::{build_authed_request, valid_signup_with_keys, SignupKeys};
+use tc_test_macros::shared_runtime_test;
+use tinycongress_api::config::{AccountsConfig, VerificationConfig};
+use tinycongress_api::reputation::verification::{
+    SendError, VerificationMethod, VerificationSender, IDV_SIGNATURE_HEADER,
+};
+
+const WEBHOOK_SECRET: &str = "test-webhook-secret-at-least-32-bytes";
+
+/// Keeps sent messages so the test can read the codes back.
+#[derive(Default)]
+struct CapturingSender {
+    messages: Mutex<Vec<(String, String)>>,
+}
+
+impl CapturingSender {
+    fn last_code(&self) -> String {
+        let messages = self.messages.lock().expect("lock");
+        let (_, message) = messages.last().expect("a message was sent");
+        message
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|word| word.len() == 6)
+            .expect("code in message")
+            .to_string()
+    }
+}
+
+#[async_trait]
+impl VerificationSender for CapturingSender {
+    async fn send(
+        &self,
+        _method: VerificationMethod,
+        destination: &str,
+        message: &str,
+    ) -> Result<(), SendError> {
+        self.messages
+            .lock()
+            .expect("lock")
+            .push((destination.to_string(), message.to_string()));
+        Ok(())
+    }
+}
+
+/// Helper: sign up a user and return (keys, account_id).
+async fn signup(app: &Router, username: &str) -> (SignupKeys, Uuid) {
+    let (json, keys) = valid_signup_with_keys(username);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/auth/signup")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(json))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let bytes = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    let body: Value = serde_json::from_slice(&bytes).expect("json");
+    let account_id = body["account_id"]
+        .as_str()
+        .expect("account_id")
+        .parse()
+        .expect("uuid");
+    (keys, account_id)
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    path: &str,
+    body: &str,
+    keys: &SignupKeys,
+) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(build_authed_request(
+            method,
+            path,
+            body,
+            &keys.device_signing_key,
+            &keys.device_kid,
+        ))
+        .await
+        .expect("response");
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+        .await
+        .expect("body");
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+async fn post_webhook(app: &Router, body: &str, signature: Option<&str>) -> StatusCode {
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri("/verifications/idv/webhook")
+        .header(CONTENT_TYPE, "application/json");
+    if let Some(signature) = signature {
+        request = request.header(IDV_SIGNATURE_HEADER, signature);
+    }
+    app.clone()
+        .oneshot(request.body(Body::from(body.to_string())).expect("request"))
+        .await
+        .expect("response")
+        .status()
+}
+
+fn sign(body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(WEBHOOK_SECRET.as_bytes()).expect("hmac");
+    mac.update(body.as_bytes());
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    format!("sha256={hex}")
+}
+
+#[shared_runtime_test]
+async fn test_verification_methods_raise_the_account_level() {
+    let builder = TestAppBuilder::new().with_transactional_pool().await;
+    let sender = Arc::new(CapturingSender::default());
+    let app = builder
+        .build()
+        .layer(Extension(VerificationConfig {
+            in_person_attestations_required: 1,
+            idv_webhook_secret: WEBHOOK_SECRET.to_string(),
+            ..VerificationConfig::default()
+        }))
+        .layer(Extension(sender.clone() as Arc<dyn VerificationSender>));
+    let (user_keys, user_id) = signup(&app, "verify-user").await;
+    let (other_keys, other_id) = signup(&app, "verify-other").await;
+    let (mod_keys, mod_id) = signup(&app, "verify-mod").await;
+    let app = app.layer(Extension(AccountsConfig {
+        admin_account_ids: vec![mod_id],
+    }));
+
+    // Phone code flow
+    let start = "/me/verifications/phone/start";
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        start,
+        r#"{"destination":"555-1234"}"#,
+        &user_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let number = r#"{"destination":"+1 (555) 123-4567"}"#;
+    let (status, _) = send(&app, Method::POST, start, number, &user_keys).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(sender.messages.lock().expect("lock")[0].0, "+15551234567");
+    let (status, _) = send(&app, Method::POST, start, number, &user_keys).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    let code = sender.last_code();
+    let wrong = if code == "000000" { "111111" } else { "000000" };
+    let confirm = "/me/verifications/phone/confirm";
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        confirm,
+        &format!(r#"{{"code":"{wrong}"}}"#),
+        &user_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, verified) = send(
+        &app,
+        Method::POST,
+        confirm,
+        &format!(r#"{{"code":"{code}"}}"#),
+        &user_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(verified["method"], "phone");
+
+    let (_, mine) = send(&app, Method::GET, "/me/verifications", "", &user_keys).await;
+    assert_eq!(mine["level"], "phone");
+
+    // The same number cannot verify a second account
+    let (status, _) = send(&app, Method::POST, start, number, &other_keys).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let code = sender.last_code();
+    let (status, _) = send(
+        &app,
+        Method::POST,
+        confirm,
+        &format!(r#"{{"code":"{code}"}}"#),
+        &other_keys,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // In-person attestation needs an in-person, ID-verified or moderator attester
+    let attest = format!("/accounts/{other_id}/verifications/in-person");
+    let (status, _) = send(&app, Method::POST, &attest, "", &user_keys).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, attested) = send(&app, Method::POST, &attest, "", &mod_keys).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(attested["verified"], true);
+    let (status, _) = send(&app, Method::POST, &attest, "", &mod_keys).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Government ID via the signed webhook
+    let body = format!(r#"{{"account_id":"{user_id}","status":"verified","reference":"p-1"}}"#);
+    assert_eq!(
+        post_webhook(&app, &body, None).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        post_webhook(&app, &body, Some(&sign("{}"))).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        post_webhook(&app, &body, Some(&sign(&body))).await,
+        StatusCode::OK
+    );
+
+    let path = format!("/accounts/{user_id}/verifications");
+    let (_, theirs) = send(&app, Method::GET, &path, "", &other_keys).await;
+    assert_eq!(theirs["level"], "government_id");
+    let methods: Vec<&str> = theirs["verifications"]
+        .as_array()
+        .expect("verifications")
+        .iter()
+        .filter_map(|v| v["method"].as_str())
+        .collect();
+    assert_eq!(methods, ["phone", "government_id"]);
+    // Only the owner and moderators see who verified the account and when
+    assert!(theirs["verifications"][1].get("provider").is_none());
+    assert!(theirs["verifications"][1].get("verified_at").is_none());
+    let (_, moderated) = send(&app, Method::GET, &path, "", &mod_keys).await;
+    assert!(moderated["verifications"][1]["provider"].is_string());
+
+    let explain = format!("/reputation/{user_id}/explanation");
+    let (_, explanation) = send(&app, Method::GET, &explain, "", &user_keys).await;
+    let sources: Vec<&str> = explanation["components"]
+        .as_array()
+        .expect("components")
+        .iter()
+        .filter_map(|c| c["source"].as_str())
+        .filter(|source| source.starts_with("verification:"))
+        .collect();
+    assert_eq!(
+        sources,
+        ["verification:phone", "verification:government_id"]
+    );
+}
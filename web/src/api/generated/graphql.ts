@@ -43,6 +43,24 @@ export type BuildInfo = {
   version: Scalars['String']['output'];
 };
 
+export type DailyStats = {
+  /** Non-revoked devices used within the 30 days ending on `date`. */
+  activeDevices: Scalars['Int']['output'];
+  /** Distinct (poll, voter) pairs with a vote cast or updated that day. */
+  ballotsCast: Scalars['Int']['output'];
+  /** UTC calendar day (`YYYY-MM-DD`). */
+  date: Scalars['String']['output'];
+  endorsementsByTopic: Array<TopicEndorsementCount>;
+  signups: Scalars['Int']['output'];
+  /** Distinct accounts that voted that day. */
+  voters: Scalars['Int']['output'];
+};
+
+export type InstanceStatsResponse = {
+  /** Completed days, newest first. Today is never included. */
+  days: Array<DailyStats>;
+};
+
 export type MutationRoot = {
   /**
    * Placeholder mutation - returns the input string
@@ -60,6 +78,25 @@ export type MutationRootEchoArgs = {
 export type QueryRoot = {
   /** Returns build metadata for the running service */
   buildInfo: BuildInfo;
+  /**
+   * Daily instance-level statistics for public dashboards, newest first.
+   *
+   * `days` defaults to 30 and may not exceed 366. Counts carry
+   * differential-privacy noise when `privacy.enabled` is set. Costs 10
+   * complexity points plus its selections, as it aggregates every day.
+   */
+  instanceStats: InstanceStatsResponse;
+  /** Number of unread notifications for the signed caller. */
+  notificationUnreadCount: Scalars['Int']['output'];
+};
+
+export type QueryRootInstanceStatsArgs = {
+  days?: InputMaybe<Scalars['Int']['input']>;
+};
+
+export type TopicEndorsementCount = {
+  endorsements: Scalars['Int']['output'];
+  topic: Scalars['String']['output'];
 };
 
 type Properties<T> = Required<{
@@ -82,6 +119,29 @@ export const MutationRootEchoArgsSchema: z.ZodObject<Properties<MutationRootEcho
   message: z.string(),
 });
 
+export const TopicEndorsementCountSchema: z.ZodObject<Properties<TopicEndorsementCount>> =
+  z.object({
+    __typename: z.literal('TopicEndorsementCount').optional(),
+    endorsements: z.number(),
+    topic: z.string(),
+  });
+
+export const DailyStatsSchema: z.ZodObject<Properties<DailyStats>> = z.object({
+  __typename: z.literal('DailyStats').optional(),
+  activeDevices: z.number(),
+  ballotsCast: z.number(),
+  date: z.string(),
+  endorsementsByTopic: z.array(z.lazy(() => TopicEndorsementCountSchema)),
+  signups: z.number(),
+  voters: z.number(),
+});
+
+export const InstanceStatsResponseSchema: z.ZodObject<Properties<InstanceStatsResponse>> =
+  z.object({
+    __typename: z.literal('InstanceStatsResponse').optional(),
+    days: z.array(z.lazy(() => DailyStatsSchema)),
+  });
+
 export const BuildInfoSchema: z.ZodObject<Properties<BuildInfo>> = z.object({
   __typename: z.literal('BuildInfo').optional(),
   buildTime: z.string(),
@@ -93,4 +153,11 @@ export const BuildInfoSchema: z.ZodObject<Properties<BuildInfo>> = z.object({
 export const QueryRootSchema: z.ZodObject<Properties<QueryRoot>> = z.object({
   __typename: z.literal('QueryRoot').optional(),
   buildInfo: z.lazy(() => BuildInfoSchema),
+  instanceStats: z.lazy(() => InstanceStatsResponseSchema),
+  notificationUnreadCount: z.number(),
 });
+
+export const QueryRootInstanceStatsArgsSchema: z.ZodObject<Properties<QueryRootInstanceStatsArgs>> =
+  z.object({
+    days: z.number().nullish(),
+  });
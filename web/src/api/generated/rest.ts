@@ -4,6 +4,23 @@
  */
 
 export interface paths {
+  '/.well-known/tinycongress.json': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /** Describe this instance: name, operator, endpoints, and policies. */
+    get: operations['get_instance_document'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
   '/accounts/lookup': {
     parameters: {
       query?: never;
@@ -12,8 +29,10 @@ export interface paths {
       cookie?: never;
     };
     /**
-     * Look up an account by username.
-     * @description Returns `{ id, username }` so the caller can use the UUID for trust actions.
+     * Look up an account by username or alias.
+     * @description Returns `{ id, username, alias }` so the caller can use the UUID for trust
+     *     actions. `username` is always canonical; `alias` is set when the handle
+     *     was an alias, so clients can switch to the canonical name.
      *     Requires authentication — callers must have a valid device session.
      */
     get: operations['account_lookup'];
@@ -25,7 +44,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/auth/backup/{username}': {
+  '/accounts/{account_id}': {
     parameters: {
       query?: never;
       header?: never;
@@ -33,15 +52,12 @@ export interface paths {
       cookie?: never;
     };
     /**
-     * GET /auth/backup/{username} -- fetch encrypted backup for login.
-     * @description Returns 200 with an encrypted backup envelope for both real and unknown
-     *     usernames. Unknown usernames receive a deterministic synthetic backup
-     *     to prevent username enumeration.
-     *
-     *     To mitigate timing side-channels, both the account lookup and backup
-     *     lookup are always performed regardless of whether the account exists.
+     * GET `/accounts/{account_id}` — an account's profile
+     * @description Open to anyone. A signed request reveals more: other members see the
+     *     account's devices, moderators also see device names, and the owner sees
+     *     everything.
      */
-    get: operations['get_backup'];
+    get: operations['get_account_profile'];
     put?: never;
     post?: never;
     delete?: never;
@@ -50,25 +66,29 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/auth/devices': {
+  '/accounts/{account_id}/crl': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    /** GET /auth/devices — list all devices for the authenticated account */
-    get: operations['list_devices'];
+    /**
+     * GET `/accounts/{account_id}/crl` — an account's device revocation list
+     * @description Open to anyone. Each signed entry can be checked offline against the
+     *     account's root key with [`crate::identity::crl::verify_crl`]. Served with
+     *     `Cache-Control: no-cache` so caches revalidate after a revocation.
+     */
+    get: operations['get_account_crl'];
     put?: never;
-    /** POST /auth/devices — add a new device key */
-    post: operations['add_device'];
+    post?: never;
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/auth/devices/{kid}': {
+  '/accounts/{account_id}/status': {
     parameters: {
       query?: never;
       header?: never;
@@ -76,34 +96,37 @@ export interface paths {
       cookie?: never;
     };
     get?: never;
-    put?: never;
+    /** PUT `/accounts/{account_id}/status` — change another account's status (admins only) */
+    put: operations['change_account_status_as_admin'];
     post?: never;
-    /** DELETE /auth/devices/:kid — revoke a device key */
-    delete: operations['revoke_device'];
+    delete?: never;
     options?: never;
     head?: never;
-    /** PATCH /auth/devices/:kid — rename a device */
-    patch: operations['rename_device'];
+    patch?: never;
     trace?: never;
   };
-  '/auth/idme/authorize': {
+  '/accounts/{account_id}/strikes': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    /** Generate the ID.me authorization URL and return it. */
-    get: operations['authorize'];
+    get?: never;
     put?: never;
-    post?: never;
+    /**
+     * Strike an account (account moderators only).
+     * @description The reason sets the penalty and, unless `expires_in_days` is given, how
+     *     long it takes to decay.
+     */
+    post: operations['issue_strike'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/auth/idme/callback': {
+  '/accounts/{account_id}/verifications': {
     parameters: {
       query?: never;
       header?: never;
@@ -111,12 +134,11 @@ export interface paths {
       cookie?: never;
     };
     /**
-     * OAuth callback from ID.me (browser redirect, unauthenticated).
-     * @description The `account_id` is embedded in the HMAC-signed state parameter.
-     *     On success, redirects to the frontend with `verification=success`.
-     *     On failure, redirects with `verification=error&message=...`.
+     * List an account's verifications, as shown on its badge.
+     * @description Providers and dates are shown only to the account itself and to
+     *     moderators; see [`crate::visibility`].
      */
-    get: operations['callback'];
+    get: operations['account_verifications'];
     put?: never;
     post?: never;
     delete?: never;
@@ -125,7 +147,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/auth/login': {
+  '/accounts/{account_id}/verifications/in-person': {
     parameters: {
       query?: never;
       header?: never;
@@ -134,113 +156,136 @@ export interface paths {
     };
     get?: never;
     put?: never;
-    /** POST /auth/login -- authenticate and register a device key */
-    post: operations['login'];
+    /**
+     * Attest to having met an account in person.
+     * @description Only accounts verified in person or by government ID, and account
+     *     moderators, may attest. The subject is verified in person once
+     *     `verification.in_person_attestations_required` accounts have attested.
+     */
+    post: operations['attest_in_person'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/auth/signup': {
+  '/api/v1/verify': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /**
+     * Verify that a device of some account signed a third-party challenge.
+     * @description Returns 200 with `verified: false` when the KID exists but the signature,
+     *     device status, or delegation does not check out, so widgets can explain
+     *     why verification failed.
+     */
+    get: operations['verify'];
     put?: never;
-    /** Handle signup request — delegates validation and persistence to [`IdentityService`]. */
-    post: operations['signup'];
+    post?: never;
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/build-info': {
+  '/auth/account/status': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
+    get?: never;
+    put?: never;
     /**
-     * Get build information
-     * @description Returns metadata about the running service including version, git SHA, and build time.
-     *
-     *     # Errors
-     *
-     *     Returns `ProblemDetails` on internal server errors.
+     * POST /auth/account/status — lock or delete the caller's own account
+     * @description A locked account can only be restored by an account admin.
      */
-    get: operations['get_build_info'];
-    put?: never;
-    post?: never;
+    post: operations['change_own_status'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/endorsements/check': {
+  '/auth/accounts/me': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    /** Check if a subject has an endorsement for a topic (public endpoint). */
-    get: operations['check_endorsement'];
+    get?: never;
     put?: never;
     post?: never;
-    delete?: never;
+    /**
+     * DELETE /auth/accounts/me — deactivate the caller's account
+     * @description Every device is revoked at once and the account is purged after
+     *     `account_deletion.grace_days`, unless it is undeleted first.
+     */
+    delete: operations['delete_account'];
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/me/endorsements': {
+  '/auth/accounts/me/username': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    /** List endorsements for the authenticated user. */
-    get: operations['my_endorsements'];
+    get?: never;
     put?: never;
     post?: never;
     delete?: never;
     options?: never;
     head?: never;
-    patch?: never;
+    /** PATCH /auth/accounts/me/username — rename the authenticated account */
+    patch: operations['change_username'];
     trace?: never;
   };
-  '/rooms': {
+  '/auth/accounts/undelete': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['list_rooms'];
+    get?: never;
     put?: never;
-    post: operations['create_room'];
+    /**
+     * POST /auth/accounts/undelete — restore a deactivated account
+     * @description Signed by the root key alone. Devices revoked by the deletion stay
+     *     revoked; log in again to enroll one.
+     */
+    post: operations['undelete_account'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/capacity': {
+  '/auth/accounts/{account_id}/sigchain': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_capacity'];
+    /**
+     * GET `/auth/accounts/{account_id}/sigchain` — the account's signed event
+     *     history
+     * @description Only the account's own devices may read it. Walk the chain by passing
+     *     each page's `next_since_seqno` back; every link commits to the previous
+     *     one, so [`crate::identity::sigchain::verify_links`] can check a page
+     *     against the last link of the page before it.
+     */
+    get: operations['list_sigchain'];
     put?: never;
     post?: never;
     delete?: never;
@@ -249,14 +294,20 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}': {
+  '/auth/accounts/{account_id}/sigchain/head': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_room'];
+    /**
+     * GET `/auth/accounts/{account_id}/sigchain/head` — the latest link's hash
+     * @description Clients compare it with the last hash they verified to tell whether the
+     *     chain has grown, or was rewritten, since. Served with
+     *     `Cache-Control: no-store`.
+     */
+    get: operations['get_sigchain_head'];
     put?: never;
     post?: never;
     delete?: never;
@@ -265,62 +316,107 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/agenda': {
+  '/auth/aliases': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_agenda'];
+    /** GET /auth/aliases — list aliases of the authenticated account */
+    get: operations['list_aliases'];
     put?: never;
-    post?: never;
+    /** POST /auth/aliases — add an alias to the authenticated account */
+    post: operations['create_alias'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/my-capabilities': {
+  '/auth/aliases/{alias}': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['my_capabilities'];
+    get?: never;
     put?: never;
     post?: never;
-    delete?: never;
+    /** DELETE /auth/aliases/:alias — remove an alias */
+    delete: operations['delete_alias'];
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls': {
+  '/auth/backup/{username}': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['list_polls'];
+    /**
+     * GET /auth/backup/{username} -- fetch encrypted backup for login.
+     * @description Returns 200 with an encrypted backup envelope for both real and unknown
+     *     usernames. Unknown usernames receive a deterministic synthetic backup
+     *     to prevent username enumeration.
+     *
+     *     If the owner set a recovery code, the request must present it in
+     *     `X-Recovery-Code`; otherwise it gets the synthetic backup too.
+     *
+     *     To mitigate timing side-channels, both the account lookup and backup
+     *     lookup are always performed regardless of whether the account exists.
+     */
+    get: operations['get_backup'];
     put?: never;
-    post: operations['create_poll'];
+    post?: never;
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}': {
+  '/auth/backups/recovery-code': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_poll_detail'];
+    get?: never;
+    /**
+     * PUT /auth/backups/recovery-code -- require (or stop requiring) a recovery
+     *     code to fetch the account's backup by root KID
+     * @description Signed by a device and, in the body, by the root key over
+     *     [`recovery_code_message`].
+     */
+    put: operations['set_recovery_code'];
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/auth/backups/{root_kid}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /**
+     * GET `/auth/backups/{root_kid}` -- fetch a backup to recover an account.
+     * @description Returns the envelope together with its salt and Argon2id parameters. If
+     *     the owner set a recovery code, the request must present it in
+     *     `X-Recovery-Code`. Unknown KIDs, KIDs without a backup, and missing or
+     *     wrong codes all get a deterministic synthetic backup, exactly like
+     *     `GET /auth/backup/{username}`.
+     */
+    get: operations['get_recovery_backup'];
     put?: never;
     post?: never;
     delete?: never;
@@ -329,39 +425,54 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/dimensions': {
+  '/auth/challenge': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /**
+     * Issue a single-use challenge for the next signed request from a device.
+     * @description Unauthenticated and rate-limited like login. Challenges are only stored
+     *     for registered devices, so unknown KIDs cannot grow the table.
+     */
+    get: operations['get_challenge'];
     put?: never;
-    post: operations['add_dimension'];
+    post?: never;
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/dimensions/{dimension_id}/evidence': {
+  '/auth/devices': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /**
+     * GET /auth/devices — list all devices for the authenticated account
+     * @description Revoked devices are included by default, with the revoking device's KID,
+     *     so the full device history can be audited; pass `include_revoked=false`
+     *     for active devices only. Each device carries its request counts for the
+     *     last 24 hours and 7 days and the last route it called, so dormant devices
+     *     stand out. Also accepts an API token with the
+     *     `devices:read` scope.
+     */
+    get: operations['list_devices'];
     put?: never;
-    post: operations['create_evidence'];
+    /** POST /auth/devices — add a new device key */
+    post: operations['add_device'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/evidence': {
+  '/auth/devices/{kid}': {
     parameters: {
       query?: never;
       header?: never;
@@ -371,20 +482,34 @@ export interface paths {
     get?: never;
     put?: never;
     post?: never;
-    delete: operations['delete_evidence'];
+    /**
+     * DELETE /auth/devices/:kid — revoke a device key
+     * @description A device added recently must present a confirmed `revoke_device`
+     *     step-up challenge if the account has enrolled a phone; see
+     *     [`crate::identity::step_up`].
+     */
+    delete: operations['revoke_device'];
     options?: never;
     head?: never;
-    patch?: never;
+    /** PATCH /auth/devices/:kid — rename a device */
+    patch: operations['rename_device'];
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/my-votes': {
+  '/auth/devices/{kid}/delegation': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['my_votes'];
+    /**
+     * GET /auth/devices/:kid/delegation — inspect a device's root delegation
+     * @description Returns the stored certificate, whether it still verifies against the
+     *     account's current root key, and the scopes the device holds. Works for
+     *     revoked devices too, so a client can see why a device is being refused.
+     *     Also accepts an API token with the `devices:read` scope.
+     */
+    get: operations['get_device_delegation'];
     put?: never;
     post?: never;
     delete?: never;
@@ -393,7 +518,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/reset': {
+  '/auth/devices/{kid}/rotate': {
     parameters: {
       query?: never;
       header?: never;
@@ -402,25 +527,33 @@ export interface paths {
     };
     get?: never;
     put?: never;
-    post?: never;
+    /**
+     * POST /auth/devices/:kid/rotate — replace this device's key pair
+     * @description Signed by the device being rotated. The new key must be certified by the
+     *     root key and cross-signed by the current device key. The old KID is
+     *     revoked and the new one registered under the same name, linked to it, in
+     *     one transaction; the rotation request becomes the old key's revocation
+     *     record in the CRL. The new key inherits the capabilities the old one
+     *     declared, so a device that declared `cert-v2` must certify its new key
+     *     with a version 2 certificate, and one that declared `sig-v2` cannot fall
+     *     back to v1 signatures by rotating.
+     */
+    post: operations['rotate_device'];
     delete?: never;
     options?: never;
     head?: never;
-    /**
-     * Sim-only: reset a poll back to draft status, clearing all timing fields.
-     *     Used by the ring buffer refill logic to recycle polls for a new cycle.
-     */
-    patch: operations['reset_poll'];
+    patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/results': {
+  '/auth/idme/authorize': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_results'];
+    /** Generate the ID.me authorization URL and return it. */
+    get: operations['authorize'];
     put?: never;
     post?: never;
     delete?: never;
@@ -429,14 +562,20 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/results/distribution': {
+  '/auth/idme/callback': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_distribution'];
+    /**
+     * OAuth callback from ID.me (browser redirect, unauthenticated).
+     * @description The `account_id` is embedded in the HMAC-signed state parameter.
+     *     On success, redirects to the frontend with `verification=success`.
+     *     On failure, redirects with `verification=error&message=...`.
+     */
+    get: operations['callback'];
     put?: never;
     post?: never;
     delete?: never;
@@ -445,7 +584,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/status': {
+  '/auth/login': {
     parameters: {
       query?: never;
       header?: never;
@@ -454,21 +593,23 @@ export interface paths {
     };
     get?: never;
     put?: never;
-    post: operations['update_poll_status'];
+    /** POST /auth/login -- authenticate and register a device key */
+    post: operations['login'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/traces': {
+  '/auth/released-usernames': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['get_poll_traces'];
+    /** GET /auth/released-usernames — list quarantined names (account admins only) */
+    get: operations['list_released_usernames'];
     put?: never;
     post?: never;
     delete?: never;
@@ -477,7 +618,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/polls/{poll_id}/vote': {
+  '/auth/released-usernames/{name}': {
     parameters: {
       query?: never;
       header?: never;
@@ -486,53 +627,58 @@ export interface paths {
     };
     get?: never;
     put?: never;
-    post: operations['cast_vote'];
-    delete?: never;
+    post?: never;
+    /** DELETE /auth/released-usernames/:name — end a name's quarantine (account admins only) */
+    delete: operations['lift_username_quarantine'];
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/roles': {
+  '/auth/reserved-usernames': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /** GET /auth/reserved-usernames — list reserved names (account admins only) */
+    get: operations['list_reserved_usernames'];
     put?: never;
-    post: operations['assign_role'];
+    /** POST /auth/reserved-usernames — reserve a name (account admins only) */
+    post: operations['reserve_username'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/rooms/{room_id}/suggestions': {
+  '/auth/reserved-usernames/{name}': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['list_suggestions'];
+    get?: never;
     put?: never;
-    post: operations['create_suggestion'];
-    delete?: never;
+    post?: never;
+    /** DELETE /auth/reserved-usernames/:name — release a reserved name (account admins only) */
+    delete: operations['release_username'];
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/budget': {
+  '/auth/security/checkup': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['budget_handler'];
+    /** GET /auth/security/checkup — summarize the account's security posture */
+    get: operations['security_checkup'];
     put?: never;
     post?: never;
     delete?: never;
@@ -541,7 +687,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/trust/denounce': {
+  '/auth/sessions': {
     parameters: {
       query?: never;
       header?: never;
@@ -550,46 +696,55 @@ export interface paths {
     };
     get?: never;
     put?: never;
-    post: operations['denounce_handler'];
-    delete?: never;
+    /**
+     * Issue a session token to the signing device.
+     * @description The signed request is the challenge: its timestamp and nonce make it
+     *     single-use, so a captured request cannot mint further sessions.
+     */
+    post: operations['create_session'];
+    /** Revoke every session issued to the signing device (log out). */
+    delete: operations['revoke_sessions'];
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/denouncements/mine': {
+  '/auth/signup': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['list_my_denouncements_handler'];
+    get?: never;
     put?: never;
-    post?: never;
+    /** Handle signup request — delegates validation and persistence to [`IdentityService`]. */
+    post: operations['signup'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/endorse': {
+  '/auth/signup-invites': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /** GET /auth/signup-invites — list invites issued by the authenticated account */
+    get: operations['list_signup_invites'];
     put?: never;
-    post: operations['endorse_handler'];
+    /** POST /auth/signup-invites — issue a signup invite code */
+    post: operations['create_signup_invite'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/invites': {
+  '/auth/step-up/challenges': {
     parameters: {
       query?: never;
       header?: never;
@@ -598,69 +753,95 @@ export interface paths {
     };
     get?: never;
     put?: never;
-    post: operations['create_invite_handler'];
+    /**
+     * POST /auth/step-up/challenges — text a code authorizing an action
+     * @description Used after a request is refused with `STEP_UP_REQUIRED`. The challenge
+     *     can only be confirmed and used by the device that created it.
+     */
+    post: operations['create_step_up_challenge'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/invites/mine': {
+  '/auth/step-up/challenges/{challenge_id}/confirm': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['list_invites_handler'];
+    get?: never;
     put?: never;
-    post?: never;
+    /**
+     * POST /auth/step-up/challenges/{challenge_id}/confirm — confirm a texted code
+     * @description Confirming an `enroll_phone` challenge enrolls the phone. Any other
+     *     confirmed challenge is passed in `X-Step-Up-Challenge` when retrying
+     *     the action, within `step_up.confirmation_ttl_secs`.
+     */
+    post: operations['confirm_step_up_challenge'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/invites/{id}/accept': {
+  '/auth/step-up/phone': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /** GET /auth/step-up/phone — the phone enrolled for step-up codes */
+    get: operations['get_step_up_phone'];
     put?: never;
-    post: operations['accept_invite_handler'];
-    delete?: never;
+    /**
+     * POST /auth/step-up/phone — text a code to enroll a phone
+     * @description Confirm the code with `POST /auth/step-up/challenges/{challenge_id}/confirm`
+     *     to finish enrolling. Only one phone may be enrolled; remove it first to
+     *     change numbers.
+     */
+    post: operations['enroll_step_up_phone'];
+    /**
+     * DELETE /auth/step-up/phone — stop requiring step-up codes
+     * @description Always requires a confirmed `remove_phone` challenge in the
+     *     `X-Step-Up-Challenge` header.
+     */
+    delete: operations['remove_step_up_phone'];
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/revoke': {
+  '/auth/tokens': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get?: never;
+    /** GET /auth/tokens — list API tokens for the authenticated account */
+    get: operations['list_tokens'];
     put?: never;
-    post: operations['revoke_handler'];
+    /** POST /auth/tokens — issue a new API token */
+    post: operations['create_token'];
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-  '/trust/scores/me': {
+  '/auth/tokens/usage': {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    get: operations['scores_me_handler'];
+    /** GET /auth/tokens/usage — busiest API tokens across all accounts (admins only) */
+    get: operations['top_token_consumers'];
     put?: never;
     post?: never;
     delete?: never;
@@ -669,7 +850,7 @@ export interface paths {
     patch?: never;
     trace?: never;
   };
-  '/verifiers/endorsements': {
+  '/auth/tokens/{id}': {
     parameters: {
       query?: never;
       header?: never;
@@ -678,544 +859,7343 @@ export interface paths {
     };
     get?: never;
     put?: never;
+    post?: never;
+    /** DELETE /auth/tokens/:id — revoke an API token */
+    delete: operations['revoke_token'];
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/auth/usernames/{name}/availability': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /** GET /auth/usernames/:name/availability — whether a new account could take a name */
+    get: operations['check_username_availability'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/badges/{file}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
     /**
-     * Create an endorsement as an authorized verifier.
-     * @description Checks that the authenticated caller has the `authorized_verifier` endorsement,
-     *     resolves the target username to an account, and creates the endorsement.
+     * Public reputation badge for an account.
+     * @description The path segment is `{username}.svg` or `{username}.json`. An account
+     *     alias redirects (301) to the badge for the canonical username. Responses
+     *     carry `Cache-Control: public` and an `ETag`; a matching `If-None-Match`
+     *     returns 304 without a body. When privacy noise is on, a request signed by
+     *     the subject or an admin gets exact counts, cached privately.
+     */
+    get: operations['get_badge'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/build-info': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /**
+     * Get build information
+     * @description Returns metadata about the running service including version, git SHA, and build time.
      *
      *     # Errors
      *
-     *     Returns an error response for unauthorized, forbidden, not-found, or internal errors.
+     *     Returns `ProblemDetails` on internal server errors.
      */
-    post: operations['create_endorsement_as_verifier'];
+    get: operations['get_build_info'];
+    put?: never;
+    post?: never;
     delete?: never;
     options?: never;
     head?: never;
     patch?: never;
     trace?: never;
   };
-}
-export type webhooks = Record<string, never>;
-export interface components {
-  schemas: {
-    AcceptInviteResponse: {
-      accepted_at: string;
-      /** Format: uuid */
-      endorser_id: string;
-    };
-    /** @description Account lookup response — returns only what the UI needs to target a user. */
-    AccountLookupResponse: {
-      /** Format: uuid */
-      id: string;
-      username: string;
-    };
-    AddDeviceRequest: {
-      /** @description Base64url-encoded certificate (root key's signature over device pubkey) */
-      certificate: string;
-      name: string;
-      /** @description Base64url-encoded Ed25519 public key */
-      pubkey: string;
+  '/changelog': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    AddDeviceResponse: {
-      created_at: string;
-      device_kid: string;
+    /**
+     * List API-visible behavior changes, optionally only those newer than the
+     *     caller's revision.
+     */
+    get: operations['get_changelog'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/developer/clients': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    AssignRoleRequest: {
-      /** Format: uuid */
-      account_id: string;
-      role: string;
+    /** List the caller's OAuth clients. Secrets are never returned. */
+    get: operations['list_clients'];
+    put?: never;
+    /** Register an OAuth client. The secret is returned only in this response. */
+    post: operations['create_client'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/developer/clients/{client_id}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    AssignRoleResponse: {
-      /** Format: uuid */
-      account_id: string;
-      role: string;
-      /** Format: uuid */
-      room_id: string;
+    get?: never;
+    put?: never;
+    post?: never;
+    /**
+     * Revoke a client. Both its current and previous secrets stop working, and
+     *     every access token issued to it is revoked.
+     */
+    delete: operations['revoke_client'];
+    options?: never;
+    head?: never;
+    /** Change a client's name, redirect URIs or scopes. */
+    patch: operations['update_client'];
+    trace?: never;
+  };
+  '/developer/clients/{client_id}/secret': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    AuthorizeResponse: {
-      url: string;
+    get?: never;
+    put?: never;
+    /**
+     * Issue a new client secret. The previous secret keeps working for a grace
+     *     period so deployed clients can switch over.
+     */
+    post: operations['rotate_client_secret'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/endorsements/check': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    BackupResponse: {
-      /** @description Base64url-encoded encrypted backup envelope */
-      encrypted_backup: string;
-      root_kid: string;
+    /** Check if a subject has an endorsement for a topic (public endpoint). */
+    get: operations['check_endorsement'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/endorsements/disputes': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    BotTraceResponse: {
-      completed_at?: string | null;
-      created_at: string;
-      error?: string | null;
-      id: string;
-      run_mode: string;
-      status: string;
-      steps: unknown[];
-      task: string;
-      /** Format: double */
-      total_cost_usd: number;
+    /** List open disputes awaiting review (account moderators only). */
+    get: operations['list_open_disputes'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/endorsements/{endorsement_id}/dispute': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    BucketResponse: {
-      /** Format: int64 */
-      count: number;
-      label: string;
+    get?: never;
+    put?: never;
+    /**
+     * Dispute an endorsement of the authenticated account.
+     * @description The signed request is stored with the dispute and the issuer is notified.
+     */
+    post: operations['dispute_endorsement'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/endorsements/{endorsement_id}/dispute/resolve': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    BudgetResponse: {
-      /** Format: int64 */
-      denouncements_available: number;
-      /** Format: int32 */
-      denouncements_total: number;
-      /** Format: int64 */
-      denouncements_used: number;
-      /** Format: int64 */
-      out_of_slot_count: number;
-      /** Format: int64 */
-      slots_available: number;
-      /** Format: int32 */
-      slots_total: number;
-      /** Format: int64 */
-      slots_used: number;
+    get?: never;
+    put?: never;
+    /**
+     * Uphold or dismiss the open dispute on an endorsement (account moderators
+     *     only).
+     * @description Upholding revokes the endorsement. Either outcome returns it to public
+     *     aggregates if it was paused.
+     */
+    post: operations['resolve_dispute'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/feed': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
     /**
-     * @description Build metadata exposed via GraphQL, REST, and logs.
-     *
-     *     Loaded from environment variables at startup (see [`BuildInfo::from_env`]).
-     *     These are typically set by the CI pipeline or Dockerfile at image build time.
+     * The caller's feed: recent endorsements and newly opened polls, ranked by
+     *     trust closeness, followed topics, and recency.
      */
-    BuildInfo: {
-      /**
-       * @description Build timestamp in RFC 3339 format. Read from `BUILD_TIME` env var.
-       *     Defaults to `"unknown"`.
-       */
-      buildTime: string;
-      /** @description Git commit SHA. Read from `GIT_SHA` env var. Defaults to `"unknown"`. */
-      gitSha: string;
-      /** @description Optional build message (e.g., CI run URL). Read from `BUILD_MESSAGE` env var. */
-      message?: string | null;
-      /**
-       * @description Application version string. Read from `APP_VERSION` or `VERSION` env var.
-       *     Defaults to `"dev"`.
-       */
-      version: string;
-    };
-    /** @description OAuth callback query parameters from ID.me. */
-    CallbackQuery: {
-      code?: string | null;
-      error?: string | null;
-      error_description?: string | null;
-      state?: string | null;
-    };
-    CreateDimensionRequest: {
-      description?: string | null;
-      max_label?: string | null;
-      /** Format: float */
-      max_value?: number;
-      min_label?: string | null;
-      /** Format: float */
-      min_value?: number;
-      name: string;
-      /** Format: int32 */
-      sort_order?: number;
+    get: operations['get_feed'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/integrity/alerts': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreateEndorsementRequest: {
-      evidence?: unknown;
-      topic: string;
-      username: string;
+    /**
+     * List records the background integrity sweep found failing, open ones
+     *     only unless `include_resolved` is set.
+     */
+    get: operations['list_alerts'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/integrity/alerts/{id}/resolve': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreateEvidenceBody: {
-      evidence: components['schemas']['EvidenceItem'][];
+    get?: never;
+    put?: never;
+    /**
+     * Mark an alert resolved once the record has been repaired or explained.
+     *     The next sweep reopens it if the record still fails.
+     */
+    post: operations['resolve_alert'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/me/endorsements': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreateInviteRequest: {
-      attestation: unknown;
-      delivery_method: string;
-      /** @description base64url-encoded invite envelope bytes */
-      envelope: string;
-      relationship_depth?: string | null;
-      /** Format: float */
-      weight?: number | null;
+    /**
+     * List endorsements for the authenticated user.
+     * @description Without query parameters every endorsement is returned. Passing `limit`,
+     *     `cursor` or `as_of` switches to keyset pagination over a fixed snapshot.
+     */
+    get: operations['my_endorsements'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/me/strikes': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreateInviteResponse: {
-      expires_at: string;
-      /** Format: uuid */
-      id: string;
+    /** List strikes against the authenticated account, with their history. */
+    get: operations['my_strikes'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/me/strikes/{strike_id}/appeal': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreatePollRequest: {
-      description?: string | null;
-      question: string;
+    get?: never;
+    put?: never;
+    /**
+     * Appeal a strike against the authenticated account.
+     * @description Each strike can be appealed once, before it expires. The penalty still
+     *     applies while the appeal is pending.
+     */
+    post: operations['appeal_strike'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/me/verifications': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreateRoomRequest: {
-      constraint_config?: unknown;
-      constraint_type?: string;
-      description?: string | null;
-      eligibility_topic?: string;
-      engine_config?: unknown;
-      engine_type?: string;
-      name: string;
-      /** Format: int32 */
-      poll_duration_secs?: number | null;
+    /** List the authenticated account's verifications. */
+    get: operations['my_verifications'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/me/verifications/{method}/confirm': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreateSuggestionRequest: {
-      suggestion_text: string;
+    get?: never;
+    put?: never;
+    /** Confirm a code sent by `POST /me/verifications/{method}/start`. */
+    post: operations['confirm_verification'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/me/verifications/{method}/start': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    CreatedEndorsementResponse: {
-      created_at: string;
-      /** Format: uuid */
-      id: string;
-      /** Format: uuid */
-      issuer_id: string;
-      /** Format: uuid */
-      subject_id: string;
-      topic: string;
+    get?: never;
+    put?: never;
+    /**
+     * Send a one-time code to an email address or phone number.
+     * @description Requesting a new code replaces the previous one. Codes can be
+     *     re-requested after `verification.resend_cooldown_secs`.
+     */
+    post: operations['start_verification'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/notifications': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DenounceRequest: {
-      reason: string;
-      /** Format: uuid */
-      target_id: string;
+    /** List the caller's notifications with keyset pagination. */
+    get: operations['list_notifications'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/notifications/preferences': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DenouncementResponse: {
-      created_at: string;
-      /** Format: uuid */
-      id: string;
-      reason: string;
-      /** Format: uuid */
-      target_id: string;
-      target_username: string;
+    /** Per-category notification settings for the caller. */
+    get: operations['get_preferences'];
+    /**
+     * Enable or disable notification categories for the caller.
+     * @description Disabling a category stops new notifications of that category; existing
+     *     ones stay in the inbox.
+     */
+    put: operations['update_preferences'];
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/notifications/{id}/read': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    /** @description Device info returned in API responses (omits certificate and raw pubkey) */
-    DeviceInfo: {
-      created_at: string;
-      device_kid: string;
-      device_name: string;
-      last_used_at?: string | null;
-      revoked_at?: string | null;
+    get?: never;
+    put?: never;
+    /** Mark one of the caller's notifications as read. Idempotent. */
+    post: operations['mark_read'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/oauth/authorize': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DeviceListResponse: {
-      devices: components['schemas']['DeviceInfo'][];
+    get?: never;
+    put?: never;
+    /**
+     * Approve a client on behalf of the signed-in user and issue an
+     *     authorization code.
+     * @description The web client shows its consent screen, then calls this with the
+     *     parameters the third-party app put in its authorization link and sends
+     *     the user to `redirect_to`.
+     */
+    post: operations['authorize'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/oauth/token': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DimensionDetailResponse: {
-      description?: string | null;
-      evidence: components['schemas']['EvidenceResponse'][];
-      /** Format: uuid */
-      id: string;
-      max_label?: string | null;
-      /** Format: float */
-      max_value: number;
-      min_label?: string | null;
-      /** Format: float */
-      min_value: number;
-      name: string;
-      /** Format: int32 */
-      sort_order: number;
+    get?: never;
+    put?: never;
+    /**
+     * Exchange an authorization code for an access token.
+     * @description Clients authenticate with `client_id` and `client_secret` in the form
+     *     body and prove possession of the PKCE verifier. Each code works once.
+     */
+    post: operations['token'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/reputation/{account_id}/explanation': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DimensionDistributionResponse: {
-      buckets: components['schemas']['BucketResponse'][];
-      /** Format: uuid */
-      dimension_id: string;
-      dimension_name: string;
+    /**
+     * Itemize the aggregates, trust signals and penalties behind an account's
+     *     reputation score and tier.
+     * @description Private endorsements never count toward the score, so they are not
+     *     revealed here. Strike reasons are shown only to the account itself and
+     *     to moderators.
+     */
+    get: operations['get_explanation'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/research/exports': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DimensionResponse: {
-      description?: string | null;
-      /** Format: uuid */
-      id: string;
-      max_label?: string | null;
-      /** Format: float */
-      max_value: number;
-      min_label?: string | null;
-      /** Format: float */
-      min_value: number;
-      name: string;
-      /** Format: int32 */
-      sort_order: number;
+    /**
+     * List export requests: every request for research admins, otherwise the
+     *     caller's own.
+     */
+    get: operations['list_exports'];
+    put?: never;
+    /**
+     * Request an anonymized export. Only configured researchers may ask, and
+     *     the request must be approved by a research admin before download.
+     */
+    post: operations['create_export'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/research/exports/{id}/approve': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    DimensionStatsResponse: {
-      /** Format: int64 */
-      count: number;
-      /** Format: uuid */
-      dimension_id: string;
-      dimension_name: string;
-      /** Format: double */
-      max: number;
-      /** Format: double */
-      mean: number;
-      /** Format: double */
-      median: number;
-      /** Format: double */
-      min: number;
-      /** Format: double */
-      stddev: number;
+    get?: never;
+    put?: never;
+    /** Approve a pending export. Admins cannot approve their own requests. */
+    post: operations['approve_export'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/research/exports/{id}/download': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    EndorseRequest: {
-      attestation?: unknown;
-      /** Format: uuid */
-      subject_id: string;
-      /** Format: float */
-      weight?: number;
+    /**
+     * Download an approved export as CSV. Only the requester may download, a
+     *     limited number of times before the approval expires. Rows covering fewer
+     *     than the configured k accounts are omitted.
+     */
+    get: operations['download_export'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/research/exports/{id}/reject': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    EndorsementQuery: {
-      /** Format: uuid */
-      subject_id?: string | null;
-      topic?: string | null;
+    get?: never;
+    put?: never;
+    /** Reject a pending export. Admins cannot reject their own requests. */
+    post: operations['reject_export'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    EndorsementResponse: {
-      created_at: string;
-      /** Format: uuid */
-      id: string;
-      /** Format: uuid */
-      issuer_id?: string | null;
-      revoked: boolean;
-      /** Format: uuid */
-      subject_id: string;
-      topic: string;
+    get: operations['list_rooms'];
+    put?: never;
+    post: operations['create_room'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/capacity': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    EndorsementsListResponse: {
-      endorsements: components['schemas']['EndorsementResponse'][];
+    get: operations['get_capacity'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    EvidenceItem: {
-      claim: string;
-      source?: string | null;
-      stance: string;
+    get: operations['get_room'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/agenda': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    EvidenceResponse: {
-      claim: string;
-      id: string;
-      source?: string | null;
-      stance: string;
+    get: operations['get_agenda'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/my-capabilities': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    HasEndorsementResponse: {
-      has_endorsement: boolean;
+    get: operations['my_capabilities'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['list_polls'];
+    put?: never;
+    post: operations['create_poll'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['get_poll_detail'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/dimensions': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['add_dimension'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/dimensions/{dimension_id}/evidence': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['create_evidence'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/evidence': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post?: never;
+    delete: operations['delete_evidence'];
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/my-votes': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['my_votes'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/reset': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    /**
+     * Sim-only: reset a poll back to draft status, clearing all timing fields.
+     *     Used by the ring buffer refill logic to recycle polls for a new cycle.
+     */
+    patch: operations['reset_poll'];
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/results': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['get_results'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/results/distribution': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['get_distribution'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/status': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['update_poll_status'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/traces': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['get_poll_traces'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/polls/{poll_id}/vote': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['cast_vote'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/roles': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['assign_role'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/rooms/{room_id}/suggestions': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['list_suggestions'];
+    put?: never;
+    post: operations['create_suggestion'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/stats': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /**
+     * GET /api/v1/stats — public instance statistics
+     * @description With `privacy.enabled`, counts carry differential-privacy noise unless the
+     *     request is device-signed by an admin.
+     */
+    get: operations['get_stats'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/strikes/appeals': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /** List strikes with a pending appeal (account moderators only). */
+    get: operations['list_pending_appeals'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/strikes/{strike_id}/appeal/resolve': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    /**
+     * Grant or deny a pending appeal (account moderators only).
+     * @description Granting overturns the strike, removing its penalty. Moderators cannot
+     *     review appeals of strikes against themselves.
+     */
+    post: operations['resolve_appeal'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/topics/feed': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /**
+     * Recent endorsements in the caller's subscribed topics, with keyset
+     *     pagination, plus per-topic movement counts.
+     * @description Movement counts carry differential-privacy noise when `privacy.enabled`
+     *     is set, unless the caller is an admin.
+     */
+    get: operations['get_feed'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/topics/subscriptions': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /** Topics the caller follows. */
+    get: operations['list_subscriptions'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/topics/{topic}/subscribe': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    /**
+     * Subscribe the caller to an endorsement topic. Idempotent.
+     * @description New endorsements in the topic appear in `GET /topics/feed` from now on.
+     */
+    post: operations['subscribe'];
+    /** Unsubscribe the caller from a topic. Items already in the feed stay. */
+    delete: operations['unsubscribe'];
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/budget': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['budget_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/denounce': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['denounce_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/denouncements/mine': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['list_my_denouncements_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/distance/{account_id}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['distance_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/endorse': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['endorse_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['create_invite_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/mine': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['list_invites_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/pairing/{code}': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /**
+     * Look up the invite behind a typed pairing code. Dashes, spaces and case
+     *     are ignored. Accept it with `POST /trust/invites/{id}/accept`.
+     */
+    get: operations['resolve_pairing_code_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/quota': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['invite_quota_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/tree': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['invite_tree_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/{id}/accept': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['accept_invite_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/{id}/link': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    /**
+     * Deep link for one of the caller's invites, for sharing where a QR code
+     *     cannot be shown.
+     */
+    get: operations['invite_link_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/invites/{id}/pairing-code': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    /**
+     * Issue a short pairing code for one of the caller's open invites, for
+     *     invitees who cannot scan the QR code.
+     */
+    post: operations['create_pairing_code_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/reconfirm': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    /**
+     * Reset the decay clock on the caller's trust edge to `subject_id`.
+     * @description The request is signed by the endorser's device as usual; the body carries
+     *     the subject's co-signature over [`reconfirmation_message`], so neither side
+     *     can refresh an edge alone.
+     */
+    post: operations['reconfirm_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/reconfirmations': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['reconfirmations_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/revoke': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    post: operations['revoke_handler'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/trust/scores/me': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get: operations['scores_me_handler'];
+    put?: never;
+    post?: never;
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/verifications/idv/webhook': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    /**
+     * Receive a result from the external ID verification provider.
+     * @description The raw body must be signed with `verification.idv_webhook_secret` and
+     *     the signature sent as `X-TC-Signature: sha256=<hex HMAC-SHA256>`. Not
+     *     found unless the secret is configured.
+     */
+    post: operations['idv_webhook'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+  '/verifiers/endorsements': {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    get?: never;
+    put?: never;
+    /**
+     * Create an endorsement as an authorized verifier.
+     * @description Checks that the authenticated caller has the `authorized_verifier` endorsement,
+     *     resolves the target username to an account, and creates the endorsement.
+     *
+     *     # Errors
+     *
+     *     Returns an error response for unauthorized, forbidden, not-found, or internal errors.
+     */
+    post: operations['create_endorsement_as_verifier'];
+    delete?: never;
+    options?: never;
+    head?: never;
+    patch?: never;
+    trace?: never;
+  };
+}
+export type webhooks = Record<string, never>;
+export interface components {
+  schemas: {
+    AcceptInviteResponse: {
+      accepted_at: string;
+      /** Format: uuid */
+      endorser_id: string;
+    };
+    /** @description Account lookup response — returns only what the UI needs to target a user. */
+    AccountLookupResponse: {
+      /** @description The alias that matched, if the lookup was not by username */
+      alias?: string | null;
+      /** Format: uuid */
+      id: string;
+      /** @description Canonical username, even when the lookup matched an alias */
+      username: string;
+    };
+    /** @description An account as others see it */
+    AccountProfileResponse: {
+      /** Format: uuid */
+      account_id: string;
+      /** @description The account's devices, including revoked ones; signed-in viewers only */
+      devices?: components['schemas']['ProfileDevice'][] | null;
+      root_kid: string;
+      username: string;
+    };
+    /**
+     * @description Stored in `accounts.status`.
+     * @enum {string}
+     */
+    AccountStatus: 'active' | 'suspended' | 'locked' | 'deleted' | 'deactivated';
+    AccountStatusResponse: {
+      /** Format: uuid */
+      account_id: string;
+      changed_at: string;
+      previous_status: components['schemas']['AccountStatus'];
+      status: components['schemas']['AccountStatus'];
+    };
+    AddDeviceRequest: {
+      /**
+       * @description Base64url-encoded certificate: the root key's signature over the
+       *     device pubkey, or over `device_pubkey || certificate_timestamp` when
+       *     `certificate_timestamp` is set
+       */
+      certificate: string;
+      /**
+       * Format: int64
+       * @description Unix seconds bound into a version 2 certificate; must be within the
+       *     clock skew window. Required once the calling device has declared
+       *     `cert-v2`.
+       */
+      certificate_timestamp?: number | null;
+      name: string;
+      /** @description Base64url-encoded Ed25519 public key */
+      pubkey: string;
+    };
+    AddDeviceResponse: {
+      created_at: string;
+      device_kid: string;
+    };
+    /** @description Alias returned in responses */
+    AliasInfo: {
+      alias: string;
+      created_at: string;
+      /** @description Assigned by an operator; cannot be removed by the account holder */
+      reserved: boolean;
+    };
+    AliasListResponse: {
+      aliases: components['schemas']['AliasInfo'][];
+    };
+    /** @description API token metadata returned in responses (never includes the token itself) */
+    ApiTokenInfo: {
+      created_at: string;
+      expires_at?: string | null;
+      /** Format: uuid */
+      id: string;
+      last_used_at?: string | null;
+      name: string;
+      revoked_at?: string | null;
+      scopes: string[];
+    };
+    ApiTokenListResponse: {
+      tokens: components['schemas']['ApiTokenInfo'][];
+    };
+    ApiTokenPosture: {
+      active: number;
+      /** @description Active tokens with no expiry */
+      without_expiry: number;
+    };
+    /**
+     * @description Permission granted to an API token. Each scope covers one read endpoint group.
+     * @enum {string}
+     */
+    ApiTokenScope: 'devices:read' | 'endorsements:read' | 'trust:read';
+    /**
+     * @description Where a strike's appeal stands. Stored in
+     *     `reputation__strikes.appeal_status`.
+     * @enum {string}
+     */
+    AppealStatus: 'none' | 'pending' | 'granted' | 'denied';
+    AppealStrikeRequest: {
+      statement: string;
+    };
+    AssignRoleRequest: {
+      /** Format: uuid */
+      account_id: string;
+      role: string;
+    };
+    AssignRoleResponse: {
+      /** Format: uuid */
+      account_id: string;
+      role: string;
+      /** Format: uuid */
+      room_id: string;
+    };
+    /** @description Issued challenge */
+    AuthChallengeResponse: {
+      /** @description Value to send back in `X-Challenge`. Single use. */
+      challenge: string;
+      device_kid: string;
+      expires_at: string;
+    };
+    AuthorizeRequest: {
+      client_id: string;
+      /** @description Base64url SHA-256 of the client's code verifier */
+      code_challenge: string;
+      /** @description Must be `S256` */
+      code_challenge_method: string;
+      /** @description Must exactly match one of the client's registered redirect URIs */
+      redirect_uri: string;
+      /** @description Must be `code` */
+      response_type: string;
+      /** @description Space-separated scopes; defaults to every scope the client registered */
+      scope?: string | null;
+      /** @description Opaque value echoed back to the client */
+      state?: string | null;
+    };
+    AuthorizeResponse: {
+      code: string;
+      /**
+       * Format: int64
+       * @description Seconds until the code can no longer be exchanged
+       */
+      expires_in: number;
+      /** @description The redirect URI with `code` and `state` appended; send the user here */
+      redirect_to: string;
+      state?: string | null;
+    };
+    /** @description Argon2id parameters needed to re-derive the backup key */
+    BackupKdfParams: {
+      /** @description Always `argon2id` */
+      algorithm: string;
+      /**
+       * Format: int32
+       * @description Memory cost in KiB
+       */
+      m_cost: number;
+      /**
+       * Format: int32
+       * @description Parallelism
+       */
+      p_cost: number;
+      /**
+       * Format: int32
+       * @description Iterations
+       */
+      t_cost: number;
+    };
+    BackupPosture: {
+      /** Format: int64 */
+      age_days?: number | null;
+      created_at?: string | null;
+      /** @description Whether an encrypted root-key backup is stored for recovery */
+      present: boolean;
+    };
+    BackupResponse: {
+      /** @description Base64url-encoded encrypted backup envelope */
+      encrypted_backup: string;
+      root_kid: string;
+    };
+    /** @description Aggregate reputation summary rendered by the badge endpoints. */
+    BadgeResponse: {
+      /**
+       * Format: int64
+       * @description Active endorsements across all topics.
+       */
+      endorsement_count: number;
+      /**
+       * Format: int64
+       * @description Distinct topics with at least one active endorsement.
+       */
+      topic_count: number;
+      username: string;
+      verification_level?: null | components['schemas']['VerificationMethod'];
+      /** @description Whether the account holds an active `identity_verified` endorsement. */
+      verified: boolean;
+    };
+    BotTraceResponse: {
+      completed_at?: string | null;
+      created_at: string;
+      error?: string | null;
+      id: string;
+      run_mode: string;
+      status: string;
+      steps: unknown[];
+      task: string;
+      /** Format: double */
+      total_cost_usd: number;
+    };
+    BucketResponse: {
+      /** Format: int64 */
+      count: number;
+      label: string;
+    };
+    BudgetResponse: {
+      /** Format: int64 */
+      denouncements_available: number;
+      /** Format: int32 */
+      denouncements_total: number;
+      /** Format: int64 */
+      denouncements_used: number;
+      /** Format: int64 */
+      out_of_slot_count: number;
+      /** Format: int64 */
+      slots_available: number;
+      /** Format: int32 */
+      slots_total: number;
+      /** Format: int64 */
+      slots_used: number;
+    };
+    /**
+     * @description Build metadata exposed via GraphQL, REST, and logs.
+     *
+     *     Loaded from environment variables at startup (see [`BuildInfo::from_env`]).
+     *     These are typically set by the CI pipeline or Dockerfile at image build time.
+     */
+    BuildInfo: {
+      /**
+       * @description Build timestamp in RFC 3339 format. Read from `BUILD_TIME` env var.
+       *     Defaults to `"unknown"`.
+       */
+      buildTime: string;
+      /** @description Git commit SHA. Read from `GIT_SHA` env var. Defaults to `"unknown"`. */
+      gitSha: string;
+      /** @description Optional build message (e.g., CI run URL). Read from `BUILD_MESSAGE` env var. */
+      message?: string | null;
+      /**
+       * @description Application version string. Read from `APP_VERSION` or `VERSION` env var.
+       *     Defaults to `"dev"`.
+       */
+      version: string;
+    };
+    /** @description OAuth callback query parameters from ID.me. */
+    CallbackQuery: {
+      code?: string | null;
+      error?: string | null;
+      error_description?: string | null;
+      state?: string | null;
+    };
+    ChangeAccountStatusRequest: {
+      /** @description Why the status is changing; kept in the audit trail */
+      reason?: string | null;
+      status: components['schemas']['AccountStatus'];
+    };
+    /**
+     * @description What kind of behavior a change affects.
+     * @enum {string}
+     */
+    ChangeKind: 'added' | 'changed' | 'validation' | 'deprecated' | 'removed';
+    ChangeUsernameRequest: {
+      /** @description Follows the username rules */
+      username: string;
+    };
+    ChangeUsernameResponse: {
+      changed_at: string;
+      /** @description Earliest time of the next change */
+      next_change_at: string;
+      /** @description Quarantined for other accounts for `usernames.release_cooldown_days` */
+      previous_username: string;
+      username: string;
+    };
+    ChangelogChange: {
+      /** @description Whether clients built against an earlier revision may break */
+      breaking?: boolean;
+      /** @description Affected endpoints as `METHOD /path`; empty for cross-cutting changes */
+      endpoints?: string[];
+      kind: components['schemas']['ChangeKind'];
+      summary: string;
+    };
+    ChangelogRelease: {
+      changes: components['schemas']['ChangelogChange'][];
+      /** @description API revision, `MAJOR.MINOR.PATCH` */
+      version: string;
+    };
+    ChangelogResponse: {
+      /** @description Whether any listed release contains a breaking change */
+      breaking: boolean;
+      /** @description API revision this server implements */
+      current_version: string;
+      /** @description Releases newer than `since` (all of them without it), newest first */
+      releases: components['schemas']['ChangelogRelease'][];
+    };
+    ClientInfo: {
+      client_id: string;
+      created_at: string;
+      name: string;
+      /** @description The secret issued before the last rotation stops working at this instant */
+      previous_secret_expires_at?: string | null;
+      redirect_uris: string[];
+      revoked_at?: string | null;
+      scopes: string[];
+      secret_rotated_at?: string | null;
+      updated_at: string;
+    };
+    ClientListResponse: {
+      /** @description Newest first, including revoked clients */
+      clients: components['schemas']['ClientInfo'][];
+    };
+    /** @description A client together with its plaintext secret, returned exactly once. */
+    ClientWithSecretResponse: components['schemas']['ClientInfo'] & {
+      /** @description Store this now; it cannot be retrieved again */
+      client_secret: string;
+    };
+    ConfirmStepUpChallengeRequest: {
+      code: string;
+    };
+    ConfirmVerificationRequest: {
+      code: string;
+    };
+    CreateAliasRequest: {
+      /** @description Follows the username rules (3-64 letters, digits, `-`, `_`) */
+      alias: string;
+    };
+    CreateApiTokenRequest: {
+      /**
+       * Format: int32
+       * @description Days until the token expires; omit for a non-expiring token
+       */
+      expires_in_days?: number | null;
+      name: string;
+      /** @description Read scopes granted to the token (at least one) */
+      scopes: components['schemas']['ApiTokenScope'][];
+    };
+    CreateApiTokenResponse: components['schemas']['ApiTokenInfo'] & {
+      /** @description The bearer token. Shown once; only its hash is stored. */
+      token: string;
+    };
+    CreateClientRequest: {
+      name: string;
+      /** @description Absolute `https` URLs (plain `http` only for loopback hosts) */
+      redirect_uris: string[];
+      /** @description API token scopes the client may request, e.g. `trust:read` */
+      scopes: string[];
+    };
+    CreateDimensionRequest: {
+      description?: string | null;
+      max_label?: string | null;
+      /** Format: float */
+      max_value?: number;
+      min_label?: string | null;
+      /** Format: float */
+      min_value?: number;
+      name: string;
+      /** Format: int32 */
+      sort_order?: number;
+    };
+    CreateEndorsementRequest: {
+      evidence?: unknown;
+      topic: string;
+      username: string;
+      /** @description Who may see the endorsement (default `public`) */
+      visibility?: components['schemas']['EndorsementVisibility'];
+    };
+    CreateEvidenceBody: {
+      evidence: components['schemas']['EvidenceItem'][];
+    };
+    CreateExportRequest: {
+      dataset: components['schemas']['ResearchDataset'];
+      /** @description What the data will be used for; shown to the approving admin */
+      purpose: string;
+    };
+    CreateInviteRequest: {
+      attestation: unknown;
+      delivery_method: string;
+      /** @description base64url-encoded invite envelope bytes */
+      envelope: string;
+      relationship_depth?: string | null;
+      /** Format: float */
+      weight?: number | null;
+    };
+    CreateInviteResponse: {
+      expires_at: string;
+      /** Format: uuid */
+      id: string;
+    };
+    CreatePollRequest: {
+      description?: string | null;
+      question: string;
+    };
+    CreateRoomRequest: {
+      constraint_config?: unknown;
+      constraint_type?: string;
+      description?: string | null;
+      eligibility_topic?: string;
+      engine_config?: unknown;
+      engine_type?: string;
+      name: string;
+      /** Format: int32 */
+      poll_duration_secs?: number | null;
+    };
+    /** @description Issued session */
+    CreateSessionResponse: {
+      device_kid: string;
+      expires_at: string;
+      /** @description The bearer token. Shown once; only its hash is stored. */
+      token: string;
+    };
+    CreateSignupInviteResponse: components['schemas']['SignupInviteInfo'] & {
+      /** @description The invite code. Shown once; only its hash is stored. */
+      code: string;
+    };
+    CreateStepUpChallengeRequest: {
+      /** @description `revoke_device` or `remove_phone` */
+      action: components['schemas']['StepUpAction'];
+    };
+    CreateSuggestionRequest: {
+      suggestion_text: string;
+    };
+    CreatedEndorsementResponse: {
+      created_at: string;
+      /** Format: uuid */
+      id: string;
+      /** Format: uuid */
+      issuer_id: string;
+      /** Format: uuid */
+      subject_id: string;
+      topic: string;
+      visibility: components['schemas']['EndorsementVisibility'];
+    };
+    CrlEntry: {
+      device_kid: string;
+      /** @description The revoking device's signed request; absent for older revocations */
+      revocation?: Record<string, never> | null;
+      /** Format: date-time */
+      revoked_at: string;
+      revoked_by_kid?: string | null;
+    };
+    /**
+     * @description A device key certified by the account root, as needed to check its
+     *     revocation signatures.
+     */
+    CrlSigner: {
+      /**
+       * @description Base64url-encoded root signature; see
+       *     [`device_certificate_message`](super::service::device_certificate_message)
+       */
+      certificate: string;
+      /**
+       * Format: int64
+       * @description Login timestamp bound into a version 2 certificate
+       */
+      certificate_timestamp?: number | null;
+      device_kid: string;
+      device_pubkey: string;
+    };
+    DailyStats: {
+      /**
+       * Format: int64
+       * @description Non-revoked devices used within the 30 days ending on `date`.
+       */
+      active_devices: number;
+      /**
+       * Format: int64
+       * @description Distinct (poll, voter) pairs with a vote cast or updated that day.
+       */
+      ballots_cast: number;
+      /** @description UTC calendar day (`YYYY-MM-DD`). */
+      date: string;
+      endorsements_by_topic: components['schemas']['TopicEndorsementCount'][];
+      /** Format: int64 */
+      signups: number;
+      /**
+       * Format: int64
+       * @description Distinct accounts that voted that day.
+       */
+      voters: number;
+    };
+    /** @description Stored delegation envelope for a device, with its verification status. */
+    DelegationResponse: {
+      /** @description Base64url-encoded root signature certifying the device key */
+      certificate: string;
+      /**
+       * Format: int64
+       * @description Login timestamp (Unix seconds) bound into a version 2 certificate
+       */
+      certificate_timestamp?: number | null;
+      /**
+       * Format: int32
+       * @description 1 = root signed the raw pubkey, 2 = root signed pubkey and login
+       *     timestamp; null for devices created before versioning
+       */
+      certificate_version?: number | null;
+      device_kid: string;
+      revoked_at?: string | null;
+      /** @description KID of the account's current root key */
+      root_kid: string;
+      /** @description Key this device rotated from, if it was created by a rotation */
+      rotated_from_kid?: string | null;
+      /** @description Base64url-encoded signature by `rotated_from_kid` over the rotation */
+      rotation_signature?: string | null;
+      /**
+       * @description Capabilities the device currently holds: `["*"]` while active,
+       *     empty once revoked
+       */
+      scopes: string[];
+      status: components['schemas']['DelegationStatus'];
+    };
+    /**
+     * @description Result of re-verifying a stored delegation against the current root key.
+     * @enum {string}
+     */
+    DelegationStatus: 'valid' | 'invalid' | 'unknown_format';
+    DeleteAccountRequest: {
+      /** @description Why the account is being deleted; kept in the audit trail */
+      reason?: string | null;
+      /**
+       * @description Base64url root key signature over the account deletion message for
+       *     this account and the request's `X-Timestamp`
+       */
+      root_signature: string;
+    };
+    DeleteAccountResponse: {
+      /** Format: uuid */
+      account_id: string;
+      deactivated_at: string;
+      /** @description Until this instant `POST /auth/accounts/undelete` restores the account */
+      purge_after: string;
+    };
+    DenounceRequest: {
+      reason: string;
+      /** Format: uuid */
+      target_id: string;
+    };
+    DenouncementResponse: {
+      created_at: string;
+      /** Format: uuid */
+      id: string;
+      reason: string;
+      /** Format: uuid */
+      target_id: string;
+      target_username: string;
+    };
+    /** @description An account's published device revocation list. */
+    DeviceCrl: {
+      /** Format: uuid */
+      account_id: string;
+      /**
+       * Format: int64
+       * @description Number of revoked devices; grows with every revocation
+       */
+      crl_number: number;
+      /** Format: int32 */
+      format_version: number;
+      /** Format: date-time */
+      generated_at: string;
+      /** @description Revoked devices, oldest revocation first */
+      revoked: components['schemas']['CrlEntry'][];
+      root_kid: string;
+      /** @description Base64url-encoded account root public key */
+      root_pubkey: string;
+      /** @description Certificates of the devices that signed the revocations */
+      signers: components['schemas']['CrlSigner'][];
+    };
+    /** @description Device info returned in API responses (omits certificate and raw pubkey) */
+    DeviceInfo: {
+      /**
+       * Format: int32
+       * @description Certificate format: 1 = root signed the raw pubkey, 2 = root signed
+       *     pubkey and login timestamp; null for devices created before versioning
+       */
+      certificate_version?: number | null;
+      created_at: string;
+      device_kid: string;
+      device_name: string;
+      /** @description Route of the most recent signed request, e.g. `GET /auth/devices` */
+      last_endpoint?: string | null;
+      last_used_at?: string | null;
+      /**
+       * Format: int64
+       * @description Signed requests in the last 24 hours (hourly granularity; may trail
+       *     live traffic by up to a minute)
+       */
+      requests_24h: number;
+      /**
+       * Format: int64
+       * @description Signed requests in the last 7 days
+       */
+      requests_7d: number;
+      revoked_at?: string | null;
+      /** @description KID of the device that revoked this one, if recorded */
+      revoked_by_kid?: string | null;
+      /** @description Key this device rotated from, if it was created by a rotation */
+      rotated_from_kid?: string | null;
+    };
+    DeviceListResponse: {
+      devices: components['schemas']['DeviceInfo'][];
+    };
+    DevicePosture: {
+      active: number;
+      /**
+       * Format: int64
+       * @description Maximum active devices per account
+       */
+      limit: number;
+      /**
+       * Format: int64
+       * @description Age in days of the oldest active device
+       */
+      oldest_active_age_days?: number | null;
+      revoked: number;
+      /** @description Active devices not used in the last `STALE_DEVICE_DAYS` days */
+      stale: string[];
+    };
+    /**
+     * @description Whether the signing device is still delegated.
+     * @enum {string}
+     */
+    DeviceStatus: 'active' | 'revoked';
+    DimensionDetailResponse: {
+      description?: string | null;
+      evidence: components['schemas']['EvidenceResponse'][];
+      /** Format: uuid */
+      id: string;
+      max_label?: string | null;
+      /** Format: float */
+      max_value: number;
+      min_label?: string | null;
+      /** Format: float */
+      min_value: number;
+      name: string;
+      /** Format: int32 */
+      sort_order: number;
+    };
+    DimensionDistributionResponse: {
+      buckets: components['schemas']['BucketResponse'][];
+      /** Format: uuid */
+      dimension_id: string;
+      dimension_name: string;
+    };
+    DimensionResponse: {
+      description?: string | null;
+      /** Format: uuid */
+      id: string;
+      max_label?: string | null;
+      /** Format: float */
+      max_value: number;
+      min_label?: string | null;
+      /** Format: float */
+      min_value: number;
+      name: string;
+      /** Format: int32 */
+      sort_order: number;
+    };
+    DimensionStatsResponse: {
+      /** Format: int64 */
+      count: number;
+      /** Format: uuid */
+      dimension_id: string;
+      dimension_name: string;
+      /** Format: double */
+      max: number;
+      /** Format: double */
+      mean: number;
+      /** Format: double */
+      median: number;
+      /** Format: double */
+      min: number;
+      /** Format: double */
+      stddev: number;
+    };
+    DisputeEndorsementRequest: {
+      /**
+       * @description Leave the endorsement out of public aggregates pending review
+       *     (default `false`).
+       */
+      pause_aggregates?: boolean;
+      /** @description Why the subject contests the endorsement. */
+      statement: string;
+    };
+    DisputeResponse: {
+      created_at: string;
+      /** Format: uuid */
+      endorsement_id: string;
+      /**
+       * @description The subject's signed request. Verify it against the subject's device
+       *     key to confirm the statement.
+       */
+      envelope: unknown;
+      /** Format: uuid */
+      id: string;
+      /** Format: uuid */
+      issuer_id?: string | null;
+      pauses_aggregates: boolean;
+      resolution_note?: string | null;
+      resolved_at?: string | null;
+      /** Format: uuid */
+      resolved_by?: string | null;
+      statement: string;
+      status: components['schemas']['DisputeStatus'];
+      /** Format: uuid */
+      subject_id: string;
+      topic: string;
+    };
+    /**
+     * @description Lifecycle of an endorsement dispute.
+     * @enum {string}
+     */
+    DisputeStatus: 'open' | 'upheld' | 'dismissed';
+    DisputesListResponse: {
+      disputes: components['schemas']['DisputeResponse'][];
+    };
+    /**
+     * @description Where a distance answer came from.
+     * @enum {string}
+     */
+    DistanceOrigin: 'materialized' | 'live';
+    EndorseRequest: {
+      attestation?: unknown;
+      /** Format: uuid */
+      subject_id: string;
+      /** Format: float */
+      weight?: number;
+    };
+    EndorsementQuery: {
+      /** @description Canonical subject reference; see [`crate::subject::SubjectRef`] */
+      subject?: string | null;
+      /** Format: uuid */
+      subject_id?: string | null;
+      topic?: string | null;
+    };
+    EndorsementResponse: {
+      /**
+       * @description True while the endorsement is left out of public aggregates pending
+       *     dispute review.
+       */
+      aggregates_paused: boolean;
+      created_at: string;
+      dispute_status?: null | components['schemas']['DisputeStatus'];
+      /** Format: uuid */
+      id: string;
+      /**
+       * @description The issuer's signed request, when issued through one. Verify it
+       *     against the issuing device's public key to confirm `visibility`.
+       */
+      issuance_envelope?: unknown;
+      /** Format: uuid */
+      issuer_id?: string | null;
+      revoked: boolean;
+      /** Format: uuid */
+      subject_id: string;
+      topic: string;
+      visibility: components['schemas']['EndorsementVisibility'];
+    };
+    /**
+     * @description Who may see an endorsement.
+     * @enum {string}
+     */
+    EndorsementVisibility: 'public' | 'subject_only' | 'private';
+    EndorsementsListResponse: {
+      /** @description Snapshot instant of a paginated walk; null when unpaginated. */
+      as_of?: string | null;
+      endorsements: components['schemas']['EndorsementResponse'][];
+      /** @description Cursor for the next page; null on the last page or when unpaginated. */
+      next_cursor?: string | null;
+    };
+    EnrollStepUpPhoneRequest: {
+      /** @description Phone number in international form, e.g. `+15551234567` */
+      phone_number: string;
+    };
+    EvidenceItem: {
+      claim: string;
+      source?: string | null;
+      stance: string;
+    };
+    EvidenceResponse: {
+      claim: string;
+      id: string;
+      source?: string | null;
+      stance: string;
+    };
+    ExportRequestResponse: {
+      created_at: string;
+      dataset: components['schemas']['ResearchDataset'];
+      decided_at?: string | null;
+      /** Format: int32 */
+      downloads_remaining: number;
+      /** @description Downloads stop after this instant; set once approved */
+      expires_at?: string | null;
+      /** Format: uuid */
+      id: string;
+      purpose: string;
+      requester_username: string;
+      status: components['schemas']['ExportStatus'];
+    };
+    ExportRequestsResponse: {
+      /** @description Newest first; every request for admins, otherwise the caller's own */
+      requests: components['schemas']['ExportRequestResponse'][];
+    };
+    /**
+     * @description Where an export request stands.
+     * @enum {string}
+     */
+    ExportStatus: 'pending' | 'approved' | 'rejected';
+    /** @description One piece of activity in the feed. */
+    FeedItem:
+      | {
+          /** Format: uuid */
+          endorser_id?: string | null;
+          /** Format: uuid */
+          id: string;
+          /** @enum {string} */
+          kind: 'endorsement';
+          /** Format: date-time */
+          occurred_at: string;
+          /** Format: uuid */
+          subject_id: string;
+          subject_username: string;
+          topic: string;
+        }
+      | {
+          /** Format: uuid */
+          id: string;
+          /** @enum {string} */
+          kind: 'poll_opened';
+          /** Format: date-time */
+          occurred_at: string;
+          question: string;
+          /** Format: uuid */
+          room_id: string;
+          room_name: string;
+          /** @description The room's eligibility topic */
+          topic: string;
+        };
+    FeedResponse: {
+      /** @description Instant every page of this walk is ranked for */
+      as_of: string;
+      /** @description Best first */
+      items: components['schemas']['RankedFeedItem'][];
+      /** @description Pass back as `cursor` to fetch the next page; absent on the last page */
+      next_cursor?: string | null;
+      /** @description Whether the page was served from a precomputed ranking */
+      precomputed: boolean;
+    };
+    HasEndorsementResponse: {
+      has_endorsement: boolean;
+    };
+    /** @description Result reported by an ID verification provider. */
+    IdvWebhookRequest: {
+      /**
+       * Format: uuid
+       * @description Account the check was started for
+       */
+      account_id: string;
+      /**
+       * @description Provider's identifier for the verified person, used to keep one ID
+       *     from verifying several accounts
+       */
+      reference: string;
+      /**
+       * @description `verified` records a government ID verification; anything else is
+       *     acknowledged and ignored
+       */
+      status: string;
+    };
+    IdvWebhookResponse: {
+      /** @description Whether a verification was recorded */
+      recorded: boolean;
+    };
+    InPersonAttestationResponse: {
+      /** Format: int64 */
+      attestations: number;
+      /** Format: int32 */
+      required: number;
+      /** Format: uuid */
+      subject_id: string;
+      /** @description Whether the subject is now verified in person */
+      verified: boolean;
+    };
+    InstanceDocument: {
+      /** @description Supported REST API versions, e.g. `v1` for `/api/v1` */
+      api_versions: string[];
+      /** Format: int32 */
+      document_version: number;
+      endpoints: components['schemas']['InstanceEndpoints'];
+      /**
+       * @description Envelope versions accepted from clients, e.g. `sig-v2`; declare yours
+       *     in `TC-Client-Caps`
+       */
+      envelope_versions: string[];
+      name: string;
+      /** @description Person or organization running the instance */
+      operator?: string | null;
+      /** @description `open` or `invite_only` */
+      signup_policy: string;
+      software: components['schemas']['SoftwareInfo'];
+      /** @description Root keys of the platform verifiers this instance trusts */
+      verifier_keys: components['schemas']['VerifierKey'][];
+    };
+    /** @description Paths relative to the instance origin. */
+    InstanceEndpoints: {
+      graphql: string;
+      rest: string;
+      verify: string;
+    };
+    InstanceStatsResponse: {
+      /** @description Completed days, newest first. Today is never included. */
+      days: components['schemas']['DailyStats'][];
+    };
+    IntegrityAlertResponse: {
+      /** Format: uuid */
+      account_id?: string | null;
+      /** @description Failed check, e.g. `invalid_certificate` or `broken_sigchain` */
+      code: string;
+      detail: string;
+      first_seen_at: string;
+      /** Format: uuid */
+      id: string;
+      /** @description Last sweep that still found the failure */
+      last_seen_at: string;
+      /**
+       * @description Failing record, e.g. `device:{kid}`, `sigchain:{seqno}`, or
+       *     `endorsement:{id}`
+       */
+      record: string;
+      resolved_at?: string | null;
+      /** Format: uuid */
+      resolved_by?: string | null;
+    };
+    IntegrityAlertsResponse: {
+      /** @description Most recently seen first */
+      alerts: components['schemas']['IntegrityAlertResponse'][];
+    };
+    InviteLinkResponse: {
+      deep_link: string;
+      /** Format: uuid */
+      invite_id: string;
+    };
+    /** @description An account's invite quota for the current window. */
+    InviteQuota: {
+      /**
+       * Format: int32
+       * @description Invites allowed in the current window.
+       */
+      allowance: number;
+      /**
+       * Format: int32
+       * @description Invites still available.
+       */
+      remaining: number;
+      /**
+       * Format: int32
+       * @description Invites issued in the current window.
+       */
+      used: number;
+      /**
+       * Format: int32
+       * @description Length of the rolling window in days.
+       */
+      window_days: number;
+    };
+    InviteResponse: {
+      accepted_at?: string | null;
+      /** Format: uuid */
+      accepted_by?: string | null;
+      delivery_method: string;
+      expires_at: string;
+      /** Format: uuid */
+      id: string;
+    };
+    InviteTreeEdgeResponse: {
+      accepted_at?: string | null;
+      /** Format: int32 */
+      depth: number;
+      /** Format: uuid */
+      invite_id: string;
+      /** Format: uuid */
+      invitee_id: string;
+      invitee_username: string;
+      /** Format: uuid */
+      inviter_id: string;
+    };
+    /** @description Accepted invites downstream of `root_id`, with a per-depth summary. */
+    InviteTreeResponse: {
+      edges: components['schemas']['InviteTreeEdgeResponse'][];
+      /** @description Number of invitees at each depth; index 0 is depth 1. */
+      invitees_per_depth: number[];
+      /** Format: int32 */
+      max_depth: number;
+      /** Format: uuid */
+      root_id: string;
+      total_invitees: number;
+    };
+    InvitesResponse: {
+      invites: components['schemas']['InviteResponse'][];
+    };
+    IssueStrikeRequest: {
+      /**
+       * Format: int64
+       * @description Days until the penalty has fully decayed (default depends on
+       *     `reason_code`; at most 365)
+       */
+      expires_in_days?: number | null;
+      /** @description Shown to the struck account */
+      note?: string | null;
+      reason_code: components['schemas']['StrikeReason'];
+    };
+    /** @description Device data for login */
+    LoginDevice: {
+      /** @description Base64url-encoded certificate (root key's signature over `device_pubkey || timestamp`) */
+      certificate: string;
+      /** @description User-provided device name */
+      name: string;
+      /** @description Base64url-encoded Ed25519 public key */
+      pubkey: string;
+    };
+    /** @description Login request payload */
+    LoginRequest: {
+      device: components['schemas']['LoginDevice'];
+      /**
+       * Format: int64
+       * @description Unix timestamp (seconds) — must be within the server's clock skew window (default ±300s)
+       */
+      timestamp: number;
+      username: string;
+    };
+    /** @description Login response */
+    LoginResponse: {
+      /** Format: uuid */
+      account_id: string;
+      device_kid: string;
+      root_kid: string;
+    };
+    MarkReadResponse: {
+      /** Format: uuid */
+      id: string;
+      read_at: string;
+    };
+    MessageResponse: {
+      message: string;
+    };
+    MyCapabilitiesResponse: {
+      can_configure: boolean;
+      can_vote: boolean;
+      next_step?: string | null;
+      reason?: string | null;
+      role: string;
+    };
+    /**
+     * @description Notification category, the unit of per-user preferences.
+     * @enum {string}
+     */
+    NotificationCategory: 'security' | 'trust';
+    NotificationPreference: {
+      category: components['schemas']['NotificationCategory'];
+      enabled: boolean;
+    };
+    /** @description Every category with its effective setting. */
+    NotificationPreferencesResponse: {
+      preferences: components['schemas']['NotificationPreference'][];
+    };
+    NotificationResponse: {
+      category: string;
+      created_at: string;
+      /** Format: uuid */
+      id: string;
+      kind: string;
+      /** @description Kind-specific identifiers (e.g. `device_kid`, `endorser_id`) */
+      payload: unknown;
+      read_at?: string | null;
+    };
+    NotificationsResponse: {
+      /** @description Snapshot instant shared by every page of this walk */
+      as_of: string;
+      /** @description Pass back as `cursor` to fetch the next page; absent on the last page */
+      next_cursor?: string | null;
+      /** @description Newest first */
+      notifications: components['schemas']['NotificationResponse'][];
+      /**
+       * Format: int64
+       * @description Unread notifications across the whole inbox, not just this page
+       */
+      unread_count: number;
+    };
+    /** @description Error body of the token endpoint, as defined by RFC 6749 §5.2. */
+    OAuthErrorResponse: {
+      /** @description e.g. `invalid_grant`, `invalid_client` */
+      error: string;
+      error_description: string;
+    };
+    PairingCodeResponse: {
+      /** @description Short code the invitee types in instead of scanning the QR code */
+      code: string;
+      /** @description Link that opens the invite directly, same as the QR payload */
+      deep_link: string;
+      expires_at: string;
+    };
+    PollDetailResponse: {
+      dimensions: components['schemas']['DimensionDetailResponse'][];
+      poll: components['schemas']['PollResponse'];
+    };
+    PollDistributionResponse: {
+      dimensions: components['schemas']['DimensionDistributionResponse'][];
+    };
+    PollResponse: {
+      activated_at?: string | null;
+      closes_at?: string | null;
+      created_at: string;
+      description?: string | null;
+      /** Format: uuid */
+      id: string;
+      question: string;
+      /** Format: uuid */
+      room_id: string;
+      status: string;
+    };
+    PollResultsResponse: {
+      dimensions: components['schemas']['DimensionStatsResponse'][];
+      poll: components['schemas']['PollResponse'];
+      /** Format: int64 */
+      voter_count: number;
+    };
+    PollStatusRequest: {
+      status: components['schemas']['PollStatusTransition'];
+    };
+    /** @enum {string} */
+    PollStatusTransition: 'active' | 'closed';
+    /** @description RFC 7807 Problem Details error response. */
+    ProblemDetails: {
+      /** @description Human-readable explanation specific to this occurrence */
+      detail: string;
+      extensions?: null | components['schemas']['ProblemExtensions'];
+      /** @description URI reference identifying the specific occurrence */
+      instance?: string | null;
+      /**
+       * Format: int32
+       * @description HTTP status code
+       */
+      status: number;
+      /** @description Short human-readable summary */
+      title: string;
+      /** @description URI reference identifying the problem type */
+      type: string;
+    };
+    /** @description Extended error information mapping to GraphQL error codes. */
+    ProblemExtensions: {
+      /** @description Error code matching GraphQL error codes */
+      code: string;
+      /** @description Field that caused the error (for validation errors) */
+      field?: string | null;
+    };
+    /** @description A device in an account profile */
+    ProfileDevice: {
+      created_at: string;
+      device_kid: string;
+      /** @description Moderators and the owner only */
+      device_name?: string | null;
+      /** @description The owner only */
+      last_used_at?: string | null;
+      revoked: boolean;
+      /** @description Moderators and the owner only */
+      revoked_at?: string | null;
+    };
+    /** @description A feed item with its score for the viewer. */
+    RankedFeedItem: {
+      item: components['schemas']['FeedItem'];
+      /**
+       * Format: double
+       * @description Higher ranks first; only comparable within one viewer's feed
+       */
+      score: number;
+    };
+    Recommendation: {
+      code: components['schemas']['RecommendationCode'];
+      message: string;
+    };
+    /** @enum {string} */
+    RecommendationCode:
+      | 'no_backup'
+      | 'stale_devices'
+      | 'device_limit_near'
+      | 'non_expiring_tokens'
+      | 'review_recent_changes';
+    /**
+     * @description Re-confirmation of the caller's trust edge to `subject_id`, co-signed by
+     *     one of the subject's devices over [`reconfirmation_message`].
+     */
+    ReconfirmRequest: {
+      /** Format: uuid */
+      subject_id: string;
+      /** @description KID of the subject device that produced `subject_signature` */
+      subject_kid: string;
+      /** @description base64url Ed25519 signature by the subject device */
+      subject_signature: string;
+      /**
+       * Format: int64
+       * @description Unix seconds the subject signed at; must be within the clock-skew window
+       */
+      timestamp: number;
+    };
+    ReconfirmResponse: {
+      last_confirmed_at: string;
+      /** Format: uuid */
+      subject_id: string;
+    };
+    ReconfirmationsResponse: {
+      edges: components['schemas']['StaleEdgeResponse'][];
+      /** Format: int64 */
+      reconfirm_after_days: number;
+    };
+    /** @description Everything a new device needs to restore the root key */
+    RecoveryBackupResponse: {
+      /** @description Base64url-encoded encrypted backup envelope */
+      encrypted_backup: string;
+      kdf: components['schemas']['BackupKdfParams'];
+      root_kid: string;
+      /** @description Base64url-encoded Argon2id salt (also embedded in the envelope) */
+      salt: string;
+      /** Format: int32 */
+      version: number;
+    };
+    ReleasedUsernameInfo: {
+      /**
+       * Format: uuid
+       * @description Account that gave the name up
+       */
+      account_id: string;
+      /** @description When other accounts may claim the name */
+      available_at: string;
+      name: string;
+      released_at: string;
+    };
+    ReleasedUsernamesResponse: {
+      names: components['schemas']['ReleasedUsernameInfo'][];
+    };
+    RenameDeviceRequest: {
+      name: string;
+    };
+    /** @description Everything that produced an account's score and tier. */
+    ReputationExplanation: {
+      components: components['schemas']['ScoreComponent'][];
+      next_tier?: null | components['schemas']['ReputationTier'];
+      /**
+       * Format: double
+       * @description Points still needed to reach `next_tier`.
+       */
+      points_to_next_tier?: number | null;
+      /** Format: double */
+      score: number;
+      tier: components['schemas']['ReputationTier'];
+    };
+    /**
+     * @description Named standing of an account, derived from its reputation score.
+     *
+     *     Thresholds come from [`ReputationConfig`]; see [`score::explain`] for how
+     *     the score itself is computed.
+     * @enum {string}
+     */
+    ReputationTier: 'new' | 'established' | 'trusted' | 'exemplary';
+    /**
+     * @description A dataset that can be exported.
+     * @enum {string}
+     */
+    ResearchDataset: 'endorsement_aggregates' | 'trust_degrees';
+    ReserveUsernameRequest: {
+      /** @description Name to reserve; matched case-insensitively */
+      name: string;
+      /** @description Why the name is reserved, for other admins */
+      reason?: string | null;
+    };
+    ReservedUsernameInfo: {
+      created_at: string;
+      /**
+       * Format: uuid
+       * @description Admin who reserved it; null for the built-in names
+       */
+      created_by?: string | null;
+      name: string;
+      reason?: string | null;
+    };
+    ReservedUsernamesResponse: {
+      names: components['schemas']['ReservedUsernameInfo'][];
+    };
+    ResolveAppealRequest: {
+      note?: string | null;
+      /** @description `granted` overturns the strike; `denied` keeps it. */
+      outcome: components['schemas']['AppealStatus'];
+    };
+    ResolveDisputeRequest: {
+      note?: string | null;
+      /** @description `upheld` revokes the endorsement; `dismissed` keeps it. */
+      outcome: components['schemas']['DisputeStatus'];
+    };
+    RevokeRequest: {
+      /** Format: uuid */
+      subject_id: string;
+    };
+    RoomResponse: {
+      constraint_type: string;
+      created_at: string;
+      description?: string | null;
+      eligibility_topic: string;
+      engine_config: unknown;
+      engine_type: string;
+      /** Format: uuid */
+      id: string;
+      name: string;
+      /** Format: uuid */
+      owner_id?: string | null;
+      /** Format: int32 */
+      poll_duration_secs?: number | null;
+      status: string;
+    };
+    RotateDeviceRequest: {
+      /**
+       * @description Base64url-encoded root key's signature over the new public key, or
+       *     over `new_pubkey || certificate_timestamp` when that is set
+       */
+      certificate: string;
+      /**
+       * Format: int64
+       * @description Unix seconds bound into a version 2 certificate; must be within the
+       *     clock skew window. Required once the rotating device has declared
+       *     `cert-v2`.
+       */
+      certificate_timestamp?: number | null;
+      /** @description Base64url-encoded Ed25519 public key of the replacement key pair */
+      new_pubkey: string;
+      /**
+       * @description Base64url-encoded current device key's signature over the rotation
+       *     message (`tc-device-rotation-v1 || 0x00 || kid || 0x00 || new_pubkey`)
+       */
+      rotation_signature: string;
+    };
+    RotateDeviceResponse: {
+      created_at: string;
+      device_kid: string;
+      rotated_from_kid: string;
+    };
+    /** @description One itemized contribution to a score. */
+    ScoreComponent: {
+      kind: components['schemas']['ScoreComponentKind'];
+      /**
+       * Format: double
+       * @description Points this component adds; negative for penalties.
+       */
+      points: number;
+      /**
+       * @description What was measured, e.g. `endorsements:local_resident`,
+       *     `path_diversity`, `verification:phone` or `strike:spam`.
+       */
+      source: string;
+      /**
+       * Format: double
+       * @description The raw measurement (a count, centrality, distance, or a strike's
+       *     full penalty).
+       */
+      value: number;
+    };
+    /**
+     * @description Which part of the model a component comes from.
+     * @enum {string}
+     */
+    ScoreComponentKind: 'aggregate' | 'trust_signal' | 'verification' | 'penalty';
+    ScoreSnapshotResponse: {
+      computed_at: string;
+      /** Format: uuid */
+      context_user_id?: string | null;
+      /** Format: float */
+      eigenvector_centrality?: number | null;
+      /** Format: int32 */
+      path_diversity?: number | null;
+      /** Format: float */
+      trust_distance?: number | null;
+    };
+    ScoresResponse: {
+      scores: components['schemas']['ScoreSnapshotResponse'][];
+    };
+    SecurityCheckupResponse: {
+      api_tokens: components['schemas']['ApiTokenPosture'];
+      backup: components['schemas']['BackupPosture'];
+      devices: components['schemas']['DevicePosture'];
+      /**
+       * @description Device additions and revocations in the last `RECENT_EVENT_DAYS` days,
+       *     newest first
+       */
+      recent_events: components['schemas']['SecurityEvent'][];
+      recommendations: components['schemas']['Recommendation'][];
+    };
+    /** @description A recent device change the account owner should recognize. */
+    SecurityEvent: {
+      at: string;
+      /** @description Device that performed a revocation, if recorded */
+      by_kid?: string | null;
+      device_kid: string;
+      device_name: string;
+      kind: components['schemas']['SecurityEventKind'];
+    };
+    /** @enum {string} */
+    SecurityEventKind: 'device_added' | 'device_revoked';
+    SetRecoveryCodeRequest: {
+      /** @description New recovery code (16-128 characters), or `null` to stop requiring one */
+      recovery_code?: string | null;
+      /**
+       * @description Base64url root key signature over the recovery code message for this
+       *     account and the request's `X-Timestamp`
+       */
+      root_signature: string;
+    };
+    /** @description The latest link of an account's sigchain. */
+    SigchainHead: {
+      /** @description Base64url hash of the latest link; absent for an empty chain */
+      hash?: string | null;
+      /**
+       * Format: int64
+       * @description 0 for an empty chain
+       */
+      seqno: number;
+    };
+    /** @description One link of an account's sigchain. */
+    SigchainLink: {
+      /** Format: date-time */
+      created_at: string;
+      /** @description JSON text of the signer's signed request, hashed byte for byte */
+      envelope: string;
+      /** @description Base64url link hash */
+      hash: string;
+      link_type: string;
+      /** @description Base64url hash of the previous link; absent for seqno 1 */
+      prev_hash?: string | null;
+      /** Format: int64 */
+      seqno: number;
+      signer_kid: string;
+    };
+    /** @description A page of sigchain links, oldest first. */
+    SigchainPage: {
+      links: components['schemas']['SigchainLink'][];
+      /**
+       * Format: int64
+       * @description Pass as `since_seqno` for the next page; absent on the last page
+       */
+      next_since_seqno?: number | null;
+    };
+    /** @description Backup data included in signup request */
+    SignupBackup: {
+      /** @description Base64url-encoded encrypted backup envelope */
+      encrypted_blob: string;
+    };
+    /** @description Device data included in signup request */
+    SignupDevice: {
+      /**
+       * @description Envelope versions this client can produce, e.g. `["sig-v2", "cert-v2"]`;
+       *     see [`crate::identity::capabilities`]
+       */
+      capabilities?: string[];
+      /** @description Base64url-encoded certificate (root key's signature over canonical cert message) */
+      certificate: string;
+      /** @description User-provided device name */
+      name: string;
+      /** @description Base64url-encoded Ed25519 public key */
+      pubkey: string;
+    };
+    /** @description Invite metadata returned in responses (never includes the code itself) */
+    SignupInviteInfo: {
+      created_at: string;
+      expires_at: string;
+      /** Format: uuid */
+      id: string;
+      redeemed_at?: string | null;
+      /**
+       * Format: uuid
+       * @description Account created with this code, if redeemed
+       */
+      redeemed_by?: string | null;
+    };
+    SignupInviteListResponse: {
+      invites: components['schemas']['SignupInviteInfo'][];
+    };
+    /** @description Signup request payload — atomic creation of account + backup + first device */
+    SignupRequest: {
+      backup: components['schemas']['SignupBackup'];
+      device: components['schemas']['SignupDevice'];
+      /** @description Invite code; required when the instance is invite-only */
+      invite_code?: string | null;
+      /** @description Base64url-encoded root Ed25519 public key */
+      root_pubkey: string;
+      username: string;
+    };
+    /** @description Signup response */
+    SignupResponse: {
+      /** Format: uuid */
+      account_id: string;
+      device_kid: string;
+      root_kid: string;
+    };
+    SoftwareInfo: {
+      name: string;
+      version: string;
+    };
+    /** @description A trust edge touching the caller that is due for re-confirmation. */
+    StaleEdgeResponse: {
+      /**
+       * Format: double
+       * @description Multiplier currently applied to `weight` in trust computations
+       */
+      decay_factor: number;
+      /** Format: double */
+      effective_weight: number;
+      /** Format: uuid */
+      endorser_id: string;
+      endorser_username: string;
+      last_confirmed_at: string;
+      /** Format: uuid */
+      subject_id: string;
+      subject_username: string;
+      /** Format: float */
+      weight: number;
+    };
+    StartVerificationRequest: {
+      /** @description Email address, or phone number in international form */
+      destination: string;
+    };
+    StartVerificationResponse: {
+      /** @description When the code stops being accepted */
+      expires_at: string;
+      method: components['schemas']['VerificationMethod'];
+    };
+    /**
+     * @description What a step-up challenge confirms.
+     * @enum {string}
+     */
+    StepUpAction: 'enroll_phone' | 'revoke_device' | 'remove_phone';
+    StepUpChallengeResponse: {
+      action: components['schemas']['StepUpAction'];
+      /** Format: uuid */
+      challenge_id: string;
+      /** @description Set once the code has been confirmed */
+      confirmed_at?: string | null;
+      expires_at: string;
+      /** @description Phone the code was sent to */
+      phone_hint: string;
+    };
+    StepUpPhoneResponse: {
+      enrolled: boolean;
+      enrolled_at?: string | null;
+      /** @description Last four digits of the enrolled phone, e.g. `•••4567` */
+      phone_hint?: string | null;
+    };
+    /**
+     * @description An entry in a strike's audit trail. Stored in
+     *     `reputation__strike_events.action`.
+     * @enum {string}
+     */
+    StrikeAction: 'issued' | 'appealed' | 'appeal_granted' | 'appeal_denied';
+    StrikeEventResponse: {
+      action: components['schemas']['StrikeAction'];
+      created_at: string;
+      note?: string | null;
+    };
+    /**
+     * @description Why a strike was issued. Stored in `reputation__strikes.reason_code`.
+     * @enum {string}
+     */
+    StrikeReason:
+      | 'spam'
+      | 'harassment'
+      | 'false_endorsement'
+      | 'impersonation'
+      | 'vote_manipulation'
+      | 'other';
+    StrikeResponse: {
+      /** Format: uuid */
+      account_id: string;
+      appeal_statement?: string | null;
+      appeal_status: components['schemas']['AppealStatus'];
+      /**
+       * Format: double
+       * @description Points deducted now, after decay; zero once expired or overturned
+       */
+      current_penalty: number;
+      expires_at: string;
+      /** @description Audit trail, oldest first. Only included for the struck account. */
+      history: components['schemas']['StrikeEventResponse'][];
+      /** Format: uuid */
+      id: string;
+      issued_at: string;
+      note?: string | null;
+      /**
+       * Format: double
+       * @description Points deducted when issued
+       */
+      penalty: number;
+      reason_code: components['schemas']['StrikeReason'];
+    };
+    StrikesListResponse: {
+      strikes: components['schemas']['StrikeResponse'][];
+    };
+    SuggestionResponse: {
+      /** Format: uuid */
+      account_id: string;
+      created_at: string;
+      evidence_ids: string[];
+      filter_reason?: string | null;
+      /** Format: uuid */
+      id: string;
+      /** Format: uuid */
+      poll_id: string;
+      processed_at?: string | null;
+      /** Format: uuid */
+      room_id: string;
+      status: string;
+      suggestion_text: string;
+    };
+    /** @description Form body of `POST /oauth/token` (`application/x-www-form-urlencoded`). */
+    TokenRequest: {
+      client_id: string;
+      client_secret: string;
+      code: string;
+      code_verifier: string;
+      /** @description Must be `authorization_code` */
+      grant_type: string;
+      redirect_uri: string;
+    };
+    TokenResponse: {
+      /** @description Opaque bearer token for the read APIs covered by `scope` */
+      access_token: string;
+      /**
+       * Format: uuid
+       * @description The account that approved the client
+       */
+      account_id: string;
+      /** Format: int64 */
+      expires_in: number;
+      reputation_tier: components['schemas']['ReputationTier'];
+      /** @description Space-separated scopes granted */
+      scope: string;
+      /** @description Always `Bearer` */
+      token_type: string;
+    };
+    /** @description Request count for one token over the requested window */
+    TokenUsageInfo: {
+      /** Format: uuid */
+      account_id: string;
+      /** Format: int64 */
+      requests: number;
+      /** Format: uuid */
+      token_id: string;
+      token_name: string;
+      username: string;
+    };
+    TokenUsageResponse: {
+      /** @description First UTC day included in the counts (YYYY-MM-DD) */
+      since: string;
+      tokens: components['schemas']['TokenUsageInfo'][];
+    };
+    TopicEndorsementCount: {
+      /** Format: int64 */
+      endorsements: number;
+      topic: string;
+    };
+    TopicFeedItemResponse: {
+      created_at: string;
+      /** Format: uuid */
+      endorsement_id: string;
+      /** Format: uuid */
+      id: string;
+      /**
+       * Format: uuid
+       * @description Verifier account that issued the endorsement, if any
+       */
+      issuer_id?: string | null;
+      /** @description Whether the endorsement has been revoked since it was issued */
+      revoked: boolean;
+      /** Format: uuid */
+      subject_id: string;
+      subject_username: string;
+      topic: string;
+    };
+    TopicFeedResponse: {
+      /** @description Snapshot instant shared by every page of this walk */
+      as_of: string;
+      /** @description Endorsements in subscribed topics, newest first */
+      items: components['schemas']['TopicFeedItemResponse'][];
+      /** @description One entry per subscribed topic, alphabetical */
+      movements: components['schemas']['TopicMovementResponse'][];
+      /** @description Pass back as `cursor` to fetch the next page; absent on the last page */
+      next_cursor?: string | null;
+    };
+    /** @description Endorsement counts for one subscribed topic. */
+    TopicMovementResponse: {
+      /**
+       * Format: int64
+       * @description Active endorsements now
+       */
+      active: number;
+      /**
+       * Format: int64
+       * @description Endorsements issued in the last 7 days
+       */
+      added: number;
+      /**
+       * Format: int64
+       * @description Endorsements revoked in the last 7 days
+       */
+      revoked: number;
+      topic: string;
+    };
+    TopicSubscriptionResponse: {
+      subscribed_at: string;
+      topic: string;
+    };
+    TopicSubscriptionsResponse: {
+      /** @description Alphabetical by topic */
+      subscriptions: components['schemas']['TopicSubscriptionResponse'][];
+    };
+    TrustDistanceResponse: {
+      /** @description Whether the answer came from the precomputed table or a live traversal */
+      origin: components['schemas']['DistanceOrigin'];
+      /** Format: uuid */
+      target_id: string;
+      /**
+       * Format: float
+       * @description Minimum weighted trust distance from the caller; null if unreachable
+       */
+      trust_distance?: number | null;
+    };
+    UndeleteAccountRequest: {
+      /**
+       * @description Base64url root key signature over the account undeletion message for
+       *     this account and `timestamp`
+       */
+      root_signature: string;
+      /**
+       * Format: int64
+       * @description Unix seconds; must be within the server's clock skew window
+       */
+      timestamp: number;
+      username: string;
+    };
+    UndeleteAccountResponse: {
+      /** Format: uuid */
+      account_id: string;
+      reactivated_at: string;
+    };
+    UpdateClientRequest: {
+      name?: string | null;
+      /** @description Replaces the whole list when present */
+      redirect_uris?: string[] | null;
+      /** @description Replaces the whole list when present */
+      scopes?: string[] | null;
+    };
+    /** @description Preferences to change; categories not listed are left as they are. */
+    UpdatePreferencesRequest: {
+      preferences: components['schemas']['NotificationPreference'][];
+    };
+    UsernameAvailabilityResponse: {
+      available: boolean;
+      /** @description The rule the name breaks, for `invalid` */
+      message?: string | null;
+      /** @description The name as checked, trimmed */
+      name: string;
+      reason?: null | components['schemas']['UsernameUnavailableReason'];
+    };
+    /**
+     * @description Why a name cannot be claimed
+     *
+     *     Reserved, taken and recently released names all report `unavailable`, so
+     *     an anonymous caller cannot tell a name held by an account from one the
+     *     instance reserved or quarantined. That a valid name is unavailable at all
+     *     is no more than signup's 409 reveals. The cost is that clients cannot tell
+     *     the user why a valid name is refused.
+     * @enum {string}
+     */
+    UsernameUnavailableReason: 'invalid' | 'unavailable';
+    /**
+     * @description A way of verifying an account, weakest first.
+     * @enum {string}
+     */
+    VerificationMethod: 'email' | 'phone' | 'in_person' | 'government_id';
+    VerificationResponse: {
+      method: components['schemas']['VerificationMethod'];
+      /** @description Moderators and the owner only */
+      provider?: string | null;
+      /** @description Moderators and the owner only */
+      verified_at?: string | null;
+    };
+    /** @description Statement binding a device KID to an account, backed by user signatures. */
+    VerificationStatement: {
+      /** @description base64url root signature over the device key (see the delegation endpoint) */
+      certificate: string;
+      /** Format: int64 */
+      certificate_timestamp?: number | null;
+      /** Format: int32 */
+      certificate_version?: number | null;
+      /** @description Challenge exactly as supplied */
+      challenge: string;
+      delegation: components['schemas']['DelegationStatus'];
+      /** @description base64url device public key */
+      device_pubkey: string;
+      device_status: components['schemas']['DeviceStatus'];
+      kid: string;
+      root_kid: string;
+      /** @description base64url account root public key */
+      root_pubkey: string;
+      /** @description Whether `signature` verifies over the challenge message */
+      signature_valid: boolean;
+      username: string;
+      /**
+       * @description `true` only when the signature is valid, the device is active, and the
+       *     delegation certificate verifies
+       */
+      verified: boolean;
+    };
+    VerificationsResponse: {
+      /** Format: uuid */
+      account_id: string;
+      /**
+       * Format: int64
+       * @description Accounts that have attested to meeting this one in person
+       */
+      in_person_attestations: number;
+      /**
+       * Format: int32
+       * @description Attestations needed for in-person verification
+       */
+      in_person_attestations_required: number;
+      level?: null | components['schemas']['VerificationMethod'];
+      /** @description Completed methods, weakest first */
+      verifications: components['schemas']['VerificationResponse'][];
+    };
+    VerifierKey: {
+      name: string;
+      /** @description Base64url Ed25519 public key */
+      public_key: string;
+    };
+    VoteResponse: {
+      /** Format: uuid */
+      dimension_id: string;
+      updated_at: string;
+      /** Format: float */
+      value: number;
+    };
+  };
+  responses: never;
+  parameters: never;
+  requestBodies: never;
+  headers: never;
+  pathItems: never;
+}
+export type $defs = Record<string, never>;
+export interface operations {
+  get_instance_document: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Instance discovery document */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['InstanceDocument'];
+        };
+      };
+    };
+  };
+  account_lookup: {
+    parameters: {
+      query: {
+        /** @description Username or alias to look up */
+        username: string;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Account found */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AccountLookupResponse'];
+        };
+      };
+      /** @description Invalid username */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description User not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_account_profile: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account to show */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Account profile */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AccountProfileResponse'];
+        };
+      };
+      /** @description Signed request failed verification */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_account_crl: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account whose revoked devices to list */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Device revocation list */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DeviceCrl'];
+        };
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  change_account_status_as_admin: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account to change */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ChangeAccountStatusRequest'];
+      };
+    };
+    responses: {
+      /** @description Status changed */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AccountStatusResponse'];
+        };
+      };
+      /** @description Invalid request */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin, or targets their own account */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Transition not allowed from the current status */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  issue_strike: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account to strike */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['IssueStrikeRequest'];
+      };
+    };
+    responses: {
+      /** @description Strike issued */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StrikeResponse'];
+        };
+      };
+      /** @description Invalid note or duration */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account moderator, or targets their own account */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  account_verifications: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account to look up */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Completed verification methods */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['VerificationsResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description API token lacks the trust:read scope */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  attest_in_person: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account the caller met */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Attestation recorded */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['InPersonAttestationResponse'];
+        };
+      };
+      /** @description Cannot attest to your own account */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not verified in person or by ID */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Already attested to this account */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  verify: {
+    parameters: {
+      query: {
+        /** @description Device KID that signed the challenge */
+        kid: string;
+        /** @description Challenge issued by the third party (16-256 printable ASCII chars) */
+        challenge: string;
+        /** @description base64url Ed25519 signature by the device over the challenge message */
+        signature: string;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Verification statement */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['VerificationStatement'];
+        };
+      };
+      /** @description Malformed KID, challenge, or signature */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unknown device KID */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Too many verification requests */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  change_own_status: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ChangeAccountStatusRequest'];
+      };
+    };
+    responses: {
+      /** @description Status changed */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AccountStatusResponse'];
+        };
+      };
+      /** @description Invalid request */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account is not active */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Transition not allowed from the current status */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  delete_account: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['DeleteAccountRequest'];
+      };
+    };
+    responses: {
+      /** @description Account deactivated */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DeleteAccountResponse'];
+        };
+      };
+      /** @description Invalid request or root signature */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account is not active */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account status changed concurrently */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  change_username: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ChangeUsernameRequest'];
+      };
+    };
+    responses: {
+      /** @description Username changed */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ChangeUsernameResponse'];
+        };
+      };
+      /** @description Invalid or unchanged username */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Name taken by another account, or recently given up by one */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Changed too recently; Retry-After gives the seconds left */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  undelete_account: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['UndeleteAccountRequest'];
+      };
+    };
+    responses: {
+      /** @description Account restored */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['UndeleteAccountResponse'];
+        };
+      };
+      /** @description Invalid request, stale timestamp, or replay */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Invalid credentials */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account is not scheduled for deletion */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Grace period has ended */
+      410: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_sigchain: {
+    parameters: {
+      query?: {
+        /** @description Return links after this seqno (default 0: from the start). */
+        since_seqno?: number | null;
+        /** @description Page size (1–200, default 50). */
+        limit?: number | null;
+      };
+      header?: never;
+      path: {
+        /** @description Account whose sigchain to read */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Sigchain links */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['SigchainPage'];
+        };
+      };
+      /** @description Invalid since_seqno or limit */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Not this account's device */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_sigchain_head: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account whose sigchain head to read */
+        account_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Sigchain head */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['SigchainHead'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Not this account's device */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_aliases: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Alias list */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AliasListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_alias: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateAliasRequest'];
+      };
+    };
+    responses: {
+      /** @description Alias added */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AliasInfo'];
+        };
+      };
+      /** @description Invalid alias */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Handle already taken by a username or alias, or recently given up by another account */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Maximum alias limit reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  delete_alias: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Alias to remove */
+        alias: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Alias removed */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Alias is reserved */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Alias not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_backup: {
+    parameters: {
+      query?: never;
+      header?: {
+        /** @description Recovery code, if the account requires one */
+        'X-Recovery-Code'?: string | null;
+      };
+      path: {
+        /** @description Username to fetch backup for */
+        username: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Encrypted backup (real or synthetic) */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['BackupResponse'];
+        };
+      };
+      /** @description Invalid username */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  set_recovery_code: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['SetRecoveryCodeRequest'];
+      };
+    };
+    responses: {
+      /** @description Recovery code updated */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Invalid recovery code or root signature */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account has no backup */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_recovery_backup: {
+    parameters: {
+      query?: never;
+      header?: {
+        /** @description Recovery code, if the account requires one */
+        'X-Recovery-Code'?: string | null;
+      };
+      path: {
+        /** @description Root key ID of the account */
+        root_kid: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Encrypted backup and KDF parameters (real or synthetic) */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['RecoveryBackupResponse'];
+        };
+      };
+      /** @description Invalid KID */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Rate limit exceeded */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_challenge: {
+    parameters: {
+      query: {
+        /** @description KID of the device that will sign the challenge */
+        kid: string;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Challenge issued */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AuthChallengeResponse'];
+        };
+      };
+      /** @description Invalid KID */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Challenges are disabled */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Rate limit exceeded */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_devices: {
+    parameters: {
+      query?: {
+        /** @description Include revoked devices (default true) */
+        include_revoked?: boolean;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Device list */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DeviceListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description API token lacks the devices:read scope */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  add_device: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AddDeviceRequest'];
+      };
+    };
+    responses: {
+      /** @description Device added */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AddDeviceResponse'];
+        };
+      };
+      /** @description Invalid request */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller declared cert-v2 but sent a version 1 certificate (CERTIFICATE_UPGRADE_REQUIRED) */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device key already registered */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Maximum device limit reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  revoke_device: {
+    parameters: {
+      query?: never;
+      header?: {
+        /** @description Confirmed revoke_device challenge */
+        'x-step-up-challenge'?: string | null;
+      };
+      path: {
+        /** @description Key identifier of the device to revoke */
+        kid: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Device revoked */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Invalid KID */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Step-up confirmation required (code STEP_UP_REQUIRED) */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device already revoked */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Cannot revoke the device making this request */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  rename_device: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Key identifier of the device to rename */
+        kid: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['RenameDeviceRequest'];
+      };
+    };
+    responses: {
+      /** @description Device renamed */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Invalid request */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Cannot rename a revoked device */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_device_delegation: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Key identifier of the device to inspect */
+        kid: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Delegation envelope */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DelegationResponse'];
+        };
+      };
+      /** @description Invalid KID */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description API token lacks the devices:read scope */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  rotate_device: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Key identifier of the signing device */
+        kid: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['RotateDeviceRequest'];
+      };
+    };
+    responses: {
+      /** @description Device key rotated */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['RotateDeviceResponse'];
+        };
+      };
+      /** @description Invalid request, certificate, or rotation signature */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description A device can only rotate its own key, or it declared cert-v2 but sent a version 1 certificate (CERTIFICATE_UPGRADE_REQUIRED) */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description New key already registered or device already revoked */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  authorize: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Authorization URL generated */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AuthorizeResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  callback: {
+    parameters: {
+      query?: {
+        /** @description Authorization code from ID.me */
+        code?: string;
+        /** @description HMAC-signed state parameter */
+        state?: string;
+        /** @description Error code from ID.me */
+        error?: string;
+        /** @description Human-readable error description */
+        error_description?: string;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Redirect to frontend with verification result */
+      302: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  login: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['LoginRequest'];
+      };
+    };
+    responses: {
+      /** @description Login successful */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['LoginResponse'];
+        };
+      };
+      /** @description Validation error or replay detected */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Invalid credentials */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account is suspended, locked or deleted */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Device key already registered */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Maximum device limit reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_released_usernames: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Names still cooling off, most recently released first */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ReleasedUsernamesResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  lift_username_quarantine: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Released name to make claimable */
+        name: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Name is claimable again */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Name was never released */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_reserved_usernames: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Reserved names, alphabetically */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ReservedUsernamesResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  reserve_username: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ReserveUsernameRequest'];
+      };
+    };
+    responses: {
+      /** @description Name reserved */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ReservedUsernameInfo'];
+        };
+      };
+      /** @description Invalid name or reason */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Name already reserved */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  release_username: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Reserved name to release */
+        name: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Name released */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Name is not reserved */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  security_checkup: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Security checkup */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['SecurityCheckupResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_session: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Session issued */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['CreateSessionResponse'];
+        };
+      };
+      /** @description Unauthorized, or not a signed request */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Sessions are disabled, the device is revoked, or the account is not active */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  revoke_sessions: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Sessions revoked */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized, or not a signed request */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  signup: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['SignupRequest'];
+      };
+    };
+    responses: {
+      /** @description Account created */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['SignupResponse'];
+        };
+      };
+      /** @description Validation error */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Invite code missing or invalid on an invite-only instance */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Username or key already registered */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Maximum device limit reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_signup_invites: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Invite list */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['SignupInviteListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_signup_invite: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Invite issued */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['CreateSignupInviteResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Outstanding invite quota reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_step_up_challenge: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateStepUpChallengeRequest'];
+      };
+    };
+    responses: {
+      /** @description Code sent */
+      202: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StepUpChallengeResponse'];
+        };
+      };
+      /** @description Action cannot be requested directly */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description No phone enrolled */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description SMS is not configured or sending failed */
+      503: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  confirm_step_up_challenge: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Challenge to confirm */
+        challenge_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ConfirmStepUpChallengeRequest'];
+      };
+    };
+    responses: {
+      /** @description Challenge confirmed */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StepUpChallengeResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Incorrect code */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Challenge not found or expired */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Challenge already confirmed */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Too many incorrect codes */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_step_up_phone: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Enrolled phone, if any */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StepUpPhoneResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  enroll_step_up_phone: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['EnrollStepUpPhoneRequest'];
+      };
+    };
+    responses: {
+      /** @description Code sent */
+      202: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StepUpChallengeResponse'];
+        };
+      };
+      /** @description Invalid phone number */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description A phone is already enrolled */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description SMS is not configured or sending failed */
+      503: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  remove_step_up_phone: {
+    parameters: {
+      query?: never;
+      header?: {
+        /** @description Confirmed remove_phone challenge */
+        'x-step-up-challenge'?: string | null;
+      };
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Phone removed */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Step-up confirmation required (code STEP_UP_REQUIRED) */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description No phone enrolled */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_tokens: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Token list */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ApiTokenListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_token: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateApiTokenRequest'];
+      };
+    };
+    responses: {
+      /** @description Token issued */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['CreateApiTokenResponse'];
+        };
+      };
+      /** @description Invalid request */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Maximum token limit reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  top_token_consumers: {
+    parameters: {
+      query?: {
+        /** @description Days of usage to include, counting today (1–90, default 30) */
+        days?: number | null;
+        /** @description Maximum tokens to return (1–100, default 20) */
+        limit?: number | null;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Top consumers, busiest first */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['TokenUsageResponse'];
+        };
+      };
+      /** @description Invalid query */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  revoke_token: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description ID of the token to revoke */
+        id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Token revoked */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Token not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Token already revoked */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  check_username_availability: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Username to check */
+        name: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Availability of the name */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['UsernameAvailabilityResponse'];
+        };
+      };
+      /** @description Rate limited */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_badge: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Username or alias followed by `.svg` or `.json` */
+        file: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Badge rendered as SVG or JSON */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['BadgeResponse'];
+        };
+      };
+      /** @description Alias; redirects to the canonical username's badge */
+      301: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Cached badge is still current */
+      304: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Signed request failed verification */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unknown account or badge format */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Too many badge requests */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_build_info: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Build information retrieved successfully */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['BuildInfo'];
+        };
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ProblemDetails'];
+        };
+      };
+    };
+  };
+  get_changelog: {
+    parameters: {
+      query?: {
+        /** @description Only list releases newer than this API revision, e.g. `1.2.0` */
+        since?: string | null;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Changelog, newest release first */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ChangelogResponse'];
+        };
+      };
+      /** @description Invalid since version */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_clients: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description The caller's clients */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ClientListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_client: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateClientRequest'];
+      };
+    };
+    responses: {
+      /** @description Client registered */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ClientWithSecretResponse'];
+        };
+      };
+      /** @description Invalid name, redirect URI or scope */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Maximum client limit reached */
+      422: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  revoke_client: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Public client ID */
+        client_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Client revoked */
+      204: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Client not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Client already revoked */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  update_client: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Public client ID */
+        client_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['UpdateClientRequest'];
+      };
+    };
+    responses: {
+      /** @description Client updated */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ClientInfo'];
+        };
+      };
+      /** @description Invalid name, redirect URI or scope */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Client not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Client has been revoked */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  rotate_client_secret: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Public client ID */
+        client_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description New secret issued */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ClientWithSecretResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Client not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Client has been revoked */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  check_endorsement: {
+    parameters: {
+      query?: {
+        /** @description Account UUID to check endorsement for */
+        subject_id?: string;
+        /** @description Canonical subject instead of subject_id, e.g. `account:<uuid>`, `member:A000360`, `bill:hr-1234-118` */
+        subject?: string;
+        /** @description Endorsement topic to check */
+        topic?: string;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Endorsement check result */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['HasEndorsementResponse'];
+        };
+      };
+      /** @description Missing, conflicting, or malformed query parameters */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_open_disputes: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Open disputes, oldest first */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DisputesListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account moderator */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  dispute_endorsement: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Endorsement to dispute */
+        endorsement_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['DisputeEndorsementRequest'];
+      };
+    };
+    responses: {
+      /** @description Dispute opened */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DisputeResponse'];
+        };
+      };
+      /** @description Invalid statement */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description No active endorsement of the caller with this ID */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Endorsement already disputed */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  resolve_dispute: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Disputed endorsement */
+        endorsement_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ResolveDisputeRequest'];
+      };
+    };
+    responses: {
+      /** @description Dispute resolved */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['DisputeResponse'];
+        };
+      };
+      /** @description Invalid outcome or note */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account moderator */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Endorsement has no dispute */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Dispute already resolved */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_feed: {
+    parameters: {
+      query?: {
+        /** @description Page size (1–200, default 50). */
+        limit?: number | null;
+        /** @description Opaque cursor from the previous page's `next_cursor`. */
+        cursor?: string | null;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description One page of the ranked feed */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['FeedResponse'];
+        };
+      };
+      /** @description Invalid limit or cursor */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  list_alerts: {
+    parameters: {
+      query?: {
+        /** @description Include resolved alerts (default false) */
+        include_resolved?: boolean;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Integrity alerts */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['IntegrityAlertsResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  resolve_alert: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Integrity alert ID */
+        id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Alert resolved */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['IntegrityAlertResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account admin */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Integrity alert not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  my_endorsements: {
+    parameters: {
+      query?: {
+        /** @description Page size (1–200, default 50). */
+        limit?: number | null;
+        /** @description Opaque cursor from the previous page's `next_cursor`. */
+        cursor?: string | null;
+        /** @description RFC 3339 snapshot instant for the first page (default: now). */
+        as_of?: string | null;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description List of endorsements for the authenticated user */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['EndorsementsListResponse'];
+        };
+      };
+      /** @description Invalid pagination parameters */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description API token lacks the endorsements:read scope */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  my_strikes: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Strikes, newest first */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StrikesListResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  appeal_strike: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Strike to appeal */
+        strike_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AppealStrikeRequest'];
+      };
+    };
+    responses: {
+      /** @description Appeal filed */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StrikeResponse'];
+        };
+      };
+      /** @description Invalid statement */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description No strike against the caller with this ID */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Strike already appealed or expired */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  my_verifications: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Completed verification methods */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['VerificationsResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  confirm_verification: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description `email` or `phone` */
+        method: components['schemas']['VerificationMethod'];
+      };
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ConfirmVerificationRequest'];
+      };
+    };
+    responses: {
+      /** @description Verification recorded */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['VerificationResponse'];
+        };
+      };
+      /** @description Method does not use a code */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Incorrect code */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description No pending code */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description The destination already verifies another account */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Too many incorrect codes */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    InviteResponse: {
-      accepted_at?: string | null;
-      /** Format: uuid */
-      accepted_by?: string | null;
-      delivery_method: string;
-      expires_at: string;
-      /** Format: uuid */
-      id: string;
+  };
+  start_verification: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description `email` or `phone` */
+        method: components['schemas']['VerificationMethod'];
+      };
+      cookie?: never;
     };
-    InvitesResponse: {
-      invites: components['schemas']['InviteResponse'][];
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['StartVerificationRequest'];
+      };
     };
-    /** @description Device data for login */
-    LoginDevice: {
-      /** @description Base64url-encoded certificate (root key's signature over `device_pubkey || timestamp`) */
-      certificate: string;
-      /** @description User-provided device name */
-      name: string;
-      /** @description Base64url-encoded Ed25519 public key */
-      pubkey: string;
+    responses: {
+      /** @description Code sent */
+      202: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['StartVerificationResponse'];
+        };
+      };
+      /** @description Method does not use a code, or invalid destination */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description A code was sent recently */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Code delivery is not configured or failed */
+      503: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    /** @description Login request payload */
-    LoginRequest: {
-      device: components['schemas']['LoginDevice'];
-      /**
-       * Format: int64
-       * @description Unix timestamp (seconds) — must be within ±300s of server time
-       */
-      timestamp: number;
-      username: string;
+  };
+  list_notifications: {
+    parameters: {
+      query?: {
+        /** @description Page size (1–200, default 50). */
+        limit?: number | null;
+        /** @description Opaque cursor from the previous page's `next_cursor`. */
+        cursor?: string | null;
+        /** @description RFC 3339 snapshot instant for the first page (default: now). */
+        as_of?: string | null;
+        /** @description Only return unread notifications (default false). */
+        unread_only?: boolean;
+      };
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    /** @description Login response */
-    LoginResponse: {
-      /** Format: uuid */
-      account_id: string;
-      device_kid: string;
-      root_kid: string;
+    requestBody?: never;
+    responses: {
+      /** @description One page of notifications, newest first */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['NotificationsResponse'];
+        };
+      };
+      /** @description Invalid pagination parameters */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    MessageResponse: {
-      message: string;
+  };
+  get_preferences: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    MyCapabilitiesResponse: {
-      can_configure: boolean;
-      can_vote: boolean;
-      next_step?: string | null;
-      reason?: string | null;
-      role: string;
+    requestBody?: never;
+    responses: {
+      /** @description Notification preferences */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['NotificationPreferencesResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    PollDetailResponse: {
-      dimensions: components['schemas']['DimensionDetailResponse'][];
-      poll: components['schemas']['PollResponse'];
+  };
+  update_preferences: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    PollDistributionResponse: {
-      dimensions: components['schemas']['DimensionDistributionResponse'][];
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['UpdatePreferencesRequest'];
+      };
     };
-    PollResponse: {
-      activated_at?: string | null;
-      closes_at?: string | null;
-      created_at: string;
-      description?: string | null;
-      /** Format: uuid */
-      id: string;
-      question: string;
-      /** Format: uuid */
-      room_id: string;
-      status: string;
+    responses: {
+      /** @description Updated notification preferences */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['NotificationPreferencesResponse'];
+        };
+      };
+      /** @description Invalid request */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    PollResultsResponse: {
-      dimensions: components['schemas']['DimensionStatsResponse'][];
-      poll: components['schemas']['PollResponse'];
-      /** Format: int64 */
-      voter_count: number;
+  };
+  mark_read: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Notification ID */
+        id: string;
+      };
+      cookie?: never;
     };
-    PollStatusRequest: {
-      status: components['schemas']['PollStatusTransition'];
+    requestBody?: never;
+    responses: {
+      /** @description Notification marked read */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['MarkReadResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Notification not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    /** @enum {string} */
-    PollStatusTransition: 'active' | 'closed';
-    /** @description RFC 7807 Problem Details error response. */
-    ProblemDetails: {
-      /** @description Human-readable explanation specific to this occurrence */
-      detail: string;
-      extensions?: null | components['schemas']['ProblemExtensions'];
-      /** @description URI reference identifying the specific occurrence */
-      instance?: string | null;
-      /**
-       * Format: int32
-       * @description HTTP status code
-       */
-      status: number;
-      /** @description Short human-readable summary */
-      title: string;
-      /** @description URI reference identifying the problem type */
-      type: string;
+  };
+  authorize: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    /** @description Extended error information mapping to GraphQL error codes. */
-    ProblemExtensions: {
-      /** @description Error code matching GraphQL error codes */
-      code: string;
-      /** @description Field that caused the error (for validation errors) */
-      field?: string | null;
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AuthorizeRequest'];
+      };
     };
-    RenameDeviceRequest: {
-      name: string;
+    responses: {
+      /** @description Code issued */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['AuthorizeResponse'];
+        };
+      };
+      /** @description Unknown client, unregistered redirect URI, bad scope or PKCE parameters */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account is not active */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    RevokeRequest: {
-      /** Format: uuid */
-      subject_id: string;
+  };
+  token: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    RoomResponse: {
-      constraint_type: string;
-      created_at: string;
-      description?: string | null;
-      eligibility_topic: string;
-      engine_config: unknown;
-      engine_type: string;
-      /** Format: uuid */
-      id: string;
-      name: string;
-      /** Format: uuid */
-      owner_id?: string | null;
-      /** Format: int32 */
-      poll_duration_secs?: number | null;
-      status: string;
+    requestBody: {
+      content: {
+        'application/x-www-form-urlencoded': components['schemas']['TokenRequest'];
+      };
     };
-    ScoreSnapshotResponse: {
-      computed_at: string;
-      /** Format: uuid */
-      context_user_id?: string | null;
-      /** Format: float */
-      eigenvector_centrality?: number | null;
-      /** Format: int32 */
-      path_diversity?: number | null;
-      /** Format: float */
-      trust_distance?: number | null;
+    responses: {
+      /** @description Access token issued */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['TokenResponse'];
+        };
+      };
+      /** @description Invalid request or grant */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['OAuthErrorResponse'];
+        };
+      };
+      /** @description Client authentication failed */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['OAuthErrorResponse'];
+        };
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    ScoresResponse: {
-      scores: components['schemas']['ScoreSnapshotResponse'][];
+  };
+  get_explanation: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Account to explain */
+        account_id: string;
+      };
+      cookie?: never;
     };
-    /** @description Backup data included in signup request */
-    SignupBackup: {
-      /** @description Base64url-encoded encrypted backup envelope */
-      encrypted_blob: string;
+    requestBody?: never;
+    responses: {
+      /** @description Score, tier and the components that produced them */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ReputationExplanation'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description API token lacks the trust:read scope */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    /** @description Device data included in signup request */
-    SignupDevice: {
-      /** @description Base64url-encoded certificate (root key's signature over canonical cert message) */
-      certificate: string;
-      /** @description User-provided device name */
-      name: string;
-      /** @description Base64url-encoded Ed25519 public key */
-      pubkey: string;
+  };
+  list_exports: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    /** @description Signup request payload — atomic creation of account + backup + first device */
-    SignupRequest: {
-      backup: components['schemas']['SignupBackup'];
-      device: components['schemas']['SignupDevice'];
-      /** @description Base64url-encoded root Ed25519 public key */
-      root_pubkey: string;
-      username: string;
+    requestBody?: never;
+    responses: {
+      /** @description Export requests */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ExportRequestsResponse'];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
-    /** @description Signup response */
-    SignupResponse: {
-      /** Format: uuid */
-      account_id: string;
-      device_kid: string;
-      root_kid: string;
+  };
+  create_export: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
     };
-    SuggestionResponse: {
-      /** Format: uuid */
-      account_id: string;
-      created_at: string;
-      evidence_ids: string[];
-      filter_reason?: string | null;
-      /** Format: uuid */
-      id: string;
-      /** Format: uuid */
-      poll_id: string;
-      processed_at?: string | null;
-      /** Format: uuid */
-      room_id: string;
-      status: string;
-      suggestion_text: string;
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateExportRequest'];
+      };
     };
-    VoteResponse: {
-      /** Format: uuid */
-      dimension_id: string;
-      updated_at: string;
-      /** Format: float */
-      value: number;
+    responses: {
+      /** @description Request filed, pending approval */
+      201: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['ExportRequestResponse'];
+        };
+      };
+      /** @description Invalid dataset or purpose */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not a researcher */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Daily request limit reached */
+      429: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
   };
-  responses: never;
-  parameters: never;
-  requestBodies: never;
-  headers: never;
-  pathItems: never;
-}
-export type $defs = Record<string, never>;
-export interface operations {
-  account_lookup: {
+  approve_export: {
     parameters: {
-      query: {
-        /** @description Username to look up */
-        username: string;
-      };
+      query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Export request ID */
+        id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Account found */
+      /** @description Approved */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['AccountLookupResponse'];
+          'application/json': components['schemas']['ExportRequestResponse'];
         };
       };
-      /** @description Invalid username */
-      400: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description User not found */
+      /** @description Caller is not a research admin, or filed the request */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Export request not found */
       404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
+      /** @description Request already decided */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
       /** @description Internal server error */
       500: {
         headers: {
@@ -1225,29 +8205,50 @@ export interface operations {
       };
     };
   };
-  get_backup: {
+  download_export: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Username to fetch backup for */
-        username: string;
+        /** @description Export request ID */
+        id: string;
       };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Encrypted backup (real or synthetic) */
+      /** @description CSV export */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['BackupResponse'];
+          'text/csv': string;
         };
       };
-      /** @description Invalid username */
-      400: {
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Export not approved or approval expired */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Export request not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Download limit reached */
+      429: {
         headers: {
           [name: string]: unknown;
         };
@@ -1262,22 +8263,25 @@ export interface operations {
       };
     };
   };
-  list_devices: {
+  reject_export: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Export request ID */
+        id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Device list */
+      /** @description Rejected */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['DeviceListResponse'];
+          'application/json': components['schemas']['ExportRequestResponse'];
         };
       };
       /** @description Unauthorized */
@@ -1287,6 +8291,27 @@ export interface operations {
         };
         content?: never;
       };
+      /** @description Caller is not a research admin, or filed the request */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Export request not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Request already decided */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
       /** @description Internal server error */
       500: {
         headers: {
@@ -1296,7 +8321,34 @@ export interface operations {
       };
     };
   };
-  add_device: {
+  list_rooms: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description List of open rooms */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['RoomResponse'][];
+        };
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  create_room: {
     parameters: {
       query?: never;
       header?: never;
@@ -1305,20 +8357,20 @@ export interface operations {
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['AddDeviceRequest'];
+        'application/json': components['schemas']['CreateRoomRequest'];
       };
     };
     responses: {
-      /** @description Device added */
+      /** @description Room created */
       201: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['AddDeviceResponse'];
+          'application/json': components['schemas']['RoomResponse'];
         };
       };
-      /** @description Invalid request */
+      /** @description Validation error */
       400: {
         headers: {
           [name: string]: unknown;
@@ -1332,15 +8384,72 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Device key already registered */
+      /** @description Room name already exists */
       409: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Maximum device limit reached */
-      422: {
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_capacity: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Rooms needing content */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['RoomResponse'][];
+        };
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  get_room: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Room details */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['RoomResponse'];
+        };
+      };
+      /** @description Room not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -1355,55 +8464,73 @@ export interface operations {
       };
     };
   };
-  revoke_device: {
+  get_agenda: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Key identifier of the device to revoke */
-        kid: string;
+        /** @description Room ID */
+        room_id: string;
       };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Device revoked */
-      204: {
+      /** @description Poll agenda */
+      200: {
         headers: {
           [name: string]: unknown;
         };
-        content?: never;
+        content: {
+          'application/json': components['schemas']['PollResponse'][];
+        };
       };
-      /** @description Invalid KID */
-      400: {
+      /** @description Room not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Unauthorized */
-      401: {
+      /** @description Internal server error */
+      500: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Device not found */
-      404: {
+    };
+  };
+  my_capabilities: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description User capabilities in this room */
+      200: {
         headers: {
           [name: string]: unknown;
         };
-        content?: never;
+        content: {
+          'application/json': components['schemas']['MyCapabilitiesResponse'];
+        };
       };
-      /** @description Device already revoked */
-      409: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Cannot revoke the device making this request */
-      422: {
+      /** @description Room not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -1418,57 +8545,34 @@ export interface operations {
       };
     };
   };
-  rename_device: {
+  list_polls: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Key identifier of the device to rename */
-        kid: string;
+        /** @description Room ID */
+        room_id: string;
       };
       cookie?: never;
     };
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['RenameDeviceRequest'];
-      };
-    };
+    requestBody?: never;
     responses: {
-      /** @description Device renamed */
-      204: {
-        headers: {
-          [name: string]: unknown;
-        };
-        content?: never;
-      };
-      /** @description Invalid request */
-      400: {
+      /** @description List of polls */
+      200: {
         headers: {
           [name: string]: unknown;
         };
-        content?: never;
-      };
-      /** @description Unauthorized */
-      401: {
-        headers: {
-          [name: string]: unknown;
+        content: {
+          'application/json': components['schemas']['PollResponse'][];
         };
-        content?: never;
       };
-      /** @description Device not found */
+      /** @description Room not found */
       404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Cannot rename a revoked device */
-      409: {
-        headers: {
-          [name: string]: unknown;
-        };
-        content?: never;
-      };
       /** @description Internal server error */
       500: {
         headers: {
@@ -1478,23 +8582,37 @@ export interface operations {
       };
     };
   };
-  authorize: {
+  create_poll: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+      };
       cookie?: never;
     };
-    requestBody?: never;
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreatePollRequest'];
+      };
+    };
     responses: {
-      /** @description Authorization URL generated */
-      200: {
+      /** @description Poll created */
+      201: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['AuthorizeResponse'];
+          'application/json': components['schemas']['PollResponse'];
+        };
+      };
+      /** @description Validation error */
+      400: {
+        headers: {
+          [name: string]: unknown;
         };
+        content?: never;
       };
       /** @description Unauthorized */
       401: {
@@ -1512,26 +8630,31 @@ export interface operations {
       };
     };
   };
-  callback: {
+  get_poll_detail: {
     parameters: {
-      query?: {
-        /** @description Authorization code from ID.me */
-        code?: string;
-        /** @description HMAC-signed state parameter */
-        state?: string;
-        /** @description Error code from ID.me */
-        error?: string;
-        /** @description Human-readable error description */
-        error_description?: string;
-      };
+      query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Redirect to frontend with verification result */
-      302: {
+      /** @description Poll detail with dimensions and evidence */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['PollDetailResponse'];
+        };
+      };
+      /** @description Poll not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -1546,56 +8669,47 @@ export interface operations {
       };
     };
   };
-  login: {
+  add_dimension: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['LoginRequest'];
+        'application/json': components['schemas']['CreateDimensionRequest'];
       };
     };
     responses: {
-      /** @description Login successful */
+      /** @description Dimension added */
       201: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['LoginResponse'];
+          'application/json': components['schemas']['DimensionResponse'];
         };
       };
-      /** @description Validation error or replay detected */
+      /** @description Validation error */
       400: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Invalid credentials */
+      /** @description Unauthorized */
       401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Device key already registered */
-      409: {
-        headers: {
-          [name: string]: unknown;
-        };
-        content?: never;
-      };
-      /** @description Maximum device limit reached */
-      422: {
-        headers: {
-          [name: string]: unknown;
-        };
-        content?: never;
-      };
       /** @description Internal server error */
       500: {
         headers: {
@@ -1605,44 +8719,65 @@ export interface operations {
       };
     };
   };
-  signup: {
+  create_evidence: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+        /** @description Dimension ID */
+        dimension_id: string;
+      };
       cookie?: never;
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['SignupRequest'];
+        'application/json': components['schemas']['CreateEvidenceBody'];
       };
     };
     responses: {
-      /** @description Account created */
+      /** @description Evidence created */
       201: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['SignupResponse'];
-        };
+        content?: never;
       };
-      /** @description Validation error */
-      400: {
+      /** @description Dimension not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Username or key already registered */
-      409: {
+      /** @description Internal server error */
+      500: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Maximum device limit reached */
-      422: {
+    };
+  };
+  delete_evidence: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Evidence deleted */
+      200: {
         headers: {
           [name: string]: unknown;
         };
@@ -1657,60 +8792,75 @@ export interface operations {
       };
     };
   };
-  get_build_info: {
+  my_votes: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Build information retrieved successfully */
+      /** @description User's votes for this poll */
       200: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['BuildInfo'];
-        };
+        content: {
+          'application/json': components['schemas']['VoteResponse'][];
+        };
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Poll not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
       };
       /** @description Internal server error */
       500: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['ProblemDetails'];
-        };
+        content?: never;
       };
     };
   };
-  check_endorsement: {
+  reset_poll: {
     parameters: {
-      query?: {
-        /** @description Account UUID to check endorsement for */
-        subject_id?: string;
-        /** @description Endorsement topic to check */
-        topic?: string;
-      };
+      query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Endorsement check result */
+      /** @description Poll reset to draft */
       200: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['HasEndorsementResponse'];
-        };
+        content?: never;
       };
-      /** @description Missing required query parameters */
-      400: {
+      /** @description Poll not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -1725,26 +8875,31 @@ export interface operations {
       };
     };
   };
-  my_endorsements: {
+  get_results: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description List of endorsements for the authenticated user */
+      /** @description Poll results */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['EndorsementsListResponse'];
+          'application/json': components['schemas']['PollResultsResponse'];
         };
       };
-      /** @description Unauthorized */
-      401: {
+      /** @description Poll not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -1759,23 +8914,35 @@ export interface operations {
       };
     };
   };
-  list_rooms: {
+  get_distribution: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description List of open rooms */
+      /** @description Vote distribution */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['RoomResponse'][];
+          'application/json': components['schemas']['PollDistributionResponse'];
+        };
+      };
+      /** @description Poll not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
         };
+        content?: never;
       };
       /** @description Internal server error */
       500: {
@@ -1786,27 +8953,30 @@ export interface operations {
       };
     };
   };
-  create_room: {
+  update_poll_status: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['CreateRoomRequest'];
+        'application/json': components['schemas']['PollStatusRequest'];
       };
     };
     responses: {
-      /** @description Room created */
-      201: {
+      /** @description Status updated */
+      204: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['RoomResponse'];
-        };
+        content?: never;
       };
       /** @description Validation error */
       400: {
@@ -1822,8 +8992,8 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Room name already exists */
-      409: {
+      /** @description Poll not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -1838,22 +9008,27 @@ export interface operations {
       };
     };
   };
-  get_capacity: {
+  get_poll_traces: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Room ID */
+        room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Rooms needing content */
+      /** @description Bot traces for this poll */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['RoomResponse'][];
+          'application/json': components['schemas']['BotTraceResponse'][];
         };
       };
       /** @description Internal server error */
@@ -1865,71 +9040,64 @@ export interface operations {
       };
     };
   };
-  get_room: {
+  cast_vote: {
     parameters: {
       query?: never;
       header?: never;
       path: {
         /** @description Room ID */
         room_id: string;
+        /** @description Poll ID */
+        poll_id: string;
       };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Room details */
+      /** @description Vote recorded */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['RoomResponse'];
+          'application/json': components['schemas']['VoteResponse'][];
         };
       };
-      /** @description Room not found */
-      404: {
+      /** @description Validation error */
+      400: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-    };
-  };
-  get_agenda: {
-    parameters: {
-      query?: never;
-      header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-      };
-      cookie?: never;
-    };
-    requestBody?: never;
-    responses: {
-      /** @description Poll agenda */
-      200: {
+      /** @description Not eligible to vote */
+      403: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['PollResponse'][];
-        };
+        content?: never;
       };
-      /** @description Room not found */
+      /** @description Poll not found */
       404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
+      /** @description Poll not active */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
       /** @description Internal server error */
       500: {
         headers: {
@@ -1939,7 +9107,7 @@ export interface operations {
       };
     };
   };
-  my_capabilities: {
+  assign_role: {
     parameters: {
       query?: never;
       header?: never;
@@ -1949,15 +9117,19 @@ export interface operations {
       };
       cookie?: never;
     };
-    requestBody?: never;
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AssignRoleRequest'];
+      };
+    };
     responses: {
-      /** @description User capabilities in this room */
+      /** @description Role assigned */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['MyCapabilitiesResponse'];
+          'application/json': components['schemas']['AssignRoleResponse'];
         };
       };
       /** @description Unauthorized */
@@ -1967,6 +9139,13 @@ export interface operations {
         };
         content?: never;
       };
+      /** @description Only room owner can assign roles */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
       /** @description Room not found */
       404: {
         headers: {
@@ -1983,7 +9162,7 @@ export interface operations {
       };
     };
   };
-  list_polls: {
+  list_suggestions: {
     parameters: {
       query?: never;
       header?: never;
@@ -1995,21 +9174,14 @@ export interface operations {
     };
     requestBody?: never;
     responses: {
-      /** @description List of polls */
+      /** @description List of suggestions */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['PollResponse'][];
-        };
-      };
-      /** @description Room not found */
-      404: {
-        headers: {
-          [name: string]: unknown;
+          'application/json': components['schemas']['SuggestionResponse'][];
         };
-        content?: never;
       };
       /** @description Internal server error */
       500: {
@@ -2020,7 +9192,7 @@ export interface operations {
       };
     };
   };
-  create_poll: {
+  create_suggestion: {
     parameters: {
       query?: never;
       header?: never;
@@ -2032,20 +9204,20 @@ export interface operations {
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['CreatePollRequest'];
+        'application/json': components['schemas']['CreateSuggestionRequest'];
       };
     };
     responses: {
-      /** @description Poll created */
+      /** @description Suggestion created */
       201: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['PollResponse'];
+          'application/json': components['schemas']['SuggestionResponse'];
         };
       };
-      /** @description Validation error */
+      /** @description Validation error or daily limit reached */
       400: {
         headers: {
           [name: string]: unknown;
@@ -2068,31 +9240,77 @@ export interface operations {
       };
     };
   };
-  get_poll_detail: {
+  get_stats: {
     parameters: {
-      query?: never;
+      query?: {
+        /** @description Number of most recent days to return (default 30, max 366). */
+        days?: number | null;
+      };
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody?: never;
+    responses: {
+      /** @description Daily instance statistics */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['InstanceStatsResponse'];
+        };
+      };
+      /** @description Invalid window */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Signed request failed verification */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
       };
+    };
+  };
+  list_pending_appeals: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Poll detail with dimensions and evidence */
+      /** @description Pending appeals, oldest first */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['PollDetailResponse'];
+          'application/json': components['schemas']['StrikesListResponse'];
         };
       };
-      /** @description Poll not found */
-      404: {
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Caller is not an account moderator */
+      403: {
         headers: {
           [name: string]: unknown;
         };
@@ -2107,34 +9325,32 @@ export interface operations {
       };
     };
   };
-  add_dimension: {
+  resolve_appeal: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
+        /** @description Appealed strike */
+        strike_id: string;
       };
       cookie?: never;
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['CreateDimensionRequest'];
+        'application/json': components['schemas']['ResolveAppealRequest'];
       };
     };
     responses: {
-      /** @description Dimension added */
-      201: {
+      /** @description Appeal resolved */
+      200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['DimensionResponse'];
+          'application/json': components['schemas']['StrikeResponse'];
         };
       };
-      /** @description Validation error */
+      /** @description Invalid outcome or note */
       400: {
         headers: {
           [name: string]: unknown;
@@ -2148,44 +9364,22 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Caller is not an account moderator, or was struck */
+      403: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-    };
-  };
-  create_evidence: {
-    parameters: {
-      query?: never;
-      header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
-        /** @description Dimension ID */
-        dimension_id: string;
-      };
-      cookie?: never;
-    };
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['CreateEvidenceBody'];
-      };
-    };
-    responses: {
-      /** @description Evidence created */
-      201: {
+      /** @description Strike not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Dimension not found */
-      404: {
+      /** @description Strike has no pending appeal */
+      409: {
         headers: {
           [name: string]: unknown;
         };
@@ -2200,22 +9394,40 @@ export interface operations {
       };
     };
   };
-  delete_evidence: {
+  get_feed: {
     parameters: {
-      query?: never;
-      header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
+      query?: {
+        /** @description Page size (1–200, default 50). */
+        limit?: number | null;
+        /** @description Opaque cursor from the previous page's `next_cursor`. */
+        cursor?: string | null;
+        /** @description RFC 3339 snapshot instant for the first page (default: now). */
+        as_of?: string | null;
       };
+      header?: never;
+      path?: never;
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Evidence deleted */
+      /** @description One page of the topic feed */
       200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['TopicFeedResponse'];
+        };
+      };
+      /** @description Invalid pagination parameters */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
@@ -2230,27 +9442,22 @@ export interface operations {
       };
     };
   };
-  my_votes: {
+  list_subscriptions: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
-      };
+      path?: never;
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description User's votes for this poll */
+      /** @description Subscribed topics */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['VoteResponse'][];
+          'application/json': components['schemas']['TopicSubscriptionsResponse'];
         };
       };
       /** @description Unauthorized */
@@ -2260,13 +9467,6 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Poll not found */
-      404: {
-        headers: {
-          [name: string]: unknown;
-        };
-        content?: never;
-      };
       /** @description Internal server error */
       500: {
         headers: {
@@ -2276,29 +9476,43 @@ export interface operations {
       };
     };
   };
-  reset_poll: {
+  subscribe: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
+        /** @description Endorsement topic */
+        topic: string;
       };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Poll reset to draft */
+      /** @description Subscribed */
       200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['TopicSubscriptionResponse'];
+        };
+      };
+      /** @description Invalid or unsubscribable topic */
+      400: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Poll not found */
-      404: {
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Subscription limit reached */
+      409: {
         headers: {
           [name: string]: unknown;
         };
@@ -2313,30 +9527,33 @@ export interface operations {
       };
     };
   };
-  get_results: {
+  unsubscribe: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
+        /** @description Endorsement topic */
+        topic: string;
       };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Poll results */
-      200: {
+      /** @description Unsubscribed */
+      204: {
         headers: {
           [name: string]: unknown;
         };
-        content: {
-          'application/json': components['schemas']['PollResultsResponse'];
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
         };
+        content?: never;
       };
-      /** @description Poll not found */
+      /** @description Not subscribed to this topic */
       404: {
         headers: {
           [name: string]: unknown;
@@ -2352,38 +9569,33 @@ export interface operations {
       };
     };
   };
-  get_distribution: {
+  budget_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
-      };
+      path?: never;
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Vote distribution */
+      /** @description Endorsement and denouncement budget for the authenticated user */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['PollDistributionResponse'];
+          'application/json': components['schemas']['BudgetResponse'];
         };
       };
-      /** @description Poll not found */
-      404: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description API token lacks the trust:read scope */
+      403: {
         headers: {
           [name: string]: unknown;
         };
@@ -2391,32 +9603,29 @@ export interface operations {
       };
     };
   };
-  update_poll_status: {
+  denounce_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
-      };
+      path?: never;
       cookie?: never;
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['PollStatusRequest'];
+        'application/json': components['schemas']['DenounceRequest'];
       };
     };
     responses: {
-      /** @description Status updated */
-      204: {
+      /** @description Denouncement queued */
+      202: {
         headers: {
           [name: string]: unknown;
         };
-        content?: never;
+        content: {
+          'application/json': components['schemas']['MessageResponse'];
+        };
       };
-      /** @description Validation error */
+      /** @description Invalid request */
       400: {
         headers: {
           [name: string]: unknown;
@@ -2430,15 +9639,15 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Poll not found */
-      404: {
+      /** @description Account too new or not active enough (`ACCOUNT_TOO_NEW`, `INSUFFICIENT_ACTIVITY`) */
+      403: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Quota exceeded */
+      429: {
         headers: {
           [name: string]: unknown;
         };
@@ -2446,31 +9655,26 @@ export interface operations {
       };
     };
   };
-  get_poll_traces: {
+  list_my_denouncements_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
-      };
+      path?: never;
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Bot traces for this poll */
+      /** @description List of denouncements */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['BotTraceResponse'][];
+          'application/json': components['schemas']['DenouncementResponse'][];
         };
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
@@ -2478,66 +9682,95 @@ export interface operations {
       };
     };
   };
-  cast_vote: {
+  distance_handler: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Room ID */
-        room_id: string;
-        /** @description Poll ID */
-        poll_id: string;
+        /** @description Target account ID */
+        account_id: string;
       };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description Vote recorded */
+      /** @description Trust distance from the authenticated user to the target */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['VoteResponse'][];
+          'application/json': components['schemas']['TrustDistanceResponse'];
         };
       };
-      /** @description Validation error */
-      400: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Unauthorized */
-      401: {
+      /** @description API token lacks the trust:read scope */
+      403: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Not eligible to vote */
-      403: {
+      /** @description Account not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Poll not found */
-      404: {
+    };
+  };
+  endorse_handler: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path?: never;
+      cookie?: never;
+    };
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['EndorseRequest'];
+      };
+    };
+    responses: {
+      /** @description Endorsement queued */
+      202: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['MessageResponse'];
+        };
+      };
+      /** @description Invalid request */
+      400: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Poll not active */
-      409: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Account too new or not active enough (`ACCOUNT_TOO_NEW`, `INSUFFICIENT_ACTIVITY`) */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Quota exceeded */
+      429: {
         headers: {
           [name: string]: unknown;
         };
@@ -2545,54 +9778,51 @@ export interface operations {
       };
     };
   };
-  assign_role: {
+  create_invite_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-      };
+      path?: never;
       cookie?: never;
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['AssignRoleRequest'];
+        'application/json': components['schemas']['CreateInviteRequest'];
       };
     };
     responses: {
-      /** @description Role assigned */
-      200: {
+      /** @description Invite created */
+      201: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['AssignRoleResponse'];
+          'application/json': components['schemas']['CreateInviteResponse'];
         };
       };
-      /** @description Unauthorized */
-      401: {
+      /** @description Invalid request */
+      400: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Only room owner can assign roles */
-      403: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Room not found */
-      404: {
+      /** @description Account too new or not active enough (`ACCOUNT_TOO_NEW`, `INSUFFICIENT_ACTIVITY`) */
+      403: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Invite quota exhausted for the current window */
+      429: {
         headers: {
           [name: string]: unknown;
         };
@@ -2600,29 +9830,26 @@ export interface operations {
       };
     };
   };
-  list_suggestions: {
+  list_invites_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Room ID */
-        room_id: string;
-      };
+      path?: never;
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description List of suggestions */
+      /** @description List of invites created by the authenticated user */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['SuggestionResponse'][];
+          'application/json': components['schemas']['InvitesResponse'];
         };
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
@@ -2630,37 +9857,26 @@ export interface operations {
       };
     };
   };
-  create_suggestion: {
+  resolve_pairing_code_handler: {
     parameters: {
       query?: never;
       header?: never;
       path: {
-        /** @description Room ID */
-        room_id: string;
+        /** @description Pairing code */
+        code: string;
       };
       cookie?: never;
     };
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['CreateSuggestionRequest'];
-      };
-    };
+    requestBody?: never;
     responses: {
-      /** @description Suggestion created */
-      201: {
+      /** @description Invite the code points at */
+      200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['SuggestionResponse'];
-        };
-      };
-      /** @description Validation error or daily limit reached */
-      400: {
-        headers: {
-          [name: string]: unknown;
+          'application/json': components['schemas']['InviteLinkResponse'];
         };
-        content?: never;
       };
       /** @description Unauthorized */
       401: {
@@ -2669,8 +9885,8 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Internal server error */
-      500: {
+      /** @description Unknown or expired code, or the invite is no longer open */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -2678,7 +9894,7 @@ export interface operations {
       };
     };
   };
-  budget_handler: {
+  invite_quota_handler: {
     parameters: {
       query?: never;
       header?: never;
@@ -2687,13 +9903,13 @@ export interface operations {
     };
     requestBody?: never;
     responses: {
-      /** @description Endorsement and denouncement budget for the authenticated user */
+      /** @description Invite quota for the current window */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['BudgetResponse'];
+          'application/json': components['schemas']['InviteQuota'];
         };
       };
       /** @description Unauthorized */
@@ -2705,26 +9921,62 @@ export interface operations {
       };
     };
   };
-  denounce_handler: {
+  invite_tree_handler: {
     parameters: {
-      query?: never;
+      query?: {
+        /** @description Hops to follow from the caller (1–5, default 5). */
+        max_depth?: number | null;
+      };
       header?: never;
       path?: never;
       cookie?: never;
     };
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['DenounceRequest'];
+    requestBody?: never;
+    responses: {
+      /** @description Accepted invites downstream of the authenticated user */
+      200: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content: {
+          'application/json': components['schemas']['InviteTreeResponse'];
+        };
+      };
+      /** @description Invalid max_depth */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Unauthorized */
+      401: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+    };
+  };
+  accept_invite_handler: {
+    parameters: {
+      query?: never;
+      header?: never;
+      path: {
+        /** @description Invite ID */
+        id: string;
       };
+      cookie?: never;
     };
+    requestBody?: never;
     responses: {
-      /** @description Denouncement queued */
-      202: {
+      /** @description Invite accepted and endorsement queued */
+      200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['MessageResponse'];
+          'application/json': components['schemas']['AcceptInviteResponse'];
         };
       };
       /** @description Invalid request */
@@ -2741,8 +9993,8 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Quota exceeded */
-      429: {
+      /** @description Invite not found */
+      404: {
         headers: {
           [name: string]: unknown;
         };
@@ -2750,22 +10002,25 @@ export interface operations {
       };
     };
   };
-  list_my_denouncements_handler: {
+  invite_link_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
+      path: {
+        /** @description Invite ID */
+        id: string;
+      };
       cookie?: never;
     };
     requestBody?: never;
     responses: {
-      /** @description List of denouncements */
+      /** @description Invite deep link */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['DenouncementResponse'][];
+          'application/json': components['schemas']['InviteLinkResponse'];
         };
       };
       /** @description Unauthorized */
@@ -2775,45 +10030,51 @@ export interface operations {
         };
         content?: never;
       };
+      /** @description Invite not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
   };
-  endorse_handler: {
+  create_pairing_code_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path?: never;
-      cookie?: never;
-    };
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['EndorseRequest'];
+      path: {
+        /** @description Invite ID */
+        id: string;
       };
+      cookie?: never;
     };
+    requestBody?: never;
     responses: {
-      /** @description Endorsement queued */
-      202: {
+      /** @description Pairing code issued */
+      201: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['MessageResponse'];
+          'application/json': components['schemas']['PairingCodeResponse'];
         };
       };
-      /** @description Invalid request */
-      400: {
+      /** @description Unauthorized */
+      401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Unauthorized */
-      401: {
+      /** @description No open invite with this ID belongs to the caller */
+      404: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
-      /** @description Quota exceeded */
+      /** @description Hourly pairing code limit reached */
       429: {
         headers: {
           [name: string]: unknown;
@@ -2822,7 +10083,7 @@ export interface operations {
       };
     };
   };
-  create_invite_handler: {
+  reconfirm_handler: {
     parameters: {
       query?: never;
       header?: never;
@@ -2831,20 +10092,20 @@ export interface operations {
     };
     requestBody: {
       content: {
-        'application/json': components['schemas']['CreateInviteRequest'];
+        'application/json': components['schemas']['ReconfirmRequest'];
       };
     };
     responses: {
-      /** @description Invite created */
-      201: {
+      /** @description Edge re-confirmed */
+      200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['CreateInviteResponse'];
+          'application/json': components['schemas']['ReconfirmResponse'];
         };
       };
-      /** @description Invalid request */
+      /** @description Invalid request, stale timestamp, or bad co-signature */
       400: {
         headers: {
           [name: string]: unknown;
@@ -2858,9 +10119,16 @@ export interface operations {
         };
         content?: never;
       };
+      /** @description No active trust edge to the subject */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
   };
-  list_invites_handler: {
+  reconfirmations_handler: {
     parameters: {
       query?: never;
       header?: never;
@@ -2869,13 +10137,13 @@ export interface operations {
     };
     requestBody?: never;
     responses: {
-      /** @description List of invites created by the authenticated user */
+      /** @description Trust edges due for re-confirmation, oldest first */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['InvitesResponse'];
+          'application/json': components['schemas']['ReconfirmationsResponse'];
         };
       };
       /** @description Unauthorized */
@@ -2885,27 +10153,35 @@ export interface operations {
         };
         content?: never;
       };
+      /** @description API token lacks the trust:read scope */
+      403: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
   };
-  accept_invite_handler: {
+  revoke_handler: {
     parameters: {
       query?: never;
       header?: never;
-      path: {
-        /** @description Invite ID */
-        id: string;
-      };
+      path?: never;
       cookie?: never;
     };
-    requestBody?: never;
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['RevokeRequest'];
+      };
+    };
     responses: {
-      /** @description Invite accepted and endorsement queued */
-      200: {
+      /** @description Revocation queued */
+      202: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['AcceptInviteResponse'];
+          'application/json': components['schemas']['MessageResponse'];
         };
       };
       /** @description Invalid request */
@@ -2922,8 +10198,8 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Invite not found */
-      404: {
+      /** @description Quota exceeded */
+      429: {
         headers: {
           [name: string]: unknown;
         };
@@ -2931,34 +10207,23 @@ export interface operations {
       };
     };
   };
-  revoke_handler: {
+  scores_me_handler: {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['RevokeRequest'];
-      };
-    };
+    requestBody?: never;
     responses: {
-      /** @description Revocation queued */
-      202: {
+      /** @description Trust scores for the authenticated user */
+      200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['MessageResponse'];
-        };
-      };
-      /** @description Invalid request */
-      400: {
-        headers: {
-          [name: string]: unknown;
+          'application/json': components['schemas']['ScoresResponse'];
         };
-        content?: never;
       };
       /** @description Unauthorized */
       401: {
@@ -2967,8 +10232,8 @@ export interface operations {
         };
         content?: never;
       };
-      /** @description Quota exceeded */
-      429: {
+      /** @description API token lacks the trust:read scope */
+      403: {
         headers: {
           [name: string]: unknown;
         };
@@ -2976,31 +10241,63 @@ export interface operations {
       };
     };
   };
-  scores_me_handler: {
+  idv_webhook: {
     parameters: {
       query?: never;
       header?: never;
       path?: never;
       cookie?: never;
     };
-    requestBody?: never;
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['IdvWebhookRequest'];
+      };
+    };
     responses: {
-      /** @description Trust scores for the authenticated user */
+      /** @description Result acknowledged */
       200: {
         headers: {
           [name: string]: unknown;
         };
         content: {
-          'application/json': components['schemas']['ScoresResponse'];
+          'application/json': components['schemas']['IdvWebhookResponse'];
         };
       };
-      /** @description Unauthorized */
+      /** @description Malformed body */
+      400: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Missing or invalid signature */
       401: {
         headers: {
           [name: string]: unknown;
         };
         content?: never;
       };
+      /** @description Webhook disabled, or account not found */
+      404: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description The ID already verifies another account */
+      409: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
+      /** @description Internal server error */
+      500: {
+        headers: {
+          [name: string]: unknown;
+        };
+        content?: never;
+      };
     };
   };
   create_endorsement_as_verifier: {
@@ -18,6 +18,22 @@ async function sha256Hex(data: Uint8Array): Promise<string> {
     .join('');
 }
 
+/**
+ * Canonical string a device signs for a request:
+ * `METHOD\nPATH\nTIMESTAMP\nNONCE\nhex(SHA-256(body))`.
+ * Must match the server's format (see the tc-crypto test vectors).
+ */
+export async function canonicalRequestMessage(
+  method: string,
+  path: string,
+  timestamp: string,
+  nonce: string,
+  bodyBytes: Uint8Array
+): Promise<string> {
+  const bodyHash = await sha256Hex(bodyBytes);
+  return `${method}\n${path}\n${timestamp}\n${nonce}\n${bodyHash}`;
+}
+
 async function buildAuthHeaders(
   method: string,
   path: string,
@@ -28,8 +44,7 @@ async function buildAuthHeaders(
 ): Promise<Record<string, string>> {
   const timestamp = Math.floor(Date.now() / 1000).toString();
   const nonce = globalThis.crypto.randomUUID();
-  const bodyHash = await sha256Hex(bodyBytes);
-  const canonical = `${method}\n${path}\n${timestamp}\n${nonce}\n${bodyHash}`;
+  const canonical = await canonicalRequestMessage(method, path, timestamp, nonce, bodyBytes);
 
   const signatureBuffer = await globalThis.crypto.subtle.sign(
     'Ed25519',
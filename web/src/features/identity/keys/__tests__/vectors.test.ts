@@ -0,0 +1,103 @@
+/**
+ * Cross-language conformance against the tc-crypto test-vector corpus.
+ *
+ * tc-crypto-vectors.json is exported from crates/tc-crypto/src/test_vectors.rs
+ * (`just export-test-vectors`). The Rust tests check the same file, so any
+ * drift between the two implementations fails one side or the other.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'node:path';
+import { ed25519 } from '@noble/curves/ed25519.js';
+import { beforeAll, describe, expect, it } from 'vitest';
+import { canonicalRequestMessage } from '@/api/signing';
+import { decryptBackupEnvelope, DecryptionError } from '../crypto';
+import corpus from './tc-crypto-vectors.json';
+
+let wasmModule: typeof import('@/wasm/tc-crypto/tc_crypto.js');
+
+beforeAll(async () => {
+  wasmModule = await import('@/wasm/tc-crypto/tc_crypto.js');
+  const wasmPath = path.resolve(__dirname, '../../../../wasm/tc-crypto/tc_crypto_bg.wasm');
+  wasmModule.initSync({ module: fs.readFileSync(wasmPath) });
+});
+
+function b64(value: string): Uint8Array {
+  return wasmModule.decode_base64url(value);
+}
+
+function secretKey(label: string): Uint8Array {
+  const keypair = corpus.keypairs.find((k) => k.label === label);
+  if (!keypair) {
+    throw new Error(`no keypair labelled ${label}`);
+  }
+  return b64(keypair.seed);
+}
+
+describe('tc-crypto test vectors', () => {
+  it('is a supported corpus version', () => {
+    expect(corpus.version).toBe(1);
+  });
+
+  it.each(corpus.keypairs)('derives public key and KID for $label', (keypair) => {
+    const publicKey = ed25519.getPublicKey(b64(keypair.seed));
+    expect(wasmModule.encode_base64url(publicKey)).toBe(keypair.public_key);
+    expect(wasmModule.derive_kid(publicKey)).toBe(keypair.kid);
+  });
+
+  it.each(corpus.certificates)('produces the $name certificate', (cert) => {
+    const subject = ed25519.getPublicKey(secretKey(cert.subject));
+    const message = new Uint8Array(cert.timestamp === null ? 32 : 40);
+    message.set(subject);
+    if (cert.timestamp !== null) {
+      new DataView(message.buffer).setBigInt64(32, BigInt(cert.timestamp), true);
+    }
+    expect(wasmModule.encode_base64url(message)).toBe(cert.message);
+
+    const signature = ed25519.sign(message, secretKey(cert.signer));
+    expect(wasmModule.encode_base64url(signature)).toBe(cert.signature);
+  });
+
+  it.each(corpus.requests)('signs $method $path canonically', async (req) => {
+    const canonical = await canonicalRequestMessage(
+      req.method,
+      req.path,
+      String(req.timestamp),
+      req.nonce,
+      new TextEncoder().encode(req.body)
+    );
+    expect(canonical).toBe(req.canonical);
+
+    const signature = ed25519.sign(new TextEncoder().encode(canonical), secretKey(req.signer));
+    expect(wasmModule.encode_base64url(signature)).toBe(req.signature);
+  });
+
+  it.each(corpus.envelopes)('accepts the $name envelope layout', async (vector) => {
+    const envelope = b64(vector.envelope);
+    const view = new DataView(envelope.buffer, envelope.byteOffset, envelope.byteLength);
+    expect(view.getUint32(2, true)).toBe(vector.m_cost);
+    expect(view.getUint32(6, true)).toBe(vector.t_cost);
+    expect(view.getUint32(10, true)).toBe(vector.p_cost);
+    expect(envelope.slice(14, 30)).toEqual(b64(vector.salt));
+    expect(envelope.slice(30, 42)).toEqual(b64(vector.nonce));
+    expect(envelope.slice(42)).toEqual(b64(vector.ciphertext));
+
+    // Header checks pass; the placeholder ciphertext then fails authentication
+    await expect(decryptBackupEnvelope(envelope, 'vector-password')).rejects.toBeInstanceOf(
+      DecryptionError
+    );
+  });
+
+  it.each(corpus.webhooks)('signs the $name webhook delivery', (vector) => {
+    const payload = new TextEncoder().encode(vector.payload);
+    const secret = b64(vector.secret);
+    const timestamp = BigInt(vector.timestamp);
+    expect(wasmModule.sign_webhook(payload, secret, timestamp)).toBe(vector.header);
+    expect(wasmModule.verify_webhook(payload, secret, vector.header, timestamp, 300n)).toBe(
+      timestamp
+    );
+    expect(() =>
+      wasmModule.verify_webhook(payload, secret, vector.header, timestamp + 301n, 300n)
+    ).toThrow();
+  });
+});
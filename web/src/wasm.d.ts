@@ -21,6 +21,23 @@ declare module '@/wasm/tc-crypto/tc_crypto.js' {
    */
   export function encode_base64url(bytes: Uint8Array): string;
 
+  /**
+   * Build the `t=<timestamp>,v1=<hex>` signature header for a webhook delivery.
+   */
+  export function sign_webhook(payload: Uint8Array, secret: Uint8Array, timestamp: bigint): string;
+
+  /**
+   * Check a webhook delivery's signature header, returning the signed timestamp.
+   * Throws if no signature matches or the timestamp is outside the tolerance window.
+   */
+  export function verify_webhook(
+    payload: Uint8Array,
+    secret: Uint8Array,
+    header: string,
+    now: bigint,
+    tolerance_secs: bigint
+  ): bigint;
+
   /**
    * Initialize the WASM module. Must be called before using exported functions.
    */